@@ -191,6 +191,12 @@ impl<A: Actor> ActorContext<A> {
         self.actor_state.get_state()
     }
 
+    /// Returns whether the actor is paused. A paused actor only processes the messages of its
+    /// high priority channel, which include scheduled messages.
+    pub fn is_paused(&self) -> bool {
+        self.state() == ActorState::Paused
+    }
+
     pub(crate) fn process(&self) {
         self.actor_state.process();
     }
@@ -285,7 +285,9 @@ impl<A: Actor> ActorExecutionEnv<A> {
                 self.process_one_message(envelope).await?;
             }
             self.ctx.yield_now().await;
-            if self.inbox.is_empty() {
+            // A paused actor does not process its low priority messages, so we do not wait for
+            // them to be drained.
+            if self.inbox.is_empty() || !self.ctx.state().is_running() {
                 break;
             }
         }
@@ -28,17 +28,29 @@ use aws_sdk_kinesis::operation::{
 };
 use aws_sdk_s3::operation::abort_multipart_upload::AbortMultipartUploadError;
 use aws_sdk_s3::operation::complete_multipart_upload::CompleteMultipartUploadError;
+use aws_sdk_s3::operation::copy_object::CopyObjectError;
 use aws_sdk_s3::operation::create_multipart_upload::CreateMultipartUploadError;
 use aws_sdk_s3::operation::delete_object::DeleteObjectError;
 use aws_sdk_s3::operation::delete_objects::DeleteObjectsError;
 use aws_sdk_s3::operation::get_object::GetObjectError;
 use aws_sdk_s3::operation::head_object::HeadObjectError;
+use aws_sdk_s3::operation::list_objects_v2::ListObjectsV2Error;
 use aws_sdk_s3::operation::put_object::PutObjectError;
 use aws_sdk_s3::operation::upload_part::UploadPartError;
+use aws_sdk_s3::operation::upload_part_copy::UploadPartCopyError;
 use aws_smithy_client::SdkError;
+use hyper::http::StatusCode;
 
 use crate::retry::AwsRetryable;
 
+/// Returns whether a request answered with `status` is worth retrying: server errors and
+/// throttling are, client errors such as `403 Forbidden` or `404 Not Found` are not.
+fn is_retryable_status(status: StatusCode) -> bool {
+    status.is_server_error()
+        || status == StatusCode::TOO_MANY_REQUESTS
+        || status == StatusCode::REQUEST_TIMEOUT
+}
+
 impl<E> AwsRetryable for SdkError<E>
 where E: AwsRetryable
 {
@@ -46,9 +58,15 @@ where E: AwsRetryable
         match self {
             SdkError::ConstructionFailure(_) => false,
             SdkError::TimeoutError(_) => true,
-            SdkError::DispatchFailure(_) => false,
-            SdkError::ResponseError(_) => true,
-            SdkError::ServiceError(error) => error.err().is_retryable(),
+            // I/O errors include connections reset by the peer.
+            SdkError::DispatchFailure(failure) => failure.is_io() || failure.is_timeout(),
+            SdkError::ResponseError(error) => {
+                let status = error.raw().http().status();
+                !status.is_client_error() || is_retryable_status(status)
+            }
+            SdkError::ServiceError(error) => {
+                is_retryable_status(error.raw().http().status()) || error.err().is_retryable()
+            }
             _ => false,
         }
     }
@@ -78,6 +96,18 @@ impl AwsRetryable for UploadPartError {
     }
 }
 
+impl AwsRetryable for UploadPartCopyError {
+    fn is_retryable(&self) -> bool {
+        false
+    }
+}
+
+impl AwsRetryable for CopyObjectError {
+    fn is_retryable(&self) -> bool {
+        false
+    }
+}
+
 impl AwsRetryable for CompleteMultipartUploadError {
     fn is_retryable(&self) -> bool {
         false
@@ -108,6 +138,12 @@ impl AwsRetryable for HeadObjectError {
     }
 }
 
+impl AwsRetryable for ListObjectsV2Error {
+    fn is_retryable(&self) -> bool {
+        false
+    }
+}
+
 #[cfg(feature = "kinesis")]
 impl AwsRetryable for GetRecordsError {
     fn is_retryable(&self) -> bool {
@@ -153,6 +153,64 @@ pub fn build_index_command() -> Command {
                         .global(true),
                 ])
             )
+        .subcommand(
+            Command::new("reindex")
+                .display_order(8)
+                .about("Copies the documents of an index into another index.")
+                .long_about("Scrolls the documents stored in the source index and ingests them into the destination index. The source index must store the `_source` of its documents (`store_source: true`).")
+                .args(&[
+                    arg!(--source <SOURCE_INDEX> "ID of the index to read the documents from.")
+                        .display_order(1)
+                        .required(true),
+                    arg!(--dest <DEST_INDEX> "ID of the index to write the documents to.")
+                        .display_order(2)
+                        .required(true),
+                    arg!(--query <QUERY> "Query expressed in natural query language selecting the documents to reindex.")
+                        .default_value("*")
+                        .required(false),
+                    arg!(--"batch-size" <BATCH_SIZE> "Number of documents fetched and ingested per batch.")
+                        .default_value("1000")
+                        .required(false),
+                    Arg::new("wait")
+                        .long("wait")
+                        .short('w')
+                        .help("Wait for all documents to be commited and available for search before exiting")
+                        .action(ArgAction::SetTrue),
+                    Arg::new("force")
+                        .long("force")
+                        .short('f')
+                        .help("Force a commit after the last document is sent, and wait for all documents to be committed and available for search before exiting")
+                        .action(ArgAction::SetTrue)
+                        .conflicts_with("wait"),
+                ])
+            )
+        .subcommand(
+            Command::new("set-uri")
+                .display_order(9)
+                .about("Updates the URI of an index.")
+                .long_about("Updates the URI of an index in the metastore. With `--move-data`, the split files are first copied to the new location. The files at the former location are not deleted.")
+                .args(&[
+                    arg!(--index <INDEX> "ID of the target index")
+                        .display_order(1)
+                        .required(true),
+                    arg!(--"new-uri" <NEW_URI> "New URI of the index.")
+                        .display_order(2)
+                        .required(true),
+                    arg!(--"move-data" "Copies the split files to the new URI before updating the metastore.")
+                        .required(false),
+                ])
+            )
+        .subcommand(
+            Command::new("check")
+                .display_order(10)
+                .about("Checks the consistency of the splits of an index.")
+                .long_about("Checks that the splits of an index recorded in the metastore and the split files present in the index storage are consistent. Reports the published splits whose file is missing and the split files that are not recorded in the metastore. Fails if the index is inconsistent.")
+                .args(&[
+                    arg!(--index <INDEX> "ID of the target index")
+                        .display_order(1)
+                        .required(true),
+                ])
+            )
         .subcommand(
             Command::new("search")
                 .display_order(7)
@@ -189,6 +247,12 @@ pub fn build_index_command() -> Command {
         .arg_required_else_help(true)
 }
 
+#[derive(Debug, Eq, PartialEq)]
+pub struct CheckIndexArgs {
+    pub client_args: ClientArgs,
+    pub index_id: String,
+}
+
 #[derive(Debug, Eq, PartialEq)]
 pub struct ClearIndexArgs {
     pub client_args: ClientArgs,
@@ -219,6 +283,25 @@ pub struct IngestDocsArgs {
     pub commit_type: CommitType,
 }
 
+#[derive(Debug, Eq, PartialEq)]
+pub struct ReindexArgs {
+    pub client_args: ClientArgs,
+    pub source_index_id: String,
+    pub dest_index_id: String,
+    pub query: String,
+    pub batch_size: u64,
+    pub commit_type: CommitType,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct SetIndexUriArgs {
+    pub client_args: ClientArgs,
+    pub index_id: String,
+    pub new_index_uri: Uri,
+    pub move_data: bool,
+    pub assume_yes: bool,
+}
+
 #[derive(Debug, Eq, PartialEq)]
 pub struct SearchIndexArgs {
     pub client_args: ClientArgs,
@@ -249,13 +332,16 @@ pub struct ListIndexesArgs {
 
 #[derive(Debug, Eq, PartialEq)]
 pub enum IndexCliCommand {
+    Check(CheckIndexArgs),
     Clear(ClearIndexArgs),
     Create(CreateIndexArgs),
     Delete(DeleteIndexArgs),
     Describe(DescribeIndexArgs),
     Ingest(IngestDocsArgs),
     List(ListIndexesArgs),
+    Reindex(ReindexArgs),
     Search(SearchIndexArgs),
+    SetUri(SetIndexUriArgs),
 }
 
 impl IndexCliCommand {
@@ -271,17 +357,31 @@ impl IndexCliCommand {
             .remove_subcommand()
             .context("failed to parse index subcommand")?;
         match subcommand.as_str() {
+            "check" => Self::parse_check_args(submatches),
             "clear" => Self::parse_clear_args(submatches),
             "create" => Self::parse_create_args(submatches),
             "delete" => Self::parse_delete_args(submatches),
             "describe" => Self::parse_describe_args(submatches),
             "ingest" => Self::parse_ingest_args(submatches),
             "list" => Self::parse_list_args(submatches),
+            "reindex" => Self::parse_reindex_args(submatches),
             "search" => Self::parse_search_args(submatches),
+            "set-uri" => Self::parse_set_uri_args(submatches),
             _ => bail!("unknown index subcommand `{subcommand}`"),
         }
     }
 
+    fn parse_check_args(mut matches: ArgMatches) -> anyhow::Result<Self> {
+        let client_args = ClientArgs::parse(&mut matches)?;
+        let index_id = matches
+            .remove_one::<String>("index")
+            .expect("`index` should be a required arg.");
+        Ok(Self::Check(CheckIndexArgs {
+            client_args,
+            index_id,
+        }))
+    }
+
     fn parse_clear_args(mut matches: ArgMatches) -> anyhow::Result<Self> {
         let client_args = ClientArgs::parse(&mut matches)?;
         let index_id = matches
@@ -366,6 +466,43 @@ impl IndexCliCommand {
         }))
     }
 
+    fn parse_reindex_args(mut matches: ArgMatches) -> anyhow::Result<Self> {
+        let client_args = ClientArgs::parse(&mut matches)?;
+        let source_index_id = matches
+            .remove_one::<String>("source")
+            .expect("`source` should be a required arg.");
+        let dest_index_id = matches
+            .remove_one::<String>("dest")
+            .expect("`dest` should be a required arg.");
+        if source_index_id == dest_index_id {
+            bail!("the source and destination indexes must be different");
+        }
+        let query = matches
+            .remove_one::<String>("query")
+            .expect("`query` should have a default value.");
+        let batch_size: u64 = matches
+            .remove_one::<String>("batch-size")
+            .expect("`batch-size` should have a default value.")
+            .parse()?;
+        if batch_size == 0 || batch_size > MAX_REINDEX_BATCH_SIZE {
+            bail!("`--batch-size` must be between 1 and {MAX_REINDEX_BATCH_SIZE}");
+        }
+        let commit_type = match (matches.get_flag("wait"), matches.get_flag("force")) {
+            (false, false) => CommitType::Auto,
+            (false, true) => CommitType::Force,
+            (true, false) => CommitType::WaitFor,
+            (true, true) => bail!("`--wait` and `--force` are mutually exclusive options"),
+        };
+        Ok(Self::Reindex(ReindexArgs {
+            client_args,
+            source_index_id,
+            dest_index_id,
+            query,
+            batch_size,
+            commit_type,
+        }))
+    }
+
     fn parse_search_args(mut matches: ArgMatches) -> anyhow::Result<Self> {
         let index_id = matches
             .remove_one::<String>("index")
@@ -429,17 +566,71 @@ impl IndexCliCommand {
         }))
     }
 
+    fn parse_set_uri_args(mut matches: ArgMatches) -> anyhow::Result<Self> {
+        let client_args = ClientArgs::parse(&mut matches)?;
+        let index_id = matches
+            .remove_one::<String>("index")
+            .expect("`index` should be a required arg.");
+        let new_index_uri = matches
+            .remove_one::<String>("new-uri")
+            .map(|uri| Uri::from_str(&uri))
+            .expect("`new-uri` should be a required arg.")?;
+        let move_data = matches.get_flag("move-data");
+        let assume_yes = matches.get_flag("yes");
+        Ok(Self::SetUri(SetIndexUriArgs {
+            client_args,
+            index_id,
+            new_index_uri,
+            move_data,
+            assume_yes,
+        }))
+    }
+
     pub async fn execute(self) -> anyhow::Result<()> {
         match self {
+            Self::Check(args) => check_index_cli(args).await,
             Self::Clear(args) => clear_index_cli(args).await,
             Self::Create(args) => create_index_cli(args).await,
             Self::Delete(args) => delete_index_cli(args).await,
             Self::Describe(args) => describe_index_cli(args).await,
             Self::Ingest(args) => ingest_docs_cli(args).await,
             Self::List(args) => list_index_cli(args).await,
+            Self::Reindex(args) => reindex_cli(args).await,
             Self::Search(args) => search_index_cli(args).await,
+            Self::SetUri(args) => set_index_uri_cli(args).await,
+        }
+    }
+}
+
+pub async fn check_index_cli(args: CheckIndexArgs) -> anyhow::Result<()> {
+    debug!(args=?args, "check-index");
+    println!("❯ Checking index...");
+    let qw_client = args.client_args.client();
+    let report = qw_client.indexes().check(&args.index_id).await?;
+    println!("{} consistent split(s)", report.consistent_split_ids.len());
+    if report.is_consistent() {
+        println!("{} Index is consistent.", "✔".color(GREEN_COLOR));
+        return Ok(());
+    }
+    for (split_ids, description) in [
+        (
+            &report.missing_in_storage_split_ids,
+            "published split(s) missing in storage",
+        ),
+        (
+            &report.orphaned_in_storage_split_ids,
+            "split file(s) orphaned in storage",
+        ),
+    ] {
+        if split_ids.is_empty() {
+            continue;
+        }
+        println!("{} {description}:", split_ids.len());
+        for split_id in split_ids {
+            println!("  - {split_id}");
         }
     }
+    bail!("index `{}` is inconsistent", args.index_id)
 }
 
 pub async fn clear_index_cli(args: ClearIndexArgs) -> anyhow::Result<()> {
@@ -460,6 +651,36 @@ pub async fn clear_index_cli(args: ClearIndexArgs) -> anyhow::Result<()> {
     Ok(())
 }
 
+pub async fn set_index_uri_cli(args: SetIndexUriArgs) -> anyhow::Result<()> {
+    debug!(args=?args, "set-index-uri");
+    if !args.assume_yes {
+        let prompt = if args.move_data {
+            format!(
+                "This operation will copy the splits of the index `{}` to `{}` and update its \
+                 URI. Do you want to proceed?",
+                args.index_id, args.new_index_uri
+            )
+        } else {
+            format!(
+                "This operation will update the URI of the index `{}` to `{}` without moving its \
+                 splits. Do you want to proceed?",
+                args.index_id, args.new_index_uri
+            )
+        };
+        if !prompt_confirmation(&prompt, false) {
+            return Ok(());
+        }
+    }
+    println!("❯ Updating index URI...");
+    let qw_client = args.client_args.client();
+    qw_client
+        .indexes()
+        .update_index_uri(&args.index_id, args.new_index_uri.as_str(), args.move_data)
+        .await?;
+    println!("{} Index URI successfully updated.", "✔".color(GREEN_COLOR));
+    Ok(())
+}
+
 pub async fn create_index_cli(args: CreateIndexArgs) -> anyhow::Result<()> {
     debug!(args=?args, "create-index");
     println!("❯ Creating index...");
@@ -973,6 +1194,110 @@ pub async fn ingest_docs_cli(args: IngestDocsArgs) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Maximum number of documents per batch, bounded by the maximum number of hits a search request
+/// can return.
+const MAX_REINDEX_BATCH_SIZE: u64 = 10_000;
+
+/// Duration for which the scroll context of a reindex operation is kept alive between two pages.
+const REINDEX_SCROLL_KEEP_ALIVE: &str = "5m";
+
+pub async fn reindex_cli(args: ReindexArgs) -> anyhow::Result<()> {
+    debug!(args=?args, "reindex");
+    println!(
+        "❯ Reindexing documents from `{}` into `{}`.",
+        args.source_index_id, args.dest_index_id
+    );
+    let num_docs = reindex(args).await?;
+    println!(
+        "{} Reindexed {num_docs} documents successfully.",
+        "✔".color(GREEN_COLOR)
+    );
+    Ok(())
+}
+
+/// Scrolls the documents of the source index matching the query and ingests them into the
+/// destination index. Returns the number of reindexed documents.
+pub async fn reindex(args: ReindexArgs) -> anyhow::Result<u64> {
+    let qw_client = args.client_args.client();
+    let source_index_metadata = qw_client.indexes().get(&args.source_index_id).await?;
+    if !source_index_metadata.index_config.doc_mapping.store_source {
+        bail!(
+            "index `{}` does not store the source of its documents, reindexing requires \
+             `store_source` to be enabled",
+            args.source_index_id
+        );
+    }
+    // Fails early if the destination index does not exist.
+    qw_client.indexes().get(&args.dest_index_id).await?;
+
+    let progress_bar = ProgressBar::new_spinner();
+    progress_bar.enable_steady_tick(Duration::from_millis(100));
+    progress_bar.set_message("0 documents");
+
+    let mut num_docs = 0;
+    // We hold back the last batch so that it can be ingested with the requested commit type.
+    let mut pending_batch_opt: Option<String> = None;
+    let mut scroll_page = qw_client
+        .scroll(
+            &args.source_index_id,
+            &args.query,
+            args.batch_size,
+            REINDEX_SCROLL_KEEP_ALIVE,
+        )
+        .await?;
+    while !scroll_page.sources.is_empty() {
+        let batch = stored_sources_to_ndjson(&scroll_page.sources)?;
+        num_docs += scroll_page.sources.len() as u64;
+
+        if let Some(pending_batch) = pending_batch_opt.replace(batch) {
+            qw_client
+                .ingest(
+                    &args.dest_index_id,
+                    IngestSource::Str(pending_batch),
+                    None,
+                    None,
+                    CommitType::Auto,
+                )
+                .await?;
+        }
+        progress_bar.set_message(format!("{num_docs} documents"));
+
+        let Some(scroll_id) = scroll_page.scroll_id else {
+            break;
+        };
+        scroll_page = qw_client
+            .scroll_next(&scroll_id, REINDEX_SCROLL_KEEP_ALIVE)
+            .await?;
+    }
+    if let Some(pending_batch) = pending_batch_opt {
+        qw_client
+            .ingest(
+                &args.dest_index_id,
+                IngestSource::Str(pending_batch),
+                None,
+                None,
+                args.commit_type,
+            )
+            .await?;
+    }
+    progress_bar.finish();
+    Ok(num_docs)
+}
+
+/// Serializes the stored `_source` of the hits as NDJSON. Fails if a hit was returned without its
+/// `_source` rather than silently losing the document.
+fn stored_sources_to_ndjson(sources: &[Option<serde_json::Value>]) -> anyhow::Result<String> {
+    let mut ndjson = String::new();
+    for source_opt in sources {
+        let source = source_opt
+            .as_ref()
+            .context("document is missing its `_source` field")?;
+        ndjson.push_str(&serde_json::to_string(source)?);
+        ndjson.push('\n');
+    }
+    Ok(ndjson)
+}
+
 fn progress_bar_style() -> ProgressStyle {
     ProgressStyle::with_template(
         "{spinner:.blue} [{elapsed_precise}] {bytes}/{total_bytes} ({msg})",
@@ -1009,6 +1334,7 @@ pub async fn search_index(args: SearchIndexArgs) -> anyhow::Result<SearchRespons
         start_offset: args.start_offset as u64,
         sort_by,
         count_all: CountHits::CountAll,
+        match_all_if_empty_query: true,
         ..Default::default()
     };
     let qw_client = args.client_args.client();
@@ -1374,4 +1700,18 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_stored_sources_to_ndjson() {
+        let sources = vec![
+            Some(serde_json::json!({"id": 1})),
+            Some(serde_json::json!({"id": 2})),
+        ];
+        let ndjson = stored_sources_to_ndjson(&sources).unwrap();
+        assert_eq!(ndjson, "{\"id\":1}\n{\"id\":2}\n");
+
+        let sources = vec![Some(serde_json::json!({"id": 1})), None];
+        let error = stored_sources_to_ndjson(&sources).unwrap_err();
+        assert_eq!(error.to_string(), "document is missing its `_source` field");
+    }
 }
@@ -436,7 +436,7 @@ mod tests {
     #[test]
     fn test_get_resolvers() {
         let s3_storage_config = S3StorageConfig {
-            force_path_style_access: true,
+            force_path_style_access: Some(true),
             ..Default::default()
         };
         let storage_configs = StorageConfigs::new(vec![s3_storage_config.into()]);
@@ -18,6 +18,7 @@
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
 use std::env;
+use std::sync::Arc;
 
 use anyhow::Context;
 use opentelemetry::sdk::propagation::TraceContextPropagator;
@@ -25,11 +26,14 @@ use opentelemetry::sdk::trace::BatchConfig;
 use opentelemetry::sdk::{trace, Resource};
 use opentelemetry::{global, KeyValue};
 use opentelemetry_otlp::WithExportConfig;
-use quickwit_serve::BuildInfo;
-use tracing::Level;
+use quickwit_config::{LogFormat, LoggingConfig};
+use quickwit_serve::{set_log_filter_reloader, BuildInfo, LogFilterReloader};
+use tracing::{Level, Subscriber};
 use tracing_subscriber::fmt::time::UtcTime;
+use tracing_subscriber::fmt::MakeWriter;
 use tracing_subscriber::prelude::*;
-use tracing_subscriber::EnvFilter;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::{reload, EnvFilter, Layer};
 
 use crate::QW_ENABLE_OPENTELEMETRY_OTLP_EXPORTER_ENV_KEY;
 #[cfg(feature = "tokio-console")]
@@ -39,6 +43,7 @@ pub fn setup_logging_and_tracing(
     level: Level,
     ansi_colors: bool,
     build_info: &BuildInfo,
+    logging_config: &LoggingConfig,
 ) -> anyhow::Result<()> {
     #[cfg(feature = "tokio-console")]
     {
@@ -49,24 +54,17 @@ pub fn setup_logging_and_tracing(
     }
     let env_filter = env::var("RUST_LOG")
         .map(|_| EnvFilter::from_default_env())
-        .or_else(|_| EnvFilter::try_new(format!("quickwit={level},tantivy=WARN")))
+        .or_else(|_| EnvFilter::try_new(env_filter_directives(level, logging_config)))
         .context("Failed to set up tracing env filter.")?;
+    let (env_filter, env_filter_reload_handle) = reload::Layer::new(env_filter);
+
+    // The log levels of the node config can only be reloaded if they are not overridden by
+    // `RUST_LOG`.
+    if env::var_os("RUST_LOG").is_none() {
+        set_log_filter_reloader(log_filter_reloader(level, env_filter_reload_handle));
+    }
     global::set_text_map_propagator(TraceContextPropagator::new());
     let registry = tracing_subscriber::registry().with(env_filter);
-    let event_format = tracing_subscriber::fmt::format()
-        .with_target(true)
-        .with_timer(
-            // We do not rely on the Rfc3339 implementation, because it has a nanosecond precision.
-            // See discussion here: https://github.com/time-rs/time/discussions/418
-            UtcTime::new(
-                time::format_description::parse(
-                    "[year]-[month]-[day]T[hour]:[minute]:[second].[subsecond digits:3]Z",
-                )
-                .expect("Time format invalid."),
-            ),
-        );
-    // Note on disabling ANSI characters: setting the ansi boolean on event format is insufficient.
-    // It is thus set on layers, see https://github.com/tokio-rs/tracing/issues/1817
     if std::env::var_os(QW_ENABLE_OPENTELEMETRY_OTLP_EXPORTER_ENV_KEY).is_some() {
         let otlp_exporter = opentelemetry_otlp::new_exporter().tonic().with_env();
         // In debug mode, Quickwit can generate a lot of spans, and the default queue size of 2048
@@ -85,22 +83,217 @@ pub fn setup_logging_and_tracing(
             .context("Failed to initialize OpenTelemetry OTLP exporter.")?;
         registry
             .with(tracing_opentelemetry::layer().with_tracer(tracer))
-            .with(
-                tracing_subscriber::fmt::layer()
-                    .event_format(event_format)
-                    .with_ansi(ansi_colors),
-            )
+            .with(fmt_layer(
+                logging_config.format,
+                ansi_colors,
+                std::io::stdout,
+            ))
             .try_init()
             .context("Failed to set up tracing.")?;
     } else {
         registry
-            .with(
-                tracing_subscriber::fmt::layer()
-                    .event_format(event_format)
-                    .with_ansi(ansi_colors),
-            )
+            .with(fmt_layer(
+                logging_config.format,
+                ansi_colors,
+                std::io::stdout,
+            ))
             .try_init()
             .context("Failed to set up tracing.")?;
     }
     Ok(())
 }
+
+/// Builds the directives of the env filter: the Quickwit crates log at the configured level, or
+/// `default_level` if unset, and the per-module levels override it.
+fn env_filter_directives(default_level: Level, logging_config: &LoggingConfig) -> String {
+    let level = logging_config
+        .level
+        .clone()
+        .unwrap_or_else(|| default_level.to_string());
+    let mut directives = format!("quickwit={level},tantivy=WARN");
+
+    for (module_path, module_level) in &logging_config.module_levels {
+        directives.push_str(&format!(",{module_path}={module_level}"));
+    }
+    directives
+}
+
+/// Returns a callback rebuilding the env filter from the logging config of a reloaded node config.
+fn log_filter_reloader<S: 'static>(
+    default_level: Level,
+    env_filter_reload_handle: reload::Handle<EnvFilter, S>,
+) -> LogFilterReloader {
+    Arc::new(move |logging_config| {
+        let env_filter = EnvFilter::try_new(env_filter_directives(default_level, logging_config))?;
+        env_filter_reload_handle.reload(env_filter)?;
+        Ok(())
+    })
+}
+
+fn fmt_layer<S, W>(
+    log_format: LogFormat,
+    ansi_colors: bool,
+    make_writer: W,
+) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+    W: for<'writer> MakeWriter<'writer> + Send + Sync + 'static,
+{
+    // We do not rely on the Rfc3339 implementation, because it has a nanosecond precision.
+    // See discussion here: https://github.com/time-rs/time/discussions/418
+    let timer = UtcTime::new(
+        time::format_description::parse(
+            "[year]-[month]-[day]T[hour]:[minute]:[second].[subsecond digits:3]Z",
+        )
+        .expect("Time format invalid."),
+    );
+    // Note on disabling ANSI characters: setting the ansi boolean on event format is insufficient.
+    // It is thus set on layers, see https://github.com/tokio-rs/tracing/issues/1817
+    match log_format {
+        LogFormat::Json => tracing_subscriber::fmt::layer()
+            .json()
+            .with_target(true)
+            .with_timer(timer)
+            .with_ansi(false)
+            .with_writer(make_writer)
+            .boxed(),
+        LogFormat::Pretty => {
+            let event_format = tracing_subscriber::fmt::format()
+                .with_target(true)
+                .with_timer(timer);
+            tracing_subscriber::fmt::layer()
+                .event_format(event_format)
+                .with_ansi(ansi_colors)
+                .with_writer(make_writer)
+                .boxed()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::io;
+    use std::sync::{Arc, Mutex};
+
+    use serde_json::Value as JsonValue;
+
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for CapturedLogs {
+        type Writer = Self;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    #[test]
+    fn test_env_filter_directives() {
+        let logging_config = LoggingConfig::default();
+        assert_eq!(
+            env_filter_directives(Level::INFO, &logging_config),
+            "quickwit=INFO,tantivy=WARN"
+        );
+        let logging_config = LoggingConfig {
+            level: Some("warn".to_string()),
+            module_levels: BTreeMap::from([("quickwit_search".to_string(), "debug".to_string())]),
+            ..Default::default()
+        };
+        assert_eq!(
+            env_filter_directives(Level::INFO, &logging_config),
+            "quickwit=warn,tantivy=WARN,quickwit_search=debug"
+        );
+    }
+
+    #[test]
+    fn test_json_logs_honor_module_levels() {
+        let logging_config = LoggingConfig {
+            format: LogFormat::Json,
+            level: Some("warn".to_string()),
+            module_levels: BTreeMap::from([("quickwit_search".to_string(), "debug".to_string())]),
+        };
+        let env_filter =
+            EnvFilter::try_new(env_filter_directives(Level::INFO, &logging_config)).unwrap();
+        let captured_logs = CapturedLogs::default();
+        let subscriber = tracing_subscriber::registry()
+            .with(env_filter)
+            .with(fmt_layer(
+                logging_config.format,
+                false,
+                captured_logs.clone(),
+            ));
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::debug!(target: "quickwit_search::root", "search debug");
+            tracing::info!(target: "quickwit_indexing", "indexing info");
+            tracing::warn!(target: "quickwit_indexing", "indexing warn");
+        });
+        let logs = String::from_utf8(captured_logs.0.lock().unwrap().clone()).unwrap();
+        let log_lines: Vec<JsonValue> = logs
+            .lines()
+            .map(|log_line| serde_json::from_str(log_line).unwrap())
+            .collect();
+        assert_eq!(log_lines.len(), 2);
+
+        assert_eq!(log_lines[0]["level"], "DEBUG");
+        assert_eq!(log_lines[0]["target"], "quickwit_search::root");
+        assert_eq!(log_lines[0]["fields"]["message"], "search debug");
+
+        assert_eq!(log_lines[1]["level"], "WARN");
+        assert_eq!(log_lines[1]["target"], "quickwit_indexing");
+        assert_eq!(log_lines[1]["fields"]["message"], "indexing warn");
+    }
+
+    #[test]
+    fn test_log_filter_reloader_applies_changed_log_level() {
+        let logging_config = LoggingConfig {
+            format: LogFormat::Json,
+            ..Default::default()
+        };
+        let env_filter =
+            EnvFilter::try_new(env_filter_directives(Level::INFO, &logging_config)).unwrap();
+        let (env_filter, env_filter_reload_handle) = reload::Layer::new(env_filter);
+        let log_filter_reloader = log_filter_reloader(Level::INFO, env_filter_reload_handle);
+        let captured_logs = CapturedLogs::default();
+        let subscriber = tracing_subscriber::registry()
+            .with(env_filter)
+            .with(fmt_layer(
+                logging_config.format,
+                false,
+                captured_logs.clone(),
+            ));
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::debug!(target: "quickwit_search", "debug before reload");
+
+            let reloaded_logging_config = LoggingConfig {
+                level: Some("debug".to_string()),
+                ..logging_config.clone()
+            };
+            log_filter_reloader(&reloaded_logging_config).unwrap();
+
+            tracing::debug!(target: "quickwit_search", "debug after reload");
+        });
+        let logs = String::from_utf8(captured_logs.0.lock().unwrap().clone()).unwrap();
+        let log_lines: Vec<JsonValue> = logs
+            .lines()
+            .map(|log_line| serde_json::from_str(log_line).unwrap())
+            .collect();
+        assert_eq!(log_lines.len(), 1);
+        assert_eq!(log_lines[0]["level"], "DEBUG");
+        assert_eq!(log_lines[0]["fields"]["message"], "debug after reload");
+    }
+}
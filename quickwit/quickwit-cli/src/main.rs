@@ -25,6 +25,7 @@ use quickwit_cli::cli::{build_cli, CliCommand};
 #[cfg(feature = "jemalloc")]
 use quickwit_cli::jemalloc::start_jemalloc_metrics_loop;
 use quickwit_cli::logger::setup_logging_and_tracing;
+use quickwit_config::LoggingConfig;
 use quickwit_serve::BuildInfo;
 
 fn main() -> anyhow::Result<()> {
@@ -63,7 +64,18 @@ async fn main_impl() -> anyhow::Result<()> {
     #[cfg(feature = "jemalloc")]
     start_jemalloc_metrics_loop();
 
-    setup_logging_and_tracing(command.default_log_level(), ansi_colors, build_info)?;
+    // The logging config of a node is part of its node config, which must be loaded before
+    // setting up the tracing subscriber.
+    let logging_config = match &command {
+        CliCommand::Run(run_cli_command) => run_cli_command.load_logging_config().await?,
+        _ => LoggingConfig::default(),
+    };
+    setup_logging_and_tracing(
+        command.default_log_level(),
+        ansi_colors,
+        build_info,
+        &logging_config,
+    )?;
     let return_code: i32 = if let Err(err) = command.execute().await {
         eprintln!("{} Command failed: {:?}\n", "✘".color(RED_COLOR), err);
         1
@@ -95,13 +107,13 @@ mod tests {
     use bytesize::ByteSize;
     use quickwit_cli::cli::{build_cli, CliCommand};
     use quickwit_cli::index::{
-        ClearIndexArgs, CreateIndexArgs, DeleteIndexArgs, DescribeIndexArgs, IndexCliCommand,
-        IngestDocsArgs, SearchIndexArgs,
+        CheckIndexArgs, ClearIndexArgs, CreateIndexArgs, DeleteIndexArgs, DescribeIndexArgs,
+        IndexCliCommand, IngestDocsArgs, ReindexArgs, SearchIndexArgs, SetIndexUriArgs,
     };
     use quickwit_cli::split::{DescribeSplitArgs, SplitCliCommand};
     use quickwit_cli::tool::{
-        ExtractSplitArgs, GarbageCollectIndexArgs, LocalIngestDocsArgs, LocalSearchArgs, MergeArgs,
-        ToolCliCommand,
+        BenchmarkStorageArgs, ExtractSplitArgs, GarbageCollectCacheArgs, GarbageCollectIndexArgs,
+        LocalIngestDocsArgs, LocalSearchArgs, MergeArgs, ToolCliCommand,
     };
     use quickwit_cli::ClientArgs;
     use quickwit_common::uri::Uri;
@@ -410,6 +422,127 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_parse_reindex_args() -> anyhow::Result<()> {
+        let app = build_cli().no_binary_name(true);
+        let matches = app.try_get_matches_from([
+            "index",
+            "reindex",
+            "--source",
+            "wikipedia",
+            "--dest",
+            "wikipedia-v2",
+        ])?;
+        let command = CliCommand::parse_cli_args(matches)?;
+        assert!(matches!(
+            command,
+            CliCommand::Index(IndexCliCommand::Reindex(ReindexArgs {
+                source_index_id,
+                dest_index_id,
+                query,
+                batch_size: 1000,
+                commit_type: CommitType::Auto,
+                ..
+            })) if &source_index_id == "wikipedia" && &dest_index_id == "wikipedia-v2" && &query == "*"
+        ));
+
+        let app = build_cli().no_binary_name(true);
+        let matches = app.try_get_matches_from([
+            "index",
+            "reindex",
+            "--source",
+            "wikipedia",
+            "--dest",
+            "wikipedia-v2",
+            "--query",
+            "title:obama",
+            "--batch-size",
+            "100",
+            "--wait",
+        ])?;
+        let command = CliCommand::parse_cli_args(matches)?;
+        assert!(matches!(
+            command,
+            CliCommand::Index(IndexCliCommand::Reindex(ReindexArgs {
+                query,
+                batch_size: 100,
+                commit_type: CommitType::WaitFor,
+                ..
+            })) if &query == "title:obama"
+        ));
+
+        let app = build_cli().no_binary_name(true);
+        let matches = app.try_get_matches_from([
+            "index",
+            "reindex",
+            "--source",
+            "wikipedia",
+            "--dest",
+            "wikipedia",
+        ])?;
+        CliCommand::parse_cli_args(matches).unwrap_err();
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_check_args() -> anyhow::Result<()> {
+        let app = build_cli().no_binary_name(true);
+        let matches = app.try_get_matches_from(["index", "check", "--index", "wikipedia"])?;
+        let command = CliCommand::parse_cli_args(matches)?;
+        assert!(matches!(
+            command,
+            CliCommand::Index(IndexCliCommand::Check(CheckIndexArgs { index_id, .. }))
+                if &index_id == "wikipedia"
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_set_uri_args() -> anyhow::Result<()> {
+        let app = build_cli().no_binary_name(true);
+        let matches = app.try_get_matches_from([
+            "index",
+            "set-uri",
+            "--index",
+            "wikipedia",
+            "--new-uri",
+            "s3://new-bucket/indexes/wikipedia",
+        ])?;
+        let command = CliCommand::parse_cli_args(matches)?;
+        assert!(matches!(
+            command,
+            CliCommand::Index(IndexCliCommand::SetUri(SetIndexUriArgs {
+                index_id,
+                new_index_uri,
+                move_data: false,
+                assume_yes: false,
+                ..
+            })) if &index_id == "wikipedia" && new_index_uri == "s3://new-bucket/indexes/wikipedia"
+        ));
+
+        let app = build_cli().no_binary_name(true);
+        let matches = app.try_get_matches_from([
+            "index",
+            "set-uri",
+            "--index",
+            "wikipedia",
+            "--new-uri",
+            "s3://new-bucket/indexes/wikipedia",
+            "--move-data",
+            "--yes",
+        ])?;
+        let command = CliCommand::parse_cli_args(matches)?;
+        assert!(matches!(
+            command,
+            CliCommand::Index(IndexCliCommand::SetUri(SetIndexUriArgs {
+                move_data: true,
+                assume_yes: true,
+                ..
+            }))
+        ));
+        Ok(())
+    }
+
     #[test]
     fn test_parse_search_args() -> anyhow::Result<()> {
         let app = build_cli().no_binary_name(true);
@@ -655,8 +788,41 @@ mod tests {
                 split_id,
                 target_dir,
                 ..
-            })) if &index_id == "wikipedia" && &split_id == "ABC" && target_dir == PathBuf::from("datadir")
+            })) if index_id.as_deref() == Some("wikipedia") && split_id.as_deref() == Some("ABC") && target_dir == PathBuf::from("datadir")
+        ));
+
+        let app = build_cli().no_binary_name(true);
+        let matches = app.try_get_matches_from([
+            "tool",
+            "extract-split",
+            "--split-uri",
+            "s3://quickwit-indexes/wikipedia/ABC.split",
+            "--target-dir",
+            "datadir",
+        ])?;
+        let command = CliCommand::parse_cli_args(matches)?;
+        assert!(matches!(
+            command,
+            CliCommand::Tool(ToolCliCommand::ExtractSplit(ExtractSplitArgs {
+                index_id: None,
+                split_id: None,
+                split_uri: Some(split_uri),
+                ..
+            })) if split_uri == "s3://quickwit-indexes/wikipedia/ABC.split"
         ));
+
+        let app = build_cli().no_binary_name(true);
+        app.try_get_matches_from([
+            "tool",
+            "extract-split",
+            "--index",
+            "wikipedia",
+            "--split-uri",
+            "s3://quickwit-indexes/wikipedia/ABC.split",
+            "--target-dir",
+            "datadir",
+        ])
+        .unwrap_err();
         Ok(())
     }
 
@@ -708,6 +874,122 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_parse_garbage_collect_cache_args() -> anyhow::Result<()> {
+        let app = build_cli().no_binary_name(true);
+        let matches = app.try_get_matches_from(["tool", "gc-cache", "--config", "/config.yaml"])?;
+        let command = CliCommand::parse_cli_args(matches)?;
+        assert!(matches!(
+            command,
+            CliCommand::Tool(ToolCliCommand::GarbageCollectCache(
+                GarbageCollectCacheArgs {
+                    target_size: None,
+                    clear: false,
+                    ..
+                }
+            ))
+        ));
+
+        let app = build_cli().no_binary_name(true);
+        let matches = app.try_get_matches_from([
+            "tool",
+            "gc-cache",
+            "--target-size",
+            "10GB",
+            "--config",
+            "/config.yaml",
+        ])?;
+        let command = CliCommand::parse_cli_args(matches)?;
+        assert!(matches!(
+            command,
+            CliCommand::Tool(ToolCliCommand::GarbageCollectCache(GarbageCollectCacheArgs {
+                target_size: Some(target_size),
+                clear: false,
+                ..
+            })) if target_size == ByteSize::gb(10)
+        ));
+
+        let app = build_cli().no_binary_name(true);
+        let matches = app.try_get_matches_from(["tool", "gc-cache", "--clear"])?;
+        let command = CliCommand::parse_cli_args(matches)?;
+        assert!(matches!(
+            command,
+            CliCommand::Tool(ToolCliCommand::GarbageCollectCache(
+                GarbageCollectCacheArgs {
+                    target_size: None,
+                    clear: true,
+                    ..
+                }
+            ))
+        ));
+
+        let app = build_cli().no_binary_name(true);
+        app.try_get_matches_from(["tool", "gc-cache", "--target-size", "10GB", "--clear"])
+            .unwrap_err();
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_benchmark_storage_args() -> anyhow::Result<()> {
+        let app = build_cli().no_binary_name(true);
+        let matches = app.try_get_matches_from([
+            "tool",
+            "benchmark-storage",
+            "--storage-uri",
+            "s3://my-bucket/benchmark",
+            "--config",
+            "/config.yaml",
+        ])?;
+        let command = CliCommand::parse_cli_args(matches)?;
+        let expected_storage_uri = Uri::from_str("s3://my-bucket/benchmark").unwrap();
+        assert!(matches!(
+            command,
+            CliCommand::Tool(ToolCliCommand::BenchmarkStorage(BenchmarkStorageArgs {
+                storage_uri,
+                object_size,
+                num_requests: 20,
+                ..
+            })) if storage_uri == expected_storage_uri && object_size == ByteSize::mib(64)
+        ));
+
+        let app = build_cli().no_binary_name(true);
+        let matches = app.try_get_matches_from([
+            "tool",
+            "benchmark-storage",
+            "--storage-uri",
+            "s3://my-bucket/benchmark",
+            "--object-size",
+            "1GB",
+            "--num-requests",
+            "100",
+            "--config",
+            "/config.yaml",
+        ])?;
+        let command = CliCommand::parse_cli_args(matches)?;
+        assert!(matches!(
+            command,
+            CliCommand::Tool(ToolCliCommand::BenchmarkStorage(BenchmarkStorageArgs {
+                object_size,
+                num_requests: 100,
+                ..
+            })) if object_size == ByteSize::gb(1)
+        ));
+
+        let app = build_cli().no_binary_name(true);
+        let matches = app.try_get_matches_from([
+            "tool",
+            "benchmark-storage",
+            "--storage-uri",
+            "s3://my-bucket/benchmark",
+            "--num-requests",
+            "0",
+            "--config",
+            "/config.yaml",
+        ])?;
+        CliCommand::parse_cli_args(matches).unwrap_err();
+        Ok(())
+    }
+
     #[test]
     fn test_parse_merge_args() -> anyhow::Result<()> {
         let app = build_cli().no_binary_name(true);
@@ -25,7 +25,7 @@ use itertools::Itertools;
 use quickwit_common::runtimes::RuntimesConfig;
 use quickwit_common::uri::{Protocol, Uri};
 use quickwit_config::service::QuickwitService;
-use quickwit_config::NodeConfig;
+use quickwit_config::{LoggingConfig, NodeConfig};
 use quickwit_serve::serve_quickwit;
 use quickwit_telemetry::payload::{QuickwitFeature, QuickwitTelemetryInfo, TelemetryEvent};
 use tokio::signal;
@@ -73,6 +73,12 @@ impl RunCliCommand {
         })
     }
 
+    /// Loads the logging config from the node config.
+    pub async fn load_logging_config(&self) -> anyhow::Result<LoggingConfig> {
+        let node_config = load_node_config(&self.config_uri).await?;
+        Ok(node_config.logging_config)
+    }
+
     pub async fn execute(&self) -> anyhow::Result<()> {
         debug!(args = ?self, "run-service");
         let mut node_config = load_node_config(&self.config_uri).await?;
@@ -97,6 +103,7 @@ impl RunCliCommand {
         });
         let serve_result = serve_quickwit(
             node_config,
+            Some(self.config_uri.clone()),
             runtimes_config,
             metastore_resolver,
             storage_resolver,
@@ -26,12 +26,14 @@ use itertools::Itertools;
 use quickwit_common::uri::Uri;
 use quickwit_config::{validate_identifier, ConfigFormat, SourceConfig};
 use quickwit_metastore::checkpoint::SourceCheckpoint;
+use quickwit_serve::SourceIngestStatus;
 use quickwit_storage::{load_file, StorageResolver};
 use serde_json::Value as JsonValue;
 use tabled::{Table, Tabled};
 use tracing::debug;
 
 use crate::checklist::GREEN_COLOR;
+use crate::split::OutputFormat;
 use crate::{client_args, make_table, prompt_confirmation, ClientArgs};
 
 pub fn build_source_command() -> Command {
@@ -123,6 +125,19 @@ pub fn build_source_command() -> Command {
                         .required(true),
                 ])
             )
+        .subcommand(
+            Command::new("ingest-status")
+                .about("Displays the checkpoint and the estimated lag of the sources of an index.")
+                .args(&[
+                    arg!(--index <INDEX_ID> "ID of the target index")
+                        .display_order(1)
+                        .required(true),
+                    arg!(--"output-format" <OUTPUT_FORMAT> "Output format. Possible values are `table`, `json`, and `pretty-json`.")
+                        .alias("format")
+                        .display_order(2)
+                        .required(false),
+                ])
+            )
         .subcommand(
             Command::new("reset-checkpoint")
                 .about("Resets a source checkpoint.")
@@ -175,6 +190,13 @@ pub struct ListSourcesArgs {
     pub index_id: String,
 }
 
+#[derive(Debug, Eq, PartialEq)]
+pub struct IngestStatusArgs {
+    pub client_args: ClientArgs,
+    pub index_id: String,
+    output_format: OutputFormat,
+}
+
 #[derive(Debug, Eq, PartialEq)]
 pub struct ResetCheckpointArgs {
     pub client_args: ClientArgs,
@@ -190,6 +212,7 @@ pub enum SourceCliCommand {
     DeleteSource(DeleteSourceArgs),
     DescribeSource(DescribeSourceArgs),
     ListSources(ListSourcesArgs),
+    IngestStatus(IngestStatusArgs),
     ResetCheckpoint(ResetCheckpointArgs),
 }
 
@@ -201,6 +224,7 @@ impl SourceCliCommand {
             Self::DeleteSource(args) => delete_source_cli(args).await,
             Self::DescribeSource(args) => describe_source_cli(args).await,
             Self::ListSources(args) => list_sources_cli(args).await,
+            Self::IngestStatus(args) => ingest_status_cli(args).await,
             Self::ResetCheckpoint(args) => reset_checkpoint_cli(args).await,
         }
     }
@@ -220,6 +244,7 @@ impl SourceCliCommand {
             "delete" => Self::parse_delete_args(submatches).map(Self::DeleteSource),
             "describe" => Self::parse_describe_args(submatches).map(Self::DescribeSource),
             "list" => Self::parse_list_args(submatches).map(Self::ListSources),
+            "ingest-status" => Self::parse_ingest_status_args(submatches).map(Self::IngestStatus),
             "reset-checkpoint" => {
                 Self::parse_reset_checkpoint_args(submatches).map(Self::ResetCheckpoint)
             }
@@ -306,6 +331,23 @@ impl SourceCliCommand {
         })
     }
 
+    fn parse_ingest_status_args(mut matches: ArgMatches) -> anyhow::Result<IngestStatusArgs> {
+        let client_args = ClientArgs::parse(&mut matches)?;
+        let index_id = matches
+            .remove_one::<String>("index")
+            .expect("`index` should be a required arg.");
+        let output_format = matches
+            .remove_one::<String>("output-format")
+            .map(|output_format_str| OutputFormat::from_str(&output_format_str))
+            .transpose()?
+            .unwrap_or(OutputFormat::Table);
+        Ok(IngestStatusArgs {
+            client_args,
+            index_id,
+            output_format,
+        })
+    }
+
     fn parse_reset_checkpoint_args(mut matches: ArgMatches) -> anyhow::Result<ResetCheckpointArgs> {
         let client_args = ClientArgs::parse(&mut matches)?;
         let index_id = matches
@@ -466,6 +508,42 @@ where I: IntoIterator<Item = SourceConfig> {
     make_table("Sources", rows, false)
 }
 
+async fn ingest_status_cli(args: IngestStatusArgs) -> anyhow::Result<()> {
+    debug!(args=?args, "ingest-status-source");
+    let qw_client = args.client_args.client();
+    let ingest_statuses = qw_client
+        .ingest_status(&args.index_id)
+        .await
+        .context("failed to fetch ingest status")?;
+    let output = match args.output_format {
+        OutputFormat::Json => serde_json::to_string(&ingest_statuses)?,
+        OutputFormat::PrettyJson => serde_json::to_string_pretty(&ingest_statuses)?,
+        OutputFormat::Table => make_ingest_status_table(ingest_statuses).to_string(),
+    };
+    println!("{output}");
+    Ok(())
+}
+
+fn make_ingest_status_table<I>(ingest_statuses: I) -> Table
+where I: IntoIterator<Item = SourceIngestStatus> {
+    let rows = ingest_statuses
+        .into_iter()
+        .map(|ingest_status| IngestStatusRow {
+            source_id: ingest_status.source_id,
+            enabled: ingest_status.enabled.to_string(),
+            checkpoint: ingest_status
+                .checkpoint
+                .iter()
+                .map(|(partition_id, position)| format!("{}: {position}", partition_id.0))
+                .join("\n"),
+            lag: ingest_status
+                .lag
+                .map(|lag| lag.to_string())
+                .unwrap_or_else(|| "n/a".to_string()),
+        });
+    make_table("Ingest Status", rows, false)
+}
+
 #[derive(Tabled)]
 struct SourceRow {
     #[tabled(rename = "ID")]
@@ -476,6 +554,18 @@ struct SourceRow {
     enabled: String,
 }
 
+#[derive(Tabled)]
+struct IngestStatusRow {
+    #[tabled(rename = "ID")]
+    source_id: String,
+    #[tabled(rename = "Enabled")]
+    enabled: String,
+    #[tabled(rename = "Checkpoint")]
+    checkpoint: String,
+    #[tabled(rename = "Lag")]
+    lag: String,
+}
+
 #[derive(Tabled)]
 struct ParamsRow {
     #[tabled(rename = "Key")]
@@ -797,6 +887,64 @@ mod tests {
         assert_eq!(command, expected_command);
     }
 
+    #[test]
+    fn test_parse_ingest_status_args() {
+        let app = build_cli().no_binary_name(true);
+        let matches = app
+            .try_get_matches_from(vec![
+                "source",
+                "ingest-status",
+                "--index",
+                "hdfs-logs",
+                "--output-format",
+                "json",
+            ])
+            .unwrap();
+        let command = CliCommand::parse_cli_args(matches).unwrap();
+        let expected_command =
+            CliCommand::Source(SourceCliCommand::IngestStatus(IngestStatusArgs {
+                client_args: ClientArgs::default(),
+                index_id: "hdfs-logs".to_string(),
+                output_format: OutputFormat::Json,
+            }));
+        assert_eq!(command, expected_command);
+    }
+
+    #[test]
+    fn test_make_ingest_status_table() {
+        let checkpoint: SourceCheckpoint = [
+            (PartitionId::from("partition-1"), Position::offset(41u64)),
+            (PartitionId::from("partition-2"), Position::offset(9u64)),
+        ]
+        .into_iter()
+        .collect();
+        let ingest_statuses = vec![
+            SourceIngestStatus {
+                source_id: "bar-source".to_string(),
+                enabled: true,
+                checkpoint,
+                lag: Some(58),
+            },
+            SourceIngestStatus {
+                source_id: "foo-source".to_string(),
+                enabled: false,
+                checkpoint: SourceCheckpoint::default(),
+                lag: None,
+            },
+        ];
+        let table = make_ingest_status_table(ingest_statuses).to_string();
+        let rows: Vec<&str> = table.lines().collect();
+        assert!(rows.iter().any(|row| row.contains("bar-source")
+            && row.contains("partition-1: 00000000000000000041")
+            && row.contains("58")));
+        assert!(rows
+            .iter()
+            .any(|row| row.contains("partition-2: 00000000000000000009")));
+        assert!(rows
+            .iter()
+            .any(|row| row.contains("foo-source") && row.contains("false") && row.contains("n/a")));
+    }
+
     #[test]
     fn test_make_list_sources_table() {
         let sources = [
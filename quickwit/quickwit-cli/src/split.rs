@@ -108,7 +108,7 @@ pub fn build_split_command() -> Command {
 }
 
 #[derive(Debug, Eq, PartialEq)]
-enum OutputFormat {
+pub(crate) enum OutputFormat {
     Table, // Default
     Json,
     PrettyJson,
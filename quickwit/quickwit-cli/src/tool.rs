@@ -17,21 +17,27 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
+use std::collections::hash_map::RandomState;
 use std::collections::{HashSet, VecDeque};
+use std::hash::BuildHasher;
 use std::io::{stdout, IsTerminal, Stdout, Write};
 use std::num::NonZeroUsize;
-use std::path::PathBuf;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::time::{Duration, Instant};
 use std::{env, fmt, io};
 
 use anyhow::{bail, Context};
+use bytesize::ByteSize;
 use clap::{arg, ArgMatches, Command};
 use colored::{ColoredString, Colorize};
 use humantime::format_duration;
 use quickwit_actors::{ActorExitStatus, ActorHandle, Universe};
 use quickwit_cluster::{ChannelTransport, Cluster, ClusterMember, FailureDetectorConfig};
+use quickwit_common::fs::get_cache_directory_path;
 use quickwit_common::pubsub::EventBroker;
+use quickwit_common::rand::append_random_suffix;
 use quickwit_common::runtimes::RuntimesConfig;
 use quickwit_common::uri::Uri;
 use quickwit_config::service::QuickwitService;
@@ -39,32 +45,43 @@ use quickwit_config::{
     IndexerConfig, NodeConfig, SourceConfig, SourceInputFormat, SourceParams, TransformConfig,
     VecSourceParams, CLI_INGEST_SOURCE_ID,
 };
-use quickwit_index_management::{clear_cache_directory, IndexService};
+use quickwit_directories::{get_hotcache_from_split, BundleDirectory};
+use quickwit_index_management::{
+    clear_cache_directory, get_split_cache_num_bytes, prune_split_cache, IndexService,
+};
 use quickwit_indexing::actors::{IndexingService, MergePipeline, MergePipelineId};
 use quickwit_indexing::models::{
     DetachIndexingPipeline, DetachMergePipeline, IndexingStatistics, SpawnPipeline,
 };
-use quickwit_indexing::IndexingPipeline;
+use quickwit_indexing::{get_tantivy_directory_from_split_bundle, IndexingPipeline};
 use quickwit_ingest::IngesterPool;
-use quickwit_metastore::IndexMetadataResponseExt;
+use quickwit_metastore::{
+    IndexMetadataResponseExt, ListSplitsRequestExt, MetastoreServiceStreamSplitsExt,
+};
 use quickwit_proto::indexing::CpuCapacity;
-use quickwit_proto::metastore::{IndexMetadataRequest, MetastoreService, MetastoreServiceClient};
+use quickwit_proto::metastore::{
+    IndexMetadataRequest, ListSplitsRequest, MetastoreService, MetastoreServiceClient,
+};
 use quickwit_proto::search::{CountHits, SearchResponse};
 use quickwit_proto::types::{NodeId, PipelineUid};
 use quickwit_search::{single_node_search, SearchResponseRest};
 use quickwit_serve::{
     search_request_from_api_request, BodyFormat, SearchRequestQueryString, SortBy,
 };
-use quickwit_storage::{BundleStorage, Storage};
+use quickwit_storage::{OwnedBytes, Storage, StorageResolver};
+use tabled::Tabled;
 use thousands::Separable;
 use tracing::{debug, info};
 
 use crate::checklist::{GREEN_COLOR, RED_COLOR};
 use crate::{
-    config_cli_arg, get_resolvers, load_node_config, run_index_checklist, start_actor_runtimes,
-    THROUGHPUT_WINDOW_SIZE,
+    config_cli_arg, get_resolvers, load_node_config, make_table, run_index_checklist,
+    start_actor_runtimes, THROUGHPUT_WINDOW_SIZE,
 };
 
+/// Name of the file the hotcache of a split is extracted to.
+const HOTCACHE_FILE_NAME: &str = "hotcache";
+
 pub fn build_tool_command() -> Command {
     Command::new("tool")
         .about("Performs utility operations. Requires a node config.")
@@ -131,10 +148,14 @@ pub fn build_tool_command() -> Command {
                 .args(&[
                     arg!(--index <INDEX> "ID of the target index")
                         .display_order(1)
-                        .required(true),
+                        .required_unless_present("split-uri"),
                     arg!(--split <SPLIT> "ID of the target split")
                         .display_order(2)
-                        .required(true),
+                        .required_unless_present("split-uri"),
+                    arg!(--"split-uri" <SPLIT_URI> "Path or URI of the split file to extract, in lieu of `--index` and `--split`.")
+                        .display_order(3)
+                        .required(false)
+                        .conflicts_with_all(["index", "split"]),
                     arg!(--"target-dir" <TARGET_DIR> "Directory to extract the split to."),
                 ])
             )
@@ -153,6 +174,35 @@ pub fn build_tool_command() -> Command {
                         .required(false),
                 ])
             )
+        .subcommand(
+            Command::new("gc-cache")
+                .display_order(10)
+                .about("Reports the size of the searcher split cache and prunes it. The searcher using the cache must be stopped.")
+                .args(&[
+                    arg!(--"target-size" <TARGET_SIZE> "Evicts the least recently used splits until the cache fits in the target size (e.g. `10GB`).")
+                        .required(false),
+                    arg!(--clear "Clears the searcher split cache and the indexer split cache entirely.")
+                        .required(false)
+                        .conflicts_with("target-size"),
+                ])
+            )
+        .subcommand(
+            Command::new("benchmark-storage")
+                .display_order(10)
+                .about("Measures the read latency and throughput of a storage.")
+                .long_about("Writes a test object to the target storage, measures the latency of sequential and random `get_slice` requests of various sizes as well as the throughput of `get_all` requests, then deletes the test object.")
+                .args(&[
+                    arg!(--"storage-uri" <STORAGE_URI> "URI of the storage to benchmark (e.g. `s3://my-bucket/benchmark`).")
+                        .display_order(1)
+                        .required(true),
+                    arg!(--"object-size" <OBJECT_SIZE> "Size of the test object.")
+                        .default_value("64MiB")
+                        .required(false),
+                    arg!(--"num-requests" <NUM_REQUESTS> "Number of requests issued for each measurement.")
+                        .default_value("20")
+                        .required(false),
+                ])
+            )
         .subcommand(
             Command::new("merge")
                 .display_order(10)
@@ -203,6 +253,13 @@ pub struct GarbageCollectIndexArgs {
     pub dry_run: bool,
 }
 
+#[derive(Debug, Eq, PartialEq)]
+pub struct GarbageCollectCacheArgs {
+    pub config_uri: Uri,
+    pub target_size: Option<ByteSize>,
+    pub clear: bool,
+}
+
 #[derive(Debug, Eq, PartialEq)]
 pub struct MergeArgs {
     pub config_uri: Uri,
@@ -213,14 +270,25 @@ pub struct MergeArgs {
 #[derive(Debug, Eq, PartialEq)]
 pub struct ExtractSplitArgs {
     pub config_uri: Uri,
-    pub index_id: String,
-    pub split_id: String,
+    pub index_id: Option<String>,
+    pub split_id: Option<String>,
+    pub split_uri: Option<Uri>,
     pub target_dir: PathBuf,
 }
 
+#[derive(Debug, Eq, PartialEq)]
+pub struct BenchmarkStorageArgs {
+    pub config_uri: Uri,
+    pub storage_uri: Uri,
+    pub object_size: ByteSize,
+    pub num_requests: usize,
+}
+
 #[derive(Debug, Eq, PartialEq)]
 pub enum ToolCliCommand {
+    BenchmarkStorage(BenchmarkStorageArgs),
     GarbageCollect(GarbageCollectIndexArgs),
+    GarbageCollectCache(GarbageCollectCacheArgs),
     LocalIngest(LocalIngestDocsArgs),
     LocalSearch(LocalSearchArgs),
     Merge(MergeArgs),
@@ -233,7 +301,9 @@ impl ToolCliCommand {
             .remove_subcommand()
             .context("failed to parse tool subcommand")?;
         match subcommand.as_str() {
+            "benchmark-storage" => Self::parse_benchmark_storage_args(submatches),
             "gc" => Self::parse_garbage_collect_args(submatches),
+            "gc-cache" => Self::parse_garbage_collect_cache_args(submatches),
             "local-ingest" => Self::parse_local_ingest_args(submatches),
             "local-search" => Self::parse_local_search_args(submatches),
             "merge" => Self::parse_merge_args(submatches),
@@ -367,13 +437,69 @@ impl ToolCliCommand {
         }))
     }
 
+    fn parse_garbage_collect_cache_args(mut matches: ArgMatches) -> anyhow::Result<Self> {
+        let config_uri = matches
+            .get_one("config")
+            .map(|uri_str: &String| Uri::from_str(uri_str))
+            .expect("`config` should be a required arg.")?;
+        let target_size = matches
+            .remove_one::<String>("target-size")
+            .map(|target_size_str| {
+                ByteSize::from_str(&target_size_str).map_err(|error| {
+                    anyhow::anyhow!("invalid target size `{target_size_str}`: {error}")
+                })
+            })
+            .transpose()?;
+        let clear = matches.get_flag("clear");
+        Ok(Self::GarbageCollectCache(GarbageCollectCacheArgs {
+            config_uri,
+            target_size,
+            clear,
+        }))
+    }
+
+    fn parse_benchmark_storage_args(mut matches: ArgMatches) -> anyhow::Result<Self> {
+        let config_uri = matches
+            .remove_one::<String>("config")
+            .map(|uri_str| Uri::from_str(&uri_str))
+            .expect("`config` should be a required arg.")?;
+        let storage_uri = matches
+            .remove_one::<String>("storage-uri")
+            .map(|uri_str| Uri::from_str(&uri_str))
+            .expect("`storage-uri` should be a required arg.")?;
+        let object_size = matches
+            .remove_one::<String>("object-size")
+            .map(|object_size_str| {
+                ByteSize::from_str(&object_size_str).map_err(|error| {
+                    anyhow::anyhow!("invalid object size `{object_size_str}`: {error}")
+                })
+            })
+            .expect("`object-size` should have a default value.")?;
+        if object_size.as_u64() == 0 {
+            bail!("object size must be greater than zero");
+        }
+        let num_requests: usize = matches
+            .remove_one::<String>("num-requests")
+            .expect("`num-requests` should have a default value.")
+            .parse()?;
+        if num_requests == 0 {
+            bail!("number of requests must be greater than zero");
+        }
+        Ok(Self::BenchmarkStorage(BenchmarkStorageArgs {
+            config_uri,
+            storage_uri,
+            object_size,
+            num_requests,
+        }))
+    }
+
     fn parse_extract_split_args(mut matches: ArgMatches) -> anyhow::Result<Self> {
-        let index_id = matches
-            .remove_one::<String>("index")
-            .expect("`index` should be a required arg.");
-        let split_id = matches
-            .remove_one::<String>("split")
-            .expect("`split` should be a required arg.");
+        let index_id = matches.remove_one::<String>("index");
+        let split_id = matches.remove_one::<String>("split");
+        let split_uri = matches
+            .remove_one::<String>("split-uri")
+            .map(|uri_str| Uri::from_str(&uri_str))
+            .transpose()?;
         let config_uri = matches
             .remove_one::<String>("config")
             .map(|uri_str| Uri::from_str(&uri_str))
@@ -386,13 +512,16 @@ impl ToolCliCommand {
             config_uri,
             index_id,
             split_id,
+            split_uri,
             target_dir,
         }))
     }
 
     pub async fn execute(self) -> anyhow::Result<()> {
         match self {
+            Self::BenchmarkStorage(args) => benchmark_storage_cli(args).await,
             Self::GarbageCollect(args) => garbage_collect_index_cli(args).await,
+            Self::GarbageCollectCache(args) => garbage_collect_cache_cli(args).await,
             Self::LocalIngest(args) => local_ingest_docs_cli(args).await,
             Self::LocalSearch(args) => local_search_cli(args).await,
             Self::Merge(args) => merge_cli(args).await,
@@ -550,7 +679,14 @@ pub async fn local_search_cli(args: LocalSearchArgs) -> anyhow::Result<()> {
         aggs,
         format: BodyFormat::Json,
         sort_by,
+        search_after: None,
         count_all: CountHits::CountAll,
+        track_total_hits: None,
+        allow_partial_results: false,
+        match_all_if_empty_query: true,
+        split_ids: None,
+        allow_high_cardinality_aggregations: false,
+        include_split_publish_timestamp: false,
     };
     let search_request =
         search_request_from_api_request(vec![args.index_id], search_request_query_string)?;
@@ -715,36 +851,341 @@ pub async fn garbage_collect_index_cli(args: GarbageCollectIndexArgs) -> anyhow:
     Ok(())
 }
 
+pub async fn garbage_collect_cache_cli(args: GarbageCollectCacheArgs) -> anyhow::Result<()> {
+    debug!(args=?args, "garbage-collect-cache");
+    let config = load_node_config(&args.config_uri).await?;
+    let num_bytes = get_split_cache_num_bytes(&config.data_dir_path).await?;
+    println!("Searcher split cache size: {}", ByteSize(num_bytes));
+
+    if args.clear {
+        println!("❯ Clearing local cache directories...");
+        prune_split_cache(&config.data_dir_path, 0).await?;
+        if get_cache_directory_path(&config.data_dir_path).exists() {
+            clear_cache_directory(&config.data_dir_path).await?;
+        }
+        println!(
+            "{} Local cache directories cleared.",
+            "✔".color(GREEN_COLOR)
+        );
+    } else if let Some(target_size) = args.target_size {
+        println!("❯ Pruning searcher split cache...");
+        let num_bytes = prune_split_cache(&config.data_dir_path, target_size.as_u64()).await?;
+        println!(
+            "{} Searcher split cache pruned to {}.",
+            "✔".color(GREEN_COLOR),
+            ByteSize(num_bytes)
+        );
+    }
+    Ok(())
+}
+
 async fn extract_split_cli(args: ExtractSplitArgs) -> anyhow::Result<()> {
     debug!(args=?args, "extract-split");
     println!("❯ Extracting split...");
 
+    let (split_uri, storage_resolver) = match args.split_uri {
+        // Local splits are extracted in place and do not require a node config.
+        Some(split_uri) if split_uri.filepath().is_some() => {
+            (split_uri, StorageResolver::unconfigured())
+        }
+        split_uri_opt => {
+            let config = load_node_config(&args.config_uri).await?;
+            let (storage_resolver, metastore_resolver) =
+                get_resolvers(&config.storage_configs, &config.metastore_configs);
+            let split_uri = if let Some(split_uri) = split_uri_opt {
+                split_uri
+            } else {
+                let index_id = args.index_id.expect("`index` should be a required arg.");
+                let split_id = args.split_id.expect("`split` should be a required arg.");
+                let mut metastore = metastore_resolver.resolve(&config.metastore_uri).await?;
+                let index_metadata = metastore
+                    .index_metadata(IndexMetadataRequest::for_index_id(index_id.clone()))
+                    .await?
+                    .deserialize_index_metadata()?;
+                let list_splits_request =
+                    ListSplitsRequest::try_from_index_uid(index_metadata.index_uid.clone())?;
+                let split_metadata = metastore
+                    .list_splits(list_splits_request)
+                    .await?
+                    .collect_splits_metadata()
+                    .await?
+                    .into_iter()
+                    .find(|split_metadata| split_metadata.split_id() == split_id)
+                    .with_context(|| {
+                        format!("split `{split_id}` does not exist in index `{index_id}`")
+                    })?;
+                index_metadata
+                    .index_uri()
+                    .join(split_metadata.split_path())?
+            };
+            (split_uri, storage_resolver)
+        }
+    };
+    let extracted_files = extract_split(&storage_resolver, &split_uri, &args.target_dir).await?;
+    for extracted_file in &extracted_files {
+        println!("Extracted {extracted_file:?}");
+    }
+    println!("{} Split successfully extracted.", "✔".color(GREEN_COLOR));
+    Ok(())
+}
+
+/// Extracts the files bundled in the split located at `split_uri`, as well as its hotcache, into
+/// `target_dir`. Splits that are not on the local file system are first downloaded to a
+/// temporary directory.
+///
+/// Returns the paths of the extracted files.
+async fn extract_split(
+    storage_resolver: &StorageResolver,
+    split_uri: &Uri,
+    target_dir: &Path,
+) -> anyhow::Result<Vec<PathBuf>> {
+    let download_dir = tempfile::tempdir()?;
+
+    let split_path = if let Some(split_path) = split_uri.filepath() {
+        split_path.to_path_buf()
+    } else {
+        let parent_uri = split_uri
+            .parent()
+            .with_context(|| format!("failed to resolve parent of split URI `{split_uri}`"))?;
+        let split_file_name = split_uri
+            .file_name()
+            .with_context(|| format!("failed to resolve file name of split URI `{split_uri}`"))?;
+        let storage = storage_resolver.resolve(&parent_uri).await?;
+        let split_path = download_dir.path().join(split_file_name);
+        storage.copy_to_file(split_file_name, &split_path).await?;
+        split_path
+    };
+    let split_data = OwnedBytes::new(tokio::fs::read(&split_path).await?);
+    let hotcache_bytes = get_hotcache_from_split(split_data.clone())?;
+    let file_paths: Vec<PathBuf> = BundleDirectory::get_stats_split(split_data)?
+        .into_iter()
+        .map(|(file_path, _num_bytes)| file_path)
+        .filter(|file_path| file_path != Path::new(HOTCACHE_FILE_NAME))
+        .collect();
+    let bundle_directory = get_tantivy_directory_from_split_bundle(&split_path)?;
+
+    tokio::fs::create_dir_all(target_dir).await?;
+    let mut extracted_files = Vec::with_capacity(file_paths.len() + 1);
+
+    for file_path in file_paths {
+        let file_bytes = bundle_directory.atomic_read(&file_path)?;
+        let output_path = target_dir.join(&file_path);
+        tokio::fs::write(&output_path, file_bytes).await?;
+        extracted_files.push(output_path);
+    }
+    let hotcache_path = target_dir.join(HOTCACHE_FILE_NAME);
+    tokio::fs::write(&hotcache_path, hotcache_bytes.as_slice()).await?;
+    extracted_files.push(hotcache_path);
+
+    Ok(extracted_files)
+}
+
+async fn benchmark_storage_cli(args: BenchmarkStorageArgs) -> anyhow::Result<()> {
+    debug!(args=?args, "benchmark-storage");
+    println!("❯ Benchmarking storage `{}`...", args.storage_uri);
+
     let config = load_node_config(&args.config_uri).await?;
-    let (storage_resolver, metastore_resolver) =
+    let (storage_resolver, _metastore_resolver) =
         get_resolvers(&config.storage_configs, &config.metastore_configs);
-    let mut metastore = metastore_resolver.resolve(&config.metastore_uri).await?;
-    let index_metadata = metastore
-        .index_metadata(IndexMetadataRequest::for_index_id(args.index_id))
-        .await?
-        .deserialize_index_metadata()?;
-    let index_storage = storage_resolver.resolve(index_metadata.index_uri()).await?;
-    let split_file = PathBuf::from(format!("{}.split", args.split_id));
-    let split_data = index_storage.get_all(split_file.as_path()).await?;
-    let (_hotcache_bytes, bundle_storage) = BundleStorage::open_from_split_data_with_owned_bytes(
-        index_storage,
-        split_file,
-        split_data,
-    )?;
-    std::fs::create_dir_all(&args.target_dir)?;
-    for path in bundle_storage.iter_files() {
-        let mut out_path = args.target_dir.to_owned();
-        out_path.push(path);
-        println!("Copying {out_path:?}");
-        bundle_storage.copy_to_file(path, &out_path).await?;
+    let storage = storage_resolver.resolve(&args.storage_uri).await?;
+    let report = benchmark_storage(
+        &*storage,
+        args.object_size.as_u64() as usize,
+        args.num_requests,
+    )
+    .await?;
+    println!(
+        "Wrote {} test object in {}.",
+        ByteSize(report.object_size as u64),
+        format_duration(report.put_duration),
+    );
+    let rows = report
+        .measurements
+        .iter()
+        .map(StorageBenchmarkRow::from_measurement);
+    println!("\n{}\n", make_table("Storage Benchmark", rows, false));
+    println!("{} Storage benchmark completed.", "✔".color(GREEN_COLOR));
+    Ok(())
+}
+
+/// Sizes of the slices read by the `get_slice` measurements of the storage benchmark. Sizes larger
+/// than the test object are skipped.
+const BENCHMARK_SLICE_SIZES: [usize; 4] = [4 * 1024, 64 * 1024, 1024 * 1024, 8 * 1024 * 1024];
+
+#[derive(Debug)]
+struct StorageBenchmarkReport {
+    object_size: usize,
+    put_duration: Duration,
+    measurements: Vec<StorageBenchmarkMeasurement>,
+}
+
+/// Latencies of a series of identical read requests, issued one after the other.
+#[derive(Debug)]
+struct StorageBenchmarkMeasurement {
+    operation: &'static str,
+    request_size: usize,
+    /// Sorted in ascending order.
+    latencies: Vec<Duration>,
+}
+
+impl StorageBenchmarkMeasurement {
+    fn new(operation: &'static str, request_size: usize, mut latencies: Vec<Duration>) -> Self {
+        latencies.sort();
+        Self {
+            operation,
+            request_size,
+            latencies,
+        }
     }
 
-    println!("{} Split successfully extracted.", "✔".color(GREEN_COLOR));
-    Ok(())
+    /// Returns the latency percentile using the nearest-rank method.
+    fn percentile(&self, percentile: f64) -> Duration {
+        let num_requests = self.latencies.len();
+        let rank = (percentile / 100.0 * num_requests as f64).ceil() as usize;
+        self.latencies[rank.clamp(1, num_requests) - 1]
+    }
+
+    /// Returns the throughput in bytes per second.
+    fn throughput(&self) -> f64 {
+        let num_bytes = self.request_size * self.latencies.len();
+        let total_duration: Duration = self.latencies.iter().sum();
+        num_bytes as f64 / total_duration.max(Duration::from_nanos(1)).as_secs_f64()
+    }
+}
+
+#[derive(Tabled)]
+struct StorageBenchmarkRow {
+    #[tabled(rename = "Operation")]
+    operation: &'static str,
+    #[tabled(rename = "Request size")]
+    request_size: ByteSize,
+    #[tabled(rename = "p50")]
+    p50: String,
+    #[tabled(rename = "p90")]
+    p90: String,
+    #[tabled(rename = "p99")]
+    p99: String,
+    #[tabled(rename = "Throughput")]
+    throughput: String,
+}
+
+impl StorageBenchmarkRow {
+    fn from_measurement(measurement: &StorageBenchmarkMeasurement) -> Self {
+        let format_latency = |latency: Duration| format!("{:.2}ms", latency.as_secs_f64() * 1000.0);
+        Self {
+            operation: measurement.operation,
+            request_size: ByteSize(measurement.request_size as u64),
+            p50: format_latency(measurement.percentile(50.0)),
+            p90: format_latency(measurement.percentile(90.0)),
+            p99: format_latency(measurement.percentile(99.0)),
+            throughput: format!("{}/s", ByteSize(measurement.throughput() as u64)),
+        }
+    }
+}
+
+/// Writes a test object of `object_size` bytes to `storage`, measures the latency of
+/// `num_requests` sequential and random `get_slice` requests for each slice size and of
+/// `num_requests` `get_all` requests, then deletes the test object.
+async fn benchmark_storage(
+    storage: &dyn Storage,
+    object_size: usize,
+    num_requests: usize,
+) -> anyhow::Result<StorageBenchmarkReport> {
+    let object_path = PathBuf::from(append_random_suffix("quickwit-storage-benchmark"));
+    let payload: Vec<u8> = (0..object_size).map(|idx| idx as u8).collect();
+
+    let put_start = Instant::now();
+    storage.put(&object_path, Box::new(payload)).await?;
+    let put_duration = put_start.elapsed();
+
+    let measurements_res =
+        measure_storage_reads(storage, &object_path, object_size, num_requests).await;
+    // The test object is deleted even if one of the measurements failed.
+    storage.delete(&object_path).await?;
+
+    Ok(StorageBenchmarkReport {
+        object_size,
+        put_duration,
+        measurements: measurements_res?,
+    })
+}
+
+async fn measure_storage_reads(
+    storage: &dyn Storage,
+    object_path: &Path,
+    object_size: usize,
+    num_requests: usize,
+) -> anyhow::Result<Vec<StorageBenchmarkMeasurement>> {
+    let random_state = RandomState::new();
+    let mut measurements = Vec::new();
+
+    for slice_size in BENCHMARK_SLICE_SIZES {
+        if slice_size > object_size {
+            continue;
+        }
+        let num_slices = object_size / slice_size;
+
+        let mut sequential_latencies = Vec::with_capacity(num_requests);
+        for request_idx in 0..num_requests {
+            let start = (request_idx % num_slices) * slice_size;
+            let latency = time_get_slice(storage, object_path, start..start + slice_size).await?;
+            sequential_latencies.push(latency);
+        }
+        measurements.push(StorageBenchmarkMeasurement::new(
+            "get_slice (sequential)",
+            slice_size,
+            sequential_latencies,
+        ));
+        let mut random_latencies = Vec::with_capacity(num_requests);
+        for request_idx in 0..num_requests {
+            let start = (random_state.hash_one(request_idx) as usize % num_slices) * slice_size;
+            let latency = time_get_slice(storage, object_path, start..start + slice_size).await?;
+            random_latencies.push(latency);
+        }
+        measurements.push(StorageBenchmarkMeasurement::new(
+            "get_slice (random)",
+            slice_size,
+            random_latencies,
+        ));
+    }
+    let mut get_all_latencies = Vec::with_capacity(num_requests);
+    for _ in 0..num_requests {
+        let start = Instant::now();
+        let bytes = storage.get_all(object_path).await?;
+        get_all_latencies.push(start.elapsed());
+
+        if bytes.len() != object_size {
+            bail!(
+                "expected `get_all` to return {object_size} bytes, got {}",
+                bytes.len()
+            );
+        }
+    }
+    measurements.push(StorageBenchmarkMeasurement::new(
+        "get_all",
+        object_size,
+        get_all_latencies,
+    ));
+    Ok(measurements)
+}
+
+async fn time_get_slice(
+    storage: &dyn Storage,
+    object_path: &Path,
+    range: Range<usize>,
+) -> anyhow::Result<Duration> {
+    let expected_len = range.len();
+    let start = Instant::now();
+    let bytes = storage.get_slice(object_path, range).await?;
+    let latency = start.elapsed();
+
+    if bytes.len() != expected_len {
+        bail!(
+            "expected `get_slice` to return {expected_len} bytes, got {}",
+            bytes.len()
+        );
+    }
+    Ok(latency)
 }
 
 /// Starts a tokio task that displays the indexing statistics
@@ -952,3 +1393,94 @@ async fn create_empty_cluster(config: &NodeConfig) -> anyhow::Result<Cluster> {
 
     Ok(cluster)
 }
+
+#[cfg(test)]
+mod tests {
+    use quickwit_common::split_file;
+    use quickwit_indexing::TestSandbox;
+    use quickwit_storage::RamStorage;
+    use serde_json::json;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_extract_split() {
+        let index_id = "test-extract-split";
+        let doc_mapping_yaml = r#"
+            field_mappings:
+              - name: body
+                type: text
+        "#;
+        let test_sandbox = TestSandbox::create(index_id, doc_mapping_yaml, "{}", &["body"])
+            .await
+            .unwrap();
+        test_sandbox
+            .add_documents(vec![json!({"body": "hello"}), json!({"body": "world"})])
+            .await
+            .unwrap();
+        let splits = test_sandbox
+            .metastore()
+            .list_splits(ListSplitsRequest::try_from_index_uid(test_sandbox.index_uid()).unwrap())
+            .await
+            .unwrap()
+            .collect_splits()
+            .await
+            .unwrap();
+        assert_eq!(splits.len(), 1);
+
+        let split_id = splits[0].split_id();
+        let index_uri = Uri::from_str(&format!("ram://quickwit-test-indexes/{index_id}")).unwrap();
+        let split_uri = index_uri.join(split_file(split_id)).unwrap();
+        let target_dir = tempfile::tempdir().unwrap();
+        let extracted_files = extract_split(
+            &test_sandbox.storage_resolver(),
+            &split_uri,
+            target_dir.path(),
+        )
+        .await
+        .unwrap();
+
+        let split_data = test_sandbox
+            .storage()
+            .get_all(Path::new(&split_file(split_id)))
+            .await
+            .unwrap();
+        let expected_files: Vec<PathBuf> = BundleDirectory::get_stats_split(split_data)
+            .unwrap()
+            .into_iter()
+            .map(|(file_path, _num_bytes)| target_dir.path().join(file_path))
+            .collect();
+        assert_eq!(extracted_files.len(), expected_files.len());
+
+        for expected_file in &expected_files {
+            assert!(extracted_files.contains(expected_file));
+            assert!(expected_file.exists(), "{expected_file:?} should exist");
+        }
+        assert!(target_dir.path().join("meta.json").exists());
+        assert!(target_dir.path().join(HOTCACHE_FILE_NAME).exists());
+
+        test_sandbox.assert_quit().await;
+    }
+
+    #[tokio::test]
+    async fn test_benchmark_storage() {
+        let storage = RamStorage::default();
+        let object_size = 256 * 1024;
+        let report = benchmark_storage(&storage, object_size, 5).await.unwrap();
+        assert_eq!(report.object_size, object_size);
+
+        // 4KiB and 64KiB slices, sequential and random, plus `get_all`.
+        assert_eq!(report.measurements.len(), 5);
+
+        for measurement in &report.measurements {
+            assert_eq!(measurement.latencies.len(), 5);
+            assert!(measurement.throughput() > 0.0);
+            assert!(measurement.percentile(50.0) <= measurement.percentile(99.0));
+        }
+        let get_all_measurement = report.measurements.last().unwrap();
+        assert_eq!(get_all_measurement.operation, "get_all");
+        assert_eq!(get_all_measurement.request_size, object_size);
+
+        assert!(storage.list_files().await.is_empty());
+    }
+}
@@ -30,8 +30,8 @@ use helpers::{TestEnv, TestStorageType};
 use quickwit_cli::checklist::ChecklistError;
 use quickwit_cli::cli::build_cli;
 use quickwit_cli::index::{
-    create_index_cli, delete_index_cli, search_index, CreateIndexArgs, DeleteIndexArgs,
-    SearchIndexArgs,
+    create_index_cli, delete_index_cli, reindex, search_index, set_index_uri_cli, CreateIndexArgs,
+    DeleteIndexArgs, ReindexArgs, SearchIndexArgs, SetIndexUriArgs,
 };
 use quickwit_cli::tool::{
     garbage_collect_index_cli, local_ingest_docs_cli, GarbageCollectIndexArgs, LocalIngestDocsArgs,
@@ -49,6 +49,7 @@ use quickwit_proto::metastore::{
     DeleteSplitsRequest, EntityKind, IndexMetadataRequest, ListSplitsRequest,
     MarkSplitsForDeletionRequest, MetastoreError, MetastoreService, StageSplitsRequest,
 };
+use quickwit_rest_client::rest_client::CommitType;
 use serde_json::{json, Number, Value};
 use tokio::time::{sleep, Duration};
 
@@ -533,6 +534,147 @@ async fn test_search_index_cli() {
     assert_eq!(search_res.num_hits, 0);
 }
 
+#[tokio::test]
+async fn test_reindex_cli() {
+    quickwit_common::setup_logging_for_tests();
+    let index_id = append_random_suffix("test-reindex-cmd");
+    let test_env = create_test_env(index_id.clone(), TestStorageType::LocalFileSystem)
+        .await
+        .unwrap();
+    test_env.start_server().await.unwrap();
+
+    // The source index must store the source of its documents.
+    let index_config_path = test_env.resource_files["index_config"].as_path();
+    let index_config = std::fs::read_to_string(index_config_path).unwrap();
+    let source_index_config = index_config.replace(
+        "      field_mappings:",
+        "      store_source: true\n      field_mappings:",
+    );
+    std::fs::write(index_config_path, &source_index_config).unwrap();
+    create_logs_index(&test_env).await.unwrap();
+    local_ingest_docs(test_env.resource_files["logs"].as_path(), &test_env)
+        .await
+        .unwrap();
+
+    // The destination index indexes `level` with the raw tokenizer.
+    let dest_index_id = append_random_suffix("test-reindex-cmd-dest");
+    let dest_index_uri = test_env.metastore_uri.join(&dest_index_id).unwrap();
+    let dest_index_config = index_config
+        .replace(&index_id, &dest_index_id)
+        .replace(test_env.index_uri.as_str(), dest_index_uri.as_str())
+        .replace(
+            "        - name: level\n          type: text\n          stored: false",
+            "        - name: level\n          type: text\n          tokenizer: raw",
+        );
+    let dest_index_config_path = index_config_path.with_file_name("dest_index_config.yaml");
+    std::fs::write(&dest_index_config_path, dest_index_config).unwrap();
+    let client_args = || ClientArgs {
+        cluster_endpoint: test_env.cluster_endpoint.clone(),
+        ..Default::default()
+    };
+    create_index_cli(CreateIndexArgs {
+        client_args: client_args(),
+        index_config_uri: Uri::from_str(&format!("file://{}", dest_index_config_path.display()))
+            .unwrap(),
+        overwrite: false,
+        assume_yes: true,
+    })
+    .await
+    .unwrap();
+
+    let reindex_args = |source_index_id: &str, dest_index_id: &str| ReindexArgs {
+        client_args: client_args(),
+        source_index_id: source_index_id.to_string(),
+        dest_index_id: dest_index_id.to_string(),
+        query: "*".to_string(),
+        batch_size: 2,
+        commit_type: CommitType::Force,
+    };
+    let num_reindexed_docs = reindex(reindex_args(&index_id, &dest_index_id))
+        .await
+        .unwrap();
+    assert_eq!(num_reindexed_docs, 5);
+
+    let search_args = |index_id: &str| SearchIndexArgs {
+        client_args: client_args(),
+        index_id: index_id.to_string(),
+        query: "*".to_string(),
+        aggregation: None,
+        max_hits: 20,
+        start_offset: 0,
+        search_fields: None,
+        snippet_fields: None,
+        start_timestamp: None,
+        end_timestamp: None,
+        sort_by_score: false,
+    };
+    let source_search_res = search_index(search_args(&index_id)).await.unwrap();
+    let dest_search_res = search_index(search_args(&dest_index_id)).await.unwrap();
+    assert_eq!(source_search_res.num_hits, 5);
+    assert_eq!(dest_search_res.num_hits, source_search_res.num_hits);
+
+    // The destination index does not store the source of its documents.
+    let error = reindex(reindex_args(&dest_index_id, &index_id))
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("store_source"));
+}
+
+#[tokio::test]
+async fn test_set_index_uri_cli() {
+    quickwit_common::setup_logging_for_tests();
+    let index_id = append_random_suffix("test-set-uri-cmd");
+    let test_env = create_test_env(index_id.clone(), TestStorageType::LocalFileSystem)
+        .await
+        .unwrap();
+    test_env.start_server().await.unwrap();
+    create_logs_index(&test_env).await.unwrap();
+    local_ingest_docs(test_env.resource_files["logs"].as_path(), &test_env)
+        .await
+        .unwrap();
+
+    let new_index_path = test_env.data_dir_path.join("moved-indexes").join(&index_id);
+    let new_index_uri = Uri::from_str(&format!("file://{}", new_index_path.display())).unwrap();
+    let args = SetIndexUriArgs {
+        client_args: ClientArgs {
+            cluster_endpoint: test_env.cluster_endpoint.clone(),
+            ..Default::default()
+        },
+        index_id: index_id.clone(),
+        new_index_uri: new_index_uri.clone(),
+        move_data: true,
+        assume_yes: true,
+    };
+    set_index_uri_cli(args).await.unwrap();
+
+    // The metastore of the server has been updated, so we read the disk with a fresh metastore.
+    let mut metastore = MetastoreResolver::unconfigured()
+        .resolve(&test_env.metastore_uri)
+        .await
+        .unwrap();
+    let index_metadata = metastore
+        .index_metadata(IndexMetadataRequest::for_index_id(index_id.clone()))
+        .await
+        .unwrap()
+        .deserialize_index_metadata()
+        .unwrap();
+    assert_eq!(index_metadata.index_uri(), &new_index_uri);
+
+    let splits_metadata = metastore
+        .list_splits(ListSplitsRequest::try_from_index_uid(index_metadata.index_uid).unwrap())
+        .await
+        .unwrap()
+        .collect_splits_metadata()
+        .await
+        .unwrap();
+    assert_eq!(splits_metadata.len(), 1);
+
+    for split_metadata in splits_metadata {
+        let split_filename = quickwit_common::split_file(split_metadata.split_id());
+        assert!(new_index_path.join(split_filename).try_exists().unwrap());
+    }
+}
+
 #[tokio::test]
 async fn test_delete_index_cli_dry_run() {
     quickwit_common::setup_logging_for_tests();
@@ -65,6 +65,9 @@ const MARKED_FOR_DELETION_GRACE_PERIOD: usize = if cfg!(any(test, feature = "tes
 // `{INDEXING_TASK_PREFIX}{PIPELINE_ULID}`.
 const INDEXING_TASK_PREFIX: &str = "indexer.task:";
 
+// Suffix appended to the chitchat value of the indexing tasks whose source is paused.
+const PAUSED_INDEXING_TASK_SUFFIX: &str = ":paused";
+
 #[derive(Clone)]
 pub struct Cluster {
     cluster_id: String,
@@ -471,10 +474,16 @@ fn indexing_task_to_chitchat_kv(indexing_task: &IndexingTask) -> (String, String
         source_id,
         shard_ids,
         pipeline_uid: _,
+        paused,
     } = indexing_task;
     let key = format!("{INDEXING_TASK_PREFIX}{}", indexing_task.pipeline_uid());
     let shard_ids_str = shard_ids.iter().sorted().join(",");
-    let value = format!("{index_uid}:{source_id}:{shard_ids_str}");
+    let paused_suffix = if *paused {
+        PAUSED_INDEXING_TASK_SUFFIX
+    } else {
+        ""
+    };
+    let value = format!("{index_uid}:{source_id}:{shard_ids_str}{paused_suffix}");
     (key, value)
 }
 
@@ -489,6 +498,10 @@ fn parse_shard_ids_str(shard_ids_str: &str) -> Vec<ShardId> {
 fn chitchat_kv_to_indexing_task(key: &str, value: &str) -> Option<IndexingTask> {
     let pipeline_uid_str = key.strip_prefix(INDEXING_TASK_PREFIX)?;
     let pipeline_uid = PipelineUid::from_str(pipeline_uid_str).ok()?;
+    let (value, paused) = match value.strip_suffix(PAUSED_INDEXING_TASK_SUFFIX) {
+        Some(value) => (value, true),
+        None => (value, false),
+    };
     let (source_uid, shards_str) = value.rsplit_once(':')?;
     let (index_uid, source_id) = source_uid.rsplit_once(':')?;
     let shard_ids = parse_shard_ids_str(shards_str);
@@ -497,6 +510,7 @@ fn chitchat_kv_to_indexing_task(key: &str, value: &str) -> Option<IndexingTask>
         source_id: source_id.to_string(),
         pipeline_uid: Some(pipeline_uid),
         shard_ids,
+        paused,
     })
 }
 
@@ -896,12 +910,14 @@ mod tests {
             index_uid: "index-1:11111111111111111111111111".to_string(),
             source_id: "source-1".to_string(),
             shard_ids: Vec::new(),
+            paused: false,
         };
         let indexing_task2 = IndexingTask {
             pipeline_uid: Some(PipelineUid::from_u128(2u128)),
             index_uid: "index-1:11111111111111111111111111".to_string(),
             source_id: "source-1".to_string(),
             shard_ids: Vec::new(),
+            paused: false,
         };
         cluster2
             .set_self_key_value(GRPC_ADVERTISE_ADDR_KEY, "127.0.0.1:1001")
@@ -980,6 +996,7 @@ mod tests {
                     index_uid: format!("index-{index_id}:11111111111111111111111111"),
                     source_id: format!("source-{source_id}"),
                     shard_ids: Vec::new(),
+                    paused: false,
                 }
             })
             .collect_vec();
@@ -1208,6 +1225,7 @@ mod tests {
                 index_uid: "test:test1".to_string(),
                 source_id: "my-source1".to_string(),
                 shard_ids: vec![ShardId::from(1), ShardId::from(2)],
+                paused: false,
             }],
             &mut node_state,
         );
@@ -1218,6 +1236,7 @@ mod tests {
                 index_uid: "test:test1".to_string(),
                 source_id: "my-source1".to_string(),
                 shard_ids: vec![ShardId::from(1), ShardId::from(2), ShardId::from(3)],
+                paused: false,
             }],
             &mut node_state,
         );
@@ -1228,12 +1247,14 @@ mod tests {
                     index_uid: "test:test1".to_string(),
                     source_id: "my-source1".to_string(),
                     shard_ids: vec![ShardId::from(1), ShardId::from(2)],
+                    paused: false,
                 },
                 IndexingTask {
                     pipeline_uid: Some(PipelineUid::from_u128(2u128)),
                     index_uid: "test:test1".to_string(),
                     source_id: "my-source1".to_string(),
                     shard_ids: vec![ShardId::from(3), ShardId::from(4)],
+                    paused: false,
                 },
             ],
             &mut node_state,
@@ -1246,12 +1267,14 @@ mod tests {
                     index_uid: "test:test1".to_string(),
                     source_id: "my-source1".to_string(),
                     shard_ids: vec![ShardId::from(1), ShardId::from(2)],
+                    paused: false,
                 },
                 IndexingTask {
                     pipeline_uid: Some(PipelineUid::from_u128(2u128)),
                     index_uid: "test:test2".to_string(),
                     source_id: "my-source1".to_string(),
                     shard_ids: vec![ShardId::from(3), ShardId::from(4)],
+                    paused: false,
                 },
             ],
             &mut node_state,
@@ -1264,12 +1287,34 @@ mod tests {
                     index_uid: "test:test1".to_string(),
                     source_id: "my-source1".to_string(),
                     shard_ids: vec![ShardId::from(1), ShardId::from(2)],
+                    paused: false,
                 },
                 IndexingTask {
                     pipeline_uid: Some(PipelineUid::from_u128(2u128)),
                     index_uid: "test:test1".to_string(),
                     source_id: "my-source2".to_string(),
                     shard_ids: vec![ShardId::from(3), ShardId::from(4)],
+                    paused: false,
+                },
+            ],
+            &mut node_state,
+        );
+        // paused source.
+        test_serialize_indexing_tasks_aux(
+            &[
+                IndexingTask {
+                    pipeline_uid: Some(PipelineUid::from_u128(1u128)),
+                    index_uid: "test:test1".to_string(),
+                    source_id: "my-source1".to_string(),
+                    shard_ids: vec![ShardId::from(1), ShardId::from(2)],
+                    paused: true,
+                },
+                IndexingTask {
+                    pipeline_uid: Some(PipelineUid::from_u128(2u128)),
+                    index_uid: "test:test1".to_string(),
+                    source_id: "my-source2".to_string(),
+                    shard_ids: Vec::new(),
+                    paused: true,
                 },
             ],
             &mut node_state,
@@ -1307,5 +1352,16 @@ mod tests {
         assert_eq!(&task.index_uid, "my_index:uid");
         assert_eq!(&task.source_id, "my_source");
         assert_eq!(&task.shard_ids, &[ShardId::from(1), ShardId::from(3)]);
+        assert!(!task.paused);
+
+        let task = super::chitchat_kv_to_indexing_task(
+            "indexer.task:01BX5ZZKBKACTAV9WEVGEMMVS0",
+            "my_index:uid:my_source::paused",
+        )
+        .unwrap();
+        assert_eq!(&task.index_uid, "my_index:uid");
+        assert_eq!(&task.source_id, "my_source");
+        assert!(task.shard_ids.is_empty());
+        assert!(task.paused);
     }
 }
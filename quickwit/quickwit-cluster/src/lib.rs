@@ -71,7 +71,7 @@ pub async fn start_cluster_service(node_config: &NodeConfig) -> anyhow::Result<C
     let generation_id = GenerationId::now();
     let is_ready = false;
     let indexing_cpu_capacity = if node_config.is_service_enabled(QuickwitService::Indexer) {
-        node_config.indexer_config.cpu_capacity
+        node_config.indexer_config.scheduling_cpu_capacity()
     } else {
         CpuCapacity::zero()
     };
@@ -18,6 +18,7 @@
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
 use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 
 use anyhow::ensure;
 use heck::{ToSnakeCase, ToUpperCamelCase};
@@ -54,6 +55,13 @@ impl Codegen {
 
         for proto in args.protos {
             println!("cargo:rerun-if-changed={proto}");
+
+            if let Some(file_descriptor_set_dir) = &args.file_descriptor_set_dir {
+                let file_descriptor_set_path =
+                    file_descriptor_set_path(file_descriptor_set_dir, &proto);
+                args.prost_config
+                    .file_descriptor_set_path(file_descriptor_set_path);
+            }
             args.prost_config.compile_protos(&[proto], &args.includes)?;
         }
         Ok(())
@@ -64,6 +72,16 @@ impl Codegen {
     }
 }
 
+/// Returns the path of the file descriptor set of `proto`, for instance
+/// `<dir>/metastore_descriptor_set.bin` for `protos/quickwit/metastore.proto`.
+fn file_descriptor_set_path(file_descriptor_set_dir: &str, proto: &str) -> PathBuf {
+    let proto_stem = Path::new(proto)
+        .file_stem()
+        .expect("proto path should have a file name")
+        .to_string_lossy();
+    Path::new(file_descriptor_set_dir).join(format!("{proto_stem}_descriptor_set.bin"))
+}
+
 #[derive(Default)]
 pub struct CodegenBuilder {
     protos: Vec<String>,
@@ -74,6 +92,7 @@ pub struct CodegenBuilder {
     error_type_path: String,
     generate_extra_service_methods: bool,
     generate_prom_labels_for_requests: bool,
+    file_descriptor_set_dir: Option<String>,
 }
 
 impl CodegenBuilder {
@@ -117,6 +136,12 @@ impl CodegenBuilder {
         self
     }
 
+    /// Writes the file descriptor set of each proto to `<path>/<proto_stem>_descriptor_set.bin`.
+    pub fn with_file_descriptor_set_dir(mut self, path: &str) -> Self {
+        self.file_descriptor_set_dir = Some(path.to_string());
+        self
+    }
+
     pub fn run(self) -> anyhow::Result<()> {
         ensure!(!self.protos.is_empty(), "proto file list is empty");
         ensure!(!self.output_dir.is_empty(), "output directory is undefined");
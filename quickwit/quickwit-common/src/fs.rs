@@ -39,6 +39,11 @@ pub fn get_cache_directory_path(data_dir_path: &Path) -> PathBuf {
     data_dir_path.join("indexer-split-cache").join("splits")
 }
 
+/// Helper function to get the searcher split cache path.
+pub fn get_split_cache_directory_path(data_dir_path: &Path) -> PathBuf {
+    data_dir_path.join("searcher-split-cache")
+}
+
 #[cfg(test)]
 mod tests {
     use tempfile;
@@ -20,6 +20,7 @@
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex, Weak};
 
+use tokio::sync::Notify;
 use tracing::debug;
 
 #[derive(Clone, Default)]
@@ -30,6 +31,7 @@ pub struct KillSwitch {
 struct Inner {
     alive: AtomicBool,
     children: Mutex<Vec<Weak<Inner>>>,
+    killed: Notify,
 }
 
 impl Default for Inner {
@@ -37,6 +39,7 @@ impl Default for Inner {
         Self {
             alive: AtomicBool::new(true),
             children: Mutex::default(),
+            killed: Notify::new(),
         }
     }
 }
@@ -65,6 +68,22 @@ impl KillSwitch {
         self.inner.kill();
     }
 
+    /// Resolves once the kill switch is dead.
+    pub async fn killed(&self) {
+        loop {
+            let killed = self.inner.killed.notified();
+            tokio::pin!(killed);
+            // Registers interest in the notification before checking the flag, so that a kill
+            // happening in between is not missed.
+            killed.as_mut().enable();
+
+            if self.is_dead() {
+                return;
+            }
+            killed.await;
+        }
+    }
+
     // Creates a child killswitch.
     //
     // If the parent kill switch is dead to begin with, the child will be dead too.
@@ -87,6 +106,7 @@ impl Inner {
     pub fn kill(&self) {
         debug!("kill-switch-activated");
         self.alive.store(false, Ordering::Relaxed);
+        self.killed.notify_waiters();
         let mut lock = self.children.lock().unwrap();
         for weak in lock.drain(..) {
             if let Some(inner) = weak.upgrade() {
@@ -112,6 +132,21 @@ mod tests {
         assert!(kill_switch.is_dead());
     }
 
+    #[tokio::test]
+    async fn test_kill_switch_killed() {
+        let kill_switch = KillSwitch::default();
+        let child_kill_switch = kill_switch.child();
+
+        let killed_handle = tokio::spawn(async move { child_kill_switch.killed().await });
+        tokio::task::yield_now().await;
+        assert!(!killed_handle.is_finished());
+
+        kill_switch.kill();
+        killed_handle.await.unwrap();
+        // Resolves immediately once dead.
+        kill_switch.killed().await;
+    }
+
     #[test]
     fn test_kill_switch_child() {
         let kill_switch = KillSwitch::default();
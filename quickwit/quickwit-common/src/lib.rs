@@ -48,6 +48,7 @@ pub mod uri;
 use std::env;
 use std::fmt::{Debug, Display};
 use std::ops::{Range, RangeInclusive};
+use std::path::PathBuf;
 use std::str::FromStr;
 
 pub use coolid::new_coolid;
@@ -76,6 +77,16 @@ pub fn split_file(split_id: impl Display) -> String {
     format!("{split_id}.split")
 }
 
+/// Returns the path of a split file relative to the root of the index storage. Splits are
+/// stored at the root unless the index nests them under a prefix, such as `2024/03`.
+pub fn split_path(split_path_prefix_opt: Option<&str>, split_id: impl Display) -> PathBuf {
+    let split_file = split_file(split_id);
+    match split_path_prefix_opt {
+        Some(split_path_prefix) => PathBuf::from(split_path_prefix).join(split_file),
+        None => PathBuf::from(split_file),
+    }
+}
+
 pub fn get_from_env<T: FromStr + Debug>(key: &str, default_value: T) -> T {
     if let Ok(value_str) = std::env::var(key) {
         if let Ok(value) = T::from_str(&value_str) {
@@ -204,6 +215,15 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_split_path() {
+        assert_eq!(split_path(None, "split-1"), PathBuf::from("split-1.split"));
+        assert_eq!(
+            split_path(Some("2024/03"), "split-1"),
+            PathBuf::from("2024/03/split-1.split")
+        );
+    }
+
     #[test]
     fn test_get_from_env() {
         const TEST_KEY: &str = "TEST_KEY";
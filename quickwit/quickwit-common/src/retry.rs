@@ -64,6 +64,9 @@ pub struct RetryParams {
     pub base_delay: Duration,
     pub max_delay: Duration,
     pub max_attempts: usize,
+    /// Whether to randomize the delays between attempts, so that concurrent requests failing at
+    /// the same time do not retry in lockstep.
+    pub jitter: bool,
 }
 
 impl Default for RetryParams {
@@ -72,6 +75,7 @@ impl Default for RetryParams {
             base_delay: DEFAULT_BASE_DELAY,
             max_delay: DEFAULT_MAX_DELAY,
             max_attempts: DEFAULT_MAX_ATTEMPTS,
+            jitter: true,
         }
     }
 }
@@ -84,6 +88,8 @@ impl RetryParams {
     /// The caller should pass the number of attempts that have been performed so far. Not to be
     /// confused with the number of retries, which is one less than the number of attempts.
     ///
+    /// Without jitter, the delay is the capped exponential backoff itself.
+    ///
     /// # Panics
     ///
     /// Panics if `num_attempts` is zero.
@@ -92,6 +98,9 @@ impl RetryParams {
 
         let delay_ms = self.base_delay.as_millis() as u64 * 2u64.pow(num_attempts as u32 - 1);
         let ceil_delay_ms = delay_ms.min(self.max_delay.as_millis() as u64);
+        if !self.jitter {
+            return Duration::from_millis(ceil_delay_ms);
+        }
         let half_delay_ms = ceil_delay_ms / 2;
         let jitter_range = 0..half_delay_ms + 1;
         let jittered_delay_ms = half_delay_ms + rand::thread_rng().gen_range(jitter_range);
@@ -258,4 +267,18 @@ mod tests {
             .collect();
         assert_eq!(simulate_retries(retry_sequence).await, Ok(()));
     }
+
+    #[test]
+    fn test_compute_delay_without_jitter() {
+        let retry_params = RetryParams {
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(500),
+            max_attempts: 10,
+            jitter: false,
+        };
+        assert_eq!(retry_params.compute_delay(1), Duration::from_millis(100));
+        assert_eq!(retry_params.compute_delay(2), Duration::from_millis(200));
+        assert_eq!(retry_params.compute_delay(3), Duration::from_millis(400));
+        assert_eq!(retry_params.compute_delay(4), Duration::from_millis(500));
+    }
 }
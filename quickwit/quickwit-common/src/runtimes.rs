@@ -43,6 +43,12 @@ pub enum RuntimeType {
     ///
     /// Task are expect to yield within 500 micros.
     NonBlocking,
+
+    /// The admin runtime serves the admin and control requests, such as health checks or index
+    /// creation, so that they remain responsive when the node is saturated by searches.
+    ///
+    /// Tasks are expected to be short and to yield within 500 micros.
+    Admin,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -51,6 +57,8 @@ pub struct RuntimesConfig {
     pub num_threads_non_blocking: usize,
     /// Number of worker threads allocated to the blocking runtime.
     pub num_threads_blocking: usize,
+    /// Number of worker threads allocated to the admin runtime.
+    pub num_threads_admin: usize,
 }
 
 impl RuntimesConfig {
@@ -59,6 +67,7 @@ impl RuntimesConfig {
         RuntimesConfig {
             num_threads_blocking: 1,
             num_threads_non_blocking: 1,
+            num_threads_admin: 1,
         }
     }
 
@@ -68,9 +77,12 @@ impl RuntimesConfig {
         // On the other hand the blocking actors are cpu intensive. We allocate
         // almost all of the threads to them.
         let num_threads_blocking = (num_cpus - num_threads_non_blocking).max(1);
+        // Admin requests are rare and light: a single thread is enough to keep them responsive.
+        let num_threads_admin = 1;
         RuntimesConfig {
             num_threads_non_blocking,
             num_threads_blocking,
+            num_threads_admin,
         }
     }
 }
@@ -106,6 +118,17 @@ fn start_runtimes(config: RuntimesConfig) -> HashMap<RuntimeType, Runtime> {
         .build()
         .unwrap();
     runtimes.insert(RuntimeType::NonBlocking, non_blocking_runtime);
+    let admin_runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(config.num_threads_admin)
+        .thread_name_fn(|| {
+            static ATOMIC_ID: AtomicUsize = AtomicUsize::new(0);
+            let id = ATOMIC_ID.fetch_add(1, Ordering::AcqRel);
+            format!("admin-{id}")
+        })
+        .enable_all()
+        .build()
+        .unwrap();
+    runtimes.insert(RuntimeType::Admin, admin_runtime);
     runtimes
 }
 
@@ -151,6 +174,7 @@ mod tests {
         let runtime = RuntimesConfig::with_num_cpus(10);
         assert_eq!(runtime.num_threads_blocking, 8);
         assert_eq!(runtime.num_threads_non_blocking, 2);
+        assert_eq!(runtime.num_threads_admin, 1);
     }
 
     #[test]
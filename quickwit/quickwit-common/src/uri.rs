@@ -43,6 +43,9 @@ pub enum Protocol {
     Ram = 6,
     S3 = 7,
     Google = 8,
+    Http = 9,
+    Https = 10,
+    WebHdfs = 11,
 }
 
 impl Protocol {
@@ -56,6 +59,9 @@ impl Protocol {
             Protocol::Ram => "ram",
             Protocol::S3 => "s3",
             Protocol::Google => "gs",
+            Protocol::Http => "http",
+            Protocol::Https => "https",
+            Protocol::WebHdfs => "webhdfs",
         }
     }
 
@@ -74,6 +80,10 @@ impl Protocol {
     pub fn is_database(&self) -> bool {
         matches!(&self, Protocol::PostgreSQL)
     }
+
+    pub fn is_http(&self) -> bool {
+        matches!(&self, Protocol::Http | Protocol::Https)
+    }
 }
 
 impl Display for Protocol {
@@ -95,6 +105,9 @@ impl FromStr for Protocol {
             "ram" => Ok(Protocol::Ram),
             "s3" => Ok(Protocol::S3),
             "gs" => Ok(Protocol::Google),
+            "http" => Ok(Protocol::Http),
+            "https" => Ok(Protocol::Https),
+            "webhdfs" => Ok(Protocol::WebHdfs),
             _ => bail!("unknown URI protocol `{protocol}`"),
         }
     }
@@ -191,6 +204,9 @@ impl Uri {
         if protocol == Protocol::Google && path.components().count() < 2 {
             return None;
         }
+        if (protocol.is_http() || protocol == Protocol::WebHdfs) && path.components().count() < 2 {
+            return None;
+        }
         let parent_path = path.parent()?;
 
         Some(Self {
@@ -219,6 +235,11 @@ impl Uri {
         if self.protocol() == Protocol::Google && path.components().count() < 2 {
             return None;
         }
+        if (self.protocol().is_http() || self.protocol() == Protocol::WebHdfs)
+            && path.components().count() < 2
+        {
+            return None;
+        }
         path.file_name().map(Path::new)
     }
 
@@ -496,10 +517,14 @@ mod tests {
         );
 
         assert_eq!(
-            Uri::from_str("http://localhost:9000/quickwit")
+            Uri::from_str("https://cdn.example.com/indexes/hdfs-logs").unwrap(),
+            "https://cdn.example.com/indexes/hdfs-logs"
+        );
+        assert_eq!(
+            Uri::from_str("ftp://localhost:21/quickwit")
                 .unwrap_err()
                 .to_string(),
-            "unknown URI protocol `http`"
+            "unknown URI protocol `ftp`"
         );
     }
 
@@ -524,6 +549,18 @@ mod tests {
             Uri::for_test("postgresql://localhost:5432/metastore").protocol(),
             Protocol::PostgreSQL
         );
+        assert_eq!(
+            Uri::for_test("http://localhost:8080/splits").protocol(),
+            Protocol::Http
+        );
+        assert_eq!(
+            Uri::for_test("https://cdn.example.com/splits").protocol(),
+            Protocol::Https
+        );
+        assert_eq!(
+            Uri::for_test("webhdfs://namenode:9870/quickwit").protocol(),
+            Protocol::WebHdfs
+        );
     }
 
     #[test]
@@ -663,6 +700,20 @@ mod tests {
             Uri::for_test("gs://bucket/foo/bar/").parent().unwrap(),
             "gs://bucket/foo"
         );
+        assert!(Uri::for_test("https://localhost:8080").parent().is_none());
+        assert!(Uri::for_test("webhdfs://namenode:9870").parent().is_none());
+        assert_eq!(
+            Uri::for_test("webhdfs://namenode:9870/quickwit/indexes")
+                .parent()
+                .unwrap(),
+            "webhdfs://namenode:9870/quickwit"
+        );
+        assert_eq!(
+            Uri::for_test("https://localhost:8080/foo")
+                .parent()
+                .unwrap(),
+            "https://localhost:8080"
+        );
     }
 
     #[test]
@@ -729,6 +780,15 @@ mod tests {
             Uri::for_test("gs://bucket/foo/").file_name().unwrap(),
             Path::new("foo"),
         );
+        assert!(Uri::for_test("https://localhost:8080")
+            .file_name()
+            .is_none());
+        assert_eq!(
+            Uri::for_test("https://localhost:8080/foo.split")
+                .file_name()
+                .unwrap(),
+            Path::new("foo.split"),
+        );
     }
 
     #[test]
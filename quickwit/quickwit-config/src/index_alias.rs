@@ -0,0 +1,131 @@
+// Copyright (C) 2024 Quickwit, Inc.
+//
+// Quickwit is offered under the AGPL v3.0 and as commercial software.
+// For commercial licensing, contact us at hello@quickwit.io.
+//
+// AGPL:
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as
+// published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::BTreeSet;
+
+use anyhow::bail;
+use quickwit_proto::types::IndexId;
+use serde::{Deserialize, Serialize};
+
+use crate::validate_identifier;
+
+pub type IndexAliasId = String;
+
+/// An index alias is a logical name that resolves to one or more indexes. Searches targeting an
+/// alias fan out to all its member indexes while ingest requests are routed to its write index.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct IndexAlias {
+    pub alias_id: IndexAliasId,
+    pub index_ids: BTreeSet<IndexId>,
+    /// Index receiving the documents ingested into the alias. Must be one of the member indexes.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub write_index_id: Option<IndexId>,
+}
+
+impl IndexAlias {
+    #[cfg(any(test, feature = "testsuite"))]
+    pub fn for_test(alias_id: &str, index_ids: &[&str], write_index_id: Option<&str>) -> Self {
+        IndexAlias {
+            alias_id: alias_id.to_string(),
+            index_ids: index_ids
+                .iter()
+                .map(|index_id| index_id.to_string())
+                .collect(),
+            write_index_id: write_index_id.map(|index_id| index_id.to_string()),
+        }
+    }
+
+    /// Validates the alias ID and checks that the write index, if any, is a member of the alias.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        validate_identifier("Index alias ID", &self.alias_id)?;
+
+        if self.index_ids.is_empty() {
+            bail!(
+                "index alias `{}` must reference at least one index",
+                self.alias_id
+            );
+        }
+        for index_id in &self.index_ids {
+            validate_identifier("Index ID", index_id)?;
+        }
+        if let Some(write_index_id) = &self.write_index_id {
+            if !self.index_ids.contains(write_index_id) {
+                bail!(
+                    "write index `{write_index_id}` of index alias `{}` must be one of its indexes",
+                    self.alias_id
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_alias_serde() {
+        let index_alias_json = r#"{
+            "alias_id": "logs",
+            "index_ids": ["logs-2024-01-02", "logs-2024-01-01"],
+            "write_index_id": "logs-2024-01-02"
+        }"#;
+        let index_alias: IndexAlias = serde_json::from_str(index_alias_json).unwrap();
+        assert_eq!(
+            index_alias,
+            IndexAlias::for_test(
+                "logs",
+                &["logs-2024-01-01", "logs-2024-01-02"],
+                Some("logs-2024-01-02")
+            )
+        );
+        let index_alias_json = r#"{"alias_id": "logs", "index_ids": ["logs-2024-01-01"]}"#;
+        let index_alias: IndexAlias = serde_json::from_str(index_alias_json).unwrap();
+        assert!(index_alias.write_index_id.is_none());
+    }
+
+    #[test]
+    fn test_index_alias_validate() {
+        let index_alias = IndexAlias::for_test(
+            "logs",
+            &["logs-2024-01-01", "logs-2024-01-02"],
+            Some("logs-2024-01-02"),
+        );
+        index_alias.validate().unwrap();
+
+        let index_alias = IndexAlias::for_test("logs", &["logs-2024-01-01"], None);
+        index_alias.validate().unwrap();
+
+        let index_alias = IndexAlias::for_test("logs-*", &["logs-2024-01-01"], None);
+        index_alias.validate().unwrap_err();
+
+        let index_alias = IndexAlias::for_test("logs", &[], None);
+        let error = index_alias.validate().unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("must reference at least one index"));
+
+        let index_alias = IndexAlias::for_test("logs", &["logs-2024-01-01"], Some("logs-2024"));
+        let error = index_alias.validate().unwrap_err();
+        assert!(error.to_string().contains("must be one of its indexes"));
+    }
+}
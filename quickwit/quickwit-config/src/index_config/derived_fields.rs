@@ -0,0 +1,208 @@
+// Copyright (C) 2024 Quickwit, Inc.
+//
+// Quickwit is offered under the AGPL v3.0 and as commercial software.
+// For commercial licensing, contact us at hello@quickwit.io.
+//
+// AGPL:
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as
+// published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use anyhow::{bail, Context};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Declarative rule deriving a field from the other fields of a document at indexing time,
+/// before the document goes through the doc mapper.
+///
+/// Nested fields are addressed with dots (`resource.url`). Rules are applied in order, so a rule
+/// can read a field derived by a previous rule. A rule whose source fields are missing, or of
+/// an unexpected type, leaves the document unchanged.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum DerivedFieldRule {
+    /// Copies the value of `source` into `target`.
+    Copy { source: String, target: String },
+    /// Joins the values of `sources` with `separator`. Missing sources are skipped.
+    Concat {
+        sources: Vec<String>,
+        #[serde(default)]
+        separator: String,
+        target: String,
+    },
+    /// Extracts the capture group `group` of the regular expression `pattern` from the string
+    /// value of `source`.
+    RegexExtract {
+        source: String,
+        pattern: String,
+        #[serde(default = "DerivedFieldRule::default_regex_group")]
+        group: usize,
+        target: String,
+    },
+    /// Lowercases the string value of `source`. The value is modified in place if `target` is
+    /// not set.
+    Lowercase {
+        source: String,
+        #[serde(default)]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        target: Option<String>,
+    },
+}
+
+impl DerivedFieldRule {
+    fn default_regex_group() -> usize {
+        1
+    }
+
+    pub fn validate(&self) -> anyhow::Result<()> {
+        let (source_fields, target_field): (&[String], &str) = match self {
+            Self::Copy { source, target } => (std::slice::from_ref(source), target),
+            Self::Concat {
+                sources, target, ..
+            } => {
+                if sources.is_empty() {
+                    bail!("derived field `{target}`: `concat` rule requires at least one source");
+                }
+                (sources, target)
+            }
+            Self::RegexExtract {
+                source,
+                pattern,
+                group,
+                target,
+            } => {
+                let regex = Regex::new(pattern).with_context(|| {
+                    format!("derived field `{target}`: invalid regular expression `{pattern}`")
+                })?;
+                // `captures_len` includes the implicit group 0 matching the whole pattern.
+                if *group >= regex.captures_len() {
+                    bail!(
+                        "derived field `{target}`: regular expression `{pattern}` has no capture \
+                         group {group}"
+                    );
+                }
+                (std::slice::from_ref(source), target)
+            }
+            Self::Lowercase { source, target } => (
+                std::slice::from_ref(source),
+                target.as_deref().unwrap_or(source),
+            ),
+        };
+        if target_field.is_empty() {
+            bail!("derived field rule target must not be empty");
+        }
+        if source_fields
+            .iter()
+            .any(|source_field| source_field.is_empty())
+        {
+            bail!("derived field `{target_field}`: source field names must not be empty");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_derived_field_rule_deserialize() {
+        let rules_yaml = r#"
+            - type: copy
+              source: url
+              target: url_copy
+            - type: concat
+              sources: [first_name, last_name]
+              separator: " "
+              target: full_name
+            - type: regex_extract
+              source: url
+              pattern: "^https?://[^/]*\\.([a-z]+)(?:/|$)"
+              target: tld
+            - type: lowercase
+              source: host
+        "#;
+        let rules: Vec<DerivedFieldRule> = serde_yaml::from_str(rules_yaml).unwrap();
+        assert_eq!(
+            rules,
+            vec![
+                DerivedFieldRule::Copy {
+                    source: "url".to_string(),
+                    target: "url_copy".to_string(),
+                },
+                DerivedFieldRule::Concat {
+                    sources: vec!["first_name".to_string(), "last_name".to_string()],
+                    separator: " ".to_string(),
+                    target: "full_name".to_string(),
+                },
+                DerivedFieldRule::RegexExtract {
+                    source: "url".to_string(),
+                    pattern: r"^https?://[^/]*\.([a-z]+)(?:/|$)".to_string(),
+                    group: 1,
+                    target: "tld".to_string(),
+                },
+                DerivedFieldRule::Lowercase {
+                    source: "host".to_string(),
+                    target: None,
+                },
+            ]
+        );
+        for rule in &rules {
+            rule.validate().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_derived_field_rule_deserialize_rejects_unknown_rule() {
+        let rule_yaml = r#"
+            type: script
+            source: "1 + 1"
+            target: two
+        "#;
+        serde_yaml::from_str::<DerivedFieldRule>(rule_yaml).unwrap_err();
+    }
+
+    #[test]
+    fn test_derived_field_rule_validate() {
+        let rule = DerivedFieldRule::RegexExtract {
+            source: "url".to_string(),
+            pattern: "(".to_string(),
+            group: 1,
+            target: "tld".to_string(),
+        };
+        let error = rule.validate().unwrap_err();
+        assert!(error.to_string().contains("invalid regular expression"));
+
+        let rule = DerivedFieldRule::RegexExtract {
+            source: "url".to_string(),
+            pattern: "([a-z]+)".to_string(),
+            group: 2,
+            target: "tld".to_string(),
+        };
+        let error = rule.validate().unwrap_err();
+        assert!(error.to_string().contains("has no capture group 2"));
+
+        let rule = DerivedFieldRule::Concat {
+            sources: Vec::new(),
+            separator: String::new(),
+            target: "full_name".to_string(),
+        };
+        let error = rule.validate().unwrap_err();
+        assert!(error.to_string().contains("at least one source"));
+
+        let rule = DerivedFieldRule::Copy {
+            source: "url".to_string(),
+            target: String::new(),
+        };
+        rule.validate().unwrap_err();
+    }
+}
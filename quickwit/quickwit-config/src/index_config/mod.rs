@@ -17,9 +17,11 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
+mod derived_fields;
 pub(crate) mod serialize;
+mod split_path_template;
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::num::NonZeroU32;
 use std::str::FromStr;
 use std::sync::Arc;
@@ -27,20 +29,25 @@ use std::time::Duration;
 
 use anyhow::Context;
 use bytesize::ByteSize;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use cron::Schedule;
+pub use derived_fields::DerivedFieldRule;
 use humantime::parse_duration;
+use quickwit_common::is_false;
 use quickwit_common::uri::Uri;
+use quickwit_datetime::Timezone;
 use quickwit_doc_mapper::{
     DefaultDocMapper, DefaultDocMapperBuilder, DocMapper, FieldMappingEntry, Mode, ModeType,
-    QuickwitJsonOptions, TokenizerEntry,
+    OnFieldError, QuickwitJsonOptions, TokenizerEntry,
 };
 use quickwit_proto::types::IndexId;
 use serde::{Deserialize, Serialize};
 pub use serialize::load_index_config_from_user_config;
+pub use split_path_template::{render_split_path_template, split_path_prefix_matches_template};
 
 use crate::index_config::serialize::VersionedIndexConfig;
 use crate::merge_policy_config::{MergePolicyConfig, StableLogMergePolicyConfig};
+use crate::storage_config::IndexStorageConfig;
 use crate::TestableForRegression;
 
 // Note(fmassot): `DocMapping` is a struct only used for
@@ -64,6 +71,14 @@ pub struct DocMapping {
     #[schema(value_type = Vec<String>)]
     #[serde(default)]
     pub tag_fields: BTreeSet<String>,
+    /// Numeric fast fields whose min / max values are recorded in the split metadata, in order
+    /// to prune the splits that cannot match range queries on these fields.
+    #[schema(value_type = Vec<String>)]
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub min_max_fields: BTreeSet<String>,
+    /// Alternative names for fields, mapping each alias to the path of the field it stands for.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub aliases: BTreeMap<String, String>,
     #[serde(default)]
     pub store_source: bool,
     #[serde(default)]
@@ -87,6 +102,13 @@ pub struct DocMapping {
     #[schema(value_type = u32)]
     #[serde(default = "DefaultDocMapper::default_max_num_partitions")]
     pub max_num_partitions: NonZeroU32,
+    #[serde(default = "DefaultDocMapper::default_max_nesting_depth")]
+    pub max_nesting_depth: usize,
+    #[serde(default)]
+    pub on_field_error: OnFieldError,
+    #[schema(value_type = String, default = "UTC")]
+    #[serde(default, skip_serializing_if = "Timezone::is_utc")]
+    pub default_timezone: Timezone,
     #[serde(default)]
     pub tokenizers: Vec<TokenizerEntry>,
 }
@@ -142,6 +164,11 @@ pub struct IndexingSettings {
     #[schema(default = 60)]
     #[serde(default = "IndexingSettings::default_commit_timeout_secs")]
     pub commit_timeout_secs: usize,
+    /// Cron expression (or `hourly`, `daily`, ...) forcing commits at fixed times, in UTC, on top
+    /// of the commit timeout and the size triggers.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit_schedule: Option<String>,
     #[schema(default = 8)]
     #[serde(default = "IndexingSettings::default_docstore_compression_level")]
     pub docstore_compression_level: i32,
@@ -159,6 +186,22 @@ pub struct IndexingSettings {
     pub merge_policy: MergePolicyConfig,
     #[serde(default)]
     pub resources: IndexingResources,
+    /// Rules deriving new fields from the fields of the ingested documents, applied before the
+    /// documents are parsed by the doc mapper.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub derived_fields: Vec<DerivedFieldRule>,
+    /// Where to write the documents that fail to be parsed, transformed, or mapped. When unset,
+    /// these documents are dropped.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dead_letter_queue: Option<DeadLetterQueueConfig>,
+    /// Prefix under which splits are stored, relative to the index URI, e.g. `{year}/{month}`.
+    /// The `{year}`, `{month}`, `{day}`, and `{hour}` placeholders are evaluated from the start of
+    /// the split time range. When unset, splits are stored at the root of the index URI.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub split_path_template: Option<String>,
 }
 
 impl IndexingSettings {
@@ -170,6 +213,35 @@ impl IndexingSettings {
         60
     }
 
+    pub fn commit_schedule(&self) -> anyhow::Result<Option<Schedule>> {
+        let Some(commit_schedule) = &self.commit_schedule else {
+            return Ok(None);
+        };
+        let schedule = Schedule::from_str(&prepend_at_char(commit_schedule))
+            .with_context(|| format!("failed to parse commit schedule `{commit_schedule}`"))?;
+        Ok(Some(schedule))
+    }
+
+    /// Returns the maximum duration a split started now can remain uncommitted.
+    pub fn commit_delay(&self) -> Duration {
+        self.commit_delay_at(Utc::now())
+    }
+
+    /// Returns the maximum duration a split started at `now` can remain uncommitted: the commit
+    /// timeout, or the duration until the next scheduled commit if it comes first.
+    pub fn commit_delay_at(&self, now: DateTime<Utc>) -> Duration {
+        let commit_timeout = self.commit_timeout();
+        // The schedule is validated when the index config is loaded.
+        let Ok(Some(schedule)) = self.commit_schedule() else {
+            return commit_timeout;
+        };
+        let Some(next_commit_date) = schedule.after(&now).next() else {
+            return commit_timeout;
+        };
+        let duration_until_next_commit = (next_commit_date - now).to_std().unwrap_or_default();
+        commit_timeout.min(duration_until_next_commit)
+    }
+
     pub fn default_docstore_blocksize() -> usize {
         1_000_000
     }
@@ -195,12 +267,57 @@ impl Default for IndexingSettings {
     fn default() -> Self {
         Self {
             commit_timeout_secs: Self::default_commit_timeout_secs(),
+            commit_schedule: None,
             docstore_blocksize: Self::default_docstore_blocksize(),
             docstore_compression_level: Self::default_docstore_compression_level(),
             split_num_docs_target: Self::default_split_num_docs_target(),
             merge_policy: MergePolicyConfig::default(),
             resources: IndexingResources::default(),
+            derived_fields: Vec::new(),
+            dead_letter_queue: None,
+            split_path_template: None,
+        }
+    }
+}
+
+/// Dead letter queue collecting the documents that could not be indexed.
+///
+/// Failed documents are written, along with their error, as NDJSON files under `uri`. One file is
+/// written per batch of documents containing failures, up to `max_num_files` files per source.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DeadLetterQueueConfig {
+    #[schema(value_type = String)]
+    pub uri: Uri,
+    /// Maximum size of a dead letter file. Failed documents that do not fit in the file of their
+    /// batch are dropped.
+    #[schema(value_type = String, default = "10 MB")]
+    #[serde(default = "DeadLetterQueueConfig::default_max_file_size")]
+    pub max_file_size: ByteSize,
+    /// Maximum number of dead letter files of a source. Once it is reached, failed documents are
+    /// dropped.
+    #[schema(default = 1000)]
+    #[serde(default = "DeadLetterQueueConfig::default_max_num_files")]
+    pub max_num_files: usize,
+}
+
+impl DeadLetterQueueConfig {
+    fn default_max_file_size() -> ByteSize {
+        ByteSize::mb(10)
+    }
+
+    fn default_max_num_files() -> usize {
+        1_000
+    }
+
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.max_file_size.as_u64() == 0 {
+            anyhow::bail!("dead letter queue `max_file_size` must be strictly positive");
         }
+        if self.max_num_files == 0 {
+            anyhow::bail!("dead letter queue `max_num_files` must be strictly positive");
+        }
+        Ok(())
     }
 }
 
@@ -209,6 +326,10 @@ impl Default for IndexingSettings {
 pub struct SearchSettings {
     #[serde(default)]
     pub default_search_fields: Vec<String>,
+    /// If true, default search fields that are not declared in the doc mapping are ignored
+    /// with a warning instead of being rejected.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub ignore_missing_default_search_fields: bool,
 }
 
 #[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
@@ -303,6 +424,8 @@ pub struct IndexConfig {
     pub indexing_settings: IndexingSettings,
     pub search_settings: SearchSettings,
     pub retention_policy: Option<RetentionPolicy>,
+    /// S3 storage config of the index, overriding the S3 storage config of the nodes.
+    pub storage_config: Option<IndexStorageConfig>,
 }
 
 impl IndexConfig {
@@ -380,6 +503,7 @@ impl IndexConfig {
                 r#"attributes.server"#.to_string(),
                 r"attributes.server\.status".to_string(),
             ],
+            ignore_missing_default_search_fields: false,
         };
         IndexConfig {
             index_id: index_id.to_string(),
@@ -388,6 +512,7 @@ impl IndexConfig {
             indexing_settings,
             search_settings,
             retention_policy: Default::default(),
+            storage_config: None,
         }
     }
 }
@@ -447,10 +572,15 @@ impl TestableForRegression for IndexConfig {
                 .into_iter()
                 .map(|tag_field| tag_field.to_string())
                 .collect::<BTreeSet<String>>(),
+            min_max_fields: BTreeSet::new(),
+            aliases: BTreeMap::new(),
             store_source: true,
             mode: Mode::default(),
             partition_key: Some("tenant_id".to_string()),
             max_num_partitions: NonZeroU32::new(100).unwrap(),
+            max_nesting_depth: DefaultDocMapper::default_max_nesting_depth(),
+            on_field_error: OnFieldError::default(),
+            default_timezone: Timezone::default(),
             timestamp_field: Some("timestamp".to_string()),
             tokenizers: vec![tokenizer],
         };
@@ -477,6 +607,7 @@ impl TestableForRegression for IndexConfig {
         };
         let search_settings = SearchSettings {
             default_search_fields: vec!["message".to_string()],
+            ignore_missing_default_search_fields: false,
         };
         IndexConfig {
             index_id: "my-index".to_string(),
@@ -485,6 +616,7 @@ impl TestableForRegression for IndexConfig {
             indexing_settings,
             retention_policy,
             search_settings,
+            storage_config: None,
         }
     }
 
@@ -523,12 +655,18 @@ pub fn build_doc_mapper(
         store_source: doc_mapping.store_source,
         index_field_presence: doc_mapping.index_field_presence,
         default_search_fields: search_settings.default_search_fields.clone(),
+        ignore_missing_default_search_fields: search_settings.ignore_missing_default_search_fields,
         timestamp_field: doc_mapping.timestamp_field.clone(),
         field_mappings: doc_mapping.field_mappings.clone(),
         tag_fields: doc_mapping.tag_fields.iter().cloned().collect(),
+        min_max_fields: doc_mapping.min_max_fields.iter().cloned().collect(),
+        aliases: doc_mapping.aliases.clone(),
         mode: doc_mapping.mode.clone(),
         partition_key: doc_mapping.partition_key.clone(),
         max_num_partitions: doc_mapping.max_num_partitions,
+        max_nesting_depth: doc_mapping.max_nesting_depth,
+        on_field_error: doc_mapping.on_field_error,
+        default_timezone: doc_mapping.default_timezone,
         tokenizers: doc_mapping.tokenizers.clone(),
     };
     Ok(Arc::new(builder.try_build()?))
@@ -617,6 +755,7 @@ mod tests {
             index_config.search_settings,
             SearchSettings {
                 default_search_fields: vec!["severity_text".to_string(), "body".to_string()],
+                ignore_missing_default_search_fields: false,
             }
         );
     }
@@ -659,6 +798,7 @@ mod tests {
                 index_config.search_settings,
                 SearchSettings {
                     default_search_fields: vec!["body".to_string()],
+                    ignore_missing_default_search_fields: false,
                 }
             );
         }
@@ -693,6 +833,7 @@ mod tests {
                 index_config.search_settings,
                 SearchSettings {
                     default_search_fields: vec!["body".to_string()],
+                    ignore_missing_default_search_fields: false,
                 }
             );
         }
@@ -755,6 +896,218 @@ mod tests {
             .contains("failed to parse human-readable duration `x`"));
     }
 
+    #[test]
+    fn test_index_config_with_dead_letter_queue() {
+        let config_yaml = r#"
+            version: 0.7
+            index_id: hdfs-logs
+            index_uri: "s3://my-index"
+            doc_mapping: {}
+            indexing_settings:
+              dead_letter_queue:
+                uri: "s3://my-dead-letter-queue"
+        "#;
+        let index_config = load_index_config_from_user_config(
+            ConfigFormat::Yaml,
+            config_yaml.as_bytes(),
+            &Uri::for_test("s3://my-index"),
+        )
+        .unwrap();
+        assert_eq!(
+            index_config.indexing_settings.dead_letter_queue,
+            Some(DeadLetterQueueConfig {
+                uri: Uri::for_test("s3://my-dead-letter-queue"),
+                max_file_size: ByteSize::mb(10),
+                max_num_files: 1_000,
+            })
+        );
+
+        let config_yaml = r#"
+            version: 0.7
+            index_id: hdfs-logs
+            index_uri: "s3://my-index"
+            doc_mapping: {}
+            indexing_settings:
+              dead_letter_queue:
+                uri: "s3://my-dead-letter-queue"
+                max_file_size: 0
+        "#;
+        let error = load_index_config_from_user_config(
+            ConfigFormat::Yaml,
+            config_yaml.as_bytes(),
+            &Uri::for_test("s3://my-index"),
+        )
+        .unwrap_err();
+        assert!(error
+            .root_cause()
+            .to_string()
+            .contains("must be strictly positive"));
+
+        let config_yaml = r#"
+            version: 0.7
+            index_id: hdfs-logs
+            index_uri: "s3://my-index"
+            doc_mapping: {}
+            indexing_settings:
+              dead_letter_queue:
+                uri: "s3://my-dead-letter-queue"
+                max_num_files: 0
+        "#;
+        let error = load_index_config_from_user_config(
+            ConfigFormat::Yaml,
+            config_yaml.as_bytes(),
+            &Uri::for_test("s3://my-index"),
+        )
+        .unwrap_err();
+        assert!(error
+            .root_cause()
+            .to_string()
+            .contains("`max_num_files` must be strictly positive"));
+    }
+
+    #[test]
+    fn test_indexing_settings_commit_delay() {
+        let now = DateTime::parse_from_rfc3339("2024-01-01T10:59:30Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let mut indexing_settings = IndexingSettings {
+            commit_timeout_secs: 60,
+            ..Default::default()
+        };
+        assert_eq!(
+            indexing_settings.commit_delay_at(now),
+            Duration::from_secs(60)
+        );
+        // The next scheduled commit comes before the commit timeout.
+        indexing_settings.commit_schedule = Some("hourly".to_string());
+        assert_eq!(
+            indexing_settings.commit_delay_at(now),
+            Duration::from_secs(30)
+        );
+        // The commit timeout comes before the next scheduled commit.
+        indexing_settings.commit_timeout_secs = 10;
+        assert_eq!(
+            indexing_settings.commit_delay_at(now),
+            Duration::from_secs(10)
+        );
+        // Commits are scheduled at the boundary following `now`, not at `now`.
+        indexing_settings.commit_timeout_secs = 3600;
+        indexing_settings.commit_schedule = Some("0 */15 * * * *".to_string());
+        let boundary = DateTime::parse_from_rfc3339("2024-01-01T11:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            indexing_settings.commit_delay_at(boundary),
+            Duration::from_secs(15 * 60)
+        );
+    }
+
+    #[test]
+    fn test_index_config_with_invalid_commit_schedule() {
+        let config_yaml = r#"
+            version: 0.7
+            index_id: hdfs-logs
+            index_uri: "s3://my-index"
+            doc_mapping: {}
+            indexing_settings:
+              commit_schedule: "every minute"
+        "#;
+        let error = load_index_config_from_user_config(
+            ConfigFormat::Yaml,
+            config_yaml.as_bytes(),
+            &Uri::for_test("s3://my-index"),
+        )
+        .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("failed to parse commit schedule `every minute`"));
+    }
+
+    #[test]
+    fn test_index_config_with_split_path_template() {
+        let config_yaml = r#"
+            version: 0.7
+            index_id: hdfs-logs
+            index_uri: "s3://my-index"
+            doc_mapping: {}
+            indexing_settings:
+              split_path_template: "{year}/{month}"
+        "#;
+        let index_config = load_index_config_from_user_config(
+            ConfigFormat::Yaml,
+            config_yaml.as_bytes(),
+            &Uri::for_test("s3://my-index"),
+        )
+        .unwrap();
+        assert_eq!(
+            index_config
+                .indexing_settings
+                .split_path_template
+                .as_deref(),
+            Some("{year}/{month}")
+        );
+
+        let config_yaml = r#"
+            version: 0.7
+            index_id: hdfs-logs
+            index_uri: "s3://my-index"
+            doc_mapping: {}
+            indexing_settings:
+              split_path_template: "../{year}"
+        "#;
+        let error = load_index_config_from_user_config(
+            ConfigFormat::Yaml,
+            config_yaml.as_bytes(),
+            &Uri::for_test("s3://my-index"),
+        )
+        .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("split path template `../{year}`"));
+    }
+
+    #[test]
+    fn test_index_config_with_storage_config() {
+        let config_yaml = r#"
+            version: 0.7
+            index_id: hdfs-logs
+            index_uri: "s3://minio-bucket/hdfs-logs"
+            doc_mapping: {}
+            storage:
+              flavor: minio
+              endpoint: http://minio:9000
+              profile: minio
+        "#;
+        let index_config = load_index_config_from_user_config(
+            ConfigFormat::Yaml,
+            config_yaml.as_bytes(),
+            &Uri::for_test("s3://minio-bucket"),
+        )
+        .unwrap();
+        let storage_config = index_config.storage_config.unwrap();
+        assert_eq!(
+            storage_config.endpoint.as_deref(),
+            Some("http://minio:9000")
+        );
+        assert_eq!(storage_config.profile.as_deref(), Some("minio"));
+
+        let config_yaml = r#"
+            version: 0.7
+            index_id: hdfs-logs
+            index_uri: "file:///indexes/hdfs-logs"
+            doc_mapping: {}
+            storage:
+              endpoint: http://minio:9000
+        "#;
+        let error = load_index_config_from_user_config(
+            ConfigFormat::Yaml,
+            config_yaml.as_bytes(),
+            &Uri::for_test("file:///indexes"),
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("S3 index URI"));
+    }
+
     #[test]
     fn test_retention_policy_serialization() {
         let retention_policy = RetentionPolicy {
@@ -22,9 +22,10 @@ use quickwit_common::uri::Uri;
 use serde::{Deserialize, Serialize};
 use tracing::info;
 
+use super::split_path_template::validate_split_path_template;
 use crate::{
-    build_doc_mapper, validate_identifier, ConfigFormat, DocMapping, IndexConfig, IndexingSettings,
-    RetentionPolicy, SearchSettings,
+    build_doc_mapper, validate_identifier, ConfigFormat, DocMapping, IndexConfig,
+    IndexStorageConfig, IndexingSettings, RetentionPolicy, SearchSettings,
 };
 
 /// Alias for the latest serialization format.
@@ -88,6 +89,13 @@ impl IndexConfigForSerialization {
 
         let index_uri = self.index_uri_or_fallback_to_default(default_index_root_uri)?;
 
+        if index_uri.protocol().is_http() {
+            anyhow::bail!(
+                "failed to validate index config. index URI `{index_uri}` is an HTTP URI, but \
+                 HTTP storages are read-only"
+            );
+        }
+
         if let Some(retention_policy) = &self.retention_policy {
             retention_policy.validate()?;
 
@@ -105,6 +113,19 @@ impl IndexConfigForSerialization {
         build_doc_mapper(&self.doc_mapping, &self.search_settings)?;
 
         self.indexing_settings.merge_policy.validate()?;
+        self.indexing_settings.commit_schedule()?;
+        for derived_field_rule in &self.indexing_settings.derived_fields {
+            derived_field_rule.validate()?;
+        }
+        if let Some(dead_letter_queue_config) = &self.indexing_settings.dead_letter_queue {
+            dead_letter_queue_config.validate()?;
+        }
+        if let Some(split_path_template) = &self.indexing_settings.split_path_template {
+            validate_split_path_template(split_path_template)?;
+        }
+        if let Some(storage_config) = &self.storage_config {
+            storage_config.validate(&index_uri)?;
+        }
 
         Ok(IndexConfig {
             index_id: self.index_id,
@@ -113,6 +134,7 @@ impl IndexConfigForSerialization {
             indexing_settings: self.indexing_settings,
             search_settings: self.search_settings,
             retention_policy: self.retention_policy,
+            storage_config: self.storage_config,
         })
     }
 }
@@ -148,6 +170,10 @@ pub struct IndexConfigV0_7 {
     #[serde(rename = "retention")]
     #[serde(default)]
     pub retention_policy: Option<RetentionPolicy>,
+    #[serde(rename = "storage")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub storage_config: Option<IndexStorageConfig>,
 }
 
 impl From<IndexConfig> for IndexConfigV0_7 {
@@ -159,6 +185,7 @@ impl From<IndexConfig> for IndexConfigV0_7 {
             indexing_settings: index_config.indexing_settings,
             search_settings: index_config.search_settings,
             retention_policy: index_config.retention_policy,
+            storage_config: index_config.storage_config,
         }
     }
 }
@@ -226,6 +253,22 @@ mod test {
         assert!(validation_err.contains("the retention policy requires a timestamp field"));
     }
 
+    #[test]
+    fn test_index_config_rejects_http_index_uri() {
+        let config_yaml = r#"
+            version: 0.7
+            index_id: hdfs-logs
+            index_uri: https://cdn.example.com/hdfs-logs
+            doc_mapping: {}
+        "#;
+        let config_parse_result: anyhow::Result<IndexConfig> =
+            ConfigFormat::Yaml.parse(config_yaml.as_bytes());
+        assert!(config_parse_result
+            .unwrap_err()
+            .to_string()
+            .contains("HTTP storages are read-only"));
+    }
+
     #[test]
     fn test_minimal_index_config_missing_root_uri_no_default_uri() {
         let config_yaml = r#"
@@ -0,0 +1,183 @@
+// Copyright (C) 2024 Quickwit, Inc.
+//
+// Quickwit is offered under the AGPL v3.0 and as commercial software.
+// For commercial licensing, contact us at hello@quickwit.io.
+//
+// AGPL:
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as
+// published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use anyhow::bail;
+use chrono::{Datelike, NaiveDateTime, Timelike};
+
+/// The supported placeholders and the number of digits they are rendered with.
+const PLACEHOLDERS: [(&str, usize); 4] =
+    [("{year}", 4), ("{month}", 2), ("{day}", 2), ("{hour}", 2)];
+
+/// Validates a split path template such as `{year}/{month}`.
+///
+/// The template must be a relative path made of alphanumeric characters, `-`, `_`, `=`, `.`,
+/// and the `{year}`, `{month}`, `{day}`, and `{hour}` placeholders.
+pub fn validate_split_path_template(split_path_template: &str) -> anyhow::Result<()> {
+    if split_path_template.is_empty() {
+        bail!("split path template must not be empty");
+    }
+    for segment in split_path_template.split('/') {
+        if segment.is_empty() || segment == "." || segment == ".." {
+            bail!(
+                "split path template `{split_path_template}` must be a relative path without \
+                 empty, `.`, or `..` segments"
+            );
+        }
+        let segment_without_placeholders = PLACEHOLDERS
+            .iter()
+            .fold(segment.to_string(), |segment, (placeholder, _)| {
+                segment.replace(placeholder, "")
+            });
+        if let Some(invalid_char) = segment_without_placeholders
+            .chars()
+            .find(|c| !c.is_ascii_alphanumeric() && !matches!(c, '-' | '_' | '=' | '.'))
+        {
+            bail!(
+                "split path template `{split_path_template}` contains invalid character \
+                 `{invalid_char}`. supported placeholders are `{{year}}`, `{{month}}`, `{{day}}`, \
+                 and `{{hour}}`"
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Renders a split path template for a split whose time range starts at `start_timestamp_secs`.
+/// Dates are evaluated in UTC.
+pub fn render_split_path_template(split_path_template: &str, start_timestamp_secs: i64) -> String {
+    let datetime = NaiveDateTime::from_timestamp_opt(start_timestamp_secs, 0).unwrap_or_default();
+    split_path_template
+        .replace("{year}", &format!("{:04}", datetime.year()))
+        .replace("{month}", &format!("{:02}", datetime.month()))
+        .replace("{day}", &format!("{:02}", datetime.day()))
+        .replace("{hour}", &format!("{:02}", datetime.hour()))
+}
+
+/// Returns whether `split_path_prefix` may have been rendered from the split path template, that
+/// is, whether it is made of the template segments with the placeholders replaced by digits.
+pub fn split_path_prefix_matches_template(
+    split_path_template: &str,
+    split_path_prefix: &str,
+) -> bool {
+    let template_segments: Vec<&str> = split_path_template.split('/').collect();
+    let prefix_segments: Vec<&str> = split_path_prefix.split('/').collect();
+
+    template_segments.len() == prefix_segments.len()
+        && template_segments.into_iter().zip(prefix_segments).all(
+            |(template_segment, prefix_segment)| {
+                segment_matches_template(template_segment, prefix_segment)
+            },
+        )
+}
+
+fn segment_matches_template(mut template_segment: &str, mut segment: &str) -> bool {
+    while let Some(template_char) = template_segment.chars().next() {
+        if let Some((placeholder, num_digits)) = PLACEHOLDERS
+            .iter()
+            .find(|(placeholder, _)| template_segment.starts_with(placeholder))
+        {
+            if segment.len() < *num_digits
+                || !segment.as_bytes()[..*num_digits]
+                    .iter()
+                    .all(u8::is_ascii_digit)
+            {
+                return false;
+            }
+            template_segment = &template_segment[placeholder.len()..];
+            segment = &segment[*num_digits..];
+        } else {
+            let Some(segment_remainder) = segment.strip_prefix(template_char) else {
+                return false;
+            };
+            template_segment = &template_segment[template_char.len_utf8()..];
+            segment = segment_remainder;
+        }
+    }
+    segment.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_split_path_template() {
+        validate_split_path_template("{year}/{month}").unwrap();
+        validate_split_path_template("year={year}/month={month}/{day}-{hour}").unwrap();
+        validate_split_path_template("archive/{year}").unwrap();
+
+        validate_split_path_template("").unwrap_err();
+        validate_split_path_template("/{year}").unwrap_err();
+        validate_split_path_template("{year}/").unwrap_err();
+        validate_split_path_template("../{year}").unwrap_err();
+        validate_split_path_template("{year}//{month}").unwrap_err();
+        validate_split_path_template("{minute}").unwrap_err();
+        validate_split_path_template("{year").unwrap_err();
+    }
+
+    #[test]
+    fn test_render_split_path_template() {
+        // 2024-03-05T07:08:09Z
+        let timestamp_secs = 1_709_622_489;
+        assert_eq!(
+            render_split_path_template("{year}/{month}", timestamp_secs),
+            "2024/03"
+        );
+        assert_eq!(
+            render_split_path_template("year={year}/{month}-{day}/{hour}", timestamp_secs),
+            "year=2024/03-05/07"
+        );
+        assert_eq!(render_split_path_template("{year}", 0), "1970");
+    }
+
+    #[test]
+    fn test_split_path_prefix_matches_template() {
+        assert!(split_path_prefix_matches_template(
+            "{year}/{month}",
+            "2024/03"
+        ));
+        assert!(split_path_prefix_matches_template(
+            "year={year}/{month}-{day}/{hour}",
+            "year=2024/03-05/07"
+        ));
+        assert!(split_path_prefix_matches_template("archive", "archive"));
+
+        assert!(!split_path_prefix_matches_template(
+            "{year}/{month}",
+            "2024"
+        ));
+        assert!(!split_path_prefix_matches_template(
+            "{year}/{month}",
+            "2024/03/05"
+        ));
+        assert!(!split_path_prefix_matches_template(
+            "{year}/{month}",
+            "2024/3"
+        ));
+        assert!(!split_path_prefix_matches_template(
+            "{year}/{month}",
+            "2024/033"
+        ));
+        assert!(!split_path_prefix_matches_template("{year}", "other-index"));
+        assert!(!split_path_prefix_matches_template(
+            "year={year}",
+            "month=2024"
+        ));
+    }
+}
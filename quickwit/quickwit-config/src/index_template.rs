@@ -0,0 +1,307 @@
+// Copyright (C) 2024 Quickwit, Inc.
+//
+// Quickwit is offered under the AGPL v3.0 and as commercial software.
+// For commercial licensing, contact us at hello@quickwit.io.
+//
+// AGPL:
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as
+// published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use std::str::FromStr;
+
+use anyhow::{bail, Context};
+use itertools::Itertools;
+use quickwit_common::uri::Uri;
+use quickwit_proto::types::IndexId;
+use serde::{Deserialize, Serialize};
+
+use crate::index_config::serialize::IndexConfigV0_7;
+use crate::{
+    validate_identifier, validate_index_id_pattern, DocMapping, IndexConfig, IndexingSettings,
+    RetentionPolicy, SearchSettings,
+};
+
+pub type IndexTemplateId = String;
+
+/// An index template is applied to create an index automatically when documents are ingested into
+/// an index that does not exist yet and whose ID matches one of the template's index ID patterns.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct IndexTemplate {
+    pub template_id: IndexTemplateId,
+    pub index_id_patterns: Vec<String>,
+    /// Root URI under which the indexes created from this template are stored. Defaults to the
+    /// node's `default_index_root_uri`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index_root_uri: Option<Uri>,
+    /// When several templates match an index ID, the template with the highest priority wins.
+    #[serde(default)]
+    pub priority: usize,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub doc_mapping: DocMapping,
+    #[serde(default)]
+    pub indexing_settings: IndexingSettings,
+    #[serde(default)]
+    pub search_settings: SearchSettings,
+    #[serde(rename = "retention")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retention_policy: Option<RetentionPolicy>,
+}
+
+impl IndexTemplate {
+    #[cfg(any(test, feature = "testsuite"))]
+    pub fn for_test(template_id: &str, index_id_patterns: &[&str], priority: usize) -> Self {
+        let doc_mapping_json = r#"{
+            "field_mappings": [
+                {
+                    "name": "timestamp",
+                    "type": "datetime",
+                    "fast": true
+                },
+                {
+                    "name": "message",
+                    "type": "text"
+                }
+            ],
+            "timestamp_field": "timestamp"
+        }"#;
+        IndexTemplate {
+            template_id: template_id.to_string(),
+            index_id_patterns: index_id_patterns
+                .iter()
+                .map(|index_id_pattern| index_id_pattern.to_string())
+                .collect(),
+            index_root_uri: None,
+            priority,
+            description: None,
+            doc_mapping: serde_json::from_str(doc_mapping_json).unwrap(),
+            indexing_settings: IndexingSettings::default(),
+            search_settings: SearchSettings::default(),
+            retention_policy: None,
+        }
+    }
+
+    /// Validates the template ID, the index ID patterns, and the index settings.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        validate_identifier("Index template ID", &self.template_id)?;
+
+        if self.index_id_patterns.is_empty() {
+            bail!(
+                "index template `{}` must define at least one index ID pattern",
+                self.template_id
+            );
+        }
+        for index_id_pattern in &self.index_id_patterns {
+            validate_index_id_pattern(index_id_pattern)?;
+        }
+        // Instantiating the template with a dummy index ID validates the doc mapping and the
+        // index settings.
+        let dummy_index_root_uri = Uri::from_str("ram:///indexes").expect("URI should be valid");
+        self.apply_template(
+            "index-template-validation".to_string(),
+            &dummy_index_root_uri,
+        )
+        .with_context(|| format!("index template `{}` is invalid", self.template_id))?;
+        Ok(())
+    }
+
+    /// Returns whether `index_id` matches one of the template's index ID patterns.
+    pub fn matches_index_id(&self, index_id: &str) -> bool {
+        self.index_id_patterns
+            .iter()
+            .any(|index_id_pattern| index_id_patterns_overlap(index_id_pattern, index_id))
+    }
+
+    /// Returns a pair of patterns that overlap if this template and `other` have the same
+    /// priority: an index ID could match both and it would be ambiguous which template applies.
+    pub fn find_ambiguous_overlap<'a>(
+        &'a self,
+        other: &'a IndexTemplate,
+    ) -> Option<(&'a str, &'a str)> {
+        if self.template_id == other.template_id || self.priority != other.priority {
+            return None;
+        }
+        self.index_id_patterns
+            .iter()
+            .cartesian_product(&other.index_id_patterns)
+            .find(|(left_pattern, right_pattern)| {
+                index_id_patterns_overlap(left_pattern, right_pattern)
+            })
+            .map(|(left_pattern, right_pattern)| (left_pattern.as_str(), right_pattern.as_str()))
+    }
+
+    /// Builds the [`IndexConfig`] of the index `index_id` created from this template.
+    pub fn apply_template(
+        &self,
+        index_id: IndexId,
+        default_index_root_uri: &Uri,
+    ) -> anyhow::Result<IndexConfig> {
+        let index_root_uri = self
+            .index_root_uri
+            .as_ref()
+            .unwrap_or(default_index_root_uri);
+        let index_uri = index_root_uri.join(&index_id).with_context(|| {
+            format!("failed to create index URI from root URI `{index_root_uri}`")
+        })?;
+        let index_config_for_serialization = IndexConfigV0_7 {
+            index_id,
+            index_uri: Some(index_uri),
+            doc_mapping: self.doc_mapping.clone(),
+            indexing_settings: self.indexing_settings.clone(),
+            search_settings: self.search_settings.clone(),
+            retention_policy: self.retention_policy.clone(),
+            storage_config: None,
+        };
+        index_config_for_serialization.validate_and_build(None)
+    }
+}
+
+/// Returns whether two index ID patterns can match a common index ID. Patterns are made of literal
+/// characters and `*` wildcards, which match any (possibly empty) sequence of characters. An index
+/// ID is a pattern without wildcards, so this function also checks whether a pattern matches an
+/// index ID.
+fn index_id_patterns_overlap(left_pattern: &str, right_pattern: &str) -> bool {
+    let left = left_pattern.as_bytes();
+    let right = right_pattern.as_bytes();
+    let num_columns = right.len() + 1;
+    // `visited[i * num_columns + j]` is set once we have explored the state where the first `i`
+    // characters of `left` and the first `j` characters of `right` have been consumed.
+    let mut visited = vec![false; (left.len() + 1) * num_columns];
+    let mut stack = vec![(0, 0)];
+
+    while let Some((i, j)) = stack.pop() {
+        if std::mem::replace(&mut visited[i * num_columns + j], true) {
+            continue;
+        }
+        if i == left.len() && j == right.len() {
+            return true;
+        }
+        if i < left.len() && left[i] == b'*' {
+            // The wildcard either stops matching or absorbs the next character of `right`.
+            stack.push((i + 1, j));
+            if j < right.len() {
+                stack.push((i, j + 1));
+            }
+        }
+        if j < right.len() && right[j] == b'*' {
+            stack.push((i, j + 1));
+            if i < left.len() {
+                stack.push((i + 1, j));
+            }
+        }
+        if i < left.len() && j < right.len() && left[i] == right[j] {
+            stack.push((i + 1, j + 1));
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_id_patterns_overlap() {
+        assert!(index_id_patterns_overlap("test-index", "test-index"));
+        assert!(!index_id_patterns_overlap("test-index", "test-indexes"));
+        assert!(index_id_patterns_overlap("test-*", "test-index"));
+        assert!(index_id_patterns_overlap("*-index", "test-index"));
+        assert!(index_id_patterns_overlap("test*index", "test-index"));
+        assert!(index_id_patterns_overlap("test*", "test"));
+        assert!(!index_id_patterns_overlap("test-*", "prod-index"));
+
+        assert!(index_id_patterns_overlap("*", "test-*"));
+        assert!(index_id_patterns_overlap("test-*", "*-index"));
+        assert!(index_id_patterns_overlap("logs-*-foo", "logs-bar-*"));
+        assert!(!index_id_patterns_overlap("logs-*", "traces-*"));
+        assert!(!index_id_patterns_overlap("*-logs", "*-traces"));
+        assert!(!index_id_patterns_overlap("a*b", "c*"));
+    }
+
+    #[test]
+    fn test_index_template_matches_index_id() {
+        let index_template = IndexTemplate::for_test("test-template", &["logs-*", "traces"], 0);
+        assert!(index_template.matches_index_id("logs-foo"));
+        assert!(index_template.matches_index_id("traces"));
+        assert!(!index_template.matches_index_id("traces-foo"));
+        assert!(!index_template.matches_index_id("metrics"));
+    }
+
+    #[test]
+    fn test_index_template_find_ambiguous_overlap() {
+        let index_template_foo = IndexTemplate::for_test("test-template-foo", &["logs-*"], 0);
+        let index_template_bar = IndexTemplate::for_test("test-template-bar", &["*-foo"], 0);
+        assert_eq!(
+            index_template_foo.find_ambiguous_overlap(&index_template_bar),
+            Some(("logs-*", "*-foo"))
+        );
+        assert!(index_template_foo
+            .find_ambiguous_overlap(&index_template_foo)
+            .is_none());
+
+        let index_template_baz = IndexTemplate::for_test("test-template-baz", &["*-foo"], 1);
+        assert!(index_template_foo
+            .find_ambiguous_overlap(&index_template_baz)
+            .is_none());
+
+        let index_template_qux = IndexTemplate::for_test("test-template-qux", &["traces-*"], 0);
+        assert!(index_template_foo
+            .find_ambiguous_overlap(&index_template_qux)
+            .is_none());
+    }
+
+    #[test]
+    fn test_index_template_validate() {
+        let index_template = IndexTemplate::for_test("test-template", &["logs-*"], 0);
+        index_template.validate().unwrap();
+
+        let index_template = IndexTemplate::for_test("test-template", &[], 0);
+        let error = index_template.validate().unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("must define at least one index ID pattern"));
+
+        let index_template = IndexTemplate::for_test("test-template", &["logs-**"], 0);
+        index_template.validate().unwrap_err();
+
+        let mut index_template = IndexTemplate::for_test("test-template", &["logs-*"], 0);
+        index_template.doc_mapping.timestamp_field = Some("unknown".to_string());
+        index_template.validate().unwrap_err();
+    }
+
+    #[test]
+    fn test_index_template_apply_template() {
+        let mut index_template = IndexTemplate::for_test("test-template", &["logs-*"], 0);
+        let default_index_root_uri = Uri::for_test("s3://default-bucket/indexes");
+
+        let index_config = index_template
+            .apply_template("logs-foo".to_string(), &default_index_root_uri)
+            .unwrap();
+        assert_eq!(index_config.index_id, "logs-foo");
+        assert_eq!(
+            index_config.index_uri,
+            "s3://default-bucket/indexes/logs-foo"
+        );
+        assert_eq!(index_config.doc_mapping, index_template.doc_mapping);
+
+        index_template.index_root_uri = Some(Uri::for_test("s3://logs-bucket"));
+        let index_config = index_template
+            .apply_template("logs-foo".to_string(), &default_index_root_uri)
+            .unwrap();
+        assert_eq!(index_config.index_uri, "s3://logs-bucket/logs-foo");
+    }
+}
@@ -29,7 +29,9 @@ use quickwit_common::uri::Uri;
 use regex::Regex;
 
 mod config_value;
+mod index_alias;
 mod index_config;
+mod index_template;
 pub mod merge_policy_config;
 mod metastore_config;
 mod node_config;
@@ -39,21 +41,25 @@ mod source_config;
 mod storage_config;
 mod templating;
 
+pub use index_alias::{IndexAlias, IndexAliasId};
 // We export that one for backward compatibility.
 // See #2048
 use index_config::serialize::{IndexConfigV0_7, VersionedIndexConfig};
 pub use index_config::{
-    build_doc_mapper, load_index_config_from_user_config, DocMapping, IndexConfig,
-    IndexingResources, IndexingSettings, RetentionPolicy, SearchSettings,
+    build_doc_mapper, load_index_config_from_user_config, render_split_path_template,
+    split_path_prefix_matches_template, DeadLetterQueueConfig, DerivedFieldRule, DocMapping,
+    IndexConfig, IndexingResources, IndexingSettings, RetentionPolicy, SearchSettings,
 };
+pub use index_template::{IndexTemplate, IndexTemplateId};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use serde_json::Value as JsonValue;
 pub use source_config::{
     load_source_config_from_user_config, FileSourceParams, GcpPubSubSourceParams,
     KafkaSourceParams, KinesisSourceParams, PulsarSourceAuth, PulsarSourceParams, RegionOrEndpoint,
-    SourceConfig, SourceInputFormat, SourceParams, TransformConfig, VecSourceParams,
-    VoidSourceParams, CLI_INGEST_SOURCE_ID, INGEST_API_SOURCE_ID, INGEST_V2_SOURCE_ID,
+    SourceConfig, SourceInputFormat, SourceParams, SourceStartFrom, TransformConfig,
+    VecSourceParams, VoidSourceParams, CLI_INGEST_SOURCE_ID, INGEST_API_SOURCE_ID,
+    INGEST_V2_SOURCE_ID, RESERVED_SOURCE_IDS,
 };
 use tracing::warn;
 
@@ -64,21 +70,27 @@ pub use crate::metastore_config::{
     MetastoreBackend, MetastoreConfig, MetastoreConfigs, PostgresMetastoreConfig,
 };
 pub use crate::node_config::{
-    enable_ingest_v2, IndexerConfig, IngestApiConfig, JaegerConfig, NodeConfig, SearcherConfig,
-    SplitCacheLimits, DEFAULT_QW_CONFIG_PATH,
+    enable_ingest_v2, ClusterConfig, IndexerConfig, IngestApiConfig, JaegerConfig, JanitorConfig,
+    ListSplitsCacheConfig, LogFormat, LoggingConfig, NodeConfig, SearcherConfig, SplitCacheLimits,
+    ThrottlingStep, WalFsyncPolicy, DEFAULT_QW_CONFIG_PATH,
 };
 use crate::source_config::serialize::{SourceConfigV0_7, VersionedSourceConfig};
 pub use crate::storage_config::{
-    AzureStorageConfig, FileStorageConfig, GoogleCloudStorageConfig, RamStorageConfig,
-    S3StorageConfig, StorageBackend, StorageBackendFlavor, StorageConfig, StorageConfigs,
+    AzureStorageConfig, FileStorageConfig, GoogleCloudStorageConfig, IndexStorageConfig,
+    RamStorageConfig, S3RetryPolicyConfig, S3ServerSideEncryptionAlgorithm,
+    S3ServerSideEncryptionConfig, S3StorageConfig, StorageBackend, StorageBackendFlavor,
+    StorageConfig, StorageConfigs, WebHdfsStorageConfig,
 };
 
 #[derive(utoipa::OpenApi)]
 #[openapi(components(schemas(
     IndexingResources,
     IndexingSettings,
+    DerivedFieldRule,
+    DeadLetterQueueConfig,
     SearchSettings,
     RetentionPolicy,
+    IndexStorageConfig,
     MergePolicyConfig,
     DocMapping,
     VersionedSourceConfig,
@@ -109,7 +109,7 @@ impl Default for StableLogMergePolicyConfig {
     }
 }
 
-fn parse_human_duration<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+pub(crate) fn parse_human_duration<'de, D>(deserializer: D) -> Result<Duration, D::Error>
 where D: Deserializer<'de> {
     let value: String = Deserialize::deserialize(deserializer)?;
     let duration = humantime::parse_duration(&value).map_err(|error| {
@@ -120,7 +120,7 @@ where D: Deserializer<'de> {
     Ok(duration)
 }
 
-fn serialize_duration<S>(value: &Duration, s: S) -> Result<S::Ok, S::Error>
+pub(crate) fn serialize_duration<S>(value: &Duration, s: S) -> Result<S::Ok, S::Error>
 where S: Serializer {
     let value_str = humantime::format_duration(*value).to_string();
     s.serialize_str(&value_str)
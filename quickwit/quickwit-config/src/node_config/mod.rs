@@ -19,21 +19,24 @@
 
 mod serialize;
 
-use std::collections::{HashMap, HashSet};
-use std::env;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::net::SocketAddr;
 use std::num::{NonZeroU32, NonZeroU64, NonZeroUsize};
 use std::path::PathBuf;
+use std::str::FromStr;
 use std::time::Duration;
+use std::{env, fmt};
 
 use anyhow::{bail, ensure};
 use bytesize::ByteSize;
 use http::HeaderMap;
+use itertools::Itertools;
 use once_cell::sync::Lazy;
 use quickwit_common::net::HostAddr;
 use quickwit_common::uri::Uri;
-use quickwit_proto::indexing::CpuCapacity;
-use serde::{Deserialize, Serialize};
+use quickwit_proto::indexing::{CpuCapacity, PIPELINE_FULL_CAPACITY};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use tracing::level_filters::LevelFilter;
 use tracing::warn;
 
 use crate::node_config::serialize::load_node_config_with_env;
@@ -57,9 +60,17 @@ pub struct RestConfig {
 pub struct GrpcConfig {
     #[serde(default = "GrpcConfig::default_max_message_size")]
     pub max_message_size: ByteSize,
+    /// Exposes the gRPC reflection service, which lets tools such as grpcurl discover the
+    /// Quickwit gRPC services without their proto files.
+    #[serde(default)]
+    pub enable_reflection: bool,
 }
 
 impl GrpcConfig {
+    /// Protobuf messages cannot exceed 2GiB, and messages that large would exhaust the memory of
+    /// the nodes well before that anyway.
+    const MAX_MESSAGE_SIZE_UPPER_BOUND: ByteSize = ByteSize::gib(1);
+
     fn default_max_message_size() -> ByteSize {
         ByteSize::mib(20)
     }
@@ -70,6 +81,12 @@ impl GrpcConfig {
             "max gRPC message size (`grpc.max_message_size`) must be at least 1MB, got `{}`",
             self.max_message_size
         );
+        ensure!(
+            self.max_message_size <= Self::MAX_MESSAGE_SIZE_UPPER_BOUND,
+            "max gRPC message size (`grpc.max_message_size`) must be at most {}, got `{}`",
+            Self::MAX_MESSAGE_SIZE_UPPER_BOUND,
+            self.max_message_size
+        );
         Ok(())
     }
 }
@@ -78,6 +95,67 @@ impl Default for GrpcConfig {
     fn default() -> Self {
         Self {
             max_message_size: Self::default_max_message_size(),
+            enable_reflection: false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClusterConfig {
+    /// Interval at which the node checks its readiness and reports it to the cluster.
+    #[serde(
+        default = "ClusterConfig::default_readiness_interval",
+        deserialize_with = "deserialize_human_duration",
+        serialize_with = "serialize_duration"
+    )]
+    pub readiness_interval: Duration,
+    /// Maximum amount of time the node waits for the metastore to become reachable at startup
+    /// before giving up.
+    #[serde(
+        default = "ClusterConfig::default_metastore_connect_timeout",
+        deserialize_with = "deserialize_human_duration",
+        serialize_with = "serialize_duration"
+    )]
+    pub metastore_connect_timeout: Duration,
+}
+
+impl ClusterConfig {
+    /// Tests use a shorter readiness interval, so the lower bound must allow it.
+    const MIN_READINESS_INTERVAL: Duration = if cfg!(any(test, feature = "testsuite")) {
+        Duration::from_millis(25)
+    } else {
+        Duration::from_secs(1)
+    };
+
+    fn default_readiness_interval() -> Duration {
+        if cfg!(any(test, feature = "testsuite")) {
+            Duration::from_millis(25)
+        } else {
+            Duration::from_secs(10)
+        }
+    }
+
+    fn default_metastore_connect_timeout() -> Duration {
+        Duration::from_secs(30)
+    }
+
+    pub fn validate(&self) -> anyhow::Result<()> {
+        ensure!(
+            self.readiness_interval >= Self::MIN_READINESS_INTERVAL,
+            "readiness interval (`cluster.readiness_interval`) must be at least {}, got `{}`",
+            humantime::format_duration(Self::MIN_READINESS_INTERVAL),
+            humantime::format_duration(self.readiness_interval)
+        );
+        Ok(())
+    }
+}
+
+impl Default for ClusterConfig {
+    fn default() -> Self {
+        Self {
+            readiness_interval: Self::default_readiness_interval(),
+            metastore_connect_timeout: Self::default_metastore_connect_timeout(),
         }
     }
 }
@@ -89,8 +167,27 @@ pub struct IndexerConfig {
     pub split_store_max_num_bytes: ByteSize,
     #[serde(default = "IndexerConfig::default_split_store_max_num_splits")]
     pub split_store_max_num_splits: usize,
+    /// Maximum number of bytes the indexing and merge operations, including the splits downloaded
+    /// for merges, are allowed to take in the scratch directories of the indexer. The delete
+    /// tasks of the janitor are bounded by a separate quota of the same size. Unlimited if not
+    /// set.
+    #[serde(default)]
+    pub scratch_max_bytes: Option<ByteSize>,
+    /// The indexing directory is purged on startup. While the indexer runs, the scratch
+    /// directories that no pipeline uses are removed once they have not been modified for this
+    /// long.
+    #[serde(
+        default = "IndexerConfig::default_scratch_cleanup_grace_period",
+        deserialize_with = "deserialize_human_duration",
+        serialize_with = "serialize_duration"
+    )]
+    pub scratch_cleanup_grace_period: Duration,
     #[serde(default = "IndexerConfig::default_max_concurrent_split_uploads")]
     pub max_concurrent_split_uploads: usize,
+    /// Maximum number of indexing pipelines running concurrently on the node. The pipelines
+    /// assigned to the node beyond this limit are rejected. Unlimited if not set.
+    #[serde(default)]
+    pub max_concurrent_pipelines: Option<NonZeroUsize>,
     /// Enables the OpenTelemetry exporter endpoint to ingest logs and traces via the OpenTelemetry
     /// Protocol (OTLP).
     #[serde(default = "IndexerConfig::default_enable_otlp_endpoint")]
@@ -129,19 +226,41 @@ impl IndexerConfig {
         1_000
     }
 
+    pub fn default_scratch_cleanup_grace_period() -> Duration {
+        Duration::from_secs(60 * 60)
+    }
+
     fn default_cpu_capacity() -> CpuCapacity {
         CpuCapacity::one_cpu_thread() * (num_cpus::get() as u32)
     }
 
+    /// Returns the indexing CPU capacity advertised to the control plane. When
+    /// `max_concurrent_pipelines` is set, the capacity is capped so that the scheduler never
+    /// assigns more full pipelines to the node than it is willing to run.
+    pub fn scheduling_cpu_capacity(&self) -> CpuCapacity {
+        let Some(max_concurrent_pipelines) = self.max_concurrent_pipelines else {
+            return self.cpu_capacity;
+        };
+        let max_num_pipelines = u32::try_from(max_concurrent_pipelines.get()).unwrap_or(u32::MAX);
+        let max_pipelines_capacity = CpuCapacity::from_cpu_millis(
+            PIPELINE_FULL_CAPACITY
+                .cpu_millis()
+                .saturating_mul(max_num_pipelines),
+        );
+        self.cpu_capacity.min(max_pipelines_capacity)
+    }
+
     #[cfg(any(test, feature = "testsuite"))]
     pub fn for_test() -> anyhow::Result<Self> {
-        use quickwit_proto::indexing::PIPELINE_FULL_CAPACITY;
         let indexer_config = IndexerConfig {
             enable_cooperative_indexing: false,
             enable_otlp_endpoint: true,
             split_store_max_num_bytes: ByteSize::mb(1),
             split_store_max_num_splits: 3,
+            scratch_max_bytes: None,
+            scratch_cleanup_grace_period: Self::default_scratch_cleanup_grace_period(),
             max_concurrent_split_uploads: 4,
+            max_concurrent_pipelines: None,
             cpu_capacity: PIPELINE_FULL_CAPACITY * 4u32,
         };
         Ok(indexer_config)
@@ -155,7 +274,10 @@ impl Default for IndexerConfig {
             enable_otlp_endpoint: Self::default_enable_otlp_endpoint(),
             split_store_max_num_bytes: Self::default_split_store_max_num_bytes(),
             split_store_max_num_splits: Self::default_split_store_max_num_splits(),
+            scratch_max_bytes: None,
+            scratch_cleanup_grace_period: Self::default_scratch_cleanup_grace_period(),
             max_concurrent_split_uploads: Self::default_max_concurrent_split_uploads(),
+            max_concurrent_pipelines: None,
             cpu_capacity: Self::default_cpu_capacity(),
         }
     }
@@ -169,6 +291,16 @@ pub struct SplitCacheLimits {
     pub max_num_splits: NonZeroU32,
     #[serde(default = "SplitCacheLimits::default_num_concurrent_downloads")]
     pub num_concurrent_downloads: NonZeroU32,
+    /// Splits that have not been accessed for longer than this duration are evicted from the
+    /// cache, even if the cache is within its limits. `None` means splits are only evicted to
+    /// make room for new ones.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_opt_human_duration",
+        serialize_with = "serialize_opt_duration"
+    )]
+    pub max_idle: Option<Duration>,
 }
 
 impl SplitCacheLimits {
@@ -187,10 +319,33 @@ impl Default for SplitCacheLimits {
             max_num_bytes: ByteSize::gb(1),
             max_num_splits: NonZeroU32::new(100).unwrap(),
             num_concurrent_downloads: NonZeroU32::new(1).unwrap(),
+            max_idle: None,
         }
     }
 }
 
+/// Caches the responses of the `list_splits` requests issued by the node. The cache is only enabled
+/// on nodes running the metastore service, which observe all the split updates invalidating it.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ListSplitsCacheConfig {
+    /// Time to live of the cached responses.
+    #[serde(
+        deserialize_with = "deserialize_human_duration",
+        serialize_with = "serialize_duration"
+    )]
+    pub ttl: Duration,
+    /// Maximum number of cached responses.
+    #[serde(default = "ListSplitsCacheConfig::default_capacity")]
+    pub capacity: NonZeroUsize,
+}
+
+impl ListSplitsCacheConfig {
+    fn default_capacity() -> NonZeroUsize {
+        NonZeroUsize::new(1_000).unwrap()
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(deny_unknown_fields, default)]
 pub struct SearcherConfig {
@@ -199,13 +354,57 @@ pub struct SearcherConfig {
     pub fast_field_cache_capacity: ByteSize,
     pub split_footer_cache_capacity: ByteSize,
     pub partial_request_cache_capacity: ByteSize,
+    /// Time to live of the entries of the partial request cache. `None` means the entries are only
+    /// evicted to make room for new ones.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_opt_human_duration",
+        serialize_with = "serialize_opt_duration"
+    )]
+    pub partial_request_cache_ttl: Option<Duration>,
     pub max_num_concurrent_split_searches: usize,
     pub max_num_concurrent_split_streams: usize,
+    /// Maximum number of root searches running concurrently on the node. Additional searches
+    /// are queued. `None` means unlimited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_searches: Option<NonZeroUsize>,
+    /// Maximum number of root searches waiting for a slot once `max_concurrent_searches` is
+    /// reached. Searches beyond that bound are rejected.
+    pub max_queued_searches: usize,
+    /// Timeout applied to root searches that do not specify one.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_opt_human_duration",
+        serialize_with = "serialize_opt_duration"
+    )]
+    pub default_timeout: Option<Duration>,
+    /// Maximum number of splits warming up concurrently on the node, i.e. downloading the data
+    /// required to search them. `None` means warmups are only bounded by
+    /// `max_num_concurrent_split_searches`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warmup_concurrency: Option<NonZeroUsize>,
+    /// Maximum number of bytes downloaded while warming up a single split. Searching a split
+    /// exceeding this budget fails. `None` means unlimited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warmup_split_max_num_bytes: Option<ByteSize>,
+    /// Maximum number of splits opened concurrently by leaf searches on the node. Additional
+    /// leaf searches wait for an open split to be released. `None` means unlimited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_open_splits: Option<NonZeroUsize>,
+    /// Maximum number of distinct values in a split of a field targeted by a terms aggregation.
+    /// Searches exceeding it are rejected unless they explicitly allow high-cardinality
+    /// aggregations. `None` means unlimited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_terms_aggregation_cardinality: Option<NonZeroU64>,
     // Strangely, if None, this will also have the effect of not forwarding
     // to searcher.
     // TODO document and fix if necessary.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub split_cache: Option<SplitCacheLimits>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub list_splits_cache: Option<ListSplitsCacheConfig>,
 }
 
 impl Default for SearcherConfig {
@@ -214,22 +413,169 @@ impl Default for SearcherConfig {
             fast_field_cache_capacity: ByteSize::gb(1),
             split_footer_cache_capacity: ByteSize::mb(500),
             partial_request_cache_capacity: ByteSize::mb(64),
+            partial_request_cache_ttl: None,
             max_num_concurrent_split_streams: 100,
             max_num_concurrent_split_searches: 100,
             aggregation_memory_limit: ByteSize::mb(500),
             aggregation_bucket_limit: 65000,
+            max_concurrent_searches: None,
+            max_queued_searches: 100,
+            default_timeout: None,
+            warmup_concurrency: None,
+            warmup_split_max_num_bytes: None,
+            max_open_splits: None,
+            max_terms_aggregation_cardinality: None,
             split_cache: None,
+            list_splits_cache: None,
         }
     }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+fn parse_human_duration<E: de::Error>(value: &str) -> Result<Duration, E> {
+    humantime::parse_duration(value).map_err(|error| {
+        de::Error::custom(format!(
+            "failed to parse human-readable duration `{value}`: {error:?}",
+        ))
+    })
+}
+
+fn deserialize_human_duration<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+where D: Deserializer<'de> {
+    let value: String = Deserialize::deserialize(deserializer)?;
+    parse_human_duration(&value)
+}
+
+fn deserialize_opt_human_duration<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+where D: Deserializer<'de> {
+    let value_opt: Option<String> = Deserialize::deserialize(deserializer)?;
+    value_opt
+        .map(|value| parse_human_duration(&value))
+        .transpose()
+}
+
+fn serialize_duration<S>(value: &Duration, s: S) -> Result<S::Ok, S::Error>
+where S: Serializer {
+    s.serialize_str(&humantime::format_duration(*value).to_string())
+}
+
+fn serialize_opt_duration<S>(value_opt: &Option<Duration>, s: S) -> Result<S::Ok, S::Error>
+where S: Serializer {
+    match value_opt {
+        Some(value) => serialize_duration(value, s),
+        None => s.serialize_none(),
+    }
+}
+
+/// A breakpoint of the ingest throttling curve: once the ingest queues usage ratio reaches
+/// `usage_ratio`, the ingest work rate is multiplied by `multiplier`.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ThrottlingStep {
+    pub usage_ratio: f64,
+    pub multiplier: f64,
+}
+
+impl ThrottlingStep {
+    const fn new(usage_ratio: f64, multiplier: f64) -> Self {
+        Self {
+            usage_ratio,
+            multiplier,
+        }
+    }
+}
+
+/// Policy controlling when the write-ahead log of the ingester is fsynced to disk.
+///
+/// It is serialized as `always`, `never`, or `interval(<duration>)`, for instance
+/// `interval(500ms)`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WalFsyncPolicy {
+    /// The write-ahead log is fsynced after every append. Acknowledged records are never lost.
+    Always,
+    /// The write-ahead log is fsynced on the first append following the interval since the last
+    /// fsync. Records acknowledged since the last fsync can be lost if the host crashes.
+    Interval(Duration),
+    /// The write-ahead log is never explicitly fsynced and the operating system decides when
+    /// records reach the disk. Any number of acknowledged records can be lost if the process or
+    /// the host crashes.
+    Never,
+}
+
+impl Default for WalFsyncPolicy {
+    /// The write-ahead log has historically been fsynced at most every 5 seconds.
+    fn default() -> Self {
+        Self::Interval(Duration::from_secs(5))
+    }
+}
+
+impl FromStr for WalFsyncPolicy {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim() {
+            "always" => Ok(Self::Always),
+            "never" => Ok(Self::Never),
+            other => {
+                let interval_str = other
+                    .strip_prefix("interval(")
+                    .and_then(|suffix| suffix.strip_suffix(')'))
+                    .ok_or_else(|| {
+                        format!(
+                            "unknown fsync policy `{value}`, expected `always`, `never`, or \
+                             `interval(<duration>)`"
+                        )
+                    })?;
+                let interval = humantime::parse_duration(interval_str.trim()).map_err(|error| {
+                    format!("failed to parse fsync interval `{interval_str}`: {error}")
+                })?;
+                if interval.is_zero() {
+                    return Err("fsync interval must be strictly positive".to_string());
+                }
+                Ok(Self::Interval(interval))
+            }
+        }
+    }
+}
+
+impl fmt::Display for WalFsyncPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Always => write!(f, "always"),
+            Self::Interval(interval) => {
+                write!(f, "interval({})", humantime::format_duration(*interval))
+            }
+            Self::Never => write!(f, "never"),
+        }
+    }
+}
+
+impl Serialize for WalFsyncPolicy {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where S: Serializer {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for WalFsyncPolicy {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where D: Deserializer<'de> {
+        let value: String = Deserialize::deserialize(deserializer)?;
+        value.parse().map_err(de::Error::custom)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(deny_unknown_fields, default)]
 pub struct IngestApiConfig {
     pub max_queue_memory_usage: ByteSize,
     pub max_queue_disk_usage: ByteSize,
     pub replication_factor: usize,
     pub content_length_limit: ByteSize,
+    /// Breakpoints, sorted by increasing usage ratio, mapping the ingest queues usage ratio to a
+    /// multiplier of the ingest work rate.
+    pub throttling_curve: Vec<ThrottlingStep>,
+    /// Policy controlling when the write-ahead log of the ingester is fsynced to disk.
+    pub fsync_policy: WalFsyncPolicy,
 }
 
 impl Default for IngestApiConfig {
@@ -239,6 +585,8 @@ impl Default for IngestApiConfig {
             max_queue_disk_usage: ByteSize::gib(4),   // TODO maybe we want more?
             replication_factor: 1,
             content_length_limit: ByteSize::mib(10),
+            throttling_curve: Self::default_throttling_curve(),
+            fsync_policy: WalFsyncPolicy::default(),
         }
     }
 }
@@ -271,6 +619,50 @@ impl IngestApiConfig {
             .expect("replication factor should be either 1 or 2"))
     }
 
+    pub fn default_throttling_curve() -> Vec<ThrottlingStep> {
+        vec![
+            ThrottlingStep::new(0.0, 2.0),
+            ThrottlingStep::new(0.25, 1.0),
+            ThrottlingStep::new(0.70, 2.0 / 3.0),
+            ThrottlingStep::new(0.80, 1.0 / 2.0),
+            ThrottlingStep::new(0.90, 1.0 / 4.0),
+            ThrottlingStep::new(0.95, 1.0 / 8.0),
+            ThrottlingStep::new(0.98, 1.0 / 16.0),
+            ThrottlingStep::new(0.99, 1.0 / 32.0),
+        ]
+    }
+
+    fn validate_throttling_curve(&self) -> anyhow::Result<()> {
+        for step in &self.throttling_curve {
+            ensure!(
+                (0.0..=1.0).contains(&step.usage_ratio),
+                "throttling curve usage ratios must be between 0 and 1, got `{}`",
+                step.usage_ratio
+            );
+            ensure!(
+                step.multiplier > 0.0 && step.multiplier.is_finite(),
+                "throttling curve multipliers must be strictly positive, got `{}`",
+                step.multiplier
+            );
+        }
+        for (previous_step, step) in self.throttling_curve.iter().tuple_windows() {
+            ensure!(
+                previous_step.usage_ratio < step.usage_ratio,
+                "throttling curve usage ratios must be strictly increasing, got `{}` followed by \
+                 `{}`",
+                previous_step.usage_ratio,
+                step.usage_ratio
+            );
+            ensure!(
+                previous_step.multiplier >= step.multiplier,
+                "throttling curve multipliers must be non-increasing, got `{}` followed by `{}`",
+                previous_step.multiplier,
+                step.multiplier
+            );
+        }
+        Ok(())
+    }
+
     fn validate(&self) -> anyhow::Result<()> {
         self.replication_factor()?;
         ensure!(
@@ -284,10 +676,112 @@ impl IngestApiConfig {
             self.max_queue_disk_usage,
             self.max_queue_memory_usage
         );
+        self.validate_throttling_curve()?;
         Ok(())
     }
 }
 
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// One JSON object per line, for log collectors.
+    Json,
+    /// Human-readable text, colored when the output supports it.
+    #[default]
+    Pretty,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LoggingConfig {
+    #[serde(default)]
+    pub format: LogFormat,
+    /// Log level of the Quickwit crates. Defaults to the log level of the CLI command.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub level: Option<String>,
+    /// Log level overrides keyed by module path, for instance `quickwit_search: debug`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub module_levels: BTreeMap<String, String>,
+}
+
+impl LoggingConfig {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if let Some(level) = &self.level {
+            validate_log_level("logging.level", level)?;
+        }
+        for (module_path, level) in &self.module_levels {
+            let is_valid_module_path = !module_path.is_empty()
+                && module_path
+                    .chars()
+                    .all(|character| character.is_alphanumeric() || matches!(character, '_' | ':'));
+            ensure!(
+                is_valid_module_path,
+                "invalid module path `{module_path}` in `logging.module_levels`"
+            );
+            validate_log_level(&format!("logging.module_levels.{module_path}"), level)?;
+        }
+        Ok(())
+    }
+}
+
+fn validate_log_level(key: &str, level: &str) -> anyhow::Result<()> {
+    if LevelFilter::from_str(level).is_err() {
+        bail!(
+            "invalid log level `{level}` for `{key}`, expected one of `off`, `error`, `warn`, \
+             `info`, `debug`, or `trace`"
+        );
+    }
+    Ok(())
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct JanitorConfig {
+    /// Maximum number of delete tasks executing concurrently on the node, all indexes combined.
+    #[serde(default = "JanitorConfig::default_max_concurrent_delete_tasks")]
+    pub max_concurrent_delete_tasks: NonZeroUsize,
+    /// Maximum write throughput of each delete task. When the index defines a lower
+    /// `max_merge_write_throughput`, the latter applies.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_delete_task_throughput: Option<ByteSize>,
+    /// Enables the deletion of the split files present in the index storages but not recorded in
+    /// the metastore, which are typically left behind by crashed uploads.
+    #[serde(default)]
+    pub enable_orphan_split_files_gc: bool,
+    /// Split files not recorded in the metastore are deleted only once their split ID is older
+    /// than this grace period, so that uploads in progress are not affected.
+    #[serde(
+        default = "JanitorConfig::default_orphan_split_files_grace_period",
+        deserialize_with = "deserialize_human_duration",
+        serialize_with = "serialize_duration"
+    )]
+    pub orphan_split_files_grace_period: Duration,
+}
+
+impl JanitorConfig {
+    fn default_max_concurrent_delete_tasks() -> NonZeroUsize {
+        NonZeroUsize::new(2).unwrap()
+    }
+
+    fn default_orphan_split_files_grace_period() -> Duration {
+        Duration::from_secs(60 * 60 * 24)
+    }
+}
+
+impl Default for JanitorConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent_delete_tasks: Self::default_max_concurrent_delete_tasks(),
+            max_delete_task_throughput: None,
+            enable_orphan_split_files_gc: false,
+            orphan_split_files_grace_period: Self::default_orphan_split_files_grace_period(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct JaegerConfig {
@@ -372,12 +866,15 @@ pub struct NodeConfig {
     pub default_index_root_uri: Uri,
     pub rest_config: RestConfig,
     pub grpc_config: GrpcConfig,
+    pub cluster_config: ClusterConfig,
     pub storage_configs: StorageConfigs,
     pub metastore_configs: MetastoreConfigs,
     pub indexer_config: IndexerConfig,
     pub searcher_config: SearcherConfig,
     pub ingest_api_config: IngestApiConfig,
+    pub janitor_config: JanitorConfig,
     pub jaeger_config: JaegerConfig,
+    pub logging_config: LoggingConfig,
 }
 
 impl NodeConfig {
@@ -487,6 +984,307 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_indexer_config_scheduling_cpu_capacity() {
+        let mut indexer_config = IndexerConfig {
+            cpu_capacity: PIPELINE_FULL_CAPACITY * 4u32,
+            ..Default::default()
+        };
+        assert_eq!(
+            indexer_config.scheduling_cpu_capacity(),
+            PIPELINE_FULL_CAPACITY * 4u32
+        );
+        indexer_config.max_concurrent_pipelines = NonZeroUsize::new(2);
+        assert_eq!(
+            indexer_config.scheduling_cpu_capacity(),
+            PIPELINE_FULL_CAPACITY * 2u32
+        );
+        indexer_config.max_concurrent_pipelines = NonZeroUsize::new(8);
+        assert_eq!(
+            indexer_config.scheduling_cpu_capacity(),
+            PIPELINE_FULL_CAPACITY * 4u32
+        );
+    }
+
+    #[test]
+    fn test_cluster_config_serialization() {
+        {
+            let cluster_config: ClusterConfig = serde_json::from_str(r#"{}"#).unwrap();
+            assert_eq!(cluster_config, ClusterConfig::default());
+            assert_eq!(cluster_config.readiness_interval, Duration::from_millis(25));
+            cluster_config.validate().unwrap();
+            assert_eq!(
+                cluster_config.metastore_connect_timeout,
+                Duration::from_secs(30)
+            );
+        }
+        {
+            let cluster_config: ClusterConfig = serde_yaml::from_str(
+                r#"
+                readiness_interval: 2s
+                metastore_connect_timeout: 2m
+            "#,
+            )
+            .unwrap();
+            assert_eq!(cluster_config.readiness_interval, Duration::from_secs(2));
+            assert_eq!(
+                cluster_config.metastore_connect_timeout,
+                Duration::from_secs(120)
+            );
+            cluster_config.validate().unwrap();
+
+            let cluster_config_json = serde_json::to_value(&cluster_config).unwrap();
+            assert_eq!(
+                cluster_config_json
+                    .get("readiness_interval")
+                    .unwrap()
+                    .as_str()
+                    .unwrap(),
+                "2s"
+            );
+            assert_eq!(
+                cluster_config_json
+                    .get("metastore_connect_timeout")
+                    .unwrap()
+                    .as_str()
+                    .unwrap(),
+                "2m"
+            );
+        }
+        {
+            let cluster_config: ClusterConfig =
+                serde_yaml::from_str(r#"readiness_interval: 10ms"#).unwrap();
+            assert_eq!(
+                cluster_config.validate().unwrap_err().to_string(),
+                "readiness interval (`cluster.readiness_interval`) must be at least 25ms, got \
+                 `10ms`"
+            );
+        }
+    }
+
+    #[test]
+    fn test_searcher_config_serialization() {
+        {
+            let searcher_config: SearcherConfig = serde_json::from_str(r#"{}"#).unwrap();
+            assert_eq!(&searcher_config, &SearcherConfig::default());
+            assert!(searcher_config.max_concurrent_searches.is_none());
+            assert!(searcher_config.default_timeout.is_none());
+            assert!(searcher_config.partial_request_cache_ttl.is_none());
+            assert!(searcher_config.warmup_concurrency.is_none());
+            assert!(searcher_config.warmup_split_max_num_bytes.is_none());
+            assert!(searcher_config.max_open_splits.is_none());
+            assert!(searcher_config.max_terms_aggregation_cardinality.is_none());
+        }
+        {
+            let searcher_config: SearcherConfig = serde_yaml::from_str(
+                r#"
+                    max_concurrent_searches: 10
+                    max_queued_searches: 5
+                    default_timeout: 30s
+                    partial_request_cache_ttl: 5m
+                    warmup_concurrency: 4
+                    warmup_split_max_num_bytes: 200MB
+                    max_open_splits: 50
+                    max_terms_aggregation_cardinality: 100000
+                "#,
+            )
+            .unwrap();
+            assert_eq!(
+                searcher_config.max_concurrent_searches,
+                Some(NonZeroUsize::new(10).unwrap())
+            );
+            assert_eq!(searcher_config.max_queued_searches, 5);
+            assert_eq!(
+                searcher_config.default_timeout,
+                Some(Duration::from_secs(30))
+            );
+            assert_eq!(
+                searcher_config.partial_request_cache_ttl,
+                Some(Duration::from_secs(300))
+            );
+            assert_eq!(
+                searcher_config.warmup_concurrency,
+                Some(NonZeroUsize::new(4).unwrap())
+            );
+            assert_eq!(
+                searcher_config.warmup_split_max_num_bytes,
+                Some(ByteSize::mb(200))
+            );
+            assert_eq!(
+                searcher_config.max_open_splits,
+                Some(NonZeroUsize::new(50).unwrap())
+            );
+            assert_eq!(
+                searcher_config.max_terms_aggregation_cardinality,
+                Some(NonZeroU64::new(100_000).unwrap())
+            );
+            let searcher_config_json = serde_json::to_value(&searcher_config).unwrap();
+            assert_eq!(
+                searcher_config_json
+                    .get("default_timeout")
+                    .unwrap()
+                    .as_str()
+                    .unwrap(),
+                "30s"
+            );
+        }
+        {
+            let error =
+                serde_yaml::from_str::<SearcherConfig>(r#"default_timeout: forever"#).unwrap_err();
+            assert!(error
+                .to_string()
+                .contains("failed to parse human-readable duration"));
+        }
+    }
+
+    #[test]
+    fn test_list_splits_cache_config_serialization() {
+        let list_splits_cache_config: ListSplitsCacheConfig =
+            serde_yaml::from_str(r#"ttl: 5s"#).unwrap();
+        assert_eq!(list_splits_cache_config.ttl, Duration::from_secs(5));
+        assert_eq!(list_splits_cache_config.capacity.get(), 1_000);
+
+        let list_splits_cache_config_json = serde_json::to_value(list_splits_cache_config).unwrap();
+        assert_eq!(
+            list_splits_cache_config_json,
+            serde_json::json!({"ttl": "5s", "capacity": 1000})
+        );
+        serde_yaml::from_str::<ListSplitsCacheConfig>(r#"capacity: 10"#).unwrap_err();
+    }
+
+    #[test]
+    fn test_split_cache_limits_serialization() {
+        {
+            let split_cache_limits: SplitCacheLimits =
+                serde_yaml::from_str(r#"max_num_bytes: 1G"#).unwrap();
+            assert_eq!(split_cache_limits.max_num_bytes, ByteSize::gb(1));
+            assert!(split_cache_limits.max_idle.is_none());
+        }
+        {
+            let split_cache_limits: SplitCacheLimits = serde_yaml::from_str(
+                r#"
+                    max_num_bytes: 1G
+                    max_idle: 6h
+                "#,
+            )
+            .unwrap();
+            assert_eq!(
+                split_cache_limits.max_idle,
+                Some(Duration::from_secs(6 * 3600))
+            );
+            let split_cache_limits_json = serde_json::to_value(split_cache_limits).unwrap();
+            assert_eq!(
+                split_cache_limits_json
+                    .get("max_idle")
+                    .unwrap()
+                    .as_str()
+                    .unwrap(),
+                "6h"
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_ingest_api_throttling_curve() {
+        {
+            let ingest_api_config: IngestApiConfig = serde_yaml::from_str(
+                r#"
+                    throttling_curve:
+                      - usage_ratio: 0.0
+                        multiplier: 1.0
+                      - usage_ratio: 0.5
+                        multiplier: 0.5
+                "#,
+            )
+            .unwrap();
+            ingest_api_config.validate().unwrap();
+            assert_eq!(
+                ingest_api_config.throttling_curve,
+                vec![ThrottlingStep::new(0.0, 1.0), ThrottlingStep::new(0.5, 0.5)]
+            );
+        }
+        {
+            let ingest_api_config: IngestApiConfig = serde_yaml::from_str(
+                r#"
+                    throttling_curve:
+                      - usage_ratio: 0.5
+                        multiplier: 1.0
+                      - usage_ratio: 0.4
+                        multiplier: 0.5
+                "#,
+            )
+            .unwrap();
+            assert_eq!(
+                ingest_api_config.validate().unwrap_err().to_string(),
+                "throttling curve usage ratios must be strictly increasing, got `0.5` followed by \
+                 `0.4`"
+            );
+        }
+        {
+            let ingest_api_config: IngestApiConfig = serde_yaml::from_str(
+                r#"
+                    throttling_curve:
+                      - usage_ratio: 0.5
+                        multiplier: 0.5
+                      - usage_ratio: 0.9
+                        multiplier: 1.0
+                "#,
+            )
+            .unwrap();
+            assert_eq!(
+                ingest_api_config.validate().unwrap_err().to_string(),
+                "throttling curve multipliers must be non-increasing, got `0.5` followed by `1`"
+            );
+        }
+        {
+            let ingest_api_config: IngestApiConfig = serde_yaml::from_str(
+                r#"
+                    throttling_curve:
+                      - usage_ratio: 1.5
+                        multiplier: 0.5
+                "#,
+            )
+            .unwrap();
+            assert_eq!(
+                ingest_api_config.validate().unwrap_err().to_string(),
+                "throttling curve usage ratios must be between 0 and 1, got `1.5`"
+            );
+        }
+        IngestApiConfig::default().validate().unwrap();
+    }
+
+    #[test]
+    fn test_wal_fsync_policy_serialization() {
+        let ingest_api_config: IngestApiConfig = serde_yaml::from_str("{}").unwrap();
+        assert_eq!(
+            ingest_api_config.fsync_policy,
+            WalFsyncPolicy::Interval(Duration::from_secs(5))
+        );
+
+        for (fsync_policy_str, expected_fsync_policy) in [
+            ("always", WalFsyncPolicy::Always),
+            ("never", WalFsyncPolicy::Never),
+            (
+                "interval(500ms)",
+                WalFsyncPolicy::Interval(Duration::from_millis(500)),
+            ),
+        ] {
+            let ingest_api_config: IngestApiConfig =
+                serde_yaml::from_str(&format!("fsync_policy: {fsync_policy_str}")).unwrap();
+            assert_eq!(ingest_api_config.fsync_policy, expected_fsync_policy);
+
+            let serialized_fsync_policy = serde_json::to_value(expected_fsync_policy).unwrap();
+            assert_eq!(serialized_fsync_policy, fsync_policy_str);
+        }
+        for invalid_fsync_policy_str in ["sometimes", "interval(0s)", "interval(forever)"] {
+            serde_yaml::from_str::<IngestApiConfig>(&format!(
+                "fsync_policy: {invalid_fsync_policy_str}"
+            ))
+            .unwrap_err();
+        }
+    }
+
     #[test]
     fn test_validate_ingest_api_config() {
         {
@@ -532,17 +1330,93 @@ mod tests {
         )
         .unwrap();
         assert_eq!(grpc_config.max_message_size, ByteSize::mib(4));
+        assert!(!grpc_config.enable_reflection);
+
+        let grpc_config: GrpcConfig = serde_yaml::from_str(
+            r#"
+                enable_reflection: true
+            "#,
+        )
+        .unwrap();
+        assert!(grpc_config.enable_reflection);
+    }
+
+    #[test]
+    fn test_logging_config_serialization() {
+        let logging_config: LoggingConfig = serde_yaml::from_str("{}").unwrap();
+        assert_eq!(logging_config, LoggingConfig::default());
+        assert_eq!(logging_config.format, LogFormat::Pretty);
+
+        let logging_config_yaml = r#"
+            format: json
+            level: warn
+            module_levels:
+              quickwit_search: debug
+              quickwit_indexing::actors: trace
+        "#;
+        let logging_config: LoggingConfig = serde_yaml::from_str(logging_config_yaml).unwrap();
+        assert_eq!(logging_config.format, LogFormat::Json);
+        assert_eq!(logging_config.level.as_deref(), Some("warn"));
+        assert_eq!(logging_config.module_levels.len(), 2);
+        assert_eq!(logging_config.module_levels["quickwit_search"], "debug");
+        logging_config.validate().unwrap();
+
+        serde_yaml::from_str::<LoggingConfig>("format: xml").unwrap_err();
+    }
+
+    #[test]
+    fn test_logging_config_validate() {
+        let logging_config = LoggingConfig {
+            level: Some("verbose".to_string()),
+            ..Default::default()
+        };
+        let error = logging_config.validate().unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("invalid log level `verbose` for `logging.level`"));
+
+        let logging_config = LoggingConfig {
+            module_levels: BTreeMap::from([("quickwit_search".to_string(), "loud".to_string())]),
+            ..Default::default()
+        };
+        let error = logging_config.validate().unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("`logging.module_levels.quickwit_search`"));
+
+        let logging_config = LoggingConfig {
+            module_levels: BTreeMap::from([(
+                "quickwit=info,tantivy".to_string(),
+                "debug".to_string(),
+            )]),
+            ..Default::default()
+        };
+        logging_config.validate().unwrap_err();
     }
 
     #[test]
     fn test_grpc_config_validate() {
         let grpc_config = GrpcConfig {
             max_message_size: ByteSize::mb(1),
+            ..Default::default()
         };
         assert!(grpc_config.validate().is_ok());
 
         let grpc_config = GrpcConfig {
             max_message_size: ByteSize::kb(1),
+            ..Default::default()
+        };
+        assert!(grpc_config.validate().is_err());
+
+        let grpc_config = GrpcConfig {
+            max_message_size: ByteSize::gib(1),
+            ..Default::default()
+        };
+        assert!(grpc_config.validate().is_ok());
+
+        let grpc_config = GrpcConfig {
+            max_message_size: ByteSize::gib(2),
+            ..Default::default()
         };
         assert!(grpc_config.validate().is_err());
     }
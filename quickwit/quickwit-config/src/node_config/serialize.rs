@@ -36,8 +36,9 @@ use crate::service::QuickwitService;
 use crate::storage_config::StorageConfigs;
 use crate::templating::render_config;
 use crate::{
-    validate_identifier, validate_node_id, ConfigFormat, IndexerConfig, IngestApiConfig,
-    JaegerConfig, MetastoreConfigs, NodeConfig, SearcherConfig,
+    validate_identifier, validate_node_id, ClusterConfig, ConfigFormat, IndexerConfig,
+    IngestApiConfig, JaegerConfig, JanitorConfig, LoggingConfig, MetastoreConfigs, NodeConfig,
+    SearcherConfig,
 };
 
 pub const DEFAULT_CLUSTER_ID: &str = "quickwit-default-cluster";
@@ -188,6 +189,9 @@ struct NodeConfigBuilder {
     #[serde(rename = "grpc")]
     #[serde(default)]
     grpc_config: GrpcConfig,
+    #[serde(rename = "cluster")]
+    #[serde(default)]
+    cluster_config: ClusterConfig,
     #[serde(rename = "storage")]
     #[serde(default)]
     storage_configs: StorageConfigs,
@@ -203,9 +207,15 @@ struct NodeConfigBuilder {
     #[serde(rename = "ingest_api")]
     #[serde(default)]
     ingest_api_config: IngestApiConfig,
+    #[serde(rename = "janitor")]
+    #[serde(default)]
+    janitor_config: JanitorConfig,
     #[serde(rename = "jaeger")]
     #[serde(default)]
     jaeger_config: JaegerConfig,
+    #[serde(rename = "logging")]
+    #[serde(default)]
+    logging_config: LoggingConfig,
 }
 
 impl NodeConfigBuilder {
@@ -242,6 +252,7 @@ impl NodeConfigBuilder {
             .build_and_validate(listen_ip, env_vars)?;
 
         self.grpc_config.validate()?;
+        self.cluster_config.validate()?;
 
         let gossip_listen_port = self
             .gossip_listen_port
@@ -288,6 +299,7 @@ impl NodeConfigBuilder {
         self.storage_configs.validate()?;
         self.storage_configs.apply_flavors();
         self.ingest_api_config.validate()?;
+        self.logging_config.validate()?;
 
         let node_config = NodeConfig {
             cluster_id: self.cluster_id.resolve(env_vars)?,
@@ -303,12 +315,15 @@ impl NodeConfigBuilder {
             default_index_root_uri,
             rest_config,
             grpc_config: self.grpc_config,
+            cluster_config: self.cluster_config,
             metastore_configs: self.metastore_configs,
             storage_configs: self.storage_configs,
             indexer_config: self.indexer_config,
             searcher_config: self.searcher_config,
             ingest_api_config: self.ingest_api_config,
+            janitor_config: self.janitor_config,
             jaeger_config: self.jaeger_config,
+            logging_config: self.logging_config,
         };
 
         validate(&node_config)?;
@@ -350,12 +365,15 @@ impl Default for NodeConfigBuilder {
             default_index_root_uri: ConfigValue::none(),
             rest_config_builder: RestConfigBuilder::default(),
             grpc_config: GrpcConfig::default(),
+            cluster_config: ClusterConfig::default(),
             storage_configs: StorageConfigs::default(),
             metastore_configs: MetastoreConfigs::default(),
             indexer_config: IndexerConfig::default(),
             searcher_config: SearcherConfig::default(),
             ingest_api_config: IngestApiConfig::default(),
+            janitor_config: JanitorConfig::default(),
             jaeger_config: JaegerConfig::default(),
+            logging_config: LoggingConfig::default(),
         }
     }
 }
@@ -442,12 +460,15 @@ pub fn node_config_for_test() -> NodeConfig {
         default_index_root_uri,
         rest_config,
         grpc_config: GrpcConfig::default(),
+        cluster_config: ClusterConfig::default(),
         storage_configs: StorageConfigs::default(),
         metastore_configs: MetastoreConfigs::default(),
         indexer_config: IndexerConfig::default(),
         searcher_config: SearcherConfig::default(),
         ingest_api_config: IngestApiConfig::default(),
+        janitor_config: JanitorConfig::default(),
         jaeger_config: JaegerConfig::default(),
+        logging_config: LoggingConfig::default(),
     }
 }
 
@@ -457,12 +478,14 @@ mod tests {
     use std::net::Ipv4Addr;
     use std::num::NonZeroU64;
     use std::path::Path;
+    use std::time::Duration;
 
     use bytesize::ByteSize;
     use itertools::Itertools;
 
     use super::*;
     use crate::storage_config::StorageBackendFlavor;
+    use crate::LogFormat;
 
     fn get_config_filepath(config_filename: &str) -> String {
         format!(
@@ -540,7 +563,7 @@ mod tests {
             s3_storage_config.endpoint.as_ref().unwrap(),
             "http://localhost:4566"
         );
-        assert!(s3_storage_config.force_path_style_access);
+        assert_eq!(s3_storage_config.force_path_style_access, Some(true));
         assert!(s3_storage_config.disable_multi_object_delete);
         assert!(s3_storage_config.disable_multipart_upload);
 
@@ -553,7 +576,10 @@ mod tests {
                 enable_otlp_endpoint: true,
                 split_store_max_num_bytes: ByteSize::tb(1),
                 split_store_max_num_splits: 10_000,
+                scratch_max_bytes: Some(ByteSize::gb(500)),
+                scratch_cleanup_grace_period: Duration::from_secs(30 * 60),
                 max_concurrent_split_uploads: 8,
+                max_concurrent_pipelines: None,
                 cpu_capacity: IndexerConfig::default_cpu_capacity(),
                 enable_cooperative_indexing: false,
             }
@@ -573,9 +599,18 @@ mod tests {
                 fast_field_cache_capacity: ByteSize::gb(10),
                 split_footer_cache_capacity: ByteSize::gb(1),
                 partial_request_cache_capacity: ByteSize::mb(64),
+                partial_request_cache_ttl: None,
                 max_num_concurrent_split_searches: 150,
                 max_num_concurrent_split_streams: 120,
+                max_concurrent_searches: None,
+                max_queued_searches: 100,
+                default_timeout: None,
+                warmup_concurrency: None,
+                warmup_split_max_num_bytes: None,
+                max_open_splits: None,
+                max_terms_aggregation_cardinality: None,
                 split_cache: None,
+                list_splits_cache: None,
             }
         );
         assert_eq!(
@@ -1233,4 +1268,102 @@ mod tests {
         .to_string();
         assert!(error_message.contains("replication factor"));
     }
+
+    #[tokio::test]
+    async fn test_node_config_logging_config() {
+        let node_config_yaml = r#"
+            version: 0.7
+            logging:
+              format: json
+              level: warn
+              module_levels:
+                quickwit_search: debug
+        "#;
+        let node_config = load_node_config_with_env(
+            ConfigFormat::Yaml,
+            node_config_yaml.as_bytes(),
+            &Default::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(node_config.logging_config.format, LogFormat::Json);
+        assert_eq!(node_config.logging_config.level.as_deref(), Some("warn"));
+        assert_eq!(
+            node_config.logging_config.module_levels["quickwit_search"],
+            "debug"
+        );
+
+        let node_config_yaml = r#"
+            version: 0.7
+            logging:
+              module_levels:
+                quickwit_search: verbose
+        "#;
+        let error_message = load_node_config_with_env(
+            ConfigFormat::Yaml,
+            node_config_yaml.as_bytes(),
+            &Default::default(),
+        )
+        .await
+        .unwrap_err()
+        .to_string();
+        assert!(error_message.contains("invalid log level `verbose`"));
+    }
+
+    #[tokio::test]
+    async fn test_node_config_janitor_config() {
+        let node_config_yaml = r#"
+            version: 0.7
+        "#;
+        let node_config = load_node_config_with_env(
+            ConfigFormat::Yaml,
+            node_config_yaml.as_bytes(),
+            &Default::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(node_config.janitor_config, JanitorConfig::default());
+
+        let node_config_yaml = r#"
+            version: 0.7
+            janitor:
+              max_concurrent_delete_tasks: 1
+              max_delete_task_throughput: 20MB
+              enable_orphan_split_files_gc: true
+              orphan_split_files_grace_period: 12h
+        "#;
+        let node_config = load_node_config_with_env(
+            ConfigFormat::Yaml,
+            node_config_yaml.as_bytes(),
+            &Default::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            node_config.janitor_config.max_concurrent_delete_tasks.get(),
+            1
+        );
+        assert_eq!(
+            node_config.janitor_config.max_delete_task_throughput,
+            Some(ByteSize::mb(20))
+        );
+        assert!(node_config.janitor_config.enable_orphan_split_files_gc);
+        assert_eq!(
+            node_config.janitor_config.orphan_split_files_grace_period,
+            Duration::from_secs(12 * 60 * 60)
+        );
+
+        let node_config_yaml = r#"
+            version: 0.7
+            janitor:
+              max_concurrent_delete_tasks: 0
+        "#;
+        load_node_config_with_env(
+            ConfigFormat::Yaml,
+            node_config_yaml.as_bytes(),
+            &Default::default(),
+        )
+        .await
+        .unwrap_err();
+    }
 }
@@ -19,16 +19,18 @@
 
 pub(crate) mod serialize;
 
+use std::fmt;
 use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 use bytes::Bytes;
+use chrono::{DateTime, SecondsFormat, Utc};
 use quickwit_common::is_false;
 use quickwit_common::uri::Uri;
 use quickwit_proto::metastore::SourceType;
 use serde::de::Error;
-use serde::{Deserialize, Deserializer, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value as JsonValue;
 pub use serialize::load_source_config_from_user_config;
 // For backward compatibility.
@@ -190,6 +192,7 @@ impl TestableForRegression for SourceConfig {
                 client_log_level: None,
                 client_params: serde_json::json!({}),
                 enable_backfill_mode: false,
+                start_from: None,
             }),
             transform_config: Some(TransformConfig {
                 vrl_script: ".message = downcase(string!(.message))".to_string(),
@@ -315,6 +318,82 @@ pub struct KafkaSourceParams {
     #[serde(default)]
     #[serde(skip_serializing_if = "is_false")]
     pub enable_backfill_mode: bool,
+    /// Position from which the source starts consuming the partitions that do not have a
+    /// checkpoint yet. Possible values are `beginning`, `end`, and `timestamp(<rfc3339>)`. When
+    /// unset, the `auto.offset.reset` client parameter applies.
+    #[schema(value_type = String)]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_from: Option<SourceStartFrom>,
+}
+
+/// Position from which a source starts consuming a partition for which no checkpoint exists.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SourceStartFrom {
+    /// Starts from the oldest record available.
+    Beginning,
+    /// Starts from the next record written after the source starts.
+    End,
+    /// Starts from the first record whose timestamp is greater than or equal to the given
+    /// timestamp.
+    Timestamp(DateTime<Utc>),
+}
+
+impl FromStr for SourceStartFrom {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim() {
+            "beginning" => Ok(Self::Beginning),
+            "end" => Ok(Self::End),
+            other => {
+                let timestamp_str = other
+                    .strip_prefix("timestamp(")
+                    .and_then(|suffix| suffix.strip_suffix(')'))
+                    .ok_or_else(|| {
+                        format!(
+                            "unknown start position `{value}`, expected `beginning`, `end`, or \
+                             `timestamp(<rfc3339>)`"
+                        )
+                    })?;
+                let timestamp = DateTime::parse_from_rfc3339(timestamp_str.trim())
+                    .map_err(|error| {
+                        format!("failed to parse RFC 3339 timestamp `{timestamp_str}`: {error}")
+                    })?
+                    .with_timezone(&Utc);
+                Ok(Self::Timestamp(timestamp))
+            }
+        }
+    }
+}
+
+impl fmt::Display for SourceStartFrom {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Beginning => write!(f, "beginning"),
+            Self::End => write!(f, "end"),
+            Self::Timestamp(timestamp) => write!(
+                f,
+                "timestamp({})",
+                timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+            ),
+        }
+    }
+}
+
+impl Serialize for SourceStartFrom {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where S: Serializer {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for SourceStartFrom {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where D: Deserializer<'de> {
+        let value: String = Deserialize::deserialize(deserializer)?;
+        value.parse().map_err(D::Error::custom)
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
@@ -582,6 +661,7 @@ mod tests {
                 client_log_level: None,
                 client_params: json! {{"bootstrap.servers": "localhost:9092"}},
                 enable_backfill_mode: false,
+                start_from: None,
             }),
             transform_config: Some(TransformConfig {
                 vrl_script: ".message = downcase(string!(.message))".to_string(),
@@ -601,6 +681,7 @@ mod tests {
                 client_log_level: None,
                 client_params: json!(null),
                 enable_backfill_mode: false,
+                start_from: None,
             };
             let params_yaml = serde_yaml::to_string(&params).unwrap();
 
@@ -615,6 +696,7 @@ mod tests {
                 client_log_level: Some("info".to_string()),
                 client_params: json! {{"bootstrap.servers": "localhost:9092"}},
                 enable_backfill_mode: false,
+                start_from: None,
             };
             let params_yaml = serde_yaml::to_string(&params).unwrap();
 
@@ -638,6 +720,7 @@ mod tests {
                     client_log_level: None,
                     client_params: json!(null),
                     enable_backfill_mode: false,
+                    start_from: None,
                 }
             );
         }
@@ -656,9 +739,54 @@ mod tests {
                     client_log_level: Some("info".to_string()),
                     client_params: json! {{"bootstrap.servers": "localhost:9092"}},
                     enable_backfill_mode: true,
+                    start_from: None,
                 }
             );
         }
+        {
+            let yaml = r#"
+                    topic: my-topic
+                    start_from: timestamp(2024-01-01T12:00:00+02:00)
+                "#;
+            let params = serde_yaml::from_str::<KafkaSourceParams>(yaml).unwrap();
+            let expected_timestamp = DateTime::parse_from_rfc3339("2024-01-01T10:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc);
+            assert_eq!(
+                params.start_from,
+                Some(SourceStartFrom::Timestamp(expected_timestamp))
+            );
+        }
+        {
+            let yaml = r#"
+                    topic: my-topic
+                    start_from: yesterday
+                "#;
+            let error = serde_yaml::from_str::<KafkaSourceParams>(yaml).unwrap_err();
+            assert!(error.to_string().contains("unknown start position"));
+        }
+    }
+
+    #[test]
+    fn test_source_start_from_serialization() {
+        for start_from_str in [
+            "beginning",
+            "end",
+            "timestamp(2024-01-01T10:00:00Z)",
+            "timestamp(2024-01-01T10:00:00.123Z)",
+        ] {
+            let start_from = SourceStartFrom::from_str(start_from_str).unwrap();
+            assert_eq!(start_from.to_string(), start_from_str);
+
+            let start_from_json = serde_json::to_string(&start_from).unwrap();
+            assert_eq!(start_from_json, format!("\"{start_from_str}\""));
+            assert_eq!(
+                serde_json::from_str::<SourceStartFrom>(&start_from_json).unwrap(),
+                start_from
+            );
+        }
+        SourceStartFrom::from_str("timestamp(2024-01-01)").unwrap_err();
+        SourceStartFrom::from_str("timestamp(2024-01-01T10:00:00Z").unwrap_err();
     }
 
     #[tokio::test]
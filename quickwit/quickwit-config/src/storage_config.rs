@@ -17,14 +17,23 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
 use std::ops::Deref;
+use std::str::FromStr;
+use std::time::Duration;
 use std::{env, fmt};
 
-use anyhow::ensure;
+use anyhow::{bail, ensure, Context};
+use bytesize::ByteSize;
 use itertools::Itertools;
+use quickwit_common::is_false;
+use quickwit_common::uri::{Protocol, Uri};
 use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, EnumMap};
 
+use crate::merge_policy_config::{parse_human_duration, serialize_duration};
+
 /// Lists the storage backends supported by Quickwit.
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -39,6 +48,11 @@ pub enum StorageBackend {
     S3,
     /// Google Cloud Storage
     Google,
+    /// HTTP(S) server, read-only
+    Http,
+    /// Hadoop Distributed File System, through the WebHDFS REST API
+    #[serde(rename = "webhdfs")]
+    WebHdfs,
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
@@ -104,6 +118,12 @@ impl StorageConfigs {
                 "{left:?} storage config is defined multiple times",
             );
         }
+        if let Some(azure_storage_config) = self.find_azure() {
+            azure_storage_config.validate()?;
+        }
+        if let Some(s3_storage_config) = self.find_s3() {
+            s3_storage_config.validate()?;
+        }
         Ok(())
     }
 
@@ -151,6 +171,15 @@ impl StorageConfigs {
                 _ => None,
             })
     }
+
+    pub fn find_webhdfs(&self) -> Option<&WebHdfsStorageConfig> {
+        self.0
+            .iter()
+            .find_map(|storage_config| match storage_config {
+                StorageConfig::WebHdfs(webhdfs_storage_config) => Some(webhdfs_storage_config),
+                _ => None,
+            })
+    }
 }
 
 impl Deref for StorageConfigs {
@@ -169,6 +198,8 @@ pub enum StorageConfig {
     Ram(RamStorageConfig),
     S3(S3StorageConfig),
     Google(GoogleCloudStorageConfig),
+    #[serde(rename = "webhdfs")]
+    WebHdfs(WebHdfsStorageConfig),
 }
 
 impl StorageConfig {
@@ -177,6 +208,7 @@ impl StorageConfig {
             Self::Azure(azure_storage_config) => azure_storage_config.redact(),
             Self::File(_) | Self::Ram(_) | Self::Google(_) => {}
             Self::S3(s3_storage_config) => s3_storage_config.redact(),
+            Self::WebHdfs(webhdfs_storage_config) => webhdfs_storage_config.redact(),
         }
     }
 
@@ -214,6 +246,13 @@ impl StorageConfig {
             _ => None,
         }
     }
+
+    pub fn as_webhdfs(&self) -> Option<&WebHdfsStorageConfig> {
+        match self {
+            Self::WebHdfs(webhdfs_storage_config) => Some(webhdfs_storage_config),
+            _ => None,
+        }
+    }
 }
 
 impl From<AzureStorageConfig> for StorageConfig {
@@ -246,6 +285,12 @@ impl From<GoogleCloudStorageConfig> for StorageConfig {
     }
 }
 
+impl From<WebHdfsStorageConfig> for StorageConfig {
+    fn from(webhdfs_storage_config: WebHdfsStorageConfig) -> Self {
+        Self::WebHdfs(webhdfs_storage_config)
+    }
+}
+
 impl StorageConfig {
     pub fn backend(&self) -> StorageBackend {
         match self {
@@ -254,6 +299,7 @@ impl StorageConfig {
             Self::Ram(_) => StorageBackend::Ram,
             Self::S3(_) => StorageBackend::S3,
             Self::Google(_) => StorageBackend::Google,
+            Self::WebHdfs(_) => StorageBackend::WebHdfs,
         }
     }
 }
@@ -268,6 +314,22 @@ pub struct AzureStorageConfig {
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub access_key: Option<String>,
+    /// Shared access signature (SAS) token used to authenticate the requests instead of the
+    /// account access key.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sas_token: Option<String>,
+    /// URL of the container, for instance `https://<account>.blob.core.windows.net/<container>`.
+    /// When set, the requests are sent to the blob service endpoint of the URL instead of the
+    /// public endpoint of the account.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub container_url: Option<String>,
+    /// Maximum number of concurrent in-flight requests to Azure Blob Storage, shared by all the
+    /// operations of the node.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_requests: Option<NonZeroUsize>,
 }
 
 impl AzureStorageConfig {
@@ -275,11 +337,29 @@ impl AzureStorageConfig {
 
     pub const AZURE_STORAGE_ACCESS_KEY_ENV_VAR: &'static str = "QW_AZURE_STORAGE_ACCESS_KEY";
 
-    /// Redacts the access key.
+    pub const AZURE_STORAGE_SAS_TOKEN_ENV_VAR: &'static str = "QW_AZURE_STORAGE_SAS_TOKEN";
+
+    /// Redacts the access key and the SAS token.
     pub fn redact(&mut self) {
         if let Some(access_key) = self.access_key.as_mut() {
             *access_key = "***redacted***".to_string();
         }
+        if let Some(sas_token) = self.sas_token.as_mut() {
+            *sas_token = "***redacted***".to_string();
+        }
+    }
+
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.access_key.is_some() && self.sas_token.is_some() {
+            bail!("Azure storage config cannot define both an access key and a SAS token");
+        }
+        if let Some(container_url) = &self.container_url {
+            ensure!(
+                container_url.starts_with("https://") || container_url.starts_with("http://"),
+                "Azure container URL `{container_url}` must start with `https://` or `http://`"
+            );
+        }
+        Ok(())
     }
 
     /// Attempts to find the account name in the environment variable `QW_AZURE_STORAGE_ACCOUNT` or
@@ -297,6 +377,14 @@ impl AzureStorageConfig {
             .ok()
             .or_else(|| self.access_key.clone())
     }
+
+    /// Attempts to find the SAS token in the environment variable `QW_AZURE_STORAGE_SAS_TOKEN` or
+    /// the config.
+    pub fn resolve_sas_token(&self) -> Option<String> {
+        env::var(Self::AZURE_STORAGE_SAS_TOKEN_ENV_VAR)
+            .ok()
+            .or_else(|| self.sas_token.clone())
+    }
 }
 
 impl fmt::Debug for AzureStorageConfig {
@@ -307,6 +395,12 @@ impl fmt::Debug for AzureStorageConfig {
                 "access_key",
                 &self.access_key.as_ref().map(|_| "***redacted***"),
             )
+            .field(
+                "sas_token",
+                &self.sas_token.as_ref().map(|_| "***redacted***"),
+            )
+            .field("container_url", &self.container_url)
+            .field("max_concurrent_requests", &self.max_concurrent_requests)
             .finish()
     }
 }
@@ -325,32 +419,177 @@ pub struct S3StorageConfig {
     pub region: Option<String>,
     #[serde(default)]
     pub endpoint: Option<String>,
+    /// Forces path-style (`endpoint/bucket/key`) requests when set to `true`, and
+    /// virtual-hosted-style (`bucket.endpoint/key`) requests when set to `false`. When unset, the
+    /// addressing style is inferred from the flavor and the bucket name.
+    #[serde(alias = "force_path_style")]
     #[serde(default)]
-    pub force_path_style_access: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub force_path_style_access: Option<bool>,
     #[serde(alias = "disable_multi_object_delete_requests")]
     #[serde(default)]
     pub disable_multi_object_delete: bool,
     #[serde(default)]
     pub disable_multipart_upload: bool,
+    /// Sends unsigned requests, so that public buckets can be read without credentials.
+    #[serde(default)]
+    pub anonymous: bool,
+    /// Name of the profile of the AWS shared config and credentials files to source the
+    /// credentials from.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    /// Maximum number of concurrent in-flight requests to S3-compatible object stores, shared by
+    /// all the operations of the node.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_requests: Option<NonZeroUsize>,
+    /// Suffix appended to the user agent of the requests, e.g. to identify the node in S3 access
+    /// logs.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+    /// Headers added to every request, e.g. to tag requests for cost attribution.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub request_headers: BTreeMap<String, String>,
+    /// Retry policy applied to the requests failing with a transient error.
+    #[serde(default)]
+    pub retry_policy: S3RetryPolicyConfig,
+    /// Maximum gap between two byte ranges of an object read together for them to be fetched
+    /// with a single request.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range_merge_gap: Option<ByteSize>,
+    /// Server-side encryption applied to the objects written to the object store.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server_side_encryption: Option<S3ServerSideEncryptionConfig>,
 }
 
 impl S3StorageConfig {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        self.validate_credentials()?;
+        self.validate_request_tagging()?;
+        self.retry_policy.validate()?;
+        self.validate_server_side_encryption()?;
+
+        Ok(())
+    }
+
+    fn validate_request_tagging(&self) -> anyhow::Result<()> {
+        if let Some(user_agent) = &self.user_agent {
+            // Same rules as the application name of the AWS SDK.
+            let is_valid_char =
+                |char: char| char.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(char);
+            ensure!(
+                !user_agent.is_empty() && user_agent.chars().all(is_valid_char),
+                "S3 storage config user agent `{user_agent}` must be a non-empty string of \
+                 alphanumeric characters or symbols among `!#$%&'*+-.^_`|~`"
+            );
+        }
+        for (header_name, header_value) in &self.request_headers {
+            let parsed_header_name = http::HeaderName::from_bytes(header_name.as_bytes())
+                .with_context(|| format!("invalid S3 request header name `{header_name}`"))?;
+            http::HeaderValue::from_str(header_value).with_context(|| {
+                format!("invalid value `{header_value}` for S3 request header `{header_name}`")
+            })?;
+            let parsed_header_name = parsed_header_name.as_str();
+            ensure!(
+                !parsed_header_name.starts_with("x-amz-")
+                    && !["authorization", "host", "user-agent"].contains(&parsed_header_name),
+                "S3 request header `{header_name}` is reserved"
+            );
+        }
+        Ok(())
+    }
+
+    fn validate_server_side_encryption(&self) -> anyhow::Result<()> {
+        let Some(server_side_encryption) = &self.server_side_encryption else {
+            return Ok(());
+        };
+        if let Some(kms_key_id) = &server_side_encryption.kms_key_id {
+            ensure!(
+                server_side_encryption.algorithm == S3ServerSideEncryptionAlgorithm::AwsKms,
+                "S3 server-side encryption KMS key `{kms_key_id}` requires the `aws:kms` algorithm"
+            );
+            ensure!(
+                !kms_key_id.is_empty(),
+                "S3 server-side encryption KMS key must not be empty"
+            );
+        }
+        Ok(())
+    }
+
+    fn validate_credentials(&self) -> anyhow::Result<()> {
+        if self.anonymous
+            && (self.access_key_id.is_some()
+                || self.secret_access_key.is_some()
+                || self.profile.is_some())
+        {
+            bail!("S3 storage config cannot define credentials in anonymous mode");
+        }
+        match (&self.access_key_id, &self.secret_access_key) {
+            (Some(_), None) => {
+                bail!("S3 storage config defines `access_key_id` but not `secret_access_key`")
+            }
+            (None, Some(_)) => {
+                bail!("S3 storage config defines `secret_access_key` but not `access_key_id`")
+            }
+            (Some(_), Some(_)) if self.profile.is_some() => {
+                bail!("S3 storage config cannot define both `profile` and static credentials")
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the storage config applying to the URIs of an index: this config, with the
+    /// properties defined in the storage config of the index taking precedence.
+    pub fn with_index_storage_config(
+        &self,
+        index_storage_config: &IndexStorageConfig,
+    ) -> S3StorageConfig {
+        let mut s3_storage_config = self.clone();
+
+        if let Some(flavor) = index_storage_config.flavor {
+            s3_storage_config.flavor = Some(flavor);
+        }
+        if let Some(region) = &index_storage_config.region {
+            s3_storage_config.region = Some(region.clone());
+        }
+        if let Some(endpoint) = &index_storage_config.endpoint {
+            s3_storage_config.endpoint = Some(endpoint.clone());
+        }
+        if let Some(force_path_style_access) = index_storage_config.force_path_style_access {
+            s3_storage_config.force_path_style_access = Some(force_path_style_access);
+        }
+        // The credentials of the index replace the credentials of the node as a whole.
+        if index_storage_config.anonymous || index_storage_config.profile.is_some() {
+            s3_storage_config.access_key_id = None;
+            s3_storage_config.secret_access_key = None;
+            s3_storage_config.anonymous = index_storage_config.anonymous;
+            s3_storage_config.profile = index_storage_config.profile.clone();
+        }
+        s3_storage_config.apply_flavor();
+        s3_storage_config
+    }
+
     fn apply_flavor(&mut self) {
         match self.flavor {
             Some(StorageBackendFlavor::DigitalOcean) => {
-                self.force_path_style_access = true;
+                self.force_path_style_access.get_or_insert(true);
                 self.disable_multi_object_delete = true;
             }
             Some(StorageBackendFlavor::Garage) => {
                 self.region = Some("garage".to_string());
-                self.force_path_style_access = true;
+                self.force_path_style_access.get_or_insert(true);
             }
             Some(StorageBackendFlavor::Gcs) => {
                 self.disable_multi_object_delete = true;
                 self.disable_multipart_upload = true;
             }
             Some(StorageBackendFlavor::MinIO) => {
-                self.force_path_style_access = true;
+                self.force_path_style_access.get_or_insert(true);
             }
             _ => {}
         }
@@ -369,7 +608,10 @@ impl S3StorageConfig {
     }
 
     pub fn force_path_style_access(&self) -> Option<bool> {
-        Some(env::var("QW_S3_FORCE_PATH_STYLE_ACCESS").is_ok() || self.force_path_style_access)
+        if env::var("QW_S3_FORCE_PATH_STYLE_ACCESS").is_ok() {
+            return Some(true);
+        }
+        self.force_path_style_access
     }
 }
 
@@ -388,10 +630,181 @@ impl fmt::Debug for S3StorageConfig {
                 "disable_multi_object_delete",
                 &self.disable_multi_object_delete,
             )
+            .field("anonymous", &self.anonymous)
+            .field("profile", &self.profile)
+            .field("max_concurrent_requests", &self.max_concurrent_requests)
+            .field("user_agent", &self.user_agent)
+            .field("request_headers", &self.request_headers)
+            .field("retry_policy", &self.retry_policy)
+            .field("range_merge_gap", &self.range_merge_gap)
+            .field("server_side_encryption", &self.server_side_encryption)
             .finish()
     }
 }
 
+/// S3 storage config of an index, overriding the S3 storage config of the nodes for the URIs of
+/// the index. This allows a node to serve indexes stored in distinct S3-compatible object stores.
+/// The properties left unset are inherited from the S3 storage config of the node.
+///
+/// Static credentials cannot be defined here because index configs are exposed by the REST API:
+/// the credentials are sourced from a profile of the AWS shared config and credentials files of
+/// the nodes instead.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct IndexStorageConfig {
+    #[schema(value_type = Option<String>)]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flavor: Option<StorageBackendFlavor>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+    #[serde(alias = "force_path_style")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub force_path_style_access: Option<bool>,
+    /// Name of the profile of the AWS shared config and credentials files to source the
+    /// credentials from.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    /// Sends unsigned requests, so that public buckets can be read without credentials.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_false")]
+    pub anonymous: bool,
+}
+
+impl IndexStorageConfig {
+    pub fn validate(&self, index_uri: &Uri) -> anyhow::Result<()> {
+        ensure!(
+            index_uri.protocol() == Protocol::S3,
+            "index storage config requires an S3 index URI, got `{index_uri}`"
+        );
+        if let Some(profile) = &self.profile {
+            ensure!(
+                !profile.trim().is_empty(),
+                "index storage config profile must not be empty"
+            );
+            ensure!(
+                !self.anonymous,
+                "index storage config cannot define a profile in anonymous mode"
+            );
+        }
+        if let Some(endpoint) = &self.endpoint {
+            let endpoint_uri = Uri::from_str(endpoint)
+                .with_context(|| format!("invalid index storage config endpoint `{endpoint}`"))?;
+            ensure!(
+                endpoint_uri.protocol().is_http(),
+                "index storage config endpoint `{endpoint}` must be an HTTP(S) URL"
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Retry policy of the requests issued to S3-compatible object stores. Only transient errors,
+/// such as server errors, throttling, or connection resets, are retried.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct S3RetryPolicyConfig {
+    /// Maximum number of attempts of a request, including the first one.
+    #[serde(default = "S3RetryPolicyConfig::default_max_attempts")]
+    pub max_attempts: NonZeroUsize,
+    /// Delay before the first retry. The delay doubles after each attempt.
+    #[serde(
+        default = "S3RetryPolicyConfig::default_base_delay",
+        deserialize_with = "parse_human_duration",
+        serialize_with = "serialize_duration"
+    )]
+    pub base_delay: Duration,
+    /// Upper bound of the delay between two attempts.
+    #[serde(
+        default = "S3RetryPolicyConfig::default_max_delay",
+        deserialize_with = "parse_human_duration",
+        serialize_with = "serialize_duration"
+    )]
+    pub max_delay: Duration,
+    /// Randomizes the delays, so that requests failing at the same time do not retry in
+    /// lockstep.
+    #[serde(default = "S3RetryPolicyConfig::default_jitter")]
+    pub jitter: bool,
+}
+
+impl S3RetryPolicyConfig {
+    fn default_max_attempts() -> NonZeroUsize {
+        NonZeroUsize::new(3).unwrap()
+    }
+
+    fn default_base_delay() -> Duration {
+        Duration::from_millis(250)
+    }
+
+    fn default_max_delay() -> Duration {
+        Duration::from_secs(20)
+    }
+
+    fn default_jitter() -> bool {
+        true
+    }
+
+    fn validate(&self) -> anyhow::Result<()> {
+        ensure!(
+            self.base_delay <= self.max_delay,
+            "S3 retry policy base delay ({:?}) must not exceed max delay ({:?})",
+            self.base_delay,
+            self.max_delay
+        );
+        Ok(())
+    }
+}
+
+impl Default for S3RetryPolicyConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: Self::default_max_attempts(),
+            base_delay: Self::default_base_delay(),
+            max_delay: Self::default_max_delay(),
+            jitter: Self::default_jitter(),
+        }
+    }
+}
+
+/// Server-side encryption algorithm of the objects written to S3.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum S3ServerSideEncryptionAlgorithm {
+    /// Encryption with keys managed by S3 (SSE-S3).
+    #[serde(rename = "AES256")]
+    Aes256,
+    /// Encryption with keys managed by AWS KMS (SSE-KMS).
+    #[serde(rename = "aws:kms")]
+    AwsKms,
+}
+
+impl S3ServerSideEncryptionAlgorithm {
+    /// Returns the value of the `x-amz-server-side-encryption` header.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Aes256 => "AES256",
+            Self::AwsKms => "aws:kms",
+        }
+    }
+}
+
+/// Server-side encryption of the objects written to S3.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct S3ServerSideEncryptionConfig {
+    pub algorithm: S3ServerSideEncryptionAlgorithm,
+    /// ID or ARN of the KMS key encrypting the objects. Only valid with the `aws:kms` algorithm.
+    /// Defaults to the AWS managed key of S3.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kms_key_id: Option<String>,
+}
+
 #[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct FileStorageConfig;
@@ -403,9 +816,20 @@ pub struct RamStorageConfig;
 #[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct GoogleCloudStorageConfig {
+    /// Path to a service account JSON key file.
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub credential_path: Option<String>,
+    /// Service account to fetch tokens for from the metadata server, e.g. when relying on
+    /// workload identity. Defaults to the `default` service account of the instance.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_account: Option<String>,
+    /// Maximum number of concurrent in-flight requests to Google Cloud Storage, shared by all the
+    /// operations of the node.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_requests: Option<NonZeroUsize>,
 }
 
 impl GoogleCloudStorageConfig {
@@ -421,6 +845,51 @@ impl GoogleCloudStorageConfig {
     }
 }
 
+#[derive(Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WebHdfsStorageConfig {
+    /// User on behalf of whom the requests are sent, passed as the `user.name` query parameter.
+    /// Only honored by clusters relying on simple (pseudo) authentication.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_name: Option<String>,
+    /// Delegation token authenticating the requests, passed as the `delegation` query parameter.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delegation_token: Option<String>,
+}
+
+impl WebHdfsStorageConfig {
+    pub const WEBHDFS_DELEGATION_TOKEN_ENV_VAR: &'static str = "QW_WEBHDFS_DELEGATION_TOKEN";
+
+    /// Redacts the delegation token.
+    pub fn redact(&mut self) {
+        if let Some(delegation_token) = self.delegation_token.as_mut() {
+            *delegation_token = "***redacted***".to_string();
+        }
+    }
+
+    /// Attempts to find the delegation token in the environment variable
+    /// `QW_WEBHDFS_DELEGATION_TOKEN` or the config.
+    pub fn resolve_delegation_token(&self) -> Option<String> {
+        env::var(Self::WEBHDFS_DELEGATION_TOKEN_ENV_VAR)
+            .ok()
+            .or_else(|| self.delegation_token.clone())
+    }
+}
+
+impl fmt::Debug for WebHdfsStorageConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WebHdfsStorageConfig")
+            .field("user_name", &self.user_name)
+            .field(
+                "delegation_token",
+                &self.delegation_token.as_ref().map(|_| "***redacted***"),
+            )
+            .finish()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -436,6 +905,9 @@ mod tests {
                     account: test-account
                 s3:
                     endpoint: http://localhost:4566
+                webhdfs:
+                    user_name: quickwit
+                    delegation_token: test-token
             "#;
         let storage_configs: StorageConfigs = serde_yaml::from_str(storage_configs_yaml).unwrap();
 
@@ -450,6 +922,11 @@ mod tests {
                 ..Default::default()
             }
             .into(),
+            WebHdfsStorageConfig {
+                user_name: Some("quickwit".to_string()),
+                delegation_token: Some("test-token".to_string()),
+            }
+            .into(),
         ]);
         assert_eq!(storage_configs, expected_storage_configs);
     }
@@ -481,19 +958,33 @@ mod tests {
         storage_configs.apply_flavors();
 
         let do_storage_config = storage_configs[0].as_s3().unwrap();
-        assert!(do_storage_config.force_path_style_access);
+        assert_eq!(do_storage_config.force_path_style_access, Some(true));
         assert!(do_storage_config.disable_multi_object_delete);
 
         let garage_storage_config = storage_configs[1].as_s3().unwrap();
         assert_eq!(garage_storage_config.region, Some("garage".to_string()));
-        assert!(garage_storage_config.force_path_style_access);
+        assert_eq!(garage_storage_config.force_path_style_access, Some(true));
 
         let gcs_storage_config = storage_configs[2].as_s3().unwrap();
         assert!(gcs_storage_config.disable_multi_object_delete);
         assert!(gcs_storage_config.disable_multipart_upload);
 
         let minio_storage_config = storage_configs[3].as_s3().unwrap();
-        assert!(minio_storage_config.force_path_style_access);
+        assert_eq!(minio_storage_config.force_path_style_access, Some(true));
+    }
+
+    #[test]
+    fn test_storage_configs_apply_flavors_keeps_explicit_addressing_style() {
+        let mut storage_configs = StorageConfigs::new(vec![S3StorageConfig {
+            flavor: Some(StorageBackendFlavor::MinIO),
+            force_path_style_access: Some(false),
+            ..Default::default()
+        }
+        .into()]);
+        storage_configs.apply_flavors();
+
+        let minio_storage_config = storage_configs[0].as_s3().unwrap();
+        assert_eq!(minio_storage_config.force_path_style_access, Some(false));
     }
 
     #[test]
@@ -513,11 +1004,165 @@ mod tests {
         storage_configs.validate().unwrap_err();
     }
 
+    #[test]
+    fn test_storage_s3_config_validate() {
+        let s3_storage_config = S3StorageConfig {
+            access_key_id: Some("test-access-key-id".to_string()),
+            ..Default::default()
+        };
+        let error = s3_storage_config.validate().unwrap_err();
+        assert!(error.to_string().contains("not `secret_access_key`"));
+
+        let s3_storage_config = S3StorageConfig {
+            access_key_id: Some("test-access-key-id".to_string()),
+            secret_access_key: Some("test-secret-access-key".to_string()),
+            profile: Some("test-profile".to_string()),
+            ..Default::default()
+        };
+        s3_storage_config.validate().unwrap_err();
+
+        let s3_storage_config = S3StorageConfig {
+            anonymous: true,
+            ..Default::default()
+        };
+        s3_storage_config.validate().unwrap();
+
+        let s3_storage_config = S3StorageConfig {
+            anonymous: true,
+            profile: Some("test-profile".to_string()),
+            ..Default::default()
+        };
+        let error = s3_storage_config.validate().unwrap_err();
+        assert!(error.to_string().contains("anonymous mode"));
+
+        let s3_storage_config = S3StorageConfig {
+            user_agent: Some("quickwit-node-1".to_string()),
+            request_headers: BTreeMap::from([(
+                "x-quickwit-node-id".to_string(),
+                "node-1".to_string(),
+            )]),
+            ..Default::default()
+        };
+        s3_storage_config.validate().unwrap();
+
+        let s3_storage_config = S3StorageConfig {
+            user_agent: Some("quickwit node".to_string()),
+            ..Default::default()
+        };
+        let error = s3_storage_config.validate().unwrap_err();
+        assert!(error.to_string().contains("user agent"));
+
+        let s3_storage_config = S3StorageConfig {
+            request_headers: BTreeMap::from([(
+                "x-amz-meta-node-id".to_string(),
+                "node-1".to_string(),
+            )]),
+            ..Default::default()
+        };
+        let error = s3_storage_config.validate().unwrap_err();
+        assert!(error.to_string().contains("reserved"));
+
+        let s3_storage_config = S3StorageConfig {
+            request_headers: BTreeMap::from([("node id".to_string(), "node-1".to_string())]),
+            ..Default::default()
+        };
+        s3_storage_config.validate().unwrap_err();
+    }
+
+    #[test]
+    fn test_storage_s3_config_with_index_storage_config() {
+        let s3_storage_config = S3StorageConfig {
+            region: Some("us-east-1".to_string()),
+            access_key_id: Some("test-access-key-id".to_string()),
+            secret_access_key: Some("test-secret-access-key".to_string()),
+            user_agent: Some("quickwit-node-1".to_string()),
+            ..Default::default()
+        };
+        let index_storage_config = IndexStorageConfig {
+            endpoint: Some("http://minio:9000".to_string()),
+            ..Default::default()
+        };
+        let minio_storage_config =
+            s3_storage_config.with_index_storage_config(&index_storage_config);
+        assert_eq!(
+            minio_storage_config.endpoint.as_deref(),
+            Some("http://minio:9000")
+        );
+        // The properties left unset are inherited from the storage config of the node.
+        assert_eq!(minio_storage_config.region.as_deref(), Some("us-east-1"));
+        assert_eq!(
+            minio_storage_config.access_key_id.as_deref(),
+            Some("test-access-key-id")
+        );
+        assert_eq!(
+            minio_storage_config.user_agent.as_deref(),
+            Some("quickwit-node-1")
+        );
+
+        let index_storage_config = IndexStorageConfig {
+            flavor: Some(StorageBackendFlavor::Garage),
+            endpoint: Some("http://garage:3900".to_string()),
+            profile: Some("garage".to_string()),
+            ..Default::default()
+        };
+        let garage_storage_config =
+            s3_storage_config.with_index_storage_config(&index_storage_config);
+        assert_eq!(garage_storage_config.region.as_deref(), Some("garage"));
+        assert_eq!(garage_storage_config.force_path_style_access, Some(true));
+        assert_eq!(garage_storage_config.profile.as_deref(), Some("garage"));
+        // The credentials of the index replace the credentials of the node.
+        assert!(garage_storage_config.access_key_id.is_none());
+        assert!(garage_storage_config.secret_access_key.is_none());
+        garage_storage_config.validate().unwrap();
+    }
+
+    #[test]
+    fn test_index_storage_config_validate() {
+        let index_uri = Uri::for_test("s3://minio-bucket/indexes/index-1");
+        let index_storage_config_yaml = r#"
+            flavor: minio
+            endpoint: http://minio:9000
+            profile: minio
+        "#;
+        let index_storage_config: IndexStorageConfig =
+            serde_yaml::from_str(index_storage_config_yaml).unwrap();
+        index_storage_config.validate(&index_uri).unwrap();
+
+        let error = index_storage_config
+            .validate(&Uri::for_test("file:///indexes/index-1"))
+            .unwrap_err();
+        assert!(error.to_string().contains("S3 index URI"));
+
+        let index_storage_config = IndexStorageConfig {
+            profile: Some("minio".to_string()),
+            anonymous: true,
+            ..Default::default()
+        };
+        let error = index_storage_config.validate(&index_uri).unwrap_err();
+        assert!(error.to_string().contains("anonymous mode"));
+
+        let index_storage_config = IndexStorageConfig {
+            profile: Some(" ".to_string()),
+            ..Default::default()
+        };
+        index_storage_config.validate(&index_uri).unwrap_err();
+
+        let index_storage_config = IndexStorageConfig {
+            endpoint: Some("minio:9000".to_string()),
+            ..Default::default()
+        };
+        index_storage_config.validate(&index_uri).unwrap_err();
+
+        // Static credentials are rejected.
+        serde_yaml::from_str::<IndexStorageConfig>("secret_access_key: test").unwrap_err();
+    }
+
     #[test]
     fn test_storage_configs_redact() {
         let mut storage_configs = StorageConfigs(vec![
             AzureStorageConfig {
                 access_key: Some("test-azure-access-key".to_string()),
+                sas_token: Some("test-azure-sas-token".to_string()),
                 ..Default::default()
             }
             .into(),
@@ -538,6 +1183,15 @@ mod tests {
                 .unwrap(),
             "***redacted***"
         );
+        assert_eq!(
+            storage_configs
+                .find_azure()
+                .unwrap()
+                .sas_token
+                .as_ref()
+                .unwrap(),
+            "***redacted***"
+        );
         assert_eq!(
             storage_configs
                 .find_s3()
@@ -575,9 +1229,51 @@ mod tests {
             let expected_azure_config = AzureStorageConfig {
                 account_name: Some("test-account".to_string()),
                 access_key: Some("test-access-key".to_string()),
+                ..Default::default()
             };
             assert_eq!(azure_storage_config, expected_azure_config);
         }
+        {
+            let azure_storage_config_yaml = r#"
+                sas_token: sv=2022-11-02&ss=b&srt=co&sp=rwdl&se=2024-01-01T00:00:00Z&sig=abc
+                container_url: https://test-account.blob.core.windows.net/test-container
+            "#;
+            let azure_storage_config: AzureStorageConfig =
+                serde_yaml::from_str(azure_storage_config_yaml).unwrap();
+
+            let expected_azure_config = AzureStorageConfig {
+                sas_token: Some(
+                    "sv=2022-11-02&ss=b&srt=co&sp=rwdl&se=2024-01-01T00:00:00Z&sig=abc".to_string(),
+                ),
+                container_url: Some(
+                    "https://test-account.blob.core.windows.net/test-container".to_string(),
+                ),
+                ..Default::default()
+            };
+            assert_eq!(azure_storage_config, expected_azure_config);
+            azure_storage_config.validate().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_storage_azure_config_validate() {
+        let azure_storage_config = AzureStorageConfig {
+            access_key: Some("test-access-key".to_string()),
+            sas_token: Some("sv=2022-11-02&sig=abc".to_string()),
+            ..Default::default()
+        };
+        let error = azure_storage_config.validate().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Azure storage config cannot define both an access key and a SAS token"
+        );
+
+        let azure_storage_config = AzureStorageConfig {
+            sas_token: Some("sv=2022-11-02&sig=abc".to_string()),
+            container_url: Some("test-account.blob.core.windows.net/test-container".to_string()),
+            ..Default::default()
+        };
+        azure_storage_config.validate().unwrap_err();
     }
 
     #[test]
@@ -591,6 +1287,23 @@ mod tests {
 
             let expected_google_cloud_storage_config = GoogleCloudStorageConfig {
                 credential_path: Some("/path/to/credential.json".to_string()),
+                ..Default::default()
+            };
+            assert_eq!(
+                google_cloud_storage_config,
+                expected_google_cloud_storage_config
+            );
+        }
+        {
+            let google_cloud_storage_config_yaml = r#"
+                service_account: quickwit@my-project.iam.gserviceaccount.com
+            "#;
+            let google_cloud_storage_config: GoogleCloudStorageConfig =
+                serde_yaml::from_str(google_cloud_storage_config_yaml).unwrap();
+
+            let expected_google_cloud_storage_config = GoogleCloudStorageConfig {
+                service_account: Some("quickwit@my-project.iam.gserviceaccount.com".to_string()),
+                ..Default::default()
             };
             assert_eq!(
                 google_cloud_storage_config,
@@ -621,6 +1334,7 @@ mod tests {
                 force_path_style_access: true
                 disable_multi_object_delete_requests: true
                 disable_multipart_upload: true
+                anonymous: true
             "#;
             let s3_storage_config: S3StorageConfig =
                 serde_yaml::from_str(s3_storage_config_yaml).unwrap();
@@ -628,13 +1342,127 @@ mod tests {
             let expected_s3_config = S3StorageConfig {
                 region: Some("us-east-1".to_string()),
                 endpoint: Some("http://localhost:4566".to_string()),
-                force_path_style_access: true,
+                force_path_style_access: Some(true),
                 disable_multi_object_delete: true,
                 disable_multipart_upload: true,
+                anonymous: true,
                 ..Default::default()
             };
             assert_eq!(s3_storage_config, expected_s3_config);
         }
+        {
+            let s3_storage_config_yaml = r#"
+                endpoint: http://minio:9000
+                force_path_style: false
+            "#;
+            let s3_storage_config: S3StorageConfig =
+                serde_yaml::from_str(s3_storage_config_yaml).unwrap();
+            assert_eq!(s3_storage_config.force_path_style_access, Some(false));
+        }
+        {
+            let s3_storage_config_yaml = r#"
+                retry_policy:
+                  max_attempts: 10
+                  base_delay: 500ms
+                  jitter: false
+            "#;
+            let s3_storage_config: S3StorageConfig =
+                serde_yaml::from_str(s3_storage_config_yaml).unwrap();
+
+            let expected_retry_policy = S3RetryPolicyConfig {
+                max_attempts: NonZeroUsize::new(10).unwrap(),
+                base_delay: Duration::from_millis(500),
+                max_delay: Duration::from_secs(20),
+                jitter: false,
+            };
+            assert_eq!(s3_storage_config.retry_policy, expected_retry_policy);
+            s3_storage_config.validate().unwrap();
+        }
+        {
+            let s3_storage_config_yaml = r#"
+                retry_policy:
+                  max_attempts: 0
+            "#;
+            serde_yaml::from_str::<S3StorageConfig>(s3_storage_config_yaml).unwrap_err();
+        }
+        {
+            let s3_storage_config = S3StorageConfig {
+                retry_policy: S3RetryPolicyConfig {
+                    base_delay: Duration::from_secs(30),
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            let error = s3_storage_config.validate().unwrap_err();
+            assert!(error.to_string().contains("must not exceed max delay"));
+        }
+        {
+            let s3_storage_config_yaml = r#"
+                range_merge_gap: 1MiB
+            "#;
+            let s3_storage_config: S3StorageConfig =
+                serde_yaml::from_str(s3_storage_config_yaml).unwrap();
+            assert_eq!(s3_storage_config.range_merge_gap, Some(ByteSize::mib(1)));
+        }
+    }
+
+    #[test]
+    fn test_storage_s3_config_server_side_encryption_serde() {
+        {
+            let s3_storage_config_yaml = r#"
+                server_side_encryption:
+                  algorithm: aws:kms
+                  kms_key_id: arn:aws:kms:us-east-1:123456789012:key/my-key
+            "#;
+            let s3_storage_config: S3StorageConfig =
+                serde_yaml::from_str(s3_storage_config_yaml).unwrap();
+            s3_storage_config.validate().unwrap();
+
+            let expected_server_side_encryption = S3ServerSideEncryptionConfig {
+                algorithm: S3ServerSideEncryptionAlgorithm::AwsKms,
+                kms_key_id: Some("arn:aws:kms:us-east-1:123456789012:key/my-key".to_string()),
+            };
+            assert_eq!(
+                s3_storage_config.server_side_encryption,
+                Some(expected_server_side_encryption)
+            );
+        }
+        {
+            let s3_storage_config_yaml = r#"
+                server_side_encryption:
+                  algorithm: AES256
+            "#;
+            let s3_storage_config: S3StorageConfig =
+                serde_yaml::from_str(s3_storage_config_yaml).unwrap();
+            s3_storage_config.validate().unwrap();
+
+            let server_side_encryption = s3_storage_config.server_side_encryption.unwrap();
+            assert_eq!(
+                server_side_encryption.algorithm,
+                S3ServerSideEncryptionAlgorithm::Aes256
+            );
+            assert!(server_side_encryption.kms_key_id.is_none());
+        }
+        {
+            let s3_storage_config_yaml = r#"
+                server_side_encryption:
+                  algorithm: AES256
+                  kms_key_id: my-key
+            "#;
+            let s3_storage_config: S3StorageConfig =
+                serde_yaml::from_str(s3_storage_config_yaml).unwrap();
+            let error = s3_storage_config.validate().unwrap_err();
+            assert!(error
+                .to_string()
+                .contains("requires the `aws:kms` algorithm"));
+        }
+        {
+            let s3_storage_config_yaml = r#"
+                server_side_encryption:
+                  algorithm: aws:unknown
+            "#;
+            serde_yaml::from_str::<S3StorageConfig>(s3_storage_config_yaml).unwrap_err();
+        }
     }
 
     #[test]
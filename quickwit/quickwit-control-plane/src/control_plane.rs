@@ -17,8 +17,9 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
-use std::collections::BTreeSet;
-use std::time::Duration;
+use std::collections::{BTreeSet, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use anyhow::Context;
 use async_trait::async_trait;
@@ -28,24 +29,29 @@ use quickwit_actors::{
     WeakMailbox,
 };
 use quickwit_common::pubsub::EventSubscriber;
-use quickwit_config::SourceConfig;
+use quickwit_common::uri::Uri;
+use quickwit_config::{IndexConfig, IndexTemplate, SourceConfig};
 use quickwit_ingest::{IngesterPool, LocalShardsUpdate};
-use quickwit_metastore::IndexMetadata;
+use quickwit_metastore::{
+    AddSourceRequestExt, CreateIndexRequestExt, IndexMetadata, IndexMetadataResponseExt,
+    ListIndexTemplatesResponseExt,
+};
 use quickwit_proto::control_plane::{
     ControlPlaneError, ControlPlaneResult, GetDebugStateRequest, GetDebugStateResponse,
-    GetOrCreateOpenShardsRequest, GetOrCreateOpenShardsResponse, PhysicalIndexingPlanEntry,
-    ShardTableEntry,
+    GetOrCreateOpenShardsRequest, GetOrCreateOpenShardsResponse, PauseSourceRequest,
+    PauseSourceResponse, PhysicalIndexingPlanEntry, ShardTableEntry,
 };
 use quickwit_proto::indexing::ShardPositionsUpdate;
 use quickwit_proto::metastore::{
     serde_utils as metastore_serde_utils, AddSourceRequest, CreateIndexRequest,
     CreateIndexResponse, DeleteIndexRequest, DeleteShardsRequest, DeleteShardsSubrequest,
-    DeleteSourceRequest, EmptyResponse, MetastoreError, MetastoreService, MetastoreServiceClient,
-    ToggleSourceRequest,
+    DeleteSourceRequest, EmptyResponse, IndexMetadataRequest, ListIndexTemplatesRequest,
+    MetastoreError, MetastoreService, MetastoreServiceClient, RenameIndexRequest,
+    RenameIndexResponse, ToggleSourceRequest,
 };
-use quickwit_proto::types::{IndexUid, NodeId, ShardId, SourceUid};
+use quickwit_proto::types::{IndexUid, NodeId, ShardId, SourceId, SourceUid};
 use serde::Serialize;
-use tracing::error;
+use tracing::{error, info, warn};
 
 use crate::indexing_scheduler::{IndexingScheduler, IndexingSchedulerState};
 use crate::ingest::IngestController;
@@ -59,6 +65,10 @@ pub(crate) const CONTROL_PLAN_LOOP_INTERVAL: Duration = if cfg!(any(test, featur
     Duration::from_secs(3)
 };
 
+/// Duration during which the index templates listed from the metastore are reused to create
+/// indexes, so that ingesting into missing indexes does not list them on every request.
+const INDEX_TEMPLATES_CACHE_TTL: Duration = Duration::from_secs(30);
+
 #[derive(Debug)]
 struct ControlPlanLoop;
 
@@ -66,6 +76,11 @@ struct ControlPlanLoop;
 pub struct ControlPlane {
     metastore: MetastoreServiceClient,
     model: ControlPlaneModel,
+    // Root URI of the indexes created automatically from an index template that does not specify
+    // one.
+    default_index_root_uri: Uri,
+    // Index templates last listed from the metastore, along with the time they were listed at.
+    index_templates_cache_opt: Option<(Instant, Arc<Vec<IndexTemplate>>)>,
     // The control plane state is split into to independent functions, that we naturally isolated
     // code wise and state wise.
     //
@@ -85,6 +100,7 @@ impl ControlPlane {
         indexer_pool: IndexerPool,
         ingester_pool: IngesterPool,
         metastore: MetastoreServiceClient,
+        default_index_root_uri: Uri,
         replication_factor: usize,
     ) -> (Mailbox<Self>, ActorHandle<Supervisor<Self>>) {
         universe.spawn_builder().supervise_fn(move || {
@@ -98,6 +114,8 @@ impl ControlPlane {
             ControlPlane {
                 model: Default::default(),
                 metastore: metastore.clone(),
+                default_index_root_uri: default_index_root_uri.clone(),
+                index_templates_cache_opt: None,
                 indexing_scheduler,
                 ingest_controller,
             }
@@ -178,6 +196,163 @@ impl ControlPlane {
         Ok(())
     }
 
+    /// Returns the index templates, listed from the metastore at most once every
+    /// `INDEX_TEMPLATES_CACHE_TTL`.
+    async fn index_templates(&mut self) -> ControlPlaneResult<Arc<Vec<IndexTemplate>>> {
+        if let Some((cached_at, index_templates)) = &self.index_templates_cache_opt {
+            if cached_at.elapsed() < INDEX_TEMPLATES_CACHE_TTL {
+                return Ok(index_templates.clone());
+            }
+        }
+        let index_templates = Arc::new(
+            self.metastore
+                .list_index_templates(ListIndexTemplatesRequest {})
+                .await?
+                .deserialize_index_templates()?,
+        );
+        self.index_templates_cache_opt = Some((Instant::now(), index_templates.clone()));
+        Ok(index_templates)
+    }
+
+    /// Creates the indexes targeted by a [`GetOrCreateOpenShardsRequest`] that do not exist yet
+    /// but whose IDs match an index template. When several templates match an index ID, the
+    /// template with the highest priority is applied.
+    async fn create_indexes_from_templates(
+        &mut self,
+        get_open_shards_request: &GetOrCreateOpenShardsRequest,
+    ) -> ControlPlaneResult<()> {
+        let missing_index_ids: BTreeSet<&str> = get_open_shards_request
+            .subrequests
+            .iter()
+            .map(|subrequest| subrequest.index_id.as_str())
+            .filter(|index_id| self.model.index_uid(index_id).is_none())
+            .collect();
+
+        if missing_index_ids.is_empty() {
+            return Ok(());
+        }
+        let index_templates = self.index_templates().await?;
+
+        for index_id in missing_index_ids {
+            let Some(index_template) = index_templates
+                .iter()
+                .filter(|index_template| index_template.matches_index_id(index_id))
+                .max_by_key(|index_template| index_template.priority)
+            else {
+                continue;
+            };
+            let index_config = match index_template
+                .apply_template(index_id.to_string(), &self.default_index_root_uri)
+            {
+                Ok(index_config) => index_config,
+                Err(error) => {
+                    warn!(
+                        index_id,
+                        template_id=%index_template.template_id,
+                        error=?error,
+                        "failed to apply index template"
+                    );
+                    continue;
+                }
+            };
+            self.create_index_from_template(index_config).await?;
+            info!(
+                index_id,
+                template_id=%index_template.template_id,
+                "created index from index template"
+            );
+        }
+        Ok(())
+    }
+
+    /// Creates an index from the index config produced by an index template, along with the same
+    /// default sources as the index API.
+    ///
+    /// The operation is idempotent: an index that already exists in the metastore is loaded into
+    /// the model, and only its missing default sources are added. If adding a source fails, the
+    /// index created by this call is deleted so that it is not left without its sources.
+    async fn create_index_from_template(
+        &mut self,
+        index_config: IndexConfig,
+    ) -> ControlPlaneResult<()> {
+        let index_id = index_config.index_id.clone();
+        let create_index_request = CreateIndexRequest::try_from_index_config(index_config.clone())?;
+
+        let (mut index_metadata, is_created) =
+            match self.metastore.create_index(create_index_request).await {
+                Ok(create_index_response) => {
+                    let index_uid: IndexUid = create_index_response.index_uid.into();
+                    let index_metadata = IndexMetadata::new_with_index_uid(index_uid, index_config);
+                    (index_metadata, true)
+                }
+                Err(MetastoreError::AlreadyExists(_)) => {
+                    let index_metadata_request = IndexMetadataRequest::for_index_id(index_id);
+                    let index_metadata = self
+                        .metastore
+                        .index_metadata(index_metadata_request)
+                        .await?
+                        .deserialize_index_metadata()?;
+                    (index_metadata, false)
+                }
+                Err(metastore_error) => return Err(metastore_error.into()),
+            };
+        let index_uid = index_metadata.index_uid.clone();
+        // The sources of an existing index are added to the model one by one, so that the shard
+        // table tracks them.
+        let existing_source_configs: Vec<SourceConfig> = index_metadata
+            .sources
+            .drain()
+            .map(|(_, source_config)| source_config)
+            .collect();
+        let existing_source_ids: HashSet<SourceId> = existing_source_configs
+            .iter()
+            .map(|source_config| source_config.source_id.clone())
+            .collect();
+        self.model.add_index(index_metadata);
+
+        for source_config in existing_source_configs {
+            self.model.add_source(&index_uid, source_config)?;
+        }
+        for source_config in [
+            SourceConfig::ingest_api_default(),
+            SourceConfig::ingest_v2_default(),
+            SourceConfig::cli_ingest_source(),
+        ] {
+            if existing_source_ids.contains(&source_config.source_id) {
+                continue;
+            }
+            let add_source_request =
+                AddSourceRequest::try_from_source_config(index_uid.clone(), source_config.clone())?;
+
+            match self.metastore.add_source(add_source_request).await {
+                Ok(_) | Err(MetastoreError::AlreadyExists(_)) => {}
+                Err(metastore_error) => {
+                    if is_created {
+                        self.rollback_index_creation(&index_uid).await;
+                    }
+                    return Err(metastore_error.into());
+                }
+            }
+            self.model.add_source(&index_uid, source_config)?;
+        }
+        Ok(())
+    }
+
+    /// Deletes an index whose creation from an index template could not be completed.
+    async fn rollback_index_creation(&mut self, index_uid: &IndexUid) {
+        let delete_index_request = DeleteIndexRequest {
+            index_uid: index_uid.to_string(),
+        };
+        if let Err(metastore_error) = self.metastore.delete_index(delete_index_request).await {
+            error!(
+                index_uid=%index_uid,
+                error=?metastore_error,
+                "failed to delete index partially created from index template"
+            );
+        }
+        self.model.delete_index(index_uid);
+    }
+
     fn debug_state(&self) -> GetDebugStateResponse {
         let shard_table = self
             .model
@@ -390,6 +565,43 @@ impl Handler<DeleteIndexRequest> for ControlPlane {
     }
 }
 
+// This handler is a metastore call proxied through the control plane: we must first forward the
+// request to the metastore, and then act on the event.
+#[async_trait]
+impl Handler<RenameIndexRequest> for ControlPlane {
+    type Reply = ControlPlaneResult<RenameIndexResponse>;
+
+    async fn handle(
+        &mut self,
+        request: RenameIndexRequest,
+        _ctx: &ActorContext<Self>,
+    ) -> Result<Self::Reply, ActorExitStatus> {
+        let index_uid: IndexUid = request.index_uid.clone().into();
+
+        let response = match self.metastore.rename_index(request).await {
+            Ok(response) => response,
+            Err(metastore_error) => return convert_metastore_error(metastore_error),
+        };
+        let new_index_uid: IndexUid = response.index_uid.clone().into();
+        let index_metadata_request = IndexMetadataRequest::for_index_uid(new_index_uid);
+        let index_metadata = self
+            .metastore
+            .index_metadata(index_metadata_request)
+            .await
+            .and_then(|response| response.deserialize_index_metadata())
+            .context("failed to fetch metadata of renamed index")?;
+
+        // Indexes with shards cannot be renamed, so the ingesters do not need to be resynced.
+        self.model.delete_index(&index_uid);
+        self.model.add_index(index_metadata);
+
+        self.indexing_scheduler
+            .schedule_indexing_plan_if_needed(&self.model);
+
+        Ok(Ok(response))
+    }
+}
+
 // This handler is a metastore call proxied through the control plane: we must first forward the
 // request to the metastore, and then act on the event.
 #[async_trait]
@@ -457,6 +669,39 @@ impl Handler<ToggleSourceRequest> for ControlPlane {
     }
 }
 
+// The pause state of the sources is owned by the control plane, so this handler does not hit the
+// metastore. Instead, it waits for the indexers to pause or resume the pipelines of the source.
+#[async_trait]
+impl Handler<PauseSourceRequest> for ControlPlane {
+    type Reply = ControlPlaneResult<PauseSourceResponse>;
+
+    async fn handle(
+        &mut self,
+        request: PauseSourceRequest,
+        ctx: &ActorContext<Self>,
+    ) -> Result<Self::Reply, ActorExitStatus> {
+        let source_uid = SourceUid {
+            index_uid: request.index_uid.into(),
+            source_id: request.source_id,
+        };
+        if let Err(error) = self.model.pause_source(&source_uid, request.paused) {
+            return Ok(Err(error));
+        }
+        info!(
+            index_uid=%source_uid.index_uid,
+            source_id=%source_uid.source_id,
+            paused=request.paused,
+            "pause source"
+        );
+        let failures_future = self
+            .indexing_scheduler
+            .schedule_indexing_plan_for_paused_source(&self.model, &source_uid);
+        let failures = ctx.protect_future(failures_future).await;
+
+        Ok(Ok(PauseSourceResponse { failures }))
+    }
+}
+
 // This handler is a metastore call proxied through the control plane: we must first forward the
 // request to the metastore, and then act on the event.
 #[async_trait]
@@ -516,6 +761,15 @@ impl Handler<GetOrCreateOpenShardsRequest> for ControlPlane {
         request: GetOrCreateOpenShardsRequest,
         ctx: &ActorContext<Self>,
     ) -> Result<Self::Reply, ActorExitStatus> {
+        match self.create_indexes_from_templates(&request).await {
+            Ok(()) => {}
+            Err(ControlPlaneError::Metastore(metastore_error)) => {
+                return convert_metastore_error(metastore_error);
+            }
+            Err(control_plane_error) => {
+                return Ok(Err(control_plane_error));
+            }
+        }
         let response = match self
             .ingest_controller
             .get_or_create_open_shards(request, &mut self.model, ctx.progress())
@@ -608,18 +862,22 @@ impl EventSubscriber<ShardPositionsUpdate> for ControlPlaneEventSubscriber {
 mod tests {
     use mockall::Sequence;
     use quickwit_actors::{AskError, Observe, SupervisorMetrics};
-    use quickwit_config::{IndexConfig, SourceParams, INGEST_V2_SOURCE_ID};
+    use quickwit_config::{IndexConfig, IndexTemplate, SourceParams, INGEST_V2_SOURCE_ID};
     use quickwit_indexing::IndexingService;
     use quickwit_metastore::{
-        CreateIndexRequestExt, IndexMetadata, ListIndexesMetadataResponseExt,
+        AddSourceRequestExt, CreateIndexRequestExt, IndexMetadata, ListIndexesMetadataResponseExt,
     };
     use quickwit_proto::control_plane::GetOrCreateOpenShardsSubrequest;
-    use quickwit_proto::indexing::{ApplyIndexingPlanRequest, CpuCapacity, IndexingServiceClient};
+    use quickwit_proto::indexing::{
+        ApplyIndexingPlanRequest, ApplyIndexingPlanResponse, CpuCapacity, IndexingPipelineFailure,
+        IndexingServiceClient,
+    };
     use quickwit_proto::ingest::ingester::{IngesterServiceClient, RetainShardsResponse};
     use quickwit_proto::ingest::{Shard, ShardState};
     use quickwit_proto::metastore::{
-        DeleteShardsResponse, EntityKind, ListIndexesMetadataRequest, ListIndexesMetadataResponse,
-        ListShardsRequest, ListShardsResponse, ListShardsSubresponse, MetastoreError, SourceType,
+        DeleteShardsResponse, EntityKind, IndexMetadataResponse, ListIndexTemplatesResponse,
+        ListIndexesMetadataRequest, ListIndexesMetadataResponse, ListShardsRequest,
+        ListShardsResponse, ListShardsSubresponse, MetastoreError, SourceType,
     };
     use quickwit_proto::types::Position;
 
@@ -664,6 +922,7 @@ mod tests {
             indexer_pool,
             ingester_pool,
             MetastoreServiceClient::from(mock_metastore),
+            Uri::for_test("ram:///indexes"),
             replication_factor,
         );
         let index_config = IndexConfig::for_test("test-index", "ram:///test-index");
@@ -709,6 +968,7 @@ mod tests {
             indexer_pool,
             ingester_pool,
             MetastoreServiceClient::from(mock_metastore),
+            Uri::for_test("ram:///indexes"),
             replication_factor,
         );
         let delete_index_request = DeleteIndexRequest {
@@ -763,6 +1023,7 @@ mod tests {
             indexer_pool,
             ingester_pool,
             MetastoreServiceClient::from(mock_metastore),
+            Uri::for_test("ram:///indexes"),
             replication_factor,
         );
 
@@ -830,6 +1091,7 @@ mod tests {
             indexer_pool,
             ingester_pool,
             MetastoreServiceClient::from(mock_metastore),
+            Uri::for_test("ram:///indexes"),
             replication_factor,
         );
 
@@ -888,6 +1150,7 @@ mod tests {
             indexer_pool,
             ingester_pool,
             MetastoreServiceClient::from(mock_metastore),
+            Uri::for_test("ram:///indexes"),
             replication_factor,
         );
         let delete_source_request = DeleteSourceRequest {
@@ -957,6 +1220,7 @@ mod tests {
             indexer_pool,
             ingester_pool,
             MetastoreServiceClient::from(mock_metastore),
+            Uri::for_test("ram:///indexes"),
             replication_factor,
         );
         let get_open_shards_request = GetOrCreateOpenShardsRequest {
@@ -984,6 +1248,356 @@ mod tests {
         universe.assert_quit().await;
     }
 
+    #[tokio::test]
+    async fn test_control_plane_get_or_create_open_shards_creates_index_from_template() {
+        let universe = Universe::with_accelerated_time();
+
+        let cluster_id = "test-cluster".to_string();
+        let self_node_id: NodeId = "test-node".into();
+        let indexer_pool = IndexerPool::default();
+        let ingester_pool = IngesterPool::default();
+
+        let mut mock_metastore = MetastoreServiceClient::mock();
+        mock_metastore
+            .expect_list_indexes_metadata()
+            .returning(|_| {
+                Ok(ListIndexesMetadataResponse::try_from_indexes_metadata(Vec::new()).unwrap())
+            });
+        mock_metastore.expect_list_shards().returning(|_| {
+            Ok(ListShardsResponse {
+                subresponses: Vec::new(),
+            })
+        });
+        mock_metastore
+            .expect_list_index_templates()
+            .return_once(|_| {
+                let mut index_template_bar =
+                    IndexTemplate::for_test("test-template-bar", &["logs-foo*"], 1);
+                index_template_bar.index_root_uri = Some(Uri::for_test("ram:///logs"));
+
+                let index_templates = [
+                    IndexTemplate::for_test("test-template-foo", &["logs-*"], 0),
+                    index_template_bar,
+                    IndexTemplate::for_test("test-template-baz", &["traces-*"], 0),
+                ];
+                Ok(ListIndexTemplatesResponse::try_from_index_templates(&index_templates).unwrap())
+            });
+        mock_metastore
+            .expect_create_index()
+            .times(1)
+            .returning(|request| {
+                let index_config = request.deserialize_index_config().unwrap();
+                assert_eq!(index_config.index_id, "logs-foo");
+                assert_eq!(index_config.index_uri, "ram:///logs/logs-foo");
+
+                let expected_doc_mapping =
+                    IndexTemplate::for_test("test-template-bar", &["logs-foo*"], 1).doc_mapping;
+                assert_eq!(index_config.doc_mapping, expected_doc_mapping);
+                Ok(CreateIndexResponse {
+                    index_uid: "logs-foo:0".to_string(),
+                })
+            });
+        mock_metastore
+            .expect_add_source()
+            .times(3)
+            .returning(|request| {
+                assert_eq!(request.index_uid, "logs-foo:0");
+                Ok(EmptyResponse {})
+            });
+        let replication_factor = 1;
+
+        let (control_plane_mailbox, _control_plane_handle) = ControlPlane::spawn(
+            &universe,
+            cluster_id,
+            self_node_id,
+            indexer_pool,
+            ingester_pool,
+            MetastoreServiceClient::from(mock_metastore),
+            Uri::for_test("ram:///indexes"),
+            replication_factor,
+        );
+        let get_open_shards_request = GetOrCreateOpenShardsRequest {
+            subrequests: vec![GetOrCreateOpenShardsSubrequest {
+                subrequest_id: 0,
+                index_id: "logs-foo".to_string(),
+                source_id: INGEST_V2_SOURCE_ID.to_string(),
+            }],
+            closed_shards: Vec::new(),
+            unavailable_leaders: Vec::new(),
+        };
+        // The index is created from the template with the highest priority, but there is no
+        // ingester to host its shards.
+        let error = control_plane_mailbox
+            .ask_for_res(get_open_shards_request)
+            .await
+            .unwrap_err();
+        let AskError::ErrorReply(ControlPlaneError::Unavailable(message)) = error else {
+            panic!("expected `Unavailable` error, got `{error:?}`");
+        };
+        assert_eq!(message, "no ingester available");
+
+        universe.assert_quit().await;
+    }
+
+    #[tokio::test]
+    async fn test_control_plane_create_index_from_template_is_idempotent() {
+        let universe = Universe::with_accelerated_time();
+
+        let cluster_id = "test-cluster".to_string();
+        let self_node_id: NodeId = "test-node".into();
+        let indexer_pool = IndexerPool::default();
+        let ingester_pool = IngesterPool::default();
+
+        let mut mock_metastore = MetastoreServiceClient::mock();
+        mock_metastore
+            .expect_list_indexes_metadata()
+            .returning(|_| {
+                Ok(ListIndexesMetadataResponse::try_from_indexes_metadata(Vec::new()).unwrap())
+            });
+        mock_metastore.expect_list_shards().returning(|_| {
+            Ok(ListShardsResponse {
+                subresponses: Vec::new(),
+            })
+        });
+        mock_metastore
+            .expect_list_index_templates()
+            .return_once(|_| {
+                let index_templates = [IndexTemplate::for_test("test-template", &["logs-*"], 0)];
+                Ok(ListIndexTemplatesResponse::try_from_index_templates(&index_templates).unwrap())
+            });
+        // The index was created in the meantime, along with one of its default sources.
+        mock_metastore
+            .expect_create_index()
+            .times(1)
+            .returning(|_request| {
+                Err(MetastoreError::AlreadyExists(EntityKind::Index {
+                    index_id: "logs-foo".to_string(),
+                }))
+            });
+        mock_metastore
+            .expect_index_metadata()
+            .times(1)
+            .returning(|request| {
+                assert_eq!(request.index_id.as_deref(), Some("logs-foo"));
+                let mut index_metadata = IndexMetadata::for_test("logs-foo", "ram:///logs-foo");
+                index_metadata
+                    .add_source(SourceConfig::ingest_api_default())
+                    .unwrap();
+                Ok(IndexMetadataResponse::try_from_index_metadata(&index_metadata).unwrap())
+            });
+        mock_metastore
+            .expect_add_source()
+            .times(2)
+            .returning(|request| {
+                let source_config = request.deserialize_source_config().unwrap();
+                assert_ne!(
+                    source_config.source_id,
+                    SourceConfig::ingest_api_default().source_id
+                );
+                Ok(EmptyResponse {})
+            });
+        let replication_factor = 1;
+
+        let (control_plane_mailbox, _control_plane_handle) = ControlPlane::spawn(
+            &universe,
+            cluster_id,
+            self_node_id,
+            indexer_pool,
+            ingester_pool,
+            MetastoreServiceClient::from(mock_metastore),
+            Uri::for_test("ram:///indexes"),
+            replication_factor,
+        );
+        let get_open_shards_request = GetOrCreateOpenShardsRequest {
+            subrequests: vec![GetOrCreateOpenShardsSubrequest {
+                subrequest_id: 0,
+                index_id: "logs-foo".to_string(),
+                source_id: INGEST_V2_SOURCE_ID.to_string(),
+            }],
+            closed_shards: Vec::new(),
+            unavailable_leaders: Vec::new(),
+        };
+        // There is no ingester to host the shards of the index.
+        let error = control_plane_mailbox
+            .ask_for_res(get_open_shards_request)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            AskError::ErrorReply(ControlPlaneError::Unavailable(_))
+        ));
+        universe.assert_quit().await;
+    }
+
+    #[tokio::test]
+    async fn test_control_plane_create_index_from_template_rolls_back_on_error() {
+        let universe = Universe::with_accelerated_time();
+
+        let cluster_id = "test-cluster".to_string();
+        let self_node_id: NodeId = "test-node".into();
+        let indexer_pool = IndexerPool::default();
+        let ingester_pool = IngesterPool::default();
+
+        let mut mock_metastore = MetastoreServiceClient::mock();
+        mock_metastore
+            .expect_list_indexes_metadata()
+            .returning(|_| {
+                Ok(ListIndexesMetadataResponse::try_from_indexes_metadata(Vec::new()).unwrap())
+            });
+        mock_metastore.expect_list_shards().returning(|_| {
+            Ok(ListShardsResponse {
+                subresponses: Vec::new(),
+            })
+        });
+        mock_metastore
+            .expect_list_index_templates()
+            .return_once(|_| {
+                let index_templates = [IndexTemplate::for_test("test-template", &["logs-*"], 0)];
+                Ok(ListIndexTemplatesResponse::try_from_index_templates(&index_templates).unwrap())
+            });
+        // The index is created again after the rollback of the first attempt.
+        mock_metastore
+            .expect_create_index()
+            .times(2)
+            .returning(|_request| {
+                Ok(CreateIndexResponse {
+                    index_uid: "logs-foo:0".to_string(),
+                })
+            });
+        let mut seq = Sequence::new();
+        mock_metastore
+            .expect_add_source()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_request| Ok(EmptyResponse {}));
+        mock_metastore
+            .expect_add_source()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_request| {
+                Err(MetastoreError::FailedPrecondition {
+                    entity: EntityKind::Index {
+                        index_id: "logs-foo".to_string(),
+                    },
+                    message: "failed to add source".to_string(),
+                })
+            });
+        mock_metastore
+            .expect_add_source()
+            .times(3)
+            .in_sequence(&mut seq)
+            .returning(|_request| Ok(EmptyResponse {}));
+        mock_metastore
+            .expect_delete_index()
+            .times(1)
+            .returning(|request| {
+                assert_eq!(request.index_uid, "logs-foo:0");
+                Ok(EmptyResponse {})
+            });
+        let replication_factor = 1;
+
+        let (control_plane_mailbox, _control_plane_handle) = ControlPlane::spawn(
+            &universe,
+            cluster_id,
+            self_node_id,
+            indexer_pool,
+            ingester_pool,
+            MetastoreServiceClient::from(mock_metastore),
+            Uri::for_test("ram:///indexes"),
+            replication_factor,
+        );
+        let get_open_shards_request = GetOrCreateOpenShardsRequest {
+            subrequests: vec![GetOrCreateOpenShardsSubrequest {
+                subrequest_id: 0,
+                index_id: "logs-foo".to_string(),
+                source_id: INGEST_V2_SOURCE_ID.to_string(),
+            }],
+            closed_shards: Vec::new(),
+            unavailable_leaders: Vec::new(),
+        };
+        let error = control_plane_mailbox
+            .ask_for_res(get_open_shards_request.clone())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            AskError::ErrorReply(ControlPlaneError::Metastore(
+                MetastoreError::FailedPrecondition { .. }
+            ))
+        ));
+        // The index was removed from the model, so the next attempt creates it again. There is no
+        // ingester to host its shards.
+        let error = control_plane_mailbox
+            .ask_for_res(get_open_shards_request)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            AskError::ErrorReply(ControlPlaneError::Unavailable(_))
+        ));
+        universe.assert_quit().await;
+    }
+
+    #[tokio::test]
+    async fn test_control_plane_caches_index_templates() {
+        let universe = Universe::with_accelerated_time();
+
+        let cluster_id = "test-cluster".to_string();
+        let self_node_id: NodeId = "test-node".into();
+        let indexer_pool = IndexerPool::default();
+        let ingester_pool = IngesterPool::default();
+
+        let mut mock_metastore = MetastoreServiceClient::mock();
+        mock_metastore
+            .expect_list_indexes_metadata()
+            .returning(|_| {
+                Ok(ListIndexesMetadataResponse::try_from_indexes_metadata(Vec::new()).unwrap())
+            });
+        mock_metastore.expect_list_shards().returning(|_| {
+            Ok(ListShardsResponse {
+                subresponses: Vec::new(),
+            })
+        });
+        mock_metastore
+            .expect_list_index_templates()
+            .times(1)
+            .returning(|_| {
+                let index_templates = [IndexTemplate::for_test("test-template", &["logs-*"], 0)];
+                Ok(ListIndexTemplatesResponse::try_from_index_templates(&index_templates).unwrap())
+            });
+        let replication_factor = 1;
+
+        let (control_plane_mailbox, _control_plane_handle) = ControlPlane::spawn(
+            &universe,
+            cluster_id,
+            self_node_id,
+            indexer_pool,
+            ingester_pool,
+            MetastoreServiceClient::from(mock_metastore),
+            Uri::for_test("ram:///indexes"),
+            replication_factor,
+        );
+        // The index matches no template: the templates are listed once and reused.
+        for _ in 0..2 {
+            let get_open_shards_request = GetOrCreateOpenShardsRequest {
+                subrequests: vec![GetOrCreateOpenShardsSubrequest {
+                    subrequest_id: 0,
+                    index_id: "traces-foo".to_string(),
+                    source_id: INGEST_V2_SOURCE_ID.to_string(),
+                }],
+                closed_shards: Vec::new(),
+                unavailable_leaders: Vec::new(),
+            };
+            let get_open_shards_response = control_plane_mailbox
+                .ask_for_res(get_open_shards_request)
+                .await
+                .unwrap();
+            assert!(get_open_shards_response.successes.is_empty());
+            assert_eq!(get_open_shards_response.failures.len(), 1);
+        }
+        universe.assert_quit().await;
+    }
+
     #[tokio::test]
     async fn test_control_plane_supervision_reload_from_metastore() {
         let universe = Universe::default();
@@ -1045,6 +1659,7 @@ mod tests {
             indexer_pool,
             ingester_pool,
             MetastoreServiceClient::from(mock_metastore),
+            Uri::for_test("ram:///indexes"),
             1,
         );
 
@@ -1185,6 +1800,7 @@ mod tests {
             indexer_pool,
             ingester_pool,
             MetastoreServiceClient::from(mock_metastore),
+            Uri::for_test("ram:///indexes"),
             1,
         );
         let source_uid = SourceUid {
@@ -1310,6 +1926,7 @@ mod tests {
             indexer_pool,
             ingester_pool,
             MetastoreServiceClient::from(mock_metastore),
+            Uri::for_test("ram:///indexes"),
             1,
         );
         let source_uid = SourceUid {
@@ -1425,6 +2042,7 @@ mod tests {
             indexer_pool,
             ingester_pool,
             MetastoreServiceClient::from(mock_metastore),
+            Uri::for_test("ram:///indexes"),
             1,
         );
         // This update should not trigger anything in the control plane.
@@ -1438,6 +2056,114 @@ mod tests {
 
         universe.assert_quit().await;
     }
+
+    #[tokio::test]
+    async fn test_rename_index_respawns_pipelines() {
+        quickwit_common::setup_logging_for_tests();
+        let universe = Universe::default();
+        let node_id = NodeId::new("control-plane-node".to_string());
+        let indexer_pool = IndexerPool::default();
+        let (client_mailbox, client_inbox) = universe.create_test_mailbox();
+        let client = IndexingServiceClient::from_mailbox::<IndexingService>(client_mailbox);
+        let indexer_node_info = IndexerNodeInfo {
+            client,
+            indexing_tasks: Vec::new(),
+            indexing_capacity: CpuCapacity::from_cpu_millis(4_000),
+        };
+        indexer_pool.insert("indexer-node-1".to_string(), indexer_node_info);
+        let ingester_pool = IngesterPool::default();
+
+        let mut index_0 = IndexMetadata::for_test("test-index-0", "ram:///test-index-0");
+        index_0
+            .add_source(SourceConfig::ingest_api_default())
+            .unwrap();
+        let old_index_uid = index_0.index_uid.clone();
+
+        let mut renamed_index_0 = index_0.clone();
+        renamed_index_0.index_config.index_id = "test-index-0-renamed".to_string();
+        renamed_index_0.index_uid = IndexUid::from_parts("test-index-0-renamed", "1");
+        let new_index_uid = renamed_index_0.index_uid.clone();
+
+        let mut mock_metastore = MetastoreServiceClient::mock();
+        let index_0_clone = index_0.clone();
+        mock_metastore.expect_list_indexes_metadata().return_once(
+            move |_list_indexes_request: ListIndexesMetadataRequest| {
+                Ok(
+                    ListIndexesMetadataResponse::try_from_indexes_metadata(vec![index_0_clone])
+                        .unwrap(),
+                )
+            },
+        );
+        let old_index_uid_clone = old_index_uid.clone();
+        let new_index_uid_clone = new_index_uid.clone();
+        mock_metastore.expect_rename_index().return_once(
+            move |rename_index_request: RenameIndexRequest| {
+                assert_eq!(
+                    rename_index_request.index_uid,
+                    old_index_uid_clone.to_string()
+                );
+                assert_eq!(rename_index_request.new_index_id, "test-index-0-renamed");
+                Ok(RenameIndexResponse {
+                    index_uid: new_index_uid_clone.to_string(),
+                })
+            },
+        );
+        let new_index_uid_clone = new_index_uid.clone();
+        mock_metastore.expect_index_metadata().return_once(
+            move |index_metadata_request: IndexMetadataRequest| {
+                assert_eq!(
+                    index_metadata_request.index_uid.unwrap(),
+                    new_index_uid_clone.to_string()
+                );
+                Ok(IndexMetadataResponse::try_from_index_metadata(&renamed_index_0).unwrap())
+            },
+        );
+
+        let (control_plane_mailbox, _control_plane_handle) = ControlPlane::spawn(
+            &universe,
+            "cluster".to_string(),
+            node_id,
+            indexer_pool,
+            ingester_pool,
+            MetastoreServiceClient::from(mock_metastore),
+            Uri::for_test("ram:///indexes"),
+            1,
+        );
+        let control_plane_obs: ControlPlaneObservableState =
+            control_plane_mailbox.ask(Observe).await.unwrap();
+        let last_applied_physical_plan = control_plane_obs
+            .indexing_scheduler
+            .last_applied_physical_plan
+            .unwrap();
+        let indexing_tasks = last_applied_physical_plan
+            .indexing_tasks_per_indexer()
+            .get("indexer-node-1")
+            .unwrap();
+        assert_eq!(indexing_tasks.len(), 1);
+        assert_eq!(indexing_tasks[0].index_uid, old_index_uid.to_string());
+        let _ = client_inbox.drain_for_test();
+
+        control_plane_mailbox
+            .ask(RenameIndexRequest {
+                index_uid: old_index_uid.to_string(),
+                new_index_id: "test-index-0-renamed".to_string(),
+            })
+            .await
+            .unwrap()
+            .unwrap();
+
+        // The pipelines of the old index UID are shut down and new ones are spawned for the new
+        // index UID.
+        let apply_plan_requests: Vec<ApplyIndexingPlanRequest> =
+            client_inbox.drain_for_test_typed::<ApplyIndexingPlanRequest>();
+        assert_eq!(apply_plan_requests.len(), 1);
+        let indexing_tasks = &apply_plan_requests[0].indexing_tasks;
+        assert_eq!(indexing_tasks.len(), 1);
+        assert_eq!(indexing_tasks[0].index_uid, new_index_uid.to_string());
+
+        universe.assert_quit().await;
+    }
+
     #[tokio::test]
     async fn test_delete_source() {
         quickwit_common::setup_logging_for_tests();
@@ -1517,6 +2243,7 @@ mod tests {
             indexer_pool,
             ingester_pool,
             MetastoreServiceClient::from(mock_metastore),
+            Uri::for_test("ram:///indexes"),
             1,
         );
         // This update should not trigger anything in the control plane.
@@ -1531,4 +2258,137 @@ mod tests {
 
         universe.assert_quit().await;
     }
+
+    #[tokio::test]
+    async fn test_control_plane_pause_source() {
+        quickwit_common::setup_logging_for_tests();
+        let universe = Universe::with_accelerated_time();
+        let node_id = NodeId::new("control-plane-node".to_string());
+
+        let mut mock_indexer = IndexingServiceClient::mock();
+        mock_indexer
+            .expect_apply_indexing_plan()
+            .returning(|request: ApplyIndexingPlanRequest| {
+                // The indexer fails to pause its pipelines, but resumes them successfully.
+                let failures = request
+                    .indexing_tasks
+                    .iter()
+                    .filter(|indexing_task| indexing_task.paused)
+                    .map(|indexing_task| IndexingPipelineFailure {
+                        node_id: "indexer-node-1".to_string(),
+                        pipeline_uid: indexing_task.pipeline_uid,
+                        error: "failed to pause pipeline".to_string(),
+                    })
+                    .collect();
+                Ok(ApplyIndexingPlanResponse { failures })
+            });
+        let indexer_pool = IndexerPool::default();
+        let indexer_node_info = IndexerNodeInfo {
+            client: IndexingServiceClient::from(mock_indexer),
+            indexing_tasks: Vec::new(),
+            indexing_capacity: CpuCapacity::from_cpu_millis(4_000),
+        };
+        indexer_pool.insert("indexer-node-1".to_string(), indexer_node_info);
+        let ingester_pool = IngesterPool::default();
+
+        let mut index_0 = IndexMetadata::for_test("test-index-0", "ram:///test-index-0");
+        let source_config = SourceConfig::ingest_api_default();
+        let source_id = source_config.source_id.clone();
+        index_0.add_source(source_config).unwrap();
+
+        let mut mock_metastore = MetastoreServiceClient::mock();
+        let index_0_clone = index_0.clone();
+        mock_metastore.expect_list_indexes_metadata().return_once(
+            move |list_indexes_request: ListIndexesMetadataRequest| {
+                assert_eq!(list_indexes_request, ListIndexesMetadataRequest::all());
+                Ok(
+                    ListIndexesMetadataResponse::try_from_indexes_metadata(vec![index_0_clone])
+                        .unwrap(),
+                )
+            },
+        );
+        let (control_plane_mailbox, _control_plane_handle) = ControlPlane::spawn(
+            &universe,
+            "cluster".to_string(),
+            node_id,
+            indexer_pool,
+            ingester_pool,
+            MetastoreServiceClient::from(mock_metastore),
+            Uri::for_test("ram:///indexes"),
+            1,
+        );
+        let pause_source_request = PauseSourceRequest {
+            index_uid: index_0.index_uid.to_string(),
+            source_id: "missing-source".to_string(),
+            paused: true,
+        };
+        let error = control_plane_mailbox
+            .ask_for_res(pause_source_request)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            AskError::ErrorReply(ControlPlaneError::Metastore(MetastoreError::NotFound(
+                EntityKind::Source { .. }
+            )))
+        ));
+
+        let pause_source_request = PauseSourceRequest {
+            index_uid: index_0.index_uid.to_string(),
+            source_id: source_id.clone(),
+            paused: true,
+        };
+        let pause_source_response = control_plane_mailbox
+            .ask_for_res(pause_source_request)
+            .await
+            .unwrap();
+        assert_eq!(pause_source_response.failures.len(), 1);
+        assert_eq!(pause_source_response.failures[0].node_id, "indexer-node-1");
+        assert_eq!(
+            pause_source_response.failures[0].error,
+            "failed to pause pipeline"
+        );
+
+        let control_plane_obs: ControlPlaneObservableState =
+            control_plane_mailbox.ask(Observe).await.unwrap();
+        let last_applied_physical_plan = control_plane_obs
+            .indexing_scheduler
+            .last_applied_physical_plan
+            .unwrap();
+        let indexing_tasks = last_applied_physical_plan
+            .indexing_tasks_per_indexer()
+            .get("indexer-node-1")
+            .unwrap();
+        assert_eq!(indexing_tasks.len(), 1);
+        assert!(indexing_tasks[0].paused);
+        assert_eq!(
+            pause_source_response.failures[0].pipeline_uid,
+            indexing_tasks[0].pipeline_uid
+        );
+
+        let resume_source_request = PauseSourceRequest {
+            index_uid: index_0.index_uid.to_string(),
+            source_id,
+            paused: false,
+        };
+        let resume_source_response = control_plane_mailbox
+            .ask_for_res(resume_source_request)
+            .await
+            .unwrap();
+        assert!(resume_source_response.failures.is_empty());
+
+        let control_plane_obs: ControlPlaneObservableState =
+            control_plane_mailbox.ask(Observe).await.unwrap();
+        let last_applied_physical_plan = control_plane_obs
+            .indexing_scheduler
+            .last_applied_physical_plan
+            .unwrap();
+        let indexing_tasks = last_applied_physical_plan
+            .indexing_tasks_per_indexer()
+            .get("indexer-node-1")
+            .unwrap();
+        assert!(!indexing_tasks[0].paused);
+
+        universe.assert_quit().await;
+    }
 }
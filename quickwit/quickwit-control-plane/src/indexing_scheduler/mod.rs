@@ -21,18 +21,21 @@ mod scheduling;
 
 use std::cmp::Ordering;
 use std::fmt;
+use std::future::Future;
 use std::num::NonZeroU32;
 use std::time::{Duration, Instant};
 
 use fnv::{FnvHashMap, FnvHashSet};
 use itertools::Itertools;
 use quickwit_proto::indexing::{
-    ApplyIndexingPlanRequest, CpuCapacity, IndexingService, IndexingTask, PIPELINE_FULL_CAPACITY,
+    ApplyIndexingPlanRequest, ApplyIndexingPlanResponse, CpuCapacity, IndexingPipelineFailure,
+    IndexingResult, IndexingService, IndexingTask, PIPELINE_FULL_CAPACITY,
 };
 use quickwit_proto::metastore::SourceType;
-use quickwit_proto::types::{NodeId, ShardId};
+use quickwit_proto::types::{IndexUid, NodeId, PipelineUid, ShardId, SourceUid};
 use scheduling::{SourceToSchedule, SourceToScheduleType};
 use serde::Serialize;
+use tokio::task::JoinHandle;
 use tracing::{debug, error, info, warn};
 
 use crate::indexing_plan::PhysicalIndexingPlan;
@@ -180,6 +183,19 @@ fn get_sources_to_schedule(model: &ControlPlaneModel) -> Vec<SourceToSchedule> {
     sources
 }
 
+/// Sets the paused state of the tasks of the plan to the pause state of their source.
+fn set_paused_indexing_tasks(physical_plan: &mut PhysicalIndexingPlan, model: &ControlPlaneModel) {
+    for indexing_tasks in physical_plan.indexing_tasks_per_indexer_mut().values_mut() {
+        for indexing_task in indexing_tasks {
+            let source_uid = SourceUid {
+                index_uid: IndexUid::from(indexing_task.index_uid.clone()),
+                source_id: indexing_task.source_id.clone(),
+            };
+            indexing_task.paused = model.is_source_paused(&source_uid);
+        }
+    }
+}
+
 impl IndexingScheduler {
     pub fn new(cluster_id: String, self_node_id: NodeId, indexer_pool: IndexerPool) -> Self {
         IndexingScheduler {
@@ -199,10 +215,110 @@ impl IndexingScheduler {
     pub(crate) fn schedule_indexing_plan_if_needed(&mut self, model: &ControlPlaneModel) {
         crate::metrics::CONTROL_PLANE_METRICS.schedule_total.inc();
 
-        let sources = get_sources_to_schedule(model);
+        let mut indexers: Vec<(String, IndexerNodeInfo)> = self.get_indexers_from_indexer_pool();
+
+        let Some(new_physical_plan) = self.compute_physical_indexing_plan(model, &indexers) else {
+            return;
+        };
+        if let Some(last_applied_plan) = &self.state.last_applied_physical_plan {
+            let plans_diff = get_indexing_plans_diff(
+                last_applied_plan.indexing_tasks_per_indexer(),
+                new_physical_plan.indexing_tasks_per_indexer(),
+            );
+            // No need to apply the new plan as it is the same as the old one.
+            if plans_diff.is_empty() {
+                return;
+            }
+        }
+        self.apply_physical_indexing_plan(&mut indexers, new_physical_plan);
+        self.state.num_schedule_indexing_plan += 1;
+    }
+
+    /// Schedules and applies an indexing plan after a source was paused or resumed. Unlike
+    /// [`Self::schedule_indexing_plan_if_needed`], the plan is applied even if it has not changed,
+    /// so that the pipelines that previously failed to be paused or resumed are retried.
+    ///
+    /// The returned future waits for the indexers running the pipelines of the source to apply
+    /// the plan and resolves to the pipelines of the source that could not be paused or resumed.
+    pub(crate) fn schedule_indexing_plan_for_paused_source(
+        &mut self,
+        model: &ControlPlaneModel,
+        source_uid: &SourceUid,
+    ) -> impl Future<Output = Vec<IndexingPipelineFailure>> + Send + 'static {
+        crate::metrics::CONTROL_PLANE_METRICS.schedule_total.inc();
 
         let mut indexers: Vec<(String, IndexerNodeInfo)> = self.get_indexers_from_indexer_pool();
 
+        let mut apply_plan_handles = FnvHashMap::default();
+        let mut source_pipeline_uids_per_indexer: Vec<(String, Vec<PipelineUid>)> = Vec::new();
+
+        if let Some(new_physical_plan) = self.compute_physical_indexing_plan(model, &indexers) {
+            for (indexer_id, indexing_tasks) in new_physical_plan.indexing_tasks_per_indexer() {
+                let source_pipeline_uids: Vec<PipelineUid> = indexing_tasks
+                    .iter()
+                    .filter(|indexing_task| {
+                        indexing_task.index_uid == source_uid.index_uid.as_str()
+                            && indexing_task.source_id == source_uid.source_id
+                    })
+                    .map(|indexing_task| indexing_task.pipeline_uid())
+                    .collect();
+                if !source_pipeline_uids.is_empty() {
+                    source_pipeline_uids_per_indexer
+                        .push((indexer_id.clone(), source_pipeline_uids));
+                }
+            }
+            apply_plan_handles =
+                self.apply_physical_indexing_plan(&mut indexers, new_physical_plan);
+            self.state.num_schedule_indexing_plan += 1;
+        }
+        async move {
+            let mut failures = Vec::new();
+
+            for (indexer_id, source_pipeline_uids) in source_pipeline_uids_per_indexer {
+                let Some(apply_plan_handle) = apply_plan_handles.remove(&indexer_id) else {
+                    continue;
+                };
+                let error = match apply_plan_handle.await {
+                    Ok(Ok(apply_plan_response)) => {
+                        let source_failures =
+                            apply_plan_response.failures.into_iter().filter(|failure| {
+                                failure
+                                    .pipeline_uid
+                                    .map(|pipeline_uid| {
+                                        source_pipeline_uids.contains(&pipeline_uid)
+                                    })
+                                    .unwrap_or(false)
+                            });
+                        failures.extend(source_failures);
+                        continue;
+                    }
+                    Ok(Err(error)) => error.to_string(),
+                    Err(join_error) => join_error.to_string(),
+                };
+                // The indexer failed to apply the plan as a whole: we cannot tell which pipelines
+                // of the source were paused or resumed.
+                for pipeline_uid in source_pipeline_uids {
+                    let failure = IndexingPipelineFailure {
+                        node_id: indexer_id.clone(),
+                        pipeline_uid: Some(pipeline_uid),
+                        error: error.clone(),
+                    };
+                    failures.push(failure);
+                }
+            }
+            failures
+        }
+    }
+
+    /// Builds a new physical indexing plan from the model. Returns `None` if no indexing capacity
+    /// is available.
+    fn compute_physical_indexing_plan(
+        &self,
+        model: &ControlPlaneModel,
+        indexers: &[(String, IndexerNodeInfo)],
+    ) -> Option<PhysicalIndexingPlan> {
+        let sources = get_sources_to_schedule(model);
+
         let indexer_id_to_cpu_capacities: FnvHashMap<String, CpuCapacity> = indexers
             .iter()
             .filter_map(|(indexer_id, indexer_node_info)| {
@@ -218,26 +334,16 @@ impl IndexingScheduler {
             if !sources.is_empty() {
                 warn!("no indexing capacity available, cannot schedule an indexing plan");
             }
-            return;
+            return None;
         };
 
-        let new_physical_plan = build_physical_indexing_plan(
+        let mut new_physical_plan = build_physical_indexing_plan(
             &sources,
             &indexer_id_to_cpu_capacities,
             self.state.last_applied_physical_plan.as_ref(),
         );
-        if let Some(last_applied_plan) = &self.state.last_applied_physical_plan {
-            let plans_diff = get_indexing_plans_diff(
-                last_applied_plan.indexing_tasks_per_indexer(),
-                new_physical_plan.indexing_tasks_per_indexer(),
-            );
-            // No need to apply the new plan as it is the same as the old one.
-            if plans_diff.is_empty() {
-                return;
-            }
-        }
-        self.apply_physical_indexing_plan(&mut indexers, new_physical_plan);
-        self.state.num_schedule_indexing_plan += 1;
+        set_paused_indexing_tasks(&mut new_physical_plan, model);
+        Some(new_physical_plan)
     }
 
     /// Checks if the last applied plan corresponds to the running indexing tasks present in the
@@ -290,17 +396,21 @@ impl IndexingScheduler {
         self.indexer_pool.pairs()
     }
 
+    /// Sends the plan to the indexers and returns the handles of the tasks applying it, keyed by
+    /// indexer ID.
     fn apply_physical_indexing_plan(
         &mut self,
         indexers: &mut [(String, IndexerNodeInfo)],
         new_physical_plan: PhysicalIndexingPlan,
-    ) {
+    ) -> FnvHashMap<String, JoinHandle<IndexingResult<ApplyIndexingPlanResponse>>> {
         debug!(new_physical_plan=?new_physical_plan, "apply physical indexing plan");
+        let mut apply_plan_handles = FnvHashMap::default();
+
         for (node_id, indexing_tasks) in new_physical_plan.indexing_tasks_per_indexer() {
             // We don't want to block on a slow indexer so we apply this change asynchronously
             // TODO not blocking is cool, but we need to make sure there is not accumulation
             // possible here.
-            tokio::spawn({
+            let apply_plan_handle = tokio::spawn({
                 let indexer = indexers
                     .iter()
                     .find(|indexer| &indexer.0 == node_id)
@@ -308,21 +418,24 @@ impl IndexingScheduler {
                     .clone();
                 let indexing_tasks = indexing_tasks.clone();
                 async move {
-                    if let Err(error) = indexer
+                    let apply_plan_res = indexer
                         .1
                         .client
                         .clone()
                         .apply_indexing_plan(ApplyIndexingPlanRequest { indexing_tasks })
-                        .await
-                    {
+                        .await;
+                    if let Err(error) = &apply_plan_res {
                         error!(indexer_node_id=%indexer.0, err=?error, "error occurred when applying indexing plan to indexer");
                     }
+                    apply_plan_res
                 }
             });
+            apply_plan_handles.insert(node_id.clone(), apply_plan_handle);
         }
         self.state.num_applied_physical_indexing_plan += 1;
         self.state.last_applied_plan_timestamp = Some(Instant::now());
         self.state.last_applied_physical_plan = Some(new_physical_plan);
+        apply_plan_handles
     }
 }
 
@@ -512,18 +625,21 @@ mod tests {
                 index_uid: "index-1:11111111111111111111111111".to_string(),
                 source_id: "source-1".to_string(),
                 shard_ids: Vec::new(),
+                paused: false,
             };
             let task_1b = IndexingTask {
                 pipeline_uid: Some(PipelineUid::from_u128(11u128)),
                 index_uid: "index-1:11111111111111111111111111".to_string(),
                 source_id: "source-1".to_string(),
                 shard_ids: Vec::new(),
+                paused: false,
             };
             let task_2 = IndexingTask {
                 pipeline_uid: Some(PipelineUid::from_u128(20u128)),
                 index_uid: "index-1:11111111111111111111111111".to_string(),
                 source_id: "source-2".to_string(),
                 shard_ids: Vec::new(),
+                paused: false,
             };
             running_plan.insert(
                 "indexer-1".to_string(),
@@ -544,12 +660,14 @@ mod tests {
                 index_uid: "index-1:11111111111111111111111111".to_string(),
                 source_id: "source-1".to_string(),
                 shard_ids: Vec::new(),
+                paused: false,
             };
             let task_2 = IndexingTask {
                 pipeline_uid: Some(PipelineUid::from_u128(2u128)),
                 index_uid: "index-1:11111111111111111111111111".to_string(),
                 source_id: "source-2".to_string(),
                 shard_ids: Vec::new(),
+                paused: false,
             };
             running_plan.insert("indexer-1".to_string(), vec![task_1.clone()]);
             desired_plan.insert("indexer-1".to_string(), vec![task_2.clone()]);
@@ -576,12 +694,14 @@ mod tests {
                 index_uid: "index-1:11111111111111111111111111".to_string(),
                 source_id: "source-1".to_string(),
                 shard_ids: Vec::new(),
+                paused: false,
             };
             let task_2 = IndexingTask {
                 pipeline_uid: Some(PipelineUid::from_u128(2u128)),
                 index_uid: "index-2:11111111111111111111111111".to_string(),
                 source_id: "source-2".to_string(),
                 shard_ids: Vec::new(),
+                paused: false,
             };
             running_plan.insert("indexer-2".to_string(), vec![task_2.clone()]);
             desired_plan.insert("indexer-1".to_string(), vec![task_1.clone()]);
@@ -616,18 +736,21 @@ mod tests {
                 index_uid: "index-1:11111111111111111111111111".to_string(),
                 source_id: "source-1".to_string(),
                 shard_ids: Vec::new(),
+                paused: false,
             };
             let task_1b = IndexingTask {
                 pipeline_uid: Some(PipelineUid::from_u128(11u128)),
                 index_uid: "index-1:11111111111111111111111111".to_string(),
                 source_id: "source-1".to_string(),
                 shard_ids: Vec::new(),
+                paused: false,
             };
             let task_1c = IndexingTask {
                 pipeline_uid: Some(PipelineUid::from_u128(12u128)),
                 index_uid: "index-1:11111111111111111111111111".to_string(),
                 source_id: "source-1".to_string(),
                 shard_ids: Vec::new(),
+                paused: false,
             };
             running_plan.insert("indexer-1".to_string(), vec![task_1a.clone()]);
             desired_plan.insert(
@@ -654,6 +777,7 @@ mod tests {
             client_log_level: None,
             client_params: serde_json::json!({}),
             enable_backfill_mode: false,
+            start_from: None,
         };
         let index_metadata = IndexMetadata::for_test("test-index", "ram:///test-index");
         let index_uid = index_metadata.index_uid.clone();
@@ -834,6 +958,7 @@ mod tests {
                 "bootstrap.servers": "localhost:9092",
             }),
             enable_backfill_mode: true,
+            start_from: None,
         })
     }
 
@@ -242,6 +242,7 @@ fn convert_scheduling_solution_to_physical_plan_single_node_single_source(
                     source_id: previous_task.source_id.clone(),
                     pipeline_uid: previous_task.pipeline_uid,
                     shard_ids,
+                    paused: false,
                 };
                 new_tasks.push(new_task);
                 if new_tasks.len() >= max_num_pipelines as usize {
@@ -266,6 +267,7 @@ fn convert_scheduling_solution_to_physical_plan_single_node_single_source(
                     source_id: source.source_uid.source_id.clone(),
                     pipeline_uid: Some(PipelineUid::new()),
                     shard_ids: Vec::new(),
+                    paused: false,
                 }
             });
             indexing_tasks
@@ -282,6 +284,7 @@ fn convert_scheduling_solution_to_physical_plan_single_node_single_source(
                     source_id: source.source_uid.source_id.clone(),
                     pipeline_uid: Some(PipelineUid::new()),
                     shard_ids: Vec::new(),
+                    paused: false,
                 }]
             }
         }
@@ -475,6 +478,7 @@ fn add_shard_to_indexer(
             source_id: source_uid.source_id.clone(),
             pipeline_uid: Some(PipelineUid::new()),
             shard_ids: vec![missing_shard],
+            paused: false,
         });
     }
 }
@@ -734,6 +738,7 @@ mod tests {
                 source_id: source_uid.source_id.clone(),
                 pipeline_uid: Some(*pipeline_uid),
                 shard_ids: shard_ids.to_vec(),
+                paused: false,
             });
         }
         plan
@@ -1017,6 +1022,7 @@ mod tests {
             source_id: source_uid.source_id.to_string(),
             pipeline_uid: Some(PipelineUid::new()),
             shard_ids: vec![ShardId::from(1), ShardId::from(4), ShardId::from(5)],
+            paused: false,
         };
         let previous_task2 = IndexingTask {
             index_uid: source_uid.index_uid.to_string(),
@@ -1029,6 +1035,7 @@ mod tests {
                 ShardId::from(9),
                 ShardId::from(10),
             ],
+            paused: false,
         };
         {
             let sharded_source = SourceToSchedule {
@@ -1091,6 +1098,7 @@ mod tests {
             source_id: source_uid.source_id.to_string(),
             pipeline_uid: Some(pipeline_uid1),
             shard_ids: Vec::new(),
+            paused: false,
         };
         let pipeline_uid2 = PipelineUid::new();
         let previous_task2 = IndexingTask {
@@ -1098,6 +1106,7 @@ mod tests {
             source_id: source_uid.source_id.to_string(),
             pipeline_uid: Some(pipeline_uid2),
             shard_ids: Vec::new(),
+            paused: false,
         };
         {
             let sharded_source = SourceToSchedule {
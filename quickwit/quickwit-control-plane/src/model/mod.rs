@@ -55,6 +55,9 @@ pub(crate) struct ControlPlaneModel {
     index_uid_table: FnvHashMap<IndexId, IndexUid>,
     index_table: FnvHashMap<IndexUid, IndexMetadata>,
     shard_table: ShardTable,
+    // The pause state of the sources is not stored in the metastore, so it is only known by the
+    // control plane.
+    paused_sources: FnvHashSet<SourceUid>,
 }
 
 #[derive(Clone, Copy, Debug, Default, Serialize)]
@@ -81,6 +84,7 @@ impl ControlPlaneModel {
         progress: &Progress,
     ) -> ControlPlaneResult<()> {
         let now = Instant::now();
+        let paused_sources = std::mem::take(&mut self.paused_sources);
         self.clear();
 
         let index_metadatas = progress
@@ -99,6 +103,10 @@ impl ControlPlaneModel {
         for index_metadata in index_metadatas {
             self.add_index(index_metadata);
         }
+        self.paused_sources = paused_sources
+            .into_iter()
+            .filter(|source_uid| self.source_exists(source_uid))
+            .collect();
 
         for index_metadata in self.index_table.values() {
             for source_config in index_metadata.sources.values() {
@@ -174,6 +182,13 @@ impl ControlPlaneModel {
         })
     }
 
+    fn source_exists(&self, source_uid: &SourceUid) -> bool {
+        self.index_table
+            .get(&source_uid.index_uid)
+            .map(|index_metadata| index_metadata.sources.contains_key(&source_uid.source_id))
+            .unwrap_or(false)
+    }
+
     pub(crate) fn add_index(&mut self, index_metadata: IndexMetadata) {
         let index_uid = index_metadata.index_uid.clone();
         self.index_uid_table
@@ -184,7 +199,10 @@ impl ControlPlaneModel {
     pub(crate) fn delete_index(&mut self, index_uid: &IndexUid) {
         // TODO: We need to let the routers and ingesters know.
         self.index_table.remove(index_uid);
+        self.index_uid_table.remove(index_uid.index_id());
         self.shard_table.delete_index(index_uid.index_id());
+        self.paused_sources
+            .retain(|source_uid| source_uid.index_uid != *index_uid);
     }
 
     /// Adds a source to a given index. Returns an error if a source with the same source_id already
@@ -206,6 +224,7 @@ impl ControlPlaneModel {
     }
 
     pub(crate) fn delete_source(&mut self, source_uid: &SourceUid) {
+        self.paused_sources.remove(source_uid);
         // Removing shards from shard table.
         self.shard_table
             .delete_source(&source_uid.index_uid, &source_uid.source_id);
@@ -238,6 +257,32 @@ impl ControlPlaneModel {
         Ok(has_changed)
     }
 
+    /// Pauses or resumes a source. Returns `true` if the source pause state has changed, `false`
+    /// otherwise. Returns an error if the source could not be found.
+    pub(crate) fn pause_source(
+        &mut self,
+        source_uid: &SourceUid,
+        paused: bool,
+    ) -> ControlPlaneResult<bool> {
+        if !self.source_exists(source_uid) {
+            let error = MetastoreError::NotFound(EntityKind::Source {
+                index_id: source_uid.index_uid.index_id().to_string(),
+                source_id: source_uid.source_id.clone(),
+            });
+            return Err(error.into());
+        }
+        let has_changed = if paused {
+            self.paused_sources.insert(source_uid.clone())
+        } else {
+            self.paused_sources.remove(source_uid)
+        };
+        Ok(has_changed)
+    }
+
+    pub(crate) fn is_source_paused(&self, source_uid: &SourceUid) -> bool {
+        self.paused_sources.contains(source_uid)
+    }
+
     pub(crate) fn all_shards_mut(&mut self) -> impl Iterator<Item = &mut ShardEntry> + '_ {
         self.shard_table.all_shards_mut()
     }
@@ -508,4 +553,54 @@ mod tests {
             assert!(!has_changed);
         }
     }
+
+    #[test]
+    fn test_control_plane_model_pause_source() {
+        let mut model = ControlPlaneModel::default();
+        let index_metadata = IndexMetadata::for_test("test-index", "ram://");
+        let index_uid = index_metadata.index_uid.clone();
+        model.add_index(index_metadata);
+        let source_config = SourceConfig::for_test("test-source", SourceParams::void());
+        model.add_source(&index_uid, source_config).unwrap();
+
+        let source_uid = SourceUid {
+            index_uid: index_uid.clone(),
+            source_id: "test-source".to_string(),
+        };
+        assert!(!model.is_source_paused(&source_uid));
+
+        let has_changed = model.pause_source(&source_uid, true).unwrap();
+        assert!(has_changed);
+        assert!(model.is_source_paused(&source_uid));
+
+        let has_changed = model.pause_source(&source_uid, true).unwrap();
+        assert!(!has_changed);
+
+        let has_changed = model.pause_source(&source_uid, false).unwrap();
+        assert!(has_changed);
+        assert!(!model.is_source_paused(&source_uid));
+
+        let has_changed = model.pause_source(&source_uid, false).unwrap();
+        assert!(!has_changed);
+
+        let missing_source_uid = SourceUid {
+            index_uid: index_uid.clone(),
+            source_id: "missing-source".to_string(),
+        };
+        let error = model.pause_source(&missing_source_uid, true).unwrap_err();
+        assert!(matches!(
+            error,
+            ControlPlaneError::Metastore(MetastoreError::NotFound(EntityKind::Source { .. }))
+        ));
+
+        model.pause_source(&source_uid, true).unwrap();
+        model.delete_source(&source_uid);
+        assert!(!model.is_source_paused(&source_uid));
+
+        let source_config = SourceConfig::for_test("test-source", SourceParams::void());
+        model.add_source(&index_uid, source_config).unwrap();
+        model.pause_source(&source_uid, true).unwrap();
+        model.delete_index(&index_uid);
+        assert!(!model.is_source_paused(&source_uid));
+    }
 }
@@ -26,6 +26,7 @@ use quickwit_actors::{Inbox, Mailbox, Observe, Universe};
 use quickwit_cluster::{create_cluster_for_test, ChannelTransport, Cluster, ClusterChange};
 use quickwit_common::test_utils::wait_until_predicate;
 use quickwit_common::tower::{Change, Pool};
+use quickwit_common::uri::Uri;
 use quickwit_config::service::QuickwitService;
 use quickwit_config::{KafkaSourceParams, SourceConfig, SourceInputFormat, SourceParams};
 use quickwit_indexing::IndexingService;
@@ -60,6 +61,7 @@ fn index_metadata_for_test(
             "bootstrap.servers": "localhost:9092",
             }),
             enable_backfill_mode: true,
+            start_from: None,
         }),
         transform_config: None,
         input_format: SourceInputFormat::Json,
@@ -148,6 +150,7 @@ async fn start_control_plane(
         indexer_pool,
         ingester_pool,
         MetastoreServiceClient::from(metastore),
+        Uri::for_test("ram:///indexes"),
         1,
     );
 
@@ -31,7 +31,7 @@ use time::parsing::Parsed;
 use time::{OffsetDateTime, PrimitiveDateTime};
 use time_fmt::parse::time_format_item::parse_to_format_item;
 
-use crate::TantivyDateTime;
+use crate::{TantivyDateTime, Timezone};
 
 /// A date time parser that holds the format specification `Vec<FormatItem>`.
 #[self_referencing]
@@ -244,8 +244,14 @@ impl DateTimeOutputFormat {
         }
     }
 
-    pub fn format_to_json(&self, date_time: TantivyDateTime) -> Result<JsonValue, String> {
-        let date = date_time.into_utc();
+    /// Formats a datetime value. Textual formats render the datetime with the offset of the
+    /// given timezone, whereas Unix timestamps are timezone agnostic.
+    pub fn format_to_json(
+        &self,
+        date_time: TantivyDateTime,
+        timezone: &Timezone,
+    ) -> Result<JsonValue, String> {
+        let date = timezone.to_local(date_time.into_utc());
         let format_result = match &self {
             DateTimeOutputFormat::Rfc3339 => date.format(&Rfc3339).map(JsonValue::String),
             DateTimeOutputFormat::Iso8601 => date.format(&Iso8601::DEFAULT).map(JsonValue::String),
@@ -440,6 +446,37 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_date_time_output_format_to_json_with_timezone() {
+        let date_time = TantivyDateTime::from_utc(datetime!(2024-01-15 12:30:00 UTC));
+        let tokyo = Timezone::from_str("Asia/Tokyo").unwrap();
+        assert_eq!(
+            DateTimeOutputFormat::Rfc3339
+                .format_to_json(date_time, &Timezone::UTC)
+                .unwrap(),
+            "2024-01-15T12:30:00Z"
+        );
+        assert_eq!(
+            DateTimeOutputFormat::Rfc3339
+                .format_to_json(date_time, &tokyo)
+                .unwrap(),
+            "2024-01-15T21:30:00+09:00"
+        );
+        assert_eq!(
+            DateTimeOutputFormat::from_str("%Y-%m-%d %H:%M:%S %z")
+                .unwrap()
+                .format_to_json(date_time, &tokyo)
+                .unwrap(),
+            "2024-01-15 21:30:00 +0900"
+        );
+        assert_eq!(
+            DateTimeOutputFormat::TimestampSecs
+                .format_to_json(date_time, &tokyo)
+                .unwrap(),
+            serde_json::json!(1_705_321_800)
+        );
+    }
+
     #[test]
     fn test_strictly_parse_datetime_format() {
         let parser = StrptimeParser::from_str("%Y-%m-%d").unwrap();
@@ -19,9 +19,11 @@
 
 mod date_time_format;
 mod date_time_parsing;
+mod timezone;
 
 pub use date_time_format::{DateTimeInputFormat, DateTimeOutputFormat, StrptimeParser};
 pub use date_time_parsing::{
     parse_date_time_str, parse_timestamp, parse_timestamp_float, parse_timestamp_int,
 };
 pub use tantivy::DateTime as TantivyDateTime;
+pub use timezone::Timezone;
@@ -0,0 +1,159 @@
+// Copyright (C) 2024 Quickwit, Inc.
+//
+// Quickwit is offered under the AGPL v3.0 and as commercial software.
+// For commercial licensing, contact us at hello@quickwit.io.
+//
+// AGPL:
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as
+// published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use chrono::{NaiveDateTime, Offset, TimeZone};
+use chrono_tz::Tz;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize};
+use time::{OffsetDateTime, UtcOffset};
+
+/// An IANA timezone (e.g. `Europe/Paris`) used to render datetime values.
+///
+/// Datetime values are always stored in UTC. The timezone only affects how they are displayed,
+/// and daylight saving time transitions are taken into account.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Timezone(Tz);
+
+impl Default for Timezone {
+    fn default() -> Self {
+        Timezone::UTC
+    }
+}
+
+impl Timezone {
+    pub const UTC: Timezone = Timezone(Tz::UTC);
+
+    pub fn as_str(&self) -> &str {
+        self.0.name()
+    }
+
+    pub fn is_utc(&self) -> bool {
+        *self == Timezone::UTC
+    }
+
+    /// Converts a UTC datetime into the same instant expressed with the offset of the timezone.
+    pub fn to_local(&self, date_time: OffsetDateTime) -> OffsetDateTime {
+        if self.is_utc() {
+            return date_time;
+        }
+        let Some(naive_date_time) =
+            NaiveDateTime::from_timestamp_opt(date_time.unix_timestamp(), 0)
+        else {
+            return date_time;
+        };
+        let offset_secs = self
+            .0
+            .offset_from_utc_datetime(&naive_date_time)
+            .fix()
+            .local_minus_utc();
+        let utc_offset = UtcOffset::from_whole_seconds(offset_secs)
+            .expect("timezone offsets should be within ±24 hours");
+        date_time.to_offset(utc_offset)
+    }
+}
+
+impl Display for Timezone {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Timezone {
+    type Err = String;
+
+    fn from_str(timezone_str: &str) -> Result<Self, Self::Err> {
+        let timezone = timezone_str.parse::<Tz>().map_err(|_| {
+            format!(
+                "unknown timezone: `{timezone_str}`. the timezone must be a valid IANA timezone \
+                 name such as `UTC` or `Europe/Paris`"
+            )
+        })?;
+        Ok(Timezone(timezone))
+    }
+}
+
+impl Serialize for Timezone {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where S: serde::Serializer {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Timezone {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where D: Deserializer<'de> {
+        let timezone_str: String = Deserialize::deserialize(deserializer)?;
+        let timezone = timezone_str.parse().map_err(D::Error::custom)?;
+        Ok(timezone)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+
+    #[test]
+    fn test_timezone_from_str() {
+        assert_eq!(Timezone::from_str("UTC").unwrap(), Timezone::UTC);
+        assert_eq!(
+            Timezone::from_str("Europe/Paris").unwrap().as_str(),
+            "Europe/Paris"
+        );
+        let error = Timezone::from_str("Mars/Olympus_Mons").unwrap_err();
+        assert!(error.contains("unknown timezone: `Mars/Olympus_Mons`"));
+    }
+
+    #[test]
+    fn test_timezone_serde() {
+        let timezone: Timezone = serde_json::from_str(r#""Asia/Tokyo""#).unwrap();
+        assert_eq!(timezone.as_str(), "Asia/Tokyo");
+        assert_eq!(serde_json::to_string(&timezone).unwrap(), r#""Asia/Tokyo""#);
+
+        let error = serde_json::from_str::<Timezone>(r#""Asia/Nowhere""#).unwrap_err();
+        assert!(error.to_string().contains("unknown timezone"));
+    }
+
+    #[test]
+    fn test_timezone_to_local() {
+        assert_eq!(
+            Timezone::UTC.to_local(datetime!(2024-01-15 12:00:00 UTC)),
+            datetime!(2024-01-15 12:00:00 UTC)
+        );
+        let paris = Timezone::from_str("Europe/Paris").unwrap();
+        // Winter time.
+        let local_date_time = paris.to_local(datetime!(2024-01-15 12:00:00.5 UTC));
+        assert_eq!(local_date_time, datetime!(2024-01-15 13:00:00.5 +1));
+        assert_eq!(
+            local_date_time.offset(),
+            UtcOffset::from_hms(1, 0, 0).unwrap()
+        );
+        // Summer time.
+        let local_date_time = paris.to_local(datetime!(2024-07-15 12:00:00 UTC));
+        assert_eq!(
+            local_date_time.offset(),
+            UtcOffset::from_hms(2, 0, 0).unwrap()
+        );
+        assert_eq!(local_date_time.hour(), 14);
+    }
+}
@@ -23,7 +23,9 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::{fmt, io};
 
-use quickwit_storage::{BundleStorageFileOffsets, OwnedBytes, Storage, StorageResult};
+use quickwit_storage::{
+    BundleStorageFileOffsets, OwnedBytes, Storage, StorageError, StorageErrorKind, StorageResult,
+};
 use tantivy::directory::error::OpenReadError;
 use tantivy::directory::{FileHandle, FileSlice};
 use tantivy::{Directory, HasLen};
@@ -114,11 +116,18 @@ impl BundleDirectory {
     }
 
     /// Opens a split file.
-    pub fn open_split(split_file: FileSlice) -> io::Result<BundleDirectory> {
+    ///
+    /// Fails with [`StorageErrorKind::Corrupted`] if the bundle footer does not match its
+    /// checksum.
+    pub fn open_split(split_file: FileSlice) -> StorageResult<BundleDirectory> {
         // First we remove the hotcache from our file slice.
         let (body_and_bundle_metadata, _hot_cache) = split_footer(split_file)?;
-        BundleDirectory::open_bundle(body_and_bundle_metadata)
-            .map_err(|anyhow_err| io::Error::new(io::ErrorKind::Other, anyhow_err))
+        BundleDirectory::open_bundle(body_and_bundle_metadata).map_err(
+            |anyhow_err| match anyhow_err.downcast::<StorageError>() {
+                Ok(storage_error) => storage_error,
+                Err(anyhow_err) => StorageErrorKind::Io.with_error(anyhow_err),
+            },
+        )
     }
 
     /// Opens a BundleDirectory, given a file containing the bundle data.
@@ -273,4 +282,24 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_open_split_with_corrupted_footer() -> anyhow::Result<()> {
+        let hotcache = [1, 2, 3];
+        let mut split_payload_builder = SplitPayloadBuilder::default();
+        split_payload_builder.add_payload(SPLIT_FIELDS_FILE_NAME.to_string(), Box::new(vec![5]));
+        split_payload_builder.set_footer_checksum(true);
+        let mut data = split_payload_builder
+            .finalize(&hotcache)?
+            .read_all()
+            .await?
+            .to_vec();
+        // Flip a bit of the bundle footer checksum.
+        let metadata_end = data.len() - 4 - hotcache.len() - 4;
+        data[metadata_end - 1] ^= 1;
+
+        let error = BundleDirectory::open_split(FileSlice::from(data)).unwrap_err();
+        assert_eq!(error.kind(), StorageErrorKind::Corrupted);
+        Ok(())
+    }
 }
@@ -19,6 +19,7 @@
 
 use std::ops::Range;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::{fmt, io};
 
@@ -34,6 +35,7 @@ pub struct CachingDirectory {
     underlying: Arc<dyn Directory>,
     // TODO fixme: that's a pretty ugly cache we have here.
     cache: Arc<ByteRangeCache>,
+    byte_budget_opt: Option<Arc<ByteBudget>>,
 }
 
 impl CachingDirectory {
@@ -47,8 +49,42 @@ impl CachingDirectory {
             cache: Arc::new(ByteRangeCache::with_infinite_capacity(
                 &quickwit_storage::STORAGE_METRICS.shortlived_cache,
             )),
+            byte_budget_opt: None,
         }
     }
+
+    /// Bounds the number of bytes read from the underlying directory. Reads exceeding the budget
+    /// fail with an error.
+    pub fn with_byte_budget(mut self, max_num_bytes: usize) -> CachingDirectory {
+        self.byte_budget_opt = Some(Arc::new(ByteBudget {
+            num_bytes: AtomicUsize::new(0),
+            max_num_bytes,
+        }));
+        self
+    }
+}
+
+/// Tracks the number of bytes read from the underlying directory against a maximum.
+struct ByteBudget {
+    num_bytes: AtomicUsize,
+    max_num_bytes: usize,
+}
+
+impl ByteBudget {
+    fn consume(&self, path: &Path, num_bytes: usize) -> io::Result<()> {
+        let total_num_bytes = self.num_bytes.fetch_add(num_bytes, Ordering::Relaxed) + num_bytes;
+        if total_num_bytes > self.max_num_bytes {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                format!(
+                    "reading `{}` exceeds the byte budget of {} bytes",
+                    path.display(),
+                    self.max_num_bytes
+                ),
+            ));
+        }
+        Ok(())
+    }
 }
 
 impl fmt::Debug for CachingDirectory {
@@ -60,9 +96,19 @@ impl fmt::Debug for CachingDirectory {
 struct CachingFileHandle {
     path: PathBuf,
     cache: Arc<ByteRangeCache>,
+    byte_budget_opt: Option<Arc<ByteBudget>>,
     underlying_filehandle: Arc<dyn FileHandle>,
 }
 
+impl CachingFileHandle {
+    fn consume_byte_budget(&self, byte_range: &Range<usize>) -> io::Result<()> {
+        if let Some(byte_budget) = &self.byte_budget_opt {
+            byte_budget.consume(&self.path, byte_range.len())?;
+        }
+        Ok(())
+    }
+}
+
 impl fmt::Debug for CachingFileHandle {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
@@ -80,6 +126,7 @@ impl FileHandle for CachingFileHandle {
         if let Some(bytes) = self.cache.get_slice(&self.path, byte_range.clone()) {
             return Ok(bytes);
         }
+        self.consume_byte_budget(&byte_range)?;
         let owned_bytes = self.underlying_filehandle.read_bytes(byte_range.clone())?;
         self.cache
             .put_slice(self.path.clone(), byte_range, owned_bytes.clone());
@@ -90,6 +137,7 @@ impl FileHandle for CachingFileHandle {
         if let Some(owned_bytes) = self.cache.get_slice(&self.path, byte_range.clone()) {
             return Ok(owned_bytes);
         }
+        self.consume_byte_budget(&byte_range)?;
         let read_bytes = self
             .underlying_filehandle
             .read_bytes_async(byte_range.clone())
@@ -119,6 +167,7 @@ impl Directory for CachingDirectory {
         let caching_file_handle = CachingFileHandle {
             path: path.to_path_buf(),
             cache: self.cache.clone(),
+            byte_budget_opt: self.byte_budget_opt.clone(),
             underlying_filehandle,
         };
         Ok(Arc::new(caching_file_handle))
@@ -160,4 +209,23 @@ mod tests {
         assert_eq!(debug_proxy_directory.drain_read_operations().count(), 1);
         Ok(())
     }
+
+    #[test]
+    fn test_caching_directory_byte_budget() -> tantivy::Result<()> {
+        let ram_directory = RamDirectory::default();
+        let small_path = Path::new("small");
+        let large_path = Path::new("large");
+        ram_directory.atomic_write(small_path, &b"test"[..])?;
+        ram_directory.atomic_write(large_path, &b"testtest"[..])?;
+        let caching_directory =
+            CachingDirectory::new_unbounded(Arc::new(ram_directory)).with_byte_budget(10);
+        caching_directory.atomic_read(small_path)?;
+        // Cached reads do not consume the budget.
+        caching_directory.atomic_read(small_path)?;
+        let error = caching_directory.atomic_read(large_path).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("exceeds the byte budget of 10 bytes"));
+        Ok(())
+    }
 }
@@ -17,12 +17,13 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
-use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::num::NonZeroU32;
 
 use anyhow::{bail, Context};
 use fnv::FnvHashSet;
 use quickwit_common::PathHasher;
+use quickwit_datetime::Timezone;
 use quickwit_query::create_default_quickwit_tokenizer_manager;
 use quickwit_query::query_ast::QueryAst;
 use quickwit_query::tokenizers::TokenizerManager;
@@ -33,18 +34,19 @@ use tantivy::schema::{
     Field, FieldType, FieldValue, OwnedValue as TantivyValue, Schema, INDEXED, STORED,
 };
 use tantivy::TantivyDocument as Document;
+use tracing::warn;
 
 use super::field_mapping_entry::RAW_TOKENIZER_NAME;
 use super::DefaultDocMapperBuilder;
 use crate::default_doc_mapper::mapping_tree::{build_mapping_tree, MappingNode};
-use crate::default_doc_mapper::FieldMappingType;
 pub use crate::default_doc_mapper::QuickwitJsonOptions;
+use crate::default_doc_mapper::{validate_field_mapping_name, FieldMappingType};
 use crate::doc_mapper::{JsonObject, Partition};
-use crate::query_builder::build_query;
+use crate::query_builder::{build_query, resolve_field_alias, resolve_field_aliases};
 use crate::routing_expression::RoutingExpr;
 use crate::{
-    Cardinality, DocMapper, DocParsingError, Mode, QueryParserError, TokenizerEntry, WarmupInfo,
-    DYNAMIC_FIELD_NAME, FIELD_PRESENCE_FIELD_NAME, SOURCE_FIELD_NAME,
+    Cardinality, DocMapper, DocParsingError, Mode, OnFieldError, QueryParserError, TokenizerEntry,
+    WarmupInfo, DYNAMIC_FIELD_NAME, FIELD_PRESENCE_FIELD_NAME, SOURCE_FIELD_NAME,
 };
 
 const FIELD_PRESENCE_FIELD: Field = Field::from_field_id(0u32);
@@ -78,11 +80,24 @@ pub struct DefaultDocMapper {
     schema: Schema,
     /// List of field names used for tagging.
     tag_field_names: BTreeSet<String>,
+    /// List of numeric fast field names whose min / max values are recorded in the split
+    /// metadata.
+    min_max_field_names: BTreeSet<String>,
+    /// Field aliases, mapped to the path of the field they stand for.
+    aliases: BTreeMap<String, String>,
+    /// Text fields whose values are appended to other text fields, mapped to the latter.
+    copy_to_fields: HashMap<Field, Vec<Field>>,
     /// The partition key is a DSL used to route documents
     /// into specific splits.
     partition_key: RoutingExpr,
     /// Maximum number of partitions
     max_num_partitions: NonZeroU32,
+    /// Maximum nesting depth of the documents.
+    max_nesting_depth: usize,
+    /// Defines how fields whose value cannot be coerced to their mapped type are handled.
+    on_field_error: OnFieldError,
+    /// Timezone in which datetime values are rendered.
+    default_timezone: Timezone,
     /// List of required fields. Right now this is unused.
     required_fields: Vec<Field>,
     /// Defines how unmapped fields should be handle.
@@ -110,6 +125,37 @@ impl DefaultDocMapper {
     pub fn default_max_num_partitions() -> NonZeroU32 {
         NonZeroU32::new(200).unwrap()
     }
+
+    /// Default maximum nesting depth of the documents.
+    pub fn default_max_nesting_depth() -> usize {
+        100
+    }
+}
+
+/// Returns the nesting depth of a JSON object, where the root object has a depth of 1 and each
+/// nested object or array adds a level.
+///
+/// The traversal is iterative so that arbitrarily deep documents cannot overflow the stack.
+fn json_obj_depth(json_obj: &JsonObject) -> usize {
+    let mut max_depth = 1;
+    let mut stack: Vec<(&JsonValue, usize)> = json_obj.values().map(|value| (value, 1)).collect();
+
+    while let Some((json_value, parent_depth)) = stack.pop() {
+        match json_value {
+            JsonValue::Array(values) => {
+                let depth = parent_depth + 1;
+                max_depth = max_depth.max(depth);
+                stack.extend(values.iter().map(|value| (value, depth)));
+            }
+            JsonValue::Object(json_obj) => {
+                let depth = parent_depth + 1;
+                max_depth = max_depth.max(depth);
+                stack.extend(json_obj.values().map(|value| (value, depth)));
+            }
+            _ => {}
+        }
+    }
+    max_depth
 }
 
 fn validate_timestamp_field(
@@ -207,6 +253,21 @@ impl TryFrom<DefaultDocMapperBuilder> for DefaultDocMapper {
         }
         validate_fields_tokenizers(&schema, &tokenizer_manager)?;
 
+        for (alias, field_path) in &builder.aliases {
+            validate_alias(alias, field_path, &schema)?;
+        }
+
+        let mut copy_to_fields = HashMap::new();
+        for (source_field, target_field_names) in field_mappings.copy_to_fields() {
+            let target_fields = target_field_names
+                .iter()
+                .map(|target_field_name| {
+                    resolve_copy_to_target(source_field, target_field_name, &schema)
+                })
+                .collect::<anyhow::Result<Vec<Field>>>()?;
+            copy_to_fields.insert(source_field, target_fields);
+        }
+
         // Resolve default search fields
         let mut default_search_field_names = Vec::new();
         for default_search_field_name in &builder.default_search_fields {
@@ -217,11 +278,23 @@ impl TryFrom<DefaultDocMapperBuilder> for DefaultDocMapper {
                 )
             }
             let dynamic_field = schema.get_field(DYNAMIC_FIELD_NAME).ok();
-            let (default_search_field, _json_path) = schema
-                .find_field_with_default(default_search_field_name, dynamic_field)
-                .with_context(|| {
-                    format!("Unknown default search field: `{default_search_field_name}`")
-                })?;
+            let resolved_field_name =
+                resolve_field_alias(default_search_field_name, &builder.aliases);
+            let Some((default_search_field, _json_path)) =
+                schema.find_field_with_default(&resolved_field_name, dynamic_field)
+            else {
+                if builder.ignore_missing_default_search_fields {
+                    warn!(
+                        "ignoring unknown default search field `{default_search_field_name}`: the \
+                         field is not declared in the doc mapping"
+                    );
+                    continue;
+                }
+                bail!(
+                    "Unknown default search field: `{default_search_field_name}`. The field is \
+                     not declared in the doc mapping"
+                );
+            };
             if !schema.get_field_entry(default_search_field).is_indexed() {
                 bail!("default search field `{default_search_field_name}` is not indexed",);
             }
@@ -234,6 +307,13 @@ impl TryFrom<DefaultDocMapperBuilder> for DefaultDocMapper {
             validate_tag(tag_field_name, &schema)?;
         }
 
+        // Resolve min / max fields
+        let min_max_field_names: BTreeSet<String> =
+            builder.min_max_fields.iter().cloned().collect();
+        for min_max_field_name in &min_max_field_names {
+            validate_min_max_field(min_max_field_name, &schema)?;
+        }
+
         let partition_key_expr: &str = builder.partition_key.as_deref().unwrap_or("");
         let partition_key = RoutingExpr::new(partition_key_expr).with_context(|| {
             format!("failed to interpret the partition key: `{partition_key_expr}`")
@@ -256,9 +336,15 @@ impl TryFrom<DefaultDocMapperBuilder> for DefaultDocMapper {
             timestamp_field_name: builder.timestamp_field,
             field_mappings,
             tag_field_names,
+            min_max_field_names,
+            aliases: builder.aliases,
+            copy_to_fields,
             required_fields,
             partition_key,
             max_num_partitions: builder.max_num_partitions,
+            max_nesting_depth: builder.max_nesting_depth,
+            on_field_error: builder.on_field_error,
+            default_timezone: builder.default_timezone,
             mode: builder.mode,
             tokenizer_entries: builder.tokenizers,
             tokenizer_manager,
@@ -266,6 +352,55 @@ impl TryFrom<DefaultDocMapperBuilder> for DefaultDocMapper {
     }
 }
 
+/// Returns the field a text field's values are copied to, checking that it exists and that it is
+/// a text field.
+fn resolve_copy_to_target(
+    source_field: Field,
+    target_field_name: &str,
+    schema: &Schema,
+) -> anyhow::Result<Field> {
+    let source_field_name = schema.get_field_name(source_field);
+    let Ok(target_field) = schema.get_field(target_field_name) else {
+        bail!(
+            "unknown `copy_to` target field `{target_field_name}` for field `{source_field_name}`"
+        );
+    };
+    if target_field == source_field {
+        bail!("field `{source_field_name}` cannot be copied to itself");
+    }
+    if !matches!(
+        schema.get_field_entry(target_field).field_type(),
+        FieldType::Str(_)
+    ) {
+        bail!(
+            "`copy_to` target field `{target_field_name}` for field `{source_field_name}` must be \
+             a text field"
+        );
+    }
+    Ok(target_field)
+}
+
+/// Checks that an alias is a valid field name that does not shadow any field, and that it stands
+/// for an existing field.
+fn validate_alias(alias: &str, field_path: &str, schema: &Schema) -> anyhow::Result<()> {
+    validate_field_mapping_name(alias).with_context(|| format!("invalid alias `{alias}`"))?;
+
+    let alias_prefix = format!("{alias}.");
+    let colliding_field_opt = schema.fields().find(|(_, field_entry)| {
+        field_entry.name() == alias || field_entry.name().starts_with(&alias_prefix)
+    });
+    if let Some((_, field_entry)) = colliding_field_opt {
+        bail!(
+            "alias `{alias}` collides with field `{}`",
+            field_entry.name()
+        );
+    }
+    if schema.find_field(field_path).is_none() {
+        bail!("alias `{alias}` points to unknown field `{field_path}`");
+    }
+    Ok(())
+}
+
 /// Checks that a given field name is a valid candidate for a tag.
 ///
 /// The conditions are:
@@ -318,6 +453,33 @@ fn validate_tag(tag_field_name: &str, schema: &Schema) -> Result<(), anyhow::Err
     Ok(())
 }
 
+/// Checks that a given field name is a valid candidate for min / max values recording.
+///
+/// The field must be a `u64`, `i64`, or `f64` fast field.
+fn validate_min_max_field(min_max_field_name: &str, schema: &Schema) -> anyhow::Result<()> {
+    let field = schema
+        .get_field(min_max_field_name)
+        .with_context(|| format!("unknown min / max field: `{min_max_field_name}`"))?;
+    let field_type = schema.get_field_entry(field).field_type();
+    if !matches!(
+        field_type,
+        FieldType::U64(_) | FieldType::I64(_) | FieldType::F64(_)
+    ) {
+        bail!(
+            "min / max values can only be recorded for `u64`, `i64`, and `f64` fields, field \
+             `{min_max_field_name}` is of type `{}`",
+            field_type.value_type().name().to_lowercase()
+        )
+    }
+    if !field_type.is_fast() {
+        bail!(
+            "min / max fields are required to be fast fields. (`{min_max_field_name}` is not \
+             configured as fast)"
+        )
+    }
+    Ok(())
+}
+
 /// Checks that a given text/json field name has a registered tokenizer.
 fn validate_fields_tokenizers(
     schema: &Schema,
@@ -362,11 +524,18 @@ impl From<DefaultDocMapper> for DefaultDocMapperBuilder {
                 .map(ToString::to_string),
             field_mappings: default_doc_mapper.field_mappings.into(),
             tag_fields: default_doc_mapper.tag_field_names.into_iter().collect(),
+            min_max_fields: default_doc_mapper.min_max_field_names.into_iter().collect(),
+            aliases: default_doc_mapper.aliases,
             default_search_fields: default_doc_mapper.default_search_field_names,
             mode: default_doc_mapper.mode,
             partition_key: partition_key_opt,
             max_num_partitions: default_doc_mapper.max_num_partitions,
+            max_nesting_depth: default_doc_mapper.max_nesting_depth,
+            on_field_error: default_doc_mapper.on_field_error,
+            default_timezone: default_doc_mapper.default_timezone,
             tokenizers: default_doc_mapper.tokenizer_entries,
+            // Unknown default search fields have already been dropped.
+            ignore_missing_default_search_fields: false,
         }
     }
 }
@@ -381,6 +550,7 @@ impl std::fmt::Debug for DefaultDocMapper {
                 &self.default_search_field_names,
             )
             .field("timestamp_field_name", &self.timestamp_field_name())
+            .field("default_timezone", &self.default_timezone)
             // TODO: complete it.
             .finish()
     }
@@ -508,6 +678,22 @@ impl DocMapper for DefaultDocMapper {
         &self,
         json_obj: JsonObject,
     ) -> Result<(Partition, Document), DocParsingError> {
+        let (partition, document, _num_dropped_fields) =
+            self.doc_from_json_obj_with_num_dropped_fields(json_obj)?;
+        Ok((partition, document))
+    }
+
+    fn doc_from_json_obj_with_num_dropped_fields(
+        &self,
+        json_obj: JsonObject,
+    ) -> Result<(Partition, Document, usize), DocParsingError> {
+        let depth = json_obj_depth(&json_obj);
+        if depth > self.max_nesting_depth {
+            return Err(DocParsingError::TooDeeplyNested {
+                depth,
+                limit: self.max_nesting_depth,
+            });
+        }
         let partition: Partition = self.partition_key.eval_hash(&json_obj);
 
         let mut dynamic_json_obj = serde_json::Map::default();
@@ -526,9 +712,10 @@ impl DocMapper for DefaultDocMapper {
         }
 
         let mode = self.mode.mode_type();
-        self.field_mappings.doc_from_json(
+        let num_dropped_fields = self.field_mappings.doc_from_json(
             json_obj,
             mode,
+            self.on_field_error,
             &mut document,
             &mut field_path,
             &mut dynamic_json_obj,
@@ -546,6 +733,23 @@ impl DocMapper for DefaultDocMapper {
             }
         }
 
+        if !self.copy_to_fields.is_empty() {
+            let mut copied_texts: Vec<(Field, String)> = Vec::new();
+            for FieldValue { field, value } in document.field_values() {
+                let TantivyValue::Str(text) = value else {
+                    continue;
+                };
+                if let Some(target_fields) = self.copy_to_fields.get(field) {
+                    for target_field in target_fields {
+                        copied_texts.push((*target_field, text.clone()));
+                    }
+                }
+            }
+            for (target_field, text) in copied_texts {
+                document.add_text(target_field, text);
+            }
+        }
+
         // The capacity is inexact here.
 
         if self.index_field_presence {
@@ -584,7 +788,7 @@ impl DocMapper for DefaultDocMapper {
         }
 
         self.check_missing_required_fields(&document)?;
-        Ok((partition, document))
+        Ok((partition, document, num_dropped_fields))
     }
 
     fn doc_to_json(
@@ -594,8 +798,12 @@ impl DocMapper for DefaultDocMapper {
         let mut doc_json =
             extract_single_obj(&mut named_doc, DYNAMIC_FIELD_NAME)?.unwrap_or_default();
         let mut field_path: Vec<&str> = Vec::new();
-        self.field_mappings
-            .populate_json(&mut named_doc, &mut field_path, &mut doc_json);
+        self.field_mappings.populate_json(
+            &mut named_doc,
+            &mut field_path,
+            &self.default_timezone,
+            &mut doc_json,
+        );
 
         if let Some(source_json) = extract_single_obj(&mut named_doc, SOURCE_FIELD_NAME)? {
             doc_json.insert(
@@ -613,11 +821,26 @@ impl DocMapper for DefaultDocMapper {
         query_ast: &QueryAst,
         with_validation: bool,
     ) -> Result<(Box<dyn Query>, WarmupInfo), QueryParserError> {
+        if self.aliases.is_empty() {
+            return build_query(
+                query_ast,
+                split_schema,
+                self.tokenizer_manager(),
+                &self.default_search_field_names[..],
+                with_validation,
+            );
+        }
+        let resolved_query_ast = resolve_field_aliases(query_ast.clone(), &self.aliases);
+        let resolved_search_field_names: Vec<String> = self
+            .default_search_field_names
+            .iter()
+            .map(|field_name| resolve_field_alias(field_name, &self.aliases))
+            .collect();
         build_query(
-            query_ast,
+            &resolved_query_ast,
             split_schema,
             self.tokenizer_manager(),
-            &self.default_search_field_names[..],
+            &resolved_search_field_names[..],
             with_validation,
         )
     }
@@ -638,6 +861,10 @@ impl DocMapper for DefaultDocMapper {
         self.tag_field_names.clone()
     }
 
+    fn min_max_field_names(&self) -> BTreeSet<String> {
+        self.min_max_field_names.clone()
+    }
+
     fn max_num_partitions(&self) -> NonZeroU32 {
         self.max_num_partitions
     }
@@ -649,7 +876,7 @@ impl DocMapper for DefaultDocMapper {
 
 #[cfg(test)]
 mod tests {
-    use std::collections::{HashMap, HashSet};
+    use std::collections::{BTreeMap, HashMap, HashSet};
 
     use quickwit_common::PathHasher;
     use quickwit_query::query_ast::query_ast_from_user_text;
@@ -1140,6 +1367,51 @@ mod tests {
             .contains("the following fields are reserved for Quickwit internal usage"));
     }
 
+    #[test]
+    fn test_fail_to_build_doc_mapper_with_unknown_default_search_field() {
+        let doc_mapper = r#"{
+            "default_search_fields": ["body", "bdoy"],
+            "mode": "strict",
+            "field_mappings": [{"name": "body", "type": "text"}]
+        }"#;
+        let builder = serde_json::from_str::<DefaultDocMapperBuilder>(doc_mapper).unwrap();
+        let error_msg = builder.try_build().unwrap_err().to_string();
+        assert_eq!(
+            error_msg,
+            "Unknown default search field: `bdoy`. The field is not declared in the doc mapping"
+        );
+    }
+
+    #[test]
+    fn test_build_doc_mapper_ignoring_missing_default_search_fields() {
+        let doc_mapper = r#"{
+            "default_search_fields": ["body", "bdoy"],
+            "ignore_missing_default_search_fields": true,
+            "mode": "strict",
+            "field_mappings": [
+                {"name": "body", "type": "text"},
+                {"name": "title", "type": "text", "indexed": false}
+            ]
+        }"#;
+        let builder = serde_json::from_str::<DefaultDocMapperBuilder>(doc_mapper).unwrap();
+        let doc_mapper = builder.try_build().unwrap();
+        assert_eq!(doc_mapper.default_search_fields(), &["body".to_string()]);
+
+        // Fields that exist but are not indexed are still rejected.
+        let doc_mapper = r#"{
+            "default_search_fields": ["title"],
+            "ignore_missing_default_search_fields": true,
+            "mode": "strict",
+            "field_mappings": [
+                {"name": "body", "type": "text"},
+                {"name": "title", "type": "text", "indexed": false}
+            ]
+        }"#;
+        let builder = serde_json::from_str::<DefaultDocMapperBuilder>(doc_mapper).unwrap();
+        let error_msg = builder.try_build().unwrap_err().to_string();
+        assert_eq!(error_msg, "default search field `title` is not indexed");
+    }
+
     #[test]
     fn test_fail_to_parse_document_with_wrong_base64_value() -> anyhow::Result<()> {
         let doc_mapper = r#"{
@@ -1396,6 +1668,87 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_build_doc_mapper_with_min_max_fields() {
+        let doc_mapper = r#"{
+            "min_max_fields": ["status", "latency"],
+            "field_mappings": [
+                {
+                    "name": "status",
+                    "type": "u64",
+                    "fast": true
+                },
+                {
+                    "name": "latency",
+                    "type": "f64",
+                    "fast": true
+                }
+            ]
+        }"#;
+        let doc_mapper = serde_json::from_str::<DefaultDocMapper>(doc_mapper).unwrap();
+        let min_max_field_names: Vec<String> =
+            doc_mapper.min_max_field_names().into_iter().collect();
+        assert_eq!(min_max_field_names, ["latency", "status"]);
+        let min_max_named_fields = doc_mapper.min_max_named_fields().unwrap();
+        assert_eq!(min_max_named_fields.len(), 2);
+        assert_eq!(min_max_named_fields[0].name, "latency");
+    }
+
+    #[test]
+    fn test_fail_to_build_doc_mapper_with_invalid_min_max_fields() {
+        let doc_mapper_one = r#"{
+            "min_max_fields": ["city"],
+            "field_mappings": [
+                {
+                    "name": "city",
+                    "type": "text",
+                    "tokenizer": "raw",
+                    "fast": true
+                }
+            ]
+        }"#;
+        assert_eq!(
+            serde_json::from_str::<DefaultDocMapperBuilder>(doc_mapper_one)
+                .unwrap()
+                .try_build()
+                .unwrap_err()
+                .to_string(),
+            "min / max values can only be recorded for `u64`, `i64`, and `f64` fields, field \
+             `city` is of type `str`",
+        );
+
+        let doc_mapper_two = r#"{
+            "min_max_fields": ["status"],
+            "field_mappings": [
+                {
+                    "name": "status",
+                    "type": "u64"
+                }
+            ]
+        }"#;
+        assert_eq!(
+            serde_json::from_str::<DefaultDocMapperBuilder>(doc_mapper_two)
+                .unwrap()
+                .try_build()
+                .unwrap_err()
+                .to_string(),
+            "min / max fields are required to be fast fields. (`status` is not configured as fast)",
+        );
+
+        let doc_mapper_three = r#"{
+            "min_max_fields": ["unknown"],
+            "field_mappings": []
+        }"#;
+        assert_eq!(
+            serde_json::from_str::<DefaultDocMapperBuilder>(doc_mapper_three)
+                .unwrap()
+                .try_build()
+                .unwrap_err()
+                .to_string(),
+            "unknown min / max field: `unknown`",
+        );
+    }
+
     // See #1132
     #[test]
     fn test_by_default_store_source_is_false_and_fields_are_stored_individually() {
@@ -1985,4 +2338,262 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_max_nesting_depth() {
+        let doc_mapper: DefaultDocMapper =
+            serde_json::from_str(r#"{ "max_nesting_depth": 3 }"#).unwrap();
+        doc_mapper
+            .doc_from_json_str(r#"{ "a": { "b": 1 } }"#)
+            .unwrap();
+        doc_mapper
+            .doc_from_json_str(r#"{ "a": { "b": [1, 2] } }"#)
+            .unwrap();
+        let parsing_err = doc_mapper
+            .doc_from_json_str(r#"{ "a": { "b": [{ "c": 1 }] } }"#)
+            .unwrap_err();
+        assert_eq!(
+            parsing_err,
+            DocParsingError::TooDeeplyNested { depth: 4, limit: 3 }
+        );
+    }
+
+    #[test]
+    fn test_max_nesting_depth_default() {
+        let doc_mapper: DefaultDocMapper = serde_json::from_str("{}").unwrap();
+        assert_eq!(doc_mapper.max_nesting_depth, 100);
+
+        let deeply_nested_doc = format!("{}1{}", r#"{"a":"#.repeat(101), "}".repeat(101));
+        let parsing_err = doc_mapper
+            .doc_from_json_str(&deeply_nested_doc)
+            .unwrap_err();
+        assert_eq!(
+            parsing_err,
+            DocParsingError::TooDeeplyNested {
+                depth: 101,
+                limit: 100
+            }
+        );
+    }
+
+    #[test]
+    fn test_default_timezone() {
+        let doc_mapper: DefaultDocMapper = serde_json::from_str(
+            r#"{
+                "default_timezone": "Asia/Tokyo",
+                "field_mappings": [
+                    { "name": "ts", "type": "datetime", "input_formats": ["rfc3339"] }
+                ]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(doc_mapper.default_timezone.as_str(), "Asia/Tokyo");
+
+        let (_, doc) = doc_mapper
+            .doc_from_json_str(r#"{ "ts": "2024-01-15T12:30:00Z" }"#)
+            .unwrap();
+        let ts_field = doc_mapper.schema.get_field("ts").unwrap();
+        let ts_value = doc.get_first(ts_field).unwrap().clone();
+        // The value is stored as a UTC instant.
+        assert_eq!(
+            ts_value,
+            TantivyValue::Date(tantivy::DateTime::from_timestamp_secs(1_705_321_800))
+        );
+        let named_doc = BTreeMap::from([("ts".to_string(), vec![ts_value])]);
+        let doc_json = doc_mapper.doc_to_json(named_doc).unwrap();
+        assert_eq!(
+            JsonValue::Object(doc_json),
+            json!({ "ts": "2024-01-15T21:30:00+09:00" })
+        );
+
+        let builder = DefaultDocMapperBuilder::from(doc_mapper);
+        let doc_mapper_json = serde_json::to_value(&builder).unwrap();
+        assert_eq!(doc_mapper_json["default_timezone"], "Asia/Tokyo");
+    }
+
+    #[test]
+    fn test_default_timezone_invalid() {
+        let error = serde_json::from_str::<DefaultDocMapper>(
+            r#"{ "default_timezone": "Europe/Atlantis" }"#,
+        )
+        .map(|_| ())
+        .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("unknown timezone: `Europe/Atlantis`"));
+    }
+
+    const DOC_MAPPER_WITH_ON_FIELD_ERROR_JSON: &str = r#"{
+        "mode": "lenient",
+        "field_mappings": [
+            { "name": "body", "type": "text" },
+            { "name": "response_time", "type": "u64" },
+            { "name": "tags", "type": "array<i64>" }
+        ]
+    }"#;
+
+    #[test]
+    fn test_on_field_error_reject() {
+        let doc_mapper: DefaultDocMapper =
+            serde_json::from_str(DOC_MAPPER_WITH_ON_FIELD_ERROR_JSON).unwrap();
+        let parsing_err = doc_mapper
+            .doc_from_json_str(r#"{ "body": "hello", "response_time": "not a number" }"#)
+            .unwrap_err();
+        assert!(
+            matches!(parsing_err, DocParsingError::ValueError(field_name, _) if field_name == "response_time")
+        );
+    }
+
+    #[test]
+    fn test_on_field_error_skip() {
+        let mut doc_mapper_json: JsonValue =
+            serde_json::from_str(DOC_MAPPER_WITH_ON_FIELD_ERROR_JSON).unwrap();
+        doc_mapper_json["on_field_error"] = json!("skip");
+        let doc_mapper: DefaultDocMapper = serde_json::from_value(doc_mapper_json).unwrap();
+
+        let json_obj = json!({
+            "body": "hello",
+            "response_time": "not a number",
+            "tags": [1, "two"],
+        });
+        let (_, doc, num_dropped_fields) = doc_mapper
+            .doc_from_json_obj_with_num_dropped_fields(json_obj.as_object().unwrap().clone())
+            .unwrap();
+        assert_eq!(num_dropped_fields, 2);
+
+        let schema = doc_mapper.schema();
+        let body_field = schema.get_field("body").unwrap();
+        let response_time_field = schema.get_field("response_time").unwrap();
+        let tags_field = schema.get_field("tags").unwrap();
+        assert_eq!(doc.get_all(body_field).count(), 1);
+        assert_eq!(doc.get_all(response_time_field).count(), 0);
+        assert_eq!(doc.get_all(tags_field).count(), 0);
+    }
+
+    #[test]
+    fn test_doc_mapper_query_with_aliases() {
+        let doc_mapper: DefaultDocMapper = serde_json::from_str(
+            r#"{
+            "field_mappings": [
+                {"name": "message", "type": "text"},
+                {"name": "attributes", "type": "json"}
+            ],
+            "default_search_fields": ["msg"],
+            "aliases": {"msg": "message", "attrs": "attributes"}
+        }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            default_doc_mapper_query_aux(&doc_mapper, "msg:hello").unwrap(),
+            default_doc_mapper_query_aux(&doc_mapper, "message:hello").unwrap(),
+        );
+        assert_eq!(
+            default_doc_mapper_query_aux(&doc_mapper, "hello").unwrap(),
+            default_doc_mapper_query_aux(&doc_mapper, "message:hello").unwrap(),
+        );
+        assert_eq!(
+            default_doc_mapper_query_aux(&doc_mapper, "attrs.color:red").unwrap(),
+            default_doc_mapper_query_aux(&doc_mapper, "attributes.color:red").unwrap(),
+        );
+        assert_eq!(doc_mapper.default_search_fields(), &["msg".to_string()]);
+    }
+
+    #[test]
+    fn test_doc_mapper_aliases_validation() {
+        let error = serde_json::from_str::<DefaultDocMapper>(
+            r#"{
+            "field_mappings": [
+                {"name": "message", "type": "text"},
+                {"name": "msg", "type": "text"}
+            ],
+            "aliases": {"msg": "message"}
+        }"#,
+        )
+        .unwrap_err();
+        assert_eq!(error.to_string(), "alias `msg` collides with field `msg`");
+
+        let error = serde_json::from_str::<DefaultDocMapper>(
+            r#"{
+            "field_mappings": [{"name": "message", "type": "text"}],
+            "aliases": {"msg": "mesage"}
+        }"#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "alias `msg` points to unknown field `mesage`"
+        );
+    }
+
+    #[test]
+    fn test_doc_mapper_copy_to() {
+        let doc_mapper: DefaultDocMapper = serde_json::from_str(
+            r#"{
+            "field_mappings": [
+                {"name": "title", "type": "text", "copy_to": ["all", "all_raw"]},
+                {"name": "tags", "type": "array<text>", "tokenizer": "raw", "copy_to": ["all"]},
+                {"name": "all", "type": "array<text>", "stored": false},
+                {"name": "all_raw", "type": "array<text>", "tokenizer": "raw", "stored": false}
+            ]
+        }"#,
+        )
+        .unwrap();
+        let schema = doc_mapper.schema();
+        let (_, doc) = doc_mapper
+            .doc_from_json_str(r#"{"title": "hello world", "tags": ["red", "blue"]}"#)
+            .unwrap();
+        let all_field = schema.get_field("all").unwrap();
+        let mut all_texts: Vec<&str> = doc
+            .get_all(all_field)
+            .flat_map(|value| value.as_str())
+            .collect();
+        all_texts.sort();
+        assert_eq!(all_texts, ["blue", "hello world", "red"]);
+
+        let all_raw_field = schema.get_field("all_raw").unwrap();
+        let all_raw_texts: Vec<&str> = doc
+            .get_all(all_raw_field)
+            .flat_map(|value| value.as_str())
+            .collect();
+        assert_eq!(all_raw_texts, ["hello world"]);
+    }
+
+    #[test]
+    fn test_doc_mapper_copy_to_validation() {
+        let error = serde_json::from_str::<DefaultDocMapper>(
+            r#"{
+            "field_mappings": [{"name": "title", "type": "text", "copy_to": ["all"]}]
+        }"#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "unknown `copy_to` target field `all` for field `title`"
+        );
+
+        let error = serde_json::from_str::<DefaultDocMapper>(
+            r#"{
+            "field_mappings": [
+                {"name": "title", "type": "text", "copy_to": ["count"]},
+                {"name": "count", "type": "u64"}
+            ]
+        }"#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "`copy_to` target field `count` for field `title` must be a text field"
+        );
+
+        let error = serde_json::from_str::<DefaultDocMapper>(
+            r#"{
+            "field_mappings": [{"name": "title", "type": "text", "copy_to": ["title"]}]
+        }"#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "field `title` cannot be copied to itself"
+        );
+    }
 }
@@ -17,8 +17,11 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
+use std::collections::BTreeMap;
 use std::num::NonZeroU32;
 
+use quickwit_common::is_false;
+use quickwit_datetime::Timezone;
 use serde::{Deserialize, Serialize};
 
 use super::tokenizer_entry::TokenizerEntry;
@@ -45,6 +48,10 @@ pub struct DefaultDocMapperBuilder {
     /// Name of the fields that are searched by default, unless overridden.
     #[serde(default)]
     pub default_search_fields: Vec<String>,
+    /// Skips, with a warning, the default search fields that are not declared in the doc
+    /// mapping instead of failing to build the doc mapper.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub ignore_missing_default_search_fields: bool,
     /// Name of the field storing the timestamp of the event for time series data.
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -55,6 +62,14 @@ pub struct DefaultDocMapperBuilder {
     /// Name of the fields that are tagged.
     #[serde(default)]
     pub tag_fields: Vec<String>,
+    /// Name of the numeric fast fields whose min / max values are recorded in the split
+    /// metadata, in order to prune splits on range queries.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub min_max_fields: Vec<String>,
+    /// Alternative names of fields, mapped to the path of the field they stand for. Queries
+    /// targeting an alias are resolved to the underlying field.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub aliases: BTreeMap<String, String>,
     /// The partition key is a DSL used to route documents
     /// into specific splits.
     #[serde(default)]
@@ -62,6 +77,15 @@ pub struct DefaultDocMapperBuilder {
     /// Maximum number of partitions.
     #[serde(default = "DefaultDocMapper::default_max_num_partitions")]
     pub max_num_partitions: NonZeroU32,
+    /// Maximum nesting depth of the documents. Deeper documents are rejected.
+    #[serde(default = "DefaultDocMapper::default_max_nesting_depth")]
+    pub max_nesting_depth: usize,
+    /// Defines how a field whose value cannot be coerced to its mapped type is handled.
+    #[serde(default)]
+    pub on_field_error: OnFieldError,
+    /// Timezone in which datetime values are rendered. Datetime values are always stored in UTC.
+    #[serde(default, skip_serializing_if = "Timezone::is_utc")]
+    pub default_timezone: Timezone,
     #[serde_multikey(
         deserializer = Mode::from_parts,
         serializer = Mode::into_parts,
@@ -151,6 +175,17 @@ pub enum ModeType {
     Dynamic,
 }
 
+/// Defines how a field whose value cannot be coerced to its mapped type is handled.
+#[derive(Clone, Copy, Default, Debug, Eq, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum OnFieldError {
+    /// The whole document is rejected.
+    #[default]
+    Reject,
+    /// The field is dropped and the rest of the document is indexed.
+    Skip,
+}
+
 #[cfg(test)]
 impl Default for DefaultDocMapperBuilder {
     fn default() -> Self {
@@ -180,6 +215,8 @@ mod tests {
         assert_eq!(default_mapper_builder.mode.mode_type(), ModeType::Dynamic);
         assert_eq!(default_mapper_builder.store_source, false);
         assert!(default_mapper_builder.timestamp_field.is_none());
+        assert_eq!(default_mapper_builder.max_nesting_depth, 100);
+        assert_eq!(default_mapper_builder.on_field_error, OnFieldError::Reject);
     }
 
     #[test]
@@ -249,6 +249,9 @@ pub struct QuickwitIpAddrOptions {
     pub indexed: bool,
     #[serde(default)]
     pub fast: bool,
+    /// If true, malformed IP addresses are dropped instead of failing the whole document.
+    #[serde(default)]
+    pub ignore_malformed: bool,
 }
 
 impl Default for QuickwitIpAddrOptions {
@@ -258,6 +261,7 @@ impl Default for QuickwitIpAddrOptions {
             indexed: true,
             stored: true,
             fast: false,
+            ignore_malformed: false,
         }
     }
 }
@@ -432,6 +436,10 @@ pub struct QuickwitTextOptions {
     pub stored: bool,
     #[serde(default)]
     pub fast: FastFieldOptions,
+    /// Text fields to which the values of this field are appended at indexing time.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub copy_to: Vec<String>,
 }
 
 #[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
@@ -497,6 +505,7 @@ impl Default for QuickwitTextOptions {
             indexing_options: Some(TextIndexingOptions::default()),
             stored: true,
             fast: FastFieldOptions::default(),
+            copy_to: Vec::new(),
         }
     }
 }
@@ -1362,7 +1371,8 @@ mod tests {
                 "type": "ip",
                 "stored": true,
                 "fast": false,
-                "indexed": true
+                "indexed": true,
+                "ignore_malformed": false
             })
         );
     }
@@ -24,6 +24,7 @@ use std::str::FromStr;
 
 use anyhow::bail;
 use itertools::Itertools;
+use quickwit_datetime::Timezone;
 use serde_json::Value as JsonValue;
 use tantivy::schema::{
     BytesOptions, Field, IntoIpv6Addr, IpAddrOptions, JsonObjectOptions, NumericOptions,
@@ -38,7 +39,7 @@ use crate::default_doc_mapper::field_mapping_entry::{
     QuickwitBytesOptions, QuickwitIpAddrOptions, QuickwitNumericOptions, QuickwitObjectOptions,
     QuickwitTextOptions,
 };
-use crate::default_doc_mapper::{FieldMappingType, QuickwitJsonOptions};
+use crate::default_doc_mapper::{FieldMappingType, OnFieldError, QuickwitJsonOptions};
 use crate::{Cardinality, DocParsingError, FieldMappingEntry, ModeType};
 
 #[derive(Clone, Debug)]
@@ -100,6 +101,12 @@ impl LeafType {
             }
         }
     }
+
+    /// Returns true if the values that cannot be parsed should be dropped rather than failing the
+    /// whole document.
+    fn ignore_malformed(&self) -> bool {
+        matches!(self, LeafType::IpAddr(ip_addr_options) if ip_addr_options.ignore_malformed)
+    }
 }
 
 #[derive(Clone)]
@@ -124,23 +131,33 @@ impl MappingLeaf {
             if self.cardinality == Cardinality::SingleValue {
                 return Err(DocParsingError::MultiValuesNotSupported(path.join(".")));
             }
+            // All the values are parsed before being added so that a field is either fully
+            // indexed or not at all.
+            let mut values = Vec::with_capacity(els.len());
             for el_json_val in els {
                 if el_json_val.is_null() {
                     // We just ignore `null`.
                     continue;
                 }
-                let value = self
-                    .typ
-                    .value_from_json(el_json_val)
-                    .map_err(|err_msg| DocParsingError::ValueError(path.join("."), err_msg))?;
+                let value = match self.typ.value_from_json(el_json_val) {
+                    Ok(value) => value,
+                    Err(_) if self.typ.ignore_malformed() => continue,
+                    Err(err_msg) => {
+                        return Err(DocParsingError::ValueError(path.join("."), err_msg));
+                    }
+                };
+                values.push(value);
+            }
+            for value in values {
                 document.add_field_value(self.field, value);
             }
             return Ok(());
         }
-        let value = self
-            .typ
-            .value_from_json(json_val)
-            .map_err(|err_msg| DocParsingError::ValueError(path.join("."), err_msg))?;
+        let value = match self.typ.value_from_json(json_val) {
+            Ok(value) => value,
+            Err(_) if self.typ.ignore_malformed() => return Ok(()),
+            Err(err_msg) => return Err(DocParsingError::ValueError(path.join("."), err_msg)),
+        };
         document.add_field_value(self.field, value);
         Ok(())
     }
@@ -149,11 +166,16 @@ impl MappingLeaf {
         &'a self,
         named_doc: &mut BTreeMap<String, Vec<TantivyValue>>,
         field_path: &[&'a str],
+        timezone: &Timezone,
         doc_json: &mut serde_json::Map<String, JsonValue>,
     ) {
-        if let Some(json_val) =
-            extract_json_val(self.get_type(), named_doc, field_path, self.cardinality)
-        {
+        if let Some(json_val) = extract_json_val(
+            self.get_type(),
+            named_doc,
+            field_path,
+            self.cardinality,
+            timezone,
+        ) {
             insert_json_val(field_path, json_val, doc_json);
         }
     }
@@ -168,12 +190,13 @@ fn extract_json_val(
     named_doc: &mut BTreeMap<String, Vec<TantivyValue>>,
     field_path: &[&str],
     cardinality: Cardinality,
+    timezone: &Timezone,
 ) -> Option<JsonValue> {
     let full_path = field_path.join(".");
     let vals = named_doc.remove(&full_path)?;
     let mut vals_with_correct_type_it = vals
         .into_iter()
-        .flat_map(|value| value_to_json(value, leaf_type));
+        .flat_map(|value| value_to_json(value, leaf_type, timezone));
     match cardinality {
         Cardinality::SingleValue => vals_with_correct_type_it.next(),
         Cardinality::MultiValues => Some(JsonValue::Array(vals_with_correct_type_it.collect())),
@@ -183,8 +206,13 @@ fn extract_json_val(
 /// Converts Tantivy::Value into Json Value.
 ///
 /// Makes sure the type and value are consistent before converting.
-/// For certain LeafType, we use the type options to format the output.
-fn value_to_json(value: TantivyValue, leaf_type: &LeafType) -> Option<JsonValue> {
+/// For certain LeafType, we use the type options to format the output. Datetime values are
+/// rendered in the given timezone.
+fn value_to_json(
+    value: TantivyValue,
+    leaf_type: &LeafType,
+    timezone: &Timezone,
+) -> Option<JsonValue> {
     match (&value, leaf_type) {
         (TantivyValue::Str(_), LeafType::Text(_))
         | (TantivyValue::Bool(_), LeafType::Bool(_))
@@ -201,7 +229,7 @@ fn value_to_json(value: TantivyValue, leaf_type: &LeafType) -> Option<JsonValue>
         (TantivyValue::Date(date_time), LeafType::DateTime(date_time_options)) => {
             let json_value = date_time_options
                 .output_format
-                .format_to_json(*date_time)
+                .format_to_json(*date_time, timezone)
                 .expect("Invalid datetime is not allowed.");
             Some(json_value)
         }
@@ -401,19 +429,41 @@ impl MappingNode {
         field_mapping_entries
     }
 
+    /// Populates the document with the fields of the JSON object and returns the number of fields
+    /// that were dropped because of the [`OnFieldError::Skip`] policy.
     pub fn doc_from_json(
         &self,
         json_obj: serde_json::Map<String, JsonValue>,
         mode: ModeType,
+        on_field_error: OnFieldError,
         document: &mut Document,
         path: &mut Vec<String>,
         dynamic_json_obj: &mut serde_json::Map<String, JsonValue>,
-    ) -> Result<(), DocParsingError> {
+    ) -> Result<usize, DocParsingError> {
+        let mut num_dropped_fields = 0;
+
         for (field_name, val) in json_obj {
             if let Some(child_tree) = self.branches.get(&field_name) {
                 path.push(field_name);
-                child_tree.doc_from_json(val, mode, document, path, dynamic_json_obj)?;
+                let child_result = child_tree.doc_from_json(
+                    val,
+                    mode,
+                    on_field_error,
+                    document,
+                    path,
+                    dynamic_json_obj,
+                );
                 path.pop();
+
+                match child_result {
+                    Ok(num_child_dropped_fields) => num_dropped_fields += num_child_dropped_fields,
+                    Err(error)
+                        if on_field_error == OnFieldError::Skip && is_field_value_error(&error) =>
+                    {
+                        num_dropped_fields += 1;
+                    }
+                    Err(error) => return Err(error),
+                }
             } else {
                 match mode {
                     ModeType::Lenient => {
@@ -432,23 +482,55 @@ impl MappingNode {
                 }
             }
         }
-        Ok(())
+        Ok(num_dropped_fields)
+    }
+
+    /// Returns the text fields declaring a `copy_to` option, along with their target field
+    /// names.
+    pub fn copy_to_fields(&self) -> Vec<(Field, &[String])> {
+        let mut copy_to_fields = Vec::new();
+        for field_name in &self.branches_order {
+            match self.branches.get(field_name).expect("Missing field") {
+                MappingTree::Leaf(MappingLeaf {
+                    field,
+                    typ: LeafType::Text(text_options),
+                    ..
+                }) if !text_options.copy_to.is_empty() => {
+                    copy_to_fields.push((*field, &text_options.copy_to[..]));
+                }
+                MappingTree::Leaf(_) => {}
+                MappingTree::Node(child_node) => {
+                    copy_to_fields.extend(child_node.copy_to_fields());
+                }
+            }
+        }
+        copy_to_fields
     }
 
     pub fn populate_json<'a>(
         &'a self,
         named_doc: &mut BTreeMap<String, Vec<TantivyValue>>,
         field_path: &mut Vec<&'a str>,
+        timezone: &Timezone,
         doc_json: &mut serde_json::Map<String, JsonValue>,
     ) {
         for (field_name, field_mapping) in &self.branches {
             field_path.push(field_name);
-            field_mapping.populate_json(named_doc, field_path, doc_json);
+            field_mapping.populate_json(named_doc, field_path, timezone, doc_json);
             field_path.pop();
         }
     }
 }
 
+/// Returns true if the error is caused by a field value that cannot be coerced to its mapped
+/// type, in which case the field can be dropped under the [`OnFieldError::Skip`] policy.
+fn is_field_value_error(error: &DocParsingError) -> bool {
+    matches!(
+        error,
+        DocParsingError::ValueError(..) | DocParsingError::MultiValuesNotSupported(_)
+    )
+}
+
 impl From<MappingTree> for FieldMappingType {
     fn from(mapping_tree: MappingTree) -> Self {
         match mapping_tree {
@@ -493,17 +575,26 @@ impl MappingTree {
         &self,
         json_value: JsonValue,
         mode: ModeType,
+        on_field_error: OnFieldError,
         document: &mut Document,
         path: &mut Vec<String>,
         dynamic_json_obj: &mut serde_json::Map<String, JsonValue>,
-    ) -> Result<(), DocParsingError> {
+    ) -> Result<usize, DocParsingError> {
         match self {
             MappingTree::Leaf(mapping_leaf) => {
-                mapping_leaf.doc_from_json(json_value, document, path)
+                mapping_leaf.doc_from_json(json_value, document, path)?;
+                Ok(0)
             }
             MappingTree::Node(mapping_node) => {
                 if let JsonValue::Object(json_obj) = json_value {
-                    mapping_node.doc_from_json(json_obj, mode, document, path, dynamic_json_obj)
+                    mapping_node.doc_from_json(
+                        json_obj,
+                        mode,
+                        on_field_error,
+                        document,
+                        path,
+                        dynamic_json_obj,
+                    )
                 } else {
                     Err(DocParsingError::ValueError(
                         path.join("."),
@@ -518,14 +609,15 @@ impl MappingTree {
         &'a self,
         named_doc: &mut BTreeMap<String, Vec<TantivyValue>>,
         field_path: &mut Vec<&'a str>,
+        timezone: &Timezone,
         doc_json: &mut serde_json::Map<String, JsonValue>,
     ) {
         match self {
             MappingTree::Leaf(mapping_leaf) => {
-                mapping_leaf.populate_json(named_doc, field_path, doc_json)
+                mapping_leaf.populate_json(named_doc, field_path, timezone, doc_json)
             }
             MappingTree::Node(mapping_node) => {
-                mapping_node.populate_json(named_doc, field_path, doc_json);
+                mapping_node.populate_json(named_doc, field_path, timezone, doc_json);
             }
         }
     }
@@ -791,8 +883,9 @@ fn build_mapping_from_field_type<'a>(
 
 #[cfg(test)]
 mod tests {
-    use std::net::IpAddr;
+    use std::net::{IpAddr, Ipv6Addr};
 
+    use quickwit_datetime::Timezone;
     use serde_json::{json, Value as JsonValue};
     use tantivy::schema::{Field, IntoIpv6Addr, OwnedValue as TantivyValue, Value};
     use tantivy::{DateTime, TantivyDocument as Document};
@@ -805,7 +898,7 @@ mod tests {
         BinaryFormat, NumericOutputFormat, QuickwitBoolOptions, QuickwitBytesOptions,
         QuickwitIpAddrOptions, QuickwitNumericOptions, QuickwitTextOptions,
     };
-    use crate::Cardinality;
+    use crate::{Cardinality, DocParsingError};
 
     #[test]
     fn test_field_name_from_field_path() {
@@ -1024,6 +1117,53 @@ mod tests {
         assert!(err.contains("expected string value, got `1200`"));
     }
 
+    #[test]
+    fn test_parse_ip_addr_ignore_malformed() {
+        let field = Field::from_field_id(10);
+        let leaf_entry = MappingLeaf {
+            field,
+            typ: LeafType::IpAddr(QuickwitIpAddrOptions::default()),
+            cardinality: Cardinality::MultiValues,
+        };
+        let mut document = Document::default();
+        let mut path = vec!["ip".to_string()];
+        let error = leaf_entry
+            .doc_from_json(json!(["10.0.0.1", "foo"]), &mut document, &mut path)
+            .unwrap_err();
+        assert!(matches!(error, DocParsingError::ValueError(field_path, _) if field_path == "ip"));
+        assert_eq!(document.len(), 0);
+
+        let leaf_entry = MappingLeaf {
+            field,
+            typ: LeafType::IpAddr(QuickwitIpAddrOptions {
+                ignore_malformed: true,
+                ..Default::default()
+            }),
+            cardinality: Cardinality::MultiValues,
+        };
+        leaf_entry
+            .doc_from_json(
+                json!(["10.0.0.1", "foo", 1200, "2001:db8::1"]),
+                &mut document,
+                &mut path,
+            )
+            .unwrap();
+        leaf_entry
+            .doc_from_json(json!("10.0.0.300"), &mut document, &mut path)
+            .unwrap();
+        let ip_addrs: Vec<Ipv6Addr> = document
+            .get_all(field)
+            .flat_map(|val| (&val).as_ip_addr())
+            .collect();
+        assert_eq!(
+            ip_addrs,
+            [
+                "::ffff:10.0.0.1".parse::<Ipv6Addr>().unwrap(),
+                "2001:db8::1".parse::<Ipv6Addr>().unwrap()
+            ]
+        );
+    }
+
     #[test]
     fn test_parse_i64_mutivalued() {
         let typ = LeafType::I64(QuickwitNumericOptions::default());
@@ -1267,7 +1407,8 @@ mod tests {
         assert_eq!(
             value_to_json(
                 TantivyValue::Bytes(vec![1, 2, 3]),
-                &LeafType::Bytes(bytes_options_base64)
+                &LeafType::Bytes(bytes_options_base64),
+                &Timezone::UTC
             )
             .unwrap(),
             serde_json::json!("AQID")
@@ -1280,7 +1421,8 @@ mod tests {
         assert_eq!(
             value_to_json(
                 TantivyValue::Bytes(vec![1, 2, 3]),
-                &LeafType::Bytes(bytes_options_hex)
+                &LeafType::Bytes(bytes_options_hex),
+                &Timezone::UTC
             )
             .unwrap(),
             serde_json::json!("010203")
@@ -1293,7 +1435,8 @@ mod tests {
         assert_eq!(
             value_to_json(
                 TantivyValue::F64(0.1),
-                &LeafType::F64(numeric_options_number)
+                &LeafType::F64(numeric_options_number),
+                &Timezone::UTC
             )
             .unwrap(),
             serde_json::json!(0.1)
@@ -1304,7 +1447,12 @@ mod tests {
             ..Default::default()
         };
         assert_eq!(
-            value_to_json(TantivyValue::F64(0.1), &LeafType::F64(numeric_options_str)).unwrap(),
+            value_to_json(
+                TantivyValue::F64(0.1),
+                &LeafType::F64(numeric_options_str),
+                &Timezone::UTC
+            )
+            .unwrap(),
             serde_json::json!("0.1")
         );
     }
@@ -1315,7 +1463,8 @@ mod tests {
         assert_eq!(
             value_to_json(
                 TantivyValue::I64(-1),
-                &LeafType::I64(numeric_options_number)
+                &LeafType::I64(numeric_options_number),
+                &Timezone::UTC
             )
             .unwrap(),
             serde_json::json!(-1)
@@ -1326,7 +1475,12 @@ mod tests {
             ..Default::default()
         };
         assert_eq!(
-            value_to_json(TantivyValue::I64(-1), &LeafType::I64(numeric_options_str)).unwrap(),
+            value_to_json(
+                TantivyValue::I64(-1),
+                &LeafType::I64(numeric_options_str),
+                &Timezone::UTC
+            )
+            .unwrap(),
             serde_json::json!("-1")
         );
     }
@@ -1335,7 +1489,12 @@ mod tests {
     fn test_tantivy_value_to_json_value_u64() {
         let numeric_options_number = QuickwitNumericOptions::default();
         assert_eq!(
-            value_to_json(TantivyValue::U64(1), &LeafType::U64(numeric_options_number)).unwrap(),
+            value_to_json(
+                TantivyValue::U64(1),
+                &LeafType::U64(numeric_options_number),
+                &Timezone::UTC
+            )
+            .unwrap(),
             serde_json::json!(1u64)
         );
 
@@ -1344,11 +1503,41 @@ mod tests {
             ..Default::default()
         };
         assert_eq!(
-            value_to_json(TantivyValue::U64(1), &LeafType::U64(numeric_options_str)).unwrap(),
+            value_to_json(
+                TantivyValue::U64(1),
+                &LeafType::U64(numeric_options_str),
+                &Timezone::UTC
+            )
+            .unwrap(),
             serde_json::json!("1")
         );
     }
 
+    #[test]
+    fn test_tantivy_value_to_json_value_date_time() {
+        let date_time = DateTime::from_utc(datetime!(2024-01-15 12:30:00 UTC));
+        let date_time_options = QuickwitDateTimeOptions::default();
+        assert_eq!(
+            value_to_json(
+                TantivyValue::Date(date_time),
+                &LeafType::DateTime(date_time_options.clone()),
+                &Timezone::UTC
+            )
+            .unwrap(),
+            serde_json::json!("2024-01-15T12:30:00Z")
+        );
+        let timezone: Timezone = "America/New_York".parse().unwrap();
+        assert_eq!(
+            value_to_json(
+                TantivyValue::Date(date_time),
+                &LeafType::DateTime(date_time_options),
+                &timezone
+            )
+            .unwrap(),
+            serde_json::json!("2024-01-15T07:30:00-05:00")
+        );
+    }
+
     #[test]
     fn test_field_path_for_field_name() {
         assert_eq!(super::build_field_path_from_str(""), Vec::<String>::new());
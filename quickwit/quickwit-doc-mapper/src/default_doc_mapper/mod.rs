@@ -30,7 +30,7 @@ use once_cell::sync::Lazy;
 use regex::Regex;
 
 pub use self::default_mapper::DefaultDocMapper;
-pub use self::default_mapper_builder::{DefaultDocMapperBuilder, Mode, ModeType};
+pub use self::default_mapper_builder::{DefaultDocMapperBuilder, Mode, ModeType, OnFieldError};
 pub use self::field_mapping_entry::{
     BinaryFormat, FastFieldOptions, FieldMappingEntry, QuickwitBytesOptions, QuickwitJsonOptions,
     QuickwitNumericOptions, QuickwitTextNormalizer, QuickwitTextOptions, TextIndexingOptions,
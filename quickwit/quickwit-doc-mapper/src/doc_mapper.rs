@@ -54,6 +54,16 @@ pub trait DocMapper: Send + Sync + Debug + DynClone + 'static {
         json_obj: JsonObject,
     ) -> Result<(Partition, Document), DocParsingError>;
 
+    /// Transforms a JSON object into a tantivy [`Document`] and also returns the number of fields
+    /// that were dropped because their value could not be coerced to their mapped type.
+    fn doc_from_json_obj_with_num_dropped_fields(
+        &self,
+        json_obj: JsonObject,
+    ) -> Result<(Partition, Document, usize), DocParsingError> {
+        let (partition, document) = self.doc_from_json_obj(json_obj)?;
+        Ok((partition, document, 0))
+    }
+
     /// Parses a JSON byte slice into a tantivy [`Document`].
     fn doc_from_json_bytes(
         &self,
@@ -126,20 +136,19 @@ pub trait DocMapper: Send + Sync + Debug + DynClone + 'static {
     /// Returns the tag `NameField`s on the current schema.
     /// Returns an error if a tag field is not found in this schema.
     fn tag_named_fields(&self) -> anyhow::Result<Vec<NamedField>> {
-        let index_schema = self.schema();
-        self.tag_field_names()
-            .iter()
-            .map(|field_name| {
-                index_schema
-                    .get_field(field_name)
-                    .context(format!("field `{field_name}` must exist in the schema"))
-                    .map(|field| NamedField {
-                        name: field_name.clone(),
-                        field,
-                        field_type: index_schema.get_field_entry(field).field_type().clone(),
-                    })
-            })
-            .collect::<Result<Vec<_>, _>>()
+        named_fields(&self.schema(), &self.tag_field_names())
+    }
+
+    /// Returns the names of the numeric fast fields whose min / max values are recorded in the
+    /// split metadata.
+    fn min_max_field_names(&self) -> BTreeSet<String> {
+        Default::default()
+    }
+
+    /// Returns the min / max `NamedField`s on the current schema.
+    /// Returns an error if a min / max field is not found in this schema.
+    fn min_max_named_fields(&self) -> anyhow::Result<Vec<NamedField>> {
+        named_fields(&self.schema(), &self.min_max_field_names())
     }
 
     /// Returns the maximum number of partitions.
@@ -149,6 +158,25 @@ pub trait DocMapper: Send + Sync + Debug + DynClone + 'static {
     fn tokenizer_manager(&self) -> &TokenizerManager;
 }
 
+fn named_fields(
+    index_schema: &Schema,
+    field_names: &BTreeSet<String>,
+) -> anyhow::Result<Vec<NamedField>> {
+    field_names
+        .iter()
+        .map(|field_name| {
+            index_schema
+                .get_field(field_name)
+                .context(format!("field `{field_name}` must exist in the schema"))
+                .map(|field| NamedField {
+                    name: field_name.clone(),
+                    field,
+                    field_type: index_schema.get_field_entry(field).field_type().clone(),
+                })
+        })
+        .collect::<Result<Vec<_>, _>>()
+}
+
 /// A struct to wrap a tantivy field with its name.
 #[derive(Clone, Debug)]
 pub struct NamedField {
@@ -182,7 +210,8 @@ pub struct WarmupInfo {
     pub term_dict_fields: HashSet<Field>,
     /// Name of fast fields which needs to be loaded
     pub fast_field_names: HashSet<String>,
-    /// Whether to warmup field norms. Used mostly for scoring.
+    /// Whether to warmup field norms. Used mostly for scoring. Only the field norms of the fields
+    /// targeted by the query terms are warmed up, see [`WarmupInfo::field_norms_fields`].
     pub field_norms: bool,
     /// Terms to warmup, and whether their position is needed too.
     pub terms_grouped_by_field: HashMap<Field, HashMap<Term, bool>>,
@@ -215,6 +244,22 @@ impl WarmupInfo {
         }
     }
 
+    /// Returns the fields whose field norms need to be warmed up.
+    ///
+    /// Field norms are only read to score the documents matching a term, so only the fields
+    /// targeted by the query terms are returned, and none if scoring is not required.
+    pub fn field_norms_fields(&self) -> HashSet<Field> {
+        if !self.field_norms {
+            return HashSet::new();
+        }
+        self.term_dict_fields
+            .iter()
+            .chain(self.terms_grouped_by_field.keys())
+            .chain(self.term_ranges_grouped_by_field.keys())
+            .copied()
+            .collect()
+    }
+
     /// Simplify a WarmupInfo, removing some redundant tasks
     pub fn simplify(&mut self) {
         self.terms_grouped_by_field.retain(|field, terms| {
@@ -570,6 +615,28 @@ mod tests {
         assert_eq!(warmup_info, expected);
     }
 
+    #[test]
+    fn test_warmup_info_field_norms_fields() {
+        let mut warmup_info = WarmupInfo {
+            term_dict_fields: hashset_field(&[1]),
+            fast_field_names: hashset(&["fast1"]),
+            field_norms: false,
+            terms_grouped_by_field: hashmap(&[(2, "term1", false)]),
+            term_ranges_grouped_by_field: hashmap_ranges(&[(3, "term2", false)]),
+        };
+        assert!(warmup_info.field_norms_fields().is_empty());
+
+        warmup_info.field_norms = true;
+        assert_eq!(warmup_info.field_norms_fields(), hashset_field(&[1, 2, 3]));
+
+        let warmup_info = WarmupInfo {
+            fast_field_names: hashset(&["fast1"]),
+            field_norms: true,
+            ..WarmupInfo::default()
+        };
+        assert!(warmup_info.field_norms_fields().is_empty());
+    }
+
     #[test]
     #[cfg(feature = "testsuite")]
     fn test_doc_mapper_query_with_multilang_field() {
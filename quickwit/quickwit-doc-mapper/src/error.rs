@@ -57,6 +57,14 @@ pub enum DocParsingError {
     /// The document does not contain a field that is required.
     #[error("the document must contain field {0:?}")]
     RequiredField(String),
+    /// The document is nested deeper than the configured limit.
+    #[error("the document has a nesting depth of {depth}, which exceeds the limit of {limit}")]
+    TooDeeplyNested {
+        /// Nesting depth of the document.
+        depth: usize,
+        /// Maximum nesting depth allowed by the doc mapper.
+        limit: usize,
+    },
 }
 
 impl From<TantivyDocParsingError> for DocParsingError {
@@ -30,14 +30,15 @@ mod doc_mapper;
 mod error;
 mod query_builder;
 mod routing_expression;
+mod schema_fields;
 
 /// Pruning tags manipulation.
 pub mod tag_pruning;
 
 pub use default_doc_mapper::{
     analyze_text, BinaryFormat, DefaultDocMapper, DefaultDocMapperBuilder, FieldMappingEntry,
-    FieldMappingType, Mode, ModeType, QuickwitBytesOptions, QuickwitJsonOptions, TokenizerConfig,
-    TokenizerEntry,
+    FieldMappingType, Mode, ModeType, OnFieldError, QuickwitBytesOptions, QuickwitJsonOptions,
+    TokenizerConfig, TokenizerEntry,
 };
 use default_doc_mapper::{
     FastFieldOptions, FieldMappingEntryForSerialization, IndexRecordOptionSchema,
@@ -47,6 +48,7 @@ use default_doc_mapper::{
 pub use doc_mapper::{DocMapper, JsonObject, NamedField, TermRange, WarmupInfo};
 pub use error::{DocParsingError, QueryParserError};
 use quickwit_common::shared_consts::FIELD_PRESENCE_FIELD_NAME;
+pub use schema_fields::{schema_fields, SchemaField};
 
 /// Field name reserved for storing the source document.
 pub const SOURCE_FIELD_NAME: &str = "_source";
@@ -77,10 +79,12 @@ pub enum Cardinality {
     IndexRecordOptionSchema,
     ModeType,
     NgramTokenizerOption,
+    OnFieldError,
     QuickwitJsonOptions,
     QuickwitTextNormalizer,
     QuickwitTextTokenizer,
     RegexTokenizerOption,
+    SchemaField,
     TokenFilterType,
     TokenizerConfig,
     TokenizerEntry,
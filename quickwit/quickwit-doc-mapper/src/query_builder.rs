@@ -17,13 +17,13 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::convert::Infallible;
 use std::ops::Bound;
 
 use quickwit_query::query_ast::{
-    FieldPresenceQuery, FullTextQuery, PhrasePrefixQuery, QueryAst, QueryAstVisitor, RangeQuery,
-    TermSetQuery, WildcardQuery,
+    BoolQuery, ConstantScoreQuery, DisMaxQuery, FieldPresenceQuery, FullTextQuery,
+    PhrasePrefixQuery, QueryAst, QueryAstVisitor, RangeQuery, TermSetQuery, WildcardQuery,
 };
 use quickwit_query::tokenizers::TokenizerManager;
 use quickwit_query::{find_field_or_hit_dynamic, InvalidQuery};
@@ -125,6 +125,103 @@ pub(crate) fn build_query(
     Ok((query, warmup_info))
 }
 
+/// Returns the field path an alias stands for, or the field path itself if it is not an alias.
+/// The subpaths of an alias, for instance `alias.key` for an alias of a JSON field, are resolved
+/// as well.
+pub(crate) fn resolve_field_alias(field_path: &str, aliases: &BTreeMap<String, String>) -> String {
+    if let Some(resolved_field_path) = aliases.get(field_path) {
+        return resolved_field_path.clone();
+    }
+    for (alias, resolved_field_path) in aliases {
+        if let Some(subpath) = field_path
+            .strip_prefix(alias.as_str())
+            .and_then(|suffix| suffix.strip_prefix('.'))
+        {
+            return format!("{resolved_field_path}.{subpath}");
+        }
+    }
+    field_path.to_string()
+}
+
+/// Rewrites the fields targeted by the query that are aliases into the fields they stand for.
+pub(crate) fn resolve_field_aliases(
+    query_ast: QueryAst,
+    aliases: &BTreeMap<String, String>,
+) -> QueryAst {
+    let resolve_all = |query_asts: Vec<QueryAst>| -> Vec<QueryAst> {
+        query_asts
+            .into_iter()
+            .map(|query_ast| resolve_field_aliases(query_ast, aliases))
+            .collect()
+    };
+    match query_ast {
+        QueryAst::Bool(bool_query) => QueryAst::Bool(BoolQuery {
+            must: resolve_all(bool_query.must),
+            must_not: resolve_all(bool_query.must_not),
+            should: resolve_all(bool_query.should),
+            filter: resolve_all(bool_query.filter),
+            ..bool_query
+        }),
+        QueryAst::DisMax(dis_max_query) => QueryAst::DisMax(DisMaxQuery {
+            disjuncts: resolve_all(dis_max_query.disjuncts),
+            ..dis_max_query
+        }),
+        QueryAst::ConstantScore(constant_score_query) => {
+            QueryAst::ConstantScore(ConstantScoreQuery {
+                filter: Box::new(resolve_field_aliases(*constant_score_query.filter, aliases)),
+                ..constant_score_query
+            })
+        }
+        QueryAst::Boost { underlying, boost } => QueryAst::Boost {
+            underlying: Box::new(resolve_field_aliases(*underlying, aliases)),
+            boost,
+        },
+        QueryAst::Term(mut term_query) => {
+            term_query.field = resolve_field_alias(&term_query.field, aliases);
+            QueryAst::Term(term_query)
+        }
+        QueryAst::TermSet(term_set_query) => {
+            let mut terms_per_field: HashMap<String, BTreeSet<String>> = HashMap::new();
+            for (field, terms) in term_set_query.terms_per_field {
+                terms_per_field
+                    .entry(resolve_field_alias(&field, aliases))
+                    .or_default()
+                    .extend(terms);
+            }
+            QueryAst::TermSet(TermSetQuery { terms_per_field })
+        }
+        QueryAst::FieldPresence(mut field_presence_query) => {
+            field_presence_query.field = resolve_field_alias(&field_presence_query.field, aliases);
+            QueryAst::FieldPresence(field_presence_query)
+        }
+        QueryAst::FullText(mut full_text_query) => {
+            full_text_query.field = resolve_field_alias(&full_text_query.field, aliases);
+            QueryAst::FullText(full_text_query)
+        }
+        QueryAst::PhrasePrefix(mut phrase_prefix_query) => {
+            phrase_prefix_query.field = resolve_field_alias(&phrase_prefix_query.field, aliases);
+            QueryAst::PhrasePrefix(phrase_prefix_query)
+        }
+        QueryAst::Range(mut range_query) => {
+            range_query.field = resolve_field_alias(&range_query.field, aliases);
+            QueryAst::Range(range_query)
+        }
+        QueryAst::Wildcard(mut wildcard_query) => {
+            wildcard_query.field = resolve_field_alias(&wildcard_query.field, aliases);
+            QueryAst::Wildcard(wildcard_query)
+        }
+        QueryAst::UserInput(mut user_input_query) => {
+            if let Some(default_fields) = user_input_query.default_fields.as_mut() {
+                for default_field in default_fields {
+                    *default_field = resolve_field_alias(default_field, aliases);
+                }
+            }
+            QueryAst::UserInput(user_input_query)
+        }
+        QueryAst::MatchAll | QueryAst::MatchNone => query_ast,
+    }
+}
+
 fn is_fast_field(schema: &Schema, field_name: &str) -> bool {
     if let Ok((_field, field_entry, _path)) = find_field_or_hit_dynamic(field_name, schema) {
         return field_entry.is_fast();
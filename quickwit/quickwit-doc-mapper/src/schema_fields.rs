@@ -0,0 +1,120 @@
+// Copyright (C) 2024 Quickwit, Inc.
+//
+// Quickwit is offered under the AGPL v3.0 and as commercial software.
+// For commercial licensing, contact us at hello@quickwit.io.
+//
+// AGPL:
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as
+// published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+use tantivy::schema::{FieldType, Schema, TextFieldIndexing};
+
+/// Describes a field of the tantivy schema resolved by a doc mapper, along with its indexing
+/// options.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct SchemaField {
+    /// Name of the field.
+    pub name: String,
+    /// Tantivy value type of the field (`str`, `u64`, `date`, `json`, ...).
+    #[serde(rename = "type")]
+    pub field_type: String,
+    /// Whether the field is indexed.
+    pub indexed: bool,
+    /// Whether the field is stored.
+    pub stored: bool,
+    /// Whether the field is a fast field.
+    pub fast: bool,
+    /// Name of the tokenizer used to index the field, if any.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tokenizer: Option<String>,
+}
+
+/// Lists the fields of a tantivy schema, in the order they were declared, reserved fields
+/// included.
+pub fn schema_fields(schema: &Schema) -> Vec<SchemaField> {
+    schema
+        .fields()
+        .map(|(_, field_entry)| {
+            let field_type = field_entry.field_type();
+            let tokenizer = match field_type {
+                FieldType::Str(options) => options.get_indexing_options(),
+                FieldType::JsonObject(options) => options.get_text_indexing_options(),
+                _ => None,
+            }
+            .map(|text_options: &TextFieldIndexing| text_options.tokenizer().to_string());
+            SchemaField {
+                name: field_entry.name().to_string(),
+                field_type: field_type.value_type().name().to_lowercase(),
+                indexed: field_entry.is_indexed(),
+                stored: field_entry.is_stored(),
+                fast: field_entry.is_fast(),
+                tokenizer,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use tantivy::schema::{Schema, FAST, INDEXED, STORED, STRING, TEXT};
+
+    use super::{schema_fields, SchemaField};
+
+    #[test]
+    fn test_schema_fields() {
+        let mut schema_builder = Schema::builder();
+        schema_builder.add_u64_field("_field_presence", INDEXED);
+        schema_builder.add_text_field("body", TEXT | STORED);
+        schema_builder.add_text_field("service", STRING | FAST);
+        schema_builder.add_json_field("_source", STORED);
+        let schema = schema_builder.build();
+
+        let expected_fields = vec![
+            SchemaField {
+                name: "_field_presence".to_string(),
+                field_type: "u64".to_string(),
+                indexed: true,
+                stored: false,
+                fast: false,
+                tokenizer: None,
+            },
+            SchemaField {
+                name: "body".to_string(),
+                field_type: "str".to_string(),
+                indexed: true,
+                stored: true,
+                fast: false,
+                tokenizer: Some("default".to_string()),
+            },
+            SchemaField {
+                name: "service".to_string(),
+                field_type: "str".to_string(),
+                indexed: true,
+                stored: false,
+                fast: true,
+                tokenizer: Some("raw".to_string()),
+            },
+            SchemaField {
+                name: "_source".to_string(),
+                field_type: "json".to_string(),
+                indexed: false,
+                stored: true,
+                fast: false,
+                tokenizer: None,
+            },
+        ];
+        assert_eq!(schema_fields(&schema), expected_fields);
+    }
+}
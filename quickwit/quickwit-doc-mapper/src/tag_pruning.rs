@@ -61,6 +61,14 @@ fn extract_unsimplified_tags_filter_ast(query_ast: QueryAst) -> UnsimplifiedTagF
             }
             collect_tag_filters_for_clause(clause_with_resolved_occur)
         }
+        QueryAst::DisMax(dis_max_query) => {
+            let children: Vec<UnsimplifiedTagFilterAst> = dis_max_query
+                .disjuncts
+                .into_iter()
+                .map(extract_unsimplified_tags_filter_ast)
+                .collect();
+            UnsimplifiedTagFilterAst::Or(children)
+        }
         QueryAst::Term(term_query) => UnsimplifiedTagFilterAst::Tag {
             is_present: true,
             field: term_query.field,
@@ -114,6 +122,9 @@ fn extract_unsimplified_tags_filter_ast(query_ast: QueryAst) -> UnsimplifiedTagF
             }
         }
         QueryAst::Boost { underlying, .. } => extract_unsimplified_tags_filter_ast(*underlying),
+        QueryAst::ConstantScore(constant_score_query) => {
+            extract_unsimplified_tags_filter_ast(*constant_score_query.filter)
+        }
         QueryAst::UserInput(_user_text_query) => {
             panic!("Extract unsimplified should only be called on AST without UserInputQuery.");
         }
@@ -19,24 +19,26 @@
 
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
 
 use futures::Future;
 use quickwit_common::{PrettySample, Progress, ServiceStream};
 use quickwit_metastore::{
-    ListSplitsQuery, ListSplitsRequestExt, MetastoreServiceStreamSplitsExt, SplitInfo,
-    SplitMetadata, SplitState,
+    IndexMetadata, ListSplitsQuery, ListSplitsRequestExt, MetastoreServiceStreamSplitsExt,
+    SplitInfo, SplitMetadata, SplitState,
 };
 use quickwit_proto::metastore::{
     DeleteSplitsRequest, ListSplitsRequest, ListSplitsResponse, MarkSplitsForDeletionRequest,
     MetastoreError, MetastoreResult, MetastoreService, MetastoreServiceClient,
 };
 use quickwit_proto::types::{IndexUid, SplitId};
-use quickwit_storage::{BulkDeleteError, Storage};
+use quickwit_storage::{BulkDeleteError, Storage, StorageErrorKind};
 use thiserror::Error;
 use time::OffsetDateTime;
-use tracing::{error, instrument};
+use tracing::{debug, error, info, instrument};
+use ulid::Ulid;
 
 /// The maximum number of splits that the GC should delete per attempt.
 const DELETE_SPLITS_BATCH_SIZE: usize = 1000;
@@ -164,6 +166,137 @@ pub async fn run_garbage_collect(
 
     Ok(deleted_splits)
 }
+/// Deletes the split files present in the storage of an index but not recorded in the metastore,
+/// which are typically left behind by crashed uploads. Returns the paths of the deleted files, or
+/// of the files that would be deleted if `dry_run` is true.
+///
+/// The split files stored under a split path prefix, such as `2024/03`, are considered too. When
+/// several indexes share the storage URI of the index, a split file is deleted only if none of them
+/// records its split, and the files located under the URI of an index nested in the index storage
+/// are left untouched. A split file is deleted only if its split ID was generated more than
+/// `grace_period` ago. The storages that do not support listing files are skipped.
+///
+/// `indexes_metadata` holds the metadata of all the indexes, so that callers garbage collecting
+/// several indexes list them only once. The list may predate the call: the indexes created
+/// afterwards only hold split files younger than the grace period.
+pub async fn delete_orphan_split_files(
+    index_uid: IndexUid,
+    storage: Arc<dyn Storage>,
+    mut metastore: MetastoreServiceClient,
+    indexes_metadata: &[IndexMetadata],
+    grace_period: Duration,
+    dry_run: bool,
+    progress_opt: Option<&Progress>,
+) -> anyhow::Result<Vec<PathBuf>> {
+    // The files must be listed before the indexes and the splits: a split is staged in the
+    // metastore before its file is uploaded, so a listed file whose split is not found afterwards
+    // is truly orphaned.
+    let file_paths = match protect_future(progress_opt, storage.list(Path::new(""))).await {
+        Ok(file_paths) => file_paths,
+        Err(storage_error) if storage_error.kind() == StorageErrorKind::Unsupported => {
+            debug!(
+                index_id = index_uid.index_id(),
+                "skipping orphan split files deletion: storage does not support listing files"
+            );
+            return Ok(Vec::new());
+        }
+        Err(storage_error) => return Err(storage_error.into()),
+    };
+    let grace_period_timestamp_millis = (OffsetDateTime::now_utc().unix_timestamp() as u64 * 1_000)
+        .saturating_sub(grace_period.as_millis() as u64);
+
+    let candidate_split_files: Vec<(SplitId, PathBuf)> = file_paths
+        .into_iter()
+        .filter_map(|file_path| {
+            if file_path
+                .extension()
+                .and_then(|extension| extension.to_str())
+                != Some("split")
+            {
+                return None;
+            }
+            let split_id = file_path.file_stem()?.to_str()?.to_string();
+            let split_ulid = Ulid::from_str(&split_id).ok()?;
+
+            if split_ulid.timestamp_ms() > grace_period_timestamp_millis {
+                return None;
+            }
+            Some((split_id, file_path))
+        })
+        .collect();
+
+    if candidate_split_files.is_empty() {
+        return Ok(Vec::new());
+    }
+    let Some(index_uri) = indexes_metadata
+        .iter()
+        .find(|index_metadata| index_metadata.index_uid == index_uid)
+        .map(|index_metadata| index_metadata.index_uri().as_str().trim_end_matches('/'))
+    else {
+        // The index was deleted in the meantime.
+        return Ok(Vec::new());
+    };
+    // The indexes sharing the storage URI of the index, including the index itself.
+    let mut sharing_index_uids: Vec<IndexUid> = Vec::new();
+    // The paths, relative to the index storage, of the indexes nested in the index storage.
+    let mut nested_index_paths: Vec<PathBuf> = Vec::new();
+
+    for index_metadata in indexes_metadata {
+        let other_index_uri = index_metadata.index_uri().as_str().trim_end_matches('/');
+
+        if other_index_uri == index_uri {
+            sharing_index_uids.push(index_metadata.index_uid.clone());
+        } else if let Some(nested_index_path) = other_index_uri
+            .strip_prefix(index_uri)
+            .and_then(|suffix| suffix.strip_prefix('/'))
+        {
+            nested_index_paths.push(PathBuf::from(nested_index_path));
+        }
+    }
+    let candidate_split_files: Vec<(SplitId, PathBuf)> = candidate_split_files
+        .into_iter()
+        .filter(|(_, file_path)| {
+            !nested_index_paths
+                .iter()
+                .any(|nested_index_path| file_path.starts_with(nested_index_path))
+        })
+        .collect();
+
+    if candidate_split_files.is_empty() {
+        return Ok(Vec::new());
+    }
+    let list_splits_query = ListSplitsQuery::try_from_index_uids(sharing_index_uids)?;
+    let list_splits_request = ListSplitsRequest::try_from_list_splits_query(list_splits_query)?;
+    let known_split_ids: HashSet<SplitId> =
+        protect_future(progress_opt, metastore.list_splits(list_splits_request))
+            .await?
+            .collect_splits_metadata()
+            .await?
+            .into_iter()
+            .map(|split_metadata| split_metadata.split_id)
+            .collect();
+    let orphan_file_paths: Vec<PathBuf> = candidate_split_files
+        .into_iter()
+        .filter(|(split_id, _)| !known_split_ids.contains(split_id))
+        .map(|(_, file_path)| file_path)
+        .collect();
+
+    if dry_run || orphan_file_paths.is_empty() {
+        return Ok(orphan_file_paths);
+    }
+    let orphan_file_path_refs: Vec<&Path> =
+        orphan_file_paths.iter().map(PathBuf::as_path).collect();
+    protect_future(progress_opt, storage.bulk_delete(&orphan_file_path_refs)).await?;
+
+    info!(
+        index_id = index_uid.index_id(),
+        num_deleted_files = orphan_file_paths.len(),
+        "deleted orphan split file(s) {:?}",
+        PrettySample::new(&orphan_file_path_refs, 5),
+    );
+    Ok(orphan_file_paths)
+}
+
 #[instrument(skip(storage, metastore, progress_opt))]
 /// Removes any splits marked for deletion which haven't been
 /// updated after `updated_before_timestamp` in batches of 1000 splits.
@@ -354,10 +487,12 @@ mod tests {
     use quickwit_common::ServiceStream;
     use quickwit_config::IndexConfig;
     use quickwit_metastore::{
-        metastore_for_test, CreateIndexRequestExt, ListSplitsQuery,
+        metastore_for_test, CreateIndexRequestExt, ListIndexesMetadataResponseExt, ListSplitsQuery,
         MetastoreServiceStreamSplitsExt, SplitMetadata, SplitState, StageSplitsRequestExt,
     };
-    use quickwit_proto::metastore::{CreateIndexRequest, EntityKind, StageSplitsRequest};
+    use quickwit_proto::metastore::{
+        CreateIndexRequest, EntityKind, ListIndexesMetadataRequest, StageSplitsRequest,
+    };
     use quickwit_proto::types::IndexUid;
     use quickwit_storage::{
         storage_for_test, BulkDeleteError, DeleteFailure, MockStorage, PutPayload,
@@ -821,4 +956,229 @@ mod tests {
         assert_eq!(error.storage_failures.len(), 1);
         assert_eq!(error.metastore_failures.len(), 1);
     }
+
+    async fn list_all_indexes_metadata(
+        mut metastore: MetastoreServiceClient,
+    ) -> Vec<IndexMetadata> {
+        metastore
+            .list_indexes_metadata(ListIndexesMetadataRequest::all())
+            .await
+            .unwrap()
+            .deserialize_indexes_metadata()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_delete_orphan_split_files() {
+        let storage = storage_for_test();
+        let mut metastore = metastore_for_test();
+
+        let index_id = "test-delete-orphan-split-files--index";
+        let index_uri = format!("ram:///indexes/{index_id}");
+        let index_config = IndexConfig::for_test(index_id, &index_uri);
+        let create_index_request = CreateIndexRequest::try_from_index_config(index_config).unwrap();
+        let index_uid: IndexUid = metastore
+            .create_index(create_index_request)
+            .await
+            .unwrap()
+            .index_uid
+            .into();
+
+        let two_days_ago_millis =
+            (OffsetDateTime::now_utc().unix_timestamp() as u64 - 2 * 24 * 60 * 60) * 1_000;
+        let staged_split_id = Ulid::from_parts(two_days_ago_millis, 1).to_string();
+        let orphan_split_id = Ulid::from_parts(two_days_ago_millis, 2).to_string();
+        let prefixed_staged_split_id = Ulid::from_parts(two_days_ago_millis, 3).to_string();
+        let prefixed_orphan_split_id = Ulid::from_parts(two_days_ago_millis, 4).to_string();
+        let recent_split_id = Ulid::new().to_string();
+
+        let split_metadata = SplitMetadata {
+            split_id: staged_split_id.clone(),
+            index_uid: index_uid.clone(),
+            ..Default::default()
+        };
+        let prefixed_split_metadata = SplitMetadata {
+            split_id: prefixed_staged_split_id.clone(),
+            index_uid: index_uid.clone(),
+            split_path_prefix: Some("2024/03".to_string()),
+            ..Default::default()
+        };
+        let stage_splits_request = StageSplitsRequest::try_from_splits_metadata(
+            index_uid.clone(),
+            [split_metadata, prefixed_split_metadata],
+        )
+        .unwrap();
+        metastore.stage_splits(stage_splits_request).await.unwrap();
+
+        let staged_split_path = PathBuf::from(format!("{staged_split_id}.split"));
+        let orphan_split_path = PathBuf::from(format!("{orphan_split_id}.split"));
+        let prefixed_staged_split_path =
+            PathBuf::from(format!("2024/03/{prefixed_staged_split_id}.split"));
+        let prefixed_orphan_split_path =
+            PathBuf::from(format!("2024/03/{prefixed_orphan_split_id}.split"));
+        let recent_split_path = PathBuf::from(format!("{recent_split_id}.split"));
+        let non_split_path = PathBuf::from(format!("{orphan_split_id}.json"));
+
+        for file_path in [
+            &staged_split_path,
+            &orphan_split_path,
+            &prefixed_staged_split_path,
+            &prefixed_orphan_split_path,
+            &recent_split_path,
+            &non_split_path,
+        ] {
+            let payload: Box<dyn PutPayload> = Box::new(vec![0]);
+            storage.put(file_path, payload).await.unwrap();
+        }
+        let grace_period = Duration::from_secs(24 * 60 * 60);
+
+        let orphan_file_paths = delete_orphan_split_files(
+            index_uid.clone(),
+            storage.clone(),
+            metastore.clone(),
+            &list_all_indexes_metadata(metastore.clone()).await,
+            grace_period,
+            true,
+            None,
+        )
+        .await
+        .unwrap();
+        let expected_orphan_file_paths = [
+            orphan_split_path.clone(),
+            prefixed_orphan_split_path.clone(),
+        ];
+        assert_eq!(
+            orphan_file_paths.iter().sorted().collect_vec(),
+            expected_orphan_file_paths.iter().sorted().collect_vec()
+        );
+        assert!(storage.exists(&orphan_split_path).await.unwrap());
+        assert!(storage.exists(&prefixed_orphan_split_path).await.unwrap());
+
+        let orphan_file_paths = delete_orphan_split_files(
+            index_uid.clone(),
+            storage.clone(),
+            metastore.clone(),
+            &list_all_indexes_metadata(metastore.clone()).await,
+            grace_period,
+            false,
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            orphan_file_paths.iter().sorted().collect_vec(),
+            expected_orphan_file_paths.iter().sorted().collect_vec()
+        );
+        assert!(!storage.exists(&orphan_split_path).await.unwrap());
+        assert!(!storage.exists(&prefixed_orphan_split_path).await.unwrap());
+
+        for file_path in [
+            &staged_split_path,
+            &prefixed_staged_split_path,
+            &recent_split_path,
+            &non_split_path,
+        ] {
+            assert!(storage.exists(file_path).await.unwrap());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_delete_orphan_split_files_with_shared_index_uri() {
+        let storage = storage_for_test();
+        let mut metastore = metastore_for_test();
+
+        let index_uri = "ram:///indexes/test-delete-orphan-split-files-shared-uri";
+        let mut index_uids = Vec::new();
+
+        for (index_id, index_uri) in [
+            (
+                "test-delete-orphan-split-files-shared-uri--index-1",
+                index_uri.to_string(),
+            ),
+            (
+                "test-delete-orphan-split-files-shared-uri--index-2",
+                index_uri.to_string(),
+            ),
+            (
+                "test-delete-orphan-split-files-shared-uri--index-3",
+                format!("{index_uri}/nested"),
+            ),
+        ] {
+            let index_config = IndexConfig::for_test(index_id, &index_uri);
+            let create_index_request =
+                CreateIndexRequest::try_from_index_config(index_config).unwrap();
+            let index_uid: IndexUid = metastore
+                .create_index(create_index_request)
+                .await
+                .unwrap()
+                .index_uid
+                .into();
+            index_uids.push(index_uid);
+        }
+        let two_days_ago_millis =
+            (OffsetDateTime::now_utc().unix_timestamp() as u64 - 2 * 24 * 60 * 60) * 1_000;
+        let shared_split_id = Ulid::from_parts(two_days_ago_millis, 1).to_string();
+        let nested_split_id = Ulid::from_parts(two_days_ago_millis, 2).to_string();
+        let orphan_split_id = Ulid::from_parts(two_days_ago_millis, 3).to_string();
+
+        // The split belongs to the second index, which shares the storage URI of the first one.
+        let split_metadata = SplitMetadata {
+            split_id: shared_split_id.clone(),
+            index_uid: index_uids[1].clone(),
+            ..Default::default()
+        };
+        let stage_splits_request =
+            StageSplitsRequest::try_from_split_metadata(index_uids[1].clone(), split_metadata)
+                .unwrap();
+        metastore.stage_splits(stage_splits_request).await.unwrap();
+
+        let shared_split_path = PathBuf::from(format!("{shared_split_id}.split"));
+        // The split file is stored in the storage of the third index, nested in the storage of
+        // the first one.
+        let nested_split_path = PathBuf::from(format!("nested/{nested_split_id}.split"));
+        let orphan_split_path = PathBuf::from(format!("{orphan_split_id}.split"));
+
+        for file_path in [&shared_split_path, &nested_split_path, &orphan_split_path] {
+            let payload: Box<dyn PutPayload> = Box::new(vec![0]);
+            storage.put(file_path, payload).await.unwrap();
+        }
+        let orphan_file_paths = delete_orphan_split_files(
+            index_uids[0].clone(),
+            storage.clone(),
+            metastore.clone(),
+            &list_all_indexes_metadata(metastore.clone()).await,
+            Duration::from_secs(24 * 60 * 60),
+            false,
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(orphan_file_paths, [orphan_split_path.clone()]);
+        assert!(!storage.exists(&orphan_split_path).await.unwrap());
+        assert!(storage.exists(&shared_split_path).await.unwrap());
+        assert!(storage.exists(&nested_split_path).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_delete_orphan_split_files_skips_storage_without_listing() {
+        let mut mock_storage = MockStorage::new();
+        mock_storage.expect_list().times(1).returning(|_| {
+            Err(StorageErrorKind::Unsupported.with_error(anyhow::anyhow!("listing not supported")))
+        });
+        // The metastore is not called.
+        let metastore = MetastoreServiceClient::mock();
+
+        let orphan_file_paths = delete_orphan_split_files(
+            IndexUid::new_with_random_ulid("test-delete-orphan-split-files--index"),
+            Arc::new(mock_storage),
+            MetastoreServiceClient::from(metastore),
+            &[],
+            Duration::from_secs(30),
+            false,
+            None,
+        )
+        .await
+        .unwrap();
+        assert!(orphan_file_paths.is_empty());
+    }
 }
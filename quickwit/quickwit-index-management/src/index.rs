@@ -17,27 +17,38 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
-use std::path::Path;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
-use quickwit_common::fs::{empty_dir, get_cache_directory_path};
-use quickwit_config::{validate_identifier, IndexConfig, SourceConfig};
+use quickwit_common::fs::{empty_dir, get_cache_directory_path, get_split_cache_directory_path};
+use quickwit_common::uri::Uri;
+use quickwit_config::{
+    split_path_prefix_matches_template, validate_identifier, IndexAliasId, IndexConfig,
+    SourceConfig, RESERVED_SOURCE_IDS,
+};
 use quickwit_indexing::check_source_connectivity;
 use quickwit_metastore::{
     AddSourceRequestExt, CreateIndexRequestExt, IndexMetadata, IndexMetadataResponseExt,
-    ListSplitsQuery, ListSplitsRequestExt, MetastoreServiceStreamSplitsExt, SplitInfo,
-    SplitMetadata, SplitState,
+    ListIndexAliasesResponseExt, ListSplitsQuery, ListSplitsRequestExt,
+    MetastoreServiceStreamSplitsExt, Split, SplitInfo, SplitMetadata, SplitState,
+    UpdateIndexAliasesRequestExt,
 };
 use quickwit_proto::metastore::{
     AddSourceRequest, CreateIndexRequest, DeleteIndexRequest, EntityKind, IndexMetadataRequest,
-    ListSplitsRequest, MarkSplitsForDeletionRequest, MetastoreError, MetastoreService,
-    MetastoreServiceClient, ResetSourceCheckpointRequest,
+    ListIndexAliasesRequest, ListSplitsRequest, MarkSplitsForDeletionRequest, MetastoreError,
+    MetastoreService, MetastoreServiceClient, RenameIndexRequest, ResetSourceCheckpointRequest,
+    UpdateIndexAliasesRequest, UpdateIndexUriRequest,
 };
-use quickwit_proto::types::{IndexUid, SplitId};
+use quickwit_proto::types::{IndexId, IndexUid, SplitId};
 use quickwit_proto::{ServiceError, ServiceErrorCode};
-use quickwit_storage::{StorageResolver, StorageResolverError};
+use quickwit_storage::{
+    copy_across_storages, Storage, StorageErrorKind, StorageResolver, StorageResolverError,
+};
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
-use tracing::{error, info};
+use time::OffsetDateTime;
+use tracing::{error, info, warn};
 
 use crate::garbage_collection::{
     delete_splits_from_storage_and_metastore, run_garbage_collect, DeleteSplitsError,
@@ -76,6 +87,57 @@ impl ServiceError for IndexServiceError {
     }
 }
 
+/// Consistency report between the splits of an index recorded in the metastore and the split
+/// files present in the index storage.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SplitsConsistencyReport {
+    /// Splits recorded in the metastore whose file exists in storage.
+    pub consistent_split_ids: Vec<SplitId>,
+    /// Published splits whose file is missing from storage. Staged splits and splits marked for
+    /// deletion are allowed not to have a file.
+    pub missing_in_storage_split_ids: Vec<SplitId>,
+    /// Split files present in storage that are not recorded in the metastore.
+    pub orphaned_in_storage_split_ids: Vec<SplitId>,
+}
+
+impl SplitsConsistencyReport {
+    pub fn is_consistent(&self) -> bool {
+        self.missing_in_storage_split_ids.is_empty()
+            && self.orphaned_in_storage_split_ids.is_empty()
+    }
+}
+
+/// Conditions on the write index of an index alias that trigger a rollover. The rollover occurs
+/// as soon as one of the conditions is met, or unconditionally if none is set.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RolloverConditions {
+    /// Maximum number of published documents.
+    pub max_docs: Option<u64>,
+    /// Maximum time elapsed since the creation of the index.
+    pub max_age: Option<Duration>,
+    /// Maximum size of the published splits in bytes.
+    pub max_size_bytes: Option<u64>,
+}
+
+impl RolloverConditions {
+    fn is_empty(&self) -> bool {
+        self.max_docs.is_none() && self.max_age.is_none() && self.max_size_bytes.is_none()
+    }
+}
+
+/// Outcome of an index alias rollover.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RolloverReport {
+    pub alias_id: IndexAliasId,
+    /// Whether a new write index was created.
+    pub rolled_over: bool,
+    pub old_write_index_id: IndexId,
+    /// Equal to `old_write_index_id` if no rollover occurred.
+    pub new_write_index_id: IndexId,
+    /// Conditions met by the former write index.
+    pub met_conditions: Vec<String>,
+}
+
 /// Index service responsible for creating, updating and deleting indexes.
 #[derive(Clone)]
 pub struct IndexService {
@@ -102,6 +164,7 @@ impl IndexService {
         index_config: IndexConfig,
         overwrite: bool,
     ) -> Result<IndexMetadata, IndexServiceError> {
+        self.storage_resolver.register_index_config(&index_config);
         validate_storage_uri(&self.storage_resolver, &index_config)
             .await
             .map_err(IndexServiceError::InvalidConfig)?;
@@ -329,6 +392,527 @@ impl IndexService {
         Ok(())
     }
 
+    /// Checks that the splits of the index recorded in the metastore and the split files present in
+    /// the index storage are consistent.
+    ///
+    /// Orphaned split files are detected by listing the index storage, which is not supported by
+    /// all the storages. The split files are looked up at the root of the index storage and under
+    /// the split path prefixes, either recorded in the metastore or rendered from the split path
+    /// template of the index.
+    pub async fn check_index(
+        &mut self,
+        index_id: &str,
+    ) -> Result<SplitsConsistencyReport, IndexServiceError> {
+        let index_metadata_request = IndexMetadataRequest::for_index_id(index_id.to_string());
+        let index_metadata = self
+            .metastore
+            .index_metadata(index_metadata_request)
+            .await?
+            .deserialize_index_metadata()?;
+        let index_uid = index_metadata.index_uid.clone();
+        let storage = self
+            .storage_resolver
+            .resolve(index_metadata.index_uri())
+            .await?;
+        let list_splits_request = ListSplitsRequest::try_from_index_uid(index_uid)?;
+        let splits: Vec<Split> = self
+            .metastore
+            .list_splits(list_splits_request)
+            .await?
+            .collect_splits()
+            .await?;
+        let split_paths: Vec<PathBuf> = splits
+            .iter()
+            .map(|split| split.split_metadata.split_path())
+            .collect();
+        let split_paths: Vec<&Path> = split_paths.iter().map(PathBuf::as_path).collect();
+        let split_files_exist = storage.bulk_exists(&split_paths).await.map_err(|error| {
+            IndexServiceError::Internal(format!(
+                "failed to check the existence of the split files: {error}"
+            ))
+        })?;
+        let mut report = SplitsConsistencyReport::default();
+
+        for (split, split_file_exists) in splits.iter().zip(split_files_exist) {
+            if split_file_exists {
+                report
+                    .consistent_split_ids
+                    .push(split.split_id().to_string());
+            } else if split.split_state == SplitState::Published {
+                report
+                    .missing_in_storage_split_ids
+                    .push(split.split_id().to_string());
+            }
+        }
+        let known_split_ids: HashSet<&str> = splits.iter().map(|split| split.split_id()).collect();
+        let known_split_path_prefixes: HashSet<&Path> = splits
+            .iter()
+            .filter_map(|split| split.split_metadata.split_path_prefix.as_deref())
+            .map(Path::new)
+            .collect();
+        let split_path_template_opt = index_metadata
+            .index_config()
+            .indexing_settings
+            .split_path_template
+            .as_deref();
+        let file_paths = match storage.list(Path::new("")).await {
+            Ok(file_paths) => file_paths,
+            Err(storage_error) if storage_error.kind() == StorageErrorKind::Unsupported => {
+                return Err(IndexServiceError::OperationNotAllowed(format!(
+                    "cannot detect orphaned split files: storage `{}` does not support listing \
+                     files",
+                    index_metadata.index_uri()
+                )));
+            }
+            Err(storage_error) => {
+                return Err(IndexServiceError::Internal(format!(
+                    "failed to list the files of `{}`: {storage_error}",
+                    index_metadata.index_uri()
+                )));
+            }
+        };
+        for file_path in file_paths {
+            if file_path
+                .extension()
+                .and_then(|extension| extension.to_str())
+                != Some("split")
+            {
+                continue;
+            }
+            // Other nested files may belong to another index stored under the index storage.
+            let Some(parent_path) = file_path.parent() else {
+                continue;
+            };
+            let is_split_path_prefix = parent_path == Path::new("")
+                || known_split_path_prefixes.contains(parent_path)
+                || split_path_template_opt.is_some_and(|split_path_template| {
+                    parent_path.to_str().is_some_and(|split_path_prefix| {
+                        split_path_prefix_matches_template(split_path_template, split_path_prefix)
+                    })
+                });
+            if !is_split_path_prefix {
+                continue;
+            }
+            let Some(split_id) = file_path
+                .file_stem()
+                .and_then(|file_stem| file_stem.to_str())
+            else {
+                continue;
+            };
+            if !known_split_ids.contains(split_id) {
+                report
+                    .orphaned_in_storage_split_ids
+                    .push(split_id.to_string());
+            }
+        }
+        report.consistent_split_ids.sort_unstable();
+        report.missing_in_storage_split_ids.sort_unstable();
+        report.orphaned_in_storage_split_ids.sort_unstable();
+
+        if !report.is_consistent() {
+            info!(
+                index_id = %index_id,
+                num_missing_in_storage_splits = report.missing_in_storage_split_ids.len(),
+                num_orphaned_in_storage_splits = report.orphaned_in_storage_split_ids.len(),
+                "index splits are inconsistent"
+            );
+        }
+        Ok(report)
+    }
+
+    /// Updates the URI of the index `index_id` to `new_index_uri`.
+    ///
+    /// If `move_data` is true, the published split files are first copied to the new location,
+    /// after checking that the latter is writable. The files at the former location are left
+    /// untouched and must be deleted manually once the operation has succeeded. When both
+    /// locations belong to the same storage, e.g. the same bucket, the files are copied
+    /// server-side. Moving the data requires the index to be quiesced: its sources must be
+    /// disabled and no split may be staged, i.e. being uploaded or merged.
+    pub async fn update_index_uri(
+        &mut self,
+        index_id: &str,
+        new_index_uri: Uri,
+        move_data: bool,
+    ) -> Result<IndexMetadata, IndexServiceError> {
+        let index_metadata_request = IndexMetadataRequest::for_index_id(index_id.to_string());
+        let index_metadata = self
+            .metastore
+            .index_metadata(index_metadata_request)
+            .await?
+            .deserialize_index_metadata()?;
+        let index_uid = index_metadata.index_uid.clone();
+        let new_storage = self.storage_resolver.resolve(&new_index_uri).await?;
+
+        if move_data {
+            let enabled_source_ids: Vec<&str> = index_metadata
+                .sources
+                .values()
+                .filter(|source_config| {
+                    source_config.enabled
+                        && !RESERVED_SOURCE_IDS.contains(&source_config.source_id.as_str())
+                })
+                .map(|source_config| source_config.source_id.as_str())
+                .collect();
+            if !enabled_source_ids.is_empty() {
+                return Err(IndexServiceError::OperationNotAllowed(format!(
+                    "cannot move the split files of index `{index_id}` while sources {} are \
+                     enabled, disable them first",
+                    enabled_source_ids.join(", ")
+                )));
+            }
+            let published_splits = self.quiesced_published_splits(&index_uid).await?;
+
+            check_storage_is_writable(&*new_storage)
+                .await
+                .map_err(|error| {
+                    IndexServiceError::InvalidConfig(anyhow::anyhow!(
+                        "index URI `{new_index_uri}` is not writable: {error}"
+                    ))
+                })?;
+            let storage = self
+                .storage_resolver
+                .resolve(index_metadata.index_uri())
+                .await?;
+            // When both index URIs live in the same storage, the split files are copied
+            // server-side rather than downloaded and uploaded back.
+            let shared_storage_opt =
+                match common_ancestor_uri(index_metadata.index_uri(), &new_index_uri) {
+                    Some((ancestor_uri, source_dir, target_dir)) => {
+                        let shared_storage = self.storage_resolver.resolve(&ancestor_uri).await?;
+                        Some((shared_storage, source_dir, target_dir))
+                    }
+                    None => None,
+                };
+            for split_metadata in &published_splits {
+                let split_path = split_metadata.split_path();
+                let copy_res = match &shared_storage_opt {
+                    Some((shared_storage, source_dir, target_dir)) => {
+                        shared_storage
+                            .copy(&source_dir.join(&split_path), &target_dir.join(&split_path))
+                            .await
+                    }
+                    None => {
+                        copy_across_storages(&*storage, &*new_storage, &split_path, &split_path)
+                            .await
+                    }
+                };
+                // A published split whose file is missing would become unsearchable: the
+                // operation is aborted and the index URI left untouched.
+                copy_res.map_err(|error| {
+                    IndexServiceError::Internal(format!(
+                        "failed to copy split file `{}` to `{new_index_uri}`: {error}",
+                        split_path.display()
+                    ))
+                })?;
+            }
+            // Splits published while the files were being copied would be lost.
+            let current_published_splits = self.quiesced_published_splits(&index_uid).await?;
+
+            if !current_published_splits
+                .iter()
+                .map(SplitMetadata::split_id)
+                .eq(published_splits.iter().map(SplitMetadata::split_id))
+            {
+                return Err(IndexServiceError::OperationNotAllowed(format!(
+                    "the splits of index `{index_id}` changed while their files were being copied \
+                     to `{new_index_uri}`, the index URI was left untouched"
+                )));
+            }
+            info!(
+                index_id = %index_id,
+                num_splits = published_splits.len(),
+                "copied split files to `{new_index_uri}`"
+            );
+        }
+        let update_index_uri_request = UpdateIndexUriRequest {
+            index_uid: index_uid.to_string(),
+            index_uri: new_index_uri.to_string(),
+        };
+        self.metastore
+            .update_index_uri(update_index_uri_request)
+            .await?;
+        info!(index_id = %index_id, "index URI updated to `{new_index_uri}`");
+
+        let index_metadata_request = IndexMetadataRequest::for_index_id(index_id.to_string());
+        let index_metadata = self
+            .metastore
+            .index_metadata(index_metadata_request)
+            .await?
+            .deserialize_index_metadata()?;
+        Ok(index_metadata)
+    }
+
+    /// Returns the published splits of the index sorted by split ID, provided no split is staged.
+    async fn quiesced_published_splits(
+        &mut self,
+        index_uid: &IndexUid,
+    ) -> Result<Vec<SplitMetadata>, IndexServiceError> {
+        let list_splits_query = ListSplitsQuery::for_index(index_uid.clone())
+            .with_split_states([SplitState::Staged, SplitState::Published]);
+        let list_splits_request = ListSplitsRequest::try_from_list_splits_query(list_splits_query)?;
+        let splits: Vec<Split> = self
+            .metastore
+            .list_splits(list_splits_request)
+            .await?
+            .collect_splits()
+            .await?;
+        let mut published_splits = Vec::with_capacity(splits.len());
+
+        for split in splits {
+            if split.split_state == SplitState::Staged {
+                return Err(IndexServiceError::OperationNotAllowed(format!(
+                    "cannot move the split files of index `{}` while split `{}` is staged, wait \
+                     for the ongoing indexing and merge operations to complete",
+                    index_uid.index_id(),
+                    split.split_id()
+                )));
+            }
+            published_splits.push(split.split_metadata);
+        }
+        published_splits.sort_unstable_by(|left, right| left.split_id.cmp(&right.split_id));
+        Ok(published_splits)
+    }
+
+    /// Renames the index `index_id` to `new_index_id`. The index keeps its incarnation, sources,
+    /// and splits. The index URI, and therefore the location of the split files, is left
+    /// untouched.
+    pub async fn rename_index(
+        &mut self,
+        index_id: &str,
+        new_index_id: &str,
+    ) -> Result<IndexMetadata, IndexServiceError> {
+        let index_metadata_request = IndexMetadataRequest::for_index_id(index_id.to_string());
+        let index_uid = self
+            .metastore
+            .index_metadata(index_metadata_request)
+            .await?
+            .deserialize_index_metadata()?
+            .index_uid;
+        let rename_index_request = RenameIndexRequest {
+            index_uid: index_uid.to_string(),
+            new_index_id: new_index_id.to_string(),
+        };
+        let new_index_uid: IndexUid = self
+            .metastore
+            .rename_index(rename_index_request)
+            .await?
+            .index_uid
+            .into();
+        info!(index_id = %index_id, new_index_id = %new_index_id, "index renamed");
+
+        let index_metadata_request = IndexMetadataRequest::for_index_uid(new_index_uid);
+        let index_metadata = self
+            .metastore
+            .index_metadata(index_metadata_request)
+            .await?
+            .deserialize_index_metadata()?;
+        Ok(index_metadata)
+    }
+
+    /// Rolls over the index alias `alias_id` if its write index meets one of the `conditions`:
+    /// creates a new index with the same config as the current write index, then makes it the
+    /// write index of the alias. The former write index remains a member of the alias, so it is
+    /// still searched.
+    ///
+    /// The new index ID defaults to the ID of the current write index with its numeric suffix
+    /// incremented, for instance `logs-000002` for `logs-000001`.
+    pub async fn rollover_index_alias(
+        &mut self,
+        alias_id: &str,
+        new_index_id_opt: Option<IndexId>,
+        conditions: &RolloverConditions,
+    ) -> Result<RolloverReport, IndexServiceError> {
+        let index_aliases = self
+            .metastore
+            .list_index_aliases(ListIndexAliasesRequest {})
+            .await?
+            .deserialize_index_aliases()?;
+        let mut index_alias = index_aliases
+            .into_iter()
+            .find(|index_alias| index_alias.alias_id == alias_id)
+            .ok_or_else(|| {
+                MetastoreError::NotFound(EntityKind::IndexAlias {
+                    alias_id: alias_id.to_string(),
+                })
+            })?;
+        let old_write_index_id = index_alias.write_index_id.clone().ok_or_else(|| {
+            IndexServiceError::OperationNotAllowed(format!(
+                "index alias `{alias_id}` does not have a write index"
+            ))
+        })?;
+        let index_metadata_request = IndexMetadataRequest::for_index_id(old_write_index_id.clone());
+        let write_index_metadata = self
+            .metastore
+            .index_metadata(index_metadata_request)
+            .await?
+            .deserialize_index_metadata()?;
+        let met_conditions = self
+            .check_rollover_conditions(&write_index_metadata, conditions)
+            .await?;
+
+        if !conditions.is_empty() && met_conditions.is_empty() {
+            return Ok(RolloverReport {
+                alias_id: alias_id.to_string(),
+                rolled_over: false,
+                old_write_index_id: old_write_index_id.clone(),
+                new_write_index_id: old_write_index_id,
+                met_conditions,
+            });
+        }
+        let new_index_id = match new_index_id_opt {
+            Some(new_index_id) => new_index_id,
+            None => next_rollover_index_id(&old_write_index_id)?,
+        };
+        validate_identifier("Index ID", &new_index_id).map_err(|_| {
+            IndexServiceError::InvalidIdentifier(format!("invalid index ID: `{new_index_id}`"))
+        })?;
+        let mut new_index_config = write_index_metadata.into_index_config();
+        let index_root_uri = new_index_config.index_uri.parent().ok_or_else(|| {
+            IndexServiceError::Internal(format!(
+                "failed to get the root URI of index `{old_write_index_id}`"
+            ))
+        })?;
+        new_index_config.index_uri = index_root_uri
+            .join(&new_index_id)
+            .map_err(IndexServiceError::InvalidConfig)?;
+        new_index_config.index_id = new_index_id.clone();
+        let new_index_uri = new_index_config.index_uri.clone();
+
+        // A previous rollover attempt may have created the new index and failed to update the
+        // alias, in which case the index is reused so that the rollover can be retried.
+        let created_new_index = match self.create_index(new_index_config, false).await {
+            Ok(_) => true,
+            Err(IndexServiceError::Metastore(MetastoreError::AlreadyExists(
+                EntityKind::Index { .. },
+            ))) => {
+                self.check_leftover_rollover_index(&new_index_id, &new_index_uri)
+                    .await?;
+                false
+            }
+            Err(error) => return Err(error),
+        };
+        // The alias is only updated if it was not modified since it was read, so concurrent
+        // rollovers cannot both succeed.
+        let expected_index_alias = index_alias.clone();
+        index_alias.index_ids.insert(new_index_id.clone());
+        index_alias.write_index_id = Some(new_index_id.clone());
+        let update_index_aliases_request =
+            UpdateIndexAliasesRequest::try_from_index_aliases([&index_alias], Vec::new())?
+                .try_with_expected_index_aliases([&expected_index_alias])?;
+
+        if let Err(error) = self
+            .metastore
+            .update_index_aliases(update_index_aliases_request)
+            .await
+        {
+            // The update was rejected, so the new index is not referenced by the alias. Other
+            // errors leave the outcome of the update unknown, so the index is kept.
+            if created_new_index && matches!(error, MetastoreError::FailedPrecondition { .. }) {
+                if let Err(delete_error) = self.delete_index(&new_index_id, false).await {
+                    warn!(
+                        index_id = %new_index_id,
+                        error = %delete_error,
+                        "failed to delete index created by rolled back rollover"
+                    );
+                }
+            }
+            return Err(error.into());
+        }
+        info!(
+            alias_id = %alias_id,
+            old_write_index_id = %old_write_index_id,
+            new_write_index_id = %new_index_id,
+            "rolled over index alias"
+        );
+        Ok(RolloverReport {
+            alias_id: alias_id.to_string(),
+            rolled_over: true,
+            old_write_index_id,
+            new_write_index_id: new_index_id,
+            met_conditions,
+        })
+    }
+
+    /// Checks that the existing index `index_id` was left behind by a failed rollover: it has the
+    /// URI the rollover assigns to it and no splits since it never became the write index of the
+    /// alias. Returns an `AlreadyExists` error otherwise.
+    async fn check_leftover_rollover_index(
+        &mut self,
+        index_id: &str,
+        expected_index_uri: &Uri,
+    ) -> Result<(), IndexServiceError> {
+        let index_metadata_request = IndexMetadataRequest::for_index_id(index_id.to_string());
+        let index_metadata = self
+            .metastore
+            .index_metadata(index_metadata_request)
+            .await?
+            .deserialize_index_metadata()?;
+        let list_splits_query =
+            ListSplitsQuery::for_index(index_metadata.index_uid.clone()).with_limit(1);
+        let list_splits_request = ListSplitsRequest::try_from_list_splits_query(list_splits_query)?;
+        let has_splits = !self
+            .metastore
+            .list_splits(list_splits_request)
+            .await?
+            .collect_splits_metadata()
+            .await?
+            .is_empty();
+
+        if index_metadata.index_uri() != expected_index_uri || has_splits {
+            return Err(MetastoreError::AlreadyExists(EntityKind::Index {
+                index_id: index_id.to_string(),
+            })
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Returns the names of the rollover conditions met by the index.
+    async fn check_rollover_conditions(
+        &mut self,
+        index_metadata: &IndexMetadata,
+        conditions: &RolloverConditions,
+    ) -> Result<Vec<String>, IndexServiceError> {
+        let mut met_conditions = Vec::new();
+
+        if let Some(max_age) = conditions.max_age {
+            let index_age_secs =
+                OffsetDateTime::now_utc().unix_timestamp() - index_metadata.create_timestamp;
+            if index_age_secs >= max_age.as_secs() as i64 {
+                met_conditions.push("max_age".to_string());
+            }
+        }
+        if conditions.max_docs.is_none() && conditions.max_size_bytes.is_none() {
+            return Ok(met_conditions);
+        }
+        let query = ListSplitsQuery::for_index(index_metadata.index_uid.clone())
+            .with_split_state(SplitState::Published);
+        let list_splits_request = ListSplitsRequest::try_from_list_splits_query(query)?;
+        let splits_metadata = self
+            .metastore
+            .list_splits(list_splits_request)
+            .await?
+            .collect_splits_metadata()
+            .await?;
+        let num_docs: u64 = splits_metadata
+            .iter()
+            .map(|split_metadata| split_metadata.num_docs as u64)
+            .sum();
+        let num_bytes: u64 = splits_metadata
+            .iter()
+            .map(|split_metadata| split_metadata.footer_offsets.end)
+            .sum();
+
+        if matches!(conditions.max_docs, Some(max_docs) if num_docs >= max_docs) {
+            met_conditions.push("max_docs".to_string());
+        }
+        if matches!(conditions.max_size_bytes, Some(max_size_bytes) if num_bytes >= max_size_bytes)
+        {
+            met_conditions.push("max_size".to_string());
+        }
+        Ok(met_conditions)
+    }
+
     /// Creates a source config for index `index_id`.
     pub async fn create_source(
         &mut self,
@@ -396,6 +980,21 @@ impl IndexService {
     }
 }
 
+/// Increments the numeric suffix of `index_id`, preserving its zero padding.
+fn next_rollover_index_id(index_id: &str) -> Result<IndexId, IndexServiceError> {
+    let prefix = index_id.trim_end_matches(|character: char| character.is_ascii_digit());
+    let suffix = &index_id[prefix.len()..];
+
+    let Ok(counter) = suffix.parse::<u64>() else {
+        return Err(IndexServiceError::InvalidIdentifier(format!(
+            "failed to derive the ID of the new write index from `{index_id}`: the index ID must \
+             end with a number or the new index ID must be provided"
+        )));
+    };
+    let width = suffix.len();
+    Ok(format!("{prefix}{:0width$}", counter + 1))
+}
+
 /// Clears the cache directory of a given source.
 ///
 /// * `data_dir_path` - Path to directory where data (tmp data, splits kept for caching purpose) is
@@ -407,6 +1006,71 @@ pub async fn clear_cache_directory(data_dir_path: &Path) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Returns the total size of the splits stored in the searcher split cache directory.
+///
+/// * `data_dir_path` - Path to directory where data (tmp data, splits kept for caching purpose) is
+///   persisted.
+pub async fn get_split_cache_num_bytes(data_dir_path: &Path) -> anyhow::Result<u64> {
+    let split_cache_directory_path = get_split_cache_directory_path(data_dir_path);
+    let num_bytes = tokio::task::spawn_blocking(move || {
+        quickwit_storage::split_cache_directory_num_bytes(&split_cache_directory_path)
+    })
+    .await??;
+    Ok(num_bytes)
+}
+
+/// Prunes the searcher split cache directory, evicting the least recently used splits first,
+/// until it fits in `target_num_bytes`. Returns the total size of the remaining splits.
+///
+/// The searcher using the split cache directory must not be running.
+///
+/// * `data_dir_path` - Path to directory where data (tmp data, splits kept for caching purpose) is
+///   persisted.
+pub async fn prune_split_cache(data_dir_path: &Path, target_num_bytes: u64) -> anyhow::Result<u64> {
+    let split_cache_directory_path = get_split_cache_directory_path(data_dir_path);
+    info!(path = %split_cache_directory_path.display(), target_num_bytes, "pruning split cache directory");
+    let num_bytes = tokio::task::spawn_blocking(move || {
+        quickwit_storage::prune_split_cache_directory(&split_cache_directory_path, target_num_bytes)
+    })
+    .await??;
+    Ok(num_bytes)
+}
+
+/// Checks that the storage is writable by writing and deleting a probe file.
+async fn check_storage_is_writable(storage: &dyn Storage) -> anyhow::Result<()> {
+    let probe_path = Path::new(".quickwit-write-probe");
+    storage.put(probe_path, Box::new(Vec::new())).await?;
+    storage.delete(probe_path).await?;
+    Ok(())
+}
+
+/// Returns the closest common ancestor of `uri` and `other_uri` within the same storage, i.e.
+/// without crossing a bucket boundary, along with the paths of both URIs relative to it.
+fn common_ancestor_uri(uri: &Uri, other_uri: &Uri) -> Option<(Uri, PathBuf, PathBuf)> {
+    let mut ancestor_uri_opt = uri.parent();
+
+    while let Some(ancestor_uri) = ancestor_uri_opt {
+        if let (Some(relative_path), Some(other_relative_path)) = (
+            relative_path(&ancestor_uri, uri),
+            relative_path(&ancestor_uri, other_uri),
+        ) {
+            return Some((ancestor_uri, relative_path, other_relative_path));
+        }
+        ancestor_uri_opt = ancestor_uri.parent();
+    }
+    None
+}
+
+/// Returns the path of `uri` relative to `ancestor_uri`, if the latter is an ancestor of `uri`.
+fn relative_path(ancestor_uri: &Uri, uri: &Uri) -> Option<PathBuf> {
+    let relative_path = uri.as_str().strip_prefix(ancestor_uri.as_str())?;
+
+    if !ancestor_uri.as_str().ends_with('/') && !relative_path.starts_with('/') {
+        return None;
+    }
+    Some(PathBuf::from(relative_path.trim_start_matches('/')))
+}
+
 /// Validates the storage URI by effectively resolving it.
 pub async fn validate_storage_uri(
     storage_resolver: &StorageResolver,
@@ -420,11 +1084,13 @@ pub async fn validate_storage_uri(
 mod tests {
 
     use quickwit_common::uri::Uri;
-    use quickwit_config::IndexConfig;
+    use quickwit_config::{IndexAlias, IndexConfig, SourceParams};
     use quickwit_metastore::{
         metastore_for_test, MetastoreServiceExt, SplitMetadata, StageSplitsRequestExt,
     };
-    use quickwit_proto::metastore::StageSplitsRequest;
+    use quickwit_proto::metastore::{
+        PublishSplitsRequest, StageSplitsRequest, ToggleSourceRequest,
+    };
     use quickwit_storage::PutPayload;
 
     use super::*;
@@ -528,4 +1194,497 @@ mod tests {
         assert!(splits.is_empty());
         assert!(!storage.exists(split_path).await.unwrap());
     }
+
+    #[tokio::test]
+    async fn test_check_index() {
+        let mut metastore = metastore_for_test();
+        let storage_resolver = StorageResolver::for_test();
+        let storage = storage_resolver
+            .resolve(&Uri::for_test("ram:///indexes/test-index"))
+            .await
+            .unwrap();
+        let mut index_service = IndexService::new(metastore.clone(), storage_resolver);
+        let index_id = "test-index";
+        let mut index_config = IndexConfig::for_test(index_id, "ram:///indexes/test-index");
+        index_config.indexing_settings.split_path_template = Some("{year}/{month}".to_string());
+        let index_uid = index_service
+            .create_index(index_config, false)
+            .await
+            .unwrap()
+            .index_uid;
+
+        let splits_metadata: Vec<SplitMetadata> = [
+            ("published-split", None),
+            ("missing-split", None),
+            ("staged-split", None),
+            ("prefixed-split", Some("archive")),
+        ]
+        .into_iter()
+        .map(|(split_id, split_path_prefix_opt)| SplitMetadata {
+            split_id: split_id.to_string(),
+            index_uid: index_uid.clone(),
+            split_path_prefix: split_path_prefix_opt.map(ToString::to_string),
+            ..Default::default()
+        })
+        .collect();
+        let stage_splits_request =
+            StageSplitsRequest::try_from_splits_metadata(index_uid.clone(), splits_metadata)
+                .unwrap();
+        metastore.stage_splits(stage_splits_request).await.unwrap();
+        let publish_splits_request = PublishSplitsRequest {
+            index_uid: index_uid.to_string(),
+            staged_split_ids: vec![
+                "published-split".to_string(),
+                "missing-split".to_string(),
+                "prefixed-split".to_string(),
+            ],
+            ..Default::default()
+        };
+        metastore
+            .publish_splits(publish_splits_request)
+            .await
+            .unwrap();
+
+        for file_path in [
+            "published-split.split",
+            "orphan-split.split",
+            "archive/prefixed-split.split",
+            "archive/prefixed-orphan-split.split",
+            "2024/03/templated-orphan-split.split",
+            // Nested files outside of the split path prefixes, such as the files of another index,
+            // are ignored.
+            "foo/bar.split",
+            "2024/03/05/baz.split",
+        ] {
+            storage
+                .put(Path::new(file_path), Box::new(vec![0]))
+                .await
+                .unwrap();
+        }
+        let report = index_service.check_index(index_id).await.unwrap();
+        assert!(!report.is_consistent());
+        assert_eq!(
+            report,
+            SplitsConsistencyReport {
+                consistent_split_ids: vec![
+                    "prefixed-split".to_string(),
+                    "published-split".to_string()
+                ],
+                missing_in_storage_split_ids: vec!["missing-split".to_string()],
+                orphaned_in_storage_split_ids: vec![
+                    "orphan-split".to_string(),
+                    "prefixed-orphan-split".to_string(),
+                    "templated-orphan-split".to_string(),
+                ],
+            }
+        );
+
+        storage
+            .put(Path::new("missing-split.split"), Box::new(vec![0]))
+            .await
+            .unwrap();
+        for file_path in [
+            "orphan-split.split",
+            "archive/prefixed-orphan-split.split",
+            "2024/03/templated-orphan-split.split",
+        ] {
+            storage.delete(Path::new(file_path)).await.unwrap();
+        }
+        let report = index_service.check_index(index_id).await.unwrap();
+        assert!(report.is_consistent());
+        assert_eq!(
+            report.consistent_split_ids,
+            [
+                "missing-split".to_string(),
+                "prefixed-split".to_string(),
+                "published-split".to_string()
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_index_uri() {
+        let mut metastore = metastore_for_test();
+        let storage_resolver = StorageResolver::for_test();
+        let storage = storage_resolver
+            .resolve(&Uri::for_test("ram:///indexes/test-index"))
+            .await
+            .unwrap();
+        let new_storage = storage_resolver
+            .resolve(&Uri::for_test("ram:///moved-indexes/test-index"))
+            .await
+            .unwrap();
+        let mut index_service = IndexService::new(metastore.clone(), storage_resolver);
+        let index_id = "test-index";
+        let index_config = IndexConfig::for_test(index_id, "ram:///indexes/test-index");
+        let index_uid = index_service
+            .create_index(index_config, false)
+            .await
+            .unwrap()
+            .index_uid;
+
+        let splits_metadata: Vec<SplitMetadata> = ["published-split", "staged-split"]
+            .into_iter()
+            .map(|split_id| SplitMetadata {
+                split_id: split_id.to_string(),
+                index_uid: index_uid.clone(),
+                ..Default::default()
+            })
+            .collect();
+        let stage_splits_request =
+            StageSplitsRequest::try_from_splits_metadata(index_uid.clone(), splits_metadata)
+                .unwrap();
+        metastore.stage_splits(stage_splits_request).await.unwrap();
+        let publish_splits_request = PublishSplitsRequest {
+            index_uid: index_uid.to_string(),
+            staged_split_ids: vec!["published-split".to_string()],
+            ..Default::default()
+        };
+        metastore
+            .publish_splits(publish_splits_request)
+            .await
+            .unwrap();
+
+        // The index is not quiesced: a split is staged.
+        let error = index_service
+            .update_index_uri(
+                index_id,
+                Uri::for_test("ram:///moved-indexes/test-index"),
+                true,
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(error, IndexServiceError::OperationNotAllowed(_)));
+
+        let mark_splits_for_deletion_request = MarkSplitsForDeletionRequest {
+            index_uid: index_uid.to_string(),
+            split_ids: vec!["staged-split".to_string()],
+        };
+        metastore
+            .mark_splits_for_deletion(mark_splits_for_deletion_request)
+            .await
+            .unwrap();
+
+        // The index is not quiesced: a source is enabled.
+        let source_config = SourceConfig::for_test("test-source", SourceParams::void());
+        index_service
+            .create_source(index_uid.clone(), source_config)
+            .await
+            .unwrap();
+        let error = index_service
+            .update_index_uri(
+                index_id,
+                Uri::for_test("ram:///moved-indexes/test-index"),
+                true,
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(error, IndexServiceError::OperationNotAllowed(_)));
+
+        let toggle_source_request = ToggleSourceRequest {
+            index_uid: index_uid.to_string(),
+            source_id: "test-source".to_string(),
+            enable: false,
+        };
+        metastore
+            .toggle_source(toggle_source_request)
+            .await
+            .unwrap();
+
+        // The file of a published split is missing.
+        let error = index_service
+            .update_index_uri(
+                index_id,
+                Uri::for_test("ram:///moved-indexes/test-index"),
+                true,
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(error, IndexServiceError::Internal(_)));
+
+        let index_metadata_request = IndexMetadataRequest::for_index_id(index_id.to_string());
+        let index_metadata = metastore
+            .index_metadata(index_metadata_request)
+            .await
+            .unwrap()
+            .deserialize_index_metadata()
+            .unwrap();
+        assert_eq!(index_metadata.index_uri(), &"ram:///indexes/test-index");
+
+        let split_path = Path::new("published-split.split");
+        storage.put(split_path, Box::new(vec![0])).await.unwrap();
+
+        let index_metadata = index_service
+            .update_index_uri(
+                index_id,
+                Uri::for_test("ram:///moved-indexes/test-index"),
+                true,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            index_metadata.index_uri(),
+            &"ram:///moved-indexes/test-index"
+        );
+        assert_eq!(
+            new_storage.get_all(split_path).await.unwrap().as_slice(),
+            &[0]
+        );
+        assert!(!new_storage
+            .exists(Path::new(".quickwit-write-probe"))
+            .await
+            .unwrap());
+
+        let index_metadata = index_service
+            .update_index_uri(index_id, Uri::for_test("ram:///indexes/test-index"), false)
+            .await
+            .unwrap();
+        assert_eq!(index_metadata.index_uri(), &"ram:///indexes/test-index");
+    }
+
+    #[tokio::test]
+    async fn test_rename_index() {
+        let mut metastore = metastore_for_test();
+        let mut index_service = IndexService::new(metastore.clone(), StorageResolver::for_test());
+        let index_config = IndexConfig::for_test("test-index", "ram:///indexes/test-index");
+        let index_uid = index_service
+            .create_index(index_config, false)
+            .await
+            .unwrap()
+            .index_uid;
+
+        let split_metadata = SplitMetadata {
+            split_id: "test-split".to_string(),
+            index_uid: index_uid.clone(),
+            ..Default::default()
+        };
+        let stage_splits_request =
+            StageSplitsRequest::try_from_splits_metadata(index_uid.clone(), vec![split_metadata])
+                .unwrap();
+        metastore.stage_splits(stage_splits_request).await.unwrap();
+
+        let index_metadata = index_service
+            .rename_index("test-index", "test-index-renamed")
+            .await
+            .unwrap();
+        assert_eq!(index_metadata.index_id(), "test-index-renamed");
+        assert_eq!(
+            index_metadata.index_uid.incarnation_id(),
+            index_uid.incarnation_id()
+        );
+        assert_eq!(index_metadata.index_uri(), &"ram:///indexes/test-index");
+
+        let splits_metadata = metastore
+            .list_splits(
+                ListSplitsRequest::try_from_index_uid(index_metadata.index_uid.clone()).unwrap(),
+            )
+            .await
+            .unwrap()
+            .collect_splits_metadata()
+            .await
+            .unwrap();
+        assert_eq!(splits_metadata.len(), 1);
+        assert_eq!(splits_metadata[0].index_uid, index_metadata.index_uid);
+
+        let error = index_service
+            .rename_index("test-index", "test-index-other")
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            IndexServiceError::Metastore(MetastoreError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_common_ancestor_uri() {
+        assert_eq!(
+            common_ancestor_uri(
+                &Uri::for_test("s3://bucket/indexes/test-index"),
+                &Uri::for_test("s3://bucket/moved-indexes/test-index")
+            ),
+            Some((
+                Uri::for_test("s3://bucket"),
+                PathBuf::from("indexes/test-index"),
+                PathBuf::from("moved-indexes/test-index")
+            ))
+        );
+        assert_eq!(
+            common_ancestor_uri(
+                &Uri::for_test("s3://bucket/indexes/test-index"),
+                &Uri::for_test("s3://bucket/indexes/test-index-v2")
+            ),
+            Some((
+                Uri::for_test("s3://bucket/indexes"),
+                PathBuf::from("test-index"),
+                PathBuf::from("test-index-v2")
+            ))
+        );
+        assert_eq!(
+            common_ancestor_uri(
+                &Uri::for_test("s3://bucket/indexes/test-index"),
+                &Uri::for_test("s3://bucket/indexes")
+            ),
+            Some((
+                Uri::for_test("s3://bucket/indexes"),
+                PathBuf::from("test-index"),
+                PathBuf::from("")
+            ))
+        );
+        assert_eq!(
+            common_ancestor_uri(
+                &Uri::for_test("s3://bucket/indexes/test-index"),
+                &Uri::for_test("s3://other-bucket/indexes/test-index")
+            ),
+            None
+        );
+        assert_eq!(
+            common_ancestor_uri(
+                &Uri::for_test("s3://bucket/indexes/test-index"),
+                &Uri::for_test("file:///indexes/test-index")
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_next_rollover_index_id() {
+        assert_eq!(
+            next_rollover_index_id("logs-000001").unwrap(),
+            "logs-000002"
+        );
+        assert_eq!(next_rollover_index_id("logs-9").unwrap(), "logs-10");
+        assert_eq!(next_rollover_index_id("logs-099").unwrap(), "logs-100");
+        next_rollover_index_id("logs").unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn test_rollover_index_alias() {
+        let mut metastore = metastore_for_test();
+        let storage_resolver = StorageResolver::for_test();
+        let mut index_service = IndexService::new(metastore.clone(), storage_resolver);
+        let index_config = IndexConfig::for_test("logs-000001", "ram:///indexes/logs-000001");
+        let index_uid = index_service
+            .create_index(index_config, false)
+            .await
+            .unwrap()
+            .index_uid;
+
+        let index_alias = IndexAlias::for_test("logs", &["logs-000001"], Some("logs-000001"));
+        let update_index_aliases_request =
+            UpdateIndexAliasesRequest::try_from_index_aliases([&index_alias], Vec::new()).unwrap();
+        metastore
+            .update_index_aliases(update_index_aliases_request)
+            .await
+            .unwrap();
+
+        let split_metadata = SplitMetadata {
+            split_id: "test-split".to_string(),
+            index_uid: index_uid.clone(),
+            num_docs: 10,
+            ..Default::default()
+        };
+        let stage_splits_request =
+            StageSplitsRequest::try_from_splits_metadata(index_uid.clone(), vec![split_metadata])
+                .unwrap();
+        metastore.stage_splits(stage_splits_request).await.unwrap();
+        let publish_splits_request = PublishSplitsRequest {
+            index_uid: index_uid.to_string(),
+            staged_split_ids: vec!["test-split".to_string()],
+            ..Default::default()
+        };
+        metastore
+            .publish_splits(publish_splits_request)
+            .await
+            .unwrap();
+
+        let conditions = RolloverConditions {
+            max_docs: Some(100),
+            ..Default::default()
+        };
+        let rollover_report = index_service
+            .rollover_index_alias("logs", None, &conditions)
+            .await
+            .unwrap();
+        assert!(!rollover_report.rolled_over);
+        assert_eq!(rollover_report.new_write_index_id, "logs-000001");
+        assert!(rollover_report.met_conditions.is_empty());
+
+        let conditions = RolloverConditions {
+            max_docs: Some(10),
+            max_age: Some(Duration::from_secs(3600)),
+            ..Default::default()
+        };
+        let rollover_report = index_service
+            .rollover_index_alias("logs", None, &conditions)
+            .await
+            .unwrap();
+        assert!(rollover_report.rolled_over);
+        assert_eq!(rollover_report.old_write_index_id, "logs-000001");
+        assert_eq!(rollover_report.new_write_index_id, "logs-000002");
+        assert_eq!(rollover_report.met_conditions, ["max_docs"]);
+
+        let index_metadata = metastore
+            .index_metadata(IndexMetadataRequest::for_index_id(
+                "logs-000002".to_string(),
+            ))
+            .await
+            .unwrap()
+            .deserialize_index_metadata()
+            .unwrap();
+        assert_eq!(index_metadata.index_uri(), &"ram:///indexes/logs-000002");
+
+        let index_aliases = metastore
+            .list_index_aliases(ListIndexAliasesRequest {})
+            .await
+            .unwrap()
+            .deserialize_index_aliases()
+            .unwrap();
+        assert_eq!(
+            index_aliases,
+            [IndexAlias::for_test(
+                "logs",
+                &["logs-000001", "logs-000002"],
+                Some("logs-000002")
+            )]
+        );
+
+        // A previous attempt created the next index but failed to update the alias: the index is
+        // reused.
+        let index_config = IndexConfig::for_test("logs-000003", "ram:///indexes/logs-000003");
+        index_service
+            .create_index(index_config, false)
+            .await
+            .unwrap();
+        let rollover_report = index_service
+            .rollover_index_alias("logs", None, &RolloverConditions::default())
+            .await
+            .unwrap();
+        assert!(rollover_report.rolled_over);
+        assert_eq!(rollover_report.new_write_index_id, "logs-000003");
+
+        // The next index exists but was not created by a rollover.
+        let index_config = IndexConfig::for_test("logs-000004", "ram:///other/logs-000004");
+        index_service
+            .create_index(index_config, false)
+            .await
+            .unwrap();
+        let error = index_service
+            .rollover_index_alias("logs", None, &RolloverConditions::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            IndexServiceError::Metastore(MetastoreError::AlreadyExists(EntityKind::Index { .. }))
+        ));
+
+        let error = index_service
+            .rollover_index_alias("unknown", None, &RolloverConditions::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            IndexServiceError::Metastore(MetastoreError::NotFound(EntityKind::IndexAlias { .. }))
+        ));
+    }
 }
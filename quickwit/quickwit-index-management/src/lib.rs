@@ -20,5 +20,8 @@
 mod garbage_collection;
 mod index;
 
-pub use garbage_collection::run_garbage_collect;
-pub use index::{clear_cache_directory, validate_storage_uri, IndexService, IndexServiceError};
+pub use garbage_collection::{delete_orphan_split_files, run_garbage_collect};
+pub use index::{
+    clear_cache_directory, get_split_cache_num_bytes, prune_split_cache, validate_storage_uri,
+    IndexService, IndexServiceError, RolloverConditions, RolloverReport, SplitsConsistencyReport,
+};
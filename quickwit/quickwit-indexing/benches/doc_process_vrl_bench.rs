@@ -132,7 +132,9 @@ fn create_doc_processor(
         doc_mapper,
         indexer_mailbox,
         transform_config_opt,
+        &[],
         SourceInputFormat::Json,
+        None,
     )
     .unwrap();
     let (mailbox, handle) = universe.spawn_builder().spawn(doc_processor);
@@ -48,7 +48,7 @@ use quickwit_common::temp_dir::TempDirectory;
 use quickwit_indexing::actors::MergeExecutor;
 use quickwit_indexing::merge_policy::MergeOperation;
 use quickwit_indexing::models::MergeScratch;
-use quickwit_indexing::{get_tantivy_directory_from_split_bundle, TestSandbox};
+use quickwit_indexing::{get_tantivy_directory_from_split_bundle, ScratchSpaceQuota, TestSandbox};
 use quickwit_metastore::{
     ListSplitsQuery, ListSplitsRequestExt, MetastoreServiceStreamSplitsExt, SplitMetadata,
     SplitState,
@@ -298,6 +298,7 @@ async fn test_merge_executor_controlled_directory_kill_switch() -> anyhow::Resul
         merge_scratch_directory,
         downloaded_splits_directory,
         tantivy_dirs,
+        downloaded_splits_scratch_space: Vec::new(),
     };
     let pipeline_id = IndexingPipelineId {
         index_uid: IndexUid::new_with_random_ulid(index_id),
@@ -314,6 +315,7 @@ async fn test_merge_executor_controlled_directory_kill_switch() -> anyhow::Resul
         metastore,
         doc_mapper,
         io_controls,
+        ScratchSpaceQuota::unlimited(),
         merge_packager_mailbox,
     );
 
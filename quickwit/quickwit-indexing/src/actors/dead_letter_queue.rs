@@ -0,0 +1,292 @@
+// Copyright (C) 2024 Quickwit, Inc.
+//
+// Quickwit is offered under the AGPL v3.0 and as commercial software.
+// For commercial licensing, contact us at hello@quickwit.io.
+//
+// AGPL:
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as
+// published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::Context;
+use bytes::Bytes;
+use quickwit_config::DeadLetterQueueConfig;
+use quickwit_storage::{Storage, StorageErrorKind};
+use serde::Serialize;
+use time::OffsetDateTime;
+use tracing::{info, warn};
+use ulid::Ulid;
+
+/// Line of a dead letter file.
+#[derive(Serialize)]
+struct DeadLetter<'a> {
+    index_id: &'a str,
+    source_id: &'a str,
+    /// Unix timestamp, in seconds, at which the document failed.
+    timestamp: i64,
+    /// Error of the first failed document of the raw document.
+    error: String,
+    /// Number of failed documents of the raw document, which can be greater than one for raw
+    /// documents yielding several documents, such as OTLP requests.
+    num_failed_docs: usize,
+    /// Raw document, decoded as UTF-8 (invalid sequences are replaced).
+    doc: String,
+}
+
+/// Collects the documents that fail to be processed and writes them, along with their error, to
+/// a storage as NDJSON files.
+///
+/// Failed documents are buffered until [`DeadLetterQueue::flush`] is called, and the buffer is
+/// bounded by the `max_file_size` of the dead letter queue config: documents that do not fit are
+/// dropped. Likewise, documents are dropped once the source has `max_num_files` dead letter files.
+pub struct DeadLetterQueue {
+    storage: Arc<dyn Storage>,
+    index_id: String,
+    source_id: String,
+    max_file_size: usize,
+    max_num_files: usize,
+    // Number of dead letter files of the source, listed from the storage on the first flush.
+    num_files_opt: Option<usize>,
+    buffer: Vec<u8>,
+    num_buffered_docs: usize,
+    num_dropped_docs: usize,
+}
+
+impl DeadLetterQueue {
+    pub fn new(
+        storage: Arc<dyn Storage>,
+        index_id: String,
+        source_id: String,
+        config: &DeadLetterQueueConfig,
+    ) -> Self {
+        Self {
+            storage,
+            index_id,
+            source_id,
+            max_file_size: config.max_file_size.as_u64() as usize,
+            max_num_files: config.max_num_files,
+            num_files_opt: None,
+            buffer: Vec::new(),
+            num_buffered_docs: 0,
+            num_dropped_docs: 0,
+        }
+    }
+
+    /// Buffers a raw document, `error` being the error of its first failed document.
+    pub(super) fn push(&mut self, raw_doc: &Bytes, error: String, num_failed_docs: usize) {
+        let dead_letter = DeadLetter {
+            index_id: &self.index_id,
+            source_id: &self.source_id,
+            timestamp: OffsetDateTime::now_utc().unix_timestamp(),
+            error,
+            num_failed_docs,
+            doc: String::from_utf8_lossy(raw_doc).into_owned(),
+        };
+        let mut line =
+            serde_json::to_vec(&dead_letter).expect("dead letter should be JSON serializable");
+        line.push(b'\n');
+
+        if self.buffer.len() + line.len() > self.max_file_size {
+            self.num_dropped_docs += 1;
+            return;
+        }
+        self.buffer.extend_from_slice(&line);
+        self.num_buffered_docs += 1;
+    }
+
+    fn source_dir_path(&self) -> PathBuf {
+        Path::new(&self.index_id).join(&self.source_id)
+    }
+
+    /// Returns the number of dead letter files of the source, listing them from the storage the
+    /// first time. Storages that do not support listing are assumed to be empty.
+    async fn num_files(&mut self) -> anyhow::Result<usize> {
+        if let Some(num_files) = self.num_files_opt {
+            return Ok(num_files);
+        }
+        let num_files = match self.storage.list(&self.source_dir_path()).await {
+            Ok(file_paths) => file_paths.len(),
+            Err(error) if error.kind() == StorageErrorKind::Unsupported => {
+                warn!(
+                    index_id=%self.index_id,
+                    source_id=%self.source_id,
+                    "dead letter queue storage does not support listing, counting dead letter \
+                     files from zero"
+                );
+                0
+            }
+            Err(error) => {
+                return Err(error).context("failed to list dead letter files");
+            }
+        };
+        self.num_files_opt = Some(num_files);
+        Ok(num_files)
+    }
+
+    /// Writes the buffered documents to a new dead letter file.
+    ///
+    /// The buffered documents are dropped if the source already has `max_num_files` dead letter
+    /// files. Failing to write the file returns an error, so that the batch checkpoint is not
+    /// published and the documents are not lost.
+    pub(super) async fn flush(&mut self) -> anyhow::Result<()> {
+        if self.num_buffered_docs > 0 && self.num_files().await? >= self.max_num_files {
+            self.num_dropped_docs += std::mem::take(&mut self.num_buffered_docs);
+            self.buffer.clear();
+        }
+        let num_dropped_docs = std::mem::take(&mut self.num_dropped_docs);
+        if num_dropped_docs > 0 {
+            warn!(
+                index_id=%self.index_id,
+                source_id=%self.source_id,
+                num_dropped_docs,
+                max_file_size=self.max_file_size,
+                max_num_files=self.max_num_files,
+                "dead letter queue is full, dropping failed documents"
+            );
+        }
+        if self.num_buffered_docs == 0 {
+            return Ok(());
+        }
+        let num_docs = std::mem::take(&mut self.num_buffered_docs);
+        let payload = std::mem::take(&mut self.buffer);
+        let file_path = self
+            .source_dir_path()
+            .join(format!("{}.ndjson", Ulid::new()));
+        self.storage
+            .put(&file_path, Box::new(payload))
+            .await
+            .with_context(|| {
+                format!(
+                    "failed to write {num_docs} failed documents to dead letter file `{}`",
+                    file_path.display()
+                )
+            })?;
+        if let Some(num_files) = self.num_files_opt.as_mut() {
+            *num_files += 1;
+        }
+        info!(
+            index_id=%self.index_id,
+            source_id=%self.source_id,
+            num_docs,
+            file_path=%file_path.display(),
+            "wrote failed documents to dead letter queue"
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytesize::ByteSize;
+    use quickwit_common::uri::Uri;
+    use quickwit_storage::{MockStorage, RamStorage};
+    use serde_json::Value as JsonValue;
+
+    use super::*;
+
+    async fn read_dead_letters(storage: &RamStorage) -> Vec<Vec<JsonValue>> {
+        let mut files = Vec::new();
+        for file_path in storage.list_files().await {
+            let file_content = storage.get_all(&file_path).await.unwrap();
+            let dead_letters: Vec<JsonValue> = file_content
+                .as_slice()
+                .split(|byte| *byte == b'\n')
+                .filter(|line| !line.is_empty())
+                .map(|line| serde_json::from_slice(line).unwrap())
+                .collect();
+            files.push(dead_letters);
+        }
+        files
+    }
+
+    #[tokio::test]
+    async fn test_dead_letter_queue_is_bounded() {
+        let storage = Arc::new(RamStorage::default());
+        let config = DeadLetterQueueConfig {
+            uri: Uri::for_test("ram:///dead-letter-queue"),
+            max_file_size: ByteSize::b(300),
+            max_num_files: 2,
+        };
+        let mut dead_letter_queue = DeadLetterQueue::new(
+            storage.clone(),
+            "my-index".to_string(),
+            "my-source".to_string(),
+            &config,
+        );
+        // Nothing to write.
+        dead_letter_queue.flush().await.unwrap();
+        assert!(storage.list_files().await.is_empty());
+
+        let error = "doc parsing error: invalid JSON";
+        for _ in 0..3 {
+            dead_letter_queue.push(&Bytes::from_static(b"{"), error.to_string(), 1);
+        }
+        dead_letter_queue.flush().await.unwrap();
+
+        let files = read_dead_letters(&storage).await;
+        assert_eq!(files.len(), 1);
+        // Each dead letter is about 140 bytes long, so only two of them fit in a file.
+        assert_eq!(files[0].len(), 2);
+        assert_eq!(files[0][0]["index_id"], "my-index");
+        assert_eq!(files[0][0]["source_id"], "my-source");
+        assert_eq!(files[0][0]["error"], "doc parsing error: invalid JSON");
+        assert_eq!(files[0][0]["num_failed_docs"], 1);
+        assert_eq!(files[0][0]["doc"], "{");
+        assert!(files[0][0]["timestamp"].is_i64());
+
+        // The source has at most two dead letter files.
+        for _ in 0..2 {
+            dead_letter_queue.push(&Bytes::from_static(b"{"), error.to_string(), 1);
+            dead_letter_queue.flush().await.unwrap();
+        }
+        assert_eq!(storage.list_files().await.len(), 2);
+
+        // The files written before a restart count as well.
+        let mut dead_letter_queue = DeadLetterQueue::new(
+            storage.clone(),
+            "my-index".to_string(),
+            "my-source".to_string(),
+            &config,
+        );
+        dead_letter_queue.push(&Bytes::from_static(b"{"), error.to_string(), 1);
+        dead_letter_queue.flush().await.unwrap();
+        assert_eq!(storage.list_files().await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_dead_letter_queue_flush_error() {
+        let mut mock_storage = MockStorage::new();
+        mock_storage.expect_list().returning(|_| Ok(Vec::new()));
+        mock_storage
+            .expect_put()
+            .returning(|_, _| Err(StorageErrorKind::Io.with_error(anyhow::anyhow!("disk full"))));
+        let config = DeadLetterQueueConfig {
+            uri: Uri::for_test("ram:///dead-letter-queue"),
+            max_file_size: ByteSize::mb(1),
+            max_num_files: 10,
+        };
+        let mut dead_letter_queue = DeadLetterQueue::new(
+            Arc::new(mock_storage),
+            "my-index".to_string(),
+            "my-source".to_string(),
+            &config,
+        );
+        dead_letter_queue.push(&Bytes::from_static(b"{"), "invalid JSON".to_string(), 1);
+        let error = dead_letter_queue.flush().await.unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("failed to write 1 failed documents to dead letter file"));
+    }
+}
@@ -0,0 +1,307 @@
+// Copyright (C) 2024 Quickwit, Inc.
+//
+// Quickwit is offered under the AGPL v3.0 and as commercial software.
+// For commercial licensing, contact us at hello@quickwit.io.
+//
+// AGPL:
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as
+// published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use anyhow::Context;
+use quickwit_config::DerivedFieldRule;
+use quickwit_doc_mapper::JsonObject;
+use regex::Regex;
+use serde_json::Value as JsonValue;
+
+/// Derived field rule, ready to be applied to documents.
+enum CompiledRule {
+    Copy {
+        source: String,
+        target: String,
+    },
+    Concat {
+        sources: Vec<String>,
+        separator: String,
+        target: String,
+    },
+    RegexExtract {
+        source: String,
+        regex: Regex,
+        group: usize,
+        target: String,
+    },
+    Lowercase {
+        source: String,
+        target: String,
+    },
+}
+
+/// Applies the derived field rules of an index to the documents, before they reach the doc
+/// mapper.
+pub(super) struct DerivedFields {
+    rules: Vec<CompiledRule>,
+}
+
+impl DerivedFields {
+    pub fn try_new(derived_field_rules: &[DerivedFieldRule]) -> anyhow::Result<Self> {
+        let mut rules = Vec::with_capacity(derived_field_rules.len());
+        for derived_field_rule in derived_field_rules {
+            let rule = match derived_field_rule.clone() {
+                DerivedFieldRule::Copy { source, target } => CompiledRule::Copy { source, target },
+                DerivedFieldRule::Concat {
+                    sources,
+                    separator,
+                    target,
+                } => CompiledRule::Concat {
+                    sources,
+                    separator,
+                    target,
+                },
+                DerivedFieldRule::RegexExtract {
+                    source,
+                    pattern,
+                    group,
+                    target,
+                } => {
+                    let regex = Regex::new(&pattern)
+                        .with_context(|| format!("invalid regular expression `{pattern}`"))?;
+                    CompiledRule::RegexExtract {
+                        source,
+                        regex,
+                        group,
+                        target,
+                    }
+                }
+                DerivedFieldRule::Lowercase { source, target } => {
+                    let target = target.unwrap_or_else(|| source.clone());
+                    CompiledRule::Lowercase { source, target }
+                }
+            };
+            rules.push(rule);
+        }
+        Ok(Self { rules })
+    }
+
+    pub fn apply(&self, json_obj: &mut JsonObject) {
+        for rule in &self.rules {
+            apply_rule(rule, json_obj);
+        }
+    }
+}
+
+fn apply_rule(rule: &CompiledRule, json_obj: &mut JsonObject) {
+    match rule {
+        CompiledRule::Copy { source, target } => {
+            if let Some(value) = get_value(json_obj, source) {
+                let value = value.clone();
+                set_value(json_obj, target, value);
+            }
+        }
+        CompiledRule::Concat {
+            sources,
+            separator,
+            target,
+        } => {
+            let parts: Vec<String> = sources
+                .iter()
+                .filter_map(|source| get_value(json_obj, source).and_then(scalar_to_string))
+                .collect();
+            if !parts.is_empty() {
+                set_value(json_obj, target, JsonValue::String(parts.join(separator)));
+            }
+        }
+        CompiledRule::RegexExtract {
+            source,
+            regex,
+            group,
+            target,
+        } => {
+            let Some(JsonValue::String(text)) = get_value(json_obj, source) else {
+                return;
+            };
+            let Some(extracted) = regex
+                .captures(text)
+                .and_then(|captures| captures.get(*group))
+                .map(|group_match| group_match.as_str().to_string())
+            else {
+                return;
+            };
+            set_value(json_obj, target, JsonValue::String(extracted));
+        }
+        CompiledRule::Lowercase { source, target } => {
+            let Some(JsonValue::String(text)) = get_value(json_obj, source) else {
+                return;
+            };
+            let lowercased = text.to_lowercase();
+            set_value(json_obj, target, JsonValue::String(lowercased));
+        }
+    }
+}
+
+fn scalar_to_string(value: &JsonValue) -> Option<String> {
+    match value {
+        JsonValue::String(text) => Some(text.clone()),
+        JsonValue::Number(number) => Some(number.to_string()),
+        JsonValue::Bool(boolean) => Some(boolean.to_string()),
+        JsonValue::Null | JsonValue::Array(_) | JsonValue::Object(_) => None,
+    }
+}
+
+fn get_value<'a>(json_obj: &'a JsonObject, path: &str) -> Option<&'a JsonValue> {
+    let mut path_segments = path.split('.');
+    let first_segment = path_segments.next()?;
+    let mut value = json_obj.get(first_segment)?;
+    for path_segment in path_segments {
+        value = value.as_object()?.get(path_segment)?;
+    }
+    Some(value)
+}
+
+/// Sets the value at `path`, creating the intermediate objects if necessary. If one of the
+/// intermediate values exists but is not an object, the document is left unchanged.
+fn set_value(json_obj: &mut JsonObject, path: &str, value: JsonValue) {
+    let Some((parent_path, field_name)) = path.rsplit_once('.') else {
+        json_obj.insert(path.to_string(), value);
+        return;
+    };
+    let mut parent_obj = json_obj;
+    for path_segment in parent_path.split('.') {
+        let child_value = parent_obj
+            .entry(path_segment)
+            .or_insert_with(|| JsonValue::Object(JsonObject::new()));
+        let Some(child_obj) = child_value.as_object_mut() else {
+            return;
+        };
+        parent_obj = child_obj;
+    }
+    parent_obj.insert(field_name.to_string(), value);
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn apply_rules(rules_json: JsonValue, doc_json: JsonValue) -> JsonValue {
+        let rules: Vec<DerivedFieldRule> = serde_json::from_value(rules_json).unwrap();
+        let derived_fields = DerivedFields::try_new(&rules).unwrap();
+        let JsonValue::Object(mut json_obj) = doc_json else {
+            panic!("expected a JSON object");
+        };
+        derived_fields.apply(&mut json_obj);
+        JsonValue::Object(json_obj)
+    }
+
+    #[test]
+    fn test_derived_fields_copy() {
+        let rules = json!([{"type": "copy", "source": "url", "target": "url_copy"}]);
+        assert_eq!(
+            apply_rules(rules.clone(), json!({"url": "https://quickwit.io"})),
+            json!({"url": "https://quickwit.io", "url_copy": "https://quickwit.io"})
+        );
+        // Missing source.
+        assert_eq!(
+            apply_rules(rules, json!({"body": "hello"})),
+            json!({"body": "hello"})
+        );
+        // Nested source and target, any value type.
+        let rules = json!([{"type": "copy", "source": "http.status", "target": "meta.status"}]);
+        assert_eq!(
+            apply_rules(rules, json!({"http": {"status": 404}})),
+            json!({"http": {"status": 404}, "meta": {"status": 404}})
+        );
+    }
+
+    #[test]
+    fn test_derived_fields_concat() {
+        let rules = json!([{
+            "type": "concat",
+            "sources": ["first_name", "middle_name", "last_name", "age"],
+            "separator": " ",
+            "target": "full_name"
+        }]);
+        assert_eq!(
+            apply_rules(
+                rules.clone(),
+                json!({"first_name": "Ada", "last_name": "Lovelace", "age": 36})
+            ),
+            json!({
+                "first_name": "Ada",
+                "last_name": "Lovelace",
+                "age": 36,
+                "full_name": "Ada Lovelace 36"
+            })
+        );
+        assert_eq!(apply_rules(rules, json!({})), json!({}));
+    }
+
+    #[test]
+    fn test_derived_fields_regex_extract() {
+        let rules = json!([{
+            "type": "regex_extract",
+            "source": "url",
+            "pattern": r"^https?://[^/]*\.([a-z]+)(?::\d+)?(?:/|$)",
+            "target": "tld"
+        }]);
+        assert_eq!(
+            apply_rules(
+                rules.clone(),
+                json!({"url": "https://www.quickwit.io/docs"})
+            ),
+            json!({"url": "https://www.quickwit.io/docs", "tld": "io"})
+        );
+        // No match.
+        assert_eq!(
+            apply_rules(rules.clone(), json!({"url": "not a url"})),
+            json!({"url": "not a url"})
+        );
+        // Not a string.
+        assert_eq!(apply_rules(rules, json!({"url": 42})), json!({"url": 42}));
+    }
+
+    #[test]
+    fn test_derived_fields_lowercase() {
+        let rules = json!([{"type": "lowercase", "source": "host"}]);
+        assert_eq!(
+            apply_rules(rules, json!({"host": "WWW.Quickwit.IO"})),
+            json!({"host": "www.quickwit.io"})
+        );
+        let rules = json!([{"type": "lowercase", "source": "host", "target": "host_lower"}]);
+        assert_eq!(
+            apply_rules(rules, json!({"host": "WWW.Quickwit.IO"})),
+            json!({"host": "WWW.Quickwit.IO", "host_lower": "www.quickwit.io"})
+        );
+    }
+
+    #[test]
+    fn test_derived_fields_rules_are_chained() {
+        let rules = json!([
+            {"type": "lowercase", "source": "url", "target": "url_lower"},
+            {"type": "regex_extract", "source": "url_lower", "pattern": r"\.([a-z]+)$", "target": "tld"}
+        ]);
+        assert_eq!(
+            apply_rules(rules, json!({"url": "QUICKWIT.IO"})),
+            json!({"url": "QUICKWIT.IO", "url_lower": "quickwit.io", "tld": "io"})
+        );
+    }
+
+    #[test]
+    fn test_derived_fields_does_not_overwrite_non_object_parent() {
+        let rules = json!([{"type": "copy", "source": "url", "target": "http.url"}]);
+        assert_eq!(
+            apply_rules(rules, json!({"url": "quickwit.io", "http": "1.1"})),
+            json!({"url": "quickwit.io", "http": "1.1"})
+        );
+    }
+}
@@ -26,7 +26,7 @@ use async_trait::async_trait;
 use bytes::Bytes;
 use quickwit_actors::{Actor, ActorContext, ActorExitStatus, Handler, Mailbox, QueueCapacity};
 use quickwit_common::runtimes::RuntimeType;
-use quickwit_config::{SourceInputFormat, TransformConfig};
+use quickwit_config::{DerivedFieldRule, SourceInputFormat, TransformConfig};
 use quickwit_doc_mapper::{DocMapper, DocParsingError, JsonObject};
 use quickwit_opentelemetry::otlp::{
     parse_otlp_spans_json, parse_otlp_spans_protobuf, JsonSpanIterator, OtlpTraceError,
@@ -39,6 +39,8 @@ use thiserror::Error;
 use tokio::runtime::Handle;
 use tracing::warn;
 
+use super::dead_letter_queue::DeadLetterQueue;
+use super::derived_fields::DerivedFields;
 #[cfg(feature = "vrl")]
 use super::vrl_processing::*;
 use crate::actors::Indexer;
@@ -251,6 +253,10 @@ pub struct DocProcessorCounters {
     pub num_oltp_trace_errors: AtomicU64,
     pub num_valid_docs: AtomicU64,
 
+    /// Number of fields dropped from otherwise valid documents because their value could not be
+    /// coerced to their mapped type (see the `on_field_error` doc mapping parameter).
+    pub num_dropped_fields: AtomicU64,
+
     /// Number of bytes that went through the indexer
     /// during its entire lifetime.
     ///
@@ -267,6 +273,7 @@ impl DocProcessorCounters {
             num_transform_errors: Default::default(),
             num_oltp_trace_errors: Default::default(),
             num_valid_docs: Default::default(),
+            num_dropped_fields: Default::default(),
             num_bytes_total: Default::default(),
         }
     }
@@ -302,6 +309,11 @@ impl DocProcessorCounters {
             .inc_by(num_bytes);
     }
 
+    pub fn record_dropped_fields(&self, num_dropped_fields: u64) {
+        self.num_dropped_fields
+            .fetch_add(num_dropped_fields, Ordering::Relaxed);
+    }
+
     pub fn record_error(&self, error: DocProcessorError, num_bytes: u64) {
         let label = match error {
             DocProcessorError::DocMapperParsing(_) => {
@@ -344,17 +356,22 @@ pub struct DocProcessor {
     publish_lock: PublishLock,
     #[cfg(feature = "vrl")]
     transform_opt: Option<VrlProgram>,
+    derived_fields: DerivedFields,
     input_format: SourceInputFormat,
+    dead_letter_queue_opt: Option<DeadLetterQueue>,
 }
 
 impl DocProcessor {
+    #[allow(clippy::too_many_arguments)]
     pub fn try_new(
         index_id: String,
         source_id: String,
         doc_mapper: Arc<dyn DocMapper>,
         indexer_mailbox: Mailbox<Indexer>,
         transform_config_opt: Option<TransformConfig>,
+        derived_field_rules: &[DerivedFieldRule],
         input_format: SourceInputFormat,
+        dead_letter_queue_opt: Option<DeadLetterQueue>,
     ) -> anyhow::Result<Self> {
         let timestamp_field_opt = extract_timestamp_field(&*doc_mapper)?;
         if cfg!(not(feature = "vrl")) && transform_config_opt.is_some() {
@@ -370,7 +387,9 @@ impl DocProcessor {
             transform_opt: transform_config_opt
                 .map(VrlProgram::try_from_transform_config)
                 .transpose()?,
+            derived_fields: DerivedFields::try_new(derived_field_rules)?,
             input_format,
+            dead_letter_queue_opt,
         };
         Ok(doc_processor)
     }
@@ -403,6 +422,15 @@ impl DocProcessor {
         #[cfg(not(feature = "vrl"))]
         let transform_opt: Option<&mut VrlProgram> = None;
 
+        let raw_doc_for_dead_letter_queue_opt = self
+            .dead_letter_queue_opt
+            .is_some()
+            .then(|| raw_doc.clone());
+        // A raw document can yield several documents (e.g. the spans of an OTLP request), but it
+        // is written only once to the dead letter queue, along with its first error.
+        let mut first_error_opt: Option<String> = None;
+        let mut num_failed_docs = 0;
+
         for json_doc_result in parse_raw_doc(self.input_format, raw_doc, num_bytes, transform_opt) {
             let processed_doc_result =
                 json_doc_result.and_then(|json_doc| self.process_json_doc(json_doc));
@@ -419,17 +447,38 @@ impl DocProcessor {
                         "{}",
                         error
                     );
+                    num_failed_docs += 1;
+
+                    if raw_doc_for_dead_letter_queue_opt.is_some() && first_error_opt.is_none() {
+                        first_error_opt = Some(error.to_string());
+                    }
                     self.counters.record_error(error, num_bytes as u64);
                 }
             }
         }
+        if let (Some(dead_letter_queue), Some(raw_doc), Some(first_error)) = (
+            self.dead_letter_queue_opt.as_mut(),
+            &raw_doc_for_dead_letter_queue_opt,
+            first_error_opt,
+        ) {
+            dead_letter_queue.push(raw_doc, first_error, num_failed_docs);
+        }
     }
 
     fn process_json_doc(&self, json_doc: JsonDoc) -> Result<ProcessedDoc, DocProcessorError> {
         let num_bytes = json_doc.num_bytes;
+        let mut json_obj = json_doc.json_obj;
+        self.derived_fields.apply(&mut json_obj);
 
-        let (partition, doc) = self.doc_mapper.doc_from_json_obj(json_doc.json_obj)?;
+        let (partition, doc, num_dropped_fields) = self
+            .doc_mapper
+            .doc_from_json_obj_with_num_dropped_fields(json_obj)?;
         let timestamp_opt = self.extract_timestamp(&doc)?;
+
+        if num_dropped_fields > 0 {
+            self.counters
+                .record_dropped_fields(num_dropped_fields as u64);
+        }
         Ok(ProcessedDoc {
             doc,
             timestamp_opt,
@@ -510,6 +559,11 @@ impl Handler<RawDocBatch> for DocProcessor {
             self.process_raw_doc(raw_doc, &mut processed_docs);
             ctx.record_progress();
         }
+        // The failed documents are written before the batch checkpoint can be published, so that
+        // they are not lost if the pipeline restarts.
+        if let Some(dead_letter_queue) = self.dead_letter_queue_opt.as_mut() {
+            ctx.protect_future(dead_letter_queue.flush()).await?;
+        }
         let processed_doc_batch = ProcessedDocBatch {
             docs: processed_docs,
             checkpoint_delta: raw_doc_batch.checkpoint_delta,
@@ -556,15 +610,17 @@ mod tests {
     use std::sync::Arc;
 
     use bytes::Bytes;
+    use bytesize::ByteSize;
     use prost::Message;
     use quickwit_actors::Universe;
     use quickwit_common::uri::Uri;
-    use quickwit_config::{build_doc_mapper, SearchSettings};
+    use quickwit_config::{build_doc_mapper, DeadLetterQueueConfig, SearchSettings};
     use quickwit_doc_mapper::{default_doc_mapper_for_test, DefaultDocMapper};
     use quickwit_metastore::checkpoint::SourceCheckpointDelta;
     use quickwit_opentelemetry::otlp::OtlpGrpcTracesService;
     use quickwit_proto::opentelemetry::proto::collector::trace::v1::ExportTraceServiceRequest;
     use quickwit_proto::opentelemetry::proto::trace::v1::{ResourceSpans, ScopeSpans, Span};
+    use quickwit_storage::{MockStorage, RamStorage, Storage, StorageErrorKind};
     use serde_json::Value as JsonValue;
     use tantivy::schema::NamedFieldDocument;
     use tantivy::Document;
@@ -585,7 +641,9 @@ mod tests {
             doc_mapper.clone(),
             indexer_mailbox,
             None,
+            &[],
             SourceInputFormat::Json,
+            None,
         )
         .unwrap();
         let (doc_processor_mailbox, doc_processor_handle) =
@@ -649,6 +707,160 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_doc_processor_derived_fields() {
+        let doc_mapper: Arc<dyn DocMapper> = Arc::new(
+            serde_json::from_str::<DefaultDocMapper>(
+                r#"{
+                    "mode": "strict",
+                    "field_mappings": [
+                        { "name": "host", "type": "text", "tokenizer": "raw" },
+                        { "name": "tld", "type": "text", "tokenizer": "raw" }
+                    ]
+                }"#,
+            )
+            .unwrap(),
+        );
+        let derived_field_rules: Vec<DerivedFieldRule> = serde_json::from_str(
+            r#"[
+                { "type": "lowercase", "source": "host" },
+                { "type": "regex_extract", "source": "host", "pattern": "\\.([a-z]+)$", "target": "tld" }
+            ]"#,
+        )
+        .unwrap();
+        let universe = Universe::with_accelerated_time();
+        let (indexer_mailbox, indexer_inbox) = universe.create_test_mailbox();
+        let doc_processor = DocProcessor::try_new(
+            "my-index".to_string(),
+            "my-source".to_string(),
+            doc_mapper.clone(),
+            indexer_mailbox,
+            None,
+            &derived_field_rules,
+            SourceInputFormat::Json,
+            None,
+        )
+        .unwrap();
+        let (doc_processor_mailbox, doc_processor_handle) =
+            universe.spawn_builder().spawn(doc_processor);
+        doc_processor_mailbox
+            .send_message(RawDocBatch::for_test(
+                &[r#"{"host": "WWW.Quickwit.IO"}"#],
+                0..1,
+            ))
+            .await
+            .unwrap();
+        let counters = doc_processor_handle
+            .process_pending_and_observe()
+            .await
+            .state;
+        assert_eq!(counters.num_valid_docs.load(Ordering::Relaxed), 1);
+
+        let output_messages = indexer_inbox.drain_for_test();
+        assert_eq!(output_messages.len(), 1);
+        let batch = *(output_messages
+            .into_iter()
+            .next()
+            .unwrap()
+            .downcast::<ProcessedDocBatch>()
+            .unwrap());
+        assert_eq!(batch.docs.len(), 1);
+
+        let schema = doc_mapper.schema();
+        let NamedFieldDocument(named_field_doc_map) = batch.docs[0].doc.to_named_doc(&schema);
+        let doc_json = JsonValue::Object(doc_mapper.doc_to_json(named_field_doc_map).unwrap());
+        assert_eq!(doc_json["host"], "www.quickwit.io");
+        assert_eq!(doc_json["tld"], "io");
+        universe.assert_quit().await;
+    }
+
+    #[tokio::test]
+    async fn test_doc_processor_dead_letter_queue() {
+        let universe = Universe::with_accelerated_time();
+        let doc_mapper = Arc::new(default_doc_mapper_for_test());
+        let (indexer_mailbox, indexer_inbox) = universe.create_test_mailbox();
+        let dead_letter_queue_storage = Arc::new(RamStorage::default());
+        let dead_letter_queue_config = DeadLetterQueueConfig {
+            uri: Uri::for_test("ram:///dead-letter-queue"),
+            max_file_size: ByteSize::mb(1),
+            max_num_files: 10,
+        };
+        let dead_letter_queue = DeadLetterQueue::new(
+            dead_letter_queue_storage.clone(),
+            "my-index".to_string(),
+            "my-source".to_string(),
+            &dead_letter_queue_config,
+        );
+        let doc_processor = DocProcessor::try_new(
+            "my-index".to_string(),
+            "my-source".to_string(),
+            doc_mapper,
+            indexer_mailbox,
+            None,
+            &[],
+            SourceInputFormat::Json,
+            Some(dead_letter_queue),
+        )
+        .unwrap();
+        let (doc_processor_mailbox, doc_processor_handle) =
+            universe.spawn_builder().spawn(doc_processor);
+        doc_processor_mailbox
+            .send_message(RawDocBatch::for_test(
+                &[
+                    r#"{"body": "happy", "response_date": "2021-12-19T16:39:57+00:00", "response_time": 12, "response_payload": "YWJj"}"#, // missing timestamp
+                    r#"{"body": "happy", "timestamp": 1628837062, "response_date": "2021-12-19T16:39:59+00:00", "response_time": 2, "response_payload": "YWJj"}"#, // ok
+                    r#"{"body": "happy2", "timestamp": 1628837062, "response_date": "2021-12-19T16:40:57+00:00", "response_time": 13, "response_payload": "YWJj"}"#, // ok
+                    "{", // invalid json
+                ],
+                0..4,
+            ))
+            .await
+            .unwrap();
+        let counters = doc_processor_handle
+            .process_pending_and_observe()
+            .await
+            .state;
+        assert_eq!(counters.num_doc_parsing_errors.load(Ordering::Relaxed), 2);
+        assert_eq!(counters.num_valid_docs.load(Ordering::Relaxed), 2);
+
+        let output_messages = indexer_inbox.drain_for_test();
+        assert_eq!(output_messages.len(), 1);
+        let batch = *(output_messages
+            .into_iter()
+            .next()
+            .unwrap()
+            .downcast::<ProcessedDocBatch>()
+            .unwrap());
+        assert_eq!(batch.docs.len(), 2);
+
+        let dead_letter_files = dead_letter_queue_storage.list_files().await;
+        assert_eq!(dead_letter_files.len(), 1);
+        assert!(dead_letter_files[0].starts_with("my-index/my-source"));
+        let dead_letter_file = dead_letter_queue_storage
+            .get_all(&dead_letter_files[0])
+            .await
+            .unwrap();
+        let dead_letters: Vec<JsonValue> = serde_json::Deserializer::from_slice(&dead_letter_file)
+            .into_iter::<JsonValue>()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(dead_letters.len(), 2);
+        assert!(dead_letters[0]["doc"]
+            .as_str()
+            .unwrap()
+            .contains("2021-12-19T16:39:57+00:00"));
+        assert!(dead_letters[0]["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("doc mapper parsing error"));
+        assert_eq!(dead_letters[1]["doc"], "{");
+        assert!(dead_letters[1]["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("doc parsing error"));
+        universe.assert_quit().await;
+    }
+
     const DOCMAPPER_WITH_PARTITION_JSON: &str = r#"
         {
             "tag_fields": ["tenant"],
@@ -659,6 +871,59 @@ mod tests {
             ]
         }"#;
 
+    #[tokio::test]
+    async fn test_doc_processor_on_field_error_skip() {
+        let doc_mapper: Arc<dyn DocMapper> = Arc::new(
+            serde_json::from_str::<DefaultDocMapper>(
+                r#"{
+                    "on_field_error": "skip",
+                    "field_mappings": [
+                        { "name": "body", "type": "text" },
+                        { "name": "response_time", "type": "u64" }
+                    ]
+                }"#,
+            )
+            .unwrap(),
+        );
+        let universe = Universe::with_accelerated_time();
+        let (indexer_mailbox, indexer_inbox) = universe.create_test_mailbox();
+        let doc_processor = DocProcessor::try_new(
+            "my-index".to_string(),
+            "my-source".to_string(),
+            doc_mapper,
+            indexer_mailbox,
+            None,
+            &[],
+            SourceInputFormat::Json,
+            None,
+        )
+        .unwrap();
+        let (doc_processor_mailbox, doc_processor_handle) =
+            universe.spawn_builder().spawn(doc_processor);
+        doc_processor_mailbox
+            .send_message(RawDocBatch::for_test(
+                &[
+                    r#"{"body": "happy", "response_time": 12}"#,
+                    r#"{"body": "happy", "response_time": "twelve"}"#,
+                ],
+                0..2,
+            ))
+            .await
+            .unwrap();
+        let counters = doc_processor_handle
+            .process_pending_and_observe()
+            .await
+            .state;
+        assert_eq!(counters.num_valid_docs.load(Ordering::Relaxed), 2);
+        assert_eq!(counters.num_doc_parsing_errors.load(Ordering::Relaxed), 0);
+        assert_eq!(counters.num_dropped_fields.load(Ordering::Relaxed), 1);
+
+        let processed_doc_batches: Vec<ProcessedDocBatch> = indexer_inbox.drain_for_test_typed();
+        assert_eq!(processed_doc_batches.len(), 1);
+        assert_eq!(processed_doc_batches[0].docs.len(), 2);
+        universe.assert_quit().await;
+    }
+
     #[tokio::test]
     async fn test_doc_processor_partitioning() -> anyhow::Result<()> {
         let doc_mapper: Arc<dyn DocMapper> = Arc::new(
@@ -672,7 +937,9 @@ mod tests {
             doc_mapper,
             indexer_mailbox,
             None,
+            &[],
             SourceInputFormat::Json,
+            None,
         )
         .unwrap();
         let (doc_processor_mailbox, doc_processor_handle) =
@@ -728,7 +995,9 @@ mod tests {
             doc_mapper,
             indexer_mailbox,
             None,
+            &[],
             SourceInputFormat::Json,
+            None,
         )
         .unwrap();
         let (doc_processor_mailbox, doc_processor_handle) =
@@ -760,7 +1029,9 @@ mod tests {
             doc_mapper,
             indexer_mailbox,
             None,
+            &[],
             SourceInputFormat::Json,
+            None,
         )
         .unwrap();
         let (doc_processor_mailbox, doc_processor_handle) =
@@ -806,7 +1077,9 @@ mod tests {
             doc_mapper,
             indexer_mailbox,
             None,
+            &[],
             SourceInputFormat::OtlpTraceJson,
+            None,
         )
         .unwrap();
 
@@ -871,6 +1144,135 @@ mod tests {
         universe.assert_quit().await;
     }
 
+    #[tokio::test]
+    async fn test_doc_processor_dead_letter_queue_otlp_trace() {
+        let universe = Universe::with_accelerated_time();
+        // The spans lack the timestamp field of this doc mapper, so they all fail.
+        let doc_mapper = Arc::new(default_doc_mapper_for_test());
+        let (indexer_mailbox, _indexer_inbox) = universe.create_test_mailbox();
+        let dead_letter_queue_storage = Arc::new(RamStorage::default());
+        let dead_letter_queue_config = DeadLetterQueueConfig {
+            uri: Uri::for_test("ram:///dead-letter-queue"),
+            max_file_size: ByteSize::mb(1),
+            max_num_files: 10,
+        };
+        let dead_letter_queue = DeadLetterQueue::new(
+            dead_letter_queue_storage.clone(),
+            "my-index".to_string(),
+            "my-source".to_string(),
+            &dead_letter_queue_config,
+        );
+        let doc_processor = DocProcessor::try_new(
+            "my-index".to_string(),
+            "my-source".to_string(),
+            doc_mapper,
+            indexer_mailbox,
+            None,
+            &[],
+            SourceInputFormat::OtlpTraceJson,
+            Some(dead_letter_queue),
+        )
+        .unwrap();
+        let (doc_processor_mailbox, doc_processor_handle) =
+            universe.spawn_builder().spawn(doc_processor);
+
+        let spans = (0..3)
+            .map(|i| Span {
+                trace_id: vec![i + 1; 16],
+                span_id: vec![i + 1; 8],
+                start_time_unix_nano: 1_000_000_001,
+                end_time_unix_nano: 1_000_000_002,
+                ..Default::default()
+            })
+            .collect();
+        let request = ExportTraceServiceRequest {
+            resource_spans: vec![ResourceSpans {
+                scope_spans: vec![ScopeSpans {
+                    spans,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        };
+        let raw_doc_batch = RawDocBatch {
+            docs: vec![Bytes::from(serde_json::to_vec(&request).unwrap())],
+            checkpoint_delta: SourceCheckpointDelta::from_range(0..1),
+            force_commit: false,
+        };
+        doc_processor_mailbox
+            .send_message(raw_doc_batch)
+            .await
+            .unwrap();
+        let counters = doc_processor_handle
+            .process_pending_and_observe()
+            .await
+            .state;
+        assert_eq!(counters.num_valid_docs.load(Ordering::Relaxed), 0);
+
+        // The request is written once, although each of its spans failed.
+        let dead_letter_files = dead_letter_queue_storage.list_files().await;
+        assert_eq!(dead_letter_files.len(), 1);
+        let dead_letter_file = dead_letter_queue_storage
+            .get_all(&dead_letter_files[0])
+            .await
+            .unwrap();
+        let dead_letters: Vec<JsonValue> = serde_json::Deserializer::from_slice(&dead_letter_file)
+            .into_iter::<JsonValue>()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(dead_letters.len(), 1);
+        assert_eq!(dead_letters[0]["num_failed_docs"], 3);
+        universe.assert_quit().await;
+    }
+
+    #[tokio::test]
+    async fn test_doc_processor_dead_letter_queue_write_failure() {
+        let universe = Universe::with_accelerated_time();
+        let doc_mapper = Arc::new(default_doc_mapper_for_test());
+        let (indexer_mailbox, indexer_inbox) = universe.create_test_mailbox();
+        let mut mock_storage = MockStorage::new();
+        mock_storage.expect_list().returning(|_| Ok(Vec::new()));
+        mock_storage
+            .expect_put()
+            .returning(|_, _| Err(StorageErrorKind::Io.with_error(anyhow::anyhow!("disk full"))));
+        let dead_letter_queue_config = DeadLetterQueueConfig {
+            uri: Uri::for_test("ram:///dead-letter-queue"),
+            max_file_size: ByteSize::mb(1),
+            max_num_files: 10,
+        };
+        let dead_letter_queue = DeadLetterQueue::new(
+            Arc::new(mock_storage),
+            "my-index".to_string(),
+            "my-source".to_string(),
+            &dead_letter_queue_config,
+        );
+        let doc_processor = DocProcessor::try_new(
+            "my-index".to_string(),
+            "my-source".to_string(),
+            doc_mapper,
+            indexer_mailbox,
+            None,
+            &[],
+            SourceInputFormat::Json,
+            Some(dead_letter_queue),
+        )
+        .unwrap();
+        let (doc_processor_mailbox, doc_processor_handle) =
+            universe.spawn_builder().spawn(doc_processor);
+        doc_processor_mailbox
+            .send_message(RawDocBatch::for_test(&["{"], 0..1))
+            .await
+            .unwrap();
+        let (exit_status, _) = doc_processor_handle.join().await;
+        assert!(matches!(exit_status, ActorExitStatus::Failure(_)));
+
+        // The batch is not forwarded, so its checkpoint is not published.
+        assert!(indexer_inbox
+            .drain_for_test_typed::<ProcessedDocBatch>()
+            .is_empty());
+        universe.assert_quit().await;
+    }
+
     #[tokio::test]
     async fn test_doc_processor_otlp_trace_proto() {
         let root_uri = Uri::for_test("ram:///indexes");
@@ -886,7 +1288,9 @@ mod tests {
             doc_mapper,
             indexer_mailbox,
             None,
+            &[],
             SourceInputFormat::OtlpTraceProtobuf,
+            None,
         )
         .unwrap();
 
@@ -979,7 +1383,9 @@ mod tests_vrl {
             doc_mapper.clone(),
             indexer_mailbox,
             Some(transform_config),
+            &[],
             SourceInputFormat::Json,
+            None,
         )
         .unwrap();
         let (doc_processor_mailbox, doc_processor_handle) =
@@ -1069,7 +1475,9 @@ mod tests_vrl {
             doc_mapper.clone(),
             indexer_mailbox,
             Some(transform_config),
+            &[],
             SourceInputFormat::PlainText,
+            None,
         )
         .unwrap();
         let (doc_processor_mailbox, doc_processor_handle) =
@@ -57,6 +57,7 @@ use tracing::{info, info_span, warn, Span};
 use ulid::Ulid;
 
 use crate::actors::IndexSerializer;
+use crate::controlled_directory::ScratchSpaceQuota;
 use crate::models::{
     CommitTrigger, EmptySplit, IndexedSplitBatchBuilder, IndexedSplitBuilder, NewPublishLock,
     NewPublishToken, ProcessedDoc, ProcessedDocBatch, PublishLock,
@@ -68,6 +69,8 @@ const OTHER_PARTITION_ID: u64 = 3264326757911759461u64;
 #[derive(Debug)]
 struct CommitTimeout {
     workbench_id: Ulid,
+    /// `Schedule` if the commit schedule is due before the commit timeout, `Timeout` otherwise.
+    commit_trigger: CommitTrigger,
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
@@ -103,6 +106,7 @@ struct IndexerState {
     max_num_partitions: NonZeroU32,
     index_settings: IndexSettings,
     cooperative_indexing_permits: Option<Arc<Semaphore>>,
+    scratch_space_quota: ScratchSpaceQuota,
 }
 
 impl IndexerState {
@@ -134,6 +138,7 @@ impl IndexerState {
             self.indexing_directory.clone(),
             index_builder,
             io_controls,
+            self.scratch_space_quota.clone(),
         )?;
         info!(
             split_id=%indexed_split.split_id(),
@@ -252,13 +257,17 @@ impl IndexerState {
     ) -> anyhow::Result<&'a mut IndexingWorkbench> {
         if indexing_workbench_opt.is_none() {
             let indexing_workbench = self.create_workbench(ctx).await?;
+            let commit_delay = self.indexing_settings.commit_delay();
+            let commit_trigger = if commit_delay < self.indexing_settings.commit_timeout() {
+                CommitTrigger::Schedule
+            } else {
+                CommitTrigger::Timeout
+            };
             let commit_timeout_message = CommitTimeout {
                 workbench_id: indexing_workbench.workbench_id,
+                commit_trigger,
             };
-            ctx.schedule_self_msg(
-                self.indexing_settings.commit_timeout(),
-                commit_timeout_message,
-            );
+            ctx.schedule_self_msg(commit_delay, commit_timeout_message);
             *indexing_workbench_opt = Some(indexing_workbench);
         }
         let current_indexing_workbench = indexing_workbench_opt.as_mut().context(
@@ -465,7 +474,8 @@ impl Handler<CommitTimeout> for Indexer {
                 return Ok(());
             }
         }
-        self.send_to_serializer(CommitTrigger::Timeout, ctx).await?;
+        self.send_to_serializer(commit_timeout.commit_trigger, ctx)
+            .await?;
         Ok(())
     }
 }
@@ -522,6 +532,7 @@ impl Indexer {
         indexing_directory: TempDirectory,
         indexing_settings: IndexingSettings,
         cooperative_indexing_permits: Option<Arc<Semaphore>>,
+        scratch_space_quota: ScratchSpaceQuota,
         index_serializer_mailbox: Mailbox<IndexSerializer>,
     ) -> Self {
         let schema = doc_mapper.schema();
@@ -548,6 +559,7 @@ impl Indexer {
                 index_settings,
                 max_num_partitions: doc_mapper.max_num_partitions(),
                 cooperative_indexing_permits,
+                scratch_space_quota,
             },
             index_serializer_mailbox,
             indexing_workbench_opt: None,
@@ -765,6 +777,7 @@ mod tests {
             indexing_directory,
             indexing_settings,
             None,
+            ScratchSpaceQuota::unlimited(),
             index_serializer_mailbox,
         );
         let (indexer_mailbox, indexer_handle) = universe.spawn_builder().spawn(indexer);
@@ -901,6 +914,7 @@ mod tests {
             indexing_directory,
             indexing_settings,
             None,
+            ScratchSpaceQuota::unlimited(),
             index_serializer_mailbox,
         );
         let (indexer_mailbox, _indexer_handle) = universe.spawn_builder().spawn(indexer);
@@ -977,6 +991,7 @@ mod tests {
             indexing_directory,
             indexing_settings,
             None,
+            ScratchSpaceQuota::unlimited(),
             index_serializer_mailbox,
         );
         let (indexer_mailbox, indexer_handle) = universe.spawn_builder().spawn(indexer);
@@ -1028,6 +1043,133 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_indexer_triggers_commit_on_schedule() -> anyhow::Result<()> {
+        let universe = Universe::with_accelerated_time();
+        let pipeline_id = IndexingPipelineId {
+            index_uid: IndexUid::new_with_random_ulid("test-index"),
+            source_id: "test-source".to_string(),
+            node_id: "test-node".to_string(),
+            pipeline_uid: PipelineUid::default(),
+        };
+        let doc_mapper = Arc::new(default_doc_mapper_for_test());
+        let schema = doc_mapper.schema();
+        let body_field = schema.get_field("body").unwrap();
+        let timestamp_field = schema.get_field("timestamp").unwrap();
+        let indexing_directory = TempDirectory::for_test();
+        let mut indexing_settings = IndexingSettings::for_test();
+        indexing_settings.commit_timeout_secs = 3600;
+        // Every second.
+        indexing_settings.commit_schedule = Some("* * * * * *".to_string());
+        let (index_serializer_mailbox, index_serializer_inbox) = universe.create_test_mailbox();
+        let mut metastore = MetastoreServiceClient::mock();
+        metastore
+            .expect_last_delete_opstamp()
+            .returning(move |_last_delete_opstamp_request| Ok(LastDeleteOpstampResponse::new(10)));
+        metastore.expect_publish_splits().never();
+        let indexer = Indexer::new(
+            pipeline_id,
+            doc_mapper,
+            MetastoreServiceClient::from(metastore),
+            indexing_directory,
+            indexing_settings,
+            None,
+            ScratchSpaceQuota::unlimited(),
+            index_serializer_mailbox,
+        );
+        let (indexer_mailbox, indexer_handle) = universe.spawn_builder().spawn(indexer);
+        indexer_mailbox
+            .send_message(ProcessedDocBatch {
+                docs: vec![ProcessedDoc {
+                    doc: doc!(
+                        body_field=>"this is a test document",
+                        timestamp_field=>DateTime::from_timestamp_secs(1_662_529_435)
+                    ),
+                    timestamp_opt: Some(DateTime::from_timestamp_secs(1_662_529_435)),
+                    partition: 1,
+                    num_bytes: 30,
+                }],
+                checkpoint_delta: SourceCheckpointDelta::from_range(0..1),
+                force_commit: false,
+            })
+            .await?;
+        universe.sleep(Duration::from_secs(2)).await;
+
+        let indexer_counters = indexer_handle.process_pending_and_observe().await.state;
+        assert_eq!(indexer_counters.num_splits_emitted, 1);
+        assert_eq!(indexer_counters.num_split_batches_emitted, 1);
+
+        let messages: Vec<IndexedSplitBatchBuilder> = index_serializer_inbox.drain_for_test_typed();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].commit_trigger, CommitTrigger::Schedule);
+        assert_eq!(messages[0].splits[0].split_attrs.num_docs, 1);
+        universe.assert_quit().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_indexer_commit_schedule_does_not_prevent_num_docs_commit() -> anyhow::Result<()> {
+        let pipeline_id = IndexingPipelineId {
+            index_uid: IndexUid::new_with_random_ulid("test-index"),
+            source_id: "test-source".to_string(),
+            node_id: "test-node".to_string(),
+            pipeline_uid: PipelineUid::default(),
+        };
+        let doc_mapper = Arc::new(default_doc_mapper_for_test());
+        let schema = doc_mapper.schema();
+        let body_field = schema.get_field("body").unwrap();
+        let timestamp_field = schema.get_field("timestamp").unwrap();
+        let indexing_directory = TempDirectory::for_test();
+        let mut indexing_settings = IndexingSettings::for_test();
+        indexing_settings.split_num_docs_target = 2;
+        indexing_settings.commit_schedule = Some("yearly".to_string());
+        let universe = Universe::with_accelerated_time();
+        let (index_serializer_mailbox, index_serializer_inbox) = universe.create_test_mailbox();
+        let mut metastore = MetastoreServiceClient::mock();
+        metastore
+            .expect_last_delete_opstamp()
+            .returning(move |_last_delete_opstamp_request| Ok(LastDeleteOpstampResponse::new(10)));
+        metastore.expect_publish_splits().never();
+        let indexer = Indexer::new(
+            pipeline_id,
+            doc_mapper,
+            MetastoreServiceClient::from(metastore),
+            indexing_directory,
+            indexing_settings,
+            None,
+            ScratchSpaceQuota::unlimited(),
+            index_serializer_mailbox,
+        );
+        let (indexer_mailbox, indexer_handle) = universe.spawn_builder().spawn(indexer);
+        let docs = (0..2)
+            .map(|_| ProcessedDoc {
+                doc: doc!(
+                    body_field=>"this is a test document",
+                    timestamp_field=>DateTime::from_timestamp_secs(1_662_529_435)
+                ),
+                timestamp_opt: Some(DateTime::from_timestamp_secs(1_662_529_435)),
+                partition: 1,
+                num_bytes: 30,
+            })
+            .collect();
+        indexer_mailbox
+            .send_message(ProcessedDocBatch {
+                docs,
+                checkpoint_delta: SourceCheckpointDelta::from_range(0..2),
+                force_commit: false,
+            })
+            .await?;
+        let indexer_counters = indexer_handle.process_pending_and_observe().await.state;
+        assert_eq!(indexer_counters.num_splits_emitted, 1);
+
+        let messages: Vec<IndexedSplitBatchBuilder> = index_serializer_inbox.drain_for_test_typed();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].commit_trigger, CommitTrigger::NumDocsLimit);
+        assert_eq!(messages[0].splits[0].split_attrs.num_docs, 2);
+        universe.assert_quit().await;
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_indexer_triggers_commit_on_drained_mailbox() -> anyhow::Result<()> {
         let universe = Universe::new();
@@ -1059,6 +1201,7 @@ mod tests {
             indexing_directory,
             indexing_settings,
             Some(Arc::new(Semaphore::new(1))),
+            ScratchSpaceQuota::unlimited(),
             index_serializer_mailbox,
         );
         let (indexer_mailbox, indexer_handle) = universe.spawn_builder().spawn(indexer);
@@ -1134,6 +1277,7 @@ mod tests {
             indexing_directory,
             indexing_settings,
             None,
+            ScratchSpaceQuota::unlimited(),
             index_serializer_mailbox,
         );
         let (indexer_mailbox, indexer_handle) = universe.spawn_builder().spawn(indexer);
@@ -1217,6 +1361,7 @@ mod tests {
             indexing_directory,
             indexing_settings,
             None,
+            ScratchSpaceQuota::unlimited(),
             index_serializer_mailbox,
         );
         let (indexer_mailbox, indexer_handle) = universe.spawn_builder().spawn(indexer);
@@ -1312,6 +1457,7 @@ mod tests {
             indexing_directory,
             indexing_settings,
             None,
+            ScratchSpaceQuota::unlimited(),
             index_serializer_mailbox,
         );
         let (indexer_mailbox, indexer_handle) = universe.spawn_builder().spawn(indexer);
@@ -1383,6 +1529,7 @@ mod tests {
             indexing_directory,
             indexing_settings,
             None,
+            ScratchSpaceQuota::unlimited(),
             index_serializer_mailbox,
         );
         let (indexer_mailbox, indexer_handle) = universe.spawn_builder().spawn(indexer);
@@ -1455,6 +1602,7 @@ mod tests {
             indexing_directory,
             indexing_settings,
             None,
+            ScratchSpaceQuota::unlimited(),
             index_serializer_mailbox,
         );
         let (indexer_mailbox, indexer_handle) = universe.spawn_builder().spawn(indexer);
@@ -1519,6 +1667,7 @@ mod tests {
             indexing_directory,
             indexing_settings,
             None,
+            ScratchSpaceQuota::unlimited(),
             index_serializer_mailbox,
         );
         let (indexer_mailbox, indexer_handle) = universe.spawn_builder().spawn(indexer);
@@ -1587,6 +1736,7 @@ mod tests {
             indexing_directory,
             indexing_settings,
             None,
+            ScratchSpaceQuota::unlimited(),
             index_serializer_mailbox,
         );
         let (indexer_mailbox, indexer_handle) = universe.spawn_builder().spawn(indexer);
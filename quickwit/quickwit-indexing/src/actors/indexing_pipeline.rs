@@ -44,12 +44,14 @@ use tokio::sync::Semaphore;
 use tracing::{debug, error, info, instrument};
 
 use super::MergePlanner;
+use crate::actors::dead_letter_queue::DeadLetterQueue;
 use crate::actors::doc_processor::DocProcessor;
 use crate::actors::index_serializer::IndexSerializer;
 use crate::actors::publisher::PublisherType;
 use crate::actors::sequencer::Sequencer;
 use crate::actors::uploader::UploaderType;
 use crate::actors::{Indexer, Packager, Publisher, Uploader};
+use crate::controlled_directory::ScratchSpaceQuota;
 use crate::merge_policy::MergePolicy;
 use crate::models::IndexingStatistics;
 use crate::source::{
@@ -116,6 +118,15 @@ pub struct Spawn {
     retry_count: usize,
 }
 
+/// Pauses the source of the pipeline. The other actors keep running and process the batches
+/// already emitted.
+#[derive(Clone, Copy, Debug)]
+pub struct PausePipeline;
+
+/// Resumes the source of a pipeline paused with [`PausePipeline`].
+#[derive(Clone, Copy, Debug)]
+pub struct ResumePipeline;
+
 pub struct IndexingPipeline {
     params: IndexingPipelineParams,
     previous_generations_statistics: IndexingStatistics,
@@ -127,6 +138,8 @@ pub struct IndexingPipeline {
     // requiring a respawn of the pipeline.
     // We keep the list of shards here however, to reassign them after a respawn.
     shard_ids: BTreeSet<ShardId>,
+    // Whether the source of the pipeline is paused. Like the shards, this survives respawns.
+    paused: bool,
 }
 
 #[async_trait]
@@ -168,6 +181,7 @@ impl IndexingPipeline {
             kill_switch: KillSwitch::default(),
             statistics: IndexingStatistics::default(),
             shard_ids: Default::default(),
+            paused: false,
         }
     }
 
@@ -268,6 +282,7 @@ impl IndexingPipeline {
         let pipeline_metrics_opt = handles.indexer.last_observation().pipeline_metrics_opt;
         self.statistics.pipeline_metrics_opt = pipeline_metrics_opt;
         self.statistics.shard_ids = self.shard_ids.clone();
+        self.statistics.paused = self.paused;
         ctx.observe(self);
     }
 
@@ -377,7 +392,8 @@ impl IndexingPipeline {
 
         // Packager
         let tag_fields = self.params.doc_mapper.tag_named_fields()?;
-        let packager = Packager::new("Packager", tag_fields, uploader_mailbox);
+        let min_max_fields = self.params.doc_mapper.min_max_named_fields()?;
+        let packager = Packager::new("Packager", tag_fields, min_max_fields, uploader_mailbox);
         let (packager_mailbox, packager_handle) = ctx
             .spawn_actor()
             .set_kill_switch(self.kill_switch.clone())
@@ -398,6 +414,7 @@ impl IndexingPipeline {
             self.params.indexing_directory.clone(),
             self.params.indexing_settings.clone(),
             self.params.cooperative_indexing_permits.clone(),
+            self.params.scratch_space_quota.clone(),
             index_serializer_mailbox,
         );
         let (indexer_mailbox, indexer_handle) = ctx
@@ -410,13 +427,33 @@ impl IndexingPipeline {
             .set_kill_switch(self.kill_switch.clone())
             .spawn(indexer);
 
+        let dead_letter_queue_opt = match &self.params.indexing_settings.dead_letter_queue {
+            Some(dead_letter_queue_config) => {
+                let dead_letter_queue_storage = ctx
+                    .protect_future(
+                        self.params
+                            .source_storage_resolver
+                            .resolve(&dead_letter_queue_config.uri),
+                    )
+                    .await?;
+                Some(DeadLetterQueue::new(
+                    dead_letter_queue_storage,
+                    index_id.to_string(),
+                    source_id.to_string(),
+                    dead_letter_queue_config,
+                ))
+            }
+            None => None,
+        };
         let doc_processor = DocProcessor::try_new(
             index_id.to_string(),
             source_id.to_string(),
             self.params.doc_mapper.clone(),
             indexer_mailbox,
             self.params.source_config.transform_config.clone(),
+            &self.params.indexing_settings.derived_fields,
             self.params.source_config.input_format,
+            dead_letter_queue_opt,
         )?;
         let (doc_processor_mailbox, doc_processor_handle) = ctx
             .spawn_actor()
@@ -462,6 +499,9 @@ impl IndexingPipeline {
             .set_mailboxes(source_mailbox, source_inbox)
             .set_kill_switch(self.kill_switch.clone())
             .spawn(actor_source);
+        if self.paused {
+            source_handle.pause();
+        }
         let assign_shards_message = AssignShards(Assignment {
             shard_ids: self.shard_ids.clone(),
         });
@@ -575,6 +615,45 @@ impl Handler<AssignShards> for IndexingPipeline {
     }
 }
 
+#[async_trait]
+impl Handler<PausePipeline> for IndexingPipeline {
+    type Reply = ();
+
+    async fn handle(
+        &mut self,
+        _message: PausePipeline,
+        ctx: &ActorContext<Self>,
+    ) -> Result<(), ActorExitStatus> {
+        self.paused = true;
+        // If the pipeline is not running, the source will be paused right after it is respawned.
+        if let Some(handles) = &self.handles_opt {
+            info!(pipeline_id=?self.params.pipeline_id, "pausing indexing pipeline");
+            handles.source_handle.pause();
+        }
+        self.perform_observe(ctx);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Handler<ResumePipeline> for IndexingPipeline {
+    type Reply = ();
+
+    async fn handle(
+        &mut self,
+        _message: ResumePipeline,
+        ctx: &ActorContext<Self>,
+    ) -> Result<(), ActorExitStatus> {
+        self.paused = false;
+        if let Some(handles) = &self.handles_opt {
+            info!(pipeline_id=?self.params.pipeline_id, "resuming indexing pipeline");
+            handles.source_handle.resume();
+        }
+        self.perform_observe(ctx);
+        Ok(())
+    }
+}
+
 pub struct IndexingPipelineParams {
     pub pipeline_id: IndexingPipelineId,
     pub metastore: MetastoreServiceClient,
@@ -583,6 +662,7 @@ pub struct IndexingPipelineParams {
     // Indexing-related parameters
     pub doc_mapper: Arc<dyn DocMapper>,
     pub indexing_directory: TempDirectory,
+    pub scratch_space_quota: ScratchSpaceQuota,
     pub indexing_settings: IndexingSettings,
     pub split_store: IndexingSplitStore,
     pub max_concurrent_split_uploads_index: usize,
@@ -710,6 +790,7 @@ mod tests {
             source_config,
             source_storage_resolver: StorageResolver::for_test(),
             indexing_directory: TempDirectory::for_test(),
+            scratch_space_quota: ScratchSpaceQuota::unlimited(),
             indexing_settings: IndexingSettings::for_test(),
             ingester_pool: IngesterPool::default(),
             metastore: MetastoreServiceClient::from(metastore),
@@ -809,6 +890,7 @@ mod tests {
             source_config,
             source_storage_resolver: StorageResolver::for_test(),
             indexing_directory: TempDirectory::for_test(),
+            scratch_space_quota: ScratchSpaceQuota::unlimited(),
             indexing_settings: IndexingSettings::for_test(),
             ingester_pool: IngesterPool::default(),
             metastore: MetastoreServiceClient::from(metastore),
@@ -874,6 +956,7 @@ mod tests {
             pipeline_id: pipeline_id.clone(),
             doc_mapper: doc_mapper.clone(),
             indexing_directory: TempDirectory::for_test(),
+            scratch_space_quota: ScratchSpaceQuota::unlimited(),
             metastore: metastore.clone(),
             split_store: split_store.clone(),
             merge_policy: default_merge_policy(),
@@ -891,6 +974,7 @@ mod tests {
             source_config,
             source_storage_resolver: StorageResolver::for_test(),
             indexing_directory: TempDirectory::for_test(),
+            scratch_space_quota: ScratchSpaceQuota::unlimited(),
             indexing_settings: IndexingSettings::for_test(),
             ingester_pool: IngesterPool::default(),
             metastore,
@@ -1012,6 +1096,7 @@ mod tests {
             source_config,
             source_storage_resolver: StorageResolver::for_test(),
             indexing_directory: TempDirectory::for_test(),
+            scratch_space_quota: ScratchSpaceQuota::unlimited(),
             indexing_settings: IndexingSettings::for_test(),
             ingester_pool: IngesterPool::default(),
             metastore: MetastoreServiceClient::from(metastore),
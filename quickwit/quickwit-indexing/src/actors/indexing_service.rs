@@ -19,8 +19,11 @@
 
 use std::collections::{HashMap, HashSet};
 use std::fmt::{Debug, Display, Formatter};
-use std::path::PathBuf;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use std::{fs, io};
 
 use anyhow::Context;
 use async_trait::async_trait;
@@ -43,8 +46,8 @@ use quickwit_ingest::{
 };
 use quickwit_metastore::{IndexMetadata, IndexMetadataResponseExt, ListIndexesMetadataResponseExt};
 use quickwit_proto::indexing::{
-    ApplyIndexingPlanRequest, ApplyIndexingPlanResponse, IndexingError, IndexingPipelineId,
-    IndexingTask, PipelineMetrics,
+    ApplyIndexingPlanRequest, ApplyIndexingPlanResponse, IndexingError, IndexingPipelineFailure,
+    IndexingPipelineId, IndexingTask, PipelineMetrics,
 };
 use quickwit_proto::metastore::{
     IndexMetadataRequest, ListIndexesMetadataRequest, MetastoreService, MetastoreServiceClient,
@@ -56,7 +59,9 @@ use tokio::sync::Semaphore;
 use tracing::{debug, error, info, warn};
 
 use super::merge_pipeline::{MergePipeline, MergePipelineParams};
-use super::MergePlanner;
+use super::{MergePlanner, PausePipeline, ResumePipeline};
+use crate::controlled_directory::ScratchSpaceQuota;
+use crate::metrics::INDEXER_METRICS;
 use crate::models::{DetachIndexingPipeline, DetachMergePipeline, ObservePipeline, SpawnPipeline};
 use crate::source::{AssignShards, Assignment};
 use crate::split_store::{LocalSplitStore, SplitStoreQuota};
@@ -65,6 +70,101 @@ use crate::{IndexingPipeline, IndexingPipelineParams, IndexingSplitStore, Indexi
 /// Name of the indexing directory, usually located at `<data_dir_path>/indexing`.
 pub const INDEXING_DIR_NAME: &str = "indexing";
 
+/// Removes the entries of the indexing directory that are not used by any pipeline and have not
+/// been modified for at least `grace_period`. Those are the scratch directories leaked by pipelines
+/// that did not clean up after themselves. The indexing directory is purged on startup, so this
+/// only matters for long-running indexers.
+async fn remove_stale_scratch_directories(
+    indexing_root_directory: PathBuf,
+    in_use_directories: HashSet<PathBuf>,
+    grace_period: Duration,
+) -> io::Result<()> {
+    let (num_removed_entries, num_reclaimed_bytes) = tokio::task::spawn_blocking(move || {
+        remove_stale_entries_blocking(&indexing_root_directory, &in_use_directories, grace_period)
+    })
+    .await
+    .expect("the task should not panic")?;
+
+    if num_removed_entries > 0 {
+        info!(
+            num_removed_entries,
+            num_reclaimed_bytes, "removed stale indexing scratch directories"
+        );
+    }
+    Ok(())
+}
+
+fn remove_stale_entries_blocking(
+    root_directory: &Path,
+    in_use_directories: &HashSet<PathBuf>,
+    grace_period: Duration,
+) -> io::Result<(usize, u64)> {
+    let entries = match fs::read_dir(root_directory) {
+        Ok(entries) => entries,
+        Err(io_error) if io_error.kind() == io::ErrorKind::NotFound => return Ok((0, 0)),
+        Err(io_error) => return Err(io_error),
+    };
+    let now = SystemTime::now();
+    let mut num_removed_entries = 0;
+    let mut num_reclaimed_bytes = 0;
+
+    for entry_res in entries {
+        let entry_path = entry_res?.path();
+
+        if in_use_directories.contains(&entry_path) {
+            continue;
+        }
+        // Another process may remove the entry concurrently, in which case we just skip it.
+        let (last_modified, num_bytes) = match entry_stats(&entry_path) {
+            Ok(entry_stats) => entry_stats,
+            Err(io_error) if io_error.kind() == io::ErrorKind::NotFound => continue,
+            Err(io_error) => return Err(io_error),
+        };
+        let age = now.duration_since(last_modified).unwrap_or_default();
+
+        if age < grace_period {
+            continue;
+        }
+        let remove_res = if entry_path.is_dir() {
+            fs::remove_dir_all(&entry_path)
+        } else {
+            fs::remove_file(&entry_path)
+        };
+        match remove_res {
+            Ok(()) => {}
+            Err(io_error) if io_error.kind() == io::ErrorKind::NotFound => continue,
+            Err(io_error) => return Err(io_error),
+        }
+        info!(
+            path=%entry_path.display(),
+            age_secs = age.as_secs(),
+            num_bytes,
+            "removed stale indexing scratch directory"
+        );
+        num_removed_entries += 1;
+        num_reclaimed_bytes += num_bytes;
+    }
+    Ok((num_removed_entries, num_reclaimed_bytes))
+}
+
+/// Returns the most recent modification time and the total size of a file or directory tree.
+fn entry_stats(path: &Path) -> io::Result<(SystemTime, u64)> {
+    let metadata = fs::symlink_metadata(path)?;
+    let mut last_modified = metadata.modified()?;
+    let mut num_bytes = metadata.len();
+
+    if metadata.is_dir() {
+        num_bytes = 0;
+
+        for entry_res in fs::read_dir(path)? {
+            let (entry_last_modified, entry_num_bytes) = entry_stats(&entry_res?.path())?;
+            last_modified = last_modified.max(entry_last_modified);
+            num_bytes += entry_num_bytes;
+        }
+    }
+    Ok((last_modified, num_bytes))
+}
+
 #[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
 pub struct IndexingServiceCounters {
     pub num_running_pipelines: usize,
@@ -99,12 +199,16 @@ impl<'a> From<&'a IndexingPipelineId> for MergePipelineId {
 struct MergePipelineHandle {
     mailbox: Mailbox<MergePlanner>,
     handle: ActorHandle<MergePipeline>,
+    indexing_directory_path: PathBuf,
 }
 
 struct PipelineHandle {
     mailbox: Mailbox<IndexingPipeline>,
     handle: ActorHandle<IndexingPipeline>,
     indexing_pipeline_id: IndexingPipelineId,
+    indexing_directory_path: PathBuf,
+    // Whether the pipeline was last successfully paused rather than resumed.
+    paused: bool,
 }
 
 /// The indexing service is (single) actor service running on indexer and in charge
@@ -126,8 +230,11 @@ pub struct IndexingService {
     counters: IndexingServiceCounters,
     local_split_store: Arc<LocalSplitStore>,
     max_concurrent_split_uploads: usize,
+    max_concurrent_pipelines_opt: Option<NonZeroUsize>,
     merge_pipeline_handles: HashMap<MergePipelineId, MergePipelineHandle>,
     cooperative_indexing_permits: Option<Arc<Semaphore>>,
+    scratch_space_quota: ScratchSpaceQuota,
+    scratch_cleanup_grace_period: Duration,
     event_broker: EventBroker,
 }
 
@@ -171,6 +278,10 @@ impl IndexingService {
         } else {
             None
         };
+        let scratch_space_quota = indexer_config
+            .scratch_max_bytes
+            .map(ScratchSpaceQuota::new)
+            .unwrap_or_default();
         Ok(IndexingService {
             node_id,
             indexing_root_directory,
@@ -184,8 +295,11 @@ impl IndexingService {
             indexing_pipelines: Default::default(),
             counters: Default::default(),
             max_concurrent_split_uploads: indexer_config.max_concurrent_split_uploads,
+            max_concurrent_pipelines_opt: indexer_config.max_concurrent_pipelines,
             merge_pipeline_handles: HashMap::new(),
             cooperative_indexing_permits,
+            scratch_space_quota,
+            scratch_cleanup_grace_period: indexer_config.scratch_cleanup_grace_period,
             event_broker,
         })
     }
@@ -199,6 +313,7 @@ impl IndexingService {
             .remove(&pipeline_uid)
             .ok_or(IndexingError::MissingPipeline { pipeline_uid })?;
         self.counters.num_running_pipelines -= 1;
+        self.update_running_pipelines_gauge();
         Ok(pipeline_handle.handle)
     }
 
@@ -266,6 +381,13 @@ impl IndexingService {
                 pipeline_uid: pipeline_id.pipeline_uid,
             });
         }
+        if let Some(max_concurrent_pipelines) = self.max_concurrent_pipelines_opt {
+            if self.indexing_pipelines.len() >= max_concurrent_pipelines.get() {
+                return Err(IndexingError::TooManyPipelines {
+                    max_concurrent_pipelines: max_concurrent_pipelines.get(),
+                });
+            }
+        }
         let pipeline_uid_str = pipeline_id.pipeline_uid.to_string();
         let indexing_directory = temp_dir::Builder::default()
             .join(pipeline_id.index_uid.index_id())
@@ -274,6 +396,7 @@ impl IndexingService {
             .join(&pipeline_uid_str)
             .tempdir_in(&self.indexing_root_directory)
             .map_err(IndexingError::Io)?;
+        self.storage_resolver.register_index_config(&index_config);
         let storage = self
             .storage_resolver
             .resolve(&index_config.index_uri)
@@ -281,15 +404,21 @@ impl IndexingService {
             .map_err(|err| IndexingError::StorageResolverError(err.to_string()))?;
         let merge_policy =
             crate::merge_policy::merge_policy_from_settings(&index_config.indexing_settings);
-        let split_store = IndexingSplitStore::new(storage.clone(), self.local_split_store.clone());
+        let split_store = IndexingSplitStore::new(
+            storage.clone(),
+            self.local_split_store.clone(),
+            index_config.indexing_settings.split_path_template.clone(),
+        );
 
         let doc_mapper = build_doc_mapper(&index_config.doc_mapping, &index_config.search_settings)
             .map_err(IndexingError::InvalidParams)?;
 
+        let indexing_directory_path = indexing_directory.path().to_path_buf();
         let merge_pipeline_params = MergePipelineParams {
             pipeline_id: pipeline_id.clone(),
             doc_mapper: doc_mapper.clone(),
             indexing_directory: indexing_directory.clone(),
+            scratch_space_quota: self.scratch_space_quota.clone(),
             metastore: self.metastore.clone(),
             split_store: split_store.clone(),
             merge_policy: merge_policy.clone(),
@@ -318,6 +447,7 @@ impl IndexingService {
             // Indexing-related parameters
             doc_mapper,
             indexing_directory,
+            scratch_space_quota: self.scratch_space_quota.clone(),
             indexing_settings: index_config.indexing_settings.clone(),
             split_store,
             max_concurrent_split_uploads_index,
@@ -341,13 +471,22 @@ impl IndexingService {
             mailbox: pipeline_mailbox,
             handle: pipeline_handle,
             indexing_pipeline_id: pipeline_id.clone(),
+            indexing_directory_path,
+            paused: false,
         };
         self.indexing_pipelines
             .insert(pipeline_id.pipeline_uid, pipeline_handle);
         self.counters.num_running_pipelines += 1;
+        self.update_running_pipelines_gauge();
         Ok(())
     }
 
+    fn update_running_pipelines_gauge(&self) {
+        INDEXER_METRICS
+            .running_pipelines
+            .set(self.counters.num_running_pipelines as i64);
+    }
+
     async fn index_metadata(
         &self,
         ctx: &ActorContext<Self>,
@@ -393,6 +532,7 @@ impl IndexingService {
                     }
                 }
             });
+        self.update_running_pipelines_gauge();
         // Evict and kill merge pipelines that are not needed.
         let needed_merge_pipeline_ids: HashSet<MergePipelineId> = self
             .indexing_pipelines
@@ -453,12 +593,17 @@ impl IndexingService {
         {
             return Ok(merge_pipeline_mailbox_handle.mailbox.clone());
         }
+        let indexing_directory_path = merge_pipeline_params
+            .indexing_directory
+            .path()
+            .to_path_buf();
         let merge_pipeline = MergePipeline::new(merge_pipeline_params, ctx.spawn_ctx());
         let merge_planner_mailbox = merge_pipeline.merge_planner_mailbox().clone();
         let (_pipeline_mailbox, pipeline_handle) = ctx.spawn_actor().spawn(merge_pipeline);
         let merge_pipeline_mailbox_handle = MergePipelineHandle {
             mailbox: merge_planner_mailbox.clone(),
             handle: pipeline_handle,
+            indexing_directory_path,
         };
         self.merge_pipeline_handles
             .insert(merge_pipeline_id, merge_pipeline_mailbox_handle);
@@ -533,20 +678,64 @@ impl IndexingService {
         }
     }
 
+    /// Pauses or resumes the pipelines whose paused state differs from the one of their task.
+    ///
+    /// Returns the pipelines that could not be paused or resumed.
+    async fn pause_or_resume_pipelines(
+        &mut self,
+        tasks: &[IndexingTask],
+    ) -> Vec<IndexingPipelineFailure> {
+        let mut failures = Vec::new();
+
+        for task in tasks {
+            let pipeline_uid = task.pipeline_uid();
+            let Some(pipeline_handle) = self.indexing_pipelines.get_mut(&pipeline_uid) else {
+                continue;
+            };
+            if pipeline_handle.paused == task.paused {
+                continue;
+            }
+            let pause_res = if task.paused {
+                pipeline_handle.mailbox.ask(PausePipeline).await
+            } else {
+                pipeline_handle.mailbox.ask(ResumePipeline).await
+            };
+            match pause_res {
+                Ok(()) => {
+                    pipeline_handle.paused = task.paused;
+                }
+                Err(error) => {
+                    error!(pipeline_uid=%pipeline_uid, paused=task.paused, error=%error, "failed to pause or resume indexing pipeline");
+                    let failure = IndexingPipelineFailure {
+                        node_id: self.node_id.clone(),
+                        pipeline_uid: Some(pipeline_uid),
+                        error: error.to_string(),
+                    };
+                    failures.push(failure);
+                }
+            }
+        }
+        failures
+    }
+
     /// Applies the indexing plan by:
     /// - Stopping the running pipelines not present in the provided plan.
     /// - Starting the pipelines that are not running.
+    /// - Pausing or resuming the pipelines according to their task.
     /// Note: the indexing is a list of `IndexingTask` and has no ordinal
     /// like a pipeline. We assign an ordinal for each `IndexingTask` from
     /// [0, n) with n the number of indexing tasks given a (index_id, source_id).
+    ///
+    /// Returns the pipelines that could not be paused or resumed.
     async fn apply_indexing_plan(
         &mut self,
         tasks: &[IndexingTask],
         ctx: &ActorContext<Self>,
-    ) -> Result<(), IndexingError> {
+    ) -> Result<Vec<IndexingPipelineFailure>, IndexingError> {
         self.find_and_shutdown_decommissioned_pipelines(tasks).await;
         let failed_spawning_pipeline_ids = self.find_and_spawn_new_pipelines(tasks, ctx).await?;
         self.assign_shards_to_pipelines(tasks).await;
+        let failures = self.pause_or_resume_pipelines(tasks).await;
         self.update_cluster_running_indexing_tasks_in_chitchat()
             .await;
         if !failed_spawning_pipeline_ids.is_empty() {
@@ -554,7 +743,7 @@ impl IndexingService {
                 pipeline_ids: failed_spawning_pipeline_ids,
             });
         }
-        Ok(())
+        Ok(failures)
     }
 
     /// Spawns the pipelines with supplied ids and returns a list of failed pipelines.
@@ -667,6 +856,7 @@ impl IndexingService {
                     .iter()
                     .cloned()
                     .collect(),
+                paused: handle.paused,
             })
             .collect();
         indexing_tasks.sort_unstable_by_key(|task| task.pipeline_uid);
@@ -787,6 +977,47 @@ impl Handler<SuperviseLoop> for IndexingService {
     }
 }
 
+#[derive(Debug)]
+struct RemoveStaleScratchDirectories;
+
+#[async_trait]
+impl Handler<RemoveStaleScratchDirectories> for IndexingService {
+    type Reply = ();
+
+    async fn handle(
+        &mut self,
+        _message: RemoveStaleScratchDirectories,
+        ctx: &ActorContext<Self>,
+    ) -> Result<(), ActorExitStatus> {
+        let in_use_directories: HashSet<PathBuf> = self
+            .indexing_pipelines
+            .values()
+            .map(|pipeline_handle| pipeline_handle.indexing_directory_path.clone())
+            .chain(
+                self.merge_pipeline_handles
+                    .values()
+                    .map(|merge_pipeline_handle| {
+                        merge_pipeline_handle.indexing_directory_path.clone()
+                    }),
+            )
+            .collect();
+        if let Err(error) = remove_stale_scratch_directories(
+            self.indexing_root_directory.clone(),
+            in_use_directories,
+            self.scratch_cleanup_grace_period,
+        )
+        .await
+        {
+            warn!(%error, "failed to remove stale indexing scratch directories");
+        }
+        ctx.schedule_self_msg(
+            self.scratch_cleanup_grace_period,
+            RemoveStaleScratchDirectories,
+        );
+        Ok(())
+    }
+}
+
 #[async_trait]
 impl Actor for IndexingService {
     type ObservableState = IndexingServiceCounters;
@@ -797,6 +1028,12 @@ impl Actor for IndexingService {
 
     async fn initialize(&mut self, ctx: &ActorContext<Self>) -> Result<(), ActorExitStatus> {
         self.run_ingest_api_queues_gc().await?;
+        // The indexing directory was purged on startup, so there is nothing to clean up before the
+        // grace period elapses.
+        ctx.schedule_self_msg(
+            self.scratch_cleanup_grace_period,
+            RemoveStaleScratchDirectories,
+        );
         self.handle(SuperviseLoop, ctx).await
     }
 }
@@ -832,7 +1069,7 @@ impl Handler<ApplyIndexingPlanRequest> for IndexingService {
         Ok(self
             .apply_indexing_plan(&plan_request.indexing_tasks, ctx)
             .await
-            .map(|_| ApplyIndexingPlanResponse {}))
+            .map(|failures| ApplyIndexingPlanResponse { failures }))
     }
 }
 
@@ -864,7 +1101,9 @@ mod tests {
         IngestApiConfig, KafkaSourceParams, SourceConfig, SourceInputFormat, SourceParams,
         VecSourceParams,
     };
-    use quickwit_ingest::{init_ingest_api, CreateQueueIfNotExistsRequest};
+    use quickwit_ingest::{
+        init_ingest_api, CommitType, CreateQueueIfNotExistsRequest, DocBatchBuilder, IngestRequest,
+    };
     use quickwit_metastore::{
         metastore_for_test, AddSourceRequestExt, CreateIndexRequestExt,
         ListIndexesMetadataResponseExt,
@@ -1009,6 +1248,248 @@ mod tests {
         universe.assert_quit().await;
     }
 
+    #[tokio::test]
+    async fn test_indexing_service_max_concurrent_pipelines() {
+        quickwit_common::setup_logging_for_tests();
+        let transport = ChannelTransport::default();
+        let cluster = create_cluster_for_test(Vec::new(), &["indexer"], &transport, true)
+            .await
+            .unwrap();
+        let mut metastore = metastore_for_test();
+
+        let index_id = append_random_suffix("test-indexing-service");
+        let index_uri = format!("ram:///indexes/{index_id}");
+        let index_config = IndexConfig::for_test(&index_id, &index_uri);
+        let create_index_request = CreateIndexRequest::try_from_index_config(index_config).unwrap();
+        metastore.create_index(create_index_request).await.unwrap();
+
+        let universe = Universe::with_accelerated_time();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut indexer_config = IndexerConfig::for_test().unwrap();
+        indexer_config.max_concurrent_pipelines = NonZeroUsize::new(2);
+        let indexing_service = IndexingService::new(
+            "test-node".to_string(),
+            temp_dir.path().to_path_buf(),
+            indexer_config,
+            1,
+            cluster,
+            metastore,
+            None,
+            IngesterPool::default(),
+            StorageResolver::unconfigured(),
+            EventBroker::default(),
+        )
+        .await
+        .unwrap();
+        let (indexing_service, indexing_service_handle) =
+            universe.spawn_builder().spawn(indexing_service);
+
+        let source_config = SourceConfig {
+            source_id: "test-indexing-service--source".to_string(),
+            max_num_pipelines_per_indexer: NonZeroUsize::new(3).unwrap(),
+            desired_num_pipelines: NonZeroUsize::new(3).unwrap(),
+            enabled: true,
+            source_params: SourceParams::void(),
+            transform_config: None,
+            input_format: SourceInputFormat::Json,
+        };
+        let mut pipeline_ids = Vec::new();
+
+        for pipeline_ord in 0..2 {
+            let spawn_pipeline_msg = SpawnPipeline {
+                index_id: index_id.clone(),
+                pipeline_uid: PipelineUid::from_u128(pipeline_ord),
+                source_config: source_config.clone(),
+            };
+            let pipeline_id = indexing_service
+                .ask_for_res(spawn_pipeline_msg)
+                .await
+                .unwrap();
+            pipeline_ids.push(pipeline_id);
+        }
+        let spawn_pipeline_msg = SpawnPipeline {
+            index_id: index_id.clone(),
+            pipeline_uid: PipelineUid::from_u128(2),
+            source_config: source_config.clone(),
+        };
+        let error = indexing_service
+            .ask_for_res(spawn_pipeline_msg.clone())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            IndexingError::TooManyPipelines {
+                max_concurrent_pipelines: 2
+            }
+        ));
+        assert_eq!(
+            indexing_service_handle
+                .observe()
+                .await
+                .num_running_pipelines,
+            2
+        );
+
+        // Detaching a pipeline frees a slot.
+        let pipeline_handle = indexing_service
+            .ask_for_res(DetachIndexingPipeline {
+                pipeline_id: pipeline_ids[0].clone(),
+            })
+            .await
+            .unwrap();
+        pipeline_handle.kill().await;
+        indexing_service
+            .ask_for_res(spawn_pipeline_msg)
+            .await
+            .unwrap();
+        assert_eq!(
+            indexing_service_handle
+                .observe()
+                .await
+                .num_running_pipelines,
+            2
+        );
+        universe.assert_quit().await;
+    }
+
+    #[tokio::test]
+    async fn test_indexing_service_pause_resume_pipelines() {
+        quickwit_common::setup_logging_for_tests();
+        let transport = ChannelTransport::default();
+        let cluster = create_cluster_for_test(Vec::new(), &["indexer"], &transport, true)
+            .await
+            .unwrap();
+        let mut metastore = metastore_for_test();
+
+        let index_id = append_random_suffix("test-indexing-service-pause-resume");
+        let index_uri = format!("ram:///indexes/{index_id}");
+        let index_config = IndexConfig::for_test(&index_id, &index_uri);
+
+        let create_index_request = CreateIndexRequest::try_from_index_config(index_config).unwrap();
+        let index_uid: IndexUid = metastore
+            .create_index(create_index_request)
+            .await
+            .unwrap()
+            .index_uid
+            .into();
+        let source_config = SourceConfig::ingest_api_default();
+        let add_source_request =
+            AddSourceRequest::try_from_source_config(index_uid.clone(), source_config.clone())
+                .unwrap();
+        metastore.add_source(add_source_request).await.unwrap();
+
+        let universe = Universe::with_accelerated_time();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let (indexing_service, _indexing_service_handle) =
+            spawn_indexing_service_for_test(temp_dir.path(), &universe, metastore, cluster).await;
+        let queues_dir_path = temp_dir.path().join(QUEUES_DIR_NAME);
+        let ingest_api_service =
+            init_ingest_api(&universe, &queues_dir_path, &IngestApiConfig::default())
+                .await
+                .unwrap();
+        let create_queue_request = CreateQueueIfNotExistsRequest {
+            queue_id: index_id.clone(),
+        };
+        ingest_api_service
+            .ask_for_res(create_queue_request)
+            .await
+            .unwrap();
+
+        let pipeline_uid = PipelineUid::from_u128(1111u128);
+        let mut indexing_task = IndexingTask {
+            index_uid: index_uid.to_string(),
+            source_id: source_config.source_id.clone(),
+            pipeline_uid: Some(pipeline_uid),
+            shard_ids: Vec::new(),
+            paused: true,
+        };
+        let apply_plan_response = indexing_service
+            .ask_for_res(ApplyIndexingPlanRequest {
+                indexing_tasks: vec![indexing_task.clone()],
+            })
+            .await
+            .unwrap();
+        assert!(apply_plan_response.failures.is_empty());
+
+        let pipeline_id = IndexingPipelineId {
+            node_id: "test-node".to_string(),
+            index_uid: index_uid.clone(),
+            source_id: source_config.source_id.clone(),
+            pipeline_uid,
+        };
+
+        let mut doc_batch_builder = DocBatchBuilder::new(index_id.clone());
+        for doc_id in 0..3 {
+            doc_batch_builder.ingest_doc(format!(r#"{{"body": "doc-{doc_id}"}}"#).as_bytes());
+        }
+        let ingest_request = IngestRequest {
+            doc_batches: vec![doc_batch_builder.build()],
+            commit: CommitType::Auto.into(),
+        };
+        ingest_api_service
+            .ask_for_res(ingest_request)
+            .await
+            .unwrap();
+
+        universe.sleep(Duration::from_secs(5)).await;
+        let observation = indexing_service
+            .ask_for_res(ObservePipeline {
+                pipeline_id: pipeline_id.clone(),
+            })
+            .await
+            .unwrap();
+        assert!(observation.paused);
+        assert_eq!(observation.num_docs, 0);
+        assert_eq!(observation.generation, 1);
+
+        // Re-applying the plan keeps the pipeline paused.
+        let apply_plan_response = indexing_service
+            .ask_for_res(ApplyIndexingPlanRequest {
+                indexing_tasks: vec![indexing_task.clone()],
+            })
+            .await
+            .unwrap();
+        assert!(apply_plan_response.failures.is_empty());
+
+        let observation = indexing_service
+            .ask_for_res(ObservePipeline {
+                pipeline_id: pipeline_id.clone(),
+            })
+            .await
+            .unwrap();
+        assert!(observation.paused);
+
+        indexing_task.paused = false;
+        let apply_plan_response = indexing_service
+            .ask_for_res(ApplyIndexingPlanRequest {
+                indexing_tasks: vec![indexing_task],
+            })
+            .await
+            .unwrap();
+        assert!(apply_plan_response.failures.is_empty());
+
+        for _ in 0..10 {
+            universe.sleep(Duration::from_secs(1)).await;
+            let observation = indexing_service
+                .ask_for_res(ObservePipeline {
+                    pipeline_id: pipeline_id.clone(),
+                })
+                .await
+                .unwrap();
+            assert!(!observation.paused);
+
+            if observation.num_docs == 3 {
+                break;
+            }
+        }
+        let observation = indexing_service
+            .ask_for_res(ObservePipeline { pipeline_id })
+            .await
+            .unwrap();
+        assert_eq!(observation.num_docs, 3);
+        universe.assert_quit().await;
+    }
+
     #[tokio::test]
     async fn test_indexing_service_supervise_pipelines() {
         quickwit_common::setup_logging_for_tests();
@@ -1126,12 +1607,14 @@ mod tests {
                 source_id: "test-indexing-service--source-1".to_string(),
                 shard_ids: Vec::new(),
                 pipeline_uid: Some(PipelineUid::from_u128(0u128)),
+                paused: false,
             },
             IndexingTask {
                 index_uid: metadata.index_uid.to_string(),
                 source_id: "test-indexing-service--source-1".to_string(),
                 shard_ids: Vec::new(),
                 pipeline_uid: Some(PipelineUid::from_u128(1u128)),
+                paused: false,
             },
         ];
         indexing_service
@@ -1151,6 +1634,7 @@ mod tests {
             client_log_level: None,
             client_params: serde_json::Value::Null,
             enable_backfill_mode: false,
+            start_from: None,
         };
         let source_config_2 = SourceConfig {
             source_id: "test-indexing-service--source-2".to_string(),
@@ -1172,24 +1656,28 @@ mod tests {
                 source_id: INGEST_API_SOURCE_ID.to_string(),
                 shard_ids: Vec::new(),
                 pipeline_uid: Some(PipelineUid::from_u128(3u128)),
+                paused: false,
             },
             IndexingTask {
                 index_uid: metadata.index_uid.to_string(),
                 source_id: "test-indexing-service--source-1".to_string(),
                 shard_ids: Vec::new(),
                 pipeline_uid: Some(PipelineUid::from_u128(1u128)),
+                paused: false,
             },
             IndexingTask {
                 index_uid: metadata.index_uid.to_string(),
                 source_id: "test-indexing-service--source-1".to_string(),
                 shard_ids: Vec::new(),
                 pipeline_uid: Some(PipelineUid::from_u128(2u128)),
+                paused: false,
             },
             IndexingTask {
                 index_uid: metadata.index_uid.to_string(),
                 source_id: source_config_2.source_id.clone(),
                 shard_ids: Vec::new(),
                 pipeline_uid: Some(PipelineUid::from_u128(4u128)),
+                paused: false,
             },
         ];
         indexing_service
@@ -1230,18 +1718,21 @@ mod tests {
                 source_id: INGEST_API_SOURCE_ID.to_string(),
                 shard_ids: Vec::new(),
                 pipeline_uid: Some(PipelineUid::from_u128(3u128)),
+                paused: false,
             },
             IndexingTask {
                 index_uid: metadata.index_uid.to_string(),
                 source_id: "test-indexing-service--source-1".to_string(),
                 shard_ids: Vec::new(),
                 pipeline_uid: Some(PipelineUid::from_u128(1u128)),
+                paused: false,
             },
             IndexingTask {
                 index_uid: metadata.index_uid.to_string(),
                 source_id: source_config_2.source_id.clone(),
                 shard_ids: Vec::new(),
                 pipeline_uid: Some(PipelineUid::from_u128(4u128)),
+                paused: false,
             },
         ];
         indexing_service
@@ -1507,6 +1998,66 @@ mod tests {
         universe.quit().await;
     }
 
+    #[tokio::test]
+    async fn test_remove_stale_scratch_directories() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let indexing_root_directory = temp_dir.path().join(INDEXING_DIR_NAME);
+
+        let stale_directory = indexing_root_directory.join("stale-pipeline");
+        let stale_split_directory = stale_directory.join("split-1");
+        let stale_split_file = stale_split_directory.join("hotcache");
+        fs::create_dir_all(&stale_split_directory).unwrap();
+        fs::write(&stale_split_file, b"stale").unwrap();
+
+        let stale_file = indexing_root_directory.join("stale-file");
+        fs::write(&stale_file, b"stale").unwrap();
+
+        // The scratch directory of a running pipeline is never removed.
+        let in_use_directory = indexing_root_directory.join("running-pipeline");
+        fs::create_dir_all(&in_use_directory).unwrap();
+
+        // The root of the active directory is old but one of its splits was just written.
+        let active_directory = indexing_root_directory.join("active-pipeline");
+        let active_split_file = active_directory.join("split-2").join("hotcache");
+        fs::create_dir_all(active_directory.join("split-2")).unwrap();
+        fs::write(&active_split_file, b"active").unwrap();
+
+        let two_hours_ago = filetime::FileTime::from_system_time(
+            SystemTime::now() - Duration::from_secs(2 * 60 * 60),
+        );
+        for path in [
+            &stale_split_file,
+            &stale_split_directory,
+            &stale_directory,
+            &stale_file,
+            &active_directory,
+            &in_use_directory,
+        ] {
+            filetime::set_file_mtime(path, two_hours_ago).unwrap();
+        }
+        remove_stale_scratch_directories(
+            indexing_root_directory.clone(),
+            HashSet::from([in_use_directory.clone()]),
+            Duration::from_secs(60 * 60),
+        )
+        .await
+        .unwrap();
+
+        assert!(!stale_directory.exists());
+        assert!(!stale_file.exists());
+        assert!(active_split_file.exists());
+        assert!(in_use_directory.exists());
+
+        // A missing indexing directory is not an error.
+        remove_stale_scratch_directories(
+            temp_dir.path().join("missing"),
+            HashSet::new(),
+            Duration::from_secs(60 * 60),
+        )
+        .await
+        .unwrap();
+    }
+
     #[tokio::test]
     async fn test_indexing_service_ingest_api_gc() {
         let index_id = "test-ingest-api-gc-index".to_string();
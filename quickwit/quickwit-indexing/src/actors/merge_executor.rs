@@ -49,7 +49,7 @@ use tokio::runtime::Handle;
 use tracing::{debug, info, instrument, warn};
 
 use crate::actors::Packager;
-use crate::controlled_directory::ControlledDirectory;
+use crate::controlled_directory::{ControlledDirectory, ScratchSpaceQuota};
 use crate::merge_policy::MergeOperationType;
 use crate::models::{IndexedSplit, IndexedSplitBatch, MergeScratch, PublishLock, SplitAttrs};
 
@@ -59,6 +59,7 @@ pub struct MergeExecutor {
     metastore: MetastoreServiceClient,
     doc_mapper: Arc<dyn DocMapper>,
     io_controls: IoControls,
+    scratch_space_quota: ScratchSpaceQuota,
     merge_packager_mailbox: Mailbox<Packager>,
 }
 
@@ -129,6 +130,9 @@ impl Handler<MergeScratch> for MergeExecutor {
                 operation_type = %merge_op.operation_type,
                 "merge-operation-success"
             );
+            if merge_op.operation_type == MergeOperationType::Merge {
+                merge_op.record_metrics(&indexed_split.split_attrs);
+            }
             ctx.send_message(
                 &self.merge_packager_mailbox,
                 IndexedSplitBatch {
@@ -276,6 +280,7 @@ impl MergeExecutor {
         metastore: MetastoreServiceClient,
         doc_mapper: Arc<dyn DocMapper>,
         io_controls: IoControls,
+        scratch_space_quota: ScratchSpaceQuota,
         merge_packager_mailbox: Mailbox<Packager>,
     ) -> Self {
         MergeExecutor {
@@ -283,6 +288,7 @@ impl MergeExecutor {
             metastore,
             doc_mapper,
             io_controls,
+            scratch_space_quota,
             merge_packager_mailbox,
         }
     }
@@ -475,6 +481,7 @@ impl MergeExecutor {
                 .clone()
                 .set_kill_switch(ctx.kill_switch().clone())
                 .set_progress(ctx.progress().clone()),
+            self.scratch_space_quota.clone(),
         );
         let mut directory_stack: Vec<Box<dyn Directory>> = vec![
             output_directory.box_clone(),
@@ -585,8 +592,10 @@ mod tests {
                 fast: true
             timestamp_field: ts
         "#;
+        // The index ID is specific to this test so that the merge metrics are not updated by other
+        // tests.
         let test_sandbox =
-            TestSandbox::create("test-index", doc_mapping_yaml, "", &["body"]).await?;
+            TestSandbox::create("test-merge-executor", doc_mapping_yaml, "", &["body"]).await?;
         let index_uid = test_sandbox.index_uid();
         let pipeline_id = IndexingPipelineId {
             index_uid: index_uid.clone(),
@@ -610,6 +619,10 @@ mod tests {
             .await
             .unwrap();
         assert_eq!(split_metas.len(), 4);
+        let num_split_bytes: u64 = split_metas
+            .iter()
+            .map(|split_meta| split_meta.footer_offsets.end)
+            .sum();
         let merge_scratch_directory = TempDirectory::for_test();
         let downloaded_splits_directory =
             merge_scratch_directory.named_temp_child("downloaded-splits-")?;
@@ -631,6 +644,7 @@ mod tests {
             tantivy_dirs,
             merge_scratch_directory,
             downloaded_splits_directory,
+            downloaded_splits_scratch_space: Vec::new(),
         };
         let (merge_packager_mailbox, merge_packager_inbox) =
             test_sandbox.universe().create_test_mailbox();
@@ -639,6 +653,7 @@ mod tests {
             metastore,
             test_sandbox.doc_mapper(),
             IoControls::default(),
+            ScratchSpaceQuota::unlimited(),
             merge_packager_mailbox,
         );
         let (merge_executor_mailbox, merge_executor_handle) = test_sandbox
@@ -660,6 +675,37 @@ mod tests {
             .try_into()?;
         let searcher = reader.searcher();
         assert_eq!(searcher.segment_readers().len(), 1);
+
+        let labels = ["test-merge-executor", "test-source"];
+        let metrics = &crate::metrics::INDEXER_METRICS;
+        assert_eq!(
+            metrics
+                .merge_operations_total
+                .with_label_values(labels)
+                .get(),
+            1
+        );
+        assert_eq!(
+            metrics
+                .merge_input_splits_total
+                .with_label_values(labels)
+                .get(),
+            4
+        );
+        assert_eq!(
+            metrics
+                .merge_output_splits_total
+                .with_label_values(labels)
+                .get(),
+            1
+        );
+        assert_eq!(
+            metrics.merged_bytes_total.with_label_values(labels).get(),
+            num_split_bytes
+        );
+        let write_amplification = metrics.merge_write_amplification.with_label_values(labels);
+        assert_eq!(write_amplification.get_sample_count(), 1);
+        assert_eq!(write_amplification.get_sample_sum(), 1.0);
         test_sandbox.assert_quit().await;
         Ok(())
     }
@@ -781,6 +827,7 @@ mod tests {
             tantivy_dirs: vec![tantivy_dir],
             merge_scratch_directory,
             downloaded_splits_directory,
+            downloaded_splits_scratch_space: Vec::new(),
         };
         let (merge_packager_mailbox, merge_packager_inbox) = universe.create_test_mailbox();
         let delete_task_executor = MergeExecutor::new(
@@ -788,6 +835,7 @@ mod tests {
             metastore,
             test_sandbox.doc_mapper(),
             IoControls::default(),
+            ScratchSpaceQuota::unlimited(),
             merge_packager_mailbox,
         );
         let (delete_task_executor_mailbox, delete_task_executor_handle) =
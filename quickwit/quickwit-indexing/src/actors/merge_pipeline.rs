@@ -39,16 +39,21 @@ use quickwit_proto::metastore::{
     ListSplitsRequest, MetastoreError, MetastoreService, MetastoreServiceClient,
 };
 use time::OffsetDateTime;
-use tracing::{debug, error, info, instrument};
+use tracing::{debug, error, info, instrument, warn};
 
 use crate::actors::indexing_pipeline::wait_duration_before_retry;
 use crate::actors::merge_split_downloader::MergeSplitDownloader;
 use crate::actors::publisher::PublisherType;
 use crate::actors::{MergeExecutor, MergePlanner, Packager, Publisher, Uploader, UploaderType};
+use crate::controlled_directory::ScratchSpaceQuota;
 use crate::merge_policy::MergePolicy;
-use crate::models::MergeStatistics;
+use crate::models::{MergeStatistics, UpdatedSplits};
 use crate::split_store::IndexingSplitStore;
 
+/// Interval at which the merge pipeline looks for splits updated in the metastore, such as splits
+/// whose maturity was overridden, and forwards them to the merge planner.
+const REFRESH_UPDATED_SPLITS_INTERVAL: Duration = Duration::from_secs(60);
+
 #[derive(Debug)]
 struct ObserveLoop;
 
@@ -77,6 +82,9 @@ impl MergePipelineHandles {
 #[derive(Debug)]
 struct SuperviseLoop;
 
+#[derive(Debug)]
+struct RefreshUpdatedSplits;
+
 #[derive(Clone, Copy, Debug, Default)]
 struct Spawn {
     retry_count: usize,
@@ -90,6 +98,9 @@ pub struct MergePipeline {
     statistics: MergeStatistics,
     handles_opt: Option<MergePipelineHandles>,
     kill_switch: KillSwitch,
+    /// Splits updated at or after this timestamp have not been forwarded to the merge planner
+    /// yet.
+    updated_splits_refresh_timestamp: i64,
 }
 
 #[async_trait]
@@ -107,6 +118,7 @@ impl Actor for MergePipeline {
     async fn initialize(&mut self, ctx: &ActorContext<Self>) -> Result<(), ActorExitStatus> {
         self.handle(Spawn::default(), ctx).await?;
         self.handle(SuperviseLoop, ctx).await?;
+        ctx.schedule_self_msg(REFRESH_UPDATED_SPLITS_INTERVAL, RefreshUpdatedSplits);
         Ok(())
     }
 }
@@ -122,6 +134,7 @@ impl MergePipeline {
             previous_generations_statistics: Default::default(),
             handles_opt: None,
             kill_switch: KillSwitch::default(),
+            updated_splits_refresh_timestamp: OffsetDateTime::now_utc().unix_timestamp(),
             statistics: MergeStatistics::default(),
             merge_planner_inbox,
             merge_planner_mailbox,
@@ -219,9 +232,10 @@ impl MergePipeline {
             merge_policy=?self.params.merge_policy,
             "spawn merge pipeline",
         );
+        let now = OffsetDateTime::now_utc();
         let query = ListSplitsQuery::for_index(self.params.pipeline_id.index_uid.clone())
             .with_split_state(SplitState::Published)
-            .retain_immature(OffsetDateTime::now_utc());
+            .retain_immature(now);
         let list_splits_request = ListSplitsRequest::try_from_list_splits_query(query)?;
         let published_splits_stream = ctx
             .protect_future(self.params.metastore.list_splits(list_splits_request))
@@ -234,6 +248,7 @@ impl MergePipeline {
             num_splits = published_splits_metadata.len(),
             "loaded list of published splits"
         );
+        self.updated_splits_refresh_timestamp = now.unix_timestamp();
 
         // Merge publisher
         let merge_publisher = Publisher::new(
@@ -264,7 +279,13 @@ impl MergePipeline {
 
         // Merge Packager
         let tag_fields = self.params.doc_mapper.tag_named_fields()?;
-        let merge_packager = Packager::new("MergePackager", tag_fields, merge_uploader_mailbox);
+        let min_max_fields = self.params.doc_mapper.min_max_named_fields()?;
+        let merge_packager = Packager::new(
+            "MergePackager",
+            tag_fields,
+            min_max_fields,
+            merge_uploader_mailbox,
+        );
         let (merge_packager_mailbox, merge_packager_handler) = ctx
             .spawn_actor()
             .set_kill_switch(self.kill_switch.clone())
@@ -295,6 +316,7 @@ impl MergePipeline {
             self.params.metastore.clone(),
             self.params.doc_mapper.clone(),
             merge_executor_io_controls,
+            self.params.scratch_space_quota.clone(),
             merge_packager_mailbox,
         );
         let (merge_executor_mailbox, merge_executor_handler) = ctx
@@ -307,6 +329,7 @@ impl MergePipeline {
             split_store: self.params.split_store.clone(),
             executor_mailbox: merge_executor_mailbox,
             io_controls: split_downloader_io_controls,
+            scratch_space_quota: self.params.scratch_space_quota.clone(),
         };
         let (merge_split_downloader_mailbox, merge_split_downloader_handler) = ctx
             .spawn_actor()
@@ -351,6 +374,35 @@ impl MergePipeline {
         Ok(())
     }
 
+    /// Forwards the splits updated in the metastore since the last refresh to the merge planner.
+    /// Without it, the merge planner would keep acting on stale metadata, for instance after the
+    /// maturity of a split has been overridden.
+    async fn refresh_updated_splits(&mut self, ctx: &ActorContext<Self>) -> anyhow::Result<()> {
+        // Timestamps have a one second resolution, so we list updates from the same second
+        // again rather than missing some.
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+        let query = ListSplitsQuery::for_index(self.params.pipeline_id.index_uid.clone())
+            .with_split_state(SplitState::Published)
+            .with_update_timestamp_gte(self.updated_splits_refresh_timestamp);
+        let list_splits_request = ListSplitsRequest::try_from_list_splits_query(query)?;
+        let updated_splits_stream = ctx
+            .protect_future(self.params.metastore.list_splits(list_splits_request))
+            .await?;
+        let updated_splits = ctx
+            .protect_future(updated_splits_stream.collect_splits_metadata())
+            .await?;
+
+        if !updated_splits.is_empty() {
+            // The merge planner mailbox has a capacity of one: rather than blocking the
+            // supervision of the pipeline, we try again on the next refresh.
+            self.merge_planner_mailbox
+                .try_send_message(UpdatedSplits { updated_splits })
+                .map_err(|_| anyhow::anyhow!("merge planner mailbox is full or closed"))?;
+        }
+        self.updated_splits_refresh_timestamp = now;
+        Ok(())
+    }
+
     async fn terminate(&mut self) {
         self.kill_switch.kill();
         if let Some(handlers) = self.handles_opt.take() {
@@ -431,6 +483,25 @@ impl Handler<SuperviseLoop> for MergePipeline {
     }
 }
 
+#[async_trait]
+impl Handler<RefreshUpdatedSplits> for MergePipeline {
+    type Reply = ();
+
+    async fn handle(
+        &mut self,
+        refresh_updated_splits: RefreshUpdatedSplits,
+        ctx: &ActorContext<Self>,
+    ) -> Result<(), ActorExitStatus> {
+        if self.handles_opt.is_some() {
+            if let Err(error) = self.refresh_updated_splits(ctx).await {
+                warn!(error=?error, "failed to refresh updated splits");
+            }
+        }
+        ctx.schedule_self_msg(REFRESH_UPDATED_SPLITS_INTERVAL, refresh_updated_splits);
+        Ok(())
+    }
+}
+
 #[async_trait]
 impl Handler<Spawn> for MergePipeline {
     type Reply = ();
@@ -469,6 +540,7 @@ pub struct MergePipelineParams {
     pub pipeline_id: IndexingPipelineId,
     pub doc_mapper: Arc<dyn DocMapper>,
     pub indexing_directory: TempDirectory,
+    pub scratch_space_quota: ScratchSpaceQuota,
     pub metastore: MetastoreServiceClient,
     pub split_store: IndexingSplitStore,
     pub merge_policy: Arc<dyn MergePolicy>,
@@ -494,7 +566,7 @@ mod tests {
 
     use crate::actors::merge_pipeline::{MergePipeline, MergePipelineParams};
     use crate::merge_policy::default_merge_policy;
-    use crate::IndexingSplitStore;
+    use crate::{IndexingSplitStore, ScratchSpaceQuota};
 
     #[tokio::test]
     async fn test_merge_pipeline_simple() -> anyhow::Result<()> {
@@ -529,6 +601,7 @@ mod tests {
             pipeline_id,
             doc_mapper: Arc::new(default_doc_mapper_for_test()),
             indexing_directory: TempDirectory::for_test(),
+            scratch_space_quota: ScratchSpaceQuota::unlimited(),
             metastore: MetastoreServiceClient::from(metastore),
             split_store,
             merge_policy: default_merge_policy(),
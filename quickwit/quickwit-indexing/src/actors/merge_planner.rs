@@ -36,7 +36,7 @@ use tracing::{info, warn};
 use crate::actors::MergeSplitDownloader;
 use crate::merge_policy::MergeOperation;
 use crate::metrics::INDEXER_METRICS;
-use crate::models::NewSplits;
+use crate::models::{NewSplits, UpdatedSplits};
 use crate::MergePolicy;
 
 /// The merge planner decides when to start a merge task.
@@ -169,6 +169,35 @@ impl Handler<NewSplits> for MergePlanner {
     }
 }
 
+#[async_trait]
+impl Handler<UpdatedSplits> for MergePlanner {
+    type Reply = ();
+
+    async fn handle(
+        &mut self,
+        updated_splits: UpdatedSplits,
+        ctx: &ActorContext<Self>,
+    ) -> Result<(), ActorExitStatus> {
+        let now = OffsetDateTime::now_utc();
+        let mut immature_splits = Vec::new();
+
+        for updated_split in updated_splits.updated_splits {
+            if !belongs_to_pipeline(&self.pipeline_id, &updated_split) {
+                continue;
+            }
+            if updated_split.is_mature(now) {
+                self.dismiss_young_split(&updated_split);
+            } else {
+                immature_splits.push(updated_split);
+            }
+        }
+        self.record_splits_if_necessary(immature_splits);
+        self.send_merge_ops(ctx).await?;
+        self.recompute_known_splits_if_necessary();
+        Ok(())
+    }
+}
+
 fn max_merge_ops(merge_op: &MergeOperation) -> usize {
     merge_op
         .splits_as_slice()
@@ -294,6 +323,27 @@ impl MergePlanner {
         splits_for_partition.push(new_split);
     }
 
+    // Stops considering a young split that has become mature for merges. Splits already in a
+    // merge operation are left untouched.
+    fn dismiss_young_split(&mut self, split: &SplitMetadata) {
+        let Some(young_splits) = self.partitioned_young_splits.get_mut(&split.partition_id) else {
+            return;
+        };
+        let Some(position) = young_splits
+            .iter()
+            .position(|young_split| young_split.split_id() == split.split_id())
+        else {
+            return;
+        };
+        young_splits.swap_remove(position);
+
+        if young_splits.is_empty() {
+            self.partitioned_young_splits.remove(&split.partition_id);
+        }
+        // The split may become immature again, in which case it must be recorded anew.
+        self.known_split_ids.remove(split.split_id());
+    }
+
     // Records a list of splits.
     //
     // Internally this function will detect and avoid adding the split
@@ -469,6 +519,7 @@ mod tests {
     use quickwit_config::IndexingSettings;
     use quickwit_metastore::{SplitMaturity, SplitMetadata};
     use quickwit_proto::indexing::IndexingPipelineId;
+    use quickwit_proto::metastore::SplitMaturityOverride;
     use quickwit_proto::types::{IndexUid, PipelineUid};
     use tantivy::TrackedObject;
     use time::OffsetDateTime;
@@ -477,7 +528,7 @@ mod tests {
     use crate::merge_policy::{
         merge_policy_from_settings, MergeOperation, MergePolicy, StableLogMergePolicy,
     };
-    use crate::models::NewSplits;
+    use crate::models::{NewSplits, UpdatedSplits};
 
     fn split_metadata_for_test(
         index_uid: &IndexUid,
@@ -588,6 +639,83 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_merge_planner_honors_updated_split_maturity() -> anyhow::Result<()> {
+        let universe = Universe::with_accelerated_time();
+        let index_uid = IndexUid::new_with_random_ulid("test-index");
+        let (merge_split_downloader_mailbox, merge_split_downloader_inbox) =
+            universe.create_test_mailbox();
+        let pipeline_id = IndexingPipelineId {
+            index_uid: index_uid.clone(),
+            source_id: "test-source".to_string(),
+            node_id: "test-node".to_string(),
+            pipeline_uid: PipelineUid::default(),
+        };
+        let merge_policy = Arc::new(StableLogMergePolicy::new(
+            StableLogMergePolicyConfig {
+                min_level_num_docs: 10_000,
+                merge_factor: 3,
+                max_merge_factor: 3,
+                maturation_period: Duration::from_secs(3600),
+            },
+            50_000,
+        ));
+        let merge_planner = MergePlanner::new(
+            pipeline_id,
+            vec![
+                split_metadata_for_test(&index_uid, "split-1", 0, 1000, 0),
+                split_metadata_for_test(&index_uid, "split-2", 0, 1000, 0),
+            ],
+            merge_policy,
+            merge_split_downloader_mailbox,
+        );
+        let (merge_planner_mailbox, merge_planner_handle) =
+            universe.spawn_builder().spawn(merge_planner);
+        {
+            // A young split forced to mature is no longer a merge candidate.
+            let mut split_1 = split_metadata_for_test(&index_uid, "split-1", 0, 1000, 0);
+            split_1.maturity_override = Some(SplitMaturityOverride::ForceMature);
+            let message = UpdatedSplits {
+                updated_splits: vec![split_1],
+            };
+            merge_planner_mailbox.send_message(message).await?;
+
+            let message = NewSplits {
+                new_splits: vec![split_metadata_for_test(&index_uid, "split-3", 0, 1000, 0)],
+            };
+            merge_planner_mailbox.send_message(message).await?;
+            merge_planner_handle.process_pending_and_observe().await;
+
+            let merge_ops = merge_split_downloader_inbox.drain_for_test();
+            assert!(merge_ops.is_empty());
+        }
+        {
+            // A mature split forced to remain immature becomes a merge candidate.
+            let mut split_4 = split_metadata_for_test(&index_uid, "split-4", 0, 1000, 0);
+            split_4.maturity = SplitMaturity::Mature;
+            split_4.maturity_override = Some(SplitMaturityOverride::ForceImmature);
+            let message = UpdatedSplits {
+                updated_splits: vec![split_4],
+            };
+            merge_planner_mailbox.send_message(message).await?;
+            merge_planner_handle.process_pending_and_observe().await;
+
+            let merge_ops = merge_split_downloader_inbox
+                .drain_for_test_typed::<TrackedObject<MergeOperation>>();
+            assert_eq!(merge_ops.len(), 1);
+
+            let merged_split_ids: Vec<&str> = merge_ops[0]
+                .splits_as_slice()
+                .iter()
+                .map(|split| split.split_id())
+                .sorted()
+                .collect();
+            assert_eq!(merged_split_ids, ["split-2", "split-3", "split-4"]);
+        }
+        universe.assert_quit().await;
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_merge_planner_priority() -> anyhow::Result<()> {
         let universe = Universe::with_accelerated_time();
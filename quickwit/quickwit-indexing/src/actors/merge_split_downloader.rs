@@ -28,6 +28,7 @@ use tantivy::{Directory, TrackedObject};
 use tracing::{debug, info, instrument};
 
 use super::MergeExecutor;
+use crate::controlled_directory::{ScratchSpaceQuota, ScratchSpaceReservation};
 use crate::merge_policy::MergeOperation;
 use crate::models::MergeScratch;
 use crate::split_store::IndexingSplitStore;
@@ -38,6 +39,7 @@ pub struct MergeSplitDownloader {
     pub split_store: IndexingSplitStore,
     pub executor_mailbox: Mailbox<MergeExecutor>,
     pub io_controls: IoControls,
+    pub scratch_space_quota: ScratchSpaceQuota,
 }
 
 impl Actor for MergeSplitDownloader {
@@ -76,7 +78,7 @@ impl Handler<TrackedObject<MergeOperation>> for MergeSplitDownloader {
             .join("downloaded-splits")
             .tempdir_in(merge_scratch_directory.path())
             .map_err(|error| anyhow::anyhow!(error))?;
-        let tantivy_dirs = self
+        let (tantivy_dirs, downloaded_splits_scratch_space) = self
             .download_splits(
                 merge_operation.splits_as_slice(),
                 downloaded_splits_directory.path(),
@@ -88,6 +90,7 @@ impl Handler<TrackedObject<MergeOperation>> for MergeSplitDownloader {
             merge_scratch_directory,
             downloaded_splits_directory,
             tantivy_dirs,
+            downloaded_splits_scratch_space,
         };
         ctx.send_message(&self.executor_mailbox, msg).await?;
         Ok(())
@@ -100,9 +103,10 @@ impl MergeSplitDownloader {
         splits: &[SplitMetadata],
         download_directory: &Path,
         ctx: &ActorContext<Self>,
-    ) -> Result<Vec<Box<dyn Directory>>, quickwit_actors::ActorExitStatus> {
+    ) -> Result<(Vec<Box<dyn Directory>>, Vec<ScratchSpaceReservation>), ActorExitStatus> {
         // we download all of the split files in the scratch directory.
         let mut tantivy_dirs = Vec::new();
+        let mut scratch_space_reservations = Vec::new();
         for split in splits {
             if ctx.kill_switch().is_dead() {
                 debug!(
@@ -117,17 +121,23 @@ impl MergeSplitDownloader {
                 .set_progress(ctx.progress().clone())
                 .set_kill_switch(ctx.kill_switch().clone());
             let _protect_guard = ctx.protect_zone();
-            let tantivy_dir = self
+            let (tantivy_dir, scratch_space_reservation) = self
                 .split_store
-                .fetch_and_open_split(split.split_id(), download_directory, &io_controls)
+                .fetch_and_open_split(
+                    split,
+                    download_directory,
+                    &io_controls,
+                    &self.scratch_space_quota,
+                )
                 .await
                 .map_err(|error| {
                     let split_id = split.split_id();
                     anyhow::anyhow!(error).context(format!("failed to download split `{split_id}`"))
                 })?;
             tantivy_dirs.push(tantivy_dir);
+            scratch_space_reservations.push(scratch_space_reservation);
         }
-        Ok(tantivy_dirs)
+        Ok((tantivy_dirs, scratch_space_reservations))
     }
 }
 
@@ -136,6 +146,7 @@ mod tests {
     use std::iter;
     use std::sync::Arc;
 
+    use bytesize::ByteSize;
     use quickwit_actors::Universe;
     use quickwit_common::split_file;
     use quickwit_storage::{PutPayload, RamStorageBuilder, SplitPayloadBuilder};
@@ -151,6 +162,7 @@ mod tests {
             let split_id = new_split_id();
             SplitMetadata {
                 split_id,
+                footer_offsets: 0..1_000,
                 ..Default::default()
             }
         })
@@ -171,11 +183,13 @@ mod tests {
 
         let universe = Universe::with_accelerated_time();
         let (merge_executor_mailbox, merge_executor_inbox) = universe.create_test_mailbox();
+        let scratch_space_quota = ScratchSpaceQuota::new(ByteSize::kb(100));
         let merge_split_downloader = MergeSplitDownloader {
             scratch_directory,
             split_store,
             executor_mailbox: merge_executor_mailbox,
             io_controls: IoControls::default(),
+            scratch_space_quota: scratch_space_quota.clone(),
         };
         let (merge_split_downloader_mailbox, merge_split_downloader_handler) =
             universe.spawn_builder().spawn(merge_split_downloader);
@@ -204,6 +218,9 @@ mod tests {
                 .join(split_filename);
             assert!(split_filepath.try_exists().unwrap());
         }
+        assert_eq!(scratch_space_quota.num_bytes(), ByteSize(10_000));
+        drop(merge_scratch);
+        assert_eq!(scratch_space_quota.num_bytes(), ByteSize(0));
         universe.assert_quit().await;
         Ok(())
     }
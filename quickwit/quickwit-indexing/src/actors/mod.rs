@@ -17,6 +17,8 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
+mod dead_letter_queue;
+mod derived_fields;
 mod doc_processor;
 mod index_serializer;
 mod indexer;
@@ -33,10 +35,13 @@ mod uploader;
 #[cfg(feature = "vrl")]
 mod vrl_processing;
 
+pub use dead_letter_queue::DeadLetterQueue;
 pub use doc_processor::{DocProcessor, DocProcessorCounters};
 pub use index_serializer::IndexSerializer;
 pub use indexer::{Indexer, IndexerCounters};
-pub use indexing_pipeline::{IndexingPipeline, IndexingPipelineParams};
+pub use indexing_pipeline::{
+    IndexingPipeline, IndexingPipelineParams, PausePipeline, ResumePipeline,
+};
 pub use indexing_service::{
     IndexingService, IndexingServiceCounters, MergePipelineId, INDEXING_DIR_NAME,
 };
@@ -17,7 +17,7 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::io;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -32,11 +32,14 @@ use quickwit_common::temp_dir::TempDirectory;
 use quickwit_directories::write_hotcache;
 use quickwit_doc_mapper::tag_pruning::append_to_tag_set;
 use quickwit_doc_mapper::NamedField;
+use quickwit_metastore::FieldRange;
 use quickwit_proto::search::{
     serialize_split_fields, ListFieldType, ListFields, ListFieldsEntryResponse,
 };
-use tantivy::schema::{FieldType, Type};
-use tantivy::{FieldMetadata, InvertedIndexReader, ReloadPolicy, SegmentMeta};
+use tantivy::columnar::{DynamicColumn, HasAssociatedColumnType};
+use tantivy::fastfield::Column;
+use tantivy::schema::{FieldType, Schema, Type};
+use tantivy::{FieldMetadata, InvertedIndexReader, ReloadPolicy, SegmentMeta, SegmentReader};
 use tokio::runtime::Handle;
 use tracing::{debug, info, instrument, warn};
 
@@ -69,18 +72,23 @@ pub struct Packager {
     uploader_mailbox: Mailbox<Uploader>,
     /// List of tag fields ([`Vec<NamedField>`]) defined in the index config.
     tag_fields: Vec<NamedField>,
+    /// List of numeric fast fields ([`Vec<NamedField>`]) whose min / max values are recorded in
+    /// the split metadata.
+    min_max_fields: Vec<NamedField>,
 }
 
 impl Packager {
     pub fn new(
         actor_name: &'static str,
         tag_fields: Vec<NamedField>,
+        min_max_fields: Vec<NamedField>,
         uploader_mailbox: Mailbox<Uploader>,
     ) -> Packager {
         Packager {
             actor_name,
             uploader_mailbox,
             tag_fields,
+            min_max_fields,
         }
     }
 
@@ -91,8 +99,13 @@ impl Packager {
     ) -> anyhow::Result<PackagedSplit> {
         let segment_metas = split.index.searchable_segment_metas()?;
         assert_eq!(segment_metas.len(), 1);
-        let packaged_split =
-            create_packaged_split(&segment_metas[..], split, &self.tag_fields, ctx)?;
+        let packaged_split = create_packaged_split(
+            &segment_metas[..],
+            split,
+            &self.tag_fields,
+            &self.min_max_fields,
+            ctx,
+        )?;
         Ok(packaged_split)
     }
 }
@@ -272,10 +285,88 @@ fn try_extract_terms(
     Ok(terms)
 }
 
+/// Returns the min / max values of a numeric fast field over the given segments, or `None` if no
+/// document has a value for the field.
+fn extract_column_range<T>(
+    field_name: &str,
+    segment_readers: &[SegmentReader],
+) -> anyhow::Result<Option<(T, T)>>
+where
+    T: HasAssociatedColumnType + PartialOrd,
+    DynamicColumn: Into<Option<Column<T>>>,
+{
+    let mut range_opt: Option<(T, T)> = None;
+    for segment_reader in segment_readers {
+        let Some(column) = segment_reader.fast_fields().column_opt::<T>(field_name)? else {
+            continue;
+        };
+        if column.values.num_vals() == 0 {
+            continue;
+        }
+        let (min, max) = (column.min_value(), column.max_value());
+        range_opt = match range_opt {
+            Some((current_min, current_max)) => Some((
+                if min < current_min { min } else { current_min },
+                if max > current_max { max } else { current_max },
+            )),
+            None => Some((min, max)),
+        };
+    }
+    Ok(range_opt)
+}
+
+fn extract_field_range(
+    named_field: &NamedField,
+    segment_readers: &[SegmentReader],
+) -> anyhow::Result<Option<FieldRange>> {
+    let field_name = &named_field.name;
+    let field_range_opt = match named_field.field_type.value_type() {
+        Type::U64 => extract_column_range::<u64>(field_name, segment_readers)?
+            .map(|(min, max)| FieldRange::U64 { min, max }),
+        Type::I64 => extract_column_range::<i64>(field_name, segment_readers)?
+            .map(|(min, max)| FieldRange::I64 { min, max }),
+        Type::F64 => extract_column_range::<f64>(field_name, segment_readers)?
+            .filter(|(min, max)| !min.is_nan() && !max.is_nan())
+            .map(|(min, max)| FieldRange::F64 { min, max }),
+        value_type => bail!(
+            "min / max values cannot be recorded for field `{field_name}` of type `{}`",
+            value_type.name()
+        ),
+    };
+    Ok(field_range_opt)
+}
+
+/// Estimates the number of distinct values of each fast text field of the split. Terms shared by
+/// several segments are counted once per segment, so the estimate is an upper bound.
+fn extract_field_cardinalities(
+    schema: &Schema,
+    segment_readers: &[SegmentReader],
+) -> anyhow::Result<BTreeMap<String, u64>> {
+    let mut field_cardinalities = BTreeMap::default();
+    for (_field, field_entry) in schema.fields() {
+        let FieldType::Str(text_options) = field_entry.field_type() else {
+            continue;
+        };
+        if !text_options.is_fast() {
+            continue;
+        }
+        let field_name = field_entry.name();
+        let mut cardinality = 0u64;
+        for segment_reader in segment_readers {
+            if let Some(str_column) = segment_reader.fast_fields().str(field_name)? {
+                cardinality += str_column.num_terms() as u64;
+            }
+        }
+        field_cardinalities.insert(field_name.to_string(), cardinality);
+    }
+    Ok(field_cardinalities)
+}
+
 fn create_packaged_split(
     segment_metas: &[SegmentMeta],
     split: IndexedSplit,
     tag_fields: &[NamedField],
+    min_max_fields: &[NamedField],
     ctx: &ActorContext<Packager>,
 ) -> anyhow::Result<PackagedSplit> {
     info!(split_id = split.split_id(), "create-packaged-split");
@@ -311,6 +402,17 @@ fn create_packaged_split(
         }
     }
 
+    debug!(split_id = split.split_id(), min_max_fields =? min_max_fields, "extract-field-ranges");
+    let searcher = index_reader.searcher();
+    let mut field_ranges = BTreeMap::default();
+    for named_field in min_max_fields {
+        if let Some(field_range) = extract_field_range(named_field, searcher.segment_readers())? {
+            field_ranges.insert(named_field.name.clone(), field_range);
+        }
+    }
+    let field_cardinalities =
+        extract_field_cardinalities(&split.index.schema(), searcher.segment_readers())?;
+
     ctx.record_progress();
 
     debug!(split_id = split.split_id(), "build-hotcache");
@@ -325,6 +427,8 @@ fn create_packaged_split(
         split_attrs: split.split_attrs,
         split_scratch_directory: split.split_scratch_directory,
         tags,
+        field_ranges,
+        field_cardinalities,
         split_files,
         hotcache_bytes,
     };
@@ -454,6 +558,7 @@ mod tests {
         let timestamp_field = schema_builder.add_u64_field("timestamp", FAST);
         let tag_str = schema_builder.add_text_field("tag_str", STRING);
         let tag_many = schema_builder.add_text_field("tag_many", STRING);
+        let user_id_field = schema_builder.add_text_field("user_id", STRING | FAST);
         let tag_u64 =
             schema_builder.add_u64_field("tag_u64", NumericOptions::default().set_indexed());
         let tag_i64 =
@@ -462,6 +567,10 @@ mod tests {
             schema_builder.add_f64_field("tag_f64", NumericOptions::default().set_indexed());
         let tag_bool =
             schema_builder.add_bool_field("tag_bool", NumericOptions::default().set_indexed());
+        let status_field = schema_builder.add_u64_field("status", FAST);
+        let offset_field = schema_builder.add_i64_field("offset", FAST);
+        let latency_field = schema_builder.add_f64_field("latency", FAST);
+        schema_builder.add_u64_field("missing", FAST);
         let schema = schema_builder.build();
         let index_builder = IndexBuilder::new()
             .settings(IndexSettings::default())
@@ -488,10 +597,14 @@ mod tests {
                     timestamp_field => timestamp,
                     tag_str => "value",
                     tag_many => format!("many-{num}"),
+                    user_id_field => format!("user-{num}"),
                     tag_u64 => 42u64,
                     tag_i64 => -42i64,
                     tag_f64 => -42.02f64,
                     tag_bool => true,
+                    status_field => 200 + num as u64,
+                    offset_field => -(num as i64),
+                    latency_field => num as f64 * 0.5,
                 );
                 index_writer.add_document(doc)?;
                 num_docs += 1;
@@ -565,7 +678,11 @@ mod tests {
                 "tag_str", "tag_many", "tag_u64", "tag_i64", "tag_f64", "tag_bool",
             ],
         );
-        let packager = Packager::new("TestPackager", tag_fields, mailbox);
+        let min_max_fields = get_tag_fields(
+            indexed_split.index.schema(),
+            &["status", "offset", "latency", "missing"],
+        );
+        let packager = Packager::new("TestPackager", tag_fields, min_max_fields, mailbox);
         let (packager_mailbox, packager_handle) = universe.spawn_builder().spawn(packager);
         packager_mailbox
             .send_message(IndexedSplitBatch {
@@ -602,6 +719,21 @@ mod tests {
                 "tag_u64:42"
             ]
         );
+        assert_eq!(split.field_ranges.len(), 3);
+        assert_eq!(
+            split.field_ranges["status"],
+            FieldRange::U64 { min: 201, max: 209 }
+        );
+        assert_eq!(
+            split.field_ranges["offset"],
+            FieldRange::I64 { min: -9, max: -1 }
+        );
+        assert_eq!(
+            split.field_ranges["latency"],
+            FieldRange::F64 { min: 0.5, max: 4.5 }
+        );
+        assert_eq!(split.field_cardinalities.len(), 1);
+        assert_eq!(split.field_cardinalities["user_id"], 9);
         assert_eq!(
             split.split_attrs.time_range,
             Some(
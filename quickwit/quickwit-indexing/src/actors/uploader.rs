@@ -321,15 +321,24 @@ impl Handler<PackagedSplitBatch> for Uploader {
                         &packaged_split.serialized_split_fields,
                         &packaged_split.hotcache_bytes,
                     )?;
-                    let split_metadata = create_split_metadata(
+                    let mut split_metadata = create_split_metadata(
                         &merge_policy,
                         &packaged_split.split_attrs,
                         packaged_split.tags.clone(),
                         split_streamer.footer_range.start..split_streamer.footer_range.end,
                     );
+                    split_metadata.split_path_prefix =
+                        split_store.split_path_prefix(split_metadata.time_range.as_ref());
+                    split_metadata.field_ranges = packaged_split.field_ranges.clone();
+                    split_metadata.field_cardinalities =
+                        packaged_split.field_cardinalities.clone();
 
+                    let split_storage_uri = match &split_metadata.split_path_prefix {
+                        Some(split_path_prefix) => split_store.remote_uri().join(split_path_prefix)?,
+                        None => split_store.remote_uri().clone(),
+                    };
                     report_splits.push(ReportSplit {
-                        storage_uri: split_store.remote_uri().to_string(),
+                        storage_uri: split_storage_uri.to_string(),
                         split_id: packaged_split.split_id().to_string(),
                     });
 
@@ -566,6 +575,9 @@ mod tests {
                     serialized_split_fields: Vec::new(),
                     split_scratch_directory,
                     tags: Default::default(),
+                    field_ranges: Default::default(),
+                    field_cardinalities: Default::default(),
+                    maturity_override: None,
                     hotcache_bytes: Vec::new(),
                     split_files: Vec::new(),
                 }],
@@ -678,6 +690,9 @@ mod tests {
             serialized_split_fields: Vec::new(),
             split_scratch_directory: split_scratch_directory_1,
             tags: Default::default(),
+            field_ranges: Default::default(),
+            field_cardinalities: Default::default(),
+            maturity_override: None,
             split_files: Vec::new(),
             hotcache_bytes: Vec::new(),
         };
@@ -702,6 +717,9 @@ mod tests {
             serialized_split_fields: Vec::new(),
             split_scratch_directory: split_scratch_directory_2,
             tags: Default::default(),
+            field_ranges: Default::default(),
+            field_cardinalities: Default::default(),
+            maturity_override: None,
             split_files: Vec::new(),
             hotcache_bytes: Vec::new(),
         };
@@ -820,6 +838,9 @@ mod tests {
                     serialized_split_fields: Vec::new(),
                     split_scratch_directory,
                     tags: Default::default(),
+                    field_ranges: Default::default(),
+                    field_cardinalities: Default::default(),
+                    maturity_override: None,
                     hotcache_bytes: Vec::new(),
                     split_files: Vec::new(),
                 }],
@@ -999,6 +1020,9 @@ mod tests {
                     serialized_split_fields: Vec::new(),
                     split_scratch_directory,
                     tags: Default::default(),
+                    field_ranges: Default::default(),
+                    field_cardinalities: Default::default(),
+                    maturity_override: None,
                     hotcache_bytes: Vec::new(),
                     split_files: Vec::new(),
                 }],
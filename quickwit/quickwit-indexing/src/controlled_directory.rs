@@ -17,13 +17,16 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
+use std::collections::HashMap;
 use std::io::{BufWriter, IntoInnerError};
 use std::ops::Deref;
-use std::path::Path;
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use std::{fmt, io};
 
 use arc_swap::ArcSwap;
+use bytesize::ByteSize;
 use quickwit_common::io::{ControlledWrite, IoControls, IoControlsAccess};
 use quickwit_common::ProtectedZoneGuard;
 use tantivy::directory::error::{DeleteError, OpenReadError, OpenWriteError};
@@ -42,18 +45,25 @@ const BUFFER_NUM_BYTES: usize = 8_192;
 /// with functionalities such as
 /// - records progress everytime a write (Note there is however a buffer writer above it)
 /// - if the killswitch is activated, returns an error on the first write happening after it
+/// - returns an error on writes that would exceed the scratch space quota
 /// - in the future, record a writing speed, possibly introduce some throttling, etc.
 #[derive(Clone)]
 pub struct ControlledDirectory {
     underlying: Arc<dyn Directory>,
     io_controls: HotswappableIoControls,
+    scratch_space_usage: Arc<ScratchSpaceUsage>,
 }
 
 impl ControlledDirectory {
-    pub fn new(directory: Box<dyn Directory>, io_controls: IoControls) -> ControlledDirectory {
+    pub fn new(
+        directory: Box<dyn Directory>,
+        io_controls: IoControls,
+        scratch_space_quota: ScratchSpaceQuota,
+    ) -> ControlledDirectory {
         ControlledDirectory {
             underlying: directory.into(),
             io_controls: HotswappableIoControls::new(io_controls),
+            scratch_space_usage: Arc::new(ScratchSpaceUsage::new(scratch_space_quota)),
         }
     }
 
@@ -85,7 +95,9 @@ impl Directory for ControlledDirectory {
                 io_error: Arc::new(io_error),
                 filepath: path.to_path_buf(),
             })?;
-        self.underlying.delete(path)
+        self.underlying.delete(path)?;
+        self.scratch_space_usage.release_file(path);
+        Ok(())
     }
 
     fn exists(&self, path: &Path) -> Result<bool, OpenReadError> {
@@ -107,7 +119,11 @@ impl Directory for ControlledDirectory {
         let controlled_wrt = self.io_controls.clone().wrap_write(underlying_wrt);
         Ok(BufWriter::with_capacity(
             BUFFER_NUM_BYTES,
-            Box::new(AdoptedControlledWrite(controlled_wrt)),
+            Box::new(AdoptedControlledWrite {
+                controlled_wrt,
+                path: path.to_path_buf(),
+                scratch_space_usage: self.scratch_space_usage.clone(),
+            }),
         ))
     }
 
@@ -119,6 +135,9 @@ impl Directory for ControlledDirectory {
 
     fn atomic_write(&self, path: &Path, data: &[u8]) -> io::Result<()> {
         self.check_if_alive()?;
+        // Atomic writes replace the previous content of the file.
+        self.scratch_space_usage.release_file(path);
+        self.scratch_space_usage.allocate(path, data.len() as u64)?;
         self.underlying.atomic_write(path, data)
     }
 
@@ -158,23 +177,179 @@ impl IoControlsAccess for HotswappableIoControls {
     }
 }
 
+/// Limits the number of bytes written in the scratch directories of an indexer. The quota is
+/// shared by all the [`ControlledDirectory`] it is passed to.
+///
+/// The bytes of a file are accounted for until the file is deleted or the directory is dropped.
+#[derive(Clone, Debug, Default)]
+pub struct ScratchSpaceQuota {
+    inner: Arc<ScratchSpaceQuotaInner>,
+}
+
+#[derive(Debug, Default)]
+struct ScratchSpaceQuotaInner {
+    max_num_bytes_opt: Option<u64>,
+    num_bytes: AtomicU64,
+}
+
+impl ScratchSpaceQuota {
+    pub fn new(max_num_bytes: ByteSize) -> Self {
+        Self {
+            inner: Arc::new(ScratchSpaceQuotaInner {
+                max_num_bytes_opt: Some(max_num_bytes.as_u64()),
+                num_bytes: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// Quota that never rejects a write.
+    pub fn unlimited() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of bytes currently used in the scratch directories.
+    pub fn num_bytes(&self) -> ByteSize {
+        ByteSize(self.inner.num_bytes.load(Ordering::Relaxed))
+    }
+
+    pub fn max_num_bytes_opt(&self) -> Option<ByteSize> {
+        self.inner.max_num_bytes_opt.map(ByteSize)
+    }
+
+    /// Charges `num_bytes` to the quota for files written outside of a [`ControlledDirectory`],
+    /// such as downloaded splits. The bytes are released when the reservation is dropped.
+    pub fn reserve(&self, num_bytes: u64) -> io::Result<ScratchSpaceReservation> {
+        self.allocate(num_bytes)?;
+        Ok(ScratchSpaceReservation {
+            quota: self.clone(),
+            num_bytes,
+        })
+    }
+
+    fn allocate(&self, num_bytes: u64) -> io::Result<()> {
+        let Some(max_num_bytes) = self.inner.max_num_bytes_opt else {
+            self.inner.num_bytes.fetch_add(num_bytes, Ordering::Relaxed);
+            return Ok(());
+        };
+        self.inner
+            .num_bytes
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |current_num_bytes| {
+                let new_num_bytes = current_num_bytes + num_bytes;
+                (new_num_bytes <= max_num_bytes).then_some(new_num_bytes)
+            })
+            .map_err(|current_num_bytes| {
+                io::Error::new(
+                    io::ErrorKind::Other,
+                    format!(
+                        "scratch space quota exceeded: writing {} would bring the scratch space \
+                         usage to {}, above the limit of {} (see `indexer.scratch_max_bytes`)",
+                        ByteSize(num_bytes),
+                        ByteSize(current_num_bytes + num_bytes),
+                        ByteSize(max_num_bytes),
+                    ),
+                )
+            })?;
+        Ok(())
+    }
+
+    fn release(&self, num_bytes: u64) {
+        self.inner.num_bytes.fetch_sub(num_bytes, Ordering::Relaxed);
+    }
+}
+
+/// Bytes charged to a [`ScratchSpaceQuota`] until dropped.
+#[derive(Debug)]
+pub struct ScratchSpaceReservation {
+    quota: ScratchSpaceQuota,
+    num_bytes: u64,
+}
+
+impl Drop for ScratchSpaceReservation {
+    fn drop(&mut self) {
+        self.quota.release(self.num_bytes);
+    }
+}
+
+/// Tracks the bytes written in the files of a directory against a [`ScratchSpaceQuota`].
+struct ScratchSpaceUsage {
+    quota: ScratchSpaceQuota,
+    file_num_bytes: Mutex<HashMap<PathBuf, u64>>,
+}
+
+impl ScratchSpaceUsage {
+    fn new(quota: ScratchSpaceQuota) -> Self {
+        Self {
+            quota,
+            file_num_bytes: Mutex::default(),
+        }
+    }
+
+    fn allocate(&self, path: &Path, num_bytes: u64) -> io::Result<()> {
+        self.quota.allocate(num_bytes)?;
+        let mut file_num_bytes = self.file_num_bytes.lock().unwrap();
+        *file_num_bytes.entry(path.to_path_buf()).or_default() += num_bytes;
+        Ok(())
+    }
+
+    fn release(&self, path: &Path, num_bytes: u64) {
+        let mut file_num_bytes = self.file_num_bytes.lock().unwrap();
+        if let Some(num_bytes_in_file) = file_num_bytes.get_mut(path) {
+            *num_bytes_in_file -= num_bytes;
+            self.quota.release(num_bytes);
+        }
+    }
+
+    fn release_file(&self, path: &Path) {
+        let num_bytes_opt = self.file_num_bytes.lock().unwrap().remove(path);
+        if let Some(num_bytes) = num_bytes_opt {
+            self.quota.release(num_bytes);
+        }
+    }
+}
+
+impl Drop for ScratchSpaceUsage {
+    fn drop(&mut self) {
+        let file_num_bytes = self.file_num_bytes.get_mut().unwrap();
+        let num_bytes: u64 = file_num_bytes.values().sum();
+        self.quota.release(num_bytes);
+    }
+}
+
 // Wrapper to work around the orphan rule. (hence the word "Adopted").
-struct AdoptedControlledWrite(ControlledWrite<HotswappableIoControls, Box<dyn TerminatingWrite>>);
+struct AdoptedControlledWrite {
+    controlled_wrt: ControlledWrite<HotswappableIoControls, Box<dyn TerminatingWrite>>,
+    path: PathBuf,
+    scratch_space_usage: Arc<ScratchSpaceUsage>,
+}
 
 impl io::Write for AdoptedControlledWrite {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        self.0.write(buf)
+        let num_bytes = buf.len() as u64;
+        self.scratch_space_usage.allocate(&self.path, num_bytes)?;
+
+        match self.controlled_wrt.write(buf) {
+            Ok(num_bytes_written) => {
+                let num_bytes_unwritten = num_bytes - num_bytes_written as u64;
+                self.scratch_space_usage
+                    .release(&self.path, num_bytes_unwritten);
+                Ok(num_bytes_written)
+            }
+            Err(io_error) => {
+                self.scratch_space_usage.release(&self.path, num_bytes);
+                Err(io_error)
+            }
+        }
     }
 
     fn flush(&mut self) -> io::Result<()> {
-        self.0.flush()
+        self.controlled_wrt.flush()
     }
 }
 
 impl TerminatingWrite for AdoptedControlledWrite {
     #[inline]
     fn terminate_ref(&mut self, token: AntiCallToken) -> io::Result<()> {
-        let underlying_wrt = self.0.underlying_wrt();
+        let underlying_wrt = self.controlled_wrt.underlying_wrt();
         underlying_wrt.flush()?;
         underlying_wrt.terminate_ref(token)
     }
@@ -192,8 +367,11 @@ mod tests {
     fn test_records_progress_on_write() -> anyhow::Result<()> {
         let directory = RamDirectory::default();
         let io_controls = IoControls::default();
-        let controlled_directory =
-            ControlledDirectory::new(Box::new(directory), io_controls.clone());
+        let controlled_directory = ControlledDirectory::new(
+            Box::new(directory),
+            io_controls.clone(),
+            ScratchSpaceQuota::unlimited(),
+        );
         let progress = io_controls.progress().clone();
         assert!(progress.registered_activity_since_last_call());
         assert!(!progress.registered_activity_since_last_call());
@@ -229,8 +407,11 @@ mod tests {
     fn test_records_kill_switch_triggers_io_error() -> anyhow::Result<()> {
         let directory = RamDirectory::default();
         let io_controls = IoControls::default();
-        let controlled_directory =
-            ControlledDirectory::new(Box::new(directory), io_controls.clone());
+        let controlled_directory = ControlledDirectory::new(
+            Box::new(directory),
+            io_controls.clone(),
+            ScratchSpaceQuota::unlimited(),
+        );
         let mut wrt = controlled_directory.open_write(Path::new("test"))?;
         // We use a large buffer to force the buf writer to flush at least once.
         let large_buffer = vec![0u8; wrt.capacity() + 1];
@@ -241,4 +422,78 @@ mod tests {
         wrt.terminate()?;
         Ok(())
     }
+
+    #[test]
+    fn test_scratch_space_quota() -> anyhow::Result<()> {
+        let scratch_space_quota = ScratchSpaceQuota::new(ByteSize(20_000));
+        let controlled_directory = ControlledDirectory::new(
+            Box::new(RamDirectory::default()),
+            IoControls::default(),
+            scratch_space_quota.clone(),
+        );
+        assert_eq!(scratch_space_quota.num_bytes(), ByteSize(0));
+        assert_eq!(
+            scratch_space_quota.max_num_bytes_opt(),
+            Some(ByteSize(20_000))
+        );
+
+        let mut wrt = controlled_directory.open_write(Path::new("file-1"))?;
+        wrt.write_all(&[0u8; 12_000])?;
+        wrt.terminate()?;
+        assert_eq!(scratch_space_quota.num_bytes(), ByteSize(12_000));
+
+        controlled_directory.atomic_write(Path::new("meta.json"), &[0u8; 3_000])?;
+        assert_eq!(scratch_space_quota.num_bytes(), ByteSize(15_000));
+
+        // Overwriting a file replaces its previous usage.
+        controlled_directory.atomic_write(Path::new("meta.json"), &[0u8; 2_000])?;
+        assert_eq!(scratch_space_quota.num_bytes(), ByteSize(14_000));
+
+        let mut over_quota_wrt = controlled_directory.open_write(Path::new("file-2"))?;
+        let io_error = over_quota_wrt.write_all(&[0u8; 10_000]).unwrap_err();
+        assert!(io_error
+            .to_string()
+            .contains("scratch space quota exceeded"));
+        assert_eq!(scratch_space_quota.num_bytes(), ByteSize(14_000));
+
+        let io_error = controlled_directory
+            .atomic_write(Path::new("file-3"), &[0u8; 10_000])
+            .unwrap_err();
+        assert!(io_error
+            .to_string()
+            .contains("scratch space quota exceeded"));
+        assert_eq!(scratch_space_quota.num_bytes(), ByteSize(14_000));
+
+        // Deleting a file frees up its space.
+        controlled_directory.delete(Path::new("file-1"))?;
+        assert_eq!(scratch_space_quota.num_bytes(), ByteSize(2_000));
+
+        let mut wrt = controlled_directory.open_write(Path::new("file-4"))?;
+        wrt.write_all(&[0u8; 10_000])?;
+        wrt.terminate()?;
+        assert_eq!(scratch_space_quota.num_bytes(), ByteSize(12_000));
+
+        // Dropping the directory frees up the space of all its files.
+        drop(over_quota_wrt);
+        drop(wrt);
+        drop(controlled_directory);
+        assert_eq!(scratch_space_quota.num_bytes(), ByteSize(0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_scratch_space_reservation() {
+        let scratch_space_quota = ScratchSpaceQuota::new(ByteSize(20_000));
+        let reservation = scratch_space_quota.reserve(15_000).unwrap();
+        assert_eq!(scratch_space_quota.num_bytes(), ByteSize(15_000));
+
+        let io_error = scratch_space_quota.reserve(10_000).unwrap_err();
+        assert!(io_error
+            .to_string()
+            .contains("scratch space quota exceeded"));
+        assert_eq!(scratch_space_quota.num_bytes(), ByteSize(15_000));
+
+        drop(reservation);
+        assert_eq!(scratch_space_quota.num_bytes(), ByteSize(0));
+    }
 }
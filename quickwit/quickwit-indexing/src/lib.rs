@@ -33,7 +33,9 @@ pub use crate::actors::{
     IndexingError, IndexingPipeline, IndexingPipelineParams, IndexingService, PublisherType,
     Sequencer, SplitsUpdateMailbox,
 };
-pub use crate::controlled_directory::ControlledDirectory;
+pub use crate::controlled_directory::{
+    ControlledDirectory, ScratchSpaceQuota, ScratchSpaceReservation,
+};
 use crate::models::IndexingStatistics;
 pub use crate::split_store::{get_tantivy_directory_from_split_bundle, IndexingSplitStore};
 
@@ -52,7 +54,7 @@ use quickwit_proto::indexing::CpuCapacity;
 pub use test_utils::{mock_split, mock_split_meta, MockSplitBuilder, TestSandbox};
 
 use self::merge_policy::MergePolicy;
-pub use self::source::check_source_connectivity;
+pub use self::source::{check_source_connectivity, fetch_source_last_positions};
 
 #[derive(utoipa::OpenApi)]
 #[openapi(components(schemas(IndexingStatistics, PipelineMetrics, CpuCapacity)))]
@@ -27,13 +27,17 @@ use std::sync::Arc;
 pub(crate) use const_write_amplification::ConstWriteAmplificationMergePolicy;
 use itertools::Itertools;
 pub use nop_merge_policy::NopMergePolicy;
+use quickwit_common::metrics::{GaugeGuard, IntGauge};
 use quickwit_config::merge_policy_config::MergePolicyConfig;
 use quickwit_config::IndexingSettings;
 use quickwit_metastore::{SplitMaturity, SplitMetadata};
 use serde::Serialize;
 pub(crate) use stable_log_merge_policy::StableLogMergePolicy;
+use tokio::sync::OwnedSemaphorePermit;
 use tracing::{info_span, Span};
 
+use crate::metrics::INDEXER_METRICS;
+use crate::models::SplitAttrs;
 use crate::new_split_id;
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize)]
@@ -48,6 +52,24 @@ impl fmt::Display for MergeOperationType {
     }
 }
 
+/// Permit attached to a merge operation, used to bound the number of operations executing
+/// concurrently. It is released once the operation and all its clones are dropped, that is, once
+/// the merged split is published or the operation fails.
+pub struct MergePermit {
+    _semaphore_permit: OwnedSemaphorePermit,
+    _gauge_guard: GaugeGuard,
+}
+
+impl MergePermit {
+    /// Creates a permit tracked by `gauge`, which counts the permits currently held.
+    pub fn new(semaphore_permit: OwnedSemaphorePermit, gauge: &'static IntGauge) -> Self {
+        Self {
+            _semaphore_permit: semaphore_permit,
+            _gauge_guard: GaugeGuard::from_gauge(gauge),
+        }
+    }
+}
+
 #[derive(Clone, Serialize)]
 pub struct MergeOperation {
     #[serde(skip_serializing)]
@@ -55,6 +77,8 @@ pub struct MergeOperation {
     pub merge_split_id: String,
     pub splits: Vec<SplitMetadata>,
     pub operation_type: MergeOperationType,
+    #[serde(skip_serializing)]
+    permit_opt: Option<Arc<MergePermit>>,
 }
 
 impl MergeOperation {
@@ -67,6 +91,7 @@ impl MergeOperation {
             merge_split_id,
             splits,
             operation_type: MergeOperationType::Merge,
+            permit_opt: None,
         }
     }
 
@@ -78,9 +103,50 @@ impl MergeOperation {
             merge_split_id,
             splits: vec![split],
             operation_type: MergeOperationType::DeleteAndMerge,
+            permit_opt: None,
         }
     }
 
+    /// Attaches a permit to the operation, released when the operation is dropped.
+    pub fn with_permit(mut self, permit: MergePermit) -> Self {
+        self.permit_opt = Some(Arc::new(permit));
+        self
+    }
+
+    /// Updates the merge metrics once the operation has been executed and has produced the split
+    /// described by `merged_split_attrs`.
+    pub(crate) fn record_metrics(&self, merged_split_attrs: &SplitAttrs) {
+        let labels = [
+            merged_split_attrs.pipeline_id.index_uid.index_id(),
+            merged_split_attrs.pipeline_id.source_id.as_str(),
+        ];
+        let num_merged_bytes: u64 = self
+            .splits
+            .iter()
+            .map(|split| split.footer_offsets.end)
+            .sum();
+        INDEXER_METRICS
+            .merge_operations_total
+            .with_label_values(labels)
+            .inc();
+        INDEXER_METRICS
+            .merge_input_splits_total
+            .with_label_values(labels)
+            .inc_by(self.splits.len() as u64);
+        INDEXER_METRICS
+            .merge_output_splits_total
+            .with_label_values(labels)
+            .inc();
+        INDEXER_METRICS
+            .merged_bytes_total
+            .with_label_values(labels)
+            .inc_by(num_merged_bytes);
+        INDEXER_METRICS
+            .merge_write_amplification
+            .with_label_values(labels)
+            .observe(merged_split_attrs.num_merge_ops as f64);
+    }
+
     pub fn splits_as_slice(&self) -> &[SplitMetadata] {
         self.splits.as_slice()
     }
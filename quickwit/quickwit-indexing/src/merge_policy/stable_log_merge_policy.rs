@@ -369,6 +369,8 @@ mod tests {
     use std::sync::Arc;
     use std::time::Duration;
 
+    use quickwit_proto::metastore::SplitMaturityOverride;
+
     use super::*;
     use crate::merge_policy::tests::{aux_test_simulate_merge_planner_num_docs, create_splits};
 
@@ -482,6 +484,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_stable_log_merge_policy_honors_split_maturity_override() {
+        let merge_policy = StableLogMergePolicy::default();
+
+        // A young split forced to mature is not a merge candidate, which leaves too few splits for
+        // a merge.
+        let mut splits = create_splits(&merge_policy, vec![100; 10]);
+        assert!(!splits[3].is_mature(OffsetDateTime::now_utc()));
+        splits[3].maturity_override = Some(SplitMaturityOverride::ForceMature);
+        assert!(splits[3].is_mature(OffsetDateTime::now_utc()));
+
+        let merge_ops = merge_policy.operations(&mut splits);
+        assert!(merge_ops.is_empty());
+        assert_eq!(splits.len(), 10);
+
+        // An old split forced to remain immature is a merge candidate.
+        let mut splits = create_splits(&merge_policy, vec![100; 10]);
+        splits[3].create_timestamp = 0;
+        assert!(splits[3].is_mature(OffsetDateTime::now_utc()));
+
+        let merge_ops = merge_policy.operations(&mut splits.clone());
+        assert!(merge_ops.is_empty());
+
+        splits[3].maturity_override = Some(SplitMaturityOverride::ForceImmature);
+        let merge_ops = merge_policy.operations(&mut splits);
+        assert!(splits.is_empty());
+        assert_eq!(merge_ops.len(), 1);
+        assert_eq!(merge_ops[0].splits_as_slice().len(), 10);
+        assert!(merge_ops[0]
+            .splits_as_slice()
+            .iter()
+            .any(|split| split.split_id() == "split_03"));
+    }
+
     #[test]
     fn test_stable_log_merge_policy_many_splits_on_same_level() {
         let merge_policy = StableLogMergePolicy::default();
@@ -18,14 +18,23 @@
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
 use once_cell::sync::Lazy;
-use quickwit_common::metrics::{new_counter_vec, new_gauge_vec, IntCounterVec, IntGaugeVec};
+use quickwit_common::metrics::{
+    new_counter_vec, new_gauge, new_gauge_vec, new_histogram_vec, HistogramVec, IntCounterVec,
+    IntGauge, IntGaugeVec,
+};
 
 pub struct IndexerMetrics {
     pub processed_docs_total: IntCounterVec<3>,
     pub processed_bytes: IntCounterVec<3>,
     pub backpressure_micros: IntCounterVec<2>,
     pub available_concurrent_upload_permits: IntGaugeVec<1>,
+    pub running_pipelines: IntGauge,
     pub ongoing_merge_operations: IntGaugeVec<2>,
+    pub merge_operations_total: IntCounterVec<2>,
+    pub merge_input_splits_total: IntCounterVec<2>,
+    pub merge_output_splits_total: IntCounterVec<2>,
+    pub merged_bytes_total: IntCounterVec<2>,
+    pub merge_write_amplification: HistogramVec<2>,
 }
 
 impl Default for IndexerMetrics {
@@ -58,12 +67,48 @@ impl Default for IndexerMetrics {
                 "quickwit_indexing",
                 ["component"],
             ),
+            running_pipelines: new_gauge(
+                "running_pipelines",
+                "Number of indexing pipelines running on the node",
+                "quickwit_indexing",
+            ),
             ongoing_merge_operations: new_gauge_vec(
                 "ongoing_merge_operations",
                 "Number of ongoing merge operations",
                 "quickwit_indexing",
                 ["index", "source"],
             ),
+            merge_operations_total: new_counter_vec(
+                "merge_operations_total",
+                "Number of merge operations performed",
+                "quickwit_indexing",
+                ["index", "source"],
+            ),
+            merge_input_splits_total: new_counter_vec(
+                "merge_input_splits_total",
+                "Number of splits consumed by merge operations",
+                "quickwit_indexing",
+                ["index", "source"],
+            ),
+            merge_output_splits_total: new_counter_vec(
+                "merge_output_splits_total",
+                "Number of splits produced by merge operations",
+                "quickwit_indexing",
+                ["index", "source"],
+            ),
+            merged_bytes_total: new_counter_vec(
+                "merged_bytes_total",
+                "Number of bytes of the splits consumed by merge operations",
+                "quickwit_indexing",
+                ["index", "source"],
+            ),
+            merge_write_amplification: new_histogram_vec(
+                "merge_write_amplification",
+                "Number of merge operations the documents of the merged splits went through. Its \
+                 average is the write amplification incurred by merges.",
+                "quickwit_indexing",
+                ["index", "source"],
+            ),
         }
     }
 }
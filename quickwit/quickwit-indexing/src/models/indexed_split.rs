@@ -29,7 +29,7 @@ use tantivy::directory::MmapDirectory;
 use tantivy::{IndexBuilder, TrackedObject};
 use tracing::{instrument, Span};
 
-use crate::controlled_directory::ControlledDirectory;
+use crate::controlled_directory::{ControlledDirectory, ScratchSpaceQuota};
 use crate::merge_policy::MergeOperation;
 use crate::models::{PublishLock, SplitAttrs};
 use crate::new_split_id;
@@ -84,6 +84,7 @@ impl IndexedSplitBuilder {
         scratch_directory: TempDirectory,
         index_builder: IndexBuilder,
         io_controls: IoControls,
+        scratch_space_quota: ScratchSpaceQuota,
     ) -> anyhow::Result<Self> {
         // We avoid intermediary merge, and instead merge all segments in the packager.
         // The benefit is that we don't have to wait for potentially existing merges,
@@ -95,7 +96,8 @@ impl IndexedSplitBuilder {
         let mmap_directory = MmapDirectory::open(split_scratch_directory.path())?;
         let box_mmap_directory = Box::new(mmap_directory);
 
-        let controlled_directory = ControlledDirectory::new(box_mmap_directory, io_controls);
+        let controlled_directory =
+            ControlledDirectory::new(box_mmap_directory, io_controls, scratch_space_quota);
 
         let index_writer =
             index_builder.single_segment_index_writer(controlled_directory.clone(), 15_000_000)?;
@@ -172,6 +174,7 @@ pub enum CommitTrigger {
     MemoryLimit,
     NoMoreDocs,
     NumDocsLimit,
+    Schedule,
     Timeout,
 }
 
@@ -56,6 +56,8 @@ pub struct IndexingStatistics {
     // List of shard ids.
     #[schema(value_type = Vec<u64>)]
     pub shard_ids: BTreeSet<ShardId>,
+    /// Whether the pipeline is paused.
+    pub paused: bool,
 }
 
 impl IndexingStatistics {
@@ -23,3 +23,10 @@ use quickwit_metastore::SplitMetadata;
 pub struct NewSplits {
     pub new_splits: Vec<SplitMetadata>,
 }
+
+/// Splits whose metadata was updated in the metastore since the merge planner last heard of
+/// them, for instance because their maturity was overridden.
+#[derive(Clone, Debug)]
+pub struct UpdatedSplits {
+    pub updated_splits: Vec<SplitMetadata>,
+}
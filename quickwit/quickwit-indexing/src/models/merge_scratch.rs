@@ -20,6 +20,7 @@
 use quickwit_common::temp_dir::TempDirectory;
 use tantivy::{Directory, TrackedObject};
 
+use crate::controlled_directory::ScratchSpaceReservation;
 use crate::merge_policy::MergeOperation;
 
 #[derive(Debug)]
@@ -31,4 +32,6 @@ pub struct MergeScratch {
     pub merge_scratch_directory: TempDirectory,
     pub downloaded_splits_directory: TempDirectory,
     pub tantivy_dirs: Vec<Box<dyn Directory>>,
+    /// Scratch space charged for the downloaded splits, released once the merge is done.
+    pub downloaded_splits_scratch_space: Vec<ScratchSpaceReservation>,
 }
@@ -41,7 +41,7 @@ pub use indexing_service_message::{
     DetachIndexingPipeline, DetachMergePipeline, ObservePipeline, SpawnPipeline,
 };
 pub use indexing_statistics::IndexingStatistics;
-pub use merge_planner_message::NewSplits;
+pub use merge_planner_message::{NewSplits, UpdatedSplits};
 pub use merge_scratch::MergeScratch;
 pub use merge_statistics::MergeStatistics;
 pub use packaged_split::{PackagedSplit, PackagedSplitBatch};
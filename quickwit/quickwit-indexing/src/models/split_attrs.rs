@@ -109,5 +109,9 @@ pub fn create_split_metadata(
         footer_offsets,
         delete_opstamp: split_attrs.delete_opstamp,
         num_merge_ops: split_attrs.num_merge_ops,
+        split_path_prefix: None,
+        field_ranges: Default::default(),
+        field_cardinalities: Default::default(),
+        maturity_override: None,
     }
 }
@@ -76,6 +76,7 @@ impl TypedSourceFactory for IngestSourceFactory {
             max_attempts: usize::MAX,
             base_delay: Duration::from_secs(1),
             max_delay: Duration::from_secs(10 * 60), // 10 minutes
+            jitter: true,
         };
         IngestSource::try_new(runtime_args, retry_params).await
     }
@@ -363,6 +364,7 @@ impl IngestSource {
                     base_delay: Duration::from_secs(1),
                     max_delay: Duration::from_secs(10),
                     max_attempts: 5,
+                    jitter: true,
                 };
                 for num_attempts in 1..=retry_params.max_attempts {
                     let Err(error) = ingester
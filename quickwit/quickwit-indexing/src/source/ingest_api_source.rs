@@ -151,6 +151,7 @@ impl Source for IngestApiSource {
         let FetchResponse {
             first_position: first_position_opt,
             doc_batch: doc_batch_opt,
+            ..
         } = ctx
             .ask_for_res(&self.ingest_api_service, fetch_req)
             .await
@@ -244,7 +245,7 @@ mod tests {
     use quickwit_config::{
         IngestApiConfig, SourceConfig, SourceInputFormat, SourceParams, INGEST_API_SOURCE_ID,
     };
-    use quickwit_ingest::{init_ingest_api, CommitType, DocBatchBuilder, IngestRequest};
+    use quickwit_ingest::{init_ingest_api, CommitType, DocBatchBuilder, Flush, IngestRequest};
     use quickwit_metastore::checkpoint::{SourceCheckpoint, SourceCheckpointDelta};
     use quickwit_metastore::metastore_for_test;
     use quickwit_proto::types::IndexUid;
@@ -578,6 +579,83 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_ingest_api_source_with_flush() -> anyhow::Result<()> {
+        let universe = Universe::with_accelerated_time();
+        let metastore = metastore_for_test();
+        let index_id = append_random_suffix("test-ingest-api-source");
+        let index_uid = IndexUid::new_with_random_ulid(&index_id);
+        let temp_dir = tempfile::tempdir()?;
+        let queues_dir_path = temp_dir.path();
+
+        let ingest_api_service =
+            init_ingest_api(&universe, queues_dir_path, &IngestApiConfig::default()).await?;
+        let (doc_processor_mailbox, doc_processor_inbox) = universe.create_test_mailbox();
+        let source_config = make_source_config();
+        let ctx = SourceRuntimeArgs::for_test(
+            index_uid,
+            source_config,
+            metastore,
+            queues_dir_path.to_path_buf(),
+        );
+        let ingest_api_source = IngestApiSource::try_new(ctx, SourceCheckpoint::default()).await?;
+
+        let ingest_req = make_ingest_request(index_id.clone(), 2, 20_000, CommitType::Auto);
+        ingest_api_service
+            .ask_for_res(ingest_req)
+            .await
+            .map_err(|err| anyhow::anyhow!(err.to_string()))?;
+        let flush_res = ingest_api_service
+            .send_message(Flush {
+                index_id: index_id.clone(),
+            })
+            .await
+            .map_err(|err| anyhow::anyhow!(err.to_string()))?;
+
+        let ingest_api_source_actor = SourceActor {
+            source: Box::new(ingest_api_source),
+            doc_processor_mailbox,
+        };
+        let (_ingest_api_source_mailbox, ingest_api_source_handle) =
+            universe.spawn_builder().spawn(ingest_api_source_actor);
+        universe.sleep(Duration::from_secs(2)).await;
+        let counters = ingest_api_source_handle
+            .process_pending_and_observe()
+            .await
+            .state;
+        assert_eq!(
+            counters,
+            serde_json::json!({
+                "previous_offset": 40000u64,
+                "current_offset": 40000u64,
+                "num_docs_processed": 40000u64
+            })
+        );
+        // The indexer observes all the documents ingested before the flush, followed by a commit.
+        let doc_batches: Vec<RawDocBatch> = doc_processor_inbox.drain_for_test_typed();
+        let num_docs: usize = doc_batches
+            .iter()
+            .map(|doc_batch| doc_batch.docs.len())
+            .sum();
+        assert_eq!(num_docs, 40_000);
+        assert!(doc_batches.last().unwrap().force_commit);
+
+        ingest_api_service
+            .ask_for_res(SuggestTruncateRequest {
+                index_id: index_id.clone(),
+                up_to_position_included: 40000,
+            })
+            .await
+            .map_err(|err| anyhow::anyhow!(err.to_string()))?;
+        flush_res
+            .await
+            .map_err(|err| anyhow::anyhow!(err.to_string()))?
+            .map_err(|err| anyhow::anyhow!(err.to_string()))?;
+        ingest_api_source_handle.quit().await;
+        universe.assert_quit().await;
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_ingest_api_source_with_wait() -> anyhow::Result<()> {
         let universe = Universe::with_accelerated_time();
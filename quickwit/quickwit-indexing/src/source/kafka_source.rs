@@ -28,7 +28,7 @@ use bytes::Bytes;
 use itertools::Itertools;
 use oneshot;
 use quickwit_actors::{ActorExitStatus, Mailbox};
-use quickwit_config::KafkaSourceParams;
+use quickwit_config::{KafkaSourceParams, SourceStartFrom};
 use quickwit_metastore::checkpoint::{PartitionId, SourceCheckpoint};
 use quickwit_metastore::IndexMetadataResponseExt;
 use quickwit_proto::metastore::{IndexMetadataRequest, MetastoreService};
@@ -217,6 +217,8 @@ pub struct KafkaSource {
     group_id: GroupId,
     state: KafkaSourceState,
     backfill_mode_enabled: bool,
+    start_from_opt: Option<SourceStartFrom>,
+    client_config: ClientConfig,
     events_rx: mpsc::Receiver<KafkaEvent>,
     truncate_tx: watch::Sender<SourceCheckpoint>,
     poll_loop_jh: JoinHandle<()>,
@@ -243,6 +245,7 @@ impl KafkaSource {
     ) -> anyhow::Result<Self> {
         let topic = params.topic.clone();
         let backfill_mode_enabled = params.enable_backfill_mode;
+        let start_from_opt = params.start_from;
 
         let (events_tx, events_rx) = mpsc::channel(100);
         let (truncate_tx, truncate_rx) = watch::channel(SourceCheckpoint::default());
@@ -265,6 +268,7 @@ impl KafkaSource {
             source_id=%ctx.source_id(),
             topic=%topic,
             group_id=%group_id,
+            start_from=?start_from_opt,
             max_poll_interval_ms=%max_poll_interval_ms,
             session_timeout_ms=%session_timeout_ms,
             "Starting Kafka source."
@@ -282,6 +286,8 @@ impl KafkaSource {
             group_id,
             state: KafkaSourceState::default(),
             backfill_mode_enabled,
+            start_from_opt,
+            client_config,
             events_rx,
             truncate_tx,
             poll_loop_jh,
@@ -364,6 +370,7 @@ impl KafkaSource {
         self.state.num_inactive_partitions = 0;
 
         let mut next_offsets: Vec<(i32, Offset)> = Vec::with_capacity(partitions.len());
+        let mut partitions_without_checkpoint: Vec<i32> = Vec::new();
 
         for &partition in partitions {
             let partition_id = PartitionId::from(partition as i64);
@@ -374,6 +381,7 @@ impl KafkaSource {
 
             let Some(current_position) = checkpoint.position_for_partition(&partition_id).cloned()
             else {
+                partitions_without_checkpoint.push(partition);
                 continue;
             };
             let next_offset = match &current_position {
@@ -393,6 +401,16 @@ impl KafkaSource {
                 .insert(partition, current_position);
             next_offsets.push((partition, next_offset));
         }
+        // Partitions without a checkpoint start from the configured position if any, otherwise
+        // the consumer falls back to `auto.offset.reset`.
+        if let Some(start_from) = self.start_from_opt {
+            if !partitions_without_checkpoint.is_empty() {
+                let start_offsets = self
+                    .start_offsets(ctx, start_from, partitions_without_checkpoint)
+                    .await?;
+                next_offsets.extend(start_offsets);
+            }
+        }
         info!(
             index_id=%self.ctx.index_id(),
             source_id=%self.ctx.source_id(),
@@ -407,6 +425,38 @@ impl KafkaSource {
         Ok(())
     }
 
+    /// Resolves the offsets from which the partitions start when they don't have a checkpoint.
+    async fn start_offsets(
+        &self,
+        ctx: &SourceContext,
+        start_from: SourceStartFrom,
+        partitions: Vec<i32>,
+    ) -> anyhow::Result<Vec<(i32, Offset)>> {
+        let start_offset = match start_from {
+            SourceStartFrom::Beginning => Offset::Beginning,
+            SourceStartFrom::End => Offset::End,
+            SourceStartFrom::Timestamp(timestamp) => {
+                let consumer: BaseConsumer<DefaultConsumerContext> = self
+                    .client_config
+                    .create()
+                    .context("failed to create Kafka consumer")?;
+                let topic = self.topic.clone();
+                let timestamp_millis = timestamp.timestamp_millis();
+                let start_offsets = ctx
+                    .protect_future(spawn_blocking(move || {
+                        offsets_for_timestamp(&consumer, &topic, &partitions, timestamp_millis)
+                    }))
+                    .await??;
+                return Ok(start_offsets);
+            }
+        };
+        let start_offsets = partitions
+            .into_iter()
+            .map(|partition| (partition, start_offset))
+            .collect();
+        Ok(start_offsets)
+    }
+
     async fn process_revoke_partitions(
         &mut self,
         ctx: &SourceContext,
@@ -633,6 +683,34 @@ fn spawn_consumer_poll_loop(
     })
 }
 
+/// Looks up, for each partition, the offset of the first message whose timestamp is greater than or
+/// equal to `timestamp_millis`. Partitions without such a message start from the end.
+fn offsets_for_timestamp<C: ConsumerContext>(
+    consumer: &BaseConsumer<C>,
+    topic: &str,
+    partitions: &[i32],
+    timestamp_millis: i64,
+) -> anyhow::Result<Vec<(i32, Offset)>> {
+    let mut timestamps_tpl = TopicPartitionList::with_capacity(partitions.len());
+
+    for &partition in partitions {
+        timestamps_tpl.add_partition_offset(topic, partition, Offset::Offset(timestamp_millis))?;
+    }
+    let offsets_tpl = consumer
+        .offsets_for_times(timestamps_tpl, Timeout::After(Duration::from_secs(10)))
+        .with_context(|| {
+            format!(
+                "failed to look up offsets for timestamp `{timestamp_millis}` in topic `{topic}`"
+            )
+        })?;
+    let offsets = offsets_tpl
+        .elements()
+        .iter()
+        .map(|tple| (tple.partition(), tple.offset()))
+        .collect();
+    Ok(offsets)
+}
+
 /// Returns the preceding `Position` for the offset.
 fn previous_position_for_offset(offset: i64) -> Position {
     if offset == 0 {
@@ -672,6 +750,51 @@ pub(super) async fn check_connectivity(params: KafkaSourceParams) -> anyhow::Res
     Ok(())
 }
 
+/// Returns the position of the last record of each non-empty partition of the topic.
+pub(super) async fn fetch_last_positions(
+    params: KafkaSourceParams,
+) -> anyhow::Result<SourceCheckpoint> {
+    let mut client_config = parse_client_params(params.client_params)?;
+
+    let consumer: BaseConsumer<DefaultConsumerContext> = client_config
+        .set("group.id", "quickwit-ingest-status".to_string())
+        .set_log_level(RDKafkaLogLevel::Error)
+        .create()?;
+
+    let topic = params.topic;
+    let timeout = Timeout::After(Duration::from_secs(5));
+    spawn_blocking(move || {
+        let cluster_metadata = consumer
+            .fetch_metadata(Some(&topic), timeout)
+            .with_context(|| format!("failed to fetch metadata for topic `{topic}`"))?;
+
+        let Some(topic_metadata) = cluster_metadata.topics().first() else {
+            bail!("topic `{topic}` does not exist");
+        };
+        let mut last_positions = SourceCheckpoint::default();
+
+        for partition_metadata in topic_metadata.partitions() {
+            let partition = partition_metadata.id();
+            let (_low_watermark, high_watermark) = consumer
+                .fetch_watermarks(&topic, partition, timeout)
+                .with_context(|| {
+                    format!(
+                        "failed to fetch watermarks for partition `{partition}` of topic `{topic}`"
+                    )
+                })?;
+            // The high watermark is the offset of the next record written to the partition.
+            if high_watermark > 0 {
+                last_positions.add_partition(
+                    PartitionId::from(partition as i64),
+                    Position::offset(high_watermark - 1),
+                );
+            }
+        }
+        Ok(last_positions)
+    })
+    .await?
+}
+
 /// Creates a new `KafkaSourceConsumer`.
 fn create_consumer(
     index_uid: &IndexUid,
@@ -779,6 +902,7 @@ fn message_payload_to_doc(message: &BorrowedMessage) -> Option<Bytes> {
 mod kafka_broker_tests {
     use std::num::NonZeroUsize;
     use std::path::PathBuf;
+    use std::str::FromStr;
 
     use quickwit_actors::{ActorContext, Universe};
     use quickwit_common::rand::append_random_suffix;
@@ -796,6 +920,8 @@ mod kafka_broker_tests {
     use rdkafka::client::DefaultClientContext;
     use rdkafka::message::ToBytes;
     use rdkafka::producer::{FutureProducer, FutureRecord};
+    use time::format_description::well_known::Rfc3339;
+    use time::OffsetDateTime;
     use tokio::sync::watch;
 
     use super::*;
@@ -912,6 +1038,7 @@ mod kafka_broker_tests {
                     "bootstrap.servers": "localhost:9092",
                 }),
                 enable_backfill_mode: true,
+                start_from: None,
             }),
             transform_config: None,
             input_format: SourceInputFormat::Json,
@@ -1180,6 +1307,87 @@ mod kafka_broker_tests {
         assert_eq!(assignment, &[(2, Offset::Offset(43))])
     }
 
+    #[tokio::test]
+    async fn test_kafka_source_process_assign_partitions_start_from_timestamp() {
+        let admin_client = create_admin_client();
+        let topic = append_random_suffix("test-kafka-source--start-from-timestamp--topic");
+        create_topic(&admin_client, &topic, 2).await.unwrap();
+
+        // Partition 0 holds 3 messages produced before the start timestamp and 2 messages
+        // produced at the start timestamp. Partition 1 is empty.
+        let start_timestamp_secs = OffsetDateTime::now_utc().unix_timestamp() - 60;
+        let start_timestamp_millis = start_timestamp_secs * 1_000;
+        let message_fn = |id: i32| format!("Message #{id:0>3}");
+        populate_topic(
+            &topic,
+            3,
+            &key_fn,
+            &message_fn,
+            Some(0),
+            Some(start_timestamp_millis - 60_000),
+        )
+        .await
+        .unwrap();
+        populate_topic(
+            &topic,
+            2,
+            &key_fn,
+            &message_fn,
+            Some(0),
+            Some(start_timestamp_millis),
+        )
+        .await
+        .unwrap();
+
+        let metastore = metastore_for_test();
+        let index_id = append_random_suffix("test-kafka-source--start-from-timestamp--index");
+        let (source_id, mut source_config) = get_source_config(&topic, "earliest");
+        let index_uid = setup_index(metastore.clone(), &index_id, &source_id, &[]).await;
+
+        let SourceParams::Kafka(params) = &mut source_config.source_params else {
+            panic!(
+                "Expected Kafka source params, got {:?}.",
+                source_config.source_params
+            );
+        };
+        let start_timestamp = OffsetDateTime::from_unix_timestamp(start_timestamp_secs)
+            .unwrap()
+            .format(&Rfc3339)
+            .unwrap();
+        params.start_from =
+            Some(SourceStartFrom::from_str(&format!("timestamp({start_timestamp})")).unwrap());
+        let params = params.clone();
+
+        let ctx = SourceRuntimeArgs::for_test(
+            index_uid,
+            source_config,
+            metastore,
+            PathBuf::from("./queues"),
+        );
+        let ignored_checkpoint = SourceCheckpoint::default();
+        let mut kafka_source = KafkaSource::try_new(ctx, params, ignored_checkpoint)
+            .await
+            .unwrap();
+
+        let universe = Universe::with_accelerated_time();
+        let (source_mailbox, _source_inbox) = universe.create_test_mailbox();
+        let (observable_state_tx, _observable_state_rx) = watch::channel(json!({}));
+        let ctx: ActorContext<SourceActor> =
+            ActorContext::for_test(&universe, source_mailbox, observable_state_tx);
+        let (assignment_tx, assignment_rx) = oneshot::channel();
+
+        kafka_source
+            .process_assign_partitions(&ctx, &[0, 1], assignment_tx)
+            .await
+            .unwrap();
+
+        assert!(kafka_source.state.current_positions.is_empty());
+
+        let mut assignment = assignment_rx.await.unwrap();
+        assignment.sort_by_key(|(partition, _offset)| *partition);
+        assert_eq!(assignment, &[(0, Offset::Offset(3)), (1, Offset::End)]);
+    }
+
     #[tokio::test]
     async fn test_kafka_source_process_revoke_partitions() {
         let admin_client = create_admin_client();
@@ -1617,6 +1825,7 @@ mod kafka_broker_tests {
             client_log_level: None,
             client_params: json!({ "bootstrap.servers": bootstrap_servers }),
             enable_backfill_mode: true,
+            start_from: None,
         })
         .await
         .unwrap();
@@ -1628,6 +1837,7 @@ mod kafka_broker_tests {
             client_log_level: None,
             client_params: json!({ "bootstrap.servers": bootstrap_servers }),
             enable_backfill_mode: true,
+            start_from: None,
         })
         .await
         .unwrap_err();
@@ -1640,6 +1850,7 @@ mod kafka_broker_tests {
                 "bootstrap.servers": "192.0.2.10:9092"
             }),
             enable_backfill_mode: true,
+            start_from: None,
         })
         .await
         .unwrap_err();
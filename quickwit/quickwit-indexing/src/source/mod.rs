@@ -349,6 +349,13 @@ impl Handler<Loop> for SourceActor {
     type Reply = ();
 
     async fn handle(&mut self, _message: Loop, ctx: &SourceContext) -> Result<(), ActorExitStatus> {
+        // Scheduled loop messages are delivered with high priority, so they reach the source even
+        // when it is paused. We defer them to the low priority channel, which is only processed
+        // once the source is resumed.
+        if ctx.is_paused() {
+            ctx.send_self_message(Loop).await?;
+            return Ok(());
+        }
         let wait_for = self
             .source
             .emit_batches(&self.doc_processor_mailbox, ctx)
@@ -450,6 +457,27 @@ pub async fn check_source_connectivity(
     }
 }
 
+/// Returns the position of the last record of each partition of the source, or `None` if the source
+/// does not expose it.
+pub async fn fetch_source_last_positions(
+    source_config: &SourceConfig,
+) -> anyhow::Result<Option<SourceCheckpoint>> {
+    match &source_config.source_params {
+        #[allow(unused_variables)]
+        SourceParams::Kafka(params) => {
+            #[cfg(not(feature = "kafka"))]
+            anyhow::bail!("Quickwit binary was not compiled with the `kafka` feature");
+
+            #[cfg(feature = "kafka")]
+            {
+                let last_positions = kafka_source::fetch_last_positions(params.clone()).await?;
+                Ok(Some(last_positions))
+            }
+        }
+        _ => Ok(None),
+    }
+}
+
 #[derive(Debug)]
 pub struct SuggestTruncate(pub SourceCheckpoint);
 
@@ -19,15 +19,17 @@
 
 #[cfg(any(test, feature = "testsuite"))]
 use std::collections::HashMap;
-use std::path::{Path, PathBuf};
+use std::ops::RangeInclusive;
+use std::path::Path;
 use std::sync::Arc;
 use std::time::Instant;
 
-use anyhow::Context;
+use anyhow::{bail, Context};
 #[cfg(any(test, feature = "testsuite"))]
 use bytesize::ByteSize;
 use quickwit_common::io::{IoControls, IoControlsAccess};
 use quickwit_common::uri::Uri;
+use quickwit_config::render_split_path_template;
 use quickwit_metastore::SplitMetadata;
 use quickwit_storage::{PutPayload, Storage, StorageResult};
 use tantivy::directory::{Advice, MmapDirectory};
@@ -36,6 +38,7 @@ use time::OffsetDateTime;
 use tracing::{info, info_span, instrument, Instrument};
 
 use super::LocalSplitStore;
+use crate::controlled_directory::{ScratchSpaceQuota, ScratchSpaceReservation};
 use crate::get_tantivy_directory_from_split_bundle;
 
 /// IndexingSplitStore is a wrapper around a regular `Storage` to upload and
@@ -66,16 +69,24 @@ struct InnerIndexingSplitStore {
     /// The remote storage.
     remote_storage: Arc<dyn Storage>,
     local_split_store: Arc<LocalSplitStore>,
+    /// Template of the prefix under which new splits are stored in the remote storage.
+    split_path_template_opt: Option<String>,
 }
 
 impl IndexingSplitStore {
     /// Creates an instance of [`IndexingSplitStore`]
     ///
-    /// It needs the remote storage to work with.
-    pub fn new(remote_storage: Arc<dyn Storage>, local_split_store: Arc<LocalSplitStore>) -> Self {
+    /// It needs the remote storage to work with. New splits are stored under the prefix rendered
+    /// from `split_path_template_opt`, or at the root of the remote storage if it is `None`.
+    pub fn new(
+        remote_storage: Arc<dyn Storage>,
+        local_split_store: Arc<LocalSplitStore>,
+        split_path_template_opt: Option<String>,
+    ) -> Self {
         let inner = InnerIndexingSplitStore {
             remote_storage,
             local_split_store,
+            split_path_template_opt,
         };
         Self {
             inner: Arc::new(inner),
@@ -88,6 +99,7 @@ impl IndexingSplitStore {
         let inner = InnerIndexingSplitStore {
             remote_storage,
             local_split_store: Arc::new(LocalSplitStore::no_caching()),
+            split_path_template_opt: None,
         };
         IndexingSplitStore {
             inner: Arc::new(inner),
@@ -98,8 +110,14 @@ impl IndexingSplitStore {
         self.inner.remote_storage.uri()
     }
 
-    fn split_path(&self, split_id: &str) -> PathBuf {
-        PathBuf::from(quickwit_common::split_file(split_id))
+    /// Returns the prefix under which a new split covering `time_range` should be stored, or
+    /// `None` if it should be stored at the root of the remote storage.
+    pub fn split_path_prefix(&self, time_range: Option<&RangeInclusive<i64>>) -> Option<String> {
+        let split_path_template = self.inner.split_path_template_opt.as_ref()?;
+        let start_timestamp_secs = *time_range?.start();
+        let split_path_prefix =
+            render_split_path_template(split_path_template, start_timestamp_secs);
+        Some(split_path_prefix)
     }
 
     /// Stores a split.
@@ -111,6 +129,9 @@ impl IndexingSplitStore {
     /// the store).
     /// In other words, after calling this function the file will not be available
     /// at `split_folder` anymore.
+    ///
+    /// The split file is written only if it does not exist in the remote storage yet, so that a
+    /// split uploaded concurrently by another indexer is never overwritten.
     #[instrument("store_split", skip_all)]
     pub async fn store_split(
         &self,
@@ -121,11 +142,12 @@ impl IndexingSplitStore {
         let start = Instant::now();
         let split_num_bytes = put_payload.len();
 
-        let key = self.split_path(split.split_id());
+        let key = split.split_path();
         let is_mature = split.is_mature(OffsetDateTime::now_utc());
-        self.inner
+        let is_written = self
+            .inner
             .remote_storage
-            .put(&key, put_payload)
+            .put_if_absent(&key, put_payload)
             .instrument(info_span!("store_split_in_remote_storage", split=?split.split_id(), is_mature=is_mature, num_bytes=split_num_bytes))
             .await
             .with_context(|| {
@@ -135,7 +157,13 @@ impl IndexingSplitStore {
                     self.inner.remote_storage.uri()
                 )
             })?;
-
+        if !is_written {
+            bail!(
+                "failed uploading key {} in bucket {}: the split file already exists",
+                key.display(),
+                self.inner.remote_storage.uri()
+            );
+        }
         let elapsed_secs = start.elapsed().as_secs_f32();
         let split_size_in_megabytes = split_num_bytes as f32 / 1_000_000f32;
         let throughput_mb_s = split_size_in_megabytes / elapsed_secs;
@@ -180,18 +208,23 @@ impl IndexingSplitStore {
     ///
     /// As we fetch the split, we optimistically assume that this is for a merge
     /// operation that will be successful and we remove the split from the cache.
-    #[instrument(skip(self, output_dir_path, io_controls), fields(cache_hit))]
+    ///
+    /// The size of the split is charged to the scratch space quota for as long as the returned
+    /// reservation is alive.
+    #[instrument(skip_all, fields(split_id = split.split_id(), cache_hit))]
     pub async fn fetch_and_open_split(
         &self,
-        split_id: &str,
+        split: &SplitMetadata,
         output_dir_path: &Path,
         io_controls: &IoControls,
-    ) -> StorageResult<Box<dyn Directory>> {
-        let path = PathBuf::from(quickwit_common::split_file(split_id));
+        scratch_space_quota: &ScratchSpaceQuota,
+    ) -> StorageResult<(Box<dyn Directory>, ScratchSpaceReservation)> {
+        let scratch_space_reservation = scratch_space_quota.reserve(split.footer_offsets.end)?;
+        let path = split.split_path();
         if let Some(split_path) = self
             .inner
             .local_split_store
-            .get_cached_split(split_id, output_dir_path)
+            .get_cached_split(split.split_id(), output_dir_path)
             .await?
         {
             tracing::Span::current().record("cache_hit", true);
@@ -199,11 +232,11 @@ impl IndexingSplitStore {
                 split_path,
                 Advice::Sequential,
             )?);
-            return Ok(mmap_directory);
+            return Ok((mmap_directory, scratch_space_reservation));
         } else {
             tracing::Span::current().record("cache_hit", false);
         }
-        let dest_filepath = output_dir_path.join(&path);
+        let dest_filepath = output_dir_path.join(quickwit_common::split_file(split.split_id()));
         let dest_file = tokio::fs::File::create(&dest_filepath).await?;
         let mut dest_file_with_write_limit = io_controls.clone().wrap_write(dest_file);
         self.inner
@@ -211,7 +244,8 @@ impl IndexingSplitStore {
             .copy_to(&path, &mut dest_file_with_write_limit)
             .instrument(info_span!("fetch_split_from_remote_storage", path=?path))
             .await?;
-        get_tantivy_directory_from_split_bundle(&dest_filepath)
+        let tantivy_directory = get_tantivy_directory_from_split_bundle(&dest_filepath)?;
+        Ok((tantivy_directory, scratch_space_reservation))
     }
 
     /// Takes a snapshot of the cache view (only used for testing).
@@ -223,13 +257,14 @@ impl IndexingSplitStore {
 
 #[cfg(test)]
 mod tests {
+    use std::path::Path;
     use std::sync::Arc;
     use std::time::Duration;
 
     use bytesize::ByteSize;
     use quickwit_common::io::IoControls;
     use quickwit_metastore::{SplitMaturity, SplitMetadata};
-    use quickwit_storage::{RamStorage, SplitPayloadBuilder};
+    use quickwit_storage::{RamStorage, SplitPayloadBuilder, Storage};
     use tempfile::tempdir;
     use time::OffsetDateTime;
     use tokio::fs;
@@ -260,7 +295,8 @@ mod tests {
         )
         .await?;
         let remote_storage = Arc::new(RamStorage::default());
-        let split_store = IndexingSplitStore::new(remote_storage, Arc::new(local_split_store));
+        let split_store =
+            IndexingSplitStore::new(remote_storage, Arc::new(local_split_store), None);
 
         let split_id1 = Ulid::new().to_string();
         let split_id2 = Ulid::new().to_string();
@@ -326,7 +362,8 @@ mod tests {
         .await?;
 
         let remote_storage = Arc::new(RamStorage::default());
-        let split_store = IndexingSplitStore::new(remote_storage, Arc::new(local_split_store));
+        let split_store =
+            IndexingSplitStore::new(remote_storage, Arc::new(local_split_store), None);
 
         let split_id1 = Ulid::new().to_string();
         let split_id2 = Ulid::new().to_string();
@@ -393,13 +430,115 @@ mod tests {
             let io_controls = IoControls::default();
             // get from cache
             let _split1 = split_store
-                .fetch_and_open_split(&split_id1, output.path(), &io_controls)
+                .fetch_and_open_split(
+                    &create_test_split_metadata(&split_id1),
+                    output.path(),
+                    &io_controls,
+                    &ScratchSpaceQuota::unlimited(),
+                )
                 .await?;
             // get from remote storage
             let _split2 = split_store
-                .fetch_and_open_split(&split_id2, output.path(), &io_controls)
+                .fetch_and_open_split(
+                    &create_test_split_metadata(&split_id2),
+                    output.path(),
+                    &io_controls,
+                    &ScratchSpaceQuota::unlimited(),
+                )
                 .await?;
         }
         Ok(())
     }
+    #[tokio::test]
+    async fn test_store_split_under_split_path_prefix() -> anyhow::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let remote_storage = Arc::new(RamStorage::default());
+        let split_store = IndexingSplitStore::new(
+            remote_storage.clone(),
+            Arc::new(LocalSplitStore::no_caching()),
+            Some("{year}/{month}/{day}".to_string()),
+        );
+        assert!(split_store.split_path_prefix(None).is_none());
+
+        let split_id = Ulid::new().to_string();
+        let mut split_metadata = create_test_split_metadata(&split_id);
+        // 2024-03-05T07:08:09Z to 2024-03-06T00:00:00Z
+        split_metadata.time_range = Some(1_709_622_489..=1_709_683_200);
+        split_metadata.split_path_prefix =
+            split_store.split_path_prefix(split_metadata.time_range.as_ref());
+        assert_eq!(
+            split_metadata.split_path_prefix.as_deref(),
+            Some("2024/03/05")
+        );
+
+        let split_path = temp_dir.path().join(&split_id);
+        fs::create_dir_all(&split_path).await?;
+        split_store
+            .store_split(
+                &split_metadata,
+                &split_path,
+                Box::new(SplitPayloadBuilder::get_split_payload(
+                    &[],
+                    &[],
+                    &[5, 5, 5],
+                )?),
+            )
+            .await?;
+        let expected_split_path = format!("2024/03/05/{split_id}.split");
+        assert!(
+            remote_storage
+                .exists(Path::new(&expected_split_path))
+                .await?
+        );
+        assert!(
+            !remote_storage
+                .exists(Path::new(&format!("{split_id}.split")))
+                .await?
+        );
+
+        let output = tempfile::tempdir()?;
+        split_store
+            .fetch_and_open_split(
+                &split_metadata,
+                output.path(),
+                &IoControls::default(),
+                &ScratchSpaceQuota::unlimited(),
+            )
+            .await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_store_split_does_not_overwrite_existing_split() -> anyhow::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let remote_storage = Arc::new(RamStorage::default());
+        let split_store =
+            IndexingSplitStore::create_without_local_store_for_test(remote_storage.clone());
+        let split_id = Ulid::new().to_string();
+        let split_metadata = create_test_split_metadata(&split_id);
+        let split_key = format!("{split_id}.split");
+        remote_storage
+            .put(Path::new(&split_key), Box::new(b"existing".to_vec()))
+            .await?;
+
+        let split_path = temp_dir.path().join(&split_id);
+        fs::create_dir_all(&split_path).await?;
+        let error = split_store
+            .store_split(
+                &split_metadata,
+                &split_path,
+                Box::new(SplitPayloadBuilder::get_split_payload(
+                    &[],
+                    &[],
+                    &[5, 5, 5],
+                )?),
+            )
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("already exists"));
+
+        let split_bytes = remote_storage.get_all(Path::new(&split_key)).await?;
+        assert_eq!(split_bytes.as_slice(), b"existing");
+        Ok(())
+    }
 }
@@ -22,7 +22,17 @@ use quickwit_codegen::{Codegen, ProstConfig};
 fn main() {
     // Legacy ingest codegen
     let mut prost_config = ProstConfig::default();
-    prost_config.bytes(["DocBatch.doc_buffer"]);
+    prost_config
+        .bytes(["DocBatch.doc_buffer"])
+        .field_attribute(
+            "IngestResponse.num_docs_with_replaced_utf8",
+            "#[serde(default)]",
+        )
+        .field_attribute(
+            "IngestResponse.num_docs_skipped_invalid_utf8",
+            "#[serde(default)]",
+        )
+        .field_attribute("IngestResponse.num_dropped_fields", "#[serde(default)]");
 
     Codegen::builder()
         .with_protos(&["src/ingest_service.proto"])
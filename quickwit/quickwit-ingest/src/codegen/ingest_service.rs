@@ -41,6 +41,19 @@ pub struct IngestRequest {
 pub struct IngestResponse {
     #[prost(uint64, tag = "1")]
     pub num_docs_for_processing: u64,
+    /// Number of documents whose invalid UTF-8 sequences were replaced with `U+FFFD`.
+    #[prost(uint64, tag = "2")]
+    #[serde(default)]
+    pub num_docs_with_replaced_utf8: u64,
+    /// Number of documents skipped because they contained invalid UTF-8.
+    #[prost(uint64, tag = "3")]
+    #[serde(default)]
+    pub num_docs_skipped_invalid_utf8: u64,
+    /// Number of fields that will be dropped from the documents because their value cannot be
+    /// coerced to their mapped type (`on_field_error: skip`).
+    #[prost(uint64, tag = "4")]
+    #[serde(default)]
+    pub num_dropped_fields: u64,
 }
 /// Fetch messages with position strictly after `start_after`.
 #[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
@@ -62,6 +75,12 @@ pub struct FetchResponse {
     pub first_position: ::core::option::Option<u64>,
     #[prost(message, optional, tag = "2")]
     pub doc_batch: ::core::option::Option<DocBatch>,
+    /// Position of the last record of the queue. Only set for tail requests.
+    #[prost(uint64, optional, tag = "3")]
+    pub last_position: ::core::option::Option<u64>,
+    /// ID of the partition the queue belongs to. Only set for tail requests.
+    #[prost(string, optional, tag = "4")]
+    pub partition_id: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -103,6 +122,10 @@ pub struct SuggestTruncateRequest {
 pub struct TailRequest {
     #[prost(string, tag = "1")]
     pub index_id: ::prost::alloc::string::String,
+    /// When set, the documents are not fetched and only the position of the
+    /// last record of the queue and the partition ID are returned.
+    #[prost(bool, tag = "2")]
+    pub positions_only: bool,
 }
 #[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
 #[allow(clippy::derive_partial_eq_without_eq)]
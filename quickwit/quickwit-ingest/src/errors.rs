@@ -23,11 +23,14 @@ use mrecordlog::error::*;
 use quickwit_actors::AskError;
 use quickwit_common::tower::BufferError;
 use quickwit_proto::ingest::IngestV2Error;
+use quickwit_proto::metastore::MetastoreError;
 use quickwit_proto::{tonic, ServiceError, ServiceErrorCode};
 use serde::Serialize;
 
 #[derive(Debug, Clone, thiserror::Error, Serialize)]
 pub enum IngestServiceError {
+    #[error("bad request: {0}")]
+    BadRequest(String),
     #[error("data corruption: {0}")]
     Corruption(String),
     #[error("index `{index_id}` already exists")]
@@ -88,6 +91,16 @@ impl From<IngestV2Error> for IngestServiceError {
     }
 }
 
+impl From<MetastoreError> for IngestServiceError {
+    fn from(error: MetastoreError) -> Self {
+        match error {
+            MetastoreError::InvalidArgument { message } => IngestServiceError::BadRequest(message),
+            MetastoreError::Unavailable(_) => IngestServiceError::Unavailable,
+            _ => IngestServiceError::Internal(error.to_string()),
+        }
+    }
+}
+
 impl From<tonic::Status> for IngestServiceError {
     fn from(status: tonic::Status) -> Self {
         // TODO: Use status.details() #2859.
@@ -111,6 +124,7 @@ impl From<tonic::Status> for IngestServiceError {
 impl ServiceError for IngestServiceError {
     fn error_code(&self) -> ServiceErrorCode {
         match self {
+            IngestServiceError::BadRequest(_) => ServiceErrorCode::BadRequest,
             IngestServiceError::Corruption(_) => ServiceErrorCode::Internal,
             IngestServiceError::IndexAlreadyExists { .. } => ServiceErrorCode::BadRequest,
             IngestServiceError::IndexNotFound { .. } => ServiceErrorCode::NotFound,
@@ -136,6 +150,7 @@ impl From<CorruptedKey> for IngestServiceError {
 impl From<IngestServiceError> for tonic::Status {
     fn from(error: IngestServiceError) -> tonic::Status {
         let code = match &error {
+            IngestServiceError::BadRequest(_) => tonic::Code::InvalidArgument,
             IngestServiceError::Corruption { .. } => tonic::Code::DataLoss,
             IngestServiceError::IndexAlreadyExists { .. } => tonic::Code::AlreadyExists,
             IngestServiceError::IndexNotFound { .. } => tonic::Code::NotFound,
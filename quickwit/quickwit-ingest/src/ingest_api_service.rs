@@ -54,6 +54,9 @@ pub struct IngestApiService {
     memory_limit: usize,
     disk_limit: usize,
     memory_capacity: MemoryCapacity,
+    // Tracks the disk space left for the queues. It is not used to reserve capacity, only to
+    // expose the disk usage ratio to the ingest rate modulator.
+    disk_capacity: MemoryCapacity,
     notifications: Notifications,
 }
 
@@ -101,6 +104,8 @@ impl IngestApiService {
         let queues = Queues::open(queues_dir_path).await?;
         let partition_id = get_or_initialize_partition_id(queues_dir_path).await?;
         let memory_capacity = MemoryCapacity::new(memory_limit);
+        let disk_capacity = MemoryCapacity::new(disk_limit);
+        disk_capacity.reset_capacity(disk_limit.saturating_sub(queues.disk_usage()));
         let notifications = Notifications::new();
         info!(ingest_partition_id=%partition_id, "Ingest API partition id");
         Ok(Self {
@@ -109,6 +114,7 @@ impl IngestApiService {
             memory_limit,
             disk_limit,
             memory_capacity,
+            disk_capacity,
             notifications,
         })
     }
@@ -208,15 +214,64 @@ impl IngestApiService {
                 .ingested_num_docs
                 .inc_by(batch_num_docs as u64);
         }
+        self.update_disk_capacity();
         // TODO we could fsync here and disable autosync to have better i/o perfs.
         Ok((
             IngestResponse {
                 num_docs_for_processing: num_docs as u64,
+                ..Default::default()
             },
             notifications,
         ))
     }
 
+    async fn flush(
+        &mut self,
+        request: Flush,
+        reply: impl FnOnce(crate::Result<()>) + Send + Sync + 'static,
+        ctx: &ActorContext<Self>,
+    ) -> Result<(), ActorExitStatus> {
+        match self.flush_inner(&request.index_id, ctx).await {
+            Ok(Some(commit_position)) => {
+                self.notifications
+                    .register(vec![(request.index_id, commit_position)], move || {
+                        reply(Ok(()));
+                    })
+                    .await;
+            }
+            Ok(None) => reply(Ok(())),
+            Err(error) => reply(Err(error)),
+        }
+        Ok(())
+    }
+
+    /// Appends a commit command to the queue if it holds records that have not been indexed yet
+    /// and returns its position.
+    async fn flush_inner(
+        &mut self,
+        index_id: &str,
+        ctx: &ActorContext<Self>,
+    ) -> crate::Result<Option<u64>> {
+        if !self.queues.queue_exists(index_id) {
+            return Err(IngestServiceError::IndexNotFound {
+                index_id: index_id.to_string(),
+            });
+        }
+        if self.queues.last_position(index_id)?.is_none() {
+            return Ok(None);
+        }
+        let commit_position_opt = self
+            .queues
+            .append_batch(
+                index_id,
+                iter::once(DocCommand::Commit::<Bytes>.into_buf()),
+                ctx,
+            )
+            .await?;
+        self.update_disk_capacity();
+        Ok(commit_position_opt)
+    }
+
     fn fetch(&mut self, fetch_req: FetchRequest) -> crate::Result<FetchResponse> {
         let num_bytes_limit_opt: Option<usize> = fetch_req
             .num_bytes_limit
@@ -243,9 +298,16 @@ impl IngestApiService {
         let memory_usage = self.queues.memory_usage();
         let new_capacity = self.memory_limit - memory_usage;
         self.memory_capacity.reset_capacity(new_capacity);
+        self.update_disk_capacity();
 
         Ok(())
     }
+
+    fn update_disk_capacity(&self) {
+        let disk_usage = self.queues.disk_usage();
+        let new_capacity = self.disk_limit.saturating_sub(disk_usage);
+        self.disk_capacity.reset_capacity(new_capacity);
+    }
 }
 
 #[async_trait]
@@ -264,6 +326,16 @@ impl Actor for IngestApiService {
     }
 }
 
+/// Forces the documents buffered in the queue of an index to be committed by the indexer,
+/// regardless of the commit policy of the index.
+///
+/// The reply is sent once the documents ingested before the flush have been indexed and
+/// published, or right away if the queue does not hold any pending documents.
+#[derive(Debug)]
+pub struct Flush {
+    pub index_id: String,
+}
+
 #[derive(Debug)]
 pub struct GetPartitionId;
 
@@ -296,6 +368,22 @@ impl Handler<GetMemoryCapacity> for IngestApiService {
     }
 }
 
+#[derive(Debug)]
+pub struct GetDiskCapacity;
+
+#[async_trait]
+impl Handler<GetDiskCapacity> for IngestApiService {
+    type Reply = MemoryCapacity;
+
+    async fn handle(
+        &mut self,
+        _request: GetDiskCapacity,
+        _ctx: &ActorContext<Self>,
+    ) -> Result<Self::Reply, ActorExitStatus> {
+        Ok(self.disk_capacity.clone())
+    }
+}
+
 #[async_trait]
 impl Handler<CreateQueueRequest> for IngestApiService {
     type Reply = crate::Result<()>;
@@ -355,6 +443,19 @@ impl DeferableReplyHandler<IngestRequest> for IngestApiService {
     }
 }
 
+#[async_trait]
+impl DeferableReplyHandler<Flush> for IngestApiService {
+    type Reply = crate::Result<()>;
+    async fn handle_message(
+        &mut self,
+        flush: Flush,
+        reply: impl FnOnce(Self::Reply) + Send + Sync + 'static,
+        ctx: &ActorContext<Self>,
+    ) -> Result<(), ActorExitStatus> {
+        self.flush(flush, reply, ctx).await
+    }
+}
+
 #[async_trait]
 impl Handler<FetchRequest> for IngestApiService {
     type Reply = crate::Result<FetchResponse>;
@@ -375,7 +476,16 @@ impl Handler<TailRequest> for IngestApiService {
         request: TailRequest,
         _ctx: &ActorContext<Self>,
     ) -> Result<Self::Reply, ActorExitStatus> {
-        Ok(self.queues.tail(&request.index_id))
+        let tail_res = if request.positions_only {
+            self.queues.tail_positions(&request.index_id)
+        } else {
+            self.queues.tail(&request.index_id)
+        };
+        let tail_res = tail_res.map(|mut fetch_response| {
+            fetch_response.partition_id = Some(self.partition_id.clone());
+            fetch_response
+        });
+        Ok(tail_res)
     }
 }
 
@@ -547,4 +657,82 @@ mod tests {
         universe.assert_quit().await;
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_ingest_api_service_flush() -> anyhow::Result<()> {
+        let universe = Universe::with_accelerated_time();
+        let temp_dir = tempfile::tempdir()?;
+        let queues_dir_path = temp_dir.path();
+
+        let ingest_api_service =
+            init_ingest_api(&universe, queues_dir_path, &IngestApiConfig::default()).await?;
+
+        let flush_error = ingest_api_service
+            .ask(Flush {
+                index_id: "index-1".to_string(),
+            })
+            .await
+            .unwrap()
+            .unwrap_err();
+        assert!(matches!(
+            flush_error,
+            IngestServiceError::IndexNotFound { .. }
+        ));
+
+        let create_queue_req = CreateQueueIfNotExistsRequest {
+            queue_id: "index-1".to_string(),
+        };
+        ingest_api_service.ask_for_res(create_queue_req).await?;
+
+        // Flushing an empty queue returns right away.
+        ingest_api_service
+            .ask(Flush {
+                index_id: "index-1".to_string(),
+            })
+            .await
+            .unwrap()
+            .unwrap();
+
+        let mut batch = DocBatchBuilder::new("index-1".to_string());
+        batch.ingest_doc(Bytes::from_static(b"Test1"));
+        batch.ingest_doc(Bytes::from_static(b"Test2"));
+
+        let ingest_request = IngestRequest {
+            doc_batches: vec![batch.build()],
+            commit: CommitType::Auto.into(),
+        };
+        ingest_api_service.ask_for_res(ingest_request).await?;
+
+        let flush_response = ingest_api_service
+            .send_message(Flush {
+                index_id: "index-1".to_string(),
+            })
+            .await
+            .unwrap();
+        universe.sleep(Duration::from_secs(2)).await;
+
+        let fetch_request = FetchRequest {
+            index_id: "index-1".to_string(),
+            start_after: None,
+            num_bytes_limit: None,
+        };
+        let fetch_response = ingest_api_service.ask_for_res(fetch_request).await.unwrap();
+        let doc_batch = fetch_response.doc_batch.unwrap();
+        assert_eq!(doc_batch.num_docs(), 3);
+        assert!(matches!(
+            doc_batch.iter().nth(2),
+            Some(DocCommand::Commit::<Bytes>)
+        ));
+        ingest_api_service
+            .send_message(SuggestTruncateRequest {
+                index_id: "index-1".to_string(),
+                up_to_position_included: 2,
+            })
+            .await
+            .unwrap();
+        flush_response.await.unwrap().unwrap();
+
+        universe.assert_quit().await;
+        Ok(())
+    }
 }
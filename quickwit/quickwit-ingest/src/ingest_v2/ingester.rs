@@ -35,17 +35,18 @@ use quickwit_common::pubsub::{EventBroker, EventSubscriber};
 use quickwit_common::rate_limiter::{RateLimiter, RateLimiterSettings};
 use quickwit_common::tower::Pool;
 use quickwit_common::ServiceStream;
+use quickwit_config::WalFsyncPolicy;
 use quickwit_proto::indexing::ShardPositionsUpdate;
 use quickwit_proto::ingest::ingester::{
     AckReplicationMessage, CloseShardsRequest, CloseShardsResponse, DecommissionRequest,
-    DecommissionResponse, FetchMessage, IngesterService, IngesterServiceClient,
-    IngesterServiceStream, IngesterStatus, InitShardsRequest, InitShardsResponse,
-    ObservationMessage, OpenFetchStreamRequest, OpenObservationStreamRequest,
-    OpenReplicationStreamRequest, OpenReplicationStreamResponse, PersistFailure,
-    PersistFailureReason, PersistRequest, PersistResponse, PersistSuccess, PingRequest,
-    PingResponse, ReplicateFailureReason, ReplicateSubrequest, RetainShardsForSource,
-    RetainShardsRequest, RetainShardsResponse, SynReplicationMessage, TruncateShardsRequest,
-    TruncateShardsResponse,
+    DecommissionResponse, FetchMessage, GetReplicationPositionsRequest,
+    GetReplicationPositionsResponse, IngesterService, IngesterServiceClient, IngesterServiceStream,
+    IngesterStatus, InitShardsRequest, InitShardsResponse, ObservationMessage,
+    OpenFetchStreamRequest, OpenObservationStreamRequest, OpenReplicationStreamRequest,
+    OpenReplicationStreamResponse, PersistFailure, PersistFailureReason, PersistRequest,
+    PersistResponse, PersistSuccess, PingRequest, PingResponse, ReplicateFailureReason,
+    ReplicateSubrequest, RetainShardsForSource, RetainShardsRequest, RetainShardsResponse,
+    ShardReplicationPosition, SynReplicationMessage, TruncateShardsRequest, TruncateShardsResponse,
 };
 use quickwit_proto::ingest::{CommitTypeV2, IngestV2Error, IngestV2Result, Shard, ShardState};
 use quickwit_proto::types::{queue_id, NodeId, Position, QueueId};
@@ -56,7 +57,8 @@ use super::fetch::FetchStreamTask;
 use super::metrics::INGEST_V2_METRICS;
 use super::models::IngesterShard;
 use super::mrecordlog_utils::{
-    append_non_empty_doc_batch, check_enough_capacity, force_delete_queue, AppendDocBatchError,
+    append_non_empty_doc_batch, check_enough_capacity, force_delete_queue, wal_sync_policy,
+    AppendDocBatchError,
 };
 use super::rate_meter::RateMeter;
 use super::replication::{
@@ -110,6 +112,7 @@ pub(super) struct IngesterState {
 }
 
 impl Ingester {
+    #[allow(clippy::too_many_arguments)]
     pub async fn try_new(
         cluster: Cluster,
         ingester_pool: Pool<NodeId, IngesterServiceClient>,
@@ -118,24 +121,23 @@ impl Ingester {
         memory_capacity: ByteSize,
         rate_limiter_settings: RateLimiterSettings,
         replication_factor: usize,
+        fsync_policy: WalFsyncPolicy,
     ) -> IngestV2Result<Self> {
         let self_node_id: NodeId = cluster.self_node_id().into();
         info!(
             "opening write-ahead log located at `{}`",
             wal_dir_path.display()
         );
-        let mrecordlog = MultiRecordLog::open_with_prefs(
-            wal_dir_path,
-            mrecordlog::SyncPolicy::OnDelay(Duration::from_secs(5)),
-        )
-        .await
-        .map_err(|error| {
-            let message = format!(
-                "failed to create or open write-ahead log located at `{}`: {error}",
-                wal_dir_path.display()
-            );
-            IngestV2Error::Internal(message)
-        })?;
+        let sync_policy = wal_sync_policy(fsync_policy);
+        let mrecordlog = MultiRecordLog::open_with_prefs(wal_dir_path, sync_policy)
+            .await
+            .map_err(|error| {
+                let message = format!(
+                    "failed to create or open write-ahead log located at `{}`: {error}",
+                    wal_dir_path.display()
+                );
+                IngestV2Error::Internal(message)
+            })?;
         let observe_message = ObservationMessage {
             node_id: self_node_id.clone().into(),
             status: IngesterStatus::Ready as i32,
@@ -164,6 +166,7 @@ impl Ingester {
         info!(
             replication_factor=%replication_factor,
             wal_dir=%wal_dir_path.display(),
+            fsync_policy=%fsync_policy,
             "spawning ingester"
         );
         ingester.init().await?;
@@ -468,6 +471,11 @@ impl Ingester {
 
             let doc_batch = match subrequest.doc_batch {
                 Some(doc_batch) if !doc_batch.is_empty() => doc_batch,
+                // A force commit with an empty doc batch flushes the shard: a lone commit record
+                // is appended so that the indexer commits the pending records right away.
+                doc_batch_opt if force_commit && shard.has_pending_records() => {
+                    doc_batch_opt.unwrap_or_default()
+                }
                 _ => {
                     warn!("received empty persist request");
 
@@ -921,6 +929,35 @@ impl Ingester {
 
         Ok(DecommissionResponse {})
     }
+
+    async fn get_replication_positions_inner(
+        &mut self,
+        get_replication_positions_request: GetReplicationPositionsRequest,
+    ) -> IngestV2Result<GetReplicationPositionsResponse> {
+        let state_guard = self.state.read().await;
+        let mut replication_positions = Vec::new();
+
+        for shard_ids in get_replication_positions_request.shards {
+            for shard_id in shard_ids.shard_ids {
+                let queue_id = queue_id(&shard_ids.index_uid, &shard_ids.source_id, &shard_id);
+
+                if let Some(shard) = state_guard.shards.get(&queue_id) {
+                    let replication_position = ShardReplicationPosition {
+                        index_uid: shard_ids.index_uid.clone(),
+                        source_id: shard_ids.source_id.clone(),
+                        shard_id: Some(shard_id),
+                        replication_position_inclusive: Some(
+                            shard.replication_position_inclusive.clone(),
+                        ),
+                    };
+                    replication_positions.push(replication_position);
+                }
+            }
+        }
+        Ok(GetReplicationPositionsResponse {
+            replication_positions,
+        })
+    }
 }
 
 #[async_trait]
@@ -1067,6 +1104,19 @@ impl IngesterService for Ingester {
             "decommission"
         )
     }
+
+    async fn get_replication_positions(
+        &mut self,
+        get_replication_positions_request: GetReplicationPositionsRequest,
+    ) -> IngestV2Result<GetReplicationPositionsResponse> {
+        with_request_metrics!(
+            self.get_replication_positions_inner(get_replication_positions_request)
+                .await,
+            "ingester",
+            "server",
+            "get_replication_positions"
+        )
+    }
 }
 
 impl IngesterState {
@@ -1219,6 +1269,7 @@ mod tests {
         memory_capacity: ByteSize,
         rate_limiter_settings: RateLimiterSettings,
         replication_factor: usize,
+        fsync_policy: WalFsyncPolicy,
     }
 
     impl Default for IngesterForTest {
@@ -1230,6 +1281,7 @@ mod tests {
                 memory_capacity: ByteSize::mb(1),
                 rate_limiter_settings: RateLimiterSettings::default(),
                 replication_factor: 1,
+                fsync_policy: WalFsyncPolicy::default(),
             }
         }
     }
@@ -1263,6 +1315,11 @@ mod tests {
             self
         }
 
+        pub fn with_fsync_policy(mut self, fsync_policy: WalFsyncPolicy) -> Self {
+            self.fsync_policy = fsync_policy;
+            self
+        }
+
         pub async fn build(self) -> (IngesterContext, Ingester) {
             static GOSSIP_ADVERTISE_PORT_SEQUENCE: AtomicU16 = AtomicU16::new(1u16);
 
@@ -1293,12 +1350,13 @@ mod tests {
                 self.memory_capacity,
                 self.rate_limiter_settings,
                 self.replication_factor,
+                self.fsync_policy,
             )
             .await
             .unwrap();
 
             let ingester_env = IngesterContext {
-                _tempdir: tempdir,
+                tempdir,
                 _transport: transport,
                 node_id: self.node_id,
                 cluster,
@@ -1309,7 +1367,7 @@ mod tests {
     }
 
     pub struct IngesterContext {
-        _tempdir: tempfile::TempDir,
+        tempdir: tempfile::TempDir,
         _transport: ChannelTransport,
         node_id: NodeId,
         cluster: Cluster,
@@ -1614,6 +1672,160 @@ mod tests {
         );
     }
 
+    /// Returns whether the files of the write-ahead log contain `bytes`, that is, whether `bytes`
+    /// were flushed and fsynced to disk.
+    fn wal_files_contain(wal_dir_path: &Path, bytes: &[u8]) -> bool {
+        std::fs::read_dir(wal_dir_path)
+            .unwrap()
+            .map(|dir_entry| dir_entry.unwrap().path())
+            .filter(|path| path.is_file())
+            .any(|path| {
+                let file_content = std::fs::read(path).unwrap();
+                file_content
+                    .windows(bytes.len())
+                    .any(|window| window == bytes)
+            })
+    }
+
+    #[tokio::test]
+    async fn test_ingester_persist_with_fsync_policy() {
+        for (fsync_policy, expected_fsynced) in [
+            (WalFsyncPolicy::Always, true),
+            (WalFsyncPolicy::Interval(Duration::from_millis(200)), false),
+            (WalFsyncPolicy::Never, false),
+        ] {
+            let (ingester_ctx, mut ingester) = IngesterForTest::default()
+                .with_fsync_policy(fsync_policy)
+                .build()
+                .await;
+
+            let init_shards_request = InitShardsRequest {
+                shards: vec![Shard {
+                    index_uid: "test-index:0".to_string(),
+                    source_id: "test-source".to_string(),
+                    shard_id: Some(ShardId::from(1)),
+                    shard_state: ShardState::Open as i32,
+                    leader_id: ingester_ctx.node_id.to_string(),
+                    ..Default::default()
+                }],
+            };
+            ingester.init_shards(init_shards_request).await.unwrap();
+
+            for (doc, sleep_duration) in [
+                ("test-doc-010", Duration::ZERO),
+                ("test-doc-011", Duration::from_millis(300)),
+            ] {
+                // Makes the fsync interval elapse before persisting the second doc.
+                tokio::time::sleep(sleep_duration).await;
+
+                let persist_request = PersistRequest {
+                    leader_id: ingester_ctx.node_id.to_string(),
+                    commit_type: CommitTypeV2::Auto as i32,
+                    subrequests: vec![PersistSubrequest {
+                        subrequest_id: 0,
+                        index_uid: "test-index:0".to_string(),
+                        source_id: "test-source".to_string(),
+                        shard_id: Some(ShardId::from(1)),
+                        doc_batch: Some(DocBatchV2::for_test([doc])),
+                    }],
+                };
+                let persist_response = ingester.persist(persist_request).await.unwrap();
+                assert_eq!(persist_response.successes.len(), 1);
+
+                if doc == "test-doc-010" {
+                    assert_eq!(
+                        wal_files_contain(ingester_ctx.tempdir.path(), b"test-doc-010"),
+                        expected_fsynced,
+                        "fsync policy `{fsync_policy}`"
+                    );
+                }
+            }
+            // The first append following the fsync interval fsyncs the WAL.
+            let expected_fsynced = !matches!(fsync_policy, WalFsyncPolicy::Never);
+            for doc in ["test-doc-010", "test-doc-011"] {
+                assert_eq!(
+                    wal_files_contain(ingester_ctx.tempdir.path(), doc.as_bytes()),
+                    expected_fsynced,
+                    "fsync policy `{fsync_policy}`"
+                );
+            }
+            let state_guard = ingester.state.read().await;
+            let queue_id_01 = queue_id("test-index:0", "test-source", &ShardId::from(1));
+
+            state_guard.mrecordlog.assert_records_eq(
+                &queue_id_01,
+                ..,
+                &[(0, "\0\0test-doc-010"), (1, "\0\0test-doc-011")],
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ingester_persist_flush() {
+        let (ingester_ctx, mut ingester) = IngesterForTest::default().build().await;
+
+        let init_shards_request = InitShardsRequest {
+            shards: vec![Shard {
+                index_uid: "test-index:0".to_string(),
+                source_id: "test-source".to_string(),
+                shard_id: Some(ShardId::from(1)),
+                shard_state: ShardState::Open as i32,
+                leader_id: ingester_ctx.node_id.to_string(),
+                ..Default::default()
+            }],
+        };
+        ingester.init_shards(init_shards_request).await.unwrap();
+
+        let persist_request = PersistRequest {
+            leader_id: ingester_ctx.node_id.to_string(),
+            commit_type: CommitTypeV2::Auto as i32,
+            subrequests: vec![PersistSubrequest {
+                subrequest_id: 0,
+                index_uid: "test-index:0".to_string(),
+                source_id: "test-source".to_string(),
+                shard_id: Some(ShardId::from(1)),
+                doc_batch: Some(DocBatchV2::for_test(["test-doc-010", "test-doc-011"])),
+            }],
+        };
+        ingester.persist(persist_request).await.unwrap();
+
+        let flush_request = PersistRequest {
+            leader_id: ingester_ctx.node_id.to_string(),
+            commit_type: CommitTypeV2::Force as i32,
+            subrequests: vec![PersistSubrequest {
+                subrequest_id: 0,
+                index_uid: "test-index:0".to_string(),
+                source_id: "test-source".to_string(),
+                shard_id: Some(ShardId::from(1)),
+                doc_batch: None,
+            }],
+        };
+        let persist_response = ingester.persist(flush_request).await.unwrap();
+        assert_eq!(persist_response.successes.len(), 1);
+        assert_eq!(persist_response.failures.len(), 0);
+
+        let persist_success = &persist_response.successes[0];
+        assert_eq!(
+            persist_success.replication_position_inclusive,
+            Some(Position::offset(2u64))
+        );
+        let state_guard = ingester.state.read().await;
+
+        let queue_id_01 = queue_id("test-index:0", "test-source", &ShardId::from(1));
+        let solo_shard_01 = state_guard.shards.get(&queue_id_01).unwrap();
+        solo_shard_01.assert_replication_position(Position::offset(2u64));
+
+        state_guard.mrecordlog.assert_records_eq(
+            &queue_id_01,
+            ..,
+            &[
+                (0, "\0\0test-doc-010"),
+                (1, "\0\0test-doc-011"),
+                (2, "\0\x01"),
+            ],
+        );
+    }
+
     // This test should be run manually and independently of other tests with the `fail/failpoints`
     // feature enabled.
     #[tokio::test]
@@ -2631,6 +2843,62 @@ mod tests {
         assert_eq!(fetch_eof.eof_position(), Position::Beginning.as_eof());
     }
 
+    #[tokio::test]
+    async fn test_ingester_get_replication_positions() {
+        let (ingester_ctx, mut ingester) = IngesterForTest::default().build().await;
+
+        let init_shards_request = InitShardsRequest {
+            shards: vec![Shard {
+                index_uid: "test-index:0".to_string(),
+                source_id: "test-source".to_string(),
+                shard_id: Some(ShardId::from(1)),
+                shard_state: ShardState::Open as i32,
+                leader_id: ingester_ctx.node_id.to_string(),
+                ..Default::default()
+            }],
+        };
+        ingester.init_shards(init_shards_request).await.unwrap();
+
+        let persist_request = PersistRequest {
+            leader_id: ingester_ctx.node_id.to_string(),
+            commit_type: CommitTypeV2::Force as i32,
+            subrequests: vec![PersistSubrequest {
+                subrequest_id: 0,
+                index_uid: "test-index:0".to_string(),
+                source_id: "test-source".to_string(),
+                shard_id: Some(ShardId::from(1)),
+                doc_batch: Some(DocBatchV2::for_test(["test-doc-010"])),
+            }],
+        };
+        ingester.persist(persist_request).await.unwrap();
+
+        let get_replication_positions_request = GetReplicationPositionsRequest {
+            shards: vec![ShardIds {
+                index_uid: "test-index:0".to_string(),
+                source_id: "test-source".to_string(),
+                shard_ids: vec![ShardId::from(1), ShardId::from(1337)],
+            }],
+        };
+        let get_replication_positions_response = ingester
+            .get_replication_positions(get_replication_positions_request)
+            .await
+            .unwrap();
+        assert_eq!(
+            get_replication_positions_response
+                .replication_positions
+                .len(),
+            1
+        );
+        let replication_position = &get_replication_positions_response.replication_positions[0];
+        assert_eq!(replication_position.index_uid, "test-index:0");
+        assert_eq!(replication_position.source_id, "test-source");
+        assert_eq!(replication_position.shard_id(), ShardId::from(1));
+        assert_eq!(
+            replication_position.replication_position_inclusive,
+            Some(Position::offset(1u64))
+        );
+    }
+
     #[tokio::test]
     async fn test_ingester_open_observation_stream() {
         let (ingester_ctx, mut ingester) = IngesterForTest::default().build().await;
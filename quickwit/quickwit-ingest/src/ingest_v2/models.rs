@@ -104,6 +104,11 @@ impl IngesterShard {
         self.shard_state.is_closed() && self.truncation_position_inclusive.is_eof()
     }
 
+    /// Returns whether the shard holds records that have not been truncated yet.
+    pub fn has_pending_records(&self) -> bool {
+        self.replication_position_inclusive > self.truncation_position_inclusive
+    }
+
     pub fn is_replica(&self) -> bool {
         matches!(self.shard_type, IngesterShardType::Replica { .. })
     }
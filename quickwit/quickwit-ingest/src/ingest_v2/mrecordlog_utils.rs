@@ -20,16 +20,31 @@
 use std::io;
 use std::iter::once;
 use std::ops::RangeInclusive;
+use std::time::Duration;
 
 use bytesize::ByteSize;
 use fail::fail_point;
 use mrecordlog::error::{AppendError, DeleteQueueError};
-use mrecordlog::MultiRecordLog;
+use mrecordlog::{MultiRecordLog, SyncPolicy};
+use quickwit_config::WalFsyncPolicy;
 use quickwit_proto::ingest::DocBatchV2;
 use quickwit_proto::types::{Position, QueueId};
 
 use crate::MRecord;
 
+/// Delay between two fsyncs of the WAL large enough to never be reached, used to implement the
+/// `never` fsync policy.
+const NEVER_FSYNC_DELAY: Duration = Duration::from_secs(u32::MAX as u64);
+
+/// Translates the fsync policy of the ingester into the sync policy of the WAL.
+pub(super) fn wal_sync_policy(fsync_policy: WalFsyncPolicy) -> SyncPolicy {
+    match fsync_policy {
+        WalFsyncPolicy::Always => SyncPolicy::OnAppend,
+        WalFsyncPolicy::Interval(interval) => SyncPolicy::OnDelay(interval),
+        WalFsyncPolicy::Never => SyncPolicy::OnDelay(NEVER_FSYNC_DELAY),
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub(super) enum AppendDocBatchError {
     #[error("IO error: {0}")]
@@ -179,6 +194,22 @@ pub(super) fn queue_position_range(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_wal_sync_policy() {
+        assert!(matches!(
+            wal_sync_policy(WalFsyncPolicy::Always),
+            SyncPolicy::OnAppend
+        ));
+        assert!(matches!(
+            wal_sync_policy(WalFsyncPolicy::Interval(Duration::from_millis(500))),
+            SyncPolicy::OnDelay(delay) if delay == Duration::from_millis(500)
+        ));
+        assert!(matches!(
+            wal_sync_policy(WalFsyncPolicy::Never),
+            SyncPolicy::OnDelay(delay) if delay == NEVER_FSYNC_DELAY
+        ));
+    }
+
     #[tokio::test]
     async fn test_append_non_empty_doc_batch() {
         let tempdir = tempfile::tempdir().unwrap();
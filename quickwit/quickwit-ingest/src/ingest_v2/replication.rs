@@ -573,6 +573,13 @@ impl ReplicationTask {
             }
             let doc_batch = match subrequest.doc_batch {
                 Some(doc_batch) if !doc_batch.is_empty() => doc_batch,
+                // The leader flushed the shard: replicate its lone commit record.
+                doc_batch_opt
+                    if force_commit
+                        && to_position_inclusive > shard.replication_position_inclusive =>
+                {
+                    doc_batch_opt.unwrap_or_default()
+                }
                 _ => {
                     warn!("received empty replicate request");
 
@@ -1280,6 +1287,51 @@ mod tests {
             ..,
             &[(0, "\0\0test-doc-foo"), (1, "\0\0test-doc-moo")],
         );
+        drop(state_guard);
+
+        // Flush shard 01.
+        let replicate_request = ReplicateRequest {
+            leader_id: "test-leader".to_string(),
+            follower_id: "test-follower".to_string(),
+            commit_type: CommitTypeV2::Force as i32,
+            subrequests: vec![ReplicateSubrequest {
+                subrequest_id: 0,
+                index_uid: "test-index:0".to_string(),
+                source_id: "test-source".to_string(),
+                shard_id: Some(ShardId::from(1)),
+                doc_batch: None,
+                from_position_exclusive: Some(Position::offset(1u64)),
+                to_position_inclusive: Some(Position::offset(2u64)),
+            }],
+            replication_seqno: 5,
+        };
+        let syn_replication_message =
+            SynReplicationMessage::new_replicate_request(replicate_request);
+        syn_replication_stream_tx
+            .send(syn_replication_message)
+            .await
+            .unwrap();
+        let ack_replication_message = ack_replication_stream.next().await.unwrap().unwrap();
+        let replicate_response = into_replicate_response(ack_replication_message);
+
+        assert_eq!(replicate_response.successes.len(), 1);
+        assert_eq!(replicate_response.failures.len(), 0);
+        assert_eq!(
+            replicate_response.successes[0].replication_position_inclusive(),
+            Position::offset(2u64)
+        );
+
+        let state_guard = state.read().await;
+
+        state_guard.mrecordlog.assert_records_eq(
+            &queue_id_01,
+            ..,
+            &[
+                (0, "\0\0test-doc-foo"),
+                (1, "\0\0test-doc-moo"),
+                (2, "\0\x01"),
+            ],
+        );
     }
 
     #[tokio::test]
@@ -38,7 +38,9 @@ use std::path::{Path, PathBuf};
 use anyhow::{bail, Context};
 pub use doc_batch::*;
 pub use errors::IngestServiceError;
-pub use ingest_api_service::{GetMemoryCapacity, GetPartitionId, IngestApiService};
+pub use ingest_api_service::{
+    Flush, GetDiskCapacity, GetMemoryCapacity, GetPartitionId, IngestApiService,
+};
 pub use ingest_service::*;
 pub use ingest_v2::*;
 pub use memory_capacity::MemoryCapacity;
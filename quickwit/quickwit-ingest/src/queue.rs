@@ -17,6 +17,7 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
+use std::collections::HashMap;
 use std::ops::Bound;
 use std::path::Path;
 
@@ -36,13 +37,29 @@ const QUICKWIT_CF_PREFIX: &str = ".queue_";
 
 pub struct Queues {
     record_log: MultiRecordLog,
+    // Position of the last record of the non-empty queues, updated on append and truncation so
+    // that it can be read without scanning the queue.
+    last_positions: HashMap<String, u64>,
 }
 
 impl Queues {
     pub async fn open(queues_dir_path: &Path) -> crate::Result<Queues> {
         tokio::fs::create_dir_all(queues_dir_path).await.unwrap();
         let record_log = MultiRecordLog::open(queues_dir_path).await?;
-        Ok(Queues { record_log })
+        let mut last_positions = HashMap::new();
+
+        for real_queue_id in record_log.list_queues() {
+            let Some(queue_id) = real_queue_id.strip_prefix(QUICKWIT_CF_PREFIX) else {
+                continue;
+            };
+            if let Ok(Some((last_position, _record))) = record_log.last_record(real_queue_id) {
+                last_positions.insert(queue_id.to_string(), last_position);
+            }
+        }
+        Ok(Queues {
+            record_log,
+            last_positions,
+        })
     }
 
     pub fn queue_exists(&self, queue_id: &str) -> bool {
@@ -80,6 +97,7 @@ impl Queues {
         let real_queue_id = format!("{QUICKWIT_CF_PREFIX}{queue_id}");
         ctx.protect_future(self.record_log.delete_queue(&real_queue_id))
             .await?;
+        self.last_positions.remove(queue_id);
         Ok(())
     }
 
@@ -110,6 +128,13 @@ impl Queues {
         )
         .await?;
 
+        if self
+            .last_positions
+            .get(queue_id)
+            .is_some_and(|last_position| *last_position <= up_to_offset_included)
+        {
+            self.last_positions.remove(queue_id);
+        }
         Ok(())
     }
 
@@ -144,6 +169,10 @@ impl Queues {
             )
             .await?;
 
+        if let Some(max_position) = max_position {
+            self.last_positions
+                .insert(queue_id.to_string(), max_position);
+        }
         Ok(max_position)
     }
 
@@ -189,12 +218,36 @@ impl Queues {
         Ok(FetchResponse {
             first_position: first_key_opt,
             doc_batch: Some(doc_batch.build()),
+            last_position: None,
+            partition_id: None,
         })
     }
 
+    /// Returns the position of the last record of the queue, or `None` if the queue is empty.
+    pub fn last_position(&self, queue_id: &str) -> crate::Result<Option<u64>> {
+        if !self.queue_exists(queue_id) {
+            return Err(crate::IngestServiceError::IndexNotFound {
+                index_id: queue_id.to_string(),
+            });
+        }
+        Ok(self.last_positions.get(queue_id).copied())
+    }
+
     // Streams messages from the start of the Stream.
     pub fn tail(&self, queue_id: &str) -> crate::Result<FetchResponse> {
-        self.fetch(queue_id, None, None)
+        let mut fetch_response = self.fetch(queue_id, None, None)?;
+        fetch_response.last_position = self.last_position(queue_id)?;
+        Ok(fetch_response)
+    }
+
+    // Returns the position of the last record of the Stream without fetching any record.
+    pub fn tail_positions(&self, queue_id: &str) -> crate::Result<FetchResponse> {
+        Ok(FetchResponse {
+            first_position: None,
+            doc_batch: None,
+            last_position: self.last_position(queue_id)?,
+            partition_id: None,
+        })
     }
 
     pub fn list_queues(&self) -> crate::Result<ListQueuesResponse> {
@@ -397,6 +450,67 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_tail_last_position() {
+        let (mut queues, ctx) = QueuesForTest::new().await;
+        queues.create_queue(TEST_QUEUE_ID, &ctx).await.unwrap();
+
+        let tail_resp = queues.tail(TEST_QUEUE_ID).unwrap();
+        assert!(tail_resp.first_position.is_none());
+        assert!(tail_resp.last_position.is_none());
+
+        queues.append(TEST_QUEUE_ID, b"hello", &ctx).await.unwrap();
+        queues.append(TEST_QUEUE_ID, b"happy", &ctx).await.unwrap();
+
+        let tail_resp = queues.tail(TEST_QUEUE_ID).unwrap();
+        assert_eq!(tail_resp.first_position, Some(0));
+        assert_eq!(tail_resp.last_position, Some(1));
+
+        let tail_positions_resp = queues.tail_positions(TEST_QUEUE_ID).unwrap();
+        assert!(tail_positions_resp.first_position.is_none());
+        assert!(tail_positions_resp.doc_batch.is_none());
+        assert_eq!(tail_positions_resp.last_position, Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_last_position() {
+        let (mut queues, ctx) = QueuesForTest::new().await;
+
+        let last_position_err = queues.last_position(TEST_QUEUE_ID).unwrap_err();
+        assert!(matches!(
+            last_position_err,
+            IngestServiceError::IndexNotFound { .. }
+        ));
+
+        queues.create_queue(TEST_QUEUE_ID, &ctx).await.unwrap();
+        assert_eq!(queues.last_position(TEST_QUEUE_ID).unwrap(), None);
+
+        queues.append(TEST_QUEUE_ID, b"hello", &ctx).await.unwrap();
+        queues.append(TEST_QUEUE_ID, b"happy", &ctx).await.unwrap();
+        assert_eq!(queues.last_position(TEST_QUEUE_ID).unwrap(), Some(1));
+
+        queues
+            .suggest_truncate(TEST_QUEUE_ID, 0, &ctx)
+            .await
+            .unwrap();
+        assert_eq!(queues.last_position(TEST_QUEUE_ID).unwrap(), Some(1));
+
+        queues.reload().await;
+        assert_eq!(queues.last_position(TEST_QUEUE_ID).unwrap(), Some(1));
+
+        queues
+            .suggest_truncate(TEST_QUEUE_ID, 1, &ctx)
+            .await
+            .unwrap();
+        assert_eq!(queues.last_position(TEST_QUEUE_ID).unwrap(), None);
+
+        queues.append(TEST_QUEUE_ID, b"tax", &ctx).await.unwrap();
+        assert_eq!(queues.last_position(TEST_QUEUE_ID).unwrap(), Some(2));
+
+        queues.drop_queue(TEST_QUEUE_ID, &ctx).await.unwrap();
+        assert!(queues.last_position(TEST_QUEUE_ID).is_err());
+    }
+
     // Note this test is specific to the current implementation of truncate.
     //
     // The truncate contract is actually not as accurate as what we are testing here.
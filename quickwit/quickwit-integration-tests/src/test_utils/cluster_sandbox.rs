@@ -160,6 +160,7 @@ impl ClusterSandbox {
                 async move {
                     let result = serve_quickwit(
                         node_config,
+                        None,
                         runtimes_config,
                         metastore_resolver,
                         storage_resolver,
@@ -24,6 +24,7 @@ use quickwit_common::test_utils::wait_until_predicate;
 use quickwit_config::service::QuickwitService;
 use quickwit_config::ConfigFormat;
 use quickwit_indexing::actors::INDEXING_DIR_NAME;
+use quickwit_ingest::{get_ingest_api_service, Flush, QUEUES_DIR_NAME};
 use quickwit_janitor::actors::DELETE_SERVICE_TASK_DIR_NAME;
 use quickwit_metastore::SplitState;
 use quickwit_proto::opentelemetry::proto::collector::trace::v1::ExportTraceServiceRequest;
@@ -441,6 +442,95 @@ async fn test_commit_modes() {
     sandbox.shutdown().await.unwrap();
 }
 
+#[tokio::test]
+async fn test_flush() {
+    quickwit_common::setup_logging_for_tests();
+    let sandbox = ClusterSandbox::start_standalone_node().await.unwrap();
+    let index_id = "test_flush";
+    let index_config = r#"
+        version: 0.7
+        index_id: test_flush
+        doc_mapping:
+          field_mappings:
+          - name: body
+            type: text
+        indexing_settings:
+          commit_timeout_secs: 3600
+    "#;
+
+    sandbox
+        .indexer_rest_client
+        .indexes()
+        .create(index_config, ConfigFormat::Yaml, false)
+        .await
+        .unwrap();
+
+    ingest_with_retry(
+        &sandbox.indexer_rest_client,
+        index_id,
+        ingest_json!({"body": "flush"}),
+        CommitType::Auto,
+    )
+    .await
+    .unwrap();
+
+    // The document is not committed before the commit timeout expires.
+    assert_eq!(
+        sandbox
+            .searcher_rest_client
+            .search(
+                index_id,
+                SearchRequestQueryString {
+                    query: "body:flush".to_string(),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap()
+            .num_hits,
+        0
+    );
+
+    let queues_dir_path = sandbox.node_configs[0]
+        .node_config
+        .data_dir_path
+        .join(QUEUES_DIR_NAME);
+    let ingest_api_service = get_ingest_api_service(&queues_dir_path).await.unwrap();
+    ingest_api_service
+        .ask_for_res(Flush {
+            index_id: index_id.to_string(),
+        })
+        .await
+        .unwrap();
+
+    // The flush returns once the document is published.
+    assert_eq!(
+        sandbox
+            .searcher_rest_client
+            .search(
+                index_id,
+                SearchRequestQueryString {
+                    query: "body:flush".to_string(),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap()
+            .num_hits,
+        1
+    );
+
+    // Flushing the queue again returns right away since it holds no pending documents.
+    ingest_api_service
+        .ask_for_res(Flush {
+            index_id: index_id.to_string(),
+        })
+        .await
+        .unwrap();
+
+    sandbox.shutdown().await.unwrap();
+}
+
 #[tokio::test]
 async fn test_very_large_index_name() {
     quickwit_common::setup_logging_for_tests();
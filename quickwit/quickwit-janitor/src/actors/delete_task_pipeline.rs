@@ -22,6 +22,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
+use bytesize::ByteSize;
 use quickwit_actors::{
     Actor, ActorContext, ActorExitStatus, ActorHandle, Handler, Supervisor, SupervisorState,
 };
@@ -35,7 +36,9 @@ use quickwit_indexing::actors::{
     UploaderCounters, UploaderType,
 };
 use quickwit_indexing::merge_policy::merge_policy_from_settings;
-use quickwit_indexing::{IndexingSplitStore, PublisherType, SplitsUpdateMailbox};
+use quickwit_indexing::{
+    IndexingSplitStore, PublisherType, ScratchSpaceQuota, SplitsUpdateMailbox,
+};
 use quickwit_metastore::IndexMetadataResponseExt;
 use quickwit_proto::indexing::IndexingPipelineId;
 use quickwit_proto::metastore::{IndexMetadataRequest, MetastoreService, MetastoreServiceClient};
@@ -44,6 +47,7 @@ use quickwit_search::SearchJobPlacer;
 use quickwit_storage::Storage;
 use serde::Serialize;
 use tokio::join;
+use tokio::sync::Semaphore;
 use tracing::info;
 
 use super::delete_task_planner::DeleteTaskPlanner;
@@ -85,6 +89,9 @@ pub struct DeleteTaskPipeline {
     delete_service_task_dir: PathBuf,
     handles: Option<DeletePipelineHandle>,
     max_concurrent_split_uploads: usize,
+    delete_task_permits: Arc<Semaphore>,
+    max_delete_task_throughput_opt: Option<ByteSize>,
+    scratch_space_quota: ScratchSpaceQuota,
     state: DeleteTaskPipelineState,
     event_broker: EventBroker,
 }
@@ -127,6 +134,7 @@ impl Actor for DeleteTaskPipeline {
 }
 
 impl DeleteTaskPipeline {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         index_uid: IndexUid,
         metastore: MetastoreServiceClient,
@@ -134,6 +142,9 @@ impl DeleteTaskPipeline {
         index_storage: Arc<dyn Storage>,
         delete_service_task_dir: PathBuf,
         max_concurrent_split_uploads: usize,
+        delete_task_permits: Arc<Semaphore>,
+        max_delete_task_throughput_opt: Option<ByteSize>,
+        scratch_space_quota: ScratchSpaceQuota,
         event_broker: EventBroker,
     ) -> Self {
         Self {
@@ -144,6 +155,9 @@ impl DeleteTaskPipeline {
             delete_service_task_dir,
             handles: Default::default(),
             max_concurrent_split_uploads,
+            delete_task_permits,
+            max_delete_task_throughput_opt,
+            scratch_space_quota,
             state: DeleteTaskPipelineState::default(),
             event_broker,
         }
@@ -186,7 +200,13 @@ impl DeleteTaskPipeline {
         let doc_mapper =
             build_doc_mapper(&index_config.doc_mapping, &index_config.search_settings)?;
         let tag_fields = doc_mapper.tag_named_fields()?;
-        let packager = Packager::new("MergePackager", tag_fields, uploader_mailbox);
+        let min_max_fields = doc_mapper.min_max_named_fields()?;
+        let packager = Packager::new(
+            "MergePackager",
+            tag_fields,
+            min_max_fields,
+            uploader_mailbox,
+        );
         let (packager_mailbox, packager_supervisor_handler) = ctx.spawn_actor().supervise(packager);
         let index_pipeline_id = IndexingPipelineId {
             index_uid: self.index_uid.clone(),
@@ -198,9 +218,10 @@ impl DeleteTaskPipeline {
             .indexing_settings
             .resources
             .max_merge_write_throughput
-            .as_ref()
+            .into_iter()
+            .chain(self.max_delete_task_throughput_opt)
             .map(|bytes_per_sec| bytes_per_sec.as_u64() as f64)
-            .unwrap_or(f64::INFINITY);
+            .fold(f64::INFINITY, f64::min);
         let delete_executor_io_controls = IoControls::default()
             .set_throughput_limit(throughput_limit)
             .set_index_and_component(self.index_uid.index_id(), "deleter");
@@ -212,6 +233,7 @@ impl DeleteTaskPipeline {
             self.metastore.clone(),
             doc_mapper.clone(),
             delete_executor_io_controls,
+            self.scratch_space_quota.clone(),
             packager_mailbox,
         );
         let (delete_executor_mailbox, task_executor_supervisor_handler) =
@@ -225,6 +247,7 @@ impl DeleteTaskPipeline {
             split_store,
             executor_mailbox: delete_executor_mailbox,
             io_controls: split_download_io_controls,
+            scratch_space_quota: self.scratch_space_quota.clone(),
         };
         let (downloader_mailbox, downloader_supervisor_handler) =
             ctx.spawn_actor().supervise(merge_split_downloader);
@@ -237,6 +260,7 @@ impl DeleteTaskPipeline {
             self.metastore.clone(),
             self.search_job_placer.clone(),
             downloader_mailbox,
+            self.delete_task_permits.clone(),
         );
         let (_, task_planner_supervisor_handler) = ctx.spawn_actor().supervise(task_planner);
         self.handles = Some(DeletePipelineHandle {
@@ -285,19 +309,27 @@ impl Handler<Observe> for DeleteTaskPipeline {
 
 #[cfg(test)]
 mod tests {
+    use std::path::Path;
+    use std::sync::Arc;
+
     use async_trait::async_trait;
     use quickwit_actors::Handler;
     use quickwit_common::pubsub::EventBroker;
+    use quickwit_common::split_file;
     use quickwit_common::temp_dir::TempDirectory;
-    use quickwit_indexing::TestSandbox;
+    use quickwit_indexing::{get_tantivy_directory_from_split_bundle, TestSandbox};
     use quickwit_metastore::{ListSplitsRequestExt, MetastoreServiceStreamSplitsExt, SplitState};
     use quickwit_proto::metastore::{DeleteQuery, ListSplitsRequest, MetastoreService};
     use quickwit_proto::search::{LeafSearchRequest, LeafSearchResponse};
     use quickwit_search::{
         searcher_pool_for_test, MockSearchService, SearchError, SearchJobPlacer,
     };
+    use tantivy::schema::Value;
+    use tantivy::TantivyDocument;
+    use tokio::sync::Semaphore;
 
     use super::{ActorContext, ActorExitStatus, DeleteTaskPipeline, OBSERVE_PIPELINE_INTERVAL};
+    use crate::delete_query_for_doc_ids;
 
     #[derive(Debug)]
     struct GracefulShutdown;
@@ -393,6 +425,9 @@ mod tests {
             test_sandbox.storage(),
             delete_service_task_dir.path().into(),
             4,
+            Arc::new(Semaphore::new(1)),
+            None,
+            ScratchSpaceQuota::unlimited(),
             EventBroker::default(),
         );
 
@@ -432,6 +467,122 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_delete_pipeline_doc_ids() -> anyhow::Result<()> {
+        quickwit_common::setup_logging_for_tests();
+        let index_id = "test-delete-pipeline-doc-ids";
+        let doc_mapping_yaml = r#"
+            field_mappings:
+              - name: _id
+                type: text
+                tokenizer: raw
+              - name: body
+                type: text
+        "#;
+        let indexing_settings_yaml = r#"
+            merge_policy:
+                type: no_merge
+        "#;
+        let test_sandbox = TestSandbox::create(
+            index_id,
+            doc_mapping_yaml,
+            indexing_settings_yaml,
+            &["body"],
+        )
+        .await
+        .unwrap();
+        let index_uid = test_sandbox.index_uid();
+        let docs = vec![
+            serde_json::json!({"_id": "doc-1", "body": "info" }),
+            serde_json::json!({"_id": "doc-2", "body": "info" }),
+            serde_json::json!({"_id": "doc-3", "body": "info" }),
+        ];
+        test_sandbox.add_documents(docs).await?;
+        let mut metastore = test_sandbox.metastore();
+        let delete_query = delete_query_for_doc_ids(
+            index_uid.clone(),
+            &*test_sandbox.doc_mapper(),
+            vec!["doc-1".to_string(), "doc-3".to_string()],
+        )
+        .unwrap();
+        metastore.create_delete_task(delete_query).await.unwrap();
+
+        let mut mock_search_service = MockSearchService::new();
+        mock_search_service
+            .expect_leaf_search()
+            .returning(|_: LeafSearchRequest| {
+                Ok(LeafSearchResponse {
+                    num_hits: 2,
+                    ..Default::default()
+                })
+            });
+        let searcher_pool = searcher_pool_for_test([("127.0.0.1:1001", mock_search_service)]);
+        let search_job_placer = SearchJobPlacer::new(searcher_pool);
+        let delete_service_task_dir = TempDirectory::for_test();
+        let pipeline = DeleteTaskPipeline::new(
+            test_sandbox.index_uid(),
+            metastore.clone(),
+            search_job_placer,
+            test_sandbox.storage(),
+            delete_service_task_dir.path().into(),
+            4,
+            Arc::new(Semaphore::new(1)),
+            None,
+            ScratchSpaceQuota::unlimited(),
+            EventBroker::default(),
+        );
+        let (pipeline_mailbox, pipeline_handler) =
+            test_sandbox.universe().spawn_builder().spawn(pipeline);
+        let _ = pipeline_handler.process_pending_and_observe().await.state;
+        test_sandbox
+            .universe()
+            .sleep(OBSERVE_PIPELINE_INTERVAL * 5)
+            .await;
+        let _ = pipeline_mailbox.ask(GracefulShutdown).await;
+
+        let splits = metastore
+            .list_splits(ListSplitsRequest::try_from_index_uid(index_uid).unwrap())
+            .await
+            .unwrap()
+            .collect_splits()
+            .await
+            .unwrap();
+        let published_split = splits
+            .iter()
+            .find(|split| split.split_state == SplitState::Published)
+            .unwrap();
+        assert_eq!(published_split.split_metadata.delete_opstamp, 1);
+        assert_eq!(published_split.split_metadata.num_docs, 1);
+
+        let split_dir = TempDirectory::for_test();
+        let split_filename = split_file(published_split.split_id());
+        let split_filepath = split_dir.path().join(&split_filename);
+        test_sandbox
+            .storage()
+            .copy_to_file(Path::new(&split_filename), &split_filepath)
+            .await?;
+        let tantivy_dir = get_tantivy_directory_from_split_bundle(&split_filepath)?;
+        let searcher = tantivy::Index::open(tantivy_dir)?.reader()?.searcher();
+        let id_field = searcher.schema().get_field("_id")?;
+        let doc_ids: Vec<String> = searcher
+            .search(
+                &tantivy::query::AllQuery,
+                &tantivy::collector::TopDocs::with_limit(10),
+            )?
+            .into_iter()
+            .map(|(_, doc_address)| {
+                let doc: TantivyDocument = searcher.doc(doc_address).unwrap();
+                doc.get_first(id_field)
+                    .and_then(|value| value.as_str())
+                    .unwrap()
+                    .to_string()
+            })
+            .collect();
+        assert_eq!(doc_ids, ["doc-2"]);
+        test_sandbox.assert_quit().await;
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_delete_pipeline_shut_down() -> anyhow::Result<()> {
         quickwit_common::setup_logging_for_tests();
@@ -475,6 +626,9 @@ mod tests {
             test_sandbox.storage(),
             delete_service_task_dir.path().into(),
             4,
+            Arc::new(Semaphore::new(1)),
+            None,
+            ScratchSpaceQuota::unlimited(),
             EventBroker::default(),
         );
 
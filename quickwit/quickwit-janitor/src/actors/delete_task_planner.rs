@@ -19,9 +19,10 @@
 
 use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
+use std::sync::Arc;
 use std::time::Duration;
 
-use anyhow::Context;
+use anyhow::{bail, Context};
 use async_trait::async_trait;
 use itertools::Itertools;
 use quickwit_actors::{Actor, ActorContext, ActorExitStatus, Handler, Mailbox, QueueCapacity};
@@ -29,7 +30,7 @@ use quickwit_common::extract_time_range;
 use quickwit_common::uri::Uri;
 use quickwit_doc_mapper::tag_pruning::extract_tags_from_query;
 use quickwit_indexing::actors::MergeSplitDownloader;
-use quickwit_indexing::merge_policy::MergeOperation;
+use quickwit_indexing::merge_policy::{MergeOperation, MergePermit};
 use quickwit_metastore::{split_tag_filter, split_time_range_filter, ListSplitsResponseExt, Split};
 use quickwit_proto::metastore::{
     DeleteTask, LastDeleteOpstampRequest, ListDeleteTasksRequest, ListStaleSplitsRequest,
@@ -40,6 +41,7 @@ use quickwit_proto::types::IndexUid;
 use quickwit_search::{jobs_to_leaf_requests, IndexMetasForLeafSearch, SearchJob, SearchJobPlacer};
 use serde::Serialize;
 use tantivy::Inventory;
+use tokio::sync::Semaphore;
 use tracing::{debug, info};
 
 use crate::metrics::JANITOR_METRICS;
@@ -88,6 +90,9 @@ pub struct DeleteTaskPlanner {
     /// the delete operation.
     /// The inventory is used to avoid sending twice the same delete operation.
     ongoing_delete_operations_inventory: Inventory<MergeOperation>,
+    /// Permits shared by the delete task pipelines of the node. A permit is attached to each
+    /// delete operation and released when the operation is dropped.
+    delete_task_permits: Arc<Semaphore>,
 }
 
 #[async_trait]
@@ -127,6 +132,7 @@ impl DeleteTaskPlanner {
         metastore: MetastoreServiceClient,
         search_job_placer: SearchJobPlacer,
         merge_split_downloader_mailbox: Mailbox<MergeSplitDownloader>,
+        delete_task_permits: Arc<Semaphore>,
     ) -> Self {
         Self {
             index_uid,
@@ -136,6 +142,7 @@ impl DeleteTaskPlanner {
             search_job_placer,
             merge_split_downloader_mailbox,
             ongoing_delete_operations_inventory: Inventory::new(),
+            delete_task_permits,
         }
     }
 
@@ -193,9 +200,11 @@ impl DeleteTaskPlanner {
 
             // Sends delete operations.
             for split_with_deletes in splits_with_deletes {
+                let delete_task_permit = self.acquire_delete_task_permit(ctx).await?;
                 let delete_operation = MergeOperation::new_delete_and_merge_operation(
                     split_with_deletes.split_metadata,
-                );
+                )
+                .with_permit(delete_task_permit);
                 info!(delete_operation=?delete_operation, "planned delete operation");
                 let tracked_delete_operation = self
                     .ongoing_delete_operations_inventory
@@ -215,6 +224,28 @@ impl DeleteTaskPlanner {
         Ok(())
     }
 
+    /// Waits until the number of delete tasks executing on the node is below the configured
+    /// limit and returns a permit to execute a new one.
+    async fn acquire_delete_task_permit(
+        &self,
+        ctx: &ActorContext<Self>,
+    ) -> anyhow::Result<MergePermit> {
+        let acquire_permit_future = self.delete_task_permits.clone().acquire_owned();
+        // Waiting for the delete operations of the other pipelines is not a lack of progress.
+        let semaphore_permit = tokio::select! {
+            acquire_permit_result = ctx.protect_future(acquire_permit_future) => {
+                acquire_permit_result.context("delete task semaphore should never be closed")?
+            }
+            _ = ctx.kill_switch().killed() => {
+                bail!("kill switch was activated while waiting for a delete task permit");
+            }
+        };
+        Ok(MergePermit::new(
+            semaphore_permit,
+            &JANITOR_METRICS.num_active_delete_tasks,
+        ))
+    }
+
     /// Identifies splits that contain documents to delete and
     /// splits that do not and returns the two groups.
     async fn partition_splits_by_deletes(
@@ -544,6 +575,7 @@ mod tests {
             metastore.clone(),
             search_job_placer,
             downloader_mailbox,
+            Arc::new(Semaphore::new(4)),
         );
         let (delete_planner_mailbox, delete_planner_handle) = test_sandbox
             .universe()
@@ -608,4 +640,111 @@ mod tests {
         test_sandbox.assert_quit().await;
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_delete_task_planner_concurrency_limit() -> anyhow::Result<()> {
+        quickwit_common::setup_logging_for_tests();
+        let index_id = "test-delete-task-planner-concurrency-limit";
+        let doc_mapping_yaml = r#"
+            field_mappings:
+              - name: body
+                type: text
+        "#;
+        let indexing_settings_yaml = r#"
+            merge_policy:
+                type: no_merge
+        "#;
+        let test_sandbox = TestSandbox::create(
+            index_id,
+            doc_mapping_yaml,
+            indexing_settings_yaml,
+            &["body"],
+        )
+        .await?;
+        // Creates 3 splits, each containing a document to delete.
+        for _ in 0..3 {
+            test_sandbox
+                .add_documents(vec![serde_json::json!({"body": "delete"})])
+                .await?;
+        }
+        let mut metastore = test_sandbox.metastore();
+        let index_metadata = metastore
+            .index_metadata(IndexMetadataRequest::for_index_id(index_id.to_string()))
+            .await
+            .unwrap()
+            .deserialize_index_metadata()
+            .unwrap();
+        let index_uid = index_metadata.index_uid.clone();
+        let index_config = index_metadata.into_index_config();
+        let mut split_ids: Vec<String> = metastore
+            .list_splits(ListSplitsRequest::try_from_index_uid(index_uid.clone()).unwrap())
+            .await
+            .unwrap()
+            .collect_splits_metadata()
+            .await
+            .unwrap()
+            .iter()
+            .map(|split_metadata| split_metadata.split_id().to_string())
+            .collect();
+        split_ids.sort();
+        assert_eq!(split_ids.len(), 3);
+        let doc_mapper =
+            build_doc_mapper(&index_config.doc_mapping, &index_config.search_settings)?;
+        let doc_mapper_str = serde_json::to_string(&doc_mapper)?;
+
+        let body_delete_ast = quickwit_query::query_ast::qast_json_helper("body:delete", &[]);
+        metastore
+            .create_delete_task(DeleteQuery {
+                index_uid: index_uid.to_string(),
+                start_timestamp: None,
+                end_timestamp: None,
+                query_ast: body_delete_ast,
+            })
+            .await?;
+        let mut mock_search_service = MockSearchService::new();
+        mock_search_service
+            .expect_leaf_search()
+            .returning(|_: LeafSearchRequest| {
+                Ok(LeafSearchResponse {
+                    num_hits: 1,
+                    ..Default::default()
+                })
+            });
+        let searcher_pool = searcher_pool_for_test([("127.0.0.1:1000", mock_search_service)]);
+        let search_job_placer = SearchJobPlacer::new(searcher_pool);
+        let (downloader_mailbox, downloader_inbox) = test_sandbox.universe().create_test_mailbox();
+        let delete_task_permits = Arc::new(Semaphore::new(1));
+        let delete_planner = DeleteTaskPlanner::new(
+            index_uid,
+            index_config.index_uri.clone(),
+            doc_mapper_str,
+            metastore,
+            search_job_placer,
+            downloader_mailbox,
+            delete_task_permits.clone(),
+        );
+        let (_delete_planner_mailbox, delete_planner_handle) = test_sandbox
+            .universe()
+            .spawn_builder()
+            .spawn(delete_planner);
+
+        let mut delete_split_ids = Vec::new();
+        for _ in 0..3 {
+            let delete_operation: TrackedObject<MergeOperation> =
+                downloader_inbox.recv_typed_message().await.unwrap();
+            // The operation holds the only permit, so no other delete operation can be sent
+            // while it is ongoing.
+            assert_eq!(delete_task_permits.available_permits(), 0);
+            assert!(downloader_inbox.drain_for_test().is_empty());
+
+            delete_split_ids.push(delete_operation.splits[0].split_id().to_string());
+            drop(delete_operation);
+        }
+        delete_split_ids.sort();
+        assert_eq!(delete_split_ids, split_ids);
+
+        delete_planner_handle.kill().await;
+        test_sandbox.assert_quit().await;
+        Ok(())
+    }
 }
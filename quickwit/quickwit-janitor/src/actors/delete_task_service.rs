@@ -19,13 +19,16 @@
 
 use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
+use bytesize::ByteSize;
 use quickwit_actors::{Actor, ActorContext, ActorExitStatus, ActorHandle, Handler};
 use quickwit_common::pubsub::EventBroker;
 use quickwit_common::temp_dir::{self};
-use quickwit_config::IndexConfig;
+use quickwit_config::{IndexConfig, JanitorConfig};
+use quickwit_indexing::ScratchSpaceQuota;
 use quickwit_metastore::{IndexMetadataResponseExt, ListIndexesMetadataResponseExt};
 use quickwit_proto::metastore::{
     IndexMetadataRequest, ListIndexesMetadataRequest, MetastoreService, MetastoreServiceClient,
@@ -34,6 +37,7 @@ use quickwit_proto::types::IndexUid;
 use quickwit_search::SearchJobPlacer;
 use quickwit_storage::StorageResolver;
 use serde::Serialize;
+use tokio::sync::Semaphore;
 use tracing::{error, info, warn};
 
 use super::delete_task_pipeline::DeleteTaskPipeline;
@@ -60,16 +64,24 @@ pub struct DeleteTaskService {
     delete_service_task_dir: PathBuf,
     pipeline_handles_by_index_uid: HashMap<IndexUid, ActorHandle<DeleteTaskPipeline>>,
     max_concurrent_split_uploads: usize,
+    /// Permits shared by the pipelines, bounding the number of delete tasks executing
+    /// concurrently.
+    delete_task_permits: Arc<Semaphore>,
+    max_delete_task_throughput_opt: Option<ByteSize>,
+    scratch_space_quota: ScratchSpaceQuota,
     event_broker: EventBroker,
 }
 
 impl DeleteTaskService {
+    #[allow(clippy::too_many_arguments)]
     pub async fn new(
         metastore: MetastoreServiceClient,
         search_job_placer: SearchJobPlacer,
         storage_resolver: StorageResolver,
         data_dir_path: PathBuf,
         max_concurrent_split_uploads: usize,
+        janitor_config: &JanitorConfig,
+        scratch_space_quota: ScratchSpaceQuota,
         event_broker: EventBroker,
     ) -> anyhow::Result<Self> {
         let delete_service_task_path = data_dir_path.join(DELETE_SERVICE_TASK_DIR_NAME);
@@ -82,6 +94,11 @@ impl DeleteTaskService {
             delete_service_task_dir,
             pipeline_handles_by_index_uid: Default::default(),
             max_concurrent_split_uploads,
+            delete_task_permits: Arc::new(Semaphore::new(
+                janitor_config.max_concurrent_delete_tasks.get(),
+            )),
+            max_delete_task_throughput_opt: janitor_config.max_delete_task_throughput,
+            scratch_space_quota,
             event_broker,
         })
     }
@@ -180,6 +197,9 @@ impl DeleteTaskService {
             index_storage,
             self.delete_service_task_dir.clone(),
             self.max_concurrent_split_uploads,
+            self.delete_task_permits.clone(),
+            self.max_delete_task_throughput_opt,
+            self.scratch_space_quota.clone(),
             self.event_broker.clone(),
         );
         let (_pipeline_mailbox, pipeline_handler) = ctx.spawn_actor().spawn(pipeline);
@@ -213,14 +233,14 @@ impl Handler<UpdatePipelines> for DeleteTaskService {
 #[cfg(test)]
 mod tests {
     use quickwit_common::pubsub::EventBroker;
-    use quickwit_indexing::TestSandbox;
+    use quickwit_indexing::{ScratchSpaceQuota, TestSandbox};
     use quickwit_proto::metastore::{
         DeleteIndexRequest, DeleteQuery, ListDeleteTasksRequest, MetastoreService,
     };
     use quickwit_search::{searcher_pool_for_test, MockSearchService, SearchJobPlacer};
     use quickwit_storage::StorageResolver;
 
-    use super::{DeleteTaskService, UPDATE_PIPELINES_INTERVAL};
+    use super::{DeleteTaskService, JanitorConfig, UPDATE_PIPELINES_INTERVAL};
 
     #[tokio::test]
     async fn test_delete_task_service() -> anyhow::Result<()> {
@@ -248,6 +268,8 @@ mod tests {
             StorageResolver::unconfigured(),
             data_dir_path,
             4,
+            &JanitorConfig::default(),
+            ScratchSpaceQuota::unlimited(),
             EventBroker::default(),
         )
         .await
@@ -26,7 +26,8 @@ use futures::{stream, StreamExt};
 use itertools::Itertools;
 use quickwit_actors::{Actor, ActorContext, Handler};
 use quickwit_common::shared_consts::DELETION_GRACE_PERIOD;
-use quickwit_index_management::run_garbage_collect;
+use quickwit_config::JanitorConfig;
+use quickwit_index_management::{delete_orphan_split_files, run_garbage_collect};
 use quickwit_metastore::ListIndexesMetadataResponseExt;
 use quickwit_proto::metastore::{
     ListIndexesMetadataRequest, MetastoreService, MetastoreServiceClient,
@@ -60,6 +61,8 @@ pub struct GarbageCollectorCounters {
     pub num_failed_storage_resolution: usize,
     /// The number of splits that were unable to be removed.
     pub num_failed_splits: usize,
+    /// The number of deleted split files that were not recorded in the metastore.
+    pub num_deleted_orphan_files: usize,
 }
 
 #[derive(Debug)]
@@ -69,14 +72,24 @@ struct Loop;
 pub struct GarbageCollector {
     metastore: MetastoreServiceClient,
     storage_resolver: StorageResolver,
+    /// The grace period of the orphan split files, if their deletion is enabled.
+    orphan_split_files_grace_period_opt: Option<Duration>,
     counters: GarbageCollectorCounters,
 }
 
 impl GarbageCollector {
-    pub fn new(metastore: MetastoreServiceClient, storage_resolver: StorageResolver) -> Self {
+    pub fn new(
+        metastore: MetastoreServiceClient,
+        storage_resolver: StorageResolver,
+        janitor_config: &JanitorConfig,
+    ) -> Self {
+        let orphan_split_files_grace_period_opt = janitor_config
+            .enable_orphan_split_files_gc
+            .then_some(janitor_config.orphan_split_files_grace_period);
         Self {
             metastore,
             storage_resolver,
+            orphan_split_files_grace_period_opt,
             counters: GarbageCollectorCounters::default(),
         }
     }
@@ -102,7 +115,10 @@ impl GarbageCollector {
         };
         info!(index_ids=%indexes.iter().map(|im| im.index_id()).join(", "), "garbage collecting indexes");
 
-        let mut gc_futures = stream::iter(indexes).map(|index| {
+        let indexes_ref = &indexes;
+        let orphan_split_files_grace_period_opt = self.orphan_split_files_grace_period_opt;
+
+        let mut gc_futures = stream::iter(indexes.iter().cloned()).map(|index| {
             let metastore = self.metastore.clone();
             let storage_resolver = self.storage_resolver.clone();
             async move {
@@ -117,21 +133,44 @@ impl GarbageCollector {
             let index_uid = index.index_uid;
             let gc_res = run_garbage_collect(
                 index_uid.clone(),
-                storage,
-                metastore,
+                storage.clone(),
+                metastore.clone(),
                 STAGED_GRACE_PERIOD,
                 DELETION_GRACE_PERIOD,
                 false,
                 Some(ctx.progress()),
             ).await;
-            Some((index_uid, gc_res))
+            let orphan_gc_res_opt = if let Some(grace_period) = orphan_split_files_grace_period_opt {
+                let orphan_gc_res = delete_orphan_split_files(
+                    index_uid.clone(),
+                    storage,
+                    metastore,
+                    indexes_ref,
+                    grace_period,
+                    false,
+                    Some(ctx.progress()),
+                ).await;
+                Some(orphan_gc_res)
+            } else {
+                None
+            };
+            Some((index_uid, gc_res, orphan_gc_res_opt))
         }}).buffer_unordered(MAX_CONCURRENT_GC_TASKS);
 
         while let Some(gc_future_res) = gc_futures.next().await {
-            let Some((index_uid, gc_res)) = gc_future_res else {
+            let Some((index_uid, gc_res, orphan_gc_res_opt)) = gc_future_res else {
                 self.counters.num_failed_storage_resolution += 1;
                 continue;
             };
+            match orphan_gc_res_opt {
+                Some(Ok(deleted_orphan_files)) => {
+                    self.counters.num_deleted_orphan_files += deleted_orphan_files.len();
+                }
+                Some(Err(error)) => {
+                    error!(index_id=%index_uid.index_id(), error=?error, "failed to delete orphan split files");
+                }
+                None => {}
+            }
             let deleted_file_entries = match gc_res {
                 Ok(removal_info) => {
                     self.counters.num_successful_gc_run_on_index += 1;
@@ -207,11 +246,13 @@ impl Handler<Loop> for GarbageCollector {
 #[cfg(test)]
 mod tests {
     use std::ops::Bound;
-    use std::path::Path;
+    use std::path::{Path, PathBuf};
+    use std::str::FromStr;
     use std::sync::Arc;
 
     use quickwit_actors::Universe;
     use quickwit_common::shared_consts::DELETION_GRACE_PERIOD;
+    use quickwit_common::uri::Uri;
     use quickwit_common::ServiceStream;
     use quickwit_metastore::{
         IndexMetadata, ListSplitsRequestExt, ListSplitsResponseExt, Split, SplitMetadata,
@@ -223,6 +264,7 @@ mod tests {
     use quickwit_proto::types::IndexUid;
     use quickwit_storage::MockStorage;
     use time::OffsetDateTime;
+    use ulid::Ulid;
 
     use super::*;
 
@@ -404,6 +446,7 @@ mod tests {
         let garbage_collect_actor = GarbageCollector::new(
             MetastoreServiceClient::from(mock_metastore),
             storage_resolver,
+            &JanitorConfig::default(),
         );
         let universe = Universe::with_accelerated_time();
         let (_mailbox, handler) = universe.spawn_builder().spawn(garbage_collect_actor);
@@ -480,6 +523,7 @@ mod tests {
         let garbage_collect_actor = GarbageCollector::new(
             MetastoreServiceClient::from(mock_metastore),
             storage_resolver,
+            &JanitorConfig::default(),
         );
         let universe = Universe::with_accelerated_time();
         let (_mailbox, handle) = universe.spawn_builder().spawn(garbage_collect_actor);
@@ -517,6 +561,67 @@ mod tests {
         universe.assert_quit().await;
     }
 
+    #[tokio::test]
+    async fn test_garbage_collect_deletes_orphan_split_files_if_enabled() {
+        let storage_resolver = StorageResolver::unconfigured();
+        let two_days_ago_millis =
+            (OffsetDateTime::now_utc().unix_timestamp() as u64 - 2 * 24 * 60 * 60) * 1_000;
+        let mut orphan_split_paths = Vec::new();
+
+        for index_id in ["test-index-1", "test-index-2"] {
+            let index_uri = Uri::from_str(&format!("ram:///indexes/{index_id}")).unwrap();
+            let storage = storage_resolver.resolve(&index_uri).await.unwrap();
+            let split_id = Ulid::from_parts(two_days_ago_millis, 1).to_string();
+            let split_path = PathBuf::from(format!("{split_id}.split"));
+            storage.put(&split_path, Box::new(vec![0])).await.unwrap();
+            orphan_split_paths.push((storage, split_path));
+        }
+        let mut mock_metastore = MetastoreServiceClient::mock();
+        // The indexes are listed once per run, not once per index.
+        mock_metastore
+            .expect_list_indexes_metadata()
+            .times(1)
+            .returning(|_list_indexes_metadata| {
+                let indexes_metadata = vec![
+                    IndexMetadata::for_test("test-index-1", "ram:///indexes/test-index-1"),
+                    IndexMetadata::for_test("test-index-2", "ram:///indexes/test-index-2"),
+                ];
+                Ok(
+                    ListIndexesMetadataResponse::try_from_indexes_metadata(indexes_metadata)
+                        .unwrap(),
+                )
+            });
+        mock_metastore
+            .expect_list_splits()
+            .times(6)
+            .returning(|_list_splits_request| {
+                let splits = ListSplitsResponse::try_from_splits(Vec::new()).unwrap();
+                Ok(ServiceStream::from(vec![Ok(splits)]))
+            });
+        let janitor_config = JanitorConfig {
+            enable_orphan_split_files_gc: true,
+            orphan_split_files_grace_period: Duration::from_secs(60 * 60),
+            ..Default::default()
+        };
+        let garbage_collect_actor = GarbageCollector::new(
+            MetastoreServiceClient::from(mock_metastore),
+            storage_resolver,
+            &janitor_config,
+        );
+        let universe = Universe::with_accelerated_time();
+        let (_mailbox, handle) = universe.spawn_builder().spawn(garbage_collect_actor);
+
+        let counters = handle.process_pending_and_observe().await.state;
+        assert_eq!(counters.num_passes, 1);
+        assert_eq!(counters.num_deleted_orphan_files, 2);
+        assert_eq!(counters.num_successful_gc_run_on_index, 2);
+
+        for (storage, split_path) in orphan_split_paths {
+            assert!(!storage.exists(&split_path).await.unwrap());
+        }
+        universe.assert_quit().await;
+    }
+
     #[tokio::test]
     async fn test_garbage_collect_get_called_repeatedly_on_failure() {
         let storage_resolver = StorageResolver::unconfigured();
@@ -533,6 +638,7 @@ mod tests {
         let garbage_collect_actor = GarbageCollector::new(
             MetastoreServiceClient::from(mock_metastore),
             storage_resolver,
+            &JanitorConfig::default(),
         );
         let universe = Universe::with_accelerated_time();
         let (_mailbox, handle) = universe.spawn_builder().spawn(garbage_collect_actor);
@@ -571,6 +677,7 @@ mod tests {
         let garbage_collect_actor = GarbageCollector::new(
             MetastoreServiceClient::from(mock_metastore),
             storage_resolver,
+            &JanitorConfig::default(),
         );
         let universe = Universe::with_accelerated_time();
         let (_mailbox, handle) = universe.spawn_builder().spawn(garbage_collect_actor);
@@ -653,6 +760,7 @@ mod tests {
         let garbage_collect_actor = GarbageCollector::new(
             MetastoreServiceClient::from(mock_metastore),
             storage_resolver,
+            &JanitorConfig::default(),
         );
         let universe = Universe::with_accelerated_time();
         let (_mailbox, handle) = universe.spawn_builder().spawn(garbage_collect_actor);
@@ -740,6 +848,7 @@ mod tests {
         let garbage_collect_actor = GarbageCollector::new(
             MetastoreServiceClient::from(mock_metastore),
             storage_resolver,
+            &JanitorConfig::default(),
         );
         let universe = Universe::with_accelerated_time();
         let (_mailbox, handle) = universe.spawn_builder().spawn(garbage_collect_actor);
@@ -0,0 +1,196 @@
+// Copyright (C) 2024 Quickwit, Inc.
+//
+// Quickwit is offered under the AGPL v3.0 and as commercial software.
+// For commercial licensing, contact us at hello@quickwit.io.
+//
+// AGPL:
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as
+// published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::{BTreeSet, HashMap};
+
+use quickwit_doc_mapper::DocMapper;
+use quickwit_proto::metastore::DeleteQuery;
+use quickwit_proto::types::IndexUid;
+use quickwit_query::query_ast::{QueryAst, TermSetQuery};
+use tantivy::schema::FieldType;
+
+use crate::error::JanitorError;
+
+/// Name of the field holding the document IDs, which must be mapped to delete documents by ID.
+pub const DOC_ID_FIELD_NAME: &str = "_id";
+
+/// Maximum number of distinct document IDs of a delete query. The query is stored in the
+/// metastore and evaluated against every stale split of the index.
+pub const MAX_NUM_DOC_IDS_PER_DELETE_QUERY: usize = 1_000;
+
+/// Tokenizer the [`DOC_ID_FIELD_NAME`] field must be indexed with, so that its terms are the
+/// document IDs verbatim.
+const DOC_ID_TOKENIZER_NAME: &str = "raw";
+
+/// Builds a delete query matching the documents whose [`DOC_ID_FIELD_NAME`] field holds one of
+/// `doc_ids`.
+pub fn delete_query_for_doc_ids(
+    index_uid: IndexUid,
+    doc_mapper: &dyn DocMapper,
+    doc_ids: Vec<String>,
+) -> Result<DeleteQuery, JanitorError> {
+    let schema = doc_mapper.schema();
+
+    let Ok(doc_id_field) = schema.get_field(DOC_ID_FIELD_NAME) else {
+        return Err(JanitorError::InvalidDeleteQuery(format!(
+            "deleting documents by ID requires a `{DOC_ID_FIELD_NAME}` field mapping"
+        )));
+    };
+    // With any other tokenizer, the IDs would not match the indexed terms and the query would
+    // silently delete nothing.
+    let is_raw_tokenized = match schema.get_field_entry(doc_id_field).field_type() {
+        FieldType::Str(text_options) => text_options
+            .get_indexing_options()
+            .map(|indexing_options| indexing_options.tokenizer() == DOC_ID_TOKENIZER_NAME)
+            .unwrap_or(false),
+        _ => false,
+    };
+    if !is_raw_tokenized {
+        return Err(JanitorError::InvalidDeleteQuery(format!(
+            "deleting documents by ID requires the `{DOC_ID_FIELD_NAME}` field to be an indexed \
+             `text` field with the `{DOC_ID_TOKENIZER_NAME}` tokenizer"
+        )));
+    }
+    let doc_ids: BTreeSet<String> = doc_ids.into_iter().collect();
+
+    if doc_ids.is_empty() {
+        return Err(JanitorError::InvalidDeleteQuery(
+            "at least one document ID must be provided".to_string(),
+        ));
+    }
+    if doc_ids.len() > MAX_NUM_DOC_IDS_PER_DELETE_QUERY {
+        return Err(JanitorError::InvalidDeleteQuery(format!(
+            "too many document IDs: {} were provided, the maximum is \
+             {MAX_NUM_DOC_IDS_PER_DELETE_QUERY}",
+            doc_ids.len()
+        )));
+    }
+    let terms_per_field = HashMap::from([(DOC_ID_FIELD_NAME.to_string(), doc_ids)]);
+    let query_ast: QueryAst = TermSetQuery { terms_per_field }.into();
+
+    // The field must be indexed for the delete query to be executed.
+    doc_mapper
+        .query(schema, &query_ast, true)
+        .map_err(|error| JanitorError::InvalidDeleteQuery(error.to_string()))?;
+    let query_ast_json = serde_json::to_string(&query_ast).map_err(|_err| {
+        JanitorError::Internal("failed to serialized delete query ast".to_string())
+    })?;
+    let delete_query = DeleteQuery {
+        index_uid: index_uid.to_string(),
+        start_timestamp: None,
+        end_timestamp: None,
+        query_ast: query_ast_json,
+    };
+    Ok(delete_query)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use quickwit_config::{build_doc_mapper, DocMapping, SearchSettings};
+
+    use super::*;
+
+    fn doc_mapper_for_test(doc_mapping_json: serde_json::Value) -> Arc<dyn DocMapper> {
+        let doc_mapping: DocMapping = serde_json::from_value(doc_mapping_json).unwrap();
+        build_doc_mapper(&doc_mapping, &SearchSettings::default()).unwrap()
+    }
+
+    #[test]
+    fn test_delete_query_for_doc_ids() {
+        let index_uid = IndexUid::new_with_random_ulid("test-index");
+        let doc_mapper = doc_mapper_for_test(serde_json::json!({
+            "field_mappings": [
+                {"name": "_id", "type": "text", "tokenizer": "raw"},
+                {"name": "body", "type": "text"}
+            ]
+        }));
+        let delete_query = delete_query_for_doc_ids(
+            index_uid.clone(),
+            &*doc_mapper,
+            vec!["doc-2".to_string(), "doc-1".to_string()],
+        )
+        .unwrap();
+        assert_eq!(delete_query.index_uid, index_uid.to_string());
+        assert_eq!(
+            delete_query.query_ast,
+            r#"{"type":"term_set","terms_per_field":{"_id":["doc-1","doc-2"]}}"#
+        );
+
+        let error =
+            delete_query_for_doc_ids(index_uid.clone(), &*doc_mapper, Vec::new()).unwrap_err();
+        assert!(matches!(error, JanitorError::InvalidDeleteQuery(_)));
+
+        let doc_mapper = doc_mapper_for_test(serde_json::json!({
+            "field_mappings": [{"name": "body", "type": "text"}]
+        }));
+        let error = delete_query_for_doc_ids(index_uid, &*doc_mapper, vec!["doc-1".to_string()])
+            .unwrap_err();
+        assert!(
+            matches!(error, JanitorError::InvalidDeleteQuery(message) if message.contains("`_id` field mapping"))
+        );
+    }
+
+    #[test]
+    fn test_delete_query_for_doc_ids_requires_raw_tokenized_id() {
+        let index_uid = IndexUid::new_with_random_ulid("test-index");
+
+        for id_field_mapping in [
+            serde_json::json!({"name": "_id", "type": "text"}),
+            serde_json::json!({"name": "_id", "type": "text", "tokenizer": "lowercase"}),
+            serde_json::json!({"name": "_id", "type": "u64"}),
+        ] {
+            let doc_mapper = doc_mapper_for_test(serde_json::json!({
+                "field_mappings": [id_field_mapping]
+            }));
+            let error = delete_query_for_doc_ids(
+                index_uid.clone(),
+                &*doc_mapper,
+                vec!["doc-1".to_string()],
+            )
+            .unwrap_err();
+            assert!(
+                matches!(error, JanitorError::InvalidDeleteQuery(message) if message.contains("`raw` tokenizer"))
+            );
+        }
+    }
+
+    #[test]
+    fn test_delete_query_for_doc_ids_max_num_doc_ids() {
+        let index_uid = IndexUid::new_with_random_ulid("test-index");
+        let doc_mapper = doc_mapper_for_test(serde_json::json!({
+            "field_mappings": [{"name": "_id", "type": "text", "tokenizer": "raw"}]
+        }));
+        // Duplicate IDs are only counted once.
+        let doc_ids = (0..MAX_NUM_DOC_IDS_PER_DELETE_QUERY)
+            .map(|doc_ord| format!("doc-{doc_ord}"))
+            .chain(["doc-0".to_string()])
+            .collect();
+        delete_query_for_doc_ids(index_uid.clone(), &*doc_mapper, doc_ids).unwrap();
+
+        let doc_ids = (0..=MAX_NUM_DOC_IDS_PER_DELETE_QUERY)
+            .map(|doc_ord| format!("doc-{doc_ord}"))
+            .collect();
+        let error = delete_query_for_doc_ids(index_uid, &*doc_mapper, doc_ids).unwrap_err();
+        assert!(
+            matches!(error, JanitorError::InvalidDeleteQuery(message) if message.contains("too many document IDs"))
+        );
+    }
+}
@@ -22,6 +22,7 @@
 use quickwit_actors::{Mailbox, Universe};
 use quickwit_common::pubsub::EventBroker;
 use quickwit_config::NodeConfig;
+use quickwit_indexing::ScratchSpaceQuota;
 use quickwit_metastore::SplitInfo;
 use quickwit_proto::metastore::MetastoreServiceClient;
 use quickwit_search::SearchJobPlacer;
@@ -29,11 +30,15 @@ use quickwit_storage::StorageResolver;
 use tracing::info;
 
 pub mod actors;
+mod delete_query;
 pub mod error;
 mod janitor_service;
 mod metrics;
 mod retention_policy_execution;
 
+pub use delete_query::{
+    delete_query_for_doc_ids, DOC_ID_FIELD_NAME, MAX_NUM_DOC_IDS_PER_DELETE_QUERY,
+};
 pub use janitor_service::JanitorService;
 
 use crate::actors::{DeleteTaskService, GarbageCollector, RetentionPolicyExecutor};
@@ -52,18 +57,31 @@ pub async fn start_janitor_service(
     event_broker: EventBroker,
 ) -> anyhow::Result<Mailbox<JanitorService>> {
     info!("starting janitor service");
-    let garbage_collector = GarbageCollector::new(metastore.clone(), storage_resolver.clone());
+    let garbage_collector = GarbageCollector::new(
+        metastore.clone(),
+        storage_resolver.clone(),
+        &config.janitor_config,
+    );
     let (_, garbage_collector_handle) = universe.spawn_builder().spawn(garbage_collector);
 
     let retention_policy_executor = RetentionPolicyExecutor::new(metastore.clone());
     let (_, retention_policy_executor_handle) =
         universe.spawn_builder().spawn(retention_policy_executor);
+    // Delete tasks download and rewrite splits in their own scratch directory, which is
+    // bounded by the same setting as the indexer's.
+    let scratch_space_quota = config
+        .indexer_config
+        .scratch_max_bytes
+        .map(ScratchSpaceQuota::new)
+        .unwrap_or_default();
     let delete_task_service = DeleteTaskService::new(
         metastore,
         search_job_placer,
         storage_resolver,
         config.data_dir_path.clone(),
         config.indexer_config.max_concurrent_split_uploads,
+        &config.janitor_config,
+        scratch_space_quota,
         event_broker,
     )
     .await?;
@@ -18,10 +18,11 @@
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
 use once_cell::sync::Lazy;
-use quickwit_common::metrics::{new_gauge_vec, IntGaugeVec};
+use quickwit_common::metrics::{new_gauge, new_gauge_vec, IntGauge, IntGaugeVec};
 
 pub struct JanitorMetrics {
     pub ongoing_num_delete_operations_total: IntGaugeVec<1>,
+    pub num_active_delete_tasks: IntGauge,
 }
 
 impl Default for JanitorMetrics {
@@ -33,6 +34,11 @@ impl Default for JanitorMetrics {
                 "quickwit_janitor",
                 ["index"],
             ),
+            num_active_delete_tasks: new_gauge(
+                "num_active_delete_tasks",
+                "Number of delete tasks executing on the node (all indexes).",
+                "quickwit_janitor",
+            ),
         }
     }
 }
@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS index_templates (
+    template_id VARCHAR(255) NOT NULL,
+    index_template_json TEXT NOT NULL,
+    PRIMARY KEY (template_id)
+);
@@ -48,16 +48,18 @@ pub(crate) use metastore::index_metadata::serialize::{IndexMetadataV0_7, Version
 #[cfg(feature = "postgres")]
 pub use metastore::postgres::PostgresqlMetastore;
 pub use metastore::{
-    file_backed, AddSourceRequestExt, CreateIndexRequestExt, IndexMetadata,
-    IndexMetadataResponseExt, ListIndexesMetadataResponseExt, ListSplitsQuery,
+    file_backed, AddSourceRequestExt, CreateIndexRequestExt, CreateIndexTemplateRequestExt,
+    IndexMetadata, IndexMetadataResponseExt, ListIndexAliasesResponseExt,
+    ListIndexTemplatesResponseExt, ListIndexesMetadataResponseExt, ListSplitsQuery,
     ListSplitsRequestExt, ListSplitsResponseExt, MetastoreServiceExt,
     MetastoreServiceStreamSplitsExt, PublishSplitsRequestExt, StageSplitsRequestExt,
+    UpdateIndexAliasesRequestExt,
 };
 pub use metastore_factory::{MetastoreFactory, UnsupportedMetastore};
 pub use metastore_resolver::MetastoreResolver;
 use quickwit_common::is_disjoint;
 use quickwit_doc_mapper::tag_pruning::TagFilterAst;
-pub use split_metadata::{Split, SplitInfo, SplitMaturity, SplitMetadata, SplitState};
+pub use split_metadata::{FieldRange, Split, SplitInfo, SplitMaturity, SplitMetadata, SplitState};
 pub(crate) use split_metadata_version::{SplitMetadataV0_7, VersionedSplitMetadata};
 
 #[derive(utoipa::OpenApi)]
@@ -18,23 +18,39 @@
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
 use std::fmt;
+use std::future::Future;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
+use futures::TryStreamExt;
+use lru::LruCache;
+use quickwit_common::pubsub::{EventBroker, EventSubscriber};
 use quickwit_common::uri::Uri;
+use quickwit_common::ServiceStream;
 use quickwit_proto::control_plane::{ControlPlaneService, ControlPlaneServiceClient};
 use quickwit_proto::metastore::{
     AcquireShardsRequest, AcquireShardsResponse, AddSourceRequest, CreateIndexRequest,
-    CreateIndexResponse, DeleteIndexRequest, DeleteQuery, DeleteShardsRequest,
-    DeleteShardsResponse, DeleteSourceRequest, DeleteSplitsRequest, DeleteTask, EmptyResponse,
-    IndexMetadataRequest, IndexMetadataResponse, LastDeleteOpstampRequest,
-    LastDeleteOpstampResponse, ListDeleteTasksRequest, ListDeleteTasksResponse,
+    CreateIndexResponse, CreateIndexTemplateRequest, DeleteIndexRequest,
+    DeleteIndexTemplatesRequest, DeleteQuery, DeleteShardsRequest, DeleteShardsResponse,
+    DeleteSourceRequest, DeleteSplitsRequest, DeleteTask, EmptyResponse, IndexMetadataRequest,
+    IndexMetadataResponse, LastDeleteOpstampRequest, LastDeleteOpstampResponse,
+    ListDeleteTasksRequest, ListDeleteTasksResponse, ListIndexAliasesRequest,
+    ListIndexAliasesResponse, ListIndexTemplatesRequest, ListIndexTemplatesResponse,
     ListIndexesMetadataRequest, ListIndexesMetadataResponse, ListShardsRequest, ListShardsResponse,
     ListSplitsRequest, ListSplitsResponse, ListStaleSplitsRequest, MarkSplitsForDeletionRequest,
     MetastoreResult, MetastoreService, MetastoreServiceClient, MetastoreServiceStream,
-    OpenShardsRequest, OpenShardsResponse, PublishSplitsRequest, ResetSourceCheckpointRequest,
-    StageSplitsRequest, ToggleSourceRequest, UpdateSplitsDeleteOpstampRequest,
-    UpdateSplitsDeleteOpstampResponse,
+    OpenShardsRequest, OpenShardsResponse, OverrideSplitMaturityRequest, PublishSplitsRequest,
+    RenameIndexRequest, RenameIndexResponse, ResetSourceCheckpointRequest, StageSplitsRequest,
+    ToggleSourceRequest, UpdateIndexAliasesRequest, UpdateIndexUriRequest,
+    UpdateSplitsDeleteOpstampRequest, UpdateSplitsDeleteOpstampResponse,
 };
+use quickwit_storage::AsyncDebouncer;
+use serde::Serialize;
+
+use crate::ListSplitsRequestExt;
 
 /// A [`MetastoreService`] implementation that proxies some requests to the control plane so it can
 /// track the state of the metastore accurately and react to events in real-time.
@@ -42,6 +58,8 @@ use quickwit_proto::metastore::{
 pub struct ControlPlaneMetastore {
     control_plane: ControlPlaneServiceClient,
     metastore: MetastoreServiceClient,
+    list_splits_cache_opt: Option<ListSplitsCache>,
+    read_debouncers: Arc<ReadDebouncers>,
 }
 
 impl fmt::Debug for ControlPlaneMetastore {
@@ -59,8 +77,208 @@ impl ControlPlaneMetastore {
         Self {
             control_plane,
             metastore,
+            list_splits_cache_opt: None,
+            read_debouncers: Arc::default(),
+        }
+    }
+
+    /// Caches the responses of `list_splits` requests for `ttl`, keeping at most `capacity`
+    /// responses. The cached responses of an index are invalidated as soon as the splits of the
+    /// index are staged, published, marked for deletion, or deleted, provided the corresponding
+    /// events are published on `event_broker`.
+    pub fn with_list_splits_cache(
+        mut self,
+        ttl: Duration,
+        capacity: NonZeroUsize,
+        event_broker: &EventBroker,
+    ) -> Self {
+        let list_splits_cache = ListSplitsCache::new(ttl, capacity);
+        list_splits_cache.subscribe(event_broker);
+        self.list_splits_cache_opt = Some(list_splits_cache);
+        self
+    }
+
+    /// Evicts the cached `list_splits` responses of an index whose splits were updated through
+    /// this metastore, without waiting for the corresponding event.
+    fn invalidate_list_splits_cache(&self, index_uid: &str) {
+        if let Some(list_splits_cache) = &self.list_splits_cache_opt {
+            evict_index_uid(&list_splits_cache.entries, index_uid);
+        }
+    }
+}
+
+type ReadDebouncer<T> = AsyncDebouncer<String, MetastoreResult<T>>;
+
+/// Coalesces identical concurrent read requests into a single call to the metastore, so that a
+/// burst of identical reads does not hit the backend once per request. The requests are keyed by
+/// their JSON serialization and the current write generation.
+///
+/// Only in-flight calls are shared. The write generation is bumped every time a write issued
+/// through this metastore completes, so a read issued after a write never receives the response of
+/// a read started before it. Writes are never coalesced.
+#[derive(Default)]
+struct ReadDebouncers {
+    write_generation: AtomicU64,
+    index_metadata: ReadDebouncer<IndexMetadataResponse>,
+    list_indexes_metadata: ReadDebouncer<ListIndexesMetadataResponse>,
+    list_stale_splits: ReadDebouncer<ListSplitsResponse>,
+    list_index_templates: ReadDebouncer<ListIndexTemplatesResponse>,
+    list_index_aliases: ReadDebouncer<ListIndexAliasesResponse>,
+    last_delete_opstamp: ReadDebouncer<LastDeleteOpstampResponse>,
+    list_delete_tasks: ReadDebouncer<ListDeleteTasksResponse>,
+    list_shards: ReadDebouncer<ListShardsResponse>,
+}
+
+impl ReadDebouncers {
+    fn bump_write_generation(&self) {
+        self.write_generation.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+async fn coalesce_read<R, T, F, Fut>(
+    debouncer: &ReadDebouncer<T>,
+    write_generation: &AtomicU64,
+    metastore: &MetastoreServiceClient,
+    request: R,
+    read: F,
+) -> MetastoreResult<T>
+where
+    R: Serialize,
+    T: Clone + Send + 'static,
+    F: FnOnce(MetastoreServiceClient, R) -> Fut,
+    Fut: Future<Output = MetastoreResult<T>> + Send + 'static,
+{
+    let request_json =
+        serde_json::to_string(&request).expect("request should be JSON serializable");
+    let key = format!(
+        "{}:{request_json}",
+        write_generation.load(Ordering::Relaxed)
+    );
+    let metastore = metastore.clone();
+    debouncer
+        .get_or_create(key, move || read(metastore, request))
+        .await
+}
+
+struct CachedListSplitsResponses {
+    index_uids: Vec<String>,
+    responses: Vec<ListSplitsResponse>,
+    cached_at: Instant,
+}
+
+/// Short-lived cache of `list_splits` responses, keyed by the serialized list splits query.
+#[derive(Clone)]
+struct ListSplitsCache {
+    ttl: Duration,
+    entries: Arc<ListSplitsCacheEntries>,
+}
+
+impl ListSplitsCache {
+    fn new(ttl: Duration, capacity: NonZeroUsize) -> Self {
+        Self {
+            ttl,
+            entries: Arc::new(Mutex::new(LruCache::new(capacity))),
         }
     }
+
+    fn subscribe(&self, event_broker: &EventBroker) {
+        let weak_entries = WeakListSplitsCacheEntries(Arc::downgrade(&self.entries));
+        event_broker
+            .subscribe::<StageSplitsRequest>(weak_entries.clone())
+            .forever();
+        event_broker
+            .subscribe::<PublishSplitsRequest>(weak_entries.clone())
+            .forever();
+        event_broker
+            .subscribe::<MarkSplitsForDeletionRequest>(weak_entries.clone())
+            .forever();
+        event_broker
+            .subscribe::<DeleteSplitsRequest>(weak_entries)
+            .forever();
+    }
+
+    fn get(&self, query_json: &str) -> Option<Vec<ListSplitsResponse>> {
+        let mut entries_guard = self.entries.lock().expect("lock should not be poisoned");
+        let cached_responses = entries_guard.get(query_json)?;
+
+        if cached_responses.cached_at.elapsed() > self.ttl {
+            entries_guard.pop(query_json);
+            return None;
+        }
+        Some(cached_responses.responses.clone())
+    }
+
+    fn put(&self, query_json: String, index_uids: Vec<String>, responses: Vec<ListSplitsResponse>) {
+        let cached_responses = CachedListSplitsResponses {
+            index_uids,
+            responses,
+            cached_at: Instant::now(),
+        };
+        self.entries
+            .lock()
+            .expect("lock should not be poisoned")
+            .put(query_json, cached_responses);
+    }
+}
+
+type ListSplitsCacheEntries = Mutex<LruCache<String, CachedListSplitsResponses>>;
+
+/// Evicts the cached responses involving `index_uid`.
+fn evict_index_uid(entries: &ListSplitsCacheEntries, index_uid: &str) {
+    let mut entries_guard = entries.lock().expect("lock should not be poisoned");
+    let stale_query_jsons: Vec<String> = entries_guard
+        .iter()
+        .filter(|(_, cached_responses)| {
+            cached_responses
+                .index_uids
+                .iter()
+                .any(|cached_index_uid| cached_index_uid == index_uid)
+        })
+        .map(|(query_json, _)| query_json.clone())
+        .collect();
+
+    for query_json in stale_query_jsons {
+        entries_guard.pop(&query_json);
+    }
+}
+
+#[derive(Clone)]
+struct WeakListSplitsCacheEntries(Weak<ListSplitsCacheEntries>);
+
+impl WeakListSplitsCacheEntries {
+    fn invalidate(&self, index_uid: &str) {
+        if let Some(entries) = self.0.upgrade() {
+            evict_index_uid(&entries, index_uid);
+        }
+    }
+}
+
+#[async_trait]
+impl EventSubscriber<StageSplitsRequest> for WeakListSplitsCacheEntries {
+    async fn handle_event(&mut self, request: StageSplitsRequest) {
+        self.invalidate(&request.index_uid);
+    }
+}
+
+#[async_trait]
+impl EventSubscriber<PublishSplitsRequest> for WeakListSplitsCacheEntries {
+    async fn handle_event(&mut self, request: PublishSplitsRequest) {
+        self.invalidate(&request.index_uid);
+    }
+}
+
+#[async_trait]
+impl EventSubscriber<MarkSplitsForDeletionRequest> for WeakListSplitsCacheEntries {
+    async fn handle_event(&mut self, request: MarkSplitsForDeletionRequest) {
+        self.invalidate(&request.index_uid);
+    }
+}
+
+#[async_trait]
+impl EventSubscriber<DeleteSplitsRequest> for WeakListSplitsCacheEntries {
+    async fn handle_event(&mut self, request: DeleteSplitsRequest) {
+        self.invalidate(&request.index_uid);
+    }
 }
 
 #[async_trait]
@@ -79,37 +297,51 @@ impl MetastoreService for ControlPlaneMetastore {
         &mut self,
         request: CreateIndexRequest,
     ) -> MetastoreResult<CreateIndexResponse> {
-        let response = self.control_plane.create_index(request).await?;
-        Ok(response)
+        let response = self.control_plane.create_index(request).await;
+        self.read_debouncers.bump_write_generation();
+        Ok(response?)
     }
 
     async fn delete_index(
         &mut self,
         request: DeleteIndexRequest,
     ) -> MetastoreResult<EmptyResponse> {
-        let response = self.control_plane.delete_index(request).await?;
-        Ok(response)
+        let response = self.control_plane.delete_index(request).await;
+        self.read_debouncers.bump_write_generation();
+        Ok(response?)
+    }
+
+    async fn rename_index(
+        &mut self,
+        request: RenameIndexRequest,
+    ) -> MetastoreResult<RenameIndexResponse> {
+        let response = self.control_plane.rename_index(request).await;
+        self.read_debouncers.bump_write_generation();
+        Ok(response?)
     }
 
     async fn add_source(&mut self, request: AddSourceRequest) -> MetastoreResult<EmptyResponse> {
-        let response = self.control_plane.add_source(request).await?;
-        Ok(response)
+        let response = self.control_plane.add_source(request).await;
+        self.read_debouncers.bump_write_generation();
+        Ok(response?)
     }
 
     async fn toggle_source(
         &mut self,
         request: ToggleSourceRequest,
     ) -> MetastoreResult<EmptyResponse> {
-        let response = self.control_plane.clone().toggle_source(request).await?;
-        Ok(response)
+        let response = self.control_plane.clone().toggle_source(request).await;
+        self.read_debouncers.bump_write_generation();
+        Ok(response?)
     }
 
     async fn delete_source(
         &mut self,
         request: DeleteSourceRequest,
     ) -> MetastoreResult<EmptyResponse> {
-        let response = self.control_plane.delete_source(request).await?;
-        Ok(response)
+        let response = self.control_plane.delete_source(request).await;
+        self.read_debouncers.bump_write_generation();
+        Ok(response?)
     }
 
     // Other metastore API calls.
@@ -118,63 +350,205 @@ impl MetastoreService for ControlPlaneMetastore {
         &mut self,
         request: IndexMetadataRequest,
     ) -> MetastoreResult<IndexMetadataResponse> {
-        self.metastore.index_metadata(request).await
+        coalesce_read(
+            &self.read_debouncers.index_metadata,
+            &self.read_debouncers.write_generation,
+            &self.metastore,
+            request,
+            |mut metastore, request| async move { metastore.index_metadata(request).await },
+        )
+        .await
     }
 
     async fn list_indexes_metadata(
         &mut self,
         request: ListIndexesMetadataRequest,
     ) -> MetastoreResult<ListIndexesMetadataResponse> {
-        self.metastore.list_indexes_metadata(request).await
+        coalesce_read(
+            &self.read_debouncers.list_indexes_metadata,
+            &self.read_debouncers.write_generation,
+            &self.metastore,
+            request,
+            |mut metastore, request| async move { metastore.list_indexes_metadata(request).await },
+        )
+        .await
     }
 
     async fn stage_splits(
         &mut self,
         request: StageSplitsRequest,
     ) -> MetastoreResult<EmptyResponse> {
-        self.metastore.stage_splits(request).await
+        let index_uid = request.index_uid.clone();
+        let result = self.metastore.stage_splits(request).await;
+        self.invalidate_list_splits_cache(&index_uid);
+        self.read_debouncers.bump_write_generation();
+        result
     }
 
     async fn publish_splits(
         &mut self,
         request: PublishSplitsRequest,
     ) -> MetastoreResult<EmptyResponse> {
-        self.metastore.publish_splits(request).await
+        let index_uid = request.index_uid.clone();
+        let result = self.metastore.publish_splits(request).await;
+        self.invalidate_list_splits_cache(&index_uid);
+        self.read_debouncers.bump_write_generation();
+        result
     }
 
     async fn list_splits(
         &mut self,
         request: ListSplitsRequest,
     ) -> MetastoreResult<MetastoreServiceStream<ListSplitsResponse>> {
-        self.metastore.list_splits(request).await
+        let Some(list_splits_cache) = &self.list_splits_cache_opt else {
+            return self.metastore.list_splits(request).await;
+        };
+        if let Some(responses) = list_splits_cache.get(&request.query_json) {
+            return Ok(ServiceStream::from(
+                responses.into_iter().map(Ok).collect::<Vec<_>>(),
+            ));
+        }
+        let index_uids: Vec<String> = request
+            .deserialize_list_splits_query()?
+            .index_uids
+            .iter()
+            .map(|index_uid| index_uid.to_string())
+            .collect();
+        let query_json = request.query_json.clone();
+        let responses: Vec<ListSplitsResponse> = self
+            .metastore
+            .list_splits(request)
+            .await?
+            .try_collect()
+            .await?;
+        list_splits_cache.put(query_json, index_uids, responses.clone());
+        Ok(ServiceStream::from(
+            responses.into_iter().map(Ok).collect::<Vec<_>>(),
+        ))
     }
 
     async fn list_stale_splits(
         &mut self,
         request: ListStaleSplitsRequest,
     ) -> MetastoreResult<ListSplitsResponse> {
-        self.metastore.list_stale_splits(request).await
+        coalesce_read(
+            &self.read_debouncers.list_stale_splits,
+            &self.read_debouncers.write_generation,
+            &self.metastore,
+            request,
+            |mut metastore, request| async move { metastore.list_stale_splits(request).await },
+        )
+        .await
     }
 
     async fn mark_splits_for_deletion(
         &mut self,
         request: MarkSplitsForDeletionRequest,
     ) -> MetastoreResult<EmptyResponse> {
-        self.metastore.mark_splits_for_deletion(request).await
+        let index_uid = request.index_uid.clone();
+        let result = self.metastore.mark_splits_for_deletion(request).await;
+        self.invalidate_list_splits_cache(&index_uid);
+        self.read_debouncers.bump_write_generation();
+        result
     }
 
     async fn delete_splits(
         &mut self,
         request: DeleteSplitsRequest,
     ) -> MetastoreResult<EmptyResponse> {
-        self.metastore.delete_splits(request).await
+        let index_uid = request.index_uid.clone();
+        let result = self.metastore.delete_splits(request).await;
+        self.invalidate_list_splits_cache(&index_uid);
+        self.read_debouncers.bump_write_generation();
+        result
     }
 
     async fn reset_source_checkpoint(
         &mut self,
         request: ResetSourceCheckpointRequest,
     ) -> MetastoreResult<EmptyResponse> {
-        self.metastore.reset_source_checkpoint(request).await
+        let result = self.metastore.reset_source_checkpoint(request).await;
+        self.read_debouncers.bump_write_generation();
+        result
+    }
+
+    async fn update_index_uri(
+        &mut self,
+        request: UpdateIndexUriRequest,
+    ) -> MetastoreResult<EmptyResponse> {
+        let result = self.metastore.update_index_uri(request).await;
+        self.read_debouncers.bump_write_generation();
+        result
+    }
+
+    async fn override_split_maturity(
+        &mut self,
+        request: OverrideSplitMaturityRequest,
+    ) -> MetastoreResult<EmptyResponse> {
+        let index_uid = request.index_uid.clone();
+        let result = self.metastore.override_split_maturity(request).await;
+        self.invalidate_list_splits_cache(&index_uid);
+        self.read_debouncers.bump_write_generation();
+        result
+    }
+
+    // Index templates API
+
+    async fn create_index_template(
+        &mut self,
+        request: CreateIndexTemplateRequest,
+    ) -> MetastoreResult<EmptyResponse> {
+        let result = self.metastore.create_index_template(request).await;
+        self.read_debouncers.bump_write_generation();
+        result
+    }
+
+    async fn list_index_templates(
+        &mut self,
+        request: ListIndexTemplatesRequest,
+    ) -> MetastoreResult<ListIndexTemplatesResponse> {
+        coalesce_read(
+            &self.read_debouncers.list_index_templates,
+            &self.read_debouncers.write_generation,
+            &self.metastore,
+            request,
+            |mut metastore, request| async move { metastore.list_index_templates(request).await },
+        )
+        .await
+    }
+
+    async fn delete_index_templates(
+        &mut self,
+        request: DeleteIndexTemplatesRequest,
+    ) -> MetastoreResult<EmptyResponse> {
+        let result = self.metastore.delete_index_templates(request).await;
+        self.read_debouncers.bump_write_generation();
+        result
+    }
+
+    // Index aliases API
+
+    async fn update_index_aliases(
+        &mut self,
+        request: UpdateIndexAliasesRequest,
+    ) -> MetastoreResult<EmptyResponse> {
+        let result = self.metastore.update_index_aliases(request).await;
+        self.read_debouncers.bump_write_generation();
+        result
+    }
+
+    async fn list_index_aliases(
+        &mut self,
+        request: ListIndexAliasesRequest,
+    ) -> MetastoreResult<ListIndexAliasesResponse> {
+        coalesce_read(
+            &self.read_debouncers.list_index_aliases,
+            &self.read_debouncers.write_generation,
+            &self.metastore,
+            request,
+            |mut metastore, request| async move { metastore.list_index_aliases(request).await },
+        )
+        .await
     }
 
     // Delete tasks API
@@ -183,28 +557,48 @@ impl MetastoreService for ControlPlaneMetastore {
         &mut self,
         delete_query: DeleteQuery,
     ) -> MetastoreResult<DeleteTask> {
-        self.metastore.create_delete_task(delete_query).await
+        let result = self.metastore.create_delete_task(delete_query).await;
+        self.read_debouncers.bump_write_generation();
+        result
     }
 
     async fn last_delete_opstamp(
         &mut self,
         request: LastDeleteOpstampRequest,
     ) -> MetastoreResult<LastDeleteOpstampResponse> {
-        self.metastore.last_delete_opstamp(request).await
+        coalesce_read(
+            &self.read_debouncers.last_delete_opstamp,
+            &self.read_debouncers.write_generation,
+            &self.metastore,
+            request,
+            |mut metastore, request| async move { metastore.last_delete_opstamp(request).await },
+        )
+        .await
     }
 
     async fn update_splits_delete_opstamp(
         &mut self,
         request: UpdateSplitsDeleteOpstampRequest,
     ) -> MetastoreResult<UpdateSplitsDeleteOpstampResponse> {
-        self.metastore.update_splits_delete_opstamp(request).await
+        let index_uid = request.index_uid.clone();
+        let result = self.metastore.update_splits_delete_opstamp(request).await;
+        self.invalidate_list_splits_cache(&index_uid);
+        self.read_debouncers.bump_write_generation();
+        result
     }
 
     async fn list_delete_tasks(
         &mut self,
         request: ListDeleteTasksRequest,
     ) -> MetastoreResult<ListDeleteTasksResponse> {
-        self.metastore.list_delete_tasks(request).await
+        coalesce_read(
+            &self.read_debouncers.list_delete_tasks,
+            &self.read_debouncers.write_generation,
+            &self.metastore,
+            request,
+            |mut metastore, request| async move { metastore.list_delete_tasks(request).await },
+        )
+        .await
     }
 
     // Shard API
@@ -213,27 +607,190 @@ impl MetastoreService for ControlPlaneMetastore {
         &mut self,
         request: OpenShardsRequest,
     ) -> MetastoreResult<OpenShardsResponse> {
-        self.metastore.open_shards(request).await
+        let result = self.metastore.open_shards(request).await;
+        self.read_debouncers.bump_write_generation();
+        result
     }
 
     async fn acquire_shards(
         &mut self,
         request: AcquireShardsRequest,
     ) -> MetastoreResult<AcquireShardsResponse> {
-        self.metastore.acquire_shards(request).await
+        let result = self.metastore.acquire_shards(request).await;
+        self.read_debouncers.bump_write_generation();
+        result
     }
 
     async fn list_shards(
         &mut self,
         request: ListShardsRequest,
     ) -> MetastoreResult<ListShardsResponse> {
-        self.metastore.list_shards(request).await
+        coalesce_read(
+            &self.read_debouncers.list_shards,
+            &self.read_debouncers.write_generation,
+            &self.metastore,
+            request,
+            |mut metastore, request| async move { metastore.list_shards(request).await },
+        )
+        .await
     }
 
     async fn delete_shards(
         &mut self,
         request: DeleteShardsRequest,
     ) -> MetastoreResult<DeleteShardsResponse> {
-        self.metastore.delete_shards(request).await
+        let result = self.metastore.delete_shards(request).await;
+        self.read_debouncers.bump_write_generation();
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickwit_proto::types::IndexUid;
+
+    use super::*;
+    use crate::{ListSplitsQuery, ListSplitsResponseExt};
+
+    #[tokio::test]
+    async fn test_control_plane_metastore_list_splits_cache() {
+        let mut mock_metastore = MetastoreServiceClient::mock();
+        mock_metastore
+            .expect_list_splits()
+            .times(3)
+            .returning(|_request| {
+                let responses = vec![Ok(ListSplitsResponse::empty())];
+                Ok(ServiceStream::from(responses))
+            });
+        mock_metastore
+            .expect_publish_splits()
+            .once()
+            .returning(|_request| Ok(EmptyResponse {}));
+        let mut control_plane_metastore = ControlPlaneMetastore::new(
+            ControlPlaneServiceClient::from(ControlPlaneServiceClient::mock()),
+            MetastoreServiceClient::from(mock_metastore),
+        )
+        .with_list_splits_cache(
+            Duration::from_secs(60),
+            NonZeroUsize::new(10).unwrap(),
+            &EventBroker::default(),
+        );
+        let index_uid = IndexUid::new_with_random_ulid("test-index");
+        let list_splits_query = ListSplitsQuery::for_index(index_uid.clone());
+        let list_splits_request =
+            ListSplitsRequest::try_from_list_splits_query(list_splits_query).unwrap();
+
+        // The first call to the backend.
+        for _ in 0..3 {
+            let responses: Vec<ListSplitsResponse> = control_plane_metastore
+                .list_splits(list_splits_request.clone())
+                .await
+                .unwrap()
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(responses, [ListSplitsResponse::empty()]);
+        }
+        let list_splits_cache_entries = WeakListSplitsCacheEntries(Arc::downgrade(
+            &control_plane_metastore
+                .list_splits_cache_opt
+                .as_ref()
+                .unwrap()
+                .entries,
+        ));
+        // Events about other indexes do not invalidate the cache.
+        list_splits_cache_entries
+            .clone()
+            .handle_event(PublishSplitsRequest {
+                index_uid: "other-index:00000000000000000000000000".to_string(),
+                ..Default::default()
+            })
+            .await;
+        control_plane_metastore
+            .list_splits(list_splits_request.clone())
+            .await
+            .unwrap();
+
+        // The second call to the backend.
+        list_splits_cache_entries
+            .clone()
+            .handle_event(PublishSplitsRequest {
+                index_uid: index_uid.to_string(),
+                ..Default::default()
+            })
+            .await;
+        control_plane_metastore
+            .list_splits(list_splits_request.clone())
+            .await
+            .unwrap();
+        control_plane_metastore
+            .list_splits(list_splits_request.clone())
+            .await
+            .unwrap();
+
+        // The third call to the backend: splits updated through the metastore invalidate the
+        // cache right away.
+        control_plane_metastore
+            .publish_splits(PublishSplitsRequest {
+                index_uid: index_uid.to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        control_plane_metastore
+            .list_splits(list_splits_request)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_control_plane_metastore_coalesces_concurrent_reads() {
+        let read_debouncers = ReadDebouncers::default();
+        let metastore = MetastoreServiceClient::from(MetastoreServiceClient::mock());
+        let num_reads = Arc::new(AtomicU64::new(0));
+        let (release_tx, release_rx) = tokio::sync::watch::channel(false);
+
+        let coalesced_read = || {
+            let num_reads = num_reads.clone();
+            let mut release_rx = release_rx.clone();
+            coalesce_read(
+                &read_debouncers.index_metadata,
+                &read_debouncers.write_generation,
+                &metastore,
+                IndexMetadataRequest::for_index_id("test-index".to_string()),
+                move |_metastore, request| async move {
+                    assert_eq!(request.index_id.as_deref(), Some("test-index"));
+                    num_reads.fetch_add(1, Ordering::Relaxed);
+                    // Keep the call in flight until all the requests have been issued.
+                    release_rx.wait_for(|released| *released).await.unwrap();
+                    Ok(IndexMetadataResponse {
+                        index_metadata_serialized_json: "{}".to_string(),
+                    })
+                },
+            )
+        };
+        let mut reads_before_write = futures::future::join_all((0..4).map(|_| coalesced_read()));
+        // Polling the reads issued so far registers them in the debouncer.
+        assert!(futures::poll!(&mut reads_before_write).is_pending());
+        assert_eq!(num_reads.load(Ordering::Relaxed), 1);
+
+        // A write completes while the reads are in flight: the subsequent reads must not receive
+        // the response of the reads issued before it.
+        read_debouncers.bump_write_generation();
+
+        let reads_after_write = futures::future::join_all((0..4).map(|_| coalesced_read()));
+        let (responses_before_write, responses_after_write, _) =
+            futures::future::join3(reads_before_write, reads_after_write, async {
+                release_tx.send(true).unwrap();
+            })
+            .await;
+
+        for response in responses_before_write
+            .into_iter()
+            .chain(responses_after_write)
+        {
+            assert_eq!(response.unwrap().index_metadata_serialized_json, "{}");
+        }
+        assert_eq!(num_reads.load(Ordering::Relaxed), 2);
     }
 }
@@ -34,7 +34,7 @@ use quickwit_config::{SourceConfig, INGEST_V2_SOURCE_ID};
 use quickwit_proto::metastore::{
     AcquireShardsSubrequest, AcquireShardsSubresponse, DeleteQuery, DeleteShardsSubrequest,
     DeleteTask, EntityKind, ListShardsSubrequest, ListShardsSubresponse, MetastoreError,
-    MetastoreResult, OpenShardsSubrequest, OpenShardsSubresponse,
+    MetastoreResult, OpenShardsSubrequest, OpenShardsSubresponse, SplitMaturityOverride,
 };
 use quickwit_proto::types::{IndexUid, PublishToken, SourceId, SplitId};
 use serde::{Deserialize, Serialize};
@@ -492,6 +492,44 @@ impl FileBackedIndex {
         Ok(self.metadata.checkpoint.reset_source(source_id))
     }
 
+    /// Updates the URI of the index. Returns whether a mutation occurred.
+    pub(crate) fn update_index_uri(&mut self, index_uri: &str) -> MetastoreResult<bool> {
+        self.metadata.update_index_uri(index_uri)
+    }
+
+    /// Renames the index and updates the index UID of its splits and delete tasks. Returns the
+    /// new index UID.
+    ///
+    /// Indexes with shards cannot be renamed because the queues of the ingesters are identified by
+    /// the index UID.
+    pub(crate) fn rename(&mut self, new_index_id: &str) -> MetastoreResult<IndexUid> {
+        if self
+            .per_source_shards
+            .values()
+            .any(|shards| !shards.is_empty())
+        {
+            let entity = EntityKind::Index {
+                index_id: self.index_id().to_string(),
+            };
+            let message = "indexes with shards cannot be renamed".to_string();
+            return Err(MetastoreError::FailedPrecondition { entity, message });
+        }
+        let new_index_uid = self.metadata.rename(new_index_id)?;
+
+        for split in self.splits.values_mut() {
+            split.split_metadata.index_uid = new_index_uid.clone();
+        }
+        for delete_task in &mut self.delete_tasks {
+            if let Some(delete_query) = delete_task.delete_query.as_mut() {
+                delete_query.index_uid = new_index_uid.to_string();
+            }
+        }
+        for (source_id, shards) in self.per_source_shards.iter_mut() {
+            *shards = Shards::empty(new_index_uid.clone(), source_id.clone());
+        }
+        Ok(new_index_uid)
+    }
+
     /// Creates [`DeleteTask`] from a [`DeleteQuery`].
     pub(crate) fn create_delete_task(
         &mut self,
@@ -533,6 +571,25 @@ impl FileBackedIndex {
         Ok(true)
     }
 
+    /// Overrides the maturity of a split. Returns whether a mutation occurred.
+    pub(crate) fn override_split_maturity(
+        &mut self,
+        split_id: &str,
+        maturity_override_opt: Option<SplitMaturityOverride>,
+    ) -> MetastoreResult<bool> {
+        let split = self.splits.get_mut(split_id).ok_or_else(|| {
+            MetastoreError::NotFound(EntityKind::Split {
+                split_id: split_id.to_string(),
+            })
+        })?;
+        if split.split_metadata.maturity_override == maturity_override_opt {
+            return Ok(false);
+        }
+        split.split_metadata.maturity_override = maturity_override_opt;
+        split.update_timestamp = OffsetDateTime::now_utc().unix_timestamp();
+        Ok(true)
+    }
+
     /// Lists delete tasks with opstamp > `opstamp_start`.
     pub(crate) fn list_delete_tasks(&self, opstamp_start: u64) -> MetastoreResult<Vec<DeleteTask>> {
         let delete_tasks = self
@@ -684,6 +741,10 @@ fn split_query_predicate(split: &&Split, query: &ListSplitsQuery) -> bool {
         return false;
     }
 
+    if !query.split_ids.is_empty() && !query.split_ids.contains(&split.split_metadata.split_id) {
+        return false;
+    }
+
     if !query
         .delete_opstamp
         .contains(&split.split_metadata.delete_opstamp)
@@ -27,45 +27,56 @@ mod lazy_file_backed_index;
 mod store_operations;
 
 use core::fmt;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
 use futures::future::try_join_all;
+use futures::StreamExt;
 use itertools::Itertools;
 use quickwit_common::ServiceStream;
-use quickwit_config::validate_index_id_pattern;
+use quickwit_config::{validate_index_id_pattern, IndexAlias, IndexAliasId};
 use quickwit_proto::metastore::{
     AcquireShardsRequest, AcquireShardsResponse, AcquireShardsSubrequest, AddSourceRequest,
-    CreateIndexRequest, CreateIndexResponse, DeleteIndexRequest, DeleteQuery, DeleteShardsRequest,
-    DeleteShardsResponse, DeleteShardsSubrequest, DeleteSourceRequest, DeleteSplitsRequest,
-    DeleteTask, EmptyResponse, EntityKind, IndexMetadataRequest, IndexMetadataResponse,
-    LastDeleteOpstampRequest, LastDeleteOpstampResponse, ListDeleteTasksRequest,
-    ListDeleteTasksResponse, ListIndexesMetadataRequest, ListIndexesMetadataResponse,
+    CreateIndexRequest, CreateIndexResponse, CreateIndexTemplateRequest, DeleteIndexRequest,
+    DeleteIndexTemplatesRequest, DeleteQuery, DeleteShardsRequest, DeleteShardsResponse,
+    DeleteShardsSubrequest, DeleteSourceRequest, DeleteSplitsRequest, DeleteTask, EmptyResponse,
+    EntityKind, IndexMetadataRequest, IndexMetadataResponse, LastDeleteOpstampRequest,
+    LastDeleteOpstampResponse, ListDeleteTasksRequest, ListDeleteTasksResponse,
+    ListIndexAliasesRequest, ListIndexAliasesResponse, ListIndexTemplatesRequest,
+    ListIndexTemplatesResponse, ListIndexesMetadataRequest, ListIndexesMetadataResponse,
     ListShardsRequest, ListShardsResponse, ListSplitsRequest, ListSplitsResponse,
     ListStaleSplitsRequest, MarkSplitsForDeletionRequest, MetastoreError, MetastoreResult,
     MetastoreService, MetastoreServiceStream, OpenShardsRequest, OpenShardsResponse,
-    OpenShardsSubrequest, PublishSplitsRequest, ResetSourceCheckpointRequest, StageSplitsRequest,
-    ToggleSourceRequest, UpdateSplitsDeleteOpstampRequest, UpdateSplitsDeleteOpstampResponse,
+    OpenShardsSubrequest, OverrideSplitMaturityRequest, PublishSplitsRequest, RenameIndexRequest,
+    RenameIndexResponse, ResetSourceCheckpointRequest, SplitMaturityOverride, StageSplitsRequest,
+    ToggleSourceRequest, UpdateIndexAliasesRequest, UpdateIndexUriRequest,
+    UpdateSplitsDeleteOpstampRequest, UpdateSplitsDeleteOpstampResponse,
 };
 use quickwit_proto::types::IndexUid;
 use quickwit_storage::Storage;
 use regex::RegexSet;
 use time::OffsetDateTime;
 use tokio::sync::{Mutex, OwnedMutexGuard, RwLock};
+use tracing::warn;
 
 use self::file_backed_index::FileBackedIndex;
 pub use self::file_backed_metastore_factory::FileBackedMetastoreFactory;
 use self::lazy_file_backed_index::LazyFileBackedIndex;
 use self::store_operations::{
-    check_indexes_states_exist, delete_index, fetch_index, fetch_or_init_indexes_states,
-    index_exists, put_index, put_indexes_states,
+    check_indexes_states_exist, delete_index, fetch_index, fetch_index_aliases,
+    fetch_index_templates, fetch_or_init_indexes_states, index_exists, put_index,
+    put_index_aliases, put_index_templates, put_indexes_states,
 };
 use super::{
-    AddSourceRequestExt, CreateIndexRequestExt, IndexMetadataResponseExt,
+    check_expected_index_aliases, check_index_id_is_not_alias, remove_index_from_index_aliases,
+    rename_index_in_index_aliases, validate_index_alias, validate_index_template,
+    AddSourceRequestExt, CreateIndexRequestExt, CreateIndexTemplateRequestExt,
+    IndexMetadataResponseExt, ListIndexAliasesResponseExt, ListIndexTemplatesResponseExt,
     ListIndexesMetadataResponseExt, ListSplitsRequestExt, ListSplitsResponseExt,
-    PublishSplitsRequestExt, StageSplitsRequestExt, STREAM_SPLITS_CHUNK_SIZE,
+    PublishSplitsRequestExt, StageSplitsRequestExt, UpdateIndexAliasesRequestExt,
+    STREAM_SPLITS_CHUNK_SIZE,
 };
 use crate::checkpoint::IndexCheckpointDelta;
 use crate::{IndexMetadata, ListSplitsQuery, MetastoreServiceExt, Split, SplitState};
@@ -97,6 +108,13 @@ impl From<bool> for MutationOccurred<()> {
     }
 }
 
+/// Index aliases cached in memory, so that the aliases resolved by every search and ingest request
+/// are not fetched from the storage each time.
+struct CachedIndexAliases {
+    index_aliases: BTreeMap<IndexAliasId, IndexAlias>,
+    fetched_at: Instant,
+}
+
 /// Metastore that stores all of the metadata associated to each index
 /// into as many files and stores a map of indexes
 /// (index_id, index_state) in a dedicated file `indexes_states.json`.
@@ -133,6 +151,11 @@ impl From<bool> for MutationOccurred<()> {
 pub struct FileBackedMetastore {
     storage: Arc<dyn Storage>,
     per_index_metastores: Arc<RwLock<HashMap<String, IndexState>>>,
+    // Serializes the read-modify-write operations on the index templates file.
+    index_templates_lock: Arc<Mutex<()>>,
+    // Caches the index aliases and serializes the read-modify-write operations on the index
+    // aliases file.
+    index_aliases_cache: Arc<Mutex<Option<CachedIndexAliases>>>,
     polling_interval_opt: Option<Duration>,
 }
 
@@ -152,6 +175,8 @@ impl FileBackedMetastore {
         Self {
             storage,
             per_index_metastores: Default::default(),
+            index_templates_lock: Default::default(),
+            index_aliases_cache: Default::default(),
             polling_interval_opt: None,
         }
     }
@@ -180,10 +205,57 @@ impl FileBackedMetastore {
         Ok(Self {
             storage,
             per_index_metastores,
+            index_templates_lock: Default::default(),
+            index_aliases_cache: Default::default(),
             polling_interval_opt,
         })
     }
 
+    /// Returns the index aliases. They are fetched from the storage only if they are not cached
+    /// yet or, when polling is enabled, if the cached aliases are older than the polling interval.
+    async fn cached_index_aliases<'a>(
+        &self,
+        index_aliases_cache: &'a mut Option<CachedIndexAliases>,
+    ) -> MetastoreResult<&'a BTreeMap<IndexAliasId, IndexAlias>> {
+        let is_stale = match (&*index_aliases_cache, self.polling_interval_opt) {
+            (None, _) => true,
+            (Some(cached_index_aliases), Some(polling_interval)) => {
+                cached_index_aliases.fetched_at.elapsed() >= polling_interval
+            }
+            (Some(_), None) => false,
+        };
+        if is_stale {
+            let index_aliases = fetch_index_aliases(&*self.storage).await?;
+            *index_aliases_cache = Some(CachedIndexAliases {
+                index_aliases,
+                fetched_at: Instant::now(),
+            });
+        }
+        let cached_index_aliases = index_aliases_cache
+            .as_ref()
+            .expect("index aliases should be cached");
+        Ok(&cached_index_aliases.index_aliases)
+    }
+
+    /// Stores the index aliases and updates the cache accordingly.
+    async fn store_index_aliases(
+        &self,
+        index_aliases_cache: &mut Option<CachedIndexAliases>,
+        index_aliases: BTreeMap<IndexAliasId, IndexAlias>,
+    ) -> MetastoreResult<()> {
+        if let Err(error) = put_index_aliases(&*self.storage, &index_aliases).await {
+            // We cannot know for sure whether the file was written or not, so we discard the
+            // cache.
+            *index_aliases_cache = None;
+            return Err(error);
+        }
+        *index_aliases_cache = Some(CachedIndexAliases {
+            index_aliases,
+            fetched_at: Instant::now(),
+        });
+        Ok(())
+    }
+
     async fn mutate<T>(
         &self,
         index_uid: IndexUid,
@@ -393,6 +465,16 @@ impl MetastoreService for FileBackedMetastore {
                 ),
             });
         }
+        // Holding the outer lock prevents an alias with the same ID from being created meanwhile.
+        {
+            let mut index_aliases_cache_guard = self.index_aliases_cache.lock().await;
+            let index_aliases = self
+                .cached_index_aliases(&mut index_aliases_cache_guard)
+                .await?;
+            check_index_id_is_not_alias(&index_id, |alias_id| {
+                index_aliases.contains_key(alias_id)
+            })?;
+        }
 
         // Set state to Creating` and rollback on metastore error.
         per_index_metastores_wlock.insert(index_id.clone(), IndexState::Creating);
@@ -447,6 +529,20 @@ impl MetastoreService for FileBackedMetastore {
                 index_id: index_id.to_string(),
             }));
         }
+        // The index is removed from the index aliases first, so that no alias is left referencing
+        // it once it is deleted.
+        {
+            let mut index_aliases_cache_guard = self.index_aliases_cache.lock().await;
+            let mut index_aliases = self
+                .cached_index_aliases(&mut index_aliases_cache_guard)
+                .await?
+                .clone();
+
+            if remove_index_from_index_aliases(&mut index_aliases, index_id) {
+                self.store_index_aliases(&mut index_aliases_cache_guard, index_aliases)
+                    .await?;
+            }
+        }
 
         // Set state to `Deleting` and keep the previous state in memory in case we need to insert
         // if an error occurs.
@@ -632,9 +728,268 @@ impl MetastoreService for FileBackedMetastore {
         Ok(EmptyResponse {})
     }
 
+    async fn update_index_uri(
+        &mut self,
+        request: UpdateIndexUriRequest,
+    ) -> MetastoreResult<EmptyResponse> {
+        let index_uid: IndexUid = request.index_uid.into();
+
+        self.mutate(index_uid, |index| {
+            index
+                .update_index_uri(&request.index_uri)
+                .map(MutationOccurred::from)
+        })
+        .await?;
+        Ok(EmptyResponse {})
+    }
+
+    async fn override_split_maturity(
+        &mut self,
+        request: OverrideSplitMaturityRequest,
+    ) -> MetastoreResult<EmptyResponse> {
+        let index_uid: IndexUid = request.index_uid.into();
+        let maturity_override_opt = match request.maturity_override() {
+            SplitMaturityOverride::Unspecified => None,
+            maturity_override => Some(maturity_override),
+        };
+        self.mutate(index_uid, |index| {
+            index
+                .override_split_maturity(&request.split_id, maturity_override_opt)
+                .map(MutationOccurred::from)
+        })
+        .await?;
+        Ok(EmptyResponse {})
+    }
+
+    async fn rename_index(
+        &mut self,
+        request: RenameIndexRequest,
+    ) -> MetastoreResult<RenameIndexResponse> {
+        let index_uid: IndexUid = request.index_uid.into();
+        let index_id = index_uid.index_id().to_string();
+        let new_index_id = request.new_index_id;
+
+        // Like in `mutate`, we lock the index before picking the outer lock.
+        let mut locked_index = self.get_locked_index(&index_id).await?;
+        if *locked_index.index_uid() != index_uid {
+            return Err(MetastoreError::NotFound(EntityKind::Index { index_id }));
+        }
+        let mut index = locked_index.clone();
+        let new_index_uid = index.rename(&new_index_id)?;
+
+        // We pick the outer lock here, so that we enter a critical section.
+        let mut per_index_metastores_wlock = self.per_index_metastores.write().await;
+
+        let new_index_exists = match per_index_metastores_wlock.get(&new_index_id) {
+            Some(index_state) => matches!(index_state, IndexState::Alive(_)),
+            None => index_exists(&*self.storage, &new_index_id).await?,
+        };
+        if new_index_exists {
+            return Err(MetastoreError::AlreadyExists(EntityKind::Index {
+                index_id: new_index_id,
+            }));
+        }
+        // Holding the index aliases lock until the end of the rename prevents an alias with the
+        // same ID as the new index from being created meanwhile, and lets us update the aliases
+        // referencing the index along with it.
+        let mut index_aliases_cache_guard = self.index_aliases_cache.lock().await;
+        let index_aliases = self
+            .cached_index_aliases(&mut index_aliases_cache_guard)
+            .await?
+            .clone();
+        check_index_id_is_not_alias(&new_index_id, |alias_id| {
+            index_aliases.contains_key(alias_id)
+        })?;
+        let mut renamed_index_aliases = index_aliases.clone();
+        let index_aliases_mutation_occurred =
+            rename_index_in_index_aliases(&mut renamed_index_aliases, &index_id, &new_index_id);
+
+        // The renamed index is written under its new ID while in the transitioning state
+        // `Creating`. Then, the new index becomes `Alive` and the old one `Deleting` in a single
+        // update of the indexes states file, so the index cannot be lost if an error occurs.
+        let new_index_state_opt =
+            per_index_metastores_wlock.insert(new_index_id.clone(), IndexState::Creating);
+        if let Err(error) = put_indexes_states(&*self.storage, &per_index_metastores_wlock).await {
+            if let Some(new_index_state) = new_index_state_opt {
+                per_index_metastores_wlock.insert(new_index_id, new_index_state);
+            } else {
+                per_index_metastores_wlock.remove(&new_index_id);
+            }
+            return Err(error);
+        }
+        put_index(&*self.storage, &index).await?;
+
+        if index_aliases_mutation_occurred {
+            self.store_index_aliases(&mut index_aliases_cache_guard, renamed_index_aliases)
+                .await?;
+        }
+        per_index_metastores_wlock.insert(
+            new_index_id.clone(),
+            IndexState::Alive(LazyFileBackedIndex::new(
+                self.storage.clone(),
+                new_index_id.clone(),
+                self.polling_interval_opt,
+                Some(index),
+            )),
+        );
+        let index_state_opt =
+            per_index_metastores_wlock.insert(index_id.clone(), IndexState::Deleting);
+
+        if let Err(error) = put_indexes_states(&*self.storage, &per_index_metastores_wlock).await {
+            per_index_metastores_wlock.insert(new_index_id, IndexState::Creating);
+
+            if let Some(index_state) = index_state_opt {
+                per_index_metastores_wlock.insert(index_id.clone(), index_state);
+            }
+            if index_aliases_mutation_occurred {
+                if let Err(store_error) = self
+                    .store_index_aliases(&mut index_aliases_cache_guard, index_aliases)
+                    .await
+                {
+                    warn!(
+                        index_id=%index_id,
+                        error=?store_error,
+                        "failed to restore index aliases after failed rename"
+                    );
+                }
+            }
+            return Err(error);
+        }
+        locked_index.discarded = true;
+
+        // At this point, the index is renamed. Failing to delete the metadata file of the old
+        // index leaves it in the `Deleting` state, which a subsequent deletion will clean up.
+        match delete_index(&*self.storage, &index_id).await {
+            Ok(()) | Err(MetastoreError::NotFound(EntityKind::Index { .. })) => {
+                per_index_metastores_wlock.remove(&index_id);
+
+                if let Err(error) =
+                    put_indexes_states(&*self.storage, &per_index_metastores_wlock).await
+                {
+                    per_index_metastores_wlock.insert(index_id.clone(), IndexState::Deleting);
+                    warn!(
+                        index_id=%index_id,
+                        error=?error,
+                        "failed to remove renamed index from indexes states"
+                    );
+                }
+            }
+            Err(error) => {
+                warn!(
+                    index_id=%index_id,
+                    error=?error,
+                    "failed to delete metadata file of renamed index"
+                );
+            }
+        }
+        let response = RenameIndexResponse {
+            index_uid: new_index_uid.into(),
+        };
+        Ok(response)
+    }
+
+    async fn create_index_template(
+        &mut self,
+        request: CreateIndexTemplateRequest,
+    ) -> MetastoreResult<EmptyResponse> {
+        let index_template = request.deserialize_index_template()?;
+        let _index_templates_guard = self.index_templates_lock.lock().await;
+        let mut index_templates = fetch_index_templates(&*self.storage).await?;
+
+        if !request.overwrite && index_templates.contains_key(&index_template.template_id) {
+            return Err(MetastoreError::AlreadyExists(EntityKind::IndexTemplate {
+                template_id: index_template.template_id,
+            }));
+        }
+        validate_index_template(&index_template, index_templates.values())?;
+
+        index_templates.insert(index_template.template_id.clone(), index_template);
+        put_index_templates(&*self.storage, &index_templates).await?;
+        Ok(EmptyResponse {})
+    }
+
+    async fn delete_index_templates(
+        &mut self,
+        request: DeleteIndexTemplatesRequest,
+    ) -> MetastoreResult<EmptyResponse> {
+        let _index_templates_guard = self.index_templates_lock.lock().await;
+        let mut index_templates = fetch_index_templates(&*self.storage).await?;
+        let num_index_templates = index_templates.len();
+
+        for template_id in &request.template_ids {
+            index_templates.remove(template_id);
+        }
+        if index_templates.len() != num_index_templates {
+            put_index_templates(&*self.storage, &index_templates).await?;
+        }
+        Ok(EmptyResponse {})
+    }
+
+    async fn update_index_aliases(
+        &mut self,
+        request: UpdateIndexAliasesRequest,
+    ) -> MetastoreResult<EmptyResponse> {
+        let upserted_index_aliases = request.deserialize_upserted_index_aliases()?;
+        let expected_index_aliases = request.deserialize_expected_index_aliases()?;
+
+        // We hold the read lock until the aliases are stored so that the member indexes cannot be
+        // deleted in the meantime.
+        let per_index_metastores_rlock = self.per_index_metastores.read().await;
+        let is_alive_index = |index_id: &str| {
+            matches!(
+                per_index_metastores_rlock.get(index_id),
+                Some(IndexState::Alive(_))
+            )
+        };
+        for index_alias in &upserted_index_aliases {
+            validate_index_alias(index_alias, is_alive_index)?;
+        }
+        let mut index_aliases_cache_guard = self.index_aliases_cache.lock().await;
+        let mut index_aliases = self
+            .cached_index_aliases(&mut index_aliases_cache_guard)
+            .await?
+            .clone();
+
+        // The cache lock is held until the aliases are stored, so the check cannot race with
+        // another update.
+        check_expected_index_aliases(&expected_index_aliases, |alias_id| {
+            index_aliases.get(alias_id)
+        })?;
+
+        for alias_id in &request.deleted_alias_ids {
+            index_aliases.remove(alias_id);
+        }
+        for index_alias in upserted_index_aliases {
+            index_aliases.insert(index_alias.alias_id.clone(), index_alias);
+        }
+        // All the updates are written at once, so they are applied atomically.
+        self.store_index_aliases(&mut index_aliases_cache_guard, index_aliases)
+            .await?;
+        Ok(EmptyResponse {})
+    }
+
     /// -------------------------------------------------------------------------------
     /// Read-only accessors
 
+    async fn list_index_aliases(
+        &mut self,
+        _request: ListIndexAliasesRequest,
+    ) -> MetastoreResult<ListIndexAliasesResponse> {
+        let mut index_aliases_cache_guard = self.index_aliases_cache.lock().await;
+        let index_aliases = self
+            .cached_index_aliases(&mut index_aliases_cache_guard)
+            .await?;
+        ListIndexAliasesResponse::try_from_index_aliases(index_aliases.values())
+    }
+
+    async fn list_index_templates(
+        &mut self,
+        _request: ListIndexTemplatesRequest,
+    ) -> MetastoreResult<ListIndexTemplatesResponse> {
+        let index_templates = fetch_index_templates(&*self.storage).await?;
+        ListIndexTemplatesResponse::try_from_index_templates(index_templates.values())
+    }
+
     /// Streams of splits for the given request.
     /// No error is returned if any of the requested `index_uid` does not exist.
     async fn list_splits(
@@ -642,12 +997,11 @@ impl MetastoreService for FileBackedMetastore {
         request: ListSplitsRequest,
     ) -> MetastoreResult<MetastoreServiceStream<ListSplitsResponse>> {
         let splits = self.inner_list_splits(request).await?;
-        let splits_responses: Vec<MetastoreResult<ListSplitsResponse>> = splits
+        // The splits are serialized lazily, one page at a time, as the stream is consumed.
+        let splits_responses_stream = futures::stream::iter(splits)
             .chunks(STREAM_SPLITS_CHUNK_SIZE)
-            .map(|chunk| ListSplitsResponse::try_from_splits(chunk.to_vec()))
-            .collect();
-        let splits_responses_stream = Box::pin(futures::stream::iter(splits_responses));
-        Ok(ServiceStream::new(splits_responses_stream))
+            .map(ListSplitsResponse::try_from_splits);
+        Ok(ServiceStream::new(Box::pin(splits_responses_stream)))
     }
 
     async fn list_stale_splits(
@@ -17,11 +17,12 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 
+use quickwit_config::{IndexAlias, IndexAliasId, IndexTemplate, IndexTemplateId};
 use quickwit_proto::metastore::{EntityKind, MetastoreError, MetastoreResult};
 use quickwit_storage::{Storage, StorageError, StorageErrorKind};
 use serde::{Deserialize, Serialize};
@@ -35,6 +36,12 @@ const INDEXES_STATES_FILENAME: &str = "indexes_states.json";
 /// Index metadata file managed by [`FileBackedMetastore`](crate::FileBackedMetastore).
 const META_FILENAME: &str = "metastore.json";
 
+/// Index templates file managed by [`FileBackedMetastore`](crate::FileBackedMetastore).
+const INDEX_TEMPLATES_FILENAME: &str = "index_templates.json";
+
+/// Index aliases file managed by [`FileBackedMetastore`](crate::FileBackedMetastore).
+const INDEX_ALIASES_FILENAME: &str = "index_aliases.json";
+
 /// Index state used for serialization/deserialization only.
 #[derive(Serialize, Deserialize)]
 enum IndexStateValue {
@@ -154,6 +161,106 @@ pub(crate) async fn put_indexes_states(
     Ok(())
 }
 
+/// Fetches the `INDEX_TEMPLATES_FILENAME` file and builds the map (template_id, template).
+/// If the file does not exist, no template has been created yet and an empty map is returned.
+pub(crate) async fn fetch_index_templates(
+    storage: &dyn Storage,
+) -> MetastoreResult<BTreeMap<IndexTemplateId, IndexTemplate>> {
+    let index_templates_path = Path::new(INDEX_TEMPLATES_FILENAME);
+    let exists = storage
+        .exists(index_templates_path)
+        .await
+        .map_err(|storage_err| convert_error("index templates", storage_err))?;
+    if !exists {
+        return Ok(BTreeMap::new());
+    }
+    let content = storage
+        .get_all(index_templates_path)
+        .await
+        .map_err(|storage_err| MetastoreError::Internal {
+            message: format!("failed to get `{INDEX_TEMPLATES_FILENAME}` file"),
+            cause: storage_err.to_string(),
+        })?;
+    let index_templates: BTreeMap<IndexTemplateId, IndexTemplate> =
+        serde_json::from_slice(&content[..]).map_err(|error| {
+            MetastoreError::JsonDeserializeError {
+                struct_name: "IndexTemplate".to_string(),
+                message: error.to_string(),
+            }
+        })?;
+    Ok(index_templates)
+}
+
+pub(crate) async fn put_index_templates(
+    storage: &dyn Storage,
+    index_templates: &BTreeMap<IndexTemplateId, IndexTemplate>,
+) -> MetastoreResult<()> {
+    let index_templates_path = Path::new(INDEX_TEMPLATES_FILENAME);
+    let content: Vec<u8> = serde_json::to_vec_pretty(index_templates).map_err(|serde_err| {
+        MetastoreError::JsonSerializeError {
+            struct_name: "IndexTemplate".to_string(),
+            message: serde_err.to_string(),
+        }
+    })?;
+    storage
+        .put(index_templates_path, Box::new(content))
+        .await
+        .map_err(|storage_err| MetastoreError::Internal {
+            message: format!("failed to put `{INDEX_TEMPLATES_FILENAME}` file"),
+            cause: storage_err.to_string(),
+        })?;
+    Ok(())
+}
+
+/// Fetches the `INDEX_ALIASES_FILENAME` file and builds the map (alias_id, alias).
+/// If the file does not exist, no alias has been created yet and an empty map is returned.
+pub(crate) async fn fetch_index_aliases(
+    storage: &dyn Storage,
+) -> MetastoreResult<BTreeMap<IndexAliasId, IndexAlias>> {
+    let index_aliases_path = Path::new(INDEX_ALIASES_FILENAME);
+    let exists = storage
+        .exists(index_aliases_path)
+        .await
+        .map_err(|storage_err| convert_error("index aliases", storage_err))?;
+    if !exists {
+        return Ok(BTreeMap::new());
+    }
+    let content = storage
+        .get_all(index_aliases_path)
+        .await
+        .map_err(|storage_err| MetastoreError::Internal {
+            message: format!("failed to get `{INDEX_ALIASES_FILENAME}` file"),
+            cause: storage_err.to_string(),
+        })?;
+    let index_aliases: BTreeMap<IndexAliasId, IndexAlias> = serde_json::from_slice(&content[..])
+        .map_err(|error| MetastoreError::JsonDeserializeError {
+            struct_name: "IndexAlias".to_string(),
+            message: error.to_string(),
+        })?;
+    Ok(index_aliases)
+}
+
+pub(crate) async fn put_index_aliases(
+    storage: &dyn Storage,
+    index_aliases: &BTreeMap<IndexAliasId, IndexAlias>,
+) -> MetastoreResult<()> {
+    let index_aliases_path = Path::new(INDEX_ALIASES_FILENAME);
+    let content: Vec<u8> = serde_json::to_vec_pretty(index_aliases).map_err(|serde_err| {
+        MetastoreError::JsonSerializeError {
+            struct_name: "IndexAlias".to_string(),
+            message: serde_err.to_string(),
+        }
+    })?;
+    storage
+        .put(index_aliases_path, Box::new(content))
+        .await
+        .map_err(|storage_err| MetastoreError::Internal {
+            message: format!("failed to put `{INDEX_ALIASES_FILENAME}` file"),
+            cause: storage_err.to_string(),
+        })?;
+    Ok(())
+}
+
 pub(crate) async fn fetch_index(
     storage: &dyn Storage,
     index_id: &str,
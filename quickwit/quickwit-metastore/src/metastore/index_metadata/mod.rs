@@ -21,9 +21,10 @@ pub(crate) mod serialize;
 
 use std::collections::hash_map::Entry;
 use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
 
 use quickwit_common::uri::Uri;
-use quickwit_config::{IndexConfig, SourceConfig, TestableForRegression};
+use quickwit_config::{validate_identifier, IndexConfig, SourceConfig, TestableForRegression};
 use quickwit_proto::metastore::{EntityKind, MetastoreError, MetastoreResult};
 use quickwit_proto::types::{IndexUid, Position, SourceId};
 use serde::{Deserialize, Serialize};
@@ -137,6 +138,30 @@ impl IndexMetadata {
         self.checkpoint.remove_source(source_id);
         Ok(true)
     }
+
+    /// Updates the URI of the index. Returns whether a mutation occurred.
+    pub(crate) fn update_index_uri(&mut self, index_uri: &str) -> MetastoreResult<bool> {
+        let index_uri =
+            Uri::from_str(index_uri).map_err(|error| MetastoreError::InvalidArgument {
+                message: format!("failed to parse index URI `{index_uri}`: {error}"),
+            })?;
+        let mutation_occurred = self.index_config.index_uri != index_uri;
+        self.index_config.index_uri = index_uri;
+        Ok(mutation_occurred)
+    }
+
+    /// Renames the index, preserving its incarnation. Returns the new index UID.
+    pub(crate) fn rename(&mut self, new_index_id: &str) -> MetastoreResult<IndexUid> {
+        validate_identifier("Index ID", new_index_id).map_err(|error| {
+            MetastoreError::InvalidArgument {
+                message: error.to_string(),
+            }
+        })?;
+        let new_index_uid = IndexUid::from_parts(new_index_id, self.index_uid.incarnation_id());
+        self.index_uid = new_index_uid.clone();
+        self.index_config.index_id = new_index_id.to_string();
+        Ok(new_index_uid)
+    }
 }
 
 impl TestableForRegression for IndexMetadata {
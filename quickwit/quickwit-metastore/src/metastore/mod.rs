@@ -24,23 +24,27 @@ pub mod postgres;
 
 pub mod control_plane_metastore;
 
+use std::collections::BTreeMap;
 use std::ops::{Bound, RangeInclusive};
 
 use async_trait::async_trait;
-use futures::TryStreamExt;
+use futures::{future, stream, TryStreamExt};
 pub use index_metadata::IndexMetadata;
 use itertools::Itertools;
 use once_cell::sync::Lazy;
 use quickwit_common::tower::PrometheusMetricsLayer;
-use quickwit_config::{IndexConfig, SourceConfig};
+use quickwit_common::ServiceStream;
+use quickwit_config::{IndexAlias, IndexAliasId, IndexConfig, IndexTemplate, SourceConfig};
 use quickwit_doc_mapper::tag_pruning::TagFilterAst;
 use quickwit_proto::metastore::{
-    serde_utils, AddSourceRequest, CreateIndexRequest, DeleteTask, IndexMetadataRequest,
-    IndexMetadataResponse, ListIndexesMetadataResponse, ListSplitsRequest, ListSplitsResponse,
-    MetastoreError, MetastoreResult, MetastoreService, MetastoreServiceClient,
-    MetastoreServiceStream, PublishSplitsRequest, StageSplitsRequest,
+    serde_utils, AddSourceRequest, CreateIndexRequest, CreateIndexTemplateRequest, DeleteTask,
+    EntityKind, IndexMetadataRequest, IndexMetadataResponse, ListIndexAliasesRequest,
+    ListIndexAliasesResponse, ListIndexTemplatesResponse, ListIndexesMetadataResponse,
+    ListSplitsRequest, ListSplitsResponse, MetastoreError, MetastoreResult, MetastoreService,
+    MetastoreServiceClient, MetastoreServiceStream, PublishSplitsRequest, StageSplitsRequest,
+    UpdateIndexAliasesRequest,
 };
-use quickwit_proto::types::{IndexUid, SplitId};
+use quickwit_proto::types::{IndexId, IndexUid, SplitId};
 use time::OffsetDateTime;
 
 use crate::checkpoint::IndexCheckpointDelta;
@@ -72,6 +76,80 @@ pub trait MetastoreServiceExt: MetastoreService {
             Err(error) => Err(error),
         }
     }
+
+    /// Streams the splits matching the request one by one. The splits are fetched from the
+    /// backend and deserialized one page of at most `STREAM_SPLITS_CHUNK_SIZE` splits at a time,
+    /// so large result sets are never fully materialized in memory.
+    async fn list_splits_stream(
+        &mut self,
+        request: ListSplitsRequest,
+    ) -> MetastoreResult<ServiceStream<MetastoreResult<Split>>> {
+        let list_splits_stream = self.list_splits(request).await?;
+        let splits_stream = list_splits_stream
+            .and_then(|list_splits_response| {
+                future::ready(list_splits_response.deserialize_splits())
+            })
+            .map_ok(|splits| stream::iter(splits.into_iter().map(MetastoreResult::Ok)))
+            .try_flatten();
+        Ok(ServiceStream::new(Box::pin(splits_stream)))
+    }
+
+    /// Replaces the index ID patterns that are index aliases with the IDs of the aliases' member
+    /// indexes. Patterns containing wildcards are left untouched.
+    async fn resolve_index_aliases(
+        &mut self,
+        index_id_patterns: Vec<String>,
+    ) -> MetastoreResult<Vec<String>> {
+        if index_id_patterns
+            .iter()
+            .all(|index_id_pattern| index_id_pattern.contains('*'))
+        {
+            return Ok(index_id_patterns);
+        }
+        let index_aliases = self
+            .list_index_aliases(ListIndexAliasesRequest {})
+            .await?
+            .deserialize_index_aliases()?;
+
+        if index_aliases.is_empty() {
+            return Ok(index_id_patterns);
+        }
+        let resolved_index_id_patterns = index_id_patterns
+            .into_iter()
+            .flat_map(|index_id_pattern| {
+                match index_aliases
+                    .iter()
+                    .find(|index_alias| index_alias.alias_id == index_id_pattern)
+                {
+                    Some(index_alias) => index_alias.index_ids.iter().cloned().collect(),
+                    None => vec![index_id_pattern],
+                }
+            })
+            .unique()
+            .collect();
+        Ok(resolved_index_id_patterns)
+    }
+
+    /// Returns the ID of the index receiving the documents ingested into `index_id`: the write
+    /// index of the alias if `index_id` is an index alias, `index_id` itself otherwise.
+    async fn resolve_write_index_id(&mut self, index_id: IndexId) -> MetastoreResult<IndexId> {
+        let index_aliases = self
+            .list_index_aliases(ListIndexAliasesRequest {})
+            .await?
+            .deserialize_index_aliases()?;
+
+        let Some(index_alias) = index_aliases
+            .into_iter()
+            .find(|index_alias| index_alias.alias_id == index_id)
+        else {
+            return Ok(index_id);
+        };
+        index_alias
+            .write_index_id
+            .ok_or_else(|| MetastoreError::InvalidArgument {
+                message: format!("index alias `{index_id}` does not have a write index"),
+            })
+    }
 }
 
 impl MetastoreServiceExt for MetastoreServiceClient {}
@@ -205,6 +283,314 @@ impl ListIndexesMetadataResponseExt for ListIndexesMetadataResponse {
     }
 }
 
+/// Helper trait to build a [`CreateIndexTemplateRequest`] and deserialize its payload.
+pub trait CreateIndexTemplateRequestExt {
+    /// Creates a new [`CreateIndexTemplateRequest`] from an [`IndexTemplate`].
+    fn try_from_index_template(
+        index_template: &IndexTemplate,
+        overwrite: bool,
+    ) -> MetastoreResult<CreateIndexTemplateRequest>;
+
+    /// Deserializes the `index_template_json` field of a [`CreateIndexTemplateRequest`] into an
+    /// [`IndexTemplate`].
+    fn deserialize_index_template(&self) -> MetastoreResult<IndexTemplate>;
+}
+
+impl CreateIndexTemplateRequestExt for CreateIndexTemplateRequest {
+    fn try_from_index_template(
+        index_template: &IndexTemplate,
+        overwrite: bool,
+    ) -> MetastoreResult<CreateIndexTemplateRequest> {
+        let index_template_json = serde_utils::to_json_str(index_template)?;
+        let request = Self {
+            index_template_json,
+            overwrite,
+        };
+        Ok(request)
+    }
+
+    fn deserialize_index_template(&self) -> MetastoreResult<IndexTemplate> {
+        serde_utils::from_json_str(&self.index_template_json)
+    }
+}
+
+/// Helper trait to build a [`ListIndexTemplatesResponse`] and deserialize its payload.
+pub trait ListIndexTemplatesResponseExt {
+    /// Creates a new [`ListIndexTemplatesResponse`] from a list of [`IndexTemplate`].
+    fn try_from_index_templates<'a>(
+        index_templates: impl IntoIterator<Item = &'a IndexTemplate>,
+    ) -> MetastoreResult<ListIndexTemplatesResponse>;
+
+    /// Deserializes the `index_templates_json` field of a [`ListIndexTemplatesResponse`] into a
+    /// list of [`IndexTemplate`].
+    fn deserialize_index_templates(&self) -> MetastoreResult<Vec<IndexTemplate>>;
+}
+
+impl ListIndexTemplatesResponseExt for ListIndexTemplatesResponse {
+    fn try_from_index_templates<'a>(
+        index_templates: impl IntoIterator<Item = &'a IndexTemplate>,
+    ) -> MetastoreResult<Self> {
+        let index_templates_json = index_templates
+            .into_iter()
+            .map(serde_utils::to_json_str)
+            .collect::<MetastoreResult<Vec<String>>>()?;
+        let response = Self {
+            index_templates_json,
+        };
+        Ok(response)
+    }
+
+    fn deserialize_index_templates(&self) -> MetastoreResult<Vec<IndexTemplate>> {
+        self.index_templates_json
+            .iter()
+            .map(|index_template_json| serde_utils::from_json_str(index_template_json))
+            .collect()
+    }
+}
+
+/// Validates an index template before it is stored in the metastore. In particular, the index ID
+/// patterns of the template must not overlap with the patterns of another template with the same
+/// priority, otherwise it would be ambiguous which template to apply to a given index ID.
+pub(crate) fn validate_index_template<'a>(
+    index_template: &IndexTemplate,
+    other_index_templates: impl IntoIterator<Item = &'a IndexTemplate>,
+) -> MetastoreResult<()> {
+    index_template
+        .validate()
+        .map_err(|error| MetastoreError::InvalidArgument {
+            message: format!("{error:#}"),
+        })?;
+
+    for other_index_template in other_index_templates {
+        if let Some((index_id_pattern, other_index_id_pattern)) =
+            index_template.find_ambiguous_overlap(other_index_template)
+        {
+            let message = format!(
+                "index ID pattern `{index_id_pattern}` of index template `{}` overlaps with index \
+                 ID pattern `{other_index_id_pattern}` of index template `{}` with the same \
+                 priority {}",
+                index_template.template_id,
+                other_index_template.template_id,
+                index_template.priority,
+            );
+            return Err(MetastoreError::InvalidArgument { message });
+        }
+    }
+    Ok(())
+}
+
+/// Helper trait to build a [`UpdateIndexAliasesRequest`] and deserialize its payload.
+pub trait UpdateIndexAliasesRequestExt {
+    /// Creates a new [`UpdateIndexAliasesRequest`] that upserts and deletes index aliases.
+    fn try_from_index_aliases<'a>(
+        upserted_index_aliases: impl IntoIterator<Item = &'a IndexAlias>,
+        deleted_alias_ids: Vec<String>,
+    ) -> MetastoreResult<UpdateIndexAliasesRequest>;
+
+    /// Makes the update conditional: it is only applied if the aliases are still equal to
+    /// `expected_index_aliases` when the metastore performs it.
+    fn try_with_expected_index_aliases<'a>(
+        self,
+        expected_index_aliases: impl IntoIterator<Item = &'a IndexAlias>,
+    ) -> MetastoreResult<UpdateIndexAliasesRequest>;
+
+    /// Deserializes the `upserted_index_aliases_json` field of an [`UpdateIndexAliasesRequest`]
+    /// into a list of [`IndexAlias`].
+    fn deserialize_upserted_index_aliases(&self) -> MetastoreResult<Vec<IndexAlias>>;
+
+    /// Deserializes the `expected_index_aliases_json` field of an [`UpdateIndexAliasesRequest`]
+    /// into a list of [`IndexAlias`].
+    fn deserialize_expected_index_aliases(&self) -> MetastoreResult<Vec<IndexAlias>>;
+}
+
+impl UpdateIndexAliasesRequestExt for UpdateIndexAliasesRequest {
+    fn try_from_index_aliases<'a>(
+        upserted_index_aliases: impl IntoIterator<Item = &'a IndexAlias>,
+        deleted_alias_ids: Vec<String>,
+    ) -> MetastoreResult<Self> {
+        let upserted_index_aliases_json = upserted_index_aliases
+            .into_iter()
+            .map(serde_utils::to_json_str)
+            .collect::<MetastoreResult<Vec<String>>>()?;
+        let request = Self {
+            upserted_index_aliases_json,
+            deleted_alias_ids,
+            expected_index_aliases_json: Vec::new(),
+        };
+        Ok(request)
+    }
+
+    fn try_with_expected_index_aliases<'a>(
+        mut self,
+        expected_index_aliases: impl IntoIterator<Item = &'a IndexAlias>,
+    ) -> MetastoreResult<Self> {
+        self.expected_index_aliases_json = expected_index_aliases
+            .into_iter()
+            .map(serde_utils::to_json_str)
+            .collect::<MetastoreResult<Vec<String>>>()?;
+        Ok(self)
+    }
+
+    fn deserialize_upserted_index_aliases(&self) -> MetastoreResult<Vec<IndexAlias>> {
+        self.upserted_index_aliases_json
+            .iter()
+            .map(|index_alias_json| serde_utils::from_json_str(index_alias_json))
+            .collect()
+    }
+
+    fn deserialize_expected_index_aliases(&self) -> MetastoreResult<Vec<IndexAlias>> {
+        self.expected_index_aliases_json
+            .iter()
+            .map(|index_alias_json| serde_utils::from_json_str(index_alias_json))
+            .collect()
+    }
+}
+
+/// Helper trait to build a [`ListIndexAliasesResponse`] and deserialize its payload.
+pub trait ListIndexAliasesResponseExt {
+    /// Creates a new [`ListIndexAliasesResponse`] from a list of [`IndexAlias`].
+    fn try_from_index_aliases<'a>(
+        index_aliases: impl IntoIterator<Item = &'a IndexAlias>,
+    ) -> MetastoreResult<ListIndexAliasesResponse>;
+
+    /// Deserializes the `index_aliases_json` field of a [`ListIndexAliasesResponse`] into a list
+    /// of [`IndexAlias`].
+    fn deserialize_index_aliases(&self) -> MetastoreResult<Vec<IndexAlias>>;
+}
+
+impl ListIndexAliasesResponseExt for ListIndexAliasesResponse {
+    fn try_from_index_aliases<'a>(
+        index_aliases: impl IntoIterator<Item = &'a IndexAlias>,
+    ) -> MetastoreResult<Self> {
+        let index_aliases_json = index_aliases
+            .into_iter()
+            .map(serde_utils::to_json_str)
+            .collect::<MetastoreResult<Vec<String>>>()?;
+        let response = Self { index_aliases_json };
+        Ok(response)
+    }
+
+    fn deserialize_index_aliases(&self) -> MetastoreResult<Vec<IndexAlias>> {
+        self.index_aliases_json
+            .iter()
+            .map(|index_alias_json| serde_utils::from_json_str(index_alias_json))
+            .collect()
+    }
+}
+
+/// Validates an index alias before it is stored in the metastore. The alias ID must not be the ID
+/// of an existing index and the member indexes of the alias must exist.
+pub(crate) fn validate_index_alias(
+    index_alias: &IndexAlias,
+    index_exists: impl Fn(&str) -> bool,
+) -> MetastoreResult<()> {
+    index_alias
+        .validate()
+        .map_err(|error| MetastoreError::InvalidArgument {
+            message: format!("{error:#}"),
+        })?;
+
+    if index_exists(&index_alias.alias_id) {
+        let message = format!(
+            "index alias `{}` conflicts with an existing index with the same ID",
+            index_alias.alias_id
+        );
+        return Err(MetastoreError::InvalidArgument { message });
+    }
+    let missing_index_ids: Vec<IndexId> = index_alias
+        .index_ids
+        .iter()
+        .filter(|index_id| !index_exists(index_id))
+        .cloned()
+        .collect();
+
+    if !missing_index_ids.is_empty() {
+        return Err(MetastoreError::NotFound(EntityKind::Indexes {
+            index_ids: missing_index_ids,
+        }));
+    }
+    Ok(())
+}
+
+/// Returns a `FailedPrecondition` error if one of the index aliases expected by an update was
+/// modified or deleted since the update was prepared.
+pub(crate) fn check_expected_index_aliases<'a>(
+    expected_index_aliases: &[IndexAlias],
+    current_index_alias: impl Fn(&str) -> Option<&'a IndexAlias>,
+) -> MetastoreResult<()> {
+    for expected_index_alias in expected_index_aliases {
+        if current_index_alias(&expected_index_alias.alias_id) != Some(expected_index_alias) {
+            return Err(MetastoreError::FailedPrecondition {
+                entity: EntityKind::IndexAlias {
+                    alias_id: expected_index_alias.alias_id.clone(),
+                },
+                message: "the index alias was modified concurrently".to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Returns an error if an index alias with the same ID as the index `index_id` exists: indexes and
+/// index aliases share the same namespace.
+pub(crate) fn check_index_id_is_not_alias(
+    index_id: &str,
+    alias_exists: impl Fn(&str) -> bool,
+) -> MetastoreResult<()> {
+    if alias_exists(index_id) {
+        let message =
+            format!("index `{index_id}` conflicts with an existing index alias with the same ID");
+        return Err(MetastoreError::InvalidArgument { message });
+    }
+    Ok(())
+}
+
+/// Removes the index `index_id` from the index aliases referencing it, so that no alias is left
+/// dangling once the index is deleted. The write index of an alias is unset if it is the removed
+/// index, and the aliases left without any index are deleted. Returns whether an alias was
+/// modified.
+pub(crate) fn remove_index_from_index_aliases(
+    index_aliases: &mut BTreeMap<IndexAliasId, IndexAlias>,
+    index_id: &str,
+) -> bool {
+    let mut mutation_occurred = false;
+
+    index_aliases.retain(|_, index_alias| {
+        if index_alias.index_ids.remove(index_id) {
+            mutation_occurred = true;
+
+            if index_alias.write_index_id.as_deref() == Some(index_id) {
+                index_alias.write_index_id = None;
+            }
+        }
+        !index_alias.index_ids.is_empty()
+    });
+    mutation_occurred
+}
+
+/// Replaces the index `index_id` with `new_index_id` in the index aliases referencing it, including
+/// as their write index, so that the aliases follow a renamed index. Returns whether an alias was
+/// modified.
+pub(crate) fn rename_index_in_index_aliases(
+    index_aliases: &mut BTreeMap<IndexAliasId, IndexAlias>,
+    index_id: &str,
+    new_index_id: &str,
+) -> bool {
+    let mut mutation_occurred = false;
+
+    for index_alias in index_aliases.values_mut() {
+        if index_alias.index_ids.remove(index_id) {
+            mutation_occurred = true;
+            index_alias.index_ids.insert(new_index_id.to_string());
+
+            if index_alias.write_index_id.as_deref() == Some(index_id) {
+                index_alias.write_index_id = Some(new_index_id.to_string());
+            }
+        }
+    }
+    mutation_occurred
+}
+
 /// Helper trait to build a [`AddSourceRequest`] and deserialize its payload.
 pub trait AddSourceRequestExt {
     /// Creates a new [`AddSourceRequest`] from a [`SourceConfig`].
@@ -406,6 +792,9 @@ pub struct ListSplitsQuery {
     /// A specific split state(s) to filter by.
     pub split_states: Vec<SplitState>,
 
+    /// A specific set of split ID(s) to filter by.
+    pub split_ids: Vec<SplitId>,
+
     /// A specific set of tag(s) to filter by.
     pub tags: Option<TagFilterAst>,
 
@@ -438,6 +827,7 @@ impl ListSplitsQuery {
             limit: None,
             offset: None,
             split_states: Vec::new(),
+            split_ids: Vec::new(),
             tags: None,
             time_range: Default::default(),
             delete_opstamp: Default::default(),
@@ -462,6 +852,7 @@ impl ListSplitsQuery {
             limit: None,
             offset: None,
             split_states: Vec::new(),
+            split_ids: Vec::new(),
             tags: None,
             time_range: Default::default(),
             delete_opstamp: Default::default(),
@@ -496,6 +887,12 @@ impl ListSplitsQuery {
         self
     }
 
+    /// Select splits which have any of the given split IDs.
+    pub fn with_split_ids(mut self, split_ids: impl AsRef<[SplitId]>) -> Self {
+        self.split_ids.extend_from_slice(split_ids.as_ref());
+        self
+    }
+
     /// Select splits which match the given tag filter.
     pub fn with_tags_filter(mut self, tags: TagFilterAst) -> Self {
         self.tags = Some(tags);
@@ -806,4 +1203,36 @@ mod tests {
         let response = ListIndexesMetadataResponse::empty();
         assert_eq!(response.deserialize_indexes_metadata().unwrap(), vec![]);
     }
+
+    #[test]
+    fn test_remove_index_from_index_aliases() {
+        let mut index_aliases: BTreeMap<IndexAliasId, IndexAlias> = [
+            IndexAlias::for_test("alias-0", &["index-0", "index-1"], Some("index-1")),
+            IndexAlias::for_test("alias-1", &["index-1"], None),
+            IndexAlias::for_test("alias-2", &["index-0"], Some("index-0")),
+        ]
+        .into_iter()
+        .map(|index_alias| (index_alias.alias_id.clone(), index_alias))
+        .collect();
+
+        assert!(!remove_index_from_index_aliases(
+            &mut index_aliases,
+            "index-2"
+        ));
+        assert_eq!(index_aliases.len(), 3);
+
+        assert!(remove_index_from_index_aliases(
+            &mut index_aliases,
+            "index-1"
+        ));
+        assert_eq!(index_aliases.len(), 2);
+        assert_eq!(
+            index_aliases["alias-0"],
+            IndexAlias::for_test("alias-0", &["index-0"], None)
+        );
+        assert_eq!(
+            index_aliases["alias-2"],
+            IndexAlias::for_test("alias-2", &["index-0"], Some("index-0"))
+        );
+    }
 }
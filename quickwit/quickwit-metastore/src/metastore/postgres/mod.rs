@@ -24,6 +24,7 @@ mod model;
 mod split_stream;
 mod utils;
 
+use std::collections::{BTreeMap, HashSet};
 use std::fmt::{self, Write};
 use std::time::Duration;
 
@@ -31,21 +32,28 @@ use async_trait::async_trait;
 use futures::StreamExt;
 use quickwit_common::uri::Uri;
 use quickwit_common::{PrettySample, ServiceStream};
-use quickwit_config::{validate_index_id_pattern, PostgresMetastoreConfig, INGEST_V2_SOURCE_ID};
+use quickwit_config::{
+    validate_index_id_pattern, IndexAlias, IndexAliasId, IndexTemplate, PostgresMetastoreConfig,
+    INGEST_V2_SOURCE_ID,
+};
 use quickwit_doc_mapper::tag_pruning::TagFilterAst;
 use quickwit_proto::ingest::{Shard, ShardState};
 use quickwit_proto::metastore::{
-    AcquireShardsRequest, AcquireShardsResponse, AcquireShardsSubresponse, AddSourceRequest,
-    CreateIndexRequest, CreateIndexResponse, DeleteIndexRequest, DeleteQuery, DeleteShardsRequest,
+    serde_utils, AcquireShardsRequest, AcquireShardsResponse, AcquireShardsSubresponse,
+    AddSourceRequest, CreateIndexRequest, CreateIndexResponse, CreateIndexTemplateRequest,
+    DeleteIndexRequest, DeleteIndexTemplatesRequest, DeleteQuery, DeleteShardsRequest,
     DeleteShardsResponse, DeleteSourceRequest, DeleteSplitsRequest, DeleteTask, EmptyResponse,
     EntityKind, IndexMetadataRequest, IndexMetadataResponse, LastDeleteOpstampRequest,
     LastDeleteOpstampResponse, ListDeleteTasksRequest, ListDeleteTasksResponse,
-    ListIndexesMetadataRequest, ListIndexesMetadataResponse, ListShardsRequest, ListShardsResponse,
-    ListShardsSubresponse, ListSplitsRequest, ListSplitsResponse, ListStaleSplitsRequest,
-    MarkSplitsForDeletionRequest, MetastoreError, MetastoreResult, MetastoreService,
-    MetastoreServiceStream, OpenShardsRequest, OpenShardsResponse, OpenShardsSubrequest,
-    OpenShardsSubresponse, PublishSplitsRequest, ResetSourceCheckpointRequest, StageSplitsRequest,
-    ToggleSourceRequest, UpdateSplitsDeleteOpstampRequest, UpdateSplitsDeleteOpstampResponse,
+    ListIndexAliasesRequest, ListIndexAliasesResponse, ListIndexTemplatesRequest,
+    ListIndexTemplatesResponse, ListIndexesMetadataRequest, ListIndexesMetadataResponse,
+    ListShardsRequest, ListShardsResponse, ListShardsSubresponse, ListSplitsRequest,
+    ListSplitsResponse, ListStaleSplitsRequest, MarkSplitsForDeletionRequest, MetastoreError,
+    MetastoreResult, MetastoreService, MetastoreServiceStream, OpenShardsRequest,
+    OpenShardsResponse, OpenShardsSubrequest, OpenShardsSubresponse, OverrideSplitMaturityRequest,
+    PublishSplitsRequest, RenameIndexRequest, RenameIndexResponse, ResetSourceCheckpointRequest,
+    SplitMaturityOverride, StageSplitsRequest, ToggleSourceRequest, UpdateIndexAliasesRequest,
+    UpdateIndexUriRequest, UpdateSplitsDeleteOpstampRequest, UpdateSplitsDeleteOpstampResponse,
 };
 use quickwit_proto::types::{IndexUid, Position, PublishToken, SourceId};
 use sea_query::{all, Asterisk, Cond, Expr, PostgresQueryBuilder, Query};
@@ -59,16 +67,21 @@ use self::migrator::run_migrations;
 use self::model::{PgDeleteTask, PgIndex, PgShard, PgSplit, Splits};
 use self::split_stream::SplitStream;
 use self::utils::{append_query_filters, establish_connection};
-use super::STREAM_SPLITS_CHUNK_SIZE;
+use super::{
+    check_expected_index_aliases, check_index_id_is_not_alias, remove_index_from_index_aliases,
+    rename_index_in_index_aliases, validate_index_alias, validate_index_template,
+    STREAM_SPLITS_CHUNK_SIZE,
+};
 use crate::checkpoint::{
     IndexCheckpointDelta, PartitionId, SourceCheckpoint, SourceCheckpointDelta,
 };
 use crate::metastore::postgres::utils::split_maturity_timestamp;
 use crate::metastore::PublishSplitsRequestExt;
 use crate::{
-    AddSourceRequestExt, CreateIndexRequestExt, IndexMetadata, IndexMetadataResponseExt,
-    ListIndexesMetadataResponseExt, ListSplitsRequestExt, ListSplitsResponseExt,
-    MetastoreServiceExt, Split, SplitState, StageSplitsRequestExt,
+    AddSourceRequestExt, CreateIndexRequestExt, CreateIndexTemplateRequestExt, IndexMetadata,
+    IndexMetadataResponseExt, ListIndexesMetadataResponseExt, ListSplitsRequestExt,
+    ListSplitsResponseExt, MetastoreServiceExt, Split, SplitMetadata, SplitState,
+    StageSplitsRequestExt, UpdateIndexAliasesRequestExt,
 };
 
 /// PostgreSQL metastore implementation.
@@ -174,6 +187,83 @@ async fn index_metadata(
         .index_metadata()
 }
 
+/// Removes the index `index_id` from the index aliases referencing it. See
+/// [`remove_index_from_index_aliases`].
+async fn remove_index_from_pg_index_aliases(
+    tx: &mut Transaction<'_, Postgres>,
+    index_id: &str,
+) -> MetastoreResult<()> {
+    mutate_pg_index_aliases_referencing(tx, index_id, |index_aliases| {
+        remove_index_from_index_aliases(index_aliases, index_id)
+    })
+    .await
+}
+
+/// Replaces the index `index_id` with `new_index_id` in the index aliases referencing it. See
+/// [`rename_index_in_index_aliases`].
+async fn rename_index_in_pg_index_aliases(
+    tx: &mut Transaction<'_, Postgres>,
+    index_id: &str,
+    new_index_id: &str,
+) -> MetastoreResult<()> {
+    mutate_pg_index_aliases_referencing(tx, index_id, |index_aliases| {
+        rename_index_in_index_aliases(index_aliases, index_id, new_index_id)
+    })
+    .await
+}
+
+/// Locks the index aliases referencing the index `index_id` and applies `mutate_fn` to them. The
+/// aliases removed by `mutate_fn` are deleted and the others are updated if `mutate_fn` returns
+/// `true`.
+async fn mutate_pg_index_aliases_referencing(
+    tx: &mut Transaction<'_, Postgres>,
+    index_id: &str,
+    mutate_fn: impl FnOnce(&mut BTreeMap<IndexAliasId, IndexAlias>) -> bool,
+) -> MetastoreResult<()> {
+    let index_aliases_json: Vec<String> = sqlx::query_scalar(
+        r#"
+        SELECT index_alias_json
+        FROM index_aliases
+        WHERE (index_alias_json::jsonb -> 'index_ids') ? $1
+        FOR UPDATE
+        "#,
+    )
+    .bind(index_id)
+    .fetch_all(tx.as_mut())
+    .await?;
+
+    let mut index_aliases = BTreeMap::new();
+
+    for index_alias_json in &index_aliases_json {
+        let index_alias: IndexAlias = serde_utils::from_json_str(index_alias_json)?;
+        index_aliases.insert(index_alias.alias_id.clone(), index_alias);
+    }
+    let referencing_alias_ids: Vec<IndexAliasId> = index_aliases.keys().cloned().collect();
+
+    if !mutate_fn(&mut index_aliases) {
+        return Ok(());
+    }
+    let deleted_alias_ids: Vec<&str> = referencing_alias_ids
+        .iter()
+        .filter(|alias_id| !index_aliases.contains_key(*alias_id))
+        .map(|alias_id| alias_id.as_str())
+        .collect();
+    sqlx::query("DELETE FROM index_aliases WHERE alias_id = ANY($1)")
+        .bind(&deleted_alias_ids)
+        .execute(tx.as_mut())
+        .await?;
+
+    for index_alias in index_aliases.values() {
+        let index_alias_json = serde_utils::to_json_str(index_alias)?;
+        sqlx::query("UPDATE index_aliases SET index_alias_json = $2 WHERE alias_id = $1")
+            .bind(&index_alias.alias_id)
+            .bind(&index_alias_json)
+            .execute(tx.as_mut())
+            .await?;
+    }
+    Ok(())
+}
+
 async fn try_apply_delta_v2(
     tx: &mut Transaction<'_, Postgres>,
     index_uid: &IndexUid,
@@ -379,17 +469,31 @@ impl MetastoreService for PostgresqlMetastore {
                 message: error.to_string(),
             }
         })?;
-        sqlx::query(
-            "INSERT INTO indexes (index_uid, index_id, index_metadata_json) VALUES ($1, $2, $3)",
-        )
-        .bind(index_metadata.index_uid.to_string())
-        .bind(index_metadata.index_uid.index_id())
-        .bind(&index_metadata_json)
-        .execute(&self.connection_pool)
-        .await
-        .map_err(|sqlx_error| convert_sqlx_err(index_metadata.index_id(), sqlx_error))?;
-        Ok(CreateIndexResponse {
-            index_uid: index_metadata.index_uid.to_string(),
+        run_with_tx!(self.connection_pool, tx, {
+            let index_id = index_metadata.index_id();
+            let alias_exists: bool = sqlx::query_scalar(
+                "SELECT EXISTS(SELECT 1 FROM index_aliases WHERE alias_id = $1)",
+            )
+            .bind(index_id)
+            .fetch_one(tx.as_mut())
+            .await?;
+            check_index_id_is_not_alias(index_id, |_| alias_exists)?;
+
+            sqlx::query(
+                r#"
+                INSERT INTO indexes (index_uid, index_id, index_metadata_json)
+                VALUES ($1, $2, $3)
+                "#,
+            )
+            .bind(index_metadata.index_uid.to_string())
+            .bind(index_id)
+            .bind(&index_metadata_json)
+            .execute(tx.as_mut())
+            .await
+            .map_err(|sqlx_error| convert_sqlx_err(index_id, sqlx_error))?;
+            Ok(CreateIndexResponse {
+                index_uid: index_metadata.index_uid.to_string(),
+            })
         })
     }
 
@@ -399,21 +503,25 @@ impl MetastoreService for PostgresqlMetastore {
         request: DeleteIndexRequest,
     ) -> MetastoreResult<EmptyResponse> {
         let index_uid: IndexUid = request.index_uid.into();
-        let delete_result = sqlx::query("DELETE FROM indexes WHERE index_uid = $1")
-            .bind(index_uid.as_str())
-            .execute(&self.connection_pool)
-            .await?;
-        // FIXME: This is not idempotent.
-        if delete_result.rows_affected() == 0 {
-            return Err(MetastoreError::NotFound(EntityKind::Index {
-                index_id: index_uid.index_id().to_string(),
-            }));
-        }
-        info!(
-            index_id = index_uid.index_id(),
-            "deleted index successfully"
-        );
-        Ok(EmptyResponse {})
+        run_with_tx!(self.connection_pool, tx, {
+            let delete_result = sqlx::query("DELETE FROM indexes WHERE index_uid = $1")
+                .bind(index_uid.as_str())
+                .execute(tx.as_mut())
+                .await?;
+            // FIXME: This is not idempotent.
+            if delete_result.rows_affected() == 0 {
+                return Err(MetastoreError::NotFound(EntityKind::Index {
+                    index_id: index_uid.index_id().to_string(),
+                }));
+            }
+            // No alias is left referencing the deleted index.
+            remove_index_from_pg_index_aliases(tx, index_uid.index_id()).await?;
+            info!(
+                index_id = index_uid.index_id(),
+                "deleted index successfully"
+            );
+            Ok(EmptyResponse {})
+        })
     }
 
     #[instrument(skip_all, fields(split_ids))]
@@ -1010,6 +1118,381 @@ impl MetastoreService for PostgresqlMetastore {
         Ok(EmptyResponse {})
     }
 
+    #[instrument(skip(self))]
+    async fn update_index_uri(
+        &mut self,
+        request: UpdateIndexUriRequest,
+    ) -> MetastoreResult<EmptyResponse> {
+        let index_uid: IndexUid = request.index_uid.into();
+        run_with_tx!(self.connection_pool, tx, {
+            mutate_index_metadata(tx, index_uid, |index_metadata| {
+                index_metadata.update_index_uri(&request.index_uri)
+            })
+            .await?;
+            Ok(())
+        })?;
+        Ok(EmptyResponse {})
+    }
+
+    #[instrument(skip(self))]
+    async fn override_split_maturity(
+        &mut self,
+        request: OverrideSplitMaturityRequest,
+    ) -> MetastoreResult<EmptyResponse> {
+        let index_uid: IndexUid = request.index_uid.into();
+        let maturity_override = match request.maturity_override() {
+            SplitMaturityOverride::Unspecified => None,
+            maturity_override => Some(maturity_override),
+        };
+        run_with_tx!(self.connection_pool, tx, {
+            let split_metadata_json_opt: Option<String> = sqlx::query_scalar(
+                r#"
+                SELECT split_metadata_json
+                FROM splits
+                WHERE
+                    index_uid = $1
+                    AND split_id = $2
+                FOR UPDATE
+            "#,
+            )
+            .bind(index_uid.as_str())
+            .bind(&request.split_id)
+            .fetch_optional(tx.as_mut())
+            .await?;
+
+            let Some(split_metadata_json) = split_metadata_json_opt else {
+                if index_opt_for_uid(tx.as_mut(), index_uid.clone())
+                    .await?
+                    .is_none()
+                {
+                    return Err(MetastoreError::NotFound(EntityKind::Index {
+                        index_id: index_uid.index_id().to_string(),
+                    }));
+                }
+                return Err(MetastoreError::NotFound(EntityKind::Split {
+                    split_id: request.split_id,
+                }));
+            };
+            let mut split_metadata: SplitMetadata = serde_json::from_str(&split_metadata_json)
+                .map_err(|error| MetastoreError::JsonDeserializeError {
+                    struct_name: "SplitMetadata".to_string(),
+                    message: error.to_string(),
+                })?;
+            if split_metadata.maturity_override == maturity_override {
+                return Ok(());
+            }
+            split_metadata.maturity_override = maturity_override;
+
+            let split_metadata_json = serde_json::to_string(&split_metadata).map_err(|error| {
+                MetastoreError::JsonSerializeError {
+                    struct_name: "SplitMetadata".to_string(),
+                    message: error.to_string(),
+                }
+            })?;
+            sqlx::query(
+                r#"
+                UPDATE splits
+                SET
+                    split_metadata_json = $3,
+                    maturity_timestamp = to_timestamp($4),
+                    update_timestamp = (CURRENT_TIMESTAMP AT TIME ZONE 'UTC')
+                WHERE
+                    index_uid = $1
+                    AND split_id = $2
+            "#,
+            )
+            .bind(index_uid.as_str())
+            .bind(&request.split_id)
+            .bind(split_metadata_json)
+            .bind(split_maturity_timestamp(&split_metadata))
+            .execute(tx.as_mut())
+            .await?;
+            Ok(())
+        })?;
+        Ok(EmptyResponse {})
+    }
+
+    #[instrument(skip(self))]
+    async fn rename_index(
+        &mut self,
+        request: RenameIndexRequest,
+    ) -> MetastoreResult<RenameIndexResponse> {
+        let index_uid: IndexUid = request.index_uid.into();
+        let new_index_id = request.new_index_id;
+
+        run_with_tx!(self.connection_pool, tx, {
+            let index_id = index_uid.index_id();
+            let mut index_metadata = index_metadata(tx, index_id).await?;
+
+            if index_metadata.index_uid != index_uid {
+                return Err(MetastoreError::NotFound(EntityKind::Index {
+                    index_id: index_id.to_string(),
+                }));
+            }
+            // The queues of the ingesters are identified by the index UID.
+            let num_shards: i64 =
+                sqlx::query_scalar("SELECT COUNT(*) FROM shards WHERE index_uid = $1")
+                    .bind(index_uid.as_str())
+                    .fetch_one(tx.as_mut())
+                    .await?;
+
+            if num_shards > 0 {
+                let entity = EntityKind::Index {
+                    index_id: index_id.to_string(),
+                };
+                let message = "indexes with shards cannot be renamed".to_string();
+                return Err(MetastoreError::FailedPrecondition { entity, message });
+            }
+            if index_opt(tx.as_mut(), &new_index_id).await?.is_some() {
+                return Err(MetastoreError::AlreadyExists(EntityKind::Index {
+                    index_id: new_index_id,
+                }));
+            }
+            let alias_exists: bool = sqlx::query_scalar(
+                "SELECT EXISTS(SELECT 1 FROM index_aliases WHERE alias_id = $1)",
+            )
+            .bind(&new_index_id)
+            .fetch_one(tx.as_mut())
+            .await?;
+            check_index_id_is_not_alias(&new_index_id, |_| alias_exists)?;
+
+            let new_index_uid = index_metadata.rename(&new_index_id)?;
+            let index_metadata_json = serde_utils::to_json_str(&index_metadata)?;
+
+            // The splits and delete tasks reference the index by UID: we insert the renamed index,
+            // move the splits and delete tasks over to it, and finally delete the old index.
+            sqlx::query(
+                r#"
+                INSERT INTO indexes (index_uid, index_id, index_metadata_json, create_timestamp)
+                SELECT $1, $2, $3, create_timestamp
+                FROM indexes
+                WHERE index_uid = $4
+                "#,
+            )
+            .bind(new_index_uid.as_str())
+            .bind(&new_index_id)
+            .bind(&index_metadata_json)
+            .bind(index_uid.as_str())
+            .execute(tx.as_mut())
+            .await
+            .map_err(|sqlx_error| convert_sqlx_err(&new_index_id, sqlx_error))?;
+
+            sqlx::query(
+                r#"
+                UPDATE splits
+                SET
+                    index_uid = $1,
+                    split_metadata_json =
+                        jsonb_set(split_metadata_json::jsonb, '{index_uid}', to_jsonb($1::TEXT))::TEXT
+                WHERE index_uid = $2
+                "#,
+            )
+            .bind(new_index_uid.as_str())
+            .bind(index_uid.as_str())
+            .execute(tx.as_mut())
+            .await?;
+
+            sqlx::query(
+                r#"
+                UPDATE delete_tasks
+                SET
+                    index_uid = $1,
+                    delete_query_json =
+                        jsonb_set(delete_query_json::jsonb, '{index_uid}', to_jsonb($1::TEXT))::TEXT
+                WHERE index_uid = $2
+                "#,
+            )
+            .bind(new_index_uid.as_str())
+            .bind(index_uid.as_str())
+            .execute(tx.as_mut())
+            .await?;
+
+            sqlx::query("DELETE FROM indexes WHERE index_uid = $1")
+                .bind(index_uid.as_str())
+                .execute(tx.as_mut())
+                .await?;
+
+            // The aliases follow the renamed index.
+            rename_index_in_pg_index_aliases(tx, index_id, &new_index_id).await?;
+            info!(
+                index_id,
+                new_index_id = new_index_id.as_str(),
+                "renamed index successfully"
+            );
+            let response = RenameIndexResponse {
+                index_uid: new_index_uid.into(),
+            };
+            Ok(response)
+        })
+    }
+
+    #[instrument(skip(self))]
+    async fn create_index_template(
+        &mut self,
+        request: CreateIndexTemplateRequest,
+    ) -> MetastoreResult<EmptyResponse> {
+        let index_template = request.deserialize_index_template()?;
+        let index_template_json = serde_utils::to_json_str(&index_template)?;
+
+        run_with_tx!(self.connection_pool, tx, {
+            // Prevents concurrent transactions from creating templates that overlap ambiguously.
+            sqlx::query("LOCK TABLE index_templates IN SHARE ROW EXCLUSIVE MODE")
+                .execute(tx.as_mut())
+                .await?;
+            let index_templates_json: Vec<String> =
+                sqlx::query_scalar("SELECT index_template_json FROM index_templates")
+                    .fetch_all(tx.as_mut())
+                    .await?;
+            let index_templates = index_templates_json
+                .iter()
+                .map(|index_template_json| serde_utils::from_json_str(index_template_json))
+                .collect::<MetastoreResult<Vec<IndexTemplate>>>()?;
+
+            if !request.overwrite
+                && index_templates
+                    .iter()
+                    .any(|other| other.template_id == index_template.template_id)
+            {
+                return Err(MetastoreError::AlreadyExists(EntityKind::IndexTemplate {
+                    template_id: index_template.template_id,
+                }));
+            }
+            validate_index_template(&index_template, &index_templates)?;
+
+            sqlx::query(
+                r#"
+                INSERT INTO index_templates (template_id, index_template_json)
+                VALUES ($1, $2)
+                ON CONFLICT (template_id)
+                DO UPDATE SET index_template_json = EXCLUDED.index_template_json
+                "#,
+            )
+            .bind(&index_template.template_id)
+            .bind(&index_template_json)
+            .execute(tx.as_mut())
+            .await?;
+            Ok(())
+        })?;
+        Ok(EmptyResponse {})
+    }
+
+    #[instrument(skip(self))]
+    async fn list_index_templates(
+        &mut self,
+        _request: ListIndexTemplatesRequest,
+    ) -> MetastoreResult<ListIndexTemplatesResponse> {
+        let index_templates_json: Vec<String> = sqlx::query_scalar(
+            "SELECT index_template_json FROM index_templates ORDER BY template_id",
+        )
+        .fetch_all(&self.connection_pool)
+        .await?;
+        Ok(ListIndexTemplatesResponse {
+            index_templates_json,
+        })
+    }
+
+    #[instrument(skip(self))]
+    async fn delete_index_templates(
+        &mut self,
+        request: DeleteIndexTemplatesRequest,
+    ) -> MetastoreResult<EmptyResponse> {
+        sqlx::query("DELETE FROM index_templates WHERE template_id = ANY($1)")
+            .bind(&request.template_ids)
+            .execute(&self.connection_pool)
+            .await?;
+        Ok(EmptyResponse {})
+    }
+
+    #[instrument(skip(self))]
+    async fn update_index_aliases(
+        &mut self,
+        request: UpdateIndexAliasesRequest,
+    ) -> MetastoreResult<EmptyResponse> {
+        let upserted_index_aliases = request.deserialize_upserted_index_aliases()?;
+        let expected_index_aliases = request.deserialize_expected_index_aliases()?;
+        let expected_alias_ids: Vec<&str> = expected_index_aliases
+            .iter()
+            .map(|index_alias| index_alias.alias_id.as_str())
+            .collect();
+        let index_ids: Vec<&str> = upserted_index_aliases
+            .iter()
+            .flat_map(|index_alias| {
+                std::iter::once(&index_alias.alias_id).chain(&index_alias.index_ids)
+            })
+            .map(|index_id| index_id.as_str())
+            .collect();
+
+        run_with_tx!(self.connection_pool, tx, {
+            // Locks the member indexes so that they cannot be deleted until the transaction
+            // commits.
+            let existing_index_ids: HashSet<String> = sqlx::query_scalar(
+                "SELECT index_id FROM indexes WHERE index_id = ANY($1) FOR SHARE",
+            )
+            .bind(&index_ids)
+            .fetch_all(tx.as_mut())
+            .await?
+            .into_iter()
+            .collect();
+
+            for index_alias in &upserted_index_aliases {
+                validate_index_alias(index_alias, |index_id| {
+                    existing_index_ids.contains(index_id)
+                })?;
+            }
+            // Locks the expected aliases so that they cannot be modified until the transaction
+            // commits.
+            let current_index_aliases_json: Vec<String> = sqlx::query_scalar(
+                "SELECT index_alias_json FROM index_aliases WHERE alias_id = ANY($1) FOR UPDATE",
+            )
+            .bind(&expected_alias_ids)
+            .fetch_all(tx.as_mut())
+            .await?;
+            let mut current_index_aliases = BTreeMap::new();
+
+            for index_alias_json in &current_index_aliases_json {
+                let index_alias: IndexAlias = serde_utils::from_json_str(index_alias_json)?;
+                current_index_aliases.insert(index_alias.alias_id.clone(), index_alias);
+            }
+            check_expected_index_aliases(&expected_index_aliases, |alias_id| {
+                current_index_aliases.get(alias_id)
+            })?;
+            sqlx::query("DELETE FROM index_aliases WHERE alias_id = ANY($1)")
+                .bind(&request.deleted_alias_ids)
+                .execute(tx.as_mut())
+                .await?;
+
+            for index_alias in &upserted_index_aliases {
+                let index_alias_json = serde_utils::to_json_str(index_alias)?;
+                sqlx::query(
+                    r#"
+                    INSERT INTO index_aliases (alias_id, index_alias_json)
+                    VALUES ($1, $2)
+                    ON CONFLICT (alias_id)
+                    DO UPDATE SET index_alias_json = EXCLUDED.index_alias_json
+                    "#,
+                )
+                .bind(&index_alias.alias_id)
+                .bind(&index_alias_json)
+                .execute(tx.as_mut())
+                .await?;
+            }
+            Ok(())
+        })?;
+        Ok(EmptyResponse {})
+    }
+
+    #[instrument(skip(self))]
+    async fn list_index_aliases(
+        &mut self,
+        _request: ListIndexAliasesRequest,
+    ) -> MetastoreResult<ListIndexAliasesResponse> {
+        let index_aliases_json: Vec<String> =
+            sqlx::query_scalar("SELECT index_alias_json FROM index_aliases ORDER BY alias_id")
+                .fetch_all(&self.connection_pool)
+                .await?;
+        Ok(ListIndexAliasesResponse { index_aliases_json })
+    }
+
     /// Retrieves the last delete opstamp for a given `index_id`.
     #[instrument(skip(self))]
     async fn last_delete_opstamp(
@@ -1640,6 +2123,19 @@ mod tests {
             )
         );
 
+        let mut select_statement = Query::select();
+        let sql = select_statement.column(Asterisk).from(Splits::Table);
+        let query = ListSplitsQuery::for_index(index_uid.clone())
+            .with_split_ids(["split-1".to_string(), "split-2".to_string()]);
+        append_query_filters(sql, &query);
+
+        assert_eq!(
+            sql.to_string(PostgresQueryBuilder),
+            format!(
+                r#"SELECT * FROM "splits" WHERE "index_uid" = '{index_uid}' AND "split_id" IN ('split-1', 'split-2')"#
+            )
+        );
+
         let mut select_statement = Query::select();
         let sql = select_statement.column(Asterisk).from(Splits::Table);
 
@@ -23,7 +23,7 @@ use std::str::FromStr;
 use std::time::Duration;
 
 use quickwit_common::uri::Uri;
-use quickwit_proto::metastore::{MetastoreError, MetastoreResult};
+use quickwit_proto::metastore::{MetastoreError, MetastoreResult, SplitMaturityOverride};
 use sea_query::{any, Cond, Expr, Func, Order, SelectStatement};
 use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
 use sqlx::{ConnectOptions, Pool, Postgres};
@@ -108,6 +108,10 @@ pub(super) fn append_query_filters(sql: &mut SelectStatement, query: &ListSplits
         );
     };
 
+    if !query.split_ids.is_empty() {
+        sql.cond_where(Expr::col(Splits::SplitId).is_in(query.split_ids.iter().cloned()));
+    };
+
     if let Some(tags) = query.tags.as_ref() {
         sql.cond_where(tags_filter_expression_helper(tags));
     };
@@ -188,10 +192,19 @@ pub(super) fn append_query_filters(sql: &mut SelectStatement, query: &ListSplits
     }
 }
 
+/// Unix timestamp of `9999-12-31T23:59:59Z`, used for splits forced to remain immature.
+const FORCED_IMMATURE_TIMESTAMP: i64 = 253_402_300_799;
+
 /// Returns the unix timestamp at which the split becomes mature.
 /// If the split is mature (`SplitMaturity::Mature`), we return 0
 /// as we don't want the maturity to depend on datetime.
+/// A maturity override takes precedence over the split maturity.
 pub(super) fn split_maturity_timestamp(split_metadata: &SplitMetadata) -> i64 {
+    match split_metadata.maturity_override {
+        Some(SplitMaturityOverride::ForceMature) => return 0,
+        Some(SplitMaturityOverride::ForceImmature) => return FORCED_IMMATURE_TIMESTAMP,
+        Some(SplitMaturityOverride::Unspecified) | None => {}
+    }
     match split_metadata.maturity {
         SplitMaturity::Mature => 0,
         SplitMaturity::Immature { maturation_period } => {
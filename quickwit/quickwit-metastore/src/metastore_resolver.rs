@@ -20,13 +20,17 @@
 use std::collections::HashMap;
 use std::fmt;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::ensure;
 use once_cell::sync::Lazy;
+use quickwit_common::retry::RetryParams;
 use quickwit_common::uri::{Protocol, Uri};
 use quickwit_config::{MetastoreBackend, MetastoreConfig, MetastoreConfigs};
 use quickwit_proto::metastore::MetastoreServiceClient;
 use quickwit_storage::StorageResolver;
+use tokio::time::Instant;
+use tracing::warn;
 
 use crate::metastore::file_backed::FileBackedMetastoreFactory;
 #[cfg(feature = "postgres")]
@@ -35,6 +39,13 @@ use crate::{MetastoreFactory, MetastoreResolverError};
 
 type FactoryAndConfig = (Box<dyn MetastoreFactory>, MetastoreConfig);
 
+const CONNECT_RETRY_PARAMS: RetryParams = RetryParams {
+    base_delay: Duration::from_millis(250),
+    max_delay: Duration::from_secs(5),
+    max_attempts: usize::MAX,
+    jitter: true,
+};
+
 /// Returns the [`MetastoreServiceClient`] instance associated with the protocol of a URI. The
 /// actual creation of metastore objects is delegated to pre-registered [`MetastoreFactory`]. The
 /// resolver is only responsible for dispatching to the appropriate factory.
@@ -65,6 +76,7 @@ impl MetastoreResolver {
             Protocol::File => MetastoreBackend::File,
             Protocol::Ram => MetastoreBackend::File,
             Protocol::S3 => MetastoreBackend::File,
+            Protocol::WebHdfs => MetastoreBackend::File,
             Protocol::PostgreSQL => MetastoreBackend::PostgreSQL,
             _ => {
                 return Err(MetastoreResolverError::UnsupportedBackend(
@@ -82,6 +94,38 @@ impl MetastoreResolver {
         Ok(metastore)
     }
 
+    /// Resolves the given `uri`, retrying with exponential backoff while the metastore is
+    /// unreachable. Gives up and returns the last error once `connect_timeout` has elapsed.
+    pub async fn resolve_with_timeout(
+        &self,
+        uri: &Uri,
+        connect_timeout: Duration,
+    ) -> Result<MetastoreServiceClient, MetastoreResolverError> {
+        let deadline = Instant::now() + connect_timeout;
+        let mut num_attempts = 0;
+
+        loop {
+            let error = match self.resolve(uri).await {
+                Ok(metastore) => return Ok(metastore),
+                Err(error @ MetastoreResolverError::Initialization(_)) => error,
+                Err(error) => return Err(error),
+            };
+            num_attempts += 1;
+            let delay = CONNECT_RETRY_PARAMS.compute_delay(num_attempts);
+
+            if Instant::now() + delay > deadline {
+                return Err(error);
+            }
+            warn!(
+                num_attempts,
+                delay_ms = delay.as_millis(),
+                %error,
+                "failed to connect to metastore, retrying"
+            );
+            tokio::time::sleep(delay).await;
+        }
+    }
+
     /// Creates and returns a [`MetastoreResolver`] holding the default configuration for each
     /// backend. Note that if the environment (env vars, instance metadata, ...) fails
     /// to provide the necessary credentials, the default Azure or S3 file-backed metastores
@@ -177,8 +221,39 @@ impl MetastoreResolverBuilder {
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use quickwit_config::FileMetastoreConfig;
+    use quickwit_proto::metastore::MetastoreError;
 
     use super::*;
+    use crate::metastore_factory::MockMetastoreFactory;
+
+    fn metastore_resolver_unreachable_for(num_failed_attempts: usize) -> MetastoreResolver {
+        let num_attempts = AtomicUsize::new(0);
+        let mut mock_metastore_factory = MockMetastoreFactory::new();
+        mock_metastore_factory
+            .expect_backend()
+            .return_const(MetastoreBackend::File);
+        mock_metastore_factory
+            .expect_resolve()
+            .returning(move |_, _| {
+                if num_attempts.fetch_add(1, Ordering::Relaxed) < num_failed_attempts {
+                    let error = MetastoreError::Connection {
+                        message: "connection refused".to_string(),
+                    };
+                    return Err(MetastoreResolverError::Initialization(error));
+                }
+                Ok(MetastoreServiceClient::mock().into())
+            });
+        MetastoreResolver::builder()
+            .register(
+                mock_metastore_factory,
+                FileMetastoreConfig::default().into(),
+            )
+            .build()
+            .unwrap()
+    }
 
     #[tokio::test]
     async fn test_metastore_resolver_should_not_raise_errors_on_file() {
@@ -205,4 +280,24 @@ mod tests {
             metastore_resolver.resolve(&postgres_uri).await.unwrap();
         }
     }
+
+    #[tokio::test]
+    async fn test_metastore_resolver_resolve_with_timeout() {
+        let metastore_uri = Uri::for_test("ram:///metastore");
+        {
+            let metastore_resolver = metastore_resolver_unreachable_for(2);
+            metastore_resolver
+                .resolve_with_timeout(&metastore_uri, Duration::from_secs(30))
+                .await
+                .unwrap();
+        }
+        {
+            let metastore_resolver = metastore_resolver_unreachable_for(usize::MAX);
+            let error = metastore_resolver
+                .resolve_with_timeout(&metastore_uri, Duration::from_millis(100))
+                .await
+                .unwrap_err();
+            assert!(matches!(error, MetastoreResolverError::Initialization(_)));
+        }
+    }
 }
@@ -17,7 +17,7 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt;
 use std::ops::{Range, RangeInclusive};
 use std::path::PathBuf;
@@ -25,6 +25,7 @@ use std::str::FromStr;
 use std::time::Duration;
 
 use bytesize::ByteSize;
+use quickwit_proto::metastore::SplitMaturityOverride;
 use quickwit_proto::types::IndexUid;
 use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, DurationMilliSeconds};
@@ -132,6 +133,26 @@ pub struct SplitMetadata {
     /// Number of merge operations that was involved to create
     /// this split.
     pub num_merge_ops: usize,
+
+    /// Prefix under which the split file is stored, relative to the index URI, e.g. `2024/03`.
+    /// Splits without a prefix are stored at the root of the index URI.
+    pub split_path_prefix: Option<String>,
+
+    /// Min / max values of the numeric fields registered in the
+    /// [`DocMapping`](quickwit_config::DocMapping) `min_max_fields` attribute. They allow
+    /// pruning the splits that cannot match a range query on these fields. A field is absent
+    /// if no document of the split has a value for it, or if the split was created before the
+    /// field was registered.
+    pub field_ranges: BTreeMap<String, FieldRange>,
+
+    /// Number of distinct values of the fast text fields of the split. Searchers rely on it to
+    /// reject terms aggregations whose buckets would not fit in memory. A field is absent if the
+    /// split was created before these statistics were collected.
+    pub field_cardinalities: BTreeMap<String, u64>,
+
+    /// Maturity forced by an operator via the metastore. When set, it takes precedence over the
+    /// maturity computed by the merge policy.
+    pub maturity_override: Option<SplitMaturityOverride>,
 }
 impl fmt::Debug for SplitMetadata {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -175,6 +196,18 @@ impl fmt::Debug for SplitMetadata {
         debug_struct.field("footer_offsets", &self.footer_offsets);
         debug_struct.field("delete_opstamp", &self.delete_opstamp);
         debug_struct.field("num_merge_ops", &self.num_merge_ops);
+        if let Some(split_path_prefix) = &self.split_path_prefix {
+            debug_struct.field("split_path_prefix", split_path_prefix);
+        }
+        if !self.field_ranges.is_empty() {
+            debug_struct.field("field_ranges", &self.field_ranges);
+        }
+        if !self.field_cardinalities.is_empty() {
+            debug_struct.field("field_cardinalities", &self.field_cardinalities);
+        }
+        if let Some(maturity_override) = &self.maturity_override {
+            debug_struct.field("maturity_override", maturity_override);
+        }
         debug_struct.finish()
     }
 }
@@ -204,8 +237,19 @@ impl SplitMetadata {
         &self.split_id
     }
 
-    /// Returns true if the split is mature at the unix `timestamp`.
+    /// Returns the path of the split file relative to the index URI.
+    pub fn split_path(&self) -> PathBuf {
+        quickwit_common::split_path(self.split_path_prefix.as_deref(), self.split_id())
+    }
+
+    /// Returns true if the split is mature at the unix `timestamp`. A maturity override, if any,
+    /// takes precedence over the split maturity.
     pub fn is_mature(&self, datetime: OffsetDateTime) -> bool {
+        match self.maturity_override {
+            Some(SplitMaturityOverride::ForceMature) => return true,
+            Some(SplitMaturityOverride::ForceImmature) => return false,
+            Some(SplitMaturityOverride::Unspecified) | None => {}
+        }
         match self.maturity {
             SplitMaturity::Mature => true,
             SplitMaturity::Immature {
@@ -228,11 +272,9 @@ impl SplitMetadata {
 
     /// Converts the split metadata into a [`SplitInfo`].
     pub fn as_split_info(&self) -> SplitInfo {
-        let file_name = quickwit_common::split_file(self.split_id());
-
         SplitInfo {
             uncompressed_docs_size_bytes: ByteSize(self.uncompressed_docs_size_in_bytes),
-            file_name: PathBuf::from(file_name),
+            file_name: self.split_path(),
             file_size_bytes: ByteSize(self.footer_offsets.end),
             split_id: self.split_id.clone(),
             num_docs: self.num_docs,
@@ -280,6 +322,10 @@ impl quickwit_config::TestableForRegression for SplitMetadata {
             tags: ["234".to_string(), "aaa".to_string()].into_iter().collect(),
             footer_offsets: 1000..2000,
             num_merge_ops: 3,
+            split_path_prefix: None,
+            field_ranges: BTreeMap::new(),
+            field_cardinalities: BTreeMap::new(),
+            maturity_override: None,
         }
     }
 
@@ -355,6 +401,37 @@ pub enum SplitMaturity {
     },
 }
 
+/// Range of the values of a numeric fast field in a split, bounds included.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+#[serde(rename_all = "snake_case")]
+pub enum FieldRange {
+    /// Range of an `i64` field.
+    I64 {
+        /// Minimum value.
+        min: i64,
+        /// Maximum value.
+        max: i64,
+    },
+    /// Range of a `u64` field.
+    U64 {
+        /// Minimum value.
+        min: u64,
+        /// Maximum value.
+        max: u64,
+    },
+    /// Range of an `f64` field.
+    F64 {
+        /// Minimum value.
+        min: f64,
+        /// Maximum value.
+        max: f64,
+    },
+}
+
+// The bounds of a `FieldRange::F64` are never NaN.
+impl Eq for FieldRange {}
+
 /// Helper function to provide a UTC now timestamp to use
 /// as a default in deserialization.
 ///
@@ -394,6 +471,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_split_metadata_field_ranges_serialization() {
+        let split_metadata = SplitMetadata::for_test("split-1".to_string());
+        let split_metadata_json = serde_json::to_value(&split_metadata).unwrap();
+        assert!(split_metadata_json.get("field_ranges").is_none());
+
+        let mut split_metadata = SplitMetadata::for_test("split-1".to_string());
+        split_metadata.field_ranges.insert(
+            "latency".to_string(),
+            FieldRange::F64 { min: 0.5, max: 3.0 },
+        );
+        split_metadata
+            .field_ranges
+            .insert("status".to_string(), FieldRange::U64 { min: 200, max: 503 });
+        let split_metadata_json = serde_json::to_value(&split_metadata).unwrap();
+        assert_eq!(
+            split_metadata_json["field_ranges"],
+            serde_json::json!({
+                "latency": {"type": "f64", "min": 0.5, "max": 3.0},
+                "status": {"type": "u64", "min": 200, "max": 503},
+            })
+        );
+        let deserialized_split_metadata: SplitMetadata =
+            serde_json::from_value(split_metadata_json).unwrap();
+        assert_eq!(deserialized_split_metadata, split_metadata);
+    }
+
     #[test]
     fn test_split_metadata_debug() {
         let split_metadata = SplitMetadata {
@@ -422,6 +526,10 @@ mod tests {
             footer_offsets: 0..1024,
             delete_opstamp: 0,
             num_merge_ops: 0,
+            split_path_prefix: None,
+            field_ranges: BTreeMap::new(),
+            field_cardinalities: BTreeMap::new(),
+            maturity_override: None,
         };
 
         let expected_output = "SplitMetadata { split_id: \"split-1\", index_uid: \
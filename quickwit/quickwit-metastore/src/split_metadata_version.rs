@@ -17,13 +17,14 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::ops::{Range, RangeInclusive};
 
+use quickwit_proto::metastore::SplitMaturityOverride;
 use quickwit_proto::types::IndexUid;
 use serde::{Deserialize, Serialize};
 
-use crate::split_metadata::{utc_now_timestamp, SplitMaturity};
+use crate::split_metadata::{utc_now_timestamp, FieldRange, SplitMaturity};
 use crate::SplitMetadata;
 
 #[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
@@ -91,6 +92,28 @@ pub(crate) struct SplitMetadataV0_7 {
 
     #[serde(default)]
     num_merge_ops: usize,
+
+    /// Prefix under which the split file is stored, relative to the index URI.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub split_path_prefix: Option<String>,
+
+    /// Min / max values of the numeric fields registered in the doc mapping `min_max_fields`
+    /// attribute.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    #[schema(value_type = Object)]
+    pub field_ranges: BTreeMap<String, FieldRange>,
+
+    /// Number of distinct values of the fast text fields.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub field_cardinalities: BTreeMap<String, u64>,
+
+    /// Maturity forced via the metastore, overriding `maturity`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maturity_override: Option<SplitMaturityOverride>,
 }
 
 impl From<SplitMetadataV0_7> for SplitMetadata {
@@ -126,6 +149,10 @@ impl From<SplitMetadataV0_7> for SplitMetadata {
             tags: v6.tags,
             footer_offsets: v6.footer_offsets,
             num_merge_ops: v6.num_merge_ops,
+            split_path_prefix: v6.split_path_prefix,
+            field_ranges: v6.field_ranges,
+            field_cardinalities: v6.field_cardinalities,
+            maturity_override: v6.maturity_override,
         }
     }
 }
@@ -147,6 +174,10 @@ impl From<SplitMetadata> for SplitMetadataV0_7 {
             tags: split.tags,
             footer_offsets: split.footer_offsets,
             num_merge_ops: split.num_merge_ops,
+            split_path_prefix: split.split_path_prefix,
+            field_ranges: split.field_ranges,
+            field_cardinalities: split.field_cardinalities,
+            maturity_override: split.maturity_override,
         }
     }
 }
@@ -23,21 +23,26 @@
 //  - index_exists
 //  - index_metadata
 //  - list_indexes
+//  - update_index_uri
+//  - rename_index
 //  - delete_index
 
 use quickwit_common::rand::append_random_suffix;
-use quickwit_config::IndexConfig;
+use quickwit_config::{IndexConfig, SourceConfig, SourceParams};
 use quickwit_proto::metastore::{
-    CreateIndexRequest, DeleteIndexRequest, EntityKind, IndexMetadataRequest,
-    ListIndexesMetadataRequest, MetastoreError, MetastoreService, StageSplitsRequest,
+    AddSourceRequest, CreateIndexRequest, DeleteIndexRequest, EntityKind, IndexMetadataRequest,
+    ListIndexesMetadataRequest, ListSplitsRequest, MetastoreError, MetastoreService,
+    RenameIndexRequest, StageSplitsRequest, UpdateIndexUriRequest,
 };
 use quickwit_proto::types::IndexUid;
 
 use super::DefaultForTest;
-use crate::tests::cleanup_index;
+use crate::metastore::MetastoreServiceStreamSplitsExt;
+use crate::tests::{cleanup_index, collect_split_ids};
 use crate::{
-    CreateIndexRequestExt, IndexMetadataResponseExt, ListIndexesMetadataResponseExt,
-    MetastoreServiceExt, SplitMetadata, StageSplitsRequestExt,
+    AddSourceRequestExt, CreateIndexRequestExt, IndexMetadataResponseExt,
+    ListIndexesMetadataResponseExt, ListSplitsRequestExt, MetastoreServiceExt, SplitMetadata,
+    StageSplitsRequestExt,
 };
 
 pub async fn test_metastore_create_index<
@@ -295,6 +300,183 @@ pub async fn test_metastore_list_indexes<MetastoreToTest: MetastoreServiceExt +
     cleanup_index(&mut metastore, index_uid_4).await;
 }
 
+pub async fn test_metastore_update_index_uri<
+    MetastoreToTest: MetastoreService + MetastoreServiceExt + DefaultForTest,
+>() {
+    let mut metastore = MetastoreToTest::default_for_test().await;
+
+    let index_id = append_random_suffix("test-update-index-uri");
+    let index_uri = format!("ram:///indexes/{index_id}");
+    let index_config = IndexConfig::for_test(&index_id, &index_uri);
+
+    let index_uid_not_existing = IndexUid::new_with_random_ulid("index-not-found");
+    let error = metastore
+        .update_index_uri(UpdateIndexUriRequest {
+            index_uid: index_uid_not_existing.to_string(),
+            index_uri: "ram:///indexes/index-not-found".to_string(),
+        })
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        MetastoreError::NotFound(EntityKind::Index { .. })
+    ));
+
+    let create_index_request = CreateIndexRequest::try_from_index_config(index_config).unwrap();
+    let index_uid: IndexUid = metastore
+        .create_index(create_index_request)
+        .await
+        .unwrap()
+        .index_uid
+        .into();
+
+    let error = metastore
+        .update_index_uri(UpdateIndexUriRequest {
+            index_uid: index_uid.to_string(),
+            index_uri: "".to_string(),
+        })
+        .await
+        .unwrap_err();
+    assert!(matches!(error, MetastoreError::InvalidArgument { .. }));
+
+    let new_index_uri = format!("ram:///moved-indexes/{index_id}");
+    metastore
+        .update_index_uri(UpdateIndexUriRequest {
+            index_uid: index_uid.to_string(),
+            index_uri: new_index_uri.clone(),
+        })
+        .await
+        .unwrap();
+
+    let index_metadata = metastore
+        .index_metadata(IndexMetadataRequest::for_index_id(index_id.to_string()))
+        .await
+        .unwrap()
+        .deserialize_index_metadata()
+        .unwrap();
+    assert_eq!(index_metadata.index_uri().as_str(), new_index_uri);
+
+    cleanup_index(&mut metastore, index_uid).await;
+}
+
+pub async fn test_metastore_rename_index<
+    MetastoreToTest: MetastoreService + MetastoreServiceExt + DefaultForTest,
+>() {
+    let mut metastore = MetastoreToTest::default_for_test().await;
+
+    let index_id = append_random_suffix("test-rename-index");
+    let index_uri = format!("ram:///indexes/{index_id}");
+    let index_config = IndexConfig::for_test(&index_id, &index_uri);
+
+    let index_uid_not_existing = IndexUid::new_with_random_ulid("index-not-found");
+    let error = metastore
+        .rename_index(RenameIndexRequest {
+            index_uid: index_uid_not_existing.to_string(),
+            new_index_id: "index-renamed".to_string(),
+        })
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        MetastoreError::NotFound(EntityKind::Index { .. })
+    ));
+
+    let create_index_request = CreateIndexRequest::try_from_index_config(index_config).unwrap();
+    let index_uid: IndexUid = metastore
+        .create_index(create_index_request)
+        .await
+        .unwrap()
+        .index_uid
+        .into();
+
+    let source_id = format!("{index_id}--source");
+    let source_config = SourceConfig::for_test(&source_id, SourceParams::void());
+    let add_source_request =
+        AddSourceRequest::try_from_source_config(index_uid.clone(), source_config).unwrap();
+    metastore.add_source(add_source_request).await.unwrap();
+
+    let split_id = format!("{index_id}--split");
+    let split_metadata = SplitMetadata {
+        split_id: split_id.clone(),
+        index_uid: index_uid.clone(),
+        ..Default::default()
+    };
+    let stage_splits_request =
+        StageSplitsRequest::try_from_split_metadata(index_uid.clone(), split_metadata).unwrap();
+    metastore.stage_splits(stage_splits_request).await.unwrap();
+
+    let other_index_id = append_random_suffix("test-rename-index-other");
+    let other_index_uri = format!("ram:///indexes/{other_index_id}");
+    let other_index_config = IndexConfig::for_test(&other_index_id, &other_index_uri);
+    let create_index_request =
+        CreateIndexRequest::try_from_index_config(other_index_config).unwrap();
+    let other_index_uid: IndexUid = metastore
+        .create_index(create_index_request)
+        .await
+        .unwrap()
+        .index_uid
+        .into();
+
+    let error = metastore
+        .rename_index(RenameIndexRequest {
+            index_uid: index_uid.to_string(),
+            new_index_id: other_index_id.clone(),
+        })
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        MetastoreError::AlreadyExists(EntityKind::Index { .. })
+    ));
+
+    let error = metastore
+        .rename_index(RenameIndexRequest {
+            index_uid: index_uid.to_string(),
+            new_index_id: "-invalid-index-id".to_string(),
+        })
+        .await
+        .unwrap_err();
+    assert!(matches!(error, MetastoreError::InvalidArgument { .. }));
+
+    let new_index_id = append_random_suffix("test-rename-index-renamed");
+    let new_index_uid: IndexUid = metastore
+        .rename_index(RenameIndexRequest {
+            index_uid: index_uid.to_string(),
+            new_index_id: new_index_id.clone(),
+        })
+        .await
+        .unwrap()
+        .index_uid
+        .into();
+    assert_eq!(new_index_uid.index_id(), new_index_id);
+    assert_eq!(new_index_uid.incarnation_id(), index_uid.incarnation_id());
+
+    assert!(!metastore.index_exists(&index_id).await.unwrap());
+
+    let index_metadata = metastore
+        .index_metadata(IndexMetadataRequest::for_index_id(new_index_id.clone()))
+        .await
+        .unwrap()
+        .deserialize_index_metadata()
+        .unwrap();
+    assert_eq!(index_metadata.index_uid, new_index_uid);
+    assert_eq!(index_metadata.index_id(), new_index_id);
+    assert!(index_metadata.sources.contains_key(&source_id));
+
+    let splits = metastore
+        .list_splits(ListSplitsRequest::try_from_index_uid(new_index_uid.clone()).unwrap())
+        .await
+        .unwrap()
+        .collect_splits()
+        .await
+        .unwrap();
+    assert_eq!(collect_split_ids(&splits), &[split_id.as_str()]);
+    assert_eq!(splits[0].split_metadata.index_uid, new_index_uid);
+
+    cleanup_index(&mut metastore, new_index_uid).await;
+    cleanup_index(&mut metastore, other_index_uid).await;
+}
+
 pub async fn test_metastore_delete_index<
     MetastoreToTest: MetastoreService + MetastoreServiceExt + DefaultForTest,
 >() {
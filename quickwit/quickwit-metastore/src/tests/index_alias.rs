@@ -0,0 +1,352 @@
+// Copyright (C) 2024 Quickwit, Inc.
+//
+// Quickwit is offered under the AGPL v3.0 and as commercial software.
+// For commercial licensing, contact us at hello@quickwit.io.
+//
+// AGPL:
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as
+// published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+// Index alias API tests
+//
+//  - update_index_aliases
+//  - list_index_aliases
+//  - create_index, rename_index, and delete_index interactions with aliases
+
+use quickwit_common::rand::append_random_suffix;
+use quickwit_config::{IndexAlias, IndexConfig};
+use quickwit_proto::metastore::{
+    CreateIndexRequest, DeleteIndexRequest, EntityKind, ListIndexAliasesRequest, MetastoreError,
+    MetastoreService, RenameIndexRequest, UpdateIndexAliasesRequest,
+};
+use quickwit_proto::types::IndexUid;
+
+use super::DefaultForTest;
+use crate::{
+    CreateIndexRequestExt, ListIndexAliasesResponseExt, MetastoreServiceExt,
+    UpdateIndexAliasesRequestExt,
+};
+
+async fn create_indexes(metastore: &mut dyn MetastoreService, index_ids: &[&str]) -> Vec<IndexUid> {
+    let mut index_uids = Vec::with_capacity(index_ids.len());
+
+    for index_id in index_ids {
+        let index_uri = format!("ram:///indexes/{index_id}");
+        let index_config = IndexConfig::for_test(index_id, &index_uri);
+        let create_index_request = CreateIndexRequest::try_from_index_config(index_config).unwrap();
+        let index_uid: IndexUid = metastore
+            .create_index(create_index_request)
+            .await
+            .unwrap()
+            .index_uid
+            .into();
+        index_uids.push(index_uid);
+    }
+    index_uids
+}
+
+async fn find_index_alias(
+    metastore: &mut dyn MetastoreService,
+    alias_id: &str,
+) -> Option<IndexAlias> {
+    // Other tests may run concurrently against the same metastore, so we look up the alias
+    // created by the calling test.
+    metastore
+        .list_index_aliases(ListIndexAliasesRequest {})
+        .await
+        .unwrap()
+        .deserialize_index_aliases()
+        .unwrap()
+        .into_iter()
+        .find(|index_alias| index_alias.alias_id == alias_id)
+}
+
+pub async fn test_metastore_update_index_aliases<
+    MetastoreToTest: MetastoreService + MetastoreServiceExt + DefaultForTest,
+>() {
+    let mut metastore = MetastoreToTest::default_for_test().await;
+
+    let alias_id = append_random_suffix("test-update-index-aliases");
+    let index_id_0 = format!("{alias_id}-0");
+    let index_id_1 = format!("{alias_id}-1");
+    let index_id_2 = format!("{alias_id}-2");
+    create_indexes(&mut metastore, &[&index_id_0, &index_id_1, &index_id_2]).await;
+
+    let index_alias =
+        IndexAlias::for_test(&alias_id, &[&index_id_0, &index_id_1], Some(&index_id_1));
+    let update_index_aliases_request =
+        UpdateIndexAliasesRequest::try_from_index_aliases([&index_alias], Vec::new()).unwrap();
+    metastore
+        .update_index_aliases(update_index_aliases_request)
+        .await
+        .unwrap();
+    assert_eq!(
+        find_index_alias(&mut metastore, &alias_id).await.unwrap(),
+        index_alias
+    );
+
+    // Rolls over the alias to a new write index.
+    let index_alias =
+        IndexAlias::for_test(&alias_id, &[&index_id_1, &index_id_2], Some(&index_id_2));
+    let update_index_aliases_request =
+        UpdateIndexAliasesRequest::try_from_index_aliases([&index_alias], Vec::new()).unwrap();
+    metastore
+        .update_index_aliases(update_index_aliases_request)
+        .await
+        .unwrap();
+    assert_eq!(
+        find_index_alias(&mut metastore, &alias_id).await.unwrap(),
+        index_alias
+    );
+
+    // The alias references an index that does not exist.
+    let index_id_not_found = format!("{alias_id}-not-found");
+    let invalid_index_alias = IndexAlias::for_test(&alias_id, &[&index_id_not_found], None);
+    let update_index_aliases_request =
+        UpdateIndexAliasesRequest::try_from_index_aliases([&invalid_index_alias], Vec::new())
+            .unwrap();
+    let error = metastore
+        .update_index_aliases(update_index_aliases_request)
+        .await
+        .unwrap_err();
+    let MetastoreError::NotFound(EntityKind::Indexes { index_ids }) = error else {
+        panic!("expected `NotFound` error, got `{error:?}`");
+    };
+    assert_eq!(index_ids, [index_id_not_found]);
+
+    // The alias ID is the ID of an existing index.
+    let invalid_index_alias = IndexAlias::for_test(&index_id_0, &[&index_id_1], None);
+    let update_index_aliases_request =
+        UpdateIndexAliasesRequest::try_from_index_aliases([&invalid_index_alias], Vec::new())
+            .unwrap();
+    let error = metastore
+        .update_index_aliases(update_index_aliases_request)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, MetastoreError::InvalidArgument { .. }));
+
+    // The write index is not a member of the alias.
+    let invalid_index_alias = IndexAlias::for_test(&alias_id, &[&index_id_1], Some(&index_id_2));
+    let update_index_aliases_request =
+        UpdateIndexAliasesRequest::try_from_index_aliases([&invalid_index_alias], Vec::new())
+            .unwrap();
+    let error = metastore
+        .update_index_aliases(update_index_aliases_request)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, MetastoreError::InvalidArgument { .. }));
+
+    // Failed updates leave the alias untouched.
+    assert_eq!(
+        find_index_alias(&mut metastore, &alias_id).await.unwrap(),
+        index_alias
+    );
+
+    // The update expects a stale state of the alias.
+    let stale_index_alias =
+        IndexAlias::for_test(&alias_id, &[&index_id_0, &index_id_1], Some(&index_id_1));
+    let updated_index_alias = IndexAlias::for_test(
+        &alias_id,
+        &[&index_id_0, &index_id_1, &index_id_2],
+        Some(&index_id_0),
+    );
+    let update_index_aliases_request =
+        UpdateIndexAliasesRequest::try_from_index_aliases([&updated_index_alias], Vec::new())
+            .unwrap()
+            .try_with_expected_index_aliases([&stale_index_alias])
+            .unwrap();
+    let error = metastore
+        .update_index_aliases(update_index_aliases_request)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, MetastoreError::FailedPrecondition { .. }));
+    assert_eq!(
+        find_index_alias(&mut metastore, &alias_id).await.unwrap(),
+        index_alias
+    );
+
+    // The update expects the current state of the alias.
+    let update_index_aliases_request =
+        UpdateIndexAliasesRequest::try_from_index_aliases([&updated_index_alias], Vec::new())
+            .unwrap()
+            .try_with_expected_index_aliases([&index_alias])
+            .unwrap();
+    metastore
+        .update_index_aliases(update_index_aliases_request)
+        .await
+        .unwrap();
+    assert_eq!(
+        find_index_alias(&mut metastore, &alias_id).await.unwrap(),
+        updated_index_alias
+    );
+
+    let update_index_aliases_request =
+        UpdateIndexAliasesRequest::try_from_index_aliases([], vec![alias_id.clone()]).unwrap();
+    metastore
+        .update_index_aliases(update_index_aliases_request.clone())
+        .await
+        .unwrap();
+    assert!(find_index_alias(&mut metastore, &alias_id).await.is_none());
+
+    // Deleting an alias that does not exist is a no-op.
+    metastore
+        .update_index_aliases(update_index_aliases_request)
+        .await
+        .unwrap();
+}
+
+pub async fn test_metastore_resolve_index_aliases<
+    MetastoreToTest: MetastoreService + MetastoreServiceExt + DefaultForTest,
+>() {
+    let mut metastore = MetastoreToTest::default_for_test().await;
+
+    let alias_id = append_random_suffix("test-resolve-index-aliases");
+    let index_id_0 = format!("{alias_id}-0");
+    let index_id_1 = format!("{alias_id}-1");
+    create_indexes(&mut metastore, &[&index_id_0, &index_id_1]).await;
+
+    let read_alias_id = format!("{alias_id}-read");
+    let index_aliases = [
+        IndexAlias::for_test(&alias_id, &[&index_id_0, &index_id_1], Some(&index_id_1)),
+        IndexAlias::for_test(&read_alias_id, &[&index_id_0, &index_id_1], None),
+    ];
+    let update_index_aliases_request =
+        UpdateIndexAliasesRequest::try_from_index_aliases(&index_aliases, Vec::new()).unwrap();
+    metastore
+        .update_index_aliases(update_index_aliases_request)
+        .await
+        .unwrap();
+
+    let resolved_index_id_patterns = metastore
+        .resolve_index_aliases(vec![
+            alias_id.clone(),
+            index_id_0.clone(),
+            "test-index-*".to_string(),
+        ])
+        .await
+        .unwrap();
+    assert_eq!(
+        resolved_index_id_patterns,
+        [
+            index_id_0.clone(),
+            index_id_1.clone(),
+            "test-index-*".to_string()
+        ]
+    );
+
+    let write_index_id = metastore
+        .resolve_write_index_id(alias_id.clone())
+        .await
+        .unwrap();
+    assert_eq!(write_index_id, index_id_1);
+
+    let write_index_id = metastore
+        .resolve_write_index_id(index_id_0.clone())
+        .await
+        .unwrap();
+    assert_eq!(write_index_id, index_id_0);
+
+    let error = metastore
+        .resolve_write_index_id(read_alias_id.clone())
+        .await
+        .unwrap_err();
+    assert!(matches!(error, MetastoreError::InvalidArgument { .. }));
+
+    let update_index_aliases_request =
+        UpdateIndexAliasesRequest::try_from_index_aliases([], vec![alias_id, read_alias_id])
+            .unwrap();
+    metastore
+        .update_index_aliases(update_index_aliases_request)
+        .await
+        .unwrap();
+}
+
+pub async fn test_metastore_index_aliases_follow_index_lifecycle<
+    MetastoreToTest: MetastoreService + MetastoreServiceExt + DefaultForTest,
+>() {
+    let mut metastore = MetastoreToTest::default_for_test().await;
+
+    let alias_id = append_random_suffix("test-index-aliases-lifecycle");
+    let index_id_0 = format!("{alias_id}-0");
+    let index_id_1 = format!("{alias_id}-1");
+    let index_uids = create_indexes(&mut metastore, &[&index_id_0, &index_id_1]).await;
+
+    let index_alias =
+        IndexAlias::for_test(&alias_id, &[&index_id_0, &index_id_1], Some(&index_id_1));
+    let update_index_aliases_request =
+        UpdateIndexAliasesRequest::try_from_index_aliases([&index_alias], Vec::new()).unwrap();
+    metastore
+        .update_index_aliases(update_index_aliases_request)
+        .await
+        .unwrap();
+
+    // Indexes and index aliases share the same namespace.
+    let index_uri = format!("ram:///indexes/{alias_id}");
+    let index_config = IndexConfig::for_test(&alias_id, &index_uri);
+    let create_index_request = CreateIndexRequest::try_from_index_config(index_config).unwrap();
+    let error = metastore
+        .create_index(create_index_request)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, MetastoreError::InvalidArgument { .. }));
+
+    let error = metastore
+        .rename_index(RenameIndexRequest {
+            index_uid: index_uids[0].to_string(),
+            new_index_id: alias_id.clone(),
+        })
+        .await
+        .unwrap_err();
+    assert!(matches!(error, MetastoreError::InvalidArgument { .. }));
+
+    // Renaming the write index of the alias renames it in the alias as well.
+    let renamed_index_id_1 = format!("{alias_id}-1-renamed");
+    let renamed_index_uid_1: IndexUid = metastore
+        .rename_index(RenameIndexRequest {
+            index_uid: index_uids[1].to_string(),
+            new_index_id: renamed_index_id_1.clone(),
+        })
+        .await
+        .unwrap()
+        .index_uid
+        .into();
+    assert_eq!(
+        find_index_alias(&mut metastore, &alias_id).await.unwrap(),
+        IndexAlias::for_test(
+            &alias_id,
+            &[&index_id_0, &renamed_index_id_1],
+            Some(&renamed_index_id_1)
+        )
+    );
+
+    // Deleting the write index of the alias removes it from the alias and unsets the write index.
+    metastore
+        .delete_index(DeleteIndexRequest {
+            index_uid: renamed_index_uid_1.to_string(),
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        find_index_alias(&mut metastore, &alias_id).await.unwrap(),
+        IndexAlias::for_test(&alias_id, &[&index_id_0], None)
+    );
+
+    // Deleting the last index of the alias deletes the alias.
+    metastore
+        .delete_index(DeleteIndexRequest {
+            index_uid: index_uids[0].to_string(),
+        })
+        .await
+        .unwrap();
+    assert!(find_index_alias(&mut metastore, &alias_id).await.is_none());
+}
@@ -0,0 +1,188 @@
+// Copyright (C) 2024 Quickwit, Inc.
+//
+// Quickwit is offered under the AGPL v3.0 and as commercial software.
+// For commercial licensing, contact us at hello@quickwit.io.
+//
+// AGPL:
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as
+// published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+// Index template API tests
+//
+//  - create_index_template
+//  - list_index_templates
+//  - delete_index_templates
+
+use quickwit_common::rand::append_random_suffix;
+use quickwit_config::IndexTemplate;
+use quickwit_proto::metastore::{
+    CreateIndexTemplateRequest, DeleteIndexTemplatesRequest, EntityKind, ListIndexTemplatesRequest,
+    MetastoreError, MetastoreService,
+};
+
+use super::DefaultForTest;
+use crate::{CreateIndexTemplateRequestExt, ListIndexTemplatesResponseExt, MetastoreServiceExt};
+
+async fn list_index_templates_with_ids(
+    metastore: &mut dyn MetastoreService,
+    template_ids: &[&str],
+) -> Vec<IndexTemplate> {
+    // Other tests may run concurrently against the same metastore, so we only keep the templates
+    // created by the calling test.
+    metastore
+        .list_index_templates(ListIndexTemplatesRequest {})
+        .await
+        .unwrap()
+        .deserialize_index_templates()
+        .unwrap()
+        .into_iter()
+        .filter(|index_template| template_ids.contains(&index_template.template_id.as_str()))
+        .collect()
+}
+
+pub async fn test_metastore_create_index_template<
+    MetastoreToTest: MetastoreService + MetastoreServiceExt + DefaultForTest,
+>() {
+    let mut metastore = MetastoreToTest::default_for_test().await;
+
+    let template_id = append_random_suffix("test-create-index-template");
+    let index_id_prefix = append_random_suffix("test-index-template");
+    let index_id_pattern = format!("{index_id_prefix}-foo-*");
+    let mut index_template = IndexTemplate::for_test(&template_id, &[&index_id_pattern], 0);
+
+    let create_index_template_request =
+        CreateIndexTemplateRequest::try_from_index_template(&index_template, false).unwrap();
+    metastore
+        .create_index_template(create_index_template_request.clone())
+        .await
+        .unwrap();
+
+    let error = metastore
+        .create_index_template(create_index_template_request)
+        .await
+        .unwrap_err();
+    let MetastoreError::AlreadyExists(EntityKind::IndexTemplate {
+        template_id: error_template_id,
+    }) = error
+    else {
+        panic!("expected `AlreadyExists` error, got `{error:?}`");
+    };
+    assert_eq!(error_template_id, template_id);
+
+    index_template.description = Some("Overwritten template".to_string());
+    let create_index_template_request =
+        CreateIndexTemplateRequest::try_from_index_template(&index_template, true).unwrap();
+    metastore
+        .create_index_template(create_index_template_request)
+        .await
+        .unwrap();
+
+    let index_templates = list_index_templates_with_ids(&mut metastore, &[&template_id]).await;
+    assert_eq!(index_templates, [index_template.clone()]);
+
+    // An invalid template is rejected.
+    let invalid_template_id = append_random_suffix("test-create-index-template");
+    let invalid_index_template =
+        IndexTemplate::for_test(&invalid_template_id, &[&format!("{index_id_prefix}-**")], 0);
+    let create_index_template_request =
+        CreateIndexTemplateRequest::try_from_index_template(&invalid_index_template, false)
+            .unwrap();
+    let error = metastore
+        .create_index_template(create_index_template_request)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, MetastoreError::InvalidArgument { .. }));
+
+    // A template whose patterns overlap with the patterns of a template with the same priority is
+    // rejected.
+    let other_template_id = append_random_suffix("test-create-index-template");
+    let mut other_index_template = IndexTemplate::for_test(
+        &other_template_id,
+        &[&format!("{index_id_prefix}-*-bar")],
+        0,
+    );
+    let create_index_template_request =
+        CreateIndexTemplateRequest::try_from_index_template(&other_index_template, false).unwrap();
+    let error = metastore
+        .create_index_template(create_index_template_request)
+        .await
+        .unwrap_err();
+    let MetastoreError::InvalidArgument { message } = error else {
+        panic!("expected `InvalidArgument` error, got `{error:?}`");
+    };
+    assert!(message.contains(&index_id_pattern));
+
+    // With a different priority, it is clear which template applies.
+    other_index_template.priority = 1;
+    let create_index_template_request =
+        CreateIndexTemplateRequest::try_from_index_template(&other_index_template, false).unwrap();
+    metastore
+        .create_index_template(create_index_template_request)
+        .await
+        .unwrap();
+
+    let mut index_templates =
+        list_index_templates_with_ids(&mut metastore, &[&template_id, &other_template_id]).await;
+    index_templates.sort_by(|left, right| left.priority.cmp(&right.priority));
+    assert_eq!(index_templates, [index_template, other_index_template]);
+
+    metastore
+        .delete_index_templates(DeleteIndexTemplatesRequest {
+            template_ids: vec![template_id.clone(), other_template_id.clone()],
+        })
+        .await
+        .unwrap();
+}
+
+pub async fn test_metastore_delete_index_templates<
+    MetastoreToTest: MetastoreService + MetastoreServiceExt + DefaultForTest,
+>() {
+    let mut metastore = MetastoreToTest::default_for_test().await;
+
+    let template_id_foo = append_random_suffix("test-delete-index-templates-foo");
+    let template_id_bar = append_random_suffix("test-delete-index-templates-bar");
+
+    for template_id in [&template_id_foo, &template_id_bar] {
+        let index_id_pattern = format!("{template_id}-*");
+        let index_template = IndexTemplate::for_test(template_id, &[&index_id_pattern], 0);
+        let create_index_template_request =
+            CreateIndexTemplateRequest::try_from_index_template(&index_template, false).unwrap();
+        metastore
+            .create_index_template(create_index_template_request)
+            .await
+            .unwrap();
+    }
+    metastore
+        .delete_index_templates(DeleteIndexTemplatesRequest {
+            template_ids: vec![template_id_foo.clone(), "template-not-found".to_string()],
+        })
+        .await
+        .unwrap();
+
+    let index_templates =
+        list_index_templates_with_ids(&mut metastore, &[&template_id_foo, &template_id_bar]).await;
+    assert_eq!(index_templates.len(), 1);
+    assert_eq!(index_templates[0].template_id, template_id_bar);
+
+    // Deleting templates is idempotent.
+    metastore
+        .delete_index_templates(DeleteIndexTemplatesRequest {
+            template_ids: vec![template_id_foo.clone(), template_id_bar.clone()],
+        })
+        .await
+        .unwrap();
+
+    let index_templates =
+        list_index_templates_with_ids(&mut metastore, &[&template_id_foo, &template_id_bar]).await;
+    assert!(index_templates.is_empty());
+}
@@ -237,6 +237,64 @@ pub async fn test_metastore_stream_splits<MetastoreToTest: MetastoreServiceExt +
     );
 }
 
+pub async fn test_metastore_list_splits_stream<
+    MetastoreToTest: MetastoreServiceExt + DefaultForTest,
+>() {
+    let mut metastore = MetastoreToTest::default_for_test().await;
+
+    let index_id = append_random_suffix("test-list-splits-stream");
+    let index_uri = format!("ram:///indexes/{index_id}");
+    let index_config = IndexConfig::for_test(&index_id, &index_uri);
+
+    let create_index_request = CreateIndexRequest::try_from_index_config(index_config).unwrap();
+    let index_uid: IndexUid = metastore
+        .create_index(create_index_request)
+        .await
+        .unwrap()
+        .index_uid
+        .into();
+
+    let num_splits = 3_000;
+    for split_idxs in &(0..num_splits).chunks(500) {
+        let splits_metadata = split_idxs.map(|split_idx| SplitMetadata {
+            split_id: format!("{index_id}--split-{split_idx:0>4}"),
+            index_uid: index_uid.clone(),
+            ..Default::default()
+        });
+        let stage_splits_request =
+            StageSplitsRequest::try_from_splits_metadata(index_uid.clone(), splits_metadata)
+                .unwrap();
+        metastore.stage_splits(stage_splits_request).await.unwrap();
+    }
+    // The backend sends the splits in bounded pages.
+    let list_splits_request = ListSplitsRequest::try_from_index_uid(index_uid.clone()).unwrap();
+    let mut list_splits_stream = metastore.list_splits(list_splits_request).await.unwrap();
+    let mut num_pages = 0;
+
+    while let Some(list_splits_response) = list_splits_stream.try_next().await.unwrap() {
+        let num_splits_in_page = list_splits_response.deserialize_splits().unwrap().len();
+        assert!(num_splits_in_page <= 100);
+        num_pages += 1;
+    }
+    assert!(num_pages >= num_splits / 100);
+
+    // The splits are yielded one by one.
+    let list_splits_request = ListSplitsRequest::try_from_index_uid(index_uid.clone()).unwrap();
+    let mut splits_stream = metastore
+        .list_splits_stream(list_splits_request)
+        .await
+        .unwrap();
+    let mut num_streamed_splits = 0;
+
+    while let Some(split) = splits_stream.try_next().await.unwrap() {
+        assert_eq!(split.split_metadata.index_uid, index_uid);
+        num_streamed_splits += 1;
+    }
+    assert_eq!(num_streamed_splits, num_splits);
+
+    cleanup_index(&mut metastore, index_uid).await;
+}
+
 pub async fn test_metastore_list_splits<MetastoreToTest: MetastoreServiceExt + DefaultForTest>() {
     let mut metastore = MetastoreToTest::default_for_test().await;
 
@@ -894,6 +952,21 @@ pub async fn test_metastore_list_splits<MetastoreToTest: MetastoreServiceExt + D
         let split_ids = collect_split_ids(&splits);
         assert_eq!(split_ids, &[&split_id_2, &split_id_3]);
 
+        let query = ListSplitsQuery::for_index(index_uid.clone()).with_split_ids([
+            split_id_2.clone(),
+            split_id_5.clone(),
+            "unknown-split".to_string(),
+        ]);
+        let splits = metastore
+            .list_splits(ListSplitsRequest::try_from_list_splits_query(query).unwrap())
+            .await
+            .unwrap()
+            .collect_splits()
+            .await
+            .unwrap();
+        let split_ids = collect_split_ids(&splits);
+        assert_eq!(split_ids, &[&split_id_2, &split_id_5]);
+
         cleanup_index(&mut metastore, index_uid).await;
     }
 }
@@ -32,6 +32,8 @@ use quickwit_proto::types::IndexUid;
 
 pub(crate) mod delete_task;
 pub(crate) mod index;
+pub(crate) mod index_alias;
+pub(crate) mod index_template;
 pub(crate) mod list_splits;
 pub(crate) mod shard;
 pub(crate) mod source;
@@ -218,12 +220,80 @@ macro_rules! metastore_test_suite {
                 $crate::tests::index::test_metastore_list_all_indexes::<$metastore_type>().await;
             }
 
+            #[tokio::test]
+            async fn test_metastore_update_index_uri() {
+                let _ = tracing_subscriber::fmt::try_init();
+                $crate::tests::index::test_metastore_update_index_uri::<$metastore_type>().await;
+            }
+
+            #[tokio::test]
+            async fn test_metastore_rename_index() {
+                let _ = tracing_subscriber::fmt::try_init();
+                $crate::tests::index::test_metastore_rename_index::<$metastore_type>().await;
+            }
+
             #[tokio::test]
             async fn test_metastore_delete_index() {
                 let _ = tracing_subscriber::fmt::try_init();
                 $crate::tests::index::test_metastore_delete_index::<$metastore_type>().await;
             }
 
+            // Index template API tests
+            //
+            //  - create_index_template
+            //  - list_index_templates
+            //  - delete_index_templates
+
+            #[tokio::test]
+            async fn test_metastore_create_index_template() {
+                let _ = tracing_subscriber::fmt::try_init();
+                $crate::tests::index_template::test_metastore_create_index_template::<
+                    $metastore_type,
+                >()
+                .await;
+            }
+
+            #[tokio::test]
+            async fn test_metastore_delete_index_templates() {
+                let _ = tracing_subscriber::fmt::try_init();
+                $crate::tests::index_template::test_metastore_delete_index_templates::<
+                    $metastore_type,
+                >()
+                .await;
+            }
+
+            // Index alias API tests
+            //
+            //  - update_index_aliases
+            //  - list_index_aliases
+
+            #[tokio::test]
+            async fn test_metastore_update_index_aliases() {
+                let _ = tracing_subscriber::fmt::try_init();
+                $crate::tests::index_alias::test_metastore_update_index_aliases::<
+                    $metastore_type,
+                >()
+                .await;
+            }
+
+            #[tokio::test]
+            async fn test_metastore_resolve_index_aliases() {
+                let _ = tracing_subscriber::fmt::try_init();
+                $crate::tests::index_alias::test_metastore_resolve_index_aliases::<
+                    $metastore_type,
+                >()
+                .await;
+            }
+
+            #[tokio::test]
+            async fn test_metastore_index_aliases_follow_index_lifecycle() {
+                let _ = tracing_subscriber::fmt::try_init();
+                $crate::tests::index_alias::test_metastore_index_aliases_follow_index_lifecycle::<
+                    $metastore_type,
+                >()
+                .await;
+            }
+
             // Split API tests
             //
             //  - stage_splits
@@ -279,6 +349,12 @@ macro_rules! metastore_test_suite {
                 $crate::tests::list_splits::test_metastore_stream_splits::<$metastore_type>().await;
             }
 
+            #[tokio::test]
+            async fn test_metastore_list_splits_stream() {
+                let _ = tracing_subscriber::fmt::try_init();
+                $crate::tests::list_splits::test_metastore_list_splits_stream::<$metastore_type>().await;
+            }
+
             #[tokio::test]
             async fn test_metastore_list_all_splits() {
                 let _ = tracing_subscriber::fmt::try_init();
@@ -359,6 +435,13 @@ macro_rules! metastore_test_suite {
                     .await;
             }
 
+            #[tokio::test]
+            async fn test_metastore_override_split_maturity() {
+                let _ = tracing_subscriber::fmt::try_init();
+                $crate::tests::split::test_metastore_override_split_maturity::<$metastore_type>()
+                    .await;
+            }
+
             #[tokio::test]
             async fn test_metastore_stage_splits() {
                 let _ = tracing_subscriber::fmt::try_init();
@@ -24,8 +24,9 @@ use quickwit_common::rand::append_random_suffix;
 use quickwit_config::IndexConfig;
 use quickwit_proto::metastore::{
     CreateIndexRequest, DeleteSplitsRequest, EntityKind, IndexMetadataRequest, ListSplitsRequest,
-    ListStaleSplitsRequest, MarkSplitsForDeletionRequest, MetastoreError, PublishSplitsRequest,
-    StageSplitsRequest, UpdateSplitsDeleteOpstampRequest,
+    ListStaleSplitsRequest, MarkSplitsForDeletionRequest, MetastoreError,
+    OverrideSplitMaturityRequest, PublishSplitsRequest, SplitMaturityOverride, StageSplitsRequest,
+    UpdateSplitsDeleteOpstampRequest,
 };
 use quickwit_proto::types::{IndexUid, Position};
 use time::OffsetDateTime;
@@ -38,7 +39,8 @@ use crate::metastore::MetastoreServiceStreamSplitsExt;
 use crate::tests::{cleanup_index, collect_split_ids};
 use crate::{
     CreateIndexRequestExt, IndexMetadataResponseExt, ListSplitsQuery, ListSplitsRequestExt,
-    ListSplitsResponseExt, MetastoreServiceExt, SplitMetadata, SplitState, StageSplitsRequestExt,
+    ListSplitsResponseExt, MetastoreServiceExt, SplitMaturity, SplitMetadata, SplitState,
+    StageSplitsRequestExt,
 };
 
 pub async fn test_metastore_publish_splits_empty_splits_array_is_allowed<
@@ -1828,3 +1830,128 @@ pub async fn test_metastore_update_splits_delete_opstamp<
         cleanup_index(&mut metastore, index_uid).await;
     }
 }
+
+pub async fn test_metastore_override_split_maturity<
+    MetastoreToTest: MetastoreServiceExt + DefaultForTest,
+>() {
+    let mut metastore = MetastoreToTest::default_for_test().await;
+    let current_timestamp = OffsetDateTime::now_utc().unix_timestamp();
+    let index_id = append_random_suffix("test-override-split-maturity");
+    let index_uri = format!("ram:///indexes/{index_id}");
+    let index_config = IndexConfig::for_test(&index_id, &index_uri);
+
+    let create_index_request = CreateIndexRequest::try_from_index_config(index_config).unwrap();
+    let index_uid: IndexUid = metastore
+        .create_index(create_index_request)
+        .await
+        .unwrap()
+        .index_uid
+        .into();
+
+    let split_id = format!("{index_id}--split");
+    let split_metadata = SplitMetadata {
+        split_id: split_id.clone(),
+        index_uid: index_uid.clone(),
+        create_timestamp: current_timestamp,
+        maturity: SplitMaturity::Immature {
+            maturation_period: Duration::from_secs(3600),
+        },
+        ..Default::default()
+    };
+    let stage_splits_request =
+        StageSplitsRequest::try_from_split_metadata(index_uid.clone(), split_metadata).unwrap();
+    metastore.stage_splits(stage_splits_request).await.unwrap();
+
+    let publish_splits_request = PublishSplitsRequest {
+        index_uid: index_uid.to_string(),
+        staged_split_ids: vec![split_id.clone()],
+        ..Default::default()
+    };
+    metastore
+        .publish_splits(publish_splits_request)
+        .await
+        .unwrap();
+
+    let now = OffsetDateTime::from_unix_timestamp(current_timestamp).unwrap();
+    let query = ListSplitsQuery::for_index(index_uid.clone()).retain_immature(now);
+    let splits = metastore
+        .list_splits(ListSplitsRequest::try_from_list_splits_query(query).unwrap())
+        .await
+        .unwrap()
+        .collect_splits()
+        .await
+        .unwrap();
+    assert_eq!(collect_split_ids(&splits), &[&split_id]);
+    assert!(splits[0].split_metadata.maturity_override.is_none());
+
+    let override_split_maturity_request = OverrideSplitMaturityRequest {
+        index_uid: index_uid.to_string(),
+        split_id: split_id.clone(),
+        maturity_override: SplitMaturityOverride::ForceMature as i32,
+    };
+    metastore
+        .override_split_maturity(override_split_maturity_request)
+        .await
+        .unwrap();
+
+    let query = ListSplitsQuery::for_index(index_uid.clone()).retain_immature(now);
+    let splits = metastore
+        .list_splits(ListSplitsRequest::try_from_list_splits_query(query).unwrap())
+        .await
+        .unwrap()
+        .collect_splits()
+        .await
+        .unwrap();
+    assert!(splits.is_empty());
+
+    let query = ListSplitsQuery::for_index(index_uid.clone()).retain_mature(now);
+    let splits = metastore
+        .list_splits(ListSplitsRequest::try_from_list_splits_query(query).unwrap())
+        .await
+        .unwrap()
+        .collect_splits()
+        .await
+        .unwrap();
+    assert_eq!(collect_split_ids(&splits), &[&split_id]);
+    assert_eq!(
+        splits[0].split_metadata.maturity_override,
+        Some(SplitMaturityOverride::ForceMature)
+    );
+
+    let override_split_maturity_request = OverrideSplitMaturityRequest {
+        index_uid: index_uid.to_string(),
+        split_id: split_id.clone(),
+        maturity_override: SplitMaturityOverride::Unspecified as i32,
+    };
+    metastore
+        .override_split_maturity(override_split_maturity_request)
+        .await
+        .unwrap();
+
+    let query = ListSplitsQuery::for_index(index_uid.clone()).retain_immature(now);
+    let splits = metastore
+        .list_splits(ListSplitsRequest::try_from_list_splits_query(query).unwrap())
+        .await
+        .unwrap()
+        .collect_splits()
+        .await
+        .unwrap();
+    assert_eq!(collect_split_ids(&splits), &[&split_id]);
+    assert!(splits[0].split_metadata.maturity_override.is_none());
+
+    let override_split_maturity_request = OverrideSplitMaturityRequest {
+        index_uid: index_uid.to_string(),
+        split_id: "split-not-found".to_string(),
+        maturity_override: SplitMaturityOverride::ForceImmature as i32,
+    };
+    let error = metastore
+        .override_split_maturity(override_split_maturity_request)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        MetastoreError::NotFound(EntityKind::Split { .. })
+    ));
+
+    cleanup_index(&mut metastore, index_uid).await;
+}
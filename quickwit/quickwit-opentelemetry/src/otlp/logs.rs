@@ -26,6 +26,8 @@ use quickwit_config::{load_index_config_from_user_config, ConfigFormat, IndexCon
 use quickwit_ingest::{
     CommitType, DocBatch, DocBatchBuilder, IngestRequest, IngestService, IngestServiceClient,
 };
+use quickwit_metastore::MetastoreServiceExt;
+use quickwit_proto::metastore::MetastoreServiceClient;
 use quickwit_proto::opentelemetry::proto::collector::logs::v1::logs_service_server::LogsService;
 use quickwit_proto::opentelemetry::proto::collector::logs::v1::{
     ExportLogsPartialSuccess, ExportLogsServiceRequest, ExportLogsServiceResponse,
@@ -220,11 +222,21 @@ struct ParsedLogRecords {
 #[derive(Clone)]
 pub struct OtlpGrpcLogsService {
     ingest_service: IngestServiceClient,
+    metastore_opt: Option<MetastoreServiceClient>,
 }
 
 impl OtlpGrpcLogsService {
     pub fn new(ingest_service: IngestServiceClient) -> Self {
-        Self { ingest_service }
+        Self {
+            ingest_service,
+            metastore_opt: None,
+        }
+    }
+
+    /// Resolves index aliases to their write index before ingesting the logs.
+    pub fn with_metastore(mut self, metastore: MetastoreServiceClient) -> Self {
+        self.metastore_opt = Some(metastore);
+        self
     }
 
     pub fn index_config(default_index_root_uri: &Uri) -> anyhow::Result<IndexConfig> {
@@ -426,6 +438,16 @@ impl OtlpGrpcLogsService {
         Ok(())
     }
 
+    /// Returns the ID of the index receiving the logs exported to `index_id`, which may be
+    /// an index alias.
+    async fn resolve_write_index_id(&mut self, index_id: IndexId) -> Result<IndexId, Status> {
+        let Some(metastore) = &mut self.metastore_opt else {
+            return Ok(index_id);
+        };
+        let write_index_id = metastore.resolve_write_index_id(index_id).await?;
+        Ok(write_index_id)
+    }
+
     async fn export_instrumented(
         &mut self,
         request: ExportLogsServiceRequest,
@@ -470,7 +492,9 @@ impl LogsService for OtlpGrpcLogsService {
     ) -> Result<Response<ExportLogsServiceResponse>, Status> {
         let index_id = extract_otel_index_id_from_metadata(request.metadata(), &OtelSignal::Logs)?;
         let request = request.into_inner();
-        self.clone()
+        let mut service = self.clone();
+        let index_id = service.resolve_write_index_id(index_id).await?;
+        service
             .export_instrumented(request, index_id)
             .await
             .map(Response::new)
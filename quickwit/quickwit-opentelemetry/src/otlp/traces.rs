@@ -28,6 +28,8 @@ use quickwit_config::{load_index_config_from_user_config, ConfigFormat, IndexCon
 use quickwit_ingest::{
     CommitType, DocBatch, DocBatchBuilder, IngestRequest, IngestService, IngestServiceClient,
 };
+use quickwit_metastore::MetastoreServiceExt;
+use quickwit_proto::metastore::MetastoreServiceClient;
 use quickwit_proto::opentelemetry::proto::collector::trace::v1::trace_service_server::TraceService;
 use quickwit_proto::opentelemetry::proto::collector::trace::v1::{
     ExportTracePartialSuccess, ExportTraceServiceRequest, ExportTraceServiceResponse,
@@ -679,6 +681,7 @@ struct ParsedSpans {
 pub struct OtlpGrpcTracesService {
     ingest_service: IngestServiceClient,
     commit_type: CommitType,
+    metastore_opt: Option<MetastoreServiceClient>,
 }
 
 impl OtlpGrpcTracesService {
@@ -686,9 +689,16 @@ impl OtlpGrpcTracesService {
         Self {
             ingest_service,
             commit_type: commit_type_opt.unwrap_or_default(),
+            metastore_opt: None,
         }
     }
 
+    /// Resolves index aliases to their write index before ingesting the spans.
+    pub fn with_metastore(mut self, metastore: MetastoreServiceClient) -> Self {
+        self.metastore_opt = Some(metastore);
+        self
+    }
+
     pub fn index_config(default_index_root_uri: &Uri) -> anyhow::Result<IndexConfig> {
         let index_config_str =
             OTEL_TRACES_INDEX_CONFIG.replace("${INDEX_ID}", OTEL_TRACES_INDEX_ID);
@@ -792,6 +802,16 @@ impl OtlpGrpcTracesService {
         Ok(())
     }
 
+    /// Returns the ID of the index receiving the spans exported to `index_id`, which may be
+    /// an index alias.
+    async fn resolve_write_index_id(&mut self, index_id: IndexId) -> Result<IndexId, Status> {
+        let Some(metastore) = &mut self.metastore_opt else {
+            return Ok(index_id);
+        };
+        let write_index_id = metastore.resolve_write_index_id(index_id).await?;
+        Ok(write_index_id)
+    }
+
     async fn export_instrumented(
         &mut self,
         request: ExportTraceServiceRequest,
@@ -837,7 +857,9 @@ impl TraceService for OtlpGrpcTracesService {
         let index_id =
             extract_otel_index_id_from_metadata(request.metadata(), &OtelSignal::Traces)?;
         let request = request.into_inner();
-        self.clone()
+        let mut service = self.clone();
+        let index_id = service.resolve_write_index_id(index_id).await?;
+        service
             .export_instrumented(request, index_id)
             .await
             .map(Response::new)
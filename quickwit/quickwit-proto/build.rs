@@ -23,6 +23,10 @@ use glob::glob;
 use quickwit_codegen::Codegen;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // The file descriptor sets of the gRPC services exposed by the gRPC reflection service are
+    // written to `OUT_DIR`.
+    let out_dir = std::env::var("OUT_DIR")?;
+
     // Prost + tonic + Quickwit codegen for control plane, indexing, metastore, ingest and search
     // services.
     //
@@ -31,6 +35,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .with_protos(&["protos/quickwit/control_plane.proto"])
         .with_includes(&["protos"])
         .with_output_dir("src/codegen/quickwit")
+        .with_file_descriptor_set_dir(&out_dir)
         .with_result_type_path("crate::control_plane::ControlPlaneResult")
         .with_error_type_path("crate::control_plane::ControlPlaneError")
         .run()
@@ -75,6 +80,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .with_protos(&["protos/quickwit/metastore.proto"])
         .with_includes(&["protos"])
         .with_output_dir("src/codegen/quickwit")
+        .with_file_descriptor_set_dir(&out_dir)
         .with_result_type_path("crate::metastore::MetastoreResult")
         .with_error_type_path("crate::metastore::MetastoreError")
         .generate_extra_service_methods()
@@ -118,6 +124,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         ])
         .with_includes(&["protos"])
         .with_output_dir("src/codegen/quickwit")
+        .with_file_descriptor_set_dir(&out_dir)
         .with_result_type_path("crate::ingest::IngestV2Result")
         .with_error_type_path("crate::ingest::IngestV2Error")
         .run()
@@ -136,6 +143,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .type_attribute("ListFieldSerialized", "#[derive(Eq)]")
         .type_attribute("SortByValue", "#[derive(Ord, PartialOrd)]")
         .type_attribute("SortField", "#[derive(Eq, Hash)]")
+        .file_descriptor_set_path(PathBuf::from(&out_dir).join("search_descriptor_set.bin"))
         .out_dir("src/codegen/quickwit")
         .compile_with_config(prost_config, &["protos/quickwit/search.proto"], &["protos"])?;
 
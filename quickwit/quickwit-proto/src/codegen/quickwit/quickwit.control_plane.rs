@@ -1,6 +1,26 @@
 #[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PauseSourceRequest {
+    #[prost(string, tag = "1")]
+    pub index_uid: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub source_id: ::prost::alloc::string::String,
+    /// Whether to pause or resume the source.
+    #[prost(bool, tag = "3")]
+    pub paused: bool,
+}
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PauseSourceResponse {
+    /// The pipelines of the source that could not be paused or resumed.
+    #[prost(message, repeated, tag = "1")]
+    pub failures: ::prost::alloc::vec::Vec<super::indexing::IndexingPipelineFailure>,
+}
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GetOrCreateOpenShardsRequest {
     #[prost(message, repeated, tag = "1")]
     pub subrequests: ::prost::alloc::vec::Vec<GetOrCreateOpenShardsSubrequest>,
@@ -165,6 +185,17 @@ pub trait ControlPlaneService: std::fmt::Debug + dyn_clone::DynClone + Send + Sy
         &mut self,
         request: super::metastore::DeleteSourceRequest,
     ) -> crate::control_plane::ControlPlaneResult<super::metastore::EmptyResponse>;
+    /// Renames an index, preserving its incarnation.
+    async fn rename_index(
+        &mut self,
+        request: super::metastore::RenameIndexRequest,
+    ) -> crate::control_plane::ControlPlaneResult<super::metastore::RenameIndexResponse>;
+    /// Pauses or resumes the indexing pipelines of a source. The pause state is kept by the control plane, which
+    /// propagates it to the indexers through the indexing plan.
+    async fn pause_source(
+        &mut self,
+        request: PauseSourceRequest,
+    ) -> crate::control_plane::ControlPlaneResult<PauseSourceResponse>;
     /// Returns the list of open shards for one or several sources. If the control plane is not able to find any
     /// for a source, it will pick a pair of leader-follower ingesters and will open a new shard.
     async fn get_or_create_open_shards(
@@ -296,6 +327,18 @@ impl ControlPlaneService for ControlPlaneServiceClient {
     ) -> crate::control_plane::ControlPlaneResult<super::metastore::EmptyResponse> {
         self.inner.delete_source(request).await
     }
+    async fn rename_index(
+        &mut self,
+        request: super::metastore::RenameIndexRequest,
+    ) -> crate::control_plane::ControlPlaneResult<super::metastore::RenameIndexResponse> {
+        self.inner.rename_index(request).await
+    }
+    async fn pause_source(
+        &mut self,
+        request: PauseSourceRequest,
+    ) -> crate::control_plane::ControlPlaneResult<PauseSourceResponse> {
+        self.inner.pause_source(request).await
+    }
     async fn get_or_create_open_shards(
         &mut self,
         request: GetOrCreateOpenShardsRequest,
@@ -358,6 +401,22 @@ pub mod control_plane_service_mock {
         > {
             self.inner.lock().await.delete_source(request).await
         }
+        async fn rename_index(
+            &mut self,
+            request: super::super::metastore::RenameIndexRequest,
+        ) -> crate::control_plane::ControlPlaneResult<
+            super::super::metastore::RenameIndexResponse,
+        > {
+            self.inner.lock().await.rename_index(request).await
+        }
+        async fn pause_source(
+            &mut self,
+            request: super::PauseSourceRequest,
+        ) -> crate::control_plane::ControlPlaneResult<
+            super::PauseSourceResponse,
+        > {
+            self.inner.lock().await.pause_source(request).await
+        }
         async fn get_or_create_open_shards(
             &mut self,
             request: super::GetOrCreateOpenShardsRequest,
@@ -470,6 +529,40 @@ for Box<dyn ControlPlaneService> {
         Box::pin(fut)
     }
 }
+impl tower::Service<super::metastore::RenameIndexRequest>
+for Box<dyn ControlPlaneService> {
+    type Response = super::metastore::RenameIndexResponse;
+    type Error = crate::control_plane::ControlPlaneError;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+    fn poll_ready(
+        &mut self,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+    fn call(&mut self, request: super::metastore::RenameIndexRequest) -> Self::Future {
+        let mut svc = self.clone();
+        let fut = async move { svc.rename_index(request).await };
+        Box::pin(fut)
+    }
+}
+impl tower::Service<PauseSourceRequest>
+for Box<dyn ControlPlaneService> {
+    type Response = PauseSourceResponse;
+    type Error = crate::control_plane::ControlPlaneError;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+    fn poll_ready(
+        &mut self,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+    fn call(&mut self, request: PauseSourceRequest) -> Self::Future {
+        let mut svc = self.clone();
+        let fut = async move { svc.pause_source(request).await };
+        Box::pin(fut)
+    }
+}
 impl tower::Service<GetOrCreateOpenShardsRequest> for Box<dyn ControlPlaneService> {
     type Response = GetOrCreateOpenShardsResponse;
     type Error = crate::control_plane::ControlPlaneError;
@@ -531,6 +624,16 @@ struct ControlPlaneServiceTowerServiceStack {
         super::metastore::EmptyResponse,
         crate::control_plane::ControlPlaneError,
     >,
+    rename_index_svc: quickwit_common::tower::BoxService<
+        super::metastore::RenameIndexRequest,
+        super::metastore::RenameIndexResponse,
+        crate::control_plane::ControlPlaneError,
+    >,
+    pause_source_svc: quickwit_common::tower::BoxService<
+        PauseSourceRequest,
+        PauseSourceResponse,
+        crate::control_plane::ControlPlaneError,
+    >,
     get_or_create_open_shards_svc: quickwit_common::tower::BoxService<
         GetOrCreateOpenShardsRequest,
         GetOrCreateOpenShardsResponse,
@@ -551,6 +654,8 @@ impl Clone for ControlPlaneServiceTowerServiceStack {
             add_source_svc: self.add_source_svc.clone(),
             toggle_source_svc: self.toggle_source_svc.clone(),
             delete_source_svc: self.delete_source_svc.clone(),
+            rename_index_svc: self.rename_index_svc.clone(),
+            pause_source_svc: self.pause_source_svc.clone(),
             get_or_create_open_shards_svc: self.get_or_create_open_shards_svc.clone(),
             get_debug_state_svc: self.get_debug_state_svc.clone(),
         }
@@ -590,6 +695,18 @@ impl ControlPlaneService for ControlPlaneServiceTowerServiceStack {
     ) -> crate::control_plane::ControlPlaneResult<super::metastore::EmptyResponse> {
         self.delete_source_svc.ready().await?.call(request).await
     }
+    async fn rename_index(
+        &mut self,
+        request: super::metastore::RenameIndexRequest,
+    ) -> crate::control_plane::ControlPlaneResult<super::metastore::RenameIndexResponse> {
+        self.rename_index_svc.ready().await?.call(request).await
+    }
+    async fn pause_source(
+        &mut self,
+        request: PauseSourceRequest,
+    ) -> crate::control_plane::ControlPlaneResult<PauseSourceResponse> {
+        self.pause_source_svc.ready().await?.call(request).await
+    }
     async fn get_or_create_open_shards(
         &mut self,
         request: GetOrCreateOpenShardsRequest,
@@ -653,6 +770,26 @@ type DeleteSourceLayer = quickwit_common::tower::BoxLayer<
     super::metastore::EmptyResponse,
     crate::control_plane::ControlPlaneError,
 >;
+type RenameIndexLayer = quickwit_common::tower::BoxLayer<
+    quickwit_common::tower::BoxService<
+        super::metastore::RenameIndexRequest,
+        super::metastore::RenameIndexResponse,
+        crate::control_plane::ControlPlaneError,
+    >,
+    super::metastore::RenameIndexRequest,
+    super::metastore::RenameIndexResponse,
+    crate::control_plane::ControlPlaneError,
+>;
+type PauseSourceLayer = quickwit_common::tower::BoxLayer<
+    quickwit_common::tower::BoxService<
+        PauseSourceRequest,
+        PauseSourceResponse,
+        crate::control_plane::ControlPlaneError,
+    >,
+    PauseSourceRequest,
+    PauseSourceResponse,
+    crate::control_plane::ControlPlaneError,
+>;
 type GetOrCreateOpenShardsLayer = quickwit_common::tower::BoxLayer<
     quickwit_common::tower::BoxService<
         GetOrCreateOpenShardsRequest,
@@ -680,6 +817,8 @@ pub struct ControlPlaneServiceTowerLayerStack {
     add_source_layers: Vec<AddSourceLayer>,
     toggle_source_layers: Vec<ToggleSourceLayer>,
     delete_source_layers: Vec<DeleteSourceLayer>,
+    rename_index_layers: Vec<RenameIndexLayer>,
+    pause_source_layers: Vec<PauseSourceLayer>,
     get_or_create_open_shards_layers: Vec<GetOrCreateOpenShardsLayer>,
     get_debug_state_layers: Vec<GetDebugStateLayer>,
 }
@@ -821,6 +960,60 @@ impl ControlPlaneServiceTowerLayerStack {
         >>::Service as tower::Service<
             super::metastore::DeleteSourceRequest,
         >>::Future: Send + 'static,
+        L: tower::Layer<
+                quickwit_common::tower::BoxService<
+                    super::metastore::RenameIndexRequest,
+                    super::metastore::RenameIndexResponse,
+                    crate::control_plane::ControlPlaneError,
+                >,
+            > + Clone + Send + Sync + 'static,
+        <L as tower::Layer<
+            quickwit_common::tower::BoxService<
+                super::metastore::RenameIndexRequest,
+                super::metastore::RenameIndexResponse,
+                crate::control_plane::ControlPlaneError,
+            >,
+        >>::Service: tower::Service<
+                super::metastore::RenameIndexRequest,
+                Response = super::metastore::RenameIndexResponse,
+                Error = crate::control_plane::ControlPlaneError,
+            > + Clone + Send + Sync + 'static,
+        <<L as tower::Layer<
+            quickwit_common::tower::BoxService<
+                super::metastore::RenameIndexRequest,
+                super::metastore::RenameIndexResponse,
+                crate::control_plane::ControlPlaneError,
+            >,
+        >>::Service as tower::Service<
+            super::metastore::RenameIndexRequest,
+        >>::Future: Send + 'static,
+        L: tower::Layer<
+                quickwit_common::tower::BoxService<
+                    PauseSourceRequest,
+                    PauseSourceResponse,
+                    crate::control_plane::ControlPlaneError,
+                >,
+            > + Clone + Send + Sync + 'static,
+        <L as tower::Layer<
+            quickwit_common::tower::BoxService<
+                PauseSourceRequest,
+                PauseSourceResponse,
+                crate::control_plane::ControlPlaneError,
+            >,
+        >>::Service: tower::Service<
+                PauseSourceRequest,
+                Response = PauseSourceResponse,
+                Error = crate::control_plane::ControlPlaneError,
+            > + Clone + Send + Sync + 'static,
+        <<L as tower::Layer<
+            quickwit_common::tower::BoxService<
+                PauseSourceRequest,
+                PauseSourceResponse,
+                crate::control_plane::ControlPlaneError,
+            >,
+        >>::Service as tower::Service<
+            PauseSourceRequest,
+        >>::Future: Send + 'static,
         L: tower::Layer<
                 quickwit_common::tower::BoxService<
                     GetOrCreateOpenShardsRequest,
@@ -884,6 +1077,10 @@ impl ControlPlaneServiceTowerLayerStack {
             .push(quickwit_common::tower::BoxLayer::new(layer.clone()));
         self.delete_source_layers
             .push(quickwit_common::tower::BoxLayer::new(layer.clone()));
+        self.rename_index_layers
+            .push(quickwit_common::tower::BoxLayer::new(layer.clone()));
+        self.pause_source_layers
+            .push(quickwit_common::tower::BoxLayer::new(layer.clone()));
         self.get_or_create_open_shards_layers
             .push(quickwit_common::tower::BoxLayer::new(layer.clone()));
         self.get_debug_state_layers
@@ -995,6 +1192,48 @@ impl ControlPlaneServiceTowerLayerStack {
         self.delete_source_layers.push(quickwit_common::tower::BoxLayer::new(layer));
         self
     }
+    pub fn stack_rename_index_layer<L>(mut self, layer: L) -> Self
+    where
+        L: tower::Layer<
+                quickwit_common::tower::BoxService<
+                    super::metastore::RenameIndexRequest,
+                    super::metastore::RenameIndexResponse,
+                    crate::control_plane::ControlPlaneError,
+                >,
+            > + Send + Sync + 'static,
+        L::Service: tower::Service<
+                super::metastore::RenameIndexRequest,
+                Response = super::metastore::RenameIndexResponse,
+                Error = crate::control_plane::ControlPlaneError,
+            > + Clone + Send + Sync + 'static,
+        <L::Service as tower::Service<
+            super::metastore::RenameIndexRequest,
+        >>::Future: Send + 'static,
+    {
+        self.rename_index_layers.push(quickwit_common::tower::BoxLayer::new(layer));
+        self
+    }
+    pub fn stack_pause_source_layer<L>(mut self, layer: L) -> Self
+    where
+        L: tower::Layer<
+                quickwit_common::tower::BoxService<
+                    PauseSourceRequest,
+                    PauseSourceResponse,
+                    crate::control_plane::ControlPlaneError,
+                >,
+            > + Send + Sync + 'static,
+        L::Service: tower::Service<
+                PauseSourceRequest,
+                Response = PauseSourceResponse,
+                Error = crate::control_plane::ControlPlaneError,
+            > + Clone + Send + Sync + 'static,
+        <L::Service as tower::Service<
+            PauseSourceRequest,
+        >>::Future: Send + 'static,
+    {
+        self.pause_source_layers.push(quickwit_common::tower::BoxLayer::new(layer));
+        self
+    }
     pub fn stack_get_or_create_open_shards_layer<L>(mut self, layer: L) -> Self
     where
         L: tower::Layer<
@@ -1122,6 +1361,22 @@ impl ControlPlaneServiceTowerLayerStack {
                 quickwit_common::tower::BoxService::new(boxed_instance.clone()),
                 |svc, layer| layer.layer(svc),
             );
+        let rename_index_svc = self
+            .rename_index_layers
+            .into_iter()
+            .rev()
+            .fold(
+                quickwit_common::tower::BoxService::new(boxed_instance.clone()),
+                |svc, layer| layer.layer(svc),
+            );
+        let pause_source_svc = self
+            .pause_source_layers
+            .into_iter()
+            .rev()
+            .fold(
+                quickwit_common::tower::BoxService::new(boxed_instance.clone()),
+                |svc, layer| layer.layer(svc),
+            );
         let get_or_create_open_shards_svc = self
             .get_or_create_open_shards_layers
             .into_iter()
@@ -1145,6 +1400,8 @@ impl ControlPlaneServiceTowerLayerStack {
             add_source_svc,
             toggle_source_svc,
             delete_source_svc,
+            rename_index_svc,
+            pause_source_svc,
             get_or_create_open_shards_svc,
             get_debug_state_svc,
         };
@@ -1268,6 +1525,24 @@ where
                 crate::control_plane::ControlPlaneError,
             >,
         >
+        + tower::Service<
+            super::metastore::RenameIndexRequest,
+            Response = super::metastore::RenameIndexResponse,
+            Error = crate::control_plane::ControlPlaneError,
+            Future = BoxFuture<
+                super::metastore::RenameIndexResponse,
+                crate::control_plane::ControlPlaneError,
+            >,
+        >
+        + tower::Service<
+            PauseSourceRequest,
+            Response = PauseSourceResponse,
+            Error = crate::control_plane::ControlPlaneError,
+            Future = BoxFuture<
+                PauseSourceResponse,
+                crate::control_plane::ControlPlaneError,
+            >,
+        >
         + tower::Service<
             GetOrCreateOpenShardsRequest,
             Response = GetOrCreateOpenShardsResponse,
@@ -1319,6 +1594,18 @@ where
     ) -> crate::control_plane::ControlPlaneResult<super::metastore::EmptyResponse> {
         self.call(request).await
     }
+    async fn rename_index(
+        &mut self,
+        request: super::metastore::RenameIndexRequest,
+    ) -> crate::control_plane::ControlPlaneResult<super::metastore::RenameIndexResponse> {
+        self.call(request).await
+    }
+    async fn pause_source(
+        &mut self,
+        request: PauseSourceRequest,
+    ) -> crate::control_plane::ControlPlaneResult<PauseSourceResponse> {
+        self.call(request).await
+    }
     async fn get_or_create_open_shards(
         &mut self,
         request: GetOrCreateOpenShardsRequest,
@@ -1418,6 +1705,26 @@ where
             .map(|response| response.into_inner())
             .map_err(|error| error.into())
     }
+    async fn rename_index(
+        &mut self,
+        request: super::metastore::RenameIndexRequest,
+    ) -> crate::control_plane::ControlPlaneResult<super::metastore::RenameIndexResponse> {
+        self.inner
+            .rename_index(request)
+            .await
+            .map(|response| response.into_inner())
+            .map_err(|error| error.into())
+    }
+    async fn pause_source(
+        &mut self,
+        request: PauseSourceRequest,
+    ) -> crate::control_plane::ControlPlaneResult<PauseSourceResponse> {
+        self.inner
+            .pause_source(request)
+            .await
+            .map(|response| response.into_inner())
+            .map_err(|error| error.into())
+    }
     async fn get_or_create_open_shards(
         &mut self,
         request: GetOrCreateOpenShardsRequest,
@@ -1509,6 +1816,28 @@ for ControlPlaneServiceGrpcServerAdapter {
             .map(tonic::Response::new)
             .map_err(|error| error.into())
     }
+    async fn rename_index(
+        &self,
+        request: tonic::Request<super::metastore::RenameIndexRequest>,
+    ) -> Result<tonic::Response<super::metastore::RenameIndexResponse>, tonic::Status> {
+        self.inner
+            .clone()
+            .rename_index(request.into_inner())
+            .await
+            .map(tonic::Response::new)
+            .map_err(|error| error.into())
+    }
+    async fn pause_source(
+        &self,
+        request: tonic::Request<PauseSourceRequest>,
+    ) -> Result<tonic::Response<PauseSourceResponse>, tonic::Status> {
+        self.inner
+            .clone()
+            .pause_source(request.into_inner())
+            .await
+            .map(tonic::Response::new)
+            .map_err(|error| error.into())
+    }
     async fn get_or_create_open_shards(
         &self,
         request: tonic::Request<GetOrCreateOpenShardsRequest>,
@@ -1778,6 +2107,73 @@ pub mod control_plane_service_grpc_client {
                 );
             self.inner.unary(req, path, codec).await
         }
+        /// Renames an index, preserving its incarnation.
+        pub async fn rename_index(
+            &mut self,
+            request: impl tonic::IntoRequest<
+                super::super::metastore::RenameIndexRequest,
+            >,
+        ) -> std::result::Result<
+            tonic::Response<super::super::metastore::RenameIndexResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/quickwit.control_plane.ControlPlaneService/RenameIndex",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "quickwit.control_plane.ControlPlaneService",
+                        "RenameIndex",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// Pauses or resumes the indexing pipelines of a source. The pause state is kept by the control plane, which
+        /// propagates it to the indexers through the indexing plan.
+        pub async fn pause_source(
+            &mut self,
+            request: impl tonic::IntoRequest<
+                super::PauseSourceRequest,
+            >,
+        ) -> std::result::Result<
+            tonic::Response<super::PauseSourceResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/quickwit.control_plane.ControlPlaneService/PauseSource",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "quickwit.control_plane.ControlPlaneService",
+                        "PauseSource",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
         /// Returns the list of open shards for one or several sources. If the control plane is not able to find any
         /// for a source, it will pick a pair of leader-follower ingesters and will open a new shard.
         pub async fn get_or_create_open_shards(
@@ -1890,6 +2286,23 @@ pub mod control_plane_service_grpc_server {
             tonic::Response<super::super::metastore::EmptyResponse>,
             tonic::Status,
         >;
+        /// Renames an index, preserving its incarnation.
+        async fn rename_index(
+            &self,
+            request: tonic::Request<super::super::metastore::RenameIndexRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::metastore::RenameIndexResponse>,
+            tonic::Status,
+        >;
+        /// Pauses or resumes the indexing pipelines of a source. The pause state is kept by the control plane, which
+        /// propagates it to the indexers through the indexing plan.
+        async fn pause_source(
+            &self,
+            request: tonic::Request<super::PauseSourceRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::PauseSourceResponse>,
+            tonic::Status,
+        >;
         /// Returns the list of open shards for one or several sources. If the control plane is not able to find any
         /// for a source, it will pick a pair of leader-follower ingesters and will open a new shard.
         async fn get_or_create_open_shards(
@@ -2231,6 +2644,104 @@ pub mod control_plane_service_grpc_server {
                     };
                     Box::pin(fut)
                 }
+                "/quickwit.control_plane.ControlPlaneService/RenameIndex" => {
+                    #[allow(non_camel_case_types)]
+                    struct RenameIndexSvc<T: ControlPlaneServiceGrpc>(pub Arc<T>);
+                    impl<
+                        T: ControlPlaneServiceGrpc,
+                    > tonic::server::UnaryService<
+                        super::super::metastore::RenameIndexRequest,
+                    > for RenameIndexSvc<T> {
+                        type Response = super::super::metastore::RenameIndexResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::super::metastore::RenameIndexRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                (*inner).rename_index(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = RenameIndexSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/quickwit.control_plane.ControlPlaneService/PauseSource" => {
+                    #[allow(non_camel_case_types)]
+                    struct PauseSourceSvc<T: ControlPlaneServiceGrpc>(pub Arc<T>);
+                    impl<
+                        T: ControlPlaneServiceGrpc,
+                    > tonic::server::UnaryService<
+                        super::PauseSourceRequest,
+                    > for PauseSourceSvc<T> {
+                        type Response = super::PauseSourceResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::PauseSourceRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                (*inner).pause_source(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = PauseSourceSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/quickwit.control_plane.ControlPlaneService/GetOrCreateOpenShards" => {
                     #[allow(non_camel_case_types)]
                     struct GetOrCreateOpenShardsSvc<T: ControlPlaneServiceGrpc>(
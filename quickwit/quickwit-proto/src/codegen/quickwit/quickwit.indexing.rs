@@ -21,11 +21,30 @@ pub struct IndexingTask {
     /// The shards assigned to the indexer.
     #[prost(message, repeated, tag = "3")]
     pub shard_ids: ::prost::alloc::vec::Vec<crate::types::ShardId>,
+    /// Whether the source of the pipeline is paused.
+    #[prost(bool, tag = "5")]
+    pub paused: bool,
 }
 #[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct ApplyIndexingPlanResponse {}
+pub struct ApplyIndexingPlanResponse {
+    /// The pipelines of the plan that could not be paused or resumed.
+    #[prost(message, repeated, tag = "1")]
+    pub failures: ::prost::alloc::vec::Vec<IndexingPipelineFailure>,
+}
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct IndexingPipelineFailure {
+    /// The ID of the node running the pipeline.
+    #[prost(string, tag = "1")]
+    pub node_id: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub pipeline_uid: ::core::option::Option<crate::types::PipelineUid>,
+    #[prost(string, tag = "3")]
+    pub error: ::prost::alloc::string::String,
+}
 /// BEGIN quickwit-codegen
 #[allow(unused_imports)]
 use std::str::FromStr;
@@ -43,6 +43,8 @@ pub struct PersistSubrequest {
     pub source_id: ::prost::alloc::string::String,
     #[prost(message, optional, tag = "4")]
     pub shard_id: ::core::option::Option<crate::types::ShardId>,
+    /// When empty and the commit type is `CommitTypeV2::Force`, a commit record is appended to the shard if
+    /// it holds pending records, which forces the indexer to commit them right away.
     #[prost(message, optional, tag = "5")]
     pub doc_batch: ::core::option::Option<super::DocBatchV2>,
 }
@@ -387,6 +389,34 @@ pub struct DecommissionResponse {}
 #[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetReplicationPositionsRequest {
+    #[prost(message, repeated, tag = "1")]
+    pub shards: ::prost::alloc::vec::Vec<super::ShardIds>,
+}
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetReplicationPositionsResponse {
+    /// The shards that are not hosted by the ingester are omitted.
+    #[prost(message, repeated, tag = "1")]
+    pub replication_positions: ::prost::alloc::vec::Vec<ShardReplicationPosition>,
+}
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ShardReplicationPosition {
+    #[prost(string, tag = "1")]
+    pub index_uid: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub source_id: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "3")]
+    pub shard_id: ::core::option::Option<crate::types::ShardId>,
+    #[prost(message, optional, tag = "4")]
+    pub replication_position_inclusive: ::core::option::Option<crate::types::Position>,
+}
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct OpenObservationStreamRequest {}
 #[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -582,6 +612,11 @@ pub trait IngesterService: std::fmt::Debug + dyn_clone::DynClone + Send + Sync +
         &mut self,
         request: DecommissionRequest,
     ) -> crate::ingest::IngestV2Result<DecommissionResponse>;
+    /// Returns the replication positions of a set of shards hosted by the ingester.
+    async fn get_replication_positions(
+        &mut self,
+        request: GetReplicationPositionsRequest,
+    ) -> crate::ingest::IngestV2Result<GetReplicationPositionsResponse>;
 }
 dyn_clone::clone_trait_object!(IngesterService);
 #[cfg(any(test, feature = "testsuite"))]
@@ -730,6 +765,12 @@ impl IngesterService for IngesterServiceClient {
     ) -> crate::ingest::IngestV2Result<DecommissionResponse> {
         self.inner.decommission(request).await
     }
+    async fn get_replication_positions(
+        &mut self,
+        request: GetReplicationPositionsRequest,
+    ) -> crate::ingest::IngestV2Result<GetReplicationPositionsResponse> {
+        self.inner.get_replication_positions(request).await
+    }
 }
 #[cfg(any(test, feature = "testsuite"))]
 pub mod ingester_service_mock {
@@ -804,6 +845,12 @@ pub mod ingester_service_mock {
         ) -> crate::ingest::IngestV2Result<super::DecommissionResponse> {
             self.inner.lock().await.decommission(request).await
         }
+        async fn get_replication_positions(
+            &mut self,
+            request: super::GetReplicationPositionsRequest,
+        ) -> crate::ingest::IngestV2Result<super::GetReplicationPositionsResponse> {
+            self.inner.lock().await.get_replication_positions(request).await
+        }
     }
     impl From<MockIngesterService> for IngesterServiceClient {
         fn from(mock: MockIngesterService) -> Self {
@@ -981,6 +1028,22 @@ impl tower::Service<DecommissionRequest> for Box<dyn IngesterService> {
         Box::pin(fut)
     }
 }
+impl tower::Service<GetReplicationPositionsRequest> for Box<dyn IngesterService> {
+    type Response = GetReplicationPositionsResponse;
+    type Error = crate::ingest::IngestV2Error;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+    fn poll_ready(
+        &mut self,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+    fn call(&mut self, request: GetReplicationPositionsRequest) -> Self::Future {
+        let mut svc = self.clone();
+        let fut = async move { svc.get_replication_positions(request).await };
+        Box::pin(fut)
+    }
+}
 /// A tower service stack is a set of tower services.
 #[derive(Debug)]
 struct IngesterServiceTowerServiceStack {
@@ -1035,6 +1098,11 @@ struct IngesterServiceTowerServiceStack {
         DecommissionResponse,
         crate::ingest::IngestV2Error,
     >,
+    get_replication_positions_svc: quickwit_common::tower::BoxService<
+        GetReplicationPositionsRequest,
+        GetReplicationPositionsResponse,
+        crate::ingest::IngestV2Error,
+    >,
 }
 impl Clone for IngesterServiceTowerServiceStack {
     fn clone(&self) -> Self {
@@ -1050,6 +1118,7 @@ impl Clone for IngesterServiceTowerServiceStack {
             close_shards_svc: self.close_shards_svc.clone(),
             ping_svc: self.ping_svc.clone(),
             decommission_svc: self.decommission_svc.clone(),
+            get_replication_positions_svc: self.get_replication_positions_svc.clone(),
         }
     }
 }
@@ -1115,6 +1184,12 @@ impl IngesterService for IngesterServiceTowerServiceStack {
     ) -> crate::ingest::IngestV2Result<DecommissionResponse> {
         self.decommission_svc.ready().await?.call(request).await
     }
+    async fn get_replication_positions(
+        &mut self,
+        request: GetReplicationPositionsRequest,
+    ) -> crate::ingest::IngestV2Result<GetReplicationPositionsResponse> {
+        self.get_replication_positions_svc.ready().await?.call(request).await
+    }
 }
 type PersistLayer = quickwit_common::tower::BoxLayer<
     quickwit_common::tower::BoxService<
@@ -1216,6 +1291,16 @@ type DecommissionLayer = quickwit_common::tower::BoxLayer<
     DecommissionResponse,
     crate::ingest::IngestV2Error,
 >;
+type GetReplicationPositionsLayer = quickwit_common::tower::BoxLayer<
+    quickwit_common::tower::BoxService<
+        GetReplicationPositionsRequest,
+        GetReplicationPositionsResponse,
+        crate::ingest::IngestV2Error,
+    >,
+    GetReplicationPositionsRequest,
+    GetReplicationPositionsResponse,
+    crate::ingest::IngestV2Error,
+>;
 #[derive(Debug, Default)]
 pub struct IngesterServiceTowerLayerStack {
     persist_layers: Vec<PersistLayer>,
@@ -1228,6 +1313,7 @@ pub struct IngesterServiceTowerLayerStack {
     close_shards_layers: Vec<CloseShardsLayer>,
     ping_layers: Vec<PingLayer>,
     decommission_layers: Vec<DecommissionLayer>,
+    get_replication_positions_layers: Vec<GetReplicationPositionsLayer>,
 }
 impl IngesterServiceTowerLayerStack {
     pub fn stack_layer<L>(mut self, layer: L) -> Self
@@ -1486,6 +1572,31 @@ impl IngesterServiceTowerLayerStack {
                 crate::ingest::IngestV2Error,
             >,
         >>::Service as tower::Service<DecommissionRequest>>::Future: Send + 'static,
+        L: tower::Layer<
+                quickwit_common::tower::BoxService<
+                    GetReplicationPositionsRequest,
+                    GetReplicationPositionsResponse,
+                    crate::ingest::IngestV2Error,
+                >,
+            > + Clone + Send + Sync + 'static,
+        <L as tower::Layer<
+            quickwit_common::tower::BoxService<
+                GetReplicationPositionsRequest,
+                GetReplicationPositionsResponse,
+                crate::ingest::IngestV2Error,
+            >,
+        >>::Service: tower::Service<
+                GetReplicationPositionsRequest,
+                Response = GetReplicationPositionsResponse,
+                Error = crate::ingest::IngestV2Error,
+            > + Clone + Send + Sync + 'static,
+        <<L as tower::Layer<
+            quickwit_common::tower::BoxService<
+                GetReplicationPositionsRequest,
+                GetReplicationPositionsResponse,
+                crate::ingest::IngestV2Error,
+            >,
+        >>::Service as tower::Service<GetReplicationPositionsRequest>>::Future: Send + 'static,
     {
         self.persist_layers.push(quickwit_common::tower::BoxLayer::new(layer.clone()));
         self.open_replication_stream_layers
@@ -1505,6 +1616,8 @@ impl IngesterServiceTowerLayerStack {
         self.ping_layers.push(quickwit_common::tower::BoxLayer::new(layer.clone()));
         self.decommission_layers
             .push(quickwit_common::tower::BoxLayer::new(layer.clone()));
+        self.get_replication_positions_layers
+            .push(quickwit_common::tower::BoxLayer::new(layer.clone()));
         self
     }
     pub fn stack_persist_layer<L>(mut self, layer: L) -> Self
@@ -1703,6 +1816,26 @@ impl IngesterServiceTowerLayerStack {
         self.decommission_layers.push(quickwit_common::tower::BoxLayer::new(layer));
         self
     }
+    pub fn stack_get_replication_positions_layer<L>(mut self, layer: L) -> Self
+    where
+        L: tower::Layer<
+                quickwit_common::tower::BoxService<
+                    GetReplicationPositionsRequest,
+                    GetReplicationPositionsResponse,
+                    crate::ingest::IngestV2Error,
+                >,
+            > + Send + Sync + 'static,
+        L::Service: tower::Service<
+                GetReplicationPositionsRequest,
+                Response = GetReplicationPositionsResponse,
+                Error = crate::ingest::IngestV2Error,
+            > + Clone + Send + Sync + 'static,
+        <L::Service as tower::Service<GetReplicationPositionsRequest>>::Future: Send + 'static,
+    {
+        self.get_replication_positions_layers
+            .push(quickwit_common::tower::BoxLayer::new(layer));
+        self
+    }
     pub fn build<T>(self, instance: T) -> IngesterServiceClient
     where
         T: IngesterService,
@@ -1829,6 +1962,14 @@ impl IngesterServiceTowerLayerStack {
                 quickwit_common::tower::BoxService::new(boxed_instance.clone()),
                 |svc, layer| layer.layer(svc),
             );
+        let get_replication_positions_svc = self
+            .get_replication_positions_layers
+            .into_iter()
+            .rev()
+            .fold(
+                quickwit_common::tower::BoxService::new(boxed_instance.clone()),
+                |svc, layer| layer.layer(svc),
+            );
         let tower_svc_stack = IngesterServiceTowerServiceStack {
             inner: boxed_instance.clone(),
             persist_svc,
@@ -1841,6 +1982,7 @@ impl IngesterServiceTowerLayerStack {
             close_shards_svc,
             ping_svc,
             decommission_svc,
+            get_replication_positions_svc,
         };
         IngesterServiceClient::new(tower_svc_stack)
     }
@@ -1985,6 +2127,11 @@ where
             Response = DecommissionResponse,
             Error = crate::ingest::IngestV2Error,
             Future = BoxFuture<DecommissionResponse, crate::ingest::IngestV2Error>,
+        >        + tower::Service<
+            GetReplicationPositionsRequest,
+            Response = GetReplicationPositionsResponse,
+            Error = crate::ingest::IngestV2Error,
+            Future = BoxFuture<GetReplicationPositionsResponse, crate::ingest::IngestV2Error>,
         >,
 {
     async fn persist(
@@ -2047,6 +2194,12 @@ where
     ) -> crate::ingest::IngestV2Result<DecommissionResponse> {
         self.call(request).await
     }
+    async fn get_replication_positions(
+        &mut self,
+        request: GetReplicationPositionsRequest,
+    ) -> crate::ingest::IngestV2Result<GetReplicationPositionsResponse> {
+        self.call(request).await
+    }
 }
 #[derive(Debug, Clone)]
 pub struct IngesterServiceGrpcClientAdapter<T> {
@@ -2194,6 +2347,16 @@ where
             .map(|response| response.into_inner())
             .map_err(|error| error.into())
     }
+    async fn get_replication_positions(
+        &mut self,
+        request: GetReplicationPositionsRequest,
+    ) -> crate::ingest::IngestV2Result<GetReplicationPositionsResponse> {
+        self.inner
+            .get_replication_positions(request)
+            .await
+            .map(|response| response.into_inner())
+            .map_err(|error| error.into())
+    }
 }
 #[derive(Debug)]
 pub struct IngesterServiceGrpcServerAdapter {
@@ -2332,6 +2495,17 @@ for IngesterServiceGrpcServerAdapter {
             .map(tonic::Response::new)
             .map_err(|error| error.into())
     }
+    async fn get_replication_positions(
+        &self,
+        request: tonic::Request<GetReplicationPositionsRequest>,
+    ) -> Result<tonic::Response<GetReplicationPositionsResponse>, tonic::Status> {
+        self.inner
+            .clone()
+            .get_replication_positions(request.into_inner())
+            .await
+            .map(tonic::Response::new)
+            .map_err(|error| error.into())
+    }
 }
 /// Generated client implementations.
 pub mod ingester_service_grpc_client {
@@ -2726,6 +2900,37 @@ pub mod ingester_service_grpc_client {
                 );
             self.inner.unary(req, path, codec).await
         }
+        /// Returns the replication positions of a set of shards hosted by the ingester.
+        pub async fn get_replication_positions(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetReplicationPositionsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetReplicationPositionsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/quickwit.ingest.ingester.IngesterService/GetReplicationPositions",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "quickwit.ingest.ingester.IngesterService",
+                        "GetReplicationPositions",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -2829,6 +3034,14 @@ pub mod ingester_service_grpc_server {
             tonic::Response<super::DecommissionResponse>,
             tonic::Status,
         >;
+        /// Returns the replication positions of a set of shards hosted by the ingester.
+        async fn get_replication_positions(
+            &self,
+            request: tonic::Request<super::GetReplicationPositionsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetReplicationPositionsResponse>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct IngesterServiceGrpcServer<T: IngesterServiceGrpc> {
@@ -3369,6 +3582,52 @@ pub mod ingester_service_grpc_server {
                     };
                     Box::pin(fut)
                 }
+                "/quickwit.ingest.ingester.IngesterService/GetReplicationPositions" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetReplicationPositionsSvc<T: IngesterServiceGrpc>(pub Arc<T>);
+                    impl<
+                        T: IngesterServiceGrpc,
+                    > tonic::server::UnaryService<super::GetReplicationPositionsRequest>
+                    for GetReplicationPositionsSvc<T> {
+                        type Response = super::GetReplicationPositionsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetReplicationPositionsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                (*inner).get_replication_positions(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetReplicationPositionsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
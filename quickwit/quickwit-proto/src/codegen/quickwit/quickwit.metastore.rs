@@ -160,6 +160,95 @@ pub struct ResetSourceCheckpointRequest {
 #[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateIndexUriRequest {
+    #[prost(string, tag = "1")]
+    pub index_uid: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub index_uri: ::prost::alloc::string::String,
+}
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OverrideSplitMaturityRequest {
+    #[prost(string, tag = "1")]
+    pub index_uid: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub split_id: ::prost::alloc::string::String,
+    #[prost(enumeration = "SplitMaturityOverride", tag = "3")]
+    pub maturity_override: i32,
+}
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RenameIndexRequest {
+    #[prost(string, tag = "1")]
+    pub index_uid: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub new_index_id: ::prost::alloc::string::String,
+}
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RenameIndexResponse {
+    #[prost(string, tag = "1")]
+    pub index_uid: ::prost::alloc::string::String,
+}
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateIndexTemplateRequest {
+    #[prost(string, tag = "1")]
+    pub index_template_json: ::prost::alloc::string::String,
+    #[prost(bool, tag = "2")]
+    pub overwrite: bool,
+}
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListIndexTemplatesRequest {}
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListIndexTemplatesResponse {
+    #[prost(string, repeated, tag = "1")]
+    pub index_templates_json: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeleteIndexTemplatesRequest {
+    #[prost(string, repeated, tag = "1")]
+    pub template_ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateIndexAliasesRequest {
+    /// Aliases to create or replace.
+    #[prost(string, repeated, tag = "1")]
+    pub upserted_index_aliases_json: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// IDs of the aliases to delete. Deletions are applied before upserts.
+    #[prost(string, repeated, tag = "2")]
+    pub deleted_alias_ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Current state the aliases must be in for the update to apply. The update fails with a
+    /// `FailedPrecondition` error if one of these aliases was modified or deleted in the meantime.
+    #[prost(string, repeated, tag = "3")]
+    pub expected_index_aliases_json: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListIndexAliasesRequest {}
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListIndexAliasesResponse {
+    #[prost(string, repeated, tag = "1")]
+    pub index_aliases_json: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct DeleteTask {
     #[prost(int64, tag = "1")]
     pub create_timestamp: i64,
@@ -447,6 +536,42 @@ impl SourceType {
         }
     }
 }
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum SplitMaturityOverride {
+    /// Clears any previously set override.
+    Unspecified = 0,
+    /// Considers the split mature regardless of its age and number of merge ops.
+    ForceMature = 1,
+    /// Considers the split immature regardless of its age and number of merge ops.
+    ForceImmature = 2,
+}
+impl SplitMaturityOverride {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            SplitMaturityOverride::Unspecified => "SPLIT_MATURITY_OVERRIDE_UNSPECIFIED",
+            SplitMaturityOverride::ForceMature => "SPLIT_MATURITY_OVERRIDE_FORCE_MATURE",
+            SplitMaturityOverride::ForceImmature => {
+                "SPLIT_MATURITY_OVERRIDE_FORCE_IMMATURE"
+            }
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "SPLIT_MATURITY_OVERRIDE_UNSPECIFIED" => Some(Self::Unspecified),
+            "SPLIT_MATURITY_OVERRIDE_FORCE_MATURE" => Some(Self::ForceMature),
+            "SPLIT_MATURITY_OVERRIDE_FORCE_IMMATURE" => Some(Self::ForceImmature),
+            _ => None,
+        }
+    }
+}
 /// BEGIN quickwit-codegen
 #[allow(unused_imports)]
 use std::str::FromStr;
@@ -521,6 +646,48 @@ impl PrometheusLabels<1> for ResetSourceCheckpointRequest {
         ])
     }
 }
+impl PrometheusLabels<1> for UpdateIndexUriRequest {
+    fn labels(&self) -> OwnedPrometheusLabels<1usize> {
+        OwnedPrometheusLabels::new([std::borrow::Cow::Borrowed("update_index_uri")])
+    }
+}
+impl PrometheusLabels<1> for OverrideSplitMaturityRequest {
+    fn labels(&self) -> OwnedPrometheusLabels<1usize> {
+        OwnedPrometheusLabels::new([
+            std::borrow::Cow::Borrowed("override_split_maturity"),
+        ])
+    }
+}
+impl PrometheusLabels<1> for RenameIndexRequest {
+    fn labels(&self) -> OwnedPrometheusLabels<1usize> {
+        OwnedPrometheusLabels::new([std::borrow::Cow::Borrowed("rename_index")])
+    }
+}
+impl PrometheusLabels<1> for CreateIndexTemplateRequest {
+    fn labels(&self) -> OwnedPrometheusLabels<1usize> {
+        OwnedPrometheusLabels::new([std::borrow::Cow::Borrowed("create_index_template")])
+    }
+}
+impl PrometheusLabels<1> for ListIndexTemplatesRequest {
+    fn labels(&self) -> OwnedPrometheusLabels<1usize> {
+        OwnedPrometheusLabels::new([std::borrow::Cow::Borrowed("list_index_templates")])
+    }
+}
+impl PrometheusLabels<1> for DeleteIndexTemplatesRequest {
+    fn labels(&self) -> OwnedPrometheusLabels<1usize> {
+        OwnedPrometheusLabels::new([std::borrow::Cow::Borrowed("delete_index_templates")])
+    }
+}
+impl PrometheusLabels<1> for UpdateIndexAliasesRequest {
+    fn labels(&self) -> OwnedPrometheusLabels<1usize> {
+        OwnedPrometheusLabels::new([std::borrow::Cow::Borrowed("update_index_aliases")])
+    }
+}
+impl PrometheusLabels<1> for ListIndexAliasesRequest {
+    fn labels(&self) -> OwnedPrometheusLabels<1usize> {
+        OwnedPrometheusLabels::new([std::borrow::Cow::Borrowed("list_index_aliases")])
+    }
+}
 impl PrometheusLabels<1> for LastDeleteOpstampRequest {
     fn labels(&self) -> OwnedPrometheusLabels<1usize> {
         OwnedPrometheusLabels::new([std::borrow::Cow::Borrowed("last_delete_opstamp")])
@@ -642,6 +809,46 @@ pub trait MetastoreService: std::fmt::Debug + dyn_clone::DynClone + Send + Sync
         &mut self,
         request: ResetSourceCheckpointRequest,
     ) -> crate::metastore::MetastoreResult<EmptyResponse>;
+    /// Updates the URI of an index.
+    async fn update_index_uri(
+        &mut self,
+        request: UpdateIndexUriRequest,
+    ) -> crate::metastore::MetastoreResult<EmptyResponse>;
+    /// Overrides the maturity of a split.
+    async fn override_split_maturity(
+        &mut self,
+        request: OverrideSplitMaturityRequest,
+    ) -> crate::metastore::MetastoreResult<EmptyResponse>;
+    /// Renames an index, preserving its incarnation.
+    async fn rename_index(
+        &mut self,
+        request: RenameIndexRequest,
+    ) -> crate::metastore::MetastoreResult<RenameIndexResponse>;
+    /// Creates an index template.
+    async fn create_index_template(
+        &mut self,
+        request: CreateIndexTemplateRequest,
+    ) -> crate::metastore::MetastoreResult<EmptyResponse>;
+    /// Lists the index templates.
+    async fn list_index_templates(
+        &mut self,
+        request: ListIndexTemplatesRequest,
+    ) -> crate::metastore::MetastoreResult<ListIndexTemplatesResponse>;
+    /// Deletes index templates.
+    async fn delete_index_templates(
+        &mut self,
+        request: DeleteIndexTemplatesRequest,
+    ) -> crate::metastore::MetastoreResult<EmptyResponse>;
+    /// Atomically creates, replaces, and deletes index aliases.
+    async fn update_index_aliases(
+        &mut self,
+        request: UpdateIndexAliasesRequest,
+    ) -> crate::metastore::MetastoreResult<EmptyResponse>;
+    /// Lists the index aliases.
+    async fn list_index_aliases(
+        &mut self,
+        request: ListIndexAliasesRequest,
+    ) -> crate::metastore::MetastoreResult<ListIndexAliasesResponse>;
     /// Gets last opstamp for a given `index_id`.
     async fn last_delete_opstamp(
         &mut self,
@@ -862,6 +1069,54 @@ impl MetastoreService for MetastoreServiceClient {
     ) -> crate::metastore::MetastoreResult<EmptyResponse> {
         self.inner.reset_source_checkpoint(request).await
     }
+    async fn update_index_uri(
+        &mut self,
+        request: UpdateIndexUriRequest,
+    ) -> crate::metastore::MetastoreResult<EmptyResponse> {
+        self.inner.update_index_uri(request).await
+    }
+    async fn override_split_maturity(
+        &mut self,
+        request: OverrideSplitMaturityRequest,
+    ) -> crate::metastore::MetastoreResult<EmptyResponse> {
+        self.inner.override_split_maturity(request).await
+    }
+    async fn rename_index(
+        &mut self,
+        request: RenameIndexRequest,
+    ) -> crate::metastore::MetastoreResult<RenameIndexResponse> {
+        self.inner.rename_index(request).await
+    }
+    async fn create_index_template(
+        &mut self,
+        request: CreateIndexTemplateRequest,
+    ) -> crate::metastore::MetastoreResult<EmptyResponse> {
+        self.inner.create_index_template(request).await
+    }
+    async fn list_index_templates(
+        &mut self,
+        request: ListIndexTemplatesRequest,
+    ) -> crate::metastore::MetastoreResult<ListIndexTemplatesResponse> {
+        self.inner.list_index_templates(request).await
+    }
+    async fn delete_index_templates(
+        &mut self,
+        request: DeleteIndexTemplatesRequest,
+    ) -> crate::metastore::MetastoreResult<EmptyResponse> {
+        self.inner.delete_index_templates(request).await
+    }
+    async fn update_index_aliases(
+        &mut self,
+        request: UpdateIndexAliasesRequest,
+    ) -> crate::metastore::MetastoreResult<EmptyResponse> {
+        self.inner.update_index_aliases(request).await
+    }
+    async fn list_index_aliases(
+        &mut self,
+        request: ListIndexAliasesRequest,
+    ) -> crate::metastore::MetastoreResult<ListIndexAliasesResponse> {
+        self.inner.list_index_aliases(request).await
+    }
     async fn last_delete_opstamp(
         &mut self,
         request: LastDeleteOpstampRequest,
@@ -1012,6 +1267,54 @@ pub mod metastore_service_mock {
         ) -> crate::metastore::MetastoreResult<super::EmptyResponse> {
             self.inner.lock().await.reset_source_checkpoint(request).await
         }
+        async fn update_index_uri(
+            &mut self,
+            request: super::UpdateIndexUriRequest,
+        ) -> crate::metastore::MetastoreResult<super::EmptyResponse> {
+            self.inner.lock().await.update_index_uri(request).await
+        }
+        async fn override_split_maturity(
+            &mut self,
+            request: super::OverrideSplitMaturityRequest,
+        ) -> crate::metastore::MetastoreResult<super::EmptyResponse> {
+            self.inner.lock().await.override_split_maturity(request).await
+        }
+        async fn rename_index(
+            &mut self,
+            request: super::RenameIndexRequest,
+        ) -> crate::metastore::MetastoreResult<super::RenameIndexResponse> {
+            self.inner.lock().await.rename_index(request).await
+        }
+        async fn create_index_template(
+            &mut self,
+            request: super::CreateIndexTemplateRequest,
+        ) -> crate::metastore::MetastoreResult<super::EmptyResponse> {
+            self.inner.lock().await.create_index_template(request).await
+        }
+        async fn list_index_templates(
+            &mut self,
+            request: super::ListIndexTemplatesRequest,
+        ) -> crate::metastore::MetastoreResult<super::ListIndexTemplatesResponse> {
+            self.inner.lock().await.list_index_templates(request).await
+        }
+        async fn delete_index_templates(
+            &mut self,
+            request: super::DeleteIndexTemplatesRequest,
+        ) -> crate::metastore::MetastoreResult<super::EmptyResponse> {
+            self.inner.lock().await.delete_index_templates(request).await
+        }
+        async fn update_index_aliases(
+            &mut self,
+            request: super::UpdateIndexAliasesRequest,
+        ) -> crate::metastore::MetastoreResult<super::EmptyResponse> {
+            self.inner.lock().await.update_index_aliases(request).await
+        }
+        async fn list_index_aliases(
+            &mut self,
+            request: super::ListIndexAliasesRequest,
+        ) -> crate::metastore::MetastoreResult<super::ListIndexAliasesResponse> {
+            self.inner.lock().await.list_index_aliases(request).await
+        }
         async fn last_delete_opstamp(
             &mut self,
             request: super::LastDeleteOpstampRequest,
@@ -1295,8 +1598,8 @@ impl tower::Service<ResetSourceCheckpointRequest> for Box<dyn MetastoreService>
         Box::pin(fut)
     }
 }
-impl tower::Service<LastDeleteOpstampRequest> for Box<dyn MetastoreService> {
-    type Response = LastDeleteOpstampResponse;
+impl tower::Service<UpdateIndexUriRequest> for Box<dyn MetastoreService> {
+    type Response = EmptyResponse;
     type Error = crate::metastore::MetastoreError;
     type Future = BoxFuture<Self::Response, Self::Error>;
     fn poll_ready(
@@ -1305,14 +1608,14 @@ impl tower::Service<LastDeleteOpstampRequest> for Box<dyn MetastoreService> {
     ) -> std::task::Poll<Result<(), Self::Error>> {
         std::task::Poll::Ready(Ok(()))
     }
-    fn call(&mut self, request: LastDeleteOpstampRequest) -> Self::Future {
+    fn call(&mut self, request: UpdateIndexUriRequest) -> Self::Future {
         let mut svc = self.clone();
-        let fut = async move { svc.last_delete_opstamp(request).await };
+        let fut = async move { svc.update_index_uri(request).await };
         Box::pin(fut)
     }
 }
-impl tower::Service<DeleteQuery> for Box<dyn MetastoreService> {
-    type Response = DeleteTask;
+impl tower::Service<OverrideSplitMaturityRequest> for Box<dyn MetastoreService> {
+    type Response = EmptyResponse;
     type Error = crate::metastore::MetastoreError;
     type Future = BoxFuture<Self::Response, Self::Error>;
     fn poll_ready(
@@ -1321,14 +1624,14 @@ impl tower::Service<DeleteQuery> for Box<dyn MetastoreService> {
     ) -> std::task::Poll<Result<(), Self::Error>> {
         std::task::Poll::Ready(Ok(()))
     }
-    fn call(&mut self, request: DeleteQuery) -> Self::Future {
+    fn call(&mut self, request: OverrideSplitMaturityRequest) -> Self::Future {
         let mut svc = self.clone();
-        let fut = async move { svc.create_delete_task(request).await };
+        let fut = async move { svc.override_split_maturity(request).await };
         Box::pin(fut)
     }
 }
-impl tower::Service<UpdateSplitsDeleteOpstampRequest> for Box<dyn MetastoreService> {
-    type Response = UpdateSplitsDeleteOpstampResponse;
+impl tower::Service<RenameIndexRequest> for Box<dyn MetastoreService> {
+    type Response = RenameIndexResponse;
     type Error = crate::metastore::MetastoreError;
     type Future = BoxFuture<Self::Response, Self::Error>;
     fn poll_ready(
@@ -1337,14 +1640,14 @@ impl tower::Service<UpdateSplitsDeleteOpstampRequest> for Box<dyn MetastoreServi
     ) -> std::task::Poll<Result<(), Self::Error>> {
         std::task::Poll::Ready(Ok(()))
     }
-    fn call(&mut self, request: UpdateSplitsDeleteOpstampRequest) -> Self::Future {
+    fn call(&mut self, request: RenameIndexRequest) -> Self::Future {
         let mut svc = self.clone();
-        let fut = async move { svc.update_splits_delete_opstamp(request).await };
+        let fut = async move { svc.rename_index(request).await };
         Box::pin(fut)
     }
 }
-impl tower::Service<ListDeleteTasksRequest> for Box<dyn MetastoreService> {
-    type Response = ListDeleteTasksResponse;
+impl tower::Service<CreateIndexTemplateRequest> for Box<dyn MetastoreService> {
+    type Response = EmptyResponse;
     type Error = crate::metastore::MetastoreError;
     type Future = BoxFuture<Self::Response, Self::Error>;
     fn poll_ready(
@@ -1353,14 +1656,14 @@ impl tower::Service<ListDeleteTasksRequest> for Box<dyn MetastoreService> {
     ) -> std::task::Poll<Result<(), Self::Error>> {
         std::task::Poll::Ready(Ok(()))
     }
-    fn call(&mut self, request: ListDeleteTasksRequest) -> Self::Future {
+    fn call(&mut self, request: CreateIndexTemplateRequest) -> Self::Future {
         let mut svc = self.clone();
-        let fut = async move { svc.list_delete_tasks(request).await };
+        let fut = async move { svc.create_index_template(request).await };
         Box::pin(fut)
     }
 }
-impl tower::Service<ListStaleSplitsRequest> for Box<dyn MetastoreService> {
-    type Response = ListSplitsResponse;
+impl tower::Service<ListIndexTemplatesRequest> for Box<dyn MetastoreService> {
+    type Response = ListIndexTemplatesResponse;
     type Error = crate::metastore::MetastoreError;
     type Future = BoxFuture<Self::Response, Self::Error>;
     fn poll_ready(
@@ -1369,14 +1672,14 @@ impl tower::Service<ListStaleSplitsRequest> for Box<dyn MetastoreService> {
     ) -> std::task::Poll<Result<(), Self::Error>> {
         std::task::Poll::Ready(Ok(()))
     }
-    fn call(&mut self, request: ListStaleSplitsRequest) -> Self::Future {
+    fn call(&mut self, request: ListIndexTemplatesRequest) -> Self::Future {
         let mut svc = self.clone();
-        let fut = async move { svc.list_stale_splits(request).await };
+        let fut = async move { svc.list_index_templates(request).await };
         Box::pin(fut)
     }
 }
-impl tower::Service<OpenShardsRequest> for Box<dyn MetastoreService> {
-    type Response = OpenShardsResponse;
+impl tower::Service<DeleteIndexTemplatesRequest> for Box<dyn MetastoreService> {
+    type Response = EmptyResponse;
     type Error = crate::metastore::MetastoreError;
     type Future = BoxFuture<Self::Response, Self::Error>;
     fn poll_ready(
@@ -1385,14 +1688,14 @@ impl tower::Service<OpenShardsRequest> for Box<dyn MetastoreService> {
     ) -> std::task::Poll<Result<(), Self::Error>> {
         std::task::Poll::Ready(Ok(()))
     }
-    fn call(&mut self, request: OpenShardsRequest) -> Self::Future {
+    fn call(&mut self, request: DeleteIndexTemplatesRequest) -> Self::Future {
         let mut svc = self.clone();
-        let fut = async move { svc.open_shards(request).await };
+        let fut = async move { svc.delete_index_templates(request).await };
         Box::pin(fut)
     }
 }
-impl tower::Service<AcquireShardsRequest> for Box<dyn MetastoreService> {
-    type Response = AcquireShardsResponse;
+impl tower::Service<UpdateIndexAliasesRequest> for Box<dyn MetastoreService> {
+    type Response = EmptyResponse;
     type Error = crate::metastore::MetastoreError;
     type Future = BoxFuture<Self::Response, Self::Error>;
     fn poll_ready(
@@ -1401,14 +1704,14 @@ impl tower::Service<AcquireShardsRequest> for Box<dyn MetastoreService> {
     ) -> std::task::Poll<Result<(), Self::Error>> {
         std::task::Poll::Ready(Ok(()))
     }
-    fn call(&mut self, request: AcquireShardsRequest) -> Self::Future {
+    fn call(&mut self, request: UpdateIndexAliasesRequest) -> Self::Future {
         let mut svc = self.clone();
-        let fut = async move { svc.acquire_shards(request).await };
+        let fut = async move { svc.update_index_aliases(request).await };
         Box::pin(fut)
     }
 }
-impl tower::Service<DeleteShardsRequest> for Box<dyn MetastoreService> {
-    type Response = DeleteShardsResponse;
+impl tower::Service<ListIndexAliasesRequest> for Box<dyn MetastoreService> {
+    type Response = ListIndexAliasesResponse;
     type Error = crate::metastore::MetastoreError;
     type Future = BoxFuture<Self::Response, Self::Error>;
     fn poll_ready(
@@ -1417,14 +1720,14 @@ impl tower::Service<DeleteShardsRequest> for Box<dyn MetastoreService> {
     ) -> std::task::Poll<Result<(), Self::Error>> {
         std::task::Poll::Ready(Ok(()))
     }
-    fn call(&mut self, request: DeleteShardsRequest) -> Self::Future {
+    fn call(&mut self, request: ListIndexAliasesRequest) -> Self::Future {
         let mut svc = self.clone();
-        let fut = async move { svc.delete_shards(request).await };
+        let fut = async move { svc.list_index_aliases(request).await };
         Box::pin(fut)
     }
 }
-impl tower::Service<ListShardsRequest> for Box<dyn MetastoreService> {
-    type Response = ListShardsResponse;
+impl tower::Service<LastDeleteOpstampRequest> for Box<dyn MetastoreService> {
+    type Response = LastDeleteOpstampResponse;
     type Error = crate::metastore::MetastoreError;
     type Future = BoxFuture<Self::Response, Self::Error>;
     fn poll_ready(
@@ -1433,63 +1736,191 @@ impl tower::Service<ListShardsRequest> for Box<dyn MetastoreService> {
     ) -> std::task::Poll<Result<(), Self::Error>> {
         std::task::Poll::Ready(Ok(()))
     }
-    fn call(&mut self, request: ListShardsRequest) -> Self::Future {
+    fn call(&mut self, request: LastDeleteOpstampRequest) -> Self::Future {
         let mut svc = self.clone();
-        let fut = async move { svc.list_shards(request).await };
+        let fut = async move { svc.last_delete_opstamp(request).await };
         Box::pin(fut)
     }
 }
-/// A tower service stack is a set of tower services.
-#[derive(Debug)]
-struct MetastoreServiceTowerServiceStack {
-    inner: Box<dyn MetastoreService>,
-    create_index_svc: quickwit_common::tower::BoxService<
-        CreateIndexRequest,
-        CreateIndexResponse,
-        crate::metastore::MetastoreError,
-    >,
-    index_metadata_svc: quickwit_common::tower::BoxService<
-        IndexMetadataRequest,
-        IndexMetadataResponse,
-        crate::metastore::MetastoreError,
-    >,
-    list_indexes_metadata_svc: quickwit_common::tower::BoxService<
-        ListIndexesMetadataRequest,
-        ListIndexesMetadataResponse,
-        crate::metastore::MetastoreError,
-    >,
-    delete_index_svc: quickwit_common::tower::BoxService<
-        DeleteIndexRequest,
-        EmptyResponse,
-        crate::metastore::MetastoreError,
-    >,
-    list_splits_svc: quickwit_common::tower::BoxService<
-        ListSplitsRequest,
-        MetastoreServiceStream<ListSplitsResponse>,
-        crate::metastore::MetastoreError,
-    >,
-    stage_splits_svc: quickwit_common::tower::BoxService<
-        StageSplitsRequest,
-        EmptyResponse,
-        crate::metastore::MetastoreError,
-    >,
-    publish_splits_svc: quickwit_common::tower::BoxService<
-        PublishSplitsRequest,
-        EmptyResponse,
-        crate::metastore::MetastoreError,
-    >,
-    mark_splits_for_deletion_svc: quickwit_common::tower::BoxService<
-        MarkSplitsForDeletionRequest,
-        EmptyResponse,
-        crate::metastore::MetastoreError,
-    >,
-    delete_splits_svc: quickwit_common::tower::BoxService<
-        DeleteSplitsRequest,
-        EmptyResponse,
-        crate::metastore::MetastoreError,
-    >,
-    add_source_svc: quickwit_common::tower::BoxService<
-        AddSourceRequest,
+impl tower::Service<DeleteQuery> for Box<dyn MetastoreService> {
+    type Response = DeleteTask;
+    type Error = crate::metastore::MetastoreError;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+    fn poll_ready(
+        &mut self,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+    fn call(&mut self, request: DeleteQuery) -> Self::Future {
+        let mut svc = self.clone();
+        let fut = async move { svc.create_delete_task(request).await };
+        Box::pin(fut)
+    }
+}
+impl tower::Service<UpdateSplitsDeleteOpstampRequest> for Box<dyn MetastoreService> {
+    type Response = UpdateSplitsDeleteOpstampResponse;
+    type Error = crate::metastore::MetastoreError;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+    fn poll_ready(
+        &mut self,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+    fn call(&mut self, request: UpdateSplitsDeleteOpstampRequest) -> Self::Future {
+        let mut svc = self.clone();
+        let fut = async move { svc.update_splits_delete_opstamp(request).await };
+        Box::pin(fut)
+    }
+}
+impl tower::Service<ListDeleteTasksRequest> for Box<dyn MetastoreService> {
+    type Response = ListDeleteTasksResponse;
+    type Error = crate::metastore::MetastoreError;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+    fn poll_ready(
+        &mut self,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+    fn call(&mut self, request: ListDeleteTasksRequest) -> Self::Future {
+        let mut svc = self.clone();
+        let fut = async move { svc.list_delete_tasks(request).await };
+        Box::pin(fut)
+    }
+}
+impl tower::Service<ListStaleSplitsRequest> for Box<dyn MetastoreService> {
+    type Response = ListSplitsResponse;
+    type Error = crate::metastore::MetastoreError;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+    fn poll_ready(
+        &mut self,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+    fn call(&mut self, request: ListStaleSplitsRequest) -> Self::Future {
+        let mut svc = self.clone();
+        let fut = async move { svc.list_stale_splits(request).await };
+        Box::pin(fut)
+    }
+}
+impl tower::Service<OpenShardsRequest> for Box<dyn MetastoreService> {
+    type Response = OpenShardsResponse;
+    type Error = crate::metastore::MetastoreError;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+    fn poll_ready(
+        &mut self,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+    fn call(&mut self, request: OpenShardsRequest) -> Self::Future {
+        let mut svc = self.clone();
+        let fut = async move { svc.open_shards(request).await };
+        Box::pin(fut)
+    }
+}
+impl tower::Service<AcquireShardsRequest> for Box<dyn MetastoreService> {
+    type Response = AcquireShardsResponse;
+    type Error = crate::metastore::MetastoreError;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+    fn poll_ready(
+        &mut self,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+    fn call(&mut self, request: AcquireShardsRequest) -> Self::Future {
+        let mut svc = self.clone();
+        let fut = async move { svc.acquire_shards(request).await };
+        Box::pin(fut)
+    }
+}
+impl tower::Service<DeleteShardsRequest> for Box<dyn MetastoreService> {
+    type Response = DeleteShardsResponse;
+    type Error = crate::metastore::MetastoreError;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+    fn poll_ready(
+        &mut self,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+    fn call(&mut self, request: DeleteShardsRequest) -> Self::Future {
+        let mut svc = self.clone();
+        let fut = async move { svc.delete_shards(request).await };
+        Box::pin(fut)
+    }
+}
+impl tower::Service<ListShardsRequest> for Box<dyn MetastoreService> {
+    type Response = ListShardsResponse;
+    type Error = crate::metastore::MetastoreError;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+    fn poll_ready(
+        &mut self,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+    fn call(&mut self, request: ListShardsRequest) -> Self::Future {
+        let mut svc = self.clone();
+        let fut = async move { svc.list_shards(request).await };
+        Box::pin(fut)
+    }
+}
+/// A tower service stack is a set of tower services.
+#[derive(Debug)]
+struct MetastoreServiceTowerServiceStack {
+    inner: Box<dyn MetastoreService>,
+    create_index_svc: quickwit_common::tower::BoxService<
+        CreateIndexRequest,
+        CreateIndexResponse,
+        crate::metastore::MetastoreError,
+    >,
+    index_metadata_svc: quickwit_common::tower::BoxService<
+        IndexMetadataRequest,
+        IndexMetadataResponse,
+        crate::metastore::MetastoreError,
+    >,
+    list_indexes_metadata_svc: quickwit_common::tower::BoxService<
+        ListIndexesMetadataRequest,
+        ListIndexesMetadataResponse,
+        crate::metastore::MetastoreError,
+    >,
+    delete_index_svc: quickwit_common::tower::BoxService<
+        DeleteIndexRequest,
+        EmptyResponse,
+        crate::metastore::MetastoreError,
+    >,
+    list_splits_svc: quickwit_common::tower::BoxService<
+        ListSplitsRequest,
+        MetastoreServiceStream<ListSplitsResponse>,
+        crate::metastore::MetastoreError,
+    >,
+    stage_splits_svc: quickwit_common::tower::BoxService<
+        StageSplitsRequest,
+        EmptyResponse,
+        crate::metastore::MetastoreError,
+    >,
+    publish_splits_svc: quickwit_common::tower::BoxService<
+        PublishSplitsRequest,
+        EmptyResponse,
+        crate::metastore::MetastoreError,
+    >,
+    mark_splits_for_deletion_svc: quickwit_common::tower::BoxService<
+        MarkSplitsForDeletionRequest,
+        EmptyResponse,
+        crate::metastore::MetastoreError,
+    >,
+    delete_splits_svc: quickwit_common::tower::BoxService<
+        DeleteSplitsRequest,
+        EmptyResponse,
+        crate::metastore::MetastoreError,
+    >,
+    add_source_svc: quickwit_common::tower::BoxService<
+        AddSourceRequest,
         EmptyResponse,
         crate::metastore::MetastoreError,
     >,
@@ -1508,6 +1939,46 @@ struct MetastoreServiceTowerServiceStack {
         EmptyResponse,
         crate::metastore::MetastoreError,
     >,
+    update_index_uri_svc: quickwit_common::tower::BoxService<
+        UpdateIndexUriRequest,
+        EmptyResponse,
+        crate::metastore::MetastoreError,
+    >,
+    override_split_maturity_svc: quickwit_common::tower::BoxService<
+        OverrideSplitMaturityRequest,
+        EmptyResponse,
+        crate::metastore::MetastoreError,
+    >,
+    rename_index_svc: quickwit_common::tower::BoxService<
+        RenameIndexRequest,
+        RenameIndexResponse,
+        crate::metastore::MetastoreError,
+    >,
+    create_index_template_svc: quickwit_common::tower::BoxService<
+        CreateIndexTemplateRequest,
+        EmptyResponse,
+        crate::metastore::MetastoreError,
+    >,
+    list_index_templates_svc: quickwit_common::tower::BoxService<
+        ListIndexTemplatesRequest,
+        ListIndexTemplatesResponse,
+        crate::metastore::MetastoreError,
+    >,
+    delete_index_templates_svc: quickwit_common::tower::BoxService<
+        DeleteIndexTemplatesRequest,
+        EmptyResponse,
+        crate::metastore::MetastoreError,
+    >,
+    update_index_aliases_svc: quickwit_common::tower::BoxService<
+        UpdateIndexAliasesRequest,
+        EmptyResponse,
+        crate::metastore::MetastoreError,
+    >,
+    list_index_aliases_svc: quickwit_common::tower::BoxService<
+        ListIndexAliasesRequest,
+        ListIndexAliasesResponse,
+        crate::metastore::MetastoreError,
+    >,
     last_delete_opstamp_svc: quickwit_common::tower::BoxService<
         LastDeleteOpstampRequest,
         LastDeleteOpstampResponse,
@@ -1571,6 +2042,14 @@ impl Clone for MetastoreServiceTowerServiceStack {
             toggle_source_svc: self.toggle_source_svc.clone(),
             delete_source_svc: self.delete_source_svc.clone(),
             reset_source_checkpoint_svc: self.reset_source_checkpoint_svc.clone(),
+            update_index_uri_svc: self.update_index_uri_svc.clone(),
+            override_split_maturity_svc: self.override_split_maturity_svc.clone(),
+            rename_index_svc: self.rename_index_svc.clone(),
+            create_index_template_svc: self.create_index_template_svc.clone(),
+            list_index_templates_svc: self.list_index_templates_svc.clone(),
+            delete_index_templates_svc: self.delete_index_templates_svc.clone(),
+            update_index_aliases_svc: self.update_index_aliases_svc.clone(),
+            list_index_aliases_svc: self.list_index_aliases_svc.clone(),
             last_delete_opstamp_svc: self.last_delete_opstamp_svc.clone(),
             create_delete_task_svc: self.create_delete_task_svc.clone(),
             update_splits_delete_opstamp_svc: self
@@ -1665,6 +2144,54 @@ impl MetastoreService for MetastoreServiceTowerServiceStack {
     ) -> crate::metastore::MetastoreResult<EmptyResponse> {
         self.reset_source_checkpoint_svc.ready().await?.call(request).await
     }
+    async fn update_index_uri(
+        &mut self,
+        request: UpdateIndexUriRequest,
+    ) -> crate::metastore::MetastoreResult<EmptyResponse> {
+        self.update_index_uri_svc.ready().await?.call(request).await
+    }
+    async fn override_split_maturity(
+        &mut self,
+        request: OverrideSplitMaturityRequest,
+    ) -> crate::metastore::MetastoreResult<EmptyResponse> {
+        self.override_split_maturity_svc.ready().await?.call(request).await
+    }
+    async fn rename_index(
+        &mut self,
+        request: RenameIndexRequest,
+    ) -> crate::metastore::MetastoreResult<RenameIndexResponse> {
+        self.rename_index_svc.ready().await?.call(request).await
+    }
+    async fn create_index_template(
+        &mut self,
+        request: CreateIndexTemplateRequest,
+    ) -> crate::metastore::MetastoreResult<EmptyResponse> {
+        self.create_index_template_svc.ready().await?.call(request).await
+    }
+    async fn list_index_templates(
+        &mut self,
+        request: ListIndexTemplatesRequest,
+    ) -> crate::metastore::MetastoreResult<ListIndexTemplatesResponse> {
+        self.list_index_templates_svc.ready().await?.call(request).await
+    }
+    async fn delete_index_templates(
+        &mut self,
+        request: DeleteIndexTemplatesRequest,
+    ) -> crate::metastore::MetastoreResult<EmptyResponse> {
+        self.delete_index_templates_svc.ready().await?.call(request).await
+    }
+    async fn update_index_aliases(
+        &mut self,
+        request: UpdateIndexAliasesRequest,
+    ) -> crate::metastore::MetastoreResult<EmptyResponse> {
+        self.update_index_aliases_svc.ready().await?.call(request).await
+    }
+    async fn list_index_aliases(
+        &mut self,
+        request: ListIndexAliasesRequest,
+    ) -> crate::metastore::MetastoreResult<ListIndexAliasesResponse> {
+        self.list_index_aliases_svc.ready().await?.call(request).await
+    }
     async fn last_delete_opstamp(
         &mut self,
         request: LastDeleteOpstampRequest,
@@ -1856,60 +2383,140 @@ type ResetSourceCheckpointLayer = quickwit_common::tower::BoxLayer<
     EmptyResponse,
     crate::metastore::MetastoreError,
 >;
-type LastDeleteOpstampLayer = quickwit_common::tower::BoxLayer<
+type UpdateIndexUriLayer = quickwit_common::tower::BoxLayer<
     quickwit_common::tower::BoxService<
-        LastDeleteOpstampRequest,
-        LastDeleteOpstampResponse,
+        UpdateIndexUriRequest,
+        EmptyResponse,
         crate::metastore::MetastoreError,
     >,
-    LastDeleteOpstampRequest,
-    LastDeleteOpstampResponse,
+    UpdateIndexUriRequest,
+    EmptyResponse,
     crate::metastore::MetastoreError,
 >;
-type CreateDeleteTaskLayer = quickwit_common::tower::BoxLayer<
+type OverrideSplitMaturityLayer = quickwit_common::tower::BoxLayer<
     quickwit_common::tower::BoxService<
-        DeleteQuery,
-        DeleteTask,
+        OverrideSplitMaturityRequest,
+        EmptyResponse,
         crate::metastore::MetastoreError,
     >,
-    DeleteQuery,
-    DeleteTask,
+    OverrideSplitMaturityRequest,
+    EmptyResponse,
     crate::metastore::MetastoreError,
 >;
-type UpdateSplitsDeleteOpstampLayer = quickwit_common::tower::BoxLayer<
+type RenameIndexLayer = quickwit_common::tower::BoxLayer<
     quickwit_common::tower::BoxService<
-        UpdateSplitsDeleteOpstampRequest,
-        UpdateSplitsDeleteOpstampResponse,
+        RenameIndexRequest,
+        RenameIndexResponse,
         crate::metastore::MetastoreError,
     >,
-    UpdateSplitsDeleteOpstampRequest,
-    UpdateSplitsDeleteOpstampResponse,
+    RenameIndexRequest,
+    RenameIndexResponse,
     crate::metastore::MetastoreError,
 >;
-type ListDeleteTasksLayer = quickwit_common::tower::BoxLayer<
+type CreateIndexTemplateLayer = quickwit_common::tower::BoxLayer<
     quickwit_common::tower::BoxService<
-        ListDeleteTasksRequest,
-        ListDeleteTasksResponse,
+        CreateIndexTemplateRequest,
+        EmptyResponse,
         crate::metastore::MetastoreError,
     >,
-    ListDeleteTasksRequest,
-    ListDeleteTasksResponse,
+    CreateIndexTemplateRequest,
+    EmptyResponse,
     crate::metastore::MetastoreError,
 >;
-type ListStaleSplitsLayer = quickwit_common::tower::BoxLayer<
+type ListIndexTemplatesLayer = quickwit_common::tower::BoxLayer<
     quickwit_common::tower::BoxService<
-        ListStaleSplitsRequest,
-        ListSplitsResponse,
+        ListIndexTemplatesRequest,
+        ListIndexTemplatesResponse,
         crate::metastore::MetastoreError,
     >,
-    ListStaleSplitsRequest,
-    ListSplitsResponse,
+    ListIndexTemplatesRequest,
+    ListIndexTemplatesResponse,
     crate::metastore::MetastoreError,
 >;
-type OpenShardsLayer = quickwit_common::tower::BoxLayer<
+type DeleteIndexTemplatesLayer = quickwit_common::tower::BoxLayer<
     quickwit_common::tower::BoxService<
-        OpenShardsRequest,
-        OpenShardsResponse,
+        DeleteIndexTemplatesRequest,
+        EmptyResponse,
+        crate::metastore::MetastoreError,
+    >,
+    DeleteIndexTemplatesRequest,
+    EmptyResponse,
+    crate::metastore::MetastoreError,
+>;
+type UpdateIndexAliasesLayer = quickwit_common::tower::BoxLayer<
+    quickwit_common::tower::BoxService<
+        UpdateIndexAliasesRequest,
+        EmptyResponse,
+        crate::metastore::MetastoreError,
+    >,
+    UpdateIndexAliasesRequest,
+    EmptyResponse,
+    crate::metastore::MetastoreError,
+>;
+type ListIndexAliasesLayer = quickwit_common::tower::BoxLayer<
+    quickwit_common::tower::BoxService<
+        ListIndexAliasesRequest,
+        ListIndexAliasesResponse,
+        crate::metastore::MetastoreError,
+    >,
+    ListIndexAliasesRequest,
+    ListIndexAliasesResponse,
+    crate::metastore::MetastoreError,
+>;
+type LastDeleteOpstampLayer = quickwit_common::tower::BoxLayer<
+    quickwit_common::tower::BoxService<
+        LastDeleteOpstampRequest,
+        LastDeleteOpstampResponse,
+        crate::metastore::MetastoreError,
+    >,
+    LastDeleteOpstampRequest,
+    LastDeleteOpstampResponse,
+    crate::metastore::MetastoreError,
+>;
+type CreateDeleteTaskLayer = quickwit_common::tower::BoxLayer<
+    quickwit_common::tower::BoxService<
+        DeleteQuery,
+        DeleteTask,
+        crate::metastore::MetastoreError,
+    >,
+    DeleteQuery,
+    DeleteTask,
+    crate::metastore::MetastoreError,
+>;
+type UpdateSplitsDeleteOpstampLayer = quickwit_common::tower::BoxLayer<
+    quickwit_common::tower::BoxService<
+        UpdateSplitsDeleteOpstampRequest,
+        UpdateSplitsDeleteOpstampResponse,
+        crate::metastore::MetastoreError,
+    >,
+    UpdateSplitsDeleteOpstampRequest,
+    UpdateSplitsDeleteOpstampResponse,
+    crate::metastore::MetastoreError,
+>;
+type ListDeleteTasksLayer = quickwit_common::tower::BoxLayer<
+    quickwit_common::tower::BoxService<
+        ListDeleteTasksRequest,
+        ListDeleteTasksResponse,
+        crate::metastore::MetastoreError,
+    >,
+    ListDeleteTasksRequest,
+    ListDeleteTasksResponse,
+    crate::metastore::MetastoreError,
+>;
+type ListStaleSplitsLayer = quickwit_common::tower::BoxLayer<
+    quickwit_common::tower::BoxService<
+        ListStaleSplitsRequest,
+        ListSplitsResponse,
+        crate::metastore::MetastoreError,
+    >,
+    ListStaleSplitsRequest,
+    ListSplitsResponse,
+    crate::metastore::MetastoreError,
+>;
+type OpenShardsLayer = quickwit_common::tower::BoxLayer<
+    quickwit_common::tower::BoxService<
+        OpenShardsRequest,
+        OpenShardsResponse,
         crate::metastore::MetastoreError,
     >,
     OpenShardsRequest,
@@ -1961,6 +2568,14 @@ pub struct MetastoreServiceTowerLayerStack {
     toggle_source_layers: Vec<ToggleSourceLayer>,
     delete_source_layers: Vec<DeleteSourceLayer>,
     reset_source_checkpoint_layers: Vec<ResetSourceCheckpointLayer>,
+    update_index_uri_layers: Vec<UpdateIndexUriLayer>,
+    override_split_maturity_layers: Vec<OverrideSplitMaturityLayer>,
+    rename_index_layers: Vec<RenameIndexLayer>,
+    create_index_template_layers: Vec<CreateIndexTemplateLayer>,
+    list_index_templates_layers: Vec<ListIndexTemplatesLayer>,
+    delete_index_templates_layers: Vec<DeleteIndexTemplatesLayer>,
+    update_index_aliases_layers: Vec<UpdateIndexAliasesLayer>,
+    list_index_aliases_layers: Vec<ListIndexAliasesLayer>,
     last_delete_opstamp_layers: Vec<LastDeleteOpstampLayer>,
     create_delete_task_layers: Vec<CreateDeleteTaskLayer>,
     update_splits_delete_opstamp_layers: Vec<UpdateSplitsDeleteOpstampLayer>,
@@ -2305,6 +2920,208 @@ impl MetastoreServiceTowerLayerStack {
         >>::Service as tower::Service<
             ResetSourceCheckpointRequest,
         >>::Future: Send + 'static,
+        L: tower::Layer<
+                quickwit_common::tower::BoxService<
+                    UpdateIndexUriRequest,
+                    EmptyResponse,
+                    crate::metastore::MetastoreError,
+                >,
+            > + Clone + Send + Sync + 'static,
+        <L as tower::Layer<
+            quickwit_common::tower::BoxService<
+                UpdateIndexUriRequest,
+                EmptyResponse,
+                crate::metastore::MetastoreError,
+            >,
+        >>::Service: tower::Service<
+                UpdateIndexUriRequest,
+                Response = EmptyResponse,
+                Error = crate::metastore::MetastoreError,
+            > + Clone + Send + Sync + 'static,
+        <<L as tower::Layer<
+            quickwit_common::tower::BoxService<
+                UpdateIndexUriRequest,
+                EmptyResponse,
+                crate::metastore::MetastoreError,
+            >,
+        >>::Service as tower::Service<UpdateIndexUriRequest>>::Future: Send + 'static,
+        L: tower::Layer<
+                quickwit_common::tower::BoxService<
+                    OverrideSplitMaturityRequest,
+                    EmptyResponse,
+                    crate::metastore::MetastoreError,
+                >,
+            > + Clone + Send + Sync + 'static,
+        <L as tower::Layer<
+            quickwit_common::tower::BoxService<
+                OverrideSplitMaturityRequest,
+                EmptyResponse,
+                crate::metastore::MetastoreError,
+            >,
+        >>::Service: tower::Service<
+                OverrideSplitMaturityRequest,
+                Response = EmptyResponse,
+                Error = crate::metastore::MetastoreError,
+            > + Clone + Send + Sync + 'static,
+        <<L as tower::Layer<
+            quickwit_common::tower::BoxService<
+                OverrideSplitMaturityRequest,
+                EmptyResponse,
+                crate::metastore::MetastoreError,
+            >,
+        >>::Service as tower::Service<
+            OverrideSplitMaturityRequest,
+        >>::Future: Send + 'static,
+        L: tower::Layer<
+                quickwit_common::tower::BoxService<
+                    RenameIndexRequest,
+                    RenameIndexResponse,
+                    crate::metastore::MetastoreError,
+                >,
+            > + Clone + Send + Sync + 'static,
+        <L as tower::Layer<
+            quickwit_common::tower::BoxService<
+                RenameIndexRequest,
+                RenameIndexResponse,
+                crate::metastore::MetastoreError,
+            >,
+        >>::Service: tower::Service<
+                RenameIndexRequest,
+                Response = RenameIndexResponse,
+                Error = crate::metastore::MetastoreError,
+            > + Clone + Send + Sync + 'static,
+        <<L as tower::Layer<
+            quickwit_common::tower::BoxService<
+                RenameIndexRequest,
+                RenameIndexResponse,
+                crate::metastore::MetastoreError,
+            >,
+        >>::Service as tower::Service<RenameIndexRequest>>::Future: Send + 'static,
+        L: tower::Layer<
+                quickwit_common::tower::BoxService<
+                    CreateIndexTemplateRequest,
+                    EmptyResponse,
+                    crate::metastore::MetastoreError,
+                >,
+            > + Clone + Send + Sync + 'static,
+        <L as tower::Layer<
+            quickwit_common::tower::BoxService<
+                CreateIndexTemplateRequest,
+                EmptyResponse,
+                crate::metastore::MetastoreError,
+            >,
+        >>::Service: tower::Service<
+                CreateIndexTemplateRequest,
+                Response = EmptyResponse,
+                Error = crate::metastore::MetastoreError,
+            > + Clone + Send + Sync + 'static,
+        <<L as tower::Layer<
+            quickwit_common::tower::BoxService<
+                CreateIndexTemplateRequest,
+                EmptyResponse,
+                crate::metastore::MetastoreError,
+            >,
+        >>::Service as tower::Service<CreateIndexTemplateRequest>>::Future: Send + 'static,
+        L: tower::Layer<
+                quickwit_common::tower::BoxService<
+                    ListIndexTemplatesRequest,
+                    ListIndexTemplatesResponse,
+                    crate::metastore::MetastoreError,
+                >,
+            > + Clone + Send + Sync + 'static,
+        <L as tower::Layer<
+            quickwit_common::tower::BoxService<
+                ListIndexTemplatesRequest,
+                ListIndexTemplatesResponse,
+                crate::metastore::MetastoreError,
+            >,
+        >>::Service: tower::Service<
+                ListIndexTemplatesRequest,
+                Response = ListIndexTemplatesResponse,
+                Error = crate::metastore::MetastoreError,
+            > + Clone + Send + Sync + 'static,
+        <<L as tower::Layer<
+            quickwit_common::tower::BoxService<
+                ListIndexTemplatesRequest,
+                ListIndexTemplatesResponse,
+                crate::metastore::MetastoreError,
+            >,
+        >>::Service as tower::Service<ListIndexTemplatesRequest>>::Future: Send + 'static,
+        L: tower::Layer<
+                quickwit_common::tower::BoxService<
+                    DeleteIndexTemplatesRequest,
+                    EmptyResponse,
+                    crate::metastore::MetastoreError,
+                >,
+            > + Clone + Send + Sync + 'static,
+        <L as tower::Layer<
+            quickwit_common::tower::BoxService<
+                DeleteIndexTemplatesRequest,
+                EmptyResponse,
+                crate::metastore::MetastoreError,
+            >,
+        >>::Service: tower::Service<
+                DeleteIndexTemplatesRequest,
+                Response = EmptyResponse,
+                Error = crate::metastore::MetastoreError,
+            > + Clone + Send + Sync + 'static,
+        <<L as tower::Layer<
+            quickwit_common::tower::BoxService<
+                DeleteIndexTemplatesRequest,
+                EmptyResponse,
+                crate::metastore::MetastoreError,
+            >,
+        >>::Service as tower::Service<DeleteIndexTemplatesRequest>>::Future: Send + 'static,
+        L: tower::Layer<
+                quickwit_common::tower::BoxService<
+                    UpdateIndexAliasesRequest,
+                    EmptyResponse,
+                    crate::metastore::MetastoreError,
+                >,
+            > + Clone + Send + Sync + 'static,
+        <L as tower::Layer<
+            quickwit_common::tower::BoxService<
+                UpdateIndexAliasesRequest,
+                EmptyResponse,
+                crate::metastore::MetastoreError,
+            >,
+        >>::Service: tower::Service<
+                UpdateIndexAliasesRequest,
+                Response = EmptyResponse,
+                Error = crate::metastore::MetastoreError,
+            > + Clone + Send + Sync + 'static,
+        <<L as tower::Layer<
+            quickwit_common::tower::BoxService<
+                UpdateIndexAliasesRequest,
+                EmptyResponse,
+                crate::metastore::MetastoreError,
+            >,
+        >>::Service as tower::Service<UpdateIndexAliasesRequest>>::Future: Send + 'static,
+        L: tower::Layer<
+                quickwit_common::tower::BoxService<
+                    ListIndexAliasesRequest,
+                    ListIndexAliasesResponse,
+                    crate::metastore::MetastoreError,
+                >,
+            > + Clone + Send + Sync + 'static,
+        <L as tower::Layer<
+            quickwit_common::tower::BoxService<
+                ListIndexAliasesRequest,
+                ListIndexAliasesResponse,
+                crate::metastore::MetastoreError,
+            >,
+        >>::Service: tower::Service<
+                ListIndexAliasesRequest,
+                Response = ListIndexAliasesResponse,
+                Error = crate::metastore::MetastoreError,
+            > + Clone + Send + Sync + 'static,
+        <<L as tower::Layer<
+            quickwit_common::tower::BoxService<
+                ListIndexAliasesRequest,
+                ListIndexAliasesResponse,
+                crate::metastore::MetastoreError,
+            >,
+        >>::Service as tower::Service<ListIndexAliasesRequest>>::Future: Send + 'static,
         L: tower::Layer<
                 quickwit_common::tower::BoxService<
                     LastDeleteOpstampRequest,
@@ -2559,6 +3376,22 @@ impl MetastoreServiceTowerLayerStack {
             .push(quickwit_common::tower::BoxLayer::new(layer.clone()));
         self.reset_source_checkpoint_layers
             .push(quickwit_common::tower::BoxLayer::new(layer.clone()));
+        self.update_index_uri_layers
+            .push(quickwit_common::tower::BoxLayer::new(layer.clone()));
+        self.override_split_maturity_layers
+            .push(quickwit_common::tower::BoxLayer::new(layer.clone()));
+        self.rename_index_layers
+            .push(quickwit_common::tower::BoxLayer::new(layer.clone()));
+        self.create_index_template_layers
+            .push(quickwit_common::tower::BoxLayer::new(layer.clone()));
+        self.list_index_templates_layers
+            .push(quickwit_common::tower::BoxLayer::new(layer.clone()));
+        self.delete_index_templates_layers
+            .push(quickwit_common::tower::BoxLayer::new(layer.clone()));
+        self.update_index_aliases_layers
+            .push(quickwit_common::tower::BoxLayer::new(layer.clone()));
+        self.list_index_aliases_layers
+            .push(quickwit_common::tower::BoxLayer::new(layer.clone()));
         self.last_delete_opstamp_layers
             .push(quickwit_common::tower::BoxLayer::new(layer.clone()));
         self.create_delete_task_layers
@@ -2835,61 +3668,216 @@ impl MetastoreServiceTowerLayerStack {
             .push(quickwit_common::tower::BoxLayer::new(layer));
         self
     }
-    pub fn stack_last_delete_opstamp_layer<L>(mut self, layer: L) -> Self
+    pub fn stack_update_index_uri_layer<L>(mut self, layer: L) -> Self
     where
         L: tower::Layer<
                 quickwit_common::tower::BoxService<
-                    LastDeleteOpstampRequest,
-                    LastDeleteOpstampResponse,
+                    UpdateIndexUriRequest,
+                    EmptyResponse,
                     crate::metastore::MetastoreError,
                 >,
             > + Send + Sync + 'static,
         L::Service: tower::Service<
-                LastDeleteOpstampRequest,
-                Response = LastDeleteOpstampResponse,
+                UpdateIndexUriRequest,
+                Response = EmptyResponse,
                 Error = crate::metastore::MetastoreError,
             > + Clone + Send + Sync + 'static,
-        <L::Service as tower::Service<LastDeleteOpstampRequest>>::Future: Send + 'static,
+        <L::Service as tower::Service<UpdateIndexUriRequest>>::Future: Send + 'static,
     {
-        self.last_delete_opstamp_layers
-            .push(quickwit_common::tower::BoxLayer::new(layer));
+        self.update_index_uri_layers.push(quickwit_common::tower::BoxLayer::new(layer));
         self
     }
-    pub fn stack_create_delete_task_layer<L>(mut self, layer: L) -> Self
+    pub fn stack_override_split_maturity_layer<L>(mut self, layer: L) -> Self
     where
         L: tower::Layer<
                 quickwit_common::tower::BoxService<
-                    DeleteQuery,
-                    DeleteTask,
+                    OverrideSplitMaturityRequest,
+                    EmptyResponse,
                     crate::metastore::MetastoreError,
                 >,
             > + Send + Sync + 'static,
         L::Service: tower::Service<
-                DeleteQuery,
-                Response = DeleteTask,
+                OverrideSplitMaturityRequest,
+                Response = EmptyResponse,
                 Error = crate::metastore::MetastoreError,
             > + Clone + Send + Sync + 'static,
-        <L::Service as tower::Service<DeleteQuery>>::Future: Send + 'static,
+        <L::Service as tower::Service<
+            OverrideSplitMaturityRequest,
+        >>::Future: Send + 'static,
     {
-        self.create_delete_task_layers
+        self.override_split_maturity_layers
             .push(quickwit_common::tower::BoxLayer::new(layer));
         self
     }
-    pub fn stack_update_splits_delete_opstamp_layer<L>(mut self, layer: L) -> Self
+    pub fn stack_rename_index_layer<L>(mut self, layer: L) -> Self
     where
         L: tower::Layer<
                 quickwit_common::tower::BoxService<
-                    UpdateSplitsDeleteOpstampRequest,
-                    UpdateSplitsDeleteOpstampResponse,
+                    RenameIndexRequest,
+                    RenameIndexResponse,
                     crate::metastore::MetastoreError,
                 >,
             > + Send + Sync + 'static,
         L::Service: tower::Service<
-                UpdateSplitsDeleteOpstampRequest,
-                Response = UpdateSplitsDeleteOpstampResponse,
+                RenameIndexRequest,
+                Response = RenameIndexResponse,
                 Error = crate::metastore::MetastoreError,
             > + Clone + Send + Sync + 'static,
-        <L::Service as tower::Service<
+        <L::Service as tower::Service<RenameIndexRequest>>::Future: Send + 'static,
+    {
+        self.rename_index_layers.push(quickwit_common::tower::BoxLayer::new(layer));
+        self
+    }
+    pub fn stack_create_index_template_layer<L>(mut self, layer: L) -> Self
+    where
+        L: tower::Layer<
+                quickwit_common::tower::BoxService<
+                    CreateIndexTemplateRequest,
+                    EmptyResponse,
+                    crate::metastore::MetastoreError,
+                >,
+            > + Send + Sync + 'static,
+        L::Service: tower::Service<
+                CreateIndexTemplateRequest,
+                Response = EmptyResponse,
+                Error = crate::metastore::MetastoreError,
+            > + Clone + Send + Sync + 'static,
+        <L::Service as tower::Service<CreateIndexTemplateRequest>>::Future: Send + 'static,
+    {
+        self.create_index_template_layers.push(quickwit_common::tower::BoxLayer::new(layer));
+        self
+    }
+    pub fn stack_list_index_templates_layer<L>(mut self, layer: L) -> Self
+    where
+        L: tower::Layer<
+                quickwit_common::tower::BoxService<
+                    ListIndexTemplatesRequest,
+                    ListIndexTemplatesResponse,
+                    crate::metastore::MetastoreError,
+                >,
+            > + Send + Sync + 'static,
+        L::Service: tower::Service<
+                ListIndexTemplatesRequest,
+                Response = ListIndexTemplatesResponse,
+                Error = crate::metastore::MetastoreError,
+            > + Clone + Send + Sync + 'static,
+        <L::Service as tower::Service<ListIndexTemplatesRequest>>::Future: Send + 'static,
+    {
+        self.list_index_templates_layers.push(quickwit_common::tower::BoxLayer::new(layer));
+        self
+    }
+    pub fn stack_delete_index_templates_layer<L>(mut self, layer: L) -> Self
+    where
+        L: tower::Layer<
+                quickwit_common::tower::BoxService<
+                    DeleteIndexTemplatesRequest,
+                    EmptyResponse,
+                    crate::metastore::MetastoreError,
+                >,
+            > + Send + Sync + 'static,
+        L::Service: tower::Service<
+                DeleteIndexTemplatesRequest,
+                Response = EmptyResponse,
+                Error = crate::metastore::MetastoreError,
+            > + Clone + Send + Sync + 'static,
+        <L::Service as tower::Service<DeleteIndexTemplatesRequest>>::Future: Send + 'static,
+    {
+        self.delete_index_templates_layers.push(quickwit_common::tower::BoxLayer::new(layer));
+        self
+    }
+    pub fn stack_update_index_aliases_layer<L>(mut self, layer: L) -> Self
+    where
+        L: tower::Layer<
+                quickwit_common::tower::BoxService<
+                    UpdateIndexAliasesRequest,
+                    EmptyResponse,
+                    crate::metastore::MetastoreError,
+                >,
+            > + Send + Sync + 'static,
+        L::Service: tower::Service<
+                UpdateIndexAliasesRequest,
+                Response = EmptyResponse,
+                Error = crate::metastore::MetastoreError,
+            > + Clone + Send + Sync + 'static,
+        <L::Service as tower::Service<UpdateIndexAliasesRequest>>::Future: Send + 'static,
+    {
+        self.update_index_aliases_layers.push(quickwit_common::tower::BoxLayer::new(layer));
+        self
+    }
+    pub fn stack_list_index_aliases_layer<L>(mut self, layer: L) -> Self
+    where
+        L: tower::Layer<
+                quickwit_common::tower::BoxService<
+                    ListIndexAliasesRequest,
+                    ListIndexAliasesResponse,
+                    crate::metastore::MetastoreError,
+                >,
+            > + Send + Sync + 'static,
+        L::Service: tower::Service<
+                ListIndexAliasesRequest,
+                Response = ListIndexAliasesResponse,
+                Error = crate::metastore::MetastoreError,
+            > + Clone + Send + Sync + 'static,
+        <L::Service as tower::Service<ListIndexAliasesRequest>>::Future: Send + 'static,
+    {
+        self.list_index_aliases_layers.push(quickwit_common::tower::BoxLayer::new(layer));
+        self
+    }
+    pub fn stack_last_delete_opstamp_layer<L>(mut self, layer: L) -> Self
+    where
+        L: tower::Layer<
+                quickwit_common::tower::BoxService<
+                    LastDeleteOpstampRequest,
+                    LastDeleteOpstampResponse,
+                    crate::metastore::MetastoreError,
+                >,
+            > + Send + Sync + 'static,
+        L::Service: tower::Service<
+                LastDeleteOpstampRequest,
+                Response = LastDeleteOpstampResponse,
+                Error = crate::metastore::MetastoreError,
+            > + Clone + Send + Sync + 'static,
+        <L::Service as tower::Service<LastDeleteOpstampRequest>>::Future: Send + 'static,
+    {
+        self.last_delete_opstamp_layers
+            .push(quickwit_common::tower::BoxLayer::new(layer));
+        self
+    }
+    pub fn stack_create_delete_task_layer<L>(mut self, layer: L) -> Self
+    where
+        L: tower::Layer<
+                quickwit_common::tower::BoxService<
+                    DeleteQuery,
+                    DeleteTask,
+                    crate::metastore::MetastoreError,
+                >,
+            > + Send + Sync + 'static,
+        L::Service: tower::Service<
+                DeleteQuery,
+                Response = DeleteTask,
+                Error = crate::metastore::MetastoreError,
+            > + Clone + Send + Sync + 'static,
+        <L::Service as tower::Service<DeleteQuery>>::Future: Send + 'static,
+    {
+        self.create_delete_task_layers
+            .push(quickwit_common::tower::BoxLayer::new(layer));
+        self
+    }
+    pub fn stack_update_splits_delete_opstamp_layer<L>(mut self, layer: L) -> Self
+    where
+        L: tower::Layer<
+                quickwit_common::tower::BoxService<
+                    UpdateSplitsDeleteOpstampRequest,
+                    UpdateSplitsDeleteOpstampResponse,
+                    crate::metastore::MetastoreError,
+                >,
+            > + Send + Sync + 'static,
+        L::Service: tower::Service<
+                UpdateSplitsDeleteOpstampRequest,
+                Response = UpdateSplitsDeleteOpstampResponse,
+                Error = crate::metastore::MetastoreError,
+            > + Clone + Send + Sync + 'static,
+        <L::Service as tower::Service<
             UpdateSplitsDeleteOpstampRequest,
         >>::Future: Send + 'static,
     {
@@ -3161,6 +4149,70 @@ impl MetastoreServiceTowerLayerStack {
                 quickwit_common::tower::BoxService::new(boxed_instance.clone()),
                 |svc, layer| layer.layer(svc),
             );
+        let update_index_uri_svc = self
+            .update_index_uri_layers
+            .into_iter()
+            .rev()
+            .fold(
+                quickwit_common::tower::BoxService::new(boxed_instance.clone()),
+                |svc, layer| layer.layer(svc),
+            );
+        let override_split_maturity_svc = self
+            .override_split_maturity_layers
+            .into_iter()
+            .rev()
+            .fold(
+                quickwit_common::tower::BoxService::new(boxed_instance.clone()),
+                |svc, layer| layer.layer(svc),
+            );
+        let rename_index_svc = self
+            .rename_index_layers
+            .into_iter()
+            .rev()
+            .fold(
+                quickwit_common::tower::BoxService::new(boxed_instance.clone()),
+                |svc, layer| layer.layer(svc),
+            );
+        let create_index_template_svc = self
+            .create_index_template_layers
+            .into_iter()
+            .rev()
+            .fold(
+                quickwit_common::tower::BoxService::new(boxed_instance.clone()),
+                |svc, layer| layer.layer(svc),
+            );
+        let list_index_templates_svc = self
+            .list_index_templates_layers
+            .into_iter()
+            .rev()
+            .fold(
+                quickwit_common::tower::BoxService::new(boxed_instance.clone()),
+                |svc, layer| layer.layer(svc),
+            );
+        let delete_index_templates_svc = self
+            .delete_index_templates_layers
+            .into_iter()
+            .rev()
+            .fold(
+                quickwit_common::tower::BoxService::new(boxed_instance.clone()),
+                |svc, layer| layer.layer(svc),
+            );
+        let update_index_aliases_svc = self
+            .update_index_aliases_layers
+            .into_iter()
+            .rev()
+            .fold(
+                quickwit_common::tower::BoxService::new(boxed_instance.clone()),
+                |svc, layer| layer.layer(svc),
+            );
+        let list_index_aliases_svc = self
+            .list_index_aliases_layers
+            .into_iter()
+            .rev()
+            .fold(
+                quickwit_common::tower::BoxService::new(boxed_instance.clone()),
+                |svc, layer| layer.layer(svc),
+            );
         let last_delete_opstamp_svc = self
             .last_delete_opstamp_layers
             .into_iter()
@@ -3248,6 +4300,14 @@ impl MetastoreServiceTowerLayerStack {
             toggle_source_svc,
             delete_source_svc,
             reset_source_checkpoint_svc,
+            update_index_uri_svc,
+            override_split_maturity_svc,
+            rename_index_svc,
+            create_index_template_svc,
+            list_index_templates_svc,
+            delete_index_templates_svc,
+            update_index_aliases_svc,
+            list_index_aliases_svc,
             last_delete_opstamp_svc,
             create_delete_task_svc,
             update_splits_delete_opstamp_svc,
@@ -3417,6 +4477,54 @@ where
             Error = crate::metastore::MetastoreError,
             Future = BoxFuture<EmptyResponse, crate::metastore::MetastoreError>,
         >
+        + tower::Service<
+            UpdateIndexUriRequest,
+            Response = EmptyResponse,
+            Error = crate::metastore::MetastoreError,
+            Future = BoxFuture<EmptyResponse, crate::metastore::MetastoreError>,
+        >
+        + tower::Service<
+            OverrideSplitMaturityRequest,
+            Response = EmptyResponse,
+            Error = crate::metastore::MetastoreError,
+            Future = BoxFuture<EmptyResponse, crate::metastore::MetastoreError>,
+        >
+        + tower::Service<
+            RenameIndexRequest,
+            Response = RenameIndexResponse,
+            Error = crate::metastore::MetastoreError,
+            Future = BoxFuture<RenameIndexResponse, crate::metastore::MetastoreError>,
+        >
+        + tower::Service<
+            CreateIndexTemplateRequest,
+            Response = EmptyResponse,
+            Error = crate::metastore::MetastoreError,
+            Future = BoxFuture<EmptyResponse, crate::metastore::MetastoreError>,
+        >
+        + tower::Service<
+            ListIndexTemplatesRequest,
+            Response = ListIndexTemplatesResponse,
+            Error = crate::metastore::MetastoreError,
+            Future = BoxFuture<ListIndexTemplatesResponse, crate::metastore::MetastoreError>,
+        >
+        + tower::Service<
+            DeleteIndexTemplatesRequest,
+            Response = EmptyResponse,
+            Error = crate::metastore::MetastoreError,
+            Future = BoxFuture<EmptyResponse, crate::metastore::MetastoreError>,
+        >
+        + tower::Service<
+            UpdateIndexAliasesRequest,
+            Response = EmptyResponse,
+            Error = crate::metastore::MetastoreError,
+            Future = BoxFuture<EmptyResponse, crate::metastore::MetastoreError>,
+        >
+        + tower::Service<
+            ListIndexAliasesRequest,
+            Response = ListIndexAliasesResponse,
+            Error = crate::metastore::MetastoreError,
+            Future = BoxFuture<ListIndexAliasesResponse, crate::metastore::MetastoreError>,
+        >
         + tower::Service<
             LastDeleteOpstampRequest,
             Response = LastDeleteOpstampResponse,
@@ -3556,6 +4664,54 @@ where
     ) -> crate::metastore::MetastoreResult<EmptyResponse> {
         self.call(request).await
     }
+    async fn update_index_uri(
+        &mut self,
+        request: UpdateIndexUriRequest,
+    ) -> crate::metastore::MetastoreResult<EmptyResponse> {
+        self.call(request).await
+    }
+    async fn override_split_maturity(
+        &mut self,
+        request: OverrideSplitMaturityRequest,
+    ) -> crate::metastore::MetastoreResult<EmptyResponse> {
+        self.call(request).await
+    }
+    async fn rename_index(
+        &mut self,
+        request: RenameIndexRequest,
+    ) -> crate::metastore::MetastoreResult<RenameIndexResponse> {
+        self.call(request).await
+    }
+    async fn create_index_template(
+        &mut self,
+        request: CreateIndexTemplateRequest,
+    ) -> crate::metastore::MetastoreResult<EmptyResponse> {
+        self.call(request).await
+    }
+    async fn list_index_templates(
+        &mut self,
+        request: ListIndexTemplatesRequest,
+    ) -> crate::metastore::MetastoreResult<ListIndexTemplatesResponse> {
+        self.call(request).await
+    }
+    async fn delete_index_templates(
+        &mut self,
+        request: DeleteIndexTemplatesRequest,
+    ) -> crate::metastore::MetastoreResult<EmptyResponse> {
+        self.call(request).await
+    }
+    async fn update_index_aliases(
+        &mut self,
+        request: UpdateIndexAliasesRequest,
+    ) -> crate::metastore::MetastoreResult<EmptyResponse> {
+        self.call(request).await
+    }
+    async fn list_index_aliases(
+        &mut self,
+        request: ListIndexAliasesRequest,
+    ) -> crate::metastore::MetastoreResult<ListIndexAliasesResponse> {
+        self.call(request).await
+    }
     async fn last_delete_opstamp(
         &mut self,
         request: LastDeleteOpstampRequest,
@@ -3791,49 +4947,129 @@ where
             .map(|response| response.into_inner())
             .map_err(|error| error.into())
     }
-    async fn last_delete_opstamp(
+    async fn update_index_uri(
         &mut self,
-        request: LastDeleteOpstampRequest,
-    ) -> crate::metastore::MetastoreResult<LastDeleteOpstampResponse> {
+        request: UpdateIndexUriRequest,
+    ) -> crate::metastore::MetastoreResult<EmptyResponse> {
         self.inner
-            .last_delete_opstamp(request)
+            .update_index_uri(request)
             .await
             .map(|response| response.into_inner())
             .map_err(|error| error.into())
     }
-    async fn create_delete_task(
+    async fn override_split_maturity(
         &mut self,
-        request: DeleteQuery,
-    ) -> crate::metastore::MetastoreResult<DeleteTask> {
+        request: OverrideSplitMaturityRequest,
+    ) -> crate::metastore::MetastoreResult<EmptyResponse> {
         self.inner
-            .create_delete_task(request)
+            .override_split_maturity(request)
             .await
             .map(|response| response.into_inner())
             .map_err(|error| error.into())
     }
-    async fn update_splits_delete_opstamp(
+    async fn rename_index(
         &mut self,
-        request: UpdateSplitsDeleteOpstampRequest,
-    ) -> crate::metastore::MetastoreResult<UpdateSplitsDeleteOpstampResponse> {
+        request: RenameIndexRequest,
+    ) -> crate::metastore::MetastoreResult<RenameIndexResponse> {
         self.inner
-            .update_splits_delete_opstamp(request)
+            .rename_index(request)
             .await
             .map(|response| response.into_inner())
             .map_err(|error| error.into())
     }
-    async fn list_delete_tasks(
+    async fn create_index_template(
         &mut self,
-        request: ListDeleteTasksRequest,
-    ) -> crate::metastore::MetastoreResult<ListDeleteTasksResponse> {
+        request: CreateIndexTemplateRequest,
+    ) -> crate::metastore::MetastoreResult<EmptyResponse> {
         self.inner
-            .list_delete_tasks(request)
+            .create_index_template(request)
             .await
             .map(|response| response.into_inner())
             .map_err(|error| error.into())
     }
-    async fn list_stale_splits(
+    async fn list_index_templates(
         &mut self,
-        request: ListStaleSplitsRequest,
+        request: ListIndexTemplatesRequest,
+    ) -> crate::metastore::MetastoreResult<ListIndexTemplatesResponse> {
+        self.inner
+            .list_index_templates(request)
+            .await
+            .map(|response| response.into_inner())
+            .map_err(|error| error.into())
+    }
+    async fn delete_index_templates(
+        &mut self,
+        request: DeleteIndexTemplatesRequest,
+    ) -> crate::metastore::MetastoreResult<EmptyResponse> {
+        self.inner
+            .delete_index_templates(request)
+            .await
+            .map(|response| response.into_inner())
+            .map_err(|error| error.into())
+    }
+    async fn update_index_aliases(
+        &mut self,
+        request: UpdateIndexAliasesRequest,
+    ) -> crate::metastore::MetastoreResult<EmptyResponse> {
+        self.inner
+            .update_index_aliases(request)
+            .await
+            .map(|response| response.into_inner())
+            .map_err(|error| error.into())
+    }
+    async fn list_index_aliases(
+        &mut self,
+        request: ListIndexAliasesRequest,
+    ) -> crate::metastore::MetastoreResult<ListIndexAliasesResponse> {
+        self.inner
+            .list_index_aliases(request)
+            .await
+            .map(|response| response.into_inner())
+            .map_err(|error| error.into())
+    }
+    async fn last_delete_opstamp(
+        &mut self,
+        request: LastDeleteOpstampRequest,
+    ) -> crate::metastore::MetastoreResult<LastDeleteOpstampResponse> {
+        self.inner
+            .last_delete_opstamp(request)
+            .await
+            .map(|response| response.into_inner())
+            .map_err(|error| error.into())
+    }
+    async fn create_delete_task(
+        &mut self,
+        request: DeleteQuery,
+    ) -> crate::metastore::MetastoreResult<DeleteTask> {
+        self.inner
+            .create_delete_task(request)
+            .await
+            .map(|response| response.into_inner())
+            .map_err(|error| error.into())
+    }
+    async fn update_splits_delete_opstamp(
+        &mut self,
+        request: UpdateSplitsDeleteOpstampRequest,
+    ) -> crate::metastore::MetastoreResult<UpdateSplitsDeleteOpstampResponse> {
+        self.inner
+            .update_splits_delete_opstamp(request)
+            .await
+            .map(|response| response.into_inner())
+            .map_err(|error| error.into())
+    }
+    async fn list_delete_tasks(
+        &mut self,
+        request: ListDeleteTasksRequest,
+    ) -> crate::metastore::MetastoreResult<ListDeleteTasksResponse> {
+        self.inner
+            .list_delete_tasks(request)
+            .await
+            .map(|response| response.into_inner())
+            .map_err(|error| error.into())
+    }
+    async fn list_stale_splits(
+        &mut self,
+        request: ListStaleSplitsRequest,
     ) -> crate::metastore::MetastoreResult<ListSplitsResponse> {
         self.inner
             .list_stale_splits(request)
@@ -4058,6 +5294,94 @@ for MetastoreServiceGrpcServerAdapter {
             .map(tonic::Response::new)
             .map_err(|error| error.into())
     }
+    async fn update_index_uri(
+        &self,
+        request: tonic::Request<UpdateIndexUriRequest>,
+    ) -> Result<tonic::Response<EmptyResponse>, tonic::Status> {
+        self.inner
+            .clone()
+            .update_index_uri(request.into_inner())
+            .await
+            .map(tonic::Response::new)
+            .map_err(|error| error.into())
+    }
+    async fn override_split_maturity(
+        &self,
+        request: tonic::Request<OverrideSplitMaturityRequest>,
+    ) -> Result<tonic::Response<EmptyResponse>, tonic::Status> {
+        self.inner
+            .clone()
+            .override_split_maturity(request.into_inner())
+            .await
+            .map(tonic::Response::new)
+            .map_err(|error| error.into())
+    }
+    async fn rename_index(
+        &self,
+        request: tonic::Request<RenameIndexRequest>,
+    ) -> Result<tonic::Response<RenameIndexResponse>, tonic::Status> {
+        self.inner
+            .clone()
+            .rename_index(request.into_inner())
+            .await
+            .map(tonic::Response::new)
+            .map_err(|error| error.into())
+    }
+    async fn create_index_template(
+        &self,
+        request: tonic::Request<CreateIndexTemplateRequest>,
+    ) -> Result<tonic::Response<EmptyResponse>, tonic::Status> {
+        self.inner
+            .clone()
+            .create_index_template(request.into_inner())
+            .await
+            .map(tonic::Response::new)
+            .map_err(|error| error.into())
+    }
+    async fn list_index_templates(
+        &self,
+        request: tonic::Request<ListIndexTemplatesRequest>,
+    ) -> Result<tonic::Response<ListIndexTemplatesResponse>, tonic::Status> {
+        self.inner
+            .clone()
+            .list_index_templates(request.into_inner())
+            .await
+            .map(tonic::Response::new)
+            .map_err(|error| error.into())
+    }
+    async fn delete_index_templates(
+        &self,
+        request: tonic::Request<DeleteIndexTemplatesRequest>,
+    ) -> Result<tonic::Response<EmptyResponse>, tonic::Status> {
+        self.inner
+            .clone()
+            .delete_index_templates(request.into_inner())
+            .await
+            .map(tonic::Response::new)
+            .map_err(|error| error.into())
+    }
+    async fn update_index_aliases(
+        &self,
+        request: tonic::Request<UpdateIndexAliasesRequest>,
+    ) -> Result<tonic::Response<EmptyResponse>, tonic::Status> {
+        self.inner
+            .clone()
+            .update_index_aliases(request.into_inner())
+            .await
+            .map(tonic::Response::new)
+            .map_err(|error| error.into())
+    }
+    async fn list_index_aliases(
+        &self,
+        request: tonic::Request<ListIndexAliasesRequest>,
+    ) -> Result<tonic::Response<ListIndexAliasesResponse>, tonic::Status> {
+        self.inner
+            .clone()
+            .list_index_aliases(request.into_inner())
+            .await
+            .map(tonic::Response::new)
+            .map_err(|error| error.into())
+    }
     async fn last_delete_opstamp(
         &self,
         request: tonic::Request<LastDeleteOpstampRequest>,
@@ -4653,14 +5977,11 @@ pub mod metastore_service_grpc_client {
                 );
             self.inner.unary(req, path, codec).await
         }
-        /// Gets last opstamp for a given `index_id`.
-        pub async fn last_delete_opstamp(
+        /// Updates the URI of an index.
+        pub async fn update_index_uri(
             &mut self,
-            request: impl tonic::IntoRequest<super::LastDeleteOpstampRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::LastDeleteOpstampResponse>,
-            tonic::Status,
-        > {
+            request: impl tonic::IntoRequest<super::UpdateIndexUriRequest>,
+        ) -> std::result::Result<tonic::Response<super::EmptyResponse>, tonic::Status> {
             self.inner
                 .ready()
                 .await
@@ -4672,23 +5993,23 @@ pub mod metastore_service_grpc_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/quickwit.metastore.MetastoreService/LastDeleteOpstamp",
+                "/quickwit.metastore.MetastoreService/UpdateIndexUri",
             );
             let mut req = request.into_request();
             req.extensions_mut()
                 .insert(
                     GrpcMethod::new(
                         "quickwit.metastore.MetastoreService",
-                        "LastDeleteOpstamp",
+                        "UpdateIndexUri",
                     ),
                 );
             self.inner.unary(req, path, codec).await
         }
-        /// Creates a delete task.
-        pub async fn create_delete_task(
+        /// Overrides the maturity of a split.
+        pub async fn override_split_maturity(
             &mut self,
-            request: impl tonic::IntoRequest<super::DeleteQuery>,
-        ) -> std::result::Result<tonic::Response<super::DeleteTask>, tonic::Status> {
+            request: impl tonic::IntoRequest<super::OverrideSplitMaturityRequest>,
+        ) -> std::result::Result<tonic::Response<super::EmptyResponse>, tonic::Status> {
             self.inner
                 .ready()
                 .await
@@ -4700,26 +6021,23 @@ pub mod metastore_service_grpc_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/quickwit.metastore.MetastoreService/CreateDeleteTask",
+                "/quickwit.metastore.MetastoreService/OverrideSplitMaturity",
             );
             let mut req = request.into_request();
             req.extensions_mut()
                 .insert(
                     GrpcMethod::new(
                         "quickwit.metastore.MetastoreService",
-                        "CreateDeleteTask",
+                        "OverrideSplitMaturity",
                     ),
                 );
             self.inner.unary(req, path, codec).await
         }
-        /// Updates splits `delete_opstamp`.
-        pub async fn update_splits_delete_opstamp(
+        /// Renames an index, preserving its incarnation.
+        pub async fn rename_index(
             &mut self,
-            request: impl tonic::IntoRequest<super::UpdateSplitsDeleteOpstampRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::UpdateSplitsDeleteOpstampResponse>,
-            tonic::Status,
-        > {
+            request: impl tonic::IntoRequest<super::RenameIndexRequest>,
+        ) -> std::result::Result<tonic::Response<super::RenameIndexResponse>, tonic::Status> {
             self.inner
                 .ready()
                 .await
@@ -4731,26 +6049,23 @@ pub mod metastore_service_grpc_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/quickwit.metastore.MetastoreService/UpdateSplitsDeleteOpstamp",
+                "/quickwit.metastore.MetastoreService/RenameIndex",
             );
             let mut req = request.into_request();
             req.extensions_mut()
                 .insert(
                     GrpcMethod::new(
                         "quickwit.metastore.MetastoreService",
-                        "UpdateSplitsDeleteOpstamp",
+                        "RenameIndex",
                     ),
                 );
             self.inner.unary(req, path, codec).await
         }
-        /// Lists delete tasks with `delete_task.opstamp` > `opstamp_start` for a given `index_id`.
-        pub async fn list_delete_tasks(
+        /// Creates an index template.
+        pub async fn create_index_template(
             &mut self,
-            request: impl tonic::IntoRequest<super::ListDeleteTasksRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::ListDeleteTasksResponse>,
-            tonic::Status,
-        > {
+            request: impl tonic::IntoRequest<super::CreateIndexTemplateRequest>,
+        ) -> std::result::Result<tonic::Response<super::EmptyResponse>, tonic::Status> {
             self.inner
                 .ready()
                 .await
@@ -4762,26 +6077,23 @@ pub mod metastore_service_grpc_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/quickwit.metastore.MetastoreService/ListDeleteTasks",
+                "/quickwit.metastore.MetastoreService/CreateIndexTemplate",
             );
             let mut req = request.into_request();
             req.extensions_mut()
                 .insert(
                     GrpcMethod::new(
                         "quickwit.metastore.MetastoreService",
-                        "ListDeleteTasks",
+                        "CreateIndexTemplate",
                     ),
                 );
             self.inner.unary(req, path, codec).await
         }
-        /// Lists splits with `split.delete_opstamp` < `delete_opstamp` for a given `index_id`.
-        pub async fn list_stale_splits(
+        /// Lists the index templates.
+        pub async fn list_index_templates(
             &mut self,
-            request: impl tonic::IntoRequest<super::ListStaleSplitsRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::ListSplitsResponse>,
-            tonic::Status,
-        > {
+            request: impl tonic::IntoRequest<super::ListIndexTemplatesRequest>,
+        ) -> std::result::Result<tonic::Response<super::ListIndexTemplatesResponse>, tonic::Status> {
             self.inner
                 .ready()
                 .await
@@ -4793,31 +6105,23 @@ pub mod metastore_service_grpc_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/quickwit.metastore.MetastoreService/ListStaleSplits",
+                "/quickwit.metastore.MetastoreService/ListIndexTemplates",
             );
             let mut req = request.into_request();
             req.extensions_mut()
                 .insert(
                     GrpcMethod::new(
                         "quickwit.metastore.MetastoreService",
-                        "ListStaleSplits",
+                        "ListIndexTemplates",
                     ),
                 );
             self.inner.unary(req, path, codec).await
         }
-        /// Shard API
-        ///
-        /// Note that for the file-backed metastore implementation, the requests are not processed atomically.
-        /// Indeed, each request comprises one or more subrequests that target different indexes and sources processed
-        /// independently. Responses list the requests that succeeded or failed in the fields `successes` and
-        /// `failures`.
-        pub async fn open_shards(
+        /// Deletes index templates.
+        pub async fn delete_index_templates(
             &mut self,
-            request: impl tonic::IntoRequest<super::OpenShardsRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::OpenShardsResponse>,
-            tonic::Status,
-        > {
+            request: impl tonic::IntoRequest<super::DeleteIndexTemplatesRequest>,
+        ) -> std::result::Result<tonic::Response<super::EmptyResponse>, tonic::Status> {
             self.inner
                 .ready()
                 .await
@@ -4829,25 +6133,23 @@ pub mod metastore_service_grpc_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/quickwit.metastore.MetastoreService/OpenShards",
+                "/quickwit.metastore.MetastoreService/DeleteIndexTemplates",
             );
             let mut req = request.into_request();
             req.extensions_mut()
                 .insert(
-                    GrpcMethod::new("quickwit.metastore.MetastoreService", "OpenShards"),
+                    GrpcMethod::new(
+                        "quickwit.metastore.MetastoreService",
+                        "DeleteIndexTemplates",
+                    ),
                 );
             self.inner.unary(req, path, codec).await
         }
-        /// Acquires a set of shards for indexing. This RPC locks the shards for publishing thanks to a publish token and only
-        /// the last indexer that has acquired the shards is allowed to publish. The response returns for each subrequest the
-        /// list of acquired shards along with the positions to index from.
-        pub async fn acquire_shards(
+        /// Atomically creates, replaces, and deletes index aliases.
+        pub async fn update_index_aliases(
             &mut self,
-            request: impl tonic::IntoRequest<super::AcquireShardsRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::AcquireShardsResponse>,
-            tonic::Status,
-        > {
+            request: impl tonic::IntoRequest<super::UpdateIndexAliasesRequest>,
+        ) -> std::result::Result<tonic::Response<super::EmptyResponse>, tonic::Status> {
             self.inner
                 .ready()
                 .await
@@ -4859,27 +6161,23 @@ pub mod metastore_service_grpc_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/quickwit.metastore.MetastoreService/AcquireShards",
+                "/quickwit.metastore.MetastoreService/UpdateIndexAliases",
             );
             let mut req = request.into_request();
             req.extensions_mut()
                 .insert(
                     GrpcMethod::new(
                         "quickwit.metastore.MetastoreService",
-                        "AcquireShards",
+                        "UpdateIndexAliases",
                     ),
                 );
             self.inner.unary(req, path, codec).await
         }
-        /// Deletes a set of shards. This RPC deletes the shards from the metastore and the storage.
-        /// If the shard did not exist to begin with, the operation is successful and does not return any error.
-        pub async fn delete_shards(
+        /// Lists the index aliases.
+        pub async fn list_index_aliases(
             &mut self,
-            request: impl tonic::IntoRequest<super::DeleteShardsRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::DeleteShardsResponse>,
-            tonic::Status,
-        > {
+            request: impl tonic::IntoRequest<super::ListIndexAliasesRequest>,
+        ) -> std::result::Result<tonic::Response<super::ListIndexAliasesResponse>, tonic::Status> {
             self.inner
                 .ready()
                 .await
@@ -4891,23 +6189,24 @@ pub mod metastore_service_grpc_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/quickwit.metastore.MetastoreService/DeleteShards",
+                "/quickwit.metastore.MetastoreService/ListIndexAliases",
             );
             let mut req = request.into_request();
             req.extensions_mut()
                 .insert(
                     GrpcMethod::new(
                         "quickwit.metastore.MetastoreService",
-                        "DeleteShards",
+                        "ListIndexAliases",
                     ),
                 );
             self.inner.unary(req, path, codec).await
         }
-        pub async fn list_shards(
+        /// Gets last opstamp for a given `index_id`.
+        pub async fn last_delete_opstamp(
             &mut self,
-            request: impl tonic::IntoRequest<super::ListShardsRequest>,
+            request: impl tonic::IntoRequest<super::LastDeleteOpstampRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::ListShardsResponse>,
+            tonic::Response<super::LastDeleteOpstampResponse>,
             tonic::Status,
         > {
             self.inner
@@ -4921,43 +6220,292 @@ pub mod metastore_service_grpc_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/quickwit.metastore.MetastoreService/ListShards",
+                "/quickwit.metastore.MetastoreService/LastDeleteOpstamp",
             );
             let mut req = request.into_request();
             req.extensions_mut()
                 .insert(
-                    GrpcMethod::new("quickwit.metastore.MetastoreService", "ListShards"),
+                    GrpcMethod::new(
+                        "quickwit.metastore.MetastoreService",
+                        "LastDeleteOpstamp",
+                    ),
                 );
             self.inner.unary(req, path, codec).await
         }
-    }
-}
-/// Generated server implementations.
-pub mod metastore_service_grpc_server {
-    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
-    use tonic::codegen::*;
-    /// Generated trait containing gRPC methods that should be implemented for use with MetastoreServiceGrpcServer.
-    #[async_trait]
-    pub trait MetastoreServiceGrpc: Send + Sync + 'static {
-        /// Creates an index.
-        ///
-        /// This API creates a new index in the metastore.
-        /// An error will occur if an index that already exists in the storage is specified.
-        async fn create_index(
-            &self,
-            request: tonic::Request<super::CreateIndexRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::CreateIndexResponse>,
-            tonic::Status,
-        >;
-        /// Returns the `IndexMetadata` of an index identified by its IndexID or its IndexUID.
-        async fn index_metadata(
-            &self,
-            request: tonic::Request<super::IndexMetadataRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::IndexMetadataResponse>,
-            tonic::Status,
-        >;
+        /// Creates a delete task.
+        pub async fn create_delete_task(
+            &mut self,
+            request: impl tonic::IntoRequest<super::DeleteQuery>,
+        ) -> std::result::Result<tonic::Response<super::DeleteTask>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/quickwit.metastore.MetastoreService/CreateDeleteTask",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "quickwit.metastore.MetastoreService",
+                        "CreateDeleteTask",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// Updates splits `delete_opstamp`.
+        pub async fn update_splits_delete_opstamp(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UpdateSplitsDeleteOpstampRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::UpdateSplitsDeleteOpstampResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/quickwit.metastore.MetastoreService/UpdateSplitsDeleteOpstamp",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "quickwit.metastore.MetastoreService",
+                        "UpdateSplitsDeleteOpstamp",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// Lists delete tasks with `delete_task.opstamp` > `opstamp_start` for a given `index_id`.
+        pub async fn list_delete_tasks(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListDeleteTasksRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListDeleteTasksResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/quickwit.metastore.MetastoreService/ListDeleteTasks",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "quickwit.metastore.MetastoreService",
+                        "ListDeleteTasks",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// Lists splits with `split.delete_opstamp` < `delete_opstamp` for a given `index_id`.
+        pub async fn list_stale_splits(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListStaleSplitsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListSplitsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/quickwit.metastore.MetastoreService/ListStaleSplits",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "quickwit.metastore.MetastoreService",
+                        "ListStaleSplits",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// Shard API
+        ///
+        /// Note that for the file-backed metastore implementation, the requests are not processed atomically.
+        /// Indeed, each request comprises one or more subrequests that target different indexes and sources processed
+        /// independently. Responses list the requests that succeeded or failed in the fields `successes` and
+        /// `failures`.
+        pub async fn open_shards(
+            &mut self,
+            request: impl tonic::IntoRequest<super::OpenShardsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::OpenShardsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/quickwit.metastore.MetastoreService/OpenShards",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("quickwit.metastore.MetastoreService", "OpenShards"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// Acquires a set of shards for indexing. This RPC locks the shards for publishing thanks to a publish token and only
+        /// the last indexer that has acquired the shards is allowed to publish. The response returns for each subrequest the
+        /// list of acquired shards along with the positions to index from.
+        pub async fn acquire_shards(
+            &mut self,
+            request: impl tonic::IntoRequest<super::AcquireShardsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::AcquireShardsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/quickwit.metastore.MetastoreService/AcquireShards",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "quickwit.metastore.MetastoreService",
+                        "AcquireShards",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// Deletes a set of shards. This RPC deletes the shards from the metastore and the storage.
+        /// If the shard did not exist to begin with, the operation is successful and does not return any error.
+        pub async fn delete_shards(
+            &mut self,
+            request: impl tonic::IntoRequest<super::DeleteShardsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::DeleteShardsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/quickwit.metastore.MetastoreService/DeleteShards",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "quickwit.metastore.MetastoreService",
+                        "DeleteShards",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn list_shards(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListShardsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListShardsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/quickwit.metastore.MetastoreService/ListShards",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("quickwit.metastore.MetastoreService", "ListShards"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+    }
+}
+/// Generated server implementations.
+pub mod metastore_service_grpc_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with MetastoreServiceGrpcServer.
+    #[async_trait]
+    pub trait MetastoreServiceGrpc: Send + Sync + 'static {
+        /// Creates an index.
+        ///
+        /// This API creates a new index in the metastore.
+        /// An error will occur if an index that already exists in the storage is specified.
+        async fn create_index(
+            &self,
+            request: tonic::Request<super::CreateIndexRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CreateIndexResponse>,
+            tonic::Status,
+        >;
+        /// Returns the `IndexMetadata` of an index identified by its IndexID or its IndexUID.
+        async fn index_metadata(
+            &self,
+            request: tonic::Request<super::IndexMetadataRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::IndexMetadataResponse>,
+            tonic::Status,
+        >;
         /// Gets an indexes metadatas.
         async fn list_indexes_metadata(
             &self,
@@ -5015,13 +6563,53 @@ pub mod metastore_service_grpc_server {
         /// Removes source.
         async fn delete_source(
             &self,
-            request: tonic::Request<super::DeleteSourceRequest>,
+            request: tonic::Request<super::DeleteSourceRequest>,
+        ) -> std::result::Result<tonic::Response<super::EmptyResponse>, tonic::Status>;
+        /// Resets source checkpoint.
+        async fn reset_source_checkpoint(
+            &self,
+            request: tonic::Request<super::ResetSourceCheckpointRequest>,
+        ) -> std::result::Result<tonic::Response<super::EmptyResponse>, tonic::Status>;
+        /// Updates the URI of an index.
+        async fn update_index_uri(
+            &self,
+            request: tonic::Request<super::UpdateIndexUriRequest>,
+        ) -> std::result::Result<tonic::Response<super::EmptyResponse>, tonic::Status>;
+        /// Overrides the maturity of a split.
+        async fn override_split_maturity(
+            &self,
+            request: tonic::Request<super::OverrideSplitMaturityRequest>,
+        ) -> std::result::Result<tonic::Response<super::EmptyResponse>, tonic::Status>;
+        /// Renames an index, preserving its incarnation.
+        async fn rename_index(
+            &self,
+            request: tonic::Request<super::RenameIndexRequest>,
+        ) -> std::result::Result<tonic::Response<super::RenameIndexResponse>, tonic::Status>;
+        /// Creates an index template.
+        async fn create_index_template(
+            &self,
+            request: tonic::Request<super::CreateIndexTemplateRequest>,
+        ) -> std::result::Result<tonic::Response<super::EmptyResponse>, tonic::Status>;
+        /// Lists the index templates.
+        async fn list_index_templates(
+            &self,
+            request: tonic::Request<super::ListIndexTemplatesRequest>,
+        ) -> std::result::Result<tonic::Response<super::ListIndexTemplatesResponse>, tonic::Status>;
+        /// Deletes index templates.
+        async fn delete_index_templates(
+            &self,
+            request: tonic::Request<super::DeleteIndexTemplatesRequest>,
         ) -> std::result::Result<tonic::Response<super::EmptyResponse>, tonic::Status>;
-        /// Resets source checkpoint.
-        async fn reset_source_checkpoint(
+        /// Atomically creates, replaces, and deletes index aliases.
+        async fn update_index_aliases(
             &self,
-            request: tonic::Request<super::ResetSourceCheckpointRequest>,
+            request: tonic::Request<super::UpdateIndexAliasesRequest>,
         ) -> std::result::Result<tonic::Response<super::EmptyResponse>, tonic::Status>;
+        /// Lists the index aliases.
+        async fn list_index_aliases(
+            &self,
+            request: tonic::Request<super::ListIndexAliasesRequest>,
+        ) -> std::result::Result<tonic::Response<super::ListIndexAliasesResponse>, tonic::Status>;
         /// Gets last opstamp for a given `index_id`.
         async fn last_delete_opstamp(
             &self,
@@ -5227,23 +6815,298 @@ pub mod metastore_service_grpc_server {
             match req.uri().path() {
                 "/quickwit.metastore.MetastoreService/CreateIndex" => {
                     #[allow(non_camel_case_types)]
-                    struct CreateIndexSvc<T: MetastoreServiceGrpc>(pub Arc<T>);
+                    struct CreateIndexSvc<T: MetastoreServiceGrpc>(pub Arc<T>);
+                    impl<
+                        T: MetastoreServiceGrpc,
+                    > tonic::server::UnaryService<super::CreateIndexRequest>
+                    for CreateIndexSvc<T> {
+                        type Response = super::CreateIndexResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CreateIndexRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                (*inner).create_index(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = CreateIndexSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/quickwit.metastore.MetastoreService/IndexMetadata" => {
+                    #[allow(non_camel_case_types)]
+                    struct IndexMetadataSvc<T: MetastoreServiceGrpc>(pub Arc<T>);
+                    impl<
+                        T: MetastoreServiceGrpc,
+                    > tonic::server::UnaryService<super::IndexMetadataRequest>
+                    for IndexMetadataSvc<T> {
+                        type Response = super::IndexMetadataResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::IndexMetadataRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                (*inner).index_metadata(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = IndexMetadataSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/quickwit.metastore.MetastoreService/ListIndexesMetadata" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListIndexesMetadataSvc<T: MetastoreServiceGrpc>(pub Arc<T>);
+                    impl<
+                        T: MetastoreServiceGrpc,
+                    > tonic::server::UnaryService<super::ListIndexesMetadataRequest>
+                    for ListIndexesMetadataSvc<T> {
+                        type Response = super::ListIndexesMetadataResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListIndexesMetadataRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                (*inner).list_indexes_metadata(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ListIndexesMetadataSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/quickwit.metastore.MetastoreService/DeleteIndex" => {
+                    #[allow(non_camel_case_types)]
+                    struct DeleteIndexSvc<T: MetastoreServiceGrpc>(pub Arc<T>);
+                    impl<
+                        T: MetastoreServiceGrpc,
+                    > tonic::server::UnaryService<super::DeleteIndexRequest>
+                    for DeleteIndexSvc<T> {
+                        type Response = super::EmptyResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DeleteIndexRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                (*inner).delete_index(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = DeleteIndexSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/quickwit.metastore.MetastoreService/ListSplits" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListSplitsSvc<T: MetastoreServiceGrpc>(pub Arc<T>);
+                    impl<
+                        T: MetastoreServiceGrpc,
+                    > tonic::server::ServerStreamingService<super::ListSplitsRequest>
+                    for ListSplitsSvc<T> {
+                        type Response = super::ListSplitsResponse;
+                        type ResponseStream = T::ListSplitsStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListSplitsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move { (*inner).list_splits(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ListSplitsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/quickwit.metastore.MetastoreService/StageSplits" => {
+                    #[allow(non_camel_case_types)]
+                    struct StageSplitsSvc<T: MetastoreServiceGrpc>(pub Arc<T>);
+                    impl<
+                        T: MetastoreServiceGrpc,
+                    > tonic::server::UnaryService<super::StageSplitsRequest>
+                    for StageSplitsSvc<T> {
+                        type Response = super::EmptyResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::StageSplitsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                (*inner).stage_splits(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = StageSplitsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/quickwit.metastore.MetastoreService/PublishSplits" => {
+                    #[allow(non_camel_case_types)]
+                    struct PublishSplitsSvc<T: MetastoreServiceGrpc>(pub Arc<T>);
                     impl<
                         T: MetastoreServiceGrpc,
-                    > tonic::server::UnaryService<super::CreateIndexRequest>
-                    for CreateIndexSvc<T> {
-                        type Response = super::CreateIndexResponse;
+                    > tonic::server::UnaryService<super::PublishSplitsRequest>
+                    for PublishSplitsSvc<T> {
+                        type Response = super::EmptyResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::CreateIndexRequest>,
+                            request: tonic::Request<super::PublishSplitsRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                (*inner).create_index(request).await
+                                (*inner).publish_splits(request).await
                             };
                             Box::pin(fut)
                         }
@@ -5255,7 +7118,7 @@ pub mod metastore_service_grpc_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = CreateIndexSvc(inner);
+                        let method = PublishSplitsSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -5271,25 +7134,25 @@ pub mod metastore_service_grpc_server {
                     };
                     Box::pin(fut)
                 }
-                "/quickwit.metastore.MetastoreService/IndexMetadata" => {
+                "/quickwit.metastore.MetastoreService/MarkSplitsForDeletion" => {
                     #[allow(non_camel_case_types)]
-                    struct IndexMetadataSvc<T: MetastoreServiceGrpc>(pub Arc<T>);
+                    struct MarkSplitsForDeletionSvc<T: MetastoreServiceGrpc>(pub Arc<T>);
                     impl<
                         T: MetastoreServiceGrpc,
-                    > tonic::server::UnaryService<super::IndexMetadataRequest>
-                    for IndexMetadataSvc<T> {
-                        type Response = super::IndexMetadataResponse;
+                    > tonic::server::UnaryService<super::MarkSplitsForDeletionRequest>
+                    for MarkSplitsForDeletionSvc<T> {
+                        type Response = super::EmptyResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::IndexMetadataRequest>,
+                            request: tonic::Request<super::MarkSplitsForDeletionRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                (*inner).index_metadata(request).await
+                                (*inner).mark_splits_for_deletion(request).await
                             };
                             Box::pin(fut)
                         }
@@ -5301,7 +7164,7 @@ pub mod metastore_service_grpc_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = IndexMetadataSvc(inner);
+                        let method = MarkSplitsForDeletionSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -5317,25 +7180,25 @@ pub mod metastore_service_grpc_server {
                     };
                     Box::pin(fut)
                 }
-                "/quickwit.metastore.MetastoreService/ListIndexesMetadata" => {
+                "/quickwit.metastore.MetastoreService/DeleteSplits" => {
                     #[allow(non_camel_case_types)]
-                    struct ListIndexesMetadataSvc<T: MetastoreServiceGrpc>(pub Arc<T>);
+                    struct DeleteSplitsSvc<T: MetastoreServiceGrpc>(pub Arc<T>);
                     impl<
                         T: MetastoreServiceGrpc,
-                    > tonic::server::UnaryService<super::ListIndexesMetadataRequest>
-                    for ListIndexesMetadataSvc<T> {
-                        type Response = super::ListIndexesMetadataResponse;
+                    > tonic::server::UnaryService<super::DeleteSplitsRequest>
+                    for DeleteSplitsSvc<T> {
+                        type Response = super::EmptyResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::ListIndexesMetadataRequest>,
+                            request: tonic::Request<super::DeleteSplitsRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                (*inner).list_indexes_metadata(request).await
+                                (*inner).delete_splits(request).await
                             };
                             Box::pin(fut)
                         }
@@ -5347,7 +7210,7 @@ pub mod metastore_service_grpc_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = ListIndexesMetadataSvc(inner);
+                        let method = DeleteSplitsSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -5363,13 +7226,13 @@ pub mod metastore_service_grpc_server {
                     };
                     Box::pin(fut)
                 }
-                "/quickwit.metastore.MetastoreService/DeleteIndex" => {
+                "/quickwit.metastore.MetastoreService/AddSource" => {
                     #[allow(non_camel_case_types)]
-                    struct DeleteIndexSvc<T: MetastoreServiceGrpc>(pub Arc<T>);
+                    struct AddSourceSvc<T: MetastoreServiceGrpc>(pub Arc<T>);
                     impl<
                         T: MetastoreServiceGrpc,
-                    > tonic::server::UnaryService<super::DeleteIndexRequest>
-                    for DeleteIndexSvc<T> {
+                    > tonic::server::UnaryService<super::AddSourceRequest>
+                    for AddSourceSvc<T> {
                         type Response = super::EmptyResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
@@ -5377,11 +7240,55 @@ pub mod metastore_service_grpc_server {
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::DeleteIndexRequest>,
+                            request: tonic::Request<super::AddSourceRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move { (*inner).add_source(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = AddSourceSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/quickwit.metastore.MetastoreService/ToggleSource" => {
+                    #[allow(non_camel_case_types)]
+                    struct ToggleSourceSvc<T: MetastoreServiceGrpc>(pub Arc<T>);
+                    impl<
+                        T: MetastoreServiceGrpc,
+                    > tonic::server::UnaryService<super::ToggleSourceRequest>
+                    for ToggleSourceSvc<T> {
+                        type Response = super::EmptyResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ToggleSourceRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                (*inner).delete_index(request).await
+                                (*inner).toggle_source(request).await
                             };
                             Box::pin(fut)
                         }
@@ -5393,7 +7300,7 @@ pub mod metastore_service_grpc_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = DeleteIndexSvc(inner);
+                        let method = ToggleSourceSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -5409,25 +7316,26 @@ pub mod metastore_service_grpc_server {
                     };
                     Box::pin(fut)
                 }
-                "/quickwit.metastore.MetastoreService/ListSplits" => {
+                "/quickwit.metastore.MetastoreService/DeleteSource" => {
                     #[allow(non_camel_case_types)]
-                    struct ListSplitsSvc<T: MetastoreServiceGrpc>(pub Arc<T>);
+                    struct DeleteSourceSvc<T: MetastoreServiceGrpc>(pub Arc<T>);
                     impl<
                         T: MetastoreServiceGrpc,
-                    > tonic::server::ServerStreamingService<super::ListSplitsRequest>
-                    for ListSplitsSvc<T> {
-                        type Response = super::ListSplitsResponse;
-                        type ResponseStream = T::ListSplitsStream;
+                    > tonic::server::UnaryService<super::DeleteSourceRequest>
+                    for DeleteSourceSvc<T> {
+                        type Response = super::EmptyResponse;
                         type Future = BoxFuture<
-                            tonic::Response<Self::ResponseStream>,
+                            tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::ListSplitsRequest>,
+                            request: tonic::Request<super::DeleteSourceRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
-                            let fut = async move { (*inner).list_splits(request).await };
+                            let fut = async move {
+                                (*inner).delete_source(request).await
+                            };
                             Box::pin(fut)
                         }
                     }
@@ -5438,7 +7346,7 @@ pub mod metastore_service_grpc_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = ListSplitsSvc(inner);
+                        let method = DeleteSourceSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -5449,18 +7357,18 @@ pub mod metastore_service_grpc_server {
                                 max_decoding_message_size,
                                 max_encoding_message_size,
                             );
-                        let res = grpc.server_streaming(method, req).await;
+                        let res = grpc.unary(method, req).await;
                         Ok(res)
                     };
                     Box::pin(fut)
                 }
-                "/quickwit.metastore.MetastoreService/StageSplits" => {
+                "/quickwit.metastore.MetastoreService/ResetSourceCheckpoint" => {
                     #[allow(non_camel_case_types)]
-                    struct StageSplitsSvc<T: MetastoreServiceGrpc>(pub Arc<T>);
+                    struct ResetSourceCheckpointSvc<T: MetastoreServiceGrpc>(pub Arc<T>);
                     impl<
                         T: MetastoreServiceGrpc,
-                    > tonic::server::UnaryService<super::StageSplitsRequest>
-                    for StageSplitsSvc<T> {
+                    > tonic::server::UnaryService<super::ResetSourceCheckpointRequest>
+                    for ResetSourceCheckpointSvc<T> {
                         type Response = super::EmptyResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
@@ -5468,11 +7376,11 @@ pub mod metastore_service_grpc_server {
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::StageSplitsRequest>,
+                            request: tonic::Request<super::ResetSourceCheckpointRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                (*inner).stage_splits(request).await
+                                (*inner).reset_source_checkpoint(request).await
                             };
                             Box::pin(fut)
                         }
@@ -5484,7 +7392,7 @@ pub mod metastore_service_grpc_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = StageSplitsSvc(inner);
+                        let method = ResetSourceCheckpointSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -5500,13 +7408,13 @@ pub mod metastore_service_grpc_server {
                     };
                     Box::pin(fut)
                 }
-                "/quickwit.metastore.MetastoreService/PublishSplits" => {
+                "/quickwit.metastore.MetastoreService/UpdateIndexUri" => {
                     #[allow(non_camel_case_types)]
-                    struct PublishSplitsSvc<T: MetastoreServiceGrpc>(pub Arc<T>);
+                    struct UpdateIndexUriSvc<T: MetastoreServiceGrpc>(pub Arc<T>);
                     impl<
                         T: MetastoreServiceGrpc,
-                    > tonic::server::UnaryService<super::PublishSplitsRequest>
-                    for PublishSplitsSvc<T> {
+                    > tonic::server::UnaryService<super::UpdateIndexUriRequest>
+                    for UpdateIndexUriSvc<T> {
                         type Response = super::EmptyResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
@@ -5514,11 +7422,11 @@ pub mod metastore_service_grpc_server {
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::PublishSplitsRequest>,
+                            request: tonic::Request<super::UpdateIndexUriRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                (*inner).publish_splits(request).await
+                                (*inner).update_index_uri(request).await
                             };
                             Box::pin(fut)
                         }
@@ -5530,7 +7438,7 @@ pub mod metastore_service_grpc_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = PublishSplitsSvc(inner);
+                        let method = UpdateIndexUriSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -5546,13 +7454,13 @@ pub mod metastore_service_grpc_server {
                     };
                     Box::pin(fut)
                 }
-                "/quickwit.metastore.MetastoreService/MarkSplitsForDeletion" => {
+                "/quickwit.metastore.MetastoreService/OverrideSplitMaturity" => {
                     #[allow(non_camel_case_types)]
-                    struct MarkSplitsForDeletionSvc<T: MetastoreServiceGrpc>(pub Arc<T>);
+                    struct OverrideSplitMaturitySvc<T: MetastoreServiceGrpc>(pub Arc<T>);
                     impl<
                         T: MetastoreServiceGrpc,
-                    > tonic::server::UnaryService<super::MarkSplitsForDeletionRequest>
-                    for MarkSplitsForDeletionSvc<T> {
+                    > tonic::server::UnaryService<super::OverrideSplitMaturityRequest>
+                    for OverrideSplitMaturitySvc<T> {
                         type Response = super::EmptyResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
@@ -5560,11 +7468,11 @@ pub mod metastore_service_grpc_server {
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::MarkSplitsForDeletionRequest>,
+                            request: tonic::Request<super::OverrideSplitMaturityRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                (*inner).mark_splits_for_deletion(request).await
+                                (*inner).override_split_maturity(request).await
                             };
                             Box::pin(fut)
                         }
@@ -5576,7 +7484,7 @@ pub mod metastore_service_grpc_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = MarkSplitsForDeletionSvc(inner);
+                        let method = OverrideSplitMaturitySvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -5592,25 +7500,25 @@ pub mod metastore_service_grpc_server {
                     };
                     Box::pin(fut)
                 }
-                "/quickwit.metastore.MetastoreService/DeleteSplits" => {
+                "/quickwit.metastore.MetastoreService/RenameIndex" => {
                     #[allow(non_camel_case_types)]
-                    struct DeleteSplitsSvc<T: MetastoreServiceGrpc>(pub Arc<T>);
+                    struct RenameIndexSvc<T: MetastoreServiceGrpc>(pub Arc<T>);
                     impl<
                         T: MetastoreServiceGrpc,
-                    > tonic::server::UnaryService<super::DeleteSplitsRequest>
-                    for DeleteSplitsSvc<T> {
-                        type Response = super::EmptyResponse;
+                    > tonic::server::UnaryService<super::RenameIndexRequest>
+                    for RenameIndexSvc<T> {
+                        type Response = super::RenameIndexResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::DeleteSplitsRequest>,
+                            request: tonic::Request<super::RenameIndexRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                (*inner).delete_splits(request).await
+                                (*inner).rename_index(request).await
                             };
                             Box::pin(fut)
                         }
@@ -5622,7 +7530,7 @@ pub mod metastore_service_grpc_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = DeleteSplitsSvc(inner);
+                        let method = RenameIndexSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -5638,13 +7546,13 @@ pub mod metastore_service_grpc_server {
                     };
                     Box::pin(fut)
                 }
-                "/quickwit.metastore.MetastoreService/AddSource" => {
+                "/quickwit.metastore.MetastoreService/CreateIndexTemplate" => {
                     #[allow(non_camel_case_types)]
-                    struct AddSourceSvc<T: MetastoreServiceGrpc>(pub Arc<T>);
+                    struct CreateIndexTemplateSvc<T: MetastoreServiceGrpc>(pub Arc<T>);
                     impl<
                         T: MetastoreServiceGrpc,
-                    > tonic::server::UnaryService<super::AddSourceRequest>
-                    for AddSourceSvc<T> {
+                    > tonic::server::UnaryService<super::CreateIndexTemplateRequest>
+                    for CreateIndexTemplateSvc<T> {
                         type Response = super::EmptyResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
@@ -5652,10 +7560,12 @@ pub mod metastore_service_grpc_server {
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::AddSourceRequest>,
+                            request: tonic::Request<super::CreateIndexTemplateRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
-                            let fut = async move { (*inner).add_source(request).await };
+                            let fut = async move {
+                                (*inner).create_index_template(request).await
+                            };
                             Box::pin(fut)
                         }
                     }
@@ -5666,7 +7576,7 @@ pub mod metastore_service_grpc_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = AddSourceSvc(inner);
+                        let method = CreateIndexTemplateSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -5682,25 +7592,25 @@ pub mod metastore_service_grpc_server {
                     };
                     Box::pin(fut)
                 }
-                "/quickwit.metastore.MetastoreService/ToggleSource" => {
+                "/quickwit.metastore.MetastoreService/ListIndexTemplates" => {
                     #[allow(non_camel_case_types)]
-                    struct ToggleSourceSvc<T: MetastoreServiceGrpc>(pub Arc<T>);
+                    struct ListIndexTemplatesSvc<T: MetastoreServiceGrpc>(pub Arc<T>);
                     impl<
                         T: MetastoreServiceGrpc,
-                    > tonic::server::UnaryService<super::ToggleSourceRequest>
-                    for ToggleSourceSvc<T> {
-                        type Response = super::EmptyResponse;
+                    > tonic::server::UnaryService<super::ListIndexTemplatesRequest>
+                    for ListIndexTemplatesSvc<T> {
+                        type Response = super::ListIndexTemplatesResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::ToggleSourceRequest>,
+                            request: tonic::Request<super::ListIndexTemplatesRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                (*inner).toggle_source(request).await
+                                (*inner).list_index_templates(request).await
                             };
                             Box::pin(fut)
                         }
@@ -5712,7 +7622,7 @@ pub mod metastore_service_grpc_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = ToggleSourceSvc(inner);
+                        let method = ListIndexTemplatesSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -5728,13 +7638,13 @@ pub mod metastore_service_grpc_server {
                     };
                     Box::pin(fut)
                 }
-                "/quickwit.metastore.MetastoreService/DeleteSource" => {
+                "/quickwit.metastore.MetastoreService/DeleteIndexTemplates" => {
                     #[allow(non_camel_case_types)]
-                    struct DeleteSourceSvc<T: MetastoreServiceGrpc>(pub Arc<T>);
+                    struct DeleteIndexTemplatesSvc<T: MetastoreServiceGrpc>(pub Arc<T>);
                     impl<
                         T: MetastoreServiceGrpc,
-                    > tonic::server::UnaryService<super::DeleteSourceRequest>
-                    for DeleteSourceSvc<T> {
+                    > tonic::server::UnaryService<super::DeleteIndexTemplatesRequest>
+                    for DeleteIndexTemplatesSvc<T> {
                         type Response = super::EmptyResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
@@ -5742,11 +7652,11 @@ pub mod metastore_service_grpc_server {
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::DeleteSourceRequest>,
+                            request: tonic::Request<super::DeleteIndexTemplatesRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                (*inner).delete_source(request).await
+                                (*inner).delete_index_templates(request).await
                             };
                             Box::pin(fut)
                         }
@@ -5758,7 +7668,7 @@ pub mod metastore_service_grpc_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = DeleteSourceSvc(inner);
+                        let method = DeleteIndexTemplatesSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -5774,13 +7684,13 @@ pub mod metastore_service_grpc_server {
                     };
                     Box::pin(fut)
                 }
-                "/quickwit.metastore.MetastoreService/ResetSourceCheckpoint" => {
+                "/quickwit.metastore.MetastoreService/UpdateIndexAliases" => {
                     #[allow(non_camel_case_types)]
-                    struct ResetSourceCheckpointSvc<T: MetastoreServiceGrpc>(pub Arc<T>);
+                    struct UpdateIndexAliasesSvc<T: MetastoreServiceGrpc>(pub Arc<T>);
                     impl<
                         T: MetastoreServiceGrpc,
-                    > tonic::server::UnaryService<super::ResetSourceCheckpointRequest>
-                    for ResetSourceCheckpointSvc<T> {
+                    > tonic::server::UnaryService<super::UpdateIndexAliasesRequest>
+                    for UpdateIndexAliasesSvc<T> {
                         type Response = super::EmptyResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
@@ -5788,11 +7698,11 @@ pub mod metastore_service_grpc_server {
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::ResetSourceCheckpointRequest>,
+                            request: tonic::Request<super::UpdateIndexAliasesRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                (*inner).reset_source_checkpoint(request).await
+                                (*inner).update_index_aliases(request).await
                             };
                             Box::pin(fut)
                         }
@@ -5804,7 +7714,53 @@ pub mod metastore_service_grpc_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = ResetSourceCheckpointSvc(inner);
+                        let method = UpdateIndexAliasesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/quickwit.metastore.MetastoreService/ListIndexAliases" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListIndexAliasesSvc<T: MetastoreServiceGrpc>(pub Arc<T>);
+                    impl<
+                        T: MetastoreServiceGrpc,
+                    > tonic::server::UnaryService<super::ListIndexAliasesRequest>
+                    for ListIndexAliasesSvc<T> {
+                        type Response = super::ListIndexAliasesResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListIndexAliasesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                (*inner).list_index_aliases(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ListIndexAliasesSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
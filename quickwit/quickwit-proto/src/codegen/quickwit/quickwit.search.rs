@@ -184,6 +184,31 @@ pub struct SearchRequest {
     pub search_after: ::core::option::Option<PartialHit>,
     #[prost(enumeration = "CountHits", tag = "17")]
     pub count_hits: i32,
+    /// Maximum duration of the search, in milliseconds. If not set, the
+    /// searcher default timeout applies, if any.
+    #[prost(uint64, optional, tag = "18")]
+    pub timeout_millis: ::core::option::Option<u64>,
+    /// If set, hits are counted exactly up to this bound only. Beyond it, the
+    /// response reports the bound as a lower bound of the number of hits.
+    #[prost(uint32, optional, tag = "19")]
+    pub track_total_hits_up_to: ::core::option::Option<u32>,
+    /// If set, the failure of a subset of the leaf searches does not fail the
+    /// whole search: the results of the successful leaf searches are returned
+    /// along with the failed splits.
+    #[prost(bool, tag = "20")]
+    pub allow_partial_results: bool,
+    /// If not empty, only these splits are searched. They must be published
+    /// splits of the searched indexes. The time range and tags of the request
+    /// are not used to prune them.
+    #[prost(string, repeated, tag = "21")]
+    pub split_ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// If set, each hit carries the publish timestamp of the split it belongs to.
+    #[prost(bool, tag = "22")]
+    pub include_split_publish_timestamp: bool,
+    /// If set, terms aggregations are executed even on fields whose number of
+    /// distinct values exceeds the searcher `max_terms_aggregation_cardinality`.
+    #[prost(bool, tag = "23")]
+    pub allow_high_cardinality_aggregations: bool,
 }
 #[derive(Serialize, Deserialize, utoipa::ToSchema)]
 #[derive(Eq, Hash)]
@@ -223,6 +248,24 @@ pub struct SearchResponse {
     /// Scroll Id (only set if scroll_secs was set in the request)
     #[prost(string, optional, tag = "6")]
     pub scroll_id: ::core::option::Option<::prost::alloc::string::String>,
+    /// Whether `num_hits` is exact or a lower bound.
+    #[prost(enumeration = "HitsRelation", tag = "7")]
+    pub num_hits_relation: i32,
+    /// Splits that could not be searched. Only set if `allow_partial_results`
+    /// was set in the request.
+    #[prost(message, repeated, tag = "8")]
+    pub failed_splits: ::prost::alloc::vec::Vec<SplitSearchError>,
+    /// Whether some splits could not be searched, in which case the response
+    /// only covers the splits that were successfully searched.
+    #[prost(bool, tag = "9")]
+    pub partial: bool,
+    /// Number of hits matching the query in each of the searched indexes, keyed
+    /// by index ID. Only set if the request targets several indexes.
+    #[prost(map = "string, uint64", tag = "10")]
+    pub num_hits_per_index: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        u64,
+    >,
 }
 #[derive(Serialize, Deserialize, utoipa::ToSchema)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -278,6 +321,9 @@ pub struct SplitIdAndFooterOffsets {
     /// The highest timestamp appearing in the split
     #[prost(int64, optional, tag = "5")]
     pub timestamp_end: ::core::option::Option<i64>,
+    /// The prefix under which the split file is stored, relative to the index URI.
+    #[prost(string, optional, tag = "6")]
+    pub split_path_prefix: ::core::option::Option<::prost::alloc::string::String>,
 }
 /// Hits returned by a FetchDocRequest.
 ///
@@ -322,6 +368,10 @@ pub struct Hit {
     /// The index id of the hit
     #[prost(string, tag = "4")]
     pub index_id: ::prost::alloc::string::String,
+    /// The publish timestamp of the split the hit belongs to, in seconds since
+    /// the Unix epoch. Only set if requested.
+    #[prost(int64, optional, tag = "5")]
+    pub split_publish_timestamp: ::core::option::Option<i64>,
 }
 /// A partial hit, is a hit for which we have not fetch the content yet.
 /// Instead, it holds a document_uri which is enough information to
@@ -523,6 +573,86 @@ pub struct LeafListTermsResponse {
 #[derive(Serialize, Deserialize, utoipa::ToSchema)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListFacetsRequest {
+    /// Index ID patterns
+    #[prost(string, repeated, tag = "1")]
+    pub index_id_patterns: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Field to enumerate the values of. The field must be an indexed text field,
+    /// typically using the `raw` tokenizer.
+    #[prost(string, tag = "2")]
+    pub field: ::prost::alloc::string::String,
+    /// Only enumerates the values starting with this prefix.
+    #[prost(string, optional, tag = "3")]
+    pub prefix: ::core::option::Option<::prost::alloc::string::String>,
+    /// Maximum number of values to return.
+    #[prost(uint64, tag = "4")]
+    pub max_facets: u64,
+}
+#[derive(Serialize, Deserialize, utoipa::ToSchema)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FacetCount {
+    /// Distinct value of the field.
+    #[prost(string, tag = "1")]
+    pub value: ::prost::alloc::string::String,
+    /// Number of documents containing the value.
+    #[prost(uint64, tag = "2")]
+    pub num_docs: u64,
+}
+#[derive(Serialize, Deserialize, utoipa::ToSchema)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListFacetsResponse {
+    /// Most frequent values, sorted by decreasing number of documents and then by value.
+    #[prost(message, repeated, tag = "1")]
+    pub facets: ::prost::alloc::vec::Vec<FacetCount>,
+    /// Whether some segments held more distinct values than the leaves read. When set,
+    /// the values and counts are computed over a subset of the values and may be inexact.
+    #[prost(bool, tag = "2")]
+    pub truncated: bool,
+    /// Elapsed time to perform the request. This time is measured
+    /// server-side and expressed in microseconds.
+    #[prost(uint64, tag = "3")]
+    pub elapsed_time_micros: u64,
+}
+#[derive(Serialize, Deserialize, utoipa::ToSchema)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LeafListFacetsRequest {
+    /// List facets request. This is a perfect copy of the original list facets request.
+    #[prost(message, optional, tag = "1")]
+    pub list_facets_request: ::core::option::Option<ListFacetsRequest>,
+    /// Index split ids to apply the listing on.
+    /// This ids are resolved from the index_uri defined in the request.
+    #[prost(message, repeated, tag = "2")]
+    pub split_offsets: ::prost::alloc::vec::Vec<SplitIdAndFooterOffsets>,
+    /// Index URI. The index URI defines the location of the storage that contains the
+    /// split files.
+    #[prost(string, tag = "3")]
+    pub index_uri: ::prost::alloc::string::String,
+}
+#[derive(Serialize, Deserialize, utoipa::ToSchema)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LeafListFacetsResponse {
+    /// Counts of all the values read by the leaf. They are not truncated to `max_facets`
+    /// so that the root can merge exact counts.
+    #[prost(message, repeated, tag = "1")]
+    pub facets: ::prost::alloc::vec::Vec<FacetCount>,
+    /// Whether some segments held more distinct values than the leaf read.
+    #[prost(bool, tag = "2")]
+    pub truncated: bool,
+    /// The list of splits that failed.
+    #[prost(message, repeated, tag = "3")]
+    pub failed_splits: ::prost::alloc::vec::Vec<SplitSearchError>,
+    /// Total number of splits the leaf(s) were in charge of.
+    /// num_attempted_splits = num_successful_splits + num_failed_splits.
+    #[prost(uint64, tag = "4")]
+    pub num_attempted_splits: u64,
+}
+#[derive(Serialize, Deserialize, utoipa::ToSchema)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SearchStreamRequest {
     /// Index ID
     #[prost(string, tag = "1")]
@@ -666,6 +796,36 @@ impl CountHits {
 #[serde(rename_all = "snake_case")]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
+pub enum HitsRelation {
+    /// The number of hits is exact.
+    Equal = 0,
+    /// The number of hits is a lower bound of the actual number of hits.
+    GreaterThanOrEqual = 1,
+}
+impl HitsRelation {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            HitsRelation::Equal => "EQUAL",
+            HitsRelation::GreaterThanOrEqual => "GREATER_THAN_OR_EQUAL",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "EQUAL" => Some(Self::Equal),
+            "GREATER_THAN_OR_EQUAL" => Some(Self::GreaterThanOrEqual),
+            _ => None,
+        }
+    }
+}
+#[derive(Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
 pub enum SortOrder {
     /// Ascending order.
     Asc = 0,
@@ -1023,6 +1183,66 @@ pub mod search_service_client {
                 );
             self.inner.unary(req, path, codec).await
         }
+        /// Root list facets API.
+        /// This RPC identifies the set of splits on which the listing should run on,
+        /// and dispatches the several calls to `LeafListFacets`.
+        ///
+        /// It is also in charge of merging back the facet counts.
+        pub async fn root_list_facets(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListFacetsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListFacetsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/quickwit.search.SearchService/RootListFacets",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("quickwit.search.SearchService", "RootListFacets"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// Performs a leaf list facets on a given set of splits.
+        pub async fn leaf_list_facets(
+            &mut self,
+            request: impl tonic::IntoRequest<super::LeafListFacetsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::LeafListFacetsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/quickwit.search.SearchService/LeafListFacets",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("quickwit.search.SearchService", "LeafListFacets"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
         /// Performs a scroll request.
         pub async fn scroll(
             &mut self,
@@ -1256,6 +1476,26 @@ pub mod search_service_server {
             tonic::Response<super::LeafListTermsResponse>,
             tonic::Status,
         >;
+        /// Root list facets API.
+        /// This RPC identifies the set of splits on which the listing should run on,
+        /// and dispatches the several calls to `LeafListFacets`.
+        ///
+        /// It is also in charge of merging back the facet counts.
+        async fn root_list_facets(
+            &self,
+            request: tonic::Request<super::ListFacetsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListFacetsResponse>,
+            tonic::Status,
+        >;
+        /// Performs a leaf list facets on a given set of splits.
+        async fn leaf_list_facets(
+            &self,
+            request: tonic::Request<super::LeafListFacetsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::LeafListFacetsResponse>,
+            tonic::Status,
+        >;
         /// Performs a scroll request.
         async fn scroll(
             &self,
@@ -1646,6 +1886,98 @@ pub mod search_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/quickwit.search.SearchService/RootListFacets" => {
+                    #[allow(non_camel_case_types)]
+                    struct RootListFacetsSvc<T: SearchService>(pub Arc<T>);
+                    impl<
+                        T: SearchService,
+                    > tonic::server::UnaryService<super::ListFacetsRequest>
+                    for RootListFacetsSvc<T> {
+                        type Response = super::ListFacetsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListFacetsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                (*inner).root_list_facets(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = RootListFacetsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/quickwit.search.SearchService/LeafListFacets" => {
+                    #[allow(non_camel_case_types)]
+                    struct LeafListFacetsSvc<T: SearchService>(pub Arc<T>);
+                    impl<
+                        T: SearchService,
+                    > tonic::server::UnaryService<super::LeafListFacetsRequest>
+                    for LeafListFacetsSvc<T> {
+                        type Response = super::LeafListFacetsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::LeafListFacetsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                (*inner).leaf_list_facets(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = LeafListFacetsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/quickwit.search.SearchService/Scroll" => {
                     #[allow(non_camel_case_types)]
                     struct ScrollSvc<T: SearchService>(pub Arc<T>);
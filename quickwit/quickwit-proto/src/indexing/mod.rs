@@ -49,6 +49,11 @@ pub enum IndexingError {
         source_id: SourceId,
         pipeline_uid: PipelineUid,
     },
+    #[error(
+        "the node is already running the maximum number of indexing pipelines \
+         ({max_concurrent_pipelines})"
+    )]
+    TooManyPipelines { max_concurrent_pipelines: usize },
     #[error("I/O error `{0}`")]
     Io(io::Error),
     #[error("invalid params `{0}`")]
@@ -83,6 +88,9 @@ impl From<IndexingError> for tonic::Status {
             } => tonic::Status::already_exists(format!(
                 "pipeline {index_id}/{source_id} {pipeline_uid} already exists "
             )),
+            IndexingError::TooManyPipelines { .. } => {
+                tonic::Status::resource_exhausted(error.to_string())
+            }
             IndexingError::Io(error) => tonic::Status::internal(error.to_string()),
             IndexingError::InvalidParams(error) => {
                 tonic::Status::invalid_argument(error.to_string())
@@ -126,6 +134,7 @@ impl ServiceError for IndexingError {
             Self::MissingPipeline { .. } => ServiceErrorCode::NotFound,
             Self::MissingMergePipeline { .. } => ServiceErrorCode::NotFound,
             Self::PipelineAlreadyExists { .. } => ServiceErrorCode::BadRequest,
+            Self::TooManyPipelines { .. } => ServiceErrorCode::RateLimited,
             Self::InvalidParams(_) => ServiceErrorCode::BadRequest,
             Self::SpawnPipelinesError { .. } => ServiceErrorCode::Internal,
             Self::Io(_) => ServiceErrorCode::Internal,
@@ -249,6 +249,20 @@ impl ReplicateSuccess {
     }
 }
 
+impl ShardReplicationPosition {
+    pub fn shard_id(&self) -> &ShardId {
+        self.shard_id
+            .as_ref()
+            .expect("`shard_id` should be a required field")
+    }
+
+    pub fn replication_position_inclusive(&self) -> &Position {
+        self.replication_position_inclusive
+            .as_ref()
+            .expect("`replication_position_inclusive` should be a required field")
+    }
+}
+
 impl ReplicateFailure {
     pub fn shard_id(&self) -> &ShardId {
         self.shard_id
@@ -32,7 +32,8 @@ use tracing::Span;
 use tracing_opentelemetry::OpenTelemetrySpanExt;
 
 pub mod control_plane;
-pub use {bytes, tonic};
+pub use bytes;
+pub use tonic;
 pub mod error;
 pub mod indexing;
 pub mod ingest;
@@ -42,6 +43,16 @@ pub mod types;
 
 pub use error::{ServiceError, ServiceErrorCode};
 
+/// Encoded file descriptor sets of the control plane, ingest, metastore, and search gRPC services,
+/// used to serve gRPC reflection.
+pub const QUICKWIT_FILE_DESCRIPTOR_SETS: [&[u8]; 5] = [
+    tonic::include_file_descriptor_set!("control_plane_descriptor_set"),
+    tonic::include_file_descriptor_set!("ingester_descriptor_set"),
+    tonic::include_file_descriptor_set!("metastore_descriptor_set"),
+    tonic::include_file_descriptor_set!("router_descriptor_set"),
+    tonic::include_file_descriptor_set!("search_descriptor_set"),
+];
+
 use crate::search::ReportSplitsRequest;
 
 pub mod jaeger {
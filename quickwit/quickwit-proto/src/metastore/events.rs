@@ -22,8 +22,9 @@
 use quickwit_common::pubsub::Event;
 
 use super::{
-    AddSourceRequest, CreateIndexRequest, DeleteIndexRequest, DeleteSourceRequest, SourceType,
-    ToggleSourceRequest,
+    AddSourceRequest, CreateIndexRequest, DeleteIndexRequest, DeleteSourceRequest,
+    DeleteSplitsRequest, MarkSplitsForDeletionRequest, PublishSplitsRequest, SourceType,
+    StageSplitsRequest, ToggleSourceRequest,
 };
 use crate::types::{IndexUid, SourceId};
 
@@ -70,3 +71,7 @@ impl Event for CreateIndexRequest {}
 impl Event for DeleteIndexRequest {}
 impl Event for DeleteSourceRequest {}
 impl Event for ToggleSourceRequest {}
+impl Event for StageSplitsRequest {}
+impl Event for PublishSplitsRequest {}
+impl Event for MarkSplitsForDeletionRequest {}
+impl Event for DeleteSplitsRequest {}
@@ -51,6 +51,16 @@ pub enum EntityKind {
         /// Index IDs.
         index_ids: Vec<IndexId>,
     },
+    /// An index alias.
+    IndexAlias {
+        /// Index alias ID.
+        alias_id: String,
+    },
+    /// An index template.
+    IndexTemplate {
+        /// Index template ID.
+        template_id: String,
+    },
     /// A source.
     Source {
         /// Index ID.
@@ -84,6 +94,10 @@ impl fmt::Display for EntityKind {
             } => write!(f, "checkpoint delta `{index_id}/{source_id}`"),
             EntityKind::Index { index_id } => write!(f, "index `{}`", index_id),
             EntityKind::Indexes { index_ids } => write!(f, "indexes `{}`", index_ids.join(", ")),
+            EntityKind::IndexAlias { alias_id } => write!(f, "index alias `{alias_id}`"),
+            EntityKind::IndexTemplate { template_id } => {
+                write!(f, "index template `{template_id}`")
+            }
             EntityKind::Shard { queue_id } => write!(f, "shard `{queue_id}`"),
             EntityKind::Source {
                 index_id,
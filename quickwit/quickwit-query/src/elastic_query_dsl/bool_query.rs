@@ -26,7 +26,6 @@ use crate::not_nan_f32::NotNaNf32;
 use crate::query_ast::{self, QueryAst};
 
 /// # Unsupported features
-/// - minimum_should_match
 /// - named queries
 #[serde_as]
 #[derive(Deserialize, Debug, PartialEq, Eq, Clone)]
@@ -46,6 +45,79 @@ pub struct BoolQuery {
     filter: Vec<ElasticQueryDslInner>,
     #[serde(default)]
     pub boost: Option<NotNaNf32>,
+    #[serde(default)]
+    minimum_should_match: Option<MinimumShouldMatch>,
+}
+
+/// Elasticsearch's `minimum_should_match` parameter.
+///
+/// Both the absolute (`2`, `-1`) and the percentage (`"50%"`, `"-25%"`) forms are
+/// supported. Combinations (`"3<90%"`) are not.
+#[derive(Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(try_from = "MinimumShouldMatchForSerialization")]
+enum MinimumShouldMatch {
+    Absolute(i64),
+    Percentage(i64),
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum MinimumShouldMatchForSerialization {
+    Int(i64),
+    Str(String),
+}
+
+impl TryFrom<MinimumShouldMatchForSerialization> for MinimumShouldMatch {
+    type Error = String;
+
+    fn try_from(value: MinimumShouldMatchForSerialization) -> Result<Self, Self::Error> {
+        let value_str = match value {
+            MinimumShouldMatchForSerialization::Int(absolute) => {
+                return Ok(MinimumShouldMatch::Absolute(absolute));
+            }
+            MinimumShouldMatchForSerialization::Str(value_str) => value_str,
+        };
+        let value_str = value_str.trim();
+        if let Some(percentage_str) = value_str.strip_suffix('%') {
+            let percentage: i64 = percentage_str
+                .trim()
+                .parse()
+                .map_err(|_| format!("invalid `minimum_should_match` percentage: `{value_str}`"))?;
+            if !(-100..=100).contains(&percentage) {
+                return Err(format!(
+                    "`minimum_should_match` percentage must be between -100% and 100%, got \
+                     `{value_str}`"
+                ));
+            }
+            return Ok(MinimumShouldMatch::Percentage(percentage));
+        }
+        let absolute: i64 = value_str
+            .parse()
+            .map_err(|_| format!("invalid `minimum_should_match` value: `{value_str}`"))?;
+        Ok(MinimumShouldMatch::Absolute(absolute))
+    }
+}
+
+impl MinimumShouldMatch {
+    /// Resolves the number of should clauses that must match, given the total number
+    /// of should clauses.
+    ///
+    /// Negative values express the number (or percentage) of should clauses that are
+    /// allowed to be missing. Percentages are rounded down.
+    fn resolve(self, num_should_clauses: usize) -> usize {
+        let num_should_clauses = num_should_clauses as i64;
+        let num_required = match self {
+            MinimumShouldMatch::Absolute(absolute) if absolute >= 0 => absolute,
+            MinimumShouldMatch::Absolute(absolute) => num_should_clauses + absolute,
+            MinimumShouldMatch::Percentage(percentage) if percentage >= 0 => {
+                num_should_clauses * percentage / 100
+            }
+            MinimumShouldMatch::Percentage(percentage) => {
+                num_should_clauses - num_should_clauses * -percentage / 100
+            }
+        };
+        num_required.max(0) as usize
+    }
 }
 
 impl BoolQuery {
@@ -57,6 +129,7 @@ impl BoolQuery {
             should: children,
             filter: Vec::new(),
             boost: None,
+            minimum_should_match: None,
         }
     }
 }
@@ -70,11 +143,15 @@ fn convert_vec(query_dsls: Vec<ElasticQueryDslInner>) -> anyhow::Result<Vec<Quer
 
 impl ConvertableToQueryAst for BoolQuery {
     fn convert_to_query_ast(self) -> anyhow::Result<QueryAst> {
+        let minimum_should_match = self
+            .minimum_should_match
+            .map(|minimum_should_match| minimum_should_match.resolve(self.should.len()));
         let bool_query_ast = query_ast::BoolQuery {
             must: convert_vec(self.must)?,
             must_not: convert_vec(self.must_not)?,
             should: convert_vec(self.should)?,
             filter: convert_vec(self.filter)?,
+            minimum_should_match,
         };
         Ok(bool_query_ast.into())
     }
@@ -88,8 +165,11 @@ impl From<BoolQuery> for ElasticQueryDslInner {
 
 #[cfg(test)]
 mod tests {
+    use super::MinimumShouldMatch;
     use crate::elastic_query_dsl::bool_query::BoolQuery;
     use crate::elastic_query_dsl::term_query::term_query_from_field_value;
+    use crate::elastic_query_dsl::ConvertableToQueryAst;
+    use crate::query_ast::QueryAst;
 
     #[test]
     fn test_dsl_bool_query_deserialize_simple() {
@@ -111,6 +191,7 @@ mod tests {
                 should: Vec::new(),
                 filter: Vec::new(),
                 boost: None,
+                minimum_should_match: None,
             }
         );
     }
@@ -130,6 +211,7 @@ mod tests {
                 should: Vec::new(),
                 filter: vec![term_query_from_field_value("product_id", "2").into(),],
                 boost: None,
+                minimum_should_match: None,
             }
         );
     }
@@ -152,7 +234,87 @@ mod tests {
                 should: Vec::new(),
                 filter: Vec::new(),
                 boost: None,
+                minimum_should_match: None,
             }
         );
     }
+
+    fn bool_query_with_three_shoulds(minimum_should_match_json: &str) -> BoolQuery {
+        let bool_query_json = format!(
+            r#"{{
+            "should": [
+                {{ "term": {{"product_id": {{"value": "1" }}}} }},
+                {{ "term": {{"product_id": {{"value": "2" }}}} }},
+                {{ "term": {{"product_id": {{"value": "3" }}}} }}
+            ],
+            "minimum_should_match": {minimum_should_match_json}
+        }}"#
+        );
+        serde_json::from_str(&bool_query_json).unwrap()
+    }
+
+    fn resolved_minimum_should_match(bool_query: BoolQuery) -> Option<usize> {
+        let QueryAst::Bool(bool_query_ast) = bool_query.convert_to_query_ast().unwrap() else {
+            panic!("expected a bool query");
+        };
+        assert_eq!(bool_query_ast.should.len(), 3);
+        bool_query_ast.minimum_should_match
+    }
+
+    #[test]
+    fn test_dsl_bool_query_minimum_should_match_absolute() {
+        let bool_query = bool_query_with_three_shoulds("2");
+        assert_eq!(
+            bool_query.minimum_should_match,
+            Some(MinimumShouldMatch::Absolute(2))
+        );
+        assert_eq!(resolved_minimum_should_match(bool_query), Some(2));
+
+        let bool_query = bool_query_with_three_shoulds(r#""2""#);
+        assert_eq!(resolved_minimum_should_match(bool_query), Some(2));
+
+        let bool_query = bool_query_with_three_shoulds("-1");
+        assert_eq!(resolved_minimum_should_match(bool_query), Some(2));
+
+        let bool_query = bool_query_with_three_shoulds("-5");
+        assert_eq!(resolved_minimum_should_match(bool_query), Some(0));
+    }
+
+    #[test]
+    fn test_dsl_bool_query_minimum_should_match_percentage() {
+        let bool_query = bool_query_with_three_shoulds(r#""50%""#);
+        assert_eq!(
+            bool_query.minimum_should_match,
+            Some(MinimumShouldMatch::Percentage(50))
+        );
+        // 3 * 50% = 1.5, rounded down.
+        assert_eq!(resolved_minimum_should_match(bool_query), Some(1));
+
+        let bool_query = bool_query_with_three_shoulds(r#""100%""#);
+        assert_eq!(resolved_minimum_should_match(bool_query), Some(3));
+
+        // 3 * 25% = 0.75 clauses allowed to be missing, rounded down.
+        let bool_query = bool_query_with_three_shoulds(r#""-25%""#);
+        assert_eq!(resolved_minimum_should_match(bool_query), Some(3));
+
+        let bool_query = bool_query_with_three_shoulds(r#""-50%""#);
+        assert_eq!(resolved_minimum_should_match(bool_query), Some(2));
+    }
+
+    #[test]
+    fn test_dsl_bool_query_minimum_should_match_invalid() {
+        for invalid_minimum_should_match in [r#""150%""#, r#""-101%""#, r#""abc%""#, r#""abc""#] {
+            let bool_query_json = format!(
+                r#"{{
+                "should": {{ "term": {{"product_id": {{"value": "1" }}}} }},
+                "minimum_should_match": {invalid_minimum_should_match}
+            }}"#
+            );
+            let error = serde_json::from_str::<BoolQuery>(&bool_query_json).unwrap_err();
+            assert!(
+                error.to_string().contains("minimum_should_match"),
+                "unexpected error: {error}"
+            );
+        }
+    }
 }
@@ -0,0 +1,106 @@
+// Copyright (C) 2024 Quickwit, Inc.
+//
+// Quickwit is offered under the AGPL v3.0 and as commercial software.
+// For commercial licensing, contact us at hello@quickwit.io.
+//
+// AGPL:
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as
+// published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use serde::Deserialize;
+
+use crate::elastic_query_dsl::{ConvertableToQueryAst, ElasticQueryDslInner};
+use crate::not_nan_f32::NotNaNf32;
+use crate::query_ast::{self, QueryAst};
+
+/// # Unsupported features
+/// - named queries
+#[derive(Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ConstantScoreQuery {
+    filter: Box<ElasticQueryDslInner>,
+    /// Score assigned to every matching document.
+    #[serde(default)]
+    boost: Option<NotNaNf32>,
+}
+
+impl ConvertableToQueryAst for ConstantScoreQuery {
+    fn convert_to_query_ast(self) -> anyhow::Result<QueryAst> {
+        let filter = self.filter.convert_to_query_ast()?;
+        Ok(query_ast::ConstantScoreQuery {
+            filter: Box::new(filter),
+            score: self.boost.unwrap_or(NotNaNf32::ONE),
+        }
+        .into())
+    }
+}
+
+impl From<ConstantScoreQuery> for ElasticQueryDslInner {
+    fn from(constant_score_query: ConstantScoreQuery) -> Self {
+        ElasticQueryDslInner::ConstantScore(constant_score_query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ConstantScoreQuery;
+    use crate::elastic_query_dsl::term_query::term_query_from_field_value;
+    use crate::elastic_query_dsl::ConvertableToQueryAst;
+    use crate::not_nan_f32::NotNaNf32;
+    use crate::query_ast::{self, QueryAst};
+
+    #[test]
+    fn test_dsl_constant_score_query_deserialize_simple() {
+        let constant_score_query_json = r#"{
+            "filter": { "term": {"user.id": {"value": "kimchy" }} },
+            "boost": 1.2
+        }"#;
+        let constant_score_query: ConstantScoreQuery =
+            serde_json::from_str(constant_score_query_json).unwrap();
+        assert_eq!(
+            &constant_score_query,
+            &ConstantScoreQuery {
+                filter: Box::new(term_query_from_field_value("user.id", "kimchy").into()),
+                boost: Some(NotNaNf32::try_from(1.2f32).unwrap()),
+            }
+        );
+        let QueryAst::ConstantScore(query_ast::ConstantScoreQuery { filter, score }) =
+            constant_score_query.convert_to_query_ast().unwrap()
+        else {
+            panic!("expected a constant_score query");
+        };
+        assert!(matches!(*filter, QueryAst::Term(_)));
+        assert_eq!(score, NotNaNf32::try_from(1.2f32).unwrap());
+    }
+
+    #[test]
+    fn test_dsl_constant_score_query_default_boost() {
+        let constant_score_query_json = r#"{
+            "filter": { "term": {"user.id": {"value": "kimchy" }} }
+        }"#;
+        let constant_score_query: ConstantScoreQuery =
+            serde_json::from_str(constant_score_query_json).unwrap();
+        let QueryAst::ConstantScore(query_ast::ConstantScoreQuery { score, .. }) =
+            constant_score_query.convert_to_query_ast().unwrap()
+        else {
+            panic!("expected a constant_score query");
+        };
+        assert_eq!(score, NotNaNf32::ONE);
+    }
+
+    #[test]
+    fn test_dsl_constant_score_query_requires_filter() {
+        let constant_score_query_json = r#"{ "boost": 1.2 }"#;
+        serde_json::from_str::<ConstantScoreQuery>(constant_score_query_json).unwrap_err();
+    }
+}
@@ -0,0 +1,133 @@
+// Copyright (C) 2024 Quickwit, Inc.
+//
+// Quickwit is offered under the AGPL v3.0 and as commercial software.
+// For commercial licensing, contact us at hello@quickwit.io.
+//
+// AGPL:
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as
+// published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use serde::Deserialize;
+
+use crate::elastic_query_dsl::{ConvertableToQueryAst, ElasticQueryDslInner};
+use crate::not_nan_f32::NotNaNf32;
+use crate::query_ast::{self, QueryAst};
+
+/// # Unsupported features
+/// - named queries
+#[derive(Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct DisMaxQuery {
+    queries: Vec<ElasticQueryDslInner>,
+    #[serde(default)]
+    tie_breaker: Option<NotNaNf32>,
+    #[serde(default)]
+    boost: Option<NotNaNf32>,
+}
+
+impl ConvertableToQueryAst for DisMaxQuery {
+    fn convert_to_query_ast(self) -> anyhow::Result<QueryAst> {
+        let tie_breaker = self.tie_breaker.unwrap_or(NotNaNf32::ZERO);
+        let tie_breaker_f32: f32 = tie_breaker.into();
+        if !(0.0..=1.0).contains(&tie_breaker_f32) {
+            anyhow::bail!("`tie_breaker` must be between 0 and 1, got {tie_breaker_f32}");
+        }
+        let disjuncts = self
+            .queries
+            .into_iter()
+            .map(|query| query.convert_to_query_ast())
+            .collect::<anyhow::Result<Vec<QueryAst>>>()?;
+        let dis_max_query_ast: QueryAst = query_ast::DisMaxQuery {
+            disjuncts,
+            tie_breaker,
+        }
+        .into();
+        Ok(dis_max_query_ast.boost(self.boost))
+    }
+}
+
+impl From<DisMaxQuery> for ElasticQueryDslInner {
+    fn from(dis_max_query: DisMaxQuery) -> Self {
+        ElasticQueryDslInner::DisMax(dis_max_query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DisMaxQuery;
+    use crate::elastic_query_dsl::term_query::term_query_from_field_value;
+    use crate::elastic_query_dsl::ConvertableToQueryAst;
+    use crate::not_nan_f32::NotNaNf32;
+    use crate::query_ast::{self, QueryAst};
+
+    #[test]
+    fn test_dsl_dis_max_query_deserialize_simple() {
+        let dis_max_query_json = r#"{
+            "queries": [
+                { "term": {"title": {"value": "quick" }} },
+                { "term": {"body": {"value": "quick" }} }
+            ],
+            "tie_breaker": 0.7
+        }"#;
+        let dis_max_query: DisMaxQuery = serde_json::from_str(dis_max_query_json).unwrap();
+        assert_eq!(
+            &dis_max_query,
+            &DisMaxQuery {
+                queries: vec![
+                    term_query_from_field_value("title", "quick").into(),
+                    term_query_from_field_value("body", "quick").into(),
+                ],
+                tie_breaker: Some(NotNaNf32::try_from(0.7f32).unwrap()),
+                boost: None,
+            }
+        );
+        let QueryAst::DisMax(dis_max_query_ast) = dis_max_query.convert_to_query_ast().unwrap()
+        else {
+            panic!("expected a dis_max query");
+        };
+        assert_eq!(dis_max_query_ast.disjuncts.len(), 2);
+        assert_eq!(
+            dis_max_query_ast.tie_breaker,
+            NotNaNf32::try_from(0.7f32).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_dsl_dis_max_query_default_tie_breaker_and_boost() {
+        let dis_max_query_json = r#"{
+            "queries": [{ "term": {"title": {"value": "quick" }} }],
+            "boost": 2.0
+        }"#;
+        let dis_max_query: DisMaxQuery = serde_json::from_str(dis_max_query_json).unwrap();
+        let QueryAst::Boost { underlying, boost } = dis_max_query.convert_to_query_ast().unwrap()
+        else {
+            panic!("expected a boost query");
+        };
+        assert_eq!(boost, NotNaNf32::try_from(2.0f32).unwrap());
+        let QueryAst::DisMax(query_ast::DisMaxQuery { tie_breaker, .. }) = *underlying else {
+            panic!("expected a dis_max query");
+        };
+        assert_eq!(tie_breaker, NotNaNf32::ZERO);
+    }
+
+    #[test]
+    fn test_dsl_dis_max_query_invalid_tie_breaker() {
+        let dis_max_query_json = r#"{
+            "queries": [{ "term": {"title": {"value": "quick" }} }],
+            "tie_breaker": 1.5
+        }"#;
+        let dis_max_query: DisMaxQuery = serde_json::from_str(dis_max_query_json).unwrap();
+        let error = dis_max_query.convert_to_query_ast().unwrap_err();
+        assert!(error.to_string().contains("tie_breaker"));
+    }
+}
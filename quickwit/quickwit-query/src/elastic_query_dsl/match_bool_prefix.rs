@@ -37,7 +37,7 @@ pub(crate) struct MatchBoolPrefixQuery {
 impl ConvertableToQueryAst for MatchBoolPrefixQuery {
     fn convert_to_query_ast(self) -> anyhow::Result<QueryAst> {
         let full_text_params = FullTextParams {
-            tokenizer: None,
+            tokenizer: self.params.analyzer,
             mode: crate::query_ast::FullTextMode::BoolPrefix {
                 operator: self.params.operator,
                 max_expansions: default_max_expansions(),
@@ -42,6 +42,9 @@ pub(crate) struct MatchQueryParams {
     pub(crate) operator: BooleanOperand,
     #[serde(default)]
     pub(crate) zero_terms_query: MatchAllOrNone,
+    // Name of the tokenizer to use at query time instead of the field's own tokenizer.
+    #[serde(default)]
+    pub(crate) analyzer: Option<String>,
     // Regardless of this option Quickwit behaves in elasticsearch definition of
     // lenient. We include this property here just to accept user queries containing
     // this option.
@@ -52,7 +55,7 @@ pub(crate) struct MatchQueryParams {
 impl ConvertableToQueryAst for MatchQuery {
     fn convert_to_query_ast(self) -> anyhow::Result<QueryAst> {
         let full_text_params = FullTextParams {
-            tokenizer: None,
+            tokenizer: self.params.analyzer,
             mode: self.params.operator.into(),
             zero_terms_query: self.params.zero_terms_query,
         };
@@ -88,6 +91,7 @@ impl From<String> for MatchQueryParams {
             query,
             zero_terms_query: Default::default(),
             operator: Default::default(),
+            analyzer: None,
             _lenient: false,
         }
     }
@@ -137,6 +141,7 @@ mod tests {
                 query: "hello".to_string(),
                 operator: BooleanOperand::And,
                 zero_terms_query: crate::MatchAllOrNone::MatchAll,
+                analyzer: None,
                 _lenient: false,
             },
         };
@@ -159,4 +164,77 @@ mod tests {
         );
         assert_eq!(params.zero_terms_query, MatchAllOrNone::MatchAll);
     }
+
+    #[test]
+    fn test_deserialize_match_query_analyzer() {
+        let match_query: MatchQuery =
+            serde_json::from_str(r#"{"my_field": {"query": "my_query", "analyzer": "raw"}}"#)
+                .unwrap();
+        assert_eq!(match_query.params.analyzer.as_deref(), Some("raw"));
+        let QueryAst::FullText(FullTextQuery { params, .. }) =
+            match_query.convert_to_query_ast().unwrap()
+        else {
+            panic!()
+        };
+        assert_eq!(params.tokenizer.as_deref(), Some("raw"));
+    }
+
+    #[test]
+    fn test_match_query_analyzer_override_on_stemmed_field() {
+        use tantivy::collector::Count;
+        use tantivy::schema::{Schema, TextFieldIndexing, TextOptions};
+        use tantivy::{doc, Index};
+
+        use crate::create_default_quickwit_tokenizer_manager;
+
+        let tokenizer_manager = create_default_quickwit_tokenizer_manager();
+        let mut schema_builder = Schema::builder();
+        let text_options = TextOptions::default()
+            .set_indexing_options(TextFieldIndexing::default().set_tokenizer("en_stem"));
+        let body = schema_builder.add_text_field("body", text_options);
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema.clone());
+        index.set_tokenizers(tokenizer_manager.tantivy_manager().clone());
+        let mut index_writer = index.writer_with_num_threads(1, 15_000_000).unwrap();
+        for text in ["running", "runs", "run"] {
+            index_writer.add_document(doc!(body => text)).unwrap();
+        }
+        index_writer.commit().unwrap();
+        let searcher = index.reader().unwrap().searcher();
+
+        let num_hits = |match_query_json: &str| -> usize {
+            let match_query: MatchQuery = serde_json::from_str(match_query_json).unwrap();
+            let tantivy_query = match_query
+                .convert_to_query_ast()
+                .unwrap()
+                .build_tantivy_query(&schema, &tokenizer_manager, &[], true)
+                .unwrap();
+            searcher.search(&tantivy_query, &Count).unwrap()
+        };
+        // With the field tokenizer, the query is stemmed too.
+        assert_eq!(num_hits(r#"{"body": "running"}"#), 3);
+        // With the raw analyzer, the query is looked up verbatim among the indexed terms.
+        assert_eq!(
+            num_hits(r#"{"body": {"query": "running", "analyzer": "raw"}}"#),
+            0
+        );
+        assert_eq!(
+            num_hits(r#"{"body": {"query": "run", "analyzer": "raw"}}"#),
+            3
+        );
+        assert_eq!(
+            num_hits(r#"{"body": {"query": "Run", "analyzer": "raw"}}"#),
+            0
+        );
+
+        let match_query: MatchQuery =
+            serde_json::from_str(r#"{"body": {"query": "running", "analyzer": "does_not_exist"}}"#)
+                .unwrap();
+        let error = match_query
+            .convert_to_query_ast()
+            .unwrap()
+            .build_tantivy_query(&schema, &tokenizer_manager, &[], true)
+            .unwrap_err();
+        assert!(error.to_string().contains("does_not_exist"));
+    }
 }
@@ -20,6 +20,8 @@
 use serde::{Deserialize, Serialize};
 
 mod bool_query;
+mod constant_score_query;
+mod dis_max_query;
 mod exists_query;
 mod match_bool_prefix;
 mod match_phrase_query;
@@ -41,6 +43,8 @@ use range_query::RangeQuery;
 pub(crate) use string_or_struct::StringOrStructForSerialization;
 use term_query::TermQuery;
 
+use crate::elastic_query_dsl::constant_score_query::ConstantScoreQuery;
+use crate::elastic_query_dsl::dis_max_query::DisMaxQuery;
 use crate::elastic_query_dsl::exists_query::ExistsQuery;
 use crate::elastic_query_dsl::match_bool_prefix::MatchBoolPrefixQuery;
 use crate::elastic_query_dsl::match_phrase_query::MatchPhraseQuery;
@@ -68,6 +72,8 @@ pub(crate) struct MatchNoneQuery;
 pub(crate) enum ElasticQueryDslInner {
     QueryString(QueryStringQuery),
     Bool(BoolQuery),
+    DisMax(DisMaxQuery),
+    ConstantScore(ConstantScoreQuery),
     Term(TermQuery),
     Terms(TermsQuery),
     MatchAll(MatchAllQuery),
@@ -102,6 +108,10 @@ impl ConvertableToQueryAst for ElasticQueryDslInner {
         match self {
             Self::QueryString(query_string_query) => query_string_query.convert_to_query_ast(),
             Self::Bool(bool_query) => bool_query.convert_to_query_ast(),
+            Self::DisMax(dis_max_query) => dis_max_query.convert_to_query_ast(),
+            Self::ConstantScore(constant_score_query) => {
+                constant_score_query.convert_to_query_ast()
+            }
             Self::Term(term_query) => term_query.convert_to_query_ast(),
             Self::Terms(terms_query) => terms_query.convert_to_query_ast(),
             Self::MatchAll(match_all_query) => {
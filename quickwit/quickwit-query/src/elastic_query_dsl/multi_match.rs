@@ -185,6 +185,7 @@ mod tests {
                         query: "quick brown fox".to_string(),
                         operator: crate::BooleanOperand::Or,
                         zero_terms_query: Default::default(),
+                        analyzer: None,
                         _lenient: false,
                     },
                 }
@@ -195,6 +196,7 @@ mod tests {
                         query: "quick brown fox".to_string(),
                         operator: crate::BooleanOperand::Or,
                         zero_terms_query: Default::default(),
+                        analyzer: None,
                         _lenient: false,
                     },
                 }
@@ -20,13 +20,13 @@
 use serde::{Deserialize, Serialize};
 use tantivy::schema::Schema as TantivySchema;
 
+use super::tantivy_query_ast::TantivyBoolQuery;
 use super::{BuildTantivyAst, TantivyQueryAst};
 use crate::query_ast::QueryAst;
 use crate::tokenizers::TokenizerManager;
 use crate::InvalidQuery;
 
 /// # Unsupported features
-/// - minimum_should_match
 /// - named queries
 ///
 /// Edge cases of BooleanQuery are not obvious,
@@ -48,6 +48,12 @@ pub struct BoolQuery {
     pub should: Vec<QueryAst>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub filter: Vec<QueryAst>,
+    /// Minimum number of `should` clauses a document has to match.
+    ///
+    /// If `None`, we follow the usual boolean query semantics: should clauses are
+    /// only required if there are no must or filter clauses.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub minimum_should_match: Option<usize>,
 }
 
 impl From<BoolQuery> for QueryAst {
@@ -64,7 +70,7 @@ impl BuildTantivyAst for BoolQuery {
         search_fields: &[String],
         with_validation: bool,
     ) -> Result<TantivyQueryAst, InvalidQuery> {
-        let mut boolean_query = super::tantivy_query_ast::TantivyBoolQuery::default();
+        let mut boolean_query = TantivyBoolQuery::default();
         for must in &self.must {
             let must_leaf = must.build_tantivy_ast_call(
                 schema,
@@ -101,6 +107,138 @@ impl BuildTantivyAst for BoolQuery {
             )?;
             boolean_query.filter.push(filter_leaf);
         }
+        // A minimum of zero should clauses follows the usual boolean query semantics.
+        if let Some(minimum_should_match) = self.minimum_should_match.filter(|&min| min > 0) {
+            if minimum_should_match > self.should.len() {
+                return Ok(TantivyQueryAst::match_none());
+            }
+            boolean_query.minimum_should_match = Some(minimum_should_match);
+        }
         Ok(TantivyQueryAst::Bool(boolean_query))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use tantivy::schema::{Schema, TEXT};
+
+    use super::BoolQuery;
+    use crate::query_ast::{BuildTantivyAst, QueryAst, TermQuery};
+    use crate::{create_default_quickwit_tokenizer_manager, MatchAllOrNone};
+
+    fn three_should_bool_query(minimum_should_match: Option<usize>) -> BoolQuery {
+        let should = ["a", "b", "c"]
+            .into_iter()
+            .map(|value| {
+                QueryAst::from(TermQuery {
+                    field: "body".to_string(),
+                    value: value.to_string(),
+                })
+            })
+            .collect();
+        BoolQuery {
+            should,
+            minimum_should_match,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_bool_query_minimum_should_match_build_tantivy_ast() {
+        let mut schema_builder = Schema::builder();
+        schema_builder.add_text_field("body", TEXT);
+        let schema = schema_builder.build();
+        let tokenizer_manager = create_default_quickwit_tokenizer_manager();
+        {
+            let tantivy_ast = three_should_bool_query(Some(2))
+                .build_tantivy_ast_call(&schema, &tokenizer_manager, &[], true)
+                .unwrap();
+            let bool_query = tantivy_ast.as_bool_query().unwrap();
+            assert_eq!(bool_query.should.len(), 3);
+            assert!(bool_query.filter.is_empty());
+            assert_eq!(bool_query.minimum_should_match, Some(2));
+        }
+        {
+            let tantivy_ast = three_should_bool_query(Some(4))
+                .build_tantivy_ast_call(&schema, &tokenizer_manager, &[], true)
+                .unwrap()
+                .simplify();
+            assert_eq!(
+                tantivy_ast.const_predicate(),
+                Some(MatchAllOrNone::MatchNone)
+            );
+        }
+        {
+            let tantivy_ast = three_should_bool_query(Some(0))
+                .build_tantivy_ast_call(&schema, &tokenizer_manager, &[], true)
+                .unwrap();
+            let bool_query = tantivy_ast.as_bool_query().unwrap();
+            assert_eq!(bool_query.should.len(), 3);
+            assert!(bool_query.filter.is_empty());
+            assert_eq!(bool_query.minimum_should_match, None);
+        }
+    }
+
+    #[test]
+    fn test_bool_query_minimum_should_match_matches() {
+        use tantivy::collector::DocSetCollector;
+        use tantivy::{doc, Index};
+
+        let mut schema_builder = Schema::builder();
+        let body = schema_builder.add_text_field("body", TEXT);
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema.clone());
+        let mut index_writer = index.writer_with_num_threads(1, 15_000_000).unwrap();
+        for text in ["a", "a b", "b c", "a b c", "d"] {
+            index_writer.add_document(doc!(body => text)).unwrap();
+        }
+        index_writer.commit().unwrap();
+        let searcher = index.reader().unwrap().searcher();
+        let tokenizer_manager = create_default_quickwit_tokenizer_manager();
+        let num_matches = |minimum_should_match: Option<usize>| {
+            let tantivy_query: Box<dyn tantivy::query::Query> =
+                three_should_bool_query(minimum_should_match)
+                    .build_tantivy_ast_call(&schema, &tokenizer_manager, &[], true)
+                    .unwrap()
+                    .simplify()
+                    .into();
+            searcher
+                .search(&tantivy_query, &DocSetCollector)
+                .unwrap()
+                .len()
+        };
+        assert_eq!(num_matches(None), 4);
+        assert_eq!(num_matches(Some(1)), 4);
+        assert_eq!(num_matches(Some(2)), 3);
+        assert_eq!(num_matches(Some(3)), 1);
+        assert_eq!(num_matches(Some(4)), 0);
+    }
+
+    #[test]
+    fn test_bool_query_minimum_should_match_many_clauses() {
+        let mut schema_builder = Schema::builder();
+        schema_builder.add_text_field("body", TEXT);
+        let schema = schema_builder.build();
+        let tokenizer_manager = create_default_quickwit_tokenizer_manager();
+        let should = (0..50)
+            .map(|i| {
+                QueryAst::from(TermQuery {
+                    field: "body".to_string(),
+                    value: format!("term{i}"),
+                })
+            })
+            .collect();
+        let bool_query = BoolQuery {
+            should,
+            minimum_should_match: Some(25),
+            ..Default::default()
+        };
+        let tantivy_ast = bool_query
+            .build_tantivy_ast_call(&schema, &tokenizer_manager, &[], true)
+            .unwrap()
+            .simplify();
+        let bool_query = tantivy_ast.as_bool_query().unwrap();
+        assert_eq!(bool_query.should.len(), 50);
+        assert_eq!(bool_query.minimum_should_match, Some(25));
+    }
+}
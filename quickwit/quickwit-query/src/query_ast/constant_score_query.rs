@@ -0,0 +1,138 @@
+// Copyright (C) 2024 Quickwit, Inc.
+//
+// Quickwit is offered under the AGPL v3.0 and as commercial software.
+// For commercial licensing, contact us at hello@quickwit.io.
+//
+// AGPL:
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as
+// published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+use tantivy::query::ConstScoreQuery as TantivyConstScoreQuery;
+use tantivy::schema::Schema as TantivySchema;
+
+use crate::not_nan_f32::NotNaNf32;
+use crate::query_ast::tantivy_query_ast::TantivyQueryAst;
+use crate::query_ast::{BuildTantivyAst, QueryAst};
+use crate::tokenizers::TokenizerManager;
+use crate::{InvalidQuery, MatchAllOrNone};
+
+/// Matches the documents matched by `filter`, all with the same `score`.
+///
+/// The scores of the filter clauses are never computed.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ConstantScoreQuery {
+    pub filter: Box<QueryAst>,
+    #[serde(default = "default_score")]
+    pub score: NotNaNf32,
+}
+
+fn default_score() -> NotNaNf32 {
+    NotNaNf32::ONE
+}
+
+impl From<ConstantScoreQuery> for QueryAst {
+    fn from(constant_score_query: ConstantScoreQuery) -> Self {
+        QueryAst::ConstantScore(constant_score_query)
+    }
+}
+
+impl BuildTantivyAst for ConstantScoreQuery {
+    fn build_tantivy_ast_impl(
+        &self,
+        schema: &TantivySchema,
+        tokenizer_manager: &TokenizerManager,
+        search_fields: &[String],
+        with_validation: bool,
+    ) -> Result<TantivyQueryAst, InvalidQuery> {
+        let filter_ast = self
+            .filter
+            .build_tantivy_ast_call(schema, tokenizer_manager, search_fields, with_validation)?
+            .simplify();
+        if filter_ast.const_predicate() == Some(MatchAllOrNone::MatchNone) {
+            return Ok(TantivyQueryAst::match_none());
+        }
+        let constant_score_query =
+            TantivyConstScoreQuery::new(filter_ast.into(), self.score.into());
+        Ok(constant_score_query.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tantivy::collector::TopDocs;
+    use tantivy::schema::{Schema, TEXT};
+    use tantivy::{doc, Index};
+
+    use super::ConstantScoreQuery;
+    use crate::create_default_quickwit_tokenizer_manager;
+    use crate::not_nan_f32::NotNaNf32;
+    use crate::query_ast::{QueryAst, TermQuery};
+
+    #[test]
+    fn test_constant_score_query_scores() {
+        let mut schema_builder = Schema::builder();
+        let body = schema_builder.add_text_field("body", TEXT);
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema.clone());
+        let mut index_writer = index.writer_with_num_threads(1, 15_000_000).unwrap();
+        for text in ["a", "a a a a", "a b c d e f", "b"] {
+            index_writer.add_document(doc!(body => text)).unwrap();
+        }
+        index_writer.commit().unwrap();
+        let searcher = index.reader().unwrap().searcher();
+        let tokenizer_manager = create_default_quickwit_tokenizer_manager();
+        let term_query_ast: QueryAst = TermQuery {
+            field: "body".to_string(),
+            value: "a".to_string(),
+        }
+        .into();
+
+        // Sanity check: term scoring does depend on the term frequency and field norm.
+        let term_query = term_query_ast
+            .build_tantivy_query(&schema, &tokenizer_manager, &[], true)
+            .unwrap();
+        let term_scores: Vec<f32> = searcher
+            .search(&term_query, &TopDocs::with_limit(10))
+            .unwrap()
+            .into_iter()
+            .map(|(score, _)| score)
+            .collect();
+        assert_eq!(term_scores.len(), 3);
+        assert!(term_scores[0] > term_scores[2]);
+
+        let constant_score_query_ast: QueryAst = ConstantScoreQuery {
+            filter: Box::new(term_query_ast),
+            score: NotNaNf32::try_from(1.5f32).unwrap(),
+        }
+        .into();
+        let constant_score_query = constant_score_query_ast
+            .build_tantivy_query(&schema, &tokenizer_manager, &[], true)
+            .unwrap();
+        let constant_scores: Vec<f32> = searcher
+            .search(&constant_score_query, &TopDocs::with_limit(10))
+            .unwrap()
+            .into_iter()
+            .map(|(score, _)| score)
+            .collect();
+        assert_eq!(constant_scores, vec![1.5f32; 3]);
+    }
+
+    #[test]
+    fn test_constant_score_query_default_score() {
+        let constant_score_query: ConstantScoreQuery =
+            serde_json::from_str(r#"{"filter": {"type": "match_all"}}"#).unwrap();
+        assert_eq!(constant_score_query.score, NotNaNf32::ONE);
+        assert_eq!(*constant_score_query.filter, QueryAst::MatchAll);
+    }
+}
@@ -0,0 +1,173 @@
+// Copyright (C) 2024 Quickwit, Inc.
+//
+// Quickwit is offered under the AGPL v3.0 and as commercial software.
+// For commercial licensing, contact us at hello@quickwit.io.
+//
+// AGPL:
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as
+// published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+use tantivy::query::DisjunctionMaxQuery as TantivyDisjunctionMaxQuery;
+use tantivy::schema::Schema as TantivySchema;
+
+use crate::not_nan_f32::NotNaNf32;
+use crate::query_ast::tantivy_query_ast::TantivyQueryAst;
+use crate::query_ast::{BuildTantivyAst, QueryAst};
+use crate::tokenizers::TokenizerManager;
+use crate::InvalidQuery;
+
+/// Matches documents matching any of the disjuncts.
+///
+/// Contrary to a boolean union, the score of a document is the score of its best matching
+/// disjunct, plus `tie_breaker` times the scores of the other matching disjuncts.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DisMaxQuery {
+    pub disjuncts: Vec<QueryAst>,
+    #[serde(default = "default_tie_breaker")]
+    pub tie_breaker: NotNaNf32,
+}
+
+fn default_tie_breaker() -> NotNaNf32 {
+    NotNaNf32::ZERO
+}
+
+impl From<DisMaxQuery> for QueryAst {
+    fn from(dis_max_query: DisMaxQuery) -> Self {
+        QueryAst::DisMax(dis_max_query)
+    }
+}
+
+impl BuildTantivyAst for DisMaxQuery {
+    fn build_tantivy_ast_impl(
+        &self,
+        schema: &TantivySchema,
+        tokenizer_manager: &TokenizerManager,
+        search_fields: &[String],
+        with_validation: bool,
+    ) -> Result<TantivyQueryAst, InvalidQuery> {
+        if self.disjuncts.is_empty() {
+            return Ok(TantivyQueryAst::match_none());
+        }
+        let mut disjuncts = Vec::with_capacity(self.disjuncts.len());
+        for disjunct in &self.disjuncts {
+            let disjunct_ast = disjunct.build_tantivy_ast_call(
+                schema,
+                tokenizer_manager,
+                search_fields,
+                with_validation,
+            )?;
+            disjuncts.push(disjunct_ast.simplify().into());
+        }
+        let dis_max_query =
+            TantivyDisjunctionMaxQuery::with_tie_breaker(disjuncts, self.tie_breaker.into());
+        Ok(dis_max_query.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use tantivy::collector::TopDocs;
+    use tantivy::schema::{Schema, TEXT};
+    use tantivy::{doc, DocAddress, Index, Score};
+
+    use super::DisMaxQuery;
+    use crate::not_nan_f32::NotNaNf32;
+    use crate::query_ast::{BoolQuery, BuildTantivyAst, QueryAst, TermQuery};
+    use crate::{create_default_quickwit_tokenizer_manager, MatchAllOrNone};
+
+    fn term_query_ast(value: &str) -> QueryAst {
+        TermQuery {
+            field: "body".to_string(),
+            value: value.to_string(),
+        }
+        .into()
+    }
+
+    fn scores(index: &Index, query_ast: &QueryAst) -> HashMap<DocAddress, Score> {
+        let tokenizer_manager = create_default_quickwit_tokenizer_manager();
+        let tantivy_query = query_ast
+            .build_tantivy_query(&index.schema(), &tokenizer_manager, &[], true)
+            .unwrap();
+        let searcher = index.reader().unwrap().searcher();
+        searcher
+            .search(&tantivy_query, &TopDocs::with_limit(10))
+            .unwrap()
+            .into_iter()
+            .map(|(score, doc_address)| (doc_address, score))
+            .collect()
+    }
+
+    #[test]
+    fn test_dis_max_query_score() {
+        let mut schema_builder = Schema::builder();
+        let body = schema_builder.add_text_field("body", TEXT);
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema);
+        let mut index_writer = index.writer_with_num_threads(1, 15_000_000).unwrap();
+        for text in ["a b", "a", "b c", "c"] {
+            index_writer.add_document(doc!(body => text)).unwrap();
+        }
+        index_writer.commit().unwrap();
+
+        let both_doc = DocAddress::new(0, 0);
+        let score_a = scores(&index, &term_query_ast("a"))[&both_doc];
+        let score_b = scores(&index, &term_query_ast("b"))[&both_doc];
+        let bool_query_ast: QueryAst = BoolQuery {
+            should: vec![term_query_ast("a"), term_query_ast("b")],
+            ..Default::default()
+        }
+        .into();
+        let sum_score = scores(&index, &bool_query_ast)[&both_doc];
+        assert!((sum_score - (score_a + score_b)).abs() < 1e-5);
+
+        for tie_breaker in [0.0f32, 0.3f32, 1.0f32] {
+            let dis_max_query_ast: QueryAst = DisMaxQuery {
+                disjuncts: vec![term_query_ast("a"), term_query_ast("b")],
+                tie_breaker: NotNaNf32::try_from(tie_breaker).unwrap(),
+            }
+            .into();
+            let dis_max_scores = scores(&index, &dis_max_query_ast);
+            // Documents matching any of the disjuncts match.
+            assert_eq!(dis_max_scores.len(), 3);
+            let expected_score = score_a.max(score_b) + tie_breaker * score_a.min(score_b);
+            let dis_max_score = dis_max_scores[&both_doc];
+            assert!(
+                (dis_max_score - expected_score).abs() < 1e-5,
+                "tie_breaker={tie_breaker}: got {dis_max_score}, expected {expected_score}"
+            );
+            if tie_breaker < 1.0 {
+                assert!(dis_max_score < sum_score);
+            }
+        }
+    }
+
+    #[test]
+    fn test_dis_max_query_no_disjuncts() {
+        let dis_max_query = DisMaxQuery {
+            disjuncts: Vec::new(),
+            tie_breaker: NotNaNf32::ZERO,
+        };
+        let schema = Schema::builder().build();
+        let tokenizer_manager = create_default_quickwit_tokenizer_manager();
+        let tantivy_ast = dis_max_query
+            .build_tantivy_ast_call(&schema, &tokenizer_manager, &[], true)
+            .unwrap();
+        assert_eq!(
+            tantivy_ast.const_predicate(),
+            Some(MatchAllOrNone::MatchNone)
+        );
+    }
+}
@@ -24,6 +24,8 @@ use tantivy::schema::Schema as TantivySchema;
 use crate::tokenizers::TokenizerManager;
 
 mod bool_query;
+mod constant_score_query;
+mod dis_max_query;
 mod field_presence;
 mod full_text_query;
 mod phrase_prefix_query;
@@ -37,6 +39,8 @@ mod visitor;
 mod wildcard_query;
 
 pub use bool_query::BoolQuery;
+pub use constant_score_query::ConstantScoreQuery;
+pub use dis_max_query::DisMaxQuery;
 pub use field_presence::FieldPresenceQuery;
 pub use full_text_query::{FullTextMode, FullTextParams, FullTextQuery};
 pub use phrase_prefix_query::PhrasePrefixQuery;
@@ -55,6 +59,8 @@ use crate::{BooleanOperand, InvalidQuery, NotNaNf32};
 #[serde(rename_all = "snake_case")]
 pub enum QueryAst {
     Bool(BoolQuery),
+    DisMax(DisMaxQuery),
+    ConstantScore(ConstantScoreQuery),
     Term(TermQuery),
     TermSet(TermSetQuery),
     FieldPresence(FieldPresenceQuery),
@@ -82,6 +88,7 @@ impl QueryAst {
                 must_not,
                 should,
                 filter,
+                minimum_should_match,
             }) => {
                 let must = parse_user_query_in_asts(must, default_search_fields)?;
                 let must_not = parse_user_query_in_asts(must_not, default_search_fields)?;
@@ -92,6 +99,26 @@ impl QueryAst {
                     must_not,
                     should,
                     filter,
+                    minimum_should_match,
+                }
+                .into())
+            }
+            QueryAst::DisMax(DisMaxQuery {
+                disjuncts,
+                tie_breaker,
+            }) => {
+                let disjuncts = parse_user_query_in_asts(disjuncts, default_search_fields)?;
+                Ok(DisMaxQuery {
+                    disjuncts,
+                    tie_breaker,
+                }
+                .into())
+            }
+            QueryAst::ConstantScore(ConstantScoreQuery { filter, score }) => {
+                let filter = filter.parse_user_query(default_search_fields)?;
+                Ok(ConstantScoreQuery {
+                    filter: Box::new(filter),
+                    score,
                 }
                 .into())
             }
@@ -191,6 +218,14 @@ impl BuildTantivyAst for QueryAst {
                 search_fields,
                 with_validation,
             ),
+            QueryAst::DisMax(dis_max_query) => dis_max_query.build_tantivy_ast_call(
+                schema,
+                tokenizer_manager,
+                search_fields,
+                with_validation,
+            ),
+            QueryAst::ConstantScore(constant_score_query) => constant_score_query
+                .build_tantivy_ast_call(schema, tokenizer_manager, search_fields, with_validation),
             QueryAst::Term(term_query) => term_query.build_tantivy_ast_call(
                 schema,
                 tokenizer_manager,
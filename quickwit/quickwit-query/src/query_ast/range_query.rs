@@ -17,13 +17,14 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
+use std::net::{IpAddr, Ipv6Addr};
 use std::ops::Bound;
 
 use serde::{Deserialize, Serialize};
 use tantivy::query::{
     FastFieldRangeWeight as TantivyFastFieldRangeQuery, RangeQuery as TantivyRangeQuery,
 };
-use tantivy::schema::Schema as TantivySchema;
+use tantivy::schema::{IntoIpv6Addr, Schema as TantivySchema};
 use tantivy::DateTime;
 
 use super::QueryAst;
@@ -197,6 +198,57 @@ where
     Ok((lower_bound, upper_bound))
 }
 
+/// Parses an IP network in CIDR notation, such as `10.0.0.0/8` or `2001:db8::/32`, and returns
+/// its first and last addresses. IPv4 networks are mapped to the IPv6 address space.
+fn parse_cidr(text: &str) -> Option<(Ipv6Addr, Ipv6Addr)> {
+    let (ip_str, prefix_len_str) = text.split_once('/')?;
+    let ip_addr: IpAddr = ip_str.parse().ok()?;
+    let prefix_len: u32 = prefix_len_str.parse().ok()?;
+    let ipv6_prefix_len = match ip_addr {
+        IpAddr::V4(_) if prefix_len <= 32 => prefix_len + 96,
+        IpAddr::V6(_) if prefix_len <= 128 => prefix_len,
+        _ => return None,
+    };
+    let mask = u128::MAX.checked_shl(128 - ipv6_prefix_len).unwrap_or(0);
+    let first_addr = u128::from(ip_addr.into_ipv6_addr()) & mask;
+    let last_addr = first_addr | !mask;
+    Some((Ipv6Addr::from(first_addr), Ipv6Addr::from(last_addr)))
+}
+
+/// Converts a bound of an IP range into an IPv6 bound. The bound value can be an IP address or
+/// an IP network in CIDR notation. The network is then entirely included in the range if the
+/// bound is inclusive, and entirely excluded otherwise.
+fn convert_ip_bound(bound: &Bound<JsonLiteral>, is_lower_bound: bool) -> Option<Bound<Ipv6Addr>> {
+    if let Bound::Included(JsonLiteral::String(text)) | Bound::Excluded(JsonLiteral::String(text)) =
+        bound
+    {
+        if let Some((first_addr, last_addr)) = parse_cidr(text) {
+            let ip_bound = match (bound, is_lower_bound) {
+                (Bound::Included(_), true) => Bound::Included(first_addr),
+                (Bound::Included(_), false) => Bound::Included(last_addr),
+                (_, true) => Bound::Excluded(last_addr),
+                (_, false) => Bound::Excluded(first_addr),
+            };
+            return Some(ip_bound);
+        }
+    }
+    convert_bound(bound)
+}
+
+fn convert_ip_bounds(
+    lower_bound: &Bound<JsonLiteral>,
+    upper_bound: &Bound<JsonLiteral>,
+    field_name: &str,
+) -> Result<(Bound<Ipv6Addr>, Bound<Ipv6Addr>), InvalidQuery> {
+    let invalid_query = || InvalidQuery::InvalidBoundary {
+        expected_value_type: "IP address or CIDR network",
+        field_name: field_name.to_string(),
+    };
+    let lower_bound = convert_ip_bound(lower_bound, true).ok_or_else(invalid_query)?;
+    let upper_bound = convert_ip_bound(upper_bound, false).ok_or_else(invalid_query)?;
+    Ok((lower_bound, upper_bound))
+}
+
 /// Converts a given bound JsonLiteral bound into a bound of type T.
 impl From<RangeQuery> for QueryAst {
     fn from(range_query: RangeQuery) -> Self {
@@ -334,7 +386,7 @@ impl BuildTantivyAst for RangeQuery {
             }
             tantivy::schema::FieldType::IpAddr(_) => {
                 let (lower_bound, upper_bound) =
-                    convert_bounds(&self.lower_bound, &self.upper_bound, field_entry.name())?;
+                    convert_ip_bounds(&self.lower_bound, &self.upper_bound, field_entry.name())?;
                 TantivyRangeQuery::new_ip_bounds(self.field.clone(), lower_bound, upper_bound)
                     .into()
             }
@@ -352,12 +404,13 @@ fn map_bound<TFrom, TTo>(bound: &Bound<TFrom>, transform: impl Fn(&TFrom) -> TTo
 
 #[cfg(test)]
 mod tests {
+    use std::net::Ipv6Addr;
     use std::ops::Bound;
 
     use tantivy::schema::{Schema, FAST, STORED, TEXT};
     use tantivy::DateOptions;
 
-    use super::RangeQuery;
+    use super::{parse_cidr, RangeQuery};
     use crate::query_ast::tantivy_query_ast::TantivyBoolQuery;
     use crate::query_ast::BuildTantivyAst;
     use crate::{
@@ -375,6 +428,7 @@ mod tests {
             .set_precision(tantivy::DateTimePrecision::Milliseconds);
         schema_builder.add_date_field("my_date_field", date_options);
         schema_builder.add_u64_field("my_u64_not_fastfield", STORED);
+        schema_builder.add_ip_addr_field("my_ip_field", FAST);
         if dynamic_mode {
             schema_builder.add_json_field("_dynamic", TEXT | STORED | FAST);
         }
@@ -526,6 +580,7 @@ mod tests {
             must_not,
             should,
             filter,
+            ..
         } = tantivy_ast.as_bool_query().unwrap();
         assert!(must.is_empty());
         assert!(must_not.is_empty());
@@ -571,4 +626,108 @@ mod tests {
             .unwrap_err();
         assert!(matches!(err, InvalidQuery::SchemaError { .. }));
     }
+
+    #[test]
+    fn test_parse_cidr() {
+        assert_eq!(
+            parse_cidr("10.0.0.0/8").unwrap(),
+            (
+                "::ffff:10.0.0.0".parse::<Ipv6Addr>().unwrap(),
+                "::ffff:10.255.255.255".parse::<Ipv6Addr>().unwrap()
+            )
+        );
+        assert_eq!(
+            parse_cidr("192.168.1.17/32").unwrap(),
+            (
+                "::ffff:192.168.1.17".parse::<Ipv6Addr>().unwrap(),
+                "::ffff:192.168.1.17".parse::<Ipv6Addr>().unwrap()
+            )
+        );
+        assert_eq!(
+            parse_cidr("0.0.0.0/0").unwrap(),
+            (
+                "::ffff:0.0.0.0".parse::<Ipv6Addr>().unwrap(),
+                "::ffff:255.255.255.255".parse::<Ipv6Addr>().unwrap()
+            )
+        );
+        assert_eq!(
+            parse_cidr("2001:db8::1/32").unwrap(),
+            (
+                "2001:db8::".parse::<Ipv6Addr>().unwrap(),
+                "2001:db8:ffff:ffff:ffff:ffff:ffff:ffff"
+                    .parse::<Ipv6Addr>()
+                    .unwrap()
+            )
+        );
+        assert_eq!(
+            parse_cidr("::/0").unwrap(),
+            (Ipv6Addr::UNSPECIFIED, Ipv6Addr::from(u128::MAX))
+        );
+        assert!(parse_cidr("10.0.0.1").is_none());
+        assert!(parse_cidr("10.0.0.0/33").is_none());
+        assert!(parse_cidr("2001:db8::/129").is_none());
+        assert!(parse_cidr("foo/8").is_none());
+    }
+
+    #[test]
+    fn test_range_query_ip_cidr() {
+        let schema = make_schema(false);
+        let range_query = RangeQuery {
+            field: "my_ip_field".to_string(),
+            lower_bound: Bound::Included(JsonLiteral::String("10.0.0.0/8".to_string())),
+            upper_bound: Bound::Included(JsonLiteral::String("10.0.0.0/8".to_string())),
+        };
+        let tantivy_ast = range_query
+            .build_tantivy_ast_call(
+                &schema,
+                &create_default_quickwit_tokenizer_manager(),
+                &[],
+                true,
+            )
+            .unwrap();
+        let leaf_str = format!("{:?}", tantivy_ast.as_leaf().unwrap());
+        assert!(leaf_str.starts_with(
+            "RangeQuery { field: \"my_ip_field\", value_type: IpAddr, lower_bound: Included([0, \
+             0, 0, 0, 0, 0, 0, 0, 0, 0, 255, 255, 10, 0, 0, 0]), upper_bound: Included([0, 0, 0, \
+             0, 0, 0, 0, 0, 0, 0, 255, 255, 10, 255, 255, 255])"
+        ));
+
+        let range_query = RangeQuery {
+            field: "my_ip_field".to_string(),
+            lower_bound: Bound::Excluded(JsonLiteral::String("10.0.0.0/24".to_string())),
+            upper_bound: Bound::Excluded(JsonLiteral::String("10.0.2.0/24".to_string())),
+        };
+        let tantivy_ast = range_query
+            .build_tantivy_ast_call(
+                &schema,
+                &create_default_quickwit_tokenizer_manager(),
+                &[],
+                true,
+            )
+            .unwrap();
+        let leaf_str = format!("{:?}", tantivy_ast.as_leaf().unwrap());
+        assert!(leaf_str.starts_with(
+            "RangeQuery { field: \"my_ip_field\", value_type: IpAddr, lower_bound: Excluded([0, \
+             0, 0, 0, 0, 0, 0, 0, 0, 0, 255, 255, 10, 0, 0, 255]), upper_bound: Excluded([0, 0, \
+             0, 0, 0, 0, 0, 0, 0, 0, 255, 255, 10, 0, 2, 0])"
+        ));
+
+        let range_query = RangeQuery {
+            field: "my_ip_field".to_string(),
+            lower_bound: Bound::Included(JsonLiteral::String("10.0.0.0/40".to_string())),
+            upper_bound: Bound::Unbounded,
+        };
+        let invalid_query = range_query
+            .build_tantivy_ast_call(
+                &schema,
+                &create_default_quickwit_tokenizer_manager(),
+                &[],
+                true,
+            )
+            .unwrap_err();
+        assert!(matches!(
+            invalid_query,
+            InvalidQuery::InvalidBoundary { .. }
+        ));
+    }
 }
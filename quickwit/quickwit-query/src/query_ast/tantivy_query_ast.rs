@@ -148,6 +148,8 @@ pub(crate) struct TantivyBoolQuery {
     pub must_not: Vec<TantivyQueryAst>,
     pub should: Vec<TantivyQueryAst>,
     pub filter: Vec<TantivyQueryAst>,
+    /// Minimum number of should clauses a document has to match. Must be strictly positive.
+    pub minimum_should_match: Option<usize>,
 }
 
 fn simplify_asts(asts: Vec<TantivyQueryAst>) -> Vec<TantivyQueryAst> {
@@ -180,6 +182,9 @@ impl TantivyBoolQuery {
                 }
             }
         }
+        if self.should.is_empty() && self.minimum_should_match.is_some() {
+            return TantivyQueryAst::match_none();
+        }
         if self.should.is_empty()
             && self.must.is_empty()
             && self.filter.is_empty()
@@ -202,6 +207,18 @@ impl TantivyBoolQuery {
             has_no_positive_ast_so_far,
         );
         has_no_positive_ast_so_far &= self.should.is_empty();
+        if let Some(minimum_should_match) = self.minimum_should_match {
+            // The should clauses that never match were removed above, and cannot contribute to
+            // the number of matching should clauses anyway.
+            let num_should_matchable = self
+                .should
+                .iter()
+                .filter(|should| should.const_predicate() != Some(MatchAllOrNone::MatchNone))
+                .count();
+            if minimum_should_match > num_should_matchable {
+                return TantivyQueryAst::match_none();
+            }
+        }
         remove_with_guard(
             &mut self.must_not,
             MatchAllOrNone::MatchNone,
@@ -226,6 +243,8 @@ impl TantivyBoolQuery {
                 }
                 self.must.push(TantivyQueryAst::match_all());
             } else if let Some(ast) = self.must.pop().or(self.should.pop()) {
+                // A single should clause has to match, whatever the minimum number of should
+                // clauses to match, which is at most one at this point.
                 return ast;
             }
             // We do not optimize a single filter clause for the moment.
@@ -266,7 +285,16 @@ impl From<TantivyBoolQuery> for Box<dyn TantivyQuery> {
                 Box::new(TantivyConstScoreQuery::new(filter_query, 0.0f32)),
             ));
         }
-        Box::new(tantivy::query::BooleanQuery::from(clause))
+        let boolean_query = match bool_query.minimum_should_match {
+            Some(minimum_should_match) => {
+                tantivy::query::BooleanQuery::with_minimum_required_clauses(
+                    clause,
+                    minimum_should_match,
+                )
+            }
+            None => tantivy::query::BooleanQuery::from(clause),
+        };
+        Box::new(boolean_query)
     }
 }
 
@@ -426,6 +454,41 @@ mod tests {
         assert_eq!(bool_query.const_predicate(), Some(MatchAllOrNone::MatchAll));
     }
 
+    #[test]
+    fn test_simplify_bool_query_with_minimum_should_match() {
+        {
+            let bool_query = TantivyBoolQuery {
+                should: vec![
+                    EmptyQuery.into(),
+                    TantivyQueryAst::match_none(),
+                    EmptyQuery.into(),
+                ],
+                minimum_should_match: Some(2),
+                ..Default::default()
+            }
+            .simplify();
+            let bool_query = bool_query.as_bool_query().unwrap();
+            assert_eq!(bool_query.should.len(), 2);
+            assert_eq!(bool_query.minimum_should_match, Some(2));
+        }
+        {
+            let bool_query = TantivyBoolQuery {
+                should: vec![
+                    EmptyQuery.into(),
+                    TantivyQueryAst::match_none(),
+                    EmptyQuery.into(),
+                ],
+                minimum_should_match: Some(3),
+                ..Default::default()
+            }
+            .simplify();
+            assert_eq!(
+                bool_query.const_predicate(),
+                Some(MatchAllOrNone::MatchNone)
+            );
+        }
+    }
+
     #[test]
     fn test_simplify_empty_bool_query_matches_all() {
         let empty_bool_query = TantivyBoolQuery::default().simplify();
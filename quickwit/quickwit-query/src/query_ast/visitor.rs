@@ -21,8 +21,8 @@ use crate::not_nan_f32::NotNaNf32;
 use crate::query_ast::field_presence::FieldPresenceQuery;
 use crate::query_ast::user_input_query::UserInputQuery;
 use crate::query_ast::{
-    BoolQuery, FullTextQuery, PhrasePrefixQuery, QueryAst, RangeQuery, TermQuery, TermSetQuery,
-    WildcardQuery,
+    BoolQuery, ConstantScoreQuery, DisMaxQuery, FullTextQuery, PhrasePrefixQuery, QueryAst,
+    RangeQuery, TermQuery, TermSetQuery, WildcardQuery,
 };
 
 /// Simple trait to implement a Visitor over the QueryAst.
@@ -32,6 +32,10 @@ pub trait QueryAstVisitor<'a> {
     fn visit(&mut self, query_ast: &'a QueryAst) -> Result<(), Self::Err> {
         match query_ast {
             QueryAst::Bool(bool_query) => self.visit_bool(bool_query),
+            QueryAst::DisMax(dis_max_query) => self.visit_dis_max(dis_max_query),
+            QueryAst::ConstantScore(constant_score_query) => {
+                self.visit_constant_score(constant_score_query)
+            }
             QueryAst::Term(term_query) => self.visit_term(term_query),
             QueryAst::TermSet(term_set_query) => self.visit_term_set(term_set_query),
             QueryAst::FullText(full_text_query) => self.visit_full_text(full_text_query),
@@ -61,6 +65,20 @@ pub trait QueryAstVisitor<'a> {
         Ok(())
     }
 
+    fn visit_dis_max(&mut self, dis_max_query: &'a DisMaxQuery) -> Result<(), Self::Err> {
+        for ast in &dis_max_query.disjuncts {
+            self.visit(ast)?;
+        }
+        Ok(())
+    }
+
+    fn visit_constant_score(
+        &mut self,
+        constant_score_query: &'a ConstantScoreQuery,
+    ) -> Result<(), Self::Err> {
+        self.visit(&constant_score_query.filter)
+    }
+
     fn visit_term(&mut self, _term_query: &'a TermQuery) -> Result<(), Self::Err> {
         Ok(())
     }
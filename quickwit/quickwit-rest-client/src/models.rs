@@ -22,6 +22,8 @@ use std::time::Duration;
 
 use reqwest::StatusCode;
 use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
 
 use crate::error::{ApiError, Error, ErrorResponsePayload};
 
@@ -83,6 +85,48 @@ pub enum IngestSource {
     Stdin,
 }
 
+/// A page of documents returned by the Elasticsearch-compatible scroll API.
+#[derive(Debug)]
+pub struct ScrollPage {
+    /// The scroll ID to pass to the next scroll request.
+    pub scroll_id: Option<String>,
+    /// The `_source` of the hits of the page, `None` for the hits returned without one.
+    pub sources: Vec<Option<JsonValue>>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ScrollResponse {
+    #[serde(rename = "_scroll_id")]
+    scroll_id: Option<String>,
+    hits: ScrollHits,
+}
+
+#[derive(Deserialize)]
+struct ScrollHits {
+    hits: Vec<ScrollHit>,
+}
+
+#[derive(Deserialize)]
+struct ScrollHit {
+    #[serde(rename = "_source")]
+    source: Option<JsonValue>,
+}
+
+impl From<ScrollResponse> for ScrollPage {
+    fn from(scroll_response: ScrollResponse) -> Self {
+        let sources = scroll_response
+            .hits
+            .hits
+            .into_iter()
+            .map(|hit| hit.source)
+            .collect();
+        ScrollPage {
+            scroll_id: scroll_response.scroll_id,
+            sources,
+        }
+    }
+}
+
 /// A structure that represent a timeout. Unlike Duration it can also represent an infinite or no
 /// timeout value.
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
@@ -26,7 +26,10 @@ use quickwit_indexing::actors::IndexingServiceCounters;
 pub use quickwit_ingest::CommitType;
 use quickwit_metastore::{IndexMetadata, Split, SplitInfo};
 use quickwit_search::SearchResponseRest;
-use quickwit_serve::{ListSplitsQueryParams, ListSplitsResponse, SearchRequestQueryString};
+use quickwit_serve::{
+    ListSplitsQueryParams, ListSplitsResponse, SearchRequestQueryString, SourceIngestStatus,
+    SplitsConsistencyReport,
+};
 use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
 use reqwest::{Client, ClientBuilder, Method, StatusCode, Url};
 use serde::Serialize;
@@ -34,7 +37,7 @@ use serde_json::json;
 use tracing::warn;
 
 use crate::error::Error;
-use crate::models::{ApiResponse, IngestSource, Timeout};
+use crate::models::{ApiResponse, IngestSource, ScrollPage, ScrollResponse, Timeout};
 use crate::BatchLineReader;
 
 pub const DEFAULT_BASE_URL: &str = "http://127.0.0.1:7280";
@@ -228,6 +231,64 @@ impl QuickwitClient {
         Ok(search_response)
     }
 
+    /// Opens a scroll on the index using the Elasticsearch-compatible API and returns the first
+    /// page of documents. Subsequent pages are fetched with [`QuickwitClient::scroll_next`].
+    pub async fn scroll(
+        &self,
+        index_id: &str,
+        query: &str,
+        page_size: u64,
+        keep_alive: &str,
+    ) -> Result<ScrollPage, Error> {
+        let path = format!("_elastic/{index_id}/_search");
+        let query_params = [
+            ("q", query.to_string()),
+            ("size", page_size.to_string()),
+            ("scroll", keep_alive.to_string()),
+        ];
+        let response = self
+            .transport
+            .send(
+                Method::GET,
+                &path,
+                None,
+                Some(&query_params),
+                None,
+                self.search_timeout,
+            )
+            .await?;
+        let scroll_response: ScrollResponse = response.deserialize().await?;
+        Ok(scroll_response.into())
+    }
+
+    /// Fetches the next page of documents of a scroll opened with [`QuickwitClient::scroll`].
+    pub async fn scroll_next(
+        &self,
+        scroll_id: &str,
+        keep_alive: &str,
+    ) -> Result<ScrollPage, Error> {
+        let body = Bytes::from(
+            json!({
+                "scroll_id": scroll_id,
+                "scroll": keep_alive,
+            })
+            .to_string(),
+        );
+        let response = self
+            .transport
+            .send::<()>(
+                Method::POST,
+                "_elastic/_search/scroll",
+                None,
+                None,
+                Some(body),
+                self.search_timeout,
+            )
+            .await?;
+        let scroll_response: ScrollResponse = response.deserialize().await?;
+        Ok(scroll_response.into())
+    }
+
     pub fn indexes(&self) -> IndexClient {
         IndexClient::new(&self.transport, self.timeout)
     }
@@ -311,6 +372,17 @@ impl QuickwitClient {
 
         Ok(())
     }
+
+    /// Returns the checkpoint and the estimated lag of each source of an index.
+    pub async fn ingest_status(&self, index_id: &str) -> Result<Vec<SourceIngestStatus>, Error> {
+        let path = format!("{index_id}/ingest-status");
+        let response = self
+            .transport
+            .send::<()>(Method::GET, &path, None, None, None, self.timeout)
+            .await?;
+        let ingest_statuses = response.deserialize().await?;
+        Ok(ingest_statuses)
+    }
 }
 
 pub enum IngestEvent {
@@ -381,6 +453,56 @@ impl<'a> IndexClient<'a> {
         Ok(())
     }
 
+    pub async fn check(&self, index_id: &str) -> Result<SplitsConsistencyReport, Error> {
+        let path = format!("indexes/{index_id}/check");
+        // Listing the files of the index storage can take an arbitrarily long time.
+        let response = self
+            .transport
+            .send::<()>(Method::GET, &path, None, None, None, Timeout::none())
+            .await?;
+        let report = response.deserialize().await?;
+        Ok(report)
+    }
+
+    pub async fn update_index_uri(
+        &self,
+        index_id: &str,
+        index_uri: &str,
+        move_data: bool,
+    ) -> Result<IndexMetadata, Error> {
+        let path = format!("indexes/{index_id}/index-uri");
+        let body = Bytes::from(
+            json!({
+                "index_uri": index_uri,
+                "move_data": move_data,
+            })
+            .to_string(),
+        );
+        // Copying the split files can take an arbitrarily long time.
+        let timeout = if move_data {
+            Timeout::none()
+        } else {
+            self.timeout
+        };
+        let response = self
+            .transport
+            .send::<()>(Method::PUT, &path, None, None, Some(body), timeout)
+            .await?;
+        let index_metadata = response.deserialize().await?;
+        Ok(index_metadata)
+    }
+
+    pub async fn rename(&self, index_id: &str, new_index_id: &str) -> Result<IndexMetadata, Error> {
+        let path = format!("indexes/{index_id}/rename");
+        let body = Bytes::from(json!({ "new_index_id": new_index_id }).to_string());
+        let response = self
+            .transport
+            .send::<()>(Method::PUT, &path, None, None, Some(body), self.timeout)
+            .await?;
+        let index_metadata = response.deserialize().await?;
+        Ok(index_metadata)
+    }
+
     pub async fn delete(&self, index_id: &str, dry_run: bool) -> Result<Vec<SplitInfo>, Error> {
         let path = format!("indexes/{index_id}");
         let response = self
@@ -652,6 +774,7 @@ fn header_from_config_format(config_format: ConfigFormat) -> HeaderMap {
 
 #[cfg(test)]
 mod test {
+    use std::collections::BTreeMap;
     use std::path::PathBuf;
     use std::str::FromStr;
 
@@ -659,7 +782,7 @@ mod test {
     use quickwit_indexing::mock_split;
     use quickwit_ingest::CommitType;
     use quickwit_metastore::IndexMetadata;
-    use quickwit_search::SearchResponseRest;
+    use quickwit_search::{NumHitsRelation, SearchResponseRest};
     use quickwit_serve::{ListSplitsQueryParams, ListSplitsResponse, SearchRequestQueryString};
     use reqwest::header::CONTENT_TYPE;
     use reqwest::{StatusCode, Url};
@@ -697,11 +820,16 @@ mod test {
         };
         let expected_search_response = SearchResponseRest {
             num_hits: 0,
+            num_hits_relation: NumHitsRelation::Eq,
+            num_hits_per_index: BTreeMap::new(),
             hits: Vec::new(),
             snippets: None,
+            split_publish_timestamps: None,
             aggregations: None,
             elapsed_time_micros: 100,
             errors: Vec::new(),
+            partial: false,
+            search_after: None,
         };
         Mock::given(method("POST"))
             .and(path("/api/v1/my-index/search"))
@@ -720,6 +848,47 @@ mod test {
         );
     }
 
+    #[tokio::test]
+    async fn test_scroll_endpoints() {
+        let mock_server = MockServer::start().await;
+        let server_url = Url::parse(&mock_server.uri()).unwrap();
+        let qw_client = QuickwitClientBuilder::new(server_url).build();
+        Mock::given(method("GET"))
+            .and(path("/api/v1/_elastic/my-index/_search"))
+            .and(query_param("q", "level:info"))
+            .and(query_param("size", "2"))
+            .and(query_param("scroll", "1m"))
+            .respond_with(ResponseTemplate::new(StatusCode::OK).set_body_json(json!({
+                "_scroll_id": "scroll-1",
+                "hits": {"hits": [{"_source": {"id": 1}}, {"_source": {"id": 2}}, {"_id": 3}]}
+            })))
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/_elastic/_search/scroll"))
+            .and(body_json(json!({"scroll_id": "scroll-1", "scroll": "1m"})))
+            .respond_with(ResponseTemplate::new(StatusCode::OK).set_body_json(json!({
+                "_scroll_id": "scroll-1",
+                "hits": {"hits": []}
+            })))
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+        let first_page = qw_client
+            .scroll("my-index", "level:info", 2, "1m")
+            .await
+            .unwrap();
+        assert_eq!(first_page.scroll_id.as_deref(), Some("scroll-1"));
+        assert_eq!(
+            first_page.sources,
+            vec![Some(json!({"id": 1})), Some(json!({"id": 2})), None]
+        );
+
+        let next_page = qw_client.scroll_next("scroll-1", "1m").await.unwrap();
+        assert!(next_page.sources.is_empty());
+    }
+
     fn get_ndjson_filepath(ndjson_dataset_filename: &str) -> String {
         format!(
             "{}/resources/tests/{}",
@@ -765,6 +934,41 @@ mod test {
             .unwrap();
     }
 
+    #[tokio::test]
+    async fn test_ingest_status_endpoint() {
+        let mock_server = MockServer::start().await;
+        let server_url = Url::parse(&mock_server.uri()).unwrap();
+        let qw_client = QuickwitClientBuilder::new(server_url).build();
+        Mock::given(method("GET"))
+            .and(path("/api/v1/my-index/ingest-status"))
+            .respond_with(ResponseTemplate::new(StatusCode::OK).set_body_json(json!([
+                {
+                    "source_id": "_ingest-api-source",
+                    "enabled": true,
+                    "checkpoint": {"my-partition": "00000000000000000009"},
+                    "lag": 32,
+                },
+                {
+                    "source_id": "my-file-source",
+                    "enabled": false,
+                    "checkpoint": {},
+                    "lag": null,
+                },
+            ])))
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+        let ingest_statuses = qw_client.ingest_status("my-index").await.unwrap();
+        assert_eq!(ingest_statuses.len(), 2);
+        assert_eq!(ingest_statuses[0].source_id, "_ingest-api-source");
+        assert!(ingest_statuses[0].enabled);
+        assert_eq!(ingest_statuses[0].lag, Some(32));
+        assert_eq!(ingest_statuses[1].source_id, "my-file-source");
+        assert!(!ingest_statuses[1].enabled);
+        assert!(ingest_statuses[1].checkpoint.is_empty());
+        assert!(ingest_statuses[1].lag.is_none());
+    }
+
     #[tokio::test]
     async fn test_ingest_endpoint_with_force_commit() {
         let mock_server = MockServer::start().await;
@@ -936,6 +1140,62 @@ mod test {
             .await;
         qw_client.indexes().clear("my-index").await.unwrap_err();
 
+        // GET check index
+        Mock::given(method("GET"))
+            .and(path("/api/v1/indexes/my-index/check"))
+            .respond_with(ResponseTemplate::new(StatusCode::OK).set_body_json(json!({
+                "consistent_split_ids": ["split-1"],
+                "missing_in_storage_split_ids": ["split-2"],
+                "orphaned_in_storage_split_ids": [],
+            })))
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+        let report = qw_client.indexes().check("my-index").await.unwrap();
+        assert_eq!(report.consistent_split_ids, ["split-1"]);
+        assert_eq!(report.missing_in_storage_split_ids, ["split-2"]);
+        assert!(report.orphaned_in_storage_split_ids.is_empty());
+
+        // PUT index URI
+        Mock::given(method("PUT"))
+            .and(path("/api/v1/indexes/my-index/index-uri"))
+            .and(body_json(
+                json!({"index_uri": "ram:///indexes/test-index", "move_data": true}),
+            ))
+            .respond_with(
+                ResponseTemplate::new(StatusCode::OK).set_body_json(index_metadata.clone()),
+            )
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+        assert_eq!(
+            qw_client
+                .indexes()
+                .update_index_uri("my-index", "ram:///indexes/test-index", true)
+                .await
+                .unwrap(),
+            index_metadata
+        );
+
+        // PUT rename index
+        Mock::given(method("PUT"))
+            .and(path("/api/v1/indexes/my-index/rename"))
+            .and(body_json(json!({"new_index_id": "my-index-renamed"})))
+            .respond_with(
+                ResponseTemplate::new(StatusCode::OK).set_body_json(index_metadata.clone()),
+            )
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+        assert_eq!(
+            qw_client
+                .indexes()
+                .rename("my-index", "my-index-renamed")
+                .await
+                .unwrap(),
+            index_metadata
+        );
+
         // DELETE index
         Mock::given(method("DELETE"))
             .and(path("/api/v1/indexes/my-index"))
@@ -249,6 +249,24 @@ impl SearchServiceClient {
         }
     }
 
+    /// Perform leaf list facets.
+    pub async fn leaf_list_facets(
+        &mut self,
+        request: quickwit_proto::search::LeafListFacetsRequest,
+    ) -> crate::Result<quickwit_proto::search::LeafListFacetsResponse> {
+        match &mut self.client_impl {
+            SearchServiceClientImpl::Grpc(grpc_client) => {
+                let tonic_request = Request::new(request);
+                let tonic_response = grpc_client
+                    .leaf_list_facets(tonic_request)
+                    .await
+                    .map_err(|tonic_error| parse_grpc_error(&tonic_error))?;
+                Ok(tonic_response.into_inner())
+            }
+            SearchServiceClientImpl::Local(service) => service.leaf_list_facets(request).await,
+        }
+    }
+
     /// Gets the value associated to a key stored locally in the targetted node.
     /// This call is not "distributed".
     /// If the key is not present on the targetted search `None` is simply returned.
@@ -17,25 +17,30 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
+use std::collections::HashSet;
+use std::net::SocketAddr;
 use std::time::Duration;
 
 use base64::Engine;
 use futures::future::ready;
 use futures::{Future, StreamExt};
 use quickwit_proto::search::{
-    FetchDocsRequest, FetchDocsResponse, GetKvRequest, LeafListFieldsRequest, LeafListTermsRequest,
-    LeafListTermsResponse, LeafSearchRequest, LeafSearchResponse, LeafSearchStreamRequest,
-    LeafSearchStreamResponse, ListFieldsResponse, PutKvRequest,
+    FetchDocsRequest, FetchDocsResponse, GetKvRequest, LeafListFacetsRequest,
+    LeafListFacetsResponse, LeafListFieldsRequest, LeafListTermsRequest, LeafListTermsResponse,
+    LeafSearchRequest, LeafSearchResponse, LeafSearchStreamRequest, LeafSearchStreamResponse,
+    ListFieldsResponse, PutKvRequest,
 };
 use tantivy::aggregation::intermediate_agg_result::IntermediateAggregationResults;
 use tokio::sync::mpsc::error::SendError;
 use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+use tokio::time::Instant;
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use tracing::{debug, error, info, warn};
 
 use crate::retry::search::LeafSearchRetryPolicy;
 use crate::retry::search_stream::{LeafSearchStreamRetryPolicy, SuccessfulSplitIds};
 use crate::retry::{retry_client, DefaultRetryPolicy, RetryPolicy};
+use crate::searcher_health::SearchRequestKind;
 use crate::{SearchError, SearchJobPlacer, SearchServiceClient};
 
 /// Maximum number of put requests emitted to perform a replicated given PUT KV.
@@ -44,6 +49,9 @@ const MAX_PUT_KV_ATTEMPTS: usize = 6;
 /// Maximum number of get requests emitted to perform a GET KV request.
 const MAX_GET_KV_ATTEMPTS: usize = 6;
 
+/// Maximum number of attempts made to execute a leaf search request, including retries.
+const MAX_LEAF_SEARCH_ATTEMPTS: usize = 3;
+
 /// We attempt to store our KVs on two nodes.
 const TARGET_NUM_REPLICATION: usize = 2;
 
@@ -67,13 +75,15 @@ impl ClusterClient {
         request: FetchDocsRequest,
         mut client: SearchServiceClient,
     ) -> crate::Result<FetchDocsResponse> {
-        let mut response_res = client.fetch_docs(request.clone()).await;
+        let mut response_res = self
+            .fetch_docs_on_client(&mut client, request.clone())
+            .await;
         let retry_policy = DefaultRetryPolicy {};
         if let Some(retry_request) = retry_policy.retry_request(request, &response_res) {
             assert!(!retry_request.split_offsets.is_empty());
             client = retry_client(
                 &self.search_job_placer,
-                client.grpc_addr(),
+                &HashSet::from([client.grpc_addr()]),
                 &retry_request.split_offsets[0].split_id,
             )
             .await?;
@@ -81,37 +91,92 @@ impl ClusterClient {
                 "Fetch docs response error: `{:?}`. Retry once to execute {:?} with {:?}",
                 response_res, retry_request, client
             );
-            response_res = client.fetch_docs(retry_request).await;
+            response_res = self.fetch_docs_on_client(&mut client, retry_request).await;
         }
         response_res
     }
 
-    /// Leaf search with retry on another node client.
+    /// Leaf search with retries on other node clients.
+    ///
+    /// Failing splits are retried on the next candidate node in rendez-vous order, up to
+    /// [`MAX_LEAF_SEARCH_ATTEMPTS`] attempts. Beyond the first retry, only nodes that have not
+    /// been tried yet are considered. Non-retryable errors, such as an invalid query, are never
+    /// retried.
     pub async fn leaf_search(
         &self,
         request: LeafSearchRequest,
         mut client: SearchServiceClient,
     ) -> crate::Result<LeafSearchResponse> {
-        let mut response_res = client.leaf_search(request.clone()).await;
+        let mut response_res = self
+            .leaf_search_on_client(&mut client, request.clone())
+            .await;
         let retry_policy = LeafSearchRetryPolicy {};
-        if let Some(retry_request) = retry_policy.retry_request(request, &response_res) {
+        let mut retry_request_opt = retry_policy.retry_request(request, &response_res);
+        let mut excluded_addrs: HashSet<SocketAddr> = HashSet::new();
+
+        for num_retries in 0..MAX_LEAF_SEARCH_ATTEMPTS - 1 {
+            let Some(retry_request) = retry_request_opt else {
+                break;
+            };
             assert!(!retry_request.split_offsets.is_empty());
+            excluded_addrs.insert(client.grpc_addr());
             client = retry_client(
                 &self.search_job_placer,
-                client.grpc_addr(),
+                &excluded_addrs,
                 &retry_request.split_offsets[0].split_id,
             )
             .await?;
+            if num_retries > 0 && excluded_addrs.contains(&client.grpc_addr()) {
+                // All the nodes have already been tried.
+                break;
+            }
             debug!(
-                "Leaf search response error: `{:?}`. Retry once to execute {:?} with {:?}",
+                "Leaf search response error: `{:?}`. Retry to execute {:?} with {:?}",
                 response_res, retry_request, client
             );
-            let retry_result = client.leaf_search(retry_request).await;
+            let retry_result = self
+                .leaf_search_on_client(&mut client, retry_request.clone())
+                .await;
+            retry_request_opt = retry_policy.retry_request(retry_request, &retry_result);
             response_res = merge_leaf_search_results(response_res, retry_result);
         }
         response_res
     }
 
+    /// Fetches docs on a single client, recording the outcome in the searcher health.
+    async fn fetch_docs_on_client(
+        &self,
+        client: &mut SearchServiceClient,
+        request: FetchDocsRequest,
+    ) -> crate::Result<FetchDocsResponse> {
+        let start = Instant::now();
+        let response_res = client.fetch_docs(request).await;
+        self.search_job_placer.record_outcome(
+            client.grpc_addr(),
+            SearchRequestKind::FetchDocs,
+            &response_res,
+            start.elapsed(),
+        );
+        response_res
+    }
+
+    /// Leaf search on a single client, recording the outcome in the searcher health.
+    async fn leaf_search_on_client(
+        &self,
+        client: &mut SearchServiceClient,
+        request: LeafSearchRequest,
+    ) -> crate::Result<LeafSearchResponse> {
+        let start = Instant::now();
+        let response_res = client.leaf_search(request).await;
+        self.search_job_placer.record_outcome(
+            client.grpc_addr(),
+            SearchRequestKind::LeafSearch,
+            &response_res,
+            start.elapsed(),
+        );
+        response_res
+    }
+
     /// Leaf search with retry on another node client.
     pub async fn leaf_list_fields(
         &self,
@@ -143,7 +208,7 @@ impl ClusterClient {
                 assert!(!retry_request.split_offsets.is_empty());
                 let retry_client_res = retry_client(
                     &client_pool,
-                    client.grpc_addr(),
+                    &HashSet::from([client.grpc_addr()]),
                     &retry_request.split_offsets[0].split_id,
                 )
                 .await;
@@ -181,6 +246,15 @@ impl ClusterClient {
         client.leaf_list_terms(request.clone()).await
     }
 
+    /// Leaf list facets.
+    pub async fn leaf_list_facets(
+        &self,
+        request: LeafListFacetsRequest,
+        mut client: SearchServiceClient,
+    ) -> crate::Result<LeafListFacetsResponse> {
+        client.leaf_list_facets(request).await
+    }
+
     /// Attempts to store a given search context within the cluster.
     ///
     /// This function may fail silently, if no clients was available.
@@ -294,12 +368,17 @@ fn merge_leaf_search_response(
         (Some(left), None) => Some(left),
         (None, None) => None,
     };
+    // The retryable failed splits of the left response were retried: their outcome is in the
+    // right response.
+    let mut failed_splits = left_response.failed_splits;
+    failed_splits.retain(|failed_split| !failed_split.retryable_error);
+    failed_splits.extend(right_response.failed_splits);
     Ok(LeafSearchResponse {
         intermediate_aggregation_result,
         num_hits: left_response.num_hits + right_response.num_hits,
         num_attempted_splits: left_response.num_attempted_splits
             + right_response.num_attempted_splits,
-        failed_splits: right_response.failed_splits,
+        failed_splits,
         partial_hits: left_response.partial_hits,
     })
 }
@@ -349,6 +428,7 @@ async fn forward_leaf_search_stream(
 mod tests {
     use std::collections::HashSet;
     use std::net::SocketAddr;
+    use std::sync::{Arc, Mutex};
 
     use quickwit_proto::search::{
         PartialHit, SearchRequest, SearchStreamRequest, SortValue, SplitIdAndFooterOffsets,
@@ -380,6 +460,7 @@ mod tests {
                 split_footer_start: 0,
                 timestamp_start: None,
                 timestamp_end: None,
+                split_path_prefix: None,
             }],
             ..Default::default()
         }
@@ -403,6 +484,7 @@ mod tests {
                     split_footer_end: 100,
                     timestamp_start: None,
                     timestamp_end: None,
+                    split_path_prefix: None,
                 },
                 SplitIdAndFooterOffsets {
                     split_id: "split_2".to_string(),
@@ -410,6 +492,7 @@ mod tests {
                     split_footer_end: 100,
                     timestamp_start: None,
                     timestamp_end: None,
+                    split_path_prefix: None,
                 },
             ],
         }
@@ -437,6 +520,7 @@ mod tests {
                     split_footer_end: 100,
                     timestamp_start: None,
                     timestamp_end: None,
+                    split_path_prefix: None,
                 },
                 SplitIdAndFooterOffsets {
                     split_id: "split_2".to_string(),
@@ -444,6 +528,7 @@ mod tests {
                     split_footer_end: 100,
                     timestamp_start: None,
                     timestamp_end: None,
+                    split_path_prefix: None,
                 },
             ],
         }
@@ -596,6 +681,155 @@ mod tests {
         assert_eq!(result.unwrap().num_hits, 2);
     }
 
+    /// Returns a mock search service recording its leaf search attempts in `attempts` and
+    /// failing the first `num_failures` leaf search attempts made on the cluster.
+    fn mock_search_service_failing_first_attempts(
+        grpc_addr: &'static str,
+        attempts: Arc<Mutex<Vec<&'static str>>>,
+        num_failures: usize,
+        error: SearchError,
+    ) -> MockSearchService {
+        let mut mock_search_service = MockSearchService::new();
+        mock_search_service
+            .expect_leaf_search()
+            .returning(move |request: LeafSearchRequest| {
+                let mut attempts_guard = attempts.lock().unwrap();
+                attempts_guard.push(grpc_addr);
+
+                if attempts_guard.len() <= num_failures {
+                    return Err(error.clone());
+                }
+                let partial_hits = request
+                    .split_offsets
+                    .iter()
+                    .map(|split_offsets| mock_partial_hit(&split_offsets.split_id, 3, 1))
+                    .collect();
+                Ok(LeafSearchResponse {
+                    num_hits: request.split_offsets.len() as u64,
+                    partial_hits,
+                    num_attempted_splits: request.split_offsets.len() as u64,
+                    ..Default::default()
+                })
+            });
+        mock_search_service
+    }
+
+    async fn leaf_search_with_failing_first_attempts(
+        num_failures: usize,
+        error: SearchError,
+    ) -> (crate::Result<LeafSearchResponse>, Vec<&'static str>) {
+        let attempts: Arc<Mutex<Vec<&'static str>>> = Arc::default();
+        let searcher_pool = searcher_pool_for_test(
+            ["127.0.0.1:1001", "127.0.0.1:1002", "127.0.0.1:1003"].map(|grpc_addr| {
+                let mock_search_service = mock_search_service_failing_first_attempts(
+                    grpc_addr,
+                    attempts.clone(),
+                    num_failures,
+                    error.clone(),
+                );
+                (grpc_addr, mock_search_service)
+            }),
+        );
+        let search_job_placer = SearchJobPlacer::new(searcher_pool);
+        let first_client = search_job_placer
+            .assign_job(SearchJob::for_test("split_1", 0), &HashSet::new())
+            .await
+            .unwrap();
+        let cluster_client = ClusterClient::new(search_job_placer);
+        let leaf_search_res = cluster_client
+            .leaf_search(mock_leaf_search_request(), first_client)
+            .await;
+        let attempts = attempts.lock().unwrap().clone();
+        (leaf_search_res, attempts)
+    }
+
+    #[tokio::test]
+    async fn test_cluster_client_leaf_search_retry_on_next_candidate_node() {
+        let (leaf_search_res, attempts) =
+            leaf_search_with_failing_first_attempts(1, SearchError::Internal("error".to_string()))
+                .await;
+        let leaf_search_response = leaf_search_res.unwrap();
+        assert_eq!(leaf_search_response.num_hits, 2);
+        assert_eq!(leaf_search_response.partial_hits.len(), 2);
+        assert_eq!(leaf_search_response.partial_hits[0].split_id, "split_1");
+        assert!(leaf_search_response.failed_splits.is_empty());
+        assert_eq!(attempts.len(), 2);
+        assert_ne!(attempts[0], attempts[1]);
+    }
+
+    #[tokio::test]
+    async fn test_cluster_client_leaf_search_retry_is_bounded() {
+        let (leaf_search_res, attempts) =
+            leaf_search_with_failing_first_attempts(2, SearchError::Internal("error".to_string()))
+                .await;
+        assert_eq!(leaf_search_res.unwrap().num_hits, 2);
+        assert_eq!(attempts.len(), 3);
+        assert_eq!(attempts.iter().collect::<HashSet<_>>().len(), 3);
+
+        let (leaf_search_res, attempts) =
+            leaf_search_with_failing_first_attempts(3, SearchError::Internal("error".to_string()))
+                .await;
+        leaf_search_res.unwrap_err();
+        assert_eq!(attempts.len(), MAX_LEAF_SEARCH_ATTEMPTS);
+    }
+
+    #[tokio::test]
+    async fn test_cluster_client_leaf_search_shifts_jobs_away_from_erroring_node() {
+        let mut mock_search_service_1 = MockSearchService::new();
+        mock_search_service_1
+            .expect_leaf_search()
+            .returning(|_: LeafSearchRequest| Err(SearchError::Internal("error".to_string())));
+        let mut mock_search_service_2 = MockSearchService::new();
+        mock_search_service_2
+            .expect_leaf_search()
+            .returning(|request: LeafSearchRequest| {
+                Ok(LeafSearchResponse {
+                    num_hits: request.split_offsets.len() as u64,
+                    num_attempted_splits: request.split_offsets.len() as u64,
+                    ..Default::default()
+                })
+            });
+        let searcher_pool = searcher_pool_for_test([
+            ("127.0.0.1:1001", mock_search_service_1),
+            ("127.0.0.1:1002", mock_search_service_2),
+        ]);
+        let erroring_searcher_addr: SocketAddr = ([127, 0, 0, 1], 1001).into();
+        let healthy_searcher_addr: SocketAddr = ([127, 0, 0, 1], 1002).into();
+        let search_job_placer = SearchJobPlacer::new(searcher_pool.clone());
+        let cluster_client = ClusterClient::new(search_job_placer.clone());
+
+        for _ in 0..5 {
+            let erroring_client = searcher_pool.get(&erroring_searcher_addr).unwrap();
+            let leaf_search_response = cluster_client
+                .leaf_search(mock_leaf_search_request(), erroring_client)
+                .await
+                .unwrap();
+            assert_eq!(leaf_search_response.num_hits, 2);
+        }
+        let jobs: Vec<SearchJob> = (0..10)
+            .map(|split_ord| SearchJob::for_test(&format!("split_{split_ord}"), 1))
+            .collect();
+        let assigned_addrs: Vec<SocketAddr> = search_job_placer
+            .assign_jobs(jobs, &HashSet::new())
+            .await
+            .unwrap()
+            .map(|(client, _jobs)| client.grpc_addr())
+            .collect();
+        assert_eq!(assigned_addrs, vec![healthy_searcher_addr]);
+    }
+
+    #[tokio::test]
+    async fn test_cluster_client_leaf_search_no_retry_on_non_retryable_error() {
+        let (leaf_search_res, attempts) = leaf_search_with_failing_first_attempts(
+            1,
+            SearchError::InvalidQuery("invalid query".to_string()),
+        )
+        .await;
+        let search_error = leaf_search_res.unwrap_err();
+        assert!(matches!(search_error, SearchError::InvalidQuery(_)));
+        assert_eq!(attempts.len(), 1);
+    }
+
     #[test]
     fn test_merge_leaf_search_retry_on_partial_success() -> anyhow::Result<()> {
         let split_error = SplitSearchError {
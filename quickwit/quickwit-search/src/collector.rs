@@ -390,7 +390,11 @@ impl SegmentCollector for QuickwitSegmentCollector {
         }
 
         self.num_hits += 1;
-        self.collect_top_k(doc_id, score);
+        // Aggregation-only and count-only requests do not need any hit, so we do not even extract
+        // the sort values.
+        if self.top_k_hits.max_len() > 0 {
+            self.collect_top_k(doc_id, score);
+        }
 
         match self.aggregation.as_mut() {
             Some(AggregationSegmentCollectors::FindTraceIdsSegmentCollector(collector)) => {
@@ -449,7 +453,7 @@ pub enum QuickwitAggregations {
 }
 
 impl QuickwitAggregations {
-    fn fast_field_names(&self) -> HashSet<String> {
+    pub(crate) fn fast_field_names(&self) -> HashSet<String> {
         match self {
             QuickwitAggregations::FindTraceIdsAggregation(collector) => {
                 collector.fast_field_names()
@@ -564,11 +568,20 @@ pub(crate) struct QuickwitCollector {
 }
 
 impl QuickwitCollector {
+    /// Returns whether the collector needs to collect the top hits. If the request does not ask
+    /// for any hit (e.g. `max_hits=0` to only compute aggregations), the leaves only count the
+    /// matching documents and run the aggregation collectors.
+    pub fn collects_hits(&self) -> bool {
+        self.max_hits > 0
+    }
+
     pub fn fast_field_names(&self) -> HashSet<String> {
         let mut fast_field_names = HashSet::default();
-        self.sort_by.first.add_fast_field(&mut fast_field_names);
-        if let Some(sort_by_second) = &self.sort_by.second {
-            sort_by_second.add_fast_field(&mut fast_field_names);
+        if self.collects_hits() {
+            self.sort_by.first.add_fast_field(&mut fast_field_names);
+            if let Some(sort_by_second) = &self.sort_by.second {
+                sort_by_second.add_fast_field(&mut fast_field_names);
+            }
         }
         if let Some(aggregations) = &self.aggregation {
             fast_field_names.extend(aggregations.fast_field_names());
@@ -599,7 +612,11 @@ impl Collector for QuickwitCollector {
     ) -> tantivy::Result<Self::Child> {
         // Regardless of the start_offset, we need to collect top-K
         // starting from 0 for every leaves.
-        let leaf_max_hits = self.max_hits + self.start_offset;
+        let leaf_max_hits = if self.collects_hits() {
+            self.max_hits + self.start_offset
+        } else {
+            0
+        };
 
         let timestamp_filter_opt = match &self.timestamp_filter_builder_opt {
             Some(timestamp_filter_builder) => timestamp_filter_builder.build(segment_reader)?,
@@ -622,7 +639,16 @@ impl Collector for QuickwitCollector {
             ),
             None => None,
         };
-        let score_extractor = get_score_extractor(&self.sort_by, segment_reader)?;
+        let score_extractor = if self.collects_hits() {
+            get_score_extractor(&self.sort_by, segment_reader)?
+        } else {
+            // The sort values are never extracted, so we avoid opening the sort fast fields.
+            let doc_id_sort_by: SortByPair = SortByComponent::DocId {
+                order: SortOrder::Desc,
+            }
+            .into();
+            get_score_extractor(&doc_id_sort_by, segment_reader)?
+        };
         let (order1, order2) = self.sort_by.sort_orders();
         let sort_key_mapper = HitSortingMapper { order1, order2 };
         let split_search_after_order = if let Some(search_after) = &self.search_after {
@@ -654,6 +680,10 @@ impl Collector for QuickwitCollector {
         // We do not need BM25 scoring in Quickwit if it is not opted-in.
         // By returning false, we inform tantivy that it does not need to decompress
         // term frequencies.
+        // Scores are only used to sort the hits.
+        if !self.collects_hits() {
+            return false;
+        }
         self.sort_by.first.requires_scoring()
             || self
                 .sort_by
@@ -1109,11 +1139,14 @@ impl IncrementalCollector {
 #[cfg(test)]
 mod tests {
     use std::cmp::Ordering;
+    use std::collections::HashSet;
 
     use quickwit_proto::search::{
         LeafSearchResponse, PartialHit, SearchRequest, SortByValue, SortField, SortOrder,
         SortValue, SplitSearchError,
     };
+    use tantivy::aggregation::agg_req::Aggregations;
+    use tantivy::aggregation::intermediate_agg_result::IntermediateAggregationResults;
     use tantivy::collector::Collector;
     use tantivy::TantivyDocument;
 
@@ -1498,6 +1531,77 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_aggregation_only_collector() {
+        let index = make_index();
+        let reader = index.reader().unwrap();
+        let searcher = reader.searcher();
+        let dataset = sort_dataset();
+
+        let aggregation_request = r#"{"sort1_stats": {"stats": {"field": "sort1"}}}"#;
+        let make_aggregation_request = |max_hits: u64| SearchRequest {
+            aggregation_request: Some(aggregation_request.to_string()),
+            ..make_request(max_hits, "_score,-sort2")
+        };
+        let aggregation_only_collector = super::make_collector_for_split(
+            "fake_split_id".to_string(),
+            &MockDocMapper,
+            &make_aggregation_request(0),
+            Default::default(),
+        )
+        .unwrap();
+        assert!(!aggregation_only_collector.collects_hits());
+        assert!(!aggregation_only_collector.requires_scoring());
+        assert_eq!(
+            aggregation_only_collector.fast_field_names(),
+            HashSet::from_iter(["sort1".to_string()])
+        );
+        let aggregation_only_response = searcher
+            .search(&tantivy::query::AllQuery, &aggregation_only_collector)
+            .unwrap();
+        assert_eq!(aggregation_only_response.num_hits, dataset.len() as u64);
+        assert!(aggregation_only_response.partial_hits.is_empty());
+
+        let collector = super::make_collector_for_split(
+            "fake_split_id".to_string(),
+            &MockDocMapper,
+            &make_aggregation_request(5),
+            Default::default(),
+        )
+        .unwrap();
+        assert!(collector.collects_hits());
+        let response = searcher
+            .search(&tantivy::query::AllQuery, &collector)
+            .unwrap();
+        assert_eq!(response.partial_hits.len(), 5);
+        assert_eq!(
+            aggregation_only_response.intermediate_aggregation_result,
+            response.intermediate_aggregation_result
+        );
+
+        let intermediate_aggregation_results: IntermediateAggregationResults =
+            postcard::from_bytes(
+                &aggregation_only_response
+                    .intermediate_aggregation_result
+                    .unwrap(),
+            )
+            .unwrap();
+        let aggregations: Aggregations = serde_json::from_str(aggregation_request).unwrap();
+        let aggregation_results = intermediate_aggregation_results
+            .into_final_result(aggregations, &Default::default())
+            .unwrap();
+        let aggregation_results_json = serde_json::to_value(aggregation_results).unwrap();
+        let sort1_values: Vec<u64> = dataset.iter().filter_map(|(val1, _)| *val1).collect();
+        assert_eq!(
+            aggregation_results_json["sort1_stats"]["count"],
+            sort1_values.len()
+        );
+        assert_eq!(
+            aggregation_results_json["sort1_stats"]["sum"],
+            sort1_values.iter().sum::<u64>() as f64
+        );
+    }
+
     #[test]
     fn test_search_after() {
         let index = make_index();
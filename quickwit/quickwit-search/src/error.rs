@@ -30,6 +30,8 @@ use tokio::task::JoinError;
 #[allow(missing_docs)]
 #[derive(Error, Debug, Serialize, Deserialize, Clone)]
 pub enum SearchError {
+    #[error("could not find document with address `{doc_address}`")]
+    DocNotFound { doc_address: String },
     #[error("could not find indexes matching the IDs `{index_ids:?}`")]
     IndexesNotFound { index_ids: Vec<String> },
     #[error("internal error: `{0}`")]
@@ -42,17 +44,35 @@ pub enum SearchError {
     InvalidQuery(String),
     #[error("storage not found: `{0}`)")]
     StorageResolver(#[from] StorageResolverError),
+    #[error("search timed out: {0}")]
+    Timeout(String),
+    #[error("too many requests")]
+    TooManyRequests,
+}
+
+impl SearchError {
+    /// Returns whether executing the same request on another searcher could succeed. Errors
+    /// caused by the request itself, such as an invalid query, are not retryable.
+    pub fn is_retryable(&self) -> bool {
+        !matches!(
+            self.error_code(),
+            ServiceErrorCode::BadRequest | ServiceErrorCode::NotFound
+        )
+    }
 }
 
 impl ServiceError for SearchError {
     fn error_code(&self) -> ServiceErrorCode {
         match self {
+            SearchError::DocNotFound { .. } => ServiceErrorCode::NotFound,
             SearchError::IndexesNotFound { .. } => ServiceErrorCode::NotFound,
             SearchError::Internal(_) => ServiceErrorCode::Internal,
             SearchError::InvalidAggregationRequest(_) => ServiceErrorCode::BadRequest,
             SearchError::InvalidArgument(_) => ServiceErrorCode::BadRequest,
             SearchError::InvalidQuery(_) => ServiceErrorCode::BadRequest,
             SearchError::StorageResolver(_) => ServiceErrorCode::BadRequest,
+            SearchError::Timeout(_) => ServiceErrorCode::Timeout,
+            SearchError::TooManyRequests => ServiceErrorCode::RateLimited,
         }
     }
 }
@@ -30,7 +30,7 @@ use quickwit_proto::search::{
 use quickwit_storage::Storage;
 use tantivy::query::Query;
 use tantivy::schema::{Document as DocumentTrait, Field, OwnedValue, TantivyDocument, Value};
-use tantivy::{ReloadPolicy, Score, Searcher, SnippetGenerator, Term};
+use tantivy::{DocAddress, ReloadPolicy, Score, Searcher, SnippetGenerator, Term};
 use tracing::{error, Instrument};
 
 use crate::leaf::open_index_with_caches;
@@ -188,6 +188,19 @@ async fn fetch_docs_in_split(
         .reload_policy(ReloadPolicy::Manual)
         .try_into()?;
     let searcher = Arc::new(index_reader.searcher());
+    // Addresses built from user input (e.g. when fetching a single doc by its address) may not
+    // point to an actual document of the split.
+    global_doc_addrs.retain(|global_doc_addr| {
+        let DocAddress {
+            segment_ord,
+            doc_id,
+        } = global_doc_addr.doc_addr;
+        searcher
+            .segment_readers()
+            .get(segment_ord as usize)
+            .map(|segment_reader| doc_id < segment_reader.max_doc())
+            .unwrap_or(false)
+    });
     let fields_snippet_generator_opt = if let Some(snippet_request) = snippet_request_opt {
         Some(create_fields_snippet_generator(&searcher, doc_mapper.clone(), snippet_request).await?)
     } else {
@@ -18,7 +18,6 @@
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
 use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 
 use anyhow::Context;
@@ -33,7 +32,8 @@ use quickwit_proto::search::{
 use quickwit_query::query_ast::QueryAst;
 use quickwit_query::tokenizers::TokenizerManager;
 use quickwit_storage::{
-    wrap_storage_with_cache, BundleStorage, MemorySizedCache, OwnedBytes, SplitCache, Storage,
+    wrap_storage_with_cache, BundleStorage, MemorySizedCache, OwnedBytes, ReadBatchingStorage,
+    SplitCache, Storage, StorageCacheMetrics,
 };
 use tantivy::directory::FileSlice;
 use tantivy::fastfield::FastFieldReaders;
@@ -51,13 +51,20 @@ async fn get_split_footer_from_cache_or_fetch(
     split_and_footer_offsets: &SplitIdAndFooterOffsets,
     footer_cache: &MemorySizedCache<String>,
 ) -> anyhow::Result<OwnedBytes> {
+    let footer_cache_metrics =
+        StorageCacheMetrics::new("splitfooter", index_storage.uri().protocol());
     {
         let possible_val = footer_cache.get(&split_and_footer_offsets.split_id);
         if let Some(footer_data) = possible_val {
+            footer_cache_metrics.record_hit(footer_data.len());
             return Ok(footer_data);
         }
     }
-    let split_file = PathBuf::from(format!("{}.split", split_and_footer_offsets.split_id));
+    footer_cache_metrics.record_miss();
+    let split_file = quickwit_common::split_path(
+        split_and_footer_offsets.split_path_prefix.as_deref(),
+        &split_and_footer_offsets.split_id,
+    );
     let footer_data_opt = index_storage
         .get_slice(
             &split_file,
@@ -89,7 +96,10 @@ pub(crate) async fn open_split_bundle(
     index_storage: Arc<dyn Storage>,
     split_and_footer_offsets: &SplitIdAndFooterOffsets,
 ) -> anyhow::Result<(FileSlice, BundleStorage)> {
-    let split_file = PathBuf::from(format!("{}.split", split_and_footer_offsets.split_id));
+    let split_file = quickwit_common::split_path(
+        split_and_footer_offsets.split_path_prefix.as_deref(),
+        &split_and_footer_offsets.split_id,
+    );
     let footer_data = get_split_footer_from_cache_or_fetch(
         index_storage.clone(),
         split_and_footer_offsets,
@@ -118,7 +128,9 @@ pub(crate) async fn open_split_bundle(
 /// Opens a `tantivy::Index` for the given split with several cache layers:
 /// - A split footer cache given by `SearcherContext.split_footer_cache`.
 /// - A fast fields cache given by `SearcherContext.storage_long_term_cache`.
-/// - An ephemeral unbounded cache directory whose lifetime is tied to the returned `Index`.
+/// - An ephemeral unbounded cache directory whose lifetime is tied to the returned `Index`. In that
+///   case, the concurrent reads issued during the warmup are also batched into a single
+///   `get_slices` call on the split file.
 #[instrument(skip_all, fields(split_footer_start=split_and_footer_offsets.split_footer_start, split_footer_end=split_and_footer_offsets.split_footer_end))]
 pub(crate) async fn open_index_with_caches(
     searcher_context: &SearcherContext,
@@ -127,17 +139,26 @@ pub(crate) async fn open_index_with_caches(
     tokenizer_manager: Option<&TokenizerManager>,
     ephemeral_unbounded_cache: bool,
 ) -> anyhow::Result<Index> {
+    let index_storage: Arc<dyn Storage> = if ephemeral_unbounded_cache {
+        Arc::new(ReadBatchingStorage::new(index_storage))
+    } else {
+        index_storage
+    };
     let (hotcache_bytes, bundle_storage) =
         open_split_bundle(searcher_context, index_storage, split_and_footer_offsets).await?;
 
     let bundle_storage_with_cache = wrap_storage_with_cache(
+        "fastfields",
         searcher_context.fast_fields_cache.clone(),
         Arc::new(bundle_storage),
     );
     let directory = StorageDirectory::new(bundle_storage_with_cache);
 
     let hot_directory = if ephemeral_unbounded_cache {
-        let caching_directory = CachingDirectory::new_unbounded(Arc::new(directory));
+        let mut caching_directory = CachingDirectory::new_unbounded(Arc::new(directory));
+        if let Some(max_num_bytes) = searcher_context.searcher_config.warmup_split_max_num_bytes {
+            caching_directory = caching_directory.with_byte_budget(max_num_bytes.as_u64() as usize);
+        }
         HotDirectory::open(caching_directory, hotcache_bytes.read_bytes()?)?
     } else {
         HotDirectory::open(directory, hotcache_bytes.read_bytes()?)?
@@ -183,7 +204,7 @@ pub(crate) async fn warmup(searcher: &Searcher, warmup_info: &WarmupInfo) -> any
             .instrument(debug_span!("warm_up_term_dicts"));
     let warm_up_fastfields_future = warm_up_fastfields(searcher, &warmup_info.fast_field_names)
         .instrument(debug_span!("warm_up_fastfields"));
-    let warm_up_fieldnorms_future = warm_up_fieldnorms(searcher, warmup_info.field_norms)
+    let warm_up_fieldnorms_future = warm_up_fieldnorms(searcher, &warmup_info.field_norms_fields())
         .instrument(debug_span!("warm_up_fieldnorms"));
     // TODO merge warm_up_postings into warm_up_term_dict_fields
     let warm_up_postings_future = warm_up_postings(searcher, &warmup_info.term_dict_fields)
@@ -307,15 +328,12 @@ async fn warm_up_term_ranges(
     Ok(())
 }
 
-async fn warm_up_fieldnorms(searcher: &Searcher, requires_scoring: bool) -> anyhow::Result<()> {
-    if !requires_scoring {
-        return Ok(());
-    }
+async fn warm_up_fieldnorms(searcher: &Searcher, fields: &HashSet<Field>) -> anyhow::Result<()> {
     let mut warm_up_futures = Vec::new();
-    for field in searcher.schema().fields() {
+    for field in fields {
         for segment_reader in searcher.segment_readers() {
             let fieldnorm_readers = segment_reader.fieldnorms_readers();
-            let file_handle_opt = fieldnorm_readers.get_inner_file().open_read(field.0);
+            let file_handle_opt = fieldnorm_readers.get_inner_file().open_read(*field);
             if let Some(file_handle) = file_handle_opt {
                 warm_up_futures.push(async move { file_handle.read_bytes_async().await })
             }
@@ -343,6 +361,8 @@ async fn leaf_search_single_split(
     }
 
     let split_id = split.split_id.to_string();
+    // The split remains open until the end of the search.
+    let _open_split_permit = searcher_context.open_split_permits.acquire().await;
     let index = open_index_with_caches(
         searcher_context,
         storage,
@@ -372,7 +392,10 @@ async fn leaf_search_single_split(
     warmup_info.merge(collector_warmup_info);
     warmup_info.simplify();
 
+    let warmup_permit_opt = searcher_context.acquire_warmup_permit().await;
     warmup(&searcher, &warmup_info).await?;
+    drop(warmup_permit_opt);
+
     let span = info_span!("tantivy_search");
     let leaf_search_response = crate::run_cpu_intensive(move || {
         let _span_guard = span.enter();
@@ -578,11 +601,7 @@ pub async fn leaf_search(
     let mut leaf_search_single_split_futures: Vec<_> = Vec::with_capacity(splits.len());
 
     for split in splits {
-        let leaf_split_search_permit = searcher_context.leaf_search_split_semaphore
-            .clone()
-            .acquire_owned()
-            .await
-            .expect("Failed to acquire permit. This should never happen! Please, report on https://github.com/quickwit-oss/quickwit/issues.");
+        let leaf_split_search_permit = searcher_context.acquire_split_search_permit().await;
 
         let mut request = (*request).clone();
 
@@ -685,7 +704,7 @@ async fn leaf_search_single_split_wrapper(
         Err(err) => locked_incremental_merge_collector.add_failed_split(SplitSearchError {
             split_id: split.split_id.clone(),
             error: format!("{err}"),
-            retryable_error: true,
+            retryable_error: err.is_retryable(),
         }),
     }
     if let Some(last_hit) = locked_incremental_merge_collector.peek_worst_hit() {
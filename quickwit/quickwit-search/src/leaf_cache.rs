@@ -18,16 +18,28 @@
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
 use std::ops::Bound;
+use std::time::Duration;
 
 use prost::Message;
 use quickwit_proto::search::{
     CountHits, LeafSearchResponse, SearchRequest, SplitIdAndFooterOffsets,
 };
 use quickwit_storage::{MemorySizedCache, OwnedBytes};
+use tokio::time::Instant;
+
+/// Number of bytes of the expiration deadline prepended to the cached responses.
+const DEADLINE_NUM_BYTES: usize = std::mem::size_of::<u64>();
 
 /// A cache to memoize `leaf_search_single_split` results.
+///
+/// Splits are immutable, so an entry stays valid as long as the split exists. Entries are
+/// nonetheless dropped after `ttl_opt`, if set, to bound the lifetime of the results of splits that
+/// have since been deleted.
 pub struct LeafSearchCache {
     content: MemorySizedCache<CacheKey>,
+    ttl_opt: Option<Duration>,
+    /// Reference point of the expiration deadlines of the cached entries.
+    created_at: Instant,
 }
 
 // TODO we could be smarter about search_after. If we have a cached request with a search_after
@@ -46,23 +58,33 @@ pub struct LeafSearchCache {
 // queries which vary only by search_after.
 
 impl LeafSearchCache {
-    pub fn new(capacity: usize) -> LeafSearchCache {
+    pub fn new(capacity: usize, ttl_opt: Option<Duration>) -> LeafSearchCache {
         LeafSearchCache {
             content: MemorySizedCache::with_capacity_in_bytes(
                 capacity,
                 &quickwit_storage::STORAGE_METRICS.partial_request_cache,
             ),
+            ttl_opt,
+            created_at: Instant::now(),
         }
     }
+
     pub fn get(
         &self,
         split_info: SplitIdAndFooterOffsets,
         search_request: SearchRequest,
     ) -> Option<LeafSearchResponse> {
         let key = CacheKey::from_split_meta_and_request(split_info, search_request);
-        let encoded_result = self.content.get(&key)?;
+        let encoded_entry = self.content.get(&key)?;
+        let (deadline_bytes, encoded_result) =
+            encoded_entry.as_slice().split_at(DEADLINE_NUM_BYTES);
+        let deadline_millis = u64::from_le_bytes(deadline_bytes.try_into().ok()?);
+
+        if self.elapsed_millis() >= deadline_millis {
+            return None;
+        }
         // this should never fail
-        LeafSearchResponse::decode(&*encoded_result).ok()
+        LeafSearchResponse::decode(encoded_result).ok()
     }
 
     pub fn put(
@@ -73,8 +95,19 @@ impl LeafSearchCache {
     ) {
         let key = CacheKey::from_split_meta_and_request(split_info, search_request);
 
-        let encoded_result = result.encode_to_vec();
-        self.content.put(key, OwnedBytes::new(encoded_result));
+        let deadline_millis = self.ttl_opt.map_or(u64::MAX, |ttl| {
+            self.elapsed_millis().saturating_add(ttl.as_millis() as u64)
+        });
+        let mut encoded_entry = Vec::with_capacity(DEADLINE_NUM_BYTES + result.encoded_len());
+        encoded_entry.extend_from_slice(&deadline_millis.to_le_bytes());
+        result
+            .encode(&mut encoded_entry)
+            .expect("vec should have enough capacity");
+        self.content.put(key, OwnedBytes::new(encoded_entry));
+    }
+
+    fn elapsed_millis(&self) -> u64 {
+        self.created_at.elapsed().as_millis() as u64
     }
 }
 
@@ -190,6 +223,8 @@ impl std::ops::RangeBounds<i64> for Range {
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use quickwit_proto::search::{
         LeafSearchResponse, PartialHit, SearchRequest, SortValue, SplitIdAndFooterOffsets,
     };
@@ -198,7 +233,7 @@ mod tests {
 
     #[test]
     fn test_leaf_search_cache_no_timestamp() {
-        let cache = LeafSearchCache::new(64_000_000);
+        let cache = LeafSearchCache::new(64_000_000, None);
 
         let split_1 = SplitIdAndFooterOffsets {
             split_id: "split_1".to_string(),
@@ -206,6 +241,7 @@ mod tests {
             split_footer_end: 100,
             timestamp_start: None,
             timestamp_end: None,
+            split_path_prefix: None,
         };
 
         let split_2 = SplitIdAndFooterOffsets {
@@ -214,6 +250,7 @@ mod tests {
             split_footer_end: 100,
             timestamp_start: None,
             timestamp_end: None,
+            split_path_prefix: None,
         };
 
         let query_1 = SearchRequest {
@@ -260,7 +297,7 @@ mod tests {
 
     #[test]
     fn test_leaf_search_cache_timestamp() {
-        let cache = LeafSearchCache::new(64_000_000);
+        let cache = LeafSearchCache::new(64_000_000, None);
 
         let split_1 = SplitIdAndFooterOffsets {
             split_id: "split_1".to_string(),
@@ -268,6 +305,7 @@ mod tests {
             split_footer_end: 100,
             timestamp_start: Some(100),
             timestamp_end: Some(199),
+            split_path_prefix: None,
         };
         let split_2 = SplitIdAndFooterOffsets {
             split_id: "split_2".to_string(),
@@ -275,6 +313,7 @@ mod tests {
             split_footer_end: 100,
             timestamp_start: Some(150),
             timestamp_end: Some(249),
+            split_path_prefix: None,
         };
         let split_3 = SplitIdAndFooterOffsets {
             split_id: "split_3".to_string(),
@@ -282,6 +321,7 @@ mod tests {
             split_footer_end: 100,
             timestamp_start: Some(150),
             timestamp_end: Some(249),
+            split_path_prefix: None,
         };
 
         let query_1 = SearchRequest {
@@ -361,4 +401,40 @@ mod tests {
         assert!(cache.get(split_3.clone(), query_2).is_none());
         assert!(cache.get(split_3, query_2bis).is_some());
     }
+
+    #[tokio::test]
+    async fn test_leaf_search_cache_ttl() {
+        tokio::time::pause();
+        let cache = LeafSearchCache::new(64_000_000, Some(Duration::from_secs(60)));
+
+        let split = SplitIdAndFooterOffsets {
+            split_id: "split_1".to_string(),
+            split_footer_start: 0,
+            split_footer_end: 100,
+            timestamp_start: None,
+            timestamp_end: None,
+            split_path_prefix: None,
+        };
+        let query = SearchRequest {
+            index_id_patterns: vec!["test-idx".to_string()],
+            query_ast: "test".to_string(),
+            max_hits: 10,
+            ..Default::default()
+        };
+        let result = LeafSearchResponse {
+            num_hits: 1234,
+            ..Default::default()
+        };
+        cache.put(split.clone(), query.clone(), result.clone());
+
+        tokio::time::advance(Duration::from_secs(59)).await;
+        assert_eq!(cache.get(split.clone(), query.clone()).unwrap(), result);
+
+        tokio::time::advance(Duration::from_secs(1)).await;
+        assert!(cache.get(split.clone(), query.clone()).is_none());
+
+        // Putting the entry again refreshes its deadline.
+        cache.put(split.clone(), query.clone(), result.clone());
+        assert_eq!(cache.get(split, query).unwrap(), result);
+    }
 }
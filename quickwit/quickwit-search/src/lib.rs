@@ -31,15 +31,20 @@ mod filters;
 mod find_trace_ids_collector;
 mod leaf;
 mod leaf_cache;
+mod list_facets;
 mod list_fields;
 mod list_fields_cache;
 mod list_terms;
 mod retry;
 mod root;
+mod schema_compatibility;
 mod scroll_context;
+mod search_batch;
 mod search_job_placer;
+mod search_permits;
 mod search_response_rest;
 mod search_stream;
+mod searcher_health;
 mod service;
 mod thread_pool;
 
@@ -65,11 +70,11 @@ pub use find_trace_ids_collector::FindTraceIdsCollector;
 use quickwit_config::SearcherConfig;
 use quickwit_doc_mapper::tag_pruning::TagFilterAst;
 use quickwit_metastore::{
-    ListSplitsQuery, ListSplitsRequestExt, MetastoreServiceStreamSplitsExt, SplitMetadata,
+    ListSplitsQuery, ListSplitsRequestExt, MetastoreServiceStreamSplitsExt, Split, SplitMetadata,
     SplitState,
 };
 use quickwit_proto::search::{PartialHit, SearchRequest, SearchResponse, SplitIdAndFooterOffsets};
-use quickwit_proto::types::IndexUid;
+use quickwit_proto::types::{IndexUid, SplitId};
 use quickwit_storage::StorageResolver;
 pub use service::SearcherContext;
 use tantivy::DocAddress;
@@ -81,9 +86,15 @@ pub use crate::cluster_client::ClusterClient;
 pub use crate::error::{parse_grpc_error, SearchError};
 use crate::fetch_docs::fetch_docs;
 use crate::leaf::leaf_search;
-pub use crate::root::{jobs_to_leaf_requests, root_search, IndexMetasForLeafSearch, SearchJob};
+pub use crate::list_facets::{leaf_list_facets, root_list_facets, MAX_NUM_FACETS};
+pub use crate::root::{
+    jobs_to_leaf_requests, root_fetch_doc, root_search, root_validate_query,
+    root_validate_query_batch, IndexMetasForLeafSearch, SearchJob,
+};
+pub use crate::search_batch::root_search_batch;
 pub use crate::search_job_placer::{Job, SearchJobPlacer};
-pub use crate::search_response_rest::SearchResponseRest;
+pub use crate::search_permits::{OpenSplitPermit, OpenSplitPermits, SearchPermits};
+pub use crate::search_response_rest::{NumHitsRelation, SearchResponseRest};
 pub use crate::search_stream::root_search_stream;
 pub use crate::service::{MockSearchService, SearchService, SearchServiceImpl};
 use crate::thread_pool::run_cpu_intensive;
@@ -164,6 +175,7 @@ fn extract_split_and_footer_offsets(split_metadata: &SplitMetadata) -> SplitIdAn
             .time_range
             .as_ref()
             .map(|time_range| *time_range.end()),
+        split_path_prefix: split_metadata.split_path_prefix.clone(),
     }
 }
 
@@ -175,6 +187,29 @@ async fn list_relevant_splits(
     tags_filter_opt: Option<TagFilterAst>,
     metastore: &mut MetastoreServiceClient,
 ) -> crate::Result<Vec<SplitMetadata>> {
+    let splits_metadata: Vec<SplitMetadata> = list_relevant_published_splits(
+        index_uids,
+        start_timestamp,
+        end_timestamp,
+        tags_filter_opt,
+        metastore,
+    )
+    .await?
+    .into_iter()
+    .map(|split| split.split_metadata)
+    .collect();
+    Ok(splits_metadata)
+}
+
+/// Same as [`list_relevant_splits`], but returns the splits along with their state and
+/// timestamps, such as their publish timestamp.
+async fn list_relevant_published_splits(
+    index_uids: Vec<IndexUid>,
+    start_timestamp: Option<i64>,
+    end_timestamp: Option<i64>,
+    tags_filter_opt: Option<TagFilterAst>,
+    metastore: &mut MetastoreServiceClient,
+) -> crate::Result<Vec<Split>> {
     let mut query =
         ListSplitsQuery::try_from_index_uids(index_uids)?.with_split_state(SplitState::Published);
 
@@ -188,12 +223,30 @@ async fn list_relevant_splits(
         query = query.with_tags_filter(tags_filter);
     }
     let list_splits_request = ListSplitsRequest::try_from_list_splits_query(query)?;
-    let splits_metadata: Vec<SplitMetadata> = metastore
+    let splits: Vec<Split> = metastore
         .list_splits(list_splits_request)
         .await?
-        .collect_splits_metadata()
+        .collect_splits()
         .await?;
-    Ok(splits_metadata)
+    Ok(splits)
+}
+
+/// Lists the published splits of the given indexes whose ID is listed in `split_ids`.
+async fn list_published_splits_by_id(
+    index_uids: Vec<IndexUid>,
+    split_ids: &[SplitId],
+    metastore: &mut MetastoreServiceClient,
+) -> crate::Result<Vec<Split>> {
+    let query = ListSplitsQuery::try_from_index_uids(index_uids)?
+        .with_split_state(SplitState::Published)
+        .with_split_ids(split_ids);
+    let list_splits_request = ListSplitsRequest::try_from_list_splits_query(query)?;
+    let splits: Vec<Split> = metastore
+        .list_splits(list_splits_request)
+        .await?
+        .collect_splits()
+        .await?;
+    Ok(splits)
 }
 
 /// Converts a Tantivy `NamedFieldDocument` into a json string using the
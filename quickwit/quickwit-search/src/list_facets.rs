@@ -0,0 +1,437 @@
+// Copyright (C) 2024 Quickwit, Inc.
+//
+// Quickwit is offered under the AGPL v3.0 and as commercial software.
+// For commercial licensing, contact us at hello@quickwit.io.
+//
+// AGPL:
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as
+// published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::{HashMap, HashSet};
+use std::ops::Bound;
+use std::sync::Arc;
+
+use anyhow::Context;
+use futures::future::try_join_all;
+use itertools::{Either, Itertools};
+use quickwit_common::PrettySample;
+use quickwit_config::build_doc_mapper;
+use quickwit_doc_mapper::{NamedField, TermRange};
+use quickwit_metastore::{
+    ListIndexesMetadataResponseExt, ListSplitsQuery, ListSplitsRequestExt,
+    MetastoreServiceStreamSplitsExt, SplitMetadata, SplitState,
+};
+use quickwit_proto::metastore::{
+    ListIndexesMetadataRequest, ListSplitsRequest, MetastoreService, MetastoreServiceClient,
+};
+use quickwit_proto::search::{
+    FacetCount, LeafListFacetsRequest, LeafListFacetsResponse, ListFacetsRequest,
+    ListFacetsResponse, SplitIdAndFooterOffsets, SplitSearchError,
+};
+use quickwit_proto::types::IndexUid;
+use quickwit_storage::Storage;
+use tantivy::schema::{FieldType, Schema};
+use tantivy::{ReloadPolicy, Term};
+use tracing::{error, info, instrument};
+
+use crate::leaf::open_index_with_caches;
+use crate::root::check_all_index_metadata_found;
+use crate::{ClusterClient, SearchError, SearchJob, SearcherContext};
+
+/// Maximum number of facets returned by a list facets request.
+pub const MAX_NUM_FACETS: usize = 1_000;
+
+/// Maximum number of distinct terms read from the term dictionary of a segment. Terms are read
+/// in lexicographic order, so when a segment holds more distinct terms matching the prefix, the
+/// response is flagged as truncated.
+const MAX_NUM_TERMS_PER_SEGMENT: u64 = 100_000;
+
+/// Performs a distributed list facets.
+/// 1. Sends leaf requests over gRPC to multiple leaf nodes.
+/// 2. Merges the facet counts.
+/// 3. Builds the response and returns.
+#[instrument(skip(list_facets_request, cluster_client, metastore))]
+pub async fn root_list_facets(
+    list_facets_request: &ListFacetsRequest,
+    mut metastore: MetastoreServiceClient,
+    cluster_client: &ClusterClient,
+) -> crate::Result<ListFacetsResponse> {
+    let start_instant = tokio::time::Instant::now();
+    let list_indexes_metadata_request = if list_facets_request.index_id_patterns.is_empty() {
+        ListIndexesMetadataRequest::all()
+    } else {
+        ListIndexesMetadataRequest {
+            index_id_patterns: list_facets_request.index_id_patterns.clone(),
+        }
+    };
+    let indexes_metadata = metastore
+        .list_indexes_metadata(list_indexes_metadata_request)
+        .await?
+        .deserialize_indexes_metadata()?;
+    check_all_index_metadata_found(
+        &indexes_metadata[..],
+        &list_facets_request.index_id_patterns[..],
+    )?;
+    // The request contains a wildcard, but couldn't find any index.
+    if indexes_metadata.is_empty() {
+        return Ok(ListFacetsResponse::default());
+    }
+    for index_metadata in &indexes_metadata {
+        let index_config = &index_metadata.index_config;
+        let doc_mapper = build_doc_mapper(&index_config.doc_mapping, &index_config.search_settings)
+            .map_err(|err| {
+                SearchError::Internal(format!("failed to build doc mapper. cause: {err}"))
+            })?;
+        resolve_facet_field(&doc_mapper.schema(), &list_facets_request.field)?;
+    }
+    let index_uids: Vec<IndexUid> = indexes_metadata
+        .iter()
+        .map(|index_metadata| index_metadata.index_uid.clone())
+        .collect();
+    let index_uid_to_index_uri: HashMap<IndexUid, String> = indexes_metadata
+        .iter()
+        .map(|index_metadata| {
+            (
+                index_metadata.index_uid.clone(),
+                index_metadata.index_uri().to_string(),
+            )
+        })
+        .collect();
+    let query =
+        ListSplitsQuery::try_from_index_uids(index_uids)?.with_split_state(SplitState::Published);
+    let list_splits_request = ListSplitsRequest::try_from_list_splits_query(query)?;
+    let split_metadatas: Vec<SplitMetadata> = metastore
+        .list_splits(list_splits_request)
+        .await?
+        .collect_splits_metadata()
+        .await?;
+
+    let jobs: Vec<SearchJob> = split_metadatas.iter().map(SearchJob::from).collect();
+    let assigned_leaf_search_jobs = cluster_client
+        .search_job_placer
+        .assign_jobs(jobs, &HashSet::default())
+        .await?;
+    let mut leaf_request_tasks = Vec::new();
+
+    for (client, client_jobs) in assigned_leaf_search_jobs {
+        let leaf_requests =
+            jobs_to_leaf_requests(list_facets_request, &index_uid_to_index_uri, client_jobs)?;
+        for leaf_request in leaf_requests {
+            leaf_request_tasks.push(cluster_client.leaf_list_facets(leaf_request, client.clone()));
+        }
+    }
+    let leaf_responses: Vec<LeafListFacetsResponse> = try_join_all(leaf_request_tasks).await?;
+
+    let failed_splits: Vec<&SplitSearchError> = leaf_responses
+        .iter()
+        .flat_map(|leaf_response| &leaf_response.failed_splits)
+        .collect();
+
+    if !failed_splits.is_empty() {
+        error!(failed_splits = ?failed_splits, "leaf list facets response contains at least one failed split");
+        let errors: String = failed_splits
+            .iter()
+            .map(|split_error| split_error.to_string())
+            .join(", ");
+        return Err(SearchError::Internal(errors));
+    }
+    let truncated = leaf_responses
+        .iter()
+        .any(|leaf_response| leaf_response.truncated);
+    let mut facets = merge_facet_counts(
+        leaf_responses
+            .into_iter()
+            .flat_map(|leaf_response| leaf_response.facets)
+            .map(|facet_count| (facet_count.value, facet_count.num_docs)),
+    );
+    let max_facets = (list_facets_request.max_facets as usize).min(MAX_NUM_FACETS);
+    facets.truncate(max_facets);
+
+    Ok(ListFacetsResponse {
+        facets,
+        truncated,
+        elapsed_time_micros: start_instant.elapsed().as_micros() as u64,
+    })
+}
+
+/// Builds a list of [`LeafListFacetsRequest`], one per index, from a list of [`SearchJob`].
+fn jobs_to_leaf_requests(
+    request: &ListFacetsRequest,
+    index_uid_to_uri: &HashMap<IndexUid, String>,
+    jobs: Vec<SearchJob>,
+) -> crate::Result<Vec<LeafListFacetsRequest>> {
+    let mut leaf_requests = Vec::new();
+    // Group jobs by index uid.
+    for (index_uid, job_group) in &jobs.into_iter().group_by(|job| job.index_uid.clone()) {
+        let index_uri = index_uid_to_uri.get(&index_uid).ok_or_else(|| {
+            SearchError::Internal(format!(
+                "received list facets job for an unknown index {index_uid}. it should never happen"
+            ))
+        })?;
+        let leaf_request = LeafListFacetsRequest {
+            list_facets_request: Some(request.clone()),
+            index_uri: index_uri.to_string(),
+            split_offsets: job_group.into_iter().map(|job| job.offsets).collect(),
+        };
+        leaf_requests.push(leaf_request);
+    }
+    Ok(leaf_requests)
+}
+
+/// `leaf` step of list facets: counts the values of a field across the given splits.
+///
+/// The counts of all the values read are returned, sorted by decreasing number of documents
+/// and then by value, so that the root can merge exact counts.
+#[instrument(skip_all, fields(field = request.field))]
+pub async fn leaf_list_facets(
+    searcher_context: Arc<SearcherContext>,
+    request: &ListFacetsRequest,
+    index_storage: Arc<dyn Storage>,
+    splits: &[SplitIdAndFooterOffsets],
+) -> crate::Result<LeafListFacetsResponse> {
+    info!(split_offsets = ?PrettySample::new(splits, 5));
+    let leaf_list_facets_single_split_futures = splits.iter().map(|split| {
+        let searcher_context = searcher_context.clone();
+        let index_storage = index_storage.clone();
+        async move {
+            let _leaf_split_search_permit = searcher_context.acquire_split_search_permit().await;
+            leaf_list_facets_single_split(&searcher_context, request, index_storage, split)
+                .await
+                .map_err(|error| (split.split_id.clone(), error))
+        }
+    });
+    let split_results = futures::future::join_all(leaf_list_facets_single_split_futures).await;
+
+    let (split_facet_counts, errors): (Vec<SplitFacetCounts>, Vec<(String, SearchError)>) =
+        split_results
+            .into_iter()
+            .partition_map(|split_result| match split_result {
+                Ok(split_facet_counts) => Either::Left(split_facet_counts),
+                Err(error) => Either::Right(error),
+            });
+    let truncated = split_facet_counts
+        .iter()
+        .any(|split_facet_counts| split_facet_counts.truncated);
+    let facets = merge_facet_counts(
+        split_facet_counts
+            .into_iter()
+            .flat_map(|split_facet_counts| split_facet_counts.facet_counts),
+    );
+    let failed_splits = errors
+        .into_iter()
+        .map(|(split_id, error)| SplitSearchError {
+            split_id,
+            error: error.to_string(),
+            retryable_error: true,
+        })
+        .collect();
+    Ok(LeafListFacetsResponse {
+        facets,
+        truncated,
+        failed_splits,
+        num_attempted_splits: splits.len() as u64,
+    })
+}
+
+/// Merges facet counts, summing the number of documents of identical values, and sorts them by
+/// decreasing number of documents and then by value.
+fn merge_facet_counts(facet_counts: impl IntoIterator<Item = (String, u64)>) -> Vec<FacetCount> {
+    let mut merged_facet_counts: HashMap<String, u64> = HashMap::new();
+
+    for (value, num_docs) in facet_counts {
+        *merged_facet_counts.entry(value).or_default() += num_docs;
+    }
+    let mut facet_counts: Vec<FacetCount> = merged_facet_counts
+        .into_iter()
+        .map(|(value, num_docs)| FacetCount { value, num_docs })
+        .collect();
+    facet_counts.sort_unstable_by(|left, right| {
+        right
+            .num_docs
+            .cmp(&left.num_docs)
+            .then_with(|| left.value.cmp(&right.value))
+    });
+    facet_counts
+}
+
+fn resolve_facet_field(schema: &Schema, field_name: &str) -> crate::Result<NamedField> {
+    let field = schema.get_field(field_name).map_err(|_| {
+        SearchError::InvalidArgument(format!(
+            "failed to list facets of `{field_name}`, field doesn't exist"
+        ))
+    })?;
+    let field_entry = schema.get_field_entry(field);
+
+    if !field_entry.is_indexed() || !matches!(field_entry.field_type(), FieldType::Str(_)) {
+        return Err(SearchError::InvalidArgument(format!(
+            "failed to list facets of `{field_name}`, field must be an indexed text field"
+        )));
+    }
+    Ok(NamedField {
+        name: field_name.to_string(),
+        field,
+        field_type: field_entry.field_type().clone(),
+    })
+}
+
+/// Returns the range of terms starting with `prefix_opt`.
+fn facet_term_range(named_field: &NamedField, prefix_opt: Option<&str>) -> TermRange {
+    let limit = Some(MAX_NUM_TERMS_PER_SEGMENT);
+
+    let Some(prefix) = prefix_opt.filter(|prefix| !prefix.is_empty()) else {
+        return TermRange {
+            start: Bound::Unbounded,
+            end: Bound::Unbounded,
+            limit,
+        };
+    };
+    let start_term = Term::from_field_text(named_field.field, prefix);
+    let mut end_bytes = prefix.as_bytes().to_vec();
+
+    // The end of the range is the smallest value greater than all the values starting with the
+    // prefix.
+    while let Some(last_byte) = end_bytes.pop() {
+        if last_byte != u8::MAX {
+            end_bytes.push(last_byte + 1);
+            let mut end_term = Term::from_field_text(named_field.field, "");
+            end_term.append_bytes(&end_bytes);
+
+            return TermRange {
+                start: Bound::Included(start_term),
+                end: Bound::Excluded(end_term),
+                limit,
+            };
+        }
+    }
+    TermRange {
+        start: Bound::Included(start_term),
+        end: Bound::Unbounded,
+        limit,
+    }
+}
+
+fn value_bytes_bound(term_bound: &Bound<Term>) -> Bound<&[u8]> {
+    match term_bound {
+        Bound::Included(term) => Bound::Included(term.serialized_value_bytes()),
+        Bound::Excluded(term) => Bound::Excluded(term.serialized_value_bytes()),
+        Bound::Unbounded => Bound::Unbounded,
+    }
+}
+
+/// Facet counts of a single split.
+struct SplitFacetCounts {
+    facet_counts: HashMap<String, u64>,
+    /// Whether a segment held more than `MAX_NUM_TERMS_PER_SEGMENT` terms in the range.
+    truncated: bool,
+}
+
+/// Apply a leaf list facets on a single split.
+#[instrument(skip_all, fields(split_id = split.split_id))]
+async fn leaf_list_facets_single_split(
+    searcher_context: &SearcherContext,
+    request: &ListFacetsRequest,
+    index_storage: Arc<dyn Storage>,
+    split: &SplitIdAndFooterOffsets,
+) -> crate::Result<SplitFacetCounts> {
+    let index = open_index_with_caches(searcher_context, index_storage, split, None, true).await?;
+    let named_field = resolve_facet_field(&index.schema(), &request.field)?;
+    let term_range = facet_term_range(&named_field, request.prefix.as_deref());
+    let reader = index
+        .reader_builder()
+        .reload_policy(ReloadPolicy::Manual)
+        .try_into()?;
+    let searcher = reader.searcher();
+
+    let start_bound = value_bytes_bound(&term_range.start);
+    let end_bound = value_bytes_bound(&term_range.end);
+    let mut facet_counts: HashMap<String, u64> = HashMap::new();
+    let mut truncated = false;
+
+    for segment_reader in searcher.segment_readers() {
+        let inverted_index = segment_reader.inverted_index(named_field.field)?;
+        let dict = inverted_index.terms();
+        // Reads one extra term to detect whether the segment holds more terms than the limit.
+        let limit = term_range.limit.map(|limit| limit + 1);
+        dict.file_slice_for_range((start_bound, end_bound), limit)
+            .read_bytes_async()
+            .await
+            .with_context(|| "failed to load sstable range")?;
+
+        let mut range = dict.range();
+        if let Some(limit) = limit {
+            range = range.limit(limit);
+        }
+        match start_bound {
+            Bound::Included(start_bytes) => range = range.ge(start_bytes),
+            Bound::Excluded(start_bytes) => range = range.gt(start_bytes),
+            Bound::Unbounded => {}
+        }
+        match end_bound {
+            Bound::Included(end_bytes) => range = range.le(end_bytes),
+            Bound::Excluded(end_bytes) => range = range.lt(end_bytes),
+            Bound::Unbounded => {}
+        }
+        let mut stream = range
+            .into_stream()
+            .with_context(|| "failed to create stream over sstable")?;
+        let mut num_terms: u64 = 0;
+
+        while stream.advance() {
+            num_terms += 1;
+
+            if term_range.limit.is_some_and(|limit| num_terms > limit) {
+                truncated = true;
+                break;
+            }
+            let value = String::from_utf8_lossy(stream.key()).into_owned();
+            *facet_counts.entry(value).or_default() += stream.value().doc_freq as u64;
+        }
+    }
+    Ok(SplitFacetCounts {
+        facet_counts,
+        truncated,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_facet_counts() {
+        let facet_counts = [
+            ("info".to_string(), 3),
+            ("warn".to_string(), 2),
+            ("error".to_string(), 4),
+            ("warn".to_string(), 2),
+        ];
+        let merged_facet_counts = merge_facet_counts(facet_counts);
+        assert_eq!(
+            merged_facet_counts,
+            [
+                FacetCount {
+                    value: "error".to_string(),
+                    num_docs: 4,
+                },
+                FacetCount {
+                    value: "warn".to_string(),
+                    num_docs: 4,
+                },
+                FacetCount {
+                    value: "info".to_string(),
+                    num_docs: 3,
+                },
+            ]
+        );
+    }
+}
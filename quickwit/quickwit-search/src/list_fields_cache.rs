@@ -86,6 +86,7 @@ mod tests {
             split_footer_end: 100,
             timestamp_start: None,
             timestamp_end: None,
+            split_path_prefix: None,
         };
 
         let split_2 = SplitIdAndFooterOffsets {
@@ -94,6 +95,7 @@ mod tests {
             split_footer_end: 100,
             timestamp_start: None,
             timestamp_end: None,
+            split_path_prefix: None,
         };
 
         let result = ListFieldsEntryResponse {
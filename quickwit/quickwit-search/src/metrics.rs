@@ -28,6 +28,8 @@ pub struct SearchMetrics {
     pub leaf_searches_splits_total: IntCounter,
     pub leaf_search_split_duration_secs: Histogram,
     pub active_search_threads_count: IntGauge,
+    pub leaf_search_open_splits: IntGauge,
+    pub leaf_search_open_split_waiters: IntGauge,
 }
 
 impl Default for SearchMetrics {
@@ -49,6 +51,17 @@ impl Default for SearchMetrics {
                 "Number of threads in use in the CPU thread pool",
                 "quickwit_search",
             ),
+            leaf_search_open_splits: new_gauge(
+                "leaf_search_open_splits",
+                "Number of splits currently opened by leaf searches.",
+                "quickwit_search",
+            ),
+            leaf_search_open_split_waiters: new_gauge(
+                "leaf_search_open_split_waiters",
+                "Number of leaf searches waiting for an open split slot, once `max_open_splits` \
+                 is reached.",
+                "quickwit_search",
+            ),
         }
     }
 }
@@ -28,7 +28,7 @@ use crate::SearchError;
 pub struct LeafSearchRetryPolicy {}
 
 impl RetryPolicy<LeafSearchRequest, LeafSearchResponse, SearchError> for LeafSearchRetryPolicy {
-    // Build a retry request on failing split ids only.
+    // Build a retry request on retryable failing split ids only.
     fn retry_request(
         &self,
         mut request: LeafSearchRequest,
@@ -36,18 +36,19 @@ impl RetryPolicy<LeafSearchRequest, LeafSearchResponse, SearchError> for LeafSea
     ) -> Option<LeafSearchRequest> {
         match response_res {
             Ok(response) => {
-                if response.failed_splits.is_empty() {
-                    return None;
-                }
                 request.split_offsets.retain(|split_metadata| {
-                    response
-                        .failed_splits
-                        .iter()
-                        .any(|failed_split| failed_split.split_id == split_metadata.split_id)
+                    response.failed_splits.iter().any(|failed_split| {
+                        failed_split.retryable_error
+                            && failed_split.split_id == split_metadata.split_id
+                    })
                 });
+                if request.split_offsets.is_empty() {
+                    return None;
+                }
                 Some(request)
             }
-            Err(_) => Some(request),
+            Err(error) if error.is_retryable() => Some(request),
+            Err(_) => None,
         }
     }
 }
@@ -81,6 +82,7 @@ mod tests {
                     split_footer_start: 0,
                     timestamp_start: None,
                     timestamp_end: None,
+                    split_path_prefix: None,
                 },
                 SplitIdAndFooterOffsets {
                     split_id: "split_2".to_string(),
@@ -88,6 +90,7 @@ mod tests {
                     split_footer_start: 0,
                     timestamp_start: None,
                     timestamp_end: None,
+                    split_path_prefix: None,
                 },
             ],
         }
@@ -117,6 +120,35 @@ mod tests {
         assert!(retry_policy.retry_request(request, &response_res).is_none())
     }
 
+    #[test]
+    fn test_should_not_retry_on_non_retryable_error() {
+        let retry_policy = LeafSearchRetryPolicy {};
+        let request = mock_leaf_search_request();
+        let response_res = Result::<LeafSearchResponse, SearchError>::Err(
+            SearchError::InvalidQuery("test".to_string()),
+        );
+        assert!(retry_policy.retry_request(request, &response_res).is_none());
+    }
+
+    #[test]
+    fn test_should_not_retry_on_non_retryable_failed_splits() {
+        let retry_policy = LeafSearchRetryPolicy {};
+        let request = mock_leaf_search_request();
+        let split_error = SplitSearchError {
+            error: "error".to_string(),
+            split_id: "split_2".to_string(),
+            retryable_error: false,
+        };
+        let response_res = Ok(LeafSearchResponse {
+            num_hits: 0,
+            partial_hits: Vec::new(),
+            failed_splits: vec![split_error],
+            num_attempted_splits: 1,
+            ..Default::default()
+        });
+        assert!(retry_policy.retry_request(request, &response_res).is_none());
+    }
+
     #[test]
     fn test_should_retry_on_failed_splits() {
         let retry_policy = LeafSearchRetryPolicy {};
@@ -18,31 +18,38 @@
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
 use std::collections::{HashMap, HashSet};
+use std::num::NonZeroU64;
+use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::Context;
-use futures::future::try_join_all;
+use futures::future::{join_all, try_join_all};
+use futures::StreamExt;
 use itertools::Itertools;
 use quickwit_common::shared_consts::{DELETION_GRACE_PERIOD, SCROLL_BATCH_LEN};
 use quickwit_common::uri::Uri;
 use quickwit_common::PrettySample;
-use quickwit_config::build_doc_mapper;
+use quickwit_config::{build_doc_mapper, validate_index_id_pattern};
 use quickwit_doc_mapper::tag_pruning::extract_tags_from_query;
-use quickwit_doc_mapper::DYNAMIC_FIELD_NAME;
-use quickwit_metastore::{IndexMetadata, ListIndexesMetadataResponseExt, SplitMetadata};
+use quickwit_doc_mapper::{DocMapper, DYNAMIC_FIELD_NAME};
+use quickwit_metastore::{
+    FieldRange, IndexMetadata, IndexMetadataResponseExt, ListIndexesMetadataResponseExt, Split,
+    SplitMetadata,
+};
 use quickwit_proto::metastore::{
-    ListIndexesMetadataRequest, MetastoreService, MetastoreServiceClient,
+    IndexMetadataRequest, ListIndexesMetadataRequest, MetastoreService, MetastoreServiceClient,
 };
 use quickwit_proto::search::{
-    FetchDocsRequest, FetchDocsResponse, Hit, LeafHit, LeafSearchRequest, LeafSearchResponse,
-    PartialHit, SearchRequest, SearchResponse, SnippetRequest, SortDatetimeFormat, SortField,
-    SortValue, SplitIdAndFooterOffsets,
+    FetchDocsRequest, FetchDocsResponse, Hit, HitsRelation, LeafHit, LeafSearchRequest,
+    LeafSearchResponse, PartialHit, SearchRequest, SearchResponse, SnippetRequest,
+    SortDatetimeFormat, SortField, SortValue, SplitIdAndFooterOffsets, SplitSearchError,
 };
 use quickwit_proto::types::{IndexUid, SplitId};
 use quickwit_query::query_ast::{
-    BoolQuery, QueryAst, QueryAstVisitor, RangeQuery, TermQuery, TermSetQuery,
+    BoolQuery, DisMaxQuery, QueryAst, QueryAstVisitor, RangeQuery, TermQuery, TermSetQuery,
 };
 use serde::{Deserialize, Serialize};
+use tantivy::aggregation::agg_req::{AggregationVariants, Aggregations};
 use tantivy::aggregation::agg_result::AggregationResults;
 use tantivy::aggregation::intermediate_agg_result::IntermediateAggregationResults;
 use tantivy::collector::Collector;
@@ -53,12 +60,13 @@ use tracing::{debug, error, info, info_span, instrument};
 use crate::cluster_client::ClusterClient;
 use crate::collector::{make_merge_collector, QuickwitAggregations};
 use crate::find_trace_ids_collector::Span;
+use crate::schema_compatibility::{requested_field_names, validate_field_types_compatibility};
 use crate::scroll_context::{ScrollContext, ScrollKeyAndStartOffset};
 use crate::search_job_placer::Job;
 use crate::service::SearcherContext;
 use crate::{
-    extract_split_and_footer_offsets, list_relevant_splits, SearchError, SearchJobPlacer,
-    SearchServiceClient,
+    extract_split_and_footer_offsets, list_published_splits_by_id, list_relevant_published_splits,
+    list_relevant_splits, GlobalDocAddress, SearchError, SearchJobPlacer, SearchServiceClient,
 };
 
 /// Maximum accepted scroll TTL.
@@ -66,6 +74,9 @@ const MAX_SCROLL_TTL: Duration = Duration::from_secs(DELETION_GRACE_PERIOD.as_se
 
 const SORT_DOC_FIELD_NAMES: &[&str] = &["_shard_doc", "_doc"];
 
+/// Maximum number of groups of queries validated concurrently by [`root_validate_query_batch`].
+const MAX_CONCURRENT_QUERY_VALIDATIONS: usize = 8;
+
 /// SearchJob to be assigned to search clients by the [`SearchJobPlacer`].
 #[derive(Debug, Clone, PartialEq)]
 pub struct SearchJob {
@@ -162,6 +173,7 @@ struct RequestMetadata {
 /// - resolved query ASTs are the same across indexes.
 /// - if a sort field is of type datetime, it must be a datetime field on all indexes. This
 ///   contraint come from the need to support datetime formatting on sort values.
+/// - the fields queried, sorted on, or aggregated on have compatible types across indexes.
 /// Returns the timestamp field, the resolved query AST and the indexes metadatas
 /// needed for leaf search requests.
 /// Note: the requirements on timestamp fields and resolved query ASTs can be lifted
@@ -169,6 +181,38 @@ struct RequestMetadata {
 fn validate_request_and_build_metadata(
     indexes_metadata: &[IndexMetadata],
     search_request: &SearchRequest,
+) -> crate::Result<RequestMetadata> {
+    let doc_mappers = build_doc_mappers(indexes_metadata)?;
+    validate_request_and_build_metadata_with_doc_mappers(
+        indexes_metadata,
+        &doc_mappers,
+        search_request,
+    )
+}
+
+/// Builds the doc mapper of each index, in the order of `indexes_metadata`.
+fn build_doc_mappers(indexes_metadata: &[IndexMetadata]) -> crate::Result<Vec<Arc<dyn DocMapper>>> {
+    indexes_metadata
+        .iter()
+        .map(|index_metadata| {
+            build_doc_mapper(
+                &index_metadata.index_config.doc_mapping,
+                &index_metadata.index_config.search_settings,
+            )
+            .map_err(|err| {
+                SearchError::Internal(format!("failed to build doc mapper. cause: {err}"))
+            })
+        })
+        .collect()
+}
+
+/// Same as [`validate_request_and_build_metadata`] with doc mappers built beforehand, so that
+/// they can be shared by several requests. `doc_mappers` must be in the order of
+/// `indexes_metadata`.
+fn validate_request_and_build_metadata_with_doc_mappers(
+    indexes_metadata: &[IndexMetadata],
+    doc_mappers: &[Arc<dyn DocMapper>],
+    search_request: &SearchRequest,
 ) -> crate::Result<RequestMetadata> {
     validate_sort_by_fields_and_search_after(
         &search_request.sort_fields,
@@ -182,20 +226,40 @@ fn validate_request_and_build_metadata(
     let mut timestamp_field_opt: Option<String> = None;
     let mut sort_fields_is_datetime: HashMap<String, bool> = HashMap::new();
 
-    for index_metadata in indexes_metadata {
-        let doc_mapper = build_doc_mapper(
-            &index_metadata.index_config.doc_mapping,
-            &index_metadata.index_config.search_settings,
-        )
-        .map_err(|err| {
-            SearchError::Internal(format!("failed to build doc mapper. cause: {err}"))
-        })?;
+    let mut indexes_doc_mapper_and_query_ast = Vec::with_capacity(indexes_metadata.len());
+
+    for (index_metadata, doc_mapper) in indexes_metadata.iter().zip(doc_mappers) {
         let query_ast_resolved_for_index = query_ast
             .clone()
             .parse_user_query(doc_mapper.default_search_fields())
             // We convert the error to return a 400 to the user (and not a 500).
             .map_err(|err| SearchError::InvalidQuery(err.to_string()))?;
+        indexes_doc_mapper_and_query_ast.push((
+            index_metadata,
+            doc_mapper,
+            query_ast_resolved_for_index,
+        ));
+    }
+
+    // Type mismatches between indexes are reported before validating the request against each
+    // index, which would fail with a less explicit error.
+    if indexes_doc_mapper_and_query_ast.len() > 1 {
+        let index_schemas: Vec<(&str, Schema)> = indexes_doc_mapper_and_query_ast
+            .iter()
+            .map(|(index_metadata, doc_mapper, _)| (index_metadata.index_id(), doc_mapper.schema()))
+            .collect();
+        let field_names = requested_field_names(
+            indexes_doc_mapper_and_query_ast
+                .iter()
+                .map(|(_, _, query_ast_resolved_for_index)| query_ast_resolved_for_index),
+            search_request,
+        );
+        validate_field_types_compatibility(&index_schemas, &field_names)?;
+    }
 
+    for (index_metadata, doc_mapper, query_ast_resolved_for_index) in
+        indexes_doc_mapper_and_query_ast
+    {
         // Validate uniqueness of resolved query AST.
         if let Some(query_ast_resolved) = &query_ast_resolved_opt {
             if query_ast_resolved != &query_ast_resolved_for_index {
@@ -351,9 +415,41 @@ fn simplify_search_request_for_scroll_api(req: &SearchRequest) -> crate::Result<
         scroll_ttl_secs: None,
         search_after: None,
         count_hits: req.count_hits,
+        timeout_millis: req.timeout_millis,
+        track_total_hits_up_to: req.track_total_hits_up_to,
+        allow_partial_results: req.allow_partial_results,
+        split_ids: req.split_ids.clone(),
+        include_split_publish_timestamp: req.include_split_publish_timestamp,
+        allow_high_cardinality_aggregations: req.allow_high_cardinality_aggregations,
     })
 }
 
+/// Returns the number of hits to report for a request and whether it is exact or a lower bound,
+/// given the number of hits counted by the leaves.
+///
+/// `num_hits_is_exact` is false if the leaves skipped some splits, which only happens when hits
+/// are underestimated.
+pub(crate) fn bound_num_hits(
+    search_request: &SearchRequest,
+    num_hits: u64,
+    num_hits_is_exact: bool,
+) -> (u64, HitsRelation) {
+    if let Some(track_total_hits_up_to) = search_request.track_total_hits_up_to {
+        let track_total_hits_up_to = track_total_hits_up_to as u64;
+        if num_hits > track_total_hits_up_to
+            || (num_hits == track_total_hits_up_to && !num_hits_is_exact)
+        {
+            return (track_total_hits_up_to, HitsRelation::GreaterThanOrEqual);
+        }
+    }
+    let num_hits_relation = if num_hits_is_exact {
+        HitsRelation::Equal
+    } else {
+        HitsRelation::GreaterThanOrEqual
+    };
+    (num_hits, num_hits_relation)
+}
+
 /// Validates sort fields and search after values.
 /// - validate sort fields length.
 /// - search after values must be set for all sort fields.
@@ -517,8 +613,13 @@ async fn search_partial_hits_phase_with_scroll(
     indexes_metas_for_leaf_search: &IndexesMetasForLeafSearch,
     mut search_request: SearchRequest,
     split_metadatas: &[SplitMetadata],
+    split_publish_timestamps: &HashMap<SplitId, i64>,
     cluster_client: &ClusterClient,
-) -> crate::Result<(LeafSearchResponse, Option<ScrollKeyAndStartOffset>)> {
+) -> crate::Result<(
+    LeafSearchResponse,
+    HashMap<IndexUid, u64>,
+    Option<ScrollKeyAndStartOffset>,
+)> {
     let scroll_ttl_opt = get_scroll_ttl_duration(&search_request)?;
 
     if let Some(scroll_ttl) = scroll_ttl_opt {
@@ -528,7 +629,7 @@ async fn search_partial_hits_phase_with_scroll(
         // We increase max hits to add populate the scroll cache.
         search_request.max_hits = SCROLL_BATCH_LEN as u64;
         search_request.scroll_ttl_secs = None;
-        let mut leaf_search_resp = search_partial_hits_phase(
+        let (mut leaf_search_resp, num_hits_per_index) = search_partial_hits_phase(
             searcher_context,
             indexes_metas_for_leaf_search,
             &search_request,
@@ -544,8 +645,11 @@ async fn search_partial_hits_phase_with_scroll(
         let scroll_ctx = ScrollContext {
             indexes_metas_for_leaf_search: indexes_metas_for_leaf_search.clone(),
             split_metadatas: split_metadatas.to_vec(),
+            split_publish_timestamps: split_publish_timestamps.clone(),
             search_request: scroll_context_search_request,
             total_num_hits: leaf_search_resp.num_hits,
+            total_num_hits_is_lower_bound: leaf_search_resp.num_attempted_splits
+                < split_metadatas.len() as u64,
             max_hits_per_page: max_hits,
             cached_partial_hits_start_offset: search_request.start_offset,
             cached_partial_hits,
@@ -562,9 +666,13 @@ async fn search_partial_hits_phase_with_scroll(
         cluster_client
             .put_kv(&scroll_key, &payload, scroll_ttl)
             .await;
-        Ok((leaf_search_resp, Some(scroll_key_and_start_offset)))
+        Ok((
+            leaf_search_resp,
+            num_hits_per_index,
+            Some(scroll_key_and_start_offset),
+        ))
     } else {
-        let leaf_search_resp = search_partial_hits_phase(
+        let (leaf_search_resp, num_hits_per_index) = search_partial_hits_phase(
             searcher_context,
             indexes_metas_for_leaf_search,
             &search_request,
@@ -572,7 +680,7 @@ async fn search_partial_hits_phase_with_scroll(
             cluster_client,
         )
         .await?;
-        Ok((leaf_search_resp, None))
+        Ok((leaf_search_resp, num_hits_per_index, None))
     }
 }
 
@@ -617,6 +725,29 @@ fn get_count_from_metadata(split_metadatas: &[SplitMetadata]) -> Vec<LeafSearchR
         .collect()
 }
 
+/// Builds the leaf search response standing for a leaf search request that failed altogether,
+/// reporting all of its splits as failed.
+fn failed_leaf_search_response(error: &SearchError, split_ids: Vec<String>) -> LeafSearchResponse {
+    let failed_splits = split_ids
+        .into_iter()
+        .map(|split_id| SplitSearchError {
+            error: error.to_string(),
+            split_id,
+            retryable_error: error.is_retryable(),
+        })
+        .collect();
+    LeafSearchResponse {
+        num_hits: 0,
+        partial_hits: Vec::new(),
+        failed_splits,
+        num_attempted_splits: 0,
+        intermediate_aggregation_result: None,
+    }
+}
+
+/// Runs the leaf search phase and merges the leaf responses.
+///
+/// Also returns the number of hits contributed by each index, counted before the merge.
 #[instrument(level = "debug", skip_all)]
 pub(crate) async fn search_partial_hits_phase(
     searcher_context: &SearcherContext,
@@ -624,17 +755,27 @@ pub(crate) async fn search_partial_hits_phase(
     search_request: &SearchRequest,
     split_metadatas: &[SplitMetadata],
     cluster_client: &ClusterClient,
-) -> crate::Result<LeafSearchResponse> {
-    let leaf_search_responses: Vec<LeafSearchResponse> =
+) -> crate::Result<(LeafSearchResponse, HashMap<IndexUid, u64>)> {
+    let (leaf_search_responses, leaf_search_index_uids): (Vec<LeafSearchResponse>, Vec<IndexUid>) =
         if is_metadata_count_request(search_request) {
-            get_count_from_metadata(split_metadatas)
+            let index_uids = split_metadatas
+                .iter()
+                .map(|split_metadata| split_metadata.index_uid.clone())
+                .collect();
+            (get_count_from_metadata(split_metadatas), index_uids)
         } else {
+            let split_id_to_index_uid: HashMap<&str, &IndexUid> = split_metadatas
+                .iter()
+                .map(|split_metadata| (split_metadata.split_id(), &split_metadata.index_uid))
+                .collect();
             let jobs: Vec<SearchJob> = split_metadatas.iter().map(SearchJob::from).collect();
             let assigned_leaf_search_jobs = cluster_client
                 .search_job_placer
                 .assign_jobs(jobs, &HashSet::default())
                 .await?;
             let mut leaf_request_tasks = Vec::new();
+            let mut leaf_request_split_ids: Vec<Vec<String>> = Vec::new();
+            let mut leaf_request_index_uids: Vec<IndexUid> = Vec::new();
             for (client, client_jobs) in assigned_leaf_search_jobs {
                 let leaf_requests = jobs_to_leaf_requests(
                     search_request,
@@ -642,13 +783,55 @@ pub(crate) async fn search_partial_hits_phase(
                     client_jobs,
                 )?;
                 for leaf_request in leaf_requests {
+                    // Leaf requests are built per index, so their first split is enough to
+                    // identify the index they target.
+                    let index_uid = leaf_request
+                        .split_offsets
+                        .first()
+                        .and_then(|split_offsets| {
+                            split_id_to_index_uid.get(split_offsets.split_id.as_str())
+                        })
+                        .map(|index_uid| (*index_uid).clone())
+                        .ok_or_else(|| {
+                            SearchError::Internal(
+                                "leaf search request does not target any known split".to_string(),
+                            )
+                        })?;
+                    leaf_request_index_uids.push(index_uid);
+                    leaf_request_split_ids.push(
+                        leaf_request
+                            .split_offsets
+                            .iter()
+                            .map(|split_offsets| split_offsets.split_id.clone())
+                            .collect(),
+                    );
                     leaf_request_tasks
                         .push(cluster_client.leaf_search(leaf_request, client.clone()));
                 }
             }
-            try_join_all(leaf_request_tasks).await?
+            let leaf_search_responses = if search_request.allow_partial_results {
+                let leaf_search_results = join_all(leaf_request_tasks).await;
+                leaf_search_results
+                    .into_iter()
+                    .zip(leaf_request_split_ids)
+                    .map(|(leaf_search_result, split_ids)| {
+                        leaf_search_result
+                            .unwrap_or_else(|error| failed_leaf_search_response(&error, split_ids))
+                    })
+                    .collect()
+            } else {
+                try_join_all(leaf_request_tasks).await?
+            };
+            (leaf_search_responses, leaf_request_index_uids)
         };
 
+    let mut num_hits_per_index: HashMap<IndexUid, u64> = HashMap::new();
+    for (leaf_search_response, index_uid) in
+        leaf_search_responses.iter().zip(leaf_search_index_uids)
+    {
+        *num_hits_per_index.entry(index_uid).or_default() += leaf_search_response.num_hits;
+    }
+
     // Creates a collector which merges responses into one
     let merge_collector =
         make_merge_collector(search_request, &searcher_context.get_aggregation_limits())?;
@@ -676,10 +859,15 @@ pub(crate) async fn search_partial_hits_phase(
     );
     if !leaf_search_response.failed_splits.is_empty() {
         error!(failed_splits = ?leaf_search_response.failed_splits, "leaf search response contains at least one failed split");
+        // Partial results only make sense if at least one split could be searched.
+        let all_splits_failed = leaf_search_response.failed_splits.len() >= split_metadatas.len();
+        if search_request.allow_partial_results && !all_splits_failed {
+            return Ok((leaf_search_response, num_hits_per_index));
+        }
         let errors: String = leaf_search_response.failed_splits.iter().join(", ");
         return Err(SearchError::Internal(errors));
     }
-    Ok(leaf_search_response)
+    Ok((leaf_search_response, num_hits_per_index))
 }
 
 pub(crate) fn get_snippet_request(search_request: &SearchRequest) -> Option<SnippetRequest> {
@@ -697,6 +885,7 @@ pub(crate) async fn fetch_docs_phase(
     indexes_metas_for_leaf_search: &IndexesMetasForLeafSearch,
     partial_hits: &[PartialHit],
     split_metadatas: &[SplitMetadata],
+    split_publish_timestamps: &HashMap<SplitId, i64>,
     search_request: &SearchRequest,
     cluster_client: &ClusterClient,
 ) -> crate::Result<Vec<Hit>> {
@@ -760,6 +949,7 @@ pub(crate) async fn fetch_docs_phase(
             build_hit_with_position(
                 leaf_hit,
                 &split_id_to_index_id_map,
+                split_publish_timestamps,
                 &hit_order,
                 &sort_field_1_datetime_format_opt,
                 &sort_field_2_datetime_format_opt,
@@ -776,9 +966,67 @@ pub(crate) async fn fetch_docs_phase(
     Ok(hits)
 }
 
+/// Fetches a single document given its global address.
+///
+/// Returns `None` if the split is not one of the published splits of the index, or if the
+/// address does not point to a document of the split.
+#[instrument(skip(metastore, cluster_client))]
+pub async fn root_fetch_doc(
+    index_id: String,
+    doc_address: GlobalDocAddress,
+    mut metastore: MetastoreServiceClient,
+    cluster_client: &ClusterClient,
+) -> crate::Result<Option<Hit>> {
+    let index_metadata = metastore
+        .index_metadata(IndexMetadataRequest::for_index_id(index_id))
+        .await?
+        .deserialize_index_metadata()?;
+    let index_uid = index_metadata.index_uid.clone();
+    let split_metadatas =
+        list_relevant_splits(vec![index_uid.clone()], None, None, None, &mut metastore).await?;
+    let Some(split_metadata) = split_metadatas
+        .into_iter()
+        .find(|split_metadata| split_metadata.split_id == doc_address.split)
+    else {
+        return Ok(None);
+    };
+    let index_config = &index_metadata.index_config;
+    let doc_mapper = build_doc_mapper(&index_config.doc_mapping, &index_config.search_settings)
+        .map_err(|err| {
+            SearchError::Internal(format!("failed to build doc mapper. cause: {err}"))
+        })?;
+    let doc_mapper_str = serde_json::to_string(&doc_mapper).map_err(|err| {
+        SearchError::Internal(format!("failed to serialize doc mapper. cause: {err}"))
+    })?;
+    let indexes_metas_for_leaf_search: IndexesMetasForLeafSearch = HashMap::from([(
+        index_uid,
+        IndexMetasForLeafSearch {
+            index_uri: index_metadata.index_uri().clone(),
+            doc_mapper_str,
+        },
+    )]);
+    let partial_hit = PartialHit {
+        split_id: doc_address.split,
+        segment_ord: doc_address.doc_addr.segment_ord,
+        doc_id: doc_address.doc_addr.doc_id,
+        ..Default::default()
+    };
+    let hits = fetch_docs_phase(
+        &indexes_metas_for_leaf_search,
+        &[partial_hit],
+        &[split_metadata],
+        &HashMap::new(),
+        &SearchRequest::default(),
+        cluster_client,
+    )
+    .await?;
+    Ok(hits.into_iter().next())
+}
+
 fn build_hit_with_position(
     mut leaf_hit: LeafHit,
     split_id_to_index_id_map: &HashMap<&SplitId, &str>,
+    split_publish_timestamps: &HashMap<SplitId, i64>,
     hit_order: &HashMap<(String, u32, u32), usize>,
     sort_field_1_datetime_format_opt: &Option<SortDatetimeFormat>,
     sort_field_2_datetime_format_opt: &Option<SortDatetimeFormat>,
@@ -813,8 +1061,12 @@ fn build_hit_with_position(
     let position = *hit_order.get(&key).expect("hit order must be present");
     let index_id = split_id_to_index_id_map
         .get(&partial_hit_ref.split_id)
-        .map(|split_id| split_id.to_string())
+        .map(|index_id| index_id.to_string())
         .unwrap_or_default();
+    // Only populated if the split publish timestamps were requested.
+    let split_publish_timestamp = split_publish_timestamps
+        .get(&partial_hit_ref.split_id)
+        .copied();
 
     Result::<(usize, Hit), SearchError>::Ok((
         position,
@@ -823,6 +1075,7 @@ fn build_hit_with_position(
             partial_hit: leaf_hit.partial_hit,
             snippet: leaf_hit.leaf_snippet_json,
             index_id,
+            split_publish_timestamp,
         },
     ))
 }
@@ -852,17 +1105,20 @@ async fn root_search_aux(
     indexes_metas_for_leaf_search: &IndexesMetasForLeafSearch,
     search_request: SearchRequest,
     split_metadatas: Vec<SplitMetadata>,
+    split_publish_timestamps: HashMap<SplitId, i64>,
     cluster_client: &ClusterClient,
 ) -> crate::Result<SearchResponse> {
     debug!(split_metadatas = ?PrettySample::new(&split_metadatas, 5));
-    let (first_phase_result, scroll_key_and_start_offset_opt): (
+    let (first_phase_result, leaf_num_hits_per_index, scroll_key_and_start_offset_opt): (
         LeafSearchResponse,
+        HashMap<IndexUid, u64>,
         Option<ScrollKeyAndStartOffset>,
     ) = search_partial_hits_phase_with_scroll(
         searcher_context,
         indexes_metas_for_leaf_search,
         search_request.clone(),
         &split_metadatas[..],
+        &split_publish_timestamps,
         cluster_client,
     )
     .await?;
@@ -871,6 +1127,7 @@ async fn root_search_aux(
         indexes_metas_for_leaf_search,
         &first_phase_result.partial_hits,
         &split_metadatas[..],
+        &split_publish_timestamps,
         &search_request,
         cluster_client,
     )
@@ -882,15 +1139,42 @@ async fn root_search_aux(
         searcher_context,
     )?;
 
+    // Leaves only count the hits of the splits they actually searched.
+    let num_hits_is_exact = first_phase_result.num_attempted_splits >= split_metadatas.len() as u64;
+    let (num_hits, num_hits_relation) = bound_num_hits(
+        &search_request,
+        first_phase_result.num_hits,
+        num_hits_is_exact,
+    );
+
+    let partial = !first_phase_result.failed_splits.is_empty();
+
+    // Per-index hit counts are only reported when several indexes are searched. Indexes without
+    // any matching split are reported with zero hits.
+    let mut num_hits_per_index: HashMap<String, u64> = HashMap::new();
+    if indexes_metas_for_leaf_search.len() > 1 {
+        for index_uid in indexes_metas_for_leaf_search.keys() {
+            let num_index_hits = leaf_num_hits_per_index
+                .get(index_uid)
+                .copied()
+                .unwrap_or_default();
+            num_hits_per_index.insert(index_uid.index_id().to_string(), num_index_hits);
+        }
+    }
+
     Ok(SearchResponse {
         aggregation: aggregation_result_json_opt,
-        num_hits: first_phase_result.num_hits,
+        num_hits,
         hits,
         elapsed_time_micros: 0u64,
         errors: Vec::new(),
         scroll_id: scroll_key_and_start_offset_opt
             .as_ref()
             .map(ToString::to_string),
+        num_hits_relation: num_hits_relation as i32,
+        failed_splits: first_phase_result.failed_splits,
+        partial,
+        num_hits_per_index,
     })
 }
 
@@ -1001,6 +1285,8 @@ pub async fn root_search(
     check_all_index_metadata_found(&indexes_metadata[..], &search_request.index_id_patterns[..])?;
 
     if indexes_metadata.is_empty() {
+        // No split can match the requested split IDs, if any.
+        check_all_split_ids_found(&[], &search_request.split_ids)?;
         // We go through root_search_aux instead of directly
         // returning an empty response to make sure we generate
         // a (pretty useless) scroll id if requested.
@@ -1009,6 +1295,7 @@ pub async fn root_search(
             &HashMap::default(),
             search_request,
             Vec::new(),
+            HashMap::new(),
             cluster_client,
         )
         .await?;
@@ -1038,24 +1325,68 @@ pub async fn root_search(
             &mut search_request.end_timestamp,
         );
     }
+    let range_queries = extract_range_queries_from_ast(&request_metadata.query_ast_resolved);
     let tag_filter_ast = extract_tags_from_query(request_metadata.query_ast_resolved);
 
     // TODO if search after is set, we sort by timestamp and we don't want to count all results,
     // we can refine more here. Same if we sort by _shard_doc
-    let split_metadatas: Vec<SplitMetadata> = list_relevant_splits(
-        index_uids,
-        search_request.start_timestamp,
-        search_request.end_timestamp,
-        tag_filter_ast,
-        &mut metastore,
-    )
-    .await?;
+    let splits: Vec<Split> = if search_request.split_ids.is_empty() {
+        list_relevant_published_splits(
+            index_uids,
+            search_request.start_timestamp,
+            search_request.end_timestamp,
+            tag_filter_ast,
+            &mut metastore,
+        )
+        .await?
+    } else {
+        // The splits are explicitly requested: we do not prune them on time range or tags.
+        let splits =
+            list_published_splits_by_id(index_uids, &search_request.split_ids, &mut metastore)
+                .await?;
+        check_all_split_ids_found(&splits, &search_request.split_ids)?;
+        splits
+    };
+    let split_publish_timestamps: HashMap<SplitId, i64> =
+        if search_request.include_split_publish_timestamp {
+            splits
+                .iter()
+                .filter_map(|split| {
+                    let publish_timestamp = split.publish_timestamp?;
+                    Some((split.split_id().to_string(), publish_timestamp))
+                })
+                .collect()
+        } else {
+            HashMap::new()
+        };
+    let split_metadatas: Vec<SplitMetadata> = splits
+        .into_iter()
+        .map(|split| split.split_metadata)
+        .collect();
+    let split_metadatas: Vec<SplitMetadata> = if search_request.split_ids.is_empty() {
+        prune_splits_on_field_ranges(split_metadatas, &range_queries)
+    } else {
+        split_metadatas
+    };
+    if !search_request.allow_high_cardinality_aggregations {
+        if let Some(max_cardinality) = searcher_context
+            .searcher_config
+            .max_terms_aggregation_cardinality
+        {
+            check_terms_aggregation_cardinality(
+                &search_request,
+                &split_metadatas,
+                max_cardinality,
+            )?;
+        }
+    }
 
     let mut search_response = root_search_aux(
         searcher_context,
         &request_metadata.indexes_meta_for_leaf_search,
         search_request,
         split_metadatas,
+        split_publish_timestamps,
         cluster_client,
     )
     .await?;
@@ -1064,6 +1395,165 @@ pub async fn root_search(
     Ok(search_response)
 }
 
+/// Validates the query of a search request against the doc mappers of the targeted indexes,
+/// without searching any split.
+///
+/// Returns the query AST resolved with the default search fields of the indexes.
+pub async fn root_validate_query(
+    search_request: SearchRequest,
+    metastore: MetastoreServiceClient,
+) -> crate::Result<QueryAst> {
+    let (indexes_metadata, doc_mappers) =
+        load_indexes_for_query_validation(search_request.index_id_patterns.clone(), metastore)
+            .await?;
+    let request_metadata = validate_request_and_build_metadata_with_doc_mappers(
+        &indexes_metadata,
+        &doc_mappers,
+        &search_request,
+    )?;
+    Ok(request_metadata.query_ast_resolved)
+}
+
+/// Validates a batch of search requests.
+///
+/// Requests targeting the same indexes are grouped so that the indexes metadata are fetched and
+/// their doc mappers are built once per group. At most [`MAX_CONCURRENT_QUERY_VALIDATIONS`] groups
+/// are validated concurrently.
+///
+/// The validation results are returned in the order of the requests.
+pub async fn root_validate_query_batch(
+    search_requests: Vec<SearchRequest>,
+    metastore: MetastoreServiceClient,
+) -> Vec<crate::Result<QueryAst>> {
+    let mut validation_results: Vec<Option<crate::Result<QueryAst>>> =
+        (0..search_requests.len()).map(|_| None).collect();
+    let mut search_requests_per_index_id_patterns: HashMap<
+        Vec<String>,
+        Vec<(usize, SearchRequest)>,
+    > = HashMap::new();
+
+    for (request_ord, search_request) in search_requests.into_iter().enumerate() {
+        if let Err(error) = validate_index_id_patterns(&search_request.index_id_patterns) {
+            validation_results[request_ord] = Some(Err(error));
+            continue;
+        }
+        let index_id_patterns: Vec<String> = search_request
+            .index_id_patterns
+            .iter()
+            .cloned()
+            .sorted()
+            .dedup()
+            .collect();
+        search_requests_per_index_id_patterns
+            .entry(index_id_patterns)
+            .or_default()
+            .push((request_ord, search_request));
+    }
+    let mut group_validation_stream = futures::stream::iter(search_requests_per_index_id_patterns)
+        .map(|(index_id_patterns, search_requests)| {
+            validate_query_group(index_id_patterns, search_requests, metastore.clone())
+        })
+        .buffer_unordered(MAX_CONCURRENT_QUERY_VALIDATIONS);
+
+    while let Some(group_validation_results) = group_validation_stream.next().await {
+        for (request_ord, validation_result) in group_validation_results {
+            validation_results[request_ord] = Some(validation_result);
+        }
+    }
+    validation_results
+        .into_iter()
+        .map(|validation_result_opt| {
+            validation_result_opt.expect("every search request should have been validated")
+        })
+        .collect()
+}
+
+/// Validates a group of search requests targeting the same indexes.
+async fn validate_query_group(
+    index_id_patterns: Vec<String>,
+    search_requests: Vec<(usize, SearchRequest)>,
+    metastore: MetastoreServiceClient,
+) -> Vec<(usize, crate::Result<QueryAst>)> {
+    let (indexes_metadata, doc_mappers) =
+        match load_indexes_for_query_validation(index_id_patterns, metastore).await {
+            Ok(indexes_metadata_and_doc_mappers) => indexes_metadata_and_doc_mappers,
+            Err(error) => {
+                return search_requests
+                    .into_iter()
+                    .map(|(request_ord, _)| (request_ord, Err(error.clone())))
+                    .collect();
+            }
+        };
+    search_requests
+        .into_iter()
+        .map(|(request_ord, search_request)| {
+            let validation_result = validate_request_and_build_metadata_with_doc_mappers(
+                &indexes_metadata,
+                &doc_mappers,
+                &search_request,
+            )
+            .map(|request_metadata| request_metadata.query_ast_resolved);
+            (request_ord, validation_result)
+        })
+        .collect()
+}
+
+/// Fetches the metadata of the indexes matching `index_id_patterns` and builds their doc mappers.
+///
+/// Returns an error if a pattern is invalid or if no index matches the patterns.
+async fn load_indexes_for_query_validation(
+    index_id_patterns: Vec<String>,
+    mut metastore: MetastoreServiceClient,
+) -> crate::Result<(Vec<IndexMetadata>, Vec<Arc<dyn DocMapper>>)> {
+    validate_index_id_patterns(&index_id_patterns)?;
+
+    let list_indexes_metadatas_request = ListIndexesMetadataRequest {
+        index_id_patterns: index_id_patterns.clone(),
+    };
+    let indexes_metadata: Vec<IndexMetadata> = metastore
+        .list_indexes_metadata(list_indexes_metadatas_request)
+        .await?
+        .deserialize_indexes_metadata()?;
+
+    check_all_index_metadata_found(&indexes_metadata[..], &index_id_patterns[..])?;
+
+    if indexes_metadata.is_empty() {
+        // There is no doc mapper to validate the query against.
+        return Err(SearchError::IndexesNotFound {
+            index_ids: index_id_patterns,
+        });
+    }
+    let doc_mappers = build_doc_mappers(&indexes_metadata)?;
+    Ok((indexes_metadata, doc_mappers))
+}
+
+fn validate_index_id_patterns(index_id_patterns: &[String]) -> crate::Result<()> {
+    for index_id_pattern in index_id_patterns {
+        validate_index_id_pattern(index_id_pattern)
+            .map_err(|error| SearchError::InvalidArgument(error.to_string()))?;
+    }
+    Ok(())
+}
+
+/// Returns an error if some of the requested splits are not among the published splits of the
+/// searched indexes.
+fn check_all_split_ids_found(splits: &[Split], split_ids: &[String]) -> crate::Result<()> {
+    let requested_split_ids: HashSet<&str> = split_ids.iter().map(String::as_str).collect();
+    let found_split_ids: HashSet<&str> = splits.iter().map(|split| split.split_id()).collect();
+    let missing_split_ids: Vec<&str> = requested_split_ids
+        .difference(&found_split_ids)
+        .copied()
+        .sorted()
+        .collect();
+    if !missing_split_ids.is_empty() {
+        return Err(SearchError::InvalidArgument(format!(
+            "split(s) `{}` not found among the published splits of the searched indexes",
+            missing_split_ids.join(", ")
+        )));
+    }
+    Ok(())
+}
+
 /// Converts search after with datetime format to nanoseconds (representation in tantivy).
 /// If the sort field is a datetime field and no datetime format is set, the default format is
 /// milliseconds.
@@ -1266,6 +1756,11 @@ impl<'a, 'b> QueryAstVisitor<'b> for ExtractTimestampRange<'a> {
         Ok(())
     }
 
+    fn visit_dis_max(&mut self, _dis_max_query: &'b DisMaxQuery) -> Result<(), Self::Err> {
+        // none of the disjuncts is a strict requirement
+        Ok(())
+    }
+
     fn visit_range(&mut self, range_query: &'b RangeQuery) -> Result<(), Self::Err> {
         use std::ops::Bound;
 
@@ -1313,6 +1808,158 @@ impl<'a, 'b> QueryAstVisitor<'b> for ExtractTimestampRange<'a> {
     }
 }
 
+/// Extracts the range queries of the `QueryAst` that are strict requirements, i.e. that every
+/// matching document must satisfy.
+fn extract_range_queries_from_ast(query_ast: &QueryAst) -> Vec<RangeQuery> {
+    let mut range_queries_extractor = ExtractRangeQueries::default();
+    range_queries_extractor
+        .visit(query_ast)
+        .expect("can't fail unwrapping Infallible");
+    range_queries_extractor.range_queries
+}
+
+#[derive(Default)]
+struct ExtractRangeQueries {
+    range_queries: Vec<RangeQuery>,
+}
+
+impl<'a> QueryAstVisitor<'a> for ExtractRangeQueries {
+    type Err = std::convert::Infallible;
+
+    fn visit_bool(&mut self, bool_query: &'a BoolQuery) -> Result<(), Self::Err> {
+        // we only want to visit sub-queries which are strict (positive) requirements
+        for ast in bool_query.must.iter().chain(bool_query.filter.iter()) {
+            self.visit(ast)?;
+        }
+        Ok(())
+    }
+
+    fn visit_dis_max(&mut self, _dis_max_query: &'a DisMaxQuery) -> Result<(), Self::Err> {
+        // none of the disjuncts is a strict requirement
+        Ok(())
+    }
+
+    fn visit_range(&mut self, range_query: &'a RangeQuery) -> Result<(), Self::Err> {
+        self.range_queries.push(range_query.clone());
+        Ok(())
+    }
+}
+
+/// Removes the splits for which the min / max values recorded for a field prove that no document
+/// can match one of the range queries.
+fn prune_splits_on_field_ranges(
+    split_metadatas: Vec<SplitMetadata>,
+    range_queries: &[RangeQuery],
+) -> Vec<SplitMetadata> {
+    if range_queries.is_empty() {
+        return split_metadatas;
+    }
+    let num_splits_before_pruning = split_metadatas.len();
+    let split_metadatas: Vec<SplitMetadata> = split_metadatas
+        .into_iter()
+        .filter(|split_metadata| {
+            range_queries.iter().all(|range_query| {
+                let Some(field_range) = split_metadata.field_ranges.get(&range_query.field) else {
+                    return true;
+                };
+                field_range_may_match(field_range, range_query)
+            })
+        })
+        .collect();
+    let num_pruned_splits = num_splits_before_pruning - split_metadatas.len();
+    if num_pruned_splits > 0 {
+        debug!(num_pruned_splits, "pruned splits on field ranges");
+    }
+    split_metadatas
+}
+
+/// Rejects the request if one of its terms aggregations targets a field with more than
+/// `max_cardinality` distinct values in one of the splits, as the buckets of such an aggregation
+/// could exhaust the memory of the searchers.
+fn check_terms_aggregation_cardinality(
+    search_request: &SearchRequest,
+    split_metadatas: &[SplitMetadata],
+    max_cardinality: NonZeroU64,
+) -> crate::Result<()> {
+    let Some(aggregation_request) = &search_request.aggregation_request else {
+        return Ok(());
+    };
+    let QuickwitAggregations::TantivyAggregations(aggregations) =
+        serde_json::from_str(aggregation_request)?
+    else {
+        return Ok(());
+    };
+    let mut terms_fields = HashSet::new();
+    collect_terms_aggregation_fields(&aggregations, &mut terms_fields);
+
+    for split_metadata in split_metadatas {
+        for &field in &terms_fields {
+            let Some(&cardinality) = split_metadata.field_cardinalities.get(field) else {
+                continue;
+            };
+            if cardinality > max_cardinality.get() {
+                return Err(SearchError::InvalidAggregationRequest(format!(
+                    "terms aggregation on field `{field}` rejected: split `{}` holds \
+                     {cardinality} distinct values for this field, which exceeds the limit of \
+                     {max_cardinality}. Narrow the search with a query or a time range, aggregate \
+                     on a field with fewer distinct values, or set \
+                     `allow_high_cardinality_aggregations` to bypass this check",
+                    split_metadata.split_id
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn collect_terms_aggregation_fields<'a>(
+    aggregations: &'a Aggregations,
+    terms_fields: &mut HashSet<&'a str>,
+) {
+    for aggregation in aggregations.values() {
+        if let AggregationVariants::Terms(terms_aggregation) = &aggregation.agg {
+            terms_fields.insert(&terms_aggregation.field);
+        }
+        collect_terms_aggregation_fields(&aggregation.sub_aggregation, terms_fields);
+    }
+}
+
+/// Returns false if the range query cannot match any value of the field range.
+///
+/// Bounds that cannot be interpreted as the type of the field range are ignored.
+fn field_range_may_match(field_range: &FieldRange, range_query: &RangeQuery) -> bool {
+    match *field_range {
+        FieldRange::I64 { min, max } => bounds_may_match(min, max, range_query),
+        FieldRange::U64 { min, max } => bounds_may_match(min, max, range_query),
+        FieldRange::F64 { min, max } => bounds_may_match(min, max, range_query),
+    }
+}
+
+fn bounds_may_match<T>(min: T, max: T, range_query: &RangeQuery) -> bool
+where T: PartialOrd + for<'a> quickwit_query::InterpretUserInput<'a> {
+    use std::ops::Bound;
+
+    let lower_bound_may_match = match &range_query.lower_bound {
+        Bound::Included(lower_bound) => {
+            T::interpret_json(lower_bound).map_or(true, |lower_bound| max >= lower_bound)
+        }
+        Bound::Excluded(lower_bound) => {
+            T::interpret_json(lower_bound).map_or(true, |lower_bound| max > lower_bound)
+        }
+        Bound::Unbounded => true,
+    };
+    let upper_bound_may_match = match &range_query.upper_bound {
+        Bound::Included(upper_bound) => {
+            T::interpret_json(upper_bound).map_or(true, |upper_bound| min <= upper_bound)
+        }
+        Bound::Excluded(upper_bound) => {
+            T::interpret_json(upper_bound).map_or(true, |upper_bound| min < upper_bound)
+        }
+        Bound::Unbounded => true,
+    };
+    lower_bound_may_match && upper_bound_may_match
+}
+
 async fn assign_client_fetch_docs_jobs(
     partial_hits: &[PartialHit],
     split_metadatas: &[SplitMetadata],
@@ -1468,6 +2115,43 @@ mod tests {
         validate_requested_snippet_fields(&schema, snippet_fields)
     }
 
+    #[test]
+    fn test_bound_num_hits() {
+        let search_request = SearchRequest::default();
+        assert_eq!(
+            bound_num_hits(&search_request, 10, true),
+            (10, HitsRelation::Equal)
+        );
+        assert_eq!(
+            bound_num_hits(&search_request, 10, false),
+            (10, HitsRelation::GreaterThanOrEqual)
+        );
+        let search_request = SearchRequest {
+            track_total_hits_up_to: Some(10),
+            ..Default::default()
+        };
+        assert_eq!(
+            bound_num_hits(&search_request, 9, true),
+            (9, HitsRelation::Equal)
+        );
+        assert_eq!(
+            bound_num_hits(&search_request, 10, true),
+            (10, HitsRelation::Equal)
+        );
+        assert_eq!(
+            bound_num_hits(&search_request, 10, false),
+            (10, HitsRelation::GreaterThanOrEqual)
+        );
+        assert_eq!(
+            bound_num_hits(&search_request, 11, true),
+            (10, HitsRelation::GreaterThanOrEqual)
+        );
+        assert_eq!(
+            bound_num_hits(&search_request, 11, false),
+            (10, HitsRelation::GreaterThanOrEqual)
+        );
+    }
+
     #[test]
     fn test_validate_requested_snippet_fields() {
         check_snippet_fields_validation(&["desc".to_string()]).unwrap();
@@ -1536,6 +2220,7 @@ mod tests {
         let indexing_settings = IndexingSettings::default();
         let search_settings = SearchSettings {
             default_search_fields: vec!["body".to_string()],
+            ignore_missing_default_search_fields: false,
         };
         IndexMetadata::new(IndexConfig {
             index_id: index_id.to_string(),
@@ -1544,6 +2229,7 @@ mod tests {
             indexing_settings,
             search_settings,
             retention_policy: Default::default(),
+            storage_config: None,
         })
     }
 
@@ -1708,6 +2394,7 @@ mod tests {
         let indexing_settings = IndexingSettings::default();
         let search_settings = SearchSettings {
             default_search_fields: vec!["body".to_string()],
+            ignore_missing_default_search_fields: false,
         };
         IndexMetadata::new(IndexConfig {
             index_id: index_id.to_string(),
@@ -1716,6 +2403,7 @@ mod tests {
             indexing_settings,
             search_settings,
             retention_policy: Default::default(),
+            storage_config: None,
         })
     }
 
@@ -1747,17 +2435,49 @@ mod tests {
         .unwrap_err();
         assert_eq!(
             search_error.to_string(),
-            "sort datetime field `response_date` must be of type datetime on all indexes"
+            "Invalid argument: field `response_date` has incompatible types across indexes: \
+             `Date` in index `test-index-1` and `I64` in index `test-index-2`"
         );
     }
 
     #[test]
-    fn test_convert_sort_datetime_value() {
-        let mut sort_value = SortValue::U64(1617000000000000000);
-        convert_sort_datetime_value(&mut sort_value, SortDatetimeFormat::UnixTimestampMillis)
-            .unwrap();
-        assert_eq!(sort_value, SortValue::U64(1617000000000));
-        let mut sort_value = SortValue::I64(1617000000000000000);
+    fn test_validate_request_and_build_metadatas_fail_with_incompatible_aggregation_field_types() {
+        let request_query_ast = qast_helper("body:test", &[]);
+        let search_request = quickwit_proto::search::SearchRequest {
+            index_id_patterns: vec!["test-index".to_string()],
+            query_ast: serde_json::to_string(&request_query_ast).unwrap(),
+            max_hits: 10,
+            aggregation_request: Some(
+                r#"{"response_dates": {"terms": {"field": "response_date"}}}"#.to_string(),
+            ),
+            ..Default::default()
+        };
+        let index_metadata_with_other_config =
+            index_metadata_for_multi_indexes_test_with_incompatible_sort_type(
+                "test-index-1",
+                "ram:///test-index-1",
+            );
+        let index_metadata = IndexMetadata::for_test("test-index-2", "ram:///test-index-2");
+        let search_error = validate_request_and_build_metadata(
+            &[index_metadata_with_other_config, index_metadata],
+            &search_request,
+        )
+        .unwrap_err();
+        assert!(matches!(search_error, SearchError::InvalidArgument(_)));
+        assert_eq!(
+            search_error.to_string(),
+            "Invalid argument: field `response_date` has incompatible types across indexes: `I64` \
+             in index `test-index-1` and `Date` in index `test-index-2`"
+        );
+    }
+
+    #[test]
+    fn test_convert_sort_datetime_value() {
+        let mut sort_value = SortValue::U64(1617000000000000000);
+        convert_sort_datetime_value(&mut sort_value, SortDatetimeFormat::UnixTimestampMillis)
+            .unwrap();
+        assert_eq!(sort_value, SortValue::U64(1617000000000));
+        let mut sort_value = SortValue::I64(1617000000000000000);
         convert_sort_datetime_value(&mut sort_value, SortDatetimeFormat::UnixTimestampMillis)
             .unwrap();
         assert_eq!(sort_value, SortValue::I64(1617000000000));
@@ -2374,6 +3094,7 @@ mod tests {
         .unwrap();
         assert_eq!(search_response.num_hits, 3);
         assert_eq!(search_response.hits.len(), 3);
+        assert!(search_response.num_hits_per_index.is_empty());
         Ok(())
     }
 
@@ -2465,9 +3186,95 @@ mod tests {
         .unwrap();
         assert_eq!(search_response.num_hits, 3);
         assert_eq!(search_response.hits.len(), 3);
+        assert!(search_response
+            .hits
+            .iter()
+            .all(|hit| hit.split_publish_timestamp.is_none()));
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_root_search_include_split_publish_timestamp() {
+        let search_request = quickwit_proto::search::SearchRequest {
+            index_id_patterns: vec!["test-index".to_string()],
+            query_ast: qast_json_helper("test", &["body"]),
+            max_hits: 10,
+            include_split_publish_timestamp: true,
+            ..Default::default()
+        };
+        let mut metastore = MetastoreServiceClient::mock();
+        let index_metadata = IndexMetadata::for_test("test-index", "ram:///test-index");
+        let index_uid = index_metadata.index_uid.clone();
+        metastore
+            .expect_list_indexes_metadata()
+            .returning(move |_index_ids_query| {
+                Ok(ListIndexesMetadataResponse::try_from_indexes_metadata(vec![
+                    index_metadata.clone()
+                ])
+                .unwrap())
+            });
+        metastore.expect_list_splits().returning(move |_filter| {
+            let mut split_1 = MockSplitBuilder::new("split1")
+                .with_index_uid(&index_uid)
+                .build();
+            split_1.publish_timestamp = Some(1_700_000_003);
+            let mut split_2 = MockSplitBuilder::new("split2")
+                .with_index_uid(&index_uid)
+                .build();
+            split_2.publish_timestamp = Some(1_700_000_007);
+            let splits_response =
+                ListSplitsResponse::try_from_splits(vec![split_1, split_2]).unwrap();
+            Ok(ServiceStream::from(vec![Ok(splits_response)]))
+        });
+        let mut mock_search_service = MockSearchService::new();
+        mock_search_service.expect_leaf_search().returning(
+            |_leaf_search_req: quickwit_proto::search::LeafSearchRequest| {
+                Ok(quickwit_proto::search::LeafSearchResponse {
+                    num_hits: 3,
+                    partial_hits: vec![
+                        mock_partial_hit("split1", 3, 1),
+                        mock_partial_hit("split2", 2, 2),
+                        mock_partial_hit("split1", 1, 3),
+                    ],
+                    failed_splits: Vec::new(),
+                    num_attempted_splits: 2,
+                    ..Default::default()
+                })
+            },
+        );
+        mock_search_service.expect_fetch_docs().returning(
+            |fetch_docs_req: quickwit_proto::search::FetchDocsRequest| {
+                Ok(quickwit_proto::search::FetchDocsResponse {
+                    hits: get_doc_for_fetch_req(fetch_docs_req),
+                })
+            },
+        );
+        let searcher_pool = searcher_pool_for_test([("127.0.0.1:1001", mock_search_service)]);
+        let search_job_placer = SearchJobPlacer::new(searcher_pool);
+        let cluster_client = ClusterClient::new(search_job_placer);
+        let search_response = root_search(
+            &SearcherContext::for_test(),
+            search_request,
+            MetastoreServiceClient::from(metastore),
+            &cluster_client,
+        )
+        .await
+        .unwrap();
+        assert_eq!(search_response.hits.len(), 3);
+        for hit in &search_response.hits {
+            let expected_split_publish_timestamp =
+                match hit.partial_hit.as_ref().unwrap().split_id.as_str() {
+                    "split1" => 1_700_000_003,
+                    "split2" => 1_700_000_007,
+                    split_id => panic!("unexpected split `{split_id}`"),
+                };
+            assert_eq!(
+                hit.split_publish_timestamp,
+                Some(expected_split_publish_timestamp)
+            );
+        }
+    }
+
     #[tokio::test]
     async fn test_root_search_multiple_splits_sort_heteregeneous_field_ascending(
     ) -> anyhow::Result<()> {
@@ -3082,6 +3889,146 @@ mod tests {
         Ok(())
     }
 
+    fn mock_search_service_failing_on_split2() -> MockSearchService {
+        let mut mock_search_service = MockSearchService::new();
+        mock_search_service.expect_leaf_search().returning(
+            |leaf_search_req: quickwit_proto::search::LeafSearchRequest| {
+                if leaf_search_req.split_offsets[0].split_id == "split2" {
+                    return Err(SearchError::Internal("mock_error".to_string()));
+                }
+                Ok(quickwit_proto::search::LeafSearchResponse {
+                    num_hits: 2,
+                    partial_hits: vec![
+                        mock_partial_hit("split1", 3, 1),
+                        mock_partial_hit("split1", 1, 3),
+                    ],
+                    failed_splits: Vec::new(),
+                    num_attempted_splits: 1,
+                    ..Default::default()
+                })
+            },
+        );
+        mock_search_service.expect_fetch_docs().returning(
+            |fetch_docs_req: quickwit_proto::search::FetchDocsRequest| {
+                Ok(quickwit_proto::search::FetchDocsResponse {
+                    hits: get_doc_for_fetch_req(fetch_docs_req),
+                })
+            },
+        );
+        mock_search_service
+    }
+
+    async fn root_search_with_failing_leaf(
+        allow_partial_results: bool,
+    ) -> crate::Result<SearchResponse> {
+        let search_request = quickwit_proto::search::SearchRequest {
+            index_id_patterns: vec!["test-index".to_string()],
+            query_ast: qast_json_helper("test", &["body"]),
+            max_hits: 10,
+            allow_partial_results,
+            ..Default::default()
+        };
+        let mut metastore = MetastoreServiceClient::mock();
+        let index_metadata = IndexMetadata::for_test("test-index", "ram:///test-index");
+        let index_uid = index_metadata.index_uid.clone();
+        metastore
+            .expect_list_indexes_metadata()
+            .returning(move |_indexes_metadata_request| {
+                Ok(ListIndexesMetadataResponse::try_from_indexes_metadata(vec![
+                    index_metadata.clone()
+                ])
+                .unwrap())
+            });
+        metastore.expect_list_splits().returning(move |_filter| {
+            let splits = vec![
+                MockSplitBuilder::new("split1")
+                    .with_index_uid(&index_uid)
+                    .build(),
+                MockSplitBuilder::new("split2")
+                    .with_index_uid(&index_uid)
+                    .build(),
+            ];
+            let splits_response = ListSplitsResponse::try_from_splits(splits).unwrap();
+            Ok(ServiceStream::from(vec![Ok(splits_response)]))
+        });
+        let searcher_pool = searcher_pool_for_test([
+            ("127.0.0.1:1001", mock_search_service_failing_on_split2()),
+            ("127.0.0.1:1002", mock_search_service_failing_on_split2()),
+        ]);
+        let search_job_placer = SearchJobPlacer::new(searcher_pool);
+        let cluster_client = ClusterClient::new(search_job_placer);
+        root_search(
+            &SearcherContext::for_test(),
+            search_request,
+            MetastoreServiceClient::from(metastore),
+            &cluster_client,
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_root_search_failing_leaf_with_partial_results() {
+        let search_response = root_search_with_failing_leaf(true).await.unwrap();
+        assert!(search_response.partial);
+        assert_eq!(search_response.num_hits, 2);
+        assert_eq!(search_response.hits.len(), 2);
+        assert_eq!(
+            search_response.num_hits_relation(),
+            HitsRelation::GreaterThanOrEqual
+        );
+        assert_eq!(search_response.failed_splits.len(), 1);
+        assert_eq!(search_response.failed_splits[0].split_id, "split2");
+    }
+
+    #[tokio::test]
+    async fn test_root_search_failing_leaf_without_partial_results() {
+        root_search_with_failing_leaf(false).await.unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn test_root_search_all_leaves_failing_with_partial_results() {
+        let search_request = quickwit_proto::search::SearchRequest {
+            index_id_patterns: vec!["test-index".to_string()],
+            query_ast: qast_json_helper("test", &["body"]),
+            max_hits: 10,
+            allow_partial_results: true,
+            ..Default::default()
+        };
+        let mut metastore = MetastoreServiceClient::mock();
+        let index_metadata = IndexMetadata::for_test("test-index", "ram:///test-index");
+        let index_uid = index_metadata.index_uid.clone();
+        metastore
+            .expect_list_indexes_metadata()
+            .returning(move |_indexes_metadata_request| {
+                Ok(ListIndexesMetadataResponse::try_from_indexes_metadata(vec![
+                    index_metadata.clone()
+                ])
+                .unwrap())
+            });
+        metastore.expect_list_splits().returning(move |_filter| {
+            let splits = vec![MockSplitBuilder::new("split2")
+                .with_index_uid(&index_uid)
+                .build()];
+            let splits_response = ListSplitsResponse::try_from_splits(splits).unwrap();
+            Ok(ServiceStream::from(vec![Ok(splits_response)]))
+        });
+        let searcher_pool = searcher_pool_for_test([
+            ("127.0.0.1:1001", mock_search_service_failing_on_split2()),
+            ("127.0.0.1:1002", mock_search_service_failing_on_split2()),
+        ]);
+        let search_job_placer = SearchJobPlacer::new(searcher_pool);
+        let cluster_client = ClusterClient::new(search_job_placer);
+        let search_error = root_search(
+            &SearcherContext::for_test(),
+            search_request,
+            MetastoreServiceClient::from(metastore),
+            &cluster_client,
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(search_error, SearchError::Internal(_)));
+    }
+
     #[tokio::test]
     async fn test_root_search_single_split_retry_single_node() -> anyhow::Result<()> {
         let search_request = quickwit_proto::search::SearchRequest {
@@ -3452,6 +4399,70 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_root_validate_query_batch() {
+        let mut mock_metastore = MetastoreServiceClient::mock();
+        let index_metadata_1 = IndexMetadata::for_test("test-index-1", "ram:///test-index-1");
+        let index_metadata_2 =
+            index_metadata_for_multi_indexes_test("test-index-2", "ram:///test-index-2");
+        // The indexes metadata are fetched once per group of requests targeting the same indexes.
+        mock_metastore
+            .expect_list_indexes_metadata()
+            .times(3)
+            .returning(
+                move |list_indexes_metadata_request: ListIndexesMetadataRequest| {
+                    let indexes_metadata =
+                        match list_indexes_metadata_request.index_id_patterns[0].as_str() {
+                            "test-index-1" => vec![index_metadata_1.clone()],
+                            "test-index-2" => vec![index_metadata_2.clone()],
+                            _ => Vec::new(),
+                        };
+                    Ok(
+                        ListIndexesMetadataResponse::try_from_indexes_metadata(indexes_metadata)
+                            .unwrap(),
+                    )
+                },
+            );
+        let search_request = |index_id: &str, user_text: &str| SearchRequest {
+            index_id_patterns: vec![index_id.to_string()],
+            query_ast: serde_json::to_string(&query_ast_from_user_text(user_text, None)).unwrap(),
+            ..Default::default()
+        };
+        let search_requests = vec![
+            search_request("test-index-1", "body:test"),
+            search_request("test-index-1", "invalid_field:test"),
+            search_request("test-index-2", "test"),
+            search_request("test-index-2", "body:("),
+            search_request("test-index-3", "body:test"),
+            search_request("test-index!", "body:test"),
+        ];
+        let validation_results = root_validate_query_batch(
+            search_requests,
+            MetastoreServiceClient::from(mock_metastore),
+        )
+        .await;
+        assert_eq!(validation_results.len(), 6);
+
+        let query_ast = validation_results[0].as_ref().unwrap();
+        assert_eq!(query_ast, &qast_helper("body:test", &[]));
+
+        let error = validation_results[1].as_ref().unwrap_err();
+        assert!(matches!(error, SearchError::InvalidQuery(_)));
+
+        // The query is resolved with the default search fields of the index.
+        let query_ast = validation_results[2].as_ref().unwrap();
+        assert_eq!(query_ast, &qast_helper("test", &["body"]));
+
+        let error = validation_results[3].as_ref().unwrap_err();
+        assert!(matches!(error, SearchError::InvalidQuery(_)));
+
+        let error = validation_results[4].as_ref().unwrap_err();
+        assert!(matches!(error, SearchError::IndexesNotFound { .. }));
+
+        let error = validation_results[5].as_ref().unwrap_err();
+        assert!(matches!(error, SearchError::InvalidArgument(_)));
+    }
+
     #[tokio::test]
     async fn test_root_search_invalid_aggregation() -> anyhow::Result<()> {
         let agg_req = r#"
@@ -3699,6 +4710,163 @@ mod tests {
         assert_eq!(timestamp_range_extractor.end_timestamp, Some(1620283880));
     }
 
+    #[test]
+    fn test_prune_splits_on_field_ranges() {
+        use std::ops::Bound;
+
+        use quickwit_query::JsonLiteral;
+
+        let to_json_bound = |bound: Bound<i64>| match bound {
+            Bound::Included(value) => Bound::Included(JsonLiteral::Number(value.into())),
+            Bound::Excluded(value) => Bound::Excluded(JsonLiteral::Number(value.into())),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+        let range_query = |lower_bound: Bound<i64>, upper_bound: Bound<i64>| RangeQuery {
+            field: "status".to_string(),
+            lower_bound: to_json_bound(lower_bound),
+            upper_bound: to_json_bound(upper_bound),
+        };
+        let split_with_range = |split_id: &str, min: u64, max: u64| {
+            let mut split_metadata = SplitMetadata::for_test(split_id.to_string());
+            split_metadata
+                .field_ranges
+                .insert("status".to_string(), FieldRange::U64 { min, max });
+            split_metadata
+        };
+        let split_metadatas = vec![
+            split_with_range("split-200", 200, 299),
+            split_with_range("split-400", 400, 499),
+            split_with_range("split-500", 500, 599),
+            SplitMetadata::for_test("split-no-range".to_string()),
+        ];
+        let pruned_split_ids = |range_queries: &[RangeQuery]| {
+            prune_splits_on_field_ranges(split_metadatas.clone(), range_queries)
+                .iter()
+                .map(|split_metadata| split_metadata.split_id.clone())
+                .collect::<Vec<String>>()
+        };
+        assert_eq!(
+            pruned_split_ids(&[]),
+            ["split-200", "split-400", "split-500", "split-no-range"]
+        );
+        assert_eq!(
+            pruned_split_ids(&[range_query(Bound::Included(400), Bound::Unbounded)]),
+            ["split-400", "split-500", "split-no-range"]
+        );
+        assert_eq!(
+            pruned_split_ids(&[range_query(Bound::Excluded(499), Bound::Unbounded)]),
+            ["split-500", "split-no-range"]
+        );
+        assert_eq!(
+            pruned_split_ids(&[range_query(Bound::Unbounded, Bound::Excluded(400))]),
+            ["split-200", "split-no-range"]
+        );
+        assert_eq!(
+            pruned_split_ids(&[range_query(Bound::Unbounded, Bound::Included(400))]),
+            ["split-200", "split-400", "split-no-range"]
+        );
+        assert_eq!(
+            pruned_split_ids(&[
+                range_query(Bound::Included(300), Bound::Unbounded),
+                range_query(Bound::Unbounded, Bound::Excluded(500)),
+            ]),
+            ["split-400", "split-no-range"]
+        );
+        // A bound that cannot be interpreted as a `u64` does not prune anything.
+        assert_eq!(
+            pruned_split_ids(&[range_query(Bound::Included(-1), Bound::Excluded(100))]),
+            ["split-no-range"]
+        );
+        assert_eq!(
+            pruned_split_ids(&[range_query(Bound::Included(-1), Bound::Unbounded)]),
+            ["split-200", "split-400", "split-500", "split-no-range"]
+        );
+    }
+
+    #[test]
+    fn test_check_terms_aggregation_cardinality() {
+        let mut split_metadata = SplitMetadata::for_test("split-1".to_string());
+        split_metadata
+            .field_cardinalities
+            .insert("user_id".to_string(), 2_000_000);
+        split_metadata
+            .field_cardinalities
+            .insert("status".to_string(), 12);
+        let split_metadatas = vec![split_metadata];
+        let max_cardinality = NonZeroU64::new(100_000).unwrap();
+        let search_request_with_terms_agg = |field: &str| SearchRequest {
+            aggregation_request: Some(
+                serde_json::json!({
+                    "by_day": {
+                        "histogram": {"field": "timestamp", "interval": 86400},
+                        "aggs": {"top_values": {"terms": {"field": field}}}
+                    }
+                })
+                .to_string(),
+            ),
+            ..Default::default()
+        };
+        check_terms_aggregation_cardinality(
+            &search_request_with_terms_agg("status"),
+            &split_metadatas,
+            max_cardinality,
+        )
+        .unwrap();
+        check_terms_aggregation_cardinality(
+            &search_request_with_terms_agg("field_without_stats"),
+            &split_metadatas,
+            max_cardinality,
+        )
+        .unwrap();
+
+        let error = check_terms_aggregation_cardinality(
+            &search_request_with_terms_agg("user_id"),
+            &split_metadatas,
+            max_cardinality,
+        )
+        .unwrap_err();
+        let SearchError::InvalidAggregationRequest(error_message) = error else {
+            panic!("expected an invalid aggregation request error, got `{error:?}`");
+        };
+        assert_eq!(
+            error_message,
+            "terms aggregation on field `user_id` rejected: split `split-1` holds 2000000 \
+             distinct values for this field, which exceeds the limit of 100000. Narrow the search \
+             with a query or a time range, aggregate on a field with fewer distinct values, or \
+             set `allow_high_cardinality_aggregations` to bypass this check"
+        );
+    }
+
+    #[test]
+    fn test_extract_range_queries_from_ast() {
+        use std::ops::Bound;
+
+        use quickwit_query::JsonLiteral;
+
+        let range_query = |field: &str| -> QueryAst {
+            RangeQuery {
+                field: field.to_string(),
+                lower_bound: Bound::Included(JsonLiteral::Number(400.into())),
+                upper_bound: Bound::Unbounded,
+            }
+            .into()
+        };
+        let query_ast: QueryAst = BoolQuery {
+            must: vec![range_query("status")],
+            filter: vec![range_query("latency")],
+            should: vec![range_query("size")],
+            must_not: vec![range_query("offset")],
+            ..Default::default()
+        }
+        .into();
+        let range_queries = extract_range_queries_from_ast(&query_ast);
+        let range_query_fields: Vec<&str> = range_queries
+            .iter()
+            .map(|range_query| range_query.field.as_str())
+            .collect();
+        assert_eq!(range_query_fields, ["status", "latency"]);
+    }
+
     fn create_search_resp(index_uri: &str, hit_range: Range<usize>) -> LeafSearchResponse {
         let (num_total_hits, split_id) = match index_uri {
             "ram:///test-index-1" => (TOTAL_NUM_HITS_INDEX_1, "split1"),
@@ -4019,6 +5187,15 @@ mod tests {
                 .collect_vec(),
             vec!["test-index-2", "test-index-1", "test-index-1"]
         );
+        let expected_num_hits_per_index: HashMap<String, u64> = HashMap::from([
+            ("test-index-1".to_string(), 2),
+            ("test-index-2".to_string(), 1),
+            ("test-index-3".to_string(), 0),
+        ]);
+        assert_eq!(
+            search_response.num_hits_per_index,
+            expected_num_hits_per_index
+        );
         Ok(())
     }
 }
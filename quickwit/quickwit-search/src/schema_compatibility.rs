@@ -0,0 +1,243 @@
+// Copyright (C) 2024 Quickwit, Inc.
+//
+// Quickwit is offered under the AGPL v3.0 and as commercial software.
+// For commercial licensing, contact us at hello@quickwit.io.
+//
+// AGPL:
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as
+// published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+//! Checks that the fields targeted by a search request have compatible types across the indexes
+//! it spans, so that a type mismatch is reported upfront rather than as an obscure error in one of
+//! the leaves.
+
+use std::collections::BTreeSet;
+use std::convert::Infallible;
+
+use quickwit_doc_mapper::DYNAMIC_FIELD_NAME;
+use quickwit_proto::search::SearchRequest;
+use quickwit_query::query_ast::{
+    FieldPresenceQuery, FullTextQuery, PhrasePrefixQuery, QueryAst, QueryAstVisitor, RangeQuery,
+    TermQuery, TermSetQuery, WildcardQuery,
+};
+use tantivy::schema::{FieldType, Schema};
+
+use crate::collector::QuickwitAggregations;
+use crate::SearchError;
+
+/// Collects the names of the fields targeted by a query.
+#[derive(Default)]
+struct QueriedFieldNames {
+    field_names: BTreeSet<String>,
+}
+
+impl<'a> QueryAstVisitor<'a> for QueriedFieldNames {
+    type Err = Infallible;
+
+    fn visit_term(&mut self, term_query: &'a TermQuery) -> Result<(), Infallible> {
+        self.field_names.insert(term_query.field.clone());
+        Ok(())
+    }
+
+    fn visit_term_set(&mut self, term_set_query: &'a TermSetQuery) -> Result<(), Infallible> {
+        self.field_names
+            .extend(term_set_query.terms_per_field.keys().cloned());
+        Ok(())
+    }
+
+    fn visit_full_text(&mut self, full_text_query: &'a FullTextQuery) -> Result<(), Infallible> {
+        self.field_names.insert(full_text_query.field.clone());
+        Ok(())
+    }
+
+    fn visit_phrase_prefix(
+        &mut self,
+        phrase_prefix_query: &'a PhrasePrefixQuery,
+    ) -> Result<(), Infallible> {
+        self.field_names.insert(phrase_prefix_query.field.clone());
+        Ok(())
+    }
+
+    fn visit_range(&mut self, range_query: &'a RangeQuery) -> Result<(), Infallible> {
+        self.field_names.insert(range_query.field.clone());
+        Ok(())
+    }
+
+    fn visit_exists(&mut self, exists_query: &'a FieldPresenceQuery) -> Result<(), Infallible> {
+        self.field_names.insert(exists_query.field.clone());
+        Ok(())
+    }
+
+    fn visit_wildcard(&mut self, wildcard_query: &'a WildcardQuery) -> Result<(), Infallible> {
+        self.field_names.insert(wildcard_query.field.clone());
+        Ok(())
+    }
+}
+
+/// Returns the names of the fields queried, sorted on, or aggregated on by the search request.
+/// `query_asts` are the query ASTs resolved against each of the target indexes.
+pub(crate) fn requested_field_names<'a>(
+    query_asts: impl IntoIterator<Item = &'a QueryAst>,
+    search_request: &SearchRequest,
+) -> BTreeSet<String> {
+    let mut queried_field_names = QueriedFieldNames::default();
+    for query_ast in query_asts {
+        // This cannot fail. The error type is Infallible.
+        let _: Result<(), Infallible> = queried_field_names.visit(query_ast);
+    }
+    let mut field_names = queried_field_names.field_names;
+
+    for sort_field in &search_request.sort_fields {
+        field_names.insert(sort_field.field_name.clone());
+    }
+    if let Some(aggregation_request) = &search_request.aggregation_request {
+        // Invalid aggregation requests are reported by the request validation.
+        if let Ok(aggregations) = serde_json::from_str::<QuickwitAggregations>(aggregation_request)
+        {
+            field_names.extend(aggregations.fast_field_names());
+        }
+    }
+    field_names
+}
+
+/// Returns the family of types a field belongs to. Fields of the same family can be searched,
+/// sorted, and aggregated together. Returns `None` for JSON fields, whose values have no fixed
+/// type.
+fn field_type_family(field_type: &FieldType) -> Option<&'static str> {
+    match field_type {
+        FieldType::Str(_) => Some("text"),
+        FieldType::U64(_) | FieldType::I64(_) | FieldType::F64(_) => Some("numeric"),
+        FieldType::Bool(_) => Some("bool"),
+        FieldType::Date(_) => Some("datetime"),
+        FieldType::Facet(_) => Some("facet"),
+        FieldType::Bytes(_) => Some("bytes"),
+        FieldType::IpAddr(_) => Some("ip"),
+        FieldType::JsonObject(_) => None,
+    }
+}
+
+/// Checks that each of the fields has compatible types in all the indexes defining it.
+///
+/// `index_schemas` pairs the index IDs with their schema. Fields missing from an index, or
+/// captured by a JSON field (including the dynamic field), are not checked for that index.
+pub(crate) fn validate_field_types_compatibility(
+    index_schemas: &[(&str, Schema)],
+    field_names: &BTreeSet<String>,
+) -> crate::Result<()> {
+    for field_name in field_names {
+        let mut first_field_type_opt: Option<(&str, &FieldType)> = None;
+
+        for (index_id, schema) in index_schemas {
+            let dynamic_field_opt = schema.get_field(DYNAMIC_FIELD_NAME).ok();
+            let Some((field, _json_path)) =
+                schema.find_field_with_default(field_name, dynamic_field_opt)
+            else {
+                continue;
+            };
+            let field_type = schema.get_field_entry(field).field_type();
+            let Some(type_family) = field_type_family(field_type) else {
+                continue;
+            };
+            let Some((first_index_id, first_field_type)) = first_field_type_opt else {
+                first_field_type_opt = Some((*index_id, field_type));
+                continue;
+            };
+            if field_type_family(first_field_type) != Some(type_family) {
+                return Err(SearchError::InvalidArgument(format!(
+                    "field `{field_name}` has incompatible types across indexes: `{}` in index \
+                     `{first_index_id}` and `{}` in index `{index_id}`",
+                    first_field_type.value_type().name(),
+                    field_type.value_type().name(),
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use quickwit_proto::search::SortField;
+    use quickwit_query::query_ast::qast_helper;
+    use tantivy::schema::{Schema, FAST, INDEXED, STORED, TEXT};
+
+    use super::*;
+
+    fn index_schemas() -> Vec<(&'static str, Schema)> {
+        let mut schema_builder = Schema::builder();
+        schema_builder.add_text_field("body", TEXT | STORED);
+        schema_builder.add_u64_field("status", INDEXED | FAST);
+        schema_builder.add_text_field("owner", TEXT);
+        let schema_1 = schema_builder.build();
+
+        let mut schema_builder = Schema::builder();
+        schema_builder.add_text_field("body", TEXT);
+        schema_builder.add_i64_field("status", INDEXED | FAST);
+        schema_builder.add_u64_field("owner", INDEXED | FAST);
+        schema_builder.add_json_field(DYNAMIC_FIELD_NAME, TEXT);
+        let schema_2 = schema_builder.build();
+
+        let mut schema_builder = Schema::builder();
+        schema_builder.add_text_field("body", TEXT);
+        schema_builder.add_json_field(DYNAMIC_FIELD_NAME, TEXT);
+        let schema_3 = schema_builder.build();
+
+        vec![
+            ("index-1", schema_1),
+            ("index-2", schema_2),
+            ("index-3", schema_3),
+        ]
+    }
+
+    #[test]
+    fn test_requested_field_names() {
+        let query_ast = qast_helper("body:hello AND status:[200 TO 299]", &[]);
+        let search_request = SearchRequest {
+            sort_fields: vec![SortField {
+                field_name: "timestamp".to_string(),
+                ..Default::default()
+            }],
+            aggregation_request: Some(r#"{"owners": {"terms": {"field": "owner"}}}"#.to_string()),
+            ..Default::default()
+        };
+        let field_names = requested_field_names([&query_ast], &search_request);
+        assert_eq!(
+            field_names,
+            BTreeSet::from_iter(["body", "owner", "status", "timestamp"].map(ToString::to_string))
+        );
+    }
+
+    #[test]
+    fn test_validate_field_types_compatibility() {
+        let index_schemas = index_schemas();
+
+        // `status` is an unsigned integer in `index-1`, a signed integer in `index-2`, and is
+        // captured by the dynamic field in `index-3`.
+        let field_names = BTreeSet::from_iter(["body".to_string(), "status".to_string()]);
+        validate_field_types_compatibility(&index_schemas, &field_names).unwrap();
+
+        // Unknown fields are reported by the request validation.
+        let field_names = BTreeSet::from_iter(["unknown".to_string()]);
+        validate_field_types_compatibility(&index_schemas, &field_names).unwrap();
+
+        let field_names = BTreeSet::from_iter(["body".to_string(), "owner".to_string()]);
+        let search_error =
+            validate_field_types_compatibility(&index_schemas, &field_names).unwrap_err();
+        assert!(matches!(search_error, SearchError::InvalidArgument(_)));
+        assert_eq!(
+            search_error.to_string(),
+            "Invalid argument: field `owner` has incompatible types across indexes: `Str` in \
+             index `index-1` and `U64` in index `index-2`"
+        );
+    }
+}
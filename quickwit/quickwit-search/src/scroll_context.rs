@@ -27,8 +27,8 @@ use anyhow::Context;
 use base64::prelude::BASE64_STANDARD;
 use base64::Engine;
 use quickwit_metastore::SplitMetadata;
-use quickwit_proto::search::{LeafSearchResponse, PartialHit, SearchRequest};
-use quickwit_proto::types::IndexUid;
+use quickwit_proto::search::{PartialHit, SearchRequest};
+use quickwit_proto::types::{IndexUid, SplitId};
 use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 use ttl_cache::TtlCache;
@@ -50,9 +50,13 @@ const SCROLL_BATCH_LEN: usize = 1_000;
 #[derive(Serialize, Deserialize)]
 pub(crate) struct ScrollContext {
     pub split_metadatas: Vec<SplitMetadata>,
+    #[serde(default)]
+    pub split_publish_timestamps: HashMap<SplitId, i64>,
     pub search_request: SearchRequest,
     pub indexes_metas_for_leaf_search: HashMap<IndexUid, IndexMetasForLeafSearch>,
     pub total_num_hits: u64,
+    #[serde(default)]
+    pub total_num_hits_is_lower_bound: bool,
     pub max_hits_per_page: u64,
     pub cached_partial_hits_start_offset: u64,
     pub cached_partial_hits: Vec<PartialHit>,
@@ -109,7 +113,7 @@ impl ScrollContext {
         }
         self.search_request.max_hits = SCROLL_BATCH_LEN as u64;
         self.search_request.start_offset = start_offset;
-        let leaf_search_response: LeafSearchResponse = crate::root::search_partial_hits_phase(
+        let (leaf_search_response, _num_hits_per_index) = crate::root::search_partial_hits_phase(
             searcher_context,
             &self.indexes_metas_for_leaf_search,
             &self.search_request,
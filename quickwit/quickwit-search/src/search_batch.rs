@@ -0,0 +1,535 @@
+// Copyright (C) 2024 Quickwit, Inc.
+//
+// Quickwit is offered under the AGPL v3.0 and as commercial software.
+// For commercial licensing, contact us at hello@quickwit.io.
+//
+// AGPL:
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as
+// published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::HashMap;
+
+use futures::future::join_all;
+use quickwit_proto::metastore::MetastoreServiceClient;
+use quickwit_proto::search::{SearchRequest, SearchResponse};
+use serde_json::{Map as JsonMap, Value as JsonValue};
+use tantivy::aggregation::agg_req::Aggregations;
+use tracing::debug;
+
+use crate::{root_search, ClusterClient, QuickwitAggregations, SearchError, SearcherContext};
+
+/// The maximum number of search requests of a batch.
+const MAX_SEARCH_BATCH_SIZE: usize = 100;
+
+/// Search requests that can be answered by a single root search.
+///
+/// When the batch holds more than one request, the aggregations of the requests are merged into
+/// a single aggregation request, in which the name of each aggregation is prefixed by the
+/// ordinal of the request it comes from.
+struct SearchBatch {
+    search_request: SearchRequest,
+    merged_aggregations: Aggregations,
+    // Ordinals of the search requests of the batch, along with whether they requested
+    // aggregations.
+    ordinals: Vec<(usize, bool)>,
+}
+
+impl SearchBatch {
+    fn new(search_request: SearchRequest) -> Self {
+        Self {
+            search_request,
+            merged_aggregations: Aggregations::default(),
+            ordinals: Vec::new(),
+        }
+    }
+
+    /// Creates a batch holding a request that cannot be merged with other requests.
+    fn single(ordinal: usize, search_request: SearchRequest) -> Self {
+        let has_aggregations = search_request.aggregation_request.is_some();
+        Self {
+            search_request,
+            merged_aggregations: Aggregations::default(),
+            ordinals: vec![(ordinal, has_aggregations)],
+        }
+    }
+
+    fn add(&mut self, ordinal: usize, aggregations_opt: Option<Aggregations>) {
+        self.ordinals.push((ordinal, aggregations_opt.is_some()));
+
+        for (aggregation_name, aggregation) in aggregations_opt.into_iter().flatten() {
+            self.merged_aggregations
+                .insert(format!("{ordinal}_{aggregation_name}"), aggregation);
+        }
+    }
+
+    /// Returns the search request to execute for the whole batch.
+    fn search_request(&self) -> crate::Result<SearchRequest> {
+        if self.ordinals.len() == 1 {
+            return Ok(self.search_request.clone());
+        }
+        let aggregation_request = if self.merged_aggregations.is_empty() {
+            None
+        } else {
+            Some(serde_json::to_string(&self.merged_aggregations)?)
+        };
+        Ok(SearchRequest {
+            aggregation_request,
+            ..self.search_request.clone()
+        })
+    }
+
+    /// Splits the response of the batch search request into one response per search request.
+    fn split_response(
+        self,
+        search_response: SearchResponse,
+    ) -> crate::Result<Vec<(usize, SearchResponse)>> {
+        if self.ordinals.len() == 1 {
+            let (ordinal, _) = self.ordinals[0];
+            return Ok(vec![(ordinal, search_response)]);
+        }
+        let mut aggregation_results: HashMap<usize, JsonMap<String, JsonValue>> = HashMap::new();
+
+        if let Some(aggregation_json) = &search_response.aggregation {
+            let merged_aggregation_results: JsonMap<String, JsonValue> =
+                serde_json::from_str(aggregation_json)?;
+
+            for (prefixed_name, aggregation_result) in merged_aggregation_results {
+                let (ordinal, aggregation_name) = prefixed_name
+                    .split_once('_')
+                    .and_then(|(ordinal_str, aggregation_name)| {
+                        let ordinal: usize = ordinal_str.parse().ok()?;
+                        Some((ordinal, aggregation_name.to_string()))
+                    })
+                    .ok_or_else(|| {
+                        SearchError::Internal(format!(
+                            "unexpected aggregation `{prefixed_name}` in batch search response"
+                        ))
+                    })?;
+                aggregation_results
+                    .entry(ordinal)
+                    .or_default()
+                    .insert(aggregation_name, aggregation_result);
+            }
+        }
+        let mut search_responses = Vec::with_capacity(self.ordinals.len());
+
+        for (ordinal, has_aggregations) in self.ordinals {
+            let aggregation = if has_aggregations && search_response.aggregation.is_some() {
+                let aggregation_results = aggregation_results.remove(&ordinal).unwrap_or_default();
+                Some(serde_json::to_string(&aggregation_results)?)
+            } else {
+                None
+            };
+            let search_response = SearchResponse {
+                aggregation,
+                ..search_response.clone()
+            };
+            search_responses.push((ordinal, search_response));
+        }
+        Ok(search_responses)
+    }
+}
+
+/// Returns the tantivy aggregations of a search request, or `None` if the aggregation request
+/// is not a tantivy aggregation request.
+fn parse_tantivy_aggregations(aggregations_json: &str) -> crate::Result<Option<Aggregations>> {
+    match serde_json::from_str(aggregations_json)? {
+        QuickwitAggregations::TantivyAggregations(aggregations) => Ok(Some(aggregations)),
+        QuickwitAggregations::FindTraceIdsAggregation(_) => Ok(None),
+    }
+}
+
+/// Groups the search requests that only differ by their aggregations into batches.
+///
+/// Requests that cannot share a leaf pass with other requests (scroll requests, requests with
+/// invalid or non-tantivy aggregations) end up in a batch of their own.
+fn group_search_requests(search_requests: Vec<SearchRequest>) -> Vec<SearchBatch> {
+    let mut batches: Vec<SearchBatch> = Vec::new();
+    let mut batch_ordinals: HashMap<SearchRequest, usize> = HashMap::new();
+
+    for (ordinal, search_request) in search_requests.into_iter().enumerate() {
+        let aggregations_opt = match &search_request.aggregation_request {
+            Some(aggregations_json) => match parse_tantivy_aggregations(aggregations_json) {
+                Ok(Some(aggregations)) => Some(aggregations),
+                // Let the root search report the invalid aggregations.
+                Ok(None) | Err(_) => {
+                    batches.push(SearchBatch::single(ordinal, search_request));
+                    continue;
+                }
+            },
+            None => None,
+        };
+        if search_request.scroll_ttl_secs.is_some() {
+            batches.push(SearchBatch::single(ordinal, search_request));
+            continue;
+        }
+        let batch_key = SearchRequest {
+            aggregation_request: None,
+            ..search_request.clone()
+        };
+        if let Some(&batch_ordinal) = batch_ordinals.get(&batch_key) {
+            batches[batch_ordinal].add(ordinal, aggregations_opt);
+        } else {
+            let mut batch = SearchBatch::new(search_request);
+            batch.add(ordinal, aggregations_opt);
+            batch_ordinals.insert(batch_key, batches.len());
+            batches.push(batch);
+        }
+    }
+    batches
+}
+
+/// Returns an error if a batch of search requests holds more than `MAX_SEARCH_BATCH_SIZE`
+/// requests.
+pub(crate) fn check_search_batch_size(num_search_requests: usize) -> crate::Result<()> {
+    if num_search_requests > MAX_SEARCH_BATCH_SIZE {
+        return Err(SearchError::InvalidArgument(format!(
+            "search batch holds {num_search_requests} requests, exceeding the maximum of \
+             {MAX_SEARCH_BATCH_SIZE}"
+        )));
+    }
+    Ok(())
+}
+
+/// Performs a batch of root searches.
+///
+/// Search requests that only differ by their aggregations are merged into a single search
+/// request, so that all their aggregations are computed in a single pass over the splits. Each
+/// merged search takes a root search permit, like a regular root search.
+///
+/// The results are returned in the order of the requests: the failure of a search only fails the
+/// requests it answers. An error is returned only if the batch holds more than
+/// `MAX_SEARCH_BATCH_SIZE` requests.
+pub async fn root_search_batch(
+    searcher_context: &SearcherContext,
+    search_requests: Vec<SearchRequest>,
+    metastore: MetastoreServiceClient,
+    cluster_client: &ClusterClient,
+) -> crate::Result<Vec<crate::Result<SearchResponse>>> {
+    let num_search_requests = search_requests.len();
+    check_search_batch_size(num_search_requests)?;
+
+    let batches = group_search_requests(search_requests);
+    debug!(
+        num_search_requests,
+        num_batches = batches.len(),
+        "root-search-batch"
+    );
+    let batch_futures = batches.into_iter().map(|batch| {
+        let metastore = metastore.clone();
+        async move {
+            let ordinals: Vec<usize> = batch.ordinals.iter().map(|(ordinal, _)| *ordinal).collect();
+            match search_batch(searcher_context, batch, metastore, cluster_client).await {
+                Ok(search_responses) => search_responses
+                    .into_iter()
+                    .map(|(ordinal, search_response)| (ordinal, Ok(search_response)))
+                    .collect(),
+                Err(search_error) => ordinals
+                    .into_iter()
+                    .map(|ordinal| (ordinal, Err(search_error.clone())))
+                    .collect::<Vec<_>>(),
+            }
+        }
+    });
+    let mut search_results: Vec<(usize, crate::Result<SearchResponse>)> = join_all(batch_futures)
+        .await
+        .into_iter()
+        .flatten()
+        .collect();
+    search_results.sort_unstable_by_key(|(ordinal, _)| *ordinal);
+    Ok(search_results
+        .into_iter()
+        .map(|(_, search_result)| search_result)
+        .collect())
+}
+
+async fn search_batch(
+    searcher_context: &SearcherContext,
+    batch: SearchBatch,
+    metastore: MetastoreServiceClient,
+    cluster_client: &ClusterClient,
+) -> crate::Result<Vec<(usize, SearchResponse)>> {
+    let _search_permit_opt = match &searcher_context.root_search_permits_opt {
+        Some(root_search_permits) => Some(root_search_permits.acquire().await?),
+        None => None,
+    };
+    let search_request = batch.search_request()?;
+    let search_response =
+        root_search(searcher_context, search_request, metastore, cluster_client).await?;
+    batch.split_response(search_response)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use quickwit_common::ServiceStream;
+    use quickwit_indexing::MockSplitBuilder;
+    use quickwit_metastore::{IndexMetadata, ListSplitsResponseExt};
+    use quickwit_proto::metastore::{
+        ListIndexesMetadataRequest, ListIndexesMetadataResponse, ListSplitsResponse,
+    };
+    use quickwit_proto::search::{LeafSearchRequest, LeafSearchResponse};
+    use quickwit_query::query_ast::qast_json_helper;
+    use serde_json::json;
+
+    use super::*;
+    use crate::{searcher_pool_for_test, MockSearchService, SearchJobPlacer};
+
+    fn search_request_for_test(query: &str, aggregations: Option<JsonValue>) -> SearchRequest {
+        SearchRequest {
+            index_id_patterns: vec!["test-index".to_string()],
+            query_ast: qast_json_helper(query, &["body"]),
+            aggregation_request: aggregations.map(|aggregations| aggregations.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_group_search_requests() {
+        let search_requests = vec![
+            search_request_for_test(
+                "test",
+                Some(json!({"avg_price": {"avg": {"field": "price"}}})),
+            ),
+            search_request_for_test("other", None),
+            search_request_for_test("test", None),
+            search_request_for_test(
+                "test",
+                Some(json!({"colors": {"terms": {"field": "color"}}})),
+            ),
+        ];
+        let batches = group_search_requests(search_requests);
+        assert_eq!(batches.len(), 2);
+
+        assert_eq!(batches[0].ordinals, [(0, true), (2, false), (3, true)]);
+        let search_request = batches[0].search_request().unwrap();
+        let aggregations: JsonValue =
+            serde_json::from_str(search_request.aggregation_request.as_ref().unwrap()).unwrap();
+        let mut aggregation_names: Vec<&String> =
+            aggregations.as_object().unwrap().keys().collect();
+        aggregation_names.sort();
+        assert_eq!(aggregation_names, ["0_avg_price", "3_colors"]);
+
+        assert_eq!(batches[1].ordinals, [(1, false)]);
+        assert_eq!(
+            batches[1].search_request().unwrap(),
+            search_request_for_test("other", None)
+        );
+    }
+
+    #[test]
+    fn test_search_batch_split_response() {
+        let mut batch = SearchBatch::new(search_request_for_test("test", None));
+        batch.add(0, Some(Aggregations::default()));
+        batch.add(1, None);
+        batch.add(2, Some(Aggregations::default()));
+
+        let search_response = SearchResponse {
+            num_hits: 5,
+            aggregation: Some(
+                json!({
+                    "0_avg_price": {"value": 10.0},
+                    "2_colors": {"buckets": []},
+                    "2_max_price": {"value": 100.0},
+                })
+                .to_string(),
+            ),
+            ..Default::default()
+        };
+        let search_responses = batch.split_response(search_response).unwrap();
+        assert_eq!(search_responses.len(), 3);
+
+        let aggregation_results: Vec<Option<JsonValue>> = search_responses
+            .iter()
+            .map(|(_, search_response)| {
+                assert_eq!(search_response.num_hits, 5);
+                search_response
+                    .aggregation
+                    .as_ref()
+                    .map(|aggregation| serde_json::from_str(aggregation).unwrap())
+            })
+            .collect();
+        assert_eq!(
+            aggregation_results,
+            [
+                Some(json!({"avg_price": {"value": 10.0}})),
+                None,
+                Some(json!({"colors": {"buckets": []}, "max_price": {"value": 100.0}})),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_root_search_batch_single_leaf_search() {
+        let mut metastore = MetastoreServiceClient::mock();
+        let index_metadata = IndexMetadata::for_test("test-index", "ram:///test-index");
+        let index_uid = index_metadata.index_uid.clone();
+        metastore
+            .expect_list_indexes_metadata()
+            .returning(move |_list_indexes_metadata_request| {
+                Ok(ListIndexesMetadataResponse::try_from_indexes_metadata(vec![
+                    index_metadata.clone()
+                ])
+                .unwrap())
+            });
+        metastore
+            .expect_list_splits()
+            .returning(move |_list_splits_request| {
+                let splits = vec![MockSplitBuilder::new("split1")
+                    .with_index_uid(&index_uid)
+                    .build()];
+                let splits_response = ListSplitsResponse::try_from_splits(splits).unwrap();
+                Ok(ServiceStream::from(vec![Ok(splits_response)]))
+            });
+        let leaf_search_requests: Arc<Mutex<Vec<LeafSearchRequest>>> = Default::default();
+        let leaf_search_requests_clone = leaf_search_requests.clone();
+
+        let mut mock_search_service = MockSearchService::new();
+        mock_search_service.expect_leaf_search().times(1).returning(
+            move |leaf_search_request: LeafSearchRequest| {
+                leaf_search_requests_clone
+                    .lock()
+                    .unwrap()
+                    .push(leaf_search_request);
+                Ok(LeafSearchResponse {
+                    num_hits: 5,
+                    num_attempted_splits: 1,
+                    ..Default::default()
+                })
+            },
+        );
+        let searcher_pool = searcher_pool_for_test([("127.0.0.1:1001", mock_search_service)]);
+        let search_job_placer = SearchJobPlacer::new(searcher_pool);
+        let cluster_client = ClusterClient::new(search_job_placer);
+        let searcher_context = SearcherContext::for_test();
+
+        let search_requests = vec![
+            search_request_for_test(
+                "test",
+                Some(json!({"avg_price": {"avg": {"field": "price"}}})),
+            ),
+            search_request_for_test(
+                "test",
+                Some(json!({"colors": {"terms": {"field": "color"}}})),
+            ),
+        ];
+        let search_responses = root_search_batch(
+            &searcher_context,
+            search_requests,
+            MetastoreServiceClient::from(metastore),
+            &cluster_client,
+        )
+        .await
+        .unwrap();
+        assert_eq!(search_responses.len(), 2);
+        assert_eq!(search_responses[0].as_ref().unwrap().num_hits, 5);
+        assert_eq!(search_responses[1].as_ref().unwrap().num_hits, 5);
+
+        let leaf_search_requests = leaf_search_requests.lock().unwrap();
+        let search_request = leaf_search_requests[0].search_request.as_ref().unwrap();
+        let aggregations: JsonValue =
+            serde_json::from_str(search_request.aggregation_request.as_ref().unwrap()).unwrap();
+        let mut aggregation_names: Vec<&String> =
+            aggregations.as_object().unwrap().keys().collect();
+        aggregation_names.sort();
+        assert_eq!(aggregation_names, ["0_avg_price", "1_colors"]);
+    }
+
+    #[tokio::test]
+    async fn test_root_search_batch_returns_per_request_results() {
+        let mut metastore = MetastoreServiceClient::mock();
+        let index_metadata = IndexMetadata::for_test("test-index", "ram:///test-index");
+        let index_uid = index_metadata.index_uid.clone();
+        metastore.expect_list_indexes_metadata().returning(
+            move |list_indexes_metadata_request: ListIndexesMetadataRequest| {
+                let indexes_metadata =
+                    if list_indexes_metadata_request.index_id_patterns == ["test-index"] {
+                        vec![index_metadata.clone()]
+                    } else {
+                        Vec::new()
+                    };
+                Ok(
+                    ListIndexesMetadataResponse::try_from_indexes_metadata(indexes_metadata)
+                        .unwrap(),
+                )
+            },
+        );
+        metastore
+            .expect_list_splits()
+            .returning(move |_list_splits_request| {
+                let splits = vec![MockSplitBuilder::new("split1")
+                    .with_index_uid(&index_uid)
+                    .build()];
+                let splits_response = ListSplitsResponse::try_from_splits(splits).unwrap();
+                Ok(ServiceStream::from(vec![Ok(splits_response)]))
+            });
+        let mut mock_search_service = MockSearchService::new();
+        mock_search_service.expect_leaf_search().times(1).returning(
+            |_leaf_search_request: LeafSearchRequest| {
+                Ok(LeafSearchResponse {
+                    num_hits: 5,
+                    num_attempted_splits: 1,
+                    ..Default::default()
+                })
+            },
+        );
+        let searcher_pool = searcher_pool_for_test([("127.0.0.1:1001", mock_search_service)]);
+        let search_job_placer = SearchJobPlacer::new(searcher_pool);
+        let cluster_client = ClusterClient::new(search_job_placer);
+        let searcher_context = SearcherContext::for_test();
+
+        let search_requests = vec![
+            search_request_for_test("test", None),
+            SearchRequest {
+                index_id_patterns: vec!["missing-index".to_string()],
+                ..search_request_for_test("test", None)
+            },
+        ];
+        let search_results = root_search_batch(
+            &searcher_context,
+            search_requests,
+            MetastoreServiceClient::from(metastore),
+            &cluster_client,
+        )
+        .await
+        .unwrap();
+        assert_eq!(search_results.len(), 2);
+        assert_eq!(search_results[0].as_ref().unwrap().num_hits, 5);
+        assert!(matches!(
+            search_results[1].as_ref().unwrap_err(),
+            SearchError::IndexesNotFound { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_root_search_batch_rejects_too_large_batch() {
+        let metastore = MetastoreServiceClient::mock();
+        let searcher_pool = searcher_pool_for_test([("127.0.0.1:1001", MockSearchService::new())]);
+        let search_job_placer = SearchJobPlacer::new(searcher_pool);
+        let cluster_client = ClusterClient::new(search_job_placer);
+        let searcher_context = SearcherContext::for_test();
+
+        let search_requests =
+            vec![search_request_for_test("test", None); MAX_SEARCH_BATCH_SIZE + 1];
+        let error = root_search_batch(
+            &searcher_context,
+            search_requests,
+            MetastoreServiceClient::from(metastore),
+            &cluster_client,
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(error, SearchError::InvalidArgument(_)));
+    }
+}
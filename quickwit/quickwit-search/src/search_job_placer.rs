@@ -22,6 +22,7 @@ use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::hash::{Hash, Hasher};
 use std::net::SocketAddr;
+use std::time::Duration;
 
 use anyhow::bail;
 use async_trait::async_trait;
@@ -29,7 +30,8 @@ use quickwit_common::pubsub::EventSubscriber;
 use quickwit_common::rendezvous_hasher::{node_affinity, sort_by_rendez_vous_hash};
 use quickwit_proto::search::{ReportSplit, ReportSplitsRequest};
 
-use crate::{SearchServiceClient, SearcherPool};
+use crate::searcher_health::{SearchRequestKind, SearcherHealth};
+use crate::{SearchError, SearchServiceClient, SearcherPool};
 
 /// Job.
 /// The unit in which distributed search is performed.
@@ -61,6 +63,8 @@ pub trait Job {
 pub struct SearchJobPlacer {
     /// Search clients pool.
     searcher_pool: SearcherPool,
+    /// Recent error rate and latency of the searchers.
+    searcher_health: SearcherHealth,
 }
 
 #[async_trait]
@@ -103,7 +107,27 @@ impl fmt::Debug for SearchJobPlacer {
 impl SearchJobPlacer {
     /// Returns an [`SearchJobPlacer`] from a search service client pool.
     pub fn new(searcher_pool: SearcherPool) -> Self {
-        Self { searcher_pool }
+        Self {
+            searcher_pool,
+            searcher_health: SearcherHealth::default(),
+        }
+    }
+
+    /// Records the outcome of a request sent to the searcher listening on `grpc_addr`.
+    ///
+    /// Searchers that are erroring or much slower than their peers are deprioritized during job
+    /// assignment until they recover.
+    pub(crate) fn record_outcome<T>(
+        &self,
+        grpc_addr: SocketAddr,
+        request_kind: SearchRequestKind,
+        result: &Result<T, SearchError>,
+        latency: Duration,
+    ) {
+        // Non-retryable errors are caused by the request, not by the searcher.
+        let is_error = matches!(result, Err(search_error) if search_error.is_retryable());
+        self.searcher_health
+            .record_outcome(grpc_addr, request_kind, is_error, latency);
     }
 }
 
@@ -135,6 +159,10 @@ impl SearchJobPlacer {
             })
             .collect();
         sort_by_rendez_vous_hash(&mut nodes[..], affinity_key);
+        // Unhealthy nodes go last. The sort is stable, so the rendez-vous hash ordering is
+        // preserved otherwise.
+        let unhealthy_addrs = self.searcher_health.nodes_to_deprioritize();
+        nodes.sort_by_key(|node| unhealthy_addrs.contains(&node.socket_addr));
         nodes
             .into_iter()
             .map(|socket_addr_and_client| socket_addr_and_client.client)
@@ -144,6 +172,7 @@ impl SearchJobPlacer {
     /// Returns a list of pair (SocketAddr, `Vec<Job>`)
     ///
     /// When exclude_addresses filters all clients it is ignored.
+    /// Unhealthy clients are only assigned jobs when all the candidate clients are unhealthy.
     pub async fn assign_jobs<J: Job>(
         &self,
         mut jobs: Vec<J>,
@@ -172,6 +201,15 @@ impl SearchJobPlacer {
                 "failed to assign search jobs. there are no available searcher nodes in the pool"
             );
         }
+        let unhealthy_addrs = self.searcher_health.nodes_to_deprioritize();
+
+        if candidate_nodes
+            .iter()
+            .any(|candidate_node| !unhealthy_addrs.contains(&candidate_node.grpc_addr))
+        {
+            candidate_nodes
+                .retain(|candidate_node| !unhealthy_addrs.contains(&candidate_node.grpc_addr));
+        }
         jobs.sort_unstable_by(Job::compare_cost);
 
         let mut job_assignments: HashMap<SocketAddr, (SearchServiceClient, Vec<J>)> =
@@ -322,4 +360,62 @@ mod tests {
             assert_eq!(assigned_jobs, expected_assigned_jobs);
         }
     }
+
+    #[tokio::test]
+    async fn test_search_job_placer_deprioritizes_unhealthy_nodes() {
+        tokio::time::pause();
+        let searcher_pool = searcher_pool_for_test([
+            ("127.0.0.1:1001", MockSearchService::new()),
+            ("127.0.0.1:1002", MockSearchService::new()),
+        ]);
+        let search_job_placer = SearchJobPlacer::new(searcher_pool);
+        let searcher_addr_1: SocketAddr = ([127, 0, 0, 1], 1001).into();
+        let searcher_addr_2: SocketAddr = ([127, 0, 0, 1], 1002).into();
+
+        let overloaded_error: crate::Result<()> = Err(SearchError::TooManyRequests);
+        for _ in 0..5 {
+            search_job_placer.record_outcome(
+                searcher_addr_1,
+                SearchRequestKind::LeafSearch,
+                &overloaded_error,
+                Duration::ZERO,
+            );
+        }
+        let jobs: Vec<SearchJob> = (0..10)
+            .map(|split_ord| SearchJob::for_test(&format!("split{split_ord}"), 1))
+            .collect();
+        let assigned_addrs: Vec<SocketAddr> = search_job_placer
+            .assign_jobs(jobs.clone(), &HashSet::new())
+            .await
+            .unwrap()
+            .map(|(client, _jobs)| client.grpc_addr())
+            .collect();
+        assert_eq!(assigned_addrs, vec![searcher_addr_2]);
+
+        let best_node_addrs: Vec<SocketAddr> = search_job_placer
+            .best_nodes_per_affinity(b"split0")
+            .await
+            .map(|client| client.grpc_addr())
+            .collect();
+        assert_eq!(best_node_addrs, vec![searcher_addr_2, searcher_addr_1]);
+
+        // Unhealthy nodes are still used when they are the only candidates.
+        let excluded_addrs = HashSet::from([searcher_addr_2]);
+        let client = search_job_placer
+            .assign_job(SearchJob::for_test("split0", 1), &excluded_addrs)
+            .await
+            .unwrap();
+        assert_eq!(client.grpc_addr(), searcher_addr_1);
+
+        // The unhealthy node recovers automatically.
+        tokio::time::advance(Duration::from_secs(60)).await;
+        let mut assigned_addrs: Vec<SocketAddr> = search_job_placer
+            .assign_jobs(jobs, &HashSet::new())
+            .await
+            .unwrap()
+            .map(|(client, _jobs)| client.grpc_addr())
+            .collect();
+        assigned_addrs.sort_unstable();
+        assert_eq!(assigned_addrs, vec![searcher_addr_1, searcher_addr_2]);
+    }
 }
@@ -0,0 +1,279 @@
+// Copyright (C) 2024 Quickwit, Inc.
+//
+// Quickwit is offered under the AGPL v3.0 and as commercial software.
+// For commercial licensing, contact us at hello@quickwit.io.
+//
+// AGPL:
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as
+// published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use quickwit_common::metrics::GaugeGuard;
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+use crate::{SearchError, SEARCH_METRICS};
+
+/// Limits the number of root searches running concurrently on a searcher.
+///
+/// Once all permits are taken, up to `max_queued` searches wait for a permit to be released.
+/// Searches beyond that bound are rejected right away with [`SearchError::TooManyRequests`].
+pub struct SearchPermits {
+    semaphore: Semaphore,
+    num_queued: AtomicUsize,
+    max_queued: usize,
+}
+
+impl SearchPermits {
+    /// Creates a new `SearchPermits` allowing `max_concurrent` searches to run concurrently.
+    pub fn new(max_concurrent: usize, max_queued: usize) -> SearchPermits {
+        SearchPermits {
+            semaphore: Semaphore::new(max_concurrent),
+            num_queued: AtomicUsize::new(0),
+            max_queued,
+        }
+    }
+
+    /// Acquires a permit, waiting in the queue if necessary.
+    pub async fn acquire(&self) -> crate::Result<SemaphorePermit<'_>> {
+        if let Ok(permit) = self.semaphore.try_acquire() {
+            return Ok(permit);
+        }
+        let _queue_slot = QueueSlot::acquire(&self.num_queued, self.max_queued)
+            .ok_or(SearchError::TooManyRequests)?;
+        let permit = self
+            .semaphore
+            .acquire()
+            .await
+            .expect("the search permits semaphore should never be closed");
+        Ok(permit)
+    }
+
+    /// Returns the number of searches currently waiting for a permit.
+    pub fn num_queued(&self) -> usize {
+        self.num_queued.load(Ordering::Acquire)
+    }
+}
+
+impl std::fmt::Debug for SearchPermits {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("SearchPermits")
+            .field("num_available", &self.semaphore.available_permits())
+            .field("num_queued", &self.num_queued())
+            .field("max_queued", &self.max_queued)
+            .finish()
+    }
+}
+
+/// Slot in the queue of searches waiting for a permit, released on drop so that searches
+/// cancelled while queued do not leak it.
+struct QueueSlot<'a> {
+    num_queued: &'a AtomicUsize,
+}
+
+impl<'a> QueueSlot<'a> {
+    fn acquire(num_queued: &'a AtomicUsize, max_queued: usize) -> Option<QueueSlot<'a>> {
+        num_queued
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |num_queued| {
+                if num_queued < max_queued {
+                    Some(num_queued + 1)
+                } else {
+                    None
+                }
+            })
+            .ok()?;
+        Some(QueueSlot { num_queued })
+    }
+}
+
+impl Drop for QueueSlot<'_> {
+    fn drop(&mut self) {
+        self.num_queued.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Limits the number of splits opened concurrently by the leaf searches of a searcher, which
+/// bounds the file descriptors and memory they hold.
+///
+/// Once all permits are taken, leaf searches wait for a permit to be released.
+pub struct OpenSplitPermits {
+    semaphore_opt: Option<Semaphore>,
+    num_open: AtomicUsize,
+    num_waiting: AtomicUsize,
+}
+
+impl OpenSplitPermits {
+    /// Creates a new `OpenSplitPermits` allowing `max_open_splits` splits to be opened
+    /// concurrently, or an unlimited number if `None`.
+    pub fn new(max_open_splits_opt: Option<usize>) -> OpenSplitPermits {
+        OpenSplitPermits {
+            semaphore_opt: max_open_splits_opt.map(Semaphore::new),
+            num_open: AtomicUsize::new(0),
+            num_waiting: AtomicUsize::new(0),
+        }
+    }
+
+    /// Waits for a permit to open a split. The split should be kept open only as long as the
+    /// returned permit is alive.
+    pub async fn acquire(&self) -> OpenSplitPermit<'_> {
+        let semaphore_permit_opt = if let Some(semaphore) = &self.semaphore_opt {
+            let semaphore_permit = match semaphore.try_acquire() {
+                Ok(semaphore_permit) => semaphore_permit,
+                Err(_) => {
+                    let _waiting_guard = CounterGuard::new(&self.num_waiting);
+                    let _waiting_gauge_guard =
+                        GaugeGuard::from_gauge(&SEARCH_METRICS.leaf_search_open_split_waiters);
+                    semaphore
+                        .acquire()
+                        .await
+                        .expect("the open split semaphore should never be closed")
+                }
+            };
+            Some(semaphore_permit)
+        } else {
+            None
+        };
+        OpenSplitPermit {
+            _open_guard: CounterGuard::new(&self.num_open),
+            _open_gauge_guard: GaugeGuard::from_gauge(&SEARCH_METRICS.leaf_search_open_splits),
+            _semaphore_permit_opt: semaphore_permit_opt,
+        }
+    }
+
+    /// Returns the number of splits currently open.
+    pub fn num_open(&self) -> usize {
+        self.num_open.load(Ordering::Acquire)
+    }
+
+    /// Returns the number of leaf searches currently waiting for a permit.
+    pub fn num_waiting(&self) -> usize {
+        self.num_waiting.load(Ordering::Acquire)
+    }
+}
+
+impl std::fmt::Debug for OpenSplitPermits {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("OpenSplitPermits")
+            .field(
+                "num_available",
+                &self
+                    .semaphore_opt
+                    .as_ref()
+                    .map(|semaphore| semaphore.available_permits()),
+            )
+            .field("num_open", &self.num_open())
+            .field("num_waiting", &self.num_waiting())
+            .finish()
+    }
+}
+
+/// Permit to keep a split open, released on drop.
+pub struct OpenSplitPermit<'a> {
+    // Fields are dropped in declaration order: the split must no longer be counted as open when
+    // the semaphore permit is handed over to a waiting leaf search.
+    _open_guard: CounterGuard<'a>,
+    _open_gauge_guard: GaugeGuard,
+    _semaphore_permit_opt: Option<SemaphorePermit<'a>>,
+}
+
+/// Increments a counter for as long as it is alive.
+struct CounterGuard<'a> {
+    counter: &'a AtomicUsize,
+}
+
+impl<'a> CounterGuard<'a> {
+    fn new(counter: &'a AtomicUsize) -> CounterGuard<'a> {
+        counter.fetch_add(1, Ordering::AcqRel);
+        CounterGuard { counter }
+    }
+}
+
+impl Drop for CounterGuard<'_> {
+    fn drop(&mut self) {
+        self.counter.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_search_permits_queue_then_reject() {
+        let search_permits = SearchPermits::new(1, 1);
+        let running_permit = search_permits.acquire().await.unwrap();
+
+        let queued_search = search_permits.acquire();
+        tokio::pin!(queued_search);
+        assert!(
+            tokio::time::timeout(Duration::from_millis(50), &mut queued_search)
+                .await
+                .is_err()
+        );
+        assert_eq!(search_permits.num_queued(), 1);
+
+        let rejected_search_err = search_permits.acquire().await.unwrap_err();
+        assert!(matches!(rejected_search_err, SearchError::TooManyRequests));
+
+        drop(running_permit);
+        let _queued_permit = queued_search.await.unwrap();
+        assert_eq!(search_permits.num_queued(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_search_permits_cancelled_search_releases_queue_slot() {
+        let search_permits = SearchPermits::new(1, 1);
+        let _running_permit = search_permits.acquire().await.unwrap();
+
+        tokio::time::timeout(Duration::from_millis(50), search_permits.acquire())
+            .await
+            .unwrap_err();
+        assert_eq!(search_permits.num_queued(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_open_split_permits_bound_open_splits() {
+        let open_split_permits = Arc::new(OpenSplitPermits::new(Some(3)));
+        let max_num_open = Arc::new(AtomicUsize::new(0));
+
+        let open_split_futures = (0..32).map(|_| {
+            let open_split_permits = open_split_permits.clone();
+            let max_num_open = max_num_open.clone();
+            tokio::spawn(async move {
+                let _open_split_permit = open_split_permits.acquire().await;
+                max_num_open.fetch_max(open_split_permits.num_open(), Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            })
+        });
+        for open_split_result in futures::future::join_all(open_split_futures).await {
+            open_split_result.unwrap();
+        }
+        assert_eq!(max_num_open.load(Ordering::SeqCst), 3);
+        assert_eq!(open_split_permits.num_open(), 0);
+        assert_eq!(open_split_permits.num_waiting(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_open_split_permits_unlimited() {
+        let open_split_permits = OpenSplitPermits::new(None);
+        let permits: Vec<OpenSplitPermit> =
+            futures::future::join_all((0..32).map(|_| open_split_permits.acquire())).await;
+        assert_eq!(open_split_permits.num_open(), 32);
+        assert_eq!(open_split_permits.num_waiting(), 0);
+        drop(permits);
+        assert_eq!(open_split_permits.num_open(), 0);
+    }
+}
@@ -17,10 +17,11 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
+use std::collections::BTreeMap;
 use std::convert::TryFrom;
 
 use quickwit_common::truncate_str;
-use quickwit_proto::search::SearchResponse;
+use quickwit_proto::search::{HitsRelation, SearchResponse};
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 
@@ -32,6 +33,13 @@ use crate::error::SearchError;
 pub struct SearchResponseRest {
     /// Overall number of documents matching the query.
     pub num_hits: u64,
+    /// Whether `num_hits` is exact (`eq`) or a lower bound (`gte`).
+    #[serde(default)]
+    pub num_hits_relation: NumHitsRelation,
+    /// Number of documents matching the query in each index. Only present if the search targets
+    /// several indexes.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub num_hits_per_index: BTreeMap<String, u64>,
     #[schema(value_type = Vec<Object>)]
     /// List of hits returned.
     pub hits: Vec<JsonValue>,
@@ -39,14 +47,46 @@ pub struct SearchResponseRest {
     #[schema(value_type = Vec<Object>)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub snippets: Option<Vec<JsonValue>>,
+    /// Publish timestamp of the split of each hit, in the order of `hits`. Only present if
+    /// requested with `include_split_publish_timestamp`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub split_publish_timestamps: Option<Vec<Option<i64>>>,
     /// Elapsed time.
     pub elapsed_time_micros: u64,
     /// Search errors.
     pub errors: Vec<String>,
+    /// Whether some splits could not be searched, in which case `errors` lists them.
+    #[serde(default)]
+    pub partial: bool,
     /// Aggregations.
     #[schema(value_type = Object)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub aggregations: Option<JsonValue>,
+    /// Opaque cursor to pass as `search_after` to the next search in order to fetch the hits
+    /// following the last hit of this response. Absent if the response has no hits.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search_after: Option<String>,
+}
+
+/// Relation between the reported number of hits and the actual number of hits, named after the
+/// `total.relation` field of Elasticsearch search responses.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, Eq, PartialEq, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum NumHitsRelation {
+    /// The number of hits is exact.
+    #[default]
+    Eq,
+    /// The number of hits is a lower bound of the actual number of hits.
+    Gte,
+}
+
+impl From<HitsRelation> for NumHitsRelation {
+    fn from(hits_relation: HitsRelation) -> Self {
+        match hits_relation {
+            HitsRelation::Equal => NumHitsRelation::Eq,
+            HitsRelation::GreaterThanOrEqual => NumHitsRelation::Gte,
+        }
+    }
 }
 
 impl TryFrom<SearchResponse> for SearchResponseRest {
@@ -55,7 +95,12 @@ impl TryFrom<SearchResponse> for SearchResponseRest {
     fn try_from(search_response: SearchResponse) -> Result<Self, Self::Error> {
         let mut documents = Vec::with_capacity(search_response.hits.len());
         let mut snippets = Vec::new();
+        let mut split_publish_timestamps = Vec::with_capacity(search_response.hits.len());
+        let mut has_split_publish_timestamp = false;
         for hit in search_response.hits {
+            has_split_publish_timestamp |= hit.split_publish_timestamp.is_some();
+            split_publish_timestamps.push(hit.split_publish_timestamp);
+
             let document: JsonValue = serde_json::from_str(&hit.json).map_err(|err| {
                 SearchError::Internal(format!(
                     "failed to serialize document `{}` to JSON: `{}`",
@@ -81,6 +126,11 @@ impl TryFrom<SearchResponse> for SearchResponseRest {
         } else {
             None
         };
+        let split_publish_timestamps_opt = if has_split_publish_timestamp {
+            Some(split_publish_timestamps)
+        } else {
+            None
+        };
 
         let aggregations_opt = if let Some(aggregation_json) = search_response.aggregation {
             let aggregation: JsonValue = serde_json::from_str(&aggregation_json)
@@ -90,13 +140,26 @@ impl TryFrom<SearchResponse> for SearchResponseRest {
             None
         };
 
+        let mut errors = search_response.errors;
+        errors.extend(
+            search_response
+                .failed_splits
+                .iter()
+                .map(|failed_split| failed_split.to_string()),
+        );
+
         Ok(SearchResponseRest {
             num_hits: search_response.num_hits,
+            num_hits_relation: search_response.num_hits_relation().into(),
+            num_hits_per_index: search_response.num_hits_per_index.into_iter().collect(),
             hits: documents,
             snippets: snippet_opt,
+            split_publish_timestamps: split_publish_timestamps_opt,
             elapsed_time_micros: search_response.elapsed_time_micros,
-            errors: search_response.errors,
+            errors,
+            partial: search_response.partial,
             aggregations: aggregations_opt,
+            search_after: None,
         })
     }
 }
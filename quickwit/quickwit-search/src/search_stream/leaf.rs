@@ -186,7 +186,9 @@ async fn leaf_search_stream_single_split(
     warmup_info.fast_field_names.extend(fast_field_names);
     warmup_info.simplify();
 
+    let warmup_permit_opt = searcher_context.acquire_warmup_permit().await;
     warmup(&searcher, &warmup_info).await?;
+    drop(warmup_permit_opt);
 
     let span = info_span!(
         "collect_fast_field",
@@ -0,0 +1,320 @@
+// Copyright (C) 2024 Quickwit, Inc.
+//
+// Quickwit is offered under the AGPL v3.0 and as commercial software.
+// For commercial licensing, contact us at hello@quickwit.io.
+//
+// AGPL:
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as
+// published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::{HashMap, HashSet};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+/// Weight of the latest outcome in the error rate and latency moving averages.
+const SMOOTHING_FACTOR: f64 = 0.2;
+
+/// Error rate above which a searcher is considered unhealthy.
+const UNHEALTHY_ERROR_RATE: f64 = 0.5;
+
+/// Half-life of the error rate of a searcher: past errors are progressively forgotten, so that a
+/// searcher that stopped erroring recovers even if it did not serve any request since.
+const ERROR_RATE_HALF_LIFE: Duration = Duration::from_secs(10);
+
+/// A searcher is considered unhealthy if its latency exceeds the median latency of the pool by
+/// this factor...
+const SLOW_LATENCY_FACTOR: f64 = 4.0;
+
+/// ... and this absolute threshold.
+const MIN_SLOW_LATENCY: Duration = Duration::from_secs(1);
+
+/// Delay after which the latency of a searcher that did not serve any request is forgotten.
+const LATENCY_EXPIRATION_DELAY: Duration = Duration::from_secs(30);
+
+/// Interval at which an unhealthy searcher that does not receive any traffic is sent a request
+/// again, to check whether it recovered.
+const PROBE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The kinds of requests sent to the searchers. Their latencies are tracked separately since they
+/// are not comparable.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum SearchRequestKind {
+    LeafSearch = 0,
+    FetchDocs = 1,
+}
+
+impl SearchRequestKind {
+    const ALL: [SearchRequestKind; 2] =
+        [SearchRequestKind::LeafSearch, SearchRequestKind::FetchDocs];
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Latency {
+    latency_secs: f64,
+    recorded_at: Instant,
+}
+
+#[derive(Debug)]
+struct NodeHealth {
+    error_rate: f64,
+    last_outcome_at: Instant,
+    latencies: [Option<Latency>; SearchRequestKind::ALL.len()],
+    last_probe_at_opt: Option<Instant>,
+}
+
+impl NodeHealth {
+    fn new(now: Instant) -> Self {
+        Self {
+            error_rate: 0.0,
+            last_outcome_at: now,
+            latencies: [None; SearchRequestKind::ALL.len()],
+            last_probe_at_opt: None,
+        }
+    }
+
+    fn error_rate(&self, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(self.last_outcome_at);
+        let decay_factor = 0.5f64.powf(elapsed.as_secs_f64() / ERROR_RATE_HALF_LIFE.as_secs_f64());
+        self.error_rate * decay_factor
+    }
+
+    fn latency_secs(&self, request_kind: SearchRequestKind, now: Instant) -> Option<f64> {
+        let latency = self.latencies[request_kind as usize]?;
+
+        if now.saturating_duration_since(latency.recorded_at) >= LATENCY_EXPIRATION_DELAY {
+            return None;
+        }
+        Some(latency.latency_secs)
+    }
+
+    fn last_request_at(&self) -> Instant {
+        match self.last_probe_at_opt {
+            Some(last_probe_at) => last_probe_at.max(self.last_outcome_at),
+            None => self.last_outcome_at,
+        }
+    }
+}
+
+/// Tracks the recent error rate and latency of the searchers of the pool, so that unhealthy
+/// searchers can be deprioritized during job assignment.
+///
+/// Searchers recover automatically: their error rate decays over time and their latency is
+/// forgotten after [`LATENCY_EXPIRATION_DELAY`]. Besides, unhealthy searchers are not
+/// deprioritized once every [`PROBE_INTERVAL`], so that they receive some probe traffic and their
+/// health gets refreshed.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SearcherHealth {
+    nodes: Arc<Mutex<HashMap<SocketAddr, NodeHealth>>>,
+}
+
+impl SearcherHealth {
+    /// Records the outcome of a request sent to a searcher.
+    pub fn record_outcome(
+        &self,
+        grpc_addr: SocketAddr,
+        request_kind: SearchRequestKind,
+        is_error: bool,
+        latency: Duration,
+    ) {
+        let now = Instant::now();
+        let error_sample = if is_error { 1.0 } else { 0.0 };
+        let mut nodes = self.nodes.lock().unwrap();
+        let node_health = nodes
+            .entry(grpc_addr)
+            .or_insert_with(|| NodeHealth::new(now));
+        node_health.error_rate = node_health.error_rate(now) * (1.0 - SMOOTHING_FACTOR)
+            + error_sample * SMOOTHING_FACTOR;
+        node_health.last_outcome_at = now;
+
+        // The latency of failed requests is not representative of the searcher's load.
+        if !is_error {
+            let latency_secs = latency.as_secs_f64();
+            let latency_secs = match node_health.latency_secs(request_kind, now) {
+                Some(previous_latency_secs) => {
+                    previous_latency_secs * (1.0 - SMOOTHING_FACTOR)
+                        + latency_secs * SMOOTHING_FACTOR
+                }
+                None => latency_secs,
+            };
+            node_health.latencies[request_kind as usize] = Some(Latency {
+                latency_secs,
+                recorded_at: now,
+            });
+        }
+    }
+
+    /// Returns the addresses of the searchers that are currently erroring or overloaded and should
+    /// be deprioritized.
+    ///
+    /// An unhealthy searcher that did not serve any request for [`PROBE_INTERVAL`] is omitted, so
+    /// that the next request sent to it acts as a probe.
+    pub fn nodes_to_deprioritize(&self) -> HashSet<SocketAddr> {
+        let now = Instant::now();
+        let mut nodes = self.nodes.lock().unwrap();
+        let slow_latencies_secs: Vec<Option<f64>> = SearchRequestKind::ALL
+            .iter()
+            .map(|request_kind| {
+                let mut latencies_secs: Vec<f64> = nodes
+                    .values()
+                    .filter_map(|node_health| node_health.latency_secs(*request_kind, now))
+                    .collect();
+                if latencies_secs.len() < 2 {
+                    return None;
+                }
+                latencies_secs.sort_unstable_by(f64::total_cmp);
+                let median_latency_secs = latencies_secs[latencies_secs.len() / 2];
+                Some(
+                    (median_latency_secs * SLOW_LATENCY_FACTOR).max(MIN_SLOW_LATENCY.as_secs_f64()),
+                )
+            })
+            .collect();
+        let mut nodes_to_deprioritize = HashSet::new();
+
+        for (grpc_addr, node_health) in nodes.iter_mut() {
+            let is_erroring = node_health.error_rate(now) > UNHEALTHY_ERROR_RATE;
+            let is_slow = SearchRequestKind::ALL
+                .iter()
+                .zip(&slow_latencies_secs)
+                .any(|(request_kind, slow_latency_secs_opt)| {
+                    matches!(
+                        (node_health.latency_secs(*request_kind, now), slow_latency_secs_opt),
+                        (Some(latency_secs), Some(slow_latency_secs)) if latency_secs > *slow_latency_secs
+                    )
+                });
+            if !is_erroring && !is_slow {
+                continue;
+            }
+            if now.saturating_duration_since(node_health.last_request_at()) >= PROBE_INTERVAL {
+                node_health.last_probe_at_opt = Some(now);
+                continue;
+            }
+            nodes_to_deprioritize.insert(*grpc_addr);
+        }
+        nodes_to_deprioritize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_searcher_health_error_rate() {
+        tokio::time::pause();
+        let searcher_health = SearcherHealth::default();
+        let grpc_addr: SocketAddr = ([127, 0, 0, 1], 1001).into();
+        let leaf_search = SearchRequestKind::LeafSearch;
+        searcher_health.record_outcome(grpc_addr, leaf_search, false, Duration::from_millis(10));
+        assert!(searcher_health.nodes_to_deprioritize().is_empty());
+
+        searcher_health.record_outcome(grpc_addr, leaf_search, true, Duration::ZERO);
+        assert!(searcher_health.nodes_to_deprioritize().is_empty());
+
+        for _ in 0..3 {
+            searcher_health.record_outcome(grpc_addr, leaf_search, true, Duration::ZERO);
+        }
+        assert_eq!(
+            searcher_health.nodes_to_deprioritize(),
+            HashSet::from([grpc_addr])
+        );
+
+        for _ in 0..3 {
+            searcher_health.record_outcome(
+                grpc_addr,
+                leaf_search,
+                false,
+                Duration::from_millis(10),
+            );
+        }
+        assert!(searcher_health.nodes_to_deprioritize().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_searcher_health_latency() {
+        tokio::time::pause();
+        let searcher_health = SearcherHealth::default();
+        let grpc_addr_1: SocketAddr = ([127, 0, 0, 1], 1001).into();
+        let grpc_addr_2: SocketAddr = ([127, 0, 0, 1], 1002).into();
+        let grpc_addr_3: SocketAddr = ([127, 0, 0, 1], 1003).into();
+        let leaf_search = SearchRequestKind::LeafSearch;
+        let fetch_docs = SearchRequestKind::FetchDocs;
+
+        searcher_health.record_outcome(grpc_addr_1, leaf_search, false, Duration::from_millis(500));
+        searcher_health.record_outcome(grpc_addr_2, leaf_search, false, Duration::from_millis(600));
+        searcher_health.record_outcome(
+            grpc_addr_3,
+            leaf_search,
+            false,
+            Duration::from_millis(1_500),
+        );
+        // Below the absolute threshold.
+        assert!(searcher_health.nodes_to_deprioritize().is_empty());
+
+        // The latencies of the different kinds of requests are not compared with each other.
+        searcher_health.record_outcome(grpc_addr_1, fetch_docs, false, Duration::from_secs(5));
+        searcher_health.record_outcome(grpc_addr_2, fetch_docs, false, Duration::from_secs(6));
+        assert!(searcher_health.nodes_to_deprioritize().is_empty());
+
+        searcher_health.record_outcome(grpc_addr_3, leaf_search, false, Duration::from_secs(60));
+        assert_eq!(
+            searcher_health.nodes_to_deprioritize(),
+            HashSet::from([grpc_addr_3])
+        );
+
+        // The latencies are forgotten after a while.
+        tokio::time::advance(LATENCY_EXPIRATION_DELAY).await;
+        assert!(searcher_health.nodes_to_deprioritize().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_searcher_health_recovery() {
+        tokio::time::pause();
+        let searcher_health = SearcherHealth::default();
+        let grpc_addr: SocketAddr = ([127, 0, 0, 1], 1001).into();
+        let leaf_search = SearchRequestKind::LeafSearch;
+
+        for _ in 0..5 {
+            searcher_health.record_outcome(grpc_addr, leaf_search, true, Duration::ZERO);
+        }
+        assert_eq!(
+            searcher_health.nodes_to_deprioritize(),
+            HashSet::from([grpc_addr])
+        );
+
+        // The unhealthy searcher is sent a probe once per probe interval.
+        tokio::time::advance(PROBE_INTERVAL).await;
+        assert!(searcher_health.nodes_to_deprioritize().is_empty());
+        assert_eq!(
+            searcher_health.nodes_to_deprioritize(),
+            HashSet::from([grpc_addr])
+        );
+        // The probe fails.
+        searcher_health.record_outcome(grpc_addr, leaf_search, true, Duration::ZERO);
+        assert_eq!(
+            searcher_health.nodes_to_deprioritize(),
+            HashSet::from([grpc_addr])
+        );
+
+        // The error rate decays over time.
+        tokio::time::advance(Duration::from_secs(30)).await;
+        assert!(searcher_health.nodes_to_deprioritize().is_empty());
+        assert!(searcher_health.nodes_to_deprioritize().is_empty());
+
+        // A single error after recovery does not mark the searcher unhealthy again.
+        searcher_health.record_outcome(grpc_addr, leaf_search, true, Duration::ZERO);
+        assert!(searcher_health.nodes_to_deprioritize().is_empty());
+    }
+}
@@ -17,9 +17,11 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
+use std::collections::HashMap;
 use std::pin::Pin;
 use std::str::FromStr;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
@@ -27,29 +29,38 @@ use bytes::Bytes;
 use quickwit_common::uri::Uri;
 use quickwit_config::SearcherConfig;
 use quickwit_doc_mapper::DocMapper;
+use quickwit_metastore::MetastoreServiceExt;
 use quickwit_proto::metastore::MetastoreServiceClient;
 use quickwit_proto::search::{
-    FetchDocsRequest, FetchDocsResponse, GetKvRequest, Hit, LeafListFieldsRequest,
-    LeafListTermsRequest, LeafListTermsResponse, LeafSearchRequest, LeafSearchResponse,
-    LeafSearchStreamRequest, LeafSearchStreamResponse, ListFieldsRequest, ListFieldsResponse,
-    ListTermsRequest, ListTermsResponse, PutKvRequest, ReportSplitsRequest, ReportSplitsResponse,
-    ScrollRequest, SearchRequest, SearchResponse, SearchStreamRequest, SnippetRequest,
+    FetchDocsRequest, FetchDocsResponse, GetKvRequest, Hit, LeafListFacetsRequest,
+    LeafListFacetsResponse, LeafListFieldsRequest, LeafListTermsRequest, LeafListTermsResponse,
+    LeafSearchRequest, LeafSearchResponse, LeafSearchStreamRequest, LeafSearchStreamResponse,
+    ListFacetsRequest, ListFacetsResponse, ListFieldsRequest, ListFieldsResponse, ListTermsRequest,
+    ListTermsResponse, PutKvRequest, ReportSplitsRequest, ReportSplitsResponse, ScrollRequest,
+    SearchRequest, SearchResponse, SearchStreamRequest, SnippetRequest,
 };
+use quickwit_query::query_ast::QueryAst;
 use quickwit_storage::{
     MemorySizedCache, QuickwitCache, SplitCache, StorageCache, StorageResolver,
 };
 use tantivy::aggregation::AggregationLimits;
-use tokio::sync::Semaphore;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore, SemaphorePermit};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
 use crate::leaf_cache::LeafSearchCache;
+use crate::list_facets::{leaf_list_facets, root_list_facets};
 use crate::list_fields::{leaf_list_fields, root_list_fields};
 use crate::list_fields_cache::ListFieldsCache;
 use crate::list_terms::{leaf_list_terms, root_list_terms};
-use crate::root::fetch_docs_phase;
+use crate::root::{bound_num_hits, fetch_docs_phase};
 use crate::scroll_context::{MiniKV, ScrollContext, ScrollKeyAndStartOffset};
+use crate::search_batch::{check_search_batch_size, root_search_batch};
+use crate::search_permits::{OpenSplitPermits, SearchPermits};
 use crate::search_stream::{leaf_search_stream, root_search_stream};
-use crate::{fetch_docs, leaf_search, root_search, ClusterClient, SearchError};
+use crate::{
+    fetch_docs, leaf_search, root_fetch_doc, root_search, root_validate_query_batch, ClusterClient,
+    GlobalDocAddress, SearchError,
+};
 
 #[derive(Clone)]
 /// The search service implementation.
@@ -77,6 +88,26 @@ pub trait SearchService: 'static + Send + Sync {
     /// It is also in charge of merging back the responses.
     async fn root_search(&self, request: SearchRequest) -> crate::Result<SearchResponse>;
 
+    /// Performs a batch of root searches.
+    ///
+    /// Requests that only differ by their aggregations are computed in a single pass over the
+    /// splits. The results are returned in the order of the requests, and the failure of a
+    /// request does not fail the others. An error is returned if the batch is too large.
+    async fn root_search_batch(
+        &self,
+        requests: Vec<SearchRequest>,
+    ) -> crate::Result<Vec<crate::Result<SearchResponse>>>;
+
+    /// Validates the queries of a batch of search requests against the doc mappers of their
+    /// indexes, without searching any split.
+    ///
+    /// Returns, in the order of the requests, the resolved query AST or the validation error of
+    /// each request.
+    async fn root_validate_query_batch(
+        &self,
+        requests: Vec<SearchRequest>,
+    ) -> Vec<crate::Result<QueryAst>>;
+
     /// Performs a leaf search on a given set of splits.
     ///
     /// It is like a regular search except that:
@@ -121,6 +152,31 @@ pub trait SearchService: 'static + Send + Sync {
         request: LeafListTermsRequest,
     ) -> crate::Result<LeafListTermsResponse>;
 
+    /// Root list facets API.
+    /// This RPC identifies the set of splits on which the listing should run on,
+    /// and dispatches the multiple calls to `LeafListFacets`.
+    ///
+    /// It is also in charge of merging back the facet counts.
+    async fn root_list_facets(
+        &self,
+        request: ListFacetsRequest,
+    ) -> crate::Result<ListFacetsResponse>;
+
+    /// Performs a leaf list facets on a given set of splits.
+    async fn leaf_list_facets(
+        &self,
+        request: LeafListFacetsRequest,
+    ) -> crate::Result<LeafListFacetsResponse>;
+
+    /// Fetches a single document of an index given its global address.
+    ///
+    /// Returns `None` if the address does not point to a document of a published split.
+    async fn root_fetch_doc(
+        &self,
+        index_id: String,
+        doc_address: GlobalDocAddress,
+    ) -> crate::Result<Option<Hit>>;
+
     /// Performs a scroll request.
     async fn scroll(&self, scroll_request: ScrollRequest) -> crate::Result<SearchResponse>;
 
@@ -166,6 +222,20 @@ impl SearchServiceImpl {
             search_after_cache: MiniKV::default(),
         }
     }
+
+    /// Replaces the index aliases among `index_id_patterns` with the IDs of their member indexes.
+    async fn resolve_index_aliases(
+        &self,
+        index_id_patterns: &mut Vec<String>,
+    ) -> crate::Result<()> {
+        let unresolved_index_id_patterns = std::mem::take(index_id_patterns);
+        *index_id_patterns = self
+            .metastore
+            .clone()
+            .resolve_index_aliases(unresolved_index_id_patterns)
+            .await?;
+        Ok(())
+    }
 }
 
 fn deserialize_doc_mapper(doc_mapper_str: &str) -> crate::Result<Arc<dyn DocMapper>> {
@@ -175,19 +245,105 @@ fn deserialize_doc_mapper(doc_mapper_str: &str) -> crate::Result<Arc<dyn DocMapp
     Ok(doc_mapper)
 }
 
+/// Returns the timeout of a root search: the one set in the request, or else the searcher default
+/// timeout.
+fn root_search_timeout_opt(
+    search_request: &SearchRequest,
+    searcher_config: &SearcherConfig,
+) -> Option<Duration> {
+    search_request
+        .timeout_millis
+        .map(Duration::from_millis)
+        .or(searcher_config.default_timeout)
+}
+
 #[async_trait]
 impl SearchService for SearchServiceImpl {
-    async fn root_search(&self, search_request: SearchRequest) -> crate::Result<SearchResponse> {
-        let search_result = root_search(
+    async fn root_search(
+        &self,
+        mut search_request: SearchRequest,
+    ) -> crate::Result<SearchResponse> {
+        self.resolve_index_aliases(&mut search_request.index_id_patterns)
+            .await?;
+        let _search_permit_opt = match &self.searcher_context.root_search_permits_opt {
+            Some(root_search_permits) => Some(root_search_permits.acquire().await?),
+            None => None,
+        };
+        let timeout_opt =
+            root_search_timeout_opt(&search_request, &self.searcher_context.searcher_config);
+        let root_search_fut = root_search(
             &self.searcher_context,
             search_request,
             self.metastore.clone(),
             &self.cluster_client,
-        )
-        .await?;
+        );
+        let search_result = if let Some(timeout) = timeout_opt {
+            tokio::time::timeout(timeout, root_search_fut)
+                .await
+                .map_err(|_| {
+                    SearchError::Timeout(format!("search did not complete within {timeout:?}"))
+                })??
+        } else {
+            root_search_fut.await?
+        };
         Ok(search_result)
     }
 
+    async fn root_search_batch(
+        &self,
+        search_requests: Vec<SearchRequest>,
+    ) -> crate::Result<Vec<crate::Result<SearchResponse>>> {
+        check_search_batch_size(search_requests.len())?;
+
+        // The requests whose index aliases cannot be resolved fail on their own.
+        let mut search_results: Vec<Option<crate::Result<SearchResponse>>> =
+            Vec::with_capacity(search_requests.len());
+        let mut resolved_search_requests: Vec<SearchRequest> =
+            Vec::with_capacity(search_requests.len());
+
+        for mut search_request in search_requests {
+            match self
+                .resolve_index_aliases(&mut search_request.index_id_patterns)
+                .await
+            {
+                Ok(()) => {
+                    resolved_search_requests.push(search_request);
+                    search_results.push(None);
+                }
+                Err(search_error) => search_results.push(Some(Err(search_error))),
+            }
+        }
+        let mut resolved_search_results = root_search_batch(
+            &self.searcher_context,
+            resolved_search_requests,
+            self.metastore.clone(),
+            &self.cluster_client,
+        )
+        .await?
+        .into_iter();
+
+        let search_results = search_results
+            .into_iter()
+            .map(|search_result_opt| {
+                search_result_opt.unwrap_or_else(|| {
+                    resolved_search_results.next().unwrap_or_else(|| {
+                        Err(SearchError::Internal(
+                            "missing search result in batch search response".to_string(),
+                        ))
+                    })
+                })
+            })
+            .collect();
+        Ok(search_results)
+    }
+
+    async fn root_validate_query_batch(
+        &self,
+        search_requests: Vec<SearchRequest>,
+    ) -> Vec<crate::Result<QueryAst>> {
+        root_validate_query_batch(search_requests, self.metastore.clone()).await
+    }
+
     async fn leaf_search(
         &self,
         leaf_search_request: LeafSearchRequest,
@@ -270,8 +426,10 @@ impl SearchService for SearchServiceImpl {
 
     async fn root_list_terms(
         &self,
-        list_terms_request: ListTermsRequest,
+        mut list_terms_request: ListTermsRequest,
     ) -> crate::Result<ListTermsResponse> {
+        self.resolve_index_aliases(&mut list_terms_request.index_id_patterns)
+            .await?;
         let search_result = root_list_terms(
             &list_terms_request,
             self.metastore.clone(),
@@ -304,6 +462,53 @@ impl SearchService for SearchServiceImpl {
         Ok(leaf_search_response)
     }
 
+    async fn root_list_facets(
+        &self,
+        mut list_facets_request: ListFacetsRequest,
+    ) -> crate::Result<ListFacetsResponse> {
+        self.resolve_index_aliases(&mut list_facets_request.index_id_patterns)
+            .await?;
+        root_list_facets(
+            &list_facets_request,
+            self.metastore.clone(),
+            &self.cluster_client,
+        )
+        .await
+    }
+
+    async fn leaf_list_facets(
+        &self,
+        leaf_list_facets_request: LeafListFacetsRequest,
+    ) -> crate::Result<LeafListFacetsResponse> {
+        let list_facets_request = leaf_list_facets_request
+            .list_facets_request
+            .ok_or_else(|| SearchError::Internal("no list facets request".to_string()))?;
+        let index_uri = Uri::from_str(&leaf_list_facets_request.index_uri)?;
+        let storage = self.storage_resolver.resolve(&index_uri).await?;
+
+        leaf_list_facets(
+            self.searcher_context.clone(),
+            &list_facets_request,
+            storage,
+            &leaf_list_facets_request.split_offsets,
+        )
+        .await
+    }
+
+    async fn root_fetch_doc(
+        &self,
+        index_id: String,
+        doc_address: GlobalDocAddress,
+    ) -> crate::Result<Option<Hit>> {
+        root_fetch_doc(
+            index_id,
+            doc_address,
+            self.metastore.clone(),
+            &self.cluster_client,
+        )
+        .await
+    }
+
     async fn scroll(&self, scroll_request: ScrollRequest) -> crate::Result<SearchResponse> {
         scroll(scroll_request, &self.cluster_client, &self.searcher_context).await
     }
@@ -329,8 +534,10 @@ impl SearchService for SearchServiceImpl {
 
     async fn root_list_fields(
         &self,
-        list_fields_req: ListFieldsRequest,
+        mut list_fields_req: ListFieldsRequest,
     ) -> crate::Result<ListFieldsResponse> {
+        self.resolve_index_aliases(&mut list_fields_req.index_id_patterns)
+            .await?;
         root_list_fields(
             list_fields_req,
             &self.cluster_client,
@@ -412,20 +619,30 @@ pub(crate) async fn scroll(
         &scroll_context.indexes_metas_for_leaf_search,
         &partial_hits[..],
         &scroll_context.split_metadatas[..],
+        &scroll_context.split_publish_timestamps,
         &scroll_context.search_request,
         cluster_client,
     )
     .await?;
 
     let next_scroll_id = Some(current_scroll.next_page(hits.len() as u64));
+    let (num_hits, num_hits_relation) = bound_num_hits(
+        &scroll_context.search_request,
+        scroll_context.total_num_hits,
+        !scroll_context.total_num_hits_is_lower_bound,
+    );
 
     Ok(SearchResponse {
         hits,
-        num_hits: scroll_context.total_num_hits,
+        num_hits,
         elapsed_time_micros: start.elapsed().as_micros() as u64,
         scroll_id: next_scroll_id.as_ref().map(ToString::to_string),
         errors: Vec::new(),
         aggregation: None,
+        num_hits_relation: num_hits_relation as i32,
+        failed_splits: Vec::new(),
+        partial: false,
+        num_hits_per_index: HashMap::new(),
     })
 }
 /// [`SearcherContext`] provides a common set of variables
@@ -434,10 +651,17 @@ pub(crate) async fn scroll(
 pub struct SearcherContext {
     /// Searcher config.
     pub searcher_config: SearcherConfig,
-    /// Fast fields cache.
+    /// Fast fields cache, shared by all indexes and bounded by
+    /// `SearcherConfig::fast_field_cache_capacity`.
     pub fast_fields_cache: Arc<dyn StorageCache>,
-    /// Counting semaphore to limit concurrent leaf search split requests.
+    /// Counting semaphore to limit concurrent leaf search split requests. Permits must be acquired
+    /// with [`SearcherContext::acquire_split_search_permit`].
     pub leaf_search_split_semaphore: Arc<Semaphore>,
+    /// Current capacity of `leaf_search_split_semaphore`, which can be changed at runtime.
+    max_num_concurrent_split_searches: AtomicUsize,
+    /// Number of permits of `leaf_search_split_semaphore` in excess after its capacity was
+    /// lowered, which are retired as they get acquired.
+    num_split_search_permits_to_retire: Mutex<usize>,
     /// Split footer cache.
     pub split_footer_cache: MemorySizedCache<String>,
     /// Counting semaphore to limit concurrent split stream requests.
@@ -448,6 +672,12 @@ pub struct SearcherContext {
     pub split_cache_opt: Option<Arc<SplitCache>>,
     /// List fields cache. Caches the list fields response for a given split.
     pub list_fields_cache: ListFieldsCache,
+    /// Limits the number of concurrent root searches. `None` if unlimited.
+    pub root_search_permits_opt: Option<SearchPermits>,
+    /// Counting semaphore to limit concurrent split warmups. `None` if unlimited.
+    pub split_warmup_semaphore_opt: Option<Semaphore>,
+    /// Limits the number of splits opened concurrently by leaf searches.
+    pub open_split_permits: OpenSplitPermits,
 }
 
 impl std::fmt::Debug for SearcherContext {
@@ -459,6 +689,12 @@ impl std::fmt::Debug for SearcherContext {
                 &self.leaf_search_split_semaphore,
             )
             .field("split_stream_semaphore", &self.split_stream_semaphore)
+            .field("root_search_permits_opt", &self.root_search_permits_opt)
+            .field(
+                "split_warmup_semaphore_opt",
+                &self.split_warmup_semaphore_opt,
+            )
+            .field("open_split_permits", &self.open_split_permits)
             .finish()
     }
 }
@@ -477,30 +713,122 @@ impl SearcherContext {
             capacity_in_bytes,
             &quickwit_storage::STORAGE_METRICS.split_footer_cache,
         );
-        let leaf_search_split_semaphore = Arc::new(Semaphore::new(
-            searcher_config.max_num_concurrent_split_searches,
-        ));
+        let max_num_concurrent_split_searches = searcher_config.max_num_concurrent_split_searches;
+        let leaf_search_split_semaphore =
+            Arc::new(Semaphore::new(max_num_concurrent_split_searches));
         let split_stream_semaphore =
             Semaphore::new(searcher_config.max_num_concurrent_split_streams);
         let fast_field_cache_capacity = searcher_config.fast_field_cache_capacity.as_u64() as usize;
         let storage_long_term_cache = Arc::new(QuickwitCache::new(fast_field_cache_capacity));
-        let leaf_search_cache =
-            LeafSearchCache::new(searcher_config.partial_request_cache_capacity.as_u64() as usize);
+        let leaf_search_cache = LeafSearchCache::new(
+            searcher_config.partial_request_cache_capacity.as_u64() as usize,
+            searcher_config.partial_request_cache_ttl,
+        );
         let list_fields_cache =
             ListFieldsCache::new(searcher_config.partial_request_cache_capacity.as_u64() as usize);
+        let root_search_permits_opt =
+            searcher_config
+                .max_concurrent_searches
+                .map(|max_concurrent_searches| {
+                    SearchPermits::new(
+                        max_concurrent_searches.get(),
+                        searcher_config.max_queued_searches,
+                    )
+                });
+        let split_warmup_semaphore_opt = searcher_config
+            .warmup_concurrency
+            .map(|warmup_concurrency| Semaphore::new(warmup_concurrency.get()));
+        let open_split_permits = OpenSplitPermits::new(
+            searcher_config
+                .max_open_splits
+                .map(|max_open_splits| max_open_splits.get()),
+        );
 
         Self {
             searcher_config,
             fast_fields_cache: storage_long_term_cache,
             leaf_search_split_semaphore,
+            max_num_concurrent_split_searches: AtomicUsize::new(max_num_concurrent_split_searches),
+            num_split_search_permits_to_retire: Mutex::new(0),
             split_footer_cache: global_split_footer_cache,
             split_stream_semaphore,
             leaf_search_cache,
             list_fields_cache,
             split_cache_opt,
+            root_search_permits_opt,
+            split_warmup_semaphore_opt,
+            open_split_permits,
+        }
+    }
+
+    /// Returns the current maximum number of concurrent leaf search split requests.
+    pub fn max_num_concurrent_split_searches(&self) -> usize {
+        self.max_num_concurrent_split_searches
+            .load(Ordering::Relaxed)
+    }
+
+    /// Changes the maximum number of concurrent leaf search split requests. When shrinking, the
+    /// in-flight split searches are not interrupted: the extra permits are retired as they are
+    /// released and acquired again.
+    pub fn set_max_num_concurrent_split_searches(&self, max_num_concurrent_split_searches: usize) {
+        let mut num_permits_to_retire = self.num_split_search_permits_to_retire.lock().unwrap();
+
+        let previous_max_num_concurrent_split_searches = self
+            .max_num_concurrent_split_searches
+            .swap(max_num_concurrent_split_searches, Ordering::Relaxed);
+
+        if max_num_concurrent_split_searches > previous_max_num_concurrent_split_searches {
+            let num_permits_to_add =
+                max_num_concurrent_split_searches - previous_max_num_concurrent_split_searches;
+            // Permits that have not been retired yet are kept instead of adding new ones.
+            let num_permits_to_keep = num_permits_to_add.min(*num_permits_to_retire);
+            *num_permits_to_retire -= num_permits_to_keep;
+            self.leaf_search_split_semaphore
+                .add_permits(num_permits_to_add - num_permits_to_keep);
+        } else if max_num_concurrent_split_searches < previous_max_num_concurrent_split_searches {
+            *num_permits_to_retire +=
+                previous_max_num_concurrent_split_searches - max_num_concurrent_split_searches;
+            // The available permits are retired right away.
+            while *num_permits_to_retire > 0 {
+                let Ok(permit) = self.leaf_search_split_semaphore.try_acquire() else {
+                    break;
+                };
+                permit.forget();
+                *num_permits_to_retire -= 1;
+            }
+        }
+    }
+
+    /// Waits for a leaf search split slot to be available.
+    pub async fn acquire_split_search_permit(&self) -> OwnedSemaphorePermit {
+        loop {
+            let permit = self
+                .leaf_search_split_semaphore
+                .clone()
+                .acquire_owned()
+                .await
+                .expect("the leaf search split semaphore should never be closed");
+            let mut num_permits_to_retire = self.num_split_search_permits_to_retire.lock().unwrap();
+
+            if *num_permits_to_retire == 0 {
+                return permit;
+            }
+            permit.forget();
+            *num_permits_to_retire -= 1;
         }
     }
 
+    /// Waits for a split warmup slot to be available. Returns `None` if the number of concurrent
+    /// split warmups is not limited.
+    pub async fn acquire_warmup_permit(&self) -> Option<SemaphorePermit<'_>> {
+        let split_warmup_semaphore = self.split_warmup_semaphore_opt.as_ref()?;
+        let permit = split_warmup_semaphore
+            .acquire()
+            .await
+            .expect("the split warmup semaphore should never be closed");
+        Some(permit)
+    }
+
     /// Returns a new instance to track the aggregation memory usage.
     pub fn get_aggregation_limits(&self) -> AggregationLimits {
         AggregationLimits::new(
@@ -509,3 +837,218 @@ impl SearcherContext {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use quickwit_common::ServiceStream;
+    use quickwit_config::IndexAlias;
+    use quickwit_indexing::MockSplitBuilder;
+    use quickwit_metastore::{
+        IndexMetadata, ListIndexAliasesResponseExt, ListIndexesMetadataResponseExt,
+        ListSplitsResponseExt,
+    };
+    use quickwit_proto::metastore::{
+        ListIndexAliasesResponse, ListIndexesMetadataResponse, ListSplitsResponse,
+    };
+    use quickwit_proto::{ServiceError, ServiceErrorCode};
+    use quickwit_query::query_ast::qast_json_helper;
+
+    use super::*;
+    use crate::{searcher_pool_for_test, MockSearchService, SearchJobPlacer};
+
+    #[test]
+    fn test_root_search_timeout_opt() {
+        let searcher_config = SearcherConfig::default();
+        let search_request = SearchRequest::default();
+        assert_eq!(
+            root_search_timeout_opt(&search_request, &searcher_config),
+            None
+        );
+        let searcher_config = SearcherConfig {
+            default_timeout: Some(Duration::from_secs(30)),
+            ..Default::default()
+        };
+        assert_eq!(
+            root_search_timeout_opt(&search_request, &searcher_config),
+            Some(Duration::from_secs(30))
+        );
+        let search_request = SearchRequest {
+            timeout_millis: Some(500),
+            ..Default::default()
+        };
+        assert_eq!(
+            root_search_timeout_opt(&search_request, &searcher_config),
+            Some(Duration::from_millis(500))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_root_search_resolves_index_aliases() {
+        let index_metadata_0 =
+            IndexMetadata::for_test("logs-2024-01-01", "ram:///indexes/logs-2024-01-01");
+        let index_uid_0 = index_metadata_0.index_uid.clone();
+        let index_metadata_1 =
+            IndexMetadata::for_test("logs-2024-01-02", "ram:///indexes/logs-2024-01-02");
+        let index_uid_1 = index_metadata_1.index_uid.clone();
+
+        let mut mock_metastore = MetastoreServiceClient::mock();
+        mock_metastore.expect_list_index_aliases().return_once(|_| {
+            let index_alias = IndexAlias::for_test(
+                "logs",
+                &["logs-2024-01-01", "logs-2024-01-02"],
+                Some("logs-2024-01-02"),
+            );
+            Ok(ListIndexAliasesResponse::try_from_index_aliases([&index_alias]).unwrap())
+        });
+        mock_metastore.expect_list_indexes_metadata().return_once(
+            move |list_indexes_metadata_request| {
+                assert_eq!(
+                    list_indexes_metadata_request.index_id_patterns,
+                    ["logs-2024-01-01", "logs-2024-01-02"]
+                );
+                Ok(ListIndexesMetadataResponse::try_from_indexes_metadata(vec![
+                    index_metadata_0,
+                    index_metadata_1,
+                ])
+                .unwrap())
+            },
+        );
+        mock_metastore.expect_list_splits().return_once(move |_| {
+            let splits = vec![
+                MockSplitBuilder::new("split-0")
+                    .with_index_uid(&index_uid_0)
+                    .build(),
+                MockSplitBuilder::new("split-1")
+                    .with_index_uid(&index_uid_1)
+                    .build(),
+            ];
+            let splits_response = ListSplitsResponse::try_from_splits(splits).unwrap();
+            Ok(ServiceStream::from(vec![Ok(splits_response)]))
+        });
+        let mut mock_search_service = MockSearchService::new();
+        mock_search_service
+            .expect_leaf_search()
+            .times(2)
+            .returning(|leaf_search_request| {
+                Ok(LeafSearchResponse {
+                    num_hits: 1,
+                    num_attempted_splits: leaf_search_request.split_offsets.len() as u64,
+                    ..Default::default()
+                })
+            });
+        let searcher_pool = searcher_pool_for_test([("127.0.0.1:1001", mock_search_service)]);
+        let cluster_client = ClusterClient::new(SearchJobPlacer::new(searcher_pool));
+        let search_service = SearchServiceImpl::new(
+            MetastoreServiceClient::from(mock_metastore),
+            StorageResolver::for_test(),
+            cluster_client,
+            Arc::new(SearcherContext::for_test()),
+        );
+        let search_request = SearchRequest {
+            index_id_patterns: vec!["logs".to_string()],
+            query_ast: qast_json_helper("test", &["body"]),
+            max_hits: 0,
+            ..Default::default()
+        };
+        let search_response = search_service.root_search(search_request).await.unwrap();
+        assert_eq!(search_response.num_hits, 2);
+    }
+
+    #[tokio::test]
+    async fn test_searcher_context_root_search_permits() {
+        let searcher_context = SearcherContext::for_test();
+        assert!(searcher_context.root_search_permits_opt.is_none());
+
+        let searcher_config = SearcherConfig {
+            max_concurrent_searches: Some(NonZeroUsize::new(2).unwrap()),
+            max_queued_searches: 0,
+            ..Default::default()
+        };
+        let searcher_context = SearcherContext::new(searcher_config, None);
+        let root_search_permits = searcher_context.root_search_permits_opt.as_ref().unwrap();
+        let _permit_0 = root_search_permits.acquire().await.unwrap();
+        let _permit_1 = root_search_permits.acquire().await.unwrap();
+        let rejected_search_err = root_search_permits.acquire().await.unwrap_err();
+        assert!(matches!(rejected_search_err, SearchError::TooManyRequests));
+        assert_eq!(
+            rejected_search_err.error_code(),
+            ServiceErrorCode::RateLimited
+        );
+    }
+
+    #[tokio::test]
+    async fn test_searcher_context_warmup_concurrency() {
+        let searcher_context = SearcherContext::for_test();
+        assert!(searcher_context.acquire_warmup_permit().await.is_none());
+
+        let searcher_config = SearcherConfig {
+            warmup_concurrency: Some(NonZeroUsize::new(2).unwrap()),
+            ..Default::default()
+        };
+        let searcher_context = Arc::new(SearcherContext::new(searcher_config, None));
+        let num_running_warmups = Arc::new(AtomicUsize::new(0));
+        let max_num_running_warmups = Arc::new(AtomicUsize::new(0));
+
+        let warmup_futures = (0..8).map(|_| {
+            let searcher_context = searcher_context.clone();
+            let num_running_warmups = num_running_warmups.clone();
+            let max_num_running_warmups = max_num_running_warmups.clone();
+            tokio::spawn(async move {
+                let _warmup_permit = searcher_context.acquire_warmup_permit().await.unwrap();
+                let num_running = num_running_warmups.fetch_add(1, Ordering::SeqCst) + 1;
+                max_num_running_warmups.fetch_max(num_running, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                num_running_warmups.fetch_sub(1, Ordering::SeqCst);
+            })
+        });
+        for warmup_result in futures::future::join_all(warmup_futures).await {
+            warmup_result.unwrap();
+        }
+        assert_eq!(max_num_running_warmups.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_searcher_context_set_max_num_concurrent_split_searches() {
+        let searcher_config = SearcherConfig {
+            max_num_concurrent_split_searches: 4,
+            ..Default::default()
+        };
+        let searcher_context = SearcherContext::new(searcher_config, None);
+        let leaf_search_split_semaphore = &searcher_context.leaf_search_split_semaphore;
+        let permit_1 = searcher_context.acquire_split_search_permit().await;
+        let permit_2 = searcher_context.acquire_split_search_permit().await;
+        let permit_3 = searcher_context.acquire_split_search_permit().await;
+
+        searcher_context.set_max_num_concurrent_split_searches(6);
+        assert_eq!(searcher_context.max_num_concurrent_split_searches(), 6);
+        assert_eq!(leaf_search_split_semaphore.available_permits(), 3);
+
+        // Three permits are available and retired right away, the fourth one once released.
+        searcher_context.set_max_num_concurrent_split_searches(2);
+        assert_eq!(searcher_context.max_num_concurrent_split_searches(), 2);
+        assert_eq!(leaf_search_split_semaphore.available_permits(), 0);
+
+        drop(permit_1);
+        let permit_4 = searcher_context.acquire_split_search_permit();
+        tokio::pin!(permit_4);
+        // The released permit is retired, so there is none left to acquire.
+        assert!(
+            tokio::time::timeout(Duration::from_millis(50), &mut permit_4)
+                .await
+                .is_err()
+        );
+        drop(permit_2);
+        let _permit_4 = permit_4.await;
+        assert_eq!(leaf_search_split_semaphore.available_permits(), 0);
+
+        // Growing the capacity again cancels the pending retirements first.
+        searcher_context.set_max_num_concurrent_split_searches(1);
+        searcher_context.set_max_num_concurrent_split_searches(3);
+        assert_eq!(leaf_search_split_semaphore.available_permits(), 1);
+        let _permit_5 = searcher_context.acquire_split_search_permit().await;
+        drop(permit_3);
+        assert_eq!(leaf_search_split_semaphore.available_permits(), 1);
+    }
+}
@@ -18,6 +18,9 @@
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
 use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Range;
+use std::path::Path;
+use std::sync::Mutex;
 
 use assert_json_diff::{assert_json_eq, assert_json_include};
 use quickwit_config::SearcherConfig;
@@ -26,12 +29,13 @@ use quickwit_doc_mapper::DefaultDocMapper;
 use quickwit_indexing::TestSandbox;
 use quickwit_opentelemetry::otlp::TraceId;
 use quickwit_proto::search::{
-    LeafListTermsResponse, ListTermsRequest, SearchRequest, SortByValue, SortField, SortOrder,
-    SortValue,
+    CountHits, FacetCount, HitsRelation, LeafListTermsResponse, ListFacetsRequest,
+    ListTermsRequest, SearchRequest, SortByValue, SortField, SortOrder, SortValue,
 };
 use quickwit_query::query_ast::{
     qast_helper, qast_json_helper, query_ast_from_user_text, QueryAst,
 };
+use quickwit_storage::RamStorage;
 use serde_json::{json, Value as JsonValue};
 use tantivy::schema::OwnedValue as TantivyValue;
 use tantivy::time::OffsetDateTime;
@@ -86,6 +90,85 @@ async fn test_single_node_simple() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_single_node_fetch_doc_by_address() -> anyhow::Result<()> {
+    let index_id = "single-node-fetch-doc-by-address";
+    let doc_mapping_yaml = r#"
+            field_mappings:
+              - name: title
+                type: text
+              - name: body
+                type: text
+        "#;
+    let test_sandbox = TestSandbox::create(index_id, doc_mapping_yaml, "{}", &["body"]).await?;
+    let docs = vec![
+        json!({"title": "snoopy", "body": "Snoopy is an anthropomorphic beagle."}),
+        json!({"title": "beagle", "body": "The beagle is a breed of small scent hound."}),
+    ];
+    test_sandbox.add_documents(docs).await?;
+
+    let searcher_pool = SearcherPool::default();
+    let cluster_client = ClusterClient::new(SearchJobPlacer::new(searcher_pool.clone()));
+    let searcher_context = Arc::new(SearcherContext::new(SearcherConfig::default(), None));
+    let search_service = Arc::new(SearchServiceImpl::new(
+        test_sandbox.metastore(),
+        test_sandbox.storage_resolver(),
+        cluster_client,
+        searcher_context,
+    ));
+    let socket_addr = SocketAddr::new(Ipv4Addr::new(127, 0, 0, 1).into(), 7280u16);
+    searcher_pool.insert(
+        socket_addr,
+        SearchServiceClient::from_service(search_service.clone(), socket_addr),
+    );
+    let search_request = SearchRequest {
+        index_id_patterns: vec![index_id.to_string()],
+        query_ast: qast_json_helper("anthropomorphic", &["body"]),
+        max_hits: 1,
+        ..Default::default()
+    };
+    let search_response = search_service.root_search(search_request).await?;
+    assert_eq!(search_response.hits.len(), 1);
+    let doc_address =
+        GlobalDocAddress::from_partial_hit(search_response.hits[0].partial_hit.as_ref().unwrap());
+
+    let hit = search_service
+        .root_fetch_doc(index_id.to_string(), doc_address.clone())
+        .await?
+        .unwrap();
+    let hit_json: JsonValue = serde_json::from_str(&hit.json)?;
+    assert_json_include!(
+        actual: hit_json,
+        expected: json!({"title": "snoopy", "body": "Snoopy is an anthropomorphic beagle."})
+    );
+    assert_eq!(hit.index_id, index_id);
+
+    let mut out_of_range_doc_address = doc_address.clone();
+    out_of_range_doc_address.doc_addr.doc_id = 1_000;
+    assert!(search_service
+        .root_fetch_doc(index_id.to_string(), out_of_range_doc_address)
+        .await?
+        .is_none());
+
+    let mut unknown_split_doc_address = doc_address;
+    unknown_split_doc_address.split = "unknown-split".to_string();
+    assert!(search_service
+        .root_fetch_doc(index_id.to_string(), unknown_split_doc_address)
+        .await?
+        .is_none());
+
+    let error = search_service
+        .root_fetch_doc(
+            "unknown-index".to_string(),
+            GlobalDocAddress::from_partial_hit(&PartialHit::default()),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(error, SearchError::IndexesNotFound { .. }));
+    test_sandbox.assert_quit().await;
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_single_node_termset() -> anyhow::Result<()> {
     let index_id = "single-node-termset-1";
@@ -960,6 +1043,86 @@ async fn test_single_node_invalid_sorting_with_query() {
     test_sandbox.assert_quit().await;
 }
 
+#[tokio::test]
+async fn test_single_node_track_total_hits() -> anyhow::Result<()> {
+    let doc_mapping_yaml = r#"
+            field_mappings:
+              - name: body
+                type: text
+        "#;
+    let index_id = "single-node-track-total-hits";
+    let test_sandbox = TestSandbox::create(index_id, doc_mapping_yaml, "{}", &["body"]).await?;
+    // Each batch of documents ends up in its own split.
+    for split_ord in 0..3 {
+        let docs: Vec<JsonValue> = (0..10)
+            .map(|doc_ord| json!({"body": format!("split {split_ord} doc {doc_ord}")}))
+            .collect();
+        test_sandbox.add_documents(docs).await?;
+    }
+    let search = |count_hits: CountHits, track_total_hits_up_to: Option<u32>| {
+        let search_request = SearchRequest {
+            index_id_patterns: vec![index_id.to_string()],
+            query_ast: qast_json_helper("split", &["body"]),
+            max_hits: 5,
+            count_hits: count_hits as i32,
+            track_total_hits_up_to,
+            ..Default::default()
+        };
+        single_node_search(
+            search_request,
+            test_sandbox.metastore(),
+            test_sandbox.storage_resolver(),
+        )
+    };
+    {
+        // `track_total_hits: true`
+        let search_response = search(CountHits::CountAll, None).await?;
+        assert_eq!(search_response.num_hits, 30);
+        assert_eq!(search_response.num_hits_relation(), HitsRelation::Equal);
+        assert_eq!(search_response.hits.len(), 5);
+    }
+    {
+        // `track_total_hits: false`: splits may be skipped, in which case the number of hits is
+        // only a lower bound.
+        let search_response = search(CountHits::Underestimate, None).await?;
+        assert_eq!(search_response.hits.len(), 5);
+        assert!(search_response.num_hits >= 5);
+        if search_response.num_hits_relation() == HitsRelation::Equal {
+            assert_eq!(search_response.num_hits, 30);
+        } else {
+            assert!(search_response.num_hits < 30);
+        }
+    }
+    {
+        // `track_total_hits: 20`
+        let search_response = search(CountHits::CountAll, Some(20)).await?;
+        assert_eq!(search_response.num_hits, 20);
+        assert_eq!(
+            search_response.num_hits_relation(),
+            HitsRelation::GreaterThanOrEqual
+        );
+        assert_eq!(search_response.hits.len(), 5);
+    }
+    {
+        // `track_total_hits: 3`, which is lower than `max_hits`.
+        let search_response = search(CountHits::Underestimate, Some(3)).await?;
+        assert_eq!(search_response.num_hits, 3);
+        assert_eq!(
+            search_response.num_hits_relation(),
+            HitsRelation::GreaterThanOrEqual
+        );
+        assert_eq!(search_response.hits.len(), 5);
+    }
+    {
+        // `track_total_hits: 100`, which is greater than the number of hits.
+        let search_response = search(CountHits::CountAll, Some(100)).await?;
+        assert_eq!(search_response.num_hits, 30);
+        assert_eq!(search_response.num_hits_relation(), HitsRelation::Equal);
+    }
+    test_sandbox.assert_quit().await;
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_single_node_split_pruning_by_tags() -> anyhow::Result<()> {
     let doc_mapping_yaml = r#"
@@ -1033,6 +1196,103 @@ async fn test_single_node_split_pruning_by_tags() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_single_node_search_with_split_ids() -> anyhow::Result<()> {
+    let doc_mapping_yaml = r#"
+            tag_fields:
+              - owner
+            field_mappings:
+              - name: body
+                type: text
+              - name: owner
+                type: text
+                tokenizer: raw
+        "#;
+    let index_id = "single-node-search-with-split-ids";
+    let test_sandbox = TestSandbox::create(index_id, doc_mapping_yaml, "{}", &["body"]).await?;
+    let index_uid = test_sandbox.index_uid();
+
+    let owners = ["paul", "adrien", "francois"];
+    for owner in owners {
+        let mut docs = Vec::new();
+        for i in 0..10 {
+            docs.push(json!({"body": format!("content num #{}", i + 1), "owner": owner}));
+        }
+        test_sandbox.add_documents(docs).await?;
+    }
+    let split_ids: Vec<String> = list_relevant_splits(
+        vec![index_uid.clone()],
+        None,
+        None,
+        extract_tags_from_query(qast_helper("owner:paul OR owner:francois", &[])),
+        &mut test_sandbox.metastore(),
+    )
+    .await?
+    .into_iter()
+    .map(|split_metadata| split_metadata.split_id)
+    .collect();
+    assert_eq!(split_ids.len(), 2);
+
+    let search_request = SearchRequest {
+        index_id_patterns: vec![index_id.to_string()],
+        query_ast: qast_json_helper("content", &["body"]),
+        max_hits: 100,
+        split_ids: split_ids.clone(),
+        ..Default::default()
+    };
+    let search_response = single_node_search(
+        search_request,
+        test_sandbox.metastore(),
+        test_sandbox.storage_resolver(),
+    )
+    .await?;
+    assert_eq!(search_response.num_hits, 20);
+    let hit_owners: BTreeSet<String> = search_response
+        .hits
+        .iter()
+        .map(|hit| {
+            let doc: JsonValue = serde_json::from_str(&hit.json).unwrap();
+            doc["owner"].as_str().unwrap().to_string()
+        })
+        .collect();
+    assert_eq!(
+        hit_owners,
+        BTreeSet::from(["francois".to_string(), "paul".to_string()])
+    );
+    let hit_split_ids: BTreeSet<&str> = search_response
+        .hits
+        .iter()
+        .map(|hit| hit.partial_hit.as_ref().unwrap().split_id.as_str())
+        .collect();
+    assert_eq!(
+        hit_split_ids,
+        split_ids
+            .iter()
+            .map(String::as_str)
+            .collect::<BTreeSet<&str>>()
+    );
+
+    let search_request = SearchRequest {
+        index_id_patterns: vec![index_id.to_string()],
+        query_ast: qast_json_helper("content", &["body"]),
+        max_hits: 100,
+        split_ids: vec![split_ids[0].clone(), "unknown-split".to_string()],
+        ..Default::default()
+    };
+    let search_error = single_node_search(
+        search_request,
+        test_sandbox.metastore(),
+        test_sandbox.storage_resolver(),
+    )
+    .await
+    .unwrap_err();
+    assert!(
+        matches!(search_error, SearchError::InvalidArgument(message) if message.contains("unknown-split"))
+    );
+    test_sandbox.assert_quit().await;
+    Ok(())
+}
+
 async fn test_search_util(test_sandbox: &TestSandbox, query: &str) -> Vec<u32> {
     let splits = test_sandbox
         .metastore()
@@ -1070,6 +1330,127 @@ async fn test_search_util(test_sandbox: &TestSandbox, query: &str) -> Vec<u32> {
         .collect::<Vec<u32>>()
 }
 
+#[tokio::test]
+async fn test_leaf_search_served_from_cache() -> anyhow::Result<()> {
+    let index_id = "leaf-search-served-from-cache";
+    let doc_mapping_yaml = r#"
+            field_mappings:
+              - name: body
+                type: text
+        "#;
+    let test_sandbox = TestSandbox::create(index_id, doc_mapping_yaml, "{}", &["body"]).await?;
+    let docs = vec![
+        json!({"body": "hello happy tax payer"}),
+        json!({"body": "hello"}),
+        json!({"body": "goodbye"}),
+    ];
+    test_sandbox.add_documents(docs).await?;
+    let splits_offsets: Vec<SplitIdAndFooterOffsets> = test_sandbox
+        .metastore()
+        .list_splits(ListSplitsRequest::try_from_index_uid(test_sandbox.index_uid()).unwrap())
+        .await?
+        .collect_splits()
+        .await?
+        .into_iter()
+        .map(|split| extract_split_and_footer_offsets(&split.split_metadata))
+        .collect();
+    let request = Arc::new(SearchRequest {
+        index_id_patterns: vec![index_id.to_string()],
+        query_ast: qast_json_helper("hello", &["body"]),
+        max_hits: 10,
+        ..Default::default()
+    });
+    let searcher_context = Arc::new(SearcherContext::new(SearcherConfig::default(), None));
+    let leaf_search_response = leaf_search(
+        searcher_context.clone(),
+        request.clone(),
+        test_sandbox.storage(),
+        splits_offsets.clone(),
+        test_sandbox.doc_mapper(),
+    )
+    .await?;
+    assert_eq!(leaf_search_response.num_hits, 2);
+    assert!(leaf_search_response.failed_splits.is_empty());
+
+    // The split is immutable, so the same leaf search can be answered without reading it: an
+    // empty storage does not make the search fail.
+    let cached_leaf_search_response = leaf_search(
+        searcher_context,
+        request,
+        Arc::new(RamStorage::default()),
+        splits_offsets,
+        test_sandbox.doc_mapper(),
+    )
+    .await?;
+    assert_eq!(cached_leaf_search_response, leaf_search_response);
+    test_sandbox.assert_quit().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_leaf_search_waits_for_open_split_permits() -> anyhow::Result<()> {
+    let index_id = "leaf-search-waits-for-open-split-permits";
+    let doc_mapping_yaml = r#"
+            field_mappings:
+              - name: body
+                type: text
+        "#;
+    let test_sandbox = TestSandbox::create(index_id, doc_mapping_yaml, "{}", &["body"]).await?;
+    let num_splits = 8;
+    for _ in 0..num_splits {
+        test_sandbox
+            .add_documents(vec![json!({"body": "hello"})])
+            .await?;
+    }
+    let splits_offsets: Vec<SplitIdAndFooterOffsets> = test_sandbox
+        .metastore()
+        .list_splits(ListSplitsRequest::try_from_index_uid(test_sandbox.index_uid()).unwrap())
+        .await?
+        .collect_splits()
+        .await?
+        .into_iter()
+        .map(|split| extract_split_and_footer_offsets(&split.split_metadata))
+        .collect();
+    assert_eq!(splits_offsets.len(), num_splits);
+
+    let request = Arc::new(SearchRequest {
+        index_id_patterns: vec![index_id.to_string()],
+        query_ast: qast_json_helper("hello", &["body"]),
+        max_hits: 10,
+        ..Default::default()
+    });
+    let searcher_config = SearcherConfig {
+        max_open_splits: Some(std::num::NonZeroUsize::new(1).unwrap()),
+        ..Default::default()
+    };
+    let searcher_context = Arc::new(SearcherContext::new(searcher_config, None));
+    let open_split_permit = searcher_context.open_split_permits.acquire().await;
+
+    let leaf_search_fut = leaf_search(
+        searcher_context.clone(),
+        request,
+        test_sandbox.storage(),
+        splits_offsets,
+        test_sandbox.doc_mapper(),
+    );
+    tokio::pin!(leaf_search_fut);
+    // All the splits queue behind the permit held by the test.
+    tokio::time::timeout(std::time::Duration::from_millis(100), &mut leaf_search_fut)
+        .await
+        .unwrap_err();
+    assert_eq!(searcher_context.open_split_permits.num_open(), 1);
+    assert!(searcher_context.open_split_permits.num_waiting() >= 1);
+
+    drop(open_split_permit);
+    let leaf_search_response = leaf_search_fut.await?;
+    assert_eq!(leaf_search_response.num_hits, num_splits as u64);
+    assert!(leaf_search_response.failed_splits.is_empty());
+    assert_eq!(searcher_context.open_split_permits.num_open(), 0);
+    assert_eq!(searcher_context.open_split_permits.num_waiting(), 0);
+    test_sandbox.assert_quit().await;
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_search_dynamic_mode() -> anyhow::Result<()> {
     let doc_mapping_yaml = r#"
@@ -1404,6 +1785,84 @@ async fn test_single_node_aggregation() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_single_node_aggregation_batch() -> anyhow::Result<()> {
+    let index_id = "single-node-agg-batch";
+    let doc_mapping_yaml = r#"
+            field_mappings:
+              - name: color
+                type: text
+                fast: true
+              - name: price
+                type: f64
+                fast: true
+        "#;
+    let test_sandbox = TestSandbox::create(index_id, doc_mapping_yaml, "{}", &["color"]).await?;
+    let docs = vec![
+        json!({"color": "blue", "price": 10.0}),
+        json!({"color": "blue", "price": 15.0}),
+        json!({"color": "green", "price": 10.0}),
+        json!({"color": "white", "price": 100.0}),
+        json!({"color": "white", "price": 1.0}),
+    ];
+    test_sandbox.add_documents(docs).await?;
+
+    let searcher_pool = SearcherPool::default();
+    let cluster_client = ClusterClient::new(SearchJobPlacer::new(searcher_pool.clone()));
+    let searcher_context = Arc::new(SearcherContext::new(SearcherConfig::default(), None));
+    let search_service = Arc::new(SearchServiceImpl::new(
+        test_sandbox.metastore(),
+        test_sandbox.storage_resolver(),
+        cluster_client,
+        searcher_context,
+    ));
+    let socket_addr = SocketAddr::new(Ipv4Addr::new(127, 0, 0, 1).into(), 7280u16);
+    searcher_pool.insert(
+        socket_addr,
+        SearchServiceClient::from_service(search_service.clone(), socket_addr),
+    );
+    let aggregation_requests = [
+        json!({"colors": {"terms": {"field": "color"}}}),
+        json!({"price_stats": {"stats": {"field": "price"}}}),
+    ];
+    let search_requests: Vec<SearchRequest> = aggregation_requests
+        .iter()
+        .map(|aggregation_request| SearchRequest {
+            index_id_patterns: vec![index_id.to_string()],
+            query_ast: qast_json_helper("*", &[]),
+            aggregation_request: Some(aggregation_request.to_string()),
+            ..Default::default()
+        })
+        .collect();
+    let batch_search_responses = search_service
+        .root_search_batch(search_requests.clone())
+        .await?
+        .into_iter()
+        .collect::<crate::Result<Vec<_>>>()?;
+    assert_eq!(batch_search_responses.len(), 2);
+
+    let price_stats_json: JsonValue =
+        serde_json::from_str(batch_search_responses[1].aggregation.as_ref().unwrap())?;
+    assert_eq!(price_stats_json["price_stats"]["count"], 5);
+    assert_eq!(price_stats_json["price_stats"]["max"], 100.0);
+
+    // The batched aggregations match the ones computed by independent searches.
+    for (search_request, batch_search_response) in search_requests
+        .into_iter()
+        .zip(batch_search_responses.into_iter())
+    {
+        let search_response = search_service.root_search(search_request).await?;
+        assert_eq!(batch_search_response.num_hits, 5);
+        let batch_aggregation_json: JsonValue =
+            serde_json::from_str(&batch_search_response.aggregation.unwrap())?;
+        let aggregation_json: JsonValue =
+            serde_json::from_str(&search_response.aggregation.unwrap())?;
+        assert_eq!(batch_aggregation_json, aggregation_json);
+    }
+    test_sandbox.assert_quit().await;
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_single_node_aggregation_missing_fast_field() {
     let index_id = "single-node-agg-2";
@@ -1645,6 +2104,52 @@ async fn test_single_node_range_queries() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_single_node_search_with_default_timezone() -> anyhow::Result<()> {
+    let index_id = "single-node-search-default-timezone";
+    let doc_mapping_yaml = r#"
+            default_timezone: Asia/Tokyo
+            field_mappings:
+              - name: datetime
+                type: datetime
+                fast: true
+              - name: log
+                type: text
+        "#;
+    let docs = vec![
+        json!({"datetime": "2023-01-10T15:13:35Z", "log": "User not found"}),
+        json!({"datetime": "2023-01-10T15:13:36Z", "log": "Request failed"}),
+        json!({"datetime": "2023-01-10T15:13:37Z", "log": "Request successful"}),
+    ];
+    let test_sandbox = TestSandbox::create(index_id, doc_mapping_yaml, "{}", &["log"]).await?;
+    test_sandbox.add_documents(docs).await?;
+    {
+        // Datetime values are stored in UTC, so queries match on the underlying instant.
+        let search_request = SearchRequest {
+            index_id_patterns: vec![index_id.to_string()],
+            query_ast: qast_json_helper(
+                "datetime:[2023-01-10T15:13:36Z TO 2023-01-10T15:13:37Z}",
+                &[],
+            ),
+            max_hits: 10,
+            ..Default::default()
+        };
+        let single_node_result = single_node_search(
+            search_request,
+            test_sandbox.metastore(),
+            test_sandbox.storage_resolver(),
+        )
+        .await?;
+        assert_eq!(single_node_result.num_hits, 1);
+        let hit_json: JsonValue = serde_json::from_str(&single_node_result.hits[0].json)?;
+        let expected_json: JsonValue =
+            json!({"datetime": "2023-01-11T00:13:36+09:00", "log": "Request failed"});
+        assert_json_include!(actual: hit_json, expected: expected_json);
+    }
+    test_sandbox.assert_quit().await;
+    Ok(())
+}
+
 fn collect_str_terms(response: LeafListTermsResponse) -> Vec<String> {
     response
         .terms
@@ -1901,3 +2406,289 @@ fn test_global_doc_address_ser_deser() {
     let doc_address_deser: GlobalDocAddress = doc_address_string.parse().unwrap();
     assert_eq!(doc_address_deser, doc_address);
 }
+
+#[tokio::test]
+async fn test_root_list_facets() -> anyhow::Result<()> {
+    let index_id = "root-list-facets";
+    let doc_mapping_yaml = r#"
+            field_mappings:
+              - name: severity
+                type: text
+                tokenizer: raw
+              - name: body
+                type: text
+        "#;
+    let test_sandbox = TestSandbox::create(index_id, doc_mapping_yaml, "{}", &["body"]).await?;
+    let severities = [
+        ("info", 5),
+        ("warn", 3),
+        ("error", 2),
+        ("debug", 1),
+        ("errno", 4),
+    ];
+    // Indexes the documents in two splits, so that facet counts are merged across splits.
+    for _ in 0..2 {
+        let docs: Vec<JsonValue> = severities
+            .iter()
+            .flat_map(|(severity, num_docs)| {
+                (0..*num_docs).map(|_| json!({"severity": severity, "body": "hello"}))
+            })
+            .collect();
+        test_sandbox.add_documents(docs).await?;
+    }
+    let searcher_pool = SearcherPool::default();
+    let cluster_client = ClusterClient::new(SearchJobPlacer::new(searcher_pool.clone()));
+    let searcher_context = Arc::new(SearcherContext::new(SearcherConfig::default(), None));
+    let search_service = Arc::new(SearchServiceImpl::new(
+        test_sandbox.metastore(),
+        test_sandbox.storage_resolver(),
+        cluster_client,
+        searcher_context,
+    ));
+    let socket_addr = SocketAddr::new(Ipv4Addr::new(127, 0, 0, 1).into(), 7280u16);
+    searcher_pool.insert(
+        socket_addr,
+        SearchServiceClient::from_service(search_service.clone(), socket_addr),
+    );
+    let facet_counts = |facet_counts: Vec<FacetCount>| -> Vec<(String, u64)> {
+        facet_counts
+            .into_iter()
+            .map(|facet_count| (facet_count.value, facet_count.num_docs))
+            .collect()
+    };
+    {
+        let request = ListFacetsRequest {
+            index_id_patterns: vec![index_id.to_string()],
+            field: "severity".to_string(),
+            prefix: None,
+            max_facets: 3,
+        };
+        let response = search_service.root_list_facets(request).await?;
+        assert_eq!(
+            facet_counts(response.facets),
+            [
+                ("info".to_string(), 10),
+                ("errno".to_string(), 8),
+                ("warn".to_string(), 6),
+            ]
+        );
+        assert!(!response.truncated);
+    }
+    {
+        let request = ListFacetsRequest {
+            index_id_patterns: vec![index_id.to_string()],
+            field: "severity".to_string(),
+            prefix: Some("err".to_string()),
+            max_facets: 10,
+        };
+        let response = search_service.root_list_facets(request).await?;
+        assert_eq!(
+            facet_counts(response.facets),
+            [("errno".to_string(), 8), ("error".to_string(), 4)]
+        );
+    }
+    {
+        let request = ListFacetsRequest {
+            index_id_patterns: vec![index_id.to_string()],
+            field: "unknown".to_string(),
+            prefix: None,
+            max_facets: 10,
+        };
+        let error = search_service.root_list_facets(request).await.unwrap_err();
+        assert!(matches!(error, SearchError::InvalidArgument(_)));
+    }
+    test_sandbox.assert_quit().await;
+    Ok(())
+}
+
+/// Reads issued to the storage to warm up a split.
+#[derive(Clone, Copy, Debug, Default)]
+struct WarmupReads {
+    num_bytes: usize,
+    num_slices: usize,
+    num_requests: usize,
+}
+
+impl WarmupReads {
+    fn record(&mut self, byte_ranges: &[Range<usize>]) {
+        self.num_bytes += byte_ranges
+            .iter()
+            .map(|byte_range| byte_range.len())
+            .sum::<usize>();
+        self.num_slices += byte_ranges.len();
+        self.num_requests += 1;
+    }
+
+    fn since(&self, previous: &WarmupReads) -> WarmupReads {
+        WarmupReads {
+            num_bytes: self.num_bytes - previous.num_bytes,
+            num_slices: self.num_slices - previous.num_slices,
+            num_requests: self.num_requests - previous.num_requests,
+        }
+    }
+}
+
+/// Returns the number of bytes fetched from the storage to warm up the split for the search
+/// request.
+async fn warmup_num_bytes(
+    test_sandbox: &TestSandbox,
+    split_offsets: &SplitIdAndFooterOffsets,
+    search_request: &SearchRequest,
+) -> anyhow::Result<usize> {
+    let warmup_reads = warmup_reads(test_sandbox, split_offsets, search_request).await?;
+    Ok(warmup_reads.num_bytes)
+}
+
+/// Returns the reads issued to the storage to warm up the split for the search request.
+async fn warmup_reads(
+    test_sandbox: &TestSandbox,
+    split_offsets: &SplitIdAndFooterOffsets,
+    search_request: &SearchRequest,
+) -> anyhow::Result<WarmupReads> {
+    let split_data = test_sandbox
+        .storage()
+        .get_all(Path::new(&quickwit_common::split_file(
+            &split_offsets.split_id,
+        )))
+        .await?;
+    let warmup_reads = Arc::new(Mutex::new(WarmupReads::default()));
+    let warmup_reads_clone = warmup_reads.clone();
+    let split_data_clone = split_data.clone();
+    let mut mock_storage = quickwit_storage::MockStorage::new();
+    mock_storage
+        .expect_get_slice()
+        .returning(move |_path, byte_range| {
+            warmup_reads_clone
+                .lock()
+                .unwrap()
+                .record(&[byte_range.clone()]);
+            Ok(split_data_clone.slice(byte_range))
+        });
+    let warmup_reads_clone = warmup_reads.clone();
+    mock_storage
+        .expect_get_slices()
+        .returning(move |_path, byte_ranges| {
+            warmup_reads_clone.lock().unwrap().record(byte_ranges);
+            Ok(byte_ranges
+                .iter()
+                .map(|byte_range| split_data.slice(byte_range.clone()))
+                .collect())
+        });
+    let searcher_context = SearcherContext::for_test();
+    let doc_mapper = test_sandbox.doc_mapper();
+    let index = crate::leaf::open_index_with_caches(
+        &searcher_context,
+        Arc::new(mock_storage),
+        split_offsets,
+        Some(doc_mapper.tokenizer_manager()),
+        true,
+    )
+    .await?;
+    let quickwit_collector = crate::collector::make_collector_for_split(
+        split_offsets.split_id.clone(),
+        doc_mapper.as_ref(),
+        search_request,
+        searcher_context.get_aggregation_limits(),
+    )?;
+    let query_ast: QueryAst = serde_json::from_str(&search_request.query_ast)?;
+    let (_query, mut warmup_info) = doc_mapper.query(index.schema(), &query_ast, false)?;
+    warmup_info.merge(quickwit_collector.warmup_info());
+    warmup_info.simplify();
+
+    let searcher = index
+        .reader_builder()
+        .reload_policy(tantivy::ReloadPolicy::Manual)
+        .try_into()?
+        .searcher();
+    let reads_before_warmup = *warmup_reads.lock().unwrap();
+    crate::leaf::warmup(&searcher, &warmup_info).await?;
+    let reads_after_warmup = *warmup_reads.lock().unwrap();
+    Ok(reads_after_warmup.since(&reads_before_warmup))
+}
+
+#[tokio::test]
+async fn test_warmup_num_bytes_depends_on_query_shape() -> anyhow::Result<()> {
+    let index_id = "warmup-num-bytes";
+    let doc_mapping_yaml = r#"
+            field_mappings:
+              - name: body
+                type: text
+                fieldnorms: true
+              - name: title
+                type: text
+                fieldnorms: true
+              - name: response_time
+                type: u64
+                fast: true
+        "#;
+    let test_sandbox = TestSandbox::create(index_id, doc_mapping_yaml, "{}", &["body"]).await?;
+    let docs: Vec<JsonValue> = (0..1_000u64)
+        .map(|doc_id| {
+            json!({
+                "body": format!("hello body{doc_id}"),
+                "title": format!("title{doc_id}"),
+                "response_time": doc_id * 7_919 % 10_007,
+            })
+        })
+        .collect();
+    test_sandbox.add_documents(docs).await?;
+
+    let splits = test_sandbox
+        .metastore()
+        .list_splits(ListSplitsRequest::try_from_index_uid(test_sandbox.index_uid()).unwrap())
+        .await?
+        .collect_splits()
+        .await?;
+    assert_eq!(splits.len(), 1);
+    let split_offsets = extract_split_and_footer_offsets(&splits[0].split_metadata);
+
+    let term_search_request = SearchRequest {
+        index_id_patterns: vec![index_id.to_string()],
+        query_ast: qast_json_helper("body:hello", &[]),
+        max_hits: 10,
+        ..Default::default()
+    };
+    let term_num_bytes =
+        warmup_num_bytes(&test_sandbox, &split_offsets, &term_search_request).await?;
+    assert!(term_num_bytes > 0);
+
+    let aggregation_search_request = SearchRequest {
+        aggregation_request: Some(
+            json!({
+                "response_time_histogram": {
+                    "histogram": {
+                        "field": "response_time",
+                        "interval": 100,
+                    }
+                }
+            })
+            .to_string(),
+        ),
+        ..term_search_request.clone()
+    };
+    let aggregation_warmup_reads =
+        warmup_reads(&test_sandbox, &split_offsets, &aggregation_search_request).await?;
+    let aggregation_num_bytes = aggregation_warmup_reads.num_bytes;
+    assert!(aggregation_num_bytes > term_num_bytes);
+    // The concurrent reads of the term dictionary and of the fast field are batched.
+    assert!(aggregation_warmup_reads.num_requests < aggregation_warmup_reads.num_slices);
+
+    // Scoring only requires the field norms of the fields targeted by the query.
+    let scored_search_request = SearchRequest {
+        sort_fields: vec![SortField {
+            field_name: "_score".to_string(),
+            sort_order: SortOrder::Desc as i32,
+            sort_datetime_format: None,
+        }],
+        ..term_search_request.clone()
+    };
+    let scored_num_bytes =
+        warmup_num_bytes(&test_sandbox, &split_offsets, &scored_search_request).await?;
+    // The field norms of `body` take at most one byte per document, and those of `title` must not
+    // be fetched.
+    assert!(scored_num_bytes >= term_num_bytes);
+    assert!(scored_num_bytes - term_num_bytes <= 1_000);
+
+    test_sandbox.assert_quit().await;
+    Ok(())
+}
@@ -0,0 +1,260 @@
+// Copyright (C) 2024 Quickwit, Inc.
+//
+// Quickwit is offered under the AGPL v3.0 and as commercial software.
+// For commercial licensing, contact us at hello@quickwit.io.
+//
+// AGPL:
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as
+// published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use std::future::Future;
+use std::task::{Context, Poll};
+
+use futures::future::BoxFuture;
+use hyper::{Method, Request};
+use quickwit_common::runtimes::RuntimeType;
+use quickwit_proto::tonic::server::NamedService;
+use tokio::runtime::Handle;
+use tower::Service;
+
+/// Runs a future on the admin runtime and waits for its output, so that the admin and control
+/// requests remain responsive when the runtime serving the searches is saturated.
+///
+/// The future runs to completion even if the returned future is dropped.
+pub(crate) async fn run_on_admin_runtime<F>(future: F) -> F::Output
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    run_on_runtime(&RuntimeType::Admin.get_runtime_handle(), future).await
+}
+
+async fn run_on_runtime<F>(runtime_handle: &Handle, future: F) -> F::Output
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    let join_handle = runtime_handle.spawn(future);
+
+    match join_handle.await {
+        Ok(output) => output,
+        Err(join_error) => match join_error.try_into_panic() {
+            Ok(panic_payload) => std::panic::resume_unwind(panic_payload),
+            Err(_) => panic!("runtimes should not cancel their tasks"),
+        },
+    }
+}
+
+/// Returns whether a REST request is an admin or control request: health checks, metrics, node
+/// info, and index creation or deletion.
+fn is_admin_rest_request(method: &Method, path: &str) -> bool {
+    if path.starts_with("/health/")
+        || matches!(path, "/metrics" | "/api/v1/version" | "/api/v1/config")
+    {
+        return true;
+    }
+    match *method {
+        Method::POST => path == "/api/v1/indexes",
+        Method::DELETE => path
+            .strip_prefix("/api/v1/indexes/")
+            .is_some_and(|index_id| !index_id.is_empty() && !index_id.contains('/')),
+        _ => false,
+    }
+}
+
+/// Wraps the REST service of a listener driven by the admin runtime. The admin requests are
+/// handled right away on the admin runtime, whereas the other requests are handed over to the
+/// runtime the REST server was started from.
+///
+/// This way, accepting connections and answering health checks do not depend on the runtime
+/// serving the searches, which may be saturated.
+#[derive(Clone)]
+pub(crate) struct AdminRuntimeRestService<S> {
+    inner: S,
+    default_runtime_handle: Handle,
+}
+
+impl<S> AdminRuntimeRestService<S> {
+    pub fn new(inner: S, default_runtime_handle: Handle) -> Self {
+        Self {
+            inner,
+            default_runtime_handle,
+        }
+    }
+}
+
+impl<S, B> Service<Request<B>> for AdminRuntimeRestService<S>
+where
+    S: Service<Request<B>>,
+    S::Response: Send + 'static,
+    S::Error: Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<S::Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let is_admin_request = is_admin_rest_request(request.method(), request.uri().path());
+        let future = self.inner.call(request);
+
+        if is_admin_request {
+            Box::pin(future)
+        } else {
+            let default_runtime_handle = self.default_runtime_handle.clone();
+            Box::pin(async move { run_on_runtime(&default_runtime_handle, future).await })
+        }
+    }
+}
+
+/// Wraps a gRPC service so that its requests are handled on the admin runtime.
+#[derive(Clone)]
+pub(crate) struct AdminRuntimeGrpcService<S> {
+    inner: S,
+}
+
+impl<S> AdminRuntimeGrpcService<S> {
+    pub fn new(inner: S) -> Self {
+        Self { inner }
+    }
+}
+
+impl<S> NamedService for AdminRuntimeGrpcService<S>
+where S: NamedService
+{
+    const NAME: &'static str = S::NAME;
+}
+
+impl<S, R> Service<R> for AdminRuntimeGrpcService<S>
+where
+    S: Service<R>,
+    S::Response: Send + 'static,
+    S::Error: Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<S::Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: R) -> Self::Future {
+        let future = self.inner.call(request);
+        Box::pin(run_on_admin_runtime(future))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+    use std::time::{Duration, Instant};
+
+    use hyper::Body;
+    use tower::{service_fn, ServiceExt};
+
+    use super::*;
+
+    fn current_thread_name() -> String {
+        std::thread::current()
+            .name()
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    #[test]
+    fn test_is_admin_rest_request() {
+        assert!(is_admin_rest_request(&Method::GET, "/health/livez"));
+        assert!(is_admin_rest_request(&Method::GET, "/health/readyz"));
+        assert!(is_admin_rest_request(&Method::GET, "/metrics"));
+        assert!(is_admin_rest_request(&Method::GET, "/api/v1/version"));
+        assert!(is_admin_rest_request(&Method::GET, "/api/v1/config"));
+        assert!(is_admin_rest_request(&Method::POST, "/api/v1/indexes"));
+        assert!(is_admin_rest_request(
+            &Method::DELETE,
+            "/api/v1/indexes/my-index"
+        ));
+
+        assert!(!is_admin_rest_request(&Method::GET, "/api/v1/indexes"));
+        assert!(!is_admin_rest_request(
+            &Method::DELETE,
+            "/api/v1/indexes/my-index/splits"
+        ));
+        assert!(!is_admin_rest_request(
+            &Method::POST,
+            "/api/v1/my-index/search"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_admin_runtime_rest_service() {
+        let service = service_fn(|_request: Request<Body>| async {
+            Ok::<_, std::convert::Infallible>(current_thread_name())
+        });
+        let default_runtime_handle = Handle::current();
+        let admin_runtime_rest_service =
+            AdminRuntimeRestService::new(service, default_runtime_handle);
+
+        // The listener is driven by the admin runtime.
+        let thread_names = run_on_admin_runtime(async move {
+            let health_request = Request::get("/health/livez").body(Body::empty()).unwrap();
+            let health_thread_name = admin_runtime_rest_service
+                .clone()
+                .oneshot(health_request)
+                .await
+                .unwrap();
+            let search_request = Request::get("/api/v1/my-index/search")
+                .body(Body::empty())
+                .unwrap();
+            let search_thread_name = admin_runtime_rest_service
+                .oneshot(search_request)
+                .await
+                .unwrap();
+            (health_thread_name, search_thread_name)
+        })
+        .await;
+        let (health_thread_name, search_thread_name) = thread_names;
+        assert!(health_thread_name.starts_with("admin-"));
+        assert!(!search_thread_name.starts_with("admin-"));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_run_on_admin_runtime_with_saturated_runtime() {
+        // Blocks the only worker thread of the test runtime. The test runtime timers are driven by
+        // the worker thread, so they cannot be used until the end of the test.
+        let (started_tx, started_rx) = mpsc::channel();
+        tokio::spawn(async move {
+            started_tx.send(()).unwrap();
+            std::thread::sleep(Duration::from_secs(1));
+        });
+        started_rx.recv().unwrap();
+
+        let saturated_runtime_task = tokio::spawn(async {});
+        let now = Instant::now();
+        let thread_name = run_on_admin_runtime(async {
+            std::thread::current()
+                .name()
+                .unwrap_or_default()
+                .to_string()
+        })
+        .await;
+        assert!(now.elapsed() < Duration::from_millis(500));
+        assert!(thread_name.starts_with("admin-"));
+        assert!(!saturated_runtime_task.is_finished());
+    }
+}
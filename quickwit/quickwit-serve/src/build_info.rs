@@ -85,6 +85,10 @@ impl BuildInfo {
 
 #[derive(Debug, Eq, PartialEq, Serialize, utoipa::ToSchema)]
 pub struct RuntimeInfo {
+    /// Operating system of the node, e.g. `linux` or `macos`.
+    pub os: &'static str,
+    /// CPU architecture of the node, e.g. `x86_64` or `aarch64`.
+    pub arch: &'static str,
     pub num_cpus_logical: usize,
     pub num_cpus_physical: usize,
     pub num_threads_blocking: usize,
@@ -101,6 +105,8 @@ impl RuntimeInfo {
             let runtimes_config = RuntimesConfig::with_num_cpus(num_cpus_logical);
 
             Self {
+                os: std::env::consts::OS,
+                arch: std::env::consts::ARCH,
                 num_cpus_logical,
                 num_cpus_physical: num_cpus::get_physical(),
                 num_threads_blocking: runtimes_config.num_threads_blocking,
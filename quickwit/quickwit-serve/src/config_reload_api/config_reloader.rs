@@ -0,0 +1,333 @@
+// Copyright (C) 2024 Quickwit, Inc.
+//
+// Quickwit is offered under the AGPL v3.0 and as commercial software.
+// For commercial licensing, contact us at hello@quickwit.io.
+//
+// AGPL:
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as
+// published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use anyhow::Context;
+use once_cell::sync::OnceCell;
+use quickwit_common::uri::Uri;
+use quickwit_config::{ConfigFormat, LoggingConfig, NodeConfig};
+use quickwit_search::SearcherContext;
+use quickwit_storage::{load_file, StorageResolver};
+use serde::Serialize;
+use serde_json::{Map as JsonMap, Value as JsonValue};
+use tokio::signal::unix::{signal, SignalKind};
+use tokio::sync::Mutex;
+use tracing::{error, info, warn};
+
+use crate::rate_modulator::SharedThrottlingCurve;
+
+/// Rebuilds the log filter of the global tracing subscriber from a logging config.
+pub type LogFilterReloader = Arc<dyn Fn(&LoggingConfig) -> anyhow::Result<()> + Send + Sync>;
+
+static LOG_FILTER_RELOADER: OnceCell<LogFilterReloader> = OnceCell::new();
+
+/// Registers the callback applying the logging settings of a reloaded node config. If no callback
+/// is registered, for instance because the log filter is set with `RUST_LOG`, the logging
+/// settings are ignored on reload.
+pub fn set_log_filter_reloader(log_filter_reloader: LogFilterReloader) {
+    if LOG_FILTER_RELOADER.set(log_filter_reloader).is_err() {
+        warn!("log filter reloader is already registered");
+    }
+}
+
+const LOGGING_LEVEL_SETTING: &str = "logging_config.level";
+const LOGGING_MODULE_LEVELS_SETTING: &str = "logging_config.module_levels";
+const MAX_NUM_CONCURRENT_SPLIT_SEARCHES_SETTING: &str =
+    "searcher_config.max_num_concurrent_split_searches";
+const THROTTLING_CURVE_SETTING: &str = "ingest_api_config.throttling_curve";
+
+/// Settings of a reloaded node config that changed, split between the ones applied to the running
+/// node and the ones ignored because they require a restart.
+#[derive(Debug, Default, Eq, PartialEq, Serialize)]
+pub(crate) struct ConfigReloadReport {
+    pub applied_settings: Vec<String>,
+    pub ignored_settings: Vec<String>,
+}
+
+impl ConfigReloadReport {
+    fn is_applied(&self, setting: &str) -> bool {
+        self.applied_settings
+            .iter()
+            .any(|applied_setting| applied_setting == setting)
+    }
+}
+
+/// Reloads the node config from the file the node was started with and applies the settings that
+/// can safely change at runtime: the log levels, the leaf search concurrency, and the ingest API
+/// throttling curve.
+pub(crate) struct ConfigReloader {
+    config_uri: Uri,
+    // Config currently in effect: the ignored settings keep their startup value.
+    node_config: Mutex<NodeConfig>,
+    searcher_context: Arc<SearcherContext>,
+    throttling_curve: SharedThrottlingCurve,
+    log_filter_reloader_opt: Option<LogFilterReloader>,
+}
+
+impl ConfigReloader {
+    pub fn new(
+        config_uri: Uri,
+        node_config: NodeConfig,
+        searcher_context: Arc<SearcherContext>,
+        throttling_curve: SharedThrottlingCurve,
+    ) -> Self {
+        Self {
+            config_uri,
+            node_config: Mutex::new(node_config),
+            searcher_context,
+            throttling_curve,
+            log_filter_reloader_opt: LOG_FILTER_RELOADER.get().cloned(),
+        }
+    }
+
+    pub async fn reload(&self) -> anyhow::Result<ConfigReloadReport> {
+        let config_content = load_file(&StorageResolver::unconfigured(), &self.config_uri)
+            .await
+            .context("failed to load node config")?;
+        let config_format = ConfigFormat::sniff_from_uri(&self.config_uri)?;
+        let new_node_config = NodeConfig::load(config_format, config_content.as_slice())
+            .await
+            .with_context(|| format!("failed to parse node config `{}`", self.config_uri))?;
+        self.apply(new_node_config).await
+    }
+
+    async fn apply(&self, mut new_node_config: NodeConfig) -> anyhow::Result<ConfigReloadReport> {
+        let mut node_config = self.node_config.lock().await;
+        // The enabled services can be overridden on the command line.
+        new_node_config.enabled_services = node_config.enabled_services.clone();
+
+        let mut report = ConfigReloadReport::default();
+
+        for setting in changed_settings(&node_config, &new_node_config)? {
+            let is_reloadable = match setting.as_str() {
+                LOGGING_LEVEL_SETTING | LOGGING_MODULE_LEVELS_SETTING => {
+                    self.log_filter_reloader_opt.is_some()
+                }
+                MAX_NUM_CONCURRENT_SPLIT_SEARCHES_SETTING | THROTTLING_CURVE_SETTING => true,
+                _ => false,
+            };
+            if is_reloadable {
+                report.applied_settings.push(setting);
+            } else {
+                report.ignored_settings.push(setting);
+            }
+        }
+        if report.is_applied(LOGGING_LEVEL_SETTING)
+            || report.is_applied(LOGGING_MODULE_LEVELS_SETTING)
+        {
+            let log_filter_reloader = self
+                .log_filter_reloader_opt
+                .as_ref()
+                .expect("logging settings should only be applied with a log filter reloader");
+            log_filter_reloader(&new_node_config.logging_config)
+                .context("failed to reload log filter")?;
+            node_config.logging_config.level = new_node_config.logging_config.level;
+            node_config.logging_config.module_levels = new_node_config.logging_config.module_levels;
+        }
+        if report.is_applied(MAX_NUM_CONCURRENT_SPLIT_SEARCHES_SETTING) {
+            let max_num_concurrent_split_searches = new_node_config
+                .searcher_config
+                .max_num_concurrent_split_searches;
+            self.searcher_context
+                .set_max_num_concurrent_split_searches(max_num_concurrent_split_searches);
+            node_config
+                .searcher_config
+                .max_num_concurrent_split_searches = max_num_concurrent_split_searches;
+        }
+        if report.is_applied(THROTTLING_CURVE_SETTING) {
+            let throttling_curve = new_node_config.ingest_api_config.throttling_curve;
+            *self
+                .throttling_curve
+                .write()
+                .expect("the lock should not be poisoned") = throttling_curve.clone();
+            node_config.ingest_api_config.throttling_curve = throttling_curve;
+        }
+        if !report.ignored_settings.is_empty() {
+            warn!(
+                ignored_settings=?report.ignored_settings,
+                "node config settings changed but require a restart to take effect"
+            );
+        }
+        info!(applied_settings=?report.applied_settings, "reloaded node config");
+        Ok(report)
+    }
+}
+
+/// Reloads the node config every time the process receives a `SIGHUP` signal.
+pub(crate) fn spawn_sighup_listener(config_reloader: Arc<ConfigReloader>) -> anyhow::Result<()> {
+    let mut sighup_stream =
+        signal(SignalKind::hangup()).context("failed to register SIGHUP handler")?;
+    tokio::spawn(async move {
+        while sighup_stream.recv().await.is_some() {
+            info!("received SIGHUP, reloading node config");
+
+            if let Err(error) = config_reloader.reload().await {
+                error!(error=?error, "failed to reload node config");
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Returns the paths of the settings that differ between two node configs, down to the fields of
+/// the config sections, e.g. `searcher_config.max_num_concurrent_split_searches`.
+fn changed_settings(
+    node_config: &NodeConfig,
+    new_node_config: &NodeConfig,
+) -> anyhow::Result<Vec<String>> {
+    let node_config_json = to_json_object(node_config)?;
+    let new_node_config_json = to_json_object(new_node_config)?;
+    let mut changed_settings = Vec::new();
+
+    for key in union_keys(&node_config_json, &new_node_config_json) {
+        let value_opt = node_config_json.get(key);
+        let new_value_opt = new_node_config_json.get(key);
+
+        if value_opt == new_value_opt {
+            continue;
+        }
+        if let (Some(JsonValue::Object(section)), Some(JsonValue::Object(new_section))) =
+            (value_opt, new_value_opt)
+        {
+            for field in union_keys(section, new_section) {
+                if section.get(field) != new_section.get(field) {
+                    changed_settings.push(format!("{key}.{field}"));
+                }
+            }
+        } else {
+            changed_settings.push(key.clone());
+        }
+    }
+    Ok(changed_settings)
+}
+
+fn to_json_object(node_config: &NodeConfig) -> anyhow::Result<JsonMap<String, JsonValue>> {
+    match serde_json::to_value(node_config)? {
+        JsonValue::Object(json_object) => Ok(json_object),
+        _ => anyhow::bail!("node config should serialize to a JSON object"),
+    }
+}
+
+fn union_keys<'a>(
+    left: &'a JsonMap<String, JsonValue>,
+    right: &'a JsonMap<String, JsonValue>,
+) -> BTreeSet<&'a String> {
+    left.keys().chain(right.keys()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::sync::RwLock;
+
+    use quickwit_config::SearcherConfig;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_config_reloader_applies_changed_log_level() {
+        let node_config = NodeConfig::for_test();
+        let searcher_context = Arc::new(SearcherContext::new(
+            node_config.searcher_config.clone(),
+            None,
+        ));
+        let throttling_curve = Arc::new(RwLock::new(
+            node_config.ingest_api_config.throttling_curve.clone(),
+        ));
+        let reloaded_logging_configs = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let reloaded_logging_configs_clone = reloaded_logging_configs.clone();
+        let log_filter_reloader: LogFilterReloader = Arc::new(move |logging_config| {
+            reloaded_logging_configs_clone
+                .lock()
+                .unwrap()
+                .push(logging_config.clone());
+            Ok(())
+        });
+        let config_reloader = ConfigReloader {
+            config_uri: Uri::for_test("file:///config/quickwit.yaml"),
+            node_config: Mutex::new(node_config.clone()),
+            searcher_context: searcher_context.clone(),
+            throttling_curve,
+            log_filter_reloader_opt: Some(log_filter_reloader),
+        };
+        let mut new_node_config = node_config.clone();
+        new_node_config.logging_config.level = Some("debug".to_string());
+        new_node_config.logging_config.module_levels =
+            BTreeMap::from([("quickwit_search".to_string(), "trace".to_string())]);
+        new_node_config.searcher_config = SearcherConfig {
+            max_num_concurrent_split_searches: 7,
+            ..node_config.searcher_config.clone()
+        };
+        new_node_config.node_id = "new-node-id".to_string();
+
+        let report = config_reloader.apply(new_node_config).await.unwrap();
+        assert_eq!(
+            report.applied_settings,
+            [
+                LOGGING_LEVEL_SETTING,
+                LOGGING_MODULE_LEVELS_SETTING,
+                MAX_NUM_CONCURRENT_SPLIT_SEARCHES_SETTING,
+            ]
+        );
+        assert_eq!(report.ignored_settings, ["node_id"]);
+
+        let reloaded_logging_configs = reloaded_logging_configs.lock().unwrap();
+        assert_eq!(reloaded_logging_configs.len(), 1);
+        assert_eq!(reloaded_logging_configs[0].level.as_deref(), Some("debug"));
+        assert_eq!(searcher_context.max_num_concurrent_split_searches(), 7);
+
+        let node_config_in_effect = config_reloader.node_config.lock().await;
+        assert_eq!(
+            node_config_in_effect.logging_config.level.as_deref(),
+            Some("debug")
+        );
+        assert_eq!(node_config_in_effect.node_id, node_config.node_id);
+    }
+
+    #[tokio::test]
+    async fn test_config_reloader_ignores_logging_settings_without_log_filter_reloader() {
+        let node_config = NodeConfig::for_test();
+        let searcher_context = Arc::new(SearcherContext::new(
+            node_config.searcher_config.clone(),
+            None,
+        ));
+        let throttling_curve = Arc::new(RwLock::new(Vec::new()));
+        let config_reloader = ConfigReloader {
+            config_uri: Uri::for_test("file:///config/quickwit.yaml"),
+            node_config: Mutex::new(node_config.clone()),
+            searcher_context,
+            throttling_curve: throttling_curve.clone(),
+            log_filter_reloader_opt: None,
+        };
+        let mut new_node_config = node_config.clone();
+        new_node_config.logging_config.level = Some("debug".to_string());
+        new_node_config.ingest_api_config.throttling_curve =
+            node_config.ingest_api_config.throttling_curve[..1].to_vec();
+
+        let report = config_reloader.apply(new_node_config).await.unwrap();
+        assert_eq!(report.applied_settings, [THROTTLING_CURVE_SETTING]);
+        assert_eq!(report.ignored_settings, [LOGGING_LEVEL_SETTING]);
+        assert_eq!(
+            *throttling_curve.read().unwrap(),
+            node_config.ingest_api_config.throttling_curve[..1]
+        );
+    }
+}
@@ -17,11 +17,9 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
-mod base;
-use base::OpendalStorage;
+mod config_reloader;
+mod rest_handler;
 
-mod google_cloud_storage;
-
-#[cfg(feature = "integration-testsuite")]
-pub use google_cloud_storage::new_emulated_google_cloud_storage;
-pub use google_cloud_storage::GoogleCloudStorageFactory;
+pub use config_reloader::{set_log_filter_reloader, LogFilterReloader};
+pub(crate) use config_reloader::{spawn_sighup_listener, ConfigReloader};
+pub(crate) use rest_handler::config_reload_handler;
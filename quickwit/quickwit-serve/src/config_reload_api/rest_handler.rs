@@ -0,0 +1,50 @@
+// Copyright (C) 2024 Quickwit, Inc.
+//
+// Quickwit is offered under the AGPL v3.0 and as commercial software.
+// For commercial licensing, contact us at hello@quickwit.io.
+//
+// AGPL:
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as
+// published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+
+use quickwit_proto::ServiceErrorCode;
+use warp::{Filter, Rejection};
+
+use super::config_reloader::{ConfigReloadReport, ConfigReloader};
+use crate::format::extract_format_from_qs;
+use crate::json_api_response::{make_json_api_response, ApiError};
+use crate::require;
+
+/// Reloads the node config from the file the node was started with. The route is not available
+/// if the node was not started from a config file.
+pub(crate) fn config_reload_handler(
+    config_reloader_opt: Option<Arc<ConfigReloader>>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = Rejection> + Clone {
+    warp::path!("config" / "reload")
+        .and(warp::post())
+        .and(require(config_reloader_opt))
+        .then(reload_config_endpoint)
+        .and(extract_format_from_qs())
+        .map(make_json_api_response)
+}
+
+async fn reload_config_endpoint(
+    config_reloader: Arc<ConfigReloader>,
+) -> Result<ConfigReloadReport, ApiError> {
+    config_reloader.reload().await.map_err(|error| ApiError {
+        service_code: ServiceErrorCode::BadRequest,
+        message: format!("{error:#}"),
+    })
+}
@@ -18,6 +18,7 @@
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
 use quickwit_config::build_doc_mapper;
+use quickwit_janitor::delete_query_for_doc_ids;
 use quickwit_janitor::error::JanitorError;
 use quickwit_metastore::IndexMetadataResponseExt;
 use quickwit_proto::metastore::{
@@ -36,8 +37,8 @@ use crate::with_arg;
 
 #[derive(utoipa::OpenApi)]
 #[openapi(
-    paths(get_delete_tasks, post_delete_request),
-    components(schemas(DeleteQueryRequest, DeleteTask, DeleteQuery,))
+    paths(get_delete_tasks, post_delete_request, post_delete_doc_ids_request),
+    components(schemas(DeleteQueryRequest, DeleteDocIdsRequest, DeleteTask, DeleteQuery,))
 )]
 pub struct DeleteTaskApi;
 
@@ -57,11 +58,21 @@ pub struct DeleteQueryRequest {
     pub end_timestamp: Option<i64>,
 }
 
+/// This struct represents the IDs of the documents to delete passed to the rest API.
+#[derive(Deserialize, Debug, Eq, PartialEq, Default, utoipa::ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DeleteDocIdsRequest {
+    /// IDs of the documents to delete, matched against the `_id` field.
+    pub doc_ids: Vec<String>,
+}
+
 /// Delete query API handlers.
 pub fn delete_task_api_handlers(
     metastore: MetastoreServiceClient,
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = Rejection> + Clone {
-    get_delete_tasks_handler(metastore.clone()).or(post_delete_tasks_handler(metastore.clone()))
+    get_delete_tasks_handler(metastore.clone())
+        .or(post_delete_tasks_handler(metastore.clone()))
+        .or(post_delete_doc_ids_handler(metastore))
 }
 
 pub fn get_delete_tasks_handler(
@@ -179,6 +190,54 @@ pub async fn post_delete_request(
     Ok(delete_task)
 }
 
+pub fn post_delete_doc_ids_handler(
+    metastore: MetastoreServiceClient,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = Rejection> + Clone {
+    warp::path!(String / "delete-tasks" / "doc-ids")
+        .and(warp::body::json())
+        .and(warp::post())
+        .and(with_arg(metastore))
+        .then(post_delete_doc_ids_request)
+        .and(extract_format_from_qs())
+        .map(make_json_api_response)
+}
+
+#[utoipa::path(
+    post,
+    tag = "Delete Tasks",
+    path = "/{index_id}/delete-tasks/doc-ids",
+    request_body = DeleteDocIdsRequest,
+    responses(
+        (status = 200, description = "Successfully added a new delete task.", body = DeleteTask)
+    ),
+    params(
+        ("index_id" = String, Path, description = "The index ID to add the delete task to."),
+    )
+)]
+/// Create Delete Task by Document IDs
+///
+/// Creates a delete task removing the documents whose `_id` field holds one of the given IDs. The
+/// index must map an `_id` field. Like any delete task, it is executed asynchronously.
+pub async fn post_delete_doc_ids_request(
+    index_id: String,
+    delete_doc_ids_request: DeleteDocIdsRequest,
+    mut metastore: MetastoreServiceClient,
+) -> Result<DeleteTask, JanitorError> {
+    let index_metadata_request = IndexMetadataRequest::for_index_id(index_id.to_string());
+    let metadata = metastore
+        .index_metadata(index_metadata_request)
+        .await?
+        .deserialize_index_metadata()?;
+    let index_uid: IndexUid = metadata.index_uid.clone();
+    let index_config = metadata.into_index_config();
+    let doc_mapper = build_doc_mapper(&index_config.doc_mapping, &index_config.search_settings)
+        .map_err(|error| JanitorError::Internal(error.to_string()))?;
+    let delete_query =
+        delete_query_for_doc_ids(index_uid, &*doc_mapper, delete_doc_ids_request.doc_ids)?;
+    let delete_task = metastore.create_delete_task(delete_query).await?;
+    Ok(delete_task)
+}
+
 #[cfg(test)]
 mod tests {
     use quickwit_indexing::TestSandbox;
@@ -249,4 +308,81 @@ mod tests {
         assert_eq!(delete_tasks.len(), 1);
         test_sandbox.assert_quit().await;
     }
+
+    #[tokio::test]
+    async fn test_delete_doc_ids_api() {
+        quickwit_common::setup_logging_for_tests();
+        let index_id = "test-delete-doc-ids-rest";
+        let doc_mapping_yaml = r#"
+            field_mappings:
+              - name: _id
+                type: text
+                tokenizer: raw
+              - name: body
+                type: text
+        "#;
+        let test_sandbox = TestSandbox::create(index_id, doc_mapping_yaml, "{}", &["body"])
+            .await
+            .unwrap();
+        let metastore = test_sandbox.metastore();
+        let delete_query_api_handlers =
+            super::delete_task_api_handlers(metastore).recover(recover_fn);
+        let resp = warp::test::request()
+            .path("/test-delete-doc-ids-rest/delete-tasks/doc-ids")
+            .method("POST")
+            .json(&true)
+            .body(r#"{"doc_ids": ["doc-1", "doc-3"]}"#)
+            .reply(&delete_query_api_handlers)
+            .await;
+        assert_eq!(resp.status(), 200);
+        let created_delete_task: DeleteTask = serde_json::from_slice(resp.body()).unwrap();
+        let created_delete_query = created_delete_task.delete_query.unwrap();
+        assert_eq!(
+            created_delete_query.index_uid,
+            test_sandbox.index_uid().to_string()
+        );
+        assert_eq!(
+            created_delete_query.query_ast,
+            r#"{"type":"term_set","terms_per_field":{"_id":["doc-1","doc-3"]}}"#
+        );
+        assert_eq!(created_delete_query.start_timestamp, None);
+        assert_eq!(created_delete_query.end_timestamp, None);
+
+        let resp = warp::test::request()
+            .path("/test-delete-doc-ids-rest/delete-tasks/doc-ids")
+            .method("POST")
+            .json(&true)
+            .body(r#"{"doc_ids": []}"#)
+            .reply(&delete_query_api_handlers)
+            .await;
+        assert_eq!(resp.status(), 400);
+        test_sandbox.assert_quit().await;
+    }
+
+    #[tokio::test]
+    async fn test_delete_doc_ids_api_requires_id_field() {
+        quickwit_common::setup_logging_for_tests();
+        let index_id = "test-delete-doc-ids-rest-no-id-field";
+        let doc_mapping_yaml = r#"
+            field_mappings:
+              - name: body
+                type: text
+        "#;
+        let test_sandbox = TestSandbox::create(index_id, doc_mapping_yaml, "{}", &["body"])
+            .await
+            .unwrap();
+        let metastore = test_sandbox.metastore();
+        let delete_query_api_handlers =
+            super::delete_task_api_handlers(metastore).recover(recover_fn);
+        let resp = warp::test::request()
+            .path("/test-delete-doc-ids-rest-no-id-field/delete-tasks/doc-ids")
+            .method("POST")
+            .json(&true)
+            .body(r#"{"doc_ids": ["doc-1"]}"#)
+            .reply(&delete_query_api_handlers)
+            .await;
+        assert_eq!(resp.status(), 400);
+        assert!(String::from_utf8_lossy(resp.body()).contains("`_id` field mapping"));
+        test_sandbox.assert_quit().await;
+    }
 }
@@ -17,16 +17,19 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
+use std::collections::HashMap;
 use std::time::Instant;
 
 use bytes::Bytes;
 use hyper::StatusCode;
 use quickwit_config::INGEST_V2_SOURCE_ID;
-use quickwit_ingest::IngestRequestV2Builder;
+use quickwit_ingest::{IngestRequestV2Builder, IngestServiceError};
+use quickwit_metastore::MetastoreServiceExt;
 use quickwit_proto::ingest::router::{
     IngestFailureReason, IngestRouterService, IngestRouterServiceClient,
 };
 use quickwit_proto::ingest::CommitTypeV2;
+use quickwit_proto::metastore::MetastoreServiceClient;
 use quickwit_proto::types::IndexId;
 use serde::{Deserialize, Serialize};
 use tracing::warn;
@@ -41,14 +44,46 @@ pub(crate) struct ElasticBulkResponse {
     pub errors: bool,
 }
 
+/// Routes the documents of a bulk request targeting an index alias to the write index of the
+/// alias. Each distinct index ID is resolved once per request.
+pub(crate) struct WriteIndexIdResolver {
+    metastore: MetastoreServiceClient,
+    write_index_ids: HashMap<IndexId, IndexId>,
+}
+
+impl WriteIndexIdResolver {
+    pub fn new(metastore: MetastoreServiceClient) -> Self {
+        Self {
+            metastore,
+            write_index_ids: HashMap::new(),
+        }
+    }
+
+    pub async fn resolve(&mut self, index_id: IndexId) -> Result<IndexId, ElasticsearchError> {
+        if let Some(write_index_id) = self.write_index_ids.get(&index_id) {
+            return Ok(write_index_id.clone());
+        }
+        let write_index_id = self
+            .metastore
+            .resolve_write_index_id(index_id.clone())
+            .await
+            .map_err(IngestServiceError::from)?;
+        self.write_index_ids
+            .insert(index_id, write_index_id.clone());
+        Ok(write_index_id)
+    }
+}
+
 pub(crate) async fn elastic_bulk_ingest_v2(
     default_index_id: Option<IndexId>,
     body: Bytes,
     bulk_options: ElasticBulkOptions,
     mut ingest_router: IngestRouterServiceClient,
+    metastore: MetastoreServiceClient,
 ) -> Result<ElasticBulkResponse, ElasticsearchError> {
     let now = Instant::now();
     let mut ingest_request_builder = IngestRequestV2Builder::default();
+    let mut write_index_id_resolver = WriteIndexIdResolver::new(metastore);
     let mut lines = lines(&body).enumerate();
 
     while let Some((line_no, line)) = lines.next() {
@@ -77,6 +112,7 @@ pub(crate) async fn elastic_bulk_ingest_v2(
                     format!("`_index` field of action on line #{line_no} is missing"),
                 )
             })?;
+        let index_id = write_index_id_resolver.resolve(index_id).await?;
         ingest_request_builder.add_doc(index_id, source);
     }
     let commit_type: CommitTypeV2 = bulk_options.refresh.into();
@@ -111,9 +147,16 @@ pub(crate) async fn elastic_bulk_ingest_v2(
 
 #[cfg(test)]
 mod tests {
+    use quickwit_config::{IndexAlias, IndexConfig};
+    use quickwit_metastore::{
+        metastore_for_test, CreateIndexRequestExt, UpdateIndexAliasesRequestExt,
+    };
     use quickwit_proto::ingest::router::{
         IngestFailure, IngestFailureReason, IngestResponseV2, IngestSuccess,
     };
+    use quickwit_proto::metastore::{
+        CreateIndexRequest, MetastoreService, UpdateIndexAliasesRequest,
+    };
     use quickwit_proto::types::{Position, ShardId};
     use warp::{Filter, Rejection, Reply};
 
@@ -127,11 +170,19 @@ mod tests {
 
     fn es_compat_bulk_handler_v2(
         ingest_router: IngestRouterServiceClient,
+    ) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+        es_compat_bulk_handler_v2_with_metastore(ingest_router, metastore_for_test())
+    }
+
+    fn es_compat_bulk_handler_v2_with_metastore(
+        ingest_router: IngestRouterServiceClient,
+        metastore: MetastoreServiceClient,
     ) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
         elastic_bulk_filter()
             .and(with_arg(ingest_router))
-            .then(|body, bulk_options, ingest_router| {
-                elastic_bulk_ingest_v2(None, body, bulk_options, ingest_router)
+            .and(with_arg(metastore))
+            .then(|body, bulk_options, ingest_router, metastore| {
+                elastic_bulk_ingest_v2(None, body, bulk_options, ingest_router, metastore)
             })
             .and(extract_format_from_qs())
             .map(make_elastic_api_response)
@@ -337,6 +388,70 @@ mod tests {
         assert_eq!(reason, "`_index` field of action on line #0 is missing");
     }
 
+    #[tokio::test]
+    async fn test_bulk_api_routes_index_alias_to_write_index() {
+        let mut metastore = metastore_for_test();
+
+        for index_id in ["my-index-1", "my-index-2"] {
+            let index_uri = format!("ram:///indexes/{index_id}");
+            let index_config = IndexConfig::for_test(index_id, &index_uri);
+            let create_index_request =
+                CreateIndexRequest::try_from_index_config(index_config).unwrap();
+            metastore.create_index(create_index_request).await.unwrap();
+        }
+        let index_alias = IndexAlias::for_test(
+            "my-alias",
+            &["my-index-1", "my-index-2"],
+            Some("my-index-2"),
+        );
+        let update_index_aliases_request =
+            UpdateIndexAliasesRequest::try_from_index_aliases([&index_alias], Vec::new()).unwrap();
+        metastore
+            .update_index_aliases(update_index_aliases_request)
+            .await
+            .unwrap();
+
+        let mut ingest_router_mock = IngestRouterServiceClient::mock();
+        ingest_router_mock
+            .expect_ingest()
+            .once()
+            .returning(|ingest_request| {
+                assert_eq!(ingest_request.subrequests.len(), 2);
+
+                let mut subrequests = ingest_request.subrequests;
+                subrequests.sort_by(|left, right| left.index_id.cmp(&right.index_id));
+
+                assert_eq!(subrequests[0].index_id, "my-index-1");
+                assert_eq!(subrequests[0].doc_batch.as_ref().unwrap().num_docs(), 1);
+
+                assert_eq!(subrequests[1].index_id, "my-index-2");
+                assert_eq!(subrequests[1].doc_batch.as_ref().unwrap().num_docs(), 2);
+
+                Ok(IngestResponseV2::default())
+            });
+        let ingest_router = IngestRouterServiceClient::from(ingest_router_mock);
+        let handler = es_compat_bulk_handler_v2_with_metastore(ingest_router, metastore);
+
+        let payload = r#"
+            {"create": {"_index": "my-alias", "_id" : "1"}}
+            {"ts": 1, "message": "my-message-1"}
+            {"create": {"_index": "my-index-1", "_id" : "1"}}
+            {"ts": 1, "message": "my-message-1"}
+            {"create": {"_index": "my-alias"}}
+            {"ts": 2, "message": "my-message-2"}
+        "#;
+        let response = warp::test::request()
+            .path("/_elastic/_bulk")
+            .method("POST")
+            .body(payload)
+            .reply(&handler)
+            .await;
+        assert_eq!(response.status(), 200);
+
+        let bulk_response: ElasticBulkResponse = serde_json::from_slice(response.body()).unwrap();
+        assert!(!bulk_response.errors);
+    }
+
     // Airmail-specific test. It should go away when we straighten out the API response.
     #[tokio::test]
     async fn test_bulk_api_returns_404_on_index_not_found() {
@@ -31,12 +31,14 @@ use hyper::StatusCode;
 use quickwit_config::NodeConfig;
 use quickwit_ingest::IngestServiceClient;
 use quickwit_proto::ingest::router::IngestRouterServiceClient;
+use quickwit_proto::metastore::MetastoreServiceClient;
+use quickwit_proto::search::CountHits;
 use quickwit_search::SearchService;
 use rest_handler::{
     es_compat_cluster_info_handler, es_compat_index_multi_search_handler,
     es_compat_index_search_handler, es_compat_scroll_handler, es_compat_search_handler,
 };
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize};
 use warp::{Filter, Rejection};
 
 use self::rest_handler::{
@@ -55,6 +57,7 @@ pub fn elastic_api_handlers(
     search_service: Arc<dyn SearchService>,
     ingest_service: IngestServiceClient,
     ingest_router: IngestRouterServiceClient,
+    metastore: MetastoreServiceClient,
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = Rejection> + Clone {
     es_compat_cluster_info_handler(node_config, BuildInfo::get())
         .or(es_compat_search_handler(search_service.clone()))
@@ -68,8 +71,13 @@ pub fn elastic_api_handlers(
         .or(es_compat_bulk_handler(
             ingest_service.clone(),
             ingest_router.clone(),
+            metastore.clone(),
+        ))
+        .or(es_compat_index_bulk_handler(
+            ingest_service,
+            ingest_router,
+            metastore,
         ))
-        .or(es_compat_index_bulk_handler(ingest_service, ingest_router))
     // Register newly created handlers here.
 }
 
@@ -81,7 +89,7 @@ pub fn elastic_api_handlers(
 ///
 /// When set to `Count` with an integer value `n`, the response accurately tracks the total
 /// hit count that match the query up to `n` documents.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
 #[serde(untagged)]
 pub enum TrackTotalHits {
     /// Track the number of hits that match the query accurately.
@@ -90,6 +98,74 @@ pub enum TrackTotalHits {
     Count(i64),
 }
 
+impl TrackTotalHits {
+    /// Returns how to count the hits of a search request returning `max_hits` hits, and the bound
+    /// up to which hits must be counted exactly, if any.
+    pub(crate) fn count_hits(&self, max_hits: u64) -> (CountHits, Option<u32>) {
+        match *self {
+            TrackTotalHits::Track(false) => (CountHits::Underestimate, None),
+            TrackTotalHits::Track(true) => (CountHits::CountAll, None),
+            TrackTotalHits::Count(count) => {
+                let track_total_hits_up_to = count.clamp(0, u32::MAX as i64) as u32;
+                // Leaves only skip splits once they have collected at least `max_hits` hits, so
+                // underestimating is enough to count exactly up to a bound lower than `max_hits`.
+                let count_hits = if track_total_hits_up_to as u64 <= max_hits {
+                    CountHits::Underestimate
+                } else {
+                    CountHits::CountAll
+                };
+                (count_hits, Some(track_total_hits_up_to))
+            }
+        }
+    }
+}
+
+// `TrackTotalHits` can be passed as a query string parameter, where booleans and integers are
+// represented as strings, so it cannot simply derive an untagged `Deserialize` implementation.
+impl<'de> Deserialize<'de> for TrackTotalHits {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where D: Deserializer<'de> {
+        struct TrackTotalHitsVisitor;
+
+        impl<'de> de::Visitor<'de> for TrackTotalHitsVisitor {
+            type Value = TrackTotalHits;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a boolean or an integer")
+            }
+
+            fn visit_bool<E>(self, value: bool) -> Result<Self::Value, E>
+            where E: de::Error {
+                Ok(TrackTotalHits::Track(value))
+            }
+
+            fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
+            where E: de::Error {
+                Ok(TrackTotalHits::Count(value))
+            }
+
+            fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+            where E: de::Error {
+                let count = i64::try_from(value)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Unsigned(value), &self))?;
+                Ok(TrackTotalHits::Count(count))
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+            where E: de::Error {
+                if let Ok(track) = value.parse::<bool>() {
+                    return Ok(TrackTotalHits::Track(track));
+                }
+                if let Ok(count) = value.parse::<i64>() {
+                    return Ok(TrackTotalHits::Count(count));
+                }
+                Err(E::invalid_value(de::Unexpected::Str(value), &self))
+            }
+        }
+        deserializer.deserialize_any(TrackTotalHitsVisitor)
+    }
+}
+
 impl From<bool> for TrackTotalHits {
     fn from(b: bool) -> Self {
         TrackTotalHits::Track(b)
@@ -122,18 +198,60 @@ mod tests {
     use mockall::predicate;
     use quickwit_config::NodeConfig;
     use quickwit_ingest::{IngestApiService, IngestServiceClient};
+    use quickwit_metastore::metastore_for_test;
     use quickwit_proto::ingest::router::IngestRouterServiceClient;
+    use quickwit_proto::search::CountHits;
     use quickwit_search::MockSearchService;
     use serde_json::Value as JsonValue;
     use warp::Filter;
 
-    use super::elastic_api_handlers;
     use super::model::ElasticsearchError;
+    use super::{elastic_api_handlers, TrackTotalHits};
     use crate::elasticsearch_api::model::MultiSearchResponse;
     use crate::elasticsearch_api::rest_handler::es_compat_cluster_info_handler;
     use crate::rest::recover_fn;
     use crate::BuildInfo;
 
+    #[test]
+    fn test_track_total_hits_deserialization() {
+        let track_total_hits: TrackTotalHits = serde_json::from_str("true").unwrap();
+        assert_eq!(track_total_hits, TrackTotalHits::Track(true));
+        let track_total_hits: TrackTotalHits = serde_json::from_str("100").unwrap();
+        assert_eq!(track_total_hits, TrackTotalHits::Count(100));
+        let track_total_hits: TrackTotalHits = serde_json::from_str("-1").unwrap();
+        assert_eq!(track_total_hits, TrackTotalHits::Count(-1));
+        // Query string parameters are deserialized from strings.
+        let track_total_hits: TrackTotalHits = serde_json::from_str(r#""false""#).unwrap();
+        assert_eq!(track_total_hits, TrackTotalHits::Track(false));
+        let track_total_hits: TrackTotalHits = serde_json::from_str(r#""100""#).unwrap();
+        assert_eq!(track_total_hits, TrackTotalHits::Count(100));
+        serde_json::from_str::<TrackTotalHits>(r#""foo""#).unwrap_err();
+    }
+
+    #[test]
+    fn test_track_total_hits_count_hits() {
+        assert_eq!(
+            TrackTotalHits::Track(true).count_hits(10),
+            (CountHits::CountAll, None)
+        );
+        assert_eq!(
+            TrackTotalHits::Track(false).count_hits(10),
+            (CountHits::Underestimate, None)
+        );
+        assert_eq!(
+            TrackTotalHits::Count(5).count_hits(10),
+            (CountHits::Underestimate, Some(5))
+        );
+        assert_eq!(
+            TrackTotalHits::Count(100).count_hits(10),
+            (CountHits::CountAll, Some(100))
+        );
+        assert_eq!(
+            TrackTotalHits::Count(-1).count_hits(10),
+            (CountHits::Underestimate, Some(0))
+        );
+    }
+
     fn ingest_service_client() -> IngestServiceClient {
         let universe = quickwit_actors::Universe::new();
         let (ingest_service_mailbox, _) = universe.create_test_mailbox::<IngestApiService>();
@@ -163,6 +281,7 @@ mod tests {
             Arc::new(mock_search_service),
             ingest_service_client(),
             ingest_router,
+            metastore_for_test(),
         );
         let msearch_payload = r#"
             {"index":"index-1"}
@@ -213,6 +332,7 @@ mod tests {
             Arc::new(mock_search_service),
             ingest_service_client(),
             ingest_router,
+            metastore_for_test(),
         );
         let msearch_payload = r#"
             {"index":"index-1"}
@@ -251,6 +371,7 @@ mod tests {
             Arc::new(mock_search_service),
             ingest_service_client(),
             ingest_router,
+            metastore_for_test(),
         );
         let msearch_payload = r#"
             {"index":"index-1"
@@ -282,6 +403,7 @@ mod tests {
             Arc::new(mock_search_service),
             ingest_service_client(),
             ingest_router,
+            metastore_for_test(),
         );
         let msearch_payload = r#"
             {"index":"index-1"}
@@ -313,6 +435,7 @@ mod tests {
             Arc::new(mock_search_service),
             ingest_service_client(),
             ingest_router,
+            metastore_for_test(),
         );
         let msearch_payload = r#"
             {"index":"index-1"}
@@ -343,6 +466,7 @@ mod tests {
             Arc::new(mock_search_service),
             ingest_service_client(),
             ingest_router,
+            metastore_for_test(),
         );
         let msearch_payload = r#"
             {}
@@ -385,6 +509,7 @@ mod tests {
             Arc::new(mock_search_service),
             ingest_service_client(),
             ingest_router,
+            metastore_for_test(),
         );
         let msearch_payload = r#"
             {"index": ["index-1", "index-2"]}
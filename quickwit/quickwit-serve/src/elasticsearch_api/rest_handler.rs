@@ -31,8 +31,8 @@ use itertools::Itertools;
 use quickwit_common::truncate_str;
 use quickwit_config::{validate_index_id_pattern, NodeConfig};
 use quickwit_proto::search::{
-    CountHits, ListFieldsResponse, PartialHit, ScrollRequest, SearchResponse, SortByValue,
-    SortDatetimeFormat,
+    CountHits, HitsRelation, ListFieldsResponse, PartialHit, ScrollRequest, SearchResponse,
+    SortByValue, SortDatetimeFormat,
 };
 use quickwit_proto::ServiceErrorCode;
 use quickwit_query::query_ast::{QueryAst, UserInputQuery};
@@ -47,6 +47,7 @@ use super::filter::{
     elastic_index_field_capabilities_filter, elastic_index_search_filter,
     elastic_multi_search_filter, elastic_scroll_filter, elasticsearch_filter,
 };
+use super::make_elastic_api_response;
 use super::model::{
     build_list_field_request_for_es_api, convert_to_es_field_capabilities_response,
     ElasticsearchError, FieldCapabilityQueryParams, FieldCapabilityRequestBody,
@@ -54,7 +55,6 @@ use super::model::{
     MultiSearchSingleResponse, ScrollQueryParams, SearchBody, SearchQueryParams,
     SearchQueryParamsCount,
 };
-use super::{make_elastic_api_response, TrackTotalHits};
 use crate::format::BodyFormat;
 use crate::json_api_response::{make_json_api_response, ApiError, JsonApiResponse};
 use crate::{with_arg, BuildInfo};
@@ -187,13 +187,13 @@ fn build_request_for_es_api(
 
     let max_hits = search_params.size.or(search_body.size).unwrap_or(10);
     let start_offset = search_params.from.or(search_body.from).unwrap_or(0);
-    let count_hits = match search_params.track_total_hits {
-        None => CountHits::Underestimate,
-        Some(TrackTotalHits::Track(false)) => CountHits::Underestimate,
-        Some(TrackTotalHits::Count(count)) if count <= max_hits as i64 => CountHits::Underestimate,
-        Some(TrackTotalHits::Track(true) | TrackTotalHits::Count(_)) => CountHits::CountAll,
-    }
-    .into();
+    let (count_hits, track_total_hits_up_to) = match search_params
+        .track_total_hits
+        .or(search_body.track_total_hits)
+    {
+        None => (CountHits::Underestimate, None),
+        Some(track_total_hits) => track_total_hits.count_hits(max_hits),
+    };
 
     let sort_fields: Vec<quickwit_proto::search::SortField> = search_params
         .sort_fields()?
@@ -235,7 +235,15 @@ fn build_request_for_es_api(
             snippet_fields: Vec::new(),
             scroll_ttl_secs,
             search_after,
-            count_hits,
+            count_hits: count_hits.into(),
+            timeout_millis: None,
+            track_total_hits_up_to,
+            allow_partial_results: search_params.allow_partial_search_results.unwrap_or(true),
+            split_ids: Vec::new(),
+            // Elasticsearch returns the version of the documents with `version`. The closest
+            // Quickwit equivalent is the publish timestamp of their split.
+            include_split_publish_timestamp: search_params.version.unwrap_or(false),
+            allow_high_cardinality_aggregations: false,
         },
         has_doc_id_field,
     ))
@@ -353,8 +361,14 @@ async fn es_compat_index_field_capabilities(
 }
 
 fn convert_hit(hit: quickwit_proto::search::Hit, append_shard_doc: bool) -> ElasticHit {
-    let fields: BTreeMap<String, serde_json::Value> =
+    let mut fields: BTreeMap<String, serde_json::Value> =
         serde_json::from_str(&hit.json).unwrap_or_default();
+    if let Some(split_publish_timestamp) = hit.split_publish_timestamp {
+        fields.insert(
+            "_split_publish_timestamp".to_string(),
+            serde_json::Value::from(vec![split_publish_timestamp]),
+        );
+    }
     let mut sort = Vec::new();
     if let Some(partial_hit) = hit.partial_hit {
         if let Some(sort_value) = partial_hit.sort_value {
@@ -512,12 +526,16 @@ fn convert_to_es_search_response(
     } else {
         None
     };
+    let relation = match resp.num_hits_relation() {
+        HitsRelation::Equal => TotalHitsRelation::Equal,
+        HitsRelation::GreaterThanOrEqual => TotalHitsRelation::GreaterThanOrEqualTo,
+    };
     ElasticsearchResponse {
         timed_out: false,
         hits: HitsMetadata {
             total: Some(TotalHits {
                 value: resp.num_hits,
-                relation: TotalHitsRelation::Equal,
+                relation,
             }),
             max_score: None,
             hits,
@@ -538,7 +556,7 @@ pub(crate) fn str_lines(body: &str) -> impl Iterator<Item = &str> {
 mod tests {
     use hyper::StatusCode;
 
-    use super::partial_hit_from_search_after_param;
+    use super::{convert_hit, partial_hit_from_search_after_param};
 
     #[test]
     fn test_partial_hit_from_search_after_param_invalid_length() {
@@ -584,4 +602,25 @@ mod tests {
              u32}`"
         );
     }
+
+    #[test]
+    fn test_convert_hit_with_split_publish_timestamp() {
+        let hit = quickwit_proto::search::Hit {
+            json: r#"{"title": "foo"}"#.to_string(),
+            split_publish_timestamp: Some(1_700_000_003),
+            ..Default::default()
+        };
+        let elastic_hit = convert_hit(hit, false);
+        assert_eq!(
+            elastic_hit.fields.get("_split_publish_timestamp").unwrap(),
+            &serde_json::json!([1_700_000_003])
+        );
+
+        let hit = quickwit_proto::search::Hit {
+            json: r#"{"title": "foo"}"#.to_string(),
+            ..Default::default()
+        };
+        let elastic_hit = convert_hit(hit, false);
+        assert!(!elastic_hit.fields.contains_key("_split_publish_timestamp"));
+    }
 }
@@ -31,8 +31,11 @@ use quickwit_proto::opentelemetry::proto::collector::trace::v1::trace_service_se
 use quickwit_proto::search::search_service_server::SearchServiceServer;
 use quickwit_proto::tonic::codegen::CompressionEncoding;
 use quickwit_proto::tonic::transport::Server;
+use quickwit_proto::QUICKWIT_FILE_DESCRIPTOR_SETS;
+use tonic_reflection::server::{ServerReflection, ServerReflectionServer};
 use tracing::*;
 
+use crate::admin_runtime::AdminRuntimeGrpcService;
 use crate::search_api::GrpcSearchAdapter;
 use crate::QuickwitServices;
 
@@ -104,17 +107,19 @@ pub(crate) async fn start_grpc_server(
     } else {
         None
     };
-    // Mount gRPC control plane service if `QuickwitService::ControlPlane` is enabled on node.
+    // Mount gRPC control plane service if `QuickwitService::ControlPlane` is enabled on node. The
+    // control plane requests, such as index creations and deletions, are handled on the admin
+    // runtime.
     let control_plane_grpc_service = if services
         .node_config
         .is_service_enabled(QuickwitService::ControlPlane)
     {
         enabled_grpc_services.insert("control-plane");
-        Some(
+        Some(AdminRuntimeGrpcService::new(
             services
                 .control_plane_service
                 .as_grpc_service(max_message_size),
-        )
+        ))
     } else {
         None
     };
@@ -123,7 +128,9 @@ pub(crate) async fn start_grpc_server(
         if let Some(otlp_traces_service) = services.otlp_traces_service_opt.clone() {
             enabled_grpc_services.insert("otlp-trace");
             let trace_service = TraceServiceServer::new(otlp_traces_service)
-                .accept_compressed(CompressionEncoding::Gzip);
+                .accept_compressed(CompressionEncoding::Gzip)
+                .max_decoding_message_size(max_message_size.0 as usize)
+                .max_encoding_message_size(max_message_size.0 as usize);
             Some(trace_service)
         } else {
             None
@@ -132,7 +139,9 @@ pub(crate) async fn start_grpc_server(
         if let Some(otlp_logs_service) = services.otlp_logs_service_opt.clone() {
             enabled_grpc_services.insert("otlp-log");
             let logs_service = LogsServiceServer::new(otlp_logs_service)
-                .accept_compressed(CompressionEncoding::Gzip);
+                .accept_compressed(CompressionEncoding::Gzip)
+                .max_decoding_message_size(max_message_size.0 as usize)
+                .max_encoding_message_size(max_message_size.0 as usize);
             Some(logs_service)
         } else {
             None
@@ -157,7 +166,18 @@ pub(crate) async fn start_grpc_server(
     // Mount gRPC jaeger service if present.
     let jaeger_grpc_service = if let Some(jaeger_service) = services.jaeger_service_opt.clone() {
         enabled_grpc_services.insert("jaeger");
-        Some(SpanReaderPluginServer::new(jaeger_service))
+        Some(
+            SpanReaderPluginServer::new(jaeger_service)
+                .max_decoding_message_size(max_message_size.0 as usize)
+                .max_encoding_message_size(max_message_size.0 as usize),
+        )
+    } else {
+        None
+    };
+    // Mount gRPC reflection service if enabled in the node config.
+    let reflection_grpc_service = if services.node_config.grpc_config.enable_reflection {
+        enabled_grpc_services.insert("reflection");
+        Some(reflection_service()?)
     } else {
         None
     };
@@ -171,6 +191,7 @@ pub(crate) async fn start_grpc_server(
         .add_optional_service(metastore_grpc_service)
         .add_optional_service(otlp_log_grpc_service)
         .add_optional_service(otlp_trace_grpc_service)
+        .add_optional_service(reflection_grpc_service)
         .add_optional_service(search_grpc_service);
 
     info!(
@@ -183,3 +204,83 @@ pub(crate) async fn start_grpc_server(
     serve_res?;
     Ok(())
 }
+
+/// Builds the gRPC reflection service, which exposes the descriptors of the control plane,
+/// ingest, metastore, and search services.
+fn reflection_service() -> anyhow::Result<ServerReflectionServer<impl ServerReflection>> {
+    let reflection_service = QUICKWIT_FILE_DESCRIPTOR_SETS
+        .into_iter()
+        .fold(
+            tonic_reflection::server::Builder::configure(),
+            |builder, file_descriptor_set| {
+                builder.register_encoded_file_descriptor_set(file_descriptor_set)
+            },
+        )
+        .build()?;
+    Ok(reflection_service)
+}
+
+#[cfg(test)]
+mod tests {
+    use quickwit_common::net::find_available_tcp_port;
+    use tonic_reflection::pb::server_reflection_client::ServerReflectionClient;
+    use tonic_reflection::pb::server_reflection_request::MessageRequest;
+    use tonic_reflection::pb::server_reflection_response::MessageResponse;
+    use tonic_reflection::pb::ServerReflectionRequest;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_reflection_service_lists_services() {
+        let grpc_port = find_available_tcp_port().unwrap();
+        let grpc_addr: SocketAddr = ([127, 0, 0, 1], grpc_port).into();
+        let reflection_service = reflection_service().unwrap();
+        tokio::spawn(async move {
+            Server::builder()
+                .add_service(reflection_service)
+                .serve(grpc_addr)
+                .await
+                .unwrap();
+        });
+        while tokio::net::TcpStream::connect(grpc_addr).await.is_err() {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        let mut reflection_client = ServerReflectionClient::connect(format!("http://{grpc_addr}"))
+            .await
+            .unwrap();
+        let list_services_request = ServerReflectionRequest {
+            host: String::new(),
+            message_request: Some(MessageRequest::ListServices(String::new())),
+        };
+        let mut response_stream = reflection_client
+            .server_reflection_info(tokio_stream::iter([list_services_request]))
+            .await
+            .unwrap()
+            .into_inner();
+        let response = response_stream.message().await.unwrap().unwrap();
+
+        let Some(MessageResponse::ListServicesResponse(list_services_response)) =
+            response.message_response
+        else {
+            panic!("expected a list services response");
+        };
+        let service_names: BTreeSet<String> = list_services_response
+            .service
+            .into_iter()
+            .map(|service| service.name)
+            .collect();
+
+        for expected_service_name in [
+            "quickwit.control_plane.ControlPlaneService",
+            "quickwit.ingest.ingester.IngesterService",
+            "quickwit.ingest.router.IngestRouterService",
+            "quickwit.metastore.MetastoreService",
+            "quickwit.search.SearchService",
+        ] {
+            assert!(
+                service_names.contains(expected_service_name),
+                "service `{expected_service_name}` is missing from {service_names:?}"
+            );
+        }
+    }
+}
@@ -26,6 +26,7 @@ use warp::hyper::StatusCode;
 use warp::reply::with_status;
 use warp::{Filter, Rejection};
 
+use crate::admin_runtime::run_on_admin_runtime;
 use crate::with_arg;
 
 #[derive(utoipa::OpenApi)]
@@ -49,7 +50,9 @@ fn liveness_handler(
         .and(warp::get())
         .and(with_arg(indexer_service_opt))
         .and(with_arg(janitor_service_opt))
-        .then(get_liveness)
+        .then(|indexer_service_opt, janitor_service_opt| {
+            run_on_admin_runtime(get_liveness(indexer_service_opt, janitor_service_opt))
+        })
 }
 
 fn readiness_handler(
@@ -58,7 +61,7 @@ fn readiness_handler(
     warp::path!("health" / "readyz")
         .and(warp::get())
         .and(with_arg(cluster))
-        .then(get_readiness)
+        .then(|cluster| run_on_admin_runtime(get_readiness(cluster)))
 }
 
 #[utoipa::path(
@@ -119,6 +122,8 @@ async fn get_readiness(cluster: Cluster) -> impl warp::Reply {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::mpsc;
+    use std::time::{Duration, Instant};
 
     use quickwit_cluster::{create_cluster_for_test, ChannelTransport};
 
@@ -146,4 +151,34 @@ mod tests {
             .await;
         assert_eq!(resp.status(), 200);
     }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_rest_health_checks_respond_with_saturated_search() {
+        let transport = ChannelTransport::default();
+        let cluster = create_cluster_for_test(Vec::new(), &[], &transport, true)
+            .await
+            .unwrap();
+        let health_check_handler = super::health_check_handlers(cluster, None, None);
+
+        // Simulates a search load saturating the only worker thread of the test runtime.
+        let (started_tx, started_rx) = mpsc::channel();
+        tokio::spawn(async move {
+            started_tx.send(()).unwrap();
+            std::thread::sleep(Duration::from_secs(1));
+        });
+        started_rx.recv().unwrap();
+
+        let now = Instant::now();
+        let resp = warp::test::request()
+            .path("/health/livez")
+            .reply(&health_check_handler)
+            .await;
+        assert_eq!(resp.status(), 200);
+        let resp = warp::test::request()
+            .path("/health/readyz")
+            .reply(&health_check_handler)
+            .await;
+        assert_eq!(resp.status(), 200);
+        assert!(now.elapsed() < Duration::from_millis(500));
+    }
 }
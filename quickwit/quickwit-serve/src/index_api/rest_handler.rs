@@ -17,17 +17,21 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
+use std::str::FromStr;
 use std::sync::Arc;
 
 use bytes::Bytes;
+use bytesize::ByteSize;
 use hyper::header::CONTENT_TYPE;
 use quickwit_common::uri::Uri;
 use quickwit_config::{
-    load_source_config_from_user_config, ConfigFormat, NodeConfig, SourceConfig, SourceParams,
-    CLI_INGEST_SOURCE_ID, INGEST_API_SOURCE_ID,
+    build_doc_mapper, load_source_config_from_user_config, ConfigFormat, NodeConfig, SourceConfig,
+    SourceParams, CLI_INGEST_SOURCE_ID, INGEST_API_SOURCE_ID,
+};
+use quickwit_doc_mapper::{analyze_text, schema_fields, SchemaField, TokenizerConfig};
+use quickwit_index_management::{
+    IndexService, IndexServiceError, RolloverConditions, RolloverReport, SplitsConsistencyReport,
 };
-use quickwit_doc_mapper::{analyze_text, TokenizerConfig};
-use quickwit_index_management::{IndexService, IndexServiceError};
 use quickwit_metastore::{
     IndexMetadata, IndexMetadataResponseExt, ListIndexesMetadataResponseExt, ListSplitsQuery,
     ListSplitsRequestExt, MetastoreServiceStreamSplitsExt, Split, SplitInfo, SplitState,
@@ -44,6 +48,7 @@ use thiserror::Error;
 use tracing::info;
 use warp::{Filter, Rejection};
 
+use crate::admin_runtime::run_on_admin_runtime;
 use crate::format::extract_format_from_qs;
 use crate::json_api_response::make_json_api_response;
 use crate::simple_list::{from_simple_list, to_simple_list};
@@ -54,17 +59,30 @@ use crate::with_arg;
     paths(
         create_index,
         clear_index,
+        check_index,
+        update_index_uri,
+        rename_index,
         delete_index,
+        rollover_index_alias,
         get_indexes_metadatas,
         list_splits,
         describe_index,
+        get_index_schema,
         mark_splits_for_deletion,
         create_source,
         reset_source_checkpoint,
         toggle_source,
         delete_source,
     ),
-    components(schemas(ToggleSource, SplitsForDeletion, IndexStats))
+    components(schemas(
+        ToggleSource,
+        SplitsForDeletion,
+        IndexStats,
+        UpdateIndexUri,
+        RenameIndex,
+        RolloverIndexAlias,
+        RolloverIndexAliasConditions
+    ))
 )]
 pub struct IndexApi;
 
@@ -77,10 +95,16 @@ pub fn index_management_handlers(
         .or(get_indexes_metadatas_handler(index_service.metastore()))
         .or(create_index_handler(index_service.clone(), node_config))
         .or(clear_index_handler(index_service.clone()))
+        .or(check_index_handler(index_service.clone()))
+        .or(update_index_uri_handler(index_service.clone()))
+        .or(rename_index_handler(index_service.clone()))
         .or(delete_index_handler(index_service.clone()))
+        // Index aliases handlers.
+        .or(rollover_index_alias_handler(index_service.clone()))
         // Splits handlers
         .or(list_splits_handler(index_service.metastore()))
         .or(describe_index_handler(index_service.metastore()))
+        .or(get_index_schema_handler(index_service.metastore()))
         .or(mark_splits_for_deletion_handler(index_service.metastore()))
         // Sources handlers.
         .or(reset_source_checkpoint_handler(index_service.metastore()))
@@ -255,6 +279,45 @@ fn describe_index_handler(
         .map(make_json_api_response)
 }
 
+#[utoipa::path(
+    get,
+    tag = "Indexes",
+    path = "/indexes/{index_id}/schema",
+    responses(
+        (status = 200, description = "Successfully fetched the schema of the index.", body = [SchemaField])
+    ),
+    params(
+        ("index_id" = String, Path, description = "The index ID to get the schema of."),
+    )
+)]
+
+/// Returns the fields of the index tantivy schema, as resolved by its doc mapper.
+async fn get_index_schema(
+    index_id: String,
+    mut metastore: MetastoreServiceClient,
+) -> Result<Vec<SchemaField>, IndexServiceError> {
+    let index_metadata_request = IndexMetadataRequest::for_index_id(index_id);
+    let index_config = metastore
+        .index_metadata(index_metadata_request)
+        .await?
+        .deserialize_index_metadata()?
+        .into_index_config();
+    let doc_mapper = build_doc_mapper(&index_config.doc_mapping, &index_config.search_settings)
+        .map_err(IndexServiceError::InvalidConfig)?;
+    Ok(schema_fields(&doc_mapper.schema()))
+}
+
+fn get_index_schema_handler(
+    metastore: MetastoreServiceClient,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = Rejection> + Clone {
+    warp::path!("indexes" / String / "schema")
+        .and(warp::get())
+        .and(with_arg(metastore))
+        .then(get_index_schema)
+        .and(extract_format_from_qs())
+        .map(make_json_api_response)
+}
+
 /// This struct represents the QueryString passed to
 /// the rest API to filter splits.
 #[derive(Debug, Clone, Deserialize, Serialize, utoipa::IntoParams, utoipa::ToSchema, Default)]
@@ -466,7 +529,21 @@ fn create_index_handler(
         .and(warp::filters::body::bytes())
         .and(with_arg(index_service))
         .and(with_arg(node_config))
-        .then(create_index)
+        .then(
+            |create_index_query_params,
+             config_format,
+             index_config_bytes,
+             index_service,
+             node_config| {
+                run_on_admin_runtime(create_index(
+                    create_index_query_params,
+                    config_format,
+                    index_config_bytes,
+                    index_service,
+                    node_config,
+                ))
+            },
+        )
         .and(extract_format_from_qs())
         .map(make_json_api_response)
 }
@@ -536,6 +613,217 @@ async fn clear_index(
     index_service.clear_index(&index_id).await
 }
 
+fn check_index_handler(
+    index_service: IndexService,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = Rejection> + Clone {
+    warp::path!("indexes" / String / "check")
+        .and(warp::get())
+        .and(with_arg(index_service))
+        .then(check_index)
+        .and(extract_format_from_qs())
+        .map(make_json_api_response)
+}
+
+#[utoipa::path(
+    get,
+    tag = "Indexes",
+    path = "/indexes/{index_id}/check",
+    responses(
+        (status = 200, description = "Successfully checked the consistency of the index splits.")
+    ),
+    params(
+        ("index_id" = String, Path, description = "The index ID to check."),
+    )
+)]
+/// Checks that the splits of an index recorded in the metastore and the split files present in
+/// the index storage are consistent.
+async fn check_index(
+    index_id: String,
+    mut index_service: IndexService,
+) -> Result<SplitsConsistencyReport, IndexServiceError> {
+    info!(index_id = %index_id, "check-index");
+    index_service.check_index(&index_id).await
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
+#[serde(deny_unknown_fields)]
+struct UpdateIndexUri {
+    /// The new URI of the index.
+    index_uri: String,
+    /// Copies the split files to the new URI before updating the metastore.
+    #[serde(default)]
+    move_data: bool,
+}
+
+fn update_index_uri_handler(
+    index_service: IndexService,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = Rejection> + Clone {
+    warp::path!("indexes" / String / "index-uri")
+        .and(warp::put())
+        .and(json_body())
+        .and(with_arg(index_service))
+        .then(update_index_uri)
+        .and(extract_format_from_qs())
+        .map(make_json_api_response)
+}
+
+#[utoipa::path(
+    put,
+    tag = "Indexes",
+    path = "/indexes/{index_id}/index-uri",
+    request_body = UpdateIndexUri,
+    responses(
+        // We return `VersionedIndexMetadata` as it's the serialized model view.
+        (status = 200, description = "Successfully updated the index URI.", body = VersionedIndexMetadata)
+    ),
+    params(
+        ("index_id" = String, Path, description = "The index ID whose URI is updated."),
+    )
+)]
+/// Updates the URI of an index, optionally copying its split files to the new location.
+async fn update_index_uri(
+    index_id: String,
+    update_index_uri: UpdateIndexUri,
+    mut index_service: IndexService,
+) -> Result<IndexMetadata, IndexServiceError> {
+    info!(index_id = %index_id, index_uri = %update_index_uri.index_uri, move_data = update_index_uri.move_data, "update-index-uri");
+    let index_uri = Uri::from_str(&update_index_uri.index_uri).map_err(|error| {
+        IndexServiceError::InvalidConfig(error.context("failed to parse index URI"))
+    })?;
+    index_service
+        .update_index_uri(&index_id, index_uri, update_index_uri.move_data)
+        .await
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
+#[serde(deny_unknown_fields)]
+struct RenameIndex {
+    /// The new ID of the index.
+    new_index_id: String,
+}
+
+fn rename_index_handler(
+    index_service: IndexService,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = Rejection> + Clone {
+    warp::path!("indexes" / String / "rename")
+        .and(warp::put())
+        .and(json_body())
+        .and(with_arg(index_service))
+        .then(rename_index)
+        .and(extract_format_from_qs())
+        .map(make_json_api_response)
+}
+
+#[utoipa::path(
+    put,
+    tag = "Indexes",
+    path = "/indexes/{index_id}/rename",
+    request_body = RenameIndex,
+    responses(
+        // We return `VersionedIndexMetadata` as it's the serialized model view.
+        (status = 200, description = "Successfully renamed the index.", body = VersionedIndexMetadata)
+    ),
+    params(
+        ("index_id" = String, Path, description = "The index ID to rename."),
+    )
+)]
+/// Renames an index. Its sources and splits are preserved.
+async fn rename_index(
+    index_id: String,
+    rename_index: RenameIndex,
+    mut index_service: IndexService,
+) -> Result<IndexMetadata, IndexServiceError> {
+    info!(index_id = %index_id, new_index_id = %rename_index.new_index_id, "rename-index");
+    index_service
+        .rename_index(&index_id, &rename_index.new_index_id)
+        .await
+}
+
+#[derive(Default, Deserialize, utoipa::ToSchema)]
+#[serde(deny_unknown_fields)]
+struct RolloverIndexAliasConditions {
+    /// Rolls over once the write index holds this many published documents.
+    #[serde(default)]
+    max_docs: Option<u64>,
+    /// Rolls over once the write index is older than this human-readable duration, e.g. `7d`.
+    #[serde(default)]
+    max_age: Option<String>,
+    /// Rolls over once the published splits of the write index reach this size, e.g. `50GB`.
+    #[serde(default)]
+    #[schema(value_type = String)]
+    max_size: Option<ByteSize>,
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
+#[serde(deny_unknown_fields)]
+struct RolloverIndexAlias {
+    /// The ID of the new write index. Defaults to the ID of the current write index with its
+    /// numeric suffix incremented.
+    #[serde(default)]
+    new_index_id: Option<String>,
+    /// The rollover occurs as soon as one of the conditions is met, or unconditionally if none is
+    /// set.
+    #[serde(default)]
+    conditions: RolloverIndexAliasConditions,
+}
+
+fn rollover_index_alias_handler(
+    index_service: IndexService,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = Rejection> + Clone {
+    warp::path!("aliases" / String / "rollover")
+        .and(warp::post())
+        .and(json_body())
+        .and(with_arg(index_service))
+        .then(rollover_index_alias)
+        .and(extract_format_from_qs())
+        .map(make_json_api_response)
+}
+
+#[utoipa::path(
+    post,
+    tag = "Indexes",
+    path = "/aliases/{alias_id}/rollover",
+    request_body = RolloverIndexAlias,
+    responses(
+        (status = 200, description = "Successfully checked the rollover conditions and rolled over the alias if they were met.")
+    ),
+    params(
+        ("alias_id" = String, Path, description = "The ID of the index alias to roll over."),
+    )
+)]
+/// Creates a new write index for an index alias if its current write index meets one of the
+/// rollover conditions. The former write index remains searchable through the alias.
+async fn rollover_index_alias(
+    alias_id: String,
+    rollover_index_alias: RolloverIndexAlias,
+    mut index_service: IndexService,
+) -> Result<RolloverReport, IndexServiceError> {
+    info!(alias_id = %alias_id, "rollover-index-alias");
+    let conditions = rollover_index_alias.conditions;
+    let max_age = conditions
+        .max_age
+        .map(|max_age| {
+            humantime::parse_duration(&max_age).map_err(|error| {
+                IndexServiceError::InvalidConfig(anyhow::anyhow!(
+                    "failed to parse human-readable duration `{max_age}`: {error}"
+                ))
+            })
+        })
+        .transpose()?;
+    let rollover_conditions = RolloverConditions {
+        max_docs: conditions.max_docs,
+        max_age,
+        max_size_bytes: conditions.max_size.map(|max_size| max_size.as_u64()),
+    };
+    index_service
+        .rollover_index_alias(
+            &alias_id,
+            rollover_index_alias.new_index_id,
+            &rollover_conditions,
+        )
+        .await
+}
+
 #[derive(Deserialize, utoipa::IntoParams, utoipa::ToSchema)]
 #[into_params(parameter_in = Query)]
 struct DeleteIndexQueryParam {
@@ -550,7 +838,13 @@ fn delete_index_handler(
         .and(warp::delete())
         .and(serde_qs::warp::query(serde_qs::Config::default()))
         .and(with_arg(index_service))
-        .then(delete_index)
+        .then(|index_id, delete_index_query_param, index_service| {
+            run_on_admin_runtime(delete_index(
+                index_id,
+                delete_index_query_param,
+                index_service,
+            ))
+        })
         .and(extract_format_from_qs())
         .map(make_json_api_response)
 }
@@ -872,12 +1166,16 @@ mod tests {
     use assert_json_diff::assert_json_include;
     use quickwit_common::uri::Uri;
     use quickwit_common::ServiceStream;
-    use quickwit_config::{SourceParams, VecSourceParams};
+    use quickwit_config::{IndexAlias, IndexConfig, SourceParams, VecSourceParams};
     use quickwit_indexing::{mock_split, MockSplitBuilder};
-    use quickwit_metastore::{metastore_for_test, IndexMetadata, ListSplitsResponseExt};
+    use quickwit_metastore::{
+        metastore_for_test, IndexMetadata, ListIndexAliasesResponseExt, ListSplitsResponseExt,
+        SplitMetadata, StageSplitsRequestExt, UpdateIndexAliasesRequestExt,
+    };
     use quickwit_proto::metastore::{
-        EmptyResponse, IndexMetadataResponse, ListIndexesMetadataResponse, ListSplitsResponse,
-        MetastoreServiceClient, SourceType,
+        EmptyResponse, IndexMetadataResponse, ListIndexAliasesRequest, ListIndexesMetadataResponse,
+        ListSplitsResponse, MetastoreServiceClient, SourceType, StageSplitsRequest,
+        UpdateIndexAliasesRequest,
     };
     use quickwit_storage::StorageResolver;
     use serde_json::Value as JsonValue;
@@ -1076,6 +1374,103 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_get_index_schema() {
+        let mut mock_metastore = MetastoreServiceClient::mock();
+        let mut index_metadata =
+            IndexMetadata::for_test("quickwit-demo-index", "ram:///indexes/quickwit-demo-index");
+        index_metadata.index_config.doc_mapping = serde_json::from_value(serde_json::json!({
+            "mode": "strict",
+            "store_source": true,
+            "timestamp_field": "timestamp",
+            "field_mappings": [
+                {
+                    "name": "timestamp",
+                    "type": "datetime",
+                    "fast": true
+                },
+                {
+                    "name": "body",
+                    "type": "text",
+                    "stored": false
+                },
+                {
+                    "name": "service",
+                    "type": "text",
+                    "tokenizer": "raw",
+                    "fast": true
+                }
+            ]
+        }))
+        .unwrap();
+        index_metadata
+            .index_config
+            .search_settings
+            .default_search_fields = vec!["body".to_string()];
+        mock_metastore
+            .expect_index_metadata()
+            .withf(|index_metadata_request| {
+                index_metadata_request.index_id.as_ref().unwrap() == "quickwit-demo-index"
+            })
+            .return_once(move |_| {
+                Ok(IndexMetadataResponse::try_from_index_metadata(index_metadata).unwrap())
+            });
+        let index_service = IndexService::new(
+            MetastoreServiceClient::from(mock_metastore),
+            StorageResolver::unconfigured(),
+        );
+        let index_management_handler =
+            super::index_management_handlers(index_service, Arc::new(NodeConfig::for_test()))
+                .recover(recover_fn);
+        let resp = warp::test::request()
+            .path("/indexes/quickwit-demo-index/schema")
+            .reply(&index_management_handler)
+            .await;
+        assert_eq!(resp.status(), 200);
+
+        let actual_response_json: JsonValue = serde_json::from_slice(resp.body()).unwrap();
+        let expected_response_json = serde_json::json!([
+            {
+                "name": "_field_presence",
+                "type": "u64",
+                "indexed": true,
+                "stored": false,
+                "fast": false
+            },
+            {
+                "name": "timestamp",
+                "type": "date",
+                "indexed": true,
+                "stored": true,
+                "fast": true
+            },
+            {
+                "name": "body",
+                "type": "str",
+                "indexed": true,
+                "stored": false,
+                "fast": false,
+                "tokenizer": "default"
+            },
+            {
+                "name": "service",
+                "type": "str",
+                "indexed": true,
+                "stored": true,
+                "fast": true,
+                "tokenizer": "raw"
+            },
+            {
+                "name": "_source",
+                "type": "json",
+                "indexed": false,
+                "stored": true,
+                "fast": false
+            }
+        ]);
+        assert_eq!(actual_response_json, expected_response_json);
+    }
+
     #[tokio::test]
     async fn test_get_all_splits() {
         let mut mock_metastore = MetastoreServiceClient::mock();
@@ -1257,6 +1652,224 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_check_index() {
+        let mut mock_metastore = MetastoreServiceClient::mock();
+        mock_metastore.expect_index_metadata().return_once(|_| {
+            Ok(
+                IndexMetadataResponse::try_from_index_metadata(IndexMetadata::for_test(
+                    "quickwit-demo-index",
+                    "ram:///indexes/quickwit-demo-index",
+                ))
+                .unwrap(),
+            )
+        });
+        mock_metastore.expect_list_splits().return_once(|_| {
+            let splits = ListSplitsResponse::try_from_splits(vec![mock_split("split_1")]).unwrap();
+            Ok(ServiceStream::from(vec![Ok(splits)]))
+        });
+        let index_service = IndexService::new(
+            MetastoreServiceClient::from(mock_metastore),
+            StorageResolver::for_test(),
+        );
+        let index_management_handler =
+            super::index_management_handlers(index_service, Arc::new(NodeConfig::for_test()))
+                .recover(recover_fn);
+        let resp = warp::test::request()
+            .path("/indexes/quickwit-demo-index/check")
+            .reply(&index_management_handler)
+            .await;
+        assert_eq!(resp.status(), 200);
+        let actual_response_json: JsonValue = serde_json::from_slice(resp.body()).unwrap();
+        let expected_response_json = serde_json::json!({
+            "consistent_split_ids": [],
+            "missing_in_storage_split_ids": ["split_1"],
+            "orphaned_in_storage_split_ids": [],
+        });
+        assert_json_include!(
+            actual: actual_response_json,
+            expected: expected_response_json
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_index_uri() {
+        let mut mock_metastore = MetastoreServiceClient::mock();
+        mock_metastore
+            .expect_index_metadata()
+            .times(2)
+            .returning(|_| {
+                Ok(
+                    IndexMetadataResponse::try_from_index_metadata(IndexMetadata::for_test(
+                        "quickwit-demo-index",
+                        "file:///path/to/index/quickwit-demo-index",
+                    ))
+                    .unwrap(),
+                )
+            });
+        mock_metastore
+            .expect_update_index_uri()
+            .withf(|request| request.index_uri == "file:///new/path/to/index/quickwit-demo-index")
+            .return_once(|_| Ok(EmptyResponse {}));
+        let index_service = IndexService::new(
+            MetastoreServiceClient::from(mock_metastore),
+            StorageResolver::unconfigured(),
+        );
+        let index_management_handler =
+            super::index_management_handlers(index_service, Arc::new(NodeConfig::for_test()))
+                .recover(recover_fn);
+        let resp = warp::test::request()
+            .path("/indexes/quickwit-demo-index/index-uri")
+            .method("PUT")
+            .json(
+                &serde_json::json!({"index_uri": "file:///new/path/to/index/quickwit-demo-index"}),
+            )
+            .reply(&index_management_handler)
+            .await;
+        assert_eq!(resp.status(), 200);
+
+        let resp = warp::test::request()
+            .path("/indexes/quickwit-demo-index/index-uri")
+            .method("PUT")
+            .json(&serde_json::json!({"index_uri": ""}))
+            .reply(&index_management_handler)
+            .await;
+        assert_eq!(resp.status(), 400);
+    }
+
+    #[tokio::test]
+    async fn test_rename_index() {
+        let mut metastore = metastore_for_test();
+        let mut index_service = IndexService::new(metastore.clone(), StorageResolver::for_test());
+        let index_config = IndexConfig::for_test("test-index", "ram:///indexes/test-index");
+        let index_uid = index_service
+            .create_index(index_config, false)
+            .await
+            .unwrap()
+            .index_uid;
+        let split_metadata = SplitMetadata {
+            split_id: "test-split".to_string(),
+            index_uid: index_uid.clone(),
+            ..Default::default()
+        };
+        let stage_splits_request =
+            StageSplitsRequest::try_from_split_metadata(index_uid, split_metadata).unwrap();
+        metastore.stage_splits(stage_splits_request).await.unwrap();
+
+        let index_management_handler =
+            super::index_management_handlers(index_service, Arc::new(NodeConfig::for_test()))
+                .recover(recover_fn);
+        let resp = warp::test::request()
+            .path("/indexes/test-index/rename")
+            .method("PUT")
+            .json(&serde_json::json!({"new_index_id": "test-index-renamed"}))
+            .reply(&index_management_handler)
+            .await;
+        assert_eq!(resp.status(), 200);
+        let actual_response_json: JsonValue = serde_json::from_slice(resp.body()).unwrap();
+        let expected_response_json = serde_json::json!({
+            "index_config": {
+                "index_id": "test-index-renamed",
+                "index_uri": "ram:///indexes/test-index",
+            }
+        });
+        assert_json_include!(
+            actual: actual_response_json,
+            expected: expected_response_json
+        );
+
+        let resp = warp::test::request()
+            .path("/indexes/test-index-renamed/splits")
+            .reply(&index_management_handler)
+            .await;
+        assert_eq!(resp.status(), 200);
+        let actual_response_json: JsonValue = serde_json::from_slice(resp.body()).unwrap();
+        assert_eq!(actual_response_json["splits"].as_array().unwrap().len(), 1);
+
+        let resp = warp::test::request()
+            .path("/indexes/test-index/rename")
+            .method("PUT")
+            .json(&serde_json::json!({"new_index_id": "test-index-other"}))
+            .reply(&index_management_handler)
+            .await;
+        assert_eq!(resp.status(), 404);
+    }
+
+    #[tokio::test]
+    async fn test_rollover_index_alias() {
+        let mut metastore = metastore_for_test();
+        let mut index_service = IndexService::new(metastore.clone(), StorageResolver::for_test());
+        let index_config = IndexConfig::for_test("logs-000001", "ram:///indexes/logs-000001");
+        index_service
+            .create_index(index_config, false)
+            .await
+            .unwrap();
+        let index_alias = IndexAlias::for_test("logs", &["logs-000001"], Some("logs-000001"));
+        let update_index_aliases_request =
+            UpdateIndexAliasesRequest::try_from_index_aliases([&index_alias], Vec::new()).unwrap();
+        metastore
+            .update_index_aliases(update_index_aliases_request)
+            .await
+            .unwrap();
+
+        let index_management_handler =
+            super::index_management_handlers(index_service, Arc::new(NodeConfig::for_test()))
+                .recover(recover_fn);
+        let resp = warp::test::request()
+            .path("/aliases/logs/rollover")
+            .method("POST")
+            .json(&serde_json::json!({"conditions": {"max_docs": 1000, "max_age": "0s"}}))
+            .reply(&index_management_handler)
+            .await;
+        assert_eq!(resp.status(), 200);
+        let resp_json: JsonValue = serde_json::from_slice(resp.body()).unwrap();
+        let expected_response_json = serde_json::json!({
+            "alias_id": "logs",
+            "rolled_over": true,
+            "old_write_index_id": "logs-000001",
+            "new_write_index_id": "logs-000002",
+            "met_conditions": ["max_age"],
+        });
+        assert_json_include!(actual: resp_json, expected: expected_response_json);
+
+        let index_aliases = metastore
+            .list_index_aliases(ListIndexAliasesRequest {})
+            .await
+            .unwrap()
+            .deserialize_index_aliases()
+            .unwrap();
+        assert_eq!(
+            index_aliases,
+            [IndexAlias::for_test(
+                "logs",
+                &["logs-000001", "logs-000002"],
+                Some("logs-000002")
+            )]
+        );
+        metastore
+            .index_metadata(IndexMetadataRequest::for_index_id(
+                "logs-000002".to_string(),
+            ))
+            .await
+            .unwrap();
+
+        let resp = warp::test::request()
+            .path("/aliases/logs/rollover")
+            .method("POST")
+            .json(&serde_json::json!({"conditions": {"max_age": "foo"}}))
+            .reply(&index_management_handler)
+            .await;
+        assert_eq!(resp.status(), 400);
+
+        let resp = warp::test::request()
+            .path("/aliases/unknown/rollover")
+            .method("POST")
+            .json(&serde_json::json!({}))
+            .reply(&index_management_handler)
+            .await;
+        assert_eq!(resp.status(), 404);
+    }
+
     #[tokio::test]
     async fn test_delete_index() {
         let mut mock_metastore = MetastoreServiceClient::mock();
@@ -19,4 +19,6 @@
 
 mod rest_handler;
 
-pub use rest_handler::{indexing_get_handler, IndexingApi};
+pub use rest_handler::{
+    indexing_get_handler, pause_source_handler, resume_source_handler, IndexingApi,
+};
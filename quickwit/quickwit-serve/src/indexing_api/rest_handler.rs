@@ -19,16 +19,24 @@
 
 use std::convert::Infallible;
 
+use itertools::Itertools;
 use quickwit_actors::{AskError, Mailbox, Observe};
 use quickwit_indexing::actors::{IndexingService, IndexingServiceCounters};
+use quickwit_metastore::IndexMetadataResponseExt;
+use quickwit_proto::control_plane::{
+    ControlPlaneError, ControlPlaneResult, ControlPlaneService, ControlPlaneServiceClient,
+    PauseSourceRequest,
+};
+use quickwit_proto::metastore::{IndexMetadataRequest, MetastoreService, MetastoreServiceClient};
+use tracing::info;
 use warp::{Filter, Rejection};
 
 use crate::format::extract_format_from_qs;
 use crate::json_api_response::make_json_api_response;
-use crate::require;
+use crate::{require, with_arg};
 
 #[derive(utoipa::OpenApi)]
-#[openapi(paths(indexing_endpoint))]
+#[openapi(paths(indexing_endpoint, pause_source_endpoint, resume_source_endpoint))]
 pub struct IndexingApi;
 
 #[utoipa::path(
@@ -61,3 +69,122 @@ pub fn indexing_get_handler(
         .and(extract_format_from_qs())
         .map(make_json_api_response)
 }
+
+/// Pauses or resumes a source through the control plane and fails with the errors of the
+/// pipelines that could not be paused or resumed.
+async fn pause_or_resume_source(
+    index_id: String,
+    source_id: String,
+    paused: bool,
+    mut metastore: MetastoreServiceClient,
+    mut control_plane: ControlPlaneServiceClient,
+) -> ControlPlaneResult<()> {
+    let index_metadata_request = IndexMetadataRequest::for_index_id(index_id);
+    let index_uid = metastore
+        .index_metadata(index_metadata_request)
+        .await?
+        .deserialize_index_metadata()?
+        .index_uid;
+    let pause_source_request = PauseSourceRequest {
+        index_uid: index_uid.to_string(),
+        source_id,
+        paused,
+    };
+    let pause_source_response = control_plane.pause_source(pause_source_request).await?;
+
+    if pause_source_response.failures.is_empty() {
+        return Ok(());
+    }
+    let action = if paused { "pause" } else { "resume" };
+    let failures = pause_source_response
+        .failures
+        .iter()
+        .map(|failure| {
+            let pipeline_uid = failure.pipeline_uid.unwrap_or_default();
+            format!(
+                "pipeline `{pipeline_uid}` on node `{}`: {}",
+                failure.node_id, failure.error
+            )
+        })
+        .join(", ");
+    let message = format!("failed to {action} some pipelines of the source: {failures}");
+    Err(ControlPlaneError::Internal(message))
+}
+
+#[utoipa::path(
+    post,
+    tag = "Indexing",
+    path = "/indexes/{index_id}/sources/{source_id}/pause",
+    responses(
+        (status = 200, description = "Successfully paused the indexing pipelines of the source.")
+    ),
+    params(
+        ("index_id" = String, Path, description = "The index ID of the source."),
+        ("source_id" = String, Path, description = "The source ID to pause."),
+    )
+)]
+/// Pauses the indexing pipelines of a source on all the indexers.
+async fn pause_source_endpoint(
+    index_id: String,
+    source_id: String,
+    metastore: MetastoreServiceClient,
+    control_plane: ControlPlaneServiceClient,
+) -> ControlPlaneResult<()> {
+    info!(index_id = %index_id, source_id = %source_id, "pause-source");
+    pause_or_resume_source(index_id, source_id, true, metastore, control_plane).await
+}
+
+fn pause_source_filter() -> impl Filter<Extract = (String, String), Error = Rejection> + Clone {
+    warp::path!("indexes" / String / "sources" / String / "pause").and(warp::post())
+}
+
+pub fn pause_source_handler(
+    metastore: MetastoreServiceClient,
+    control_plane: ControlPlaneServiceClient,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = Rejection> + Clone {
+    pause_source_filter()
+        .and(with_arg(metastore))
+        .and(with_arg(control_plane))
+        .then(pause_source_endpoint)
+        .and(extract_format_from_qs())
+        .map(make_json_api_response)
+}
+
+#[utoipa::path(
+    post,
+    tag = "Indexing",
+    path = "/indexes/{index_id}/sources/{source_id}/resume",
+    responses(
+        (status = 200, description = "Successfully resumed the indexing pipelines of the source.")
+    ),
+    params(
+        ("index_id" = String, Path, description = "The index ID of the source."),
+        ("source_id" = String, Path, description = "The source ID to resume."),
+    )
+)]
+/// Resumes the indexing pipelines of a source on all the indexers.
+async fn resume_source_endpoint(
+    index_id: String,
+    source_id: String,
+    metastore: MetastoreServiceClient,
+    control_plane: ControlPlaneServiceClient,
+) -> ControlPlaneResult<()> {
+    info!(index_id = %index_id, source_id = %source_id, "resume-source");
+    pause_or_resume_source(index_id, source_id, false, metastore, control_plane).await
+}
+
+fn resume_source_filter() -> impl Filter<Extract = (String, String), Error = Rejection> + Clone {
+    warp::path!("indexes" / String / "sources" / String / "resume").and(warp::post())
+}
+
+pub fn resume_source_handler(
+    metastore: MetastoreServiceClient,
+    control_plane: ControlPlaneServiceClient,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = Rejection> + Clone {
+    resume_source_filter()
+        .and(with_arg(metastore))
+        .and(with_arg(control_plane))
+        .then(resume_source_endpoint)
+        .and(extract_format_from_qs())
+        .map(make_json_api_response)
+}
@@ -21,5 +21,5 @@ mod rest_handler;
 
 #[cfg(test)]
 pub(crate) use rest_handler::tests::setup_ingest_service;
-pub(crate) use rest_handler::{ingest_api_handlers, lines};
-pub use rest_handler::{IngestApi, IngestApiSchemas};
+pub(crate) use rest_handler::{ingest_api_handlers, ingest_status_handler, lines};
+pub use rest_handler::{IngestApi, IngestApiSchemas, SourceIngestStatus};
@@ -17,18 +17,35 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
 use bytes::{Buf, Bytes};
-use quickwit_config::{IngestApiConfig, INGEST_V2_SOURCE_ID};
+use quickwit_config::{
+    build_doc_mapper, IngestApiConfig, SourceConfig, SourceType, INGEST_V2_SOURCE_ID,
+};
+use quickwit_doc_mapper::{DocMapper, JsonObject, OnFieldError};
+use quickwit_indexing::fetch_source_last_positions;
 use quickwit_ingest::{
     CommitType, DocBatchBuilder, DocBatchV2Builder, FetchResponse, IngestRequest, IngestResponse,
-    IngestService, IngestServiceClient, IngestServiceError, TailRequest,
+    IngestService, IngestServiceClient, IngestServiceError, IngesterPool, TailRequest,
 };
+use quickwit_metastore::checkpoint::{PartitionId, SourceCheckpoint};
+use quickwit_metastore::{IndexMetadata, IndexMetadataResponseExt, MetastoreServiceExt};
+use quickwit_proto::ingest::ingester::{GetReplicationPositionsRequest, IngesterService};
 use quickwit_proto::ingest::router::{
     IngestFailureReason, IngestRequestV2, IngestResponseV2, IngestRouterService,
     IngestRouterServiceClient, IngestSubrequest,
 };
-use quickwit_proto::types::IndexId;
-use serde::Deserialize;
+use quickwit_proto::ingest::{IngestV2Error, ShardIds};
+use quickwit_proto::metastore::{
+    IndexMetadataRequest, ListShardsRequest, ListShardsSubrequest, MetastoreError,
+    MetastoreService, MetastoreServiceClient,
+};
+use quickwit_proto::types::{IndexId, NodeId, Position, ShardId, SourceId};
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use warp::{Filter, Rejection};
 
@@ -37,7 +54,7 @@ use crate::json_api_response::make_json_api_response;
 use crate::{with_arg, BodyFormat};
 
 #[derive(utoipa::OpenApi)]
-#[openapi(paths(ingest, tail_endpoint,))]
+#[openapi(paths(ingest, tail_endpoint, ingest_status_endpoint,))]
 pub struct IngestApi;
 
 #[derive(utoipa::OpenApi)]
@@ -46,6 +63,8 @@ pub struct IngestApi;
     quickwit_ingest::FetchResponse,
     quickwit_ingest::IngestResponse,
     quickwit_ingest::CommitType,
+    OnInvalidUtf8,
+    SourceIngestStatus,
 )))]
 pub struct IngestApiSchemas;
 
@@ -55,21 +74,181 @@ struct InvalidUtf8;
 
 impl warp::reject::Reject for InvalidUtf8 {}
 
+/// Defines how documents containing invalid UTF-8 bytes are handled.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OnInvalidUtf8 {
+    /// The whole request is rejected with an error pointing to the first invalid document.
+    #[default]
+    Reject,
+    /// Invalid sequences are replaced with the `U+FFFD` replacement character.
+    Replace,
+    /// Invalid documents are dropped.
+    Skip,
+}
+
 #[derive(Clone, Debug, Default, Deserialize, PartialEq)]
 struct IngestOptions {
     #[serde(alias = "commit")]
     #[serde(default)]
     commit_type: CommitType,
+    #[serde(default)]
+    on_invalid_utf8: OnInvalidUtf8,
+}
+
+/// Documents of an ingest request body after the `on_invalid_utf8` policy has been applied.
+#[derive(Default)]
+struct Utf8CheckedDocs<'a> {
+    docs: Vec<Cow<'a, [u8]>>,
+    num_docs_with_replaced_utf8: u64,
+    num_docs_skipped_invalid_utf8: u64,
+}
+
+fn check_utf8_docs(
+    body: &Bytes,
+    on_invalid_utf8: OnInvalidUtf8,
+) -> Result<Utf8CheckedDocs<'_>, IngestServiceError> {
+    let mut checked_docs = Utf8CheckedDocs::default();
+
+    for (doc_idx, doc) in lines(body).enumerate() {
+        let Err(utf8_error) = std::str::from_utf8(doc) else {
+            checked_docs.docs.push(Cow::Borrowed(doc));
+            continue;
+        };
+        match on_invalid_utf8 {
+            OnInvalidUtf8::Reject => {
+                let message = format!(
+                    "document #{doc_idx} contains invalid UTF-8 at byte offset {}",
+                    utf8_error.valid_up_to()
+                );
+                return Err(IngestServiceError::BadRequest(message));
+            }
+            OnInvalidUtf8::Replace => {
+                let repaired_doc = String::from_utf8_lossy(doc).into_owned().into_bytes();
+                checked_docs.docs.push(Cow::Owned(repaired_doc));
+                checked_docs.num_docs_with_replaced_utf8 += 1;
+            }
+            OnInvalidUtf8::Skip => {
+                checked_docs.num_docs_skipped_invalid_utf8 += 1;
+            }
+        }
+    }
+    Ok(checked_docs)
+}
+
+/// Duration for which the doc mapper of an index is cached by the ingest handlers. Doc mappings
+/// cannot be updated, but an index can be deleted and recreated with another doc mapping under the
+/// same ID, in which case the number of dropped fields may be misreported for that long.
+const DOC_MAPPER_CACHE_TTL: Duration = Duration::from_secs(60);
+
+struct CachedDocMapper {
+    // `None` when the index does not skip the fields with invalid values.
+    doc_mapper_opt: Option<Arc<dyn DocMapper>>,
+    cached_at: Instant,
+}
+
+/// Caches the doc mappers used to count the dropped fields, so that ingest requests do not fetch
+/// the index metadata and build a doc mapper every time.
+#[derive(Clone, Default)]
+struct DocMapperCache {
+    doc_mappers: Arc<Mutex<HashMap<IndexId, CachedDocMapper>>>,
+}
+
+impl DocMapperCache {
+    /// Returns the doc mapper of the index if its `on_field_error` policy is `skip`.
+    async fn get_skipping_doc_mapper(
+        &self,
+        metastore: &mut MetastoreServiceClient,
+        index_id: &str,
+    ) -> Result<Option<Arc<dyn DocMapper>>, IngestServiceError> {
+        if let Some(cached_doc_mapper) = self.doc_mappers.lock().unwrap().get(index_id) {
+            if cached_doc_mapper.cached_at.elapsed() < DOC_MAPPER_CACHE_TTL {
+                return Ok(cached_doc_mapper.doc_mapper_opt.clone());
+            }
+        }
+        let index_metadata_request = IndexMetadataRequest::for_index_id(index_id.to_string());
+        let index_metadata = match metastore.index_metadata(index_metadata_request).await {
+            Ok(response) => response.deserialize_index_metadata()?,
+            // Missing indexes are reported by the ingest service.
+            Err(MetastoreError::NotFound(_)) => return Ok(None),
+            Err(error) => return Err(error.into()),
+        };
+        let index_config = index_metadata.into_index_config();
+
+        let doc_mapper_opt = if index_config.doc_mapping.on_field_error == OnFieldError::Skip {
+            let doc_mapper =
+                build_doc_mapper(&index_config.doc_mapping, &index_config.search_settings)
+                    .map_err(|error| IngestServiceError::Internal(error.to_string()))?;
+            Some(doc_mapper)
+        } else {
+            None
+        };
+        let mut doc_mappers_guard = self.doc_mappers.lock().unwrap();
+        doc_mappers_guard.retain(|_, cached_doc_mapper| {
+            cached_doc_mapper.cached_at.elapsed() < DOC_MAPPER_CACHE_TTL
+        });
+        doc_mappers_guard.insert(
+            index_id.to_string(),
+            CachedDocMapper {
+                doc_mapper_opt: doc_mapper_opt.clone(),
+                cached_at: Instant::now(),
+            },
+        );
+        Ok(doc_mapper_opt)
+    }
+}
+
+/// Counts the fields that the indexing pipeline will drop from the documents because their value
+/// cannot be coerced to their mapped type. Fields are only dropped when the `on_field_error`
+/// policy of the index is `skip`: otherwise, such documents are rejected as a whole.
+async fn count_dropped_fields(
+    doc_mapper_cache: &DocMapperCache,
+    metastore: &mut MetastoreServiceClient,
+    index_id: &str,
+    docs: &[Cow<'_, [u8]>],
+) -> Result<u64, IngestServiceError> {
+    let Some(doc_mapper) = doc_mapper_cache
+        .get_skipping_doc_mapper(metastore, index_id)
+        .await?
+    else {
+        return Ok(0);
+    };
+    let mut num_dropped_fields = 0;
+
+    for doc in docs {
+        // Invalid documents are rejected by the indexing pipeline.
+        let Ok(json_obj) = serde_json::from_slice::<JsonObject>(doc) else {
+            continue;
+        };
+        if let Ok((_, _, num_doc_dropped_fields)) =
+            doc_mapper.doc_from_json_obj_with_num_dropped_fields(json_obj)
+        {
+            num_dropped_fields += num_doc_dropped_fields as u64;
+        }
+    }
+    Ok(num_dropped_fields)
 }
 
 pub(crate) fn ingest_api_handlers(
     ingest_router: IngestRouterServiceClient,
     ingest_service: IngestServiceClient,
+    metastore: MetastoreServiceClient,
     config: IngestApiConfig,
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = Rejection> + Clone {
-    ingest_handler(ingest_service.clone(), config.clone())
-        .or(tail_handler(ingest_service))
-        .or(ingest_v2_handler(ingest_router, config))
+    let doc_mapper_cache = DocMapperCache::default();
+    ingest_handler(
+        ingest_service.clone(),
+        metastore.clone(),
+        doc_mapper_cache.clone(),
+        config.clone(),
+    )
+    .or(tail_handler(ingest_service))
+    .or(ingest_v2_handler(
+        ingest_router,
+        metastore,
+        doc_mapper_cache,
+        config,
+    ))
 }
 
 fn ingest_filter(
@@ -88,10 +267,14 @@ fn ingest_filter(
 
 fn ingest_handler(
     ingest_service: IngestServiceClient,
+    metastore: MetastoreServiceClient,
+    doc_mapper_cache: DocMapperCache,
     config: IngestApiConfig,
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = Rejection> + Clone {
     ingest_filter(config)
         .and(with_arg(ingest_service))
+        .and(with_arg(metastore))
+        .and(with_arg(doc_mapper_cache))
         .then(ingest)
         .map(|result| make_json_api_response(result, BodyFormat::default()))
 }
@@ -112,10 +295,14 @@ fn ingest_v2_filter(
 
 fn ingest_v2_handler(
     ingest_router: IngestRouterServiceClient,
+    metastore: MetastoreServiceClient,
+    doc_mapper_cache: DocMapperCache,
     config: IngestApiConfig,
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = Rejection> + Clone {
     ingest_v2_filter(config)
         .and(with_arg(ingest_router))
+        .and(with_arg(metastore))
+        .and(with_arg(doc_mapper_cache))
         .then(ingest_v2)
         .and(with_arg(BodyFormat::default()))
         .map(make_json_api_response)
@@ -126,19 +313,34 @@ async fn ingest_v2(
     body: Bytes,
     ingest_options: IngestOptions,
     mut ingest_router: IngestRouterServiceClient,
+    mut metastore: MetastoreServiceClient,
+    doc_mapper_cache: DocMapperCache,
 ) -> Result<IngestResponse, IngestServiceError> {
+    let checked_docs = check_utf8_docs(&body, ingest_options.on_invalid_utf8)?;
     let mut doc_batch_builder = DocBatchV2Builder::default();
 
-    for doc in lines(&body) {
+    for doc in &checked_docs.docs {
         doc_batch_builder.add_doc(doc);
     }
     let doc_batch_opt = doc_batch_builder.build();
 
     let Some(doc_batch) = doc_batch_opt else {
-        let response = IngestResponse::default();
+        let response = IngestResponse {
+            num_docs_skipped_invalid_utf8: checked_docs.num_docs_skipped_invalid_utf8,
+            ..Default::default()
+        };
         return Ok(response);
     };
     let num_docs = doc_batch.num_docs();
+    // Documents ingested into an index alias are routed to its write index.
+    let index_id = metastore.resolve_write_index_id(index_id).await?;
+    let num_dropped_fields = count_dropped_fields(
+        &doc_mapper_cache,
+        &mut metastore,
+        &index_id,
+        &checked_docs.docs,
+    )
+    .await?;
 
     let subrequest = IngestSubrequest {
         subrequest_id: 0,
@@ -151,7 +353,11 @@ async fn ingest_v2(
         subrequests: vec![subrequest],
     };
     let response = ingest_router.ingest(request).await?;
-    convert_ingest_response_v2(response, num_docs)
+    let mut ingest_response = convert_ingest_response_v2(response, num_docs)?;
+    ingest_response.num_docs_with_replaced_utf8 = checked_docs.num_docs_with_replaced_utf8;
+    ingest_response.num_docs_skipped_invalid_utf8 = checked_docs.num_docs_skipped_invalid_utf8;
+    ingest_response.num_dropped_fields = num_dropped_fields;
+    Ok(ingest_response)
 }
 
 fn convert_ingest_response_v2(
@@ -168,6 +374,7 @@ fn convert_ingest_response_v2(
     if response.successes.pop().is_some() {
         return Ok(IngestResponse {
             num_docs_for_processing: num_docs as u64,
+            ..Default::default()
         });
     }
     let ingest_failure = response.failures.pop().unwrap();
@@ -200,6 +407,7 @@ fn convert_ingest_response_v2(
     params(
         ("index_id" = String, Path, description = "The index ID to add docs to."),
         ("commit" = Option<CommitType>, Query, description = "Force or wait for commit at the end of the indexing operation."),
+        ("on_invalid_utf8" = Option<OnInvalidUtf8>, Query, description = "Reject, repair, or skip documents containing invalid UTF-8."),
     )
 )]
 /// Ingest documents
@@ -208,18 +416,33 @@ async fn ingest(
     body: Bytes,
     ingest_options: IngestOptions,
     mut ingest_service: IngestServiceClient,
+    mut metastore: MetastoreServiceClient,
+    doc_mapper_cache: DocMapperCache,
 ) -> Result<IngestResponse, IngestServiceError> {
     // The size of the body should be an upper bound of the size of the batch. The removal of the
     // end of line character for each doc compensates the addition of the `DocCommand` header.
+    let checked_docs = check_utf8_docs(&body, ingest_options.on_invalid_utf8)?;
+    // Documents ingested into an index alias are routed to its write index.
+    let index_id = metastore.resolve_write_index_id(index_id).await?;
+    let num_dropped_fields = count_dropped_fields(
+        &doc_mapper_cache,
+        &mut metastore,
+        &index_id,
+        &checked_docs.docs,
+    )
+    .await?;
     let mut doc_batch_builder = DocBatchBuilder::with_capacity(index_id, body.remaining());
-    for line in lines(&body) {
-        doc_batch_builder.ingest_doc(line);
+    for doc in &checked_docs.docs {
+        doc_batch_builder.ingest_doc(&doc[..]);
     }
     let ingest_req = IngestRequest {
         doc_batches: vec![doc_batch_builder.build()],
         commit: ingest_options.commit_type.into(),
     };
-    let ingest_response = ingest_service.ingest(ingest_req).await?;
+    let mut ingest_response = ingest_service.ingest(ingest_req).await?;
+    ingest_response.num_docs_with_replaced_utf8 = checked_docs.num_docs_with_replaced_utf8;
+    ingest_response.num_docs_skipped_invalid_utf8 = checked_docs.num_docs_skipped_invalid_utf8;
+    ingest_response.num_dropped_fields = num_dropped_fields;
     Ok(ingest_response)
 }
 
@@ -253,10 +476,221 @@ async fn tail_endpoint(
     index_id: String,
     mut ingest_service: IngestServiceClient,
 ) -> Result<FetchResponse, IngestServiceError> {
-    let fetch_response = ingest_service.tail(TailRequest { index_id }).await?;
+    let tail_request = TailRequest {
+        index_id,
+        positions_only: false,
+    };
+    let fetch_response = ingest_service.tail(tail_request).await?;
     Ok(fetch_response)
 }
 
+/// Checkpoint of a source and estimated number of records that have not been indexed yet.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct SourceIngestStatus {
+    pub source_id: SourceId,
+    pub enabled: bool,
+    #[schema(value_type = Object)]
+    pub checkpoint: SourceCheckpoint,
+    /// `None` when the source does not expose the positions of its last records.
+    pub lag: Option<u64>,
+}
+
+pub(crate) fn ingest_status_handler(
+    metastore: MetastoreServiceClient,
+    ingest_service: IngestServiceClient,
+    ingester_pool: IngesterPool,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = Rejection> + Clone {
+    warp::path!(String / "ingest-status")
+        .and(warp::get())
+        .and(with_arg(metastore))
+        .and(with_arg(ingest_service))
+        .and(with_arg(ingester_pool))
+        .then(ingest_status_endpoint)
+        .and(extract_format_from_qs())
+        .map(make_json_api_response)
+}
+
+#[utoipa::path(
+    get,
+    tag = "Ingest",
+    path = "/{index_id}/ingest-status",
+    responses(
+        (status = 200, description = "Successfully computed the ingest status of the sources.", body = [SourceIngestStatus])
+    ),
+    params(
+        ("index_id" = String, Path, description = "The index ID of the sources."),
+    )
+)]
+/// Returns the checkpoint and the estimated lag of the sources of an index.
+async fn ingest_status_endpoint(
+    index_id: IndexId,
+    mut metastore: MetastoreServiceClient,
+    mut ingest_service: IngestServiceClient,
+    ingester_pool: IngesterPool,
+) -> Result<Vec<SourceIngestStatus>, IngestServiceError> {
+    let index_metadata_request = IndexMetadataRequest::for_index_id(index_id);
+    let index_metadata = metastore
+        .index_metadata(index_metadata_request)
+        .await?
+        .deserialize_index_metadata()?;
+    let mut ingest_statuses = Vec::with_capacity(index_metadata.sources.len());
+
+    for source_config in index_metadata.sources.values() {
+        let checkpoint = index_metadata
+            .checkpoint
+            .source_checkpoint(&source_config.source_id)
+            .cloned()
+            .unwrap_or_default();
+        let lag = fetch_last_positions(
+            &index_metadata,
+            source_config,
+            &mut metastore,
+            &mut ingest_service,
+            &ingester_pool,
+        )
+        .await?
+        .and_then(|last_positions| estimate_lag(&checkpoint, &last_positions));
+
+        let ingest_status = SourceIngestStatus {
+            source_id: source_config.source_id.clone(),
+            enabled: source_config.enabled,
+            checkpoint,
+            lag,
+        };
+        ingest_statuses.push(ingest_status);
+    }
+    ingest_statuses.sort_by(|left, right| left.source_id.cmp(&right.source_id));
+    Ok(ingest_statuses)
+}
+
+/// Returns the position of the last record of each partition of a source, or `None` if the source
+/// does not expose them.
+async fn fetch_last_positions(
+    index_metadata: &IndexMetadata,
+    source_config: &SourceConfig,
+    metastore: &mut MetastoreServiceClient,
+    ingest_service: &mut IngestServiceClient,
+    ingester_pool: &IngesterPool,
+) -> Result<Option<SourceCheckpoint>, IngestServiceError> {
+    match source_config.source_type() {
+        SourceType::IngestV1 => {
+            let tail_request = TailRequest {
+                index_id: index_metadata.index_id().to_string(),
+                positions_only: true,
+            };
+            let fetch_response = match ingest_service.tail(tail_request).await {
+                Ok(fetch_response) => fetch_response,
+                // The queue of an index is created on the first ingest request: nothing is pending.
+                Err(IngestServiceError::IndexNotFound { .. }) => {
+                    return Ok(Some(SourceCheckpoint::default()));
+                }
+                Err(error) => return Err(error),
+            };
+            let mut last_positions = SourceCheckpoint::default();
+
+            if let (Some(partition_id), Some(last_position)) =
+                (fetch_response.partition_id, fetch_response.last_position)
+            {
+                last_positions.add_partition(
+                    PartitionId::from(partition_id),
+                    Position::offset(last_position),
+                );
+            }
+            Ok(Some(last_positions))
+        }
+        SourceType::IngestV2 => {
+            let last_positions = fetch_ingest_v2_last_positions(
+                index_metadata,
+                &source_config.source_id,
+                metastore,
+                ingester_pool,
+            )
+            .await?;
+            Ok(Some(last_positions))
+        }
+        _ => fetch_source_last_positions(source_config)
+            .await
+            .map_err(|error| IngestServiceError::Internal(format!("{error:#}"))),
+    }
+}
+
+/// Fetches the replication positions of the shards of an ingest V2 source from their leaders.
+async fn fetch_ingest_v2_last_positions(
+    index_metadata: &IndexMetadata,
+    source_id: &str,
+    metastore: &mut MetastoreServiceClient,
+    ingester_pool: &IngesterPool,
+) -> Result<SourceCheckpoint, IngestServiceError> {
+    let index_uid = index_metadata.index_uid.to_string();
+    let list_shards_request = ListShardsRequest {
+        subrequests: vec![ListShardsSubrequest {
+            index_uid: index_uid.clone(),
+            source_id: source_id.to_string(),
+            shard_state: None,
+        }],
+    };
+    let list_shards_response = metastore.list_shards(list_shards_request).await?;
+    let mut per_leader_shard_ids: HashMap<NodeId, Vec<ShardId>> = HashMap::new();
+
+    for list_shards_subresponse in list_shards_response.subresponses {
+        for shard in list_shards_subresponse.shards {
+            per_leader_shard_ids
+                .entry(shard.leader_id.clone().into())
+                .or_default()
+                .push(shard.shard_id().clone());
+        }
+    }
+    let mut last_positions = SourceCheckpoint::default();
+
+    for (leader_id, shard_ids) in per_leader_shard_ids {
+        let mut ingester =
+            ingester_pool
+                .get(&leader_id)
+                .ok_or_else(|| IngestV2Error::IngesterUnavailable {
+                    ingester_id: leader_id.clone(),
+                })?;
+        let get_replication_positions_request = GetReplicationPositionsRequest {
+            shards: vec![ShardIds {
+                index_uid: index_uid.clone(),
+                source_id: source_id.to_string(),
+                shard_ids,
+            }],
+        };
+        let get_replication_positions_response = ingester
+            .get_replication_positions(get_replication_positions_request)
+            .await?;
+
+        for replication_position in get_replication_positions_response.replication_positions {
+            last_positions.add_partition(
+                PartitionId::from(replication_position.shard_id().as_str()),
+                replication_position
+                    .replication_position_inclusive()
+                    .clone(),
+            );
+        }
+    }
+    Ok(last_positions)
+}
+
+/// Estimates the number of records located after the checkpoint, i.e. the records that have not
+/// been indexed yet. Returns `None` when a checkpointed position is not an offset.
+fn estimate_lag(checkpoint: &SourceCheckpoint, last_positions: &SourceCheckpoint) -> Option<u64> {
+    let mut lag = 0;
+
+    for (partition_id, last_position) in last_positions.iter() {
+        // The partition does not hold any record.
+        let Some(last_offset) = last_position.as_u64() else {
+            continue;
+        };
+        let num_pending_records = match checkpoint.position_for_partition(&partition_id) {
+            None | Some(Position::Beginning) => last_offset + 1,
+            Some(position) => last_offset.saturating_sub(position.as_u64()?),
+        };
+        lag += num_pending_records;
+    }
+    Some(lag)
+}
+
 pub(crate) fn lines(body: &Bytes) -> impl Iterator<Item = &[u8]> {
     body.split(|byte| byte == &b'\n')
         .filter(|line| !is_empty_or_blank_line(line))
@@ -269,21 +703,45 @@ fn is_empty_or_blank_line(line: &[u8]) -> bool {
 
 #[cfg(test)]
 pub(crate) mod tests {
+    use std::borrow::Cow;
     use std::str;
     use std::time::Duration;
 
     use bytes::Bytes;
     use bytesize::ByteSize;
     use quickwit_actors::{Mailbox, Universe};
-    use quickwit_config::IngestApiConfig;
+    use quickwit_config::{
+        IndexAlias, IndexConfig, IngestApiConfig, SourceConfig, SourceParams, INGEST_API_SOURCE_ID,
+        INGEST_V2_SOURCE_ID,
+    };
+    use quickwit_doc_mapper::OnFieldError;
     use quickwit_ingest::{
-        init_ingest_api, CreateQueueIfNotExistsRequest, FetchRequest, FetchResponse,
-        IngestApiService, IngestResponse, IngestServiceClient, SuggestTruncateRequest,
-        QUEUES_DIR_NAME,
+        init_ingest_api, CommitType, CreateQueueIfNotExistsRequest, DocBatchBuilder, FetchRequest,
+        FetchResponse, IngestApiService, IngestRequest, IngestResponse, IngestService,
+        IngestServiceClient, IngesterPool, SuggestTruncateRequest, QUEUES_DIR_NAME,
+    };
+    use quickwit_metastore::checkpoint::{
+        IndexCheckpointDelta, PartitionId, SourceCheckpoint, SourceCheckpointDelta,
+    };
+    use quickwit_metastore::{
+        metastore_for_test, CreateIndexRequestExt, IndexMetadata, IndexMetadataResponseExt,
+        UpdateIndexAliasesRequestExt,
+    };
+    use quickwit_proto::ingest::ingester::{
+        GetReplicationPositionsResponse, IngesterServiceClient, ShardReplicationPosition,
     };
     use quickwit_proto::ingest::router::IngestRouterServiceClient;
+    use quickwit_proto::ingest::{Shard, ShardState};
+    use quickwit_proto::metastore::{
+        CreateIndexRequest, IndexMetadataResponse, ListShardsResponse, ListShardsSubresponse,
+        MetastoreService, MetastoreServiceClient, UpdateIndexAliasesRequest,
+    };
+    use quickwit_proto::types::{Position, ShardId};
 
-    use super::ingest_api_handlers;
+    use super::{
+        count_dropped_fields, estimate_lag, ingest_api_handlers, ingest_status_handler,
+        DocMapperCache, SourceIngestStatus,
+    };
     use crate::ingest_api::lines;
 
     #[test]
@@ -337,8 +795,12 @@ pub(crate) mod tests {
         let (universe, _temp_dir, ingest_service, _) =
             setup_ingest_service(&["my-index"], &IngestApiConfig::default()).await;
         let ingest_router = IngestRouterServiceClient::mock().into();
-        let ingest_api_handlers =
-            ingest_api_handlers(ingest_router, ingest_service, IngestApiConfig::default());
+        let ingest_api_handlers = ingest_api_handlers(
+            ingest_router,
+            ingest_service,
+            metastore_for_test(),
+            IngestApiConfig::default(),
+        );
         let resp = warp::test::request()
             .path("/my-index/ingest")
             .method("POST")
@@ -368,13 +830,134 @@ pub(crate) mod tests {
         universe.assert_quit().await;
     }
 
+    #[tokio::test]
+    async fn test_ingest_api_routes_index_alias_to_write_index() {
+        let mut metastore = metastore_for_test();
+
+        for index_id in ["my-index-1", "my-index-2"] {
+            let index_uri = format!("ram:///indexes/{index_id}");
+            let index_config = IndexConfig::for_test(index_id, &index_uri);
+            let create_index_request =
+                CreateIndexRequest::try_from_index_config(index_config).unwrap();
+            metastore.create_index(create_index_request).await.unwrap();
+        }
+        let index_alias = IndexAlias::for_test(
+            "my-alias",
+            &["my-index-1", "my-index-2"],
+            Some("my-index-2"),
+        );
+        let update_index_aliases_request =
+            UpdateIndexAliasesRequest::try_from_index_aliases([&index_alias], Vec::new()).unwrap();
+        metastore
+            .update_index_aliases(update_index_aliases_request)
+            .await
+            .unwrap();
+
+        let (universe, _temp_dir, ingest_service, _) =
+            setup_ingest_service(&["my-index-2"], &IngestApiConfig::default()).await;
+        let ingest_router = IngestRouterServiceClient::mock().into();
+        let ingest_api_handlers = ingest_api_handlers(
+            ingest_router,
+            ingest_service,
+            metastore,
+            IngestApiConfig::default(),
+        );
+        let resp = warp::test::request()
+            .path("/my-alias/ingest")
+            .method("POST")
+            .body(r#"{"id": 1, "message": "push"}"#)
+            .reply(&ingest_api_handlers)
+            .await;
+        assert_eq!(resp.status(), 200);
+
+        let resp = warp::test::request()
+            .path("/my-index-2/tail")
+            .method("GET")
+            .reply(&ingest_api_handlers)
+            .await;
+        assert_eq!(resp.status(), 200);
+        let fetch_response: FetchResponse = serde_json::from_slice(resp.body()).unwrap();
+        let doc_batch = fetch_response.doc_batch.unwrap();
+        assert_eq!(doc_batch.index_id, "my-index-2");
+        assert_eq!(doc_batch.num_docs(), 1);
+
+        universe.assert_quit().await;
+    }
+
+    #[tokio::test]
+    async fn test_ingest_api_reports_dropped_fields() {
+        let mut metastore = metastore_for_test();
+
+        let mut index_config = IndexConfig::for_test("my-index", "ram:///indexes/my-index");
+        index_config.doc_mapping.on_field_error = OnFieldError::Skip;
+        let create_index_request = CreateIndexRequest::try_from_index_config(index_config).unwrap();
+        metastore.create_index(create_index_request).await.unwrap();
+
+        let (universe, _temp_dir, ingest_service, _) =
+            setup_ingest_service(&["my-index"], &IngestApiConfig::default()).await;
+        let ingest_router = IngestRouterServiceClient::mock().into();
+        let ingest_api_handlers = ingest_api_handlers(
+            ingest_router,
+            ingest_service,
+            metastore,
+            IngestApiConfig::default(),
+        );
+        let payload = r#"
+            {"timestamp": 1724240908, "body": "foo", "response_time": 0.5}
+            {"timestamp": 1724240909, "body": "bar", "response_time": "slow"}
+            {"timestamp": 1724240910, "body": "baz", "response_time": "slow"}"#;
+        let resp = warp::test::request()
+            .path("/my-index/ingest")
+            .method("POST")
+            .body(payload)
+            .reply(&ingest_api_handlers)
+            .await;
+        assert_eq!(resp.status(), 200);
+        let ingest_response: IngestResponse = serde_json::from_slice(resp.body()).unwrap();
+        assert_eq!(ingest_response.num_docs_for_processing, 3);
+        assert_eq!(ingest_response.num_dropped_fields, 2);
+
+        universe.assert_quit().await;
+    }
+
+    #[tokio::test]
+    async fn test_count_dropped_fields_caches_doc_mapper() {
+        let mut mock_metastore = MetastoreServiceClient::mock();
+        mock_metastore
+            .expect_index_metadata()
+            .once()
+            .returning(|_request| {
+                let mut index_metadata =
+                    IndexMetadata::for_test("my-index", "ram:///indexes/my-index");
+                index_metadata.index_config.doc_mapping.on_field_error = OnFieldError::Skip;
+                Ok(IndexMetadataResponse::try_from_index_metadata(index_metadata).unwrap())
+            });
+        let mut metastore = MetastoreServiceClient::from(mock_metastore);
+        let doc_mapper_cache = DocMapperCache::default();
+        let docs = [Cow::Borrowed(
+            &br#"{"timestamp": 1724240909, "body": "bar", "response_time": "slow"}"#[..],
+        )];
+
+        for _ in 0..2 {
+            let num_dropped_fields =
+                count_dropped_fields(&doc_mapper_cache, &mut metastore, "my-index", &docs)
+                    .await
+                    .unwrap();
+            assert_eq!(num_dropped_fields, 1);
+        }
+    }
+
     #[tokio::test]
     async fn test_ingest_api_returns_200_when_ingest_ndjson_and_fetch() {
         let (universe, _temp_dir, ingest_service, _) =
             setup_ingest_service(&["my-index"], &IngestApiConfig::default()).await;
         let ingest_router = IngestRouterServiceClient::mock().into();
-        let ingest_api_handlers =
-            ingest_api_handlers(ingest_router, ingest_service, IngestApiConfig::default());
+        let ingest_api_handlers = ingest_api_handlers(
+            ingest_router,
+            ingest_service,
+            metastore_for_test(),
+            IngestApiConfig::default(),
+        );
         let payload = r#"
             {"id": 1, "message": "push"}
             {"id": 2, "message": "push"}
@@ -392,6 +975,100 @@ pub(crate) mod tests {
         universe.assert_quit().await;
     }
 
+    const INVALID_UTF8_PAYLOAD: &[u8] =
+        b"{\"id\": 1, \"message\": \"push\"}\n{\"id\": 2, \"message\": \"p\xffsh\"}";
+
+    #[tokio::test]
+    async fn test_ingest_api_rejects_invalid_utf8_by_default() {
+        let (universe, _temp_dir, ingest_service, _) =
+            setup_ingest_service(&["my-index"], &IngestApiConfig::default()).await;
+        let ingest_router = IngestRouterServiceClient::mock().into();
+        let ingest_api_handlers = ingest_api_handlers(
+            ingest_router,
+            ingest_service,
+            metastore_for_test(),
+            IngestApiConfig::default(),
+        );
+        for path in [
+            "/my-index/ingest",
+            "/my-index/ingest?on_invalid_utf8=reject",
+        ] {
+            let resp = warp::test::request()
+                .path(path)
+                .method("POST")
+                .body(INVALID_UTF8_PAYLOAD)
+                .reply(&ingest_api_handlers)
+                .await;
+            assert_eq!(resp.status(), 400);
+            let body = str::from_utf8(resp.body()).unwrap();
+            assert!(body.contains("document #1 contains invalid UTF-8 at byte offset 23"));
+        }
+        universe.assert_quit().await;
+    }
+
+    #[tokio::test]
+    async fn test_ingest_api_replaces_invalid_utf8() {
+        let (universe, _temp_dir, ingest_service, _) =
+            setup_ingest_service(&["my-index"], &IngestApiConfig::default()).await;
+        let ingest_router = IngestRouterServiceClient::mock().into();
+        let ingest_api_handlers = ingest_api_handlers(
+            ingest_router,
+            ingest_service,
+            metastore_for_test(),
+            IngestApiConfig::default(),
+        );
+        let resp = warp::test::request()
+            .path("/my-index/ingest?on_invalid_utf8=replace")
+            .method("POST")
+            .body(INVALID_UTF8_PAYLOAD)
+            .reply(&ingest_api_handlers)
+            .await;
+        assert_eq!(resp.status(), 200);
+        let ingest_response: IngestResponse = serde_json::from_slice(resp.body()).unwrap();
+        assert_eq!(ingest_response.num_docs_for_processing, 2);
+        assert_eq!(ingest_response.num_docs_with_replaced_utf8, 1);
+        assert_eq!(ingest_response.num_docs_skipped_invalid_utf8, 0);
+
+        let resp = warp::test::request()
+            .path("/my-index/tail")
+            .method("GET")
+            .reply(&ingest_api_handlers)
+            .await;
+        assert_eq!(resp.status(), 200);
+        let fetch_response: FetchResponse = serde_json::from_slice(resp.body()).unwrap();
+        let doc_batch = fetch_response.doc_batch.unwrap();
+        let doc_buffer = String::from_utf8_lossy(&doc_batch.doc_buffer);
+        assert_eq!(doc_buffer.matches('\u{FFFD}').count(), 1);
+
+        universe.assert_quit().await;
+    }
+
+    #[tokio::test]
+    async fn test_ingest_api_skips_invalid_utf8() {
+        let (universe, _temp_dir, ingest_service, _) =
+            setup_ingest_service(&["my-index"], &IngestApiConfig::default()).await;
+        let ingest_router = IngestRouterServiceClient::mock().into();
+        let ingest_api_handlers = ingest_api_handlers(
+            ingest_router,
+            ingest_service,
+            metastore_for_test(),
+            IngestApiConfig::default(),
+        );
+        let resp = warp::test::request()
+            .path("/my-index/ingest?on_invalid_utf8=skip")
+            .method("POST")
+            .body(INVALID_UTF8_PAYLOAD)
+            .reply(&ingest_api_handlers)
+            .await;
+        assert_eq!(resp.status(), 200);
+        let ingest_response: IngestResponse = serde_json::from_slice(resp.body()).unwrap();
+        assert_eq!(ingest_response.num_docs_for_processing, 1);
+        assert_eq!(ingest_response.num_docs_with_replaced_utf8, 0);
+        assert_eq!(ingest_response.num_docs_skipped_invalid_utf8, 1);
+
+        universe.assert_quit().await;
+    }
+
     #[tokio::test]
     async fn test_ingest_api_return_429_if_above_limits() {
         let config = IngestApiConfig {
@@ -401,8 +1078,12 @@ pub(crate) mod tests {
         let (universe, _temp_dir, ingest_service, _) =
             setup_ingest_service(&["my-index"], &config).await;
         let ingest_router = IngestRouterServiceClient::mock().into();
-        let ingest_api_handlers =
-            ingest_api_handlers(ingest_router, ingest_service, IngestApiConfig::default());
+        let ingest_api_handlers = ingest_api_handlers(
+            ingest_router,
+            ingest_service,
+            metastore_for_test(),
+            IngestApiConfig::default(),
+        );
         let resp = warp::test::request()
             .path("/my-index/ingest")
             .method("POST")
@@ -423,8 +1104,12 @@ pub(crate) mod tests {
         let (universe, _temp_dir, ingest_service, _) =
             setup_ingest_service(&["my-index"], &IngestApiConfig::default()).await;
         let ingest_router = IngestRouterServiceClient::mock().into();
-        let ingest_api_handlers =
-            ingest_api_handlers(ingest_router, ingest_service, config.clone());
+        let ingest_api_handlers = ingest_api_handlers(
+            ingest_router,
+            ingest_service,
+            metastore_for_test(),
+            config.clone(),
+        );
         let resp = warp::test::request()
             .path("/my-index/ingest")
             .method("POST")
@@ -444,6 +1129,7 @@ pub(crate) mod tests {
         let ingest_api_handlers = ingest_api_handlers(
             ingest_router,
             ingest_service_client,
+            metastore_for_test(),
             IngestApiConfig::default(),
         );
         let handle = tokio::spawn(async move {
@@ -493,6 +1179,7 @@ pub(crate) mod tests {
         let ingest_api_handlers = ingest_api_handlers(
             ingest_router,
             ingest_service_client,
+            metastore_for_test(),
             IngestApiConfig::default(),
         );
         let handle = tokio::spawn(async move {
@@ -533,4 +1220,179 @@ pub(crate) mod tests {
         handle.await.unwrap();
         universe.assert_quit().await;
     }
+
+    #[test]
+    fn test_estimate_lag() {
+        let checkpoint: SourceCheckpoint = [
+            (PartitionId::from("partition-1"), Position::offset(9u64)),
+            (PartitionId::from("partition-2"), Position::Beginning),
+        ]
+        .into_iter()
+        .collect();
+
+        let last_positions = SourceCheckpoint::default();
+        assert_eq!(estimate_lag(&checkpoint, &last_positions), Some(0));
+
+        let last_positions: SourceCheckpoint = [
+            (PartitionId::from("partition-1"), Position::offset(19u64)),
+            (PartitionId::from("partition-2"), Position::offset(4u64)),
+            (PartitionId::from("partition-3"), Position::offset(2u64)),
+            (PartitionId::from("partition-4"), Position::Beginning),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(estimate_lag(&checkpoint, &last_positions), Some(10 + 5 + 3));
+
+        let checkpoint: SourceCheckpoint = [(
+            PartitionId::from("partition-1"),
+            Position::from("not-an-offset".to_string()),
+        )]
+        .into_iter()
+        .collect();
+        assert!(estimate_lag(&checkpoint, &last_positions).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_ingest_status_endpoint() {
+        let (universe, _temp_dir, mut ingest_service, _) =
+            setup_ingest_service(&["test-index"], &IngestApiConfig::default()).await;
+        let mut doc_batch_builder = DocBatchBuilder::new("test-index".to_string());
+        doc_batch_builder.ingest_doc(Bytes::from_static(b"test-doc-0"));
+        doc_batch_builder.ingest_doc(Bytes::from_static(b"test-doc-1"));
+        doc_batch_builder.ingest_doc(Bytes::from_static(b"test-doc-2"));
+        let ingest_request = IngestRequest {
+            doc_batches: vec![doc_batch_builder.build()],
+            commit: CommitType::Auto.into(),
+        };
+        ingest_service.ingest(ingest_request).await.unwrap();
+
+        let mut index_metadata = IndexMetadata::for_test("test-index", "ram:///indexes/test-index");
+        index_metadata
+            .add_source(SourceConfig::ingest_api_default())
+            .unwrap();
+        index_metadata
+            .add_source(SourceConfig::ingest_v2_default())
+            .unwrap();
+        index_metadata
+            .add_source(SourceConfig::for_test("file-source", SourceParams::stdin()))
+            .unwrap();
+
+        let source_delta = SourceCheckpointDelta::from_partition_delta(
+            PartitionId::from(ShardId::from(1).as_str()),
+            Position::Beginning,
+            Position::offset(3u64),
+        )
+        .unwrap();
+        index_metadata
+            .checkpoint
+            .try_apply_delta(IndexCheckpointDelta {
+                source_id: INGEST_V2_SOURCE_ID.to_string(),
+                source_delta,
+            })
+            .unwrap();
+        index_metadata
+            .checkpoint
+            .try_apply_delta(IndexCheckpointDelta::for_test("file-source", 0..10))
+            .unwrap();
+
+        let index_metadata_clone = index_metadata.clone();
+        let mut mock_metastore = MetastoreServiceClient::mock();
+        mock_metastore.expect_index_metadata().returning(move |_| {
+            Ok(IndexMetadataResponse::try_from_index_metadata(&index_metadata_clone).unwrap())
+        });
+        mock_metastore
+            .expect_list_shards()
+            .returning(|list_shards_request| {
+                assert_eq!(list_shards_request.subrequests.len(), 1);
+                let subrequest = &list_shards_request.subrequests[0];
+                assert_eq!(subrequest.source_id, INGEST_V2_SOURCE_ID);
+
+                let shards = [1, 2]
+                    .into_iter()
+                    .map(|shard_id| Shard {
+                        index_uid: subrequest.index_uid.clone(),
+                        source_id: subrequest.source_id.clone(),
+                        shard_id: Some(ShardId::from(shard_id)),
+                        shard_state: ShardState::Open as i32,
+                        leader_id: "test-ingester".to_string(),
+                        ..Default::default()
+                    })
+                    .collect();
+                let subresponse = ListShardsSubresponse {
+                    index_uid: subrequest.index_uid.clone(),
+                    source_id: subrequest.source_id.clone(),
+                    shards,
+                };
+                Ok(ListShardsResponse {
+                    subresponses: vec![subresponse],
+                })
+            });
+        let mut mock_ingester = IngesterServiceClient::mock();
+        mock_ingester.expect_get_replication_positions().returning(
+            |get_replication_positions_request| {
+                assert_eq!(get_replication_positions_request.shards.len(), 1);
+                let shard_ids = &get_replication_positions_request.shards[0];
+                assert_eq!(shard_ids.shard_ids.len(), 2);
+
+                let replication_positions = [(1, 9u64), (2, 4u64)]
+                    .into_iter()
+                    .map(|(shard_id, offset)| ShardReplicationPosition {
+                        index_uid: shard_ids.index_uid.clone(),
+                        source_id: shard_ids.source_id.clone(),
+                        shard_id: Some(ShardId::from(shard_id)),
+                        replication_position_inclusive: Some(Position::offset(offset)),
+                    })
+                    .collect();
+                Ok(GetReplicationPositionsResponse {
+                    replication_positions,
+                })
+            },
+        );
+        let ingester_pool = IngesterPool::default();
+        ingester_pool.insert("test-ingester".into(), mock_ingester.into());
+
+        let ingest_status_handler = ingest_status_handler(
+            MetastoreServiceClient::from(mock_metastore),
+            ingest_service,
+            ingester_pool,
+        );
+        let resp = warp::test::request()
+            .path("/test-index/ingest-status")
+            .method("GET")
+            .reply(&ingest_status_handler)
+            .await;
+        assert_eq!(resp.status(), 200);
+
+        let ingest_statuses: Vec<SourceIngestStatus> = serde_json::from_slice(resp.body()).unwrap();
+        assert_eq!(ingest_statuses.len(), 3);
+
+        let ingest_api_status = &ingest_statuses[0];
+        assert_eq!(ingest_api_status.source_id, INGEST_API_SOURCE_ID);
+        assert!(ingest_api_status.checkpoint.is_empty());
+        assert_eq!(ingest_api_status.lag, Some(3));
+
+        let ingest_v2_status = &ingest_statuses[1];
+        assert_eq!(ingest_v2_status.source_id, INGEST_V2_SOURCE_ID);
+        assert_eq!(
+            &ingest_v2_status.checkpoint,
+            index_metadata
+                .checkpoint
+                .source_checkpoint(INGEST_V2_SOURCE_ID)
+                .unwrap()
+        );
+        assert_eq!(ingest_v2_status.lag, Some(6 + 5));
+
+        let file_source_status = &ingest_statuses[2];
+        assert_eq!(file_source_status.source_id, "file-source");
+        assert_eq!(
+            &file_source_status.checkpoint,
+            index_metadata
+                .checkpoint
+                .source_checkpoint("file-source")
+                .unwrap()
+        );
+        assert!(file_source_status.lag.is_none());
+
+        universe.assert_quit().await;
+    }
 }
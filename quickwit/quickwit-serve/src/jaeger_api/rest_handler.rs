@@ -460,6 +460,10 @@ mod tests {
                     errors: vec![],
                     aggregation: None,
                     scroll_id: None,
+                    num_hits_relation: 0,
+                    failed_splits: Vec::new(),
+                    partial: false,
+                    num_hits_per_index: HashMap::new(),
                 })
             });
         let mock_search_service = Arc::new(mock_search_service);
@@ -491,6 +495,10 @@ mod tests {
                     errors: vec![],
                     aggregation: None,
                     scroll_id: None,
+                    num_hits_relation: 0,
+                    failed_splits: Vec::new(),
+                    partial: false,
+                    num_hits_per_index: HashMap::new(),
                 })
             });
         let mock_search_service = Arc::new(mock_search_service);
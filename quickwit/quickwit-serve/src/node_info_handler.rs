@@ -23,6 +23,7 @@ use quickwit_config::NodeConfig;
 use serde_json::json;
 use warp::{Filter, Rejection};
 
+use crate::admin_runtime::run_on_admin_runtime;
 use crate::{with_arg, BuildInfo, RuntimeInfo};
 
 #[derive(utoipa::OpenApi)]
@@ -46,7 +47,9 @@ fn node_version_handler(
         .and(warp::path::end())
         .and(with_arg(build_info))
         .and(with_arg(runtime_info))
-        .then(get_version)
+        .then(|build_info, runtime_info| {
+            run_on_admin_runtime(get_version(build_info, runtime_info))
+        })
 }
 
 async fn get_version(
@@ -66,7 +69,7 @@ fn node_config_handler(
     warp::path("config")
         .and(warp::path::end())
         .and(with_arg(config))
-        .then(get_config)
+        .then(|config| run_on_admin_runtime(get_config(config)))
 }
 
 async fn get_config(config: Arc<NodeConfig>) -> impl warp::Reply {
@@ -98,14 +101,22 @@ mod tests {
         let info_json: JsonValue = serde_json::from_slice(resp.body()).unwrap();
         let build_info_json = info_json.get("build").unwrap();
         let expected_build_info_json = serde_json::json!({
+            "build_date": build_info.build_date,
+            "build_target": build_info.build_target,
             "commit_date": build_info.commit_date,
+            "commit_hash": build_info.commit_hash,
+            "commit_short_hash": build_info.commit_short_hash,
             "version": build_info.version,
         });
         assert_json_include!(actual: build_info_json, expected: expected_build_info_json);
 
         let runtime_info_json = info_json.get("runtime").unwrap();
         let expected_runtime_info_json = serde_json::json!({
+            "os": std::env::consts::OS,
+            "arch": std::env::consts::ARCH,
             "num_cpus_physical": runtime_info.num_cpus_physical,
+            "num_threads_blocking": runtime_info.num_threads_blocking,
+            "num_threads_non_blocking": runtime_info.num_threads_non_blocking,
         });
         assert_json_include!(
             actual: runtime_info_json,
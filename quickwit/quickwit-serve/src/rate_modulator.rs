@@ -17,27 +17,41 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
+use std::sync::{Arc, RwLock};
 use std::time::Duration;
 
 use quickwit_common::tower::{ConstantRate, Rate};
+use quickwit_config::{IngestApiConfig, ThrottlingStep};
 use quickwit_ingest::MemoryCapacity;
 
+/// Throttling curve shared between the rate modulator and the config reloader, so that it can be
+/// updated while the node is running.
+pub type SharedThrottlingCurve = Arc<RwLock<Vec<ThrottlingStep>>>;
+
 #[derive(Clone)]
 pub struct RateModulator<R> {
     rate_estimator: R,
     memory_capacity: MemoryCapacity,
+    disk_capacity: MemoryCapacity,
     min_rate: ConstantRate,
+    throttling_curve: SharedThrottlingCurve,
 }
 
 impl<R> RateModulator<R>
 where R: Rate
 {
-    /// Creates a new [`RateModulator`] instance.
+    /// Creates a new [`RateModulator`] instance. The rate is modulated according to the highest
+    /// of the memory and disk usage ratios.
     ///
     /// # Panics
     ///
     /// Panics if `rate_estimator` and `min_rate` have different periods.
-    pub fn new(rate_estimator: R, memory_capacity: MemoryCapacity, min_rate: ConstantRate) -> Self {
+    pub fn new(
+        rate_estimator: R,
+        memory_capacity: MemoryCapacity,
+        disk_capacity: MemoryCapacity,
+        min_rate: ConstantRate,
+    ) -> Self {
         assert_eq!(
             rate_estimator.period(),
             min_rate.period(),
@@ -47,38 +61,150 @@ where R: Rate
         Self {
             rate_estimator,
             memory_capacity,
+            disk_capacity,
             min_rate,
+            throttling_curve: Arc::new(RwLock::new(IngestApiConfig::default_throttling_curve())),
         }
     }
+
+    /// Sets the curve mapping the usage ratio to a multiplier of the work rate. The curve is
+    /// expected to be validated, see [`IngestApiConfig`].
+    pub fn with_throttling_curve(mut self, throttling_curve: SharedThrottlingCurve) -> Self {
+        self.throttling_curve = throttling_curve;
+        self
+    }
+
+    fn multiplier(&self, usage_ratio: f64) -> f64 {
+        self.throttling_curve
+            .read()
+            .expect("the lock should not be poisoned")
+            .iter()
+            .rev()
+            .find(|step| step.usage_ratio <= usage_ratio)
+            .map(|step| step.multiplier)
+            .unwrap_or(1.0)
+    }
 }
 
 impl<R> Rate for RateModulator<R>
 where R: Rate
 {
     fn work(&self) -> u64 {
-        let memory_usage_ratio = self.memory_capacity.usage_ratio();
+        let usage_ratio = self
+            .memory_capacity
+            .usage_ratio()
+            .max(self.disk_capacity.usage_ratio());
         let work = self.rate_estimator.work().max(self.min_rate.work());
-
-        if memory_usage_ratio < 0.25 {
-            work * 2
-        } else if memory_usage_ratio > 0.99 {
-            work / 32
-        } else if memory_usage_ratio > 0.98 {
-            work / 16
-        } else if memory_usage_ratio > 0.95 {
-            work / 8
-        } else if memory_usage_ratio > 0.90 {
-            work / 4
-        } else if memory_usage_ratio > 0.80 {
-            work / 2
-        } else if memory_usage_ratio > 0.70 {
-            work * 2 / 3
-        } else {
-            work
-        }
+        (work as f64 * self.multiplier(usage_ratio)) as u64
     }
 
     fn period(&self) -> Duration {
         self.rate_estimator.period()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn capacity_with_usage_ratio(usage_ratio: f64) -> MemoryCapacity {
+        let max_capacity = 1_000;
+        let capacity = MemoryCapacity::new(max_capacity);
+        capacity.reset_capacity(max_capacity - (max_capacity as f64 * usage_ratio) as usize);
+        capacity
+    }
+
+    #[test]
+    fn test_rate_modulator_considers_memory_and_disk_usage() {
+        let rate_estimator = ConstantRate::new(1_000, Duration::from_secs(1));
+        let min_rate = ConstantRate::new(10, Duration::from_secs(1));
+        {
+            let rate_modulator = RateModulator::new(
+                rate_estimator,
+                capacity_with_usage_ratio(0.1),
+                capacity_with_usage_ratio(0.1),
+                min_rate,
+            );
+            assert_eq!(rate_modulator.work(), 2_000);
+        }
+        {
+            let rate_modulator = RateModulator::new(
+                rate_estimator,
+                capacity_with_usage_ratio(0.1),
+                capacity_with_usage_ratio(0.995),
+                min_rate,
+            );
+            assert_eq!(rate_modulator.work(), 1_000 / 32);
+        }
+        {
+            let rate_modulator = RateModulator::new(
+                rate_estimator,
+                capacity_with_usage_ratio(0.85),
+                capacity_with_usage_ratio(0.5),
+                min_rate,
+            );
+            assert_eq!(rate_modulator.work(), 1_000 / 2);
+        }
+        {
+            let rate_modulator = RateModulator::new(
+                rate_estimator,
+                capacity_with_usage_ratio(0.0),
+                capacity_with_usage_ratio(0.92),
+                min_rate,
+            );
+            assert_eq!(rate_modulator.work(), 1_000 / 4);
+        }
+    }
+
+    #[test]
+    fn test_rate_modulator_with_throttling_curve() {
+        let throttling_curve = vec![
+            ThrottlingStep {
+                usage_ratio: 0.5,
+                multiplier: 0.5,
+            },
+            ThrottlingStep {
+                usage_ratio: 0.9,
+                multiplier: 0.1,
+            },
+        ];
+        let rate_estimator = ConstantRate::new(1_000, Duration::from_secs(1));
+        let min_rate = ConstantRate::new(10, Duration::from_secs(1));
+
+        for (usage_ratio, expected_work) in [(0.1, 1_000), (0.5, 500), (0.7, 500), (0.95, 100)] {
+            let rate_modulator = RateModulator::new(
+                rate_estimator,
+                capacity_with_usage_ratio(usage_ratio),
+                capacity_with_usage_ratio(0.0),
+                min_rate,
+            )
+            .with_throttling_curve(Arc::new(RwLock::new(throttling_curve.clone())));
+            assert_eq!(
+                rate_modulator.work(),
+                expected_work,
+                "usage ratio: {usage_ratio}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_rate_modulator_with_updated_throttling_curve() {
+        let rate_estimator = ConstantRate::new(1_000, Duration::from_secs(1));
+        let min_rate = ConstantRate::new(10, Duration::from_secs(1));
+        let throttling_curve = Arc::new(RwLock::new(Vec::new()));
+        let rate_modulator = RateModulator::new(
+            rate_estimator,
+            capacity_with_usage_ratio(0.7),
+            capacity_with_usage_ratio(0.0),
+            min_rate,
+        )
+        .with_throttling_curve(throttling_curve.clone());
+        assert_eq!(rate_modulator.work(), 1_000);
+
+        *throttling_curve.write().unwrap() = vec![ThrottlingStep {
+            usage_ratio: 0.5,
+            multiplier: 0.5,
+        }];
+        assert_eq!(rate_modulator.work(), 500);
+    }
+}
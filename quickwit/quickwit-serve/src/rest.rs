@@ -32,20 +32,25 @@ use tower_http::cors::CorsLayer;
 use tracing::{error, info};
 use warp::{redirect, Filter, Rejection, Reply};
 
+use crate::admin_runtime::{run_on_admin_runtime, AdminRuntimeRestService};
 use crate::cluster_api::cluster_handler;
+use crate::config_reload_api::config_reload_handler;
 use crate::debugging_api::debugging_handler;
 use crate::delete_task_api::delete_task_api_handlers;
 use crate::elasticsearch_api::elastic_api_handlers;
 use crate::health_check_api::health_check_handlers;
 use crate::index_api::index_management_handlers;
-use crate::indexing_api::indexing_get_handler;
-use crate::ingest_api::ingest_api_handlers;
+use crate::indexing_api::{indexing_get_handler, pause_source_handler, resume_source_handler};
+use crate::ingest_api::{ingest_api_handlers, ingest_status_handler};
 use crate::jaeger_api::jaeger_api_handlers;
 use crate::json_api_response::{ApiError, JsonApiResponse};
 use crate::metrics_api::metrics_handler;
 use crate::node_info_handler::node_info_handler;
 use crate::otlp_api::otlp_ingest_api_handlers;
-use crate::search_api::{search_get_handler, search_post_handler, search_stream_handler};
+use crate::search_api::{
+    doc_by_address_handler, list_facets_handler, search_batch_handler, search_get_handler,
+    search_post_handler, search_stream_handler, validate_query_batch_handler,
+};
 use crate::ui_handler::ui_handler;
 use crate::{BodyFormat, BuildInfo, QuickwitServices, RuntimeInfo};
 
@@ -135,6 +140,7 @@ pub(crate) async fn start_rest_server(
         )
         .layer(cors)
         .service(warp_service);
+    let service = AdminRuntimeRestService::new(service, tokio::runtime::Handle::current());
 
     info!(
         rest_listen_addr=?rest_listen_addr,
@@ -147,12 +153,15 @@ pub(crate) async fn start_rest_server(
     //
     // See more of the discussion here:
     // https://github.com/hyperium/hyper/issues/2386
-    let serve_fut = async move {
+    //
+    // The listener is driven by the admin runtime so that health checks keep being answered when
+    // the node is saturated. See `AdminRuntimeRestService`.
+    let serve_fut = run_on_admin_runtime(async move {
         tokio::select! {
              res = hyper::Server::bind(&rest_listen_addr).serve(Shared::new(service)) => { res }
              _ = shutdown_signal => { Ok(()) }
         }
-    };
+    });
 
     let (serve_res, _trigger_res) = tokio::join!(serve_fut, readiness_trigger);
     serve_res?;
@@ -170,21 +179,50 @@ fn api_v1_routes(
                 RuntimeInfo::get(),
                 quickwit_services.node_config.clone(),
             ))
+            .or(config_reload_handler(
+                quickwit_services.config_reloader_opt.clone(),
+            ))
             .or(indexing_get_handler(
                 quickwit_services.indexing_service_opt.clone(),
             ))
+            .or(pause_source_handler(
+                quickwit_services.metastore_client.clone(),
+                quickwit_services.control_plane_service.clone(),
+            ))
+            .or(resume_source_handler(
+                quickwit_services.metastore_client.clone(),
+                quickwit_services.control_plane_service.clone(),
+            ))
             .or(search_get_handler(quickwit_services.search_service.clone()))
             .or(search_post_handler(
                 quickwit_services.search_service.clone(),
             ))
+            .or(search_batch_handler(
+                quickwit_services.search_service.clone(),
+            ))
             .or(search_stream_handler(
                 quickwit_services.search_service.clone(),
             ))
+            .or(doc_by_address_handler(
+                quickwit_services.search_service.clone(),
+            ))
+            .or(validate_query_batch_handler(
+                quickwit_services.search_service.clone(),
+            ))
+            .or(list_facets_handler(
+                quickwit_services.search_service.clone(),
+            ))
             .or(ingest_api_handlers(
                 quickwit_services.ingest_router_service.clone(),
                 quickwit_services.ingest_service.clone(),
+                quickwit_services.metastore_client.clone(),
                 quickwit_services.node_config.ingest_api_config.clone(),
             ))
+            .or(ingest_status_handler(
+                quickwit_services.metastore_client.clone(),
+                quickwit_services.ingest_service.clone(),
+                quickwit_services.ingester_pool.clone(),
+            ))
             .or(otlp_ingest_api_handlers(
                 quickwit_services.otlp_logs_service_opt.clone(),
                 quickwit_services.otlp_traces_service_opt.clone(),
@@ -204,6 +242,7 @@ fn api_v1_routes(
                 quickwit_services.search_service.clone(),
                 quickwit_services.ingest_service.clone(),
                 quickwit_services.ingest_router_service.clone(),
+                quickwit_services.metastore_client.clone(),
             )),
     )
 }
@@ -345,7 +384,7 @@ mod tests {
     use quickwit_cluster::{create_cluster_for_test, ChannelTransport};
     use quickwit_config::NodeConfig;
     use quickwit_index_management::IndexService;
-    use quickwit_ingest::{IngestApiService, IngestServiceClient};
+    use quickwit_ingest::{IngestApiService, IngestServiceClient, IngesterPool};
     use quickwit_proto::control_plane::ControlPlaneServiceClient;
     use quickwit_proto::ingest::router::IngestRouterServiceClient;
     use quickwit_proto::metastore::MetastoreServiceClient;
@@ -602,6 +641,7 @@ mod tests {
             index_manager: index_service,
             ingest_service: ingest_service_client(),
             ingester_service_opt: None,
+            ingester_pool: IngesterPool::default(),
             ingest_router_service: IngestRouterServiceClient::from(
                 IngestRouterServiceClient::mock(),
             ),
@@ -613,6 +653,7 @@ mod tests {
             node_config: Arc::new(node_config.clone()),
             search_service: Arc::new(MockSearchService::new()),
             jaeger_service_opt: None,
+            config_reloader_opt: None,
         };
 
         let handler = api_v1_routes(Arc::new(quickwit_services))
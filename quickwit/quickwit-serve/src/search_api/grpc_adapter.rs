@@ -120,6 +120,29 @@ impl grpc::SearchService for GrpcSearchAdapter {
         convert_to_grpc_result(leaf_search_result)
     }
 
+    #[instrument(skip(self, request))]
+    async fn root_list_facets(
+        &self,
+        request: tonic::Request<quickwit_proto::search::ListFacetsRequest>,
+    ) -> Result<tonic::Response<quickwit_proto::search::ListFacetsResponse>, tonic::Status> {
+        set_parent_span_from_request_metadata(request.metadata());
+        let list_facets_request = request.into_inner();
+        let list_facets_result = self.0.root_list_facets(list_facets_request).await;
+        convert_to_grpc_result(list_facets_result)
+    }
+
+    #[instrument(skip(self, request))]
+    async fn leaf_list_facets(
+        &self,
+        request: tonic::Request<quickwit_proto::search::LeafListFacetsRequest>,
+    ) -> Result<tonic::Response<quickwit_proto::search::LeafListFacetsResponse>, tonic::Status>
+    {
+        set_parent_span_from_request_metadata(request.metadata());
+        let leaf_list_facets_request = request.into_inner();
+        let leaf_list_facets_result = self.0.leaf_list_facets(leaf_list_facets_request).await;
+        convert_to_grpc_result(leaf_list_facets_result)
+    }
+
     async fn scroll(
         &self,
         request: tonic::Request<quickwit_proto::search::ScrollRequest>,
@@ -18,14 +18,17 @@
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
 mod grpc_adapter;
+mod relative_timestamp;
 mod rest_handler;
+mod search_after_cursor;
 
 pub use self::grpc_adapter::GrpcSearchAdapter;
-pub(crate) use self::rest_handler::{extract_index_id_patterns, extract_index_id_patterns_default};
 pub use self::rest_handler::{
-    search_get_handler, search_post_handler, search_request_from_api_request,
-    search_stream_handler, SearchApi, SearchRequestQueryString, SortBy,
+    doc_by_address_handler, list_facets_handler, search_batch_handler, search_get_handler,
+    search_post_handler, search_request_from_api_request, search_stream_handler,
+    validate_query_batch_handler, SearchApi, SearchRequestQueryString, SortBy,
 };
+pub(crate) use self::rest_handler::{extract_index_id_patterns, extract_index_id_patterns_default};
 
 #[cfg(test)]
 mod tests {
@@ -149,4 +152,59 @@ mod tests {
         );
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_search_grpc_messages_above_default_limit() -> anyhow::Result<()> {
+        // Tonic limits the size of decoded messages to 4MiB by default.
+        let max_message_size = ByteSize::mib(10);
+        let payload = "a".repeat(ByteSize::mib(5).0 as usize);
+
+        let mut mock_search_service = MockSearchService::new();
+        let hit_json = payload.clone();
+        mock_search_service
+            .expect_root_search()
+            .withf(|search_request| search_request.query_ast.len() >= ByteSize::mib(5).0 as usize)
+            .returning(move |_| {
+                Ok(quickwit_proto::search::SearchResponse {
+                    hits: vec![quickwit_proto::search::Hit {
+                        json: hit_json.clone(),
+                        ..Default::default()
+                    }],
+                    num_hits: 1,
+                    ..Default::default()
+                })
+            });
+        let search_grpc_adapter =
+            GrpcSearchAdapter::from(Arc::new(mock_search_service) as Arc<dyn SearchService>);
+        let grpc_port = quickwit_common::net::find_available_tcp_port()?;
+        let grpc_addr: SocketAddr = ([127, 0, 0, 1], grpc_port).into();
+        tokio::spawn(async move {
+            Server::builder()
+                .add_service(
+                    SearchServiceServer::new(search_grpc_adapter)
+                        .max_decoding_message_size(max_message_size.0 as usize)
+                        .max_encoding_message_size(max_message_size.0 as usize),
+                )
+                .serve(grpc_addr)
+                .await?;
+            Result::<_, anyhow::Error>::Ok(())
+        });
+        while tokio::net::TcpStream::connect(grpc_addr).await.is_err() {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        let search_request = quickwit_proto::search::SearchRequest {
+            index_id_patterns: vec!["test-index".to_string()],
+            query_ast: payload.clone(),
+            ..Default::default()
+        };
+        let mut search_client = create_search_client_from_grpc_addr(grpc_addr, max_message_size);
+        let search_response = search_client.root_search(search_request.clone()).await?;
+        assert_eq!(search_response.hits.len(), 1);
+        assert_eq!(search_response.hits[0].json.len(), payload.len());
+
+        // A client configured with a lower limit refuses to send the request.
+        let mut search_client = create_search_client_from_grpc_addr(grpc_addr, ByteSize::mib(4));
+        search_client.root_search(search_request).await.unwrap_err();
+        Ok(())
+    }
 }
@@ -0,0 +1,226 @@
+// Copyright (C) 2024 Quickwit, Inc.
+//
+// Quickwit is offered under the AGPL v3.0 and as commercial software.
+// For commercial licensing, contact us at hello@quickwit.io.
+//
+// AGPL:
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as
+// published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use serde::{de, Deserialize, Deserializer};
+use time::{Duration, OffsetDateTime, Time};
+
+/// Timestamp passed to the search REST API, either as a number of seconds since the Unix epoch or
+/// as a relative time expression.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TimestampOrExpr {
+    Timestamp(i64),
+    Expr(String),
+}
+
+/// Deserializes an optional timestamp expressed in seconds or as a relative time expression such
+/// as `now-15m`, which is resolved against the clock of the server.
+pub(crate) fn deserialize_timestamp_opt<'de, D>(deserializer: D) -> Result<Option<i64>, D::Error>
+where D: Deserializer<'de> {
+    let Some(timestamp_or_expr) = Option::<TimestampOrExpr>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+    let timestamp = match timestamp_or_expr {
+        TimestampOrExpr::Timestamp(timestamp) => timestamp,
+        TimestampOrExpr::Expr(expr) => {
+            parse_timestamp_expr(&expr, OffsetDateTime::now_utc()).map_err(de::Error::custom)?
+        }
+    };
+    Ok(Some(timestamp))
+}
+
+/// Parses a timestamp expressed in seconds or as a relative time expression, and returns it in
+/// seconds.
+///
+/// Relative time expressions start with `now`, followed by any number of offsets (`+<duration>`
+/// or `-<duration>`, e.g. `-15m`) and roundings (`/<unit>`, e.g. `/d`). Durations are parsed with
+/// `humantime`, and roundings are applied in UTC and round down to the start of the second (`s`),
+/// minute (`m`), hour (`h`), day (`d`), week (`w`), month (`M`), or year (`y`).
+fn parse_timestamp_expr(expr: &str, now: OffsetDateTime) -> Result<i64, String> {
+    let expr = expr.trim();
+    if let Ok(timestamp) = expr.parse::<i64>() {
+        return Ok(timestamp);
+    }
+    let Some(mut operations) = expr.strip_prefix("now") else {
+        return Err(format!(
+            "invalid timestamp `{expr}`: expected a number of seconds or a relative time \
+             expression starting with `now`"
+        ));
+    };
+    let mut date_time = now;
+
+    while let Some(operator) = operations.chars().next() {
+        let remaining = &operations[operator.len_utf8()..];
+        let operand_len = remaining.find(['+', '-', '/']).unwrap_or(remaining.len());
+        let operand = &remaining[..operand_len];
+        operations = &remaining[operand_len..];
+
+        date_time = match operator {
+            '+' | '-' => {
+                let std_duration = humantime::parse_duration(operand).map_err(|error| {
+                    format!("invalid duration `{operand}` in timestamp `{expr}`: {error}")
+                })?;
+                let duration = Duration::try_from(std_duration)
+                    .map_err(|_| format!("duration `{operand}` in timestamp `{expr}` overflows"))?;
+                let date_time_opt = if operator == '+' {
+                    date_time.checked_add(duration)
+                } else {
+                    date_time.checked_sub(duration)
+                };
+                date_time_opt
+                    .ok_or_else(|| format!("timestamp `{expr}` is out of the supported range"))?
+            }
+            '/' => round_down(date_time, operand).ok_or_else(|| {
+                format!("invalid rounding unit `{operand}` in timestamp `{expr}`")
+            })?,
+            _ => {
+                return Err(format!(
+                    "invalid timestamp `{expr}`: expected `+`, `-`, or `/` after `now`"
+                ));
+            }
+        };
+    }
+    Ok(date_time.unix_timestamp())
+}
+
+/// Rounds down a datetime to the start of the given calendar unit.
+fn round_down(date_time: OffsetDateTime, unit: &str) -> Option<OffsetDateTime> {
+    let date_time = date_time.to_offset(time::UtcOffset::UTC);
+    let rounded_date_time = match unit {
+        "s" => date_time.replace_nanosecond(0).ok()?,
+        "m" => {
+            date_time.replace_time(Time::from_hms(date_time.hour(), date_time.minute(), 0).ok()?)
+        }
+        "h" => date_time.replace_time(Time::from_hms(date_time.hour(), 0, 0).ok()?),
+        "d" => date_time.replace_time(Time::MIDNIGHT),
+        "w" => {
+            let days_from_monday = date_time.weekday().number_days_from_monday();
+            date_time.replace_time(Time::MIDNIGHT) - Duration::days(days_from_monday as i64)
+        }
+        "M" => date_time.replace_time(Time::MIDNIGHT).replace_day(1).ok()?,
+        "y" => date_time
+            .replace_time(Time::MIDNIGHT)
+            .replace_date(time::Date::from_ordinal_date(date_time.year(), 1).ok()?),
+        _ => return None,
+    };
+    Some(rounded_date_time)
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_timestamp_expr() {
+        // Monday, January 15th 2024.
+        let now = datetime!(2024-01-15 13:45:30.5 UTC);
+        let now_secs = now.unix_timestamp();
+
+        assert_eq!(parse_timestamp_expr("1705326330", now).unwrap(), 1705326330);
+        assert_eq!(parse_timestamp_expr("now", now).unwrap(), now_secs);
+        assert_eq!(
+            parse_timestamp_expr(" now-15m ", now).unwrap(),
+            now_secs - 15 * 60
+        );
+        assert_eq!(
+            parse_timestamp_expr("now+1h", now).unwrap(),
+            now_secs + 3600
+        );
+        assert_eq!(
+            parse_timestamp_expr("now-1d-12h", now).unwrap(),
+            now_secs - 36 * 3600
+        );
+        assert_eq!(
+            parse_timestamp_expr("now/d", now).unwrap(),
+            datetime!(2024-01-15 00:00:00 UTC).unix_timestamp()
+        );
+        assert_eq!(
+            parse_timestamp_expr("now-1d/d", now).unwrap(),
+            datetime!(2024-01-14 00:00:00 UTC).unix_timestamp()
+        );
+        assert_eq!(
+            parse_timestamp_expr("now/h+30m", now).unwrap(),
+            datetime!(2024-01-15 13:30:00 UTC).unix_timestamp()
+        );
+        assert_eq!(
+            parse_timestamp_expr("now/m", now).unwrap(),
+            datetime!(2024-01-15 13:45:00 UTC).unix_timestamp()
+        );
+        assert_eq!(
+            parse_timestamp_expr("now+3d/w", now).unwrap(),
+            datetime!(2024-01-15 00:00:00 UTC).unix_timestamp()
+        );
+        assert_eq!(
+            parse_timestamp_expr("now/M", now).unwrap(),
+            datetime!(2024-01-01 00:00:00 UTC).unix_timestamp()
+        );
+        assert_eq!(
+            parse_timestamp_expr("now-1M/y", now).unwrap(),
+            datetime!(2023-01-01 00:00:00 UTC).unix_timestamp()
+        );
+    }
+
+    #[test]
+    fn test_parse_timestamp_expr_invalid() {
+        let now = datetime!(2024-01-15 13:45:30 UTC);
+
+        let error = parse_timestamp_expr("yesterday", now).unwrap_err();
+        assert!(error.contains("expected a number of seconds or a relative time expression"));
+
+        let error = parse_timestamp_expr("now-15x", now).unwrap_err();
+        assert!(error.contains("invalid duration `15x`"));
+
+        let error = parse_timestamp_expr("now/q", now).unwrap_err();
+        assert!(error.contains("invalid rounding unit `q`"));
+
+        let error = parse_timestamp_expr("now*2", now).unwrap_err();
+        assert!(error.contains("expected `+`, `-`, or `/` after `now`"));
+    }
+
+    #[test]
+    fn test_deserialize_timestamp_opt() {
+        #[derive(Deserialize)]
+        struct TimestampRange {
+            #[serde(default, deserialize_with = "deserialize_timestamp_opt")]
+            start_timestamp: Option<i64>,
+            #[serde(default, deserialize_with = "deserialize_timestamp_opt")]
+            end_timestamp: Option<i64>,
+        }
+        let timestamp_range: TimestampRange =
+            serde_qs::from_str("start_timestamp=now-1h&end_timestamp=1705326330").unwrap();
+        let now_secs = OffsetDateTime::now_utc().unix_timestamp();
+        let start_timestamp = timestamp_range.start_timestamp.unwrap();
+        assert!((now_secs - 3600 - 5..=now_secs - 3600).contains(&start_timestamp));
+        assert_eq!(timestamp_range.end_timestamp, Some(1705326330));
+
+        let timestamp_range: TimestampRange =
+            serde_json::from_str(r#"{"start_timestamp": 1705326330, "end_timestamp": "now"}"#)
+                .unwrap();
+        assert_eq!(timestamp_range.start_timestamp, Some(1705326330));
+        assert!(
+            timestamp_range.end_timestamp.unwrap() <= OffsetDateTime::now_utc().unix_timestamp()
+        );
+
+        let timestamp_range: TimestampRange = serde_qs::from_str("").unwrap();
+        assert!(timestamp_range.start_timestamp.is_none());
+        assert!(timestamp_range.end_timestamp.is_none());
+    }
+}
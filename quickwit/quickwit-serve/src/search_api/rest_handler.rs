@@ -18,6 +18,7 @@
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
 use std::convert::TryFrom;
+use std::str::FromStr;
 use std::sync::Arc;
 
 use futures::stream::StreamExt;
@@ -25,10 +26,13 @@ use hyper::header::HeaderValue;
 use hyper::HeaderMap;
 use percent_encoding::percent_decode_str;
 use quickwit_config::validate_index_id_pattern;
-use quickwit_proto::search::{CountHits, OutputFormat, SortField, SortOrder};
+use quickwit_proto::search::{
+    CountHits, FacetCount, ListFacetsRequest, ListFacetsResponse, OutputFormat, SearchResponse,
+    SortField, SortOrder,
+};
 use quickwit_proto::ServiceError;
-use quickwit_query::query_ast::query_ast_from_user_text;
-use quickwit_search::{SearchError, SearchResponseRest, SearchService};
+use quickwit_query::query_ast::{query_ast_from_user_text, QueryAst};
+use quickwit_search::{GlobalDocAddress, SearchError, SearchResponseRest, SearchService};
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value as JsonValue;
 use tracing::info;
@@ -36,16 +40,32 @@ use warp::hyper::header::CONTENT_TYPE;
 use warp::hyper::StatusCode;
 use warp::{reply, Filter, Rejection, Reply};
 
+use super::relative_timestamp::deserialize_timestamp_opt;
+use super::search_after_cursor::{decode_search_after_cursor, encode_search_after_cursor};
+use crate::elasticsearch_api::TrackTotalHits;
 use crate::json_api_response::make_json_api_response;
 use crate::simple_list::{from_simple_list, to_simple_list};
 use crate::{with_arg, BodyFormat};
 
 #[derive(utoipa::OpenApi)]
 #[openapi(
-    paths(search_get_handler, search_post_handler, search_stream_handler,),
+    paths(
+        search_get_handler,
+        search_post_handler,
+        search_batch_handler,
+        search_stream_handler,
+        doc_by_address_handler,
+        validate_query_batch_handler,
+        list_facets_handler,
+    ),
     components(schemas(
         BodyFormat,
+        FacetCount,
+        ListFacetsResponse,
         OutputFormat,
+        QueryValidationRequest,
+        QueryValidationResult,
+        SearchBatchResult,
         SearchRequestQueryString,
         SearchResponseRest,
         SortBy,
@@ -149,6 +169,10 @@ fn default_max_hits() -> u64 {
     20
 }
 
+fn default_match_all_if_empty_query() -> bool {
+    true
+}
+
 // Deserialize a string field and return and error if it's empty.
 // We have 2 issues with this implementation:
 // - this is not generic and thus nos sustainable and we may need to
@@ -170,9 +194,7 @@ where D: Deserializer<'de> {
 
 /// This struct represents the QueryString passed to
 /// the rest API.
-#[derive(
-    Debug, Default, Eq, PartialEq, Serialize, Deserialize, utoipa::IntoParams, utoipa::ToSchema,
-)]
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize, utoipa::IntoParams, utoipa::ToSchema)]
 #[into_params(parameter_in = Query)]
 #[serde(deny_unknown_fields)]
 pub struct SearchRequestQueryString {
@@ -199,11 +221,17 @@ pub struct SearchRequestQueryString {
     #[serde(serialize_with = "to_simple_list")]
     pub snippet_fields: Option<Vec<String>>,
     /// If set, restrict search to documents with a `timestamp >= start_timestamp`.
-    /// This timestamp is expressed in seconds.
+    /// This timestamp is expressed in seconds or as a relative time expression, e.g. `now-15m`.
+    #[param(value_type = Option<String>)]
+    #[schema(value_type = Option<String>)]
+    #[serde(default, deserialize_with = "deserialize_timestamp_opt")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub start_timestamp: Option<i64>,
     /// If set, restrict search to documents with a `timestamp < end_timestamp``.
-    /// This timestamp is expressed in seconds.
+    /// This timestamp is expressed in seconds or as a relative time expression, e.g. `now/d`.
+    #[param(value_type = Option<String>)]
+    #[schema(value_type = Option<String>)]
+    #[serde(default, deserialize_with = "deserialize_timestamp_opt")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub end_timestamp: Option<i64>,
     /// Maximum number of hits to return (by default 20).
@@ -226,11 +254,74 @@ pub struct SearchRequestQueryString {
     #[serde(skip_serializing_if = "SortBy::is_empty")]
     #[param(value_type = String)]
     pub sort_by: SortBy,
+    /// Opaque cursor returned as `search_after` by a previous search. If set, only the hits
+    /// following the last hit returned by that search are returned. The sort fields must be the
+    /// same as those of the previous search.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search_after: Option<String>,
     #[param(value_type = bool)]
     #[schema(value_type = bool)]
     #[serde(with = "count_hits_from_bool")]
     #[serde(default = "count_hits_from_bool::default")]
     pub count_all: CountHits,
+    /// Counts hits exactly (`true`), underestimates them (`false`), or counts them exactly up to
+    /// the given number of hits. Takes precedence over `count_all` when set.
+    #[param(value_type = Option<String>)]
+    #[schema(value_type = Option<String>)]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub track_total_hits: Option<TrackTotalHits>,
+    /// If set, the search returns the results of the splits that could be searched instead of
+    /// failing when some splits cannot be searched.
+    #[serde(default)]
+    pub allow_partial_results: bool,
+    /// If true (default), an empty or whitespace-only query matches all documents. Otherwise,
+    /// it is parsed as a regular query.
+    #[serde(default = "default_match_all_if_empty_query")]
+    pub match_all_if_empty_query: bool,
+    /// If set, only these splits are searched. The time range and tags of the query are not used
+    /// to prune them.
+    #[serde(default)]
+    #[serde(deserialize_with = "from_simple_list")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "to_simple_list")]
+    pub split_ids: Option<Vec<String>>,
+    /// If set, terms aggregations are executed even on fields whose number of distinct values
+    /// exceeds the searcher `max_terms_aggregation_cardinality` limit.
+    #[serde(default)]
+    pub allow_high_cardinality_aggregations: bool,
+    /// If set, the response lists the publish timestamp of the split of each hit, which lets
+    /// clients order or deduplicate hits across reprocessings.
+    #[serde(default)]
+    pub include_split_publish_timestamp: bool,
+}
+
+// The default values must match the serde defaults, so that a default request behaves like a
+// request omitting all its optional parameters.
+impl Default for SearchRequestQueryString {
+    fn default() -> Self {
+        Self {
+            query: String::new(),
+            aggs: None,
+            search_fields: None,
+            snippet_fields: None,
+            start_timestamp: None,
+            end_timestamp: None,
+            max_hits: default_max_hits(),
+            start_offset: 0,
+            format: BodyFormat::default(),
+            sort_by: SortBy::default(),
+            search_after: None,
+            count_all: count_hits_from_bool::default(),
+            track_total_hits: None,
+            allow_partial_results: false,
+            match_all_if_empty_query: default_match_all_if_empty_query(),
+            split_ids: None,
+            allow_high_cardinality_aggregations: false,
+            include_split_publish_timestamp: false,
+        }
+    }
 }
 
 mod count_hits_from_bool {
@@ -268,8 +359,22 @@ pub fn search_request_from_api_request(
     // The query ast below may still contain user input query. The actual
     // parsing of the user query will happen in the root service, and might require
     // the user of the docmapper default fields (which we do not have at this point).
-    let query_ast = query_ast_from_user_text(&search_request.query, search_request.search_fields);
+    let query_ast =
+        if search_request.match_all_if_empty_query && search_request.query.trim().is_empty() {
+            QueryAst::MatchAll
+        } else {
+            query_ast_from_user_text(&search_request.query, search_request.search_fields)
+        };
     let query_ast_json = serde_json::to_string(&query_ast)?;
+    let (count_hits, track_total_hits_up_to) = match search_request.track_total_hits {
+        Some(track_total_hits) => track_total_hits.count_hits(search_request.max_hits),
+        None => (search_request.count_all, None),
+    };
+    let search_after = search_request
+        .search_after
+        .as_deref()
+        .map(|cursor| decode_search_after_cursor(cursor, &search_request.sort_by.sort_fields))
+        .transpose()?;
     let search_request = quickwit_proto::search::SearchRequest {
         index_id_patterns,
         query_ast: query_ast_json,
@@ -283,8 +388,14 @@ pub fn search_request_from_api_request(
             .map(|agg| serde_json::to_string(&agg).expect("could not serialize JsonValue")),
         sort_fields: search_request.sort_by.sort_fields,
         scroll_ttl_secs: None,
-        search_after: None,
-        count_hits: search_request.count_all.into(),
+        search_after,
+        count_hits: count_hits.into(),
+        timeout_millis: None,
+        track_total_hits_up_to,
+        allow_partial_results: search_request.allow_partial_results,
+        split_ids: search_request.split_ids.unwrap_or_default(),
+        include_split_publish_timestamp: search_request.include_split_publish_timestamp,
+        allow_high_cardinality_aggregations: search_request.allow_high_cardinality_aggregations,
     };
     Ok(search_request)
 }
@@ -295,8 +406,24 @@ async fn search_endpoint(
     search_service: &dyn SearchService,
 ) -> Result<SearchResponseRest, SearchError> {
     let search_request = search_request_from_api_request(index_id_patterns, search_request)?;
+    let sort_fields = search_request.sort_fields.clone();
     let search_response = search_service.root_search(search_request).await?;
-    let search_response_rest = SearchResponseRest::try_from(search_response)?;
+    search_response_to_rest(search_response, &sort_fields)
+}
+
+/// Converts a search response into its REST representation, including the `search_after` cursor
+/// pointing at the last hit of the response.
+fn search_response_to_rest(
+    search_response: SearchResponse,
+    sort_fields: &[SortField],
+) -> Result<SearchResponseRest, SearchError> {
+    let search_after_opt = search_response
+        .hits
+        .last()
+        .and_then(|hit| hit.partial_hit.clone())
+        .map(|partial_hit| encode_search_after_cursor(sort_fields, partial_hit));
+    let mut search_response_rest = SearchResponseRest::try_from(search_response)?;
+    search_response_rest.search_after = search_after_opt;
     Ok(search_response_rest)
 }
 
@@ -376,6 +503,221 @@ pub fn search_post_handler(
         .then(search)
 }
 
+/// The result of one of the searches of a batch.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct SearchBatchResult {
+    /// The HTTP status code of the search.
+    pub status: u16,
+    /// The search response, if the search succeeded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response: Option<SearchResponseRest>,
+    /// The search error, if the search failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl From<Result<SearchResponseRest, SearchError>> for SearchBatchResult {
+    fn from(search_result: Result<SearchResponseRest, SearchError>) -> Self {
+        match search_result {
+            Ok(search_response) => SearchBatchResult {
+                status: StatusCode::OK.as_u16(),
+                response: Some(search_response),
+                error: None,
+            },
+            Err(search_error) => SearchBatchResult {
+                status: search_error.error_code().to_http_status_code().as_u16(),
+                response: None,
+                error: Some(search_error.to_string()),
+            },
+        }
+    }
+}
+
+async fn search_batch_endpoint(
+    index_id_patterns: Vec<String>,
+    search_requests: Vec<SearchRequestQueryString>,
+    search_service: &dyn SearchService,
+) -> Result<Vec<SearchBatchResult>, SearchError> {
+    let search_requests = search_requests
+        .into_iter()
+        .map(|search_request| {
+            search_request_from_api_request(index_id_patterns.clone(), search_request)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let sort_fields_per_request: Vec<Vec<SortField>> = search_requests
+        .iter()
+        .map(|search_request| search_request.sort_fields.clone())
+        .collect();
+    let search_results = search_service.root_search_batch(search_requests).await?;
+    let search_batch_results = search_results
+        .into_iter()
+        .zip(&sort_fields_per_request)
+        .map(|(search_result, sort_fields)| {
+            search_result
+                .and_then(|search_response| search_response_to_rest(search_response, sort_fields))
+                .into()
+        })
+        .collect();
+    Ok(search_batch_results)
+}
+
+fn search_batch_filter(
+) -> impl Filter<Extract = (Vec<String>, Vec<SearchRequestQueryString>), Error = Rejection> + Clone
+{
+    warp::path!(String / "search" / "batch")
+        .and_then(extract_index_id_patterns)
+        .and(warp::post())
+        .and(warp::body::content_length_limit(1024 * 1024))
+        .and(warp::body::json())
+}
+
+async fn search_batch(
+    index_id_patterns: Vec<String>,
+    search_requests: Vec<SearchRequestQueryString>,
+    search_service: Arc<dyn SearchService>,
+) -> impl warp::Reply {
+    info!(num_requests = search_requests.len(), "search-batch");
+    let result = search_batch_endpoint(index_id_patterns, search_requests, &*search_service).await;
+    make_json_api_response(result, BodyFormat::default())
+}
+
+#[utoipa::path(
+    post,
+    tag = "Search",
+    path = "/{index_id}/search/batch",
+    request_body = Vec<SearchRequestQueryString>,
+    responses(
+        (status = 200, description = "Successfully executed the batch of searches.", body = [SearchBatchResult])
+    ),
+    params(
+        ("index_id" = String, Path, description = "The index ID to search."),
+    )
+)]
+/// Batch Search Index
+///
+/// Executes a batch of search requests. Requests that only differ by their aggregations are
+/// computed in a single pass over the splits. The results are returned in the order of the
+/// requests, each with its own status code: a failed search does not fail the others.
+pub fn search_batch_handler(
+    search_service: Arc<dyn SearchService>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = Rejection> + Clone {
+    search_batch_filter()
+        .and(with_arg(search_service))
+        .then(search_batch)
+}
+
+/// A query to validate against the doc mapper of an index.
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct QueryValidationRequest {
+    /// The ID of the index to validate the query against.
+    pub index: String,
+    /// Query text. The query language is that of tantivy.
+    pub query: String,
+}
+
+/// The result of the validation of a query.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct QueryValidationResult {
+    /// The ID of the index the query was validated against.
+    pub index: String,
+    /// Whether the query is valid.
+    pub valid: bool,
+    /// The query AST resolved with the default search fields of the index, if the query is
+    /// valid.
+    #[schema(value_type = Object)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query_ast: Option<QueryAst>,
+    /// The validation error, if the query is invalid.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+async fn validate_query_batch_endpoint(
+    query_validation_requests: Vec<QueryValidationRequest>,
+    search_service: &dyn SearchService,
+) -> Result<Vec<QueryValidationResult>, SearchError> {
+    let index_ids: Vec<String> = query_validation_requests
+        .iter()
+        .map(|query_validation_request| query_validation_request.index.clone())
+        .collect();
+    let search_requests = query_validation_requests
+        .into_iter()
+        .map(|query_validation_request| {
+            let query_ast = query_ast_from_user_text(&query_validation_request.query, None);
+            Ok(quickwit_proto::search::SearchRequest {
+                index_id_patterns: vec![query_validation_request.index],
+                query_ast: serde_json::to_string(&query_ast)?,
+                ..Default::default()
+            })
+        })
+        .collect::<Result<Vec<_>, SearchError>>()?;
+    let validation_results = search_service
+        .root_validate_query_batch(search_requests)
+        .await;
+    let query_validation_results = index_ids
+        .into_iter()
+        .zip(validation_results)
+        .map(|(index, validation_result)| match validation_result {
+            Ok(query_ast) => QueryValidationResult {
+                index,
+                valid: true,
+                query_ast: Some(query_ast),
+                error: None,
+            },
+            Err(search_error) => QueryValidationResult {
+                index,
+                valid: false,
+                query_ast: None,
+                error: Some(search_error.to_string()),
+            },
+        })
+        .collect();
+    Ok(query_validation_results)
+}
+
+fn validate_query_batch_filter(
+) -> impl Filter<Extract = (Vec<QueryValidationRequest>,), Error = Rejection> + Clone {
+    warp::path!("_validate" / "query")
+        .and(warp::post())
+        .and(warp::body::content_length_limit(1024 * 1024))
+        .and(warp::body::json())
+}
+
+async fn validate_query_batch(
+    query_validation_requests: Vec<QueryValidationRequest>,
+    search_service: Arc<dyn SearchService>,
+) -> impl warp::Reply {
+    info!(
+        num_queries = query_validation_requests.len(),
+        "validate-query-batch"
+    );
+    let result = validate_query_batch_endpoint(query_validation_requests, &*search_service).await;
+    make_json_api_response(result, BodyFormat::default())
+}
+
+#[utoipa::path(
+    post,
+    tag = "Search",
+    path = "/_validate/query",
+    request_body = Vec<QueryValidationRequest>,
+    responses(
+        (status = 200, description = "Successfully validated the queries.", body = [QueryValidationResult])
+    ),
+)]
+/// Validate Queries
+///
+/// Validates a batch of queries, each against the doc mapper of its index, without searching
+/// any split. The queries are validated concurrently and the results are returned in the order
+/// of the queries.
+pub fn validate_query_batch_handler(
+    search_service: Arc<dyn SearchService>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = Rejection> + Clone {
+    validate_query_batch_filter()
+        .and(with_arg(search_service))
+        .then(validate_query_batch)
+}
+
 #[utoipa::path(
     get,
     tag = "Search",
@@ -397,6 +739,150 @@ pub fn search_stream_handler(
         .then(search_stream)
 }
 
+async fn doc_by_address_endpoint(
+    index_id: String,
+    split_id: String,
+    doc_id: String,
+    search_service: &dyn SearchService,
+) -> Result<JsonValue, SearchError> {
+    // The document address has the same format as the `_id` of the hits returned by the
+    // Elasticsearch-compatible API: `{split_id}:{segment_ord:08x}:{doc_id:08x}`.
+    let doc_address_str = format!("{split_id}:{doc_id}");
+    let doc_address = GlobalDocAddress::from_str(&doc_address_str).map_err(|_| {
+        SearchError::InvalidArgument(format!(
+            "invalid document ID `{doc_id}`: expected `{{segment_ord:08x}}:{{doc_id:08x}}`"
+        ))
+    })?;
+    let Some(hit) = search_service.root_fetch_doc(index_id, doc_address).await? else {
+        return Err(SearchError::DocNotFound {
+            doc_address: doc_address_str,
+        });
+    };
+    let doc_json: JsonValue = serde_json::from_str(&hit.json)?;
+    Ok(doc_json)
+}
+
+async fn doc_by_address(
+    index_id: String,
+    split_id: String,
+    doc_id: String,
+    search_service: Arc<dyn SearchService>,
+) -> impl warp::Reply {
+    info!(index_id=%index_id, split_id=%split_id, doc_id=%doc_id, "doc_by_address");
+    let result = doc_by_address_endpoint(index_id, split_id, doc_id, &*search_service).await;
+    make_json_api_response(result, BodyFormat::default())
+}
+
+fn doc_by_address_filter(
+) -> impl Filter<Extract = (String, String, String), Error = Rejection> + Clone {
+    warp::path!("indexes" / String / "docs" / String / String).and(warp::get())
+}
+
+#[utoipa::path(
+    get,
+    tag = "Search",
+    path = "/indexes/{index_id}/docs/{split_id}/{doc_id}",
+    responses(
+        (status = 200, description = "Successfully fetched document."),
+        (status = 404, description = "Document not found."),
+    ),
+    params(
+        ("index_id" = String, Path, description = "The index ID of the document."),
+        ("split_id" = String, Path, description = "The split ID of the document."),
+        ("doc_id" = String, Path, description = "The address of the document in the split, formatted as `{segment_ord:08x}:{doc_id:08x}`."),
+    )
+)]
+/// Get Document by Address
+///
+/// Fetches a single document given its address, as returned in the `_id` of the hits of the
+/// Elasticsearch-compatible search API.
+pub fn doc_by_address_handler(
+    search_service: Arc<dyn SearchService>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = Rejection> + Clone {
+    doc_by_address_filter()
+        .and(with_arg(search_service))
+        .then(doc_by_address)
+}
+
+fn default_max_facets() -> u64 {
+    100
+}
+
+/// This struct represents the list facets query passed to the REST API.
+#[derive(Debug, Eq, PartialEq, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+#[serde(deny_unknown_fields)]
+struct ListFacetsQueryString {
+    /// Field to enumerate the values of. The field must be an indexed text field, typically
+    /// using the `raw` tokenizer.
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub field: String,
+    /// Only enumerates the values starting with this prefix.
+    #[serde(default)]
+    pub prefix: Option<String>,
+    /// Maximum number of values to return, capped at 1000.
+    #[param(value_type = u64)]
+    #[serde(default = "default_max_facets")]
+    pub max_facets: u64,
+}
+
+async fn list_facets_endpoint(
+    index_id_patterns: Vec<String>,
+    list_facets_query: ListFacetsQueryString,
+    search_service: &dyn SearchService,
+) -> Result<ListFacetsResponse, SearchError> {
+    let list_facets_request = ListFacetsRequest {
+        index_id_patterns,
+        field: list_facets_query.field,
+        prefix: list_facets_query.prefix,
+        max_facets: list_facets_query.max_facets,
+    };
+    search_service.root_list_facets(list_facets_request).await
+}
+
+fn list_facets_filter(
+) -> impl Filter<Extract = (Vec<String>, ListFacetsQueryString), Error = Rejection> + Clone {
+    warp::path!(String / "facets")
+        .and_then(extract_index_id_patterns)
+        .and(warp::get())
+        .and(serde_qs::warp::query(serde_qs::Config::default()))
+}
+
+async fn list_facets(
+    index_id_patterns: Vec<String>,
+    list_facets_query: ListFacetsQueryString,
+    search_service: Arc<dyn SearchService>,
+) -> impl warp::Reply {
+    info!(index_id_patterns=?index_id_patterns, request=?list_facets_query, "list-facets");
+    let result = list_facets_endpoint(index_id_patterns, list_facets_query, &*search_service).await;
+    make_json_api_response(result, BodyFormat::default())
+}
+
+#[utoipa::path(
+    get,
+    tag = "Search",
+    path = "/{index_id}/facets",
+    responses(
+        (status = 200, description = "Successfully listed the facets.", body = ListFacetsResponse)
+    ),
+    params(
+        ListFacetsQueryString,
+        ("index_id" = String, Path, description = "The index ID to list the facets of."),
+    )
+)]
+/// List Facets
+///
+/// Enumerates the most frequent values of a field along with their number of documents, e.g. to
+/// build filter UIs. The response is flagged as `truncated` when some segments hold too many
+/// distinct values to be read entirely, in which case the counts may be inexact.
+pub fn list_facets_handler(
+    search_service: Arc<dyn SearchService>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = Rejection> + Clone {
+    list_facets_filter()
+        .and(with_arg(search_service))
+        .then(list_facets)
+}
+
 /// This struct represents the search stream query passed to
 /// the REST API.
 #[derive(Deserialize, Debug, Eq, PartialEq, utoipa::IntoParams)]
@@ -417,8 +903,14 @@ struct SearchStreamRequestQueryString {
     #[serde(deserialize_with = "from_simple_list")]
     pub snippet_fields: Option<Vec<String>>,
     /// If set, restricts search to documents with a `timestamp >= start_timestamp`.
+    /// This timestamp is expressed in seconds or as a relative time expression, e.g. `now-15m`.
+    #[param(value_type = Option<String>)]
+    #[serde(default, deserialize_with = "deserialize_timestamp_opt")]
     pub start_timestamp: Option<i64>,
     /// If set, restricts search to documents with a `timestamp < end_timestamp``.
+    /// This timestamp is expressed in seconds or as a relative time expression, e.g. `now/d`.
+    #[param(value_type = Option<String>)]
+    #[serde(default, deserialize_with = "deserialize_timestamp_opt")]
     pub end_timestamp: Option<i64>,
     /// The fast field to extract.
     #[serde(deserialize_with = "deserialize_non_empty_string")]
@@ -521,10 +1013,13 @@ fn search_stream_filter(
 
 #[cfg(test)]
 mod tests {
+    use std::collections::{BTreeMap, HashSet};
+
     use assert_json_diff::{assert_json_eq, assert_json_include};
     use bytes::Bytes;
     use mockall::predicate;
-    use quickwit_search::{MockSearchService, SearchError};
+    use quickwit_proto::search::{Hit, PartialHit, SortByValue, SortValue};
+    use quickwit_search::{MockSearchService, NumHitsRelation, SearchError};
     use serde_json::{json, Value as JsonValue};
 
     use super::*;
@@ -536,7 +1031,12 @@ mod tests {
         let mock_search_service_in_arc = Arc::new(mock_search_service);
         search_get_handler(mock_search_service_in_arc.clone())
             .or(search_post_handler(mock_search_service_in_arc.clone()))
-            .or(search_stream_handler(mock_search_service_in_arc))
+            .or(search_batch_handler(mock_search_service_in_arc.clone()))
+            .or(search_stream_handler(mock_search_service_in_arc.clone()))
+            .or(validate_query_batch_handler(
+                mock_search_service_in_arc.clone(),
+            ))
+            .or(list_facets_handler(mock_search_service_in_arc))
             .recover(recover_fn)
     }
 
@@ -567,15 +1067,21 @@ mod tests {
     fn test_serialize_search_response() -> anyhow::Result<()> {
         let search_response = SearchResponseRest {
             num_hits: 55,
+            num_hits_relation: NumHitsRelation::Gte,
+            num_hits_per_index: BTreeMap::new(),
             hits: Vec::new(),
             snippets: None,
+            split_publish_timestamps: None,
             elapsed_time_micros: 0u64,
             errors: Vec::new(),
+            partial: false,
             aggregations: None,
+            search_after: None,
         };
         let search_response_json: JsonValue = serde_json::to_value(search_response)?;
         let expected_search_response_json: JsonValue = json!({
             "num_hits": 55,
+            "num_hits_relation": "gte",
             "hits": [],
             "elapsed_time_micros": 0,
         });
@@ -609,6 +1115,7 @@ mod tests {
                 sort_by: SortBy::default(),
                 aggs: Some(json!({"range":[]})),
                 count_all: CountHits::CountAll,
+                match_all_if_empty_query: true,
                 ..Default::default()
             }
         );
@@ -643,6 +1150,7 @@ mod tests {
                 format: BodyFormat::default(),
                 sort_by: SortBy::default(),
                 aggs: Some(json!({"range":[]})),
+                match_all_if_empty_query: true,
                 ..Default::default()
             }
         );
@@ -692,6 +1200,7 @@ mod tests {
                 start_offset: 22,
                 format: BodyFormat::default(),
                 sort_by: SortBy::default(),
+                match_all_if_empty_query: true,
                 ..Default::default()
             }
         );
@@ -714,6 +1223,7 @@ mod tests {
                 sort_by: SortBy::default(),
                 max_hits: 20,
                 count_all: CountHits::CountAll,
+                match_all_if_empty_query: true,
                 ..Default::default()
             }
         );
@@ -732,11 +1242,128 @@ mod tests {
                 sort_by: SortBy::default(),
                 max_hits: 20,
                 count_all: CountHits::Underestimate,
+                match_all_if_empty_query: true,
                 ..Default::default()
             }
         );
     }
 
+    #[tokio::test]
+    async fn test_rest_search_api_route_track_total_hits() {
+        let rest_search_api_filter = search_get_filter();
+        let (indexes, req) = warp::test::request()
+            .path("/quickwit-demo-index/search?query=*&max_hits=10&track_total_hits=100")
+            .filter(&rest_search_api_filter)
+            .await
+            .unwrap();
+        assert_eq!(
+            &req,
+            &super::SearchRequestQueryString {
+                query: "*".to_string(),
+                max_hits: 10,
+                track_total_hits: Some(TrackTotalHits::Count(100)),
+                match_all_if_empty_query: true,
+                ..Default::default()
+            }
+        );
+        let search_request = search_request_from_api_request(indexes, req).unwrap();
+        assert_eq!(search_request.count_hits(), CountHits::CountAll);
+        assert_eq!(search_request.track_total_hits_up_to, Some(100));
+
+        let (indexes, req) = warp::test::request()
+            .path("/quickwit-demo-index/search?query=*&track_total_hits=false")
+            .filter(&rest_search_api_filter)
+            .await
+            .unwrap();
+        assert_eq!(req.track_total_hits, Some(TrackTotalHits::Track(false)));
+        let search_request = search_request_from_api_request(indexes, req).unwrap();
+        assert_eq!(search_request.count_hits(), CountHits::Underestimate);
+        assert_eq!(search_request.track_total_hits_up_to, None);
+    }
+
+    #[tokio::test]
+    async fn test_rest_search_api_route_empty_query() {
+        let rest_search_api_filter = search_get_filter();
+        for path in [
+            "/quickwit-demo-index/search?query=",
+            "/quickwit-demo-index/search?query=%20%20",
+        ] {
+            let (indexes, req) = warp::test::request()
+                .path(path)
+                .filter(&rest_search_api_filter)
+                .await
+                .unwrap();
+            assert!(req.match_all_if_empty_query);
+            let search_request = search_request_from_api_request(indexes, req).unwrap();
+            let query_ast: QueryAst = serde_json::from_str(&search_request.query_ast).unwrap();
+            assert_eq!(query_ast, QueryAst::MatchAll);
+        }
+        let (indexes, req) = warp::test::request()
+            .path("/quickwit-demo-index/search?query=%20&match_all_if_empty_query=false")
+            .filter(&rest_search_api_filter)
+            .await
+            .unwrap();
+        assert!(!req.match_all_if_empty_query);
+        let search_request = search_request_from_api_request(indexes, req).unwrap();
+        let query_ast: QueryAst = serde_json::from_str(&search_request.query_ast).unwrap();
+        assert!(matches!(query_ast, QueryAst::UserInput(_)));
+    }
+
+    #[test]
+    fn test_search_request_query_string_default_matches_serde_defaults() {
+        let search_request: SearchRequestQueryString =
+            serde_json::from_str(r#"{"query": ""}"#).unwrap();
+        assert_eq!(search_request, SearchRequestQueryString::default());
+
+        let search_request = search_request_from_api_request(
+            vec!["my-index".to_string()],
+            SearchRequestQueryString::default(),
+        )
+        .unwrap();
+        let query_ast: QueryAst = serde_json::from_str(&search_request.query_ast).unwrap();
+        assert_eq!(query_ast, QueryAst::MatchAll);
+        assert_eq!(search_request.max_hits, 20);
+    }
+
+    #[tokio::test]
+    async fn test_rest_search_api_route_split_ids() {
+        let rest_search_api_filter = search_get_filter();
+        let (indexes, req) = warp::test::request()
+            .path("/quickwit-demo-index/search?query=*&split_ids=split-1,split-2")
+            .filter(&rest_search_api_filter)
+            .await
+            .unwrap();
+        assert_eq!(
+            req.split_ids,
+            Some(vec!["split-1".to_string(), "split-2".to_string()])
+        );
+        let search_request = search_request_from_api_request(indexes, req).unwrap();
+        assert_eq!(search_request.split_ids, vec!["split-1", "split-2"]);
+
+        let (indexes, req) = warp::test::request()
+            .path("/quickwit-demo-index/search?query=*")
+            .filter(&rest_search_api_filter)
+            .await
+            .unwrap();
+        assert!(req.split_ids.is_none());
+        let search_request = search_request_from_api_request(indexes, req).unwrap();
+        assert!(search_request.split_ids.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_rest_search_api_route_malformed_query() {
+        let rest_search_api_filter = search_get_filter();
+        let (indexes, req) = warp::test::request()
+            .path("/quickwit-demo-index/search?query=title:(")
+            .filter(&rest_search_api_filter)
+            .await
+            .unwrap();
+        let search_request = search_request_from_api_request(indexes, req).unwrap();
+        let query_ast: QueryAst = serde_json::from_str(&search_request.query_ast).unwrap();
+        let error = query_ast.parse_user_query(&[]).unwrap_err();
+        assert!(error.to_string().contains("failed to parse query"));
+    }
+
     #[tokio::test]
     async fn test_rest_search_api_route_simple_default_num_hits_default_offset() {
         let rest_search_api_filter = search_get_filter();
@@ -760,6 +1387,7 @@ mod tests {
                 start_offset: 0,
                 format: BodyFormat::default(),
                 sort_by: SortBy::default(),
+                match_all_if_empty_query: true,
                 ..Default::default()
             }
         );
@@ -785,6 +1413,7 @@ mod tests {
                 format: BodyFormat::Json,
                 search_fields: None,
                 sort_by: SortBy::default(),
+                match_all_if_empty_query: true,
                 ..Default::default()
             }
         );
@@ -983,6 +1612,240 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_rest_search_api_paginates_with_search_after_cursor() -> anyhow::Result<()> {
+        // The hits are sorted by decreasing `timestamp`, i.e. by increasing doc ID.
+        let partial_hits: Vec<PartialHit> = (0..25u32)
+            .map(|doc_id| PartialHit {
+                sort_value: Some(SortByValue {
+                    sort_value: Some(SortValue::U64(100 - doc_id as u64)),
+                }),
+                sort_value2: None,
+                split_id: "split".to_string(),
+                segment_ord: 0,
+                doc_id,
+            })
+            .collect();
+        let mut mock_search_service = MockSearchService::new();
+        mock_search_service
+            .expect_root_search()
+            .times(4)
+            .returning(move |search_request| {
+                let start = match &search_request.search_after {
+                    Some(search_after) => {
+                        partial_hits
+                            .iter()
+                            .position(|partial_hit| partial_hit == search_after)
+                            .unwrap()
+                            + 1
+                    }
+                    None => 0,
+                };
+                let hits = partial_hits[start..]
+                    .iter()
+                    .take(search_request.max_hits as usize)
+                    .map(|partial_hit| Hit {
+                        json: json!({"doc_id": partial_hit.doc_id}).to_string(),
+                        partial_hit: Some(partial_hit.clone()),
+                        ..Default::default()
+                    })
+                    .collect();
+                Ok(quickwit_proto::search::SearchResponse {
+                    num_hits: 25,
+                    hits,
+                    ..Default::default()
+                })
+            });
+        let rest_search_api_handler = search_handler(mock_search_service);
+
+        let mut doc_ids: Vec<u64> = Vec::new();
+        let mut search_after_opt: Option<String> = None;
+        loop {
+            let mut path =
+                "/quickwit-demo-index/search?query=*&max_hits=10&sort_by=timestamp".to_string();
+            if let Some(search_after) = &search_after_opt {
+                path.push_str(&format!("&search_after={search_after}"));
+            }
+            let resp = warp::test::request()
+                .path(&path)
+                .reply(&rest_search_api_handler)
+                .await;
+            assert_eq!(resp.status(), 200);
+            let resp_json: JsonValue = serde_json::from_slice(resp.body())?;
+            let hits = resp_json["hits"].as_array().unwrap();
+            if hits.is_empty() {
+                assert!(resp_json.get("search_after").is_none());
+                break;
+            }
+            doc_ids.extend(hits.iter().map(|hit| hit["doc_id"].as_u64().unwrap()));
+            search_after_opt = Some(resp_json["search_after"].as_str().unwrap().to_string());
+        }
+        let unique_doc_ids: HashSet<u64> = doc_ids.iter().copied().collect();
+        assert_eq!(doc_ids.len(), 25);
+        assert_eq!(unique_doc_ids.len(), 25);
+
+        // The cursor cannot be used with a different sort.
+        let resp = warp::test::request()
+            .path(&format!(
+                "/quickwit-demo-index/search?query=*&sort_by=-timestamp&search_after={}",
+                search_after_opt.unwrap()
+            ))
+            .reply(&rest_search_api_handler)
+            .await;
+        assert_eq!(resp.status(), 400);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rest_search_api_route_batch() -> anyhow::Result<()> {
+        let mut mock_search_service = MockSearchService::new();
+        mock_search_service
+            .expect_root_search_batch()
+            .withf(|search_requests| {
+                search_requests.len() == 2
+                    && search_requests.iter().all(|search_request| {
+                        search_request.index_id_patterns == ["quickwit-demo-index"]
+                    })
+                    && search_requests[0].aggregation_request.as_deref()
+                        == Some(r#"{"colors":{"terms":{"field":"color"}}}"#)
+                    && search_requests[1].aggregation_request.is_none()
+            })
+            .returning(|_| {
+                Ok(vec![
+                    Ok(quickwit_proto::search::SearchResponse {
+                        num_hits: 10,
+                        aggregation: Some(r#"{"colors":{"buckets":[]}}"#.to_string()),
+                        ..Default::default()
+                    }),
+                    Err(SearchError::InvalidQuery("invalid query".to_string())),
+                ])
+            });
+        let rest_search_api_handler = search_handler(mock_search_service);
+        let resp = warp::test::request()
+            .method("POST")
+            .path("/quickwit-demo-index/search/batch")
+            .json(&serde_json::json!([
+                {"query": "*", "max_hits": 0, "aggs": {"colors": {"terms": {"field": "color"}}}},
+                {"query": "*", "max_hits": 0},
+            ]))
+            .reply(&rest_search_api_handler)
+            .await;
+        assert_eq!(resp.status(), 200);
+        let resp_json: JsonValue = serde_json::from_slice(resp.body())?;
+        let expected_response_json = serde_json::json!([
+            {
+                "status": 200,
+                "response": {
+                    "num_hits": 10,
+                    "aggregations": {"colors": {"buckets": []}},
+                },
+            },
+            {
+                "status": 400,
+                "error": "invalid query",
+            },
+        ]);
+        assert_json_include!(actual: resp_json, expected: expected_response_json);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rest_validate_query_batch() -> anyhow::Result<()> {
+        let mut mock_search_service = MockSearchService::new();
+        mock_search_service
+            .expect_root_validate_query_batch()
+            .withf(|search_requests| {
+                search_requests.len() == 3
+                    && search_requests[0].index_id_patterns == ["index-1"]
+                    && search_requests[1].index_id_patterns == ["index-1"]
+                    && search_requests[2].index_id_patterns == ["index-2"]
+            })
+            .returning(|_| {
+                vec![
+                    Ok(QueryAst::MatchAll),
+                    Err(SearchError::InvalidQuery("invalid query".to_string())),
+                    Err(SearchError::IndexesNotFound {
+                        index_ids: vec!["index-2".to_string()],
+                    }),
+                ]
+            });
+        let rest_search_api_handler = search_handler(mock_search_service);
+        let resp = warp::test::request()
+            .method("POST")
+            .path("/_validate/query")
+            .json(&serde_json::json!([
+                {"index": "index-1", "query": "*"},
+                {"index": "index-1", "query": "unknown:foo"},
+                {"index": "index-2", "query": "body:foo"},
+            ]))
+            .reply(&rest_search_api_handler)
+            .await;
+        assert_eq!(resp.status(), 200);
+        let resp_json: JsonValue = serde_json::from_slice(resp.body())?;
+        let expected_response_json = serde_json::json!([
+            {
+                "index": "index-1",
+                "valid": true,
+                "query_ast": {"type": "match_all"},
+            },
+            {
+                "index": "index-1",
+                "valid": false,
+                "error": "invalid query",
+            },
+            {
+                "index": "index-2",
+                "valid": false,
+                "error": "could not find indexes matching the IDs `[\"index-2\"]`",
+            },
+        ]);
+        assert_json_eq!(resp_json, expected_response_json);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rest_list_facets() -> anyhow::Result<()> {
+        let mut mock_search_service = MockSearchService::new();
+        mock_search_service
+            .expect_root_list_facets()
+            .withf(|list_facets_request| {
+                list_facets_request.index_id_patterns == ["index-1", "index-2*"]
+                    && list_facets_request.field == "severity"
+                    && list_facets_request.prefix.as_deref() == Some("err")
+                    && list_facets_request.max_facets == 100
+            })
+            .returning(|_| {
+                Ok(ListFacetsResponse {
+                    facets: vec![FacetCount {
+                        value: "error".to_string(),
+                        num_docs: 3,
+                    }],
+                    truncated: true,
+                    elapsed_time_micros: 10,
+                })
+            });
+        let rest_search_api_handler = search_handler(mock_search_service);
+        let resp = warp::test::request()
+            .path("/index-1,index-2%2A/facets?field=severity&prefix=err")
+            .reply(&rest_search_api_handler)
+            .await;
+        assert_eq!(resp.status(), 200);
+        let resp_json: JsonValue = serde_json::from_slice(resp.body())?;
+        let expected_response_json = serde_json::json!({
+            "facets": [{"value": "error", "num_docs": 3}],
+            "truncated": true,
+            "elapsed_time_micros": 10,
+        });
+        assert_json_eq!(resp_json, expected_response_json);
+
+        let resp = warp::test::request()
+            .path("/index-1/facets?prefix=err")
+            .reply(&rest_search_api_handler)
+            .await;
+        assert_eq!(resp.status(), 400);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_rest_search_api_start_offset_and_num_hits_parameter() -> anyhow::Result<()> {
         let mut mock_search_service = MockSearchService::new();
@@ -1134,6 +1997,29 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_rest_search_api_route_relative_timestamps() {
+        let rest_search_api_filter = search_get_filter();
+        let (_indexes, req) = warp::test::request()
+            .path("/quickwit-demo-index/search?query=*&start_timestamp=now-15m&end_timestamp=now")
+            .filter(&rest_search_api_filter)
+            .await
+            .unwrap();
+        let start_timestamp = req.start_timestamp.unwrap();
+        let end_timestamp = req.end_timestamp.unwrap();
+        assert!((900..=901).contains(&(end_timestamp - start_timestamp)));
+
+        let rejection = warp::test::request()
+            .path("/quickwit-demo-index/search?query=*&start_timestamp=yesterday")
+            .filter(&rest_search_api_filter)
+            .await
+            .unwrap_err();
+        let parse_error = rejection.find::<serde_qs::Error>().unwrap();
+        assert!(parse_error
+            .to_string()
+            .contains("invalid timestamp `yesterday`"));
+    }
+
     #[tokio::test]
     async fn test_rest_search_stream_api_error() {
         let rejection = warp::test::request()
@@ -1175,6 +2061,7 @@ mod tests {
                     partial_hit: None,
                     snippet: Some(r#"{"title": [], "body": ["foo <em>bar</em> baz"]}"#.to_string()),
                     index_id: "quickwit-demo-index".to_string(),
+                    split_publish_timestamp: None,
                 }],
                 num_hits: 1,
                 elapsed_time_micros: 16,
@@ -1204,6 +2091,53 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_rest_search_api_route_serialize_results_with_split_publish_timestamp() {
+        let mut mock_search_service = MockSearchService::new();
+        mock_search_service
+            .expect_root_search()
+            .with(predicate::function(
+                |search_request: &quickwit_proto::search::SearchRequest| {
+                    search_request.include_split_publish_timestamp
+                },
+            ))
+            .returning(|_| {
+                Ok(quickwit_proto::search::SearchResponse {
+                    hits: vec![
+                        quickwit_proto::search::Hit {
+                            json: r#"{"title": "foo"}"#.to_string(),
+                            split_publish_timestamp: Some(1_700_000_003),
+                            ..Default::default()
+                        },
+                        quickwit_proto::search::Hit {
+                            json: r#"{"title": "bar"}"#.to_string(),
+                            split_publish_timestamp: Some(1_700_000_007),
+                            ..Default::default()
+                        },
+                    ],
+                    num_hits: 2,
+                    elapsed_time_micros: 16,
+                    ..Default::default()
+                })
+            });
+        let rest_search_api_handler = search_handler(mock_search_service);
+        let resp = warp::test::request()
+            .path("/quickwit-demo-index/search?query=*&include_split_publish_timestamp=true")
+            .reply(&rest_search_api_handler)
+            .await;
+
+        assert_eq!(resp.status(), 200);
+        let resp_json: JsonValue = serde_json::from_slice(resp.body()).unwrap();
+        let expected_response_json = serde_json::json!({
+            "num_hits": 2,
+            "hits": [{"title": "foo"}, {"title": "bar"}],
+            "split_publish_timestamps": [1_700_000_003, 1_700_000_007],
+            "elapsed_time_micros": 16,
+            "errors": [],
+        });
+        assert_json_include!(actual: resp_json, expected: expected_response_json);
+    }
+
     #[tokio::test]
     async fn test_rest_search_api_multi_indexes() {
         {
@@ -1264,4 +2198,49 @@ mod tests {
             );
         }
     }
+
+    #[tokio::test]
+    async fn test_rest_doc_by_address_api() -> anyhow::Result<()> {
+        let mut mock_search_service = MockSearchService::new();
+        mock_search_service
+            .expect_root_fetch_doc()
+            .withf(|index_id, doc_address| {
+                index_id == "quickwit-demo-index"
+                    && doc_address.split == "split-1"
+                    && doc_address.doc_addr.segment_ord == 0
+                    && doc_address.doc_addr.doc_id == 42
+            })
+            .returning(|_, _| {
+                Ok(Some(quickwit_proto::search::Hit {
+                    json: r#"{"title": "snoopy"}"#.to_string(),
+                    ..Default::default()
+                }))
+            });
+        mock_search_service
+            .expect_root_fetch_doc()
+            .returning(|_, _| Ok(None));
+        let rest_doc_by_address_handler =
+            doc_by_address_handler(Arc::new(mock_search_service)).recover(recover_fn);
+
+        let resp = warp::test::request()
+            .path("/indexes/quickwit-demo-index/docs/split-1/00000000:0000002a")
+            .reply(&rest_doc_by_address_handler)
+            .await;
+        assert_eq!(resp.status(), 200);
+        let resp_json: JsonValue = serde_json::from_slice(resp.body())?;
+        assert_json_eq!(resp_json, json!({"title": "snoopy"}));
+
+        let resp = warp::test::request()
+            .path("/indexes/quickwit-demo-index/docs/split-1/00000000:0000002b")
+            .reply(&rest_doc_by_address_handler)
+            .await;
+        assert_eq!(resp.status(), 404);
+
+        let resp = warp::test::request()
+            .path("/indexes/quickwit-demo-index/docs/split-1/not-a-doc-id")
+            .reply(&rest_doc_by_address_handler)
+            .await;
+        assert_eq!(resp.status(), 400);
+        Ok(())
+    }
 }
@@ -0,0 +1,158 @@
+// Copyright (C) 2024 Quickwit, Inc.
+//
+// Quickwit is offered under the AGPL v3.0 and as commercial software.
+// For commercial licensing, contact us at hello@quickwit.io.
+//
+// AGPL:
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as
+// published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use base64::prelude::BASE64_URL_SAFE_NO_PAD;
+use base64::Engine;
+use prost::Message;
+use quickwit_proto::search::{PartialHit, SortField};
+use quickwit_search::SearchError;
+
+/// Version of the payload of the cursors. It must be incremented whenever the payload changes in a
+/// non backward compatible way.
+const SEARCH_AFTER_CURSOR_VERSION: u32 = 1;
+
+/// Payload of the opaque `search_after` cursors exchanged with the clients of the search REST API.
+/// It is encoded with protobuf so that the sort values, scores in particular, round-trip exactly.
+#[derive(Clone, PartialEq, Message)]
+struct SearchAfterCursor {
+    #[prost(uint32, tag = "1")]
+    version: u32,
+    /// Sort fields of the search that produced the cursor.
+    #[prost(message, repeated, tag = "2")]
+    sort_fields: Vec<SortField>,
+    /// Sort values and address of the last hit returned by the search.
+    #[prost(message, optional, tag = "3")]
+    partial_hit: Option<PartialHit>,
+}
+
+/// Returns whether two lists of sort fields sort the hits in the same order.
+fn is_same_sort(left: &[SortField], right: &[SortField]) -> bool {
+    left.len() == right.len()
+        && left
+            .iter()
+            .zip(right)
+            .all(|(left_sort_field, right_sort_field)| {
+                left_sort_field.field_name == right_sort_field.field_name
+                    && left_sort_field.sort_order == right_sort_field.sort_order
+            })
+}
+
+/// Encodes the last hit of a page of results into an opaque cursor, which the client passes back
+/// as `search_after` to fetch the following page.
+pub(crate) fn encode_search_after_cursor(
+    sort_fields: &[SortField],
+    last_partial_hit: PartialHit,
+) -> String {
+    let search_after_cursor = SearchAfterCursor {
+        version: SEARCH_AFTER_CURSOR_VERSION,
+        sort_fields: sort_fields.to_vec(),
+        partial_hit: Some(last_partial_hit),
+    };
+    BASE64_URL_SAFE_NO_PAD.encode(search_after_cursor.encode_to_vec())
+}
+
+/// Decodes an opaque `search_after` cursor into the partial hit to search after. The cursor must
+/// have been produced by a search sorted on the same fields as `sort_fields`.
+pub(crate) fn decode_search_after_cursor(
+    cursor: &str,
+    sort_fields: &[SortField],
+) -> Result<PartialHit, SearchError> {
+    let invalid_cursor_error =
+        || SearchError::InvalidArgument(format!("invalid `search_after` cursor `{cursor}`"));
+    let payload = BASE64_URL_SAFE_NO_PAD
+        .decode(cursor)
+        .map_err(|_| invalid_cursor_error())?;
+    let search_after_cursor =
+        SearchAfterCursor::decode(payload.as_slice()).map_err(|_| invalid_cursor_error())?;
+
+    if search_after_cursor.version != SEARCH_AFTER_CURSOR_VERSION {
+        return Err(SearchError::InvalidArgument(format!(
+            "`search_after` cursor version {} is not supported, expected version \
+             {SEARCH_AFTER_CURSOR_VERSION}",
+            search_after_cursor.version
+        )));
+    }
+    if !is_same_sort(&search_after_cursor.sort_fields, sort_fields) {
+        return Err(SearchError::InvalidArgument(
+            "`search_after` cursor was produced by a search with different sort fields".to_string(),
+        ));
+    }
+    search_after_cursor
+        .partial_hit
+        .ok_or_else(invalid_cursor_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use quickwit_proto::search::{SortByValue, SortOrder, SortValue};
+
+    use super::*;
+
+    #[test]
+    fn test_search_after_cursor_round_trip() {
+        let sort_fields = vec![SortField {
+            field_name: "_score".to_string(),
+            sort_order: SortOrder::Desc as i32,
+            sort_datetime_format: None,
+        }];
+        let partial_hit = PartialHit {
+            sort_value: Some(SortByValue {
+                sort_value: Some(SortValue::F64(0.1 + 0.2)),
+            }),
+            sort_value2: None,
+            split_id: "split-1".to_string(),
+            segment_ord: 1,
+            doc_id: 42,
+        };
+        let cursor = encode_search_after_cursor(&sort_fields, partial_hit.clone());
+        assert!(cursor
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+        assert_eq!(
+            decode_search_after_cursor(&cursor, &sort_fields).unwrap(),
+            partial_hit
+        );
+
+        let other_sort_fields = vec![SortField {
+            field_name: "_score".to_string(),
+            sort_order: SortOrder::Asc as i32,
+            sort_datetime_format: None,
+        }];
+        let error = decode_search_after_cursor(&cursor, &other_sort_fields).unwrap_err();
+        assert!(error.to_string().contains("different sort fields"));
+    }
+
+    #[test]
+    fn test_decode_invalid_search_after_cursor() {
+        let error = decode_search_after_cursor("not-a-cursor!", &[]).unwrap_err();
+        assert!(matches!(error, SearchError::InvalidArgument(_)));
+
+        let cursor = BASE64_URL_SAFE_NO_PAD.encode(b"\xff\xff\xff");
+        decode_search_after_cursor(&cursor, &[]).unwrap_err();
+
+        let search_after_cursor = SearchAfterCursor {
+            version: SEARCH_AFTER_CURSOR_VERSION + 1,
+            sort_fields: Vec::new(),
+            partial_hit: Some(PartialHit::default()),
+        };
+        let cursor = BASE64_URL_SAFE_NO_PAD.encode(search_after_cursor.encode_to_vec());
+        let error = decode_search_after_cursor(&cursor, &[]).unwrap_err();
+        assert!(error.to_string().contains("version 2 is not supported"));
+    }
+}
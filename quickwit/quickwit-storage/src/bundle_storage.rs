@@ -22,11 +22,12 @@ use std::convert::TryInto;
 use std::fmt::Debug;
 use std::ops::Range;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::{Arc, Once};
 use std::{fmt, io};
 
-use anyhow::Context;
+use anyhow::{bail, Context};
 use async_trait::async_trait;
+use once_cell::sync::Lazy;
 use quickwit_common::chunk_range;
 use quickwit_common::uri::Uri;
 use serde::{Deserialize, Serialize};
@@ -34,11 +35,12 @@ use tantivy::directory::FileSlice;
 use tantivy::HasLen;
 use thiserror::Error;
 use tokio::io::{AsyncRead, AsyncWriteExt};
-use tracing::error;
+use tracing::{error, warn};
 
 use crate::storage::SendableAsync;
 use crate::{
-    BulkDeleteError, OwnedBytes, Storage, StorageError, StorageResult, VersionedComponent,
+    BulkDeleteError, OwnedBytes, Storage, StorageError, StorageErrorKind, StorageResult,
+    VersionedComponent,
 };
 
 /// BundleStorage bundles together multiple files into a single file.
@@ -106,12 +108,43 @@ pub struct CorruptedData {
 
 const SPLIT_HOTBYTES_FOOTER_LENGTH_NUM_BYTES: usize = std::mem::size_of::<u32>();
 const BUNDLE_METADATA_LENGTH_NUM_BYTES: usize = std::mem::size_of::<u32>();
+const BUNDLE_METADATA_CHECKSUM_NUM_BYTES: usize = std::mem::size_of::<u32>();
+
+/// Makes sure the missing checksum of splits created before checksums were introduced is only
+/// reported once.
+static MISSING_CHECKSUM_WARNING: Once = Once::new();
+
+/// Whether new splits are written with the V2 bundle footer, which holds a checksum. Releases
+/// prior to the checksum cannot open such splits, so the V1 footer remains the default for one
+/// release to allow rolling back.
+pub(crate) static WRITE_BUNDLE_FOOTER_CHECKSUM: Lazy<bool> =
+    Lazy::new(|| quickwit_common::get_from_env("QW_ENABLE_SPLIT_FOOTER_CHECKSUM", false));
 
 #[derive(Copy, Clone, Default)]
 #[repr(u32)]
 pub enum BundleStorageFileOffsetsVersions {
+    /// JSON file offsets.
     #[default]
     V1 = 1,
+    /// JSON file offsets followed by their CRC32C checksum.
+    V2 = 2,
+}
+
+impl BundleStorageFileOffsetsVersions {
+    /// Serializes the file offsets, header included, with this version of the format.
+    pub(crate) fn serialize_with_version(self, component: &BundleStorageFileOffsets) -> Vec<u8> {
+        let metadata_json = serde_json::to_string(component).unwrap();
+        let mut output = Vec::with_capacity(8 + metadata_json.len() + 4);
+        output.extend_from_slice(&Self::MAGIC_NUMBER.to_le_bytes());
+        output.extend_from_slice(&self.to_version_code().to_le_bytes());
+        output.extend_from_slice(metadata_json.as_bytes());
+
+        if let Self::V2 = self {
+            let checksum = crc32c::crc32c(metadata_json.as_bytes());
+            output.extend_from_slice(&checksum.to_le_bytes());
+        }
+        output
+    }
 }
 
 impl VersionedComponent for BundleStorageFileOffsetsVersions {
@@ -126,6 +159,7 @@ impl VersionedComponent for BundleStorageFileOffsetsVersions {
     fn try_from_version_code_impl(version_code: u32) -> Option<Self> {
         match version_code {
             1 => Some(Self::V1),
+            2 => Some(Self::V2),
             _ => None,
         }
     }
@@ -136,7 +170,41 @@ impl VersionedComponent for BundleStorageFileOffsetsVersions {
     }
 
     fn deserialize_impl(&self, bytes: &mut OwnedBytes) -> anyhow::Result<Self::Component> {
-        serde_json::from_reader(bytes).context("deserializing bundle storage file offsets failed")
+        match self {
+            Self::V1 => {
+                MISSING_CHECKSUM_WARNING.call_once(|| {
+                    warn!(
+                        "opening split without bundle footer checksum, its integrity cannot be \
+                         verified"
+                    );
+                });
+                serde_json::from_reader(bytes)
+                    .context("deserializing bundle storage file offsets failed")
+            }
+            Self::V2 => {
+                if bytes.len() < BUNDLE_METADATA_CHECKSUM_NUM_BYTES {
+                    bail!(StorageErrorKind::Corrupted.with_error(anyhow::anyhow!(
+                        "bundle footer is too short to hold a checksum (len={})",
+                        bytes.len()
+                    )));
+                }
+                let (metadata_json, checksum_bytes) = bytes
+                    .as_slice()
+                    .split_at(bytes.len() - BUNDLE_METADATA_CHECKSUM_NUM_BYTES);
+                let expected_checksum = u32::from_le_bytes(checksum_bytes.try_into().unwrap());
+                let checksum = crc32c::crc32c(metadata_json);
+                if checksum != expected_checksum {
+                    bail!(StorageErrorKind::Corrupted.with_error(anyhow::anyhow!(
+                        "bundle footer checksum mismatch (expected={expected_checksum:#010x}, \
+                         actual={checksum:#010x})"
+                    )));
+                }
+                let file_offsets = serde_json::from_slice(metadata_json)
+                    .context("deserializing bundle storage file offsets failed")?;
+                bytes.advance(bytes.len());
+                Ok(file_offsets)
+            }
+        }
     }
 }
 
@@ -220,6 +288,14 @@ impl Storage for BundleStorage {
         Err(unsupported_operation(&[path]))
     }
 
+    async fn put_if_absent(
+        &self,
+        path: &Path,
+        _payload: Box<dyn crate::PutPayload>,
+    ) -> crate::StorageResult<bool> {
+        Err(unsupported_operation(&[path]))
+    }
+
     async fn copy_to(
         &self,
         path: &Path,
@@ -251,6 +327,25 @@ impl Storage for BundleStorage {
             .await
     }
 
+    async fn get_slices(
+        &self,
+        path: &Path,
+        ranges: &[Range<usize>],
+    ) -> crate::StorageResult<Vec<OwnedBytes>> {
+        let file_offsets = self.metadata.get(path).ok_or_else(|| {
+            crate::StorageErrorKind::NotFound
+                .with_error(anyhow::anyhow!("missing file `{}`", path.display()))
+        })?;
+        let file_start = file_offsets.start as usize;
+        let new_ranges: Vec<Range<usize>> = ranges
+            .iter()
+            .map(|range| file_start + range.start..file_start + range.end)
+            .collect();
+        self.storage
+            .get_slices(&self.bundle_filepath, &new_ranges)
+            .await
+    }
+
     async fn get_slice_stream(
         &self,
         path: &Path,
@@ -373,6 +468,7 @@ mod tests {
 
         Ok(())
     }
+
     #[tokio::test]
     async fn bundle_storage_test() -> anyhow::Result<()> {
         let temp_dir = tempfile::tempdir()?;
@@ -446,4 +542,61 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_bundle_storage_file_offsets_checksum_mismatch() -> anyhow::Result<()> {
+        let hotcache = [1, 3, 3, 7];
+        let mut split_payload_builder = SplitPayloadBuilder::default();
+        split_payload_builder.set_footer_checksum(true);
+        let mut buffer = split_payload_builder
+            .finalize(&hotcache)?
+            .read_all()
+            .await?
+            .to_vec();
+        // Corrupt the last byte of the JSON file offsets, right before their checksum.
+        let metadata_end = buffer.len() - 4 - hotcache.len() - 4;
+        buffer[metadata_end - 5] ^= 1;
+
+        let error = BundleStorageFileOffsets::open_from_split_data(FileSlice::from(buffer))
+            .unwrap_err()
+            .downcast::<StorageError>()
+            .unwrap();
+        assert_eq!(error.kind(), StorageErrorKind::Corrupted);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_bundle_storage_file_offsets_with_checksum() -> anyhow::Result<()> {
+        let mut split_payload_builder = SplitPayloadBuilder::default();
+        split_payload_builder.add_payload("f1".to_string(), Box::new(vec![123, 76]));
+        split_payload_builder.set_footer_checksum(true);
+        let buffer = split_payload_builder
+            .finalize(&[5, 5])?
+            .read_all()
+            .await?
+            .to_vec();
+
+        let (hotcache, file_offsets) =
+            BundleStorageFileOffsets::open_from_split_data(FileSlice::from(buffer))?;
+        assert_eq!(hotcache.read_bytes().unwrap().as_ref(), &[5, 5]);
+        assert_eq!(file_offsets.get(Path::new("f1")), Some(0..2));
+        Ok(())
+    }
+
+    #[test]
+    fn test_bundle_storage_file_offsets_without_checksum() {
+        let file_offsets_json = br#"{"files":{"f1":{"start":0,"end":2}}}"#;
+        let mut split_data = vec![123, 76];
+        split_data.extend(BundleStorageFileOffsetsVersions::MAGIC_NUMBER.to_le_bytes());
+        split_data.extend((BundleStorageFileOffsetsVersions::V1 as u32).to_le_bytes());
+        split_data.extend(file_offsets_json);
+        split_data.extend((8 + file_offsets_json.len() as u32).to_le_bytes());
+        split_data.extend([5, 5]);
+        split_data.extend(2u32.to_le_bytes());
+
+        let (hotcache, file_offsets) =
+            BundleStorageFileOffsets::open_from_split_data(FileSlice::from(split_data)).unwrap();
+        assert_eq!(hotcache.read_bytes().unwrap().as_ref(), &[5, 5]);
+        assert_eq!(file_offsets.get(Path::new("f1")), Some(0..2));
+    }
 }
@@ -47,7 +47,7 @@ use crate::OwnedBytes;
 /// On the other hand, for very large queries involving enough data to saturate the cache,
 /// we are facing a scanning pattern. If variations of this  query is repeated over and over
 /// a regular LRU eviction policy would yield a hit rate of 0.
-const MIN_TIME_SINCE_LAST_ACCESS: Duration = Duration::from_secs(60);
+pub(crate) const MIN_TIME_SINCE_LAST_ACCESS: Duration = Duration::from_secs(60);
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 enum Capacity {
@@ -222,6 +222,11 @@ impl<K: Hash + Eq> MemorySizedCache<K> {
     pub fn put(&self, val: K, bytes: OwnedBytes) {
         self.inner.lock().unwrap().put(val, bytes);
     }
+
+    /// Returns the number of bytes currently held in the cache.
+    pub fn num_bytes(&self) -> u64 {
+        self.inner.lock().unwrap().num_bytes
+    }
 }
 
 impl MemorySizedCache<SliceAddress> {
@@ -311,5 +316,6 @@ mod tests {
         let data = OwnedBytes::new(&b"werwer"[..]);
         cache.put("hello.seg", data);
         assert_eq!(cache.get(&"hello.seg").unwrap(), &b"werwer"[..]);
+        assert_eq!(cache.num_bytes(), 6);
     }
 }
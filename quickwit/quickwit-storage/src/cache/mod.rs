@@ -34,6 +34,7 @@ pub use storage_with_cache::StorageWithCache;
 
 pub use self::byte_range_cache::ByteRangeCache;
 pub use self::memory_sized_cache::MemorySizedCache;
+use crate::metrics::StorageCacheMetrics;
 use crate::{OwnedBytes, Storage};
 
 /// Wraps the given directory with a slice cache that is actually global
@@ -44,13 +45,19 @@ use crate::{OwnedBytes, Storage};
 /// - it relies on the idea that all of the files we attempt to cache
 /// have universally unique names. It happens to be true today, but this might be very error prone
 /// in the future.
+///
+/// Hits and misses are reported in the storage cache metrics under `cache_name` and the protocol
+/// of the wrapped storage.
 pub fn wrap_storage_with_cache(
+    cache_name: &str,
     long_term_cache: Arc<dyn StorageCache>,
     storage: Arc<dyn Storage>,
 ) -> Arc<dyn Storage> {
+    let metrics = StorageCacheMetrics::new(cache_name, storage.uri().protocol());
     Arc::new(StorageWithCache {
         storage,
         cache: long_term_cache,
+        metrics,
     })
 }
 
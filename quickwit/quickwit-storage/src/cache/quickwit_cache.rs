@@ -31,6 +31,9 @@ const FULL_SLICE: Range<usize> = 0..usize::MAX;
 
 /// Quickwit storage cache with a size limit.
 /// It is used currently by to cache only fast fields data.
+///
+/// A single instance is shared by all the indexes searched on a node, so the capacity
+/// is a global budget and eviction follows a global LRU order across indexes.
 pub struct QuickwitCache {
     router: Vec<(&'static str, Arc<dyn StorageCache>)>,
 }
@@ -162,8 +165,10 @@ mod tests {
     use std::path::Path;
     use std::sync::Arc;
 
-    use super::QuickwitCache;
+    use super::{QuickwitCache, SimpleCache};
+    use crate::cache::memory_sized_cache::MIN_TIME_SINCE_LAST_ACCESS;
     use crate::cache::StorageCache;
+    use crate::metrics::CACHE_METRICS_FOR_TESTS;
     use crate::{MockStorageCache, OwnedBytes};
 
     #[tokio::test]
@@ -216,4 +221,52 @@ mod tests {
             &b"aaaaa"[..]
         );
     }
+
+    #[tokio::test]
+    async fn test_quickwit_cache_fast_fields_global_budget_across_indexes() {
+        tokio::time::pause();
+        let fast_field_cache = Arc::new(SimpleCache::with_capacity_in_bytes(
+            10,
+            &CACHE_METRICS_FOR_TESTS,
+        ));
+        let mut quickwit_cache = QuickwitCache::empty();
+        quickwit_cache.add_route(".fast", fast_field_cache.clone());
+
+        for index_id in ["index-1", "index-2"] {
+            let path = Path::new(index_id).join("split.fast");
+            quickwit_cache
+                .put(path, 0..4, OwnedBytes::new(&b"abcd"[..]))
+                .await;
+        }
+        assert_eq!(fast_field_cache.slice_cache.num_bytes(), 8);
+
+        tokio::time::advance(MIN_TIME_SINCE_LAST_ACCESS.mul_f32(1.1f32)).await;
+        // Touching the first index makes the second one the least recently used entry.
+        assert!(quickwit_cache
+            .get(Path::new("index-1/split.fast"), 0..4)
+            .await
+            .is_some());
+
+        tokio::time::advance(MIN_TIME_SINCE_LAST_ACCESS.mul_f32(1.1f32)).await;
+        quickwit_cache
+            .put(
+                Path::new("index-3/split.fast").to_path_buf(),
+                0..4,
+                OwnedBytes::new(&b"efgh"[..]),
+            )
+            .await;
+        assert!(fast_field_cache.slice_cache.num_bytes() <= 10);
+        assert!(quickwit_cache
+            .get(Path::new("index-1/split.fast"), 0..4)
+            .await
+            .is_some());
+        assert!(quickwit_cache
+            .get(Path::new("index-2/split.fast"), 0..4)
+            .await
+            .is_none());
+        assert!(quickwit_cache
+            .get(Path::new("index-3/split.fast"), 0..4)
+            .await
+            .is_some());
+    }
 }
@@ -19,7 +19,7 @@
 
 use std::fmt;
 use std::ops::Range;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use async_trait::async_trait;
@@ -27,13 +27,15 @@ use quickwit_common::uri::Uri;
 use tokio::io::AsyncRead;
 
 use crate::cache::StorageCache;
+use crate::metrics::StorageCacheMetrics;
 use crate::storage::SendableAsync;
-use crate::{BulkDeleteError, OwnedBytes, Storage, StorageResult};
+use crate::{BulkDeleteError, OwnedBytes, Storage, StorageErrorKind, StorageResult};
 
 /// Use with care, StorageWithCache is read-only.
 pub struct StorageWithCache {
     pub storage: Arc<dyn Storage>,
     pub cache: Arc<dyn StorageCache>,
+    pub(crate) metrics: StorageCacheMetrics,
 }
 
 impl fmt::Debug for StorageWithCache {
@@ -53,7 +55,31 @@ impl Storage for StorageWithCache {
         path: &Path,
         _payload: Box<dyn crate::PutPayload>,
     ) -> crate::StorageResult<()> {
-        unimplemented!("StorageWithCache is readonly. Failed to put {:?}", path)
+        Err(StorageErrorKind::Unsupported.with_error(anyhow::anyhow!(
+            "failed to put `{}`: `StorageWithCache` is read-only",
+            path.display()
+        )))
+    }
+
+    async fn put_if_absent(
+        &self,
+        path: &Path,
+        _payload: Box<dyn crate::PutPayload>,
+    ) -> crate::StorageResult<bool> {
+        Err(StorageErrorKind::Unsupported.with_error(anyhow::anyhow!(
+            "failed to put `{}`: `StorageWithCache` is read-only",
+            path.display()
+        )))
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> StorageResult<()> {
+        // The cache cannot be invalidated for `to`, so copying through it could serve stale
+        // bytes afterwards.
+        Err(StorageErrorKind::Unsupported.with_error(anyhow::anyhow!(
+            "failed to copy `{}` to `{}`: `StorageWithCache` is read-only",
+            from.display(),
+            to.display()
+        )))
     }
 
     async fn copy_to(&self, path: &Path, output: &mut dyn SendableAsync) -> StorageResult<()> {
@@ -62,8 +88,10 @@ impl Storage for StorageWithCache {
 
     async fn get_slice(&self, path: &Path, byte_range: Range<usize>) -> StorageResult<OwnedBytes> {
         if let Some(bytes) = self.cache.get(path, byte_range.clone()).await {
+            self.metrics.record_hit(bytes.len());
             Ok(bytes)
         } else {
+            self.metrics.record_miss();
             let bytes = self.storage.get_slice(path, byte_range.clone()).await?;
             self.cache
                 .put(path.to_owned(), byte_range, bytes.clone())
@@ -72,6 +100,49 @@ impl Storage for StorageWithCache {
         }
     }
 
+    async fn get_slices(
+        &self,
+        path: &Path,
+        byte_ranges: &[Range<usize>],
+    ) -> StorageResult<Vec<OwnedBytes>> {
+        let mut slices_opt: Vec<Option<OwnedBytes>> = Vec::with_capacity(byte_ranges.len());
+        let mut missing_byte_ranges: Vec<Range<usize>> = Vec::new();
+        for byte_range in byte_ranges {
+            let slice_opt = self.cache.get(path, byte_range.clone()).await;
+            if let Some(slice) = &slice_opt {
+                self.metrics.record_hit(slice.len());
+            } else {
+                self.metrics.record_miss();
+                missing_byte_ranges.push(byte_range.clone());
+            }
+            slices_opt.push(slice_opt);
+        }
+        if missing_byte_ranges.is_empty() {
+            return Ok(slices_opt.into_iter().flatten().collect());
+        }
+        let mut missing_slices = self
+            .storage
+            .get_slices(path, &missing_byte_ranges)
+            .await?
+            .into_iter();
+        let mut slices = Vec::with_capacity(byte_ranges.len());
+        for (byte_range, slice_opt) in byte_ranges.iter().zip(slices_opt) {
+            let slice = if let Some(slice) = slice_opt {
+                slice
+            } else {
+                let slice = missing_slices
+                    .next()
+                    .expect("the storage should return one slice per byte range");
+                self.cache
+                    .put(path.to_owned(), byte_range.clone(), slice.clone())
+                    .await;
+                slice
+            };
+            slices.push(slice);
+        }
+        Ok(slices)
+    }
+
     async fn get_slice_stream(
         &self,
         path: &Path,
@@ -85,8 +156,10 @@ impl Storage for StorageWithCache {
 
     async fn get_all(&self, path: &Path) -> StorageResult<OwnedBytes> {
         if let Some(bytes) = self.cache.get_all(path).await {
+            self.metrics.record_hit(bytes.len());
             Ok(bytes)
         } else {
+            self.metrics.record_miss();
             let bytes = self.storage.get_all(path).await?;
             self.cache.put_all(path.to_owned(), bytes.clone()).await;
             Ok(bytes)
@@ -105,6 +178,10 @@ impl Storage for StorageWithCache {
         self.storage.exists(path).await
     }
 
+    async fn list(&self, prefix: &Path) -> StorageResult<Vec<PathBuf>> {
+        self.storage.list(prefix).await
+    }
+
     async fn file_num_bytes(&self, path: &Path) -> StorageResult<u64> {
         self.storage.file_num_bytes(path).await
     }
@@ -120,6 +197,8 @@ mod tests {
     use std::path::PathBuf;
     use std::sync::Mutex;
 
+    use quickwit_common::uri::Protocol;
+
     use super::*;
     use crate::{MockStorage, MockStorageCache, OwnedBytes};
 
@@ -151,6 +230,7 @@ mod tests {
         let storage_with_cache = StorageWithCache {
             storage: Arc::new(mock_storage),
             cache: Arc::new(mock_cache),
+            metrics: StorageCacheMetrics::new("put_in_cache_test", Protocol::Ram),
         };
 
         let data1 = storage_with_cache
@@ -163,5 +243,36 @@ mod tests {
             .await
             .unwrap();
         assert_eq!(data1, data2);
+
+        let metrics = &storage_with_cache.metrics;
+        assert_eq!(metrics.hits_num_items.get(), 1);
+        assert_eq!(metrics.hits_num_bytes.get(), 3);
+        assert_eq!(metrics.misses_num_items.get(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_storage_with_cache_writes_are_unsupported() {
+        let storage_with_cache = StorageWithCache {
+            storage: Arc::new(MockStorage::default()),
+            cache: Arc::new(MockStorageCache::default()),
+            metrics: StorageCacheMetrics::new("copy_test", Protocol::Ram),
+        };
+        let error = storage_with_cache
+            .copy(Path::new("source_file"), Path::new("target_file"))
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), StorageErrorKind::Unsupported);
+
+        let error = storage_with_cache
+            .put(Path::new("target_file"), Box::new(b"abc".to_vec()))
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), StorageErrorKind::Unsupported);
+
+        let error = storage_with_cache
+            .put_if_absent(Path::new("target_file"), Box::new(b"abc".to_vec()))
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), StorageErrorKind::Unsupported);
     }
 }
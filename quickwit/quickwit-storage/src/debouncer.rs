@@ -25,14 +25,14 @@ use std::sync::{Arc, Mutex};
 
 use async_trait::async_trait;
 use fnv::FnvHashMap;
-use futures::future::{BoxFuture, WeakShared};
+use futures::future::{BoxFuture, Shared, WeakShared};
 use futures::{Future, FutureExt};
 use quickwit_common::uri::Uri;
 use tantivy::directory::OwnedBytes;
 use tokio::io::AsyncRead;
 
 use crate::storage::SendableAsync;
-use crate::{BulkDeleteError, Storage, StorageResult};
+use crate::{BulkDeleteError, Storage, StorageResult, STORAGE_METRICS};
 
 /// The AsyncDebouncer debounces inflight Futures, so that concurrent async request to the same data
 /// source can be deduplicated.
@@ -67,6 +67,29 @@ impl<K: Hash + Eq + Clone, V: Clone> AsyncDebouncer<K, V> {
         guard.retain(|_, v| v.upgrade().is_some());
     }
 
+    /// Returns the inflight future registered for `key`, if any.
+    fn get_inflight(&self, key: &K) -> Option<Shared<BoxFuture<'static, V>>> {
+        let guard = self.cache.lock().unwrap();
+        guard.get(key).and_then(WeakShared::upgrade)
+    }
+
+    /// Registers `future` as the inflight future for `key` and returns a shared handle on it. The
+    /// entry lives as long as the returned handle (or one of its clones) is alive.
+    fn register(&self, key: K, future: BoxFuture<'static, V>) -> Shared<BoxFuture<'static, V>> {
+        let future = future.shared();
+        self.cache.lock().unwrap().insert(
+            key,
+            future.clone().downgrade().expect(
+                "future has been dropped, but that shouldn't happen since it's still in scope",
+            ),
+        );
+        future
+    }
+
+    fn remove(&self, key: &K) {
+        self.cache.lock().unwrap().remove(key);
+    }
+
     /// Instead of the future directly, a constructor to build the future is passed.
     /// In case there is already an existing Future for the passed key, the constructor is not
     /// used.
@@ -136,6 +159,16 @@ impl<T: Storage> DebouncedStorage<T> {
     }
 }
 
+/// Records whether a read request initiated a fetch on the underlying storage or was served by an
+/// identical in-flight request.
+fn record_debouncer_metrics(is_fetch: bool) {
+    if is_fetch {
+        STORAGE_METRICS.debouncer_fetches_total.inc();
+    } else {
+        STORAGE_METRICS.debouncer_hits_total.inc();
+    }
+}
+
 #[async_trait]
 impl<T: Storage> Storage for DebouncedStorage<T> {
     async fn check_connectivity(&self) -> anyhow::Result<()> {
@@ -150,6 +183,18 @@ impl<T: Storage> Storage for DebouncedStorage<T> {
         self.underlying.put(path, payload).await
     }
 
+    async fn put_if_absent(
+        &self,
+        path: &Path,
+        payload: Box<dyn crate::PutPayload>,
+    ) -> crate::StorageResult<bool> {
+        self.underlying.put_if_absent(path, payload).await
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> StorageResult<()> {
+        self.underlying.copy(from, to).await
+    }
+
     async fn copy_to(&self, path: &Path, output: &mut dyn SendableAsync) -> StorageResult<()> {
         self.underlying.copy_to(path, output).await
     }
@@ -157,11 +202,77 @@ impl<T: Storage> Storage for DebouncedStorage<T> {
     async fn get_slice(&self, path: &Path, range: Range<usize>) -> StorageResult<OwnedBytes> {
         let (debouncer, underlying) = (self.slice_debouncer.clone(), self.underlying.clone());
         let key = (path.to_owned(), range);
-        debouncer
-            .get_or_create(key.clone(), || async move {
-                underlying.get_slice(&key.0, key.1).await
+        let mut is_fetch = false;
+        let result = debouncer
+            .get_or_create(key.clone(), || {
+                is_fetch = true;
+                async move { underlying.get_slice(&key.0, key.1).await }
             })
-            .await
+            .await;
+        record_debouncer_metrics(is_fetch);
+        result
+    }
+
+    async fn get_slices(
+        &self,
+        path: &Path,
+        ranges: &[Range<usize>],
+    ) -> StorageResult<Vec<OwnedBytes>> {
+        let debouncer = &self.slice_debouncer;
+        debouncer.cleanup();
+
+        let mut slice_futures = Vec::with_capacity(ranges.len());
+        let mut missing_ranges = Vec::new();
+        let mut missing_indexes = Vec::new();
+
+        for (idx, range) in ranges.iter().enumerate() {
+            let key = (path.to_owned(), range.clone());
+            if let Some(future) = debouncer.get_inflight(&key) {
+                record_debouncer_metrics(false);
+                slice_futures.push(Some(future));
+            } else {
+                missing_ranges.push(range.clone());
+                missing_indexes.push(idx);
+                slice_futures.push(None);
+            }
+        }
+        // The ranges that are not already being fetched are fetched in a single batch, and each
+        // of them is registered in the debouncer so that concurrent reads can join the batch.
+        let mut registered_keys = Vec::with_capacity(missing_ranges.len());
+
+        if !missing_ranges.is_empty() {
+            let underlying = self.underlying.clone();
+            let batch_path = path.to_owned();
+            let batch_ranges = missing_ranges.clone();
+            let batch_future =
+                async move { underlying.get_slices(&batch_path, &batch_ranges).await }
+                    .boxed()
+                    .shared();
+
+            for (batch_idx, (range, idx)) in
+                missing_ranges.into_iter().zip(missing_indexes).enumerate()
+            {
+                let slice_future = batch_future
+                    .clone()
+                    .map(move |slices_res| slices_res.map(|slices| slices[batch_idx].clone()))
+                    .boxed();
+                let key = (path.to_owned(), range);
+                record_debouncer_metrics(true);
+                slice_futures[idx] = Some(debouncer.register(key.clone(), slice_future));
+                registered_keys.push(key);
+            }
+        }
+        let slices_res = futures::future::try_join_all(
+            slice_futures
+                .into_iter()
+                .map(|future_opt| future_opt.expect("every range should have a future")),
+        )
+        .await;
+
+        for key in &registered_keys {
+            debouncer.remove(key);
+        }
+        slices_res
     }
 
     async fn get_slice_stream(
@@ -181,15 +292,22 @@ impl<T: Storage> Storage for DebouncedStorage<T> {
         self.underlying.bulk_delete(paths).await
     }
 
+    async fn list(&self, prefix: &Path) -> StorageResult<Vec<PathBuf>> {
+        self.underlying.list(prefix).await
+    }
+
     async fn get_all(&self, path: &Path) -> StorageResult<OwnedBytes> {
         let (debouncer, underlying) = (self.slice_debouncer.clone(), self.underlying.clone());
         let key = (path.to_owned(), 0..usize::MAX);
-        debouncer
-            .get_or_create(
-                key.clone(),
-                || async move { underlying.get_all(&key.0).await },
-            )
-            .await
+        let mut is_fetch = false;
+        let result = debouncer
+            .get_or_create(key.clone(), || {
+                is_fetch = true;
+                async move { underlying.get_all(&key.0).await }
+            })
+            .await;
+        record_debouncer_metrics(is_fetch);
+        result
     }
 
     fn uri(&self) -> &Uri {
@@ -206,6 +324,7 @@ mod tests {
 
     use std::ops::Range;
     use std::path::PathBuf;
+    use std::str::FromStr;
     use std::sync::atomic::{AtomicU32, Ordering};
     use std::sync::Arc;
     use std::time::Duration;
@@ -217,6 +336,7 @@ mod tests {
     use tokio::task;
 
     use super::*;
+    use crate::LocalFileStorage;
 
     #[test]
     fn test_sync_and_send() {
@@ -407,6 +527,58 @@ mod tests {
         assert_eq!(get_global_debouncer().len(), 0);
     }
 
+    #[tokio::test]
+    async fn test_debounced_storage_metrics() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let test_filepath = get_test_file(&temp_dir).await;
+        let uri = Uri::from_str(temp_dir.path().to_str().unwrap()).unwrap();
+        let storage = DebouncedStorage::new(LocalFileStorage::from_uri(&uri).unwrap());
+        let path = Path::new(test_filepath.file_name().unwrap());
+
+        let hits_before = STORAGE_METRICS.debouncer_hits_total.get();
+        let fetches_before = STORAGE_METRICS.debouncer_fetches_total.get();
+
+        let results = futures::future::join_all([
+            storage.get_slice(path, 0..4),
+            storage.get_slice(path, 0..4),
+            storage.get_slice(path, 0..4),
+            storage.get_slice(path, 0..4),
+        ])
+        .await;
+        for result in results {
+            assert_eq!(result.unwrap().as_slice(), b"nice");
+        }
+        // Other tests may use debounced storages concurrently, hence the lower bounds.
+        assert!(STORAGE_METRICS.debouncer_hits_total.get() - hits_before >= 3);
+        assert!(STORAGE_METRICS.debouncer_fetches_total.get() - fetches_before >= 1);
+    }
+
+    #[tokio::test]
+    async fn test_debounced_storage_get_slices() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let test_filepath = get_test_file(&temp_dir).await;
+        let uri = Uri::from_str(temp_dir.path().to_str().unwrap()).unwrap();
+        let storage = DebouncedStorage::new(LocalFileStorage::from_uri(&uri).unwrap());
+        let path = Path::new(test_filepath.file_name().unwrap());
+
+        let hits_before = STORAGE_METRICS.debouncer_hits_total.get();
+
+        let (slices_res, slice_res) = futures::future::join(
+            storage.get_slices(path, &[0..4, 5..10]),
+            storage.get_slice(path, 0..4),
+        )
+        .await;
+        let slices = slices_res.unwrap();
+        assert_eq!(slices.len(), 2);
+        assert_eq!(slices[0].as_slice(), b"nice");
+        assert_eq!(slices[1].as_slice(), b"cache");
+        assert_eq!(slice_res.unwrap().as_slice(), b"nice");
+
+        // The single read joins the inflight batched read.
+        assert!(STORAGE_METRICS.debouncer_hits_total.get() - hits_before >= 1);
+        assert_eq!(storage.slice_debouncer.len(), 0);
+    }
+
     async fn load_via_fn(path: PathBuf, cnt: &AtomicU32) -> Result<String, String> {
         cnt.fetch_add(1, Ordering::SeqCst);
         let contents = Box::pin(fs::read_to_string(path))
@@ -41,6 +41,10 @@ pub enum StorageErrorKind {
     Timeout,
     /// Io error.
     Io,
+    /// The operation is not supported by the storage.
+    Unsupported,
+    /// The data read from the storage failed an integrity check.
+    Corrupted,
 }
 
 /// Generic Storage Resolver Error.
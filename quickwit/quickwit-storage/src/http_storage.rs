@@ -0,0 +1,453 @@
+// Copyright (C) 2024 Quickwit, Inc.
+//
+// Quickwit is offered under the AGPL v3.0 and as commercial software.
+// For commercial licensing, contact us at hello@quickwit.io.
+//
+// AGPL:
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as
+// published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use std::fmt;
+use std::io::Cursor;
+use std::ops::Range;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures::TryStreamExt;
+use hyper::header::{CONTENT_LENGTH, RANGE};
+use hyper::StatusCode;
+use quickwit_common::uri::Uri;
+use quickwit_config::StorageBackend;
+use reqwest::{Client, RequestBuilder, Response};
+use tokio::io::{AsyncRead, AsyncWriteExt};
+use tokio_util::io::StreamReader;
+
+use crate::storage::SendableAsync;
+use crate::{
+    BulkDeleteError, OwnedBytes, PutPayload, Storage, StorageError, StorageErrorKind,
+    StorageFactory, StorageResolverError, StorageResult,
+};
+
+/// Maximum duration for establishing a connection with the server.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Maximum duration of a request, including the download of the response body, which can be an
+/// entire split.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Read-only storage serving files from an HTTP(S) server, for instance splits published behind a
+/// CDN.
+///
+/// Slices are fetched with ranged `GET` requests and file sizes with `HEAD` requests. The server
+/// must honor the `Range` header. Writes and deletes fail with [`StorageErrorKind::Unsupported`].
+#[derive(Clone)]
+pub struct HttpStorage {
+    client: Client,
+    uri: Uri,
+}
+
+impl fmt::Debug for HttpStorage {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("HttpStorage")
+            .field("uri", &self.uri)
+            .finish()
+    }
+}
+
+impl HttpStorage {
+    /// Creates an [`HttpStorage`] serving the files located under `uri`.
+    pub fn new(client: Client, uri: Uri) -> Self {
+        Self { client, uri }
+    }
+
+    fn url(&self, path: &Path) -> StorageResult<String> {
+        let file_uri = self
+            .uri
+            .join(path)
+            .map_err(|error| StorageErrorKind::Internal.with_error(error))?;
+        Ok(file_uri.into_string())
+    }
+
+    /// Sends the request and converts the HTTP error statuses into storage errors.
+    async fn send(&self, request: RequestBuilder, path: &Path) -> StorageResult<Response> {
+        let response = request
+            .send()
+            .await
+            .map_err(reqwest_error_to_storage_error)?;
+        let status = response.status();
+
+        if status.is_success() {
+            return Ok(response);
+        }
+        let error_kind = match status {
+            StatusCode::NOT_FOUND => StorageErrorKind::NotFound,
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => StorageErrorKind::Unauthorized,
+            _ => StorageErrorKind::Service,
+        };
+        Err(error_kind.with_error(anyhow::anyhow!(
+            "failed to fetch file `{}` from `{}`: server responded with status `{status}`",
+            path.display(),
+            self.uri
+        )))
+    }
+
+    async fn get(&self, path: &Path) -> StorageResult<Response> {
+        let url = self.url(path)?;
+        self.send(self.client.get(url), path).await
+    }
+
+    fn unsupported_error(&self, operation: &str) -> StorageError {
+        StorageErrorKind::Unsupported.with_error(anyhow::anyhow!(
+            "failed to {operation}: HTTP storage `{}` is read-only",
+            self.uri
+        ))
+    }
+}
+
+pub(crate) fn reqwest_error_to_storage_error(error: reqwest::Error) -> StorageError {
+    if error.is_timeout() {
+        StorageErrorKind::Timeout.with_error(error)
+    } else {
+        StorageErrorKind::Io.with_error(error)
+    }
+}
+
+#[async_trait]
+impl Storage for HttpStorage {
+    async fn check_connectivity(&self) -> anyhow::Result<()> {
+        // Any response, including an error status, proves that the server is reachable.
+        self.client.head(self.uri.as_str()).send().await?;
+        Ok(())
+    }
+
+    async fn put(&self, path: &Path, _payload: Box<dyn PutPayload>) -> StorageResult<()> {
+        Err(self.unsupported_error(&format!("put file `{}`", path.display())))
+    }
+
+    async fn put_if_absent(
+        &self,
+        path: &Path,
+        _payload: Box<dyn PutPayload>,
+    ) -> StorageResult<bool> {
+        Err(self.unsupported_error(&format!("put file `{}`", path.display())))
+    }
+
+    async fn copy_to(&self, path: &Path, output: &mut dyn SendableAsync) -> StorageResult<()> {
+        let response = self.get(path).await?;
+        let mut body_reader = StreamReader::new(
+            response
+                .bytes_stream()
+                .map_err(|error| std::io::Error::new(std::io::ErrorKind::Other, error)),
+        );
+        tokio::io::copy(&mut body_reader, output).await?;
+        output.flush().await?;
+        Ok(())
+    }
+
+    async fn get_slice(&self, path: &Path, range: Range<usize>) -> StorageResult<OwnedBytes> {
+        if range.is_empty() {
+            return Ok(OwnedBytes::empty());
+        }
+        let url = self.url(path)?;
+        let range_header_value = format!("bytes={}-{}", range.start, range.end - 1);
+        let request = self.client.get(url).header(RANGE, range_header_value);
+        let response = self.send(request, path).await?;
+
+        // Servers are allowed to ignore the `Range` header and return the whole file, which we
+        // refuse to download to serve a slice.
+        if response.status() != StatusCode::PARTIAL_CONTENT {
+            return Err(StorageErrorKind::Service.with_error(anyhow::anyhow!(
+                "failed to fetch slice {range:?} of file `{}`: server ignored the `Range` header \
+                 and responded with status `{}`",
+                path.display(),
+                response.status()
+            )));
+        }
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(reqwest_error_to_storage_error)?;
+
+        if bytes.len() != range.len() {
+            return Err(StorageErrorKind::Internal.with_error(anyhow::anyhow!(
+                "failed to fetch slice {range:?} of file `{}`: received {} bytes",
+                path.display(),
+                bytes.len()
+            )));
+        }
+        Ok(OwnedBytes::new(bytes.to_vec()))
+    }
+
+    async fn get_slice_stream(
+        &self,
+        path: &Path,
+        range: Range<usize>,
+    ) -> StorageResult<Box<dyn AsyncRead + Send + Unpin>> {
+        let bytes = self.get_slice(path, range).await?;
+        Ok(Box::new(Cursor::new(bytes)))
+    }
+
+    async fn get_all(&self, path: &Path) -> StorageResult<OwnedBytes> {
+        let bytes = self
+            .get(path)
+            .await?
+            .bytes()
+            .await
+            .map_err(reqwest_error_to_storage_error)?;
+        Ok(OwnedBytes::new(bytes.to_vec()))
+    }
+
+    async fn delete(&self, path: &Path) -> StorageResult<()> {
+        Err(self.unsupported_error(&format!("delete file `{}`", path.display())))
+    }
+
+    async fn bulk_delete<'a>(&self, paths: &[&'a Path]) -> Result<(), BulkDeleteError> {
+        Err(BulkDeleteError {
+            error: Some(self.unsupported_error("delete files")),
+            unattempted: paths.iter().map(|path| path.to_path_buf()).collect(),
+            ..Default::default()
+        })
+    }
+
+    async fn file_num_bytes(&self, path: &Path) -> StorageResult<u64> {
+        let url = self.url(path)?;
+        let response = self.send(self.client.head(url), path).await?;
+        // `Response::content_length` reports the size of the (empty) body of `HEAD` responses, so
+        // we read the header instead.
+        response
+            .headers()
+            .get(CONTENT_LENGTH)
+            .and_then(|header_value| header_value.to_str().ok())
+            .and_then(|header_value| header_value.parse::<u64>().ok())
+            .ok_or_else(|| {
+                StorageErrorKind::Service.with_error(anyhow::anyhow!(
+                    "failed to get size of file `{}`: response has no valid `Content-Length` \
+                     header",
+                    path.display()
+                ))
+            })
+    }
+
+    fn uri(&self) -> &Uri {
+        &self.uri
+    }
+}
+
+/// Storage resolver for [`HttpStorage`].
+pub struct HttpStorageFactory {
+    client: Client,
+}
+
+impl Default for HttpStorageFactory {
+    fn default() -> Self {
+        let client = Client::builder()
+            .connect_timeout(CONNECT_TIMEOUT)
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .expect("HTTP client should be built");
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl StorageFactory for HttpStorageFactory {
+    fn backend(&self) -> StorageBackend {
+        StorageBackend::Http
+    }
+
+    async fn resolve(&self, uri: &Uri) -> Result<Arc<dyn Storage>, StorageResolverError> {
+        if !uri.protocol().is_http() {
+            let message = format!("URI `{uri}` is not a valid HTTP URI");
+            return Err(StorageResolverError::InvalidUri(message));
+        }
+        let storage = HttpStorage::new(self.client.clone(), uri.clone());
+        Ok(Arc::new(storage))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+    use std::net::SocketAddr;
+
+    use hyper::service::{make_service_fn, service_fn};
+    use hyper::{Body, Method, Request, Server};
+
+    use super::*;
+
+    const FILE_CONTENT: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+
+    /// Serves `FILE_CONTENT` at `/splits/file.split`, honoring `Range` headers of the form
+    /// `bytes=<start>-<end>`, and at `/splits/no-range.split`, ignoring them.
+    async fn serve_file(request: Request<Body>) -> Result<hyper::Response<Body>, Infallible> {
+        if request.uri().path() == "/splits/no-range.split" {
+            return Ok(hyper::Response::new(Body::from(FILE_CONTENT)));
+        }
+        if request.uri().path() != "/splits/file.split" {
+            let response = hyper::Response::builder()
+                .status(StatusCode::NOT_FOUND)
+                .body(Body::empty())
+                .unwrap();
+            return Ok(response);
+        }
+        if request.method() == Method::HEAD {
+            let response = hyper::Response::builder()
+                .header(CONTENT_LENGTH, FILE_CONTENT.len())
+                .body(Body::empty())
+                .unwrap();
+            return Ok(response);
+        }
+        let range_opt = request
+            .headers()
+            .get(RANGE)
+            .and_then(|header_value| header_value.to_str().ok())
+            .and_then(|header_value| header_value.strip_prefix("bytes="))
+            .and_then(|range| range.split_once('-'))
+            .map(|(start, end)| {
+                (
+                    start.parse::<usize>().unwrap(),
+                    end.parse::<usize>().unwrap(),
+                )
+            });
+
+        let response = if let Some((start, end)) = range_opt {
+            hyper::Response::builder()
+                .status(StatusCode::PARTIAL_CONTENT)
+                .body(Body::from(&FILE_CONTENT[start..=end]))
+                .unwrap()
+        } else {
+            hyper::Response::new(Body::from(FILE_CONTENT))
+        };
+        Ok(response)
+    }
+
+    async fn start_http_server() -> SocketAddr {
+        let make_service =
+            make_service_fn(|_conn| async { Ok::<_, Infallible>(service_fn(serve_file)) });
+        let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+        let server_addr = server.local_addr();
+        tokio::spawn(server);
+        server_addr
+    }
+
+    async fn http_storage_for_test() -> Arc<dyn Storage> {
+        let server_addr = start_http_server().await;
+        let uri = format!("http://{server_addr}/splits").parse().unwrap();
+        HttpStorageFactory::default().resolve(&uri).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_http_storage_read() {
+        let storage = http_storage_for_test().await;
+        let path = Path::new("file.split");
+
+        let slice = storage.get_slice(path, 3..8).await.unwrap();
+        assert_eq!(slice.as_slice(), b"defgh");
+
+        let slice = storage.get_slice(path, 25..26).await.unwrap();
+        assert_eq!(slice.as_slice(), b"z");
+
+        let slice = storage.get_slice(path, 5..5).await.unwrap();
+        assert!(slice.is_empty());
+
+        let all = storage.get_all(path).await.unwrap();
+        assert_eq!(all.as_slice(), FILE_CONTENT);
+
+        let mut slice_stream = storage.get_slice_stream(path, 0..3).await.unwrap();
+        let mut buffer = Vec::new();
+        tokio::io::copy(&mut slice_stream, &mut buffer)
+            .await
+            .unwrap();
+        assert_eq!(buffer, b"abc");
+
+        let num_bytes = storage.file_num_bytes(path).await.unwrap();
+        assert_eq!(num_bytes, FILE_CONTENT.len() as u64);
+
+        assert!(storage.exists(path).await.unwrap());
+        assert!(!storage.exists(Path::new("missing.split")).await.unwrap());
+
+        let error = storage
+            .get_all(Path::new("missing.split"))
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), StorageErrorKind::NotFound);
+
+        storage.check_connectivity().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_http_storage_rejects_ignored_range() {
+        let storage = http_storage_for_test().await;
+        let path = Path::new("no-range.split");
+
+        let error = storage.get_slice(path, 3..8).await.unwrap_err();
+        assert_eq!(error.kind(), StorageErrorKind::Service);
+
+        let all = storage.get_all(path).await.unwrap();
+        assert_eq!(all.as_slice(), FILE_CONTENT);
+
+        let mut buffer = Vec::new();
+        storage.copy_to(path, &mut buffer).await.unwrap();
+        assert_eq!(buffer, FILE_CONTENT);
+    }
+
+    #[tokio::test]
+    async fn test_http_storage_is_read_only() {
+        let storage = http_storage_for_test().await;
+        let path = Path::new("file.split");
+
+        let error = storage
+            .put(path, Box::new(b"payload".to_vec()))
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), StorageErrorKind::Unsupported);
+
+        let error = storage
+            .put_if_absent(path, Box::new(b"payload".to_vec()))
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), StorageErrorKind::Unsupported);
+
+        let error = storage.delete(path).await.unwrap_err();
+        assert_eq!(error.kind(), StorageErrorKind::Unsupported);
+
+        let bulk_delete_error = storage.bulk_delete(&[path]).await.unwrap_err();
+        assert_eq!(
+            bulk_delete_error.error.unwrap().kind(),
+            StorageErrorKind::Unsupported
+        );
+        assert_eq!(bulk_delete_error.unattempted, [path.to_path_buf()]);
+    }
+
+    #[tokio::test]
+    async fn test_http_storage_factory() {
+        let http_storage_factory = HttpStorageFactory::default();
+        let error = http_storage_factory
+            .resolve(&Uri::for_test("s3://bucket/splits"))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, StorageResolverError::InvalidUri(_)));
+
+        let storage = http_storage_factory
+            .resolve(&Uri::for_test("https://cdn.example.com/splits"))
+            .await
+            .unwrap();
+        assert_eq!(
+            storage.uri(),
+            &Uri::for_test("https://cdn.example.com/splits")
+        );
+    }
+}
@@ -37,29 +37,34 @@ mod storage;
 pub use debouncer::AsyncDebouncer;
 pub(crate) use debouncer::DebouncedStorage;
 
-pub use self::metrics::STORAGE_METRICS;
+pub use self::metrics::{StorageCacheMetrics, STORAGE_METRICS};
 pub use self::payload::PutPayload;
-pub use self::storage::Storage;
+pub use self::storage::{copy_across_storages, Storage};
 
 mod bundle_storage;
 mod error;
 
+#[cfg(feature = "http")]
+mod http_storage;
 mod local_file_storage;
 mod object_storage;
-#[cfg(feature = "gcs")]
-mod opendal_storage;
 mod payload;
 mod prefix_storage;
 mod ram_storage;
+mod read_batching_storage;
+mod request_limiter;
 mod split;
 mod split_cache;
 mod storage_factory;
 mod storage_resolver;
 mod versioned_component;
+#[cfg(feature = "webhdfs")]
+mod webhdfs_storage;
 
 use quickwit_common::uri::Uri;
-pub use split_cache::SplitCache;
+pub use split_cache::{prune_split_cache_directory, split_cache_directory_num_bytes, SplitCache};
 pub use tantivy::directory::OwnedBytes;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
 pub use versioned_component::VersionedComponent;
 
 pub use self::bundle_storage::{BundleStorage, BundleStorageFileOffsets};
@@ -68,17 +73,20 @@ pub use self::cache::MockStorageCache;
 pub use self::cache::{
     wrap_storage_with_cache, ByteRangeCache, MemorySizedCache, QuickwitCache, StorageCache,
 };
+#[cfg(feature = "http")]
+pub use self::http_storage::{HttpStorage, HttpStorageFactory};
 pub use self::local_file_storage::{LocalFileStorage, LocalFileStorageFactory};
 #[cfg(feature = "azure")]
 pub use self::object_storage::{AzureBlobStorage, AzureBlobStorageFactory};
+#[cfg(feature = "gcs")]
+pub use self::object_storage::{GoogleCloudStorage, GoogleCloudStorageFactory};
 pub use self::object_storage::{
     MultiPartPolicy, S3CompatibleObjectStorage, S3CompatibleObjectStorageFactory,
 };
-#[cfg(all(feature = "gcs", feature = "integration-testsuite"))]
-pub use self::opendal_storage::new_emulated_google_cloud_storage;
-#[cfg(feature = "gcs")]
-pub use self::opendal_storage::GoogleCloudStorageFactory;
 pub use self::ram_storage::{RamStorage, RamStorageBuilder};
+pub use self::read_batching_storage::ReadBatchingStorage;
+pub(crate) use self::request_limiter::PermitAsyncRead;
+pub use self::request_limiter::{StorageRequestLimiter, StorageRequestPermit};
 pub use self::split::{SplitPayload, SplitPayloadBuilder};
 #[cfg(any(test, feature = "testsuite"))]
 pub use self::storage::MockStorage;
@@ -91,6 +99,8 @@ pub use self::test_suite::{
     storage_test_multi_part_upload, storage_test_single_part_upload, storage_test_suite,
     test_write_and_bulk_delete,
 };
+#[cfg(feature = "webhdfs")]
+pub use self::webhdfs_storage::{WebHdfsStorage, WebHdfsStorageFactory};
 pub use crate::error::{
     BulkDeleteError, DeleteFailure, StorageError, StorageErrorKind, StorageResolverError,
     StorageResult,
@@ -112,6 +122,51 @@ pub async fn load_file(
     Ok(bytes)
 }
 
+/// Size of the slices requested by [`stream_file`].
+const STREAM_FILE_CHUNK_NUM_BYTES: usize = 8 * 1024 * 1024; // 8 MiB
+
+/// Streams a local or remote file into `writer`, one bounded slice at a time, so that large files
+/// are never fully loaded in memory. Returns the number of bytes written.
+pub async fn stream_file<W>(
+    storage_resolver: &StorageResolver,
+    uri: &Uri,
+    writer: &mut W,
+) -> anyhow::Result<u64>
+where
+    W: AsyncWrite + Unpin + ?Sized,
+{
+    stream_file_in_chunks(storage_resolver, uri, writer, STREAM_FILE_CHUNK_NUM_BYTES).await
+}
+
+async fn stream_file_in_chunks<W>(
+    storage_resolver: &StorageResolver,
+    uri: &Uri,
+    writer: &mut W,
+    chunk_num_bytes: usize,
+) -> anyhow::Result<u64>
+where
+    W: AsyncWrite + Unpin + ?Sized,
+{
+    let parent = uri
+        .parent()
+        .ok_or_else(|| anyhow::anyhow!("URI `{uri}` is not a valid file URI"))?;
+    let storage = storage_resolver.resolve(&parent).await?;
+    let file_name = uri
+        .file_name()
+        .ok_or_else(|| anyhow::anyhow!("URI `{uri}` is not a valid file URI"))?;
+    let file_num_bytes = storage.file_num_bytes(file_name).await? as usize;
+
+    for chunk_start in (0..file_num_bytes).step_by(chunk_num_bytes) {
+        let chunk_end = (chunk_start + chunk_num_bytes).min(file_num_bytes);
+        let mut chunk_stream = storage
+            .get_slice_stream(file_name, chunk_start..chunk_end)
+            .await?;
+        tokio::io::copy(&mut chunk_stream, writer).await?;
+    }
+    writer.flush().await?;
+    Ok(file_num_bytes as u64)
+}
+
 #[cfg(any(test, feature = "testsuite", feature = "integration-testsuite"))]
 mod for_test {
     use std::sync::Arc;
@@ -129,6 +184,7 @@ pub use for_test::storage_for_test;
 
 #[cfg(test)]
 mod tests {
+    use std::path::Path;
     use std::str::FromStr;
 
     use super::*;
@@ -148,6 +204,41 @@ mod tests {
             expected_bytes.as_bytes()
         );
     }
+
+    #[tokio::test]
+    async fn test_stream_file() {
+        let storage_resolver = StorageResolver::for_test();
+        let storage = storage_resolver
+            .resolve(&Uri::for_test("ram:///data"))
+            .await
+            .unwrap();
+        let file_content: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+        storage
+            .put(Path::new("file.bin"), Box::new(file_content.clone()))
+            .await
+            .unwrap();
+        let file_uri = Uri::for_test("ram:///data/file.bin");
+
+        for chunk_num_bytes in [1_000, 3_000, 20_000] {
+            let mut output = Vec::new();
+            let num_bytes =
+                stream_file_in_chunks(&storage_resolver, &file_uri, &mut output, chunk_num_bytes)
+                    .await
+                    .unwrap();
+            assert_eq!(num_bytes, 10_000);
+            assert_eq!(output, file_content);
+        }
+        let mut output = Vec::new();
+        stream_file(&storage_resolver, &file_uri, &mut output)
+            .await
+            .unwrap();
+        assert_eq!(output, file_content);
+
+        let missing_file_uri = Uri::for_test("ram:///data/missing.bin");
+        stream_file(&storage_resolver, &missing_file_uri, &mut Vec::new())
+            .await
+            .unwrap_err();
+    }
 }
 
 #[cfg(any(test, feature = "integration-testsuite"))]
@@ -280,6 +371,53 @@ pub(crate) mod test_suite {
         Ok(())
     }
 
+    async fn test_bulk_exists(storage: &mut dyn Storage) -> anyhow::Result<()> {
+        let test_paths = [Path::new("bulk_exists_foo"), Path::new("bulk_exists_bar")];
+        storage
+            .put(test_paths[0], Box::<std::vec::Vec<u8>>::default())
+            .await?;
+        assert_eq!(storage.bulk_exists(&test_paths).await?, vec![true, false]);
+        assert!(storage.bulk_exists(&[]).await?.is_empty());
+        storage.delete(test_paths[0]).await?;
+        Ok(())
+    }
+
+    async fn test_put_if_absent(storage: &mut dyn Storage) -> anyhow::Result<()> {
+        let test_path = Path::new("put_if_absent");
+        assert!(
+            storage
+                .put_if_absent(test_path, Box::new(b"abc".to_vec()))
+                .await?
+        );
+        // The second put is a no-op.
+        assert!(
+            !storage
+                .put_if_absent(test_path, Box::new(b"def".to_vec()))
+                .await?
+        );
+        let payload = storage.get_all(test_path).await?;
+        assert_eq!(&payload[..], b"abc");
+        storage.delete(test_path).await?;
+        Ok(())
+    }
+
+    async fn test_copy(storage: &mut dyn Storage) -> anyhow::Result<()> {
+        let source_path = Path::new("copy_source");
+        let target_path = Path::new("foo/copy_target");
+        storage.put(source_path, Box::new(b"abc".to_vec())).await?;
+        storage.put(target_path, Box::new(b"def".to_vec())).await?;
+        storage.copy(source_path, target_path).await?;
+        assert_eq!(&storage.get_all(source_path).await?[..], b"abc");
+        assert_eq!(&storage.get_all(target_path).await?[..], b"abc");
+
+        let missing_path = Path::new("copy_missing_source");
+        let copy_error = storage.copy(missing_path, target_path).await.unwrap_err();
+        assert_eq!(copy_error.kind(), StorageErrorKind::NotFound);
+
+        storage.bulk_delete(&[source_path, target_path]).await?;
+        Ok(())
+    }
+
     async fn test_delete_missing_file(storage: &mut dyn Storage) -> anyhow::Result<()> {
         let test_path = Path::new("missing_file");
         assert!(!storage.exists(test_path).await.unwrap());
@@ -331,10 +469,13 @@ pub(crate) mod test_suite {
             .await
             .context("write_and_bulk_delete")?;
         test_exists(storage).await.context("exists")?;
+        test_bulk_exists(storage).await.context("bulk_exists")?;
         test_write_and_delete_with_dir_separator(storage)
             .await
             .context("write_and_delete_with_separator")?;
         test_file_size(storage).await.context("file_size")?;
+        test_put_if_absent(storage).await.context("put_if_absent")?;
+        test_copy(storage).await.context("copy")?;
         test_delete_missing_file(storage)
             .await
             .context("delete_missing_file")?;
@@ -165,6 +165,53 @@ fn delete_all_dirs_if_empty<'a>(
     .boxed()
 }
 
+impl LocalFileStorage {
+    /// Writes the payload into a temporary file and then moves it to its final location. When
+    /// `if_absent` is set, the move fails atomically if a file already exists at `path`, in which
+    /// case `false` is returned.
+    async fn put_inner(
+        &self,
+        path: &Path,
+        payload: Box<dyn crate::PutPayload>,
+        if_absent: bool,
+    ) -> StorageResult<bool> {
+        let full_path = self.full_path(path)?;
+        let parent_dir = full_path.parent().ok_or_else(|| {
+            let err = anyhow::anyhow!("no parent directory for {full_path:?}");
+            StorageErrorKind::Internal.with_error(err)
+        })?;
+        if if_absent && full_path.try_exists()? {
+            return Ok(false);
+        }
+        tokio::fs::create_dir_all(parent_dir).await?;
+        let mut reader = payload.byte_stream().await?.into_async_read();
+        let named_temp_file = tempfile::NamedTempFile::new_in(parent_dir)?;
+        let (temp_std_file, temp_filepath) = named_temp_file.into_parts();
+        let mut temp_tokio_file = tokio::fs::File::from_std(temp_std_file);
+        tokio::io::copy(&mut reader, &mut temp_tokio_file).await?;
+        temp_tokio_file.flush().await?;
+        temp_tokio_file.sync_data().await?;
+
+        if if_absent {
+            match temp_filepath.persist_noclobber(&full_path) {
+                Ok(()) => {}
+                Err(persist_error) if persist_error.error.kind() == ErrorKind::AlreadyExists => {
+                    return Ok(false);
+                }
+                Err(persist_error) => return Err(StorageErrorKind::Io.with_error(persist_error)),
+            }
+        } else {
+            temp_filepath
+                .persist(&full_path)
+                .map_err(|err| StorageErrorKind::Io.with_error(err))?;
+        }
+        // We also need to sync the parent directory to ensure it
+        // the file move has been persisted on all file systems.
+        tokio::fs::File::open(parent_dir).await?.sync_data().await?;
+        Ok(true)
+    }
+}
+
 #[async_trait]
 impl Storage for LocalFileStorage {
     async fn check_connectivity(&self) -> anyhow::Result<()> {
@@ -180,25 +227,37 @@ impl Storage for LocalFileStorage {
         path: &Path,
         payload: Box<dyn crate::PutPayload>,
     ) -> crate::StorageResult<()> {
-        let full_path = self.full_path(path)?;
-        let parent_dir = full_path.parent().ok_or_else(|| {
-            let err = anyhow::anyhow!("no parent directory for {full_path:?}");
+        self.put_inner(path, payload, false).await?;
+        Ok(())
+    }
+
+    async fn put_if_absent(
+        &self,
+        path: &Path,
+        payload: Box<dyn crate::PutPayload>,
+    ) -> crate::StorageResult<bool> {
+        self.put_inner(path, payload, true).await
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> StorageResult<()> {
+        let from_full_path = self.full_path(from)?;
+        let to_full_path = self.full_path(to)?;
+        let parent_dir = to_full_path.parent().ok_or_else(|| {
+            let err = anyhow::anyhow!("no parent directory for {to_full_path:?}");
             StorageErrorKind::Internal.with_error(err)
         })?;
-
         tokio::fs::create_dir_all(parent_dir).await?;
-        let mut reader = payload.byte_stream().await?.into_async_read();
-        let named_temp_file = tempfile::NamedTempFile::new_in(parent_dir)?;
-        let (temp_std_file, temp_filepath) = named_temp_file.into_parts();
-        let mut temp_tokio_file = tokio::fs::File::from_std(temp_std_file);
-        tokio::io::copy(&mut reader, &mut temp_tokio_file).await?;
-        temp_tokio_file.flush().await?;
-        temp_tokio_file.sync_data().await?;
+        // The file is copied to a temporary file first so that readers never observe a partially
+        // written file at `to`.
+        let temp_filepath = tempfile::NamedTempFile::new_in(parent_dir)?.into_temp_path();
+        tokio::fs::copy(&from_full_path, &temp_filepath).await?;
+        tokio::fs::File::open(&temp_filepath)
+            .await?
+            .sync_data()
+            .await?;
         temp_filepath
-            .persist(&full_path)
+            .persist(&to_full_path)
             .map_err(|err| StorageErrorKind::Io.with_error(err))?;
-        // We also need to sync the parent directory to ensure it
-        // the file move has been persisted on all file systems.
         tokio::fs::File::open(parent_dir).await?.sync_data().await?;
         Ok(())
     }
@@ -318,6 +377,44 @@ impl Storage for LocalFileStorage {
         Ok(OwnedBytes::new(content_bytes))
     }
 
+    async fn list(&self, prefix: &Path) -> StorageResult<Vec<PathBuf>> {
+        ensure_valid_relative_path(prefix)?;
+        let prefix_str = prefix.to_string_lossy();
+        let mut paths = Vec::new();
+        let mut directories = vec![self.root.clone()];
+
+        while let Some(directory) = directories.pop() {
+            let mut read_dir = match tokio::fs::read_dir(&directory).await {
+                Ok(read_dir) => read_dir,
+                Err(err) if err.kind() == ErrorKind::NotFound => continue,
+                Err(err) => return Err(err.into()),
+            };
+            while let Some(dir_entry) = read_dir.next_entry().await? {
+                let path = dir_entry.path();
+                let relative_path = path
+                    .strip_prefix(&self.root)
+                    .expect("the path should be a descendant of the storage root");
+                let relative_path_str = relative_path.to_string_lossy();
+
+                if dir_entry.file_type().await?.is_dir() {
+                    // Only the directories that may contain files starting with the prefix are
+                    // walked.
+                    let relative_dir_str = format!("{relative_path_str}/");
+                    if relative_dir_str.starts_with(prefix_str.as_ref())
+                        || prefix_str.starts_with(&relative_dir_str)
+                    {
+                        directories.push(path);
+                    }
+                    continue;
+                }
+                if relative_path_str.starts_with(prefix_str.as_ref()) {
+                    paths.push(relative_path.to_path_buf());
+                }
+            }
+        }
+        Ok(paths)
+    }
+
     fn uri(&self) -> &Uri {
         &self.uri
     }
@@ -398,6 +495,64 @@ mod tests {
         assert_eq!(exist_error.kind(), StorageErrorKind::Unauthorized);
     }
 
+    #[tokio::test]
+    async fn test_local_file_storage_list() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let uri = Uri::from_str(&format!("{}", temp_dir.path().display())).unwrap();
+        let local_file_storage = LocalFileStorage::from_uri(&uri).unwrap();
+        for path in ["foo/split1.split", "foo/split2.split", "bar.split"] {
+            local_file_storage
+                .put(Path::new(path), Box::new(b"".to_vec()))
+                .await
+                .unwrap();
+        }
+        let mut paths = local_file_storage.list(Path::new("")).await.unwrap();
+        paths.sort();
+        assert_eq!(
+            paths,
+            [
+                PathBuf::from("bar.split"),
+                PathBuf::from("foo/split1.split"),
+                PathBuf::from("foo/split2.split"),
+            ]
+        );
+        let mut paths = local_file_storage.list(Path::new("foo")).await.unwrap();
+        paths.sort();
+        assert_eq!(
+            paths,
+            [
+                PathBuf::from("foo/split1.split"),
+                PathBuf::from("foo/split2.split"),
+            ]
+        );
+        let paths = local_file_storage
+            .list(Path::new("foo/split1"))
+            .await
+            .unwrap();
+        assert_eq!(paths, [PathBuf::from("foo/split1.split")]);
+
+        let paths = local_file_storage.list(Path::new("ba")).await.unwrap();
+        assert_eq!(paths, [PathBuf::from("bar.split")]);
+
+        assert!(local_file_storage
+            .list(Path::new("qux/"))
+            .await
+            .unwrap()
+            .is_empty());
+        local_file_storage
+            .list(Path::new("../foo"))
+            .await
+            .unwrap_err();
+
+        let missing_uri = Uri::from_str(&format!("{}/missing", temp_dir.path().display())).unwrap();
+        let missing_storage = LocalFileStorage::from_uri(&missing_uri).unwrap();
+        assert!(missing_storage
+            .list(Path::new(""))
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
     #[tokio::test]
     async fn test_local_file_storage_factory() -> anyhow::Result<()> {
         let temp_dir = tempfile::tempdir()?;
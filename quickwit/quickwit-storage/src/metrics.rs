@@ -20,7 +20,10 @@
 // See https://prometheus.io/docs/practices/naming/
 
 use once_cell::sync::Lazy;
-use quickwit_common::metrics::{new_counter, new_gauge, IntCounter, IntGauge};
+use quickwit_common::metrics::{
+    new_counter, new_counter_vec, new_gauge, IntCounter, IntCounterVec, IntGauge,
+};
+use quickwit_common::uri::Protocol;
 
 /// Counters associated to storage operations.
 pub struct StorageMetrics {
@@ -29,11 +32,19 @@ pub struct StorageMetrics {
     pub fast_field_cache: CacheMetrics,
     pub split_footer_cache: CacheMetrics,
     pub searcher_split_cache: CacheMetrics,
+    pub storage_cache_hits_total: IntCounterVec<2>,
+    pub storage_cache_hits_bytes: IntCounterVec<2>,
+    pub storage_cache_misses_total: IntCounterVec<2>,
     pub object_storage_get_total: IntCounter,
     pub object_storage_put_total: IntCounter,
     pub object_storage_put_parts: IntCounter,
     pub object_storage_download_num_bytes: IntCounter,
     pub object_storage_upload_num_bytes: IntCounter,
+    pub object_storage_requests_in_flight: IntGauge,
+    pub object_storage_requests_queued: IntGauge,
+    pub object_storage_request_retries_total: IntCounterVec<1>,
+    pub debouncer_hits_total: IntCounter,
+    pub debouncer_fetches_total: IntCounter,
 }
 
 impl Default for StorageMetrics {
@@ -45,6 +56,27 @@ impl Default for StorageMetrics {
             searcher_split_cache: CacheMetrics::for_component("searcher_split"),
 
             split_footer_cache: CacheMetrics::for_component("splitfooter"),
+            storage_cache_hits_total: new_counter_vec(
+                "cache_hits_total",
+                "Number of reads served by a cache placed in front of a storage, by cache and \
+                 storage protocol.",
+                "quickwit_storage",
+                ["cache", "protocol"],
+            ),
+            storage_cache_hits_bytes: new_counter_vec(
+                "cache_hits_bytes",
+                "Number of bytes served by a cache placed in front of a storage, by cache and \
+                 storage protocol.",
+                "quickwit_storage",
+                ["cache", "protocol"],
+            ),
+            storage_cache_misses_total: new_counter_vec(
+                "cache_misses_total",
+                "Number of reads forwarded to the storage by a cache placed in front of it, by \
+                 cache and storage protocol.",
+                "quickwit_storage",
+                ["cache", "protocol"],
+            ),
             object_storage_get_total: new_counter(
                 "object_storage_gets_total",
                 "Number of objects fetched.",
@@ -71,6 +103,33 @@ impl Default for StorageMetrics {
                 "Amount of data uploaded to an object storage.",
                 "quickwit_storage",
             ),
+            object_storage_requests_in_flight: new_gauge(
+                "object_storage_requests_in_flight",
+                "Number of requests currently in flight to an object storage.",
+                "quickwit_storage",
+            ),
+            object_storage_requests_queued: new_gauge(
+                "object_storage_requests_queued",
+                "Number of requests waiting for a permit to be sent to an object storage.",
+                "quickwit_storage",
+            ),
+            object_storage_request_retries_total: new_counter_vec(
+                "object_storage_request_retries_total",
+                "Number of requests to an object storage retried after a transient error, by \
+                 operation.",
+                "quickwit_storage",
+                ["operation"],
+            ),
+            debouncer_hits_total: new_counter(
+                "debouncer_hits_total",
+                "Number of read requests served by an identical in-flight request.",
+                "quickwit_storage",
+            ),
+            debouncer_fetches_total: new_counter(
+                "debouncer_fetches_total",
+                "Number of read requests forwarded by the debouncer to the underlying storage.",
+                "quickwit_storage",
+            ),
         }
     }
 }
@@ -120,6 +179,41 @@ impl CacheMetrics {
     }
 }
 
+/// Hit and miss counters of a cache placed in front of a storage, labeled by the cache name and
+/// the protocol of the storage.
+#[derive(Clone)]
+pub struct StorageCacheMetrics {
+    pub hits_num_items: IntCounter,
+    pub hits_num_bytes: IntCounter,
+    pub misses_num_items: IntCounter,
+}
+
+impl StorageCacheMetrics {
+    pub fn new(cache_name: &str, protocol: Protocol) -> Self {
+        let label_values = [cache_name, protocol.as_str()];
+        StorageCacheMetrics {
+            hits_num_items: STORAGE_METRICS
+                .storage_cache_hits_total
+                .with_label_values(label_values),
+            hits_num_bytes: STORAGE_METRICS
+                .storage_cache_hits_bytes
+                .with_label_values(label_values),
+            misses_num_items: STORAGE_METRICS
+                .storage_cache_misses_total
+                .with_label_values(label_values),
+        }
+    }
+
+    pub fn record_hit(&self, num_bytes: usize) {
+        self.hits_num_items.inc();
+        self.hits_num_bytes.inc_by(num_bytes as u64);
+    }
+
+    pub fn record_miss(&self) {
+        self.misses_num_items.inc();
+    }
+}
+
 /// Storage counters exposes a bunch a set of storage/cache related metrics through a prometheus
 /// endpoint.
 pub static STORAGE_METRICS: Lazy<StorageMetrics> = Lazy::new(StorageMetrics::default);
@@ -21,14 +21,16 @@ use std::collections::HashMap;
 use std::num::NonZeroU32;
 use std::ops::Range;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::{fmt, io};
 
 use async_trait::async_trait;
 use azure_core::error::ErrorKind;
+use azure_core::request_options::IfMatchCondition;
 use azure_core::{Pageable, StatusCode};
 use azure_storage::prelude::*;
-use azure_storage::Error as AzureError;
+use azure_storage::{CloudLocation, Error as AzureError};
 use azure_storage_blobs::blob::operations::GetBlobResponse;
 use azure_storage_blobs::prelude::*;
 use bytes::Bytes;
@@ -41,6 +43,7 @@ use quickwit_common::uri::Uri;
 use quickwit_common::{chunk_range, ignore_error_kind, into_u64_range};
 use quickwit_config::{AzureStorageConfig, StorageBackend};
 use regex::Regex;
+use reqwest::Url;
 use tantivy::directory::OwnedBytes;
 use thiserror::Error;
 use tokio::io::{AsyncRead, AsyncWriteExt, BufReader};
@@ -49,21 +52,30 @@ use tokio_util::io::StreamReader;
 use tracing::{instrument, warn};
 
 use crate::debouncer::DebouncedStorage;
+use crate::object_storage::{resolve_conditional_put, ConditionalPutOutcome};
 use crate::storage::SendableAsync;
 use crate::{
-    BulkDeleteError, DeleteFailure, MultiPartPolicy, PutPayload, Storage, StorageError,
-    StorageErrorKind, StorageFactory, StorageResolverError, StorageResult, STORAGE_METRICS,
+    BulkDeleteError, DeleteFailure, MultiPartPolicy, PermitAsyncRead, PutPayload, Storage,
+    StorageError, StorageErrorKind, StorageFactory, StorageRequestLimiter, StorageResolverError,
+    StorageResult, STORAGE_METRICS,
 };
 
 /// Azure object storage resolver.
 pub struct AzureBlobStorageFactory {
     storage_config: AzureStorageConfig,
+    // Shared by all the storages resolved by the factory.
+    request_limiter: StorageRequestLimiter,
 }
 
 impl AzureBlobStorageFactory {
     /// Creates a new Azure blob storage factory.
     pub fn new(storage_config: AzureStorageConfig) -> Self {
-        Self { storage_config }
+        let request_limiter =
+            StorageRequestLimiter::from_config(storage_config.max_concurrent_requests);
+        Self {
+            storage_config,
+            request_limiter,
+        }
     }
 }
 
@@ -74,7 +86,8 @@ impl StorageFactory for AzureBlobStorageFactory {
     }
 
     async fn resolve(&self, uri: &Uri) -> Result<Arc<dyn Storage>, StorageResolverError> {
-        let storage = AzureBlobStorage::from_uri(&self.storage_config, uri)?;
+        let storage = AzureBlobStorage::from_uri(&self.storage_config, uri)?
+            .with_request_limiter(self.request_limiter.clone());
         Ok(Arc::new(DebouncedStorage::new(storage)))
     }
 }
@@ -86,6 +99,8 @@ pub struct AzureBlobStorage {
     prefix: PathBuf,
     multipart_policy: MultiPartPolicy,
     retry_params: RetryParams,
+    uses_sas_token: bool,
+    request_limiter: StorageRequestLimiter,
 }
 
 impl fmt::Debug for AzureBlobStorage {
@@ -93,6 +108,8 @@ impl fmt::Debug for AzureBlobStorage {
         f.debug_struct("AzureBlobStorage")
             .field("uri", &self.uri)
             .field("prefix", &self.prefix)
+            .field("uses_sas_token", &self.uses_sas_token)
+            .field("request_limiter", &self.request_limiter)
             .finish()
     }
 }
@@ -103,6 +120,14 @@ impl AzureBlobStorage {
         let storage_credentials = StorageCredentials::access_key(account.clone(), access_key);
         let container_client =
             BlobServiceClient::new(account, storage_credentials).container_client(container_name);
+        Self::from_container_client(container_client, uri, false)
+    }
+
+    fn from_container_client(
+        container_client: ContainerClient,
+        uri: Uri,
+        uses_sas_token: bool,
+    ) -> Self {
         Self {
             container_client,
             uri,
@@ -112,6 +137,8 @@ impl AzureBlobStorage {
                 max_attempts: 3,
                 ..Default::default()
             },
+            uses_sas_token,
+            request_limiter: StorageRequestLimiter::from_config(None),
         }
     }
 
@@ -125,25 +152,25 @@ impl AzureBlobStorage {
             prefix,
             multipart_policy: self.multipart_policy,
             retry_params: self.retry_params,
+            uses_sas_token: self.uses_sas_token,
+            request_limiter: self.request_limiter,
         }
     }
 
+    /// Sets the limiter bounding the number of concurrent requests issued by the storage.
+    pub fn with_request_limiter(mut self, request_limiter: StorageRequestLimiter) -> Self {
+        self.request_limiter = request_limiter;
+        self
+    }
+
     /// Creates an emulated storage for testing.
     #[cfg(feature = "integration-testsuite")]
     pub fn new_emulated(container: &str) -> Self {
         use std::str::FromStr;
 
         let container_client = ClientBuilder::emulator().container_client(container);
-        Self {
-            container_client,
-            uri: Uri::from_str(&format!("azure://tester/{container}")).unwrap(),
-            prefix: PathBuf::new(),
-            multipart_policy: MultiPartPolicy::default(),
-            retry_params: RetryParams {
-                max_attempts: 3,
-                ..Default::default()
-            },
-        }
+        let uri = Uri::from_str(&format!("azure://tester/{container}")).unwrap();
+        Self::from_container_client(container_client, uri, false)
     }
 
     /// Sets the multipart policy.
@@ -158,29 +185,61 @@ impl AzureBlobStorage {
         azure_storage_config: &AzureStorageConfig,
         uri: &Uri,
     ) -> Result<AzureBlobStorage, StorageResolverError> {
-        let account_name = azure_storage_config.resolve_account_name().ok_or_else(|| {
-            let message = format!(
-                "could not find Azure account name in environment variable `{}` or storage config",
-                AzureStorageConfig::AZURE_STORAGE_ACCOUNT_ENV_VAR
-            );
-            StorageResolverError::InvalidConfig(message)
-        })?;
-        let access_key = azure_storage_config.resolve_access_key().ok_or_else(|| {
-            let message = format!(
-                "could not find Azure access key in environment variable `{}` or storage config",
-                AzureStorageConfig::AZURE_STORAGE_ACCESS_KEY_ENV_VAR
-            );
-            StorageResolverError::InvalidConfig(message)
-        })?;
         let (container_name, prefix) = parse_azure_uri(uri).ok_or_else(|| {
             let message = format!("failed to extract container name from Azure URI: {uri}");
             StorageResolverError::InvalidUri(message)
         })?;
+        let (account_name, cloud_location) =
+            resolve_cloud_location(azure_storage_config, &container_name)?;
+
+        let (storage_credentials, uses_sas_token) =
+            if let Some(sas_token) = azure_storage_config.resolve_sas_token() {
+                let storage_credentials = StorageCredentials::sas_token(
+                    sas_token.trim_start_matches('?'),
+                )
+                .map_err(|error| {
+                    let message = format!("failed to parse Azure SAS token: {error}");
+                    StorageResolverError::InvalidConfig(message)
+                })?;
+                (storage_credentials, true)
+            } else {
+                let access_key = azure_storage_config.resolve_access_key().ok_or_else(|| {
+                    let message = format!(
+                        "could not find Azure access key or SAS token in environment variables \
+                         `{}` and `{}` or storage config",
+                        AzureStorageConfig::AZURE_STORAGE_ACCESS_KEY_ENV_VAR,
+                        AzureStorageConfig::AZURE_STORAGE_SAS_TOKEN_ENV_VAR,
+                    );
+                    StorageResolverError::InvalidConfig(message)
+                })?;
+                let storage_credentials = StorageCredentials::access_key(account_name, access_key);
+                (storage_credentials, false)
+            };
+        let container_client = ClientBuilder::with_location(cloud_location, storage_credentials)
+            .container_client(container_name);
+        let request_limiter =
+            StorageRequestLimiter::from_config(azure_storage_config.max_concurrent_requests);
         let azure_blob_storage =
-            AzureBlobStorage::new(account_name, access_key, uri.clone(), container_name);
+            AzureBlobStorage::from_container_client(container_client, uri.clone(), uses_sas_token)
+                .with_request_limiter(request_limiter);
         Ok(azure_blob_storage.with_prefix(prefix))
     }
 
+    /// Converts an Azure error into a storage error. Authentication errors are likely caused by
+    /// an expired token when the requests are authenticated with a SAS token, so we log a warning
+    /// to help operators diagnose the failure.
+    fn to_storage_error(&self, error: AzureErrorWrapper) -> StorageError {
+        if self.uses_sas_token && error.is_authentication_error() {
+            warn!(
+                uri=%self.uri,
+                error=%error,
+                "Azure rejected a request authenticated with a SAS token: the token has likely \
+                 expired and must be renewed"
+            );
+        }
+        StorageError::from(error)
+    }
+
     /// Returns the blob name (a.k.a blob key).
     fn blob_name(&self, relative_path: &Path) -> String {
         let key_path = self.prefix.join(relative_path);
@@ -197,6 +256,7 @@ impl AzureBlobStorage {
         let capacity = range_opt.as_ref().map(Range::len).unwrap_or(0);
 
         retry(&self.retry_params, || async {
+            let _permit = self.request_limiter.acquire().await;
             let mut response_stream = if let Some(range) = range_opt.as_ref() {
                 self.container_client
                     .blob_client(&name)
@@ -213,32 +273,48 @@ impl AzureBlobStorage {
             Result::<_, AzureErrorWrapper>::Ok(buf)
         })
         .await
-        .map_err(StorageError::from)
+        .map_err(|error| self.to_storage_error(error))
     }
 
-    /// Performs a single part upload.
+    /// Performs a single part upload. When `if_absent` is set, the upload is rejected if the blob
+    /// already exists.
     async fn put_single_part<'a>(
         &'a self,
         name: &'a str,
         payload: Box<dyn crate::PutPayload>,
-    ) -> StorageResult<()> {
+        if_absent: bool,
+    ) -> StorageResult<ConditionalPutOutcome> {
         crate::STORAGE_METRICS.object_storage_put_parts.inc();
         crate::STORAGE_METRICS
             .object_storage_upload_num_bytes
             .inc_by(payload.len());
-        retry(&self.retry_params, || async {
+        let num_attempts = AtomicUsize::new(0);
+        let put_result = retry(&self.retry_params, || async {
+            num_attempts.fetch_add(1, Ordering::Relaxed);
+            let _permit = self.request_limiter.acquire().await;
             let data = Bytes::from(payload.read_all().await?.to_vec());
             let hash = azure_storage_blobs::prelude::Hash::from(md5::compute(&data[..]).0);
-            self.container_client
+            let mut put_block_blob = self
+                .container_client
                 .blob_client(name)
                 .put_block_blob(data)
-                .hash(hash)
-                .into_future()
-                .await?;
+                .hash(hash);
+            if if_absent {
+                put_block_blob = put_block_blob.if_match(if_none_match_any());
+            }
+            put_block_blob.into_future().await?;
             Result::<(), AzureErrorWrapper>::Ok(())
         })
-        .await?;
-        Ok(())
+        .await;
+        let is_written = match put_result {
+            Ok(()) => true,
+            Err(error) if if_absent && error.is_already_exists_error() => false,
+            Err(error) => return Err(self.to_storage_error(error)),
+        };
+        Ok(ConditionalPutOutcome::new(
+            is_written,
+            num_attempts.load(Ordering::Relaxed),
+        ))
     }
 
     /// Performs a multipart upload.
@@ -248,7 +324,8 @@ impl AzureBlobStorage {
         payload: Box<dyn PutPayload>,
         part_len: u64,
         total_len: u64,
-    ) -> StorageResult<()> {
+        if_absent: bool,
+    ) -> StorageResult<ConditionalPutOutcome> {
         assert!(total_len > 0);
         let multipart_ranges =
             chunk_range(0..total_len as usize, part_len as usize).map(into_u64_range);
@@ -269,6 +346,9 @@ impl AzureBlobStorage {
                             extract_range_data_and_hash(moved_payload.box_clone(), range.clone())
                                 .await?;
                         let hash = azure_storage_blobs::prelude::Hash::from(hash_digest.0);
+                        // Each block holds its own permit since the blocks are uploaded
+                        // concurrently.
+                        let _permit = self.request_limiter.acquire().await;
                         moved_blob_client
                             .put_block(block_id.clone(), data)
                             .hash(hash)
@@ -288,24 +368,59 @@ impl AzureBlobStorage {
                 Ok(block_id) => block_list
                     .blocks
                     .push(BlobBlockType::new_uncommitted(block_id)),
-                Err(error) => return Err(error.into()),
+                Err(error) => return Err(self.to_storage_error(error)),
             }
         }
 
-        // Commit all uploaded blocks.
-        blob_client
-            .put_block_list(block_list)
-            .into_future()
-            .await
-            .map_err(AzureErrorWrapper::from)?;
+        // Commit all uploaded blocks. The uncommitted blocks of a rejected commit are garbage
+        // collected by Azure.
+        let num_attempts = AtomicUsize::new(0);
+        let commit_result = retry(&self.retry_params, || async {
+            num_attempts.fetch_add(1, Ordering::Relaxed);
+            let _permit = self.request_limiter.acquire().await;
+            let mut put_block_list = blob_client.put_block_list(block_list.clone());
+            if if_absent {
+                put_block_list = put_block_list.if_match(if_none_match_any());
+            }
+            put_block_list.into_future().await?;
+            Result::<(), AzureErrorWrapper>::Ok(())
+        })
+        .await;
+        let is_written = match commit_result {
+            Ok(()) => true,
+            Err(error) if if_absent && error.is_already_exists_error() => false,
+            Err(error) => return Err(self.to_storage_error(error)),
+        };
+        Ok(ConditionalPutOutcome::new(
+            is_written,
+            num_attempts.load(Ordering::Relaxed),
+        ))
+    }
 
-        Ok(())
+    async fn put_inner(
+        &self,
+        path: &Path,
+        payload: Box<dyn crate::PutPayload>,
+        if_absent: bool,
+    ) -> StorageResult<ConditionalPutOutcome> {
+        crate::STORAGE_METRICS.object_storage_put_total.inc();
+        let name = self.blob_name(path);
+        let total_len = payload.len();
+        let part_num_bytes = self.multipart_policy.part_num_bytes(total_len);
+
+        if part_num_bytes >= total_len {
+            self.put_single_part(&name, payload, if_absent).await
+        } else {
+            self.put_multi_part(&name, payload, part_num_bytes, total_len, if_absent)
+                .await
+        }
     }
 }
 
 #[async_trait]
 impl Storage for AzureBlobStorage {
     async fn check_connectivity(&self) -> anyhow::Result<()> {
+        let _permit = self.request_limiter.acquire().await;
         if let Some(first_blob_result) = self
             .container_client
             .list_blobs()
@@ -324,26 +439,28 @@ impl Storage for AzureBlobStorage {
         path: &Path,
         payload: Box<dyn crate::PutPayload>,
     ) -> crate::StorageResult<()> {
-        crate::STORAGE_METRICS.object_storage_put_total.inc();
-        let name = self.blob_name(path);
-        let total_len = payload.len();
-        let part_num_bytes = self.multipart_policy.part_num_bytes(total_len);
-
-        if part_num_bytes >= total_len {
-            self.put_single_part(&name, payload).await?;
-        } else {
-            self.put_multi_part(&name, payload, part_num_bytes, total_len)
-                .await?;
-        }
+        self.put_inner(path, payload, false).await?;
         Ok(())
     }
 
+    async fn put_if_absent(
+        &self,
+        path: &Path,
+        payload: Box<dyn crate::PutPayload>,
+    ) -> crate::StorageResult<bool> {
+        let payload_len = payload.len();
+        let outcome = self.put_inner(path, payload, true).await?;
+        resolve_conditional_put(self, path, payload_len, outcome).await
+    }
+
     async fn copy_to(&self, path: &Path, output: &mut dyn SendableAsync) -> StorageResult<()> {
+        let _permit = self.request_limiter.acquire().await;
         let name = self.blob_name(path);
         let mut output_stream = self.container_client.blob_client(name).get().into_stream();
 
         while let Some(chunk_result) = output_stream.next().await {
-            let chunk_response = chunk_result.map_err(AzureErrorWrapper::from)?;
+            let chunk_response =
+                chunk_result.map_err(|error| self.to_storage_error(error.into()))?;
             let chunk_response_body_stream = chunk_response
                 .data
                 .map_err(|err| FutureError::new(FutureErrorKind::Other, err))
@@ -360,6 +477,7 @@ impl Storage for AzureBlobStorage {
     }
 
     async fn delete(&self, path: &Path) -> StorageResult<()> {
+        let _permit = self.request_limiter.acquire().await;
         let blob_name = self.blob_name(path);
         let delete_res: Result<_, StorageError> = self
             .container_client
@@ -367,7 +485,7 @@ impl Storage for AzureBlobStorage {
             .delete()
             .into_future()
             .await
-            .map_err(|err| AzureErrorWrapper::from(err).into());
+            .map_err(|error| self.to_storage_error(error.into()));
         ignore_error_kind!(StorageErrorKind::NotFound, delete_res)?;
         Ok(())
     }
@@ -436,6 +554,8 @@ impl Storage for AzureBlobStorage {
         range: Range<usize>,
     ) -> StorageResult<Box<dyn AsyncRead + Send + Unpin>> {
         retry(&self.retry_params, || async {
+            // The permit is released once the returned reader is dropped.
+            let permit = self.request_limiter.acquire().await;
             let range = range.clone();
             let name = self.blob_name(path);
             let page_stream = self
@@ -462,10 +582,12 @@ impl Storage for AzureBlobStorage {
             } else {
                 Box::new(tokio::io::empty())
             };
+            let reader: Box<dyn AsyncRead + Send + Unpin> =
+                Box::new(PermitAsyncRead::new(reader, permit));
             Result::<Box<dyn AsyncRead + Send + Unpin>, AzureErrorWrapper>::Ok(reader)
         })
         .await
-        .map_err(|e| e.into())
+        .map_err(|error| self.to_storage_error(error))
     }
 
     #[instrument(level = "debug", skip(self), fields(fetched_bytes_len))]
@@ -486,6 +608,7 @@ impl Storage for AzureBlobStorage {
     }
 
     async fn file_num_bytes(&self, path: &Path) -> StorageResult<u64> {
+        let _permit = self.request_limiter.acquire().await;
         let name = self.blob_name(path);
         let properties_result = self
             .container_client
@@ -495,7 +618,7 @@ impl Storage for AzureBlobStorage {
             .await;
         match properties_result {
             Ok(response) => Ok(response.blob.properties.content_length),
-            Err(err) => Err(StorageError::from(AzureErrorWrapper::from(err))),
+            Err(error) => Err(self.to_storage_error(error.into())),
         }
     }
 
@@ -504,6 +627,11 @@ impl Storage for AzureBlobStorage {
     }
 }
 
+/// Makes a write conditional on the absence of the blob (`If-None-Match: *`).
+fn if_none_match_any() -> IfMatchCondition {
+    IfMatchCondition::NotMatch("*".to_string())
+}
+
 /// Copy range of payload into `Bytes` and return the computed md5.
 async fn extract_range_data_and_hash(
     payload: Box<dyn PutPayload>,
@@ -539,6 +667,61 @@ pub fn parse_azure_uri(uri: &Uri) -> Option<(String, PathBuf)> {
     Some((container, prefix))
 }
 
+/// Resolves the account name and the location of the blob service endpoint, either from the
+/// container URL when it is set, or from the account name.
+fn resolve_cloud_location(
+    azure_storage_config: &AzureStorageConfig,
+    container_name: &str,
+) -> Result<(String, CloudLocation), StorageResolverError> {
+    let Some(container_url) = &azure_storage_config.container_url else {
+        let account_name = azure_storage_config.resolve_account_name().ok_or_else(|| {
+            let message = format!(
+                "could not find Azure account name in environment variable `{}` or storage config",
+                AzureStorageConfig::AZURE_STORAGE_ACCOUNT_ENV_VAR
+            );
+            StorageResolverError::InvalidConfig(message)
+        })?;
+        let cloud_location = CloudLocation::Public {
+            account: account_name.clone(),
+        };
+        return Ok((account_name, cloud_location));
+    };
+    let invalid_container_url_error = |reason: &str| {
+        let message = format!("invalid Azure container URL `{container_url}`: {reason}");
+        StorageResolverError::InvalidConfig(message)
+    };
+    let url = Url::parse(container_url)
+        .map_err(|error| invalid_container_url_error(&error.to_string()))?;
+    let host = url
+        .host_str()
+        .ok_or_else(|| invalid_container_url_error("missing host"))?;
+    let url_container_name = url
+        .path_segments()
+        .and_then(|mut path_segments| path_segments.find(|segment| !segment.is_empty()))
+        .ok_or_else(|| invalid_container_url_error("missing container name"))?;
+    if url_container_name != container_name {
+        let reason = format!(
+            "container `{url_container_name}` does not match container `{container_name}` of the \
+             storage URI"
+        );
+        return Err(invalid_container_url_error(&reason));
+    }
+    // The account name is the first label of the host of the public endpoints, e.g.
+    // `<account>.blob.core.windows.net`.
+    let account_name = azure_storage_config
+        .resolve_account_name()
+        .unwrap_or_else(|| host.split('.').next().unwrap_or(host).to_string());
+    let endpoint = match url.port() {
+        Some(port) => format!("{}://{host}:{port}", url.scheme()),
+        None => format!("{}://{host}", url.scheme()),
+    };
+    let cloud_location = CloudLocation::Custom {
+        account: account_name.clone(),
+        uri: endpoint,
+    };
+    Ok((account_name, cloud_location))
+}
+
 /// Collect a download stream into an output buffer.
 async fn download_all(
     chunk_stream: &mut Pageable<GetBlobResponse, AzureError>,
@@ -578,6 +761,8 @@ impl Retryable for AzureErrorWrapper {
                     | StatusCode::Unauthorized
                     | StatusCode::BadRequest
                     | StatusCode::Forbidden
+                    | StatusCode::Conflict
+                    | StatusCode::PreconditionFailed
             ),
             ErrorKind::Io => true,
             _ => false,
@@ -585,6 +770,31 @@ impl Retryable for AzureErrorWrapper {
     }
 }
 
+impl AzureErrorWrapper {
+    /// Returns whether a conditional write was rejected because the blob already exists.
+    fn is_already_exists_error(&self) -> bool {
+        matches!(
+            self.inner.kind(),
+            ErrorKind::HttpResponse {
+                status: StatusCode::Conflict | StatusCode::PreconditionFailed,
+                ..
+            }
+        )
+    }
+
+    /// Returns whether the request was rejected because it could not be authenticated or
+    /// authorized.
+    fn is_authentication_error(&self) -> bool {
+        match self.inner.kind() {
+            ErrorKind::HttpResponse { status, .. } => {
+                matches!(status, StatusCode::Unauthorized | StatusCode::Forbidden)
+            }
+            ErrorKind::Credential => true,
+            _ => false,
+        }
+    }
+}
+
 impl From<AzureError> for AzureErrorWrapper {
     fn from(err: AzureError) -> Self {
         AzureErrorWrapper { inner: err }
@@ -604,6 +814,9 @@ impl From<AzureErrorWrapper> for StorageError {
         match err.inner.kind() {
             ErrorKind::HttpResponse { status, .. } => match status {
                 StatusCode::NotFound => StorageErrorKind::NotFound.with_error(err),
+                StatusCode::Unauthorized | StatusCode::Forbidden => {
+                    StorageErrorKind::Unauthorized.with_error(err)
+                }
                 _ => StorageErrorKind::Service.with_error(err),
             },
             ErrorKind::Io => StorageErrorKind::Io.with_error(err),
@@ -615,9 +828,17 @@ impl From<AzureErrorWrapper> for StorageError {
 
 #[cfg(test)]
 mod tests {
+    use std::num::NonZeroUsize;
+
+    use azure_core::error::ErrorKind;
+    use azure_core::StatusCode;
+    use azure_storage::Error as AzureError;
     use quickwit_common::uri::Uri;
+    use quickwit_config::AzureStorageConfig;
 
+    use super::{AzureBlobStorage, AzureErrorWrapper};
     use crate::object_storage::azure_blob_storage::parse_azure_uri;
+    use crate::{StorageError, StorageErrorKind, StorageResolverError};
 
     #[test]
     fn test_parse_azure_uri() {
@@ -638,4 +859,84 @@ mod tests {
         assert_eq!(container, "test-container");
         assert_eq!(prefix.to_str().unwrap(), "indexes");
     }
+
+    #[test]
+    fn test_azure_blob_storage_from_uri_with_sas_token() {
+        let azure_storage_config = AzureStorageConfig {
+            sas_token: Some("?sv=2022-11-02&sp=rwdl&se=2024-01-01T00:00:00Z&sig=abc".to_string()),
+            container_url: Some(
+                "https://test-account.blob.core.windows.net/test-container".to_string(),
+            ),
+            max_concurrent_requests: NonZeroUsize::new(4),
+            ..Default::default()
+        };
+        let azure_blob_storage = AzureBlobStorage::from_uri(
+            &azure_storage_config,
+            &Uri::for_test("azure://test-container/indexes"),
+        )
+        .unwrap();
+        assert!(azure_blob_storage.uses_sas_token);
+        assert_eq!(azure_blob_storage.prefix.to_str().unwrap(), "indexes");
+        assert_eq!(
+            azure_blob_storage.request_limiter.max_concurrent_requests(),
+            4
+        );
+
+        let error = AzureBlobStorage::from_uri(
+            &azure_storage_config,
+            &Uri::for_test("azure://other-container/indexes"),
+        )
+        .unwrap_err();
+        let StorageResolverError::InvalidConfig(message) = error else {
+            panic!("expected `InvalidConfig` error, got `{error:?}`");
+        };
+        assert!(message.contains("does not match container `other-container`"));
+    }
+
+    #[test]
+    fn test_azure_blob_storage_from_uri_requires_credentials() {
+        let azure_storage_config = AzureStorageConfig {
+            account_name: Some("test-account".to_string()),
+            ..Default::default()
+        };
+        let error = AzureBlobStorage::from_uri(
+            &azure_storage_config,
+            &Uri::for_test("azure://test-container/indexes"),
+        )
+        .unwrap_err();
+        assert!(matches!(error, StorageResolverError::InvalidConfig(_)));
+    }
+
+    #[test]
+    fn test_azure_error_to_storage_error() {
+        let azure_error = |status: StatusCode, error_code: &str| {
+            AzureErrorWrapper::from(AzureError::message(
+                ErrorKind::HttpResponse {
+                    status,
+                    error_code: Some(error_code.to_string()),
+                },
+                "request failed",
+            ))
+        };
+        let expired_sas_token_error = azure_error(StatusCode::Forbidden, "AuthenticationFailed");
+        assert!(expired_sas_token_error.is_authentication_error());
+        assert_eq!(
+            StorageError::from(expired_sas_token_error).kind(),
+            StorageErrorKind::Unauthorized
+        );
+
+        let not_found_error = azure_error(StatusCode::NotFound, "BlobNotFound");
+        assert!(!not_found_error.is_authentication_error());
+        assert_eq!(
+            StorageError::from(not_found_error).kind(),
+            StorageErrorKind::NotFound
+        );
+
+        let server_error = azure_error(StatusCode::InternalServerError, "InternalError");
+        assert!(!server_error.is_authentication_error());
+        assert_eq!(
+            StorageError::from(server_error).kind(),
+            StorageErrorKind::Service
+        );
+    }
 }
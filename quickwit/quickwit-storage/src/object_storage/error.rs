@@ -20,13 +20,16 @@
 use aws_sdk_s3::error::{DisplayErrorContext, SdkError};
 use aws_sdk_s3::operation::abort_multipart_upload::AbortMultipartUploadError;
 use aws_sdk_s3::operation::complete_multipart_upload::CompleteMultipartUploadError;
+use aws_sdk_s3::operation::copy_object::CopyObjectError;
 use aws_sdk_s3::operation::create_multipart_upload::CreateMultipartUploadError;
 use aws_sdk_s3::operation::delete_object::DeleteObjectError;
 use aws_sdk_s3::operation::delete_objects::DeleteObjectsError;
 use aws_sdk_s3::operation::get_object::GetObjectError;
 use aws_sdk_s3::operation::head_object::HeadObjectError;
+use aws_sdk_s3::operation::list_objects_v2::ListObjectsV2Error;
 use aws_sdk_s3::operation::put_object::PutObjectError;
 use aws_sdk_s3::operation::upload_part::UploadPartError;
+use aws_sdk_s3::operation::upload_part_copy::UploadPartCopyError;
 use hyper::http::StatusCode;
 
 use crate::{StorageError, StorageErrorKind};
@@ -50,11 +53,25 @@ where E: std::error::Error + ToStorageErrorKind + Send + Sync + 'static
                 let response = response_error.raw().http();
                 match response.status() {
                     StatusCode::NOT_FOUND => StorageErrorKind::NotFound,
-                    StatusCode::UNAUTHORIZED => StorageErrorKind::Unauthorized,
+                    StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+                        StorageErrorKind::Unauthorized
+                    }
                     _ => StorageErrorKind::Internal,
                 }
             }
-            SdkError::ServiceError(service_error) => service_error.err().to_storage_error_kind(),
+            SdkError::ServiceError(service_error) => {
+                // Requests are rejected with `403 Forbidden` when the credentials, if any, do not
+                // grant access to the resource, e.g. private buckets in anonymous mode. This
+                // applies to every request, signed or not: S3 does not use this status for
+                // anything else than access denials, so they are reported as `Unauthorized`
+                // rather than as opaque service errors, consistently with the response errors
+                // above.
+                if service_error.raw().http().status() == StatusCode::FORBIDDEN {
+                    StorageErrorKind::Unauthorized
+                } else {
+                    service_error.err().to_storage_error_kind()
+                }
+            }
             SdkError::TimeoutError(_) => StorageErrorKind::Timeout,
             _ => StorageErrorKind::Internal,
         };
@@ -96,6 +113,18 @@ impl ToStorageErrorKind for UploadPartError {
     }
 }
 
+impl ToStorageErrorKind for UploadPartCopyError {
+    fn to_storage_error_kind(&self) -> StorageErrorKind {
+        StorageErrorKind::Service
+    }
+}
+
+impl ToStorageErrorKind for CopyObjectError {
+    fn to_storage_error_kind(&self) -> StorageErrorKind {
+        StorageErrorKind::Service
+    }
+}
+
 impl ToStorageErrorKind for CompleteMultipartUploadError {
     fn to_storage_error_kind(&self) -> StorageErrorKind {
         StorageErrorKind::Service
@@ -133,3 +162,12 @@ impl ToStorageErrorKind for HeadObjectError {
         }
     }
 }
+
+impl ToStorageErrorKind for ListObjectsV2Error {
+    fn to_storage_error_kind(&self) -> StorageErrorKind {
+        match self {
+            ListObjectsV2Error::NoSuchBucket(_) => StorageErrorKind::NotFound,
+            _ => StorageErrorKind::Service,
+        }
+    }
+}
@@ -0,0 +1,1767 @@
+// Copyright (C) 2024 Quickwit, Inc.
+//
+// Quickwit is offered under the AGPL v3.0 and as commercial software.
+// For commercial licensing, contact us at hello@quickwit.io.
+//
+// AGPL:
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as
+// published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::HashMap;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use std::{fmt, io};
+
+use async_trait::async_trait;
+use futures::TryStreamExt;
+use hyper::header::{CONTENT_LENGTH, CONTENT_RANGE, CONTENT_TYPE, LOCATION, RANGE};
+use hyper::StatusCode;
+use once_cell::sync::OnceCell;
+use quickwit_common::retry::{retry, Retry, RetryParams};
+use quickwit_common::uri::Uri;
+use quickwit_config::{GoogleCloudStorageConfig, StorageBackend};
+use regex::Regex;
+use reqsign::{GoogleCredentialLoader, GoogleTokenLoader};
+use reqwest::redirect::Policy;
+use reqwest::{Client, RequestBuilder, Response, Url};
+use serde::Deserialize;
+use tokio::io::{AsyncRead, AsyncWriteExt};
+use tokio::sync::Mutex;
+use tokio_util::io::StreamReader;
+use tracing::{info, warn};
+
+use crate::debouncer::DebouncedStorage;
+use crate::http_storage::reqwest_error_to_storage_error;
+use crate::object_storage::{resolve_conditional_put, ConditionalPutOutcome};
+use crate::storage::SendableAsync;
+use crate::{
+    BulkDeleteError, DeleteFailure, MultiPartPolicy, OwnedBytes, PermitAsyncRead, PutPayload,
+    Storage, StorageError, StorageErrorKind, StorageFactory, StorageRequestLimiter,
+    StorageResolverError, StorageResult, STORAGE_METRICS,
+};
+
+const GOOGLE_CLOUD_STORAGE_ENDPOINT: &str = "https://storage.googleapis.com";
+
+const GOOGLE_CLOUD_STORAGE_SCOPE: &str = "https://www.googleapis.com/auth/devstorage.read_write";
+
+/// The chunks of a resumable upload, except the last one, must be a multiple of 256KiB.
+const RESUMABLE_UPLOAD_CHUNK_ALIGNMENT: u64 = 256 * 1024;
+
+/// Maximum number of requests of a batch request.
+const MAX_NUM_REQUESTS_PER_BATCH: usize = 100;
+
+/// Access tokens are renewed a bit before they expire, so that requests issued right before
+/// the expiration do not fail.
+const ACCESS_TOKEN_REFRESH_MARGIN: Duration = Duration::from_secs(60);
+
+/// Maximum duration for establishing a connection with Google Cloud Storage.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Maximum duration of a request, including the transfer of its payload, which can be as large as
+/// an upload chunk.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Google Cloud Storage resolver.
+pub struct GoogleCloudStorageFactory {
+    client: Client,
+    storage_config: GoogleCloudStorageConfig,
+    // Shared by all the storages resolved by the factory.
+    request_limiter: StorageRequestLimiter,
+}
+
+impl GoogleCloudStorageFactory {
+    /// Creates a new Google Cloud Storage factory.
+    pub fn new(storage_config: GoogleCloudStorageConfig) -> Self {
+        let request_limiter =
+            StorageRequestLimiter::from_config(storage_config.max_concurrent_requests);
+        Self {
+            client: new_client(),
+            storage_config,
+            request_limiter,
+        }
+    }
+}
+
+#[async_trait]
+impl StorageFactory for GoogleCloudStorageFactory {
+    fn backend(&self) -> StorageBackend {
+        StorageBackend::Google
+    }
+
+    async fn resolve(&self, uri: &Uri) -> Result<Arc<dyn Storage>, StorageResolverError> {
+        let storage = GoogleCloudStorage::from_uri(self.client.clone(), &self.storage_config, uri)?
+            .with_request_limiter(self.request_limiter.clone());
+        Ok(Arc::new(DebouncedStorage::new(storage)))
+    }
+}
+
+/// Builds the HTTP client used to issue requests to the JSON API. It must not follow redirects:
+/// the chunks of a resumable upload are acknowledged with `308 Resume Incomplete` responses.
+fn new_client() -> Client {
+    Client::builder()
+        .redirect(Policy::none())
+        .connect_timeout(CONNECT_TIMEOUT)
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .expect("HTTP client should be built")
+}
+
+/// Google Cloud Storage implementation, relying on the JSON API.
+///
+/// Payloads larger than the multipart threshold are uploaded with the resumable upload protocol,
+/// one chunk at a time. A failed chunk is resumed from the last byte persisted by Google Cloud
+/// Storage, and the upload session is canceled when the upload fails or is dropped midway.
+pub struct GoogleCloudStorage {
+    client: Client,
+    token_provider: Arc<AccessTokenProvider>,
+    endpoint: Url,
+    bucket: String,
+    uri: Uri,
+    prefix: PathBuf,
+    multipart_policy: MultiPartPolicy,
+    retry_params: RetryParams,
+    batch_delete_enabled: bool,
+    request_limiter: StorageRequestLimiter,
+}
+
+impl fmt::Debug for GoogleCloudStorage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("GoogleCloudStorage")
+            .field("uri", &self.uri)
+            .field("bucket", &self.bucket)
+            .field("prefix", &self.prefix)
+            .field("request_limiter", &self.request_limiter)
+            .finish()
+    }
+}
+
+#[derive(Deserialize)]
+struct ObjectMetadata {
+    name: String,
+    /// The JSON API encodes 64-bit integers as strings.
+    size: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ListObjectsResponse {
+    #[serde(default)]
+    items: Vec<ObjectMetadata>,
+    next_page_token: Option<String>,
+}
+
+impl GoogleCloudStorage {
+    fn new(
+        client: Client,
+        token_loader: GoogleTokenLoader,
+        endpoint: Url,
+        uri: Uri,
+        bucket: String,
+        prefix: PathBuf,
+    ) -> Self {
+        Self {
+            client,
+            token_provider: Arc::new(AccessTokenProvider::new(token_loader)),
+            endpoint,
+            bucket,
+            uri,
+            prefix,
+            multipart_policy: default_multipart_policy(),
+            retry_params: RetryParams::default(),
+            batch_delete_enabled: true,
+            request_limiter: StorageRequestLimiter::from_config(None),
+        }
+    }
+
+    /// Builds instance from URI.
+    pub fn from_uri(
+        client: Client,
+        google_cloud_storage_config: &GoogleCloudStorageConfig,
+        uri: &Uri,
+    ) -> Result<GoogleCloudStorage, StorageResolverError> {
+        let (bucket, prefix) = parse_google_uri(uri).ok_or_else(|| {
+            let message = format!("failed to extract bucket name from Google URI: {uri}");
+            StorageResolverError::InvalidUri(message)
+        })?;
+        let mut token_loader = GoogleTokenLoader::new(GOOGLE_CLOUD_STORAGE_SCOPE, client.clone());
+
+        // Without a credential path, the credentials are loaded from the environment
+        // (`GOOGLE_APPLICATION_CREDENTIALS`) or, when none are found, the tokens are fetched from
+        // the metadata server, which is how workload identity is supported.
+        let load_credentials_error = |error: anyhow::Error| {
+            let message = format!("failed to load Google Cloud Storage credentials: {error}");
+            StorageResolverError::InvalidConfig(message)
+        };
+        let credential_opt = match google_cloud_storage_config.resolve_credential_path() {
+            Some(credential_path) => {
+                info!(path=%credential_path, "loading Google Cloud Storage credentials from path");
+                let credential = GoogleCredentialLoader::default()
+                    .with_path(&credential_path)
+                    .load()
+                    .map_err(load_credentials_error)?
+                    .ok_or_else(|| {
+                        load_credentials_error(anyhow::anyhow!(
+                            "no credentials found in `{credential_path}`"
+                        ))
+                    })?;
+                Some(credential)
+            }
+            None => GoogleCredentialLoader::default()
+                .load()
+                .map_err(load_credentials_error)?,
+        };
+        if let Some(credential) = credential_opt {
+            token_loader = token_loader.with_credentials(credential);
+        }
+        if let Some(service_account) = &google_cloud_storage_config.service_account {
+            info!(service_account=%service_account, "fetching Google Cloud Storage tokens for service account");
+            token_loader = token_loader.with_service_account(service_account);
+        }
+        let endpoint =
+            Url::parse(GOOGLE_CLOUD_STORAGE_ENDPOINT).expect("endpoint URL should be valid");
+        let request_limiter =
+            StorageRequestLimiter::from_config(google_cloud_storage_config.max_concurrent_requests);
+        let storage = Self::new(client, token_loader, endpoint, uri.clone(), bucket, prefix)
+            .with_request_limiter(request_limiter);
+        Ok(storage)
+    }
+
+    /// Creates an emulated storage for testing. The default port of the fake GCS server is 4443.
+    #[cfg(feature = "integration-testsuite")]
+    pub fn new_emulated(uri: &Uri) -> Result<Self, StorageResolverError> {
+        let (bucket, prefix) = parse_google_uri(uri).ok_or_else(|| {
+            let message = format!("failed to extract bucket name from Google URI: {uri}");
+            StorageResolverError::InvalidUri(message)
+        })?;
+        let client = new_client();
+        let endpoint = Url::parse("http://127.0.0.1:4443").expect("endpoint URL should be valid");
+        let mut storage = Self::new(
+            client.clone(),
+            static_token_loader(client, "dummy"),
+            endpoint,
+            uri.clone(),
+            bucket,
+            prefix,
+        );
+        // The fake GCS server does not support batch requests.
+        storage.batch_delete_enabled = false;
+        Ok(storage)
+    }
+
+    /// Sets the limiter bounding the number of concurrent requests issued by the storage.
+    pub fn with_request_limiter(mut self, request_limiter: StorageRequestLimiter) -> Self {
+        self.request_limiter = request_limiter;
+        self
+    }
+
+    /// Sets the multipart policy.
+    ///
+    /// See `MultiPartPolicy`.
+    pub fn set_policy(&mut self, multipart_policy: MultiPartPolicy) {
+        self.multipart_policy = multipart_policy;
+    }
+
+    /// Returns the object name (a.k.a object key).
+    fn object_name(&self, relative_path: &Path) -> String {
+        let key_path = self.prefix.join(relative_path);
+        key_path.to_string_lossy().to_string()
+    }
+
+    fn relative_path(&self, object_name: &str) -> PathBuf {
+        Path::new(object_name)
+            .strip_prefix(&self.prefix)
+            .unwrap_or(Path::new(object_name))
+            .to_path_buf()
+    }
+
+    fn url(&self, path_segments: &[&str]) -> Url {
+        let mut url = self.endpoint.clone();
+        url.path_segments_mut()
+            .expect("HTTP URLs should have path segments")
+            .pop_if_empty()
+            .extend(path_segments);
+        url
+    }
+
+    /// Returns the URL of the object in the JSON API. The object name is percent-encoded as a
+    /// single path segment, including its slashes.
+    fn object_url(&self, name: &str) -> Url {
+        self.url(&["storage", "v1", "b", &self.bucket, "o", name])
+    }
+
+    /// Returns the URL of the collection of objects of the bucket in the JSON API.
+    fn objects_url(&self) -> Url {
+        self.url(&["storage", "v1", "b", &self.bucket, "o"])
+    }
+
+    /// Returns the upload URL of the object. When `if_absent` is set, the upload is rejected with
+    /// `412 Precondition Failed` if the object already exists.
+    fn upload_url(&self, upload_type: &str, name: &str, if_absent: bool) -> Url {
+        let mut url = self.url(&["upload", "storage", "v1", "b", &self.bucket, "o"]);
+        url.query_pairs_mut()
+            .append_pair("uploadType", upload_type)
+            .append_pair("name", name);
+        if if_absent {
+            url.query_pairs_mut().append_pair("ifGenerationMatch", "0");
+        }
+        url
+    }
+
+    /// Authenticates the request, sends it, and converts the HTTP error statuses into storage
+    /// errors. Network errors, server errors, and throttled requests are transient. `resource`
+    /// designates the object or the prefix targeted by the request in error messages.
+    async fn send(
+        &self,
+        request: RequestBuilder,
+        resource: &str,
+    ) -> Result<Response, Retry<StorageError>> {
+        let response = self.send_unchecked(request).await?;
+        self.check_status(response, resource).await
+    }
+
+    /// Authenticates the request and sends it, without checking the status of the response.
+    async fn send_unchecked(
+        &self,
+        request: RequestBuilder,
+    ) -> Result<Response, Retry<StorageError>> {
+        let access_token = self.token_provider.access_token().await?;
+        request
+            .bearer_auth(access_token)
+            .send()
+            .await
+            .map_err(|error| Retry::Transient(reqwest_error_to_storage_error(error)))
+    }
+
+    async fn check_status(
+        &self,
+        response: Response,
+        resource: &str,
+    ) -> Result<Response, Retry<StorageError>> {
+        let status = response.status();
+
+        if status.is_success() || status == StatusCode::PERMANENT_REDIRECT {
+            return Ok(response);
+        }
+        let message = response.text().await.unwrap_or_default();
+        let storage_error = status_to_error_kind(status).with_error(anyhow::anyhow!(
+            "Google Cloud Storage request on `{resource}` of `{}` failed with status `{status}`: \
+             {message}",
+            self.uri
+        ));
+        if is_transient_status(status) {
+            Err(Retry::Transient(storage_error))
+        } else {
+            Err(Retry::Permanent(storage_error))
+        }
+    }
+
+    /// Sends a media request for the object, optionally restricted to a range of bytes.
+    async fn get_media(
+        &self,
+        name: &str,
+        range_opt: Option<&Range<usize>>,
+    ) -> Result<Response, Retry<StorageError>> {
+        let mut url = self.object_url(name);
+        url.query_pairs_mut().append_pair("alt", "media");
+        let mut request = self.client.get(url);
+
+        if let Some(range) = range_opt {
+            request = request.header(RANGE, format!("bytes={}-{}", range.start, range.end - 1));
+        }
+        let response = self.send(request, name).await?;
+
+        if range_opt.is_some() && response.status() != StatusCode::PARTIAL_CONTENT {
+            return Err(Retry::Permanent(StorageErrorKind::Service.with_error(
+                anyhow::anyhow!(
+                    "failed to fetch slice {range_opt:?} of object `{name}`: Google Cloud Storage \
+                     ignored the range and responded with status `{}`",
+                    response.status()
+                ),
+            )));
+        }
+        Ok(response)
+    }
+
+    async fn get_to_vec(
+        &self,
+        path: &Path,
+        range_opt: Option<Range<usize>>,
+    ) -> StorageResult<OwnedBytes> {
+        let _permit = self.request_limiter.acquire().await;
+        let name = self.object_name(path);
+        retry(&self.retry_params, || async {
+            let response = self.get_media(&name, range_opt.as_ref()).await?;
+            let bytes = response
+                .bytes()
+                .await
+                .map_err(|error| Retry::Transient(reqwest_error_to_storage_error(error)))?;
+            STORAGE_METRICS
+                .object_storage_download_num_bytes
+                .inc_by(bytes.len() as u64);
+            Ok(OwnedBytes::new(bytes.to_vec()))
+        })
+        .await
+        .map_err(|error: Retry<StorageError>| {
+            error
+                .into_inner()
+                .add_context(format!("failed to fetch object `{name}` of `{}`", self.uri))
+        })
+    }
+
+    async fn put_single_part(
+        &self,
+        name: &str,
+        payload: &dyn PutPayload,
+        if_absent: bool,
+    ) -> StorageResult<ConditionalPutOutcome> {
+        STORAGE_METRICS.object_storage_put_parts.inc();
+        STORAGE_METRICS
+            .object_storage_upload_num_bytes
+            .inc_by(payload.len());
+        let num_attempts = AtomicUsize::new(0);
+        let is_written = retry(&self.retry_params, || async {
+            num_attempts.fetch_add(1, Ordering::Relaxed);
+            let body = read_payload_chunk(payload, 0..payload.len()).await?;
+            let request = self
+                .client
+                .post(self.upload_url("media", name, if_absent))
+                .header(CONTENT_TYPE, "application/octet-stream")
+                .body(body);
+            let response = self.send_unchecked(request).await?;
+
+            if response.status() == StatusCode::PRECONDITION_FAILED {
+                return Ok(false);
+            }
+            self.check_status(response, name).await?;
+            Ok(true)
+        })
+        .await
+        .map_err(Retry::into_inner)?;
+        Ok(ConditionalPutOutcome::new(
+            is_written,
+            num_attempts.load(Ordering::Relaxed),
+        ))
+    }
+
+    /// Uploads the payload with the resumable upload protocol: an upload session is initiated,
+    /// and the payload is sent one chunk at a time to the session URL.
+    ///
+    /// The `ifGenerationMatch` precondition is only evaluated when the upload is finalized, and
+    /// retrying a chunk starts by querying the session, which reports a finalized upload as
+    /// complete: a rejection is never caused by an earlier attempt.
+    async fn put_resumable(
+        &self,
+        name: &str,
+        payload: &dyn PutPayload,
+        part_num_bytes: u64,
+        total_len: u64,
+        if_absent: bool,
+    ) -> StorageResult<ConditionalPutOutcome> {
+        let chunk_len = (part_num_bytes / RESUMABLE_UPLOAD_CHUNK_ALIGNMENT)
+            .max(1)
+            .saturating_mul(RESUMABLE_UPLOAD_CHUNK_ALIGNMENT);
+
+        let session_url = retry(&self.retry_params, || async {
+            let request = self
+                .client
+                .post(self.upload_url("resumable", name, if_absent))
+                .header("X-Upload-Content-Length", total_len)
+                .header(CONTENT_LENGTH, 0);
+            let response = self.send(request, name).await?;
+            response
+                .headers()
+                .get(LOCATION)
+                .and_then(|header_value| header_value.to_str().ok())
+                .map(ToString::to_string)
+                .ok_or_else(|| {
+                    Retry::Permanent(StorageErrorKind::Service.with_error(anyhow::anyhow!(
+                        "failed to initiate upload of object `{name}`: response has no valid \
+                         `Location` header"
+                    )))
+                })
+        })
+        .await
+        .map_err(Retry::into_inner)?;
+
+        let mut upload_session = UploadSession::new(self.client.clone(), session_url);
+        let mut persisted_len = 0;
+
+        while persisted_len < total_len {
+            let chunk_start_hint = persisted_len;
+            let is_first_attempt = AtomicBool::new(true);
+
+            let persisted_len_opt = retry(&self.retry_params, || async {
+                let chunk_start_opt = if is_first_attempt.swap(false, Ordering::Relaxed) {
+                    Some(chunk_start_hint)
+                } else {
+                    // The previous attempt may have been persisted, in full or in part.
+                    self.query_persisted_len(&upload_session, name, total_len)
+                        .await?
+                };
+                let Some(chunk_start) = chunk_start_opt else {
+                    return Ok(None);
+                };
+                if chunk_start >= total_len {
+                    return Ok(Some(total_len));
+                }
+                let chunk = chunk_start..(chunk_start + chunk_len).min(total_len);
+                self.upload_chunk(&upload_session, name, payload, chunk, total_len)
+                    .await
+            })
+            .await
+            .map_err(Retry::into_inner)?;
+
+            let Some(new_persisted_len) = persisted_len_opt else {
+                return Ok(ConditionalPutOutcome::AlreadyExists);
+            };
+            persisted_len = new_persisted_len;
+        }
+        upload_session.complete();
+        Ok(ConditionalPutOutcome::Written)
+    }
+
+    /// Uploads a chunk of the payload and returns the number of bytes persisted so far, or `None`
+    /// if the upload was finalized and rejected because the object already exists.
+    async fn upload_chunk(
+        &self,
+        upload_session: &UploadSession,
+        name: &str,
+        payload: &dyn PutPayload,
+        chunk: Range<u64>,
+        total_len: u64,
+    ) -> Result<Option<u64>, Retry<StorageError>> {
+        STORAGE_METRICS.object_storage_put_parts.inc();
+        STORAGE_METRICS
+            .object_storage_upload_num_bytes
+            .inc_by(chunk.end - chunk.start);
+
+        let content_range = format!("bytes {}-{}/{total_len}", chunk.start, chunk.end - 1);
+        let body = read_payload_chunk(payload, chunk).await?;
+        let request = self
+            .client
+            .put(&upload_session.session_url)
+            .header(CONTENT_RANGE, content_range)
+            .body(body);
+        let response = request
+            .send()
+            .await
+            .map_err(|error| Retry::Transient(reqwest_error_to_storage_error(error)))?;
+
+        if response.status() == StatusCode::PRECONDITION_FAILED {
+            return Ok(None);
+        }
+        let response = self.check_status(response, name).await?;
+        Ok(Some(parse_persisted_len(&response, total_len)))
+    }
+
+    /// Asks the upload session how many bytes it has persisted so far, see
+    /// [`Self::upload_chunk`].
+    async fn query_persisted_len(
+        &self,
+        upload_session: &UploadSession,
+        name: &str,
+        total_len: u64,
+    ) -> Result<Option<u64>, Retry<StorageError>> {
+        let request = self
+            .client
+            .put(&upload_session.session_url)
+            .header(CONTENT_RANGE, format!("bytes */{total_len}"))
+            .header(CONTENT_LENGTH, 0);
+        let response = request
+            .send()
+            .await
+            .map_err(|error| Retry::Transient(reqwest_error_to_storage_error(error)))?;
+
+        if response.status() == StatusCode::PRECONDITION_FAILED {
+            return Ok(None);
+        }
+        let response = self.check_status(response, name).await?;
+        Ok(Some(parse_persisted_len(&response, total_len)))
+    }
+
+    async fn put_inner(
+        &self,
+        path: &Path,
+        payload: &dyn PutPayload,
+        if_absent: bool,
+    ) -> StorageResult<ConditionalPutOutcome> {
+        STORAGE_METRICS.object_storage_put_total.inc();
+        let name = self.object_name(path);
+        let total_len = payload.len();
+        let part_num_bytes = self.multipart_policy.part_num_bytes(total_len);
+        // The chunks of a resumable upload are sent one at a time, so a single permit covers the
+        // whole upload.
+        let _permit = self.request_limiter.acquire().await;
+
+        if part_num_bytes >= total_len {
+            self.put_single_part(&name, payload, if_absent).await
+        } else {
+            self.put_resumable(&name, payload, part_num_bytes, total_len, if_absent)
+                .await
+        }
+    }
+
+    async fn delete_single_object(&self, path: &Path) -> StorageResult<()> {
+        let _permit = self.request_limiter.acquire().await;
+        let name = self.object_name(path);
+        retry(&self.retry_params, || async {
+            let request = self.client.delete(self.object_url(&name));
+            match self.send(request, &name).await {
+                Ok(_) => Ok(()),
+                Err(Retry::Permanent(error)) if error.kind() == StorageErrorKind::NotFound => {
+                    Ok(())
+                }
+                Err(error) => Err(error),
+            }
+        })
+        .await
+        .map_err(Retry::into_inner)
+    }
+
+    /// Deletes the objects with a single batch request and returns the outcome of the deletion
+    /// of each object, in the order of `paths`.
+    async fn delete_batch(&self, paths: &[&Path]) -> StorageResult<Vec<Option<DeleteFailure>>> {
+        let boundary = format!("batch_{}", ulid::Ulid::new());
+        let mut body = String::new();
+
+        for (request_id, path) in paths.iter().enumerate() {
+            let object_url = self.object_url(&self.object_name(path));
+            body.push_str(&format!(
+                "--{boundary}\r\nContent-Type: application/http\r\nContent-ID: \
+                 <{request_id}>\r\n\r\nDELETE {} HTTP/1.1\r\n\r\n",
+                object_url.path()
+            ));
+        }
+        body.push_str(&format!("--{boundary}--\r\n"));
+
+        let _permit = self.request_limiter.acquire().await;
+        let (content_type, response_body) = retry(&self.retry_params, || async {
+            let request = self
+                .client
+                .post(self.url(&["batch", "storage", "v1"]))
+                .header(
+                    CONTENT_TYPE,
+                    format!("multipart/mixed; boundary={boundary}"),
+                )
+                .body(body.clone());
+            let response = self.send(request, "batch").await?;
+            let content_type = response
+                .headers()
+                .get(CONTENT_TYPE)
+                .and_then(|header_value| header_value.to_str().ok())
+                .unwrap_or_default()
+                .to_string();
+            let response_body = response
+                .text()
+                .await
+                .map_err(|error| Retry::Transient(reqwest_error_to_storage_error(error)))?;
+            Ok((content_type, response_body))
+        })
+        .await
+        .map_err(Retry::into_inner)?;
+
+        let mut responses =
+            parse_batch_response(&content_type, &response_body).ok_or_else(|| {
+                StorageErrorKind::Service.with_error(anyhow::anyhow!(
+                    "failed to parse batch response of Google Cloud Storage with content type \
+                     `{content_type}`"
+                ))
+            })?;
+        let delete_failures = (0..paths.len())
+            .map(|request_id| {
+                let Some((status, message)) = responses.remove(&request_id) else {
+                    return Some(DeleteFailure {
+                        error: Some(StorageErrorKind::Service.with_error(anyhow::anyhow!(
+                            "batch response of Google Cloud Storage has no response for request \
+                             `{request_id}`"
+                        ))),
+                        ..Default::default()
+                    });
+                };
+                // Deleting a missing object is not an error.
+                if status.is_success() || status == StatusCode::NOT_FOUND {
+                    return None;
+                }
+                let error = status_to_error_kind(status).with_error(anyhow::anyhow!(
+                    "failed to delete object with status `{status}`: {message}"
+                ));
+                Some(DeleteFailure {
+                    error: Some(error),
+                    code: Some(status.as_str().to_string()),
+                    message: Some(message),
+                })
+            })
+            .collect();
+        Ok(delete_failures)
+    }
+
+    async fn bulk_delete_sequentially(&self, paths: &[&Path]) -> Result<(), BulkDeleteError> {
+        let mut successes = Vec::with_capacity(paths.len());
+        let mut failures = HashMap::new();
+
+        for &path in paths {
+            match self.delete_single_object(path).await {
+                Ok(()) => successes.push(path.to_path_buf()),
+                Err(error) => {
+                    let failure = DeleteFailure {
+                        error: Some(error),
+                        ..Default::default()
+                    };
+                    failures.insert(path.to_path_buf(), failure);
+                }
+            }
+        }
+        if failures.is_empty() {
+            return Ok(());
+        }
+        Err(BulkDeleteError {
+            successes,
+            failures,
+            ..Default::default()
+        })
+    }
+}
+
+#[async_trait]
+impl Storage for GoogleCloudStorage {
+    async fn check_connectivity(&self) -> anyhow::Result<()> {
+        let _permit = self.request_limiter.acquire().await;
+        let mut url = self.objects_url();
+        url.query_pairs_mut().append_pair("maxResults", "1");
+        self.send(self.client.get(url), &self.object_name(Path::new("")))
+            .await
+            .map_err(Retry::into_inner)?;
+        Ok(())
+    }
+
+    async fn put(&self, path: &Path, payload: Box<dyn PutPayload>) -> StorageResult<()> {
+        self.put_inner(path, &*payload, false).await?;
+        Ok(())
+    }
+
+    async fn put_if_absent(
+        &self,
+        path: &Path,
+        payload: Box<dyn PutPayload>,
+    ) -> StorageResult<bool> {
+        let outcome = self.put_inner(path, &*payload, true).await?;
+        resolve_conditional_put(self, path, payload.len(), outcome).await
+    }
+
+    async fn copy_to(&self, path: &Path, output: &mut dyn SendableAsync) -> StorageResult<()> {
+        let _permit = self.request_limiter.acquire().await;
+        let name = self.object_name(path);
+        let mut response = retry(&self.retry_params, || self.get_media(&name, None))
+            .await
+            .map_err(Retry::into_inner)?;
+
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(reqwest_error_to_storage_error)?
+        {
+            output.write_all(&chunk).await?;
+            STORAGE_METRICS
+                .object_storage_download_num_bytes
+                .inc_by(chunk.len() as u64);
+        }
+        output.flush().await?;
+        Ok(())
+    }
+
+    async fn get_slice(&self, path: &Path, range: Range<usize>) -> StorageResult<OwnedBytes> {
+        if range.is_empty() {
+            return Ok(OwnedBytes::empty());
+        }
+        let bytes = self.get_to_vec(path, Some(range.clone())).await?;
+
+        if bytes.len() != range.len() {
+            return Err(StorageErrorKind::Internal.with_error(anyhow::anyhow!(
+                "failed to fetch slice {range:?} of object `{}`: received {} bytes",
+                path.display(),
+                bytes.len()
+            )));
+        }
+        Ok(bytes)
+    }
+
+    async fn get_slice_stream(
+        &self,
+        path: &Path,
+        range: Range<usize>,
+    ) -> StorageResult<Box<dyn AsyncRead + Send + Unpin>> {
+        if range.is_empty() {
+            return Ok(Box::new(tokio::io::empty()));
+        }
+        // The permit is released once the returned reader is dropped.
+        let permit = self.request_limiter.acquire().await;
+        let name = self.object_name(path);
+        let response = retry(&self.retry_params, || self.get_media(&name, Some(&range)))
+            .await
+            .map_err(Retry::into_inner)?;
+        let bytes_stream = response
+            .bytes_stream()
+            .map_err(|error| io::Error::new(io::ErrorKind::Other, error));
+        let reader = StreamReader::new(Box::pin(bytes_stream));
+        Ok(Box::new(PermitAsyncRead::new(reader, permit)))
+    }
+
+    async fn get_all(&self, path: &Path) -> StorageResult<OwnedBytes> {
+        self.get_to_vec(path, None).await
+    }
+
+    async fn delete(&self, path: &Path) -> StorageResult<()> {
+        self.delete_single_object(path).await
+    }
+
+    async fn bulk_delete<'a>(&self, paths: &[&'a Path]) -> Result<(), BulkDeleteError> {
+        if !self.batch_delete_enabled {
+            return self.bulk_delete_sequentially(paths).await;
+        }
+        let mut successes = Vec::with_capacity(paths.len());
+        let mut failures = HashMap::new();
+
+        for (batch_ord, batch) in paths.chunks(MAX_NUM_REQUESTS_PER_BATCH).enumerate() {
+            let delete_failures = match self.delete_batch(batch).await {
+                Ok(delete_failures) => delete_failures,
+                Err(error) => {
+                    let unattempted = paths[batch_ord * MAX_NUM_REQUESTS_PER_BATCH..]
+                        .iter()
+                        .map(|path| path.to_path_buf())
+                        .collect();
+                    return Err(BulkDeleteError {
+                        error: Some(error),
+                        successes,
+                        failures,
+                        unattempted,
+                    });
+                }
+            };
+            for (path, delete_failure_opt) in batch.iter().zip(delete_failures) {
+                if let Some(delete_failure) = delete_failure_opt {
+                    failures.insert(path.to_path_buf(), delete_failure);
+                } else {
+                    successes.push(path.to_path_buf());
+                }
+            }
+        }
+        if failures.is_empty() {
+            return Ok(());
+        }
+        Err(BulkDeleteError {
+            successes,
+            failures,
+            ..Default::default()
+        })
+    }
+
+    async fn list(&self, prefix: &Path) -> StorageResult<Vec<PathBuf>> {
+        let name_prefix = self.object_name(prefix);
+        let mut paths = Vec::new();
+        let mut page_token_opt: Option<String> = None;
+
+        loop {
+            let _permit = self.request_limiter.acquire().await;
+            let list_objects_response: ListObjectsResponse = retry(&self.retry_params, || async {
+                let mut url = self.objects_url();
+                url.query_pairs_mut().append_pair("prefix", &name_prefix);
+
+                if let Some(page_token) = &page_token_opt {
+                    url.query_pairs_mut().append_pair("pageToken", page_token);
+                }
+                let response = self.send(self.client.get(url), &name_prefix).await?;
+                response.json().await.map_err(|error| {
+                    Retry::Permanent(StorageErrorKind::Service.with_error(anyhow::anyhow!(
+                        "failed to list objects with prefix `{name_prefix}`: invalid response: \
+                         {error}"
+                    )))
+                })
+            })
+            .await
+            .map_err(Retry::into_inner)?;
+
+            for object in list_objects_response.items {
+                paths.push(self.relative_path(&object.name));
+            }
+            page_token_opt = list_objects_response.next_page_token;
+
+            if page_token_opt.is_none() {
+                break;
+            }
+        }
+        Ok(paths)
+    }
+
+    async fn file_num_bytes(&self, path: &Path) -> StorageResult<u64> {
+        let _permit = self.request_limiter.acquire().await;
+        let name = self.object_name(path);
+        let response = retry(&self.retry_params, || async {
+            self.send(self.client.get(self.object_url(&name)), &name)
+                .await
+        })
+        .await
+        .map_err(Retry::into_inner)?;
+        let object_metadata: ObjectMetadata = response.json().await.map_err(|error| {
+            StorageErrorKind::Service.with_error(anyhow::anyhow!(
+                "failed to get size of object `{name}`: invalid metadata: {error}"
+            ))
+        })?;
+        object_metadata.size.parse().map_err(|error| {
+            StorageErrorKind::Service.with_error(anyhow::anyhow!(
+                "failed to get size of object `{name}`: invalid size `{}`: {error}",
+                object_metadata.size
+            ))
+        })
+    }
+
+    fn uri(&self) -> &Uri {
+        &self.uri
+    }
+}
+
+/// Fetches and caches the OAuth 2.0 access tokens authenticating the requests.
+struct AccessTokenProvider {
+    token_loader: GoogleTokenLoader,
+    cached_token_opt: Mutex<Option<(String, Instant)>>,
+}
+
+impl AccessTokenProvider {
+    fn new(token_loader: GoogleTokenLoader) -> Self {
+        Self {
+            token_loader,
+            cached_token_opt: Mutex::new(None),
+        }
+    }
+
+    async fn access_token(&self) -> Result<String, Retry<StorageError>> {
+        let mut cached_token_guard = self.cached_token_opt.lock().await;
+
+        if let Some((access_token, expires_at)) = cached_token_guard.as_ref() {
+            if Instant::now() + ACCESS_TOKEN_REFRESH_MARGIN < *expires_at {
+                return Ok(access_token.clone());
+            }
+        }
+        // Fetching a token from the metadata server or the OAuth 2.0 endpoint can fail
+        // transiently.
+        let token =
+            self.token_loader
+                .load()
+                .await
+                .map_err(|error| {
+                    Retry::Transient(StorageErrorKind::Unauthorized.with_error(
+                        error.context("failed to fetch Google Cloud Storage access token"),
+                    ))
+                })?
+                .ok_or_else(|| {
+                    Retry::Permanent(StorageErrorKind::Unauthorized.with_error(anyhow::anyhow!(
+                        "failed to fetch Google Cloud Storage access token: no credentials found"
+                    )))
+                })?;
+        let access_token = token.access_token().to_string();
+        let expires_at = Instant::now() + Duration::from_secs(token.expires_in() as u64);
+        *cached_token_guard = Some((access_token.clone(), expires_at));
+        Ok(access_token)
+    }
+}
+
+/// Resumable upload session, canceled when dropped before completion so that the chunks uploaded
+/// so far do not linger.
+struct UploadSession {
+    client: Client,
+    session_url: String,
+    is_complete: bool,
+}
+
+impl UploadSession {
+    fn new(client: Client, session_url: String) -> Self {
+        Self {
+            client,
+            session_url,
+            is_complete: false,
+        }
+    }
+
+    fn complete(&mut self) {
+        self.is_complete = true;
+    }
+}
+
+impl Drop for UploadSession {
+    fn drop(&mut self) {
+        if self.is_complete {
+            return;
+        }
+        let Ok(runtime_handle) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let cancel_request = self
+            .client
+            .delete(&self.session_url)
+            .header(CONTENT_LENGTH, 0);
+        runtime_handle.spawn(async move {
+            // Canceled sessions respond with `499 Client Closed Request`.
+            if let Err(error) = cancel_request.send().await {
+                warn!(error=?error, "failed to cancel Google Cloud Storage upload session");
+            }
+        });
+    }
+}
+
+/// Returns the number of bytes persisted by a resumable upload session, given the response to a
+/// chunk upload or a status query: `308 Resume Incomplete` responses carry the range of persisted
+/// bytes, if any, while successful responses mean that the upload is complete.
+fn parse_persisted_len(response: &Response, total_len: u64) -> u64 {
+    if response.status() != StatusCode::PERMANENT_REDIRECT {
+        return total_len;
+    }
+    response
+        .headers()
+        .get(RANGE)
+        .and_then(|header_value| header_value.to_str().ok())
+        .and_then(|range| range.strip_prefix("bytes=0-"))
+        .and_then(|last_byte| last_byte.parse::<u64>().ok())
+        .map(|last_byte| last_byte + 1)
+        .unwrap_or(0)
+}
+
+/// Parses the `multipart/mixed` response of a batch request and returns the status and message
+/// of each inner response, keyed by request ID.
+fn parse_batch_response(
+    content_type: &str,
+    body: &str,
+) -> Option<HashMap<usize, (StatusCode, String)>> {
+    let boundary = content_type
+        .split(';')
+        .find_map(|param| param.trim().strip_prefix("boundary="))?
+        .trim_matches('"');
+    let delimiter = format!("--{boundary}");
+    let mut responses = HashMap::new();
+
+    for part in body.split(delimiter.as_str()) {
+        let request_id_opt = part.lines().find_map(|line| {
+            let (header_name, header_value) = line.split_once(':')?;
+            if !header_name.trim().eq_ignore_ascii_case("content-id") {
+                return None;
+            }
+            header_value
+                .trim()
+                .trim_start_matches('<')
+                .trim_end_matches('>')
+                .strip_prefix("response-")?
+                .parse::<usize>()
+                .ok()
+        });
+        let status_opt = part.lines().find_map(|line| {
+            let status_code = line.strip_prefix("HTTP/1.1 ")?.split_whitespace().next()?;
+            StatusCode::from_bytes(status_code.as_bytes()).ok()
+        });
+        let (Some(request_id), Some(status)) = (request_id_opt, status_opt) else {
+            continue;
+        };
+        let message = part
+            .splitn(3, "\r\n\r\n")
+            .nth(2)
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+        responses.insert(request_id, (status, message));
+    }
+    Some(responses)
+}
+
+fn status_to_error_kind(status: StatusCode) -> StorageErrorKind {
+    // Google Cloud Storage responds with `403 Forbidden` when the credentials lack the permission
+    // to access the object, whether it exists or not: only `404 Not Found` means that the object
+    // does not exist.
+    match status {
+        StatusCode::NOT_FOUND => StorageErrorKind::NotFound,
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => StorageErrorKind::Unauthorized,
+        StatusCode::REQUEST_TIMEOUT => StorageErrorKind::Timeout,
+        _ => StorageErrorKind::Service,
+    }
+}
+
+fn is_transient_status(status: StatusCode) -> bool {
+    status.is_server_error()
+        || status == StatusCode::TOO_MANY_REQUESTS
+        || status == StatusCode::REQUEST_TIMEOUT
+}
+
+async fn read_payload_chunk(
+    payload: &dyn PutPayload,
+    range: Range<u64>,
+) -> Result<Vec<u8>, Retry<StorageError>> {
+    let read_chunk = async {
+        let mut chunk_reader = payload
+            .range_byte_stream(range.clone())
+            .await?
+            .into_async_read();
+        let mut chunk = Vec::with_capacity((range.end - range.start) as usize);
+        tokio::io::copy(&mut chunk_reader, &mut chunk).await?;
+        Ok::<_, io::Error>(chunk)
+    };
+    read_chunk
+        .await
+        .map_err(|io_error| Retry::Permanent(StorageError::from(io_error)))
+}
+
+/// Google Cloud Storage accepts objects of up to 5TiB in a single upload request, but uploading
+/// large objects in chunks allows failed uploads to resume instead of starting over.
+fn default_multipart_policy() -> MultiPartPolicy {
+    MultiPartPolicy {
+        target_part_num_bytes: 64 * 1024 * 1024,          // 64MiB
+        multipart_threshold_num_bytes: 128 * 1024 * 1024, // 128MiB
+        max_num_parts: 10_000,
+        max_object_num_bytes: 5 * 1024 * 1024 * 1024 * 1024, // 5TiB
+        // The chunks of a resumable upload are sent sequentially.
+        max_concurrent_uploads: 1,
+    }
+}
+
+/// Builds a token loader always returning the same access token.
+#[cfg(any(test, feature = "integration-testsuite"))]
+fn static_token_loader(client: Client, access_token: &'static str) -> GoogleTokenLoader {
+    #[derive(Debug)]
+    struct StaticTokenLoader(&'static str);
+
+    #[async_trait]
+    impl reqsign::GoogleTokenLoad for StaticTokenLoader {
+        async fn load(&self, _: Client) -> anyhow::Result<Option<reqsign::GoogleToken>> {
+            Ok(Some(reqsign::GoogleToken::new(
+                self.0,
+                86400,
+                GOOGLE_CLOUD_STORAGE_SCOPE,
+            )))
+        }
+    }
+    GoogleTokenLoader::new(GOOGLE_CLOUD_STORAGE_SCOPE, client)
+        .with_customed_token_loader(Box::new(StaticTokenLoader(access_token)))
+}
+
+fn parse_google_uri(uri: &Uri) -> Option<(String, PathBuf)> {
+    // Ex: gs://bucket/prefix.
+    static URI_PTN: OnceCell<Regex> = OnceCell::new();
+
+    let captures = URI_PTN
+        .get_or_init(|| {
+            Regex::new(r"gs(\+[^:]+)?://(?P<bucket>[^/]+)(/(?P<prefix>.*))?$")
+                .expect("The regular expression should compile.")
+        })
+        .captures(uri.as_str())?;
+
+    let bucket = captures.name("bucket")?.as_str().to_string();
+    let prefix = captures
+        .name("prefix")
+        .map(|prefix_match| PathBuf::from(prefix_match.as_str()))
+        .unwrap_or_default();
+    Some((bucket, prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+    use std::net::SocketAddr;
+    use std::sync::Mutex;
+
+    use hyper::header::{AUTHORIZATION, HOST};
+    use hyper::service::{make_service_fn, service_fn};
+    use hyper::{Body, Method, Request, Server};
+    use percent_encoding::percent_decode_str;
+
+    use super::*;
+
+    const ACCESS_TOKEN: &str = "test-access-token";
+
+    /// In-memory state of the emulated Google Cloud Storage server.
+    #[derive(Default)]
+    struct FakeGcsState {
+        objects: HashMap<String, Vec<u8>>,
+        /// Upload sessions, keyed by session ID, with the name and the bytes of the object, and
+        /// whether the upload is conditioned on the absence of the object.
+        upload_sessions: HashMap<String, (String, Vec<u8>, bool)>,
+        num_canceled_upload_sessions: usize,
+        /// Number of single part uploads persisted without acknowledging them.
+        num_unacknowledged_uploads: usize,
+        /// Number of chunks persisted without acknowledging them, as if the response was lost.
+        num_unacknowledged_chunks: usize,
+        /// Number of chunks rejected with `400 Bad Request`.
+        num_rejected_chunks: usize,
+    }
+
+    type FakeGcs = Arc<Mutex<FakeGcsState>>;
+
+    fn json_response(status: StatusCode, body: String) -> hyper::Response<Body> {
+        hyper::Response::builder()
+            .status(status)
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    fn object_status(name: &str, is_present: bool) -> StatusCode {
+        if name.ends_with("/forbidden") {
+            StatusCode::FORBIDDEN
+        } else if is_present {
+            StatusCode::OK
+        } else {
+            StatusCode::NOT_FOUND
+        }
+    }
+
+    fn resume_incomplete_response(num_persisted_bytes: usize) -> hyper::Response<Body> {
+        let mut response_builder =
+            hyper::Response::builder().status(StatusCode::PERMANENT_REDIRECT);
+
+        if num_persisted_bytes > 0 {
+            response_builder =
+                response_builder.header(RANGE, format!("bytes=0-{}", num_persisted_bytes - 1));
+        }
+        response_builder.body(Body::empty()).unwrap()
+    }
+
+    /// Emulates the subset of the JSON API used by [`GoogleCloudStorage`] for the bucket
+    /// `test-bucket`.
+    async fn serve_gcs(
+        fake_gcs: FakeGcs,
+        request: Request<Body>,
+    ) -> Result<hyper::Response<Body>, Infallible> {
+        let url = Url::parse(&format!("http://localhost{}", request.uri())).unwrap();
+        let params: HashMap<String, String> = url.query_pairs().into_owned().collect();
+        let path = url.path().to_string();
+
+        // Upload sessions are authenticated by their URL.
+        if let Some(session_id) = path.strip_prefix("/upload/sessions/") {
+            return Ok(serve_upload_session(fake_gcs, session_id, request).await);
+        }
+        let authorization = request
+            .headers()
+            .get(AUTHORIZATION)
+            .and_then(|header_value| header_value.to_str().ok());
+        if authorization != Some(&format!("Bearer {ACCESS_TOKEN}")) {
+            return Ok(json_response(StatusCode::UNAUTHORIZED, String::new()));
+        }
+        if let Some(encoded_name) = path.strip_prefix("/storage/v1/b/test-bucket/o/") {
+            let name = percent_decode_str(encoded_name)
+                .decode_utf8_lossy()
+                .to_string();
+            let mut fake_gcs_guard = fake_gcs.lock().unwrap();
+            let status = object_status(&name, fake_gcs_guard.objects.contains_key(&name));
+
+            if status != StatusCode::OK {
+                return Ok(json_response(status, format!(r#"{{"error": "{status}"}}"#)));
+            }
+            let response = match *request.method() {
+                Method::GET if params.get("alt").map(String::as_str) == Some("media") => {
+                    let object = &fake_gcs_guard.objects[&name];
+                    let Some(range) = request.headers().get(RANGE) else {
+                        return Ok(hyper::Response::new(Body::from(object.clone())));
+                    };
+                    let (start, end) = range
+                        .to_str()
+                        .unwrap()
+                        .strip_prefix("bytes=")
+                        .unwrap()
+                        .split_once('-')
+                        .unwrap();
+                    let start: usize = start.parse().unwrap();
+                    let end: usize = end.parse::<usize>().unwrap().min(object.len() - 1);
+                    hyper::Response::builder()
+                        .status(StatusCode::PARTIAL_CONTENT)
+                        .body(Body::from(object[start..=end].to_vec()))
+                        .unwrap()
+                }
+                Method::GET => {
+                    let object_metadata = format!(
+                        r#"{{"name": "{name}", "size": "{}"}}"#,
+                        fake_gcs_guard.objects[&name].len()
+                    );
+                    json_response(StatusCode::OK, object_metadata)
+                }
+                Method::DELETE => {
+                    fake_gcs_guard.objects.remove(&name);
+                    json_response(StatusCode::NO_CONTENT, String::new())
+                }
+                _ => json_response(StatusCode::BAD_REQUEST, String::new()),
+            };
+            return Ok(response);
+        }
+        let response = match (request.method().clone(), path.as_str()) {
+            (Method::GET, "/storage/v1/b/test-bucket/o") => {
+                let prefix = params.get("prefix").cloned().unwrap_or_default();
+                let page_ord: usize = params
+                    .get("pageToken")
+                    .map_or(0, |page_token| page_token.parse().unwrap());
+                let fake_gcs_guard = fake_gcs.lock().unwrap();
+                let mut names: Vec<&String> = fake_gcs_guard
+                    .objects
+                    .keys()
+                    .filter(|name| name.starts_with(&prefix))
+                    .collect();
+                names.sort();
+                // Objects are listed two at a time to exercise the pagination.
+                let items: Vec<String> = names
+                    .iter()
+                    .skip(page_ord * 2)
+                    .take(2)
+                    .map(|name| format!(r#"{{"name": "{name}", "size": "0"}}"#))
+                    .collect();
+                let next_page_token = if names.len() > (page_ord + 1) * 2 {
+                    format!(r#", "nextPageToken": "{}""#, page_ord + 1)
+                } else {
+                    String::new()
+                };
+                let list_objects_response =
+                    format!(r#"{{"items": [{}]{next_page_token}}}"#, items.join(", "));
+                json_response(StatusCode::OK, list_objects_response)
+            }
+            (Method::POST, "/upload/storage/v1/b/test-bucket/o") => {
+                let name = params["name"].clone();
+                let host = request.headers().get(HOST).unwrap().to_str().unwrap();
+                let location = format!("http://{host}/upload/sessions/{}", ulid::Ulid::new());
+                let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+                let mut fake_gcs_guard = fake_gcs.lock().unwrap();
+                let if_absent = params.get("ifGenerationMatch").map(String::as_str) == Some("0");
+
+                match params["uploadType"].as_str() {
+                    "media" => {
+                        if if_absent && fake_gcs_guard.objects.contains_key(&name) {
+                            return Ok(json_response(
+                                StatusCode::PRECONDITION_FAILED,
+                                String::new(),
+                            ));
+                        }
+                        fake_gcs_guard.objects.insert(name, body.to_vec());
+
+                        if fake_gcs_guard.num_unacknowledged_uploads > 0 {
+                            fake_gcs_guard.num_unacknowledged_uploads -= 1;
+                            return Ok(json_response(
+                                StatusCode::SERVICE_UNAVAILABLE,
+                                String::new(),
+                            ));
+                        }
+                        json_response(StatusCode::OK, String::new())
+                    }
+                    "resumable" => {
+                        let session_id = location.rsplit('/').next().unwrap().to_string();
+                        fake_gcs_guard
+                            .upload_sessions
+                            .insert(session_id, (name, Vec::new(), if_absent));
+                        hyper::Response::builder()
+                            .header(LOCATION, location)
+                            .body(Body::empty())
+                            .unwrap()
+                    }
+                    _ => json_response(StatusCode::BAD_REQUEST, String::new()),
+                }
+            }
+            (Method::POST, "/batch/storage/v1") => {
+                let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+                let body = String::from_utf8(body.to_vec()).unwrap();
+                let mut fake_gcs_guard = fake_gcs.lock().unwrap();
+                let mut response_body = String::new();
+
+                for part in body.split("--batch_").skip(1) {
+                    let Some(content_id) = part
+                        .lines()
+                        .find_map(|line| line.strip_prefix("Content-ID: "))
+                    else {
+                        continue;
+                    };
+                    let encoded_name = part
+                        .lines()
+                        .find_map(|line| line.strip_prefix("DELETE /storage/v1/b/test-bucket/o/"))
+                        .unwrap()
+                        .strip_suffix(" HTTP/1.1")
+                        .unwrap();
+                    let name = percent_decode_str(encoded_name)
+                        .decode_utf8_lossy()
+                        .to_string();
+                    let status =
+                        object_status(&name, fake_gcs_guard.objects.remove(&name).is_some());
+                    let request_id = content_id.trim_start_matches('<').trim_end_matches('>');
+                    response_body.push_str(&format!(
+                        "--batch_response\r\nContent-Type: application/http\r\nContent-ID: \
+                         <response-{request_id}>\r\n\r\nHTTP/1.1 {status}\r\nContent-Type: \
+                         application/json\r\n\r\n{{\"error\": \"{status}\"}}\r\n"
+                    ));
+                }
+                response_body.push_str("--batch_response--\r\n");
+                hyper::Response::builder()
+                    .header(CONTENT_TYPE, "multipart/mixed; boundary=batch_response")
+                    .body(Body::from(response_body))
+                    .unwrap()
+            }
+            _ => json_response(StatusCode::BAD_REQUEST, String::new()),
+        };
+        Ok(response)
+    }
+
+    async fn serve_upload_session(
+        fake_gcs: FakeGcs,
+        session_id: &str,
+        request: Request<Body>,
+    ) -> hyper::Response<Body> {
+        if request.method() == Method::DELETE {
+            let mut fake_gcs_guard = fake_gcs.lock().unwrap();
+            fake_gcs_guard.upload_sessions.remove(session_id);
+            fake_gcs_guard.num_canceled_upload_sessions += 1;
+            return json_response(StatusCode::from_u16(499).unwrap(), String::new());
+        }
+        let content_range = request
+            .headers()
+            .get(CONTENT_RANGE)
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_string();
+        let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+        let mut fake_gcs_guard = fake_gcs.lock().unwrap();
+        let fake_gcs_state = &mut *fake_gcs_guard;
+        let Some((_, data, _)) = fake_gcs_state.upload_sessions.get_mut(session_id) else {
+            return json_response(StatusCode::NOT_FOUND, String::new());
+        };
+        let (range, total_len) = content_range
+            .strip_prefix("bytes ")
+            .unwrap()
+            .split_once('/')
+            .unwrap();
+        let total_len: usize = total_len.parse().unwrap();
+
+        if range != "*" {
+            if fake_gcs_state.num_rejected_chunks > 0 {
+                fake_gcs_state.num_rejected_chunks -= 1;
+                return json_response(StatusCode::BAD_REQUEST, String::new());
+            }
+            let (start, end) = range.split_once('-').unwrap();
+            let start: usize = start.parse().unwrap();
+            let end: usize = end.parse().unwrap();
+            assert_eq!(end + 1 - start, body.len());
+
+            // Bytes that are already persisted are ignored.
+            if end + 1 > data.len() {
+                data.extend_from_slice(&body[data.len() - start..]);
+            }
+            if fake_gcs_state.num_unacknowledged_chunks > 0 {
+                fake_gcs_state.num_unacknowledged_chunks -= 1;
+                return json_response(StatusCode::SERVICE_UNAVAILABLE, String::new());
+            }
+        }
+        if data.len() < total_len {
+            return resume_incomplete_response(data.len());
+        }
+        let (name, data, if_absent) = fake_gcs_state.upload_sessions.remove(session_id).unwrap();
+
+        if if_absent && fake_gcs_state.objects.contains_key(&name) {
+            return json_response(StatusCode::PRECONDITION_FAILED, String::new());
+        }
+        fake_gcs_state.objects.insert(name, data);
+        json_response(StatusCode::OK, String::new())
+    }
+
+    async fn start_fake_gcs_server(fake_gcs: FakeGcs) -> SocketAddr {
+        let make_service = make_service_fn(move |_conn| {
+            let fake_gcs = fake_gcs.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request| {
+                    serve_gcs(fake_gcs.clone(), request)
+                }))
+            }
+        });
+        let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+        let server_addr = server.local_addr();
+        tokio::spawn(server);
+        server_addr
+    }
+
+    fn google_cloud_storage_for_test(server_addr: SocketAddr) -> GoogleCloudStorage {
+        let client = new_client();
+        let endpoint = Url::parse(&format!("http://{server_addr}")).unwrap();
+        let mut storage = GoogleCloudStorage::new(
+            client.clone(),
+            static_token_loader(client, ACCESS_TOKEN),
+            endpoint,
+            Uri::for_test("gs://test-bucket/indexes"),
+            "test-bucket".to_string(),
+            PathBuf::from("indexes"),
+        );
+        storage.retry_params = RetryParams::for_test();
+        storage
+    }
+
+    /// Uploads payloads of more than 512KiB in chunks of 256KiB.
+    fn resumable_upload_policy() -> MultiPartPolicy {
+        MultiPartPolicy {
+            target_part_num_bytes: 256 * 1024,
+            multipart_threshold_num_bytes: 512 * 1024,
+            ..default_multipart_policy()
+        }
+    }
+
+    #[test]
+    fn test_parse_google_uri() {
+        assert!(parse_google_uri(&Uri::for_test("gs://")).is_none());
+
+        let (bucket, prefix) = parse_google_uri(&Uri::for_test("gs://test-bucket")).unwrap();
+        assert_eq!(bucket, "test-bucket");
+        assert!(prefix.to_str().unwrap().is_empty());
+
+        let (bucket, prefix) = parse_google_uri(&Uri::for_test("gs://test-bucket/")).unwrap();
+        assert_eq!(bucket, "test-bucket");
+        assert!(prefix.to_str().unwrap().is_empty());
+
+        let (bucket, prefix) =
+            parse_google_uri(&Uri::for_test("gs://test-bucket/indexes")).unwrap();
+        assert_eq!(bucket, "test-bucket");
+        assert_eq!(prefix.to_str().unwrap(), "indexes");
+    }
+
+    #[tokio::test]
+    async fn test_google_cloud_storage() {
+        let fake_gcs = FakeGcs::default();
+        let server_addr = start_fake_gcs_server(fake_gcs.clone()).await;
+        let storage = google_cloud_storage_for_test(server_addr);
+        let path = Path::new("splits/file.split");
+        let payload = b"abcdefghijklmnopqrstuvwxyz";
+
+        storage.check_connectivity().await.unwrap();
+
+        storage.put(path, Box::new(payload.to_vec())).await.unwrap();
+        assert_eq!(
+            fake_gcs.lock().unwrap().objects["indexes/splits/file.split"],
+            payload
+        );
+        let all = storage.get_all(path).await.unwrap();
+        assert_eq!(all.as_slice(), payload);
+
+        let slice = storage.get_slice(path, 3..8).await.unwrap();
+        assert_eq!(slice.as_slice(), b"defgh");
+
+        let slice = storage.get_slice(path, 5..5).await.unwrap();
+        assert!(slice.is_empty());
+
+        let error = storage.get_slice(path, 20..30).await.unwrap_err();
+        assert_eq!(error.kind(), StorageErrorKind::Internal);
+
+        let mut slice_stream = storage.get_slice_stream(path, 0..3).await.unwrap();
+        let mut buffer = Vec::new();
+        tokio::io::copy(&mut slice_stream, &mut buffer)
+            .await
+            .unwrap();
+        assert_eq!(buffer, b"abc");
+
+        let mut buffer = Vec::new();
+        storage.copy_to(path, &mut buffer).await.unwrap();
+        assert_eq!(buffer, payload);
+
+        let num_bytes = storage.file_num_bytes(path).await.unwrap();
+        assert_eq!(num_bytes, payload.len() as u64);
+
+        assert!(storage.exists(path).await.unwrap());
+        storage.delete(path).await.unwrap();
+        assert!(!storage.exists(path).await.unwrap());
+
+        // Deleting a missing object is not an error.
+        storage.delete(path).await.unwrap();
+
+        let error = storage.get_all(path).await.unwrap_err();
+        assert_eq!(error.kind(), StorageErrorKind::NotFound);
+
+        // Objects that the credentials do not allow to read are not reported as missing.
+        let error = storage.get_all(Path::new("forbidden")).await.unwrap_err();
+        assert_eq!(error.kind(), StorageErrorKind::Unauthorized);
+
+        let error = storage.exists(Path::new("forbidden")).await.unwrap_err();
+        assert_eq!(error.kind(), StorageErrorKind::Unauthorized);
+    }
+
+    #[tokio::test]
+    async fn test_google_cloud_storage_unauthorized() {
+        let server_addr = start_fake_gcs_server(FakeGcs::default()).await;
+        let mut storage = google_cloud_storage_for_test(server_addr);
+        storage.token_provider = Arc::new(AccessTokenProvider::new(static_token_loader(
+            new_client(),
+            "invalid-access-token",
+        )));
+        let error = storage.get_all(Path::new("file")).await.unwrap_err();
+        assert_eq!(error.kind(), StorageErrorKind::Unauthorized);
+    }
+
+    #[tokio::test]
+    async fn test_google_cloud_storage_list() {
+        let fake_gcs = FakeGcs::default();
+        let server_addr = start_fake_gcs_server(fake_gcs.clone()).await;
+        let storage = google_cloud_storage_for_test(server_addr);
+
+        for name in ["file-0", "splits/file-1", "splits/file-2", "splits/file-3"] {
+            storage
+                .put(Path::new(name), Box::new(b"abc".to_vec()))
+                .await
+                .unwrap();
+        }
+        let paths = storage.list(Path::new("splits/")).await.unwrap();
+        assert_eq!(
+            paths,
+            [
+                PathBuf::from("splits/file-1"),
+                PathBuf::from("splits/file-2"),
+                PathBuf::from("splits/file-3")
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_google_cloud_storage_resumable_upload() {
+        let fake_gcs = FakeGcs::default();
+        let server_addr = start_fake_gcs_server(fake_gcs.clone()).await;
+        let mut storage = google_cloud_storage_for_test(server_addr);
+        storage.set_policy(resumable_upload_policy());
+
+        let path = Path::new("splits/file.split");
+        let payload: Vec<u8> = (0..600 * 1024).map(|i| i as u8).collect();
+
+        // The first chunk is persisted but its acknowledgment is lost: the upload resumes from
+        // the last persisted byte.
+        fake_gcs.lock().unwrap().num_unacknowledged_chunks = 1;
+
+        storage.put(path, Box::new(payload.clone())).await.unwrap();
+        {
+            let fake_gcs_guard = fake_gcs.lock().unwrap();
+            assert_eq!(fake_gcs_guard.objects["indexes/splits/file.split"], payload);
+            assert!(fake_gcs_guard.upload_sessions.is_empty());
+            assert_eq!(fake_gcs_guard.num_canceled_upload_sessions, 0);
+        }
+        let slice = storage
+            .get_slice(path, 256 * 1024..256 * 1024 + 3)
+            .await
+            .unwrap();
+        assert_eq!(slice.as_slice(), &payload[256 * 1024..256 * 1024 + 3]);
+    }
+
+    #[tokio::test]
+    async fn test_google_cloud_storage_put_if_absent() {
+        let fake_gcs = FakeGcs::default();
+        let server_addr = start_fake_gcs_server(fake_gcs.clone()).await;
+        let mut storage = google_cloud_storage_for_test(server_addr);
+        let path = Path::new("splits/file.split");
+
+        assert!(storage
+            .put_if_absent(path, Box::new(b"abc".to_vec()))
+            .await
+            .unwrap());
+        assert!(!storage
+            .put_if_absent(path, Box::new(b"def".to_vec()))
+            .await
+            .unwrap());
+        assert_eq!(
+            fake_gcs.lock().unwrap().objects["indexes/splits/file.split"],
+            b"abc"
+        );
+
+        // The first attempt is persisted but its acknowledgment is lost: the retry is rejected,
+        // yet the write is reported as successful.
+        fake_gcs.lock().unwrap().num_unacknowledged_uploads = 1;
+        let other_path = Path::new("splits/other-file.split");
+        assert!(storage
+            .put_if_absent(other_path, Box::new(b"ghi".to_vec()))
+            .await
+            .unwrap());
+
+        storage.set_policy(resumable_upload_policy());
+        let payload: Vec<u8> = vec![0; 600 * 1024];
+        assert!(!storage
+            .put_if_absent(path, Box::new(payload.clone()))
+            .await
+            .unwrap());
+        assert_eq!(
+            fake_gcs.lock().unwrap().objects["indexes/splits/file.split"],
+            b"abc"
+        );
+        let resumable_path = Path::new("splits/resumable-file.split");
+        assert!(storage
+            .put_if_absent(resumable_path, Box::new(payload.clone()))
+            .await
+            .unwrap());
+        assert_eq!(
+            fake_gcs.lock().unwrap().objects["indexes/splits/resumable-file.split"],
+            payload
+        );
+    }
+
+    #[tokio::test]
+    async fn test_google_cloud_storage_cancels_failed_resumable_upload() {
+        let fake_gcs = FakeGcs::default();
+        let server_addr = start_fake_gcs_server(fake_gcs.clone()).await;
+        let mut storage = google_cloud_storage_for_test(server_addr);
+        storage.set_policy(resumable_upload_policy());
+
+        let path = Path::new("splits/file.split");
+        let payload: Vec<u8> = vec![0; 600 * 1024];
+        fake_gcs.lock().unwrap().num_rejected_chunks = 1;
+
+        let error = storage.put(path, Box::new(payload)).await.unwrap_err();
+        assert_eq!(error.kind(), StorageErrorKind::Service);
+
+        // The upload session is canceled in the background.
+        for _ in 0..100 {
+            if fake_gcs.lock().unwrap().num_canceled_upload_sessions > 0 {
+                break;
+            }
+            tokio::task::yield_now().await;
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let fake_gcs_guard = fake_gcs.lock().unwrap();
+        assert_eq!(fake_gcs_guard.num_canceled_upload_sessions, 1);
+        assert!(fake_gcs_guard.upload_sessions.is_empty());
+        assert!(fake_gcs_guard.objects.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_google_cloud_storage_bulk_delete() {
+        let fake_gcs = FakeGcs::default();
+        let server_addr = start_fake_gcs_server(fake_gcs.clone()).await;
+        let storage = google_cloud_storage_for_test(server_addr);
+
+        let path_0 = Path::new("file-0");
+        let path_1 = Path::new("splits/file-1");
+        storage.put(path_0, Box::new(b"0".to_vec())).await.unwrap();
+        storage.put(path_1, Box::new(b"1".to_vec())).await.unwrap();
+
+        storage
+            .bulk_delete(&[path_0, path_1, Path::new("missing")])
+            .await
+            .unwrap();
+        assert!(fake_gcs.lock().unwrap().objects.is_empty());
+
+        storage.put(path_0, Box::new(b"0".to_vec())).await.unwrap();
+        let forbidden_path = Path::new("forbidden");
+
+        let bulk_delete_error = storage
+            .bulk_delete(&[path_0, forbidden_path])
+            .await
+            .unwrap_err();
+        assert!(bulk_delete_error.error.is_none());
+        assert_eq!(bulk_delete_error.successes, [path_0.to_path_buf()]);
+        assert_eq!(bulk_delete_error.failures.len(), 1);
+
+        let delete_failure = &bulk_delete_error.failures[forbidden_path];
+        assert_eq!(delete_failure.code.as_deref(), Some("403"));
+        assert_eq!(
+            delete_failure.error.as_ref().unwrap().kind(),
+            StorageErrorKind::Unauthorized
+        );
+        assert!(bulk_delete_error.unattempted.is_empty());
+    }
+}
@@ -17,6 +17,10 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
+use std::path::Path;
+
+use crate::{Storage, StorageResult};
+
 mod error;
 
 mod s3_compatible_storage;
@@ -32,3 +36,52 @@ mod s3_compatible_storage_resolver;
 mod azure_blob_storage;
 #[cfg(feature = "azure")]
 pub use self::azure_blob_storage::{AzureBlobStorage, AzureBlobStorageFactory};
+
+#[cfg(feature = "gcs")]
+mod google_cloud_storage;
+#[cfg(feature = "gcs")]
+pub use self::google_cloud_storage::{GoogleCloudStorage, GoogleCloudStorageFactory};
+
+/// Outcome of a write conditioned on the absence of the object.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum ConditionalPutOutcome {
+    /// The object was written.
+    Written,
+    /// The object store rejected the first attempt because the object already exists.
+    AlreadyExists,
+    /// The object store rejected a retry because the object already exists. The object may have
+    /// been written by an earlier attempt whose response was lost.
+    AlreadyExistsOnRetry,
+}
+
+impl ConditionalPutOutcome {
+    /// Builds the outcome of a conditional write that took `num_attempts` attempts.
+    pub(crate) fn new(is_written: bool, num_attempts: usize) -> Self {
+        if is_written {
+            ConditionalPutOutcome::Written
+        } else if num_attempts > 1 {
+            ConditionalPutOutcome::AlreadyExistsOnRetry
+        } else {
+            ConditionalPutOutcome::AlreadyExists
+        }
+    }
+}
+
+/// Returns whether a conditional write succeeded, so that retrying it is idempotent: when a retry
+/// is rejected, the write is considered successful if the existing object has the length of the
+/// payload, as it was most likely created by an earlier attempt.
+pub(crate) async fn resolve_conditional_put<S: Storage + ?Sized>(
+    storage: &S,
+    path: &Path,
+    payload_len: u64,
+    outcome: ConditionalPutOutcome,
+) -> StorageResult<bool> {
+    match outcome {
+        ConditionalPutOutcome::Written => Ok(true),
+        ConditionalPutOutcome::AlreadyExists => Ok(false),
+        ConditionalPutOutcome::AlreadyExistsOnRetry => {
+            let num_bytes = storage.file_num_bytes(path).await?;
+            Ok(num_bytes == payload_len)
+        }
+    }
+}
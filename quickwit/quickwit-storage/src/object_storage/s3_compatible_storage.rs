@@ -18,68 +18,112 @@
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
 use std::collections::HashMap;
+use std::convert::Infallible;
+use std::future::Future;
 use std::ops::Range;
 use std::path::{Path, PathBuf};
-use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 use std::task::{Context, Poll};
 use std::{env, fmt, io};
 
 use anyhow::anyhow;
 use async_trait::async_trait;
+use aws_config::profile::ProfileFileCredentialsProvider;
 use aws_credential_types::provider::SharedCredentialsProvider;
-use aws_sdk_s3::config::{Credentials, Region};
+use aws_sdk_s3::client::customize::CustomizableOperation;
+use aws_sdk_s3::config::{AppName, Credentials, Region};
 use aws_sdk_s3::error::{ProvideErrorMetadata, SdkError};
 use aws_sdk_s3::operation::get_object::{GetObjectError, GetObjectOutput};
-use aws_sdk_s3::types::{CompletedMultipartUpload, CompletedPart, Delete, ObjectIdentifier};
+use aws_sdk_s3::types::{
+    CompletedMultipartUpload, CompletedPart, Delete, ObjectIdentifier, ServerSideEncryption,
+};
 use aws_sdk_s3::Client as S3Client;
+use aws_sig_auth::signer::{OperationSigningConfig, SigningRequirements};
+use aws_smithy_client::erase::DynConnector;
+use aws_smithy_client::http_connector::ConnectorSettings;
+use aws_smithy_http::body::SdkBody;
 use aws_smithy_http::byte_stream::ByteStream;
+use aws_smithy_http::result::ConnectorError;
 use base64::prelude::{Engine, BASE64_STANDARD};
-use futures::{stream, StreamExt};
+use futures::{future, stream, StreamExt};
+use hyper::http::header::{IF_NONE_MATCH, USER_AGENT};
+use hyper::http::{HeaderName, HeaderValue, StatusCode};
+use hyper::service::Service;
 use once_cell::sync::{Lazy, OnceCell};
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
 use quickwit_aws::get_aws_config;
 use quickwit_aws::retry::{aws_retry, AwsRetryable};
 use quickwit_common::retry::{Retry, RetryParams};
 use quickwit_common::uri::Uri;
 use quickwit_common::{chunk_range, into_u64_range};
-use quickwit_config::S3StorageConfig;
+use quickwit_config::{S3ServerSideEncryptionConfig, S3StorageConfig};
 use regex::Regex;
-use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt, BufReader, ReadBuf};
-use tokio::sync::Semaphore;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt, BufReader};
 use tracing::{info, instrument, warn};
 
-use crate::object_storage::MultiPartPolicy;
+use crate::object_storage::{resolve_conditional_put, ConditionalPutOutcome, MultiPartPolicy};
 use crate::storage::SendableAsync;
 use crate::{
-    BulkDeleteError, DeleteFailure, OwnedBytes, Storage, StorageError, StorageErrorKind,
-    StorageResolverError, StorageResult, STORAGE_METRICS,
+    BulkDeleteError, DeleteFailure, OwnedBytes, PermitAsyncRead, Storage, StorageError,
+    StorageErrorKind, StorageRequestLimiter, StorageResolverError, StorageResult, STORAGE_METRICS,
 };
 
-/// Semaphore to limit the number of concurent requests to the object store. Some object stores
-/// (R2, SeaweedFs...) return errors when too many concurrent requests are emitted.
-static REQUEST_SEMAPHORE: Lazy<Semaphore> = Lazy::new(|| {
+/// Limits the number of concurent requests to the object store when the S3 storage config does
+/// not define `max_concurrent_requests`. Some object stores (R2, SeaweedFs...) return errors when
+/// too many concurrent requests are emitted.
+static DEFAULT_REQUEST_LIMITER: Lazy<StorageRequestLimiter> = Lazy::new(|| {
     let num_permits: usize = env::var("QW_S3_MAX_CONCURRENCY")
         .as_deref()
         .unwrap_or("10000")
         .parse()
         .expect("QW_S3_MAX_CONCURRENCY value should be a number.");
-    Semaphore::new(num_permits)
+    StorageRequestLimiter::new(num_permits)
 });
 
-/// Wrap the async read handle together with a permit to keep the permit alive
-/// until the handle is dropped
-struct S3AsyncRead<T: AsyncRead + Send + Unpin> {
-    pub read: T,
-    pub _permit: Result<tokio::sync::SemaphorePermit<'static>, tokio::sync::AcquireError>,
+/// Maximum gap between two byte ranges read together for them to be fetched with a single request
+/// when the S3 storage config does not define `range_merge_gap`. Downloading the bytes in between
+/// is cheaper than an extra round trip.
+const DEFAULT_RANGE_MERGE_GAP: usize = 64 * 1024;
+
+/// Maximum size of a range resulting from merging nearby byte ranges. Ranges are not merged beyond
+/// this size so that a batch of small reads does not turn into one very large download.
+const MAX_MERGED_RANGE_NUM_BYTES: usize = 16 * 1024 * 1024; // 16 MiB
+
+/// Maximum size of an object copied with a single `CopyObject` request. Larger objects have to be
+/// copied part by part with `UploadPartCopy`.
+const MAX_SINGLE_COPY_NUM_BYTES: u64 = 5 * 1024 * 1024 * 1024; // 5 GiB
+
+/// Sends a request built with the S3 client fluent API, without signing it in anonymous mode.
+macro_rules! send_request {
+    ($storage:expr, $request:expr) => {
+        match $request.customize().await {
+            Ok(customizable_request) => {
+                $storage
+                    .apply_signing_policy(customizable_request)
+                    .send()
+                    .await
+            }
+            Err(sdk_error) => Err(sdk_error),
+        }
+    };
 }
 
-impl<T: AsyncRead + Send + Unpin> AsyncRead for S3AsyncRead<T> {
-    fn poll_read(
-        self: Pin<&mut Self>,
-        cx: &mut Context<'_>,
-        buf: &mut ReadBuf<'_>,
-    ) -> Poll<io::Result<()>> {
-        let self_unpin = self.get_mut();
-        Pin::new(&mut self_unpin.read).poll_read(cx, buf)
+/// Characters of an object key left as is in the `x-amz-copy-source` header.
+const COPY_SOURCE_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'/')
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+
+/// Returns the request limiter for the given S3 storage config.
+pub(crate) fn request_limiter_for_config(
+    s3_storage_config: &S3StorageConfig,
+) -> StorageRequestLimiter {
+    match s3_storage_config.max_concurrent_requests {
+        Some(max_concurrent_requests) => StorageRequestLimiter::new(max_concurrent_requests.get()),
+        None => DEFAULT_REQUEST_LIMITER.clone(),
     }
 }
 
@@ -91,8 +135,12 @@ pub struct S3CompatibleObjectStorage {
     prefix: PathBuf,
     multipart_policy: MultiPartPolicy,
     retry_params: RetryParams,
+    range_merge_gap: usize,
+    server_side_encryption_opt: Option<S3ServerSideEncryptionConfig>,
     disable_multi_object_delete: bool,
     disable_multipart_upload: bool,
+    request_limiter: StorageRequestLimiter,
+    anonymous: bool,
 }
 
 impl fmt::Debug for S3CompatibleObjectStorage {
@@ -105,10 +153,18 @@ impl fmt::Debug for S3CompatibleObjectStorage {
     }
 }
 
+/// Returns the credentials provider of the S3 client, falling back to
+/// `default_credentials_provider_opt`. In anonymous mode, no credentials provider is returned so
+/// that requests are not signed.
 fn get_credentials_provider(
     s3_storage_config: &S3StorageConfig,
+    default_credentials_provider_opt: Option<&SharedCredentialsProvider>,
 ) -> Option<SharedCredentialsProvider> {
-    match (
+    if s3_storage_config.anonymous {
+        info!("using anonymous S3 access, requests will not be signed");
+        return None;
+    }
+    let credentials_provider_opt = match (
         &s3_storage_config.access_key_id,
         &s3_storage_config.secret_access_key,
     ) {
@@ -118,8 +174,15 @@ fn get_credentials_provider(
             let credentials_provider = SharedCredentialsProvider::new(credentials);
             Some(credentials_provider)
         }
-        _ => None,
-    }
+        _ => s3_storage_config.profile.as_ref().map(|profile| {
+            info!(profile=%profile, "using S3 credentials profile defined in storage config");
+            let credentials_provider = ProfileFileCredentialsProvider::builder()
+                .profile_name(profile)
+                .build();
+            SharedCredentialsProvider::new(credentials_provider)
+        }),
+    };
+    credentials_provider_opt.or_else(|| default_credentials_provider_opt.cloned())
 }
 
 fn get_region(s3_storage_config: &S3StorageConfig) -> Option<Region> {
@@ -129,26 +192,129 @@ fn get_region(s3_storage_config: &S3StorageConfig) -> Option<Region> {
     })
 }
 
+/// Application name appended to the user agent of the requests, so that they can be identified in
+/// S3 access logs.
+fn get_app_name(s3_storage_config: &S3StorageConfig) -> AppName {
+    let app_name = s3_storage_config
+        .user_agent
+        .as_deref()
+        .unwrap_or("quickwit");
+    AppName::new(app_name.to_string()).unwrap_or_else(|_| {
+        warn!(user_agent=%app_name, "invalid S3 user agent, falling back to `quickwit`");
+        AppName::new("quickwit").expect("`quickwit` should be a valid app name")
+    })
+}
+
+/// HTTP connector appending the configured suffix (`user_agent`) to the `User-Agent` header, which
+/// is the one recorded in S3 access logs, and adding the configured headers (`request_headers`) to
+/// every request sent to the object store.
+#[derive(Clone)]
+struct RequestHeadersConnector {
+    inner: DynConnector,
+    user_agent_suffix_opt: Option<String>,
+    request_headers: Arc<Vec<(HeaderName, HeaderValue)>>,
+}
+
+impl RequestHeadersConnector {
+    fn new(inner: DynConnector, s3_storage_config: &S3StorageConfig) -> Self {
+        let request_headers = s3_storage_config
+            .request_headers
+            .iter()
+            .filter_map(|(name, value)| {
+                // The headers are validated when the storage config is loaded.
+                let header_name = HeaderName::from_bytes(name.as_bytes()).ok()?;
+                let header_value = HeaderValue::from_str(value).ok()?;
+                Some((header_name, header_value))
+            })
+            .collect();
+        Self {
+            inner,
+            user_agent_suffix_opt: s3_storage_config.user_agent.clone(),
+            request_headers: Arc::new(request_headers),
+        }
+    }
+}
+
+impl Service<hyper::http::Request<SdkBody>> for RequestHeadersConnector {
+    type Response = hyper::http::Response<SdkBody>;
+    type Error = ConnectorError;
+    type Future = <DynConnector as Service<hyper::http::Request<SdkBody>>>::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: hyper::http::Request<SdkBody>) -> Self::Future {
+        if let Some(user_agent_suffix) = &self.user_agent_suffix_opt {
+            let user_agent = match request.headers().get(USER_AGENT) {
+                Some(user_agent) => {
+                    format!(
+                        "{} {user_agent_suffix}",
+                        user_agent.to_str().unwrap_or_default()
+                    )
+                }
+                None => user_agent_suffix.clone(),
+            };
+            if let Ok(user_agent) = HeaderValue::from_str(&user_agent) {
+                request.headers_mut().insert(USER_AGENT, user_agent);
+            }
+        }
+        for (header_name, header_value) in self.request_headers.iter() {
+            request
+                .headers_mut()
+                .insert(header_name.clone(), header_value.clone());
+        }
+        self.inner.call(request)
+    }
+}
+
 async fn create_s3_client(s3_storage_config: &S3StorageConfig) -> S3Client {
     let aws_config = get_aws_config().await;
     let credentials_provider =
-        get_credentials_provider(s3_storage_config).or(aws_config.credentials_provider().cloned());
+        get_credentials_provider(s3_storage_config, aws_config.credentials_provider());
     let region = get_region(s3_storage_config).or(aws_config.region().cloned());
     let mut s3_config = aws_sdk_s3::Config::builder().region(region);
 
     s3_config.set_credentials_cache(aws_config.credentials_cache().cloned());
     s3_config.set_credentials_provider(credentials_provider);
-    s3_config.set_force_path_style(s3_storage_config.force_path_style_access());
-    s3_config.set_http_connector(aws_config.http_connector().cloned());
+    s3_config.set_app_name(Some(get_app_name(s3_storage_config)));
+
+    let connector_settings = ConnectorSettings::from_timeout_config(
+        &aws_config.timeout_config().cloned().unwrap_or_default(),
+    );
+    let connector_opt = aws_config.http_connector().and_then(|http_connector| {
+        http_connector.connector(&connector_settings, aws_config.sleep_impl())
+    });
+    let has_request_tagging =
+        s3_storage_config.user_agent.is_some() || !s3_storage_config.request_headers.is_empty();
+    match connector_opt {
+        Some(connector) if has_request_tagging => {
+            let connector = RequestHeadersConnector::new(connector, s3_storage_config);
+            s3_config.set_http_connector(Some(DynConnector::new(connector)));
+        }
+        _ => {
+            s3_config.set_http_connector(aws_config.http_connector().cloned());
+        }
+    }
     s3_config.set_retry_config(aws_config.retry_config().cloned());
     s3_config.set_sleep_impl(aws_config.sleep_impl());
     s3_config.set_timeout_config(aws_config.timeout_config().cloned());
+    set_endpoint_and_addressing_style(&mut s3_config, s3_storage_config);
+    S3Client::from_conf(s3_config.build())
+}
 
+/// Sets the custom endpoint, if any, and the addressing style of the requests. When the
+/// addressing style is not explicitly configured, the SDK picks virtual-hosted-style requests for
+/// DNS-compatible bucket names and falls back to path-style requests otherwise.
+fn set_endpoint_and_addressing_style(
+    s3_config: &mut aws_sdk_s3::config::Builder,
+    s3_storage_config: &S3StorageConfig,
+) {
     if let Some(endpoint) = s3_storage_config.endpoint() {
         info!(endpoint=%endpoint, "using S3 endpoint defined in storage config or environment variable");
         s3_config.set_endpoint_url(Some(endpoint));
     }
-    S3Client::from_conf(s3_config.build())
+    s3_config.set_force_path_style(s3_storage_config.force_path_style_access());
 }
 
 impl S3CompatibleObjectStorage {
@@ -159,10 +325,17 @@ impl S3CompatibleObjectStorage {
         bucket: String,
     ) -> Result<Self, StorageResolverError> {
         let s3_client = create_s3_client(s3_storage_config).await;
+        let retry_policy = &s3_storage_config.retry_policy;
         let retry_params = RetryParams {
-            max_attempts: 3,
-            ..Default::default()
+            base_delay: retry_policy.base_delay,
+            max_delay: retry_policy.max_delay,
+            max_attempts: retry_policy.max_attempts.get(),
+            jitter: retry_policy.jitter,
         };
+        let range_merge_gap = s3_storage_config
+            .range_merge_gap
+            .map(|range_merge_gap| range_merge_gap.as_u64() as usize)
+            .unwrap_or(DEFAULT_RANGE_MERGE_GAP);
         let disable_multi_object_delete = s3_storage_config.disable_multi_object_delete;
         let disable_multipart_upload = s3_storage_config.disable_multipart_upload;
         Ok(Self {
@@ -172,8 +345,12 @@ impl S3CompatibleObjectStorage {
             prefix: PathBuf::new(),
             multipart_policy: MultiPartPolicy::default(),
             retry_params,
+            range_merge_gap,
+            server_side_encryption_opt: s3_storage_config.server_side_encryption.clone(),
             disable_multi_object_delete,
             disable_multipart_upload,
+            request_limiter: request_limiter_for_config(s3_storage_config),
+            anonymous: s3_storage_config.anonymous,
         })
     }
 
@@ -202,17 +379,50 @@ impl S3CompatibleObjectStorage {
             prefix,
             multipart_policy: self.multipart_policy,
             retry_params: self.retry_params,
+            range_merge_gap: self.range_merge_gap,
+            server_side_encryption_opt: self.server_side_encryption_opt,
             disable_multi_object_delete: self.disable_multi_object_delete,
             disable_multipart_upload: self.disable_multipart_upload,
+            request_limiter: self.request_limiter,
+            anonymous: self.anonymous,
         }
     }
 
+    /// Sets the limiter bounding the number of concurrent requests, possibly shared with other
+    /// storages.
+    pub fn with_request_limiter(mut self, request_limiter: StorageRequestLimiter) -> Self {
+        self.request_limiter = request_limiter;
+        self
+    }
+
     /// Sets the multipart policy.
     ///
     /// See `MultiPartPolicy`.
     pub fn set_policy(&mut self, multipart_policy: MultiPartPolicy) {
         self.multipart_policy = multipart_policy;
     }
+
+    /// Explicitly disables the signing of the request in anonymous mode. Without credentials, the
+    /// SDK would otherwise fail to sign the request instead of sending it unsigned.
+    fn apply_signing_policy<O, R>(
+        &self,
+        customizable_request: CustomizableOperation<O, R>,
+    ) -> CustomizableOperation<O, R> {
+        if !self.anonymous {
+            return customizable_request;
+        }
+        customizable_request
+            .map_operation(|mut operation| {
+                if let Some(signing_config) = operation
+                    .properties_mut()
+                    .get_mut::<OperationSigningConfig>()
+                {
+                    signing_config.signing_requirements = SigningRequirements::Disabled;
+                }
+                Ok::<_, Infallible>(operation)
+            })
+            .unwrap_or_else(|never| match never {})
+    }
 }
 
 pub fn parse_s3_uri(uri: &Uri) -> Option<(String, PathBuf)> {
@@ -279,38 +489,95 @@ impl S3CompatibleObjectStorage {
             .to_path_buf()
     }
 
+    /// Runs `f` until it succeeds, fails with a permanent error, or exhausts the retry policy,
+    /// counting the retries of `operation`.
+    async fn retry<U, E, Fut>(&self, operation: &'static str, f: impl Fn() -> Fut) -> Result<U, E>
+    where
+        Fut: Future<Output = Result<U, E>>,
+        E: AwsRetryable + fmt::Debug + 'static,
+    {
+        let num_attempts = AtomicUsize::new(0);
+        aws_retry(&self.retry_params, || {
+            if num_attempts.fetch_add(1, Ordering::Relaxed) > 0 {
+                STORAGE_METRICS
+                    .object_storage_request_retries_total
+                    .with_label_values([operation])
+                    .inc();
+            }
+            f()
+        })
+        .await
+    }
+
+    /// Returns `false` if `if_absent` is set and the object already exists.
     async fn put_single_part_single_try<'a>(
         &'a self,
         bucket: &'a str,
         key: &'a str,
         payload: Box<dyn crate::PutPayload>,
         len: u64,
-    ) -> Result<(), Retry<StorageError>> {
+        if_absent: bool,
+    ) -> Result<bool, Retry<StorageError>> {
         let body = payload
             .byte_stream()
             .await
             .map_err(|io_error| Retry::Permanent(StorageError::from(io_error)))?;
-        self.s3_client
+        let put_object_request = self
+            .s3_client
             .put_object()
             .bucket(bucket)
             .key(key)
             .body(body)
             .content_length(len as i64)
-            .send()
-            .await
-            .map_err(|sdk_error| {
-                if sdk_error.is_retryable() {
-                    Retry::Transient(StorageError::from(sdk_error))
-                } else {
-                    Retry::Permanent(StorageError::from(sdk_error))
+            .set_server_side_encryption(self.server_side_encryption())
+            .set_ssekms_key_id(self.ssekms_key_id());
+        let put_object_result = if if_absent {
+            match put_object_request.customize().await {
+                Ok(customizable_request) => {
+                    self.apply_signing_policy(customizable_request)
+                        .mutate_request(set_if_none_match_header)
+                        .send()
+                        .await
                 }
-            })?;
-
+                Err(sdk_error) => Err(sdk_error),
+            }
+        } else {
+            send_request!(self, put_object_request)
+        };
+        match put_object_result {
+            Ok(_) => {}
+            Err(sdk_error) if if_absent && is_precondition_failed(&sdk_error) => {
+                return Ok(false);
+            }
+            Err(sdk_error) if sdk_error.is_retryable() => {
+                return Err(Retry::Transient(StorageError::from(sdk_error)));
+            }
+            Err(sdk_error) => {
+                return Err(Retry::Permanent(StorageError::from(sdk_error)));
+            }
+        }
         crate::STORAGE_METRICS.object_storage_put_parts.inc();
         crate::STORAGE_METRICS
             .object_storage_upload_num_bytes
             .inc_by(len);
-        Ok(())
+        Ok(true)
+    }
+
+    /// Value of the `x-amz-server-side-encryption` header of the requests writing objects.
+    fn server_side_encryption(&self) -> Option<ServerSideEncryption> {
+        self.server_side_encryption_opt
+            .as_ref()
+            .map(|server_side_encryption| {
+                ServerSideEncryption::from(server_side_encryption.algorithm.as_str())
+            })
+    }
+
+    /// Value of the `x-amz-server-side-encryption-aws-kms-key-id` header of the requests writing
+    /// objects.
+    fn ssekms_key_id(&self) -> Option<String> {
+        self.server_side_encryption_opt
+            .as_ref()
+            .and_then(|server_side_encryption| server_side_encryption.kms_key_id.clone())
     }
 
     async fn put_single_part<'a>(
@@ -318,32 +585,44 @@ impl S3CompatibleObjectStorage {
         key: &'a str,
         payload: Box<dyn crate::PutPayload>,
         len: u64,
-    ) -> StorageResult<()> {
+        if_absent: bool,
+    ) -> StorageResult<ConditionalPutOutcome> {
         let bucket = &self.bucket;
-        aws_retry(&self.retry_params, || async {
-            self.put_single_part_single_try(bucket, key, payload.clone(), len)
-                .await
-        })
-        .await
-        .map_err(|error| error.into_inner())?;
-        Ok(())
+        let num_attempts = AtomicUsize::new(0);
+        let is_written = self
+            .retry("put_object", || async {
+                num_attempts.fetch_add(1, Ordering::Relaxed);
+                self.put_single_part_single_try(bucket, key, payload.clone(), len, if_absent)
+                    .await
+            })
+            .await
+            .map_err(|error| error.into_inner())?;
+        Ok(ConditionalPutOutcome::new(
+            is_written,
+            num_attempts.load(Ordering::Relaxed),
+        ))
     }
 
     async fn create_multipart_upload(&self, key: &str) -> StorageResult<MultipartUploadId> {
-        let upload_id = aws_retry(&self.retry_params, || async {
-            self.s3_client
-                .create_multipart_upload()
-                .bucket(self.bucket.clone())
-                .key(key)
-                .send()
-                .await
-        })
-        .await?
-        .upload_id
-        .ok_or_else(|| {
-            StorageErrorKind::Internal
-                .with_error(anyhow!("the returned multipart upload id was null"))
-        })?;
+        let upload_id = self
+            .retry("create_multipart_upload", || async {
+                let _permit = self.request_limiter.acquire().await;
+                send_request!(
+                    self,
+                    self.s3_client
+                        .create_multipart_upload()
+                        .bucket(self.bucket.clone())
+                        .key(key)
+                        .set_server_side_encryption(self.server_side_encryption())
+                        .set_ssekms_key_id(self.ssekms_key_id())
+                )
+            })
+            .await?
+            .upload_id
+            .ok_or_else(|| {
+                StorageErrorKind::Internal
+                    .with_error(anyhow!("the returned multipart upload id was null"))
+            })?;
         Ok(MultipartUploadId(upload_id))
     }
 
@@ -390,30 +669,33 @@ impl S3CompatibleObjectStorage {
             .map_err(StorageError::from)
             .map_err(Retry::Permanent)?;
         let md5 = BASE64_STANDARD.encode(part.md5.0);
+        // Each part holds its own permit: the parts of a multipart upload are uploaded
+        // concurrently and every one of them is a request to the object store.
+        let _permit = self.request_limiter.acquire().await;
         crate::STORAGE_METRICS.object_storage_put_parts.inc();
         crate::STORAGE_METRICS
             .object_storage_upload_num_bytes
             .inc_by(part.len());
 
-        let upload_part_output = self
-            .s3_client
-            .upload_part()
-            .bucket(self.bucket.clone())
-            .key(key)
-            .body(byte_stream)
-            .content_length(part.len() as i64)
-            .content_md5(md5)
-            .part_number(part.part_number as i32)
-            .upload_id(upload_id.0)
-            .send()
-            .await
-            .map_err(|s3_err| {
-                if s3_err.is_retryable() {
-                    Retry::Transient(StorageError::from(s3_err))
-                } else {
-                    Retry::Permanent(StorageError::from(s3_err))
-                }
-            })?;
+        let upload_part_output = send_request!(
+            self,
+            self.s3_client
+                .upload_part()
+                .bucket(self.bucket.clone())
+                .key(key)
+                .body(byte_stream)
+                .content_length(part.len() as i64)
+                .content_md5(md5)
+                .part_number(part.part_number as i32)
+                .upload_id(upload_id.0)
+        )
+        .map_err(|s3_err| {
+            if s3_err.is_retryable() {
+                Retry::Transient(StorageError::from(s3_err))
+            } else {
+                Retry::Permanent(StorageError::from(s3_err))
+            }
+        })?;
 
         let completed_part = CompletedPart::builder()
             .set_e_tag(upload_part_output.e_tag().map(|tag| tag.to_string()))
@@ -428,7 +710,8 @@ impl S3CompatibleObjectStorage {
         payload: Box<dyn crate::PutPayload>,
         part_len: u64,
         total_len: u64,
-    ) -> StorageResult<()> {
+        if_absent: bool,
+    ) -> StorageResult<ConditionalPutOutcome> {
         let upload_id = self.create_multipart_upload(key).await?;
         let parts = self
             .create_multipart_requests(payload.clone(), total_len, part_len)
@@ -438,7 +721,7 @@ impl S3CompatibleObjectStorage {
             stream::iter(parts.into_iter().map(|part| {
                 let payload = payload.clone();
                 let upload_id = upload_id.clone();
-                aws_retry(&self.retry_params, move || {
+                self.retry("upload_part", move || {
                     self.upload_part(upload_id.clone(), key, part.clone(), payload.clone())
                 })
             }))
@@ -450,8 +733,21 @@ impl S3CompatibleObjectStorage {
             .collect();
         match completed_parts_res {
             Ok(completed_parts) => {
-                self.complete_multipart_upload(key, completed_parts, &upload_id.0)
-                    .await
+                let outcome = self
+                    .complete_multipart_upload(key, completed_parts, &upload_id.0, if_absent)
+                    .await?;
+                if outcome != ConditionalPutOutcome::Written {
+                    // The uploaded parts are discarded. The upload is already gone if an earlier
+                    // attempt to complete it went through.
+                    if let Err(abort_error) = self.abort_multipart_upload(key, &upload_id.0).await {
+                        warn!(
+                            key = %key,
+                            error = ?abort_error,
+                            "Failed to abort multipart upload."
+                        );
+                    }
+                }
+                Ok(outcome)
             }
             Err(upload_error) => {
                 let abort_multipart_upload_res: StorageResult<()> =
@@ -473,38 +769,188 @@ impl S3CompatibleObjectStorage {
         key: &str,
         completed_parts: Vec<CompletedPart>,
         upload_id: &str,
-    ) -> StorageResult<()> {
+        if_absent: bool,
+    ) -> StorageResult<ConditionalPutOutcome> {
         let completed_upload = CompletedMultipartUpload::builder()
             .set_parts(Some(completed_parts))
             .build();
-        aws_retry(&self.retry_params, || async {
-            self.s3_client
-                .complete_multipart_upload()
-                .bucket(self.bucket.clone())
-                .key(key)
-                .multipart_upload(completed_upload.clone())
-                .upload_id(upload_id)
-                .send()
+        let num_attempts = AtomicUsize::new(0);
+        let complete_result = self
+            .retry("complete_multipart_upload", || async {
+                num_attempts.fetch_add(1, Ordering::Relaxed);
+                let _permit = self.request_limiter.acquire().await;
+                let complete_request = self
+                    .s3_client
+                    .complete_multipart_upload()
+                    .bucket(self.bucket.clone())
+                    .key(key)
+                    .multipart_upload(completed_upload.clone())
+                    .upload_id(upload_id);
+                if if_absent {
+                    self.apply_signing_policy(complete_request.customize().await?)
+                        .mutate_request(set_if_none_match_header)
+                        .send()
+                        .await
+                } else {
+                    send_request!(self, complete_request)
+                }
+            })
+            .await;
+        let is_written = match complete_result {
+            Ok(_) => true,
+            Err(sdk_error) if if_absent && is_precondition_failed(&sdk_error) => false,
+            Err(sdk_error) => return Err(sdk_error.into()),
+        };
+        Ok(ConditionalPutOutcome::new(
+            is_written,
+            num_attempts.load(Ordering::Relaxed),
+        ))
+    }
+
+    async fn put_inner(
+        &self,
+        path: &Path,
+        payload: Box<dyn crate::PutPayload>,
+        if_absent: bool,
+    ) -> StorageResult<ConditionalPutOutcome> {
+        crate::STORAGE_METRICS.object_storage_put_total.inc();
+        let key = self.key(path);
+        let total_len = payload.len();
+        let part_num_bytes = self.multipart_policy.part_num_bytes(total_len);
+        if self.disable_multipart_upload || part_num_bytes >= total_len {
+            let _permit = self.request_limiter.acquire().await;
+            self.put_single_part(&key, payload, total_len, if_absent)
                 .await
+        } else {
+            // The requests of the multipart upload acquire their own permits. Holding one here as
+            // well could deadlock once the limiter is saturated by concurrent multipart uploads.
+            self.put_multipart(&key, payload, part_num_bytes, total_len, if_absent)
+                .await
+        }
+    }
+
+    async fn abort_multipart_upload(&self, key: &str, upload_id: &str) -> StorageResult<()> {
+        self.retry("abort_multipart_upload", || async {
+            let _permit = self.request_limiter.acquire().await;
+            send_request!(
+                self,
+                self.s3_client
+                    .abort_multipart_upload()
+                    .bucket(self.bucket.clone())
+                    .key(key)
+                    .upload_id(upload_id)
+            )
         })
         .await?;
         Ok(())
     }
 
-    async fn abort_multipart_upload(&self, key: &str, upload_id: &str) -> StorageResult<()> {
-        aws_retry(&self.retry_params, || async {
-            self.s3_client
-                .abort_multipart_upload()
-                .bucket(self.bucket.clone())
-                .key(key)
-                .upload_id(upload_id)
-                .send()
-                .await
+    /// Returns the URL-encoded `bucket/key` identifying the source object of a copy.
+    fn copy_source(&self, key: &str) -> String {
+        let encoded_key = utf8_percent_encode(key, COPY_SOURCE_ENCODE_SET);
+        format!("{}/{encoded_key}", self.bucket)
+    }
+
+    async fn copy_single_part(&self, copy_source: &str, key: &str) -> StorageResult<()> {
+        self.retry("copy_object", || async {
+            send_request!(
+                self,
+                self.s3_client
+                    .copy_object()
+                    .bucket(self.bucket.clone())
+                    .key(key)
+                    .copy_source(copy_source)
+                    .set_server_side_encryption(self.server_side_encryption())
+                    .set_ssekms_key_id(self.ssekms_key_id())
+            )
         })
         .await?;
         Ok(())
     }
 
+    async fn upload_part_copy(
+        &self,
+        upload_id: &MultipartUploadId,
+        copy_source: &str,
+        key: &str,
+        part_number: usize,
+        range: Range<u64>,
+    ) -> StorageResult<CompletedPart> {
+        let copy_source_range = format!("bytes={}-{}", range.start, range.end - 1);
+        let upload_part_copy_output = self
+            .retry("upload_part_copy", || async {
+                let _permit = self.request_limiter.acquire().await;
+                send_request!(
+                    self,
+                    self.s3_client
+                        .upload_part_copy()
+                        .bucket(self.bucket.clone())
+                        .key(key)
+                        .copy_source(copy_source)
+                        .copy_source_range(&copy_source_range)
+                        .part_number(part_number as i32)
+                        .upload_id(&upload_id.0)
+                )
+            })
+            .await?;
+        let e_tag = upload_part_copy_output
+            .copy_part_result()
+            .and_then(|copy_part_result| copy_part_result.e_tag())
+            .map(|e_tag| e_tag.to_string());
+        let completed_part = CompletedPart::builder()
+            .set_e_tag(e_tag)
+            .part_number(part_number as i32)
+            .build();
+        Ok(completed_part)
+    }
+
+    /// Copies an object larger than [`MAX_SINGLE_COPY_NUM_BYTES`] with a multipart upload whose
+    /// parts are copied server-side from the source object.
+    async fn copy_multipart(
+        &self,
+        copy_source: &str,
+        key: &str,
+        part_len: u64,
+        total_len: u64,
+    ) -> StorageResult<()> {
+        let upload_id = self.create_multipart_upload(key).await?;
+        let max_concurrent_upload = self.multipart_policy.max_concurrent_uploads();
+        let upload_id_ref = &upload_id;
+        let completed_parts_res: StorageResult<Vec<CompletedPart>> = stream::iter(
+            chunk_range(0..total_len as usize, part_len as usize)
+                .map(into_u64_range)
+                .enumerate()
+                .map(|(part_idx, range)| {
+                    // parts are 1-indexed
+                    self.upload_part_copy(upload_id_ref, copy_source, key, part_idx + 1, range)
+                }),
+        )
+        .buffered(max_concurrent_upload)
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect();
+        match completed_parts_res {
+            Ok(completed_parts) => {
+                self.complete_multipart_upload(key, completed_parts, &upload_id.0, false)
+                    .await?;
+                Ok(())
+            }
+            Err(copy_error) => {
+                let abort_multipart_upload_res: StorageResult<()> =
+                    self.abort_multipart_upload(key, &upload_id.0).await;
+                if let Err(abort_error) = abort_multipart_upload_res {
+                    warn!(
+                        key = %key,
+                        error = ?abort_error,
+                        "Failed to abort multipart copy."
+                    );
+                }
+                Err(copy_error)
+            }
+        }
+    }
+
     async fn create_get_object_request(
         &self,
         path: &Path,
@@ -514,14 +960,14 @@ impl S3CompatibleObjectStorage {
         let range_str = range_opt.map(|range| format!("bytes={}-{}", range.start, range.end - 1));
         crate::STORAGE_METRICS.object_storage_get_total.inc();
 
-        let get_object_output = self
-            .s3_client
-            .get_object()
-            .bucket(self.bucket.clone())
-            .key(key)
-            .set_range(range_str)
-            .send()
-            .await?;
+        let get_object_output = send_request!(
+            self,
+            self.s3_client
+                .get_object()
+                .bucket(self.bucket.clone())
+                .key(key)
+                .set_range(range_str)
+        )?;
         Ok(get_object_output)
     }
 
@@ -531,10 +977,11 @@ impl S3CompatibleObjectStorage {
         range_opt: Option<Range<usize>>,
     ) -> StorageResult<Vec<u8>> {
         let cap = range_opt.as_ref().map(Range::len).unwrap_or(0);
-        let get_object_output = aws_retry(&self.retry_params, || {
-            self.create_get_object_request(path, range_opt.clone())
-        })
-        .await?;
+        let get_object_output = self
+            .retry("get_object", || {
+                self.create_get_object_request(path, range_opt.clone())
+            })
+            .await?;
         let mut buf: Vec<u8> = Vec::with_capacity(cap);
         download_all(get_object_output.body, &mut buf).await?;
         Ok(buf)
@@ -581,7 +1028,7 @@ impl S3CompatibleObjectStorage {
     /// Bulk delete implementation based on the DeleteObjects API, also called Multi-Object Delete
     /// API: <https://docs.aws.amazon.com/AmazonS3/latest/API/API_DeleteObjects.html>
     async fn bulk_delete_multi<'a>(&self, paths: &[&'a Path]) -> Result<(), BulkDeleteError> {
-        let _permit = REQUEST_SEMAPHORE.acquire().await;
+        let _permit = self.request_limiter.acquire().await;
         let mut error = None;
         let mut successes = Vec::with_capacity(paths.len());
         let mut failures = HashMap::new();
@@ -603,15 +1050,17 @@ impl S3CompatibleObjectStorage {
                 .map(|path| ObjectIdentifier::builder().key(self.key(path)).build())
                 .collect();
             let delete = Delete::builder().set_objects(Some(objects)).build();
-            let delete_objects_res = aws_retry(&self.retry_params, || async {
-                self.s3_client
-                    .delete_objects()
-                    .bucket(self.bucket.clone())
-                    .delete(delete.clone())
-                    .send()
-                    .await
-            })
-            .await;
+            let delete_objects_res = self
+                .retry("delete_objects", || async {
+                    send_request!(
+                        self,
+                        self.s3_client
+                            .delete_objects()
+                            .bucket(self.bucket.clone())
+                            .delete(delete.clone())
+                    )
+                })
+                .await;
 
             match delete_objects_res {
                 Ok(delete_objects_output) => {
@@ -675,17 +1124,66 @@ async fn download_all(byte_stream: ByteStream, output: &mut Vec<u8>) -> io::Resu
     Ok(())
 }
 
+/// Merges the byte ranges separated by at most `merge_gap` bytes, as long as the merged range does
+/// not exceed `max_merged_num_bytes`. Returns the merged ranges and, for each of the input ranges,
+/// the ordinal of the merged range containing it.
+fn merge_byte_ranges(
+    byte_ranges: &[Range<usize>],
+    merge_gap: usize,
+    max_merged_num_bytes: usize,
+) -> (Vec<Range<usize>>, Vec<usize>) {
+    let mut sorted_ordinals: Vec<usize> = (0..byte_ranges.len()).collect();
+    sorted_ordinals.sort_by_key(|&ordinal| byte_ranges[ordinal].start);
+
+    let mut merged_byte_ranges: Vec<Range<usize>> = Vec::new();
+    let mut merged_ordinals: Vec<usize> = vec![0; byte_ranges.len()];
+
+    for ordinal in sorted_ordinals {
+        let byte_range = &byte_ranges[ordinal];
+        match merged_byte_ranges.last_mut() {
+            Some(last_byte_range)
+                if byte_range.start <= last_byte_range.end.saturating_add(merge_gap)
+                    && last_byte_range.end.max(byte_range.end) - last_byte_range.start
+                        <= max_merged_num_bytes =>
+            {
+                last_byte_range.end = last_byte_range.end.max(byte_range.end);
+            }
+            _ => merged_byte_ranges.push(byte_range.clone()),
+        }
+        merged_ordinals[ordinal] = merged_byte_ranges.len() - 1;
+    }
+    (merged_byte_ranges, merged_ordinals)
+}
+
+/// Makes the write conditional on the absence of the object (`If-None-Match: *`).
+fn set_if_none_match_header(request: &mut hyper::http::Request<aws_smithy_http::body::SdkBody>) {
+    request
+        .headers_mut()
+        .insert(IF_NONE_MATCH, HeaderValue::from_static("*"));
+}
+
+/// Returns whether a conditional write was rejected because the object already exists.
+fn is_precondition_failed<E>(sdk_error: &SdkError<E>) -> bool {
+    let status_opt = match sdk_error {
+        SdkError::ServiceError(service_error) => Some(service_error.raw().http().status()),
+        SdkError::ResponseError(response_error) => Some(response_error.raw().http().status()),
+        _ => None,
+    };
+    status_opt == Some(StatusCode::PRECONDITION_FAILED)
+}
+
 #[async_trait]
 impl Storage for S3CompatibleObjectStorage {
     async fn check_connectivity(&self) -> anyhow::Result<()> {
         // we ignore error as we never close the semaphore
-        let _permit = REQUEST_SEMAPHORE.acquire().await;
-        self.s3_client
-            .list_objects_v2()
-            .bucket(self.bucket.clone())
-            .max_keys(1)
-            .send()
-            .await?;
+        let _permit = self.request_limiter.acquire().await;
+        send_request!(
+            self,
+            self.s3_client
+                .list_objects_v2()
+                .bucket(self.bucket.clone())
+                .max_keys(1)
+        )?;
         Ok(())
     }
 
@@ -694,26 +1192,47 @@ impl Storage for S3CompatibleObjectStorage {
         path: &Path,
         payload: Box<dyn crate::PutPayload>,
     ) -> crate::StorageResult<()> {
-        crate::STORAGE_METRICS.object_storage_put_total.inc();
-        let _permit = REQUEST_SEMAPHORE.acquire().await;
-        let key = self.key(path);
-        let total_len = payload.len();
-        let part_num_bytes = self.multipart_policy.part_num_bytes(total_len);
-        if self.disable_multipart_upload || part_num_bytes >= total_len {
-            self.put_single_part(&key, payload, total_len).await?;
+        self.put_inner(path, payload, false).await?;
+        Ok(())
+    }
+
+    async fn put_if_absent(
+        &self,
+        path: &Path,
+        payload: Box<dyn crate::PutPayload>,
+    ) -> crate::StorageResult<bool> {
+        let payload_len = payload.len();
+        let outcome = self.put_inner(path, payload, true).await?;
+        resolve_conditional_put(self, path, payload_len, outcome).await
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> StorageResult<()> {
+        let total_len = self.file_num_bytes(from).await?;
+        if total_len > MAX_SINGLE_COPY_NUM_BYTES && self.disable_multipart_upload {
+            // Without multipart uploads, large objects can only be copied through the client.
+            return crate::storage::default_copy(self, from, to).await;
+        }
+        let copy_source = self.copy_source(&self.key(from));
+        let key = self.key(to);
+        if total_len <= MAX_SINGLE_COPY_NUM_BYTES {
+            let _permit = self.request_limiter.acquire().await;
+            self.copy_single_part(&copy_source, &key).await
         } else {
-            self.put_multipart(&key, payload, part_num_bytes, total_len)
-                .await?;
+            // Like multipart uploads, the part copies acquire their own permits.
+            let part_num_bytes = self
+                .multipart_policy
+                .part_num_bytes(total_len)
+                .min(MAX_SINGLE_COPY_NUM_BYTES);
+            self.copy_multipart(&copy_source, &key, part_num_bytes, total_len)
+                .await
         }
-        Ok(())
     }
 
     async fn copy_to(&self, path: &Path, output: &mut dyn SendableAsync) -> StorageResult<()> {
-        let _permit = REQUEST_SEMAPHORE.acquire().await;
-        let get_object_output = aws_retry(&self.retry_params, || {
-            self.create_get_object_request(path, None)
-        })
-        .await?;
+        let _permit = self.request_limiter.acquire().await;
+        let get_object_output = self
+            .retry("get_object", || self.create_get_object_request(path, None))
+            .await?;
         let mut body_read = BufReader::new(get_object_output.body.into_async_read());
         let num_bytes_copied = tokio::io::copy_buf(&mut body_read, output).await?;
         STORAGE_METRICS
@@ -724,18 +1243,17 @@ impl Storage for S3CompatibleObjectStorage {
     }
 
     async fn delete(&self, path: &Path) -> StorageResult<()> {
-        let _permit = REQUEST_SEMAPHORE.acquire().await;
+        let _permit = self.request_limiter.acquire().await;
         let bucket = self.bucket.clone();
         let key = self.key(path);
-        let delete_res = aws_retry(&self.retry_params, || async {
-            self.s3_client
-                .delete_object()
-                .bucket(&bucket)
-                .key(&key)
-                .send()
-                .await
-        })
-        .await;
+        let delete_res = self
+            .retry("delete_object", || async {
+                send_request!(
+                    self,
+                    self.s3_client.delete_object().bucket(&bucket).key(&key)
+                )
+            })
+            .await;
 
         match delete_res {
             Ok(_) => Ok(()),
@@ -754,7 +1272,7 @@ impl Storage for S3CompatibleObjectStorage {
 
     #[instrument(level = "debug", skip(self, range), fields(range.start = range.start, range.end = range.end))]
     async fn get_slice(&self, path: &Path, range: Range<usize>) -> StorageResult<OwnedBytes> {
-        let _permit = REQUEST_SEMAPHORE.acquire().await;
+        let _permit = self.request_limiter.acquire().await;
         self.get_to_vec(path, Some(range.clone()))
             .await
             .map(OwnedBytes::new)
@@ -768,26 +1286,53 @@ impl Storage for S3CompatibleObjectStorage {
             })
     }
 
+    #[instrument(level = "debug", skip(self, ranges), fields(num_ranges = ranges.len()))]
+    async fn get_slices(
+        &self,
+        path: &Path,
+        ranges: &[Range<usize>],
+    ) -> StorageResult<Vec<OwnedBytes>> {
+        let (merged_ranges, merged_ordinals) =
+            merge_byte_ranges(ranges, self.range_merge_gap, MAX_MERGED_RANGE_NUM_BYTES);
+        let merged_slices: Vec<OwnedBytes> = future::try_join_all(
+            merged_ranges
+                .iter()
+                .map(|merged_range| self.get_slice(path, merged_range.clone())),
+        )
+        .await?;
+        let slices = ranges
+            .iter()
+            .zip(merged_ordinals)
+            .map(|(range, merged_ordinal)| {
+                let merged_range_start = merged_ranges[merged_ordinal].start;
+                merged_slices[merged_ordinal]
+                    .slice(range.start - merged_range_start..range.end - merged_range_start)
+            })
+            .collect();
+        Ok(slices)
+    }
+
     #[instrument(level = "debug", skip(self, range), fields(range.start = range.start, range.end = range.end))]
     async fn get_slice_stream(
         &self,
         path: &Path,
         range: Range<usize>,
     ) -> crate::StorageResult<Box<dyn AsyncRead + Send + Unpin>> {
-        let permit = REQUEST_SEMAPHORE.acquire().await;
-        let get_object_output = aws_retry(&self.retry_params, || {
-            self.create_get_object_request(path, Some(range.clone()))
-        })
-        .await?;
-        Ok(Box::new(S3AsyncRead {
-            read: get_object_output.body.into_async_read(),
-            _permit: permit,
-        }))
+        let permit = self.request_limiter.acquire().await;
+        let get_object_output = self
+            .retry("get_object", || {
+                self.create_get_object_request(path, Some(range.clone()))
+            })
+            .await?;
+        Ok(Box::new(PermitAsyncRead::new(
+            get_object_output.body.into_async_read(),
+            permit,
+        )))
     }
 
     #[instrument(level = "debug", skip(self), fields(num_bytes_fetched))]
     async fn get_all(&self, path: &Path) -> StorageResult<OwnedBytes> {
-        let _permit = REQUEST_SEMAPHORE.acquire().await;
+        let _permit = self.request_limiter.acquire().await;
         let bytes = self
             .get_to_vec(path, None)
             .await
@@ -804,22 +1349,55 @@ impl Storage for S3CompatibleObjectStorage {
     }
 
     async fn file_num_bytes(&self, path: &Path) -> StorageResult<u64> {
-        let _permit = REQUEST_SEMAPHORE.acquire().await;
+        let _permit = self.request_limiter.acquire().await;
         let bucket = self.bucket.clone();
         let key = self.key(path);
-        let head_object_output = aws_retry(&self.retry_params, || async {
-            self.s3_client
-                .head_object()
-                .bucket(&bucket)
-                .key(&key)
-                .send()
-                .await
-        })
-        .await?;
+        let head_object_output = self
+            .retry("head_object", || async {
+                send_request!(self, self.s3_client.head_object().bucket(&bucket).key(&key))
+            })
+            .await?;
 
         Ok(head_object_output.content_length() as u64)
     }
 
+    async fn list(&self, prefix: &Path) -> StorageResult<Vec<PathBuf>> {
+        let bucket = self.bucket.clone();
+        let key_prefix = self.key(prefix);
+        let mut paths = Vec::new();
+        let mut continuation_token_opt: Option<String> = None;
+
+        loop {
+            let _permit = self.request_limiter.acquire().await;
+            let list_objects_output = self
+                .retry("list_objects", || async {
+                    send_request!(
+                        self,
+                        self.s3_client
+                            .list_objects_v2()
+                            .bucket(&bucket)
+                            .prefix(&key_prefix)
+                            .set_continuation_token(continuation_token_opt.clone())
+                    )
+                })
+                .await?;
+
+            for object in list_objects_output.contents().unwrap_or_default() {
+                if let Some(key) = object.key() {
+                    paths.push(self.relative_path(key));
+                }
+            }
+            continuation_token_opt = list_objects_output
+                .next_continuation_token()
+                .map(ToString::to_string);
+
+            if continuation_token_opt.is_none() {
+                break;
+            }
+        }
+        Ok(paths)
+    }
+
     fn uri(&self) -> &Uri {
         &self.uri
     }
@@ -828,8 +1406,10 @@ impl Storage for S3CompatibleObjectStorage {
 #[cfg(test)]
 mod tests {
 
+    use std::collections::BTreeMap;
     use std::path::PathBuf;
 
+    use aws_config::retry::RetryConfig;
     use aws_sdk_s3::config::{Credentials, Region};
     use aws_sdk_s3::primitives::SdkBody;
     use aws_smithy_client::test_connection::TestConnection;
@@ -837,6 +1417,7 @@ mod tests {
     use hyper::{http, Body};
     use quickwit_common::chunk_range;
     use quickwit_common::uri::Uri;
+    use quickwit_config::S3ServerSideEncryptionAlgorithm;
 
     use super::*;
     use crate::{MultiPartPolicy, S3CompatibleObjectStorage};
@@ -910,8 +1491,12 @@ mod tests {
             prefix,
             multipart_policy: MultiPartPolicy::default(),
             retry_params: RetryParams::default(),
+            range_merge_gap: DEFAULT_RANGE_MERGE_GAP,
+            server_side_encryption_opt: None,
             disable_multi_object_delete: false,
             disable_multipart_upload: false,
+            request_limiter: StorageRequestLimiter::new(10),
+            anonymous: false,
         };
         assert_eq!(
             s3_storage.relative_path("indexes/foo"),
@@ -926,6 +1511,557 @@ mod tests {
         );
     }
 
+    async fn get_object_with_test_connection(
+        s3_storage_config: &S3StorageConfig,
+        status: u16,
+        body: &'static str,
+    ) -> (StorageResult<OwnedBytes>, TestConnection<SdkBody>) {
+        let client = TestConnection::new(vec![(
+            http::Request::builder()
+                .body(SdkBody::from(Body::empty()))
+                .unwrap(),
+            http::Response::builder()
+                .status(status)
+                .body(SdkBody::from(Body::from(body)))
+                .unwrap(),
+        )]);
+        let default_credentials_provider = SharedCredentialsProvider::new(Credentials::new(
+            "mock_key",
+            "mock_secret",
+            None,
+            None,
+            "mock_provider",
+        ));
+        let credentials_provider =
+            get_credentials_provider(s3_storage_config, Some(&default_credentials_provider));
+        let mut config = aws_sdk_s3::Config::builder()
+            .region(Some(Region::new("Foo")))
+            .http_connector(client.clone());
+        config.set_credentials_provider(credentials_provider);
+        set_endpoint_and_addressing_style(&mut config, s3_storage_config);
+        let s3_client = S3Client::from_conf(config.build());
+
+        let s3_storage = S3CompatibleObjectStorage {
+            s3_client,
+            uri: Uri::for_test("s3://public-bucket/indexes"),
+            bucket: "public-bucket".to_string(),
+            prefix: PathBuf::from("indexes"),
+            multipart_policy: MultiPartPolicy::default(),
+            retry_params: RetryParams::default(),
+            range_merge_gap: DEFAULT_RANGE_MERGE_GAP,
+            server_side_encryption_opt: None,
+            disable_multi_object_delete: false,
+            disable_multipart_upload: false,
+            request_limiter: StorageRequestLimiter::new(10),
+            anonymous: s3_storage_config.anonymous,
+        };
+        let get_result = s3_storage.get_all(Path::new("foo.split")).await;
+        (get_result, client)
+    }
+
+    #[tokio::test]
+    async fn test_s3_compatible_storage_anonymous_requests_are_unsigned() {
+        let s3_storage_config = S3StorageConfig {
+            anonymous: true,
+            ..Default::default()
+        };
+        let (get_result, client) =
+            get_object_with_test_connection(&s3_storage_config, 200, "abc").await;
+        assert_eq!(get_result.unwrap().as_slice(), b"abc");
+
+        let requests = client.requests();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].actual.headers().get("authorization").is_none());
+
+        // Requests are signed otherwise.
+        let s3_storage_config = S3StorageConfig::default();
+        let (get_result, client) =
+            get_object_with_test_connection(&s3_storage_config, 200, "abc").await;
+        get_result.unwrap();
+
+        let requests = client.requests();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].actual.headers().get("authorization").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_s3_compatible_storage_addressing_style_with_custom_endpoint() {
+        let mut s3_storage_config = S3StorageConfig {
+            endpoint: Some("http://minio.local:9000".to_string()),
+            force_path_style_access: Some(true),
+            ..Default::default()
+        };
+        let (get_result, client) =
+            get_object_with_test_connection(&s3_storage_config, 200, "abc").await;
+        get_result.unwrap();
+
+        let requests = client.requests();
+        let request_uri = requests[0].actual.uri();
+        assert_eq!(request_uri.host(), Some("minio.local"));
+        assert_eq!(request_uri.path(), "/public-bucket/indexes/foo.split");
+
+        s3_storage_config.force_path_style_access = Some(false);
+        let (get_result, client) =
+            get_object_with_test_connection(&s3_storage_config, 200, "abc").await;
+        get_result.unwrap();
+
+        let requests = client.requests();
+        let request_uri = requests[0].actual.uri();
+        assert_eq!(request_uri.host(), Some("public-bucket.minio.local"));
+        assert_eq!(request_uri.path(), "/indexes/foo.split");
+
+        // The addressing style is inferred from the bucket name when unset.
+        s3_storage_config.force_path_style_access = None;
+        let (get_result, client) =
+            get_object_with_test_connection(&s3_storage_config, 200, "abc").await;
+        get_result.unwrap();
+
+        let requests = client.requests();
+        let request_uri = requests[0].actual.uri();
+        assert_eq!(request_uri.host(), Some("public-bucket.minio.local"));
+        assert_eq!(request_uri.path(), "/indexes/foo.split");
+    }
+
+    #[tokio::test]
+    async fn test_s3_compatible_storage_anonymous_private_bucket() {
+        let s3_storage_config = S3StorageConfig {
+            anonymous: true,
+            ..Default::default()
+        };
+        let (get_result, _client) = get_object_with_test_connection(
+            &s3_storage_config,
+            403,
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+            <Error>
+                <Code>AccessDenied</Code>
+                <Message>Access Denied</Message>
+            </Error>"#,
+        )
+        .await;
+        assert_eq!(
+            get_result.unwrap_err().kind(),
+            StorageErrorKind::Unauthorized
+        );
+    }
+
+    #[tokio::test]
+    async fn test_s3_compatible_storage_user_agent_and_request_headers() {
+        let client = TestConnection::new(vec![(
+            http::Request::builder()
+                .body(SdkBody::from(Body::empty()))
+                .unwrap(),
+            http::Response::builder()
+                .body(SdkBody::from(Body::from("hello")))
+                .unwrap(),
+        )]);
+        let s3_storage_config = S3StorageConfig {
+            user_agent: Some("quickwit-node-1".to_string()),
+            request_headers: BTreeMap::from([(
+                "x-quickwit-node-id".to_string(),
+                "node-1".to_string(),
+            )]),
+            ..Default::default()
+        };
+        let connector =
+            RequestHeadersConnector::new(DynConnector::new(client.clone()), &s3_storage_config);
+        let credentials = Credentials::new("mock_key", "mock_secret", None, None, "mock_provider");
+        let config = aws_sdk_s3::Config::builder()
+            .region(Some(Region::new("Foo")))
+            .app_name(get_app_name(&s3_storage_config))
+            .http_connector(DynConnector::new(connector))
+            .credentials_provider(credentials)
+            .build();
+        let s3_client = S3Client::from_conf(config);
+
+        let s3_storage = S3CompatibleObjectStorage {
+            s3_client,
+            uri: Uri::for_test("s3://bucket/indexes"),
+            bucket: "bucket".to_string(),
+            prefix: PathBuf::from("indexes"),
+            multipart_policy: MultiPartPolicy::default(),
+            retry_params: RetryParams::default(),
+            range_merge_gap: DEFAULT_RANGE_MERGE_GAP,
+            server_side_encryption_opt: None,
+            disable_multi_object_delete: false,
+            disable_multipart_upload: false,
+            request_limiter: StorageRequestLimiter::new(10),
+            anonymous: false,
+        };
+        let payload = s3_storage.get_all(Path::new("foo.split")).await.unwrap();
+        assert_eq!(payload.as_slice(), b"hello");
+
+        let requests = client.requests();
+        assert_eq!(requests.len(), 1);
+
+        let headers = requests[0].actual.headers();
+        let user_agent = headers.get("user-agent").unwrap().to_str().unwrap();
+        assert!(user_agent.starts_with("aws-sdk-rust"));
+        assert!(user_agent.ends_with(" quickwit-node-1"));
+        assert_eq!(headers.get("x-quickwit-node-id").unwrap(), "node-1");
+    }
+
+    #[tokio::test]
+    async fn test_s3_compatible_storage_retries_transient_errors() {
+        let client = TestConnection::new(vec![
+            (
+                http::Request::builder()
+                    .body(SdkBody::from(Body::empty()))
+                    .unwrap(),
+                http::Response::builder()
+                    .status(503)
+                    .body(SdkBody::from(Body::from(
+                        r#"<?xml version="1.0" encoding="UTF-8"?>
+                        <Error>
+                            <Code>SlowDown</Code>
+                            <Message>Please reduce your request rate.</Message>
+                        </Error>"#,
+                    )))
+                    .unwrap(),
+            ),
+            (
+                http::Request::builder()
+                    .body(SdkBody::from(Body::empty()))
+                    .unwrap(),
+                http::Response::builder()
+                    .body(SdkBody::from(Body::from("hello")))
+                    .unwrap(),
+            ),
+            (
+                http::Request::builder()
+                    .body(SdkBody::from(Body::empty()))
+                    .unwrap(),
+                http::Response::builder()
+                    .status(404)
+                    .body(SdkBody::from(Body::from(
+                        r#"<?xml version="1.0" encoding="UTF-8"?>
+                        <Error>
+                            <Code>NoSuchKey</Code>
+                            <Message>The specified key does not exist.</Message>
+                        </Error>"#,
+                    )))
+                    .unwrap(),
+            ),
+        ]);
+        let credentials = Credentials::new("mock_key", "mock_secret", None, None, "mock_provider");
+        let config = aws_sdk_s3::Config::builder()
+            .region(Some(Region::new("Foo")))
+            .retry_config(RetryConfig::disabled())
+            .http_connector(client.clone())
+            .credentials_provider(credentials)
+            .build();
+        let s3_client = S3Client::from_conf(config);
+
+        let s3_storage = S3CompatibleObjectStorage {
+            s3_client,
+            uri: Uri::for_test("s3://bucket/indexes"),
+            bucket: "bucket".to_string(),
+            prefix: PathBuf::from("indexes"),
+            multipart_policy: MultiPartPolicy::default(),
+            retry_params: RetryParams::for_test(),
+            range_merge_gap: DEFAULT_RANGE_MERGE_GAP,
+            server_side_encryption_opt: None,
+            disable_multi_object_delete: false,
+            disable_multipart_upload: false,
+            request_limiter: StorageRequestLimiter::new(10),
+            anonymous: false,
+        };
+        let num_retries_before = STORAGE_METRICS
+            .object_storage_request_retries_total
+            .with_label_values(["get_object"])
+            .get();
+
+        // The throttled request is retried.
+        let payload = s3_storage.get_all(Path::new("foo.split")).await.unwrap();
+        assert_eq!(payload.as_slice(), b"hello");
+        assert_eq!(client.requests().len(), 2);
+
+        let num_retries_after = STORAGE_METRICS
+            .object_storage_request_retries_total
+            .with_label_values(["get_object"])
+            .get();
+        assert!(num_retries_after > num_retries_before);
+
+        // The missing object is not.
+        let error = s3_storage
+            .get_all(Path::new("bar.split"))
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), StorageErrorKind::NotFound);
+        assert_eq!(client.requests().len(), 3);
+    }
+
+    #[test]
+    fn test_merge_byte_ranges() {
+        let (merged_ranges, merged_ordinals) = merge_byte_ranges(&[], 10, 100);
+        assert!(merged_ranges.is_empty());
+        assert!(merged_ordinals.is_empty());
+
+        let (merged_ranges, merged_ordinals) =
+            merge_byte_ranges(&[100..110, 0..10, 15..20, 5..8, 121..130], 10, 100);
+        assert_eq!(merged_ranges, vec![0..20, 100..110, 121..130]);
+        assert_eq!(merged_ordinals, vec![1, 0, 0, 0, 2]);
+
+        let (merged_ranges, merged_ordinals) = merge_byte_ranges(&[0..10, 10..20, 21..30], 0, 100);
+        assert_eq!(merged_ranges, vec![0..20, 21..30]);
+        assert_eq!(merged_ordinals, vec![0, 0, 1]);
+
+        // Merged ranges are capped, but a single range larger than the cap is kept as is.
+        let (merged_ranges, merged_ordinals) =
+            merge_byte_ranges(&[0..10, 10..20, 20..30, 30..100], 0, 20);
+        assert_eq!(merged_ranges, vec![0..20, 20..30, 30..100]);
+        assert_eq!(merged_ordinals, vec![0, 0, 1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_s3_compatible_storage_get_slices_merges_nearby_ranges() {
+        let client = TestConnection::new(vec![(
+            http::Request::builder()
+                .body(SdkBody::from(Body::empty()))
+                .unwrap(),
+            http::Response::builder()
+                .body(SdkBody::from(Body::from("0123456789")))
+                .unwrap(),
+        )]);
+        let credentials = Credentials::new("mock_key", "mock_secret", None, None, "mock_provider");
+        let config = aws_sdk_s3::Config::builder()
+            .region(Some(Region::new("Foo")))
+            .http_connector(client.clone())
+            .credentials_provider(credentials)
+            .build();
+        let s3_client = S3Client::from_conf(config);
+
+        let s3_storage = S3CompatibleObjectStorage {
+            s3_client,
+            uri: Uri::for_test("s3://bucket/indexes"),
+            bucket: "bucket".to_string(),
+            prefix: PathBuf::from("indexes"),
+            multipart_policy: MultiPartPolicy::default(),
+            retry_params: RetryParams::default(),
+            range_merge_gap: 2,
+            server_side_encryption_opt: None,
+            disable_multi_object_delete: false,
+            disable_multipart_upload: false,
+            request_limiter: StorageRequestLimiter::new(10),
+            anonymous: false,
+        };
+        let slices = s3_storage
+            .get_slices(Path::new("foo.split"), &[6..10, 0..4, 2..5])
+            .await
+            .unwrap();
+        assert_eq!(slices.len(), 3);
+        assert_eq!(slices[0].as_slice(), b"6789");
+        assert_eq!(slices[1].as_slice(), b"0123");
+        assert_eq!(slices[2].as_slice(), b"234");
+
+        let requests = client.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(
+            requests[0].actual.headers().get("range").unwrap(),
+            "bytes=0-9"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_s3_compatible_storage_server_side_encryption_headers() {
+        let client = TestConnection::new(vec![
+            (
+                http::Request::builder()
+                    .body(SdkBody::from(Body::empty()))
+                    .unwrap(),
+                http::Response::builder()
+                    .body(SdkBody::from(Body::empty()))
+                    .unwrap(),
+            ),
+            (
+                http::Request::builder()
+                    .body(SdkBody::from(Body::empty()))
+                    .unwrap(),
+                http::Response::builder()
+                    .body(SdkBody::from(Body::from(
+                        "<InitiateMultipartUploadResult><Bucket>bucket</Bucket><Key>indexes/foo.\
+                         split</Key><UploadId>upload-id</UploadId></InitiateMultipartUploadResult>",
+                    )))
+                    .unwrap(),
+            ),
+        ]);
+        let credentials = Credentials::new("mock_key", "mock_secret", None, None, "mock_provider");
+        let config = aws_sdk_s3::Config::builder()
+            .region(Some(Region::new("Foo")))
+            .http_connector(client.clone())
+            .credentials_provider(credentials)
+            .build();
+        let s3_client = S3Client::from_conf(config);
+
+        let s3_storage = S3CompatibleObjectStorage {
+            s3_client,
+            uri: Uri::for_test("s3://bucket/indexes"),
+            bucket: "bucket".to_string(),
+            prefix: PathBuf::from("indexes"),
+            multipart_policy: MultiPartPolicy::default(),
+            retry_params: RetryParams::default(),
+            range_merge_gap: DEFAULT_RANGE_MERGE_GAP,
+            server_side_encryption_opt: Some(S3ServerSideEncryptionConfig {
+                algorithm: S3ServerSideEncryptionAlgorithm::AwsKms,
+                kms_key_id: Some("arn:aws:kms:us-east-1:123456789012:key/my-key".to_string()),
+            }),
+            disable_multi_object_delete: false,
+            disable_multipart_upload: false,
+            request_limiter: StorageRequestLimiter::new(10),
+            anonymous: false,
+        };
+        s3_storage
+            .put(Path::new("foo.split"), Box::new(b"hello".to_vec()))
+            .await
+            .unwrap();
+        let upload_id = s3_storage
+            .create_multipart_upload("indexes/foo.split")
+            .await
+            .unwrap();
+        assert_eq!(upload_id.0, "upload-id");
+
+        let requests = client.requests();
+        assert_eq!(requests.len(), 2);
+
+        for request in requests.iter() {
+            let headers = request.actual.headers();
+            assert_eq!(
+                headers.get("x-amz-server-side-encryption").unwrap(),
+                "aws:kms"
+            );
+            assert_eq!(
+                headers
+                    .get("x-amz-server-side-encryption-aws-kms-key-id")
+                    .unwrap(),
+                "arn:aws:kms:us-east-1:123456789012:key/my-key"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_s3_compatible_storage_copy() {
+        let head_object_response = |content_length: u64| {
+            http::Response::builder()
+                .header("Content-Length", content_length)
+                .body(SdkBody::from(Body::empty()))
+                .unwrap()
+        };
+        let response = |body: &'static str| {
+            http::Response::builder()
+                .body(SdkBody::from(Body::from(body)))
+                .unwrap()
+        };
+        let empty_request = || {
+            http::Request::builder()
+                .body(SdkBody::from(Body::empty()))
+                .unwrap()
+        };
+        let six_gib = 6 * 1024 * 1024 * 1024;
+        let client = TestConnection::new(vec![
+            (empty_request(), head_object_response(5)),
+            (
+                empty_request(),
+                response("<CopyObjectResult><ETag>\"etag\"</ETag></CopyObjectResult>"),
+            ),
+            (empty_request(), head_object_response(six_gib)),
+            (
+                empty_request(),
+                response(
+                    "<InitiateMultipartUploadResult><Bucket>bucket</Bucket><Key>indexes/bar.\
+                     split</Key><UploadId>upload-id</UploadId></InitiateMultipartUploadResult>",
+                ),
+            ),
+            (
+                empty_request(),
+                response("<CopyPartResult><ETag>\"etag-part\"</ETag></CopyPartResult>"),
+            ),
+            (
+                empty_request(),
+                response("<CopyPartResult><ETag>\"etag-part\"</ETag></CopyPartResult>"),
+            ),
+            (
+                empty_request(),
+                response(
+                    "<CompleteMultipartUploadResult><Bucket>bucket</Bucket><Key>indexes/bar.\
+                     split</Key><ETag>\"etag\"</ETag></CompleteMultipartUploadResult>",
+                ),
+            ),
+        ]);
+        let credentials = Credentials::new("mock_key", "mock_secret", None, None, "mock_provider");
+        let config = aws_sdk_s3::Config::builder()
+            .region(Some(Region::new("Foo")))
+            .http_connector(client.clone())
+            .credentials_provider(credentials)
+            .build();
+        let s3_client = S3Client::from_conf(config);
+        let s3_storage = S3CompatibleObjectStorage {
+            s3_client,
+            uri: Uri::for_test("s3://bucket/indexes"),
+            bucket: "bucket".to_string(),
+            prefix: PathBuf::from("indexes"),
+            multipart_policy: MultiPartPolicy::default(),
+            retry_params: RetryParams::default(),
+            range_merge_gap: DEFAULT_RANGE_MERGE_GAP,
+            server_side_encryption_opt: None,
+            disable_multi_object_delete: false,
+            disable_multipart_upload: false,
+            request_limiter: StorageRequestLimiter::new(10),
+            anonymous: false,
+        };
+        // Small objects are copied with a single `CopyObject` request.
+        s3_storage
+            .copy(Path::new("foo bar.split"), Path::new("bar.split"))
+            .await
+            .unwrap();
+        // Objects larger than 5GiB are copied part by part.
+        s3_storage
+            .copy(Path::new("foo bar.split"), Path::new("bar.split"))
+            .await
+            .unwrap();
+
+        let requests = client.requests();
+        assert_eq!(requests.len(), 7);
+
+        let copy_object_request = &requests[1].actual;
+        assert_eq!(copy_object_request.method(), http::Method::PUT);
+        assert!(copy_object_request
+            .uri()
+            .path()
+            .ends_with("indexes/bar.split"));
+        assert_eq!(
+            copy_object_request
+                .headers()
+                .get("x-amz-copy-source")
+                .unwrap(),
+            "bucket/indexes/foo%20bar.split"
+        );
+        assert!(copy_object_request
+            .headers()
+            .get("x-amz-copy-source-range")
+            .is_none());
+
+        let mut copy_source_ranges: Vec<&str> = requests[4..6]
+            .iter()
+            .map(|request| {
+                let headers = request.actual.headers();
+                assert_eq!(
+                    headers.get("x-amz-copy-source").unwrap(),
+                    "bucket/indexes/foo%20bar.split"
+                );
+                headers
+                    .get("x-amz-copy-source-range")
+                    .unwrap()
+                    .to_str()
+                    .unwrap()
+            })
+            .collect();
+        copy_source_ranges.sort_unstable();
+        let last_copy_source_range = format!("bytes=5000000000-{}", six_gib - 1);
+        assert_eq!(
+            copy_source_ranges,
+            ["bytes=0-4999999999", last_copy_source_range.as_str()]
+        );
+    }
+
     #[tokio::test]
     async fn test_s3_compatible_storage_bulk_delete_single() {
         let client = TestConnection::new(vec![
@@ -964,8 +2100,12 @@ mod tests {
             prefix,
             multipart_policy: MultiPartPolicy::default(),
             retry_params: RetryParams::default(),
+            range_merge_gap: DEFAULT_RANGE_MERGE_GAP,
+            server_side_encryption_opt: None,
             disable_multi_object_delete: true,
             disable_multipart_upload: false,
+            request_limiter: StorageRequestLimiter::new(10),
+            anonymous: false,
         };
         let _ = s3_storage
             .bulk_delete(&[Path::new("foo"), Path::new("bar")])
@@ -1008,8 +2148,12 @@ mod tests {
             prefix,
             multipart_policy: MultiPartPolicy::default(),
             retry_params: RetryParams::default(),
+            range_merge_gap: DEFAULT_RANGE_MERGE_GAP,
+            server_side_encryption_opt: None,
             disable_multi_object_delete: false,
             disable_multipart_upload: false,
+            request_limiter: StorageRequestLimiter::new(10),
+            anonymous: false,
         };
         let _ = s3_storage
             .bulk_delete(&[Path::new("foo"), Path::new("bar")])
@@ -1093,8 +2237,12 @@ mod tests {
             prefix,
             multipart_policy: MultiPartPolicy::default(),
             retry_params: RetryParams::default(),
+            range_merge_gap: DEFAULT_RANGE_MERGE_GAP,
+            server_side_encryption_opt: None,
             disable_multi_object_delete: false,
             disable_multipart_upload: false,
+            request_limiter: StorageRequestLimiter::new(10),
+            anonymous: false,
         };
         let bulk_delete_error = s3_storage
             .bulk_delete(&[
@@ -21,21 +21,43 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use quickwit_common::uri::Uri;
-use quickwit_config::{S3StorageConfig, StorageBackend};
+use quickwit_config::{IndexStorageConfig, S3StorageConfig, StorageBackend};
 
+use super::s3_compatible_storage::request_limiter_for_config;
 use crate::{
-    DebouncedStorage, S3CompatibleObjectStorage, Storage, StorageFactory, StorageResolverError,
+    DebouncedStorage, S3CompatibleObjectStorage, Storage, StorageFactory, StorageRequestLimiter,
+    StorageResolverError,
 };
 
 /// S3 compatible object storage resolver.
+#[derive(Clone)]
 pub struct S3CompatibleObjectStorageFactory {
     storage_config: S3StorageConfig,
+    // Shared by all the storages resolved by this factory, so that the number of concurrent
+    // requests is bounded node-wide.
+    request_limiter: StorageRequestLimiter,
 }
 
 impl S3CompatibleObjectStorageFactory {
     /// Creates a new S3-compatible storage factory.
     pub fn new(storage_config: S3StorageConfig) -> Self {
-        Self { storage_config }
+        let request_limiter = request_limiter_for_config(&storage_config);
+        Self {
+            storage_config,
+            request_limiter,
+        }
+    }
+
+    /// Returns a factory resolving the URIs of an index with the storage config of the index
+    /// applied on top of the storage config of this factory. Both factories share the same
+    /// request limiter.
+    pub fn with_index_storage_config(&self, index_storage_config: &IndexStorageConfig) -> Self {
+        Self {
+            storage_config: self
+                .storage_config
+                .with_index_storage_config(index_storage_config),
+            request_limiter: self.request_limiter.clone(),
+        }
     }
 }
 
@@ -46,7 +68,9 @@ impl StorageFactory for S3CompatibleObjectStorageFactory {
     }
 
     async fn resolve(&self, uri: &Uri) -> Result<Arc<dyn Storage>, StorageResolverError> {
-        let storage = S3CompatibleObjectStorage::from_uri(&self.storage_config, uri).await?;
+        let storage = S3CompatibleObjectStorage::from_uri(&self.storage_config, uri)
+            .await?
+            .with_request_limiter(self.request_limiter.clone());
         Ok(Arc::new(DebouncedStorage::new(storage)))
     }
 }
@@ -60,6 +60,22 @@ impl Storage for PrefixStorage {
         self.storage.put(&self.prefix.join(path), payload).await
     }
 
+    async fn put_if_absent(
+        &self,
+        path: &Path,
+        payload: Box<dyn crate::PutPayload>,
+    ) -> crate::StorageResult<bool> {
+        self.storage
+            .put_if_absent(&self.prefix.join(path), payload)
+            .await
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> crate::StorageResult<()> {
+        self.storage
+            .copy(&self.prefix.join(from), &self.prefix.join(to))
+            .await
+    }
+
     async fn copy_to(
         &self,
         path: &Path,
@@ -76,6 +92,16 @@ impl Storage for PrefixStorage {
         self.storage.get_slice(&self.prefix.join(path), range).await
     }
 
+    async fn get_slices(
+        &self,
+        path: &Path,
+        ranges: &[Range<usize>],
+    ) -> crate::StorageResult<Vec<OwnedBytes>> {
+        self.storage
+            .get_slices(&self.prefix.join(path), ranges)
+            .await
+    }
+
     async fn get_all(&self, path: &Path) -> crate::StorageResult<OwnedBytes> {
         self.storage.get_all(&self.prefix.join(path)).await
     }
@@ -112,6 +138,17 @@ impl Storage for PrefixStorage {
         self.storage.exists(&self.prefix.join(path)).await
     }
 
+    async fn list(&self, prefix: &Path) -> crate::StorageResult<Vec<PathBuf>> {
+        let paths = self
+            .storage
+            .list(&self.prefix.join(prefix))
+            .await?
+            .into_iter()
+            .filter_map(|path| path.strip_prefix(&self.prefix).ok().map(Path::to_path_buf))
+            .collect();
+        Ok(paths)
+    }
+
     fn uri(&self) -> &Uri {
         &self.uri
     }
@@ -100,6 +100,21 @@ impl Storage for RamStorage {
         Ok(())
     }
 
+    async fn put_if_absent(
+        &self,
+        path: &Path,
+        payload: Box<dyn crate::PutPayload>,
+    ) -> crate::StorageResult<bool> {
+        let payload_bytes = payload.read_all().await?;
+        let mut files = self.files.write().await;
+
+        if files.contains_key(path) {
+            return Ok(false);
+        }
+        files.insert(path.to_path_buf(), payload_bytes);
+        Ok(true)
+    }
+
     async fn copy_to(&self, path: &Path, output: &mut dyn SendableAsync) -> StorageResult<()> {
         let payload_bytes = self.get_data(path).await.ok_or_else(|| {
             StorageErrorKind::NotFound
@@ -148,6 +163,19 @@ impl Storage for RamStorage {
         Ok(payload_bytes)
     }
 
+    async fn list(&self, prefix: &Path) -> StorageResult<Vec<PathBuf>> {
+        let prefix_str = prefix.to_string_lossy();
+        let paths = self
+            .files
+            .read()
+            .await
+            .keys()
+            .filter(|path| path.to_string_lossy().starts_with(prefix_str.as_ref()))
+            .cloned()
+            .collect();
+        Ok(paths)
+    }
+
     fn uri(&self) -> &Uri {
         &self.uri
     }
@@ -249,6 +277,28 @@ mod tests {
         assert_eq!(data_storage.uri(), data_storage_two.uri());
     }
 
+    #[tokio::test]
+    async fn test_ram_storage_list() {
+        let storage = RamStorage::builder()
+            .put("foo/split1.split", b"")
+            .put("foo/split2.split", b"")
+            .put("foobar", b"")
+            .put("bar/split3.split", b"")
+            .build();
+        let mut paths = storage.list(Path::new("foo")).await.unwrap();
+        paths.sort();
+        assert_eq!(
+            paths,
+            [
+                PathBuf::from("foo/split1.split"),
+                PathBuf::from("foo/split2.split"),
+                PathBuf::from("foobar"),
+            ]
+        );
+        assert_eq!(storage.list(Path::new("")).await.unwrap().len(), 4);
+        assert!(storage.list(Path::new("qux")).await.unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn test_ram_storage_builder() -> anyhow::Result<()> {
         let storage = RamStorage::builder()
@@ -0,0 +1,279 @@
+// Copyright (C) 2024 Quickwit, Inc.
+//
+// Quickwit is offered under the AGPL v3.0 and as commercial software.
+// For commercial licensing, contact us at hello@quickwit.io.
+//
+// AGPL:
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as
+// published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use quickwit_common::uri::Uri;
+use tantivy::directory::OwnedBytes;
+use tokio::io::AsyncRead;
+use tokio::sync::oneshot;
+
+use crate::storage::SendableAsync;
+use crate::{BulkDeleteError, PutPayload, Storage, StorageResult};
+
+type PendingRead = (Range<usize>, oneshot::Sender<StorageResult<OwnedBytes>>);
+
+type PendingReads = Mutex<HashMap<PathBuf, Vec<PendingRead>>>;
+
+/// Storage wrapper gathering the concurrent `get_slice` requests targeting the same file into a
+/// single [`Storage::get_slices`] call on the underlying storage.
+///
+/// The first request for a file yields once to the runtime before issuing the batched read, so
+/// that the requests polled within the same scheduling pass (typically, the reads issued by a
+/// `join_all` during the warmup of a split) join its batch. The underlying storage can then fetch
+/// nearby ranges with fewer requests.
+#[derive(Clone)]
+pub struct ReadBatchingStorage {
+    underlying: Arc<dyn Storage>,
+    pending_reads: Arc<PendingReads>,
+}
+
+impl fmt::Debug for ReadBatchingStorage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReadBatchingStorage")
+            .field("underlying", &self.underlying)
+            .finish()
+    }
+}
+
+impl ReadBatchingStorage {
+    /// Wraps `underlying` so that its concurrent reads are batched.
+    pub fn new(underlying: Arc<dyn Storage>) -> Self {
+        Self {
+            underlying,
+            pending_reads: Arc::default(),
+        }
+    }
+
+    /// Registers a read and returns whether the caller is in charge of issuing the batch.
+    fn enqueue_read(&self, path: &Path, pending_read: PendingRead) -> bool {
+        let mut pending_reads = self.pending_reads.lock().unwrap();
+        match pending_reads.entry(path.to_path_buf()) {
+            Entry::Occupied(mut entry) => {
+                entry.get_mut().push(pending_read);
+                false
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(vec![pending_read]);
+                true
+            }
+        }
+    }
+
+    async fn fetch_batch(&self, path: &Path, batch: Vec<PendingRead>) {
+        let ranges: Vec<Range<usize>> = batch.iter().map(|(range, _)| range.clone()).collect();
+        match self.underlying.get_slices(path, &ranges).await {
+            Ok(slices) => {
+                for ((_, result_tx), slice) in batch.into_iter().zip(slices) {
+                    let _ = result_tx.send(Ok(slice));
+                }
+            }
+            Err(storage_error) => {
+                for (_, result_tx) in batch {
+                    let _ = result_tx.send(Err(storage_error.clone()));
+                }
+            }
+        }
+    }
+}
+
+/// Removes the batch of a file from the pending reads if the request in charge of it is dropped
+/// before issuing it. The other requests of the batch then see their channel closed and fall back
+/// to reading their range on their own.
+struct PendingBatchGuard<'a> {
+    pending_reads: &'a PendingReads,
+    path: Option<&'a Path>,
+}
+
+impl<'a> PendingBatchGuard<'a> {
+    fn take_batch(mut self) -> Vec<PendingRead> {
+        let path = self.path.take().expect("the path should be set");
+        self.pending_reads
+            .lock()
+            .unwrap()
+            .remove(path)
+            .unwrap_or_default()
+    }
+}
+
+impl<'a> Drop for PendingBatchGuard<'a> {
+    fn drop(&mut self) {
+        if let Some(path) = self.path.take() {
+            self.pending_reads.lock().unwrap().remove(path);
+        }
+    }
+}
+
+#[async_trait]
+impl Storage for ReadBatchingStorage {
+    async fn check_connectivity(&self) -> anyhow::Result<()> {
+        self.underlying.check_connectivity().await
+    }
+
+    async fn put(&self, path: &Path, payload: Box<dyn PutPayload>) -> StorageResult<()> {
+        self.underlying.put(path, payload).await
+    }
+
+    async fn put_if_absent(
+        &self,
+        path: &Path,
+        payload: Box<dyn PutPayload>,
+    ) -> StorageResult<bool> {
+        self.underlying.put_if_absent(path, payload).await
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> StorageResult<()> {
+        self.underlying.copy(from, to).await
+    }
+
+    async fn copy_to(&self, path: &Path, output: &mut dyn SendableAsync) -> StorageResult<()> {
+        self.underlying.copy_to(path, output).await
+    }
+
+    async fn get_slice(&self, path: &Path, range: Range<usize>) -> StorageResult<OwnedBytes> {
+        let (result_tx, result_rx) = oneshot::channel();
+
+        if self.enqueue_read(path, (range.clone(), result_tx)) {
+            let pending_batch_guard = PendingBatchGuard {
+                pending_reads: &self.pending_reads,
+                path: Some(path),
+            };
+            tokio::task::yield_now().await;
+            let batch = pending_batch_guard.take_batch();
+            self.fetch_batch(path, batch).await;
+        }
+        match result_rx.await {
+            Ok(result) => result,
+            // The request in charge of the batch was dropped.
+            Err(_) => self.underlying.get_slice(path, range).await,
+        }
+    }
+
+    async fn get_slices(
+        &self,
+        path: &Path,
+        ranges: &[Range<usize>],
+    ) -> StorageResult<Vec<OwnedBytes>> {
+        self.underlying.get_slices(path, ranges).await
+    }
+
+    async fn get_slice_stream(
+        &self,
+        path: &Path,
+        range: Range<usize>,
+    ) -> StorageResult<Box<dyn AsyncRead + Send + Unpin>> {
+        self.underlying.get_slice_stream(path, range).await
+    }
+
+    async fn get_all(&self, path: &Path) -> StorageResult<OwnedBytes> {
+        self.underlying.get_all(path).await
+    }
+
+    async fn delete(&self, path: &Path) -> StorageResult<()> {
+        self.underlying.delete(path).await
+    }
+
+    async fn bulk_delete<'a>(&self, paths: &[&'a Path]) -> Result<(), BulkDeleteError> {
+        self.underlying.bulk_delete(paths).await
+    }
+
+    async fn list(&self, prefix: &Path) -> StorageResult<Vec<PathBuf>> {
+        self.underlying.list(prefix).await
+    }
+
+    async fn file_num_bytes(&self, path: &Path) -> StorageResult<u64> {
+        self.underlying.file_num_bytes(path).await
+    }
+
+    fn uri(&self) -> &Uri {
+        self.underlying.uri()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::future::join_all;
+
+    use super::*;
+    use crate::{MockStorage, StorageErrorKind};
+
+    fn slice_for_range(range: &Range<usize>) -> OwnedBytes {
+        OwnedBytes::new(
+            (range.start..range.end)
+                .map(|i| i as u8)
+                .collect::<Vec<u8>>(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_read_batching_storage_batches_concurrent_reads() {
+        let mut mock_storage = MockStorage::new();
+        mock_storage.expect_get_slice().never();
+        mock_storage
+            .expect_get_slices()
+            .times(1)
+            .withf(|path, ranges| path == Path::new("split") && ranges.len() == 3)
+            .returning(|_, ranges| Ok(ranges.iter().map(slice_for_range).collect()));
+        mock_storage
+            .expect_get_slices()
+            .times(1)
+            .withf(|path, ranges| path == Path::new("other_split") && ranges.len() == 1)
+            .returning(|_, ranges| Ok(ranges.iter().map(slice_for_range).collect()));
+
+        let storage = ReadBatchingStorage::new(Arc::new(mock_storage));
+        let ranges = [0..4, 10..12, 4..8];
+        let slices = join_all(
+            ranges
+                .iter()
+                .map(|range| storage.get_slice(Path::new("split"), range.clone()))
+                .chain(std::iter::once(
+                    storage.get_slice(Path::new("other_split"), 2..3),
+                )),
+        )
+        .await;
+        assert_eq!(slices[0].as_ref().unwrap().as_slice(), &[0, 1, 2, 3]);
+        assert_eq!(slices[1].as_ref().unwrap().as_slice(), &[10, 11]);
+        assert_eq!(slices[2].as_ref().unwrap().as_slice(), &[4, 5, 6, 7]);
+        assert_eq!(slices[3].as_ref().unwrap().as_slice(), &[2]);
+        assert!(storage.pending_reads.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_read_batching_storage_propagates_errors() {
+        let mut mock_storage = MockStorage::new();
+        mock_storage.expect_get_slices().times(1).returning(|_, _| {
+            Err(StorageErrorKind::Service.with_error(anyhow::anyhow!("service unavailable")))
+        });
+        let storage = ReadBatchingStorage::new(Arc::new(mock_storage));
+        let results = join_all([
+            storage.get_slice(Path::new("split"), 0..4),
+            storage.get_slice(Path::new("split"), 4..8),
+        ])
+        .await;
+        for result in results {
+            assert_eq!(result.unwrap_err().kind(), StorageErrorKind::Service);
+        }
+    }
+}
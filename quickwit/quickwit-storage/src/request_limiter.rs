@@ -0,0 +1,212 @@
+// Copyright (C) 2024 Quickwit, Inc.
+//
+// Quickwit is offered under the AGPL v3.0 and as commercial software.
+// For commercial licensing, contact us at hello@quickwit.io.
+//
+// AGPL:
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as
+// published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use std::num::NonZeroUsize;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::{fmt, io};
+
+use tokio::io::{AsyncRead, ReadBuf};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::STORAGE_METRICS;
+
+/// Maximum number of concurrent in-flight requests used when the storage config does not define
+/// `max_concurrent_requests`.
+pub(crate) const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 10_000;
+
+/// Bounds the number of concurrent in-flight requests issued to a storage backend.
+///
+/// Cloning the limiter shares the underlying budget, so that all the storages holding a clone
+/// (e.g. split downloads during search warmup and split uploads during indexing) compete for the
+/// same permits.
+#[derive(Clone)]
+pub struct StorageRequestLimiter {
+    semaphore: Arc<Semaphore>,
+    max_concurrent_requests: usize,
+}
+
+impl fmt::Debug for StorageRequestLimiter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StorageRequestLimiter")
+            .field("max_concurrent_requests", &self.max_concurrent_requests)
+            .field("available_permits", &self.semaphore.available_permits())
+            .finish()
+    }
+}
+
+impl StorageRequestLimiter {
+    /// Creates a limiter allowing at most `max_concurrent_requests` in-flight requests.
+    pub fn new(max_concurrent_requests: usize) -> Self {
+        Self {
+            semaphore: Arc::new(Semaphore::new(max_concurrent_requests)),
+            max_concurrent_requests,
+        }
+    }
+
+    /// Creates a limiter from the optional `max_concurrent_requests` of a storage config, falling
+    /// back to [`DEFAULT_MAX_CONCURRENT_REQUESTS`] when it is not set.
+    pub(crate) fn from_config(max_concurrent_requests_opt: Option<NonZeroUsize>) -> Self {
+        let max_concurrent_requests = max_concurrent_requests_opt
+            .map(NonZeroUsize::get)
+            .unwrap_or(DEFAULT_MAX_CONCURRENT_REQUESTS);
+        Self::new(max_concurrent_requests)
+    }
+
+    /// Returns the maximum number of concurrent in-flight requests.
+    pub fn max_concurrent_requests(&self) -> usize {
+        self.max_concurrent_requests
+    }
+
+    /// Waits for a permit to issue a request. The permit is released when dropped.
+    pub async fn acquire(&self) -> StorageRequestPermit {
+        let _queued_guard = QueuedRequestGuard::new();
+        let permit = self
+            .semaphore
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("the semaphore should never be closed");
+        STORAGE_METRICS.object_storage_requests_in_flight.inc();
+        StorageRequestPermit { _permit: permit }
+    }
+}
+
+/// Permit to issue a storage request, obtained from a [`StorageRequestLimiter`].
+pub struct StorageRequestPermit {
+    _permit: OwnedSemaphorePermit,
+}
+
+impl Drop for StorageRequestPermit {
+    fn drop(&mut self) {
+        STORAGE_METRICS.object_storage_requests_in_flight.dec();
+    }
+}
+
+/// Wraps the async read handle of a streamed response together with a permit to keep the permit
+/// alive until the handle is dropped.
+pub(crate) struct PermitAsyncRead<T> {
+    read: T,
+    _permit: StorageRequestPermit,
+}
+
+impl<T> PermitAsyncRead<T> {
+    pub(crate) fn new(read: T, permit: StorageRequestPermit) -> Self {
+        Self {
+            read,
+            _permit: permit,
+        }
+    }
+}
+
+impl<T: AsyncRead + Send + Unpin> AsyncRead for PermitAsyncRead<T> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let self_unpin = self.get_mut();
+        Pin::new(&mut self_unpin.read).poll_read(cx, buf)
+    }
+}
+
+/// Tracks requests waiting for a permit, including those whose future is dropped while waiting.
+struct QueuedRequestGuard;
+
+impl QueuedRequestGuard {
+    fn new() -> Self {
+        STORAGE_METRICS.object_storage_requests_queued.inc();
+        Self
+    }
+}
+
+impl Drop for QueuedRequestGuard {
+    fn drop(&mut self) {
+        STORAGE_METRICS.object_storage_requests_queued.dec();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use tokio::io::AsyncReadExt;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_storage_request_limiter() {
+        let request_limiter = StorageRequestLimiter::new(3);
+        let num_in_flight_requests = Arc::new(AtomicUsize::new(0));
+        let max_num_in_flight_requests = Arc::new(AtomicUsize::new(0));
+
+        let mut join_handles = Vec::new();
+
+        for _ in 0..20 {
+            let request_limiter = request_limiter.clone();
+            let num_in_flight_requests = num_in_flight_requests.clone();
+            let max_num_in_flight_requests = max_num_in_flight_requests.clone();
+
+            let join_handle = tokio::spawn(async move {
+                let _permit = request_limiter.acquire().await;
+                let num_in_flight = num_in_flight_requests.fetch_add(1, Ordering::SeqCst) + 1;
+                max_num_in_flight_requests.fetch_max(num_in_flight, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                num_in_flight_requests.fetch_sub(1, Ordering::SeqCst);
+            });
+            join_handles.push(join_handle);
+        }
+        for join_handle in join_handles {
+            join_handle.await.unwrap();
+        }
+        let max_num_in_flight_requests = max_num_in_flight_requests.load(Ordering::SeqCst);
+        assert!(max_num_in_flight_requests > 0);
+        assert!(max_num_in_flight_requests <= 3);
+        assert_eq!(request_limiter.semaphore.available_permits(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_storage_request_limiter_from_config() {
+        let request_limiter = StorageRequestLimiter::from_config(None);
+        assert_eq!(
+            request_limiter.max_concurrent_requests(),
+            DEFAULT_MAX_CONCURRENT_REQUESTS
+        );
+        let request_limiter = StorageRequestLimiter::from_config(NonZeroUsize::new(2));
+        assert_eq!(request_limiter.max_concurrent_requests(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_permit_async_read() {
+        let request_limiter = StorageRequestLimiter::new(1);
+        let permit = request_limiter.acquire().await;
+        let mut read = PermitAsyncRead::new(&b"hello"[..], permit);
+        assert_eq!(request_limiter.semaphore.available_permits(), 0);
+
+        let mut buffer = String::new();
+        read.read_to_string(&mut buffer).await.unwrap();
+        assert_eq!(buffer, "hello");
+        assert_eq!(request_limiter.semaphore.available_permits(), 0);
+
+        drop(read);
+        assert_eq!(request_limiter.semaphore.available_permits(), 1);
+    }
+}
@@ -30,8 +30,8 @@ use quickwit_common::shared_consts::SPLIT_FIELDS_FILE_NAME;
 use tokio::io::{AsyncReadExt, AsyncSeekExt};
 use tokio_util::io::ReaderStream;
 
-use crate::bundle_storage::BundleStorageFileOffsetsVersions;
-use crate::{BundleStorageFileOffsets, PutPayload, VersionedComponent};
+use crate::bundle_storage::{BundleStorageFileOffsetsVersions, WRITE_BUNDLE_FOOTER_CHECKSUM};
+use crate::{BundleStorageFileOffsets, PutPayload};
 
 /// Payload of a split which builds the split bundle and hotcache on the fly and streams it to the
 /// storage.
@@ -76,9 +76,9 @@ impl PutPayload for SplitPayload {
 }
 
 #[derive(Clone)]
-struct FilePayload {
-    len: u64,
-    path: PathBuf,
+pub(crate) struct FilePayload {
+    pub(crate) len: u64,
+    pub(crate) path: PathBuf,
 }
 
 #[async_trait]
@@ -106,12 +106,23 @@ impl PutPayload for FilePayload {
 }
 
 /// SplitPayloadBuilder is used to create a `SplitPayload`.
-#[derive(Default)]
 pub struct SplitPayloadBuilder {
     /// File name, payload, and range of the payload in the bundle file
     /// Range coud be computed on the fly, and is just kept here for convenience.
     payloads: Vec<(String, Box<dyn PutPayload>, Range<u64>)>,
     current_offset: usize,
+    /// Whether the bundle footer is written with its checksum.
+    footer_checksum: bool,
+}
+
+impl Default for SplitPayloadBuilder {
+    fn default() -> Self {
+        Self {
+            payloads: Vec::new(),
+            current_offset: 0,
+            footer_checksum: *WRITE_BUNDLE_FOOTER_CHECKSUM,
+        }
+    }
 }
 
 impl SplitPayloadBuilder {
@@ -133,6 +144,13 @@ impl SplitPayloadBuilder {
         Ok(offsets)
     }
 
+    /// Sets whether the bundle footer is written with its checksum, overriding the
+    /// `QW_ENABLE_SPLIT_FOOTER_CHECKSUM` environment variable. Splits written with a checksum
+    /// cannot be opened by releases prior to its introduction.
+    pub fn set_footer_checksum(&mut self, footer_checksum: bool) {
+        self.footer_checksum = footer_checksum;
+    }
+
     /// Adds the payload to the bundle file.
     pub fn add_payload(&mut self, file_name: String, payload: Box<dyn PutPayload>) {
         let range = self.current_offset as u64..self.current_offset as u64 + payload.len();
@@ -181,8 +199,13 @@ impl SplitPayloadBuilder {
         let bundle_storage_file_offsets = BundleStorageFileOffsets {
             files: metadata_with_fixed_paths,
         };
-        let metadata_json =
-            BundleStorageFileOffsetsVersions::serialize(&bundle_storage_file_offsets);
+        let bundle_storage_file_offsets_version = if self.footer_checksum {
+            BundleStorageFileOffsetsVersions::V2
+        } else {
+            BundleStorageFileOffsetsVersions::V1
+        };
+        let metadata_json = bundle_storage_file_offsets_version
+            .serialize_with_version(&bundle_storage_file_offsets);
 
         // The hotcache needs to be the next to the metadata in order to be able to read both
         // in one continuous read.
@@ -0,0 +1,93 @@
+// Copyright (C) 2024 Quickwit, Inc.
+//
+// Quickwit is offered under the AGPL v3.0 and as commercial software.
+// For commercial licensing, contact us at hello@quickwit.io.
+//
+// AGPL:
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as
+// published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tracing::info;
+
+use crate::split_cache::download_task::delete_evicted_splits;
+use crate::split_cache::split_table::SplitTable;
+
+/// Maximum interval between two sweeps of the idle splits.
+const MAX_IDLE_SPLITS_SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Minimum interval between two sweeps of the idle splits.
+const MIN_IDLE_SPLITS_SWEEP_INTERVAL: Duration = Duration::from_secs(1);
+
+fn idle_splits_sweep_interval(max_idle: Duration) -> Duration {
+    (max_idle / 10).clamp(
+        MIN_IDLE_SPLITS_SWEEP_INTERVAL,
+        MAX_IDLE_SPLITS_SWEEP_INTERVAL,
+    )
+}
+
+/// Spawns the task that periodically evicts the splits that have not been accessed for longer
+/// than `max_idle`.
+pub(crate) fn spawn_idle_eviction_task(
+    root_path: PathBuf,
+    shared_split_table: Arc<Mutex<SplitTable>>,
+    max_idle: Duration,
+) {
+    tokio::task::spawn(async move {
+        let mut sweep_interval = tokio::time::interval(idle_splits_sweep_interval(max_idle));
+        sweep_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            sweep_interval.tick().await;
+            let idle_splits = shared_split_table
+                .lock()
+                .unwrap()
+                .evict_idle_splits(max_idle);
+            if idle_splits.is_empty() {
+                continue;
+            }
+            info!(
+                num_splits = idle_splits.len(),
+                "evicting idle splits from the searcher split cache"
+            );
+            let root_path_clone = root_path.clone();
+            let _ = tokio::task::spawn_blocking(move || {
+                delete_evicted_splits(&root_path_clone, &idle_splits[..]);
+            })
+            .await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_idle_splits_sweep_interval() {
+        assert_eq!(
+            idle_splits_sweep_interval(Duration::from_millis(100)),
+            Duration::from_secs(1)
+        );
+        assert_eq!(
+            idle_splits_sweep_interval(Duration::from_secs(300)),
+            Duration::from_secs(30)
+        );
+        assert_eq!(
+            idle_splits_sweep_interval(Duration::from_secs(6 * 3600)),
+            Duration::from_secs(60)
+        );
+    }
+}
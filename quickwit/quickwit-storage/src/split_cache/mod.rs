@@ -18,6 +18,7 @@
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
 mod download_task;
+mod eviction_task;
 mod split_table;
 
 use std::collections::BTreeMap;
@@ -28,6 +29,7 @@ use std::ops::Range;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
 
 use async_trait::async_trait;
 use quickwit_common::uri::Uri;
@@ -38,6 +40,7 @@ use tracing::{error, info, warn};
 use ulid::Ulid;
 
 use crate::split_cache::download_task::{delete_evicted_splits, spawn_download_task};
+use crate::split_cache::eviction_task::spawn_idle_eviction_task;
 use crate::split_cache::split_table::{SplitGuard, SplitTable};
 use crate::{wrap_storage_with_cache, Storage, StorageCache};
 
@@ -55,7 +58,8 @@ pub struct SplitCache {
 
 impl SplitCache {
     /// Creates a new SplitCache and spawns the task that will continuously search for
-    /// download opportunities.
+    /// download opportunities. If `limits.max_idle` is set, another task periodically evicts the
+    /// splits that have not been accessed for longer than `max_idle`.
     pub fn with_root_path(
         root_path: PathBuf,
         storage_resolver: crate::StorageResolver,
@@ -114,6 +118,9 @@ impl SplitCache {
             storage_resolver,
             limits.num_concurrent_downloads,
         );
+        if let Some(max_idle) = limits.max_idle {
+            spawn_idle_eviction_task(root_path.clone(), split_table_arc.clone(), max_idle);
+        }
 
         Ok(SplitCache {
             root_path,
@@ -127,7 +134,7 @@ impl SplitCache {
             split_cache: self_arc,
             storage_root_uri: storage.uri().clone(),
         });
-        wrap_storage_with_cache(cache, storage)
+        wrap_storage_with_cache("searcher_split", cache, storage)
     }
 
     /// Report the split cache about the existence of new splits.
@@ -177,12 +184,107 @@ impl AsRef<Path> for SplitFilepath {
     }
 }
 
+/// Split file stored in a split cache directory.
+struct CachedSplitFile {
+    path: PathBuf,
+    num_bytes: u64,
+    last_used: SystemTime,
+}
+
+/// Lists the split files stored in a split cache directory. Files that are not splits are
+/// ignored.
+fn list_cached_split_files(root_path: &Path) -> io::Result<Vec<CachedSplitFile>> {
+    let read_dir = match std::fs::read_dir(root_path) {
+        Ok(read_dir) => read_dir,
+        Err(io_error) if io_error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(io_error) => return Err(io_error),
+    };
+    let mut cached_split_files = Vec::new();
+    for dir_entry_res in read_dir {
+        let path = dir_entry_res?.path();
+        if split_id_from_path(&path).is_none() {
+            continue;
+        }
+        let meta = std::fs::metadata(&path)?;
+        if !meta.is_file() {
+            continue;
+        }
+        // The access time is not maintained on all file systems, so we also look at the
+        // modification time, which is the time the split was downloaded.
+        let last_used = match (meta.accessed(), meta.modified()) {
+            (Ok(accessed), Ok(modified)) => accessed.max(modified),
+            (Ok(last_used), Err(_)) | (Err(_), Ok(last_used)) => last_used,
+            (Err(_), Err(_)) => SystemTime::UNIX_EPOCH,
+        };
+        cached_split_files.push(CachedSplitFile {
+            path,
+            num_bytes: meta.len(),
+            last_used,
+        });
+    }
+    Ok(cached_split_files)
+}
+
+/// Returns the total size of the split files stored in the split cache directory located at
+/// `root_path`.
+pub fn split_cache_directory_num_bytes(root_path: &Path) -> io::Result<u64> {
+    let num_bytes = list_cached_split_files(root_path)?
+        .iter()
+        .map(|cached_split_file| cached_split_file.num_bytes)
+        .sum();
+    Ok(num_bytes)
+}
+
+/// Removes the least recently used split files from the split cache directory located at
+/// `root_path` until the total size of the remaining ones is lower than or equal to
+/// `target_num_bytes`. The leftover temporary files of interrupted downloads are removed too.
+///
+/// A running [`SplitCache`] does not expect its files to be removed behind its back: this
+/// function should only be called while the searcher owning the directory is stopped.
+///
+/// Returns the total size of the remaining split files.
+pub fn prune_split_cache_directory(root_path: &Path, target_num_bytes: u64) -> io::Result<u64> {
+    let mut cached_split_files = list_cached_split_files(root_path)?;
+    let mut num_bytes: u64 = cached_split_files
+        .iter()
+        .map(|cached_split_file| cached_split_file.num_bytes)
+        .sum();
+    cached_split_files.sort_by_key(|cached_split_file| cached_split_file.last_used);
+
+    for cached_split_file in cached_split_files {
+        if num_bytes <= target_num_bytes {
+            break;
+        }
+        std::fs::remove_file(&cached_split_file.path)?;
+        num_bytes -= cached_split_file.num_bytes;
+    }
+    for dir_entry_res in std::fs::read_dir(root_path).into_iter().flatten() {
+        let path = dir_entry_res?.path();
+        if path.extension() == Some(OsStr::new("temp")) {
+            std::fs::remove_file(&path)?;
+        }
+    }
+    Ok(num_bytes)
+}
+
 fn split_id_from_path(split_path: &Path) -> Option<Ulid> {
     let split_filename = split_path.file_name()?.to_str()?;
     let split_id_str = split_filename.strip_suffix(".split")?;
     Ulid::from_str(split_id_str).ok()
 }
 
+/// Returns the URI of the directory containing the split file located at `split_path` in the
+/// storage rooted at `storage_root_uri`. Split files are nested under a prefix when the index
+/// defines a split path template.
+fn split_storage_uri(storage_root_uri: &Uri, split_path: &Path) -> Option<Uri> {
+    match split_path.parent() {
+        Some(split_path_prefix) if split_path_prefix != Path::new("") => {
+            storage_root_uri.join(split_path_prefix).ok()
+        }
+        _ => Some(storage_root_uri.clone()),
+    }
+}
+
 struct SplitCacheBackingStorage {
     split_cache: Arc<SplitCache>,
     storage_root_uri: Uri,
@@ -191,9 +293,10 @@ struct SplitCacheBackingStorage {
 impl SplitCacheBackingStorage {
     async fn get_impl(&self, path: &Path, byte_range: Range<usize>) -> Option<OwnedBytes> {
         let split_id = split_id_from_path(path)?;
+        let split_storage_uri = split_storage_uri(&self.storage_root_uri, path)?;
         let split_guard = self
             .split_cache
-            .get_split_guard(split_id, &self.storage_root_uri)?;
+            .get_split_guard(split_id, &split_storage_uri)?;
         // TODO touch file in cache.
         // We don't use async file io here because it spawn blocks anyway, and it feels dumb to
         // spawn block 3 times in a row.
@@ -213,9 +316,10 @@ impl SplitCacheBackingStorage {
 
     async fn get_all_impl(&self, path: &Path) -> Option<OwnedBytes> {
         let split_id = split_id_from_path(path)?;
+        let split_storage_uri = split_storage_uri(&self.storage_root_uri, path)?;
         let split_guard = self
             .split_cache
-            .get_split_guard(split_id, &self.storage_root_uri)?;
+            .get_split_guard(split_id, &split_storage_uri)?;
         // We don't use async file io here because it spawn blocks anyway, and it feels dumb to
         // spawn block 3 times in a row.
         tokio::task::spawn_blocking(move || {
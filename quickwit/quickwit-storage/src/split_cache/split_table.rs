@@ -64,7 +64,7 @@ impl Eq for SplitKey {}
 enum Status {
     Candidate(CandidateSplit),
     Downloading { alive_token: Weak<()> },
-    OnDisk { num_bytes: u64 },
+    OnDisk { num_bytes: u64, read_token: Arc<()> },
 }
 
 impl PartialEq for Status {
@@ -75,9 +75,10 @@ impl PartialEq for Status {
             }
             (Status::Downloading { .. }, Status::Downloading { .. }) => true,
             (
-                Status::OnDisk { num_bytes },
+                Status::OnDisk { num_bytes, .. },
                 Status::OnDisk {
                     num_bytes: other_num_bytes,
+                    ..
                 },
             ) => num_bytes == other_num_bytes,
             _ => false,
@@ -137,7 +138,10 @@ impl SplitTable {
                     last_accessed: 0,
                     split_ulid,
                 },
-                status: Status::OnDisk { num_bytes },
+                status: Status::OnDisk {
+                    num_bytes,
+                    read_token: Arc::new(()),
+                },
             };
             self.insert(split_info);
         }
@@ -148,9 +152,11 @@ fn compute_timestamp(start: Instant) -> LastAccessDate {
     start.elapsed().as_micros() as u64
 }
 
-// TODO improve SplitGuard with Atomic
-// Right only touch is helping.
-pub(super) struct SplitGuard;
+/// Prevents the split from being evicted by [`SplitTable::evict_idle_splits`] while it is
+/// being read.
+pub(super) struct SplitGuard {
+    _read_token: Arc<()>,
+}
 
 impl SplitTable {
     pub(super) fn get_split_guard(
@@ -158,8 +164,10 @@ impl SplitTable {
         split_ulid: Ulid,
         storage_uri: &Uri,
     ) -> Option<SplitGuard> {
-        if let Status::OnDisk { .. } = self.touch(split_ulid, storage_uri) {
-            Some(SplitGuard)
+        if let Status::OnDisk { read_token, .. } = self.touch(split_ulid, storage_uri) {
+            Some(SplitGuard {
+                _read_token: read_token,
+            })
         } else {
             None
         }
@@ -170,7 +178,7 @@ impl SplitTable {
         let split_queue: &mut BTreeSet<SplitKey> = match split_info.status {
             Status::Candidate { .. } => &mut self.candidate_splits,
             Status::Downloading { .. } => &mut self.downloading_splits,
-            Status::OnDisk { num_bytes } => {
+            Status::OnDisk { num_bytes, .. } => {
                 self.on_disk_bytes -= num_bytes;
                 crate::metrics::STORAGE_METRICS
                     .searcher_split_cache
@@ -226,7 +234,7 @@ impl SplitTable {
                 was_not_in_queue
             }
             Status::Downloading { .. } => self.downloading_splits.insert(split_info.split_key),
-            Status::OnDisk { num_bytes } => {
+            Status::OnDisk { num_bytes, .. } => {
                 self.on_disk_bytes += num_bytes;
                 crate::metrics::STORAGE_METRICS
                     .searcher_split_cache
@@ -334,7 +342,13 @@ impl SplitTable {
     }
 
     pub(crate) fn register_as_downloaded(&mut self, split_ulid: Ulid, num_bytes: u64) {
-        self.change_split_status(split_ulid, Status::OnDisk { num_bytes });
+        self.change_split_status(
+            split_ulid,
+            Status::OnDisk {
+                num_bytes,
+                read_token: Arc::new(()),
+            },
+        );
     }
 
     /// Change the state of the given split from candidate to downloading state,
@@ -415,6 +429,34 @@ impl SplitTable {
         }
     }
 
+    /// Removes the on disk splits that have not been accessed for longer than `max_idle`, and
+    /// returns their ulids. Splits currently being read are kept.
+    ///
+    /// As for the other evictions, the split files still need to be deleted by the caller.
+    pub(crate) fn evict_idle_splits(&mut self, max_idle: Duration) -> Vec<Ulid> {
+        let now = compute_timestamp(self.origin_time);
+        let idle_threshold = now.saturating_sub(max_idle.as_micros() as u64);
+        let mut idle_splits = Vec::new();
+        for split_key in &self.on_disk_splits {
+            if split_key.last_accessed >= idle_threshold {
+                break;
+            }
+            let Some(split_info) = self.split_to_status.get(&split_key.split_ulid) else {
+                continue;
+            };
+            if let Status::OnDisk { read_token, .. } = &split_info.status {
+                if Arc::strong_count(read_token) > 1 {
+                    continue;
+                }
+            }
+            idle_splits.push(split_key.split_ulid);
+        }
+        for &split_ulid in &idle_splits {
+            self.remove(split_ulid);
+        }
+        idle_splits
+    }
+
     pub(crate) fn find_download_opportunity(&mut self) -> Option<DownloadOpportunity> {
         let best_candidate_split_key = self.best_candidate()?;
         let splits_to_delete: Vec<Ulid> =
@@ -450,6 +492,7 @@ pub(crate) struct DownloadOpportunity {
 #[cfg(test)]
 mod tests {
     use std::num::NonZeroU32;
+    use std::time::Duration;
 
     use bytesize::ByteSize;
     use quickwit_common::uri::Uri;
@@ -474,6 +517,7 @@ mod tests {
                 max_num_bytes: ByteSize::kb(1),
                 max_num_splits: NonZeroU32::new(1).unwrap(),
                 num_concurrent_downloads: NonZeroU32::new(1).unwrap(),
+                max_idle: None,
             },
             Default::default(),
         );
@@ -493,6 +537,7 @@ mod tests {
                 max_num_bytes: ByteSize::kb(1),
                 max_num_splits: NonZeroU32::new(1).unwrap(),
                 num_concurrent_downloads: NonZeroU32::new(1).unwrap(),
+                max_idle: None,
             },
             Default::default(),
         );
@@ -514,6 +559,7 @@ mod tests {
                 max_num_bytes: ByteSize::kb(1),
                 max_num_splits: NonZeroU32::new(1).unwrap(),
                 num_concurrent_downloads: NonZeroU32::new(1).unwrap(),
+                max_idle: None,
             },
             Default::default(),
         );
@@ -543,6 +589,7 @@ mod tests {
                 max_num_bytes: ByteSize::mb(1),
                 max_num_splits: NonZeroU32::new(30).unwrap(),
                 num_concurrent_downloads: NonZeroU32::new(1).unwrap(),
+                max_idle: None,
             },
             Default::default(),
         );
@@ -580,6 +627,7 @@ mod tests {
                 max_num_bytes: ByteSize::mb(10),
                 max_num_splits: NonZeroU32::new(5).unwrap(),
                 num_concurrent_downloads: NonZeroU32::new(1).unwrap(),
+                max_idle: None,
             },
             Default::default(),
         );
@@ -607,6 +655,68 @@ mod tests {
         assert_eq!(split_to_download.split_ulid, new_ulid);
     }
 
+    #[test]
+    fn test_evict_idle_splits() {
+        let split_ulids = sorted_split_ulids(3);
+        let existing_splits = split_ulids
+            .iter()
+            .map(|&split_ulid| (split_ulid, 1_000))
+            .collect();
+        let mut split_table = SplitTable::with_limits_and_existing_splits(
+            SplitCacheLimits {
+                max_num_bytes: ByteSize::mb(10),
+                max_num_splits: NonZeroU32::new(5).unwrap(),
+                num_concurrent_downloads: NonZeroU32::new(1).unwrap(),
+                max_idle: None,
+            },
+            existing_splits,
+        );
+        assert_eq!(split_table.num_bytes(), 3_000);
+        // Existing splits are considered as accessed 10 minutes before the table creation.
+        assert!(split_table
+            .evict_idle_splits(Duration::from_secs(3600))
+            .is_empty());
+        assert_eq!(split_table.num_bytes(), 3_000);
+
+        split_table
+            .get_split_guard(split_ulids[0], &Uri::for_test(TEST_STORAGE_URI))
+            .unwrap();
+        let evicted_splits = split_table.evict_idle_splits(Duration::from_secs(60));
+        assert_eq!(&evicted_splits[..], &split_ulids[1..]);
+        assert_eq!(split_table.num_bytes(), 1_000);
+    }
+
+    #[test]
+    fn test_evict_idle_splits_skips_splits_being_read() {
+        let split_ulids = sorted_split_ulids(2);
+        let existing_splits = split_ulids
+            .iter()
+            .map(|&split_ulid| (split_ulid, 1_000))
+            .collect();
+        let mut split_table = SplitTable::with_limits_and_existing_splits(
+            SplitCacheLimits {
+                max_num_bytes: ByteSize::mb(10),
+                max_num_splits: NonZeroU32::new(5).unwrap(),
+                num_concurrent_downloads: NonZeroU32::new(1).unwrap(),
+                max_idle: None,
+            },
+            existing_splits,
+        );
+        let split_guard = split_table
+            .get_split_guard(split_ulids[0], &Uri::for_test(TEST_STORAGE_URI))
+            .unwrap();
+        std::thread::sleep(Duration::from_millis(10));
+
+        let evicted_splits = split_table.evict_idle_splits(Duration::ZERO);
+        assert_eq!(&evicted_splits[..], &split_ulids[1..]);
+        assert_eq!(split_table.num_bytes(), 1_000);
+
+        drop(split_guard);
+        let evicted_splits = split_table.evict_idle_splits(Duration::ZERO);
+        assert_eq!(&evicted_splits[..], &split_ulids[..1]);
+        assert_eq!(split_table.num_bytes(), 0);
+    }
+
     #[test]
     fn test_failed_download_can_be_re_reported() {
         let mut split_table = SplitTable::with_limits_and_existing_splits(
@@ -614,6 +724,7 @@ mod tests {
                 max_num_bytes: ByteSize::mb(10),
                 max_num_splits: NonZeroU32::new(5).unwrap(),
                 num_concurrent_downloads: NonZeroU32::new(1).unwrap(),
+                max_idle: None,
             },
             Default::default(),
         );
@@ -643,6 +754,7 @@ mod tests {
                 max_num_bytes: ByteSize::mb(10),
                 max_num_splits: NonZeroU32::new(5).unwrap(),
                 num_concurrent_downloads: NonZeroU32::new(1).unwrap(),
+                max_idle: None,
             },
             Default::default(),
         );
@@ -18,13 +18,16 @@
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
 use std::num::NonZeroU32;
+use std::time::{Duration, SystemTime};
 
 use bytesize::ByteSize;
+use filetime::FileTime;
 use quickwit_common::uri::Uri;
 use quickwit_config::SplitCacheLimits;
 use ulid::Ulid;
 
 use crate::split_cache::split_table::{DownloadOpportunity, SplitTable};
+use crate::split_cache::{prune_split_cache_directory, split_cache_directory_num_bytes};
 
 const TEST_STORAGE_URI: &'static str = "s3://test";
 
@@ -176,3 +179,58 @@ fn test_failed_download_can_be_re_reported() {
     let candidate2 = split_table.start_download(split_ulid).unwrap();
     assert_eq!(candidate2.split_ulid, split_ulid);
 }
+
+#[test]
+fn test_prune_split_cache_directory() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let root_path = temp_dir.path();
+    let now = SystemTime::now();
+    let mut split_ulids = Vec::new();
+
+    for age_secs in [30, 10, 20] {
+        let split_ulid = Ulid::new();
+        let split_path = root_path.join(quickwit_common::split_file(split_ulid));
+        std::fs::write(&split_path, vec![0u8; 100]).unwrap();
+        let last_used = FileTime::from_system_time(now - Duration::from_secs(age_secs));
+        filetime::set_file_times(&split_path, last_used, last_used).unwrap();
+        split_ulids.push(split_ulid);
+    }
+    let temp_split_path = root_path.join(format!("{}.temp", Ulid::new()));
+    std::fs::write(&temp_split_path, vec![0u8; 100]).unwrap();
+    let unknown_file_path = root_path.join("unknown-file");
+    std::fs::write(&unknown_file_path, vec![0u8; 100]).unwrap();
+
+    assert_eq!(split_cache_directory_num_bytes(root_path).unwrap(), 300);
+
+    // The cache already fits in the target size.
+    assert_eq!(prune_split_cache_directory(root_path, 300).unwrap(), 300);
+    assert!(!temp_split_path.exists());
+
+    // The least recently used splits are evicted first.
+    assert_eq!(prune_split_cache_directory(root_path, 150).unwrap(), 100);
+    assert_eq!(split_cache_directory_num_bytes(root_path).unwrap(), 100);
+
+    let split_exists = |split_ulid: Ulid| {
+        root_path
+            .join(quickwit_common::split_file(split_ulid))
+            .exists()
+    };
+    assert!(!split_exists(split_ulids[0]));
+    assert!(split_exists(split_ulids[1]));
+    assert!(!split_exists(split_ulids[2]));
+    assert!(unknown_file_path.exists());
+
+    assert_eq!(prune_split_cache_directory(root_path, 0).unwrap(), 0);
+    assert!(!split_exists(split_ulids[1]));
+
+    // Missing directories are considered empty.
+    let missing_dir_path = root_path.join("missing-dir");
+    assert_eq!(
+        split_cache_directory_num_bytes(&missing_dir_path).unwrap(),
+        0
+    );
+    assert_eq!(
+        prune_split_cache_directory(&missing_dir_path, 0).unwrap(),
+        0
+    );
+}
@@ -23,14 +23,20 @@ use std::ops::Range;
 use std::path::{Path, PathBuf};
 
 use async_trait::async_trait;
+use futures::{StreamExt, TryStreamExt};
 use quickwit_common::uri::Uri;
 use tempfile::TempPath;
 use tokio::fs::File;
 use tokio::io::{AsyncRead, AsyncWrite};
 use tracing::error;
 
+use crate::split::FilePayload;
 use crate::{BulkDeleteError, OwnedBytes, PutPayload, StorageErrorKind, StorageResult};
 
+/// Maximum number of concurrent requests issued by the default implementation of
+/// [`Storage::bulk_exists`].
+const BULK_EXISTS_CONCURRENCY: usize = 10;
+
 /// This trait is only used to make it build trait object with `AsyncWrite + Send + Unpin`.
 pub trait SendableAsync: AsyncWrite + Send + Unpin {}
 impl<W: AsyncWrite + Send + Unpin> SendableAsync for W {}
@@ -55,6 +61,32 @@ pub trait Storage: fmt::Debug + Send + Sync + 'static {
     /// Saves a file into the storage.
     async fn put(&self, path: &Path, payload: Box<dyn PutPayload>) -> StorageResult<()>;
 
+    /// Saves a file into the storage only if no file exists at `path` yet. Returns whether the
+    /// file was written.
+    ///
+    /// The default implementation checks for the existence of the file before writing it, which
+    /// is not atomic. Storages supporting conditional writes should override it.
+    async fn put_if_absent(
+        &self,
+        path: &Path,
+        payload: Box<dyn PutPayload>,
+    ) -> StorageResult<bool> {
+        if self.exists(path).await? {
+            return Ok(false);
+        }
+        self.put(path, payload).await?;
+        Ok(true)
+    }
+
+    /// Copies the file at `from` to `to` within the storage. The file at `to` is overwritten if
+    /// it already exists.
+    ///
+    /// The default implementation downloads the file to a local temporary file and uploads it
+    /// back. Storages supporting server-side copies should override it.
+    async fn copy(&self, from: &Path, to: &Path) -> StorageResult<()> {
+        default_copy(self, from, to).await
+    }
+
     /// Copies the file associated to `Path` into an `AsyncWrite`.
     /// This function is required to call `.flush()` before it successfully returns.
     ///
@@ -103,6 +135,25 @@ pub trait Storage: fmt::Debug + Send + Sync + 'static {
     /// Downloads a slice of a file from the storage, and returns an in memory buffer
     async fn get_slice(&self, path: &Path, range: Range<usize>) -> StorageResult<OwnedBytes>;
 
+    /// Downloads several slices of a file from the storage, and returns them in the order of
+    /// `ranges`.
+    ///
+    /// The default implementation calls [`Storage::get_slice`] concurrently for each range.
+    /// Storages for which requests are expensive should override it to fetch nearby ranges with
+    /// fewer requests.
+    async fn get_slices(
+        &self,
+        path: &Path,
+        ranges: &[Range<usize>],
+    ) -> StorageResult<Vec<OwnedBytes>> {
+        futures::future::try_join_all(
+            ranges
+                .iter()
+                .map(|range| self.get_slice(path, range.clone())),
+        )
+        .await
+    }
+
     /// Open a stream handle on the file from the storage
     async fn get_slice_stream(
         &self,
@@ -135,6 +186,28 @@ pub trait Storage: fmt::Debug + Send + Sync + 'static {
         }
     }
 
+    /// Returns whether each of the files exists or not, in the order of `paths`.
+    ///
+    /// The default implementation calls [`Storage::exists`] concurrently.
+    async fn bulk_exists<'a>(&self, paths: &[&'a Path]) -> StorageResult<Vec<bool>> {
+        futures::stream::iter(paths.iter().map(|path| self.exists(path)))
+            .buffered(BULK_EXISTS_CONCURRENCY)
+            .try_collect()
+            .await
+    }
+
+    /// Lists the files whose path starts with `prefix`, including the files located in
+    /// "subdirectories".
+    ///
+    /// Listing is not supported by all the storages: the default implementation returns an error.
+    async fn list(&self, prefix: &Path) -> StorageResult<Vec<PathBuf>> {
+        Err(StorageErrorKind::Unsupported.with_error(anyhow::anyhow!(
+            "failed to list files with prefix `{}`: listing is not supported by storage `{}`",
+            prefix.display(),
+            self.uri()
+        )))
+    }
+
     /// Returns a file size.
     async fn file_num_bytes(&self, path: &Path) -> StorageResult<u64>;
 
@@ -142,6 +215,34 @@ pub trait Storage: fmt::Debug + Send + Sync + 'static {
     fn uri(&self) -> &Uri;
 }
 
+/// Copies the file at `from` in `storage` to `to` in `target_storage`.
+///
+/// The file is streamed through a local temporary file rather than loaded in memory, so this
+/// function is suitable for large files such as splits.
+pub async fn copy_across_storages<S: Storage + ?Sized, T: Storage + ?Sized>(
+    storage: &S,
+    target_storage: &T,
+    from: &Path,
+    to: &Path,
+) -> StorageResult<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let temp_filepath = temp_dir.path().join("copy");
+    let num_bytes = storage.copy_to_file(from, &temp_filepath).await?;
+    let payload = FilePayload {
+        len: num_bytes,
+        path: temp_filepath,
+    };
+    target_storage.put(to, Box::new(payload)).await
+}
+
+pub(crate) async fn default_copy<S: Storage + ?Sized>(
+    storage: &S,
+    from: &Path,
+    to: &Path,
+) -> StorageResult<()> {
+    copy_across_storages(storage, storage, from, to).await
+}
+
 async fn default_copy_to_file<S: Storage + ?Sized>(
     storage: &S,
     path: &Path,
@@ -244,6 +345,32 @@ mod tests {
         assert_eq!(&content, CONTENT);
     }
 
+    #[tokio::test]
+    async fn test_copy_across_storages() {
+        let ram_storage = RamStorage::default();
+        let target_ram_storage = RamStorage::default();
+        let path = Path::new("foo/bar");
+        ram_storage
+            .put(path, Box::new(CONTENT.to_owned()))
+            .await
+            .unwrap();
+        copy_across_storages(&ram_storage, &target_ram_storage, path, Path::new("baz"))
+            .await
+            .unwrap();
+        let content = target_ram_storage.get_all(Path::new("baz")).await.unwrap();
+        assert_eq!(content.as_slice(), CONTENT);
+
+        let error = copy_across_storages(
+            &ram_storage,
+            &target_ram_storage,
+            Path::new("missing"),
+            Path::new("baz"),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(error.kind(), StorageErrorKind::NotFound);
+    }
+
     #[tokio::test]
     async fn test_copy_to_file_deletes_tempfile_on_failure() {
         let mut storage = MockStorage::default();
@@ -19,11 +19,11 @@
 
 use std::collections::HashMap;
 use std::fmt;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 
 use once_cell::sync::Lazy;
 use quickwit_common::uri::{Protocol, Uri};
-use quickwit_config::{StorageBackend, StorageConfigs};
+use quickwit_config::{IndexConfig, StorageBackend, StorageConfigs};
 
 use crate::local_file_storage::LocalFileStorageFactory;
 use crate::ram_storage::RamStorageFactory;
@@ -31,14 +31,53 @@ use crate::ram_storage::RamStorageFactory;
 use crate::AzureBlobStorageFactory;
 #[cfg(feature = "gcs")]
 use crate::GoogleCloudStorageFactory;
+#[cfg(feature = "http")]
+use crate::HttpStorageFactory;
+#[cfg(feature = "webhdfs")]
+use crate::WebHdfsStorageFactory;
 use crate::{S3CompatibleObjectStorageFactory, Storage, StorageFactory, StorageResolverError};
 
 /// Returns the [`Storage`] instance associated with the protocol of a URI. The actual creation of
 /// storage objects is delegated to pre-registered [`StorageFactory`]. The resolver is only
 /// responsible for dispatching to the appropriate factory.
+///
+/// Factories can be registered and deregistered at runtime. They are shared by all the clones of
+/// a resolver, so a factory registered on one clone is visible from all the others.
 #[derive(Clone)]
 pub struct StorageResolver {
-    per_backend_factories: Arc<HashMap<StorageBackend, Box<dyn StorageFactory>>>,
+    factories: Arc<RwLock<StorageFactories>>,
+}
+
+#[derive(Default)]
+struct StorageFactories {
+    per_backend_factories: HashMap<StorageBackend, Arc<dyn StorageFactory>>,
+    per_uri_prefix_factories: HashMap<Uri, Arc<dyn StorageFactory>>,
+    // Factories resolving the URIs of the indexes defining a storage config, keyed by index URI.
+    per_index_uri_factories: HashMap<Uri, Arc<dyn StorageFactory>>,
+    // S3 factory the storage configs of the indexes are applied to.
+    s3_factory_opt: Option<S3CompatibleObjectStorageFactory>,
+}
+
+impl StorageFactories {
+    /// Returns the factory registered for the longest URI prefix of `uri`, either by URI prefix
+    /// or by index, falling back to the factory registered for `backend`.
+    fn find(&self, backend: StorageBackend, uri: &Uri) -> Option<Arc<dyn StorageFactory>> {
+        let uri_str = uri.as_str();
+        self.per_uri_prefix_factories
+            .iter()
+            .chain(self.per_index_uri_factories.iter())
+            .filter(|(uri_prefix, _)| {
+                let uri_prefix = uri_prefix.as_str().trim_end_matches('/');
+                uri_str
+                    .strip_prefix(uri_prefix)
+                    .map(|suffix| suffix.is_empty() || suffix.starts_with('/'))
+                    .unwrap_or(false)
+            })
+            .max_by_key(|(uri_prefix, _)| uri_prefix.as_str().trim_end_matches('/').len())
+            .map(|(_, storage_factory)| storage_factory)
+            .or_else(|| self.per_backend_factories.get(&backend))
+            .cloned()
+    }
 }
 
 impl fmt::Debug for StorageResolver {
@@ -61,6 +100,8 @@ impl StorageResolver {
             Protocol::Ram => StorageBackend::Ram,
             Protocol::S3 => StorageBackend::S3,
             Protocol::Google => StorageBackend::Google,
+            Protocol::Http | Protocol::Https => StorageBackend::Http,
+            Protocol::WebHdfs => StorageBackend::WebHdfs,
             _ => {
                 let message = format!(
                     "Quickwit does not support {} as a storage backend",
@@ -69,7 +110,13 @@ impl StorageResolver {
                 return Err(StorageResolverError::UnsupportedBackend(message));
             }
         };
-        let storage_factory = self.per_backend_factories.get(&backend).ok_or({
+        // The lock must be released before awaiting the factory.
+        let storage_factory_opt = self
+            .factories
+            .read()
+            .expect("lock should not be poisoned")
+            .find(backend, uri);
+        let storage_factory = storage_factory_opt.ok_or_else(|| {
             let message = format!("no storage factory is registered for {}", uri.protocol());
             StorageResolverError::UnsupportedBackend(message)
         })?;
@@ -77,10 +124,93 @@ impl StorageResolver {
         Ok(storage)
     }
 
+    /// Registers a [`StorageFactory`] for its backend, replacing the factory previously
+    /// registered for that backend, if any.
+    pub fn register_factory<S: StorageFactory>(&self, storage_factory: S) {
+        self.factories
+            .write()
+            .expect("lock should not be poisoned")
+            .per_backend_factories
+            .insert(storage_factory.backend(), Arc::new(storage_factory));
+    }
+
+    /// Registers a [`StorageFactory`] for the URIs starting with `uri_prefix`. This factory takes
+    /// precedence over the factory registered for the backend, and over the factories registered
+    /// for shorter prefixes.
+    pub fn register_factory_for_uri_prefix<S: StorageFactory>(
+        &self,
+        uri_prefix: Uri,
+        storage_factory: S,
+    ) {
+        self.factories
+            .write()
+            .expect("lock should not be poisoned")
+            .per_uri_prefix_factories
+            .insert(uri_prefix, Arc::new(storage_factory));
+    }
+
+    /// Deregisters the [`StorageFactory`] registered for `backend`. Returns whether a factory was
+    /// registered.
+    pub fn deregister_factory(&self, backend: StorageBackend) -> bool {
+        self.factories
+            .write()
+            .expect("lock should not be poisoned")
+            .per_backend_factories
+            .remove(&backend)
+            .is_some()
+    }
+
+    /// Deregisters the [`StorageFactory`] registered for `uri_prefix`. Returns whether a factory
+    /// was registered.
+    pub fn deregister_factory_for_uri_prefix(&self, uri_prefix: &Uri) -> bool {
+        self.factories
+            .write()
+            .expect("lock should not be poisoned")
+            .per_uri_prefix_factories
+            .remove(uri_prefix)
+            .is_some()
+    }
+
+    /// Registers the storage config of an index, so that the URIs of the index are resolved with
+    /// the S3 storage config of the node overridden by the storage config of the index. If the
+    /// index does not define a storage config, the storage config previously registered for its
+    /// URI, if any, is deregistered.
+    pub fn register_index_config(&self, index_config: &IndexConfig) {
+        let mut factories = self.factories.write().expect("lock should not be poisoned");
+
+        let Some(index_storage_config) = &index_config.storage_config else {
+            factories
+                .per_index_uri_factories
+                .remove(&index_config.index_uri);
+            return;
+        };
+        let s3_storage_factory = factories
+            .s3_factory_opt
+            .get_or_insert_with(|| S3CompatibleObjectStorageFactory::new(Default::default()))
+            .with_index_storage_config(index_storage_config);
+        factories
+            .per_index_uri_factories
+            .insert(index_config.index_uri.clone(), Arc::new(s3_storage_factory));
+    }
+
+    /// Deregisters the storage config registered for the index located at `index_uri`. Returns
+    /// whether a storage config was registered.
+    pub fn deregister_index_config(&self, index_uri: &Uri) -> bool {
+        self.factories
+            .write()
+            .expect("lock should not be poisoned")
+            .per_index_uri_factories
+            .remove(index_uri)
+            .is_some()
+    }
+
     /// Creates and returns a default [`StorageResolver`] with the default storage configuration for
     /// each backend. Note that if the environment (env vars, instance metadata, ...) fails to
     /// provide the necessary credentials, the default Azure or S3 storage returned by this
     /// resolver will not work.
+    ///
+    /// The returned resolver is a clone of a process-wide singleton: factories registered on it
+    /// are visible from all the other unconfigured resolvers.
     pub fn unconfigured() -> Self {
         static STORAGE_RESOLVER: Lazy<StorageResolver> = Lazy::new(|| {
             let storage_configs = StorageConfigs::default();
@@ -91,12 +221,13 @@ impl StorageResolver {
 
     /// Creates and returns a [`StorageResolver`].
     pub fn configured(storage_configs: &StorageConfigs) -> Self {
+        let s3_storage_factory = S3CompatibleObjectStorageFactory::new(
+            storage_configs.find_s3().cloned().unwrap_or_default(),
+        );
         let mut builder = StorageResolver::builder()
             .register(LocalFileStorageFactory)
             .register(RamStorageFactory::default())
-            .register(S3CompatibleObjectStorageFactory::new(
-                storage_configs.find_s3().cloned().unwrap_or_default(),
-            ));
+            .register(s3_storage_factory.clone());
         #[cfg(feature = "azure")]
         {
             builder = builder.register(AzureBlobStorageFactory::new(
@@ -127,9 +258,43 @@ impl StorageResolver {
                 "Quickwit was compiled without the `gcs` feature.",
             ))
         }
-        builder
+        #[cfg(feature = "http")]
+        {
+            builder = builder.register(HttpStorageFactory::default());
+        }
+        #[cfg(not(feature = "http"))]
+        {
+            use crate::storage_factory::UnsupportedStorage;
+
+            builder = builder.register(UnsupportedStorage::new(
+                StorageBackend::Http,
+                "Quickwit was compiled without the `http` feature.",
+            ))
+        }
+        #[cfg(feature = "webhdfs")]
+        {
+            builder = builder.register(WebHdfsStorageFactory::new(
+                storage_configs.find_webhdfs().cloned().unwrap_or_default(),
+            ));
+        }
+        #[cfg(not(feature = "webhdfs"))]
+        {
+            use crate::storage_factory::UnsupportedStorage;
+
+            builder = builder.register(UnsupportedStorage::new(
+                StorageBackend::WebHdfs,
+                "Quickwit was compiled without the `webhdfs` feature.",
+            ))
+        }
+        let storage_resolver = builder
             .build()
-            .expect("Storage factory and config backends should match.")
+            .expect("Storage factory and config backends should match.");
+        storage_resolver
+            .factories
+            .write()
+            .expect("lock should not be poisoned")
+            .s3_factory_opt = Some(s3_storage_factory);
+        storage_resolver
     }
 
     /// Returns a [`StorageResolver`] for testing purposes. Unlike
@@ -146,21 +311,25 @@ impl StorageResolver {
 
 #[derive(Default)]
 pub struct StorageResolverBuilder {
-    per_backend_factories: HashMap<StorageBackend, Box<dyn StorageFactory>>,
+    per_backend_factories: HashMap<StorageBackend, Arc<dyn StorageFactory>>,
 }
 
 impl StorageResolverBuilder {
     /// Registers a [`StorageFactory`].
     pub fn register<S: StorageFactory>(mut self, storage_factory: S) -> Self {
         self.per_backend_factories
-            .insert(storage_factory.backend(), Box::new(storage_factory));
+            .insert(storage_factory.backend(), Arc::new(storage_factory));
         self
     }
 
     /// Builds the [`StorageResolver`].
     pub fn build(self) -> anyhow::Result<StorageResolver> {
+        let factories = StorageFactories {
+            per_backend_factories: self.per_backend_factories,
+            ..Default::default()
+        };
         let storage_resolver = StorageResolver {
-            per_backend_factories: Arc::new(self.per_backend_factories),
+            factories: Arc::new(RwLock::new(factories)),
         };
         Ok(storage_resolver)
     }
@@ -169,6 +338,12 @@ impl StorageResolverBuilder {
 #[cfg(test)]
 mod tests {
     use std::path::Path;
+    use std::time::Duration;
+
+    use quickwit_config::{IndexStorageConfig, S3StorageConfig};
+    use tokio::io::AsyncReadExt;
+    use tokio::net::TcpListener;
+    use tokio::time::timeout;
 
     use super::*;
     use crate::{MockStorageFactory, RamStorage};
@@ -236,6 +411,54 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_storage_resolver_register_index_config() {
+        let s3_storage_config = S3StorageConfig {
+            region: Some("us-east-1".to_string()),
+            endpoint: Some("http://127.0.0.1:1".to_string()),
+            anonymous: true,
+            ..Default::default()
+        };
+        let storage_resolver =
+            StorageResolver::configured(&StorageConfigs::new(vec![s3_storage_config.into()]));
+
+        // Each index is served by its own S3-compatible object store.
+        for index_id in ["index-1", "index-2"] {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let endpoint = format!("http://{}", listener.local_addr().unwrap());
+            let mut index_config =
+                IndexConfig::for_test(index_id, &format!("s3://bucket/indexes/{index_id}"));
+            index_config.storage_config = Some(IndexStorageConfig {
+                endpoint: Some(endpoint),
+                force_path_style_access: Some(true),
+                ..Default::default()
+            });
+            storage_resolver.register_index_config(&index_config);
+
+            let storage = storage_resolver
+                .resolve(&index_config.index_uri)
+                .await
+                .unwrap();
+            let request_handle =
+                tokio::spawn(async move { storage.get_all(Path::new("hello")).await });
+            let (mut stream, _) = timeout(Duration::from_secs(10), listener.accept())
+                .await
+                .expect("the request should be sent to the endpoint of the index")
+                .unwrap();
+            let mut request_bytes = vec![0; 1024];
+            let num_bytes = stream.read(&mut request_bytes).await.unwrap();
+            let request = String::from_utf8_lossy(&request_bytes[..num_bytes]);
+            assert!(
+                request.starts_with(&format!("GET /bucket/indexes/{index_id}/hello")),
+                "unexpected request: {request}"
+            );
+            request_handle.abort();
+        }
+        let index_uri = Uri::for_test("s3://bucket/indexes/index-1");
+        assert!(storage_resolver.deregister_index_config(&index_uri));
+        assert!(!storage_resolver.deregister_index_config(&index_uri));
+    }
+
     #[tokio::test]
     async fn test_storage_resolver_unsupported_protocol() {
         let storage_resolver = StorageResolver::unconfigured();
@@ -246,4 +469,74 @@ mod tests {
             StorageResolverError::UnsupportedBackend(_)
         ));
     }
+
+    #[tokio::test]
+    async fn test_storage_resolver_register_factory_at_runtime() {
+        let storage_resolver = StorageResolver::for_test();
+        let s3_uri = Uri::for_test("s3://bucket/indexes");
+        let resolver_error = storage_resolver.resolve(&s3_uri).await.unwrap_err();
+        assert!(matches!(
+            resolver_error,
+            StorageResolverError::UnsupportedBackend(_)
+        ));
+        let mut s3_storage_factory = MockStorageFactory::new();
+        s3_storage_factory
+            .expect_backend()
+            .returning(|| StorageBackend::S3);
+        s3_storage_factory.expect_resolve().returning(|uri| {
+            assert_eq!(uri.as_str(), "s3://bucket/indexes");
+            Ok(Arc::new(
+                RamStorage::builder().put("hello", b"hello_s3").build(),
+            ))
+        });
+        // Registering on a clone is visible from the original resolver.
+        storage_resolver
+            .clone()
+            .register_factory(s3_storage_factory);
+
+        let storage = storage_resolver.resolve(&s3_uri).await.unwrap();
+        let data = storage.get_all(Path::new("hello")).await.unwrap();
+        assert_eq!(&data[..], b"hello_s3");
+
+        assert!(storage_resolver.deregister_factory(StorageBackend::S3));
+        assert!(!storage_resolver.deregister_factory(StorageBackend::S3));
+        storage_resolver.resolve(&s3_uri).await.unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn test_storage_resolver_register_factory_for_uri_prefix() {
+        let storage_resolver = StorageResolver::for_test();
+
+        let mut ram_storage_factory = MockStorageFactory::new();
+        ram_storage_factory
+            .expect_backend()
+            .returning(|| StorageBackend::Ram);
+        ram_storage_factory.expect_resolve().returning(|_uri| {
+            Ok(Arc::new(
+                RamStorage::builder().put("hello", b"hello_prefix").build(),
+            ))
+        });
+        let uri_prefix = Uri::for_test("ram:///indexes/special");
+        storage_resolver.register_factory_for_uri_prefix(uri_prefix.clone(), ram_storage_factory);
+
+        let storage = storage_resolver
+            .resolve(&Uri::for_test("ram:///indexes/special/my-index"))
+            .await
+            .unwrap();
+        let data = storage.get_all(Path::new("hello")).await.unwrap();
+        assert_eq!(&data[..], b"hello_prefix");
+
+        // The prefix only matches whole path segments.
+        for uri in ["ram:///indexes/special-2", "ram:///indexes/other"] {
+            let storage = storage_resolver.resolve(&Uri::for_test(uri)).await.unwrap();
+            assert!(!storage.exists(Path::new("hello")).await.unwrap());
+        }
+        assert!(storage_resolver.deregister_factory_for_uri_prefix(&uri_prefix));
+
+        let storage = storage_resolver
+            .resolve(&Uri::for_test("ram:///indexes/special/my-index"))
+            .await
+            .unwrap();
+        assert!(!storage.exists(Path::new("hello")).await.unwrap());
+    }
 }
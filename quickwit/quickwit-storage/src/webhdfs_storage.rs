@@ -0,0 +1,854 @@
+// Copyright (C) 2024 Quickwit, Inc.
+//
+// Quickwit is offered under the AGPL v3.0 and as commercial software.
+// For commercial licensing, contact us at hello@quickwit.io.
+//
+// AGPL:
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as
+// published by the Free Software Foundation, either version 3 of the
+// License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::ops::Range;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use std::{fmt, io};
+
+use async_trait::async_trait;
+use hyper::header::LOCATION;
+use hyper::StatusCode;
+use percent_encoding::percent_decode_str;
+use quickwit_common::chunk_range;
+use quickwit_common::retry::{retry, Retry, RetryParams};
+use quickwit_common::uri::{Protocol, Uri};
+use quickwit_config::{StorageBackend, WebHdfsStorageConfig};
+use reqwest::redirect::Policy;
+use reqwest::{Client, Method, RequestBuilder, Response, Url};
+use serde::Deserialize;
+use tokio::io::{AsyncRead, AsyncWriteExt};
+use tracing::warn;
+use ulid::Ulid;
+
+use crate::http_storage::reqwest_error_to_storage_error;
+use crate::storage::SendableAsync;
+use crate::{
+    BulkDeleteError, DeleteFailure, OwnedBytes, PutPayload, Storage, StorageError,
+    StorageErrorKind, StorageFactory, StorageResolverError, StorageResult,
+};
+
+/// Payloads are uploaded with a `CREATE` request carrying their first chunk, followed by one
+/// `APPEND` request per remaining chunk, so that uploads do not need to hold whole splits in
+/// memory.
+const UPLOAD_CHUNK_NUM_BYTES: usize = if cfg!(test) { 8 } else { 64 * 1024 * 1024 };
+
+/// Maximum duration for establishing a connection with the namenode or a datanode.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Maximum duration of a request, including the transfer of its payload, which can be as large as
+/// an upload chunk.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Storage backed by the Hadoop Distributed File System (HDFS), accessed through the WebHDFS REST
+/// API of the namenode.
+///
+/// The URI `webhdfs://namenode:9870/quickwit/indexes` designates the directory
+/// `/quickwit/indexes` of the file system served by the namenode listening on `namenode:9870`.
+/// The namenode redirects reads and writes to the datanodes holding the data, which is why the
+/// HTTP client must not follow redirects on its own: the payload of a write is only sent to the
+/// datanode.
+///
+/// Files are uploaded to a temporary file first, which is renamed once complete, so that readers
+/// never observe partially written files. Failed requests are retried when the error is
+/// transient. Since appending to a file is not idempotent, failed uploads are retried as a whole,
+/// starting over with a new temporary file.
+#[derive(Clone)]
+pub struct WebHdfsStorage {
+    client: Client,
+    uri: Uri,
+    /// URL of the storage root in the WebHDFS REST API, e.g.
+    /// `http://namenode:9870/webhdfs/v1/quickwit/indexes`.
+    root_url: Url,
+    /// Absolute path of the storage root in the file system, e.g. `/quickwit/indexes`.
+    root_path: String,
+    user_name_opt: Option<String>,
+    delegation_token_opt: Option<String>,
+    retry_params: RetryParams,
+}
+
+impl fmt::Debug for WebHdfsStorage {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("WebHdfsStorage")
+            .field("uri", &self.uri)
+            .finish()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct FileStatusResponse {
+    file_status: FileStatus,
+}
+
+#[derive(Deserialize)]
+struct FileStatus {
+    length: u64,
+}
+
+impl WebHdfsStorage {
+    /// Creates a [`WebHdfsStorage`] storing its files under `uri`. `client` must not follow
+    /// redirects.
+    pub fn new(
+        client: Client,
+        uri: Uri,
+        storage_config: &WebHdfsStorageConfig,
+    ) -> Result<Self, StorageResolverError> {
+        let root_url = webhdfs_root_url(&uri).ok_or_else(|| {
+            let message = format!("URI `{uri}` is not a valid WebHDFS URI");
+            StorageResolverError::InvalidUri(message)
+        })?;
+        let encoded_root_path = root_url
+            .path()
+            .strip_prefix("/webhdfs/v1")
+            .unwrap_or_default();
+        let root_path = percent_decode_str(encoded_root_path)
+            .decode_utf8_lossy()
+            .trim_end_matches('/')
+            .to_string();
+        Ok(Self {
+            client,
+            uri,
+            root_url,
+            root_path,
+            user_name_opt: storage_config.user_name.clone(),
+            delegation_token_opt: storage_config.resolve_delegation_token(),
+            retry_params: RetryParams::default(),
+        })
+    }
+
+    /// Returns the absolute path of the file located at `path` in the file system.
+    fn hdfs_path(&self, path: &Path) -> String {
+        let mut hdfs_path = self.root_path.clone();
+
+        for component in path.components() {
+            hdfs_path.push('/');
+            hdfs_path.push_str(&component.as_os_str().to_string_lossy());
+        }
+        hdfs_path
+    }
+
+    /// Returns the URL of the operation `op` on the file located at `path`.
+    fn op_url(&self, path: &Path, op: &str, params: &[(&str, String)]) -> Url {
+        let mut url = self.root_url.clone();
+        url.path_segments_mut()
+            .expect("HTTP URLs should have path segments")
+            .pop_if_empty()
+            .extend(
+                path.components()
+                    .map(|component| component.as_os_str().to_string_lossy()),
+            );
+        let mut query_pairs = url.query_pairs_mut();
+        query_pairs.append_pair("op", op);
+
+        if let Some(user_name) = &self.user_name_opt {
+            query_pairs.append_pair("user.name", user_name);
+        }
+        if let Some(delegation_token) = &self.delegation_token_opt {
+            query_pairs.append_pair("delegation", delegation_token);
+        }
+        for (key, value) in params {
+            query_pairs.append_pair(key, value);
+        }
+        drop(query_pairs);
+        url
+    }
+
+    /// Sends the request and converts the HTTP error statuses into storage errors. Redirections
+    /// are returned as is. Network errors and server errors are transient.
+    async fn send(
+        &self,
+        request: RequestBuilder,
+        path: &Path,
+    ) -> Result<Response, Retry<StorageError>> {
+        let response = request
+            .send()
+            .await
+            .map_err(|error| Retry::Transient(reqwest_error_to_storage_error(error)))?;
+        let status = response.status();
+
+        if status.is_success() || status.is_redirection() {
+            return Ok(response);
+        }
+        let error_kind = match status {
+            StatusCode::NOT_FOUND => StorageErrorKind::NotFound,
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => StorageErrorKind::Unauthorized,
+            _ => StorageErrorKind::Service,
+        };
+        // The body of WebHDFS error responses is a JSON `RemoteException` describing the error.
+        let remote_exception = response.text().await.unwrap_or_default();
+        let storage_error = error_kind.with_error(anyhow::anyhow!(
+            "WebHDFS request on file `{}` of `{}` failed with status `{status}`: \
+             {remote_exception}",
+            path.display(),
+            self.uri
+        ));
+        if status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS {
+            Err(Retry::Transient(storage_error))
+        } else {
+            Err(Retry::Permanent(storage_error))
+        }
+    }
+
+    /// Sends the request to the namenode and, when the namenode redirects it to a datanode,
+    /// sends it again to the datanode with `body` as payload.
+    async fn send_to_datanode(
+        &self,
+        method: Method,
+        url: Url,
+        body: Vec<u8>,
+        path: &Path,
+    ) -> Result<Response, Retry<StorageError>> {
+        let response = self
+            .send(self.client.request(method.clone(), url), path)
+            .await?;
+
+        if !response.status().is_redirection() {
+            if !body.is_empty() {
+                return Err(Retry::Permanent(StorageErrorKind::Service.with_error(
+                    anyhow::anyhow!(
+                        "failed to write file `{}`: namenode did not redirect the request to a \
+                         datanode",
+                        path.display()
+                    ),
+                )));
+            }
+            return Ok(response);
+        }
+        let datanode_url = response
+            .headers()
+            .get(LOCATION)
+            .and_then(|header_value| header_value.to_str().ok())
+            .ok_or_else(|| {
+                Retry::Permanent(StorageErrorKind::Service.with_error(anyhow::anyhow!(
+                    "failed to access file `{}`: namenode redirect has no valid `Location` header",
+                    path.display()
+                )))
+            })?;
+        let mut request = self.client.request(method, datanode_url);
+
+        if !body.is_empty() {
+            request = request.body(body);
+        }
+        let response = self.send(request, path).await?;
+
+        if response.status().is_redirection() {
+            return Err(Retry::Permanent(StorageErrorKind::Service.with_error(
+                anyhow::anyhow!(
+                    "failed to access file `{}`: datanode redirected the request",
+                    path.display()
+                ),
+            )));
+        }
+        Ok(response)
+    }
+
+    async fn open(&self, path: &Path, params: &[(&str, String)]) -> StorageResult<OwnedBytes> {
+        retry(&self.retry_params, || async {
+            let url = self.op_url(path, "OPEN", params);
+            let response = self
+                .send_to_datanode(Method::GET, url, Vec::new(), path)
+                .await?;
+            let bytes = response
+                .bytes()
+                .await
+                .map_err(|error| Retry::Transient(reqwest_error_to_storage_error(error)))?;
+            Ok(OwnedBytes::new(bytes.to_vec()))
+        })
+        .await
+        .map_err(Retry::into_inner)
+    }
+
+    async fn delete_single_file(&self, path: &Path) -> StorageResult<()> {
+        retry(&self.retry_params, || async {
+            let url = self.op_url(path, "DELETE", &[]);
+            // The namenode responds with `{"boolean": false}` when the file does not exist.
+            match self.send(self.client.delete(url), path).await {
+                Ok(_) => Ok(()),
+                Err(Retry::Permanent(error)) if error.kind() == StorageErrorKind::NotFound => {
+                    Ok(())
+                }
+                Err(error) => Err(error),
+            }
+        })
+        .await
+        .map_err(Retry::into_inner)
+    }
+
+    /// Uploads the payload to a new temporary file, which is then renamed to `path`, overwriting
+    /// the existing file if any. The temporary file is deleted if the upload fails.
+    async fn put_via_temp_file(
+        &self,
+        path: &Path,
+        payload: &dyn PutPayload,
+    ) -> Result<(), Retry<StorageError>> {
+        let file_name = path
+            .file_name()
+            .map(|file_name| file_name.to_string_lossy())
+            .unwrap_or_default();
+        let temp_path = path.with_file_name(format!(".{file_name}.{}.temp", Ulid::new()));
+
+        let upload_result = async {
+            self.upload(&temp_path, payload).await?;
+            self.rename(&temp_path, path).await
+        }
+        .await;
+
+        if upload_result.is_err() {
+            if let Err(delete_error) = self.delete_single_file(&temp_path).await {
+                warn!(
+                    error=?delete_error,
+                    "failed to delete temporary file `{}`",
+                    temp_path.display()
+                );
+            }
+        }
+        upload_result
+    }
+
+    /// Writes the payload to the file located at `path` with a `CREATE` request carrying its first
+    /// chunk and one `APPEND` request per remaining chunk.
+    async fn upload(
+        &self,
+        path: &Path,
+        payload: &dyn PutPayload,
+    ) -> Result<(), Retry<StorageError>> {
+        let mut chunks = chunk_range(0..payload.len() as usize, UPLOAD_CHUNK_NUM_BYTES);
+        let first_chunk = chunks.next().unwrap_or(0..0);
+
+        let body = read_payload_chunk(payload, first_chunk).await?;
+        let url = self.op_url(path, "CREATE", &[("overwrite", "true".to_string())]);
+        self.send_to_datanode(Method::PUT, url, body, path).await?;
+
+        for chunk in chunks {
+            let body = read_payload_chunk(payload, chunk).await?;
+            let url = self.op_url(path, "APPEND", &[]);
+            self.send_to_datanode(Method::POST, url, body, path).await?;
+        }
+        Ok(())
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> Result<(), Retry<StorageError>> {
+        let params = [
+            ("destination", self.hdfs_path(to)),
+            ("renameoptions", "OVERWRITE".to_string()),
+        ];
+        let url = self.op_url(from, "RENAME", &params);
+        self.send(self.client.put(url), from).await?;
+        Ok(())
+    }
+}
+
+/// Converts `webhdfs://<host>:<port>/<path>` into `http://<host>:<port>/webhdfs/v1/<path>`.
+fn webhdfs_root_url(uri: &Uri) -> Option<Url> {
+    if uri.protocol() != Protocol::WebHdfs {
+        return None;
+    }
+    let authority_and_path = uri.as_str().strip_prefix("webhdfs://")?;
+    let (authority, path) = authority_and_path
+        .split_once('/')
+        .unwrap_or((authority_and_path, ""));
+    if authority.is_empty() {
+        return None;
+    }
+    Url::parse(&format!("http://{authority}/webhdfs/v1/{path}")).ok()
+}
+
+async fn read_payload_chunk(
+    payload: &dyn PutPayload,
+    range: Range<usize>,
+) -> Result<Vec<u8>, Retry<StorageError>> {
+    let read_chunk = async {
+        let mut chunk_reader = payload
+            .range_byte_stream(range.start as u64..range.end as u64)
+            .await?
+            .into_async_read();
+        let mut chunk = Vec::with_capacity(range.len());
+        tokio::io::copy(&mut chunk_reader, &mut chunk).await?;
+        Ok::<_, io::Error>(chunk)
+    };
+    read_chunk
+        .await
+        .map_err(|io_error| Retry::Permanent(StorageError::from(io_error)))
+}
+
+#[async_trait]
+impl Storage for WebHdfsStorage {
+    async fn check_connectivity(&self) -> anyhow::Result<()> {
+        // Any response, including an error status, proves that the namenode is reachable.
+        let url = self.op_url(Path::new(""), "GETFILESTATUS", &[]);
+        self.client.get(url).send().await?;
+        Ok(())
+    }
+
+    async fn put(&self, path: &Path, payload: Box<dyn PutPayload>) -> StorageResult<()> {
+        retry(&self.retry_params, || {
+            self.put_via_temp_file(path, &*payload)
+        })
+        .await
+        .map_err(Retry::into_inner)
+    }
+
+    async fn copy_to(&self, path: &Path, output: &mut dyn SendableAsync) -> StorageResult<()> {
+        let payload_bytes = self.open(path, &[]).await?;
+        output.write_all(&payload_bytes).await?;
+        output.flush().await?;
+        Ok(())
+    }
+
+    async fn get_slice(&self, path: &Path, range: Range<usize>) -> StorageResult<OwnedBytes> {
+        if range.is_empty() {
+            return Ok(OwnedBytes::empty());
+        }
+        let params = [
+            ("offset", range.start.to_string()),
+            ("length", range.len().to_string()),
+        ];
+        let bytes = self.open(path, &params).await?;
+
+        if bytes.len() != range.len() {
+            return Err(StorageErrorKind::Internal.with_error(anyhow::anyhow!(
+                "failed to fetch slice {range:?} of file `{}`: received {} bytes",
+                path.display(),
+                bytes.len()
+            )));
+        }
+        Ok(bytes)
+    }
+
+    async fn get_slice_stream(
+        &self,
+        path: &Path,
+        range: Range<usize>,
+    ) -> StorageResult<Box<dyn AsyncRead + Send + Unpin>> {
+        let bytes = self.get_slice(path, range).await?;
+        Ok(Box::new(Cursor::new(bytes)))
+    }
+
+    async fn get_all(&self, path: &Path) -> StorageResult<OwnedBytes> {
+        self.open(path, &[]).await
+    }
+
+    async fn delete(&self, path: &Path) -> StorageResult<()> {
+        self.delete_single_file(path).await
+    }
+
+    async fn bulk_delete<'a>(&self, paths: &[&'a Path]) -> Result<(), BulkDeleteError> {
+        let mut successes = Vec::with_capacity(paths.len());
+        let mut failures = HashMap::new();
+
+        for &path in paths {
+            match self.delete_single_file(path).await {
+                Ok(()) => successes.push(path.to_path_buf()),
+                Err(error) => {
+                    let failure = DeleteFailure {
+                        error: Some(error),
+                        ..Default::default()
+                    };
+                    failures.insert(path.to_path_buf(), failure);
+                }
+            }
+        }
+        if failures.is_empty() {
+            return Ok(());
+        }
+        Err(BulkDeleteError {
+            successes,
+            failures,
+            ..Default::default()
+        })
+    }
+
+    async fn file_num_bytes(&self, path: &Path) -> StorageResult<u64> {
+        let response = retry(&self.retry_params, || async {
+            let url = self.op_url(path, "GETFILESTATUS", &[]);
+            self.send(self.client.get(url), path).await
+        })
+        .await
+        .map_err(Retry::into_inner)?;
+        let file_status_response: FileStatusResponse = response.json().await.map_err(|error| {
+            StorageErrorKind::Service.with_error(anyhow::anyhow!(
+                "failed to get size of file `{}`: invalid `GETFILESTATUS` response: {error}",
+                path.display()
+            ))
+        })?;
+        Ok(file_status_response.file_status.length)
+    }
+
+    fn uri(&self) -> &Uri {
+        &self.uri
+    }
+}
+
+/// Storage resolver for [`WebHdfsStorage`].
+pub struct WebHdfsStorageFactory {
+    client: Client,
+    storage_config: WebHdfsStorageConfig,
+}
+
+impl WebHdfsStorageFactory {
+    /// Creates a new WebHDFS storage factory.
+    pub fn new(storage_config: WebHdfsStorageConfig) -> Self {
+        let client = Client::builder()
+            .redirect(Policy::none())
+            .connect_timeout(CONNECT_TIMEOUT)
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .expect("HTTP client should be built");
+        Self {
+            client,
+            storage_config,
+        }
+    }
+}
+
+#[async_trait]
+impl StorageFactory for WebHdfsStorageFactory {
+    fn backend(&self) -> StorageBackend {
+        StorageBackend::WebHdfs
+    }
+
+    async fn resolve(&self, uri: &Uri) -> Result<Arc<dyn Storage>, StorageResolverError> {
+        let storage = WebHdfsStorage::new(self.client.clone(), uri.clone(), &self.storage_config)?;
+        Ok(Arc::new(storage))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+    use std::net::SocketAddr;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    use hyper::header::HOST;
+    use hyper::service::{make_service_fn, service_fn};
+    use hyper::{Body, Request, Server};
+
+    use super::*;
+
+    const DELEGATION_TOKEN: &str = "test-delegation-token";
+
+    type Files = Arc<Mutex<HashMap<String, Vec<u8>>>>;
+
+    fn json_response(status: StatusCode, body: String) -> hyper::Response<Body> {
+        hyper::Response::builder()
+            .status(status)
+            .header("content-type", "application/json")
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    fn file_not_found_response() -> hyper::Response<Body> {
+        let remote_exception = r#"{"RemoteException": {"exception": "FileNotFoundException"}}"#;
+        json_response(StatusCode::NOT_FOUND, remote_exception.to_string())
+    }
+
+    /// Emulates a namenode that redirects `CREATE`, `APPEND`, and `OPEN` requests to a datanode
+    /// served by the same server, storing files in memory. The first `num_unavailable_responses`
+    /// requests fail with `503 Service Unavailable`.
+    async fn serve_webhdfs(
+        files: Files,
+        num_unavailable_responses: Arc<AtomicUsize>,
+        request: Request<Body>,
+    ) -> Result<hyper::Response<Body>, Infallible> {
+        let is_unavailable = num_unavailable_responses
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |num_responses| {
+                num_responses.checked_sub(1)
+            })
+            .is_ok();
+        if is_unavailable {
+            return Ok(json_response(
+                StatusCode::SERVICE_UNAVAILABLE,
+                String::new(),
+            ));
+        }
+        let url = Url::parse(&format!("http://localhost{}", request.uri())).unwrap();
+        let params: HashMap<String, String> = url.query_pairs().into_owned().collect();
+
+        if params.get("delegation").map(String::as_str) != Some(DELEGATION_TOKEN) {
+            return Ok(json_response(StatusCode::UNAUTHORIZED, String::new()));
+        }
+        let path = url.path().strip_prefix("/webhdfs/v1").unwrap().to_string();
+        let op = params.get("op").unwrap().as_str();
+        let is_datanode_request = params.contains_key("datanode");
+
+        if matches!(op, "CREATE" | "APPEND" | "OPEN") && !is_datanode_request {
+            let host = request.headers().get(HOST).unwrap().to_str().unwrap();
+            let location = format!("http://{host}{}&datanode=true", request.uri());
+            let response = hyper::Response::builder()
+                .status(StatusCode::TEMPORARY_REDIRECT)
+                .header(LOCATION, location)
+                .body(Body::empty())
+                .unwrap();
+            return Ok(response);
+        }
+        let response = match op {
+            "CREATE" => {
+                let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+                files.lock().unwrap().insert(path, body.to_vec());
+                json_response(StatusCode::CREATED, String::new())
+            }
+            "APPEND" => {
+                let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+                let mut files_guard = files.lock().unwrap();
+                let Some(file) = files_guard.get_mut(&path) else {
+                    return Ok(file_not_found_response());
+                };
+                file.extend_from_slice(&body);
+                json_response(StatusCode::OK, String::new())
+            }
+            "OPEN" => {
+                let files_guard = files.lock().unwrap();
+                let Some(file) = files_guard.get(&path) else {
+                    return Ok(file_not_found_response());
+                };
+                let offset: usize = params
+                    .get("offset")
+                    .map_or(0, |offset| offset.parse().unwrap());
+                let length: usize = params
+                    .get("length")
+                    .map_or(file.len() - offset, |length| length.parse().unwrap());
+                let end = (offset + length).min(file.len());
+                hyper::Response::new(Body::from(file[offset..end].to_vec()))
+            }
+            "GETFILESTATUS" => {
+                let files_guard = files.lock().unwrap();
+                let Some(file) = files_guard.get(&path) else {
+                    return Ok(file_not_found_response());
+                };
+                let file_status = format!(
+                    r#"{{"FileStatus": {{"length": {}, "type": "FILE"}}}}"#,
+                    file.len()
+                );
+                json_response(StatusCode::OK, file_status)
+            }
+            "DELETE" => {
+                let deleted = files.lock().unwrap().remove(&path).is_some();
+                json_response(StatusCode::OK, format!(r#"{{"boolean": {deleted}}}"#))
+            }
+            "RENAME" => {
+                assert_eq!(params["renameoptions"], "OVERWRITE");
+                let mut files_guard = files.lock().unwrap();
+                let Some(file) = files_guard.remove(&path) else {
+                    return Ok(file_not_found_response());
+                };
+                files_guard.insert(params["destination"].clone(), file);
+                hyper::Response::new(Body::empty())
+            }
+            _ => json_response(StatusCode::BAD_REQUEST, String::new()),
+        };
+        Ok(response)
+    }
+
+    async fn start_webhdfs_server(files: Files) -> SocketAddr {
+        start_flaky_webhdfs_server(files, Arc::default()).await
+    }
+
+    async fn start_flaky_webhdfs_server(
+        files: Files,
+        num_unavailable_responses: Arc<AtomicUsize>,
+    ) -> SocketAddr {
+        let make_service = make_service_fn(move |_conn| {
+            let files = files.clone();
+            let num_unavailable_responses = num_unavailable_responses.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request| {
+                    serve_webhdfs(files.clone(), num_unavailable_responses.clone(), request)
+                }))
+            }
+        });
+        let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+        let server_addr = server.local_addr();
+        tokio::spawn(server);
+        server_addr
+    }
+
+    fn webhdfs_storage_for_test(server_addr: SocketAddr) -> WebHdfsStorage {
+        let storage_config = WebHdfsStorageConfig {
+            user_name: Some("quickwit".to_string()),
+            delegation_token: Some(DELEGATION_TOKEN.to_string()),
+        };
+        let uri = Uri::for_test(&format!("webhdfs://{server_addr}/quickwit/indexes"));
+        let client = Client::builder().redirect(Policy::none()).build().unwrap();
+        let mut storage = WebHdfsStorage::new(client, uri, &storage_config).unwrap();
+        storage.retry_params = RetryParams::for_test();
+        storage
+    }
+
+    #[tokio::test]
+    async fn test_webhdfs_storage() {
+        let files = Files::default();
+        let server_addr = start_webhdfs_server(files.clone()).await;
+        let storage = webhdfs_storage_for_test(server_addr);
+        let path = Path::new("splits/file.split");
+        let payload = b"abcdefghijklmnopqrstuvwxyz";
+
+        storage.check_connectivity().await.unwrap();
+
+        // The payload spans several upload chunks: one `CREATE` followed by three `APPEND`s, on
+        // a temporary file renamed once complete.
+        storage.put(path, Box::new(payload.to_vec())).await.unwrap();
+        {
+            let files_guard = files.lock().unwrap();
+            assert_eq!(files_guard.len(), 1);
+            assert_eq!(files_guard["/quickwit/indexes/splits/file.split"], payload);
+        }
+        let all = storage.get_all(path).await.unwrap();
+        assert_eq!(all.as_slice(), payload);
+
+        let slice = storage.get_slice(path, 3..8).await.unwrap();
+        assert_eq!(slice.as_slice(), b"defgh");
+
+        let slice = storage.get_slice(path, 5..5).await.unwrap();
+        assert!(slice.is_empty());
+
+        let error = storage.get_slice(path, 20..30).await.unwrap_err();
+        assert_eq!(error.kind(), StorageErrorKind::Internal);
+
+        let mut slice_stream = storage.get_slice_stream(path, 0..3).await.unwrap();
+        let mut buffer = Vec::new();
+        tokio::io::copy(&mut slice_stream, &mut buffer)
+            .await
+            .unwrap();
+        assert_eq!(buffer, b"abc");
+
+        let num_bytes = storage.file_num_bytes(path).await.unwrap();
+        assert_eq!(num_bytes, payload.len() as u64);
+
+        // Overwriting a file replaces its content.
+        storage.put(path, Box::new(b"abc".to_vec())).await.unwrap();
+        let all = storage.get_all(path).await.unwrap();
+        assert_eq!(all.as_slice(), b"abc");
+
+        storage.put(path, Box::new(Vec::new())).await.unwrap();
+        assert_eq!(storage.file_num_bytes(path).await.unwrap(), 0);
+
+        assert!(storage.exists(path).await.unwrap());
+        storage.delete(path).await.unwrap();
+        assert!(!storage.exists(path).await.unwrap());
+
+        // Deleting a missing file is not an error.
+        storage.delete(path).await.unwrap();
+
+        let error = storage.get_all(path).await.unwrap_err();
+        assert_eq!(error.kind(), StorageErrorKind::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_webhdfs_storage_retries_transient_errors() {
+        let files = Files::default();
+        let num_unavailable_responses = Arc::new(AtomicUsize::new(2));
+        let server_addr =
+            start_flaky_webhdfs_server(files.clone(), num_unavailable_responses.clone()).await;
+        let storage = webhdfs_storage_for_test(server_addr);
+        let path = Path::new("file");
+
+        storage.put(path, Box::new(b"abc".to_vec())).await.unwrap();
+        assert_eq!(files.lock().unwrap()["/quickwit/indexes/file"], b"abc");
+
+        num_unavailable_responses.store(2, Ordering::SeqCst);
+        let all = storage.get_all(path).await.unwrap();
+        assert_eq!(all.as_slice(), b"abc");
+
+        // Errors are reported once the attempts are exhausted.
+        num_unavailable_responses.store(usize::MAX, Ordering::SeqCst);
+        let error = storage.get_all(path).await.unwrap_err();
+        assert_eq!(error.kind(), StorageErrorKind::Service);
+    }
+
+    #[tokio::test]
+    async fn test_webhdfs_storage_bulk_delete() {
+        let files = Files::default();
+        let server_addr = start_webhdfs_server(files.clone()).await;
+        let storage = webhdfs_storage_for_test(server_addr);
+
+        let path_0 = Path::new("file-0");
+        let path_1 = Path::new("file-1");
+        storage.put(path_0, Box::new(b"0".to_vec())).await.unwrap();
+        storage.put(path_1, Box::new(b"1".to_vec())).await.unwrap();
+
+        storage
+            .bulk_delete(&[path_0, path_1, Path::new("missing")])
+            .await
+            .unwrap();
+        assert!(files.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_webhdfs_storage_unauthorized() {
+        let server_addr = start_webhdfs_server(Files::default()).await;
+        let uri = Uri::for_test(&format!("webhdfs://{server_addr}/quickwit"));
+        let storage = WebHdfsStorageFactory::new(WebHdfsStorageConfig::default())
+            .resolve(&uri)
+            .await
+            .unwrap();
+
+        let error = storage.get_all(Path::new("file")).await.unwrap_err();
+        assert_eq!(error.kind(), StorageErrorKind::Unauthorized);
+    }
+
+    #[test]
+    fn test_webhdfs_storage_op_url() {
+        let storage_config = WebHdfsStorageConfig {
+            user_name: Some("quickwit".to_string()),
+            delegation_token: Some("token".to_string()),
+        };
+        let uri = Uri::for_test("webhdfs://namenode:9870/quickwit/indexes");
+        let storage = WebHdfsStorage::new(Client::new(), uri, &storage_config).unwrap();
+        assert_eq!(
+            storage.hdfs_path(Path::new("my-index/split 1.split")),
+            "/quickwit/indexes/my-index/split 1.split"
+        );
+
+        let url = storage.op_url(
+            Path::new("my-index/split 1.split"),
+            "OPEN",
+            &[("offset", "3".to_string())],
+        );
+        assert_eq!(
+            url.as_str(),
+            "http://namenode:9870/webhdfs/v1/quickwit/indexes/my-index/split%201.split?\
+             op=OPEN&user.name=quickwit&delegation=token&offset=3"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_webhdfs_storage_factory() {
+        let webhdfs_storage_factory = WebHdfsStorageFactory::new(WebHdfsStorageConfig::default());
+        let error = webhdfs_storage_factory
+            .resolve(&Uri::for_test("s3://bucket/splits"))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, StorageResolverError::InvalidUri(_)));
+
+        let storage = webhdfs_storage_factory
+            .resolve(&Uri::for_test("webhdfs://namenode:9870/quickwit"))
+            .await
+            .unwrap();
+        assert_eq!(
+            storage.uri(),
+            &Uri::for_test("webhdfs://namenode:9870/quickwit")
+        );
+    }
+}
@@ -28,20 +28,28 @@ async fn google_cloud_storage_test_suite() -> anyhow::Result<()> {
 
     use anyhow::Context;
     use quickwit_common::uri::Uri;
-    use quickwit_storage::new_emulated_google_cloud_storage;
+    use quickwit_storage::{GoogleCloudStorage, MultiPartPolicy};
     let _ = tracing_subscriber::fmt::try_init();
 
     let mut object_storage =
-        new_emulated_google_cloud_storage(&Uri::from_str("gs://sample-bucket")?)?;
+        GoogleCloudStorage::new_emulated(&Uri::from_str("gs://sample-bucket")?)?;
     quickwit_storage::storage_test_suite(&mut object_storage).await?;
 
-    let mut object_storage = new_emulated_google_cloud_storage(&Uri::from_str(
-        "gs://sample-bucket/integration-tests/test-azure-compatible-storage",
+    let mut object_storage = GoogleCloudStorage::new_emulated(&Uri::from_str(
+        "gs://sample-bucket/integration-tests/test-gcs-storage",
     )?)?;
     quickwit_storage::storage_test_single_part_upload(&mut object_storage)
         .await
         .context("test single-part upload failed")?;
 
+    object_storage.set_policy(MultiPartPolicy {
+        // Resumable upload chunks must be a multiple of 256KiB.
+        target_part_num_bytes: 5 * 1_024 * 1_024, // 5MiB
+        max_num_parts: 10_000,
+        multipart_threshold_num_bytes: 10_000_000,
+        max_object_num_bytes: 5_000_000_000_000,
+        max_concurrent_uploads: 1,
+    });
     quickwit_storage::storage_test_multi_part_upload(&mut object_storage)
         .await
         .context("test multipart upload failed")?;
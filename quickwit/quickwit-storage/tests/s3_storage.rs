@@ -96,7 +96,7 @@ pub mod s3_storage_test_suite {
         use quickwit_common::rand::append_random_suffix;
 
         let s3_storage_config = S3StorageConfig {
-            force_path_style_access: true,
+            force_path_style_access: Some(true),
             ..Default::default()
         };
         let bucket_uri =
@@ -111,7 +111,7 @@ pub mod s3_storage_test_suite {
         use quickwit_common::rand::append_random_suffix;
 
         let s3_storage_config = S3StorageConfig {
-            force_path_style_access: false,
+            force_path_style_access: Some(false),
             ..Default::default()
         };
         let bucket_uri = append_random_suffix(